@@ -137,7 +137,7 @@ mod validation;
 use crate::{Error, Result, Runtime};
 use std::path::{Path, PathBuf};
 
-pub use validation::validate_asset_size;
+pub use validation::{DEFAULT_MAX_ASSET_SIZE, validate_asset_size};
 
 use security::canonicalize_path;
 use security::validate_asset_security;
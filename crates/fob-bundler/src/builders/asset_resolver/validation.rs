@@ -1,6 +1,9 @@
 use crate::{Error, Result, Runtime, RuntimeError};
 use std::path::Path;
 
+/// Default maximum asset size (50MB).
+pub const DEFAULT_MAX_ASSET_SIZE: u64 = 50 * 1024 * 1024;
+
 /// Validate asset size to prevent DoS attacks.
 ///
 /// # Arguments
@@ -17,7 +20,7 @@ pub async fn validate_asset_size(
     max_size: Option<u64>,
     runtime: &dyn Runtime,
 ) -> Result<u64> {
-    let max_size = max_size.unwrap_or(50 * 1024 * 1024); // 50MB default
+    let max_size = max_size.unwrap_or(DEFAULT_MAX_ASSET_SIZE);
 
     let metadata = runtime.metadata(path).await.map_err(|e| match e {
         RuntimeError::FileNotFound(p) => Error::AssetNotFound {
@@ -4,7 +4,7 @@
 //! build operations to the appropriate execution path based on the
 //! BuildOptions configuration.
 
-use rolldown::{BundlerOptions, GlobalsOutputOption, IsExternal, Platform, ResolveOptions};
+use rolldown::{BundlerOptions, GlobalsOutputOption, IsExternal, ResolveOptions};
 use rustc_hash::FxHashMap;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
@@ -12,9 +12,10 @@ use std::sync::Arc;
 use crate::Result;
 use crate::analysis::AnalyzedBundle;
 use crate::builders::common::{BundlePlan, EntrySpec, execute_bundle};
+use crate::builders::naming;
+use crate::builders::sourcemap::{self, SourceMapRewrite};
 use crate::builders::unified::primitives::{EntryMode, ExternalConfig};
 use crate::builders::unified::{BuildOptions, BuildOutput, BuildResult, EntryPoints, MinifyLevel};
-use crate::target::ExportConditions;
 
 #[cfg(feature = "dts-generation")]
 use crate::plugins::DtsEmitPlugin;
@@ -25,6 +26,10 @@ use crate::plugins::DtsEmitPlugin;
 /// - `Shared`: All entries share one bundle context (with optional code splitting)
 /// - `Isolated`: Each entry is built independently
 pub async fn execute_build(options: BuildOptions) -> Result<BuildResult> {
+    if !options.outputs.is_empty() {
+        return execute_output_targets(options).await;
+    }
+
     match options.entry_mode {
         EntryMode::Shared => execute_unified_build(options).await,
         EntryMode::Isolated => execute_separate_builds(options).await,
@@ -64,9 +69,20 @@ async fn execute_unified_build(options: BuildOptions) -> Result<BuildResult> {
         .as_ref()
         .map(|c| c.to_rolldown_options());
 
+    // Manual chunks go first so they win over the size-based split
+    if let Some(manual_chunks) = &options.manual_chunks {
+        let advanced_chunks = rolldown_options
+            .advanced_chunks
+            .get_or_insert_with(Default::default);
+        advanced_chunks
+            .groups
+            .get_or_insert_with(Vec::new)
+            .insert(0, manual_chunks.to_rolldown_group());
+    }
+
     // Add DTS plugin if enabled
     #[cfg(feature = "dts-generation")]
-    let plugins = if let Some(dts_opts) = &options.dts {
+    let mut plugins = if let Some(dts_opts) = &options.dts {
         let mut plugins = Vec::new();
         if let Some(plugin) = configure_dts_plugin(dts_opts, &entries) {
             plugins.push(plugin);
@@ -77,7 +93,9 @@ async fn execute_unified_build(options: BuildOptions) -> Result<BuildResult> {
     };
 
     #[cfg(not(feature = "dts-generation"))]
-    let plugins = Vec::new();
+    let mut plugins = Vec::new();
+
+    plugins.extend(options.plugins.iter().cloned());
 
     let plan = BundlePlan {
         entries,
@@ -85,15 +103,164 @@ async fn execute_unified_build(options: BuildOptions) -> Result<BuildResult> {
         plugins,
         cwd: options.cwd.clone(),
         virtual_files: options.virtual_files.clone(),
+        env: options.env.clone(),
+        replace: options.replace.clone(),
+        inject: options.inject.clone(),
         runtime: options.runtime.clone(),
-        cache: options.cache.clone(),
+        // The manual chunks callback, plugins and `[dir]` asset names can't be
+        // hashed into the cache key
+        cache: options.cache.clone().filter(|_| {
+            options.manual_chunks.is_none()
+                && options.plugins.is_empty()
+                && !naming::uses_dir(options.asset_names.as_deref())
+        }),
         incremental: options.incremental.clone(),
+        slow_module_threshold: options.slow_module_threshold,
+        chunk_size_warning_limit: options.chunk_size_warning_limit,
+        output_validation: options.output_validation,
+        sourcemap_rewrite: SourceMapRewrite::new(options.sourcemap, &options.sourcemap_options),
+        top_level_await: options.top_level_await,
     };
 
     let analyzed = execute_bundle(plan).await?;
     Ok(build_result_from_analyzed(analyzed, BuildOutput::Single))
 }
 
+/// Render one shared build per output target.
+///
+/// Targets only differ in render options, so they reuse the validated
+/// configuration, plugin instances and virtual files, and run concurrently.
+/// Rolldown doesn't expose a scan/render split, so each target still scans
+/// the module graph itself; the graph analysis is taken from the first one.
+async fn execute_output_targets(options: BuildOptions) -> Result<BuildResult> {
+    let builds: Vec<(String, BuildOptions)> = options
+        .outputs
+        .iter()
+        .enumerate()
+        .map(|(index, target)| {
+            let mut target_options = options.clone();
+            target_options.outputs = Vec::new();
+            target_options.format = target.format;
+            if let Some(level) = &target.minify_level {
+                target_options.minify_level = Some(level.clone());
+            }
+            if let Some(template) = &target.entry_names {
+                target_options.entry_names = Some(template.clone());
+            }
+            if let Some(template) = &target.chunk_names {
+                target_options.chunk_names = Some(template.clone());
+            }
+            // Declarations don't depend on the format; emit them once
+            #[cfg(feature = "dts-generation")]
+            if index > 0 {
+                target_options.dts = None;
+            }
+            #[cfg(not(feature = "dts-generation"))]
+            let _ = index;
+            (target.name.clone(), target_options)
+        })
+        .collect();
+
+    let results = execute_targets_concurrent(builds).await;
+    merge_target_results(results)
+}
+
+/// Run target builds concurrently, returning results in target order.
+#[cfg(not(target_family = "wasm"))]
+async fn execute_targets_concurrent(
+    builds: Vec<(String, BuildOptions)>,
+) -> Vec<(String, Result<BuildResult>)> {
+    use tokio::task::JoinSet;
+
+    let mut join_set = JoinSet::new();
+    for (index, (name, options)) in builds.into_iter().enumerate() {
+        join_set.spawn(async move { (index, name, execute_unified_build(options).await) });
+    }
+
+    let mut results = Vec::new();
+    while let Some(res) = join_set.join_next().await {
+        match res {
+            Ok(result) => results.push(result),
+            Err(join_err) => {
+                let err = crate::Error::InvalidConfig(format!("Build task panicked: {}", join_err));
+                results.push((usize::MAX, "unknown".to_string(), Err(err)));
+            }
+        }
+    }
+
+    results.sort_by_key(|(index, _, _)| *index);
+    results
+        .into_iter()
+        .map(|(_, name, result)| (name, result))
+        .collect()
+}
+
+/// Sequential fallback for WASM (single-threaded).
+#[cfg(target_family = "wasm")]
+async fn execute_targets_concurrent(
+    builds: Vec<(String, BuildOptions)>,
+) -> Vec<(String, Result<BuildResult>)> {
+    let mut results = Vec::with_capacity(builds.len());
+    for (name, options) in builds {
+        let result = execute_unified_build(options).await;
+        results.push((name, result));
+    }
+    results
+}
+
+/// Combine per-target results into one multi-bundle result.
+fn merge_target_results(results: Vec<(String, Result<BuildResult>)>) -> Result<BuildResult> {
+    let mut bundles = FxHashMap::default();
+    let mut first: Option<BuildResult> = None;
+    let mut warnings = Vec::new();
+    let mut build_errors = Vec::new();
+
+    for (name, result) in results {
+        match result {
+            Ok(mut result) => {
+                let BuildOutput::Single(bundle) = result.output else {
+                    build_errors.push(format!("{}: expected a single bundle", name));
+                    continue;
+                };
+                result.output = BuildOutput::Multiple(FxHashMap::default());
+
+                match &first {
+                    None => warnings.extend(result.analysis.warnings.iter().cloned()),
+                    // Only warnings about the rendered output differ per target
+                    Some(first) => warnings.extend(
+                        result
+                            .analysis
+                            .warnings
+                            .iter()
+                            .filter(|warning| !first.analysis.warnings.contains(warning))
+                            .map(|warning| format!("[{}] {}", name, warning)),
+                    ),
+                }
+                bundles.insert(name, bundle);
+                if first.is_none() {
+                    first = Some(result);
+                }
+            }
+            Err(e) => build_errors.push(format!("{}: {}", name, e)),
+        }
+    }
+
+    if !build_errors.is_empty() {
+        return Err(crate::Error::InvalidConfig(format!(
+            "Build failed for {} output targets:\n{}",
+            build_errors.len(),
+            build_errors.join("\n")
+        )));
+    }
+
+    let mut result = first.ok_or_else(|| {
+        crate::Error::InvalidConfig("At least one output target is required".into())
+    })?;
+    result.analysis.warnings = warnings;
+    result.output = BuildOutput::Multiple(bundles);
+    Ok(result)
+}
+
 /// Execute separate builds (each entry is built independently).
 ///
 /// No code sharing between bundles; each is self-contained.
@@ -280,6 +447,7 @@ fn merge_build_results(
         errors: all_errors,
         stats,
         symbol_stats,
+        workspace: None,
     };
 
     Ok(BuildResult {
@@ -301,12 +469,23 @@ async fn build_single_component(options: &BuildOptions, entry: &str) -> Result<A
             import: entry.to_string(),
         }],
         options: rolldown_options,
-        plugins: Vec::new(),
+        plugins: options.plugins.clone(),
         cwd: options.cwd.clone(),
         virtual_files: options.virtual_files.clone(),
+        env: options.env.clone(),
+        replace: options.replace.clone(),
+        inject: options.inject.clone(),
         runtime: options.runtime.clone(),
-        cache: options.cache.clone(),
+        // Plugins and `[dir]` asset names can't be hashed into the cache key
+        cache: options.cache.clone().filter(|_| {
+            options.plugins.is_empty() && !naming::uses_dir(options.asset_names.as_deref())
+        }),
         incremental: options.incremental.clone(),
+        slow_module_threshold: options.slow_module_threshold,
+        chunk_size_warning_limit: options.chunk_size_warning_limit,
+        output_validation: options.output_validation,
+        sourcemap_rewrite: SourceMapRewrite::new(options.sourcemap, &options.sourcemap_options),
+        top_level_await: options.top_level_await,
     };
 
     execute_bundle(plan).await
@@ -316,10 +495,36 @@ async fn build_single_component(options: &BuildOptions, entry: &str) -> Result<A
 fn configure_rolldown_options(options: &BuildOptions) -> BundlerOptions {
     let mut rolldown_options = BundlerOptions {
         format: Some(options.format),
-        sourcemap: options.sourcemap,
+        sourcemap: sourcemap::rolldown_mode(options.sourcemap, &options.sourcemap_options),
         ..Default::default()
     };
 
+    // Source paths are rewritten while Rolldown renders each map
+    if options.sourcemap.is_some() && options.sourcemap_options.sanitize_paths {
+        let project_root = options
+            .cwd
+            .clone()
+            .or_else(|| std::env::current_dir().ok())
+            .unwrap_or_default();
+        rolldown_options.sourcemap_path_transform = Some(sourcemap::path_transform(project_root));
+    }
+
+    // Output file names
+    if let Some(template) = &options.entry_names {
+        rolldown_options.entry_filenames = Some(naming::chunk_filenames(template));
+    }
+    if let Some(template) = &options.chunk_names {
+        rolldown_options.chunk_filenames = Some(naming::chunk_filenames(template));
+    }
+    if let Some(template) = &options.asset_names {
+        let project_root = options
+            .cwd
+            .clone()
+            .or_else(|| std::env::current_dir().ok())
+            .unwrap_or_default();
+        rolldown_options.asset_filenames = Some(naming::asset_filenames(template, project_root));
+    }
+
     // External packages configuration
     rolldown_options.external = Some(match &options.external {
         ExternalConfig::None => {
@@ -363,15 +568,10 @@ fn configure_rolldown_options(options: &BuildOptions) -> BundlerOptions {
     }
 
     // Module resolution
-    let conditions = match options.platform {
-        Platform::Browser => ExportConditions::browser(),
-        Platform::Node => ExportConditions::node(),
-        _ => ExportConditions::browser(),
-    };
     rolldown_options.resolve = Some(configure_resolution(
         options.cwd.as_ref(),
         &options.path_aliases,
-        &conditions,
+        options.resolved_conditions(),
     ));
 
     rolldown_options
@@ -381,7 +581,7 @@ fn configure_rolldown_options(options: &BuildOptions) -> BundlerOptions {
 fn configure_resolution(
     cwd: Option<&PathBuf>,
     path_aliases: &FxHashMap<String, String>,
-    conditions: &ExportConditions,
+    conditions: Vec<String>,
 ) -> ResolveOptions {
     let modules = if let Some(cwd_path) = cwd {
         let mut paths = vec![];
@@ -408,7 +608,7 @@ fn configure_resolution(
         None
     };
 
-    let main_fields = if conditions.contains("node") {
+    let main_fields = if conditions.iter().any(|condition| condition == "node") {
         vec!["module".to_string(), "main".to_string()]
     } else {
         vec![
@@ -421,7 +621,7 @@ fn configure_resolution(
     ResolveOptions {
         alias: aliases,
         main_fields: Some(main_fields),
-        condition_names: Some(conditions.to_vec()),
+        condition_names: Some(conditions),
         extensions: Some(vec![
             ".js".to_string(),
             ".json".to_string(),
@@ -478,6 +678,7 @@ fn configure_dts_plugin(
             dts_opts.strip_internal,
             dts_opts.sourcemap,
             dts_opts.outdir.clone(),
+            dts_opts.bundle,
         );
         Some(Arc::new(plugin))
     } else {
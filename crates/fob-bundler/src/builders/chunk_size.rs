@@ -0,0 +1,293 @@
+//! Chunk size warnings with suggested dynamic-import boundaries
+//!
+//! After bundling, every output chunk larger than the configured limit
+//! produces a warning listing its largest modules. Using the module graph,
+//! we also suggest modules that would make good `import()` boundaries: their
+//! dependency subtree inside the chunk is large, and they are imported from
+//! only a few places, so lazy-loading them moves a lot of code out of the
+//! chunk with few call sites to change.
+
+use crate::{BundleOutput, Output};
+use fob_graph::{ModuleGraph, ModuleId};
+use rustc_hash::{FxHashMap, FxHashSet};
+use std::fmt;
+
+/// Default chunk size warning limit (500 KiB).
+pub const DEFAULT_CHUNK_SIZE_WARNING_LIMIT: usize = 500 * 1024;
+
+/// Maximum number of largest modules listed per warning.
+const MAX_LARGEST_MODULES: usize = 5;
+
+/// Maximum number of dynamic-import suggestions per warning.
+const MAX_SUGGESTIONS: usize = 3;
+
+/// A module is only a candidate boundary if imported from at most this many modules.
+const MAX_CANDIDATE_IMPORTERS: usize = 2;
+
+/// A candidate's subtree must account for at least 1/N of the chunk.
+const MIN_SUBTREE_FRACTION: usize = 10;
+
+/// Suggested dynamic-import boundary.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SplitSuggestion {
+    /// Module to load with `import()`
+    pub module: String,
+    /// Bytes of the module plus its dependencies that live in the same chunk
+    pub subtree_size: usize,
+    /// Modules that statically import it
+    pub importers: Vec<String>,
+}
+
+/// Warning for a chunk over the size limit.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChunkSizeWarning {
+    /// Output file name of the chunk
+    pub chunk: String,
+    /// Total chunk size in bytes
+    pub size: usize,
+    /// Configured limit in bytes
+    pub limit: usize,
+    /// Largest modules in the chunk, largest first
+    pub largest_modules: Vec<(String, usize)>,
+    /// Candidate dynamic-import boundaries, best first
+    pub suggestions: Vec<SplitSuggestion>,
+}
+
+impl fmt::Display for ChunkSizeWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Chunk {} is {} KiB (limit {} KiB)",
+            self.chunk,
+            self.size / 1024,
+            self.limit / 1024
+        )?;
+
+        if !self.largest_modules.is_empty() {
+            write!(f, "\n  Largest modules:")?;
+            for (module, size) in &self.largest_modules {
+                write!(f, "\n    {} ({} KiB)", module, size / 1024)?;
+            }
+        }
+
+        if !self.suggestions.is_empty() {
+            write!(f, "\n  Consider loading with import():")?;
+            for suggestion in &self.suggestions {
+                write!(
+                    f,
+                    "\n    {} ({} KiB including dependencies, imported by {})",
+                    suggestion.module,
+                    suggestion.subtree_size / 1024,
+                    suggestion.importers.join(", ")
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Check every chunk in a bundle against the size limit.
+pub(crate) fn check_chunk_sizes(
+    bundle: &BundleOutput,
+    graph: &ModuleGraph,
+    limit: usize,
+) -> Vec<ChunkSizeWarning> {
+    bundle
+        .assets
+        .iter()
+        .filter_map(|output| match output {
+            Output::Chunk(chunk) if chunk.code.len() > limit => {
+                let modules: Vec<(String, usize)> = chunk
+                    .modules
+                    .keys
+                    .iter()
+                    .zip(chunk.modules.values.iter())
+                    .map(|(id, rendered)| {
+                        let size = rendered.code().map(|code| code.len()).unwrap_or(0);
+                        (id.to_string(), size)
+                    })
+                    .collect();
+
+                Some(analyze_chunk(
+                    chunk.filename.as_str(),
+                    chunk.code.len(),
+                    &modules,
+                    graph,
+                    limit,
+                ))
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+/// Build the warning for one oversized chunk from its per-module sizes.
+pub(crate) fn analyze_chunk(
+    chunk: &str,
+    size: usize,
+    modules: &[(String, usize)],
+    graph: &ModuleGraph,
+    limit: usize,
+) -> ChunkSizeWarning {
+    let mut largest_modules = modules.to_vec();
+    largest_modules.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    largest_modules.truncate(MAX_LARGEST_MODULES);
+
+    ChunkSizeWarning {
+        chunk: chunk.to_string(),
+        size,
+        limit,
+        largest_modules,
+        suggestions: suggest_split_points(size, modules, graph),
+    }
+}
+
+/// Find modules whose in-chunk subtree is large and that have few importers.
+fn suggest_split_points(
+    chunk_size: usize,
+    modules: &[(String, usize)],
+    graph: &ModuleGraph,
+) -> Vec<SplitSuggestion> {
+    let ids: FxHashMap<ModuleId, (&str, usize)> = modules
+        .iter()
+        .filter_map(|(name, size)| {
+            ModuleId::new(name)
+                .ok()
+                .map(|id| (id, (name.as_str(), *size)))
+        })
+        .collect();
+    let entries: FxHashSet<ModuleId> = graph
+        .entry_points()
+        .unwrap_or_default()
+        .into_iter()
+        .collect();
+    let min_subtree = chunk_size / MIN_SUBTREE_FRACTION;
+
+    let mut suggestions: Vec<SplitSuggestion> = ids
+        .iter()
+        .filter(|(id, _)| !entries.contains(*id))
+        .filter_map(|(id, (name, size))| {
+            let importers = graph.dependents(id).ok()?;
+            if importers.is_empty() || importers.len() > MAX_CANDIDATE_IMPORTERS {
+                return None;
+            }
+
+            let subtree_size = size
+                + graph
+                    .transitive_dependencies(id)
+                    .ok()?
+                    .iter()
+                    .filter_map(|dep| ids.get(dep).map(|(_, dep_size)| *dep_size))
+                    .sum::<usize>();
+            if subtree_size < min_subtree {
+                return None;
+            }
+
+            let mut importers: Vec<String> = importers
+                .iter()
+                .map(|importer| importer.path_string().into_owned())
+                .collect();
+            importers.sort();
+
+            Some(SplitSuggestion {
+                module: name.to_string(),
+                subtree_size,
+                importers,
+            })
+        })
+        .collect();
+
+    suggestions.sort_by(|a, b| {
+        b.subtree_size
+            .cmp(&a.subtree_size)
+            .then_with(|| a.module.cmp(&b.module))
+    });
+    suggestions.truncate(MAX_SUGGESTIONS);
+    suggestions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fob_graph::{Module, SourceType};
+    use std::path::PathBuf;
+
+    fn add(graph: &ModuleGraph, id: &str, entry: bool) -> ModuleId {
+        let module_id = ModuleId::new_virtual(id);
+        let module = Module::builder(module_id.clone(), PathBuf::from(id), SourceType::JavaScript)
+            .entry(entry)
+            .build();
+        graph.add_module(module).unwrap();
+        module_id
+    }
+
+    /// entry → page → chart → chart-lib (large), entry → utils (small)
+    fn fixture() -> (ModuleGraph, Vec<(String, usize)>) {
+        let graph = ModuleGraph::new().unwrap();
+        let entry = add(&graph, "virtual:entry.js", true);
+        let page = add(&graph, "virtual:page.js", false);
+        let chart = add(&graph, "virtual:chart.js", false);
+        let chart_lib = add(&graph, "virtual:chart-lib.js", false);
+        let utils = add(&graph, "virtual:utils.js", false);
+
+        graph.add_dependency(entry.clone(), page.clone()).unwrap();
+        graph.add_dependency(entry, utils.clone()).unwrap();
+        graph.add_dependency(page.clone(), chart.clone()).unwrap();
+        graph.add_dependency(page, utils).unwrap();
+        graph.add_dependency(chart, chart_lib).unwrap();
+
+        let sizes = vec![
+            ("virtual:entry.js".to_string(), 1_000),
+            ("virtual:page.js".to_string(), 5_000),
+            ("virtual:chart.js".to_string(), 10_000),
+            ("virtual:chart-lib.js".to_string(), 400_000),
+            ("virtual:utils.js".to_string(), 2_000),
+        ];
+        (graph, sizes)
+    }
+
+    #[test]
+    fn test_largest_modules_sorted() {
+        let (graph, sizes) = fixture();
+        let warning = analyze_chunk("index.js", 418_000, &sizes, &graph, 100_000);
+
+        assert_eq!(warning.largest_modules[0].0, "virtual:chart-lib.js");
+        assert_eq!(warning.largest_modules[1].0, "virtual:chart.js");
+        assert_eq!(warning.largest_modules.len(), 5);
+    }
+
+    #[test]
+    fn test_suggests_large_subtrees_with_few_importers() {
+        let (graph, sizes) = fixture();
+        let warning = analyze_chunk("index.js", 418_000, &sizes, &graph, 100_000);
+
+        let suggested: Vec<&str> = warning
+            .suggestions
+            .iter()
+            .map(|s| s.module.as_str())
+            .collect();
+        // Entry is never suggested, utils is too small
+        assert_eq!(
+            suggested,
+            vec![
+                "virtual:page.js",
+                "virtual:chart.js",
+                "virtual:chart-lib.js"
+            ]
+        );
+        assert_eq!(warning.suggestions[0].subtree_size, 417_000);
+        assert_eq!(warning.suggestions[1].importers, vec!["virtual:page.js"]);
+    }
+
+    #[test]
+    fn test_display_lists_modules_and_suggestions() {
+        let (graph, sizes) = fixture();
+        let warning = analyze_chunk("index.js", 418_000, &sizes, &graph, 100_000);
+        let text = warning.to_string();
+
+        assert!(text.starts_with("Chunk index.js is 408 KiB (limit 97 KiB)"));
+        assert!(text.contains("Largest modules:"));
+        assert!(text.contains("Consider loading with import():"));
+    }
+}
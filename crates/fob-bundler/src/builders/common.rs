@@ -9,6 +9,7 @@ use rolldown_plugin::__inner::SharedPluginable;
 use rustc_hash::FxHashMap;
 
 use crate::analysis::AnalyzedBundle;
+use crate::builders::top_level_await::{TopLevelAwait, TopLevelAwaitPlugin};
 use crate::builders::{asset_plugin::AssetDetectionPlugin, asset_registry::AssetRegistry};
 use crate::diagnostics;
 use crate::module_collection_plugin::ModuleCollectionPlugin;
@@ -17,15 +18,15 @@ use crate::{Error, Result};
 use fob_graph::analysis::stats::compute_stats;
 use fob_graph::{AnalysisResult, CacheAnalysis, TransformationTrace};
 
+/// Maximum size of a single virtual file's content (1MB).
+pub(crate) const MAX_VIRTUAL_FILE_SIZE: usize = 1024 * 1024;
+
 /// Normalize an entry path by cleaning redundant `.` / `..` segments.
 pub(crate) fn normalize_entry_path(entry: impl AsRef<Path>) -> String {
     let cleaned: PathBuf = entry.as_ref().to_path_buf().clean();
     cleaned.to_string_lossy().into_owned()
 }
 
-// Plugin API helpers removed - plugins are now added directly via Arc<Plugin>
-// The public .plugin() method on BuildOptions has been removed.
-
 #[derive(Clone, Debug)]
 pub(crate) struct EntrySpec {
     pub name: Option<String>,
@@ -38,9 +39,17 @@ pub(crate) struct BundlePlan {
     pub plugins: Vec<SharedPluginable>,
     pub cwd: Option<PathBuf>,
     pub virtual_files: FxHashMap<String, String>,
+    pub env: FxHashMap<String, String>,
+    pub replace: FxHashMap<String, String>,
+    pub inject: FxHashMap<String, crate::builders::replace::InjectImport>,
     pub runtime: Option<Arc<dyn crate::Runtime>>,
     pub cache: Option<crate::cache::CacheConfig>,
     pub incremental: Option<crate::builders::unified::primitives::IncrementalConfig>,
+    pub slow_module_threshold: Option<std::time::Duration>,
+    pub chunk_size_warning_limit: Option<usize>,
+    pub output_validation: Option<crate::builders::output_validation::SyntaxTarget>,
+    pub sourcemap_rewrite: Option<crate::builders::sourcemap::SourceMapRewrite>,
+    pub top_level_await: TopLevelAwait,
 }
 
 pub(crate) async fn execute_bundle(plan: BundlePlan) -> Result<AnalyzedBundle> {
@@ -52,9 +61,17 @@ pub(crate) async fn execute_bundle(plan: BundlePlan) -> Result<AnalyzedBundle> {
             plugins: plan.plugins.clone(),
             cwd: plan.cwd.clone(),
             virtual_files: plan.virtual_files.clone(),
+            env: plan.env.clone(),
+            replace: plan.replace.clone(),
+            inject: plan.inject.clone(),
             runtime: plan.runtime.clone(),
             cache: None,       // Don't include cache config in the key
             incremental: None, // Don't include incremental config in the key
+            slow_module_threshold: None,
+            chunk_size_warning_limit: None,
+            output_validation: None,
+            sourcemap_rewrite: plan.sourcemap_rewrite.clone(),
+            top_level_await: plan.top_level_await,
         })
     } else {
         None
@@ -66,9 +83,17 @@ pub(crate) async fn execute_bundle(plan: BundlePlan) -> Result<AnalyzedBundle> {
         plugins,
         cwd,
         virtual_files,
+        env,
+        replace,
+        inject,
         runtime,
         cache: cache_config,
         incremental: incremental_config,
+        slow_module_threshold,
+        chunk_size_warning_limit,
+        output_validation,
+        sourcemap_rewrite,
+        top_level_await,
     } = plan;
 
     // Try to load from cache if enabled
@@ -196,8 +221,7 @@ pub(crate) async fn execute_bundle(plan: BundlePlan) -> Result<AnalyzedBundle> {
             )));
         }
 
-        // Validate virtual file content size (1MB limit)
-        const MAX_VIRTUAL_FILE_SIZE: usize = 1024 * 1024;
+        // Validate virtual file content size
         if content.len() > MAX_VIRTUAL_FILE_SIZE {
             return Err(Error::WriteFailure(format!(
                 "Virtual file content too large: {} bytes (max {} bytes)",
@@ -210,8 +234,39 @@ pub(crate) async fn execute_bundle(plan: BundlePlan) -> Result<AnalyzedBundle> {
     }
 
     // Create RuntimeFilePlugin (Virtual phase - serves virtual files)
-    let runtime_file_plugin =
-        crate::builders::runtime_file_plugin::RuntimeFilePlugin::new(Arc::clone(&bundler_runtime));
+    let runtime_file_plugin: SharedPluginable = Arc::new(
+        crate::builders::runtime_file_plugin::RuntimeFilePlugin::new(Arc::clone(&bundler_runtime)),
+    );
+
+    // Create plugin for fob:env / fob:manifest / fob:assets/* (Virtual phase)
+    let virtual_modules_plugin: SharedPluginable = Arc::new(
+        crate::builders::virtual_modules::VirtualModulesPlugin::new(env, &scan_cwd),
+    );
+
+    // Replace and inject globals after the user's plugins have compiled
+    // their modules to JavaScript (Transform phase)
+    let replace_plugin: Option<SharedPluginable> = (!replace.is_empty() || !inject.is_empty())
+        .then(|| {
+            Arc::new(crate::builders::replace::ReplacePlugin::new(
+                &replace, &inject, &scan_cwd,
+            )) as SharedPluginable
+        });
+
+    // Worker bundles see virtual files and run the user's plugins (Assets phase,
+    // ahead of the asset plugin so worker URLs aren't copied as plain files)
+    #[cfg(not(target_family = "wasm"))]
+    let worker_plugin = {
+        let mut worker_plugins = vec![
+            Arc::clone(&runtime_file_plugin),
+            Arc::clone(&virtual_modules_plugin),
+        ];
+        worker_plugins.extend(plugins.iter().cloned());
+        worker_plugins.extend(replace_plugin.iter().cloned());
+        Arc::new(crate::builders::workers::WorkerPlugin::new(
+            &options,
+            worker_plugins,
+        ))
+    };
 
     // Create asset registry and asset detection plugin (Assets phase)
     let asset_registry = Arc::new(AssetRegistry::new());
@@ -239,34 +294,82 @@ pub(crate) async fn execute_bundle(plan: BundlePlan) -> Result<AnalyzedBundle> {
         Arc::clone(&runtime),
     );
 
+    // Only ESM output can keep top-level await; other formats get a clear
+    // error per module, and ESM builds can isolate those modules
+    let output_format = options.format.unwrap_or(crate::OutputFormat::Esm);
+    let isolate_tla = top_level_await == TopLevelAwait::Isolate;
+    let tla_plugin = (isolate_tla || !matches!(output_format, crate::OutputFormat::Esm))
+        .then(|| Arc::new(TopLevelAwaitPlugin::new(scan_cwd.clone())));
+    if let (Some(plugin), true) = (&tla_plugin, isolate_tla) {
+        let advanced_chunks = options.advanced_chunks.get_or_insert_with(Default::default);
+        let groups = advanced_chunks.groups.get_or_insert_with(Vec::new);
+        // After manual chunks, which take precedence
+        let index = groups
+            .iter()
+            .take_while(|group| group.priority == Some(u32::MAX))
+            .count();
+        groups.insert(index, plugin.isolation_group());
+    }
+
     // Build plugin registry with guaranteed ordering by phase:
     // Virtual (0) → Transform (20) → Assets (30) → PostProcess (100)
     let mut registry = PluginRegistry::new();
 
     // Built-in plugins use their FobPlugin::phase() for ordering
-    registry.add(runtime_file_plugin); // Virtual = 0
+    registry.add_builtin(runtime_file_plugin, PluginPhase::Virtual);
+    registry.add_builtin(virtual_modules_plugin, PluginPhase::Virtual);
+    #[cfg(not(target_family = "wasm"))]
+    registry.add_builtin(worker_plugin, PluginPhase::Assets);
     registry.add(asset_plugin); // Assets = 30
-    // collection_plugin is already Arc<T>, so use add_with_phase
-    registry.add_with_phase(collection_plugin.clone(), PluginPhase::PostProcess);
+    // collection_plugin is already Arc<T>, so use add_builtin
+    registry.add_builtin(collection_plugin.clone(), PluginPhase::PostProcess);
+    if let Some(plugin) = &tla_plugin {
+        registry.add_builtin(plugin.clone(), PluginPhase::PostProcess);
+    }
 
     // User plugins default to Transform phase
     for plugin in plugins {
         registry.add_with_phase(plugin, PluginPhase::Transform);
     }
+    if let Some(plugin) = replace_plugin {
+        registry.add_builtin(plugin, PluginPhase::Transform);
+    }
 
     // Convert to ordered Vec for Rolldown (sorted by phase)
     let ordered_plugins = registry.into_rolldown_plugins();
 
+    // Interleave timing probes so slow modules can be attributed to a plugin
+    #[cfg(not(target_family = "wasm"))]
+    let (ordered_plugins, module_timings) = match slow_module_threshold {
+        Some(threshold) => {
+            let (plugins, timings) = crate::builders::module_timing::instrument(ordered_plugins);
+            (plugins, Some((timings, threshold)))
+        }
+        None => (ordered_plugins, None),
+    };
+    #[cfg(target_family = "wasm")]
+    let _ = slow_module_threshold;
+
     let mut bundler = RolldownBundlerBuilder::default()
         .with_options(options)
         .with_plugins(ordered_plugins)
         .build()
         .map_err(|e| Error::from_rolldown_batch(&e))?;
 
-    let bundle = bundler
-        .generate()
-        .await
-        .map_err(|e| Error::from_rolldown_batch(&e))?;
+    let generated = bundler.generate().await;
+    if let Some(plugin) = &tla_plugin {
+        // Replaces Rolldown's unsupported-feature error with one per module
+        crate::builders::top_level_await::check_format(&plugin.uses(), output_format)?;
+    }
+    let mut bundle = generated.map_err(|e| Error::from_rolldown_batch(&e))?;
+
+    if let Some(target) = output_validation {
+        crate::builders::output_validation::validate_bundle(&bundle, output_format, target)?;
+    }
+
+    if let Some(rewrite) = &sourcemap_rewrite {
+        crate::builders::sourcemap::rewrite_bundle(&mut bundle, rewrite)?;
+    }
 
     let asset_registry_opt = if !asset_registry.is_empty() {
         Some(asset_registry)
@@ -352,13 +455,31 @@ pub(crate) async fn execute_bundle(plan: BundlePlan) -> Result<AnalyzedBundle> {
     let stats = compute_stats(&graph)?;
     let entry_points = graph.entry_points()?;
     let symbol_stats = graph.symbol_statistics()?;
+
+    #[cfg(not(target_family = "wasm"))]
+    let mut warnings = match module_timings {
+        Some((timings, threshold)) => timings.slow_module_warnings(threshold),
+        None => Vec::new(),
+    };
+    #[cfg(target_family = "wasm")]
+    let mut warnings = Vec::new();
+
+    if let Some(limit) = chunk_size_warning_limit {
+        warnings.extend(
+            crate::builders::chunk_size::check_chunk_sizes(&bundle, &graph, limit)
+                .iter()
+                .map(ToString::to_string),
+        );
+    }
+
     let analysis = AnalysisResult {
         graph,
         entry_points,
-        warnings: Vec::new(),
+        warnings,
         errors: Vec::new(),
         stats,
         symbol_stats,
+        workspace: None,
     };
 
     let cache = CacheAnalysis::default();
@@ -450,6 +571,7 @@ fn try_load_from_cache(
         errors: cached.errors,
         stats: fob_graph::GraphStatistics::default(),
         symbol_stats: fob_graph::SymbolStatistics::new(0, 0),
+        workspace: None,
     };
 
     // Reconstruct asset registry if present
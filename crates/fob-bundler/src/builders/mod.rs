@@ -35,6 +35,8 @@
 
 pub(crate) mod build_executor;
 pub(crate) mod common;
+#[cfg(not(target_family = "wasm"))]
+pub(crate) mod module_timing;
 pub(crate) mod runtime_file_plugin;
 pub(crate) mod unified;
 
@@ -44,11 +46,53 @@ pub mod asset_processor;
 pub mod asset_registry;
 pub mod asset_resolver;
 
+// Chunk size warnings
+pub mod chunk_size;
+
+// Entry, chunk and asset file name templates
+pub(crate) mod naming;
+
+// Post-emit syntax validation
+pub mod output_validation;
+
+// Source map options applied around Rolldown's map generation
+pub mod sourcemap;
+
+// Top-level await detection and chunk isolation
+pub mod top_level_await;
+
+// Separate bundles for `new Worker(new URL(...))` and worklet references
+#[cfg(not(target_family = "wasm"))]
+pub(crate) mod workers;
+
+// package.json entry points checked against build output
+pub mod package_exports;
+
+// Built-in `fob:` virtual modules
+pub mod virtual_modules;
+
+// Build-time replacement of globals and import injection
+pub mod replace;
+
+// Unbundled dev serving: per-module transforms and prebundled dependencies
+#[cfg(not(target_family = "wasm"))]
+pub mod unbundled;
+
 // Re-export public API
 pub use unified::{
     BuildOptions, BuildOutput, BuildResult, CodeSplittingConfig, EntryMode, EntryPoints,
-    ExternalConfig, IncrementalConfig, MinifyLevel, build,
+    ExternalConfig, IncrementalConfig, MAX_ENTRY_POINTS, ManualChunks, MinifyLevel, OutputTarget,
+    build,
+};
+
+pub use output_validation::SyntaxTarget;
+pub use package_exports::{
+    EmittedFormat, PackageEntryPoints, PackageExportIssue, PackageExportIssueKind, PackageOutputs,
+    SubpathExport, validate_package_exports,
 };
+pub use replace::InjectImport;
+pub use sourcemap::SourceMapOptions;
+pub use top_level_await::TopLevelAwait;
 
 #[cfg(feature = "dts-generation")]
 pub use unified::DtsOptions;
@@ -0,0 +1,256 @@
+//! Per-module load/transform timing
+//!
+//! Rolldown runs `transform` hooks for a module sequentially in plugin order,
+//! so placing a lightweight probe plugin before the first plugin and after
+//! every plugin lets us attribute the time between two probes to the plugin
+//! that sits between them. The first probe also marks the start of `load`,
+//! so the time until its `transform` hook fires is the module's load time.
+//!
+//! ```text
+//! probe[0] → plugin A → probe[1] → plugin B → probe[2] ...
+//!   load start                           transform: B took probe[2] - probe[1]
+//! ```
+//!
+//! Modules whose total exceeds the configured threshold are reported as
+//! warnings after the build. Native only, since `Instant` is unavailable on WASM.
+
+use crate::SharedPluginable;
+use parking_lot::Mutex;
+use rolldown_plugin::{
+    HookLoadArgs, HookLoadReturn, HookTransformArgs, HookTransformReturn, HookUsage, Plugin,
+    PluginContext, SharedTransformPluginContext,
+};
+use rustc_hash::FxHashMap;
+use std::borrow::Cow;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Timing state for a single module.
+#[derive(Debug)]
+struct ModuleTiming {
+    /// Time of the most recent probe
+    last: Instant,
+    /// Time spent per step, keyed by step label
+    steps: Vec<(String, Duration)>,
+}
+
+/// Shared timing data collected by the probes.
+#[derive(Debug, Default)]
+pub(crate) struct ModuleTimings {
+    modules: Mutex<FxHashMap<String, ModuleTiming>>,
+    /// Label for the step ending at probe `n` (index 0 is "load")
+    labels: Vec<String>,
+}
+
+impl ModuleTimings {
+    /// Mark the start of loading a module.
+    fn start(&self, id: &str) {
+        self.modules.lock().insert(
+            id.to_string(),
+            ModuleTiming {
+                last: Instant::now(),
+                steps: Vec::new(),
+            },
+        );
+    }
+
+    /// Record that probe `slot` fired for a module.
+    fn mark(&self, id: &str, slot: usize) {
+        let now = Instant::now();
+        let mut modules = self.modules.lock();
+        let Some(timing) = modules.get_mut(id) else {
+            // Module was loaded before the probe saw it (e.g. cached); nothing to attribute
+            return;
+        };
+
+        let elapsed = now.duration_since(timing.last);
+        timing.last = now;
+        if let Some(label) = self.labels.get(slot) {
+            timing.steps.push((label.clone(), elapsed));
+        }
+    }
+
+    /// Warnings for all modules whose total time exceeds `threshold`.
+    ///
+    /// Sorted slowest first. Each warning names the step that took longest.
+    pub fn slow_module_warnings(&self, threshold: Duration) -> Vec<String> {
+        let modules = self.modules.lock();
+        let mut slow: Vec<(&String, Duration, &ModuleTiming)> = modules
+            .iter()
+            .map(|(id, timing)| (id, timing.steps.iter().map(|(_, d)| *d).sum(), timing))
+            .filter(|(_, total, _)| *total > threshold)
+            .collect();
+        slow.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(b.0)));
+
+        slow.into_iter()
+            .map(|(id, total, timing)| format_warning(id, total, &timing.steps, threshold))
+            .collect()
+    }
+}
+
+/// Format a slow-module warning.
+fn format_warning(
+    id: &str,
+    total: Duration,
+    steps: &[(String, Duration)],
+    threshold: Duration,
+) -> String {
+    let mut message = format!(
+        "Slow module: {} took {}ms to load and transform (threshold {}ms)",
+        id,
+        total.as_millis(),
+        threshold.as_millis()
+    );
+
+    if let Some((label, duration)) = steps.iter().max_by_key(|(_, d)| *d) {
+        message.push_str(&format!(
+            "; most time in {} ({}ms)",
+            label,
+            duration.as_millis()
+        ));
+    }
+
+    message
+}
+
+/// Probe plugin placed around every real plugin.
+#[derive(Debug)]
+struct TimingProbe {
+    timings: Arc<ModuleTimings>,
+    slot: usize,
+}
+
+impl Plugin for TimingProbe {
+    fn name(&self) -> Cow<'static, str> {
+        format!("fob-timing-probe-{}", self.slot).into()
+    }
+
+    fn register_hook_usage(&self) -> HookUsage {
+        if self.slot == 0 {
+            HookUsage::Load | HookUsage::Transform
+        } else {
+            HookUsage::Transform
+        }
+    }
+
+    fn load(
+        &self,
+        _ctx: &PluginContext,
+        args: &HookLoadArgs<'_>,
+    ) -> impl std::future::Future<Output = HookLoadReturn> + Send {
+        if self.slot == 0 {
+            self.timings.start(args.id);
+        }
+        async { Ok(None) }
+    }
+
+    fn transform(
+        &self,
+        _ctx: SharedTransformPluginContext,
+        args: &HookTransformArgs<'_>,
+    ) -> impl std::future::Future<Output = HookTransformReturn> + Send {
+        self.timings.mark(args.id, self.slot);
+        async { Ok(None) }
+    }
+}
+
+/// Interleave timing probes with the ordered plugin list.
+///
+/// Returns the instrumented plugin list and the shared timing data.
+pub(crate) fn instrument(
+    plugins: Vec<SharedPluginable>,
+) -> (Vec<SharedPluginable>, Arc<ModuleTimings>) {
+    let mut labels = Vec::with_capacity(plugins.len() + 1);
+    labels.push("load".to_string());
+    labels.extend(
+        plugins
+            .iter()
+            .map(|plugin| format!("{} transform", plugin.call_name())),
+    );
+
+    let timings = Arc::new(ModuleTimings {
+        modules: Mutex::default(),
+        labels,
+    });
+
+    let mut instrumented = Vec::with_capacity(plugins.len() * 2 + 1);
+    instrumented.push(probe(&timings, 0));
+    for (index, plugin) in plugins.into_iter().enumerate() {
+        instrumented.push(plugin);
+        instrumented.push(probe(&timings, index + 1));
+    }
+
+    (instrumented, timings)
+}
+
+fn probe(timings: &Arc<ModuleTimings>, slot: usize) -> SharedPluginable {
+    Arc::new(TimingProbe {
+        timings: Arc::clone(timings),
+        slot,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn timings_with(id: &str, steps: &[(&str, u64)]) -> ModuleTimings {
+        let timings = ModuleTimings::default();
+        timings.modules.lock().insert(
+            id.to_string(),
+            ModuleTiming {
+                last: Instant::now(),
+                steps: steps
+                    .iter()
+                    .map(|(label, ms)| (label.to_string(), Duration::from_millis(*ms)))
+                    .collect(),
+            },
+        );
+        timings
+    }
+
+    #[test]
+    fn test_slow_module_reports_dominant_step() {
+        let timings = timings_with("/src/big.ts", &[("load", 50), ("fob-css transform", 900)]);
+
+        let warnings = timings.slow_module_warnings(Duration::from_millis(500));
+
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].contains("/src/big.ts took 950ms"));
+        assert!(warnings[0].contains("most time in fob-css transform (900ms)"));
+    }
+
+    #[test]
+    fn test_fast_module_not_reported() {
+        let timings = timings_with("/src/small.ts", &[("load", 1), ("x transform", 2)]);
+
+        assert!(
+            timings
+                .slow_module_warnings(Duration::from_millis(500))
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_mark_attributes_time_to_slot_label() {
+        let timings = ModuleTimings {
+            modules: Mutex::default(),
+            labels: vec!["load".to_string(), "a transform".to_string()],
+        };
+
+        timings.start("/m.js");
+        timings.mark("/m.js", 0);
+        timings.mark("/m.js", 1);
+        // Unknown modules are ignored
+        timings.mark("/other.js", 1);
+
+        let modules = timings.modules.lock();
+        let steps: Vec<&str> = modules["/m.js"]
+            .steps
+            .iter()
+            .map(|(l, _)| l.as_str())
+            .collect();
+        assert_eq!(steps, vec!["load", "a transform"]);
+        assert!(!modules.contains_key("/other.js"));
+    }
+}
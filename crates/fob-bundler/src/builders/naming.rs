@@ -0,0 +1,174 @@
+//! Output file naming templates
+//!
+//! Entry, chunk and asset names are passed to Rolldown as filename templates
+//! (`[name]`, `[hash]`, `[hash:N]`, `[format]`, `[ext]`, `[extname]`). Two
+//! placeholders are filled in here because Rolldown doesn't know them:
+//! `[ext]`/`[extname]` in JavaScript templates, and `[dir]` in asset
+//! templates, the directory of the source file relative to the project root.
+
+use crate::{Error, Result};
+use rolldown_common::{
+    AssetFilenamesOutputOption, ChunkFilenamesOutputOption, RollupPreRenderedAsset,
+};
+use std::future::Future;
+use std::path::{Component, Path, PathBuf};
+use std::pin::Pin;
+use std::sync::Arc;
+
+/// Placeholder for the source directory of an asset.
+const DIR_PLACEHOLDER: &str = "[dir]";
+
+/// Check a template before the build, naming the option in the error.
+pub(crate) fn validate_template(option: &str, template: &str, allow_dir: bool) -> Result<()> {
+    if template.trim().is_empty() {
+        return Err(Error::InvalidConfig(format!("{} cannot be empty", option)));
+    }
+
+    let path = Path::new(template);
+    if path.is_absolute() || template.starts_with('/') || template.starts_with('\\') {
+        return Err(Error::InvalidConfig(format!(
+            "{} must be relative to the output directory: {}",
+            option, template
+        )));
+    }
+    if path.components().any(|c| matches!(c, Component::ParentDir)) {
+        return Err(Error::InvalidConfig(format!(
+            "{} cannot contain '..': {}",
+            option, template
+        )));
+    }
+    if !allow_dir && template.contains(DIR_PLACEHOLDER) {
+        return Err(Error::InvalidConfig(format!(
+            "{} doesn't support [dir], which is only available for asset names",
+            option
+        )));
+    }
+
+    Ok(())
+}
+
+/// Whether an asset template needs a callback, which the build cache can't hash.
+pub(crate) fn uses_dir(asset_names: Option<&str>) -> bool {
+    asset_names.is_some_and(|template| template.contains(DIR_PLACEHOLDER))
+}
+
+/// Rolldown option for an entry or chunk name template.
+pub(crate) fn chunk_filenames(template: &str) -> ChunkFilenamesOutputOption {
+    // Rolldown only fills in [ext] for assets; JavaScript chunks are always `.js`
+    template
+        .replace("[extname]", ".js")
+        .replace("[ext]", "js")
+        .into()
+}
+
+/// Rolldown option for an asset name template.
+pub(crate) fn asset_filenames(template: &str, project_root: PathBuf) -> AssetFilenamesOutputOption {
+    if !uses_dir(Some(template)) {
+        return template.to_string().into();
+    }
+
+    let template = template.to_string();
+    AssetFilenamesOutputOption::Fn(Arc::new(move |asset: &RollupPreRenderedAsset| {
+        let dir = asset
+            .original_file_names
+            .first()
+            .map(|file| source_dir(Path::new(file.as_str()), &project_root))
+            .unwrap_or_default();
+        let filename = expand_dir(&template, &dir);
+        Box::pin(async move { Ok(filename) })
+            as Pin<Box<dyn Future<Output = anyhow::Result<String>> + Send>>
+    }))
+}
+
+/// Directory of `file` relative to the project root, `/`-separated.
+///
+/// Files outside the project have no meaningful directory and get an
+/// empty one, like assets without a source file.
+fn source_dir(file: &Path, project_root: &Path) -> String {
+    let Some(parent) = file.parent() else {
+        return String::new();
+    };
+    let relative = if parent.is_absolute() {
+        match parent.strip_prefix(project_root) {
+            Ok(relative) => relative,
+            Err(_) => return String::new(),
+        }
+    } else {
+        parent
+    };
+
+    relative
+        .components()
+        .filter_map(|c| match c {
+            Component::Normal(segment) => Some(segment.to_string_lossy()),
+            _ => None,
+        })
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Replace `[dir]`, dropping the separator it leaves behind when empty.
+fn expand_dir(template: &str, dir: &str) -> String {
+    if !dir.is_empty() {
+        return template.replace(DIR_PLACEHOLDER, dir);
+    }
+    template
+        .replace(&format!("{}/", DIR_PLACEHOLDER), "")
+        .replace(DIR_PLACEHOLDER, "")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_template() {
+        assert!(validate_template("chunk_names", "chunks/[name]-[hash].js", false).is_ok());
+        assert!(validate_template("asset_names", "assets/[dir]/[name][extname]", true).is_ok());
+
+        assert!(validate_template("chunk_names", "", false).is_err());
+        assert!(validate_template("chunk_names", "/tmp/[name].js", false).is_err());
+        assert!(validate_template("chunk_names", "../[name].js", false).is_err());
+        assert!(validate_template("chunk_names", "[dir]/[name].js", false).is_err());
+    }
+
+    #[test]
+    fn test_chunk_ext_is_filled_in() {
+        let ChunkFilenamesOutputOption::String(template) = chunk_filenames("[name]-[hash].[ext]")
+        else {
+            panic!("expected a string template");
+        };
+        assert_eq!(template, "[name]-[hash].js");
+
+        let ChunkFilenamesOutputOption::String(template) = chunk_filenames("[name][extname]")
+        else {
+            panic!("expected a string template");
+        };
+        assert_eq!(template, "[name].js");
+    }
+
+    #[test]
+    fn test_source_dir() {
+        let root = Path::new("/project");
+
+        assert_eq!(
+            source_dir(Path::new("/project/src/images/logo.png"), root),
+            "src/images"
+        );
+        assert_eq!(source_dir(Path::new("/project/logo.png"), root), "");
+        assert_eq!(source_dir(Path::new("/elsewhere/logo.png"), root), "");
+        assert_eq!(source_dir(Path::new("src/logo.png"), root), "src");
+    }
+
+    #[test]
+    fn test_expand_dir() {
+        assert_eq!(
+            expand_dir("assets/[dir]/[name]-[hash][extname]", "src/images"),
+            "assets/src/images/[name]-[hash][extname]"
+        );
+        assert_eq!(
+            expand_dir("assets/[dir]/[name]-[hash][extname]", ""),
+            "assets/[name]-[hash][extname]"
+        );
+    }
+}
@@ -0,0 +1,450 @@
+//! Post-emit output validation
+//!
+//! Some plugin combinations produce output that does not parse, or that uses
+//! syntax the target runtime does not support. When enabled, every emitted JS
+//! chunk is re-parsed with oxc (as a module for ESM output, as a script
+//! otherwise) and scanned for syntax newer than the configured
+//! [`SyntaxTarget`]. Problems are reported as diagnostics pointing at the
+//! chunk, and at the original source when the chunk has a source map.
+
+use crate::diagnostics::{DiagnosticKind, DiagnosticSeverity, ExtractedDiagnostic};
+use crate::{BundleOutput, Error, Output, OutputChunk, OutputFormat, Result};
+use oxc_allocator::Allocator;
+use oxc_ast::ast::{
+    ArrowFunctionExpression, AssignmentExpression, AssignmentOperator, AwaitExpression,
+    BigIntLiteral, BinaryExpression, BinaryOperator, CatchClause, ChainExpression, ForOfStatement,
+    Function, LogicalExpression, LogicalOperator, NumericLiteral, ObjectExpression, ObjectPattern,
+    ObjectPropertyKind, PrivateIdentifier, PrivateInExpression, PropertyDefinition, StaticBlock,
+};
+use oxc_ast_visit::{Visit, walk};
+use oxc_parser::Parser;
+use oxc_semantic::ScopeFlags;
+use oxc_span::{SourceType, Span};
+use rustc_hash::FxHashSet;
+use std::fmt;
+use std::str::FromStr;
+
+/// ECMAScript syntax level emitted chunks must conform to.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum SyntaxTarget {
+    Es2015,
+    Es2016,
+    Es2017,
+    Es2018,
+    Es2019,
+    Es2020,
+    Es2021,
+    Es2022,
+    /// Any syntax the parser accepts
+    #[default]
+    EsNext,
+}
+
+impl fmt::Display for SyntaxTarget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::Es2015 => "es2015",
+            Self::Es2016 => "es2016",
+            Self::Es2017 => "es2017",
+            Self::Es2018 => "es2018",
+            Self::Es2019 => "es2019",
+            Self::Es2020 => "es2020",
+            Self::Es2021 => "es2021",
+            Self::Es2022 => "es2022",
+            Self::EsNext => "esnext",
+        };
+        f.write_str(name)
+    }
+}
+
+impl FromStr for SyntaxTarget {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "es2015" | "es6" => Ok(Self::Es2015),
+            "es2016" => Ok(Self::Es2016),
+            "es2017" => Ok(Self::Es2017),
+            "es2018" => Ok(Self::Es2018),
+            "es2019" => Ok(Self::Es2019),
+            "es2020" => Ok(Self::Es2020),
+            "es2021" => Ok(Self::Es2021),
+            "es2022" => Ok(Self::Es2022),
+            "esnext" => Ok(Self::EsNext),
+            other => Err(Error::InvalidConfig(format!(
+                "Unknown syntax target '{}'. Expected es2015-es2022 or esnext",
+                other
+            ))),
+        }
+    }
+}
+
+/// Validate every JS chunk in a bundle.
+///
+/// Returns [`Error::Bundler`] with one diagnostic per problem if any chunk is invalid.
+pub(crate) fn validate_bundle(
+    bundle: &BundleOutput,
+    format: OutputFormat,
+    target: SyntaxTarget,
+) -> Result<()> {
+    let diagnostics: Vec<ExtractedDiagnostic> = bundle
+        .assets
+        .iter()
+        .filter_map(|output| match output {
+            Output::Chunk(chunk) => Some(validate_chunk(chunk, format, target)),
+            Output::Asset(_) => None,
+        })
+        .flatten()
+        .collect();
+
+    if diagnostics.is_empty() {
+        Ok(())
+    } else {
+        Err(Error::Bundler(diagnostics))
+    }
+}
+
+fn validate_chunk(
+    chunk: &OutputChunk,
+    format: OutputFormat,
+    target: SyntaxTarget,
+) -> Vec<ExtractedDiagnostic> {
+    let problems = check_syntax(&chunk.code, source_type_for(format), target);
+    if problems.is_empty() {
+        return Vec::new();
+    }
+
+    let lookup = chunk
+        .map
+        .as_ref()
+        .map(|map| (map, map.generate_lookup_table()));
+
+    problems
+        .into_iter()
+        .map(|problem| {
+            let (line, column) = line_col(&chunk.code, problem.span.start);
+            let original = lookup.as_ref().and_then(|(map, table)| {
+                let token = map.lookup_token(table, line, column)?;
+                let source = map.get_source(token.get_source_id()?)?;
+                Some(format!(
+                    "{}:{}:{}",
+                    source,
+                    token.get_src_line() + 1,
+                    token.get_src_col() + 1
+                ))
+            });
+
+            let help = match original {
+                Some(location) => format!("Generated from {}. {}", location, problem.help),
+                None => problem.help,
+            };
+
+            ExtractedDiagnostic {
+                kind: problem.kind,
+                severity: DiagnosticSeverity::Error,
+                message: format!("Invalid output in {}: {}", chunk.filename, problem.message),
+                file: Some(chunk.filename.to_string()),
+                line: Some(line + 1),
+                column: Some(column + 1),
+                help: Some(help),
+                context: None,
+                error_chain: Vec::new(),
+            }
+        })
+        .collect()
+}
+
+/// Parse mode matching the output format.
+fn source_type_for(format: OutputFormat) -> SourceType {
+    match format {
+        OutputFormat::Esm => SourceType::mjs(),
+        _ => SourceType::cjs(),
+    }
+}
+
+/// A problem found in emitted code.
+#[derive(Debug)]
+struct SyntaxProblem {
+    kind: DiagnosticKind,
+    message: String,
+    help: String,
+    span: Span,
+}
+
+const PARSE_ERROR_HELP: &str =
+    "The emitted code does not parse; a plugin likely produced invalid output";
+
+/// Parse `code` and report parse errors or syntax above `target`.
+fn check_syntax(code: &str, source_type: SourceType, target: SyntaxTarget) -> Vec<SyntaxProblem> {
+    let allocator = Allocator::default();
+    let parsed = Parser::new(&allocator, code, source_type).parse();
+
+    if !parsed.errors.is_empty() {
+        return parsed
+            .errors
+            .iter()
+            .map(|error| {
+                let span = error
+                    .labels
+                    .as_ref()
+                    .and_then(|labels| labels.first())
+                    .map(|label| Span::sized(label.offset() as u32, label.len() as u32))
+                    .unwrap_or_default();
+                SyntaxProblem {
+                    kind: DiagnosticKind::ParseError,
+                    message: error.message.to_string(),
+                    help: PARSE_ERROR_HELP.to_string(),
+                    span,
+                }
+            })
+            .collect();
+    }
+
+    if target == SyntaxTarget::EsNext {
+        return Vec::new();
+    }
+
+    let mut checker = SyntaxLevelChecker {
+        target,
+        function_depth: 0,
+        reported: FxHashSet::default(),
+        problems: Vec::new(),
+    };
+    checker.visit_program(&parsed.program);
+    checker.problems
+}
+
+/// Visitor reporting the first use of each feature newer than the target.
+struct SyntaxLevelChecker {
+    target: SyntaxTarget,
+    /// Nesting depth of functions, used to detect top-level `await`
+    function_depth: usize,
+    reported: FxHashSet<&'static str>,
+    problems: Vec<SyntaxProblem>,
+}
+
+impl SyntaxLevelChecker {
+    fn require(&mut self, feature: &'static str, since: SyntaxTarget, span: Span) {
+        if self.target >= since || !self.reported.insert(feature) {
+            return;
+        }
+        self.problems.push(SyntaxProblem {
+            kind: DiagnosticKind::Other("UnsupportedSyntax".to_string()),
+            message: format!(
+                "{} requires {} but the target is {}",
+                feature, since, self.target
+            ),
+            help: format!(
+                "Raise the syntax target to {} or lower this syntax before bundling",
+                since
+            ),
+            span,
+        });
+    }
+}
+
+impl<'a> Visit<'a> for SyntaxLevelChecker {
+    fn visit_binary_expression(&mut self, it: &BinaryExpression<'a>) {
+        if it.operator == BinaryOperator::Exponential {
+            self.require("exponentiation operator", SyntaxTarget::Es2016, it.span);
+        }
+        walk::walk_binary_expression(self, it);
+    }
+
+    fn visit_assignment_expression(&mut self, it: &AssignmentExpression<'a>) {
+        if it.operator == AssignmentOperator::Exponential {
+            self.require("exponentiation operator", SyntaxTarget::Es2016, it.span);
+        } else if it.operator.is_logical() {
+            self.require("logical assignment", SyntaxTarget::Es2021, it.span);
+        }
+        walk::walk_assignment_expression(self, it);
+    }
+
+    fn visit_function(&mut self, it: &Function<'a>, flags: ScopeFlags) {
+        match (it.r#async, it.generator) {
+            (true, true) => self.require("async generator", SyntaxTarget::Es2018, it.span),
+            (true, false) => self.require("async function", SyntaxTarget::Es2017, it.span),
+            _ => {}
+        }
+        self.function_depth += 1;
+        walk::walk_function(self, it, flags);
+        self.function_depth -= 1;
+    }
+
+    fn visit_arrow_function_expression(&mut self, it: &ArrowFunctionExpression<'a>) {
+        if it.r#async {
+            self.require("async function", SyntaxTarget::Es2017, it.span);
+        }
+        self.function_depth += 1;
+        walk::walk_arrow_function_expression(self, it);
+        self.function_depth -= 1;
+    }
+
+    fn visit_await_expression(&mut self, it: &AwaitExpression<'a>) {
+        if self.function_depth == 0 {
+            self.require("top-level await", SyntaxTarget::Es2022, it.span);
+        }
+        walk::walk_await_expression(self, it);
+    }
+
+    fn visit_object_expression(&mut self, it: &ObjectExpression<'a>) {
+        if let Some(ObjectPropertyKind::SpreadProperty(spread)) = it
+            .properties
+            .iter()
+            .find(|property| matches!(property, ObjectPropertyKind::SpreadProperty(_)))
+        {
+            self.require("object spread", SyntaxTarget::Es2018, spread.span);
+        }
+        walk::walk_object_expression(self, it);
+    }
+
+    fn visit_object_pattern(&mut self, it: &ObjectPattern<'a>) {
+        if let Some(rest) = &it.rest {
+            self.require("object rest", SyntaxTarget::Es2018, rest.span);
+        }
+        walk::walk_object_pattern(self, it);
+    }
+
+    fn visit_for_of_statement(&mut self, it: &ForOfStatement<'a>) {
+        if it.r#await {
+            self.require("for await...of", SyntaxTarget::Es2018, it.span);
+            if self.function_depth == 0 {
+                self.require("top-level await", SyntaxTarget::Es2022, it.span);
+            }
+        }
+        walk::walk_for_of_statement(self, it);
+    }
+
+    fn visit_catch_clause(&mut self, it: &CatchClause<'a>) {
+        if it.param.is_none() {
+            self.require("optional catch binding", SyntaxTarget::Es2019, it.span);
+        }
+        walk::walk_catch_clause(self, it);
+    }
+
+    fn visit_chain_expression(&mut self, it: &ChainExpression<'a>) {
+        self.require("optional chaining", SyntaxTarget::Es2020, it.span);
+        walk::walk_chain_expression(self, it);
+    }
+
+    fn visit_logical_expression(&mut self, it: &LogicalExpression<'a>) {
+        if it.operator == LogicalOperator::Coalesce {
+            self.require("nullish coalescing", SyntaxTarget::Es2020, it.span);
+        }
+        walk::walk_logical_expression(self, it);
+    }
+
+    fn visit_big_int_literal(&mut self, it: &BigIntLiteral<'a>) {
+        self.require("BigInt literal", SyntaxTarget::Es2020, it.span);
+    }
+
+    fn visit_numeric_literal(&mut self, it: &NumericLiteral<'a>) {
+        if it.raw.is_some_and(|raw| raw.contains('_')) {
+            self.require("numeric separator", SyntaxTarget::Es2021, it.span);
+        }
+    }
+
+    fn visit_property_definition(&mut self, it: &PropertyDefinition<'a>) {
+        self.require("class field", SyntaxTarget::Es2022, it.span);
+        walk::walk_property_definition(self, it);
+    }
+
+    fn visit_private_identifier(&mut self, it: &PrivateIdentifier<'a>) {
+        self.require("private class member", SyntaxTarget::Es2022, it.span);
+    }
+
+    fn visit_private_in_expression(&mut self, it: &PrivateInExpression<'a>) {
+        self.require("private brand check", SyntaxTarget::Es2022, it.span);
+        walk::walk_private_in_expression(self, it);
+    }
+
+    fn visit_static_block(&mut self, it: &StaticBlock<'a>) {
+        self.require("class static block", SyntaxTarget::Es2022, it.span);
+        self.function_depth += 1;
+        walk::walk_static_block(self, it);
+        self.function_depth -= 1;
+    }
+}
+
+/// Zero-based line and column of a byte offset.
+pub(crate) fn line_col(code: &str, offset: u32) -> (u32, u32) {
+    let offset = (offset as usize).min(code.len());
+    let before = &code[..offset];
+    let line = before.matches('\n').count();
+    let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+    let column = before[line_start..].encode_utf16().count();
+    (line as u32, column as u32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn problems(code: &str, target: SyntaxTarget) -> Vec<String> {
+        check_syntax(code, SourceType::mjs(), target)
+            .into_iter()
+            .map(|p| p.message)
+            .collect()
+    }
+
+    #[test]
+    fn test_parse_error_reported() {
+        let found = check_syntax("const = 1;", SourceType::mjs(), SyntaxTarget::EsNext);
+        assert_eq!(found.len(), 1);
+        assert!(matches!(found[0].kind, DiagnosticKind::ParseError));
+    }
+
+    #[test]
+    fn test_import_in_script_is_parse_error() {
+        let found = check_syntax(
+            "import x from 'y';",
+            SourceType::cjs(),
+            SyntaxTarget::EsNext,
+        );
+        assert!(!found.is_empty());
+    }
+
+    #[test]
+    fn test_features_above_target_reported_once() {
+        let code = "const a = x?.y; const b = z?.w; const c = a ?? b;";
+        let found = problems(code, SyntaxTarget::Es2019);
+        assert_eq!(found.len(), 2);
+        assert!(found[0].contains("optional chaining requires es2020"));
+        assert!(found[1].contains("nullish coalescing requires es2020"));
+    }
+
+    #[test]
+    fn test_features_within_target_accepted() {
+        let code = "class A { #x = 1; static { } } const n = 1_000n; a ||= b;";
+        assert!(problems(code, SyntaxTarget::Es2022).is_empty());
+        assert_eq!(problems(code, SyntaxTarget::Es2020).len(), 4);
+    }
+
+    #[test]
+    fn test_top_level_await_detection() {
+        assert_eq!(
+            problems("await load();", SyntaxTarget::Es2021),
+            vec!["top-level await requires es2022 but the target is es2021"]
+        );
+        assert_eq!(
+            problems("async function f() { await load(); }", SyntaxTarget::Es2021),
+            Vec::<String>::new()
+        );
+    }
+
+    #[test]
+    fn test_line_col() {
+        assert_eq!(line_col("a\nbc\nd", 3), (1, 1));
+        assert_eq!(line_col("abc", 0), (0, 0));
+    }
+
+    #[test]
+    fn test_syntax_target_parse_and_order() {
+        assert_eq!(
+            "ES2020".parse::<SyntaxTarget>().unwrap(),
+            SyntaxTarget::Es2020
+        );
+        assert!("es5".parse::<SyntaxTarget>().is_err());
+        assert!(SyntaxTarget::Es2015 < SyntaxTarget::EsNext);
+    }
+}
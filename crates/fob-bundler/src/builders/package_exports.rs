@@ -0,0 +1,925 @@
+//! package.json entry point validation against build output
+//!
+//! Libraries publish their build output through the `exports`, `main`,
+//! `module` and `types` fields of package.json. Those fields are edited by
+//! hand and drift from what the build emits: a renamed chunk leaves a
+//! dangling path, an `import` condition points at CommonJS, or `types` is
+//! listed after `default` where TypeScript never reaches it. This module
+//! checks every target against the files a build produced, and can generate
+//! the fields from the entry chunks instead.
+
+use crate::{BuildOutput, BuildResult, Error, Output, Result};
+use indexmap::IndexMap;
+use oxc_allocator::Allocator;
+use oxc_parser::Parser;
+use oxc_span::SourceType;
+use path_clean::PathClean;
+use rustc_hash::FxHashMap;
+use serde::de::{self, Deserialize, Deserializer, MapAccess, SeqAccess, Visitor};
+use std::fmt;
+use std::path::Path;
+
+/// Module format of an emitted file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EmittedFormat {
+    /// JavaScript with `import`/`export` syntax
+    Esm,
+    /// JavaScript without module syntax (CommonJS, UMD, IIFE)
+    CommonJs,
+    /// TypeScript declaration file
+    Declaration,
+    /// Anything else (CSS, JSON, images, ...)
+    Other,
+}
+
+impl EmittedFormat {
+    /// Detect the format of a file from its path and contents.
+    ///
+    /// JavaScript files are parsed, so a `.js` file is classified by what it
+    /// contains rather than by the package `type`.
+    pub fn detect(path: &str, code: &str) -> Self {
+        if [".d.ts", ".d.mts", ".d.cts"]
+            .iter()
+            .any(|extension| path.ends_with(extension))
+        {
+            return Self::Declaration;
+        }
+        if ![".js", ".mjs", ".cjs"]
+            .iter()
+            .any(|extension| path.ends_with(extension))
+        {
+            return Self::Other;
+        }
+
+        let allocator = Allocator::default();
+        let ret = Parser::new(&allocator, code, SourceType::mjs()).parse();
+        if ret.module_record.has_module_syntax {
+            Self::Esm
+        } else {
+            Self::CommonJs
+        }
+    }
+}
+
+impl fmt::Display for EmittedFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::Esm => "ESM",
+            Self::CommonJs => "CommonJS",
+            Self::Declaration => "a type declaration",
+            Self::Other => "a non-JavaScript file",
+        };
+        f.write_str(name)
+    }
+}
+
+/// Files a build produced, keyed by path relative to the package root.
+#[derive(Debug, Clone, Default)]
+pub struct PackageOutputs {
+    files: FxHashMap<String, EmittedFormat>,
+}
+
+impl PackageOutputs {
+    /// Create an empty set of outputs.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Collect the chunks and assets of a build written to `out_dir`.
+    ///
+    /// `out_dir` is relative to the package root, e.g. `dist`. Multi-bundle
+    /// builds are placed in one subdirectory per component, as
+    /// [`BuildOutput::write_to`] does.
+    pub fn from_build_result(result: &BuildResult, out_dir: &str) -> Self {
+        let mut outputs = Self::new();
+        for (dir, bundle) in bundle_dirs(result, out_dir) {
+            for output in &bundle.assets {
+                let path = normalize(&format!("{dir}/{}", output.filename()));
+                let format = match output {
+                    Output::Chunk(chunk) => EmittedFormat::detect(&path, &chunk.code),
+                    // Non-UTF-8 assets are never JavaScript, so they are
+                    // classified by path alone without copying the source
+                    Output::Asset(asset) => EmittedFormat::detect(
+                        &path,
+                        std::str::from_utf8(asset.source.as_bytes()).unwrap_or_default(),
+                    ),
+                };
+                outputs.insert(&path, format);
+            }
+        }
+        outputs
+    }
+
+    /// Record a file at a path relative to the package root.
+    pub fn insert(&mut self, path: &str, format: EmittedFormat) {
+        self.files.insert(normalize(path), format);
+    }
+
+    /// Format of the file at a package-relative path, if it was emitted.
+    pub fn get(&self, path: &str) -> Option<EmittedFormat> {
+        self.files.get(&normalize(path)).copied()
+    }
+
+    /// Number of recorded files.
+    pub fn len(&self) -> usize {
+        self.files.len()
+    }
+
+    /// Whether no files were recorded.
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty()
+    }
+
+    /// Formats of the files matching a subpath pattern with one `*`.
+    fn matching(&self, pattern: &str) -> Vec<EmittedFormat> {
+        let pattern = normalize(pattern);
+        let Some((prefix, suffix)) = pattern.split_once('*') else {
+            return self.get(&pattern).into_iter().collect();
+        };
+        self.files
+            .iter()
+            .filter(|(path, _)| {
+                path.len() > prefix.len() + suffix.len()
+                    && path.starts_with(prefix)
+                    && path.ends_with(suffix)
+            })
+            .map(|(_, format)| *format)
+            .collect()
+    }
+}
+
+/// What is wrong with a package.json entry point.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PackageExportIssueKind {
+    /// The target path was not emitted by the build
+    MissingFile,
+    /// The target was emitted in a format the field or condition can't load
+    FormatMismatch {
+        expected: EmittedFormat,
+        actual: EmittedFormat,
+    },
+    /// Node infers a different format from the file extension and the
+    /// package `type` than the file contains
+    ExtensionMismatch {
+        inferred: EmittedFormat,
+        actual: EmittedFormat,
+    },
+    /// A condition is never reached because of its position
+    ConditionOrder,
+}
+
+/// Problem found in a package.json entry point field.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PackageExportIssue {
+    /// Field the target is listed under, e.g. `exports["./utils"].import`
+    pub field: String,
+    /// The target path, or the misplaced condition for `ConditionOrder`
+    pub target: String,
+    pub kind: PackageExportIssueKind,
+}
+
+impl fmt::Display for PackageExportIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.kind {
+            PackageExportIssueKind::MissingFile => {
+                write!(
+                    f,
+                    "{}: {} was not emitted by the build",
+                    self.field, self.target
+                )
+            }
+            PackageExportIssueKind::FormatMismatch { expected, actual } => write!(
+                f,
+                "{}: {} should be {} but is {}",
+                self.field, self.target, expected, actual
+            ),
+            PackageExportIssueKind::ExtensionMismatch { inferred, actual } => write!(
+                f,
+                "{}: Node loads {} as {} but it is {}; rename it or change the package \"type\"",
+                self.field, self.target, inferred, actual
+            ),
+            PackageExportIssueKind::ConditionOrder => write!(
+                f,
+                "{}: the \"{}\" condition must come {}",
+                self.field,
+                self.target,
+                if self.target == "types" {
+                    "first"
+                } else {
+                    "last"
+                }
+            ),
+        }
+    }
+}
+
+/// Validate the entry point fields of a package.json against build outputs.
+///
+/// # Errors
+///
+/// Returns [`Error::InvalidConfig`] if `package_json` is not valid JSON.
+pub fn validate_package_exports(
+    package_json: &str,
+    outputs: &PackageOutputs,
+) -> Result<Vec<PackageExportIssue>> {
+    let manifest: EntryPoints = serde_json::from_str(package_json)
+        .map_err(|e| Error::InvalidConfig(format!("Invalid package.json: {e}")))?;
+    let mut check = Check {
+        outputs,
+        is_module: manifest.package_type.as_deref() == Some("module"),
+        issues: Vec::new(),
+    };
+
+    if let Some(main) = &manifest.main {
+        check.target("main", main, None);
+    }
+    if let Some(module) = &manifest.module {
+        check.target("module", module, Some(EmittedFormat::Esm));
+    }
+    for (field, types) in [("types", &manifest.types), ("typings", &manifest.typings)] {
+        if let Some(types) = types {
+            check.target(field, types, Some(EmittedFormat::Declaration));
+        }
+    }
+    if let Some(exports) = &manifest.exports {
+        match exports {
+            ExportsTarget::Conditions(entries)
+                if entries.first().is_some_and(|(key, _)| key.starts_with('.')) =>
+            {
+                for (subpath, target) in entries {
+                    check.exports(&format!("exports[\"{subpath}\"]"), target, None);
+                }
+            }
+            target => check.exports("exports", target, None),
+        }
+    }
+
+    Ok(check.issues)
+}
+
+impl BuildResult {
+    /// Validate a package.json's entry point fields against this build.
+    ///
+    /// `out_dir` is where the output is written, relative to the package root.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidConfig`] if `package_json` is not valid JSON.
+    pub fn validate_package_exports(
+        &self,
+        package_json: &str,
+        out_dir: &str,
+    ) -> Result<Vec<PackageExportIssue>> {
+        validate_package_exports(
+            package_json,
+            &PackageOutputs::from_build_result(self, out_dir),
+        )
+    }
+}
+
+struct Check<'a> {
+    outputs: &'a PackageOutputs,
+    is_module: bool,
+    issues: Vec<PackageExportIssue>,
+}
+
+impl Check<'_> {
+    fn exports(&mut self, field: &str, target: &ExportsTarget, expected: Option<EmittedFormat>) {
+        match target {
+            ExportsTarget::Null => {}
+            ExportsTarget::Path(path) => self.target(field, path, expected),
+            ExportsTarget::Fallbacks(targets) => {
+                for (index, target) in targets.iter().enumerate() {
+                    self.exports(&format!("{field}[{index}]"), target, expected);
+                }
+            }
+            ExportsTarget::Conditions(conditions) => {
+                let last = conditions.len().saturating_sub(1);
+                for (index, (condition, target)) in conditions.iter().enumerate() {
+                    let misplaced = match condition.as_str() {
+                        "types" => index != 0,
+                        "default" => index != last,
+                        _ => false,
+                    };
+                    if misplaced {
+                        self.issues.push(PackageExportIssue {
+                            field: field.to_string(),
+                            target: condition.clone(),
+                            kind: PackageExportIssueKind::ConditionOrder,
+                        });
+                    }
+
+                    let expected = match condition.as_str() {
+                        "import" | "module" => Some(EmittedFormat::Esm),
+                        "require" => Some(EmittedFormat::CommonJs),
+                        "types" => Some(EmittedFormat::Declaration),
+                        _ => expected,
+                    };
+                    self.exports(&format!("{field}.{condition}"), target, expected);
+                }
+            }
+        }
+    }
+
+    fn target(&mut self, field: &str, path: &str, expected: Option<EmittedFormat>) {
+        let formats = self.outputs.matching(path);
+        if formats.is_empty() {
+            self.issues.push(PackageExportIssue {
+                field: field.to_string(),
+                target: path.to_string(),
+                kind: PackageExportIssueKind::MissingFile,
+            });
+            return;
+        }
+
+        for actual in formats {
+            if let Some(expected) = expected.filter(|expected| *expected != actual) {
+                self.issues.push(PackageExportIssue {
+                    field: field.to_string(),
+                    target: path.to_string(),
+                    kind: PackageExportIssueKind::FormatMismatch { expected, actual },
+                });
+                return;
+            }
+            let is_javascript = matches!(actual, EmittedFormat::Esm | EmittedFormat::CommonJs);
+            let inferred = self
+                .inferred_format(path)
+                .filter(|inferred| is_javascript && *inferred != actual);
+            if let Some(inferred) = inferred {
+                self.issues.push(PackageExportIssue {
+                    field: field.to_string(),
+                    target: path.to_string(),
+                    kind: PackageExportIssueKind::ExtensionMismatch { inferred, actual },
+                });
+                return;
+            }
+        }
+    }
+
+    /// The format Node assumes for a JavaScript file.
+    fn inferred_format(&self, path: &str) -> Option<EmittedFormat> {
+        if path.ends_with(".mjs") {
+            Some(EmittedFormat::Esm)
+        } else if path.ends_with(".cjs") {
+            Some(EmittedFormat::CommonJs)
+        } else if path.ends_with(".js") {
+            Some(if self.is_module {
+                EmittedFormat::Esm
+            } else {
+                EmittedFormat::CommonJs
+            })
+        } else {
+            None
+        }
+    }
+}
+
+/// One `exports` subpath generated from the entry chunks of a build.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SubpathExport {
+    /// `.` for the main entry, `./<name>` for the others
+    pub subpath: String,
+    /// Declaration file for the `types` condition
+    pub types: Option<String>,
+    /// ESM file for the `import` condition
+    pub import: Option<String>,
+    /// CommonJS file for the `require` condition
+    pub require: Option<String>,
+}
+
+/// package.json entry point fields generated from a build.
+///
+/// Entries with the same name in several bundles, e.g. the `esm` and `cjs`
+/// output targets of one build, become the conditions of one subpath. The
+/// main entry is the only entry, or the one named `index`; it also fills in
+/// `main`, `module` and `types` for tools that don't read `exports`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PackageEntryPoints {
+    pub subpaths: Vec<SubpathExport>,
+}
+
+impl PackageEntryPoints {
+    /// Collect the entry chunks and declarations of a build written to `out_dir`.
+    ///
+    /// `out_dir` is relative to the package root, like for
+    /// [`PackageOutputs::from_build_result`].
+    pub fn from_build_result(result: &BuildResult, out_dir: &str) -> Self {
+        let mut entries = Vec::new();
+        let mut declarations = Vec::new();
+        for (dir, bundle) in bundle_dirs(result, out_dir) {
+            for output in &bundle.assets {
+                let path = normalize(&format!("{dir}/{}", output.filename()));
+                match output {
+                    Output::Chunk(chunk) if chunk.is_entry => {
+                        let format = EmittedFormat::detect(&path, &chunk.code);
+                        entries.push((chunk.name.to_string(), path, format));
+                    }
+                    Output::Asset(_)
+                        if EmittedFormat::detect(&path, "") == EmittedFormat::Declaration =>
+                    {
+                        declarations.push(path);
+                    }
+                    _ => {}
+                }
+            }
+        }
+        Self::from_files(entries, &declarations)
+    }
+
+    /// Group entry files by name and pair them with declarations.
+    fn from_files(entries: Vec<(String, String, EmittedFormat)>, declarations: &[String]) -> Self {
+        let mut names: Vec<&str> = entries.iter().map(|(name, _, _)| name.as_str()).collect();
+        names.sort_unstable();
+        names.dedup();
+        let main = match names.as_slice() {
+            [only] => Some(*only),
+            _ => names.iter().copied().find(|name| *name == "index"),
+        };
+
+        let mut subpaths: Vec<SubpathExport> = Vec::new();
+        for name in &names {
+            let subpath = if Some(*name) == main {
+                ".".to_string()
+            } else {
+                format!("./{name}")
+            };
+            let files: Vec<&(String, String, EmittedFormat)> = entries
+                .iter()
+                .filter(|(entry, _, _)| entry == name)
+                .collect();
+            let find = |format: EmittedFormat| {
+                files
+                    .iter()
+                    .find(|(_, _, emitted)| *emitted == format)
+                    .map(|(_, path, _)| path.clone())
+            };
+            let import = find(EmittedFormat::Esm);
+            let require = find(EmittedFormat::CommonJs);
+            let types = files
+                .iter()
+                .find_map(|(_, path, _)| declaration_for(path, declarations));
+
+            subpaths.push(SubpathExport {
+                subpath,
+                types: types.map(|path| relative(&path)),
+                import: import.map(|path| relative(&path)),
+                require: require.map(|path| relative(&path)),
+            });
+        }
+        subpaths
+            .sort_by(|a, b| (a.subpath != ".", &a.subpath).cmp(&(b.subpath != ".", &b.subpath)));
+
+        Self { subpaths }
+    }
+
+    /// The `exports` map, with `types` first as TypeScript requires.
+    pub fn exports(&self) -> IndexMap<String, IndexMap<String, String>> {
+        let mut exports = IndexMap::new();
+        for subpath in &self.subpaths {
+            let mut conditions = IndexMap::new();
+            for (condition, path) in [
+                ("types", &subpath.types),
+                ("import", &subpath.import),
+                ("require", &subpath.require),
+            ] {
+                if let Some(path) = path {
+                    conditions.insert(condition.to_string(), path.clone());
+                }
+            }
+            exports.insert(subpath.subpath.clone(), conditions);
+        }
+        exports
+    }
+
+    /// Write the entry point fields into a package.json, keeping the rest.
+    ///
+    /// `exports` is replaced; `main`, `module` and `types` are set from the
+    /// main entry when it has a matching file.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidConfig`] if `package_json` is not a JSON object.
+    pub fn apply(&self, package_json: &str) -> Result<String> {
+        let mut manifest: OrderedJson = serde_json::from_str(package_json)
+            .map_err(|e| Error::InvalidConfig(format!("Invalid package.json: {e}")))?;
+        let OrderedJson::Object(fields) = &mut manifest else {
+            return Err(Error::InvalidConfig(
+                "Invalid package.json: expected an object".into(),
+            ));
+        };
+
+        if let Some(main) = self.subpaths.iter().find(|subpath| subpath.subpath == ".") {
+            let entry_fields = [
+                ("main", main.require.as_ref().or(main.import.as_ref())),
+                ("module", main.import.as_ref()),
+                ("types", main.types.as_ref()),
+            ];
+            for (field, path) in entry_fields {
+                if let Some(path) = path {
+                    fields.insert(field.to_string(), OrderedJson::Scalar(path.clone().into()));
+                }
+            }
+        }
+        let exports = self
+            .exports()
+            .into_iter()
+            .map(|(subpath, conditions)| {
+                let conditions = conditions
+                    .into_iter()
+                    .map(|(condition, path)| (condition, OrderedJson::Scalar(path.into())))
+                    .collect();
+                (subpath, OrderedJson::Object(conditions))
+            })
+            .collect();
+        fields.insert("exports".to_string(), OrderedJson::Object(exports));
+
+        let mut json = serde_json::to_string_pretty(&manifest)
+            .map_err(|e| Error::InvalidConfig(format!("Invalid package.json: {e}")))?;
+        json.push('\n');
+        Ok(json)
+    }
+}
+
+impl BuildResult {
+    /// package.json entry point fields for this build written to `out_dir`.
+    pub fn package_entry_points(&self, out_dir: &str) -> PackageEntryPoints {
+        PackageEntryPoints::from_build_result(self, out_dir)
+    }
+
+    /// Write or update the `exports`, `main`, `module` and `types` fields of
+    /// the package.json at `package_json`.
+    ///
+    /// `out_dir` is where the output is written, relative to the package
+    /// root. A missing package.json is created.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file can't be read or written, or isn't a
+    /// JSON object.
+    pub fn write_package_exports(
+        &self,
+        package_json: impl AsRef<std::path::Path>,
+        out_dir: &str,
+    ) -> Result<()> {
+        let path = package_json.as_ref();
+        let current = match std::fs::read_to_string(path) {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => "{}".to_string(),
+            Err(e) => {
+                return Err(Error::IoError {
+                    message: format!("Failed to read {}", path.display()),
+                    source: e,
+                });
+            }
+        };
+        let updated = self.package_entry_points(out_dir).apply(&current)?;
+        std::fs::write(path, updated).map_err(|e| Error::IoError {
+            message: format!("Failed to write {}", path.display()),
+            source: e,
+        })
+    }
+}
+
+/// JSON value that keeps the key order of objects, so rewriting a
+/// package.json doesn't reorder the fields.
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(untagged)]
+enum OrderedJson {
+    Object(IndexMap<String, OrderedJson>),
+    Array(Vec<OrderedJson>),
+    Scalar(serde_json::Value),
+}
+
+/// Bundles of a build with the package-relative directory they're written to.
+///
+/// Sorted by directory so results don't depend on hash map order.
+fn bundle_dirs<'a>(
+    result: &'a BuildResult,
+    out_dir: &str,
+) -> Vec<(String, &'a crate::BundleOutput)> {
+    let mut bundles: Vec<(String, &crate::BundleOutput)> = match &result.output {
+        BuildOutput::Single(bundle) => vec![(normalize(out_dir), bundle)],
+        BuildOutput::Multiple(bundles) => bundles
+            .iter()
+            .map(|(name, bundle)| (normalize(&format!("{out_dir}/{name}")), bundle))
+            .collect(),
+    };
+    bundles.sort_by(|a, b| a.0.cmp(&b.0));
+    bundles
+}
+
+/// Declaration emitted for an entry file: `<stem>.d.ts`, preferably next to it.
+fn declaration_for(entry: &str, declarations: &[String]) -> Option<String> {
+    let (dir, file) = split_dir(entry);
+    let stem = file.rsplit_once('.').map_or(file, |(stem, _)| stem);
+    let name = format!("{stem}.d.ts");
+
+    let mut candidates = declarations.iter().filter(|path| split_dir(path).1 == name);
+    let next_to_entry = candidates.clone().find(|path| split_dir(path).0 == dir);
+    next_to_entry.or_else(|| candidates.next()).cloned()
+}
+
+/// Split a normalized path into its directory and file name.
+fn split_dir(path: &str) -> (&str, &str) {
+    path.rsplit_once('/').unwrap_or(("", path))
+}
+
+/// Package-relative path as written in package.json.
+fn relative(path: &str) -> String {
+    format!("./{path}")
+}
+
+/// Clean a package-relative path into the `/`-separated form used as key.
+fn normalize(path: &str) -> String {
+    let cleaned = Path::new(&path.replace('\\', "/")).clean();
+    cleaned
+        .to_string_lossy()
+        .replace('\\', "/")
+        .trim_start_matches('/')
+        .to_string()
+}
+
+/// The package.json fields that point at build output.
+#[derive(serde::Deserialize)]
+struct EntryPoints {
+    #[serde(rename = "type")]
+    package_type: Option<String>,
+    main: Option<String>,
+    module: Option<String>,
+    types: Option<String>,
+    typings: Option<String>,
+    exports: Option<ExportsTarget>,
+}
+
+/// A value in the `exports` field.
+///
+/// Conditions keep their order from the file, which decides resolution.
+enum ExportsTarget {
+    Null,
+    Path(String),
+    Fallbacks(Vec<ExportsTarget>),
+    Conditions(Vec<(String, ExportsTarget)>),
+}
+
+impl<'de> Deserialize<'de> for ExportsTarget {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        struct TargetVisitor;
+
+        impl<'de> Visitor<'de> for TargetVisitor {
+            type Value = ExportsTarget;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("a path, an array, a conditions object or null")
+            }
+
+            fn visit_unit<E: de::Error>(self) -> std::result::Result<Self::Value, E> {
+                Ok(ExportsTarget::Null)
+            }
+
+            fn visit_str<E: de::Error>(self, value: &str) -> std::result::Result<Self::Value, E> {
+                Ok(ExportsTarget::Path(value.to_string()))
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(
+                self,
+                mut seq: A,
+            ) -> std::result::Result<Self::Value, A::Error> {
+                let mut targets = Vec::new();
+                while let Some(target) = seq.next_element()? {
+                    targets.push(target);
+                }
+                Ok(ExportsTarget::Fallbacks(targets))
+            }
+
+            fn visit_map<A: MapAccess<'de>>(
+                self,
+                mut map: A,
+            ) -> std::result::Result<Self::Value, A::Error> {
+                let mut conditions = Vec::new();
+                while let Some(entry) = map.next_entry()? {
+                    conditions.push(entry);
+                }
+                Ok(ExportsTarget::Conditions(conditions))
+            }
+        }
+
+        deserializer.deserialize_any(TargetVisitor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn outputs(files: &[(&str, EmittedFormat)]) -> PackageOutputs {
+        let mut outputs = PackageOutputs::new();
+        for (path, format) in files {
+            outputs.insert(path, *format);
+        }
+        outputs
+    }
+
+    #[test]
+    fn test_detect_format() {
+        assert_eq!(
+            EmittedFormat::detect("index.mjs", "export const a = 1;"),
+            EmittedFormat::Esm
+        );
+        assert_eq!(
+            EmittedFormat::detect("index.js", "module.exports = { a: 1 };"),
+            EmittedFormat::CommonJs
+        );
+        assert_eq!(
+            EmittedFormat::detect("index.d.ts", "export {};"),
+            EmittedFormat::Declaration
+        );
+        assert_eq!(EmittedFormat::detect("style.css", ""), EmittedFormat::Other);
+    }
+
+    #[test]
+    fn test_valid_package_has_no_issues() {
+        let package_json = r#"{
+            "type": "module",
+            "main": "./dist/index.cjs",
+            "module": "./dist/index.js",
+            "types": "./dist/index.d.ts",
+            "exports": {
+                ".": {
+                    "types": "./dist/index.d.ts",
+                    "import": "./dist/index.js",
+                    "require": "./dist/index.cjs"
+                },
+                "./utils/*": "./dist/utils/*.js",
+                "./internal": null
+            }
+        }"#;
+        let outputs = outputs(&[
+            ("dist/index.js", EmittedFormat::Esm),
+            ("dist/index.cjs", EmittedFormat::CommonJs),
+            ("dist/index.d.ts", EmittedFormat::Declaration),
+            ("dist/utils/math.js", EmittedFormat::Esm),
+        ]);
+
+        let issues = validate_package_exports(package_json, &outputs).unwrap();
+        assert!(issues.is_empty(), "{issues:?}");
+    }
+
+    #[test]
+    fn test_missing_files_and_format_mismatches() {
+        let package_json = r#"{
+            "main": "./dist/index.js",
+            "exports": {
+                "import": "./dist/index.cjs",
+                "require": "./dist/index.js",
+                "default": "./dist/missing.js"
+            }
+        }"#;
+        let outputs = outputs(&[
+            ("dist/index.js", EmittedFormat::Esm),
+            ("dist/index.cjs", EmittedFormat::CommonJs),
+        ]);
+
+        let issues = validate_package_exports(package_json, &outputs).unwrap();
+        let fields: Vec<_> = issues.iter().map(|issue| issue.field.as_str()).collect();
+        assert_eq!(
+            fields,
+            [
+                "main",
+                "exports.import",
+                "exports.require",
+                "exports.default"
+            ]
+        );
+        assert_eq!(
+            issues[0].kind,
+            PackageExportIssueKind::ExtensionMismatch {
+                inferred: EmittedFormat::CommonJs,
+                actual: EmittedFormat::Esm,
+            }
+        );
+        assert_eq!(
+            issues[1].kind,
+            PackageExportIssueKind::FormatMismatch {
+                expected: EmittedFormat::Esm,
+                actual: EmittedFormat::CommonJs,
+            }
+        );
+        assert_eq!(issues[3].kind, PackageExportIssueKind::MissingFile);
+    }
+
+    fn entry(name: &str, path: &str, format: EmittedFormat) -> (String, String, EmittedFormat) {
+        (name.to_string(), path.to_string(), format)
+    }
+
+    #[test]
+    fn test_entry_points_group_targets_by_entry() {
+        let entries = vec![
+            entry("index", "dist/esm/index.js", EmittedFormat::Esm),
+            entry("index", "dist/cjs/index.js", EmittedFormat::CommonJs),
+            entry("utils", "dist/esm/utils.js", EmittedFormat::Esm),
+        ];
+        let declarations = ["dist/esm/index.d.ts".to_string()];
+
+        let entry_points = PackageEntryPoints::from_files(entries, &declarations);
+
+        assert_eq!(
+            entry_points.subpaths,
+            [
+                SubpathExport {
+                    subpath: ".".to_string(),
+                    types: Some("./dist/esm/index.d.ts".to_string()),
+                    import: Some("./dist/esm/index.js".to_string()),
+                    require: Some("./dist/cjs/index.js".to_string()),
+                },
+                SubpathExport {
+                    subpath: "./utils".to_string(),
+                    types: None,
+                    import: Some("./dist/esm/utils.js".to_string()),
+                    require: None,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_single_entry_is_main_whatever_its_name() {
+        let entries = vec![entry("lib", "dist/lib.js", EmittedFormat::Esm)];
+        let entry_points = PackageEntryPoints::from_files(entries, &[]);
+        assert_eq!(entry_points.subpaths[0].subpath, ".");
+    }
+
+    #[test]
+    fn test_apply_updates_entry_fields_and_keeps_the_rest() {
+        let entries = vec![
+            entry("index", "dist/index.mjs", EmittedFormat::Esm),
+            entry("index", "dist/index.cjs", EmittedFormat::CommonJs),
+        ];
+        let declarations = ["dist/index.d.ts".to_string()];
+        let entry_points = PackageEntryPoints::from_files(entries, &declarations);
+
+        let updated = entry_points
+            .apply(r#"{"name": "lib", "main": "old.js", "exports": "./old.js", "license": "MIT"}"#)
+            .unwrap();
+        let manifest: serde_json::Value = serde_json::from_str(&updated).unwrap();
+
+        assert_eq!(manifest["name"], "lib");
+        assert_eq!(manifest["license"], "MIT");
+        assert_eq!(manifest["main"], "./dist/index.cjs");
+        assert_eq!(manifest["module"], "./dist/index.mjs");
+        assert_eq!(manifest["types"], "./dist/index.d.ts");
+        assert_eq!(manifest["exports"]["."]["require"], "./dist/index.cjs");
+
+        // Field order is kept and conditions start with `types`
+        assert!(updated.find("\"name\"") < updated.find("\"main\""));
+        assert!(
+            updated
+                .find("\"types\": \"./dist/index.d.ts\",\n      \"import\"")
+                .is_some()
+        );
+
+        // The generated fields pass validation
+        let outputs = outputs(&[
+            ("dist/index.mjs", EmittedFormat::Esm),
+            ("dist/index.cjs", EmittedFormat::CommonJs),
+            ("dist/index.d.ts", EmittedFormat::Declaration),
+        ]);
+        assert!(
+            validate_package_exports(&updated, &outputs)
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_apply_rejects_non_objects() {
+        assert!(PackageEntryPoints::default().apply("[]").is_err());
+    }
+
+    #[test]
+    fn test_condition_order() {
+        let package_json = r#"{
+            "exports": {
+                ".": {
+                    "default": "./index.mjs",
+                    "types": "./index.d.mts"
+                }
+            }
+        }"#;
+        let outputs = outputs(&[
+            ("index.mjs", EmittedFormat::Esm),
+            ("index.d.mts", EmittedFormat::Declaration),
+        ]);
+
+        let issues = validate_package_exports(package_json, &outputs).unwrap();
+        let misplaced: Vec<_> = issues
+            .iter()
+            .filter(|issue| issue.kind == PackageExportIssueKind::ConditionOrder)
+            .map(|issue| issue.target.as_str())
+            .collect();
+        assert_eq!(misplaced, ["default", "types"]);
+        assert_eq!(
+            issues[0].to_string(),
+            "exports[\".\"]: the \"default\" condition must come last"
+        );
+    }
+}
@@ -0,0 +1,583 @@
+//! Build-time replacement of globals and automatic import injection
+//!
+//! Users used to search and replace `process.env.NODE_ENV` in the output,
+//! which also rewrites strings, comments, object keys and local variables
+//! that happen to share the name. This plugin works on the AST instead: it
+//! only touches references to globals, that is identifiers (or static member
+//! chains starting at one) that no binding in the module shadows.
+//!
+//! - `replace` swaps such a reference for a JavaScript expression, e.g.
+//!   `process.env.NODE_ENV` → `"production"` or `__DEV__` → `false`.
+//! - `inject` leaves the reference in place and imports the global from a
+//!   module instead, e.g. `Buffer` from `buffer`, only in the modules that
+//!   use it.
+
+use crate::{Error, Result};
+use oxc_allocator::Allocator;
+use oxc_ast::ast::{Expression, IdentifierReference, ObjectProperty, Program};
+use oxc_ast_visit::{Visit, walk};
+use oxc_parser::Parser;
+use oxc_semantic::{Scoping, SemanticBuilder};
+use oxc_span::{GetSpan, SourceType, Span};
+use path_clean::PathClean;
+use rolldown_common::ModuleType;
+use rolldown_plugin::{
+    HookTransformArgs, HookTransformOutput, HookTransformReturn, HookUsage, Plugin,
+    SharedTransformPluginContext,
+};
+use rustc_hash::FxHashMap;
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+
+/// Import that provides a global, added by [`BuildOptions::inject`](crate::BuildOptions::inject).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum InjectImport {
+    /// `import Global from "source"`
+    Default { source: String },
+    /// `import { name as Global } from "source"`
+    Named { source: String, name: String },
+    /// `import * as Global from "source"`
+    Namespace { source: String },
+}
+
+impl InjectImport {
+    /// The default export of `source`.
+    pub fn default_export(source: impl Into<String>) -> Self {
+        Self::Default {
+            source: source.into(),
+        }
+    }
+
+    /// The export `name` of `source`.
+    pub fn named(source: impl Into<String>, name: impl Into<String>) -> Self {
+        Self::Named {
+            source: source.into(),
+            name: name.into(),
+        }
+    }
+
+    /// The namespace object of `source`.
+    pub fn namespace(source: impl Into<String>) -> Self {
+        Self::Namespace {
+            source: source.into(),
+        }
+    }
+
+    /// Module the global is imported from.
+    pub fn source(&self) -> &str {
+        match self {
+            Self::Default { source } | Self::Named { source, .. } | Self::Namespace { source } => {
+                source
+            }
+        }
+    }
+
+    fn statement(&self, local: &str) -> String {
+        let source = serde_json::to_string(self.source()).unwrap_or_default();
+        match self {
+            Self::Default { .. } => format!("import {} from {};", local, source),
+            Self::Named { name, .. } if name == local => {
+                format!("import {{ {} }} from {};", local, source)
+            }
+            Self::Named { name, .. } => {
+                format!("import {{ {} as {} }} from {};", name, local, source)
+            }
+            Self::Namespace { .. } => format!("import * as {} from {};", local, source),
+        }
+    }
+}
+
+/// Check replacement targets, replacement expressions and injected imports.
+pub(crate) fn validate(
+    replace: &FxHashMap<String, String>,
+    inject: &FxHashMap<String, InjectImport>,
+) -> Result<()> {
+    for (target, replacement) in replace {
+        validate_target("replace", target)?;
+        if inject.contains_key(target) {
+            return Err(Error::InvalidConfig(format!(
+                "'{}' is both replaced and injected",
+                target
+            )));
+        }
+        let allocator = Allocator::default();
+        if Parser::new(&allocator, replacement, SourceType::mjs())
+            .parse_expression()
+            .is_err()
+        {
+            return Err(Error::InvalidConfig(format!(
+                "Invalid replacement for '{}': `{}` is not a JavaScript expression",
+                target, replacement
+            )));
+        }
+    }
+    for (target, import) in inject {
+        validate_target("inject", target)?;
+        if import.source().is_empty() {
+            return Err(Error::InvalidConfig(format!(
+                "Invalid inject for '{}': import source must not be empty",
+                target
+            )));
+        }
+        if let InjectImport::Named { name, .. } = import {
+            if !is_identifier_name(name) {
+                return Err(Error::InvalidConfig(format!(
+                    "Invalid inject for '{}': '{}' is not an export name",
+                    target, name
+                )));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Targets are an identifier or a dotted member path such as `process.env.NODE_ENV`.
+fn validate_target(option: &str, target: &str) -> Result<()> {
+    if target.split('.').all(is_identifier_name) {
+        Ok(())
+    } else {
+        Err(Error::InvalidConfig(format!(
+            "Invalid {} target '{}': expected an identifier or a dotted path like `process.env.NODE_ENV`",
+            option, target
+        )))
+    }
+}
+
+fn is_identifier_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_alphabetic() || c == '_' || c == '$')
+        && chars.all(|c| c.is_alphanumeric() || c == '_' || c == '$')
+}
+
+#[derive(Debug)]
+enum Action {
+    /// Expression text, parenthesized where precedence could change its meaning
+    Replace(String),
+    Inject(InjectImport),
+}
+
+/// Replaces and injects globals in every script module.
+#[derive(Debug)]
+pub(crate) struct ReplacePlugin {
+    targets: FxHashMap<String, Action>,
+    /// First segment of every target; modules mentioning none are skipped
+    roots: Vec<String>,
+    /// Root that relative inject sources are resolved against
+    cwd: PathBuf,
+}
+
+impl ReplacePlugin {
+    /// Create the plugin from options checked by [`validate`].
+    pub(crate) fn new(
+        replace: &FxHashMap<String, String>,
+        inject: &FxHashMap<String, InjectImport>,
+        cwd: &Path,
+    ) -> Self {
+        let targets: FxHashMap<String, Action> = replace
+            .iter()
+            .map(|(target, replacement)| {
+                let action = Action::Replace(replacement_text(replacement));
+                (target.clone(), action)
+            })
+            .chain(
+                inject
+                    .iter()
+                    .map(|(target, import)| (target.clone(), Action::Inject(import.clone()))),
+            )
+            .collect();
+        let mut roots: Vec<String> = targets
+            .keys()
+            .map(|target| target.split('.').next().unwrap_or(target).to_string())
+            .collect();
+        roots.sort();
+        roots.dedup();
+
+        Self {
+            targets,
+            roots,
+            cwd: cwd.to_path_buf(),
+        }
+    }
+
+    /// Rewrite a module, or `None` if it has nothing to replace or inject.
+    ///
+    /// Modules that fail to parse are left for Rolldown to report.
+    pub(crate) fn rewrite(&self, code: &str, id: &str, source_type: SourceType) -> Option<String> {
+        if !self.roots.iter().any(|root| code.contains(root.as_str())) {
+            return None;
+        }
+
+        let allocator = Allocator::default();
+        let parsed = Parser::new(&allocator, code, source_type.with_module(true)).parse();
+        if !parsed.errors.is_empty() {
+            return None;
+        }
+        let semantic = SemanticBuilder::new().build(&parsed.program).semantic;
+
+        let mut finder = GlobalFinder {
+            targets: &self.targets,
+            scoping: semantic.scoping(),
+            edits: Vec::new(),
+            injected: BTreeMap::new(),
+        };
+        finder.visit_program(&parsed.program);
+        let GlobalFinder {
+            mut edits,
+            injected,
+            scoping,
+            ..
+        } = finder;
+
+        let mut imports = String::new();
+        let mut taken: Vec<String> = Vec::new();
+        for (target, spans) in injected {
+            let Action::Inject(import) = &self.targets[target] else {
+                continue;
+            };
+            // A module never imports itself
+            if self.is_module(import.source(), id) {
+                continue;
+            }
+            let local = if target.contains('.') {
+                let local = unique_name(&format!("_{}", target.replace('.', "_")), scoping, &taken);
+                edits.extend(spans.iter().map(|span| (*span, local.clone())));
+                taken.push(local.clone());
+                local
+            } else {
+                target.to_string()
+            };
+            let _ = writeln!(imports, "{}", import.statement(&local));
+        }
+        if !imports.is_empty() {
+            edits.push(import_position(&parsed.program, imports));
+        }
+
+        (!edits.is_empty()).then(|| apply_edits(code, edits))
+    }
+
+    /// Whether a relative or absolute inject source is the module `id`.
+    fn is_module(&self, source: &str, id: &str) -> bool {
+        if !(source.starts_with('.') || Path::new(source).is_absolute()) {
+            return false;
+        }
+        let resolved = self.cwd.join(source).clean();
+        let id = Path::new(id);
+        resolved == id || resolved == id.with_extension("")
+    }
+}
+
+impl Plugin for ReplacePlugin {
+    fn name(&self) -> Cow<'static, str> {
+        "fob:replace".into()
+    }
+
+    fn register_hook_usage(&self) -> HookUsage {
+        HookUsage::Transform
+    }
+
+    fn transform(
+        &self,
+        _ctx: SharedTransformPluginContext,
+        args: &HookTransformArgs<'_>,
+    ) -> impl std::future::Future<Output = HookTransformReturn> + Send {
+        let source_type = match args.module_type {
+            ModuleType::Js => Some(SourceType::mjs()),
+            ModuleType::Jsx => Some(SourceType::jsx()),
+            ModuleType::Ts => Some(SourceType::ts()),
+            ModuleType::Tsx => Some(SourceType::tsx()),
+            _ => None,
+        };
+        let code =
+            source_type.and_then(|source_type| self.rewrite(args.code, args.id, source_type));
+
+        async move {
+            Ok(code.map(|code| HookTransformOutput {
+                code: Some(code),
+                map: None,
+                side_effects: None,
+                module_type: None,
+            }))
+        }
+    }
+}
+
+/// Parenthesize replacements whose meaning could change next to an operator.
+fn replacement_text(replacement: &str) -> String {
+    let allocator = Allocator::default();
+    let atomic = Parser::new(&allocator, replacement, SourceType::mjs())
+        .parse_expression()
+        .is_ok_and(|expression| {
+            matches!(
+                expression,
+                Expression::BooleanLiteral(_)
+                    | Expression::NullLiteral(_)
+                    | Expression::NumericLiteral(_)
+                    | Expression::BigIntLiteral(_)
+                    | Expression::StringLiteral(_)
+                    | Expression::TemplateLiteral(_)
+                    | Expression::Identifier(_)
+                    | Expression::StaticMemberExpression(_)
+                    | Expression::ComputedMemberExpression(_)
+                    | Expression::CallExpression(_)
+                    | Expression::ArrayExpression(_)
+                    | Expression::ParenthesizedExpression(_)
+            )
+        });
+    if atomic {
+        replacement.trim().to_string()
+    } else {
+        format!("({})", replacement.trim())
+    }
+}
+
+/// Visitor collecting references to configured globals.
+struct GlobalFinder<'a> {
+    targets: &'a FxHashMap<String, Action>,
+    scoping: &'a Scoping,
+    /// Replacements by span
+    edits: Vec<(Span, String)>,
+    /// Injected targets with the spans of references that need renaming
+    injected: BTreeMap<&'a str, Vec<Span>>,
+}
+
+impl<'a> GlobalFinder<'a> {
+    /// Handle `expression` if it is a configured global; returns whether it was.
+    fn record(&mut self, expression: &Expression<'_>, shorthand: Option<Span>) -> bool {
+        let mut path = Vec::new();
+        let Some(root) = reference_path(expression, &mut path) else {
+            return false;
+        };
+        let targets = self.targets;
+        let Some((target, action)) = targets.get_key_value(path.join(".").as_str()) else {
+            return false;
+        };
+        if self
+            .scoping
+            .get_reference(root.reference_id())
+            .symbol_id()
+            .is_some()
+        {
+            return false;
+        }
+
+        match action {
+            Action::Replace(replacement) => {
+                let edit = match shorthand {
+                    // `{ __DEV__ }` becomes `{ __DEV__: false }`
+                    Some(span) => (span, format!("{}: {}", target, replacement)),
+                    None => (expression.span(), replacement.clone()),
+                };
+                self.edits.push(edit);
+            }
+            Action::Inject(_) => {
+                let spans = self.injected.entry(target.as_str()).or_default();
+                if path.len() > 1 {
+                    spans.push(expression.span());
+                }
+            }
+        }
+        true
+    }
+}
+
+impl<'a> Visit<'a> for GlobalFinder<'_> {
+    fn visit_expression(&mut self, expression: &Expression<'a>) {
+        if !self.record(expression, None) {
+            walk::walk_expression(self, expression);
+        }
+    }
+
+    fn visit_object_property(&mut self, property: &ObjectProperty<'a>) {
+        if !(property.shorthand && self.record(&property.value, Some(property.span))) {
+            walk::walk_object_property(self, property);
+        }
+    }
+}
+
+/// Root identifier and names of an identifier or static member chain.
+fn reference_path<'e, 'a>(
+    expression: &'e Expression<'a>,
+    path: &mut Vec<&'e str>,
+) -> Option<&'e IdentifierReference<'a>> {
+    match expression {
+        Expression::Identifier(identifier) => {
+            path.push(identifier.name.as_str());
+            Some(identifier)
+        }
+        Expression::StaticMemberExpression(member) => {
+            let root = reference_path(&member.object, path)?;
+            path.push(member.property.name.as_str());
+            Some(root)
+        }
+        _ => None,
+    }
+}
+
+/// A name no binding or global in the module uses.
+fn unique_name(base: &str, scoping: &Scoping, taken: &[String]) -> String {
+    let in_use = |name: &str| {
+        scoping.symbol_names().any(|symbol| symbol == name)
+            || scoping.root_unresolved_references().contains_key(name)
+            || taken.iter().any(|taken| taken == name)
+    };
+    let mut name = base.to_string();
+    let mut suffix = 1;
+    while in_use(&name) {
+        suffix += 1;
+        name = format!("{}{}", base, suffix);
+    }
+    name
+}
+
+/// Insert imports after the hashbang and directives, which must stay first.
+fn import_position(program: &Program<'_>, imports: String) -> (Span, String) {
+    let end = program
+        .directives
+        .last()
+        .map(|directive| directive.span.end)
+        .or_else(|| program.hashbang.as_ref().map(|hashbang| hashbang.span.end))
+        .unwrap_or(0);
+    let text = if end == 0 {
+        imports
+    } else {
+        format!("\n{}", imports.trim_end())
+    };
+    (Span::new(end, end), text)
+}
+
+/// Replace each span of `code`; spans must not overlap.
+fn apply_edits(code: &str, mut edits: Vec<(Span, String)>) -> String {
+    edits.sort_by_key(|(span, _)| std::cmp::Reverse((span.start, span.end)));
+    let mut code = code.to_string();
+    for (span, replacement) in edits {
+        code.replace_range(span.start as usize..span.end as usize, &replacement);
+    }
+    code
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn plugin(replace: &[(&str, &str)], inject: &[(&str, InjectImport)]) -> ReplacePlugin {
+        let replace = replace
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        let inject = inject
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.clone()))
+            .collect();
+        validate(&replace, &inject).unwrap();
+        ReplacePlugin::new(&replace, &inject, Path::new("/project"))
+    }
+
+    fn rewrite(plugin: &ReplacePlugin, code: &str) -> Option<String> {
+        plugin.rewrite(code, "/project/src/index.ts", SourceType::ts())
+    }
+
+    #[test]
+    fn replaces_global_references_only() {
+        let plugin = plugin(
+            &[
+                ("process.env.NODE_ENV", "\"production\""),
+                ("__DEV__", "false"),
+            ],
+            &[],
+        );
+        let code = "const mode: string = process.env.NODE_ENV;\n\
+                    const label = \"process.env.NODE_ENV\"; // process.env.NODE_ENV\n\
+                    const o = { __DEV__, NODE_ENV: process.env.NODE_ENV.length };\n\
+                    function f(__DEV__) { return __DEV__; }\n\
+                    if (__DEV__) f(process.env.OTHER);\n";
+        assert_eq!(
+            rewrite(&plugin, code).unwrap(),
+            "const mode: string = \"production\";\n\
+             const label = \"process.env.NODE_ENV\"; // process.env.NODE_ENV\n\
+             const o = { __DEV__: false, NODE_ENV: \"production\".length };\n\
+             function f(__DEV__) { return __DEV__; }\n\
+             if (false) f(process.env.OTHER);\n"
+        );
+    }
+
+    #[test]
+    fn keeps_shadowed_and_assigned_names() {
+        let plugin = plugin(&[("process.env.NODE_ENV", "\"production\"")], &[]);
+        assert_eq!(
+            rewrite(
+                &plugin,
+                "import process from 'node:process';\nprocess.env.NODE_ENV;"
+            ),
+            None
+        );
+        assert_eq!(rewrite(&plugin, "process.env.NODE_ENV = 'test';"), None);
+    }
+
+    #[test]
+    fn parenthesizes_compound_replacements() {
+        let plugin = plugin(&[("WIDTH", "10 + 5"), ("NAME", "'app'")], &[]);
+        assert_eq!(
+            rewrite(&plugin, "const area = WIDTH * 2, name = NAME;").unwrap(),
+            "const area = (10 + 5) * 2, name = 'app';"
+        );
+    }
+
+    #[test]
+    fn injects_imports_where_globals_are_used() {
+        let plugin = plugin(
+            &[],
+            &[
+                ("Buffer", InjectImport::named("buffer", "Buffer")),
+                ("$", InjectImport::default_export("jquery")),
+                (
+                    "Object.assign",
+                    InjectImport::default_export("./src/assign.js"),
+                ),
+            ],
+        );
+
+        assert_eq!(
+            rewrite(
+                &plugin,
+                "'use client';\nexport const b = Buffer.from($('a').text());\nObject.assign({}, b);"
+            )
+            .unwrap(),
+            "'use client';\nimport $ from \"jquery\";\nimport { Buffer } from \"buffer\";\nimport _Object_assign from \"./src/assign.js\";\nexport const b = Buffer.from($('a').text());\n_Object_assign({}, b);"
+        );
+        // Declared locally, so nothing to inject
+        assert_eq!(rewrite(&plugin, "class Buffer {}\nnew Buffer();"), None);
+        // The injected module itself is left alone
+        assert_eq!(
+            plugin.rewrite(
+                "export default Object.assign;",
+                "/project/src/assign.js",
+                SourceType::mjs()
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_options() {
+        let replace = |k: &str, v: &str| {
+            validate(
+                &FxHashMap::from_iter([(k.to_string(), v.to_string())]),
+                &FxHashMap::default(),
+            )
+        };
+        assert!(replace("process.env.NODE_ENV", "'x'").is_ok());
+        assert!(replace("process.env.", "'x'").is_err());
+        assert!(replace("typeof window", "'x'").is_err());
+        assert!(replace("__DEV__", "if (true)").is_err());
+
+        let both = validate(
+            &FxHashMap::from_iter([("Buffer".to_string(), "null".to_string())]),
+            &FxHashMap::from_iter([("Buffer".to_string(), InjectImport::namespace("buffer"))]),
+        );
+        assert!(both.is_err());
+    }
+}
@@ -0,0 +1,300 @@
+//! Source map quality options
+//!
+//! Rolldown decides whether a map is external, inline or hidden. The rest of
+//! [`SourceMapOptions`] is applied in two places: source paths are sanitized
+//! while Rolldown renders the map (through `sourcemap_path_transform`), and
+//! `sourcesContent` / `sourceRoot` are edited on the emitted map JSON. Inline
+//! maps that need editing are rendered hidden, edited, and then inlined here,
+//! so every mode goes through the same code path.
+
+use crate::{BundleOutput, Error, Output, Result, SourceMapType};
+use path_clean::PathClean;
+use rolldown_common::SourceMapPathTransform;
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::pin::Pin;
+use std::sync::Arc;
+
+/// Source map settings besides the generation mode.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SourceMapOptions {
+    /// Embed the original source text in `sourcesContent` (default: true).
+    ///
+    /// Disabling it keeps maps small and source code private, at the cost
+    /// of debuggers needing access to the original files.
+    pub sources_content: bool,
+
+    /// Value written to the `sourceRoot` field of every map.
+    pub source_root: Option<String>,
+
+    /// Rewrite sources outside the project root (default: false).
+    ///
+    /// Dependencies keep their path from the last `node_modules` segment
+    /// and other files are reduced to their file name, so maps don't reveal
+    /// the directory layout of the build machine.
+    pub sanitize_paths: bool,
+}
+
+impl Default for SourceMapOptions {
+    fn default() -> Self {
+        Self {
+            sources_content: true,
+            source_root: None,
+            sanitize_paths: false,
+        }
+    }
+}
+
+impl SourceMapOptions {
+    /// Whether emitted map JSON has to be edited.
+    fn edits_json(&self) -> bool {
+        !self.sources_content || self.source_root.is_some()
+    }
+}
+
+/// Post-processing of emitted maps, planned before the build.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(crate) struct SourceMapRewrite {
+    options: SourceMapOptions,
+    /// Maps were requested inline but are rendered hidden, and still have
+    /// to be appended to their chunks.
+    inline: bool,
+}
+
+impl SourceMapRewrite {
+    /// Plan the edits for a build, or `None` when Rolldown's output can be
+    /// used as is.
+    pub(crate) fn new(mode: Option<SourceMapType>, options: &SourceMapOptions) -> Option<Self> {
+        match mode {
+            Some(mode) if options.edits_json() => Some(Self {
+                options: options.clone(),
+                inline: matches!(mode, SourceMapType::Inline),
+            }),
+            _ => None,
+        }
+    }
+}
+
+/// The mode Rolldown should render maps in.
+pub(crate) fn rolldown_mode(
+    mode: Option<SourceMapType>,
+    options: &SourceMapOptions,
+) -> Option<SourceMapType> {
+    match mode {
+        Some(SourceMapType::Inline) if options.edits_json() => Some(SourceMapType::Hidden),
+        mode => mode,
+    }
+}
+
+/// Path transform that sanitizes sources outside `project_root`.
+pub(crate) fn path_transform(project_root: PathBuf) -> SourceMapPathTransform {
+    SourceMapPathTransform::new(Arc::new(move |source: &str, map_path: &str| {
+        let source = sanitize_source(source, Path::new(map_path), &project_root);
+        Box::pin(async move { Ok(source) })
+            as Pin<Box<dyn Future<Output = anyhow::Result<String>> + Send>>
+    }))
+}
+
+/// Rewrite one `sources` entry, given relative to the map file.
+pub(crate) fn sanitize_source(source: &str, map_path: &Path, project_root: &Path) -> String {
+    let source = source.trim_start_matches('\0');
+    let map_dir = map_path.parent().unwrap_or(Path::new(""));
+    let absolute = map_dir.join(source).clean();
+    if absolute.starts_with(project_root) {
+        return source.replace('\\', "/");
+    }
+
+    let normalized = absolute.to_string_lossy().replace('\\', "/");
+    match normalized.rfind("node_modules/") {
+        Some(index) => normalized[index..].to_string(),
+        None => absolute
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_else(|| source.to_string()),
+    }
+}
+
+/// Apply the planned edits to every emitted map.
+pub(crate) fn rewrite_bundle(bundle: &mut BundleOutput, rewrite: &SourceMapRewrite) -> Result<()> {
+    let mut inlined = Vec::new();
+
+    for index in 0..bundle.assets.len() {
+        let Output::Asset(asset) = &bundle.assets[index] else {
+            continue;
+        };
+        let Some(chunk_filename) = asset.filename.strip_suffix(".map") else {
+            continue;
+        };
+
+        let json = rewrite_map_json(asset.source.as_bytes(), &rewrite.options).map_err(|e| {
+            Error::WriteFailure(format!(
+                "Failed to rewrite source map {}: {}",
+                asset.filename, e
+            ))
+        })?;
+
+        if rewrite.inline {
+            inlined.push((chunk_filename.to_string(), json));
+            continue;
+        }
+        let mut asset = (**asset).clone();
+        asset.source = json.into();
+        bundle.assets[index] = Output::Asset(Arc::new(asset));
+    }
+
+    if inlined.is_empty() {
+        return Ok(());
+    }
+
+    for output in &mut bundle.assets {
+        let Output::Chunk(chunk) = output else {
+            continue;
+        };
+        let Some((_, json)) = inlined
+            .iter()
+            .find(|(filename, _)| *filename == chunk.filename.as_str())
+        else {
+            continue;
+        };
+
+        let mut chunk_with_map = (**chunk).clone();
+        if !chunk_with_map.code.ends_with('\n') {
+            chunk_with_map.code.push('\n');
+        }
+        chunk_with_map
+            .code
+            .push_str("//# sourceMappingURL=data:application/json;charset=utf-8;base64,");
+        chunk_with_map
+            .code
+            .push_str(&base64_encode(json.as_bytes()));
+        chunk_with_map.code.push('\n');
+        chunk_with_map.sourcemap_filename = None;
+        *chunk = Arc::new(chunk_with_map);
+    }
+
+    // The maps now live in their chunks
+    bundle.assets.retain(|output| match output {
+        Output::Asset(asset) => !inlined
+            .iter()
+            .any(|(filename, _)| asset.filename.strip_suffix(".map") == Some(filename.as_str())),
+        Output::Chunk(_) => true,
+    });
+
+    Ok(())
+}
+
+/// Apply `sourcesContent` and `sourceRoot` settings to one map.
+pub(crate) fn rewrite_map_json(
+    json: &[u8],
+    options: &SourceMapOptions,
+) -> serde_json::Result<String> {
+    let mut map: serde_json::Value = serde_json::from_slice(json)?;
+    if let Some(fields) = map.as_object_mut() {
+        if !options.sources_content {
+            fields.remove("sourcesContent");
+        }
+        if let Some(root) = &options.source_root {
+            fields.insert("sourceRoot".to_string(), root.clone().into());
+        }
+    }
+    serde_json::to_string(&map)
+}
+
+/// Standard base64 with padding, for inline `data:` URLs.
+fn base64_encode(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+    let mut out = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for group in bytes.chunks(3) {
+        let b = [
+            group[0],
+            *group.get(1).unwrap_or(&0),
+            *group.get(2).unwrap_or(&0),
+        ];
+        let n = (u32::from(b[0]) << 16) | (u32::from(b[1]) << 8) | u32::from(b[2]);
+        for (i, shift) in [18, 12, 6, 0].into_iter().enumerate() {
+            if i <= group.len() {
+                out.push(ALPHABET[(n >> shift & 0x3f) as usize] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_base64_encode() {
+        assert_eq!(base64_encode(b""), "");
+        assert_eq!(base64_encode(b"f"), "Zg==");
+        assert_eq!(base64_encode(b"fo"), "Zm8=");
+        assert_eq!(base64_encode(b"foo"), "Zm9v");
+        assert_eq!(base64_encode(b"foobar"), "Zm9vYmFy");
+    }
+
+    #[test]
+    fn test_rewrite_map_json() {
+        let json =
+            br#"{"version":3,"sources":["../src/a.ts"],"sourcesContent":["x"],"mappings":""}"#;
+        let options = SourceMapOptions {
+            sources_content: false,
+            source_root: Some("/app/".to_string()),
+            sanitize_paths: false,
+        };
+
+        let map: serde_json::Value =
+            serde_json::from_str(&rewrite_map_json(json, &options).unwrap()).unwrap();
+
+        assert!(map.get("sourcesContent").is_none());
+        assert_eq!(map["sourceRoot"], "/app/");
+        assert_eq!(map["sources"][0], "../src/a.ts");
+    }
+
+    #[test]
+    fn test_sanitize_source() {
+        let root = Path::new("/home/alice/project");
+        let map = Path::new("/home/alice/project/dist/index.js.map");
+
+        assert_eq!(sanitize_source("../src/a.ts", map, root), "../src/a.ts");
+        assert_eq!(
+            sanitize_source(
+                "../../node_modules/.pnpm/react/node_modules/react/index.js",
+                map,
+                root
+            ),
+            "node_modules/react/index.js"
+        );
+        assert_eq!(
+            sanitize_source("../../../shared/util.ts", map, root),
+            "util.ts"
+        );
+        assert_eq!(sanitize_source("\0virtual:env", map, root), "virtual:env");
+    }
+
+    #[test]
+    fn test_rewrite_only_when_json_changes() {
+        let defaults = SourceMapOptions::default();
+        assert_eq!(
+            SourceMapRewrite::new(Some(SourceMapType::File), &defaults),
+            None
+        );
+
+        let no_content = SourceMapOptions {
+            sources_content: false,
+            ..SourceMapOptions::default()
+        };
+        assert_eq!(SourceMapRewrite::new(None, &no_content), None);
+        assert!(matches!(
+            rolldown_mode(Some(SourceMapType::Inline), &no_content),
+            Some(SourceMapType::Hidden)
+        ));
+        assert!(matches!(
+            rolldown_mode(Some(SourceMapType::Inline), &defaults),
+            Some(SourceMapType::Inline)
+        ));
+    }
+}
@@ -0,0 +1,340 @@
+//! Top-level await handling
+//!
+//! Only ES module output can keep `await` at the top level of a module. A
+//! lightweight plugin records which modules use it while they are
+//! transformed, which lets a CJS or IIFE build fail with one diagnostic per
+//! module (pointing at the first top-level `await`) instead of Rolldown's
+//! generic unsupported-feature error, and lets ESM builds move those modules
+//! into chunks of their own.
+
+use crate::builders::output_validation::line_col;
+use crate::diagnostics::{DiagnosticKind, DiagnosticSeverity, ExtractedDiagnostic};
+use crate::{Error, OutputFormat, Result};
+use oxc_allocator::Allocator;
+use oxc_ast::ast::{
+    ArrowFunctionExpression, AwaitExpression, ForOfStatement, Function, StaticBlock,
+};
+use oxc_ast_visit::{Visit, walk};
+use oxc_parser::Parser;
+use oxc_semantic::ScopeFlags;
+use oxc_span::{SourceType, Span};
+use parking_lot::Mutex;
+use rolldown_plugin::{
+    HookTransformArgs, HookTransformReturn, HookUsage, Plugin, SharedTransformPluginContext,
+};
+use std::borrow::Cow;
+use std::fmt;
+use std::future::Future;
+use std::path::{Component, Path, PathBuf};
+use std::pin::Pin;
+use std::str::FromStr;
+use std::sync::Arc;
+
+/// How modules using top-level await are bundled.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum TopLevelAwait {
+    /// Leave such modules where the default chunking puts them (default).
+    ///
+    /// Every importer of the chunk they end up in waits for the `await`.
+    #[default]
+    Preserve,
+
+    /// Give every module using top-level await a chunk of its own, so only
+    /// modules that actually import it wait for the `await`. ESM only.
+    Isolate,
+}
+
+impl fmt::Display for TopLevelAwait {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Preserve => "preserve",
+            Self::Isolate => "isolate",
+        })
+    }
+}
+
+impl FromStr for TopLevelAwait {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "preserve" => Ok(Self::Preserve),
+            "isolate" => Ok(Self::Isolate),
+            other => Err(Error::InvalidConfig(format!(
+                "Unknown top-level await strategy '{}'. Expected preserve or isolate",
+                other
+            ))),
+        }
+    }
+}
+
+/// A module using top-level await.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct TopLevelAwaitUse {
+    pub module_id: String,
+    /// One-based position of the first top-level `await`
+    pub line: u32,
+    pub column: u32,
+}
+
+/// Records the modules that use top-level await.
+#[derive(Debug)]
+pub(crate) struct TopLevelAwaitPlugin {
+    uses: Arc<Mutex<Vec<TopLevelAwaitUse>>>,
+    /// Root that isolated chunks are named relative to
+    cwd: PathBuf,
+}
+
+impl TopLevelAwaitPlugin {
+    pub(crate) fn new(cwd: PathBuf) -> Self {
+        Self {
+            uses: Arc::default(),
+            cwd,
+        }
+    }
+
+    /// Modules recorded so far, sorted by id.
+    pub(crate) fn uses(&self) -> Vec<TopLevelAwaitUse> {
+        let mut uses = self.uses.lock().clone();
+        uses.sort_by(|a, b| a.module_id.cmp(&b.module_id));
+        uses
+    }
+
+    /// Rolldown chunk group placing each recorded module in its own chunk.
+    ///
+    /// Chunks are named after the module's path relative to the project,
+    /// so two `config.ts` files in different directories stay apart.
+    pub(crate) fn isolation_group(&self) -> rolldown::MatchGroup {
+        let uses = Arc::clone(&self.uses);
+        let cwd = self.cwd.clone();
+        let name = move |module_id: &str, _: &rolldown::ChunkingContext| {
+            let name = uses
+                .lock()
+                .iter()
+                .any(|recorded| recorded.module_id == module_id)
+                .then(|| chunk_name(module_id, &cwd));
+            Box::pin(async move { Ok(name) })
+                as Pin<Box<dyn Future<Output = anyhow::Result<Option<String>>> + Send>>
+        };
+        rolldown::MatchGroup {
+            name: rolldown::MatchGroupName::Dynamic(Arc::new(name)),
+            // Below manual chunks, above everything else
+            priority: Some(u32::MAX - 1),
+            ..Default::default()
+        }
+    }
+}
+
+impl Plugin for TopLevelAwaitPlugin {
+    fn name(&self) -> Cow<'static, str> {
+        "fob-top-level-await".into()
+    }
+
+    fn register_hook_usage(&self) -> HookUsage {
+        HookUsage::Transform
+    }
+
+    fn transform(
+        &self,
+        _ctx: SharedTransformPluginContext,
+        args: &HookTransformArgs<'_>,
+    ) -> impl std::future::Future<Output = HookTransformReturn> + Send {
+        // Most modules never mention `await`; skip parsing those
+        if args.code.contains("await") {
+            if let Some(span) = find_top_level_await(args.code, args.id) {
+                let (line, column) = line_col(args.code, span.start);
+                self.uses.lock().push(TopLevelAwaitUse {
+                    module_id: args.id.to_string(),
+                    line: line + 1,
+                    column: column + 1,
+                });
+            }
+        }
+        async { Ok(None) }
+    }
+}
+
+/// One error per module when the output format can't express top-level await.
+pub(crate) fn check_format(uses: &[TopLevelAwaitUse], format: OutputFormat) -> Result<()> {
+    if matches!(format, OutputFormat::Esm) || uses.is_empty() {
+        return Ok(());
+    }
+
+    let format_name = match format {
+        OutputFormat::Cjs => "CommonJS",
+        OutputFormat::Umd => "UMD",
+        _ => "IIFE",
+    };
+    Err(Error::Bundler(
+        uses.iter()
+            .map(|recorded| ExtractedDiagnostic {
+                kind: DiagnosticKind::Other("TopLevelAwait".to_string()),
+                severity: DiagnosticSeverity::Error,
+                message: format!(
+                    "Top-level await in {} can't be bundled as {}",
+                    recorded.module_id, format_name
+                ),
+                file: Some(recorded.module_id.clone()),
+                line: Some(recorded.line),
+                column: Some(recorded.column),
+                help: Some(format!(
+                    "{} output runs modules synchronously. Build this entry as ESM, \
+                     or move the await into an async function",
+                    format_name
+                )),
+                context: None,
+                error_chain: Vec::new(),
+            })
+            .collect(),
+    ))
+}
+
+/// Span of the first top-level `await` in a module, if it parses.
+fn find_top_level_await(code: &str, id: &str) -> Option<Span> {
+    let source_type = SourceType::from_path(id)
+        .unwrap_or_else(|_| SourceType::mjs())
+        .with_module(true);
+    let allocator = Allocator::default();
+    let parsed = Parser::new(&allocator, code, source_type).parse();
+    if !parsed.errors.is_empty() {
+        return None;
+    }
+
+    let mut finder = TopLevelAwaitFinder {
+        function_depth: 0,
+        found: None,
+    };
+    finder.visit_program(&parsed.program);
+    finder.found
+}
+
+/// Chunk name for an isolated module: its path relative to `cwd` without
+/// the extension, with directories joined by `-`.
+fn chunk_name(module_id: &str, cwd: &Path) -> String {
+    let path = Path::new(module_id);
+    let relative = path.strip_prefix(cwd).unwrap_or(path).with_extension("");
+    let name = relative
+        .components()
+        .filter_map(|c| match c {
+            Component::Normal(segment) => Some(segment.to_string_lossy()),
+            _ => None,
+        })
+        .collect::<Vec<_>>()
+        .join("-");
+    if name.is_empty() {
+        "tla".to_string()
+    } else {
+        name
+    }
+}
+
+/// Visitor stopping at the first `await` outside any function.
+struct TopLevelAwaitFinder {
+    function_depth: usize,
+    found: Option<Span>,
+}
+
+impl<'a> Visit<'a> for TopLevelAwaitFinder {
+    fn visit_function(&mut self, it: &Function<'a>, flags: ScopeFlags) {
+        self.function_depth += 1;
+        walk::walk_function(self, it, flags);
+        self.function_depth -= 1;
+    }
+
+    fn visit_arrow_function_expression(&mut self, it: &ArrowFunctionExpression<'a>) {
+        self.function_depth += 1;
+        walk::walk_arrow_function_expression(self, it);
+        self.function_depth -= 1;
+    }
+
+    fn visit_static_block(&mut self, it: &StaticBlock<'a>) {
+        self.function_depth += 1;
+        walk::walk_static_block(self, it);
+        self.function_depth -= 1;
+    }
+
+    fn visit_await_expression(&mut self, it: &AwaitExpression<'a>) {
+        if self.function_depth == 0 && self.found.is_none() {
+            self.found = Some(it.span);
+        }
+        walk::walk_await_expression(self, it);
+    }
+
+    fn visit_for_of_statement(&mut self, it: &ForOfStatement<'a>) {
+        if it.r#await && self.function_depth == 0 && self.found.is_none() {
+            self.found = Some(it.span);
+        }
+        walk::walk_for_of_statement(self, it);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_top_level_await() {
+        let code = "import x from 'x';\nconst config = await load();\n";
+        let span = find_top_level_await(code, "/src/config.js").unwrap();
+        assert_eq!(line_col(code, span.start), (1, 15));
+
+        assert!(find_top_level_await("for await (const x of xs) {}", "/src/a.mjs").is_some());
+        assert!(
+            find_top_level_await(
+                "export const config: Config = await load<Config>();",
+                "/src/config.ts"
+            )
+            .is_some()
+        );
+    }
+
+    #[test]
+    fn test_await_inside_functions_is_ignored() {
+        let code = "async function f() { await g(); }\nconst h = async () => { await g(); };\nclass A { static { } }";
+        assert_eq!(find_top_level_await(code, "/src/a.js"), None);
+        assert_eq!(find_top_level_await("const await = 1;", "/src/a.cjs"), None);
+    }
+
+    #[test]
+    fn test_check_format() {
+        let uses = vec![TopLevelAwaitUse {
+            module_id: "/src/config.ts".to_string(),
+            line: 2,
+            column: 16,
+        }];
+
+        assert!(check_format(&uses, OutputFormat::Esm).is_ok());
+        assert!(check_format(&[], OutputFormat::Cjs).is_ok());
+
+        let Err(Error::Bundler(diagnostics)) = check_format(&uses, OutputFormat::Iife) else {
+            panic!("expected diagnostics");
+        };
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].file.as_deref(), Some("/src/config.ts"));
+        assert_eq!(diagnostics[0].line, Some(2));
+        assert!(diagnostics[0].message.contains("IIFE"));
+    }
+
+    #[test]
+    fn test_chunk_name() {
+        let cwd = Path::new("/project");
+        assert_eq!(chunk_name("/project/src/config.ts", cwd), "src-config");
+        assert_eq!(chunk_name("/elsewhere/db.mjs", cwd), "elsewhere-db");
+    }
+
+    #[test]
+    fn test_strategy_from_str() {
+        assert_eq!(
+            "isolate".parse::<TopLevelAwait>().unwrap(),
+            TopLevelAwait::Isolate
+        );
+        assert_eq!(
+            TopLevelAwait::Preserve
+                .to_string()
+                .parse::<TopLevelAwait>()
+                .unwrap(),
+            TopLevelAwait::Preserve
+        );
+        assert!("lower".parse::<TopLevelAwait>().is_err());
+    }
+}
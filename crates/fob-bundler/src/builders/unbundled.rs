@@ -0,0 +1,609 @@
+//! Unbundled development serving
+//!
+//! Instead of bundling the project, a dev server can hand the browser one
+//! source module per request. Each module is transformed on its own
+//! (TypeScript and JSX are stripped), relative imports are rewritten to URLs
+//! under the project root, and bare imports are left to an import map.
+//! Dependencies are the exception: they are bundled once, in a single build
+//! so packages that share code also share chunks, and served under
+//! [`DEPS_URL_PREFIX`].
+//!
+//! CommonJS dependencies only have a default export once bundled, so named
+//! imports of them are rewritten to destructure the default export.
+
+use crate::builders::output_validation::line_col;
+use crate::diagnostics::{DiagnosticKind, DiagnosticSeverity, ExtractedDiagnostic};
+use crate::oxc::{Codegen, TransformOptions, Transformer};
+use crate::{BuildOptions, EntryPoints, Error, ImportMap, Platform, Result};
+use oxc_allocator::Allocator;
+use oxc_ast::ast::{
+    ExportAllDeclaration, ExportNamedDeclaration, Expression, ImportDeclaration,
+    ImportDeclarationSpecifier, ImportExpression, StringLiteral,
+};
+use oxc_ast_visit::{Visit, walk};
+use oxc_parser::Parser;
+use oxc_semantic::SemanticBuilder;
+use oxc_span::{SourceType, Span};
+use path_clean::PathClean;
+use rustc_hash::{FxHashMap, FxHashSet};
+use std::collections::BTreeSet;
+use std::path::{Component, Path, PathBuf};
+
+/// URL prefix prebundled dependencies are served under.
+pub const DEPS_URL_PREFIX: &str = "/@deps/";
+
+/// Query appended to imports of files that aren't modules, such as CSS.
+///
+/// The server answers these with a module that applies or exposes the file.
+pub const IMPORT_QUERY: &str = "?import";
+
+/// Extensions transformed and served as modules, in resolution order.
+const MODULE_EXTENSIONS: &[&str] = &["ts", "tsx", "mts", "js", "jsx", "mjs"];
+
+/// A source module ready to be served.
+#[derive(Debug, Clone)]
+pub struct UnbundledModule {
+    /// JavaScript with types and JSX removed and imports rewritten
+    pub code: String,
+    /// Project modules it imports, in source order
+    pub local_imports: Vec<PathBuf>,
+    /// Bare specifiers it imports, resolved through the import map
+    pub bare_imports: Vec<String>,
+}
+
+/// Dependencies bundled for unbundled serving.
+#[derive(Debug, Clone, Default)]
+pub struct PrebundledDependencies {
+    /// Output code by URL path, entries and shared chunks alike
+    pub files: FxHashMap<String, String>,
+    /// Maps each dependency specifier to its entry chunk
+    pub import_map: ImportMap,
+    /// Dependencies whose only export is `default` (CommonJS)
+    pub default_only: FxHashSet<String>,
+}
+
+/// Whether a file is served as a transformed module.
+pub fn is_module_path(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| MODULE_EXTENSIONS.contains(&ext))
+}
+
+/// URL of a project file, or `None` for files outside `root`.
+pub fn module_url(file: &Path, root: &Path) -> Option<String> {
+    let relative = file.strip_prefix(root).ok()?;
+    let segments: Vec<_> = relative
+        .components()
+        .map(|c| match c {
+            Component::Normal(segment) => Some(segment.to_string_lossy()),
+            _ => None,
+        })
+        .collect::<Option<_>>()?;
+    Some(format!("/{}", segments.join("/")))
+}
+
+/// Transform one module for the browser.
+///
+/// `default_only` lists dependencies whose named imports have to go through
+/// their default export (see [`PrebundledDependencies::default_only`]).
+///
+/// # Errors
+///
+/// Returns [`Error::Bundler`] with a diagnostic per parse or transform error.
+pub fn transform_module(
+    path: &Path,
+    source: &str,
+    root: &Path,
+    default_only: &FxHashSet<String>,
+) -> Result<UnbundledModule> {
+    let code = strip_types(path, source)?;
+    Ok(rewrite_imports(path, code, root, default_only))
+}
+
+/// Bare specifiers reachable from `entries` through project modules.
+///
+/// # Errors
+///
+/// Fails when a reachable module can't be read or transformed.
+pub fn scan_dependencies(entries: &[PathBuf], root: &Path) -> Result<BTreeSet<String>> {
+    let none = FxHashSet::default();
+    let mut queue: Vec<PathBuf> = entries
+        .iter()
+        .map(|entry| root.join(entry).clean())
+        .collect();
+    let mut seen = FxHashSet::default();
+    let mut dependencies = BTreeSet::new();
+
+    while let Some(file) = queue.pop() {
+        if !is_module_path(&file) || !seen.insert(file.clone()) {
+            continue;
+        }
+        let source = std::fs::read_to_string(&file)?;
+        let module = transform_module(&file, &source, root, &none)?;
+        dependencies.extend(module.bare_imports);
+        queue.extend(module.local_imports);
+    }
+
+    Ok(dependencies)
+}
+
+/// Bundle dependencies for the browser, one entry chunk per specifier.
+///
+/// # Errors
+///
+/// Returns the build error when a dependency can't be bundled.
+pub async fn prebundle_dependencies(
+    specifiers: &BTreeSet<String>,
+    root: &Path,
+) -> Result<PrebundledDependencies> {
+    let mut prebundled = PrebundledDependencies::default();
+    if specifiers.is_empty() {
+        return Ok(prebundled);
+    }
+
+    let entries: FxHashMap<String, String> = specifiers
+        .iter()
+        .map(|specifier| (specifier.replace('/', "__"), specifier.clone()))
+        .collect();
+    let result = BuildOptions {
+        entry: EntryPoints::Named(entries.clone()),
+        ..BuildOptions::new(".")
+    }
+    .cwd(root)
+    .platform(Platform::Browser)
+    .sourcemap(false)
+    .entry_names("[name].js")
+    .chunk_names("chunk-[hash].js")
+    .disable_slow_module_warnings()
+    .disable_chunk_size_warnings()
+    .build()
+    .await?;
+
+    for chunk in result.chunks() {
+        let url = format!("{}{}", DEPS_URL_PREFIX, chunk.filename);
+        if let Some(specifier) = entries.get(chunk.name.as_str()).filter(|_| chunk.is_entry) {
+            prebundled.import_map.insert(specifier.clone(), url.clone());
+            if !chunk.exports.is_empty() && chunk.exports.iter().all(|name| name == "default") {
+                prebundled.default_only.insert(specifier.clone());
+            }
+        }
+
+        // Packages read `process.env.NODE_ENV`, which browsers don't define
+        let code = if chunk.code.contains("process.env") {
+            format!(
+                "const process = {{ env: {{ NODE_ENV: \"development\" }} }};\n{}",
+                chunk.code
+            )
+        } else {
+            chunk.code.clone()
+        };
+        prebundled.files.insert(url, code);
+    }
+
+    Ok(prebundled)
+}
+
+/// Strip TypeScript and JSX from a module.
+fn strip_types(path: &Path, source: &str) -> Result<String> {
+    let source_type = SourceType::from_path(path).unwrap_or_else(|_| SourceType::mjs());
+    let allocator = Allocator::default();
+    let parsed = Parser::new(&allocator, source, source_type).parse();
+    if !parsed.errors.is_empty() {
+        return Err(module_error(
+            path,
+            source,
+            DiagnosticKind::ParseError,
+            parsed.errors.iter().map(|error| {
+                let offset = error
+                    .labels
+                    .as_ref()
+                    .and_then(|labels| labels.first())
+                    .map(|label| label.offset() as u32);
+                (error.message.to_string(), offset)
+            }),
+        ));
+    }
+
+    let mut program = parsed.program;
+    let scoping = SemanticBuilder::new()
+        .build(&program)
+        .semantic
+        .into_scoping();
+    let transformed = Transformer::new(&allocator, path, &TransformOptions::default())
+        .build_with_scoping(scoping, &mut program);
+    if !transformed.errors.is_empty() {
+        return Err(module_error(
+            path,
+            source,
+            DiagnosticKind::Transform,
+            transformed.errors.iter().map(|error| {
+                let offset = error
+                    .labels
+                    .as_ref()
+                    .and_then(|labels| labels.first())
+                    .map(|label| label.offset() as u32);
+                (error.message.to_string(), offset)
+            }),
+        ));
+    }
+
+    Ok(Codegen::new().build(&program).code)
+}
+
+/// Rewrite the import specifiers of transformed code.
+fn rewrite_imports(
+    path: &Path,
+    code: String,
+    root: &Path,
+    default_only: &FxHashSet<String>,
+) -> UnbundledModule {
+    let allocator = Allocator::default();
+    let parsed = Parser::new(&allocator, &code, SourceType::mjs()).parse();
+    let mut collector = ImportCollector {
+        default_only,
+        sources: Vec::new(),
+        interop: Vec::new(),
+        interop_sources: Vec::new(),
+    };
+    collector.visit_program(&parsed.program);
+
+    let importer_dir = path.parent().unwrap_or(root);
+    let mut module = UnbundledModule {
+        code: String::new(),
+        local_imports: Vec::new(),
+        bare_imports: Vec::new(),
+    };
+    let mut edits = std::mem::take(&mut collector.interop);
+    for (span, specifier) in std::mem::take(&mut collector.sources) {
+        if is_bare(&specifier) {
+            module.bare_imports.push(specifier);
+            continue;
+        }
+        if !specifier.starts_with("./") && !specifier.starts_with("../") {
+            continue;
+        }
+        let Some(file) = resolve_file(&importer_dir.join(&specifier).clean()) else {
+            continue;
+        };
+        let Some(mut url) = module_url(&file, root) else {
+            continue;
+        };
+        if is_module_path(&file) {
+            module.local_imports.push(file);
+        } else {
+            url.push_str(IMPORT_QUERY);
+        }
+        edits.push((span, quote(&url)));
+    }
+    module.bare_imports.extend(collector.interop_sources);
+
+    edits.sort_by_key(|(span, _)| span.start);
+    let mut rewritten = String::with_capacity(code.len());
+    let mut last = 0;
+    for (span, replacement) in edits {
+        rewritten.push_str(&code[last..span.start as usize]);
+        rewritten.push_str(&replacement);
+        last = span.end as usize;
+    }
+    rewritten.push_str(&code[last..]);
+    module.code = rewritten;
+    module
+}
+
+/// Prefix of the bindings CommonJS imports are read through.
+const INTEROP_BINDING: &str = "__fob_cjs_";
+
+/// Whether a specifier is resolved through the import map.
+fn is_bare(specifier: &str) -> bool {
+    !specifier.starts_with('.') && !specifier.starts_with('/') && !specifier.contains(':')
+}
+
+/// Find the file an import refers to, trying the extensions and index files
+/// bundlers accept.
+fn resolve_file(base: &Path) -> Option<PathBuf> {
+    if base.is_file() {
+        return Some(base.to_path_buf());
+    }
+
+    // TypeScript sources are imported with the extension they compile to
+    let swapped: &[&str] = match base.extension().and_then(|ext| ext.to_str()) {
+        Some("js") => &["ts", "tsx"],
+        Some("jsx") => &["tsx"],
+        Some("mjs") => &["mts"],
+        _ => &[],
+    };
+    let candidates = swapped
+        .iter()
+        .map(|ext| base.with_extension(ext))
+        .chain(MODULE_EXTENSIONS.iter().map(|ext| {
+            let mut file = base.as_os_str().to_owned();
+            file.push(".");
+            file.push(ext);
+            PathBuf::from(file)
+        }))
+        .chain(
+            MODULE_EXTENSIONS
+                .iter()
+                .map(|ext| base.join(format!("index.{}", ext))),
+        );
+
+    candidates.into_iter().find(|candidate| candidate.is_file())
+}
+
+fn quote(value: &str) -> String {
+    serde_json::to_string(value).unwrap_or_else(|_| format!("\"{}\"", value))
+}
+
+/// Error for a module that failed to parse or transform.
+fn module_error(
+    path: &Path,
+    source: &str,
+    kind: DiagnosticKind,
+    errors: impl Iterator<Item = (String, Option<u32>)>,
+) -> Error {
+    let file = path.to_string_lossy().into_owned();
+    Error::Bundler(
+        errors
+            .map(|(message, offset)| {
+                let position = offset.map(|offset| line_col(source, offset));
+                ExtractedDiagnostic {
+                    kind: kind.clone(),
+                    severity: DiagnosticSeverity::Error,
+                    message,
+                    file: Some(file.clone()),
+                    line: position.map(|(line, _)| line + 1),
+                    column: position.map(|(_, column)| column + 1),
+                    help: None,
+                    context: None,
+                    error_chain: Vec::new(),
+                }
+            })
+            .collect(),
+    )
+}
+
+/// Collects import specifiers, and replaces named imports of CommonJS
+/// dependencies.
+struct ImportCollector<'s> {
+    default_only: &'s FxHashSet<String>,
+    /// Specifier literals, to be rewritten
+    sources: Vec<(Span, String)>,
+    /// Whole import declarations and their replacement
+    interop: Vec<(Span, String)>,
+    /// Specifiers of the replaced declarations
+    interop_sources: Vec<String>,
+}
+
+impl ImportCollector<'_> {
+    fn add_source(&mut self, source: &StringLiteral<'_>) {
+        self.sources.push((source.span, source.value.to_string()));
+    }
+
+    /// `import a, { b as c } from "x"` becomes
+    /// `import __fob_cjs_0 from "x"; const a = __fob_cjs_0, { "b": c } = __fob_cjs_0;`
+    fn interop_import(&self, it: &ImportDeclaration<'_>) -> Option<String> {
+        let specifiers = it.specifiers.as_ref()?;
+        let specifier = it.source.value.as_str();
+        let named = specifiers
+            .iter()
+            .any(|s| matches!(s, ImportDeclarationSpecifier::ImportSpecifier(_)));
+        if !self.default_only.contains(specifier) || !named || it.import_kind.is_type() {
+            return None;
+        }
+
+        let binding = format!("{}{}", INTEROP_BINDING, self.interop.len());
+        let mut declarators = Vec::new();
+        let mut destructured = Vec::new();
+        for s in specifiers {
+            match s {
+                ImportDeclarationSpecifier::ImportDefaultSpecifier(s) => {
+                    declarators.push(format!("{} = {}", s.local.name, binding));
+                }
+                ImportDeclarationSpecifier::ImportNamespaceSpecifier(s) => {
+                    declarators.push(format!("{} = {}", s.local.name, binding));
+                }
+                ImportDeclarationSpecifier::ImportSpecifier(s) => {
+                    let imported = s.imported.name();
+                    if imported == "default" {
+                        declarators.push(format!("{} = {}", s.local.name, binding));
+                    } else {
+                        destructured.push(format!("{}: {}", quote(&imported), s.local.name));
+                    }
+                }
+            }
+        }
+        if !destructured.is_empty() {
+            declarators.push(format!("{{ {} }} = {}", destructured.join(", "), binding));
+        }
+
+        Some(format!(
+            "import {} from {}; const {};",
+            binding,
+            quote(specifier),
+            declarators.join(", ")
+        ))
+    }
+}
+
+impl<'a> Visit<'a> for ImportCollector<'_> {
+    fn visit_import_declaration(&mut self, it: &ImportDeclaration<'a>) {
+        match self.interop_import(it) {
+            Some(replacement) => {
+                self.interop.push((it.span, replacement));
+                self.interop_sources.push(it.source.value.to_string());
+            }
+            None => self.add_source(&it.source),
+        }
+    }
+
+    fn visit_export_named_declaration(&mut self, it: &ExportNamedDeclaration<'a>) {
+        if let Some(source) = &it.source {
+            self.add_source(source);
+        }
+        walk::walk_export_named_declaration(self, it);
+    }
+
+    fn visit_export_all_declaration(&mut self, it: &ExportAllDeclaration<'a>) {
+        self.add_source(&it.source);
+    }
+
+    fn visit_import_expression(&mut self, it: &ImportExpression<'a>) {
+        if let Expression::StringLiteral(source) = &it.source {
+            self.add_source(source);
+        }
+        walk::walk_import_expression(self, it);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn project(files: &[(&str, &str)]) -> TempDir {
+        let temp = TempDir::new().unwrap();
+        for (path, contents) in files {
+            let path = temp.path().join(path);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, contents).unwrap();
+        }
+        temp
+    }
+
+    #[test]
+    fn test_transform_rewrites_relative_imports() {
+        let temp = project(&[
+            ("src/main.ts", ""),
+            ("src/util.ts", ""),
+            ("src/components/index.tsx", ""),
+            ("src/app.css", ""),
+        ]);
+        let root = temp.path();
+        let source = "import { add } from './util.js';\n\
+                      import type { Props } from './components';\n\
+                      import { Button } from './components';\n\
+                      import './app.css';\n\
+                      import { h } from 'preact';\n\
+                      const lazy = () => import('./util');\n\
+                      export const x: number = add(1, 2);\n\
+                      export { Button, h, lazy };\n";
+
+        let module = transform_module(
+            &root.join("src/main.ts"),
+            source,
+            root,
+            &FxHashSet::default(),
+        )
+        .unwrap();
+
+        assert!(
+            module.code.contains("from \"/src/util.ts\""),
+            "{}",
+            module.code
+        );
+        assert!(module.code.contains("from \"/src/components/index.tsx\""));
+        assert!(module.code.contains("import \"/src/app.css?import\""));
+        assert!(module.code.contains("import(\"/src/util.ts\")"));
+        assert!(module.code.contains("from \"preact\""));
+        assert!(!module.code.contains(": number"));
+        assert_eq!(module.bare_imports, vec!["preact".to_string()]);
+        assert_eq!(
+            module.local_imports,
+            vec![
+                root.join("src/util.ts"),
+                root.join("src/components/index.tsx"),
+                root.join("src/util.ts"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_transform_strips_jsx() {
+        let temp = project(&[("src/App.tsx", "")]);
+        let root = temp.path();
+
+        let module = transform_module(
+            &root.join("src/App.tsx"),
+            "export const App = () => <div>Hello</div>;",
+            root,
+            &FxHashSet::default(),
+        )
+        .unwrap();
+
+        assert!(!module.code.contains("<div>"));
+        assert_eq!(module.bare_imports, vec!["react/jsx-runtime".to_string()]);
+    }
+
+    #[test]
+    fn test_named_imports_of_commonjs_use_default_export() {
+        let temp = project(&[("src/main.js", "")]);
+        let root = temp.path();
+        let default_only = FxHashSet::from_iter(["react".to_string()]);
+
+        let module = transform_module(
+            &root.join("src/main.js"),
+            "import React, { useState as useLocal } from 'react';\nuseLocal(React);\n",
+            root,
+            &default_only,
+        )
+        .unwrap();
+
+        assert!(
+            module.code.contains(
+                "import __fob_cjs_0 from \"react\"; \
+                 const React = __fob_cjs_0, { \"useState\": useLocal } = __fob_cjs_0;"
+            ),
+            "{}",
+            module.code
+        );
+        assert_eq!(module.bare_imports, vec!["react".to_string()]);
+    }
+
+    #[test]
+    fn test_parse_errors_have_locations() {
+        let temp = project(&[("src/main.ts", "")]);
+        let root = temp.path();
+
+        let Err(Error::Bundler(diagnostics)) = transform_module(
+            &root.join("src/main.ts"),
+            "const ok = 1;\nconst = 2;\n",
+            root,
+            &FxHashSet::default(),
+        ) else {
+            panic!("expected a parse error");
+        };
+
+        assert_eq!(diagnostics[0].line, Some(2));
+        assert!(diagnostics[0].file.as_deref().unwrap().ends_with("main.ts"));
+    }
+
+    #[test]
+    fn test_scan_dependencies_follows_local_imports() {
+        let temp = project(&[
+            ("src/main.ts", "import './a';\nimport 'lit';\n"),
+            (
+                "src/a.ts",
+                "import { b } from './b';\nimport 'lit/decorators.js';\nb();\n",
+            ),
+            ("src/b.js", "export { b } from 'shared';\n"),
+        ]);
+
+        let dependencies = scan_dependencies(&[PathBuf::from("src/main.ts")], temp.path()).unwrap();
+
+        assert_eq!(
+            dependencies.into_iter().collect::<Vec<_>>(),
+            vec!["lit", "lit/decorators.js", "shared"]
+        );
+    }
+
+    #[test]
+    fn test_module_url() {
+        let root = Path::new("/project");
+        assert_eq!(
+            module_url(Path::new("/project/src/main.ts"), root).as_deref(),
+            Some("/src/main.ts")
+        );
+        assert_eq!(module_url(Path::new("/elsewhere/main.ts"), root), None);
+    }
+}
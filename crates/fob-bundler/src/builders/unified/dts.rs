@@ -17,4 +17,10 @@ pub struct DtsOptions {
 
     /// Generate .d.ts.map source maps (default: false).
     pub sourcemap: bool,
+
+    /// Flatten each entry's declarations into a single .d.ts (default: false).
+    ///
+    /// Imports between the entry's modules are resolved and inlined, so
+    /// consumers get one self-contained declaration file per entry.
+    pub bundle: bool,
 }
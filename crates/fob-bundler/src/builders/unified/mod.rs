@@ -59,9 +59,11 @@ use crate::Result;
 pub use dts::DtsOptions;
 pub use entry::EntryPoints;
 pub use minify::MinifyLevel;
-pub use options::BuildOptions;
+pub use options::{BuildOptions, DEFAULT_SLOW_MODULE_THRESHOLD, MAX_ENTRY_POINTS};
 pub use output::{BuildOutput, BuildResult};
-pub use primitives::{CodeSplittingConfig, EntryMode, ExternalConfig, IncrementalConfig};
+pub use primitives::{
+    CodeSplittingConfig, EntryMode, ExternalConfig, IncrementalConfig, ManualChunks, OutputTarget,
+};
 
 /// Execute a build with the given options.
 ///
@@ -1,12 +1,22 @@
-use crate::{Error, OutputFormat, Platform, Result, Runtime};
+use crate::{Error, ExportConditions, OutputFormat, Platform, Result, Runtime, SharedPluginable};
 use rustc_hash::FxHashMap;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
+use std::time::Duration;
 
 #[cfg(feature = "dts-generation")]
 use super::dts::DtsOptions;
 use super::entry::EntryPoints;
-use super::primitives::{CodeSplittingConfig, EntryMode, ExternalConfig, IncrementalConfig};
+use super::minify::MinifyLevel;
+use super::primitives::{
+    CodeSplittingConfig, EntryMode, ExternalConfig, IncrementalConfig, ManualChunks, OutputTarget,
+};
+use crate::builders::chunk_size::DEFAULT_CHUNK_SIZE_WARNING_LIMIT;
+use crate::builders::naming;
+use crate::builders::output_validation::SyntaxTarget;
+use crate::builders::replace::{self, InjectImport};
+use crate::builders::sourcemap::SourceMapOptions;
+use crate::builders::top_level_await::TopLevelAwait;
 
 /// Configuration options for a build operation.
 ///
@@ -67,6 +77,14 @@ pub struct BuildOptions {
     /// Only applies when `entry_mode` is `Shared`.
     pub code_splitting: Option<CodeSplittingConfig>,
 
+    /// Explicit module-to-chunk assignment.
+    ///
+    /// Applied before the code splitting thresholds, so assigned modules
+    /// always land in their named chunk. Requires `EntryMode::Shared` and
+    /// `outdir`. Builds with manual chunks skip the build cache, since the
+    /// callback can't be part of the cache key.
+    pub manual_chunks: Option<ManualChunks>,
+
     /// External dependency configuration.
     ///
     /// - `None`: Bundle everything (was: BundlingMode::Full)
@@ -88,12 +106,48 @@ pub struct BuildOptions {
     /// Target runtime platform (default: Browser).
     pub platform: Platform,
 
+    /// Export conditions preset for package `exports` resolution.
+    ///
+    /// `None` picks the preset matching `platform`.
+    pub export_conditions: Option<ExportConditions>,
+
+    /// Extra export conditions (e.g. `"worker"`, `"deno"`), resolved ahead
+    /// of the preset.
+    pub custom_conditions: Vec<String>,
+
     /// Output module format (default: ESM).
     pub format: OutputFormat,
 
+    /// Render the build once per target instead of once in `format`.
+    ///
+    /// Each target becomes a bundle in `BuildOutput::Multiple`, keyed and
+    /// written by its name. Requires `EntryMode::Shared` and no `outfile`.
+    pub outputs: Vec<OutputTarget>,
+
     /// Source map generation strategy (default: external file).
     pub sourcemap: Option<crate::SourceMapType>,
 
+    /// `sourcesContent`, `sourceRoot` and path sanitization for generated
+    /// source maps. Ignored when `sourcemap` is `None`.
+    pub sourcemap_options: SourceMapOptions,
+
+    /// File name template for entry chunks, relative to the output directory
+    /// (default: Rolldown's `[name].js`).
+    ///
+    /// Supports `[name]`, `[hash]`, `[hash:N]`, `[format]`, `[ext]` and `[extname]`.
+    pub entry_names: Option<String>,
+
+    /// File name template for shared and dynamically imported chunks
+    /// (default: Rolldown's `[name]-[hash].js`). Same placeholders as `entry_names`.
+    pub chunk_names: Option<String>,
+
+    /// File name template for emitted assets
+    /// (default: Rolldown's `assets/[name]-[hash][extname]`).
+    ///
+    /// Besides the chunk placeholders, `[dir]` expands to the directory of the
+    /// source file relative to the project root.
+    pub asset_names: Option<String>,
+
     /// Minification level as a string (default: None/disabled).
     ///
     /// Valid values: "none", "whitespace", "syntax", "identifiers"
@@ -111,6 +165,27 @@ pub struct BuildOptions {
     /// Maps virtual paths to their content. Useful for programmatic entry points.
     pub virtual_files: FxHashMap<String, String>,
 
+    /// Build-time values exposed through the `fob:env` virtual module.
+    ///
+    /// Keys that are valid identifiers become named exports; all keys are
+    /// available on the module's default export.
+    pub env: FxHashMap<String, String>,
+
+    /// Globals replaced with a JavaScript expression at build time.
+    ///
+    /// Keys are an identifier or a dotted member path such as
+    /// `process.env.NODE_ENV`; values are expression source, e.g.
+    /// `"\"production\""`. Only references the module doesn't shadow are
+    /// replaced, never strings, comments or property names.
+    pub replace: FxHashMap<String, String>,
+
+    /// Globals imported from a module wherever they are used.
+    ///
+    /// Keys are matched like `replace` keys; e.g. `Buffer` mapped to
+    /// `InjectImport::named("buffer", "Buffer")` adds
+    /// `import { Buffer } from "buffer"` to modules using `Buffer`.
+    pub inject: FxHashMap<String, InjectImport>,
+
     /// Path aliases for import resolution (e.g., "@" → "src").
     ///
     /// Maps alias prefixes to their target directories. These are resolved
@@ -165,8 +240,48 @@ pub struct BuildOptions {
     ///
     /// Ignored on WASM (builds are always sequential).
     pub max_parallel_builds: Option<usize>,
+
+    /// Report modules whose load + transform time exceeds this threshold.
+    ///
+    /// Each slow module produces a warning naming the plugin that consumed
+    /// the most time. Defaults to [`DEFAULT_SLOW_MODULE_THRESHOLD`]; `None`
+    /// disables timing. Ignored on WASM.
+    pub slow_module_threshold: Option<Duration>,
+
+    /// Warn about output chunks larger than this many bytes.
+    ///
+    /// Warnings list the chunk's largest modules and suggest modules that
+    /// would make good dynamic-import boundaries. Defaults to
+    /// [`DEFAULT_CHUNK_SIZE_WARNING_LIMIT`]; `None` disables the check.
+    pub chunk_size_warning_limit: Option<usize>,
+
+    /// Re-parse every emitted JS chunk and fail if it is invalid or uses
+    /// syntax newer than this target.
+    ///
+    /// Disabled (`None`) by default. Intended for CI, where catching a broken
+    /// plugin combination is worth the extra parse.
+    pub output_validation: Option<SyntaxTarget>,
+
+    /// How modules using top-level await are chunked (default: preserve).
+    ///
+    /// Top-level await only works in ESM output; CJS and IIFE builds that
+    /// contain it fail with a diagnostic per module.
+    pub top_level_await: TopLevelAwait,
+
+    /// Additional Rolldown plugins, run in the transform phase in the order
+    /// they were added.
+    ///
+    /// Builds with plugins skip the build cache, since plugin behavior
+    /// can't be part of the cache key.
+    pub plugins: Vec<SharedPluginable>,
 }
 
+/// Default threshold for slow-module warnings (1 second).
+pub const DEFAULT_SLOW_MODULE_THRESHOLD: Duration = Duration::from_millis(1000);
+
+/// Maximum number of entry points in a single build (DoS protection).
+pub const MAX_ENTRY_POINTS: usize = 1000;
+
 impl BuildOptions {
     /// Create a new BuildOptions with a single entry point.
     ///
@@ -193,15 +308,26 @@ impl BuildOptions {
             entry: EntryPoints::Single(normalize_entry_path(entry)),
             entry_mode: EntryMode::Shared,
             code_splitting: None,
+            manual_chunks: None,
+            outputs: Vec::new(),
             external: ExternalConfig::None,
             outdir: None,
             outfile: None,
             platform: Platform::Browser,
+            export_conditions: None,
+            custom_conditions: Vec::new(),
             format: OutputFormat::Esm,
             sourcemap: Some(crate::SourceMapType::File),
+            sourcemap_options: SourceMapOptions::default(),
+            entry_names: None,
+            chunk_names: None,
+            asset_names: None,
             minify_level: None,
             globals: FxHashMap::default(),
             virtual_files: FxHashMap::default(),
+            env: FxHashMap::default(),
+            replace: FxHashMap::default(),
+            inject: FxHashMap::default(),
             path_aliases: FxHashMap::default(),
             cwd: None,
             runtime: None,
@@ -211,6 +337,11 @@ impl BuildOptions {
             cache: None,
             incremental: None,
             max_parallel_builds: None,
+            slow_module_threshold: Some(DEFAULT_SLOW_MODULE_THRESHOLD),
+            chunk_size_warning_limit: Some(DEFAULT_CHUNK_SIZE_WARNING_LIMIT),
+            output_validation: None,
+            top_level_await: TopLevelAwait::Preserve,
+            plugins: Vec::new(),
         }
     }
 
@@ -249,15 +380,26 @@ impl BuildOptions {
             entry: EntryPoints::Multiple(normalized),
             entry_mode: EntryMode::Isolated,
             code_splitting: None,
+            manual_chunks: None,
+            outputs: Vec::new(),
             external: ExternalConfig::None,
             outdir: None,
             outfile: None,
             platform: Platform::Browser,
+            export_conditions: None,
+            custom_conditions: Vec::new(),
             format: OutputFormat::Esm,
             sourcemap: Some(crate::SourceMapType::File),
+            sourcemap_options: SourceMapOptions::default(),
+            entry_names: None,
+            chunk_names: None,
+            asset_names: None,
             minify_level: None,
             globals: FxHashMap::default(),
             virtual_files: FxHashMap::default(),
+            env: FxHashMap::default(),
+            replace: FxHashMap::default(),
+            inject: FxHashMap::default(),
             path_aliases: FxHashMap::default(),
             cwd: None,
             runtime: None,
@@ -267,6 +409,11 @@ impl BuildOptions {
             cache: None,
             incremental: None,
             max_parallel_builds: None,
+            slow_module_threshold: Some(DEFAULT_SLOW_MODULE_THRESHOLD),
+            chunk_size_warning_limit: Some(DEFAULT_CHUNK_SIZE_WARNING_LIMIT),
+            output_validation: None,
+            top_level_await: TopLevelAwait::Preserve,
+            plugins: Vec::new(),
         }
     }
 
@@ -347,6 +494,56 @@ impl BuildOptions {
         self
     }
 
+    /// Assign modules to named chunks.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use fob_bundler::{BuildOptions, ManualChunks};
+    ///
+    /// # async fn example() -> fob_bundler::Result<()> {
+    /// let result = BuildOptions::new_multiple(["src/home.tsx", "src/about.tsx"])
+    ///     .bundle_together()
+    ///     .outdir("dist")
+    ///     .manual_chunks(ManualChunks::new(|id| {
+    ///         id.contains("/node_modules/").then(|| "vendor".to_string())
+    ///     }))
+    ///     .build()
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn manual_chunks(mut self, manual_chunks: ManualChunks) -> Self {
+        self.manual_chunks = Some(manual_chunks);
+        self
+    }
+
+    /// Add an output target, rendering the same modules in another format.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use fob_bundler::{BuildOptions, OutputFormat, OutputTarget};
+    ///
+    /// # async fn example() -> fob_bundler::Result<()> {
+    /// let result = BuildOptions::new("src/index.ts")
+    ///     .outdir("dist")
+    ///     .add_output(OutputTarget::new("esm", OutputFormat::Esm))
+    ///     .add_output(OutputTarget::new("cjs", OutputFormat::Cjs))
+    ///     .add_output(OutputTarget::new("cdn", OutputFormat::Iife).minify_level("identifiers"))
+    ///     .build()
+    ///     .await?;
+    ///
+    /// // dist/esm/index.js, dist/cjs/index.js, dist/cdn/index.js
+    /// result.write_to_force("dist")?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn add_output(mut self, target: OutputTarget) -> Self {
+        self.outputs.push(target);
+        self
+    }
+
     /// Externalize specific packages (explicit list).
     ///
     /// # Examples
@@ -402,6 +599,48 @@ impl BuildOptions {
         self
     }
 
+    /// Set the export conditions preset, overriding the platform default.
+    pub fn export_conditions(mut self, conditions: ExportConditions) -> Self {
+        self.export_conditions = Some(conditions);
+        self
+    }
+
+    /// Add a custom export condition.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use fob_bundler::BuildOptions;
+    ///
+    /// let opts = BuildOptions::new("src/index.ts")
+    ///     .condition("worker")
+    ///     .condition("my-framework");
+    /// ```
+    pub fn condition(mut self, condition: impl Into<String>) -> Self {
+        self.custom_conditions.push(condition.into());
+        self
+    }
+
+    /// Add several custom export conditions.
+    pub fn conditions<I, S>(mut self, conditions: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.custom_conditions
+            .extend(conditions.into_iter().map(Into::into));
+        self
+    }
+
+    /// Export conditions used for resolution, custom ones first.
+    pub fn resolved_conditions(&self) -> Vec<String> {
+        let preset = self.export_conditions.unwrap_or(match self.platform {
+            Platform::Node => ExportConditions::node(),
+            _ => ExportConditions::browser(),
+        });
+        preset.with_custom(&self.custom_conditions)
+    }
+
     /// Set the output format.
     pub fn format(mut self, format: OutputFormat) -> Self {
         self.format = format;
@@ -430,6 +669,55 @@ impl BuildOptions {
         self
     }
 
+    /// Set the source map mode explicitly (`None` disables source maps).
+    pub fn sourcemap_type(mut self, sourcemap: Option<crate::SourceMapType>) -> Self {
+        self.sourcemap = sourcemap;
+        self
+    }
+
+    /// Replace all source map quality options.
+    pub fn sourcemap_options(mut self, options: SourceMapOptions) -> Self {
+        self.sourcemap_options = options;
+        self
+    }
+
+    /// Include original sources in `sourcesContent` (default: true).
+    pub fn sourcemap_sources_content(mut self, include: bool) -> Self {
+        self.sourcemap_options.sources_content = include;
+        self
+    }
+
+    /// Set the `sourceRoot` of generated source maps.
+    pub fn sourcemap_source_root(mut self, root: impl Into<String>) -> Self {
+        self.sourcemap_options.source_root = Some(root.into());
+        self
+    }
+
+    /// Rewrite source paths outside the project so maps don't leak
+    /// absolute paths of the build machine.
+    pub fn sourcemap_sanitize_paths(mut self, sanitize: bool) -> Self {
+        self.sourcemap_options.sanitize_paths = sanitize;
+        self
+    }
+
+    /// Set the file name template for entry chunks, e.g. `[name]-[hash].js`.
+    pub fn entry_names(mut self, template: impl Into<String>) -> Self {
+        self.entry_names = Some(template.into());
+        self
+    }
+
+    /// Set the file name template for shared chunks, e.g. `chunks/[name]-[hash].js`.
+    pub fn chunk_names(mut self, template: impl Into<String>) -> Self {
+        self.chunk_names = Some(template.into());
+        self
+    }
+
+    /// Set the file name template for assets, e.g. `assets/[dir]/[name][extname]`.
+    pub fn asset_names(mut self, template: impl Into<String>) -> Self {
+        self.asset_names = Some(template.into());
+        self
+    }
+
     /// Set the minification level.
     ///
     /// # Supported Values
@@ -462,6 +750,70 @@ impl BuildOptions {
         self
     }
 
+    /// Expose a value through the `fob:env` virtual module.
+    pub fn env(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.env.insert(key.into(), value.into());
+        self
+    }
+
+    /// Expose multiple values through the `fob:env` virtual module.
+    pub fn env_map<I, K, V>(mut self, entries: I) -> Self
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: Into<String>,
+        V: Into<String>,
+    {
+        for (k, v) in entries {
+            self.env.insert(k.into(), v.into());
+        }
+        self
+    }
+
+    /// Replace a global with a JavaScript expression at build time.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use fob_bundler::BuildOptions;
+    ///
+    /// let opts = BuildOptions::new("src/index.ts")
+    ///     .replace("process.env.NODE_ENV", "\"production\"")
+    ///     .replace("__DEV__", "false");
+    /// ```
+    pub fn replace(mut self, target: impl Into<String>, replacement: impl Into<String>) -> Self {
+        self.replace.insert(target.into(), replacement.into());
+        self
+    }
+
+    /// Replace multiple globals at once.
+    pub fn replace_map<I, K, V>(mut self, entries: I) -> Self
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: Into<String>,
+        V: Into<String>,
+    {
+        for (k, v) in entries {
+            self.replace.insert(k.into(), v.into());
+        }
+        self
+    }
+
+    /// Import a global from a module in every module that uses it.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use fob_bundler::{BuildOptions, InjectImport};
+    ///
+    /// let opts = BuildOptions::new("src/index.ts")
+    ///     .inject("Buffer", InjectImport::named("buffer", "Buffer"))
+    ///     .inject("process", InjectImport::default_export("process/browser"));
+    /// ```
+    pub fn inject(mut self, target: impl Into<String>, import: InjectImport) -> Self {
+        self.inject.insert(target.into(), import);
+        self
+    }
+
     /// Add a path alias for import resolution.
     pub fn path_alias(mut self, alias: impl Into<String>, target: impl Into<String>) -> Self {
         self.path_aliases.insert(alias.into(), target.into());
@@ -486,6 +838,25 @@ impl BuildOptions {
         self
     }
 
+    /// Add a Rolldown plugin to the build.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use fob_bundler::builtins::CssPlugin;
+    /// use fob_bundler::{BuildOptions, Runtime};
+    /// use std::sync::Arc;
+    ///
+    /// # fn example(runtime: Arc<dyn Runtime>) {
+    /// let opts = BuildOptions::new("src/index.js")
+    ///     .plugin(Arc::new(CssPlugin::new(runtime)));
+    /// # }
+    /// ```
+    pub fn plugin(mut self, plugin: SharedPluginable) -> Self {
+        self.plugins.push(plugin);
+        self
+    }
+
     /// Enable modern decorator transformation.
     pub fn decorators(mut self, enabled: bool) -> Self {
         if enabled {
@@ -535,6 +906,15 @@ impl BuildOptions {
         self
     }
 
+    /// Bundle each entry's declarations into a single .d.ts file.
+    #[cfg(feature = "dts-generation")]
+    pub fn bundle_dts(mut self, enabled: bool) -> Self {
+        let mut dts = self.dts.unwrap_or_default();
+        dts.bundle = enabled;
+        self.dts = Some(dts);
+        self
+    }
+
     /// Enable build caching with the specified directory.
     ///
     /// # Examples
@@ -664,6 +1044,51 @@ impl BuildOptions {
         self
     }
 
+    /// Set the threshold for slow-module warnings.
+    ///
+    /// Any module whose load + transform time exceeds `threshold` is reported
+    /// as a warning in [`BuildResult::analysis`](crate::BuildResult::analysis),
+    /// naming the plugin that consumed the most time.
+    pub fn slow_module_threshold(mut self, threshold: Duration) -> Self {
+        self.slow_module_threshold = Some(threshold);
+        self
+    }
+
+    /// Disable slow-module timing and warnings.
+    pub fn disable_slow_module_warnings(mut self) -> Self {
+        self.slow_module_threshold = None;
+        self
+    }
+
+    /// Set the size (in bytes) above which output chunks produce a warning.
+    pub fn chunk_size_warning_limit(mut self, bytes: usize) -> Self {
+        self.chunk_size_warning_limit = Some(bytes);
+        self
+    }
+
+    /// Disable chunk size warnings.
+    pub fn disable_chunk_size_warnings(mut self) -> Self {
+        self.chunk_size_warning_limit = None;
+        self
+    }
+
+    /// Validate emitted chunks against a syntax target after bundling.
+    ///
+    /// Use [`SyntaxTarget::EsNext`] to only check that the output parses.
+    pub fn validate_output(mut self, target: SyntaxTarget) -> Self {
+        self.output_validation = Some(target);
+        self
+    }
+
+    /// Choose how modules using top-level await are chunked.
+    ///
+    /// [`TopLevelAwait::Isolate`] gives each such module its own chunk, so
+    /// only its importers wait on it. Requires ESM output.
+    pub fn top_level_await(mut self, strategy: TopLevelAwait) -> Self {
+        self.top_level_await = strategy;
+        self
+    }
+
     /// Validate the build options for internal consistency.
     ///
     /// # Errors
@@ -671,7 +1096,6 @@ impl BuildOptions {
     /// Returns an error if the configuration is invalid.
     pub fn validate(&self) -> Result<()> {
         // Entry count validations
-        const MAX_ENTRY_POINTS: usize = 1000;
         let entry_count = match &self.entry {
             EntryPoints::Single(_) => 1,
             EntryPoints::Multiple(v) => v.len(),
@@ -714,6 +1138,14 @@ impl BuildOptions {
                             .into(),
                     ));
                 }
+
+                if self.manual_chunks.is_some() {
+                    return Err(Error::InvalidConfig(
+                        "EntryMode::Isolated cannot use manual chunks (bundles are independent). \
+                         Use EntryMode::Shared for manual chunks."
+                            .into(),
+                    ));
+                }
             }
         }
 
@@ -751,6 +1183,109 @@ impl BuildOptions {
                     "outfile cannot be used with code splitting. Use outdir instead.".into(),
                 ));
             }
+            if self.manual_chunks.is_some() {
+                return Err(Error::InvalidConfig(
+                    "outfile cannot be used with manual chunks. Use outdir instead.".into(),
+                ));
+            }
+        }
+
+        // File name templates
+        if let Some(template) = &self.entry_names {
+            naming::validate_template("entry_names", template, false)?;
+            if self.outfile.is_some() {
+                return Err(Error::InvalidConfig(
+                    "entry_names cannot be used with outfile. Use outdir instead.".into(),
+                ));
+            }
+        }
+        if let Some(template) = &self.chunk_names {
+            naming::validate_template("chunk_names", template, false)?;
+        }
+        if let Some(template) = &self.asset_names {
+            naming::validate_template("asset_names", template, true)?;
+        }
+
+        // Output target validations
+        if !self.outputs.is_empty() {
+            if self.entry_mode == EntryMode::Isolated {
+                return Err(Error::InvalidConfig(
+                    "EntryMode::Isolated cannot be combined with output targets. \
+                     Use EntryMode::Shared instead."
+                        .into(),
+                ));
+            }
+            if self.outfile.is_some() {
+                return Err(Error::InvalidConfig(
+                    "outfile cannot be used with output targets. Use outdir instead.".into(),
+                ));
+            }
+
+            let mut names = std::collections::HashSet::new();
+            for target in &self.outputs {
+                if target.name.is_empty()
+                    || target.name == "."
+                    || target.name == ".."
+                    || target.name.contains(['/', '\\'])
+                {
+                    return Err(Error::InvalidConfig(format!(
+                        "Invalid output target name '{}': must be a single directory name",
+                        target.name
+                    )));
+                }
+                if !names.insert(target.name.as_str()) {
+                    return Err(Error::InvalidConfig(format!(
+                        "Duplicate output target name '{}'",
+                        target.name
+                    )));
+                }
+                if let Some(level) = &target.minify_level {
+                    MinifyLevel::parse(level)?;
+                }
+                if let Some(template) = &target.entry_names {
+                    naming::validate_template("entry_names", template, false)?;
+                }
+                if let Some(template) = &target.chunk_names {
+                    naming::validate_template("chunk_names", template, false)?;
+                }
+            }
+        }
+
+        // Isolating top-level await modules needs chunks, and ESM to load them
+        if self.top_level_await == TopLevelAwait::Isolate {
+            if self.outfile.is_some() {
+                return Err(Error::InvalidConfig(
+                    "outfile cannot be used with TopLevelAwait::Isolate. Use outdir instead."
+                        .into(),
+                ));
+            }
+            let non_esm = if self.outputs.is_empty() {
+                (!matches!(self.format, OutputFormat::Esm)).then_some(self.format)
+            } else {
+                self.outputs
+                    .iter()
+                    .map(|target| target.format)
+                    .find(|format| !matches!(format, OutputFormat::Esm))
+            };
+            if let Some(format) = non_esm {
+                return Err(Error::InvalidConfig(format!(
+                    "TopLevelAwait::Isolate requires ESM output, but the build emits {}",
+                    format
+                )));
+            }
+        }
+
+        replace::validate(&self.replace, &self.inject)?;
+
+        if let Some(condition) = self
+            .custom_conditions
+            .iter()
+            .find(|condition| condition.trim().is_empty())
+        {
+            return Err(Error::InvalidConfig(format!(
+                "Invalid export condition '{}': must not be empty",
+                condition
+            )));
         }
 
         // outdir and outfile are mutually exclusive
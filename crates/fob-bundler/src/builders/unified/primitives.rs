@@ -6,10 +6,17 @@
 //! 2. [`CodeSplittingConfig`] - Code splitting configuration (Option = on/off)
 //! 3. [`ExternalConfig`] - External dependencies (None, List, FromManifest)
 //! 4. [`IncrementalConfig`] - Incremental module graph caching (optional)
+//! 5. [`ManualChunks`] - Explicit module-to-chunk assignment (optional)
+//! 6. [`OutputTarget`] - Additional formats rendered from the same build (optional)
 //!
 //! These primitives can be composed explicitly to achieve any valid build configuration.
 
+use std::future::Future;
 use std::path::PathBuf;
+use std::pin::Pin;
+use std::sync::Arc;
+
+use rustc_hash::FxHashMap;
 
 /// Controls whether entry points share code or are isolated.
 ///
@@ -211,3 +218,136 @@ impl std::fmt::Display for IncrementalConfig {
         write!(f, "incremental({})", self.cache_dir.display())
     }
 }
+
+/// Assigns modules to named chunks, like Rollup's `manualChunks`.
+///
+/// The callback receives a module id (an absolute path for files) and
+/// returns the name of the chunk to place it in, or `None` to leave it to
+/// the default chunking. Modules assigned the same name share a chunk.
+#[derive(Clone)]
+pub struct ManualChunks(Arc<dyn Fn(&str) -> Option<String> + Send + Sync>);
+
+impl ManualChunks {
+    /// Create from a callback mapping module ids to chunk names.
+    pub fn new(f: impl Fn(&str) -> Option<String> + Send + Sync + 'static) -> Self {
+        Self(Arc::new(f))
+    }
+
+    /// Place the modules of each suggested group in a chunk named after it.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use fob_bundler::{Analyzer, BuildOptions, ChunkGroupOptions, ManualChunks};
+    ///
+    /// # async fn example() -> fob_bundler::Result<()> {
+    /// let entries = ["src/home.tsx", "src/about.tsx"];
+    /// let analysis = Analyzer::new().entries(entries).analyze().await?;
+    /// let groups = analysis
+    ///     .graph
+    ///     .suggest_chunk_groups(&ChunkGroupOptions::default())?;
+    ///
+    /// let result = BuildOptions::new_multiple(entries)
+    ///     .bundle_together()
+    ///     .outdir("dist")
+    ///     .manual_chunks(ManualChunks::from_groups(&groups))
+    ///     .build()
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn from_groups(groups: &[fob_graph::ChunkGroupSuggestion]) -> Self {
+        let chunks: FxHashMap<String, String> = groups
+            .iter()
+            .flat_map(|group| {
+                group
+                    .modules
+                    .iter()
+                    .map(|id| (id.path_string().into_owned(), group.name.clone()))
+            })
+            .collect();
+        Self::new(move |id| chunks.get(id).cloned())
+    }
+
+    /// Chunk name for a module, if the callback assigns one.
+    pub fn chunk_for(&self, module_id: &str) -> Option<String> {
+        (self.0)(module_id)
+    }
+
+    /// Convert to a Rolldown chunk group that takes precedence over the others.
+    pub(crate) fn to_rolldown_group(&self) -> rolldown::MatchGroup {
+        let f = Arc::clone(&self.0);
+        let name = move |module_id: &str, _: &rolldown::ChunkingContext| {
+            let name = f(module_id);
+            Box::pin(async move { Ok(name) })
+                as Pin<Box<dyn Future<Output = anyhow::Result<Option<String>>> + Send>>
+        };
+        rolldown::MatchGroup {
+            name: rolldown::MatchGroupName::Dynamic(Arc::new(name)),
+            priority: Some(u32::MAX),
+            ..Default::default()
+        }
+    }
+}
+
+impl std::fmt::Debug for ManualChunks {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("ManualChunks(<fn>)")
+    }
+}
+
+/// One output of a build that renders the same modules in several formats.
+///
+/// Targets share everything that affects which modules are bundled (entries,
+/// resolution, plugins, externals) and only override render options. Each
+/// target's bundle is keyed by its name in
+/// [`BuildOutput::Multiple`](super::BuildOutput::Multiple), so writing the
+/// result puts it in a subdirectory of the same name.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OutputTarget {
+    /// Subdirectory and bundle key, e.g. `"esm"`.
+    pub name: String,
+
+    /// Module format of this output.
+    pub format: crate::OutputFormat,
+
+    /// Minification level, overriding the build's when set.
+    pub minify_level: Option<String>,
+
+    /// Entry file name template, overriding the build's when set.
+    pub entry_names: Option<String>,
+
+    /// Chunk file name template, overriding the build's when set.
+    pub chunk_names: Option<String>,
+}
+
+impl OutputTarget {
+    /// Create a target that inherits every render option but the format.
+    pub fn new(name: impl Into<String>, format: crate::OutputFormat) -> Self {
+        Self {
+            name: name.into(),
+            format,
+            minify_level: None,
+            entry_names: None,
+            chunk_names: None,
+        }
+    }
+
+    /// Set the minification level for this output.
+    pub fn minify_level(mut self, level: impl Into<String>) -> Self {
+        self.minify_level = Some(level.into());
+        self
+    }
+
+    /// Set the entry file name template for this output.
+    pub fn entry_names(mut self, template: impl Into<String>) -> Self {
+        self.entry_names = Some(template.into());
+        self
+    }
+
+    /// Set the chunk file name template for this output.
+    pub fn chunk_names(mut self, template: impl Into<String>) -> Self {
+        self.chunk_names = Some(template.into());
+        self
+    }
+}
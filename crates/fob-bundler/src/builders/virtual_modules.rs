@@ -0,0 +1,339 @@
+//! Built-in virtual helper modules under the `fob:` namespace
+//!
+//! These modules are always available to user code without any configuration:
+//!
+//! - `fob:env` - Build-time environment values from [`BuildOptions::env`], exported
+//!   as named constants (for valid identifiers) and as a default object
+//! - `fob:manifest` - The build manifest at runtime, plus helpers to look up entry
+//!   files and the chunks to preload for them
+//! - `fob:assets/<path>` - The final (hashed) URL of an asset, resolved relative
+//!   to the project root
+//!
+//! `fob:content/<glob>` collections are left to the MDX plugin, which serves them.
+//!
+//! ```javascript
+//! import { API_URL } from 'fob:env';
+//! import manifest, { entryFile, preloadFiles } from 'fob:manifest';
+//! import logoUrl from 'fob:assets/images/logo.png';
+//! ```
+//!
+//! `fob:assets/*` modules expand to `new URL(..., import.meta.url)` so the
+//! asset detection plugin emits and rewrites them like any other asset
+//! reference. `fob:manifest` reads `globalThis.__FOB_MANIFEST__`, which the
+//! host page sets from [`BundleManifest::to_inline_script`].
+//!
+//! TypeScript declarations for all modules are available as [`TYPE_DECLARATIONS`].
+//!
+//! [`BuildOptions::env`]: crate::BuildOptions::env
+//! [`BundleManifest::to_inline_script`]: crate::output::BundleManifest::to_inline_script
+
+use crate::plugins::{FobPlugin, PluginPhase};
+use rolldown_common::{ModuleType, ResolvedExternal};
+use rolldown_plugin::{
+    HookLoadArgs, HookLoadOutput, HookLoadReturn, HookResolveIdArgs, HookResolveIdOutput,
+    HookResolveIdReturn, HookUsage, Plugin, PluginContext,
+};
+use rustc_hash::FxHashMap;
+use std::borrow::Cow;
+use std::path::{Path, PathBuf};
+
+/// Specifier prefix for all fob virtual modules.
+pub const NAMESPACE: &str = "fob:";
+
+/// Global variable `fob:manifest` reads the manifest from.
+pub const MANIFEST_GLOBAL: &str = "__FOB_MANIFEST__";
+
+/// `fob:` module prefix served by the MDX plugin rather than this one.
+const CONTENT_MODULES: &str = "content/";
+
+/// Prefix for resolved ids (`\0` marks them as virtual for other plugins).
+const RESOLVED_PREFIX: &str = "\0fob:";
+
+/// TypeScript declarations for the `fob:` virtual modules.
+///
+/// Write this to a `fob-env.d.ts` file (or reference it from `tsconfig.json`)
+/// to get type checking for imports from `fob:env`, `fob:manifest`,
+/// `fob:assets/*` and the MDX plugin's `fob:content/*`.
+pub const TYPE_DECLARATIONS: &str = r#"declare module 'fob:env' {
+  const env: Readonly<Record<string, string>>;
+  export default env;
+  export const MODE: string | undefined;
+  export const NODE_ENV: string | undefined;
+}
+
+declare module 'fob:manifest' {
+  export interface ChunkMetadata {
+    file: string;
+    imports: string[];
+    dynamicImports: string[];
+    css: string[];
+    modules: string[];
+  }
+
+  export interface BundleManifest {
+    entries: Record<string, string>;
+    chunks: Record<string, ChunkMetadata>;
+    version: string;
+  }
+
+  const manifest: BundleManifest;
+  export default manifest;
+
+  /** Output file for a named entry, if present. */
+  export function entryFile(name: string): string | undefined;
+
+  /** Output file plus all statically imported chunks, in load order. */
+  export function preloadFiles(name: string): string[];
+}
+
+declare module 'fob:assets/*' {
+  const url: string;
+  export default url;
+}
+
+declare module 'fob:content/*' {
+  export interface ContentEntry {
+    slug: string;
+    frontmatter: Record<string, unknown>;
+    Component: () => Promise<{ default: any }>;
+  }
+
+  const entries: ContentEntry[];
+  export default entries;
+}
+"#;
+
+/// Plugin that resolves and loads the `fob:` virtual modules.
+#[derive(Debug, Clone)]
+pub struct VirtualModulesPlugin {
+    /// Values exposed through `fob:env`
+    env: FxHashMap<String, String>,
+    /// Project root used to resolve `fob:assets/*`
+    cwd: PathBuf,
+}
+
+impl VirtualModulesPlugin {
+    /// Create a new plugin serving the given environment values.
+    pub fn new(env: FxHashMap<String, String>, cwd: impl Into<PathBuf>) -> Self {
+        Self {
+            env,
+            cwd: cwd.into(),
+        }
+    }
+
+    /// Generate the source for a resolved virtual module name (without prefix).
+    fn generate(&self, name: &str) -> Option<String> {
+        match name {
+            "env" => Some(generate_env_module(&self.env)),
+            "manifest" => Some(generate_manifest_module()),
+            _ => name
+                .strip_prefix("assets/")
+                .map(|asset| generate_asset_module(&self.cwd, asset)),
+        }
+    }
+}
+
+/// Check whether a `fob:` module name is one this plugin provides.
+fn is_known_module(name: &str) -> bool {
+    matches!(name, "env" | "manifest")
+        || name
+            .strip_prefix("assets/")
+            .is_some_and(|asset| !asset.is_empty())
+}
+
+impl Plugin for VirtualModulesPlugin {
+    fn name(&self) -> Cow<'static, str> {
+        "fob-virtual-modules".into()
+    }
+
+    fn register_hook_usage(&self) -> HookUsage {
+        HookUsage::ResolveId | HookUsage::Load
+    }
+
+    fn resolve_id(
+        &self,
+        _ctx: &PluginContext,
+        args: &HookResolveIdArgs,
+    ) -> impl std::future::Future<Output = HookResolveIdReturn> + Send {
+        let specifier = args.specifier.to_string();
+
+        async move {
+            let Some(name) = specifier.strip_prefix(NAMESPACE) else {
+                return Ok(None);
+            };
+            if name.starts_with(CONTENT_MODULES) {
+                return Ok(None);
+            }
+
+            if !is_known_module(name) {
+                return Err(anyhow::anyhow!(
+                    "Unknown virtual module '{}'. Available: fob:env, fob:manifest, fob:assets/<path>",
+                    specifier
+                ));
+            }
+
+            Ok(Some(HookResolveIdOutput {
+                id: format!("{}{}", RESOLVED_PREFIX, name).into(),
+                external: Some(ResolvedExternal::Bool(false)),
+                ..Default::default()
+            }))
+        }
+    }
+
+    fn load(
+        &self,
+        _ctx: &PluginContext,
+        args: &HookLoadArgs<'_>,
+    ) -> impl std::future::Future<Output = HookLoadReturn> + Send {
+        let code = args
+            .id
+            .strip_prefix(RESOLVED_PREFIX)
+            .and_then(|name| self.generate(name));
+
+        async move {
+            Ok(code.map(|code| HookLoadOutput {
+                code: code.into(),
+                module_type: Some(ModuleType::Js),
+                ..Default::default()
+            }))
+        }
+    }
+}
+
+impl FobPlugin for VirtualModulesPlugin {
+    fn phase(&self) -> PluginPhase {
+        PluginPhase::Virtual
+    }
+}
+
+/// Generate `fob:env`: named exports for identifier keys plus a default object.
+fn generate_env_module(env: &FxHashMap<String, String>) -> String {
+    let mut keys: Vec<&String> = env.keys().collect();
+    keys.sort();
+
+    let mut code = String::new();
+    let mut entries = Vec::with_capacity(keys.len());
+
+    for key in keys {
+        let value = js_string(&env[key]);
+        if is_js_identifier(key) {
+            code.push_str(&format!("export const {} = {};\n", key, value));
+        }
+        entries.push(format!("  {}: {}", js_string(key), value));
+    }
+
+    code.push_str(&format!(
+        "export default Object.freeze({{\n{}\n}});\n",
+        entries.join(",\n")
+    ));
+    code
+}
+
+/// Generate `fob:manifest`, backed by the manifest global set by the host page.
+fn generate_manifest_module() -> String {
+    format!(
+        r#"const manifest = globalThis.{global} ?? {{ entries: {{}}, chunks: {{}}, version: "" }};
+export default manifest;
+export function entryFile(name) {{
+  return manifest.entries[name];
+}}
+export function preloadFiles(name) {{
+  const seen = new Set();
+  const visit = (file) => {{
+    if (!file || seen.has(file)) return;
+    seen.add(file);
+    const chunk = manifest.chunks[file];
+    if (chunk) chunk.imports.forEach(visit);
+  }};
+  visit(manifest.entries[name]);
+  return [...seen];
+}}
+"#,
+        global = MANIFEST_GLOBAL
+    )
+}
+
+/// Generate `fob:assets/<path>` as a `new URL()` reference the asset plugin rewrites.
+fn generate_asset_module(cwd: &Path, asset: &str) -> String {
+    let path = cwd.join(asset.trim_start_matches('/'));
+    format!(
+        "export default new URL({}, import.meta.url).href;\n",
+        js_string(&path.to_string_lossy())
+    )
+}
+
+/// Encode a string as a JavaScript string literal.
+fn js_string(value: &str) -> String {
+    serde_json::to_string(value).unwrap_or_else(|_| "\"\"".to_string())
+}
+
+/// Check whether a key can be used as a named export.
+fn is_js_identifier(key: &str) -> bool {
+    let mut chars = key.chars();
+    match chars.next() {
+        Some(c) if c.is_ascii_alphabetic() || c == '_' || c == '$' => {}
+        _ => return false,
+    }
+    chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_known_module() {
+        assert!(is_known_module("env"));
+        assert!(is_known_module("manifest"));
+        assert!(is_known_module("assets/logo.png"));
+        assert!(!is_known_module("assets/"));
+        assert!(!is_known_module("unknown"));
+    }
+
+    #[test]
+    fn test_env_module() {
+        let mut env = FxHashMap::default();
+        env.insert("API_URL".to_string(), "https://api.test".to_string());
+        env.insert("my-flag".to_string(), "on".to_string());
+
+        let code = generate_env_module(&env);
+
+        assert!(code.contains("export const API_URL = \"https://api.test\";"));
+        assert!(!code.contains("export const my-flag"));
+        assert!(code.contains("\"my-flag\": \"on\""));
+        assert!(code.contains("export default Object.freeze"));
+    }
+
+    #[test]
+    fn test_env_module_escapes_values() {
+        let mut env = FxHashMap::default();
+        env.insert("QUOTE".to_string(), "a\"b\n".to_string());
+
+        let code = generate_env_module(&env);
+
+        assert!(code.contains(r#"export const QUOTE = "a\"b\n";"#));
+    }
+
+    #[test]
+    fn test_manifest_module_reads_global() {
+        let code = generate_manifest_module();
+        assert!(code.contains("globalThis.__FOB_MANIFEST__"));
+        assert!(code.contains("export function preloadFiles"));
+    }
+
+    #[test]
+    fn test_asset_module_uses_absolute_path() {
+        let code = generate_asset_module(Path::new("/project"), "images/logo.png");
+        assert_eq!(
+            code,
+            "export default new URL(\"/project/images/logo.png\", import.meta.url).href;\n"
+        );
+    }
+
+    #[test]
+    fn test_generate_unknown_returns_none() {
+        let plugin = VirtualModulesPlugin::new(FxHashMap::default(), "/project");
+        assert!(plugin.generate("nope").is_none());
+        assert!(plugin.generate("env").is_some());
+    }
+}
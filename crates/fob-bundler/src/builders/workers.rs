@@ -0,0 +1,472 @@
+//! Worker and worklet bundling
+//!
+//! Browsers load workers from a URL, so the usual pattern is
+//! `new Worker(new URL('./worker.ts', import.meta.url))`. Left alone, the URL
+//! points at a source file that isn't in the output. This plugin finds those
+//! references, bundles each worker into a single self-contained file and
+//! emits it as an asset, rewriting the reference to the emitted file.
+//!
+//! Classic workers run as scripts and get an IIFE bundle; module workers
+//! (`{ type: 'module' }`) and worklets get an ES module. Each worker is built
+//! on its own, so it never imports chunks shared with the page. Service
+//! workers are emitted without a content hash, at the output root.
+//!
+//! Recognized references:
+//!
+//! ```javascript
+//! new Worker(new URL('./worker.ts', import.meta.url), { type: 'module' })
+//! new SharedWorker(new URL('./shared.ts', import.meta.url))
+//! navigator.serviceWorker.register(new URL('./sw.ts', import.meta.url))
+//! CSS.paintWorklet.addModule(new URL('./paint.ts', import.meta.url))
+//! ```
+//!
+//! Workers referenced from inside a worker are left as they are.
+
+use oxc_allocator::Allocator;
+use oxc_ast::ast::{Argument, CallExpression, Expression, NewExpression, ObjectPropertyKind};
+use oxc_ast_visit::{Visit, walk};
+use oxc_parser::Parser;
+use oxc_span::{GetSpan, SourceType, Span};
+use parking_lot::Mutex;
+use path_clean::PathClean;
+use rolldown::{BundlerBuilder, BundlerOptions, InputItem, OutputFormat};
+use rolldown_common::{EmittedAsset, ModuleType, Output};
+use rolldown_plugin::__inner::SharedPluginable;
+use rolldown_plugin::{
+    HookTransformArgs, HookTransformOutput, HookTransformReturn, HookUsage, Plugin,
+    SharedTransformPluginContext,
+};
+use rustc_hash::FxHashMap;
+use std::borrow::Cow;
+use std::path::Path;
+
+/// What kind of global loads the worker.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum WorkerKind {
+    Worker,
+    SharedWorker,
+    ServiceWorker,
+    Worklet,
+}
+
+/// A `new URL(..., import.meta.url)` passed to a worker constructor.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct WorkerReference {
+    kind: WorkerKind,
+    /// Whether the worker runs as an ES module
+    module: bool,
+    specifier: String,
+    /// Span of the `new URL(...)` expression
+    span: Span,
+}
+
+/// Bundles workers referenced from a module and rewrites their URLs.
+pub(crate) struct WorkerPlugin {
+    /// Options of the enclosing build, which worker builds inherit
+    options: BundlerOptions,
+    /// Plugins run on worker code
+    plugins: Vec<SharedPluginable>,
+    /// Emitted asset per worker file and format, so shared workers build once
+    emitted: Mutex<FxHashMap<(String, bool), String>>,
+}
+
+impl std::fmt::Debug for WorkerPlugin {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WorkerPlugin")
+            .field("plugins", &self.plugins.len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl WorkerPlugin {
+    /// Create the plugin for a build.
+    ///
+    /// # Arguments
+    ///
+    /// * `options` - Options of the build; worker builds keep its resolution,
+    ///   transform and minify settings
+    /// * `plugins` - Plugins to run on worker code, such as virtual files and
+    ///   user plugins
+    pub(crate) fn new(options: &BundlerOptions, plugins: Vec<SharedPluginable>) -> Self {
+        Self {
+            options: BundlerOptions {
+                cwd: options.cwd.clone(),
+                platform: options.platform,
+                external: options.external.clone(),
+                resolve: options.resolve.clone(),
+                define: options.define.clone(),
+                transform: options.transform.clone(),
+                treeshake: options.treeshake.clone(),
+                minify: options.minify.clone(),
+                module_types: options.module_types.clone(),
+                tsconfig: options.tsconfig.clone(),
+                keep_names: options.keep_names,
+                drop_labels: options.drop_labels.clone(),
+                ..Default::default()
+            },
+            plugins,
+            emitted: Mutex::default(),
+        }
+    }
+
+    /// Bundle one worker into a single file.
+    async fn bundle_worker(&self, input: &str, module: bool) -> anyhow::Result<String> {
+        let options = BundlerOptions {
+            input: Some(vec![InputItem {
+                name: None,
+                import: input.to_string(),
+            }]),
+            format: Some(if module {
+                OutputFormat::Esm
+            } else {
+                OutputFormat::Iife
+            }),
+            inline_dynamic_imports: Some(true),
+            ..self.options.clone()
+        };
+
+        let mut bundler = BundlerBuilder::default()
+            .with_options(options)
+            .with_plugins(self.plugins.clone())
+            .build()
+            .map_err(|e| worker_error(input, &e))?;
+        let output = bundler
+            .generate()
+            .await
+            .map_err(|e| worker_error(input, &e))?;
+
+        output
+            .assets
+            .iter()
+            .find_map(|output| match output {
+                Output::Chunk(chunk) if chunk.is_entry => Some(chunk.code.clone()),
+                _ => None,
+            })
+            .ok_or_else(|| anyhow::anyhow!("Worker build for {} produced no entry chunk", input))
+    }
+}
+
+impl Plugin for WorkerPlugin {
+    fn name(&self) -> Cow<'static, str> {
+        "fob:workers".into()
+    }
+
+    fn register_hook_usage(&self) -> HookUsage {
+        HookUsage::Transform
+    }
+
+    fn transform(
+        &self,
+        ctx: SharedTransformPluginContext,
+        args: &HookTransformArgs<'_>,
+    ) -> impl std::future::Future<Output = HookTransformReturn> + Send {
+        let is_script = matches!(
+            args.module_type,
+            ModuleType::Js | ModuleType::Jsx | ModuleType::Ts | ModuleType::Tsx
+        );
+        // Most modules never mention `import.meta.url`; skip parsing those
+        let references = if is_script && args.code.contains("import.meta.url") {
+            find_worker_references(args.code, args.id)
+        } else {
+            Vec::new()
+        };
+        let code = args.code.to_string();
+        let importer = args.id.to_string();
+
+        async move {
+            if references.is_empty() {
+                return Ok(None);
+            }
+
+            let mut replacements = Vec::with_capacity(references.len());
+            for reference in references {
+                let input = worker_input(&reference.specifier, &importer);
+                let key = (input.clone(), reference.module);
+
+                let cached = self.emitted.lock().get(&key).cloned();
+                let reference_id = match cached {
+                    Some(reference_id) => reference_id,
+                    None => {
+                        let code = self.bundle_worker(&input, reference.module).await?;
+                        let name = Path::new(&input)
+                            .file_stem()
+                            .map(|stem| format!("{}.js", stem.to_string_lossy()));
+                        // A service worker controls the directory it's served
+                        // from, and browsers update it in place, so it keeps a
+                        // stable name at the output root
+                        let file_name = (reference.kind == WorkerKind::ServiceWorker)
+                            .then(|| name.clone())
+                            .flatten()
+                            .map(Into::into);
+                        let reference_id = ctx
+                            .emit_file(
+                                EmittedAsset {
+                                    name,
+                                    original_file_name: Some(input.clone()),
+                                    file_name,
+                                    source: code.into(),
+                                },
+                                None,
+                                None,
+                            )?
+                            .to_string();
+                        self.emitted.lock().insert(key, reference_id.clone());
+                        reference_id
+                    }
+                };
+
+                replacements.push((
+                    reference.span,
+                    format!("new URL(import.meta.ROLLUP_FILE_URL_{})", reference_id),
+                ));
+            }
+
+            Ok(Some(HookTransformOutput {
+                code: Some(apply_replacements(&code, replacements)),
+                map: None,
+                side_effects: None,
+                module_type: None,
+            }))
+        }
+    }
+}
+
+/// Entry for a worker build: relative specifiers resolve against the
+/// importing module, anything else is left to the resolver.
+fn worker_input(specifier: &str, importer: &str) -> String {
+    if !specifier.starts_with('.') {
+        return specifier.to_string();
+    }
+    let base = Path::new(importer).parent().unwrap_or(Path::new(""));
+    base.join(specifier).clean().to_string_lossy().into_owned()
+}
+
+fn worker_error(input: &str, error: &dyn std::fmt::Debug) -> anyhow::Error {
+    anyhow::anyhow!(
+        "Failed to bundle worker {}: {}",
+        input,
+        crate::Error::from_rolldown_batch(error)
+    )
+}
+
+/// Replace each span of `code`, which must not overlap.
+fn apply_replacements(code: &str, mut replacements: Vec<(Span, String)>) -> String {
+    replacements.sort_by_key(|(span, _)| std::cmp::Reverse(span.start));
+    let mut code = code.to_string();
+    for (span, replacement) in replacements {
+        code.replace_range(span.start as usize..span.end as usize, &replacement);
+    }
+    code
+}
+
+/// Worker references in a module, if it parses.
+fn find_worker_references(code: &str, id: &str) -> Vec<WorkerReference> {
+    let source_type = SourceType::from_path(id)
+        .unwrap_or_else(|_| SourceType::mjs())
+        .with_module(true);
+    let allocator = Allocator::default();
+    let parsed = Parser::new(&allocator, code, source_type).parse();
+    if !parsed.errors.is_empty() {
+        return Vec::new();
+    }
+
+    let mut finder = WorkerFinder {
+        references: Vec::new(),
+    };
+    finder.visit_program(&parsed.program);
+    finder.references
+}
+
+/// Visitor collecting worker references.
+struct WorkerFinder {
+    references: Vec<WorkerReference>,
+}
+
+impl WorkerFinder {
+    fn record(&mut self, kind: WorkerKind, arguments: &[Argument<'_>]) {
+        let Some(url) = arguments.first().and_then(Argument::as_expression) else {
+            return;
+        };
+        let Some(specifier) = new_url_specifier(url) else {
+            return;
+        };
+        let module = kind == WorkerKind::Worklet
+            || arguments
+                .get(1)
+                .and_then(Argument::as_expression)
+                .is_some_and(is_module_type_option);
+        self.references.push(WorkerReference {
+            kind,
+            module,
+            specifier,
+            span: url.span(),
+        });
+    }
+}
+
+impl<'a> Visit<'a> for WorkerFinder {
+    fn visit_new_expression(&mut self, it: &NewExpression<'a>) {
+        if let Expression::Identifier(callee) = &it.callee {
+            let kind = match callee.name.as_str() {
+                "Worker" => Some(WorkerKind::Worker),
+                "SharedWorker" => Some(WorkerKind::SharedWorker),
+                _ => None,
+            };
+            if let Some(kind) = kind {
+                self.record(kind, &it.arguments);
+            }
+        }
+        walk::walk_new_expression(self, it);
+    }
+
+    fn visit_call_expression(&mut self, it: &CallExpression<'a>) {
+        if let Some(member) = it.callee.as_member_expression() {
+            let kind = match member.static_property_name() {
+                // `CSS.paintWorklet.addModule`, `audioContext.audioWorklet.addModule`
+                Some("addModule") => Some(WorkerKind::Worklet),
+                Some("register") => match member.object() {
+                    Expression::StaticMemberExpression(object)
+                        if object.property.name == "serviceWorker" =>
+                    {
+                        Some(WorkerKind::ServiceWorker)
+                    }
+                    _ => None,
+                },
+                _ => None,
+            };
+            if let Some(kind) = kind {
+                self.record(kind, &it.arguments);
+            }
+        }
+        walk::walk_call_expression(self, it);
+    }
+}
+
+/// The path in `new URL('path', import.meta.url)`.
+fn new_url_specifier(expr: &Expression<'_>) -> Option<String> {
+    let Expression::NewExpression(new_url) = expr else {
+        return None;
+    };
+    if !matches!(&new_url.callee, Expression::Identifier(callee) if callee.name == "URL") {
+        return None;
+    }
+    if !new_url
+        .arguments
+        .get(1)
+        .and_then(Argument::as_expression)
+        .is_some_and(is_import_meta_url)
+    {
+        return None;
+    }
+
+    let specifier = match new_url.arguments.first()?.as_expression()? {
+        Expression::StringLiteral(literal) => literal.value.to_string(),
+        Expression::TemplateLiteral(template) if template.expressions.is_empty() => {
+            template.quasis.first()?.value.cooked?.to_string()
+        }
+        _ => return None,
+    };
+    // Data and remote URLs are already loadable
+    (!specifier.starts_with("data:") && !specifier.contains("://")).then_some(specifier)
+}
+
+fn is_import_meta_url(expr: &Expression<'_>) -> bool {
+    match expr {
+        Expression::StaticMemberExpression(member) => {
+            member.property.name == "url"
+                && matches!(&member.object, Expression::MetaProperty(meta)
+                    if meta.meta.name == "import" && meta.property.name == "meta")
+        }
+        _ => false,
+    }
+}
+
+/// Whether worker options contain `type: 'module'`.
+fn is_module_type_option(expr: &Expression<'_>) -> bool {
+    let Expression::ObjectExpression(object) = expr else {
+        return false;
+    };
+    object.properties.iter().any(|property| match property {
+        ObjectPropertyKind::ObjectProperty(property) => {
+            property.key.static_name().is_some_and(|name| name == "type")
+                && matches!(&property.value, Expression::StringLiteral(value) if value.value == "module")
+        }
+        ObjectPropertyKind::SpreadProperty(_) => false,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn find(code: &str) -> Vec<(WorkerKind, bool, String)> {
+        find_worker_references(code, "/src/main.ts")
+            .into_iter()
+            .map(|reference| (reference.kind, reference.module, reference.specifier))
+            .collect()
+    }
+
+    #[test]
+    fn test_find_workers() {
+        let code = "const a = new Worker(new URL('./a.ts', import.meta.url));\n\
+                    const b = new Worker(new URL(\"./b.ts\", import.meta.url), { type: 'module' });\n\
+                    const c = new SharedWorker(new URL(`./c.js`, import.meta.url), { name: 'c' });";
+
+        assert_eq!(
+            find(code),
+            vec![
+                (WorkerKind::Worker, false, "./a.ts".to_string()),
+                (WorkerKind::Worker, true, "./b.ts".to_string()),
+                (WorkerKind::SharedWorker, false, "./c.js".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_find_worklets_and_service_workers() {
+        let code = "CSS.paintWorklet.addModule(new URL('./paint.ts', import.meta.url));\n\
+                    navigator.serviceWorker.register(new URL('./sw.ts', import.meta.url), { type: 'module' });\n\
+                    registry.register(new URL('./other.ts', import.meta.url));";
+
+        assert_eq!(
+            find(code),
+            vec![
+                (WorkerKind::Worklet, true, "./paint.ts".to_string()),
+                (WorkerKind::ServiceWorker, true, "./sw.ts".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_ignores_other_urls() {
+        let code = "new Worker('./plain.js');\n\
+                    new Worker(new URL('./a.js', location.href));\n\
+                    new Worker(new URL('data:text/javascript,1', import.meta.url));\n\
+                    const url = new URL('./data.json', import.meta.url);";
+
+        assert!(find(code).is_empty());
+    }
+
+    #[test]
+    fn test_rewrite_spans() {
+        let code = "const w = new Worker(new URL('./w.ts', import.meta.url));";
+        let references = find_worker_references(code, "/src/main.ts");
+
+        let rewritten =
+            apply_replacements(code, vec![(references[0].span, "new URL(REF)".to_string())]);
+
+        assert_eq!(rewritten, "const w = new Worker(new URL(REF));");
+    }
+
+    #[test]
+    fn test_worker_input() {
+        assert_eq!(
+            worker_input("../workers/w.ts", "/project/src/app/main.ts"),
+            "/project/src/workers/w.ts"
+        );
+        assert_eq!(
+            worker_input("pkg/worker", "/project/src/main.ts"),
+            "pkg/worker"
+        );
+    }
+}
@@ -112,6 +112,9 @@ impl CssPlugin {
 
     /// Process a CSS file through lightningcss
     ///
+    /// Preprocessor plugins (Sass, Less, ...) call this on their compiled
+    /// output so it gets the same minification as plain `.css` files.
+    ///
     /// # Arguments
     ///
     /// * `path` - Path to the CSS file
@@ -120,7 +123,7 @@ impl CssPlugin {
     /// # Returns
     ///
     /// Processed CSS as a string
-    fn process_css(&self, path: &Path, source: String) -> anyhow::Result<String> {
+    pub fn process_css(&self, path: &Path, source: String) -> anyhow::Result<String> {
         let mut stylesheet = StyleSheet::parse(
             &source,
             ParserOptions {
@@ -44,9 +44,11 @@ impl std::fmt::Display for CacheKey {
 /// 1. Cache format version
 /// 2. Rolldown version
 /// 3. Sorted entry paths + content hashes
-/// 4. Serialized BundlerOptions (deterministic subset)
+/// 4. Serialized BundlerOptions (deterministic subset) and source map edits
 /// 5. Virtual files (sorted path + content hash)
 /// 6. Specified environment variables (sorted)
+/// 7. `fob:env` values (sorted)
+/// 8. Replaced and injected globals (sorted)
 pub fn compute_cache_key(plan: &BundlePlan, config: &CacheConfig) -> CacheResult<CacheKey> {
     let mut hasher = Hasher::new();
 
@@ -61,6 +63,10 @@ pub fn compute_cache_key(plan: &BundlePlan, config: &CacheConfig) -> CacheResult
 
     // 4. BundlerOptions (deterministic subset)
     hash_bundler_options(&mut hasher, &plan.options);
+    if let Some(rewrite) = &plan.sourcemap_rewrite {
+        hasher.update(format!("{:?}", rewrite).as_bytes());
+    }
+    hasher.update(plan.top_level_await.to_string().as_bytes());
 
     // 5. Virtual files (sorted)
     hash_virtual_files(&mut hasher, &plan.virtual_files);
@@ -68,6 +74,15 @@ pub fn compute_cache_key(plan: &BundlePlan, config: &CacheConfig) -> CacheResult
     // 6. Environment variables (sorted)
     hash_env_vars(&mut hasher, &config.env_vars);
 
+    // 7. fob:env values (sorted)
+    hash_env_values(&mut hasher, &plan.env);
+
+    // 8. Replaced and injected globals (sorted)
+    hash_env_values(&mut hasher, &plan.replace);
+    let mut injected: Vec<_> = plan.inject.iter().collect();
+    injected.sort_by(|a, b| a.0.cmp(b.0));
+    hasher.update(format!("{:?}", injected).as_bytes());
+
     let hash = hasher.finalize();
     Ok(CacheKey(hash.to_hex().to_string()))
 }
@@ -135,6 +150,20 @@ fn hash_bundler_options(hasher: &mut Hasher, options: &rolldown::BundlerOptions)
     if let Some(sourcemap) = &options.sourcemap {
         hasher.update(format!("{:?}", sourcemap).as_bytes());
     }
+    if options.sourcemap_path_transform.is_some() {
+        hasher.update(b"sourcemap-sanitized");
+    }
+
+    // File name templates
+    if let Some(entry_filenames) = &options.entry_filenames {
+        hasher.update(format!("entry:{:?}", entry_filenames).as_bytes());
+    }
+    if let Some(chunk_filenames) = &options.chunk_filenames {
+        hasher.update(format!("chunk:{:?}", chunk_filenames).as_bytes());
+    }
+    if let Some(asset_filenames) = &options.asset_filenames {
+        hasher.update(format!("asset:{:?}", asset_filenames).as_bytes());
+    }
 
     // Minify
     if let Some(minify) = &options.minify {
@@ -221,6 +250,19 @@ fn hash_env_vars(hasher: &mut Hasher, env_vars: &[String]) {
     }
 }
 
+/// Hash `fob:env` values (sorted for determinism).
+fn hash_env_values(hasher: &mut Hasher, env: &rustc_hash::FxHashMap<String, String>) {
+    let mut keys: Vec<_> = env.keys().collect();
+    keys.sort();
+
+    for key in keys {
+        hasher.update(key.as_bytes());
+        hasher.update(b"=");
+        hasher.update(env[key].as_bytes());
+        hasher.update(b"\0");
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -241,9 +283,17 @@ mod tests {
                 .into_iter()
                 .map(|(k, v)| (k.to_string(), v.to_string()))
                 .collect(),
+            env: Default::default(),
+            replace: Default::default(),
+            inject: Default::default(),
             runtime: None,
             cache: None,
             incremental: None,
+            slow_module_threshold: None,
+            chunk_size_warning_limit: None,
+            output_validation: None,
+            sourcemap_rewrite: None,
+            top_level_await: Default::default(),
         }
     }
 
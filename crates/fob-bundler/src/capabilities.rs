@@ -0,0 +1,194 @@
+//! Runtime introspection of what this fob build supports.
+//!
+//! [`capabilities()`] returns a serializable report that embedders (CLIs,
+//! language bindings, editor integrations) can use for feature detection
+//! instead of parsing version strings. The JSON shape is versioned by
+//! [`CAPABILITIES_SCHEMA_VERSION`]; fields are only added within a version.
+
+use serde::Serialize;
+
+use crate::builders::MAX_ENTRY_POINTS;
+use crate::builders::asset_resolver::DEFAULT_MAX_ASSET_SIZE;
+use crate::builders::chunk_size::DEFAULT_CHUNK_SIZE_WARNING_LIMIT;
+use crate::builders::common::MAX_VIRTUAL_FILE_SIZE;
+use crate::plugins::builtin_plugins;
+use fob_graph::analysis::config::{DEFAULT_MAX_DEPTH, DEFAULT_MAX_MODULES, MAX_FILE_SIZE};
+
+/// Version of the [`Capabilities`] JSON shape.
+pub const CAPABILITIES_SCHEMA_VERSION: u32 = 1;
+
+/// Features, plugins and limits of the running fob build.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Capabilities {
+    /// Version of this report's shape
+    pub schema_version: u32,
+    /// fob-bundler crate version
+    pub version: &'static str,
+    /// Cargo features compiled into fob-bundler
+    pub features: Vec<&'static str>,
+    /// Built-in plugins
+    pub plugins: Vec<PluginCapability>,
+    /// Supported output formats
+    pub output_formats: Vec<&'static str>,
+    /// Hard limits and defaults enforced by the bundler
+    pub limits: Limits,
+}
+
+/// Built-in plugin description.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PluginCapability {
+    /// Plugin name as reported to Rolldown
+    pub name: &'static str,
+    /// What the plugin handles
+    pub description: &'static str,
+}
+
+/// Size and count limits, in bytes where applicable.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Limits {
+    /// Maximum entry points per build
+    pub max_entry_points: usize,
+    /// Maximum size of a source file during graph analysis
+    pub max_file_size: usize,
+    /// Default maximum number of modules in a graph
+    pub max_modules: usize,
+    /// Default maximum import depth
+    pub max_depth: usize,
+    /// Default maximum size of an imported asset
+    pub max_asset_size: u64,
+    /// Maximum size of a virtual file
+    pub max_virtual_file_size: usize,
+    /// Default chunk size above which a warning is reported
+    pub chunk_size_warning_limit: usize,
+}
+
+/// Report the capabilities of this build.
+///
+/// # Example
+///
+/// ```
+/// let caps = fob_bundler::capabilities();
+/// assert!(caps.output_formats.contains(&"esm"));
+/// println!("{}", serde_json::to_string_pretty(&caps).unwrap());
+/// ```
+pub fn capabilities() -> Capabilities {
+    let mut features = Vec::new();
+    if cfg!(feature = "dts-generation") {
+        features.push("dts-generation");
+    }
+    if cfg!(feature = "logging") {
+        features.push("logging");
+    }
+
+    let plugins = builtin_plugins()
+        .into_iter()
+        .map(|plugin| PluginCapability {
+            name: plugin.name,
+            description: plugin.description,
+        })
+        .collect();
+
+    Capabilities {
+        schema_version: CAPABILITIES_SCHEMA_VERSION,
+        version: env!("CARGO_PKG_VERSION"),
+        features,
+        plugins,
+        output_formats: vec!["esm", "cjs", "iife"],
+        limits: Limits {
+            max_entry_points: MAX_ENTRY_POINTS,
+            max_file_size: MAX_FILE_SIZE,
+            max_modules: DEFAULT_MAX_MODULES,
+            max_depth: DEFAULT_MAX_DEPTH,
+            max_asset_size: DEFAULT_MAX_ASSET_SIZE,
+            max_virtual_file_size: MAX_VIRTUAL_FILE_SIZE,
+            chunk_size_warning_limit: DEFAULT_CHUNK_SIZE_WARNING_LIMIT,
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_capabilities_report() {
+        let caps = capabilities();
+        assert_eq!(caps.version, env!("CARGO_PKG_VERSION"));
+        assert_eq!(
+            caps.features.contains(&"dts-generation"),
+            cfg!(feature = "dts-generation")
+        );
+        assert!(caps.plugins.iter().any(|p| p.name == "fob-css"));
+        assert_eq!(caps.limits.max_entry_points, 1000);
+    }
+
+    #[test]
+    fn test_capabilities_list_every_builtin_plugin() {
+        use crate::builders::asset_plugin::AssetDetectionPlugin;
+        use crate::builders::asset_registry::AssetRegistry;
+        use crate::builders::replace::ReplacePlugin;
+        use crate::builders::runtime_file_plugin::RuntimeFilePlugin;
+        use crate::builders::top_level_await::TopLevelAwaitPlugin;
+        use crate::builders::virtual_modules::VirtualModulesPlugin;
+        use crate::module_collection_plugin::ModuleCollectionPlugin;
+        use crate::runtime::BundlerRuntime;
+        use crate::{CssPlugin, SharedPluginable};
+        use rustc_hash::FxHashMap;
+        use std::path::PathBuf;
+        use std::sync::Arc;
+
+        let runtime = Arc::new(BundlerRuntime::new("/project"));
+        let mut plugins: Vec<SharedPluginable> = vec![
+            Arc::new(RuntimeFilePlugin::new(Arc::clone(&runtime))),
+            Arc::new(VirtualModulesPlugin::new(FxHashMap::default(), "/project")),
+            Arc::new(ReplacePlugin::new(
+                &FxHashMap::default(),
+                &FxHashMap::default(),
+                std::path::Path::new("/project"),
+            )),
+            Arc::new(AssetDetectionPlugin::new(
+                Arc::new(AssetRegistry::new()),
+                "/project",
+                Vec::new(),
+                runtime.clone(),
+            )),
+            Arc::new(TopLevelAwaitPlugin::new(PathBuf::from("/project"))),
+            Arc::new(ModuleCollectionPlugin::new()),
+            Arc::new(CssPlugin::new(runtime)),
+        ];
+        #[cfg(not(target_family = "wasm"))]
+        plugins.push(Arc::new(crate::builders::workers::WorkerPlugin::new(
+            &rolldown::BundlerOptions::default(),
+            Vec::new(),
+        )));
+        #[cfg(feature = "dts-generation")]
+        plugins.push(Arc::new(crate::plugins::DtsEmitPlugin::new(
+            false, false, None, false,
+        )));
+
+        let caps = capabilities();
+        for plugin in &plugins {
+            let name = plugin.call_name();
+            assert!(
+                caps.plugins.iter().any(|p| p.name == name),
+                "{} is missing from capabilities()",
+                name
+            );
+        }
+        assert_eq!(caps.plugins.len(), plugins.len());
+    }
+
+    #[test]
+    fn test_capabilities_json_shape() {
+        let json = serde_json::to_value(capabilities()).unwrap();
+        assert_eq!(json["schemaVersion"], CAPABILITIES_SCHEMA_VERSION);
+        assert_eq!(
+            json["outputFormats"],
+            serde_json::json!(["esm", "cjs", "iife"])
+        );
+        assert!(json["limits"]["maxAssetSize"].is_u64());
+    }
+}
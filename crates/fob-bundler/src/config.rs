@@ -6,9 +6,10 @@
 //! Note: `BuildOptions` remains the public API for backward compatibility.
 //! `BuildConfig` is available for advanced use cases and future migration.
 
+use crate::builders::sourcemap::SourceMapOptions;
 use crate::builders::unified::{
     EntryPoints, MinifyLevel,
-    primitives::{CodeSplittingConfig, EntryMode, ExternalConfig},
+    primitives::{CodeSplittingConfig, EntryMode, ExternalConfig, OutputTarget},
 };
 use crate::target::{ExportConditions, NodeBuiltins};
 use crate::{OutputFormat, SourceMapType};
@@ -41,7 +42,17 @@ pub struct OutputConfig {
     pub dir: PathBuf,
     pub format: OutputFormat,
     pub sourcemap: Option<SourceMapType>,
+    pub sourcemap_options: SourceMapOptions,
     pub splitting: bool,
+    /// Entry chunk file name template, e.g. `[name]-[hash].js`
+    pub entry_names: Option<String>,
+    /// Shared chunk file name template, e.g. `chunks/[name]-[hash].js`
+    pub chunk_names: Option<String>,
+    /// Asset file name template, e.g. `assets/[dir]/[name][extname]`
+    pub asset_names: Option<String>,
+    /// Formats rendered from this build, each into its own subdirectory.
+    /// Empty means a single output in `format`.
+    pub targets: Vec<OutputTarget>,
 }
 
 /// Module resolution configuration
@@ -50,6 +61,9 @@ pub struct ResolutionConfig {
     /// Export conditions (from DeploymentTarget or custom)
     pub conditions: ExportConditions,
 
+    /// Custom export conditions resolved ahead of `conditions`
+    pub custom_conditions: Vec<String>,
+
     /// Path aliases (@/ -> ./src/)
     pub aliases: FxHashMap<String, String>,
 
@@ -93,6 +107,9 @@ pub struct BuildConfig {
 
     /// Virtual files that don't exist on disk
     pub virtual_files: FxHashMap<String, String>,
+
+    /// Additional Rolldown plugins (from the deployment target or custom)
+    pub plugins: Vec<crate::SharedPluginable>,
 }
 
 impl BuildConfig {
@@ -105,10 +122,16 @@ impl BuildConfig {
                 dir: PathBuf::from("dist"),
                 format: OutputFormat::Esm,
                 sourcemap: Some(SourceMapType::File),
+                sourcemap_options: SourceMapOptions::default(),
                 splitting: false,
+                entry_names: None,
+                chunk_names: None,
+                asset_names: None,
+                targets: Vec::new(),
             },
             resolution: ResolutionConfig {
                 conditions: ExportConditions::browser(),
+                custom_conditions: Vec::new(),
                 aliases: FxHashMap::default(),
                 external: Vec::new(),
                 node_builtins: NodeBuiltins::Error,
@@ -121,12 +144,21 @@ impl BuildConfig {
             cwd: None,
             runtime: None,
             virtual_files: FxHashMap::default(),
+            plugins: Vec::new(),
         }
     }
 
     /// Apply settings from a deployment target
+    ///
+    /// The target's preset replaces `conditions`, while its custom
+    /// conditions are added to the ones already configured.
     pub fn for_target(mut self, target: &dyn crate::DeploymentTarget) -> Self {
         self.resolution.conditions = target.conditions();
+        for condition in target.custom_conditions() {
+            if !self.resolution.custom_conditions.contains(&condition) {
+                self.resolution.custom_conditions.push(condition);
+            }
+        }
         self.resolution.node_builtins = target.node_builtins();
         self.resolution.external.extend(
             target
@@ -134,6 +166,10 @@ impl BuildConfig {
                 .into_iter()
                 .map(ExternalPattern::exact),
         );
+        if let Some(format) = target.output_format() {
+            self.output.format = format;
+        }
+        self.plugins.extend(target.plugins());
         self
     }
 
@@ -165,6 +201,36 @@ impl BuildConfig {
         self
     }
 
+    /// Set `sourcesContent`, `sourceRoot` and path sanitization options
+    pub fn sourcemap_options(mut self, options: SourceMapOptions) -> Self {
+        self.output.sourcemap_options = options;
+        self
+    }
+
+    /// Set the entry chunk file name template
+    pub fn entry_names(mut self, template: impl Into<String>) -> Self {
+        self.output.entry_names = Some(template.into());
+        self
+    }
+
+    /// Set the shared chunk file name template
+    pub fn chunk_names(mut self, template: impl Into<String>) -> Self {
+        self.output.chunk_names = Some(template.into());
+        self
+    }
+
+    /// Set the asset file name template
+    pub fn asset_names(mut self, template: impl Into<String>) -> Self {
+        self.output.asset_names = Some(template.into());
+        self
+    }
+
+    /// Add an output target (e.g. ESM + CJS + IIFE from one build)
+    pub fn add_output(mut self, target: OutputTarget) -> Self {
+        self.output.targets.push(target);
+        self
+    }
+
     /// Set the minification level
     pub fn minify(mut self, level: MinifyLevel) -> Self {
         self.optimization.minify = level;
@@ -193,6 +259,18 @@ impl BuildConfig {
         self
     }
 
+    /// Set the export conditions preset
+    pub fn conditions(mut self, conditions: ExportConditions) -> Self {
+        self.resolution.conditions = conditions;
+        self
+    }
+
+    /// Add a custom export condition (e.g. `"worker"`)
+    pub fn condition(mut self, condition: impl Into<String>) -> Self {
+        self.resolution.custom_conditions.push(condition.into());
+        self
+    }
+
     /// Add a path alias
     pub fn path_alias(mut self, alias: impl Into<String>, target: impl Into<String>) -> Self {
         self.resolution.aliases.insert(alias.into(), target.into());
@@ -217,6 +295,12 @@ impl BuildConfig {
         self
     }
 
+    /// Add a Rolldown plugin
+    pub fn plugin(mut self, plugin: crate::SharedPluginable) -> Self {
+        self.plugins.push(plugin);
+        self
+    }
+
     /// Validate the build configuration
     ///
     /// Checks for internal consistency and invalid combinations.
@@ -236,7 +320,7 @@ impl BuildConfig {
         }
 
         // Entry count validation (DoS protection)
-        const MAX_ENTRY_POINTS: usize = 1000;
+        use crate::builders::MAX_ENTRY_POINTS;
         let entry_count = match &self.entries {
             EntryPoints::Single(_) => 1,
             EntryPoints::Multiple(v) => v.len(),
@@ -317,12 +401,20 @@ impl BuildConfig {
             entry: self.entries,
             entry_mode,
             code_splitting,
+            manual_chunks: None,
             external: external_config,
             outdir: Some(self.output.dir),
             outfile: None,
             platform,
+            export_conditions: Some(self.resolution.conditions),
+            custom_conditions: self.resolution.custom_conditions,
             format: self.output.format,
             sourcemap: self.output.sourcemap,
+            sourcemap_options: self.output.sourcemap_options,
+            entry_names: self.output.entry_names,
+            chunk_names: self.output.chunk_names,
+            asset_names: self.output.asset_names,
+            outputs: self.output.targets,
             minify_level: match self.optimization.minify {
                 MinifyLevel::None => None,
                 MinifyLevel::Whitespace => Some("whitespace".to_string()),
@@ -331,6 +423,9 @@ impl BuildConfig {
             },
             globals: FxHashMap::default(),
             virtual_files: self.virtual_files,
+            env: FxHashMap::default(),
+            replace: FxHashMap::default(),
+            inject: FxHashMap::default(),
             path_aliases: self.resolution.aliases,
             cwd: self.cwd,
             runtime: self.runtime,
@@ -340,6 +435,13 @@ impl BuildConfig {
             cache: None,
             incremental: None,
             max_parallel_builds: None,
+            slow_module_threshold: Some(crate::builders::unified::DEFAULT_SLOW_MODULE_THRESHOLD),
+            chunk_size_warning_limit: Some(
+                crate::builders::chunk_size::DEFAULT_CHUNK_SIZE_WARNING_LIMIT,
+            ),
+            output_validation: None,
+            top_level_await: crate::TopLevelAwait::Preserve,
+            plugins: self.plugins,
         }
     }
 }
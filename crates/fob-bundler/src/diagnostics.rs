@@ -5,11 +5,13 @@
 //! from upstream API changes.
 
 mod miette;
+mod report;
 
 pub use miette::{
     DiagnosticError, calculate_enhanced_span, calculate_span_length, clear_source_cache,
     line_col_to_offset, load_source, to_diagnostic_error,
 };
+pub use report::Diagnostic;
 
 use serde::{Deserialize, Serialize};
 
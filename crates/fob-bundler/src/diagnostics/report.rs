@@ -0,0 +1,96 @@
+//! Binding-neutral diagnostic format.
+//!
+//! Language bindings report errors in this shape so that location and help
+//! text survive the trip into host-language error objects.
+
+use super::{DiagnosticKind, DiagnosticSeverity, ExtractedDiagnostic};
+use serde::{Deserialize, Serialize};
+
+/// A serializable error or warning with optional source location.
+///
+/// Serializes with camelCase keys and omits empty fields:
+///
+/// ```json
+/// { "kind": "MissingExport", "severity": "error", "message": "...",
+///   "file": "src/a.js", "line": 3, "column": 10, "help": "...", "related": [] }
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Diagnostic {
+    /// Error kind in PascalCase, e.g. `MissingExport` or `InvalidConfig`
+    pub kind: String,
+    pub severity: DiagnosticSeverity,
+    pub message: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub file: Option<String>,
+    /// 1-based line number
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub line: Option<u32>,
+    /// 1-based column number
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub column: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub help: Option<String>,
+    /// Further diagnostics reported together with this one
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub related: Vec<Diagnostic>,
+}
+
+impl Diagnostic {
+    /// Create an error diagnostic without location.
+    pub fn error(kind: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            kind: kind.into(),
+            severity: DiagnosticSeverity::Error,
+            message: message.into(),
+            file: None,
+            line: None,
+            column: None,
+            help: None,
+            related: Vec::new(),
+        }
+    }
+
+    /// Create an error diagnostic for a syntax error in an MDX document.
+    ///
+    /// fob-mdx depends on this crate, so bindings pass the fields of its
+    /// `MdxError`. The suggestion becomes the help text.
+    pub fn mdx_syntax(
+        message: impl Into<String>,
+        file: Option<String>,
+        line: Option<usize>,
+        column: Option<usize>,
+        suggestion: Option<String>,
+    ) -> Self {
+        Self {
+            file,
+            line: line.and_then(|line| u32::try_from(line).ok()),
+            column: column.and_then(|column| u32::try_from(column).ok()),
+            help: suggestion,
+            ..Self::error("MdxSyntax", message)
+        }
+    }
+}
+
+impl From<&ExtractedDiagnostic> for Diagnostic {
+    fn from(diag: &ExtractedDiagnostic) -> Self {
+        let kind = match &diag.kind {
+            // `Other` sometimes carries the whole unparsed error message
+            DiagnosticKind::Other(name) if name.contains(char::is_whitespace) => {
+                "Other".to_string()
+            }
+            kind => kind.to_string(),
+        };
+
+        Self {
+            kind,
+            severity: diag.severity.clone(),
+            message: diag.message.clone(),
+            file: diag.file.clone(),
+            line: diag.line,
+            column: diag.column,
+            help: diag.help.clone(),
+            related: Vec::new(),
+        }
+    }
+}
@@ -70,6 +70,7 @@ pub use fob_graph::*;
 pub mod builders;
 pub mod builtins;
 pub mod cache;
+pub mod capabilities;
 pub mod config;
 pub mod output;
 pub mod plugins;
@@ -108,11 +109,14 @@ pub use rolldown_plugin::{
 
 // Re-export bundler APIs
 pub use builders::{
-    BuildOptions, BuildOutput, BuildResult, CodeSplittingConfig, EntryMode, EntryPoints,
-    ExternalConfig, IncrementalConfig, MinifyLevel, build,
+    BuildOptions, BuildOutput, BuildResult, CodeSplittingConfig, EmittedFormat, EntryMode,
+    EntryPoints, ExternalConfig, IncrementalConfig, InjectImport, ManualChunks, MinifyLevel,
+    OutputTarget, PackageEntryPoints, PackageExportIssue, PackageExportIssueKind, PackageOutputs,
+    SourceMapOptions, SubpathExport, SyntaxTarget, TopLevelAwait, build, validate_package_exports,
 };
 pub use builtins::CssPlugin;
 pub use cache::{CacheConfig, CacheKey};
+pub use capabilities::{Capabilities, capabilities};
 pub use config::{
     BuildConfig, ExternalPattern, OptimizationConfig, OutputConfig, ResolutionConfig,
 };
@@ -209,6 +213,43 @@ impl Error {
     pub fn from_rolldown_batch(error: &dyn std::fmt::Debug) -> Self {
         Error::Bundler(diagnostics::extract_from_rolldown_error(error))
     }
+
+    /// Convert to the binding-neutral [`diagnostics::Diagnostic`] format.
+    ///
+    /// A bundler error with several diagnostics becomes its first diagnostic,
+    /// with the rest in `related`.
+    pub fn to_diagnostic(&self) -> diagnostics::Diagnostic {
+        use diagnostics::Diagnostic;
+
+        let (kind, file) = match self {
+            Error::Bundler(diagnostics) => {
+                let Some((first, rest)) = diagnostics.split_first() else {
+                    return Diagnostic::error("Bundler", "Unknown bundler error");
+                };
+                let mut diagnostic = Diagnostic::from(first);
+                diagnostic.related = rest.iter().map(Diagnostic::from).collect();
+                return diagnostic;
+            }
+            Error::InvalidConfig(_) => ("InvalidConfig", None),
+            Error::Io(_) | Error::IoError { .. } => ("Io", None),
+            Error::InvalidOutputPath(path) => ("InvalidOutputPath", Some(path.clone())),
+            Error::WriteFailure(_) => ("WriteFailure", None),
+            Error::OutputExists(_) => ("OutputExists", None),
+            Error::AssetNotFound { searched_from, .. } => {
+                ("AssetNotFound", Some(searched_from.clone()))
+            }
+            Error::AssetSecurityViolation { path, .. } => {
+                ("AssetSecurityViolation", Some(path.clone()))
+            }
+            Error::AssetTooLarge { path, .. } => ("AssetTooLarge", Some(path.clone())),
+            Error::Foundation(_) => ("Foundation", None),
+        };
+
+        let mut diagnostic = Diagnostic::error(kind, self.to_string());
+        diagnostic.file = file;
+        diagnostic.help = miette::Diagnostic::help(self).map(|help| help.to_string());
+        diagnostic
+    }
 }
 
 /// Format bundler error diagnostics for display.
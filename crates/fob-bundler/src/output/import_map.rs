@@ -1,6 +1,8 @@
 use rustc_hash::FxHashMap;
+use std::collections::BTreeMap;
 
-/// Lightweight import map representation for component bundles.
+/// Lightweight import map representation, used for component bundles and
+/// for resolving dependencies in unbundled dev serving.
 #[derive(Debug, Clone, Default)]
 pub struct ImportMap {
     entries: FxHashMap<String, String>,
@@ -21,8 +23,14 @@ impl ImportMap {
         &self.entries
     }
 
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Serialize as an import map document, with specifiers sorted.
     pub fn to_json(&self) -> String {
-        serde_json::to_string(&self.entries).unwrap_or_else(|_| "{}".to_string())
+        let imports: BTreeMap<&String, &String> = self.entries.iter().collect();
+        serde_json::json!({ "imports": imports }).to_string()
     }
 
     /// Inject import map into HTML using fob-gen's HtmlBuilder
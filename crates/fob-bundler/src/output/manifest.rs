@@ -80,6 +80,22 @@ impl BundleManifest {
             version: env!("CARGO_PKG_VERSION").to_string(),
         }
     }
+
+    /// Render a `<script>` tag that exposes this manifest to the `fob:manifest`
+    /// virtual module.
+    ///
+    /// Place it in the HTML before any bundle scripts.
+    pub fn to_inline_script(&self) -> String {
+        // Escape `<` so a module id containing `</script>` cannot end the tag early
+        let json = serde_json::to_string(self)
+            .unwrap_or_else(|_| "null".to_string())
+            .replace('<', "\\u003c");
+        format!(
+            "<script>globalThis.{} = {};</script>",
+            crate::builders::virtual_modules::MANIFEST_GLOBAL,
+            json
+        )
+    }
 }
 
 /// Metadata for a single chunk
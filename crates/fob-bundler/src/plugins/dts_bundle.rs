@@ -0,0 +1,1075 @@
+//! Declaration bundling
+//!
+//! Flattens the declarations reachable from an entry into a single file, the
+//! way api-extractor or rollup-plugin-dts do. Each module's declarations are
+//! generated separately, then concatenated dependencies first:
+//!
+//! - imports between bundled modules are removed and their local names are
+//!   pointed at the declarations they refer to,
+//! - only the entry keeps its `export` keywords; re-exports from bundled
+//!   modules become plain `export { .. }` lists, so a name re-exported along
+//!   several paths is exported once,
+//! - imports from packages are hoisted into one import per package,
+//! - top-level names that clash across modules get a `$N` suffix, with the
+//!   entry's own names always kept.
+//!
+//! Relative specifiers are bundled, everything else is treated as external.
+//! Namespace imports of bundled modules and `import("./x")` types aren't
+//! rewritten; the former is reported as an error.
+
+use anyhow::{Context, Result, bail};
+use oxc_allocator::Allocator;
+use oxc_ast::ast::{
+    Declaration, ExportDefaultDeclarationKind, ImportDeclarationSpecifier, Program, Statement,
+};
+use oxc_parser::Parser;
+use oxc_semantic::{Semantic, SemanticBuilder, SymbolId};
+use oxc_span::{GetSpan, SourceType, Span};
+use rustc_hash::{FxHashMap, FxHashSet};
+use std::collections::hash_map::Entry;
+
+/// What an import binds to in its source module.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum Import {
+    Name(String),
+    Namespace,
+}
+
+/// What an exported or imported name refers to once modules are flattened.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Binding {
+    /// A top-level declaration of the bundle, by its final name
+    Local(String),
+    /// A name imported from a module outside the bundle
+    External { source: String, import: Import },
+}
+
+/// A top-level statement that takes part in linking modules.
+#[derive(Debug)]
+enum Item {
+    Import(Span),
+    /// `export <declaration>` and `export default <named declaration>`;
+    /// outside the entry, `span.start..decl_start` is dropped.
+    ExportDeclaration {
+        span: Span,
+        decl_start: u32,
+        needs_declare: bool,
+        /// `(exported, local)` pairs
+        names: Vec<(String, String)>,
+    },
+    /// `export { a as b }`, or `export default a` when not `rewrite`
+    ExportLocals {
+        span: Span,
+        /// `(local, exported)` pairs
+        specifiers: Vec<(String, String)>,
+        rewrite: bool,
+    },
+    /// `export { a as b } from '..'`
+    ReExport {
+        span: Span,
+        source: String,
+        /// `(imported, exported)` pairs
+        specifiers: Vec<(String, String)>,
+    },
+    /// `export * from '..'`
+    ExportAll {
+        span: Span,
+        source: String,
+    },
+    /// `export * as ns from '..'`
+    ExportAllAs {
+        span: Span,
+        source: String,
+        exported: String,
+    },
+    /// Anything the entry may keep but other modules can't be flattened
+    /// with, such as `export =`; `None` when it can simply be dropped.
+    Other {
+        span: Span,
+        unsupported: Option<&'static str>,
+    },
+}
+
+/// A name bound by an import declaration.
+#[derive(Debug)]
+struct ImportBinding {
+    local: String,
+    source: String,
+    import: Import,
+    /// References to the name; the binding itself is removed with its import
+    references: Vec<Span>,
+}
+
+/// A module of the bundle, reduced to what linking needs.
+#[derive(Debug)]
+struct Module {
+    id: String,
+    code: String,
+    /// Top-level declarations and every span naming them
+    declared: Vec<(String, Vec<Span>)>,
+    imports: Vec<ImportBinding>,
+    /// Sources of imports without bindings, like `import 'x'`
+    side_effects: Vec<String>,
+    items: Vec<Item>,
+    /// Every module specifier used
+    specifiers: Vec<String>,
+    /// Specifiers resolving to modules of the bundle, by module index
+    internal: FxHashMap<String, usize>,
+}
+
+/// Exports of a module after flattening.
+#[derive(Debug, Default)]
+struct Exports {
+    names: FxHashMap<String, Binding>,
+    /// Packages re-exported with `export *`
+    star_externals: Vec<String>,
+}
+
+/// Bundle the declarations reachable from `entry` into one file.
+///
+/// `load` returns the declarations of a module, and `resolve` maps a
+/// relative specifier in a module to the id of the module it refers to.
+pub(crate) fn bundle_declarations(
+    entry: &str,
+    load: impl Fn(&str) -> Result<String>,
+    resolve: impl Fn(&str, &str) -> Option<String>,
+) -> Result<String> {
+    let modules = collect_modules(entry, &load, &resolve)?;
+    Linker::new(modules).link()
+}
+
+/// Load the module graph, dependencies first and the entry last.
+fn collect_modules(
+    entry: &str,
+    load: &impl Fn(&str) -> Result<String>,
+    resolve: &impl Fn(&str, &str) -> Option<String>,
+) -> Result<Vec<Module>> {
+    // A module being visited, its `(specifier, module id)` dependencies and
+    // the next dependency to visit
+    type Visit = (Module, Vec<(String, String)>, usize);
+
+    let open = |id: &str| -> Result<Visit> {
+        let module = analyze(id, load(id)?)?;
+        let dependencies = module
+            .specifiers
+            .iter()
+            .filter(|specifier| specifier.starts_with('.'))
+            .filter_map(|specifier| {
+                resolve(id, specifier).map(|target| (specifier.clone(), target))
+            })
+            .collect();
+        Ok((module, dependencies, 0))
+    };
+
+    let mut finished: Vec<(Module, Vec<(String, String)>)> = Vec::new();
+    let mut visited: FxHashSet<String> = FxHashSet::default();
+    let mut stack: Vec<Visit> = vec![open(entry)?];
+    visited.insert(entry.to_string());
+
+    while let Some((_, dependencies, next)) = stack.last_mut() {
+        if let Some((_, target)) = dependencies.get(*next) {
+            let target = target.clone();
+            *next += 1;
+            if visited.insert(target.clone()) {
+                stack.push(open(&target)?);
+            }
+            continue;
+        }
+        let (module, dependencies, _) = stack.pop().expect("stack is not empty");
+        finished.push((module, dependencies));
+    }
+
+    // Link specifiers only now, so imports along cycles are resolved too
+    let indices: FxHashMap<String, usize> = finished
+        .iter()
+        .enumerate()
+        .map(|(index, (module, _))| (module.id.clone(), index))
+        .collect();
+    Ok(finished
+        .into_iter()
+        .map(|(mut module, dependencies)| {
+            module.internal = dependencies
+                .into_iter()
+                .filter_map(|(specifier, target)| {
+                    indices.get(&target).map(|&index| (specifier, index))
+                })
+                .collect();
+            module
+        })
+        .collect())
+}
+
+/// Parse a module's declarations and record what linking needs.
+fn analyze(id: &str, code: String) -> Result<Module> {
+    let mut declared = Vec::new();
+    let mut imports = Vec::new();
+    let mut side_effects = Vec::new();
+    let mut items = Vec::new();
+    let mut specifiers = Vec::new();
+
+    {
+        let allocator = Allocator::default();
+        let parsed = Parser::new(&allocator, &code, SourceType::d_ts()).parse();
+        if !parsed.errors.is_empty() {
+            let messages: Vec<String> = parsed.errors.iter().map(|e| e.to_string()).collect();
+            bail!(
+                "Failed to parse declarations of {}: {}",
+                id,
+                messages.join(", ")
+            );
+        }
+
+        collect_items(
+            &parsed.program,
+            &mut items,
+            &mut imports,
+            &mut side_effects,
+            &mut specifiers,
+        );
+
+        let semantic = SemanticBuilder::new().build(&parsed.program).semantic;
+        let scoping = semantic.scoping();
+        for symbol_id in scoping.iter_bindings_in(scoping.root_scope_id()) {
+            let name = scoping.symbol_name(symbol_id);
+            match imports
+                .iter_mut()
+                .find(|import: &&mut ImportBinding| import.local == name)
+            {
+                Some(import) => import.references = references(&semantic, symbol_id),
+                None => {
+                    let mut spans = vec![scoping.symbol_span(symbol_id)];
+                    spans.extend(
+                        scoping
+                            .symbol_redeclarations(symbol_id)
+                            .iter()
+                            .map(|redeclaration| redeclaration.span),
+                    );
+                    spans.extend(references(&semantic, symbol_id));
+                    spans.sort_by_key(|span| span.start);
+                    spans.dedup();
+                    declared.push((name.to_string(), spans));
+                }
+            }
+        }
+        // Bindings come out in hash order; keep renames stable between builds
+        declared.sort_by_key(|(_, spans)| spans.first().map_or(0, |span| span.start));
+    }
+
+    Ok(Module {
+        id: id.to_string(),
+        code,
+        declared,
+        imports,
+        side_effects,
+        items,
+        specifiers,
+        internal: FxHashMap::default(),
+    })
+}
+
+/// Spans of the resolved references to a symbol.
+fn references(semantic: &Semantic<'_>, symbol_id: SymbolId) -> Vec<Span> {
+    let scoping = semantic.scoping();
+    scoping
+        .get_resolved_reference_ids(symbol_id)
+        .iter()
+        .map(|&reference_id| {
+            let node_id = scoping.get_reference(reference_id).node_id();
+            semantic.nodes().get_node(node_id).kind().span()
+        })
+        .collect()
+}
+
+/// Record the import and export statements of a module.
+fn collect_items(
+    program: &Program<'_>,
+    items: &mut Vec<Item>,
+    imports: &mut Vec<ImportBinding>,
+    side_effects: &mut Vec<String>,
+    specifiers: &mut Vec<String>,
+) {
+    for statement in &program.body {
+        match statement {
+            Statement::ImportDeclaration(decl) => {
+                let source = decl.source.value.to_string();
+                items.push(Item::Import(decl.span));
+                match &decl.specifiers {
+                    Some(list) if !list.is_empty() => {
+                        for specifier in list {
+                            let (local, import) = match specifier {
+                                ImportDeclarationSpecifier::ImportSpecifier(s) => {
+                                    (&s.local, Import::Name(s.imported.name().to_string()))
+                                }
+                                ImportDeclarationSpecifier::ImportDefaultSpecifier(s) => {
+                                    (&s.local, Import::Name("default".to_string()))
+                                }
+                                ImportDeclarationSpecifier::ImportNamespaceSpecifier(s) => {
+                                    (&s.local, Import::Namespace)
+                                }
+                            };
+                            imports.push(ImportBinding {
+                                local: local.name.to_string(),
+                                source: source.clone(),
+                                import,
+                                references: Vec::new(),
+                            });
+                        }
+                    }
+                    _ => side_effects.push(source.clone()),
+                }
+                specifiers.push(source);
+            }
+            Statement::ExportNamedDeclaration(decl) => {
+                if let Some(declaration) = &decl.declaration {
+                    items.push(Item::ExportDeclaration {
+                        span: decl.span,
+                        decl_start: declaration.span().start,
+                        needs_declare: needs_declare(declaration),
+                        names: declaration_names(declaration)
+                            .into_iter()
+                            .map(|name| (name.clone(), name))
+                            .collect(),
+                    });
+                    continue;
+                }
+
+                let pairs = decl
+                    .specifiers
+                    .iter()
+                    .map(|s| (s.local.name().to_string(), s.exported.name().to_string()))
+                    .collect();
+                match &decl.source {
+                    Some(source) => {
+                        specifiers.push(source.value.to_string());
+                        items.push(Item::ReExport {
+                            span: decl.span,
+                            source: source.value.to_string(),
+                            specifiers: pairs,
+                        });
+                    }
+                    None => items.push(Item::ExportLocals {
+                        span: decl.span,
+                        specifiers: pairs,
+                        rewrite: true,
+                    }),
+                }
+            }
+            Statement::ExportDefaultDeclaration(decl) => {
+                let named = match &decl.declaration {
+                    ExportDefaultDeclarationKind::FunctionDeclaration(f) => {
+                        f.id.as_ref().map(|id| (id.name.to_string(), !f.declare))
+                    }
+                    ExportDefaultDeclarationKind::ClassDeclaration(c) => {
+                        c.id.as_ref().map(|id| (id.name.to_string(), !c.declare))
+                    }
+                    ExportDefaultDeclarationKind::TSInterfaceDeclaration(i) => {
+                        Some((i.id.name.to_string(), false))
+                    }
+                    _ => None,
+                };
+                let item = match (named, &decl.declaration) {
+                    (Some((name, needs_declare)), declaration) => Item::ExportDeclaration {
+                        span: decl.span,
+                        decl_start: declaration.span().start,
+                        needs_declare,
+                        names: vec![("default".to_string(), name)],
+                    },
+                    (None, ExportDefaultDeclarationKind::Identifier(ident)) => Item::ExportLocals {
+                        span: decl.span,
+                        specifiers: vec![(ident.name.to_string(), "default".to_string())],
+                        rewrite: false,
+                    },
+                    // Anonymous defaults have nothing to refer to once the
+                    // `export` is gone; outside the entry they are dropped
+                    _ => Item::Other {
+                        span: decl.span,
+                        unsupported: None,
+                    },
+                };
+                items.push(item);
+            }
+            Statement::ExportAllDeclaration(decl) => {
+                let source = decl.source.value.to_string();
+                specifiers.push(source.clone());
+                items.push(match &decl.exported {
+                    Some(exported) => Item::ExportAllAs {
+                        span: decl.span,
+                        source,
+                        exported: exported.name().to_string(),
+                    },
+                    None => Item::ExportAll {
+                        span: decl.span,
+                        source,
+                    },
+                });
+            }
+            Statement::TSExportAssignment(assignment) => items.push(Item::Other {
+                span: assignment.span,
+                unsupported: Some("`export =`"),
+            }),
+            Statement::TSNamespaceExportDeclaration(decl) => items.push(Item::Other {
+                span: decl.span,
+                unsupported: Some("`export as namespace`"),
+            }),
+            _ => {}
+        }
+    }
+}
+
+/// Top-level names bound by an exported declaration.
+fn declaration_names(declaration: &Declaration<'_>) -> Vec<String> {
+    match declaration {
+        Declaration::VariableDeclaration(decl) => decl
+            .declarations
+            .iter()
+            .filter_map(|declarator| declarator.id.get_binding_identifier())
+            .map(|id| id.name.to_string())
+            .collect(),
+        _ => declaration
+            .id()
+            .map(|id| vec![id.name.to_string()])
+            .unwrap_or_default(),
+    }
+}
+
+/// Whether a declaration needs `declare` once its `export` is dropped;
+/// types and interfaces are fine without one.
+fn needs_declare(declaration: &Declaration<'_>) -> bool {
+    !declaration.declare()
+        && !declaration.is_type()
+        && !matches!(declaration, Declaration::TSImportEqualsDeclaration(_))
+}
+
+/// Names a module exports itself, which take precedence over `export *`.
+fn explicit_exports(module: &Module) -> FxHashSet<&str> {
+    let mut names = FxHashSet::default();
+    for item in &module.items {
+        match item {
+            Item::ExportDeclaration { names: pairs, .. } => {
+                names.extend(pairs.iter().map(|(exported, _)| exported.as_str()));
+            }
+            Item::ExportLocals { specifiers, .. } | Item::ReExport { specifiers, .. } => {
+                names.extend(specifiers.iter().map(|(_, exported)| exported.as_str()));
+            }
+            Item::ExportAllAs { exported, .. } => {
+                names.insert(exported.as_str());
+            }
+            _ => {}
+        }
+    }
+    names
+}
+
+/// Top-level names of the bundle.
+#[derive(Debug, Default)]
+struct Names {
+    used: FxHashSet<String>,
+    /// `(source, import, local)` for every external import, in first-use order
+    externals: Vec<(String, Import, String)>,
+}
+
+impl Names {
+    /// Claim `name`, or the first free `name$N`.
+    fn reserve(&mut self, name: &str) -> String {
+        if self.used.insert(name.to_string()) {
+            return name.to_string();
+        }
+        (1..)
+            .map(|n| format!("{}${}", name, n))
+            .find(|candidate| self.used.insert(candidate.clone()))
+            .expect("a free name exists")
+    }
+
+    /// The name a binding goes by in the bundle. External imports are
+    /// shared by every module importing them, under the first local name
+    /// they were given.
+    fn of(&mut self, binding: &Binding, preferred: &str) -> String {
+        match binding {
+            Binding::Local(name) => name.clone(),
+            Binding::External { source, import } => {
+                if let Some((_, _, local)) = self
+                    .externals
+                    .iter()
+                    .find(|(s, i, _)| s == source && i == import)
+                {
+                    return local.clone();
+                }
+                let local = self.reserve(preferred);
+                self.externals
+                    .push((source.clone(), import.clone(), local.clone()));
+                local
+            }
+        }
+    }
+
+    /// One import declaration per package, in first-use order.
+    fn render_imports(&self, side_effects: &[&str]) -> String {
+        let mut sources: Vec<&str> = Vec::new();
+        for (source, _, _) in &self.externals {
+            if !sources.contains(&source.as_str()) {
+                sources.push(source);
+            }
+        }
+
+        let mut code = String::new();
+        for &source in &sources {
+            let mut default = None;
+            let mut named = Vec::new();
+            for (_, import, local) in self.externals.iter().filter(|(s, _, _)| s == source) {
+                match import {
+                    Import::Namespace => {
+                        code.push_str(&format!("import * as {} from \"{}\";\n", local, source));
+                    }
+                    Import::Name(name) if name == "default" => default = Some(local),
+                    Import::Name(name) => named.push(specifier(name, local)),
+                }
+            }
+
+            let mut clauses = Vec::new();
+            if let Some(local) = default {
+                clauses.push(local.clone());
+            }
+            if !named.is_empty() {
+                clauses.push(format!("{{ {} }}", named.join(", ")));
+            }
+            if !clauses.is_empty() {
+                code.push_str(&format!(
+                    "import {} from \"{}\";\n",
+                    clauses.join(", "),
+                    source
+                ));
+            }
+        }
+
+        let mut seen = FxHashSet::default();
+        for &source in side_effects {
+            if !sources.contains(&source) && seen.insert(source) {
+                code.push_str(&format!("import \"{}\";\n", source));
+            }
+        }
+        code
+    }
+}
+
+/// `a`, or `a as b` when the names differ.
+fn specifier(from: &str, to: &str) -> String {
+    if from == to {
+        from.to_string()
+    } else {
+        format!("{} as {}", from, to)
+    }
+}
+
+/// Links the modules of one entry into a single file.
+struct Linker {
+    /// Dependencies first, the entry last
+    modules: Vec<Module>,
+    /// Final name of every top-level declaration, per module
+    finals: Vec<FxHashMap<String, String>>,
+    exports: Vec<Exports>,
+    names: Names,
+}
+
+impl Linker {
+    fn new(modules: Vec<Module>) -> Self {
+        let mut names = Names::default();
+        let mut finals = vec![FxHashMap::default(); modules.len()];
+
+        // The entry's names are public, so they are claimed first and never
+        // renamed
+        let entry = modules.len() - 1;
+        for index in std::iter::once(entry).chain(0..entry) {
+            for (name, _) in &modules[index].declared {
+                let final_name = names.reserve(name);
+                finals[index].insert(name.clone(), final_name);
+            }
+        }
+
+        Self {
+            exports: modules.iter().map(|_| Exports::default()).collect(),
+            modules,
+            finals,
+            names,
+        }
+    }
+
+    fn link(mut self) -> Result<String> {
+        self.resolve_exports();
+
+        let mut names = std::mem::take(&mut self.names);
+        let mut body = String::new();
+        for index in 0..self.modules.len() {
+            let code = self.render(index, &mut names)?;
+            let code = code.trim();
+            if !code.is_empty() {
+                body.push_str(code);
+                body.push('\n');
+            }
+        }
+
+        let side_effects: Vec<&str> = self
+            .modules
+            .iter()
+            .flat_map(|module| {
+                module
+                    .side_effects
+                    .iter()
+                    .filter(|source| !module.internal.contains_key(*source))
+                    .map(String::as_str)
+            })
+            .collect();
+        Ok(names.render_imports(&side_effects) + &body)
+    }
+
+    /// Fill in every module's exports. Cycles can make an export depend on
+    /// a module later in the order, so this repeats until nothing changes.
+    fn resolve_exports(&mut self) {
+        loop {
+            let mut changed = false;
+            for index in 0..self.modules.len() {
+                let (names, star_externals) = self.module_exports(index);
+                let exports = &mut self.exports[index];
+                for (name, binding) in names {
+                    if let Entry::Vacant(entry) = exports.names.entry(name) {
+                        entry.insert(binding);
+                        changed = true;
+                    }
+                }
+                for source in star_externals {
+                    if !exports.star_externals.contains(&source) {
+                        exports.star_externals.push(source);
+                        changed = true;
+                    }
+                }
+            }
+            if !changed {
+                break;
+            }
+        }
+    }
+
+    /// Exports of a module that can be resolved so far.
+    fn module_exports(&self, index: usize) -> (Vec<(String, Binding)>, Vec<String>) {
+        let module = &self.modules[index];
+        let explicit = explicit_exports(module);
+        let mut names = Vec::new();
+        let mut star_externals = Vec::new();
+
+        for item in &module.items {
+            match item {
+                Item::ExportDeclaration { names: pairs, .. } => {
+                    for (exported, local) in pairs {
+                        if let Some(binding) = self.local_binding(index, local) {
+                            names.push((exported.clone(), binding));
+                        }
+                    }
+                }
+                Item::ExportLocals { specifiers, .. } => {
+                    for (local, exported) in specifiers {
+                        if let Some(binding) = self.local_binding(index, local) {
+                            names.push((exported.clone(), binding));
+                        }
+                    }
+                }
+                Item::ReExport {
+                    source, specifiers, ..
+                } => {
+                    for (imported, exported) in specifiers {
+                        let import = Import::Name(imported.clone());
+                        if let Some(binding) = self.import_binding(index, source, &import) {
+                            names.push((exported.clone(), binding));
+                        }
+                    }
+                }
+                Item::ExportAllAs {
+                    source, exported, ..
+                } => {
+                    if !module.internal.contains_key(source) {
+                        let binding = Binding::External {
+                            source: source.clone(),
+                            import: Import::Namespace,
+                        };
+                        names.push((exported.clone(), binding));
+                    }
+                }
+                Item::ExportAll { source, .. } => match module.internal.get(source) {
+                    Some(&target) => {
+                        for (name, binding) in &self.exports[target].names {
+                            if name != "default" && !explicit.contains(name.as_str()) {
+                                names.push((name.clone(), binding.clone()));
+                            }
+                        }
+                        star_externals.extend(self.exports[target].star_externals.clone());
+                    }
+                    None => star_externals.push(source.clone()),
+                },
+                Item::Import(_) | Item::Other { .. } => {}
+            }
+        }
+
+        (names, star_externals)
+    }
+
+    /// What a top-level name of a module refers to.
+    fn local_binding(&self, index: usize, local: &str) -> Option<Binding> {
+        if let Some(final_name) = self.finals[index].get(local) {
+            return Some(Binding::Local(final_name.clone()));
+        }
+        let import = self.modules[index]
+            .imports
+            .iter()
+            .find(|import| import.local == local)?;
+        self.import_binding(index, &import.source, &import.import)
+    }
+
+    /// What an import refers to, or `None` if it can't be resolved (yet).
+    fn import_binding(&self, index: usize, source: &str, import: &Import) -> Option<Binding> {
+        let Some(&target) = self.modules[index].internal.get(source) else {
+            return Some(Binding::External {
+                source: source.to_string(),
+                import: import.clone(),
+            });
+        };
+        let Import::Name(name) = import else {
+            return None;
+        };
+        if let Some(binding) = self.exports[target].names.get(name) {
+            return Some(binding.clone());
+        }
+
+        // A name the module doesn't declare can only come from its
+        // `export * from 'package'`, if it has exactly one
+        match self.exports[target].star_externals.as_slice() {
+            [package] if name != "default" => Some(Binding::External {
+                source: package.clone(),
+                import: import.clone(),
+            }),
+            _ => None,
+        }
+    }
+
+    /// A module's code with imports removed and names linked.
+    fn render(&self, index: usize, names: &mut Names) -> Result<String> {
+        let module = &self.modules[index];
+        let is_entry = index == self.modules.len() - 1;
+        let mut statements: Vec<(Span, String)> = Vec::new();
+        let mut renames: Vec<(Span, String)> = Vec::new();
+
+        for import in &module.imports {
+            let binding = self
+                .import_binding(index, &import.source, &import.import)
+                .with_context(|| unresolved(module, &import.source, &import.import))?;
+            let name = names.of(&binding, &import.local);
+            if name != import.local {
+                renames.extend(import.references.iter().map(|&span| (span, name.clone())));
+            }
+        }
+        for (name, spans) in &module.declared {
+            let final_name = &self.finals[index][name];
+            if final_name != name {
+                renames.extend(spans.iter().map(|&span| (span, final_name.clone())));
+            }
+        }
+
+        // Names the entry has exported so far, so `export *` of the same
+        // name along several paths ends up exported once
+        let mut exported: FxHashSet<String> = explicit_exports(module)
+            .into_iter()
+            .map(str::to_string)
+            .collect();
+        let mut star_packages: FxHashSet<String> = FxHashSet::default();
+
+        for item in &module.items {
+            let (span, replacement) = match item {
+                Item::Import(span) => (*span, String::new()),
+                Item::ExportDeclaration {
+                    span,
+                    decl_start,
+                    needs_declare,
+                    ..
+                } => {
+                    if is_entry {
+                        continue;
+                    }
+                    let keyword = if *needs_declare { "declare " } else { "" };
+                    (Span::new(span.start, *decl_start), keyword.to_string())
+                }
+                Item::ExportLocals {
+                    span,
+                    specifiers,
+                    rewrite,
+                } => {
+                    if !is_entry {
+                        (*span, String::new())
+                    } else if *rewrite {
+                        let list = specifiers
+                            .iter()
+                            .map(|(local, exported)| {
+                                let name = match self.local_binding(index, local) {
+                                    Some(binding) => names.of(&binding, exported),
+                                    None => local.clone(),
+                                };
+                                specifier(&name, exported)
+                            })
+                            .collect::<Vec<_>>();
+                        (*span, export_list(&list))
+                    } else {
+                        continue;
+                    }
+                }
+                Item::ReExport {
+                    span,
+                    source,
+                    specifiers,
+                } => {
+                    if !is_entry {
+                        (*span, String::new())
+                    } else if module.internal.contains_key(source) {
+                        let mut list = Vec::new();
+                        for (imported, exported) in specifiers {
+                            let import = Import::Name(imported.clone());
+                            let binding = self
+                                .import_binding(index, source, &import)
+                                .with_context(|| unresolved(module, source, &import))?;
+                            list.push(specifier(&names.of(&binding, exported), exported));
+                        }
+                        (*span, export_list(&list))
+                    } else {
+                        continue;
+                    }
+                }
+                Item::ExportAll { span, source } => {
+                    if !is_entry {
+                        (*span, String::new())
+                    } else if let Some(&target) = module.internal.get(source) {
+                        let mut reexported: Vec<(&String, &Binding)> = self.exports[target]
+                            .names
+                            .iter()
+                            .filter(|(name, _)| *name != "default" && !exported.contains(*name))
+                            .collect();
+                        reexported.sort_by(|a, b| a.0.cmp(b.0));
+
+                        let mut list = Vec::new();
+                        for (name, binding) in reexported {
+                            exported.insert(name.clone());
+                            list.push(specifier(&names.of(binding, name), name));
+                        }
+                        let mut code = export_list(&list);
+                        for package in &self.exports[target].star_externals {
+                            if star_packages.insert(package.clone()) {
+                                code.push_str(&format!("\nexport * from \"{}\";", package));
+                            }
+                        }
+                        (*span, code)
+                    } else if star_packages.insert(source.clone()) {
+                        continue;
+                    } else {
+                        (*span, String::new())
+                    }
+                }
+                Item::ExportAllAs { span, source, .. } => {
+                    if module.internal.contains_key(source) {
+                        bail!(
+                            "`export * as` of {} in {} can't be bundled",
+                            source,
+                            module.id
+                        );
+                    }
+                    if is_entry {
+                        continue;
+                    }
+                    (*span, String::new())
+                }
+                Item::Other { span, unsupported } => {
+                    if is_entry {
+                        continue;
+                    }
+                    if let Some(what) = unsupported {
+                        bail!("{} in {} can't be bundled", what, module.id);
+                    }
+                    (*span, String::new())
+                }
+            };
+            statements.push((span, replacement));
+        }
+
+        // Names inside rewritten statements are already taken care of
+        renames.retain(|(span, _)| {
+            !statements
+                .iter()
+                .any(|(statement, _)| statement.start <= span.start && span.end <= statement.end)
+        });
+
+        let mut edits = statements;
+        edits.extend(renames);
+        edits.sort_by_key(|(span, _)| span.start);
+
+        let code = &module.code;
+        let mut out = String::with_capacity(code.len());
+        let mut last = 0;
+        for (span, replacement) in edits {
+            let mut end = span.end as usize;
+            // Removed statements take their line with them
+            if replacement.is_empty() && code[end..].starts_with('\n') {
+                end += 1;
+            }
+            out.push_str(&code[last..span.start as usize]);
+            out.push_str(&replacement);
+            last = end;
+        }
+        out.push_str(&code[last..]);
+        Ok(out)
+    }
+}
+
+/// `export { .. };`, or nothing for an empty list.
+fn export_list(specifiers: &[String]) -> String {
+    if specifiers.is_empty() {
+        String::new()
+    } else {
+        format!("export {{ {} }};", specifiers.join(", "))
+    }
+}
+
+/// Error message for an import that can't be linked.
+fn unresolved(module: &Module, source: &str, import: &Import) -> String {
+    match import {
+        Import::Namespace => format!(
+            "namespace import of {} in {} can't be bundled",
+            source, module.id
+        ),
+        Import::Name(name) => format!(
+            "{} imports {} from {}, which doesn't export it",
+            module.id, name, source
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Bundle in-memory declarations; specifiers are `./name` for `/src/name.d.ts`.
+    fn bundle(files: &[(&str, &str)]) -> Result<String> {
+        let files: FxHashMap<String, String> = files
+            .iter()
+            .map(|(name, code)| (format!("/src/{}.d.ts", name), code.to_string()))
+            .collect();
+        bundle_declarations(
+            "/src/index.d.ts",
+            |id| files.get(id).cloned().context("missing file"),
+            |_, specifier| Some(format!("/src/{}.d.ts", specifier.trim_start_matches("./"))),
+        )
+    }
+
+    #[test]
+    fn test_internal_imports_are_flattened() {
+        let code = bundle(&[
+            (
+                "index",
+                "import { Options } from \"./options\";\nexport declare function run(options: Options): void;\n",
+            ),
+            ("options", "export interface Options {\n\tdebug: boolean;\n}\n"),
+        ])
+        .unwrap();
+
+        assert!(!code.contains("./options"));
+        assert!(code.contains("interface Options"));
+        assert!(!code.contains("export interface Options"));
+        assert!(code.contains("export declare function run(options: Options): void;"));
+        assert!(code.find("interface Options") < code.find("function run"));
+    }
+
+    #[test]
+    fn test_conflicting_names_are_renamed() {
+        let code = bundle(&[
+            (
+                "index",
+                "import { makeA } from \"./a\";\nexport interface Config {\n\tb: number;\n}\nexport declare const a: ReturnType<typeof makeA>;\n",
+            ),
+            (
+                "a",
+                "export interface Config {\n\ta: string;\n}\nexport declare function makeA(): Config;\n",
+            ),
+        ])
+        .unwrap();
+
+        assert!(code.contains("interface Config$1 {\n\ta: string;"));
+        assert!(code.contains("declare function makeA(): Config$1;"));
+        assert!(code.contains("export interface Config {\n\tb: number;"));
+    }
+
+    #[test]
+    fn test_reexports_are_deduplicated() {
+        let code = bundle(&[
+            ("index", "export * from \"./a\";\nexport * from \"./b\";\n"),
+            (
+                "a",
+                "export * from \"./shared\";\nexport declare const a: number;\n",
+            ),
+            (
+                "b",
+                "export * from \"./shared\";\nexport declare const b: number;\n",
+            ),
+            ("shared", "export interface Shared {\n}\n"),
+        ])
+        .unwrap();
+
+        assert_eq!(code.matches("Shared").count(), 2, "{}", code);
+        assert!(code.contains("export { Shared, a };"));
+        assert!(code.contains("export { b };"));
+        assert!(code.contains("declare const a: number;"));
+    }
+
+    #[test]
+    fn test_external_imports_are_hoisted() {
+        let code = bundle(&[
+            (
+                "index",
+                "import type { ReactNode as Node } from \"react\";\nexport { render } from \"./a\";\nexport declare function wrap(node: Node): Node;\n",
+            ),
+            (
+                "a",
+                "import { ReactNode } from \"react\";\nexport declare function render(node: ReactNode): void;\n",
+            ),
+        ])
+        .unwrap();
+
+        assert_eq!(code.matches("from \"react\"").count(), 1, "{}", code);
+        assert!(code.starts_with("import { ReactNode } from \"react\";\n"));
+        assert!(code.contains("export declare function wrap(node: ReactNode): ReactNode;"));
+        assert!(code.contains("export { render };"));
+    }
+
+    #[test]
+    fn test_default_exports_are_linked() {
+        let code = bundle(&[
+            (
+                "index",
+                "import Widget from \"./widget\";\nexport declare function make(): Widget;\n",
+            ),
+            (
+                "widget",
+                "export default class Widget {\n\tname: string;\n}\n",
+            ),
+        ])
+        .unwrap();
+
+        assert!(code.contains("declare class Widget {"));
+        assert!(!code.contains("export default"));
+    }
+
+    #[test]
+    fn test_namespace_imports_of_bundled_modules_are_rejected() {
+        let error = bundle(&[
+            (
+                "index",
+                "import * as a from \"./a\";\nexport declare const x: a.A;\n",
+            ),
+            ("a", "export interface A {\n}\n"),
+        ])
+        .unwrap_err();
+
+        assert!(error.to_string().contains("namespace import"));
+    }
+}
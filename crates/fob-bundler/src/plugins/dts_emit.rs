@@ -21,6 +21,10 @@
 //! 3. For each TypeScript module, it uses OXC's `IsolatedDeclarations` to generate .d.ts
 //! 4. Generated .d.ts files are added as `OutputAsset` to the bundle
 //!
+//! With bundling enabled, step 3 starts from each entry chunk's entry module
+//! instead, follows its relative imports on disk, and flattens everything it
+//! reaches into one `.d.ts` per entry (see `dts_bundle`).
+//!
 //! ## Why `generate_bundle` hook?
 //!
 //! - Runs after all transformations are complete
@@ -28,12 +32,14 @@
 //! - Can add new assets to the bundle before writing
 //! - Efficient - runs once per bundle, not per module
 
+use super::dts_bundle;
 use anyhow::{Context, Result};
 use oxc_allocator::Allocator;
 use oxc_codegen::Codegen;
 use oxc_isolated_declarations::{IsolatedDeclarations, IsolatedDeclarationsOptions};
 use oxc_parser::Parser;
 use oxc_span::SourceType as OxcSourceType;
+use path_clean::PathClean;
 use rolldown_common::{Output, OutputAsset};
 use rolldown_plugin::{HookGenerateBundleArgs, HookNoopReturn, Plugin, PluginContext};
 use std::borrow::Cow;
@@ -49,6 +55,8 @@ pub struct DtsEmitPlugin {
     sourcemap: bool,
     /// Custom output directory for .d.ts files (relative to bundle output)
     dts_dir: Option<PathBuf>,
+    /// Emit one flattened .d.ts per entry instead of one per module
+    bundle: bool,
 }
 
 impl DtsEmitPlugin {
@@ -59,6 +67,7 @@ impl DtsEmitPlugin {
     /// * `strip_internal` - Remove declarations marked with @internal JSDoc tag
     /// * `sourcemap` - Generate .d.ts.map files for IDE navigation
     /// * `dts_dir` - Optional custom directory for .d.ts files
+    /// * `bundle` - Flatten each entry's declarations into a single file
     ///
     /// # Example
     ///
@@ -68,14 +77,21 @@ impl DtsEmitPlugin {
     /// let plugin = DtsEmitPlugin::new(
     ///     true,  // strip_internal
     ///     true,  // sourcemap
-    ///     Some("types".into())  // dts_dir
+    ///     Some("types".into()),  // dts_dir
+    ///     false  // bundle
     /// );
     /// ```
-    pub fn new(strip_internal: bool, sourcemap: bool, dts_dir: Option<PathBuf>) -> Self {
+    pub fn new(
+        strip_internal: bool,
+        sourcemap: bool,
+        dts_dir: Option<PathBuf>,
+        bundle: bool,
+    ) -> Self {
         Self {
             strip_internal,
             sourcemap,
             dts_dir,
+            bundle,
         }
     }
 }
@@ -103,8 +119,16 @@ impl Plugin for DtsEmitPlugin {
         let strip_internal = self.strip_internal;
         let sourcemap = self.sourcemap;
         let dts_dir = self.dts_dir.clone();
+        let bundle = self.bundle;
 
         async move {
+            if bundle {
+                let dts_assets =
+                    bundle_entries(args.bundle.as_slice(), strip_internal, dts_dir.as_deref());
+                args.bundle.extend(dts_assets);
+                return Ok(());
+            }
+
             let mut dts_assets = Vec::new();
 
             // Iterate through all outputs in the bundle
@@ -186,6 +210,93 @@ impl Plugin for DtsEmitPlugin {
     }
 }
 
+/// Generate one flattened .d.ts per TypeScript entry chunk.
+fn bundle_entries(bundle: &[Output], strip_internal: bool, dts_dir: Option<&Path>) -> Vec<Output> {
+    let mut dts_assets = Vec::new();
+
+    for output in bundle {
+        let Output::Chunk(chunk) = output else {
+            continue;
+        };
+        let Some(entry) = chunk.facade_module_id.as_ref().filter(|_| chunk.is_entry) else {
+            continue;
+        };
+        if !is_typescript_module(entry.as_ref()) {
+            continue;
+        }
+
+        let load = |id: &str| -> Result<String> {
+            let source =
+                std::fs::read_to_string(id).with_context(|| format!("Failed to read {}", id))?;
+            if id.ends_with(".d.ts") {
+                Ok(source)
+            } else {
+                generate_dts(&source, id, strip_internal)
+            }
+        };
+        let dts_content =
+            match dts_bundle::bundle_declarations(entry.as_ref(), load, resolve_declaration_source)
+            {
+                Ok(content) => content,
+                Err(e) => {
+                    // Same policy as per-module output: warn and keep building
+                    eprintln!(
+                        "Warning: Failed to bundle .d.ts for {}: {:#}",
+                        entry.as_ref(),
+                        e
+                    );
+                    continue;
+                }
+            };
+
+        let asset = OutputAsset {
+            names: vec![],
+            original_file_names: vec![entry.to_string()],
+            filename: compute_dts_filename(&chunk.filename, entry.as_ref(), dts_dir).into(),
+            source: dts_content.into(),
+        };
+        dts_assets.push(Output::Asset(Arc::new(asset)));
+    }
+
+    dts_assets
+}
+
+/// Resolve a relative import to the TypeScript source or declaration file
+/// it refers to, trying the extensions TypeScript itself would.
+fn resolve_declaration_source(importer: &str, specifier: &str) -> Option<String> {
+    let base = Path::new(importer).parent()?.join(specifier).clean();
+
+    // `./util.js` in TypeScript source refers to `./util.ts`
+    let stem = match base.extension().and_then(|ext| ext.to_str()) {
+        Some(ext @ ("js" | "mjs" | "cjs" | "jsx")) => {
+            let ts_ext = match ext {
+                "mjs" => "mts",
+                "cjs" => "cts",
+                "jsx" => "tsx",
+                _ => "ts",
+            };
+            return [base.with_extension(ts_ext), base.with_extension("d.ts")]
+                .into_iter()
+                .find(|candidate| candidate.is_file())
+                .map(|path| path.to_string_lossy().into_owned());
+        }
+        Some(_) if is_typescript_module(&base.to_string_lossy()) && base.is_file() => {
+            return Some(base.to_string_lossy().into_owned());
+        }
+        _ => base.to_string_lossy().into_owned(),
+    };
+
+    ["ts", "tsx", "d.ts", "mts", "cts"]
+        .iter()
+        .map(|ext| format!("{}.{}", stem, ext))
+        .chain(
+            ["index.ts", "index.tsx", "index.d.ts"]
+                .iter()
+                .map(|index| format!("{}/{}", stem, index)),
+        )
+        .find(|candidate| Path::new(candidate).is_file())
+}
+
 /// Check if a module path is a TypeScript file
 fn is_typescript_module(path: &str) -> bool {
     Path::new(path)
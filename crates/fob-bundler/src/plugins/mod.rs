@@ -6,10 +6,12 @@
 
 pub(crate) mod registry;
 
+#[cfg(feature = "dts-generation")]
+mod dts_bundle;
 #[cfg(feature = "dts-generation")]
 pub(crate) mod dts_emit;
 
-pub(crate) use registry::{FobPlugin, PluginPhase, PluginRegistry};
+pub(crate) use registry::{FobPlugin, PluginPhase, PluginRegistry, builtin_plugins};
 
 #[cfg(feature = "dts-generation")]
 pub(crate) use dts_emit::DtsEmitPlugin;
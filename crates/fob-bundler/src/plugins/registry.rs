@@ -62,6 +62,72 @@ pub(crate) trait FobPlugin: Plugin {
     }
 }
 
+/// A plugin that ships with fob-bundler
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct BuiltinPlugin {
+    /// Name the plugin reports to Rolldown
+    pub name: &'static str,
+    /// What the plugin handles
+    pub description: &'static str,
+}
+
+/// Built-in plugins available in this build.
+///
+/// This is what [`capabilities()`](crate::capabilities) reports. Plugins the
+/// bundler registers itself go through [`PluginRegistry::add`] or
+/// [`PluginRegistry::add_builtin`], which check in debug builds that they
+/// are listed here.
+pub(crate) fn builtin_plugins() -> Vec<BuiltinPlugin> {
+    let mut plugins = vec![
+        BuiltinPlugin {
+            name: "fob-runtime-files",
+            description: "Module loading through the configured Runtime",
+        },
+        BuiltinPlugin {
+            name: "fob-virtual-modules",
+            description: "Built-in `fob:` virtual modules",
+        },
+        BuiltinPlugin {
+            name: "fob:replace",
+            description: "AST-aware replacement and injection of globals",
+        },
+        BuiltinPlugin {
+            name: "fob:asset-handler",
+            description: "Static asset imports (images, fonts, wasm)",
+        },
+        BuiltinPlugin {
+            name: "fob-top-level-await",
+            description: "Top-level await checks and chunk isolation",
+        },
+        BuiltinPlugin {
+            name: "module-collection-plugin",
+            description: "Module data collection for graph analysis",
+        },
+        BuiltinPlugin {
+            name: "fob-css",
+            description: "CSS imports bundled with Lightning CSS",
+        },
+    ];
+    if cfg!(not(target_family = "wasm")) {
+        plugins.push(BuiltinPlugin {
+            name: "fob:workers",
+            description: "Separate bundles for workers and worklets",
+        });
+    }
+    if cfg!(feature = "dts-generation") {
+        plugins.push(BuiltinPlugin {
+            name: "fob-dts-emit",
+            description: "TypeScript declaration (.d.ts) emission",
+        });
+    }
+    plugins
+}
+
+/// Whether `name` is listed in [`builtin_plugins`]
+fn is_builtin(name: &str) -> bool {
+    builtin_plugins().iter().any(|plugin| plugin.name == name)
+}
+
 /// Plugin registry that maintains plugins in phase order
 pub(crate) struct PluginRegistry {
     plugins: Vec<(PluginPhase, SharedPluginable)>,
@@ -80,6 +146,11 @@ impl PluginRegistry {
     /// The plugin will be inserted based on its phase. Sorting happens once
     /// when converting to Rolldown plugins via `into_rolldown_plugins()`.
     pub fn add<P: FobPlugin + 'static>(&mut self, plugin: P) {
+        debug_assert!(
+            is_builtin(&plugin.name()),
+            "built-in plugin {} is missing from builtin_plugins()",
+            plugin.name()
+        );
         let phase = plugin.phase();
         let plugin_arc: SharedPluginable = Arc::new(plugin);
         self.plugins.push((phase, plugin_arc));
@@ -93,6 +164,19 @@ impl PluginRegistry {
         self.plugins.push((phase, plugin));
     }
 
+    /// Add a plugin that ships with fob-bundler, with an explicit phase
+    ///
+    /// Like [`PluginRegistry::add_with_phase`], but checks in debug builds that
+    /// the plugin is listed in [`builtin_plugins`].
+    pub fn add_builtin(&mut self, plugin: SharedPluginable, phase: PluginPhase) {
+        debug_assert!(
+            is_builtin(&plugin.call_name()),
+            "built-in plugin {} is missing from builtin_plugins()",
+            plugin.call_name()
+        );
+        self.plugins.push((phase, plugin));
+    }
+
     /// Convert to Rolldown plugins in correct order
     ///
     /// Returns a vector of `SharedPluginable` sorted by phase.
@@ -87,6 +87,22 @@ impl ExportConditions {
     pub fn contains(&self, name: &str) -> bool {
         self.as_slice().contains(&name)
     }
+
+    /// Combine custom conditions with this preset
+    ///
+    /// Custom conditions come first and duplicates are dropped, so
+    /// `["worker", "browser"]` over `Browser` gives
+    /// `["worker", "browser", "import", "module", "default"]`.
+    pub fn with_custom(&self, custom: &[String]) -> Vec<String> {
+        let mut conditions: Vec<String> = Vec::with_capacity(custom.len() + 6);
+        let preset = self.as_slice().iter().map(|s| (*s).to_string());
+        for condition in custom.iter().cloned().chain(preset) {
+            if !conditions.contains(&condition) {
+                conditions.push(condition);
+            }
+        }
+        conditions
+    }
 }
 
 /// What to do with Node.js built-in modules
@@ -114,6 +130,14 @@ pub trait DeploymentTarget: Send + Sync {
     /// Export conditions for module resolution
     fn conditions(&self) -> ExportConditions;
 
+    /// Conditions added ahead of the preset (e.g. `"deno"`)
+    ///
+    /// These are merged with conditions the user configured rather than
+    /// replacing them.
+    fn custom_conditions(&self) -> Vec<String> {
+        vec![]
+    }
+
     /// How to handle Node.js built-ins
     fn node_builtins(&self) -> NodeBuiltins;
 
@@ -122,6 +146,16 @@ pub trait DeploymentTarget: Send + Sync {
         vec![]
     }
 
+    /// Output format the target requires, if any
+    fn output_format(&self) -> Option<crate::OutputFormat> {
+        None
+    }
+
+    /// Plugins added to the build, e.g. to resolve runtime-specific specifiers
+    fn plugins(&self) -> Vec<crate::SharedPluginable> {
+        vec![]
+    }
+
     /// Generate platform-specific output files
     ///
     /// This method is called after a successful build to generate any
@@ -1,4 +1,4 @@
-use fob_bundler::{BuildOptions, NativeRuntime};
+use fob_bundler::{BuildOptions, ManualChunks, NativeRuntime};
 use std::sync::Arc;
 use tempfile::TempDir;
 
@@ -74,6 +74,40 @@ async fn app_builder_produces_analysis_bundle() {
     );
 }
 
+#[tokio::test]
+async fn app_builder_applies_manual_chunks() {
+    let project = create_app_project();
+
+    let result = BuildOptions::new_multiple([
+        project.path().join("src/main.js"),
+        project.path().join("src/dashboard.js"),
+    ])
+    .bundle_together()
+    .outdir(project.path().join("dist"))
+    .manual_chunks(ManualChunks::new(|id| {
+        id.ends_with("shared.js").then(|| "shared-lib".to_string())
+    }))
+    .cwd(project.path())
+    .runtime(Arc::new(NativeRuntime::new()))
+    .build()
+    .await
+    .expect("app bundle");
+
+    let chunk = result
+        .chunks()
+        .find(|chunk| chunk.name.as_str() == "shared-lib")
+        .expect("manual chunk emitted");
+    assert!(chunk.module_ids.iter().any(|id| id.ends_with("shared.js")));
+}
+
+#[test]
+fn manual_chunks_require_shared_entries() {
+    let options = BuildOptions::new_multiple(["src/a.js", "src/b.js"])
+        .bundle_separately()
+        .manual_chunks(ManualChunks::new(|_| None));
+    assert!(options.validate().is_err());
+}
+
 /// Test that bundler handles circular dependencies without hanging or crashing
 #[tokio::test]
 async fn app_builder_handles_circular_deps() {
@@ -0,0 +1,40 @@
+//! Tests for chunk size warnings.
+
+use fob_bundler::BuildOptions;
+use tempfile::TempDir;
+
+#[tokio::test]
+async fn oversized_chunk_produces_warning() {
+    let temp = TempDir::new().unwrap();
+
+    let result = BuildOptions::new("virtual:entry.js")
+        .virtual_file("virtual:entry.js", "export const x = 'some content';")
+        .chunk_size_warning_limit(1)
+        .disable_slow_module_warnings()
+        .cwd(temp.path())
+        .outfile(temp.path().join("out.js"))
+        .build()
+        .await
+        .unwrap();
+
+    let warnings = &result.analysis.warnings;
+    assert_eq!(warnings.len(), 1, "unexpected warnings: {:?}", warnings);
+    assert!(warnings[0].starts_with("Chunk "));
+    assert!(warnings[0].contains("Largest modules:"));
+}
+
+#[tokio::test]
+async fn small_chunk_has_no_warning() {
+    let temp = TempDir::new().unwrap();
+
+    let result = BuildOptions::new("virtual:entry.js")
+        .virtual_file("virtual:entry.js", "export const x = 1;")
+        .disable_slow_module_warnings()
+        .cwd(temp.path())
+        .outfile(temp.path().join("out.js"))
+        .build()
+        .await
+        .unwrap();
+
+    assert!(result.analysis.warnings.is_empty());
+}
@@ -0,0 +1,170 @@
+//! Tests for custom export conditions.
+
+use fob_bundler::{
+    BuildConfig, BuildOptions, BuildResult, DeploymentTarget, ExportConditions, NodeBuiltins,
+    RuntimeEnvironment,
+};
+use std::fs;
+use tempfile::TempDir;
+
+/// Project importing a package whose `exports` has a file per condition.
+fn project() -> TempDir {
+    let temp = TempDir::new().unwrap();
+    fs::create_dir_all(temp.path().join("src")).unwrap();
+    fs::write(
+        temp.path().join("src/index.js"),
+        "import { flavor } from 'flavors';\nconsole.log(flavor);\n",
+    )
+    .unwrap();
+
+    let package = temp.path().join("node_modules/flavors");
+    fs::create_dir_all(&package).unwrap();
+    fs::write(
+        package.join("package.json"),
+        r#"{
+  "name": "flavors",
+  "exports": {
+    "my-framework": "./framework.js",
+    "deno": "./deno.js",
+    "browser": "./browser.js",
+    "default": "./default.js"
+  }
+}"#,
+    )
+    .unwrap();
+    for flavor in ["framework", "deno", "browser", "default"] {
+        fs::write(
+            package.join(format!("{}.js", flavor)),
+            format!("export const flavor = 'flavor-{}';\n", flavor),
+        )
+        .unwrap();
+    }
+    temp
+}
+
+fn entry_code(result: &BuildResult) -> String {
+    result.entry_chunks().next().unwrap().code.to_string()
+}
+
+async fn build(temp: &TempDir, options: BuildOptions) -> String {
+    let result = options
+        .disable_slow_module_warnings()
+        .sourcemap(false)
+        .cwd(temp.path())
+        .outdir(temp.path().join("dist"))
+        .build()
+        .await
+        .unwrap();
+    entry_code(&result)
+}
+
+#[test]
+fn test_custom_conditions_come_before_preset() {
+    let conditions = ExportConditions::browser().with_custom(&["worker".into(), "browser".into()]);
+
+    assert_eq!(
+        conditions,
+        ["worker", "browser", "import", "module", "default"]
+    );
+}
+
+#[test]
+fn test_empty_condition_is_rejected() {
+    let options = BuildOptions::new("src/index.js").condition(" ");
+
+    assert!(options.validate().is_err());
+}
+
+#[tokio::test]
+async fn test_platform_preset_by_default() {
+    let temp = project();
+
+    let code = build(&temp, BuildOptions::new(temp.path().join("src/index.js"))).await;
+
+    assert!(code.contains("flavor-browser"), "{}", code);
+}
+
+#[tokio::test]
+async fn test_custom_condition_selects_export() {
+    let temp = project();
+
+    let code = build(
+        &temp,
+        BuildOptions::new(temp.path().join("src/index.js")).condition("my-framework"),
+    )
+    .await;
+
+    assert!(code.contains("flavor-framework"), "{}", code);
+}
+
+#[tokio::test]
+async fn test_preset_can_be_overridden() {
+    let temp = project();
+
+    let code = build(
+        &temp,
+        BuildOptions::new(temp.path().join("src/index.js"))
+            .export_conditions(ExportConditions::node()),
+    )
+    .await;
+
+    assert!(code.contains("flavor-default"), "{}", code);
+}
+
+struct DenoLike;
+
+impl DeploymentTarget for DenoLike {
+    fn name(&self) -> &'static str {
+        "deno-like"
+    }
+
+    fn runtime(&self) -> RuntimeEnvironment {
+        RuntimeEnvironment::EdgeWorker
+    }
+
+    fn conditions(&self) -> ExportConditions {
+        ExportConditions::edge()
+    }
+
+    fn custom_conditions(&self) -> Vec<String> {
+        vec!["deno".to_string()]
+    }
+
+    fn node_builtins(&self) -> NodeBuiltins {
+        NodeBuiltins::External
+    }
+}
+
+async fn build_config(temp: &TempDir, config: BuildConfig) -> String {
+    let result = config
+        .cwd(temp.path())
+        .output_dir(temp.path().join("dist"))
+        .sourcemap(false)
+        .build()
+        .await
+        .unwrap();
+    entry_code(&result)
+}
+
+#[tokio::test]
+async fn test_target_conditions_compose_with_user_conditions() {
+    let temp = project();
+    let entry = temp.path().join("src/index.js");
+
+    let code = build_config(
+        &temp,
+        BuildConfig::new(entry.as_path()).for_target(&DenoLike),
+    )
+    .await;
+    assert!(code.contains("flavor-deno"), "{}", code);
+
+    // Conditions set before the target is applied are kept
+    let code = build_config(
+        &temp,
+        BuildConfig::new(entry.as_path())
+            .condition("my-framework")
+            .for_target(&DenoLike),
+    )
+    .await;
+    assert!(code.contains("flavor-framework"), "{}", code);
+}
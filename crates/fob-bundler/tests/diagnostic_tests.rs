@@ -0,0 +1,86 @@
+//! Tests for the binding-neutral diagnostic format.
+
+use fob_bundler::Error;
+use fob_bundler::diagnostics::{
+    Diagnostic, DiagnosticKind, DiagnosticSeverity, ExtractedDiagnostic,
+};
+
+fn extracted(kind: DiagnosticKind, message: &str, file: &str, line: u32) -> ExtractedDiagnostic {
+    ExtractedDiagnostic {
+        kind,
+        severity: DiagnosticSeverity::Error,
+        message: message.to_string(),
+        file: Some(file.to_string()),
+        line: Some(line),
+        column: Some(5),
+        help: Some("check the import".to_string()),
+        context: None,
+        error_chain: Vec::new(),
+    }
+}
+
+#[test]
+fn bundler_error_keeps_location_and_related() {
+    let error = Error::Bundler(vec![
+        extracted(
+            DiagnosticKind::UnresolvedImport,
+            "Cannot resolve 'x'",
+            "a.js",
+            3,
+        ),
+        extracted(
+            DiagnosticKind::Other("Could not parse this".to_string()),
+            "Unexpected token",
+            "b.js",
+            7,
+        ),
+    ]);
+
+    let json = serde_json::to_value(error.to_diagnostic()).unwrap();
+
+    assert_eq!(json["kind"], "UnresolvedImport");
+    assert_eq!(json["severity"], "error");
+    assert_eq!(json["file"], "a.js");
+    assert_eq!(json["line"], 3);
+    assert_eq!(json["column"], 5);
+    assert_eq!(json["help"], "check the import");
+    assert_eq!(json["related"][0]["kind"], "Other");
+    assert_eq!(json["related"][0]["line"], 7);
+}
+
+#[test]
+fn config_error_has_kind_and_help() {
+    let error = Error::AssetTooLarge {
+        path: "logo.png".to_string(),
+        size: 20,
+        max_size: 10,
+    };
+
+    let diagnostic = error.to_diagnostic();
+    assert_eq!(diagnostic.kind, "AssetTooLarge");
+    assert_eq!(diagnostic.file.as_deref(), Some("logo.png"));
+    assert!(diagnostic.help.is_some());
+
+    let json = serde_json::to_value(&diagnostic).unwrap();
+    assert!(json.get("line").is_none());
+    assert!(json.get("related").is_none());
+}
+
+#[test]
+fn mdx_syntax_error_uses_suggestion_as_help() {
+    let diagnostic = Diagnostic::mdx_syntax(
+        "Unexpected character",
+        Some("post.mdx".to_string()),
+        Some(4),
+        Some(2),
+        Some("Escape `<` as `&lt;`".to_string()),
+    );
+
+    let json = serde_json::to_value(&diagnostic).unwrap();
+    assert_eq!(json["kind"], "MdxSyntax");
+    assert_eq!(json["severity"], "error");
+    assert_eq!(json["file"], "post.mdx");
+    assert_eq!(json["line"], 4);
+    assert_eq!(json["column"], 2);
+    assert_eq!(json["help"], "Escape `<` as `&lt;`");
+}
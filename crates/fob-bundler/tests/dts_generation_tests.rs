@@ -294,3 +294,60 @@ export function Button(props: ButtonProps): JSX.Element {
     assert!(dts_content.contains("ButtonProps"));
     assert!(dts_content.contains("Button"));
 }
+
+#[tokio::test]
+async fn test_library_bundles_declarations_per_entry() {
+    let dir = TempDir::new().expect("Failed to create temp dir");
+    let src = dir.path().join("src");
+    fs::create_dir(&src).expect("Failed to create src dir");
+    fs::write(
+        src.join("index.ts"),
+        r#"
+import type { Options } from "./options";
+export * from "./format";
+
+export function run(options: Options): string {
+    return options.name;
+}
+"#,
+    )
+    .expect("Failed to write index.ts");
+    fs::write(
+        src.join("options.ts"),
+        "export interface Options {\n    name: string;\n}\n",
+    )
+    .expect("Failed to write options.ts");
+    fs::write(
+        src.join("format.ts"),
+        "export function format(value: number): string {\n    return value.toFixed(2);\n}\n",
+    )
+    .expect("Failed to write format.ts");
+
+    let result = fob::BuildOptions::new(src.join("index.ts"))
+        .externalize_from("package.json")
+        .platform(Platform::Node)
+        .emit_dts(true)
+        .bundle_dts(true)
+        .cwd(dir.path())
+        .sourcemap(false)
+        .build()
+        .await
+        .expect("Failed to bundle");
+
+    let bundle = result.output.as_single().expect("single bundle");
+    let dts_assets: Vec<_> = bundle
+        .assets
+        .iter()
+        .filter(|a| a.filename().ends_with(".d.ts"))
+        .collect();
+
+    assert_eq!(dts_assets.len(), 1, "Should emit one .d.ts per entry");
+    assert_eq!(dts_assets[0].filename(), "index.d.ts");
+
+    let dts_content = String::from_utf8_lossy(dts_assets[0].content_as_bytes());
+    assert!(!dts_content.contains("./options"));
+    assert!(!dts_content.contains("./format"));
+    assert!(dts_content.contains("interface Options"));
+    assert!(dts_content.contains("export declare function run(options: Options): string;"));
+    assert!(dts_content.contains("export { format };"));
+}
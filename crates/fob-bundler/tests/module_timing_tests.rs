@@ -0,0 +1,44 @@
+//! Tests for slow-module timing warnings.
+
+use fob_bundler::BuildOptions;
+use std::time::Duration;
+use tempfile::TempDir;
+
+#[tokio::test]
+async fn zero_threshold_reports_every_module() {
+    let temp = TempDir::new().unwrap();
+
+    let result = BuildOptions::new("virtual:entry.js")
+        .virtual_file("virtual:entry.js", "export const x = 1;")
+        .slow_module_threshold(Duration::ZERO)
+        .cwd(temp.path())
+        .outfile(temp.path().join("out.js"))
+        .build()
+        .await
+        .unwrap();
+
+    let warnings = &result.analysis.warnings;
+    assert!(
+        warnings
+            .iter()
+            .any(|w| w.starts_with("Slow module: virtual:entry.js")),
+        "expected slow-module warning, got {:?}",
+        warnings
+    );
+}
+
+#[tokio::test]
+async fn disabled_timing_reports_nothing() {
+    let temp = TempDir::new().unwrap();
+
+    let result = BuildOptions::new("virtual:entry.js")
+        .virtual_file("virtual:entry.js", "export const x = 1;")
+        .disable_slow_module_warnings()
+        .cwd(temp.path())
+        .outfile(temp.path().join("out.js"))
+        .build()
+        .await
+        .unwrap();
+
+    assert!(result.analysis.warnings.is_empty());
+}
@@ -0,0 +1,50 @@
+//! Tests for entry, chunk and asset file name templates.
+
+use fob_bundler::{BuildOptions, Error};
+use tempfile::TempDir;
+
+fn options(temp: &TempDir) -> BuildOptions {
+    BuildOptions::new("virtual:entry.js")
+        .virtual_file("virtual:entry.js", "export const value = 1;")
+        .disable_slow_module_warnings()
+        .sourcemap(false)
+        .cwd(temp.path())
+        .outdir(temp.path().join("dist"))
+}
+
+#[tokio::test]
+async fn entry_names_template_is_applied() {
+    let temp = TempDir::new().unwrap();
+
+    let result = options(&temp)
+        .entry_names("js/[name]-[hash].[ext]")
+        .build()
+        .await
+        .unwrap();
+
+    let chunk = result.chunks().next().unwrap();
+    assert!(
+        chunk.filename.starts_with("js/") && chunk.filename.ends_with(".js"),
+        "unexpected file name: {}",
+        chunk.filename
+    );
+    assert!(!chunk.filename.contains('['));
+}
+
+#[tokio::test]
+async fn templates_outside_outdir_are_rejected() {
+    let temp = TempDir::new().unwrap();
+
+    let result = options(&temp).chunk_names("../[name].js").build().await;
+
+    assert!(matches!(result, Err(Error::InvalidConfig(_))));
+}
+
+#[tokio::test]
+async fn dir_is_only_available_for_assets() {
+    let temp = TempDir::new().unwrap();
+
+    let result = options(&temp).entry_names("[dir]/[name].js").build().await;
+
+    assert!(matches!(result, Err(Error::InvalidConfig(_))));
+}
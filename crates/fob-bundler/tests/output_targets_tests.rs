@@ -0,0 +1,70 @@
+//! Tests for rendering one build into several output targets.
+
+use fob_bundler::{BuildOptions, Error, OutputFormat, OutputTarget};
+use tempfile::TempDir;
+
+fn options(temp: &TempDir) -> BuildOptions {
+    BuildOptions::new("virtual:entry.js")
+        .virtual_file("virtual:entry.js", "export const value = 1;")
+        .disable_slow_module_warnings()
+        .sourcemap(false)
+        .cwd(temp.path())
+        .outdir(temp.path().join("dist"))
+}
+
+#[tokio::test]
+async fn each_target_gets_its_own_bundle() {
+    let temp = TempDir::new().unwrap();
+
+    let result = options(&temp)
+        .add_output(OutputTarget::new("esm", OutputFormat::Esm))
+        .add_output(OutputTarget::new("cjs", OutputFormat::Cjs))
+        .build()
+        .await
+        .unwrap();
+
+    let bundles = result.output.as_multiple().expect("one bundle per target");
+    assert_eq!(bundles.len(), 2);
+
+    let code = |name: &str| {
+        bundles[name]
+            .assets
+            .iter()
+            .find_map(|output| match output {
+                fob_bundler::Output::Chunk(chunk) => Some(chunk.code.clone()),
+                fob_bundler::Output::Asset(_) => None,
+            })
+            .unwrap()
+    };
+    assert!(code("esm").contains("export {"));
+    assert!(code("cjs").contains("exports"));
+
+    result.write_to_force(temp.path().join("dist")).unwrap();
+    assert!(temp.path().join("dist/esm").is_dir());
+    assert!(temp.path().join("dist/cjs").is_dir());
+}
+
+#[tokio::test]
+async fn duplicate_target_names_are_rejected() {
+    let temp = TempDir::new().unwrap();
+
+    let result = options(&temp)
+        .add_output(OutputTarget::new("dist", OutputFormat::Esm))
+        .add_output(OutputTarget::new("dist", OutputFormat::Cjs))
+        .build()
+        .await;
+
+    assert!(matches!(result, Err(Error::InvalidConfig(_))));
+}
+
+#[tokio::test]
+async fn target_names_must_be_directory_names() {
+    let temp = TempDir::new().unwrap();
+
+    let result = options(&temp)
+        .add_output(OutputTarget::new("../esm", OutputFormat::Esm))
+        .build()
+        .await;
+
+    assert!(matches!(result, Err(Error::InvalidConfig(_))));
+}
@@ -0,0 +1,56 @@
+//! Tests for post-emit output validation.
+
+use fob_bundler::{BuildOptions, Error, SyntaxTarget};
+use tempfile::TempDir;
+
+fn options(temp: &TempDir, code: &str) -> BuildOptions {
+    BuildOptions::new("virtual:entry.js")
+        .virtual_file("virtual:entry.js", code)
+        .cwd(temp.path())
+        .outfile(temp.path().join("out.js"))
+}
+
+#[tokio::test]
+async fn valid_output_passes() {
+    let temp = TempDir::new().unwrap();
+
+    let result = options(&temp, "export const value = globalThis.config ?? {};")
+        .validate_output(SyntaxTarget::Es2020)
+        .build()
+        .await;
+
+    assert!(result.is_ok(), "{:?}", result.err());
+}
+
+#[tokio::test]
+async fn syntax_above_target_fails_with_location() {
+    let temp = TempDir::new().unwrap();
+
+    let result = options(&temp, "export const value = globalThis.config ?? {};")
+        .validate_output(SyntaxTarget::Es2019)
+        .build()
+        .await;
+
+    let Err(Error::Bundler(diagnostics)) = result else {
+        panic!("expected validation failure");
+    };
+    assert_eq!(diagnostics.len(), 1);
+    assert!(
+        diagnostics[0]
+            .message
+            .contains("nullish coalescing requires es2020")
+    );
+    assert!(diagnostics[0].file.is_some());
+    assert!(diagnostics[0].line.is_some());
+}
+
+#[tokio::test]
+async fn validation_is_off_by_default() {
+    let temp = TempDir::new().unwrap();
+
+    let result = options(&temp, "export const value = globalThis.config ?? {};")
+        .build()
+        .await;
+
+    assert!(result.is_ok());
+}
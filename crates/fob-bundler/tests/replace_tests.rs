@@ -0,0 +1,68 @@
+//! Tests for build-time replacement and injection of globals.
+
+use fob_bundler::{BuildOptions, Error, InjectImport};
+use tempfile::TempDir;
+
+#[tokio::test]
+async fn replaces_globals_but_not_strings_or_locals() {
+    let temp = TempDir::new().unwrap();
+
+    let result = BuildOptions::new("virtual:entry.js")
+        .virtual_file(
+            "virtual:entry.js",
+            "const label = 'process.env.NODE_ENV';\n\
+             function check(__DEV__) { return __DEV__ + label; }\n\
+             console.log(process.env.NODE_ENV, __DEV__, check(1));",
+        )
+        .replace("process.env.NODE_ENV", "\"production\"")
+        .replace("__DEV__", "false")
+        .cwd(temp.path())
+        .outfile(temp.path().join("out.js"))
+        .build()
+        .await
+        .unwrap();
+
+    let chunk = result.output.chunks().next().expect("chunk");
+    assert!(chunk.code.contains("console.log(\"production\", false"));
+    assert!(
+        chunk.code.contains("'process.env.NODE_ENV'")
+            || chunk.code.contains("\"process.env.NODE_ENV\"")
+    );
+    assert!(chunk.code.contains("__DEV__ + label"));
+}
+
+#[tokio::test]
+async fn injects_imports_for_used_globals() {
+    let temp = TempDir::new().unwrap();
+
+    let result = BuildOptions::new("virtual:entry.js")
+        .virtual_file("virtual:entry.js", "console.log(Buffer.from('hi').length);")
+        .virtual_file(
+            "virtual:buffer.js",
+            "export const Buffer = { from: (s) => ({ length: s.length, polyfilled: true }) };",
+        )
+        .inject("Buffer", InjectImport::named("virtual:buffer.js", "Buffer"))
+        .cwd(temp.path())
+        .outfile(temp.path().join("out.js"))
+        .build()
+        .await
+        .unwrap();
+
+    let chunk = result.output.chunks().next().expect("chunk");
+    assert!(chunk.code.contains("polyfilled: true"));
+}
+
+#[tokio::test]
+async fn invalid_replacement_is_a_config_error() {
+    let temp = TempDir::new().unwrap();
+
+    let result = BuildOptions::new("virtual:entry.js")
+        .virtual_file("virtual:entry.js", "console.log(__DEV__);")
+        .replace("__DEV__", "if (true)")
+        .cwd(temp.path())
+        .outfile(temp.path().join("out.js"))
+        .build()
+        .await;
+
+    assert!(matches!(result, Err(Error::InvalidConfig(_))));
+}
@@ -0,0 +1,74 @@
+//! Tests for source map options.
+
+use fob_bundler::{BuildOptions, SourceMapOptions};
+use tempfile::TempDir;
+
+fn options(temp: &TempDir) -> BuildOptions {
+    BuildOptions::new("virtual:entry.js")
+        .virtual_file("virtual:entry.js", "export const value = 1;")
+        .disable_slow_module_warnings()
+        .cwd(temp.path())
+        .outfile(temp.path().join("out.js"))
+}
+
+fn map_json(result: &fob_bundler::BuildResult) -> serde_json::Value {
+    let map = result
+        .assets()
+        .find(|asset| asset.filename.ends_with(".map"))
+        .expect("source map asset");
+    serde_json::from_slice(map.source.as_bytes()).unwrap()
+}
+
+#[tokio::test]
+async fn sources_content_is_included_by_default() {
+    let temp = TempDir::new().unwrap();
+
+    let result = options(&temp).build().await.unwrap();
+
+    let map = map_json(&result);
+    assert!(map["sourcesContent"].is_array());
+    assert!(map.get("sourceRoot").is_none());
+}
+
+#[tokio::test]
+async fn sources_content_and_source_root_are_applied() {
+    let temp = TempDir::new().unwrap();
+
+    let result = options(&temp)
+        .sourcemap_sources_content(false)
+        .sourcemap_source_root("https://example.com/src/")
+        .build()
+        .await
+        .unwrap();
+
+    let map = map_json(&result);
+    assert!(map.get("sourcesContent").is_none());
+    assert_eq!(map["sourceRoot"], "https://example.com/src/");
+}
+
+#[tokio::test]
+async fn edited_inline_maps_stay_inline() {
+    let temp = TempDir::new().unwrap();
+
+    let result = options(&temp)
+        .sourcemap_inline()
+        .sourcemap_options(SourceMapOptions {
+            sources_content: false,
+            ..SourceMapOptions::default()
+        })
+        .build()
+        .await
+        .unwrap();
+
+    assert!(
+        result
+            .assets()
+            .all(|asset| !asset.filename.ends_with(".map"))
+    );
+    let chunk = result.chunks().next().unwrap();
+    assert!(
+        chunk
+            .code
+            .contains("//# sourceMappingURL=data:application/json;charset=utf-8;base64,")
+    );
+}
@@ -0,0 +1,90 @@
+//! Tests for top-level await handling.
+
+use fob_bundler::{BuildOptions, Error, OutputFormat, TopLevelAwait};
+use std::fs;
+use tempfile::TempDir;
+
+fn project() -> TempDir {
+    let temp = TempDir::new().unwrap();
+    fs::create_dir_all(temp.path().join("src")).unwrap();
+    fs::write(
+        temp.path().join("src/index.js"),
+        "import { config } from './config.js';\nexport const name = config.name;\n",
+    )
+    .unwrap();
+    fs::write(
+        temp.path().join("src/config.js"),
+        "const load = async () => ({ name: 'app' });\nexport const config = await load();\n",
+    )
+    .unwrap();
+    temp
+}
+
+fn options(temp: &TempDir) -> BuildOptions {
+    BuildOptions::new(temp.path().join("src/index.js"))
+        .disable_slow_module_warnings()
+        .sourcemap(false)
+        .cwd(temp.path())
+        .outdir(temp.path().join("dist"))
+}
+
+#[tokio::test]
+async fn cjs_output_reports_each_module_with_location() {
+    let temp = project();
+
+    let result = options(&temp).format(OutputFormat::Cjs).build().await;
+
+    let Err(Error::Bundler(diagnostics)) = result else {
+        panic!("expected top-level await diagnostics");
+    };
+    assert_eq!(diagnostics.len(), 1);
+    assert!(diagnostics[0].message.contains("CommonJS"));
+    assert!(
+        diagnostics[0]
+            .file
+            .as_deref()
+            .is_some_and(|file| file.ends_with("config.js"))
+    );
+    assert_eq!(diagnostics[0].line, Some(2));
+    assert_eq!(diagnostics[0].column, Some(23));
+    assert!(diagnostics[0].help.is_some());
+}
+
+#[tokio::test]
+async fn esm_output_keeps_top_level_await() {
+    let temp = project();
+
+    let result = options(&temp).build().await.unwrap();
+
+    assert_eq!(result.chunks().count(), 1);
+}
+
+#[tokio::test]
+async fn isolate_moves_modules_into_their_own_chunk() {
+    let temp = project();
+
+    let result = options(&temp)
+        .top_level_await(TopLevelAwait::Isolate)
+        .build()
+        .await
+        .unwrap();
+
+    let names: Vec<_> = result
+        .chunks()
+        .map(|chunk| chunk.name.to_string())
+        .collect();
+    assert!(names.iter().any(|name| name == "src-config"), "{:?}", names);
+}
+
+#[tokio::test]
+async fn isolate_requires_esm() {
+    let temp = project();
+
+    let result = options(&temp)
+        .format(OutputFormat::Iife)
+        .top_level_await(TopLevelAwait::Isolate)
+        .build()
+        .await;
+
+    assert!(matches!(result, Err(Error::InvalidConfig(_))));
+}
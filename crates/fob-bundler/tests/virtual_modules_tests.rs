@@ -0,0 +1,62 @@
+//! Tests for the built-in `fob:` virtual modules.
+
+use fob_bundler::BuildOptions;
+use tempfile::TempDir;
+
+#[tokio::test]
+async fn fob_env_exposes_configured_values() {
+    let temp = TempDir::new().unwrap();
+
+    let result = BuildOptions::new("virtual:entry.js")
+        .virtual_file(
+            "virtual:entry.js",
+            "import env, { API_URL } from 'fob:env';\nconsole.log(API_URL, env['feature-flag']);",
+        )
+        .env("API_URL", "https://api.example.com")
+        .env("feature-flag", "on")
+        .cwd(temp.path())
+        .outfile(temp.path().join("out.js"))
+        .build()
+        .await
+        .unwrap();
+
+    let chunk = result.output.chunks().next().expect("chunk");
+    assert!(chunk.code.contains("https://api.example.com"));
+    assert!(chunk.code.contains("feature-flag"));
+}
+
+#[tokio::test]
+async fn fob_manifest_reads_global() {
+    let temp = TempDir::new().unwrap();
+
+    let result = BuildOptions::new("virtual:entry.js")
+        .virtual_file(
+            "virtual:entry.js",
+            "import { preloadFiles } from 'fob:manifest';\nconsole.log(preloadFiles('main'));",
+        )
+        .cwd(temp.path())
+        .outfile(temp.path().join("out.js"))
+        .build()
+        .await
+        .unwrap();
+
+    let chunk = result.output.chunks().next().expect("chunk");
+    assert!(chunk.code.contains("__FOB_MANIFEST__"));
+}
+
+#[tokio::test]
+async fn unknown_fob_module_is_an_error() {
+    let temp = TempDir::new().unwrap();
+
+    let result = BuildOptions::new("virtual:entry.js")
+        .virtual_file(
+            "virtual:entry.js",
+            "import x from 'fob:nope';\nconsole.log(x);",
+        )
+        .cwd(temp.path())
+        .outfile(temp.path().join("out.js"))
+        .build()
+        .await;
+
+    assert!(result.is_err());
+}
@@ -0,0 +1,86 @@
+//! Tests for worker and worklet bundling.
+
+use fob_bundler::{BuildOptions, BuildResult};
+use std::fs;
+use tempfile::TempDir;
+
+fn project(main: &str) -> TempDir {
+    let temp = TempDir::new().unwrap();
+    fs::create_dir_all(temp.path().join("src")).unwrap();
+    fs::write(temp.path().join("src/index.js"), main).unwrap();
+    fs::write(
+        temp.path().join("src/math.ts"),
+        "export const double = (n: number): number => n * 2;\n",
+    )
+    .unwrap();
+    fs::write(
+        temp.path().join("src/worker.ts"),
+        "import { double } from './math';\n\
+         self.onmessage = (event: MessageEvent<number>) => postMessage(double(event.data));\n",
+    )
+    .unwrap();
+    temp
+}
+
+async fn build(temp: &TempDir) -> BuildResult {
+    BuildOptions::new(temp.path().join("src/index.js"))
+        .disable_slow_module_warnings()
+        .sourcemap(false)
+        .cwd(temp.path())
+        .outdir(temp.path().join("dist"))
+        .build()
+        .await
+        .unwrap()
+}
+
+fn worker_asset(result: &BuildResult) -> (String, String) {
+    let asset = result
+        .assets()
+        .find(|asset| asset.filename.contains("worker"))
+        .expect("worker asset");
+    (
+        asset.filename.to_string(),
+        String::from_utf8_lossy(asset.source.as_bytes()).into_owned(),
+    )
+}
+
+#[tokio::test]
+async fn classic_worker_is_bundled_as_a_script() {
+    let temp =
+        project("export const worker = new Worker(new URL('./worker.ts', import.meta.url));\n");
+
+    let result = build(&temp).await;
+
+    let (filename, code) = worker_asset(&result);
+    assert!(filename.ends_with(".js"), "{}", filename);
+    assert!(code.contains("n * 2"), "{}", code);
+    assert!(!code.contains("import "), "{}", code);
+
+    let entry = result.entry_chunks().next().unwrap();
+    assert!(entry.code.contains(&filename), "{}", entry.code);
+    assert!(!entry.code.contains("./worker.ts"), "{}", entry.code);
+}
+
+#[tokio::test]
+async fn module_worker_is_bundled_as_esm() {
+    let temp = project(
+        "export const worker = new Worker(new URL('./worker.ts', import.meta.url), { type: 'module' });\n",
+    );
+
+    let result = build(&temp).await;
+
+    let (_, code) = worker_asset(&result);
+    assert!(code.contains("n * 2"), "{}", code);
+    assert!(!code.contains("(function"), "{}", code);
+}
+
+#[tokio::test]
+async fn service_worker_keeps_a_stable_name() {
+    let temp =
+        project("navigator.serviceWorker.register(new URL('./worker.ts', import.meta.url));\n");
+
+    let result = build(&temp).await;
+
+    let (filename, _) = worker_asset(&result);
+    assert_eq!(filename, "worker.js");
+}
@@ -0,0 +1,181 @@
+//! __SHORT_NAME__ plugin for the fob bundler.
+//!
+//! Generated by `fob new-plugin`. The [`Plugin`] implementation wires the
+//! `load` and `transform` hooks to plain methods on [`__STRUCT_NAME__`] so the
+//! logic can be tested without running a full build.
+
+use anyhow::Context;
+use fob_bundler::{
+    HookLoadArgs, HookLoadOutput, HookLoadReturn, HookTransformArgs, HookTransformOutput,
+    HookTransformReturn, HookUsage, Plugin, PluginContext, Runtime, SharedTransformPluginContext,
+};
+use std::borrow::Cow;
+use std::path::Path;
+use std::sync::Arc;
+
+/// Configuration for [`__STRUCT_NAME__`].
+#[derive(Debug, Clone)]
+pub struct __OPTIONS_NAME__ {
+    /// File extensions handled by the `load` hook (without the leading dot).
+    pub extensions: Vec<String>,
+    /// Comment prepended to every transformed module.
+    pub banner: Option<String>,
+}
+
+impl Default for __OPTIONS_NAME__ {
+    fn default() -> Self {
+        Self {
+            extensions: vec!["__SHORT_NAME__".to_string()],
+            banner: None,
+        }
+    }
+}
+
+impl __OPTIONS_NAME__ {
+    /// Create options with default values.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the file extensions handled by the `load` hook.
+    pub fn with_extensions(mut self, extensions: Vec<String>) -> Self {
+        self.extensions = extensions;
+        self
+    }
+
+    /// Set the banner comment prepended to transformed modules.
+    pub fn with_banner(mut self, banner: impl Into<String>) -> Self {
+        self.banner = Some(banner.into());
+        self
+    }
+}
+
+/// __SHORT_NAME__ plugin.
+///
+/// All file access goes through the [`Runtime`] so the plugin works with
+/// virtual files, the native filesystem and WASM hosts alike.
+#[derive(Debug, Clone)]
+pub struct __STRUCT_NAME__ {
+    options: __OPTIONS_NAME__,
+    runtime: Arc<dyn Runtime>,
+}
+
+impl __STRUCT_NAME__ {
+    /// Create a plugin with default options.
+    pub fn new(runtime: Arc<dyn Runtime>) -> Self {
+        Self::with_options(runtime, __OPTIONS_NAME__::default())
+    }
+
+    /// Create a plugin with custom options.
+    pub fn with_options(runtime: Arc<dyn Runtime>, options: __OPTIONS_NAME__) -> Self {
+        Self { options, runtime }
+    }
+
+    /// Access the plugin options.
+    pub fn options(&self) -> &__OPTIONS_NAME__ {
+        &self.options
+    }
+
+    /// Whether the `load` hook should handle this module id.
+    pub fn handles(&self, id: &str) -> bool {
+        Path::new(id)
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .is_some_and(|ext| self.options.extensions.iter().any(|e| e == ext))
+    }
+
+    /// Load a module and convert it to JavaScript.
+    ///
+    /// Returns `Ok(None)` for ids this plugin does not handle.
+    pub async fn load_module(&self, id: &str) -> anyhow::Result<Option<String>> {
+        if !self.handles(id) {
+            return Ok(None);
+        }
+
+        let bytes = self
+            .runtime
+            .read_file(Path::new(id))
+            .await
+            .with_context(|| format!("Failed to read {}", id))?;
+        let source = String::from_utf8(bytes)
+            .with_context(|| format!("{} contains invalid UTF-8", id))?;
+
+        // Replace this with the real conversion for your file type.
+        Ok(Some(format!(
+            "export default {};\n",
+            js_string_literal(&source)
+        )))
+    }
+
+    /// Transform JavaScript produced by earlier hooks.
+    ///
+    /// Returns `None` when the code is left unchanged.
+    pub fn transform_code(&self, _id: &str, code: &str) -> Option<String> {
+        let banner = self.options.banner.as_ref()?;
+        Some(format!("/* {} */\n{}", banner, code))
+    }
+}
+
+impl Plugin for __STRUCT_NAME__ {
+    fn name(&self) -> Cow<'static, str> {
+        "__CRATE_NAME__".into()
+    }
+
+    fn register_hook_usage(&self) -> HookUsage {
+        HookUsage::Load | HookUsage::Transform
+    }
+
+    fn load(
+        &self,
+        _ctx: &PluginContext,
+        args: &HookLoadArgs<'_>,
+    ) -> impl std::future::Future<Output = HookLoadReturn> + Send {
+        let plugin = self.clone();
+        let id = args.id.to_string();
+
+        async move {
+            Ok(plugin.load_module(&id).await?.map(|code| HookLoadOutput {
+                code: code.into(),
+                ..Default::default()
+            }))
+        }
+    }
+
+    fn transform(
+        &self,
+        _ctx: SharedTransformPluginContext,
+        args: &HookTransformArgs<'_>,
+    ) -> impl std::future::Future<Output = HookTransformReturn> + Send {
+        let id = args.id.to_string();
+        let code = args.code.to_string();
+        let result = self.transform_code(&id, &code);
+
+        async move {
+            Ok(result.map(|code| HookTransformOutput {
+                code: Some(code),
+                map: None,
+                side_effects: None,
+                module_type: None,
+            }))
+        }
+    }
+}
+
+/// Encode a string as a JavaScript string literal.
+fn js_string_literal(value: &str) -> String {
+    let mut out = String::with_capacity(value.len() + 2);
+    out.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
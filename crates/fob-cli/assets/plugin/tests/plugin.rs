@@ -0,0 +1,64 @@
+//! Integration tests for __CRATE_NAME__.
+//!
+//! Uses `BundlerRuntime` virtual files so tests never touch the filesystem.
+
+use __LIB_NAME__::{__OPTIONS_NAME__, __STRUCT_NAME__};
+use fob_bundler::runtime::BundlerRuntime;
+use std::sync::Arc;
+
+fn plugin_with(files: &[(&str, &str)], options: __OPTIONS_NAME__) -> __STRUCT_NAME__ {
+    let runtime = BundlerRuntime::new("/project");
+    for (path, content) in files {
+        runtime.add_virtual_file(*path, content.as_bytes().to_vec());
+    }
+    __STRUCT_NAME__::with_options(Arc::new(runtime), options)
+}
+
+#[tokio::test]
+async fn loads_matching_files() {
+    let plugin = plugin_with(
+        &[("/project/src/data.__SHORT_NAME__", "hello")],
+        __OPTIONS_NAME__::default(),
+    );
+
+    let code = plugin
+        .load_module("/project/src/data.__SHORT_NAME__")
+        .await
+        .unwrap()
+        .expect("file should be handled");
+
+    assert_eq!(code, "export default \"hello\";\n");
+}
+
+#[tokio::test]
+async fn skips_other_files() {
+    let plugin = plugin_with(
+        &[("/project/src/index.js", "export {}")],
+        __OPTIONS_NAME__::default(),
+    );
+
+    assert!(plugin.load_module("/project/src/index.js").await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn missing_file_is_an_error() {
+    let plugin = plugin_with(&[], __OPTIONS_NAME__::default());
+
+    assert!(plugin.load_module("/project/missing.__SHORT_NAME__").await.is_err());
+}
+
+#[test]
+fn transform_adds_banner() {
+    let plugin = plugin_with(&[], __OPTIONS_NAME__::new().with_banner("built with fob"));
+
+    let code = plugin.transform_code("/project/src/index.js", "export {};");
+
+    assert_eq!(code.as_deref(), Some("/* built with fob */\nexport {};"));
+}
+
+#[test]
+fn transform_without_banner_is_noop() {
+    let plugin = plugin_with(&[], __OPTIONS_NAME__::default());
+
+    assert!(plugin.transform_code("/project/src/index.js", "export {};").is_none());
+}
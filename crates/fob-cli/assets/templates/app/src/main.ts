@@ -0,0 +1,17 @@
+/**
+ * Application entry point.
+ */
+
+import './app.css';
+
+function main() {
+  const app = document.getElementById('app');
+  if (app) {
+    app.innerHTML = `
+      <h1>Welcome to {{name}}!</h1>
+      <p>Edit src/main.ts to get started.</p>
+    `;
+  }
+}
+
+main();
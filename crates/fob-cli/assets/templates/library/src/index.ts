@@ -0,0 +1,13 @@
+/**
+ * Example library entry point.
+ *
+ * This is a simple library template. Replace with your own code.
+ */
+
+export function greet(name: string): string {
+  return `Hello, ${name}!`;
+}
+
+export function add(a: number, b: number): number {
+  return a + b;
+}
@@ -0,0 +1,50 @@
+import { StrictMode, useEffect, useState } from "react";
+import { createRoot } from "react-dom/client";
+import { posts } from "./posts";
+import "./blog.css";
+
+function currentSlug() {
+  return window.location.hash.slice(1);
+}
+
+function Blog() {
+  const [slug, setSlug] = useState(currentSlug);
+
+  useEffect(() => {
+    const onHashChange = () => setSlug(currentSlug());
+    window.addEventListener("hashchange", onHashChange);
+    return () => window.removeEventListener("hashchange", onHashChange);
+  }, []);
+
+  const post = posts.find((post) => post.slug === slug);
+  if (post) {
+    return (
+      <article>
+        <a href="#">← All posts</a>
+        <post.Content />
+      </article>
+    );
+  }
+
+  return (
+    <main>
+      <h1>{{name}}</h1>
+      <ul>
+        {posts.map((post) => (
+          <li key={post.slug}>
+            <a href={`#${post.slug}`}>{post.title}</a> <time>{post.date}</time>
+          </li>
+        ))}
+      </ul>
+    </main>
+  );
+}
+
+const container =
+  document.getElementById("app") ?? document.body.appendChild(document.createElement("div"));
+
+createRoot(container).render(
+  <StrictMode>
+    <Blog />
+  </StrictMode>,
+);
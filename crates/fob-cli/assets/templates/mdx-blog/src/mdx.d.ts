@@ -0,0 +1,8 @@
+declare module "*.mdx" {
+  import type { ComponentType } from "react";
+
+  export const meta: { title: string; date: string };
+
+  const Content: ComponentType;
+  export default Content;
+}
@@ -0,0 +1,12 @@
+import type { ComponentType } from "react";
+import HelloWorld, { meta as helloWorld } from "../posts/hello-world.mdx";
+
+export interface Post {
+  slug: string;
+  title: string;
+  date: string;
+  Content: ComponentType;
+}
+
+// Add new posts here, newest first
+export const posts: Post[] = [{ slug: "hello-world", ...helloWorld, Content: HelloWorld }];
@@ -0,0 +1,2 @@
+export { createRouter } from './router';
+export { createServer } from './server';
@@ -0,0 +1,13 @@
+// Simple file-based router example
+export function createRouter() {
+  const routes = new Map<string, () => string>();
+
+  return {
+    add(path: string, handler: () => string) {
+      routes.set(path, handler);
+    },
+    match(path: string) {
+      return routes.get(path);
+    }
+  };
+}
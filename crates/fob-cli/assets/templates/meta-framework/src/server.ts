@@ -0,0 +1,22 @@
+import { createRouter } from './router';
+
+export function createServer() {
+  const router = createRouter();
+
+  router.add('/', () => '<h1>Welcome</h1>');
+
+  return {
+    fetch(request: Request) {
+      const url = new URL(request.url);
+      const handler = router.match(url.pathname);
+
+      if (handler) {
+        return new Response(handler(), {
+          headers: { 'Content-Type': 'text/html' }
+        });
+      }
+
+      return new Response('Not found', { status: 404 });
+    }
+  };
+}
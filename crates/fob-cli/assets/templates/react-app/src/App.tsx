@@ -0,0 +1,15 @@
+import { useState } from "react";
+
+export function App() {
+  const [count, setCount] = useState(0);
+
+  return (
+    <main className="app">
+      <h1>{{name}}</h1>
+      <p>Edit <code>src/App.tsx</code> and save to rebuild.</p>
+      <button type="button" onClick={() => setCount((count) => count + 1)}>
+        Clicked {count} {count === 1 ? "time" : "times"}
+      </button>
+    </main>
+  );
+}
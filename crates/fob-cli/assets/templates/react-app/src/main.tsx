@@ -0,0 +1,13 @@
+import { StrictMode } from "react";
+import { createRoot } from "react-dom/client";
+import { App } from "./App";
+import "./app.css";
+
+const container =
+  document.getElementById("app") ?? document.body.appendChild(document.createElement("div"));
+
+createRoot(container).render(
+  <StrictMode>
+    <App />
+  </StrictMode>,
+);
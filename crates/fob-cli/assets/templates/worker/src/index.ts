@@ -0,0 +1,7 @@
+import { route } from "./router";
+
+export default {
+  async fetch(request: Request): Promise<Response> {
+    return route(request);
+  },
+};
@@ -0,0 +1,15 @@
+type Handler = (request: Request, url: URL) => Response | Promise<Response>;
+
+const routes: Record<string, Handler> = {
+  "/": () => new Response("Hello from {{name}}!"),
+  "/api/time": () => Response.json({ now: new Date().toISOString() }),
+};
+
+export async function route(request: Request): Promise<Response> {
+  const url = new URL(request.url);
+  const handler = routes[url.pathname];
+  if (!handler) {
+    return new Response("Not found", { status: 404 });
+  }
+  return handler(request, url);
+}
@@ -2,7 +2,7 @@ use clap::{Args, Subcommand};
 use std::path::PathBuf;
 
 use crate::cli::enums::*;
-use crate::cli::validation::parse_global;
+use crate::cli::validation::{parse_component_mapping, parse_global, parse_size};
 
 /// Available Fob subcommands
 #[derive(Subcommand, Debug)]
@@ -30,6 +30,83 @@ pub enum Command {
     /// Checks fob.config.json for errors and validates that all dependencies
     /// are correctly installed and compatible.
     Check(CheckArgs),
+
+    /// Scaffold a new fob plugin crate
+    ///
+    /// Generates a Rust crate with a plugin skeleton (load and transform
+    /// hooks, options struct, Runtime-based file access), integration tests
+    /// backed by virtual files, and a README.
+    NewPlugin(NewPluginArgs),
+
+    /// MDX tooling
+    ///
+    /// Utilities for working with individual MDX files, such as an
+    /// interactive playground for debugging plugins and codegen.
+    Mdx(MdxArgs),
+
+    /// Export the dependency graph of entry points
+    ///
+    /// Analyzes the imports reachable from the entry points and prints the
+    /// graph as Graphviz DOT, a Mermaid flowchart, JSON nodes and edges, or
+    /// an interactive HTML page.
+    ///
+    /// Examples:
+    ///   fob graph src/index.ts | dot -Tsvg > graph.svg
+    ///   fob graph src/index.ts --format mermaid --max-depth 2
+    ///   fob graph src/index.ts --root src/utils/index.ts --hide-externals
+    ///   fob graph src/index.ts --format html --focus src/api.ts -o graph.html
+    ///   fob graph src/index.ts --exclude node_modules
+    Graph(GraphArgs),
+
+    /// Report unused exports, import cycles, orphan files and duplicate packages
+    ///
+    /// Analyzes the modules reachable from the entry points and exits with
+    /// status 1 when any enabled check finds an issue, so it can gate CI.
+    ///
+    /// Examples:
+    ///   fob analyze src/index.ts
+    ///   fob analyze src/index.ts --format github --check unused-exports,circular
+    ///   fob analyze src/main.ts src/worker.ts --format json > analysis.json
+    Analyze(AnalyzeArgs),
+
+    /// Compare two build summaries
+    ///
+    /// Diffs summaries written by `fob build --ci --summary` and prints the
+    /// size change of every chunk and the modules added or removed. Chunks
+    /// are paired by name, ignoring content hashes.
+    ///
+    /// Examples:
+    ///   fob compare base.json head.json
+    ///   fob compare base.json head.json --format markdown > comment.md
+    Compare(CompareArgs),
+
+    /// Inspect the fob.toml config format
+    ///
+    /// Examples:
+    ///   fob config schema > fob.schema.json
+    ///   fob config schema -o .vscode/fob.schema.json
+    Config(ConfigArgs),
+
+    /// Generate a shell completion script
+    ///
+    /// Prints the completion script for the given shell to stdout.
+    ///
+    /// Examples:
+    ///   fob completions bash > /etc/bash_completion.d/fob
+    ///   fob completions zsh > "${fpath[1]}/_fob"
+    ///   fob completions fish > ~/.config/fish/completions/fob.fish
+    ///   fob completions powershell >> $PROFILE
+    Completions(CompletionsArgs),
+
+    /// Generate man pages
+    ///
+    /// Prints the fob(1) man page to stdout, or writes it together with one
+    /// page per subcommand (fob-build(1), ...) into a directory.
+    ///
+    /// Examples:
+    ///   fob man | man -l -
+    ///   fob man -o /usr/share/man/man1
+    Man(ManArgs),
 }
 
 /// Arguments for the build command
@@ -241,6 +318,62 @@ pub struct BuildArgs {
     #[arg(long)]
     pub clean: bool,
 
+    /// Validate emitted output after bundling
+    ///
+    /// Re-parses every emitted JavaScript chunk and fails the build if the
+    /// output does not parse or uses syntax newer than --target. Errors point
+    /// at the chunk and, when source maps are enabled, the original source.
+    /// Recommended for CI.
+    #[arg(long)]
+    pub validate_output: bool,
+
+    /// Rebuild whenever a source file changes
+    ///
+    /// Watches the project directory (ignoring node_modules, hidden
+    /// directories and the output directory), batches changes that arrive
+    /// together, and prints the time each rebuild took. The module graph is
+    /// cached between rebuilds. Stop with Ctrl+C.
+    #[arg(short, long)]
+    pub watch: bool,
+
+    /// Shell command to run after each successful build
+    ///
+    /// Runs in the project directory, e.g. to restart a server or run tests.
+    /// Without --watch, a failing command fails the build.
+    ///
+    /// Example:
+    ///   fob build --watch --on-success "node dist/index.js"
+    #[arg(long, value_name = "COMMAND")]
+    pub on_success: Option<String>,
+
+    /// Run non-interactively and print a JSON build summary to stdout
+    ///
+    /// The summary lists every chunk and asset with its size, the build
+    /// warnings, the --max-chunk-size budget status and timings. It is also
+    /// printed when the build fails. Exit codes: 0 on success, 1 when the
+    /// build fails, 2 when it succeeds but violates --max-chunk-size or
+    /// --fail-on-warnings.
+    ///
+    /// Examples:
+    ///   fob build --ci > build-summary.json
+    ///   fob build --ci --summary build-summary.json --max-chunk-size 250kb
+    #[arg(long, conflicts_with = "watch")]
+    pub ci: bool,
+
+    /// Write the --ci summary to this file instead of stdout
+    #[arg(long, value_name = "FILE", requires = "ci")]
+    pub summary: Option<PathBuf>,
+
+    /// Fail when any emitted chunk is larger than this size
+    ///
+    /// Accepts bytes or a kb / mb suffix, e.g. 250kb or 1.5mb.
+    #[arg(long, value_parser = parse_size, value_name = "SIZE")]
+    pub max_chunk_size: Option<u64>,
+
+    /// Fail when the build reports any warning
+    #[arg(long)]
+    pub fail_on_warnings: bool,
+
     /// Working directory for the build
     ///
     /// All relative paths in the build process are resolved relative to this
@@ -280,6 +413,15 @@ pub struct DevArgs {
     #[arg(long)]
     pub open: bool,
 
+    /// Serve source modules unbundled, resolving dependencies with an import map
+    ///
+    /// Skips the initial bundle: each module is transformed when the browser
+    /// requests it, and dependencies are bundled once and served from
+    /// /@deps/. Startup is near-instant, at the cost of one request per
+    /// module.
+    #[arg(long)]
+    pub unbundled: bool,
+
     /// Working directory for the dev server (defaults to auto-detected project root)
     ///
     /// All relative paths are resolved relative to this directory. If not specified,
@@ -303,6 +445,9 @@ pub struct InitArgs {
     /// Available templates:
     /// - library: TypeScript library with type declarations
     /// - app: Web application with dev server
+    /// - react-app: React application (aliases: react)
+    /// - mdx-blog: Blog with posts written in MDX (aliases: blog)
+    /// - worker: Cloudflare Worker deployed with Wrangler
     /// - component-library: React component library (aliases: components)
     /// - meta-framework: Educational framework example (aliases: framework)
     ///
@@ -353,4 +498,244 @@ pub struct CheckArgs {
     /// indicate configuration problems or suboptimal settings.
     #[arg(short, long)]
     pub warnings: bool,
+
+    /// Validate package.json entry points against the build output
+    ///
+    /// Builds the configured entries in memory, without writing them, and
+    /// checks that every path in `exports`, `main`, `module` and `types` is
+    /// emitted, that `import`/`require` conditions point at ESM/CommonJS
+    /// files, and that conditions are ordered so each is reachable.
+    #[arg(long)]
+    pub exports: bool,
+}
+
+/// Arguments for the graph command
+#[derive(Args, Debug)]
+pub struct GraphArgs {
+    /// Entry points to analyze
+    #[arg(value_name = "ENTRY", required = true)]
+    pub entry: Vec<PathBuf>,
+
+    /// Output format
+    #[arg(short, long, value_enum, default_value = "dot", value_name = "FORMAT")]
+    pub format: GraphFormat,
+
+    /// Write the graph to a file instead of stdout
+    #[arg(short, long, value_name = "FILE")]
+    pub output: Option<PathBuf>,
+
+    /// Only show the modules imported (directly or transitively) by this file
+    #[arg(long, value_name = "FILE")]
+    pub root: Option<PathBuf>,
+
+    /// Only show the modules this file imports and the modules importing it
+    #[arg(long, value_name = "FILE", conflicts_with = "root")]
+    pub focus: Option<PathBuf>,
+
+    /// Leave out modules and packages whose path contains PATTERN (repeatable)
+    #[arg(long, value_name = "PATTERN")]
+    pub exclude: Vec<String>,
+
+    /// Maximum number of import hops from the entry points (or --root)
+    #[arg(long, value_name = "DEPTH")]
+    pub max_depth: Option<usize>,
+
+    /// Leave out external dependencies such as npm packages
+    #[arg(long)]
+    pub hide_externals: bool,
+}
+
+/// Arguments for the analyze command
+#[derive(Args, Debug)]
+pub struct AnalyzeArgs {
+    /// Entry points to analyze
+    #[arg(value_name = "ENTRY", required = true)]
+    pub entry: Vec<PathBuf>,
+
+    /// Output format
+    #[arg(
+        short,
+        long,
+        value_enum,
+        default_value = "table",
+        value_name = "FORMAT"
+    )]
+    pub format: AnalyzeFormat,
+
+    /// Checks to run (comma-separated, defaults to all)
+    #[arg(long, value_enum, value_delimiter = ',', value_name = "CHECK")]
+    pub check: Vec<AnalyzeCheck>,
+
+    /// Directories searched for orphan files (defaults to the entry point directories)
+    #[arg(long, value_name = "DIR")]
+    pub src: Vec<PathBuf>,
+
+    /// Report issues without failing: always exit with status 0
+    #[arg(long)]
+    pub warn_only: bool,
+}
+
+/// Arguments for the compare command
+#[derive(Args, Debug)]
+pub struct CompareArgs {
+    /// Summary of the baseline build
+    #[arg(value_name = "OLD")]
+    pub old: PathBuf,
+
+    /// Summary of the build to compare against the baseline
+    #[arg(value_name = "NEW")]
+    pub new: PathBuf,
+
+    /// Output format
+    #[arg(short, long, value_enum, default_value = "text", value_name = "FORMAT")]
+    pub format: CompareFormat,
+}
+
+/// Arguments for the new-plugin command (plugin crate scaffolding)
+#[derive(Args, Debug)]
+pub struct NewPluginArgs {
+    /// Plugin name
+    ///
+    /// Short name of the plugin, e.g. `yaml` creates `fob-plugin-yaml`.
+    /// A leading `fob-plugin-` prefix is accepted and stripped.
+    #[arg(value_name = "NAME")]
+    pub name: String,
+
+    /// Directory to create the crate in (defaults to ./fob-plugin-<NAME>)
+    #[arg(short, long, value_name = "DIR")]
+    pub dir: Option<PathBuf>,
+
+    /// Overwrite files if the target directory already exists
+    #[arg(long)]
+    pub force: bool,
+}
+
+/// Arguments for the mdx command
+#[derive(Args, Debug)]
+pub struct MdxArgs {
+    /// MDX subcommand to execute
+    #[command(subcommand)]
+    pub command: MdxCommand,
+}
+
+/// Available MDX subcommands
+#[derive(Subcommand, Debug)]
+pub enum MdxCommand {
+    /// Watch an MDX file and show its compiled output
+    ///
+    /// Compiles the file, prints the result, and recompiles on every save,
+    /// printing only the lines that changed. With --serve, shows the source
+    /// and output side by side in the browser instead, with changed lines
+    /// highlighted.
+    ///
+    /// Examples:
+    ///   fob mdx repl docs/intro.mdx
+    ///   fob mdx repl post.mdx --output-format function-body --no-math
+    ///   fob mdx repl post.mdx --serve
+    Repl(MdxReplArgs),
+}
+
+/// Arguments for the mdx repl command
+#[derive(Args, Debug)]
+pub struct MdxReplArgs {
+    /// MDX file to compile
+    #[arg(value_name = "FILE")]
+    pub file: PathBuf,
+
+    /// Disable GitHub Flavored Markdown (tables, strikethrough, task lists)
+    #[arg(long)]
+    pub no_gfm: bool,
+
+    /// Disable footnotes
+    #[arg(long)]
+    pub no_footnotes: bool,
+
+    /// Disable math ($inline$ and $$block$$)
+    #[arg(long)]
+    pub no_math: bool,
+
+    /// How math is rendered
+    #[arg(long, value_enum, default_value = "tex", value_name = "OUTPUT")]
+    pub math_output: MdxMathOutput,
+
+    /// Disable the default plugins (heading IDs, image collection)
+    #[arg(long)]
+    pub no_default_plugins: bool,
+
+    /// Leave `<Include src="..." />` lines as components instead of
+    /// inlining the referenced files
+    #[arg(long)]
+    pub no_includes: bool,
+
+    /// JSX runtime module to import from (defaults to the target's runtime)
+    #[arg(long, default_value = "react/jsx-runtime", value_name = "MODULE")]
+    pub jsx_runtime: String,
+
+    /// Framework the generated JSX targets
+    #[arg(long, value_enum, default_value = "react", value_name = "TARGET")]
+    pub jsx_target: MdxJsxTarget,
+
+    /// Shape of the compiled code
+    #[arg(long, value_enum, default_value = "program", value_name = "FORMAT")]
+    pub output_format: MdxOutputFormat,
+
+    /// Module providing useMDXComponents (e.g. @mdx-js/react)
+    #[arg(long, value_name = "MODULE")]
+    pub provider_import_source: Option<String>,
+
+    /// Map an element to a component at compile time (repeatable)
+    ///
+    /// Example: --component h1=Heading --component img=./image.js#default
+    #[arg(long, value_parser = parse_component_mapping, value_name = "NAME=COMPONENT")]
+    pub component: Vec<(String, String)>,
+
+    /// Compile once and exit instead of watching
+    #[arg(long, conflicts_with = "serve")]
+    pub once: bool,
+
+    /// Serve a side-by-side view on this port instead of printing
+    #[arg(long, value_name = "PORT", num_args = 0..=1, default_missing_value = "3100")]
+    pub serve: Option<u16>,
+}
+
+/// Arguments for the config command
+#[derive(Args, Debug)]
+pub struct ConfigArgs {
+    /// Config subcommand to execute
+    #[command(subcommand)]
+    pub command: ConfigCommand,
+}
+
+/// Available config subcommands
+#[derive(Subcommand, Debug)]
+pub enum ConfigCommand {
+    /// Print the JSON Schema for fob.toml
+    ///
+    /// Point your editor at the schema to get completion and validation,
+    /// or use it to validate configs in other tools.
+    Schema(ConfigSchemaArgs),
+}
+
+/// Arguments for the config schema command
+#[derive(Args, Debug)]
+pub struct ConfigSchemaArgs {
+    /// Write the schema to this file instead of stdout
+    #[arg(short, long, value_name = "FILE")]
+    pub output: Option<PathBuf>,
+}
+
+/// Arguments for the completions command
+#[derive(Args, Debug)]
+pub struct CompletionsArgs {
+    /// Shell to generate completions for
+    #[arg(value_enum)]
+    pub shell: clap_complete::Shell,
+}
+
+/// Arguments for the man command
+#[derive(Args, Debug)]
+pub struct ManArgs {
+    /// Write a page for fob and each subcommand into this directory
+    #[arg(short, long, value_name = "DIR")]
+    pub output: Option<PathBuf>,
 }
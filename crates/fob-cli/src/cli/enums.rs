@@ -205,3 +205,123 @@ pub enum DocsMergeStrategy {
     #[value(name = "skip")]
     Skip,
 }
+
+/// Output format of `fob graph`
+#[derive(Copy, Clone, PartialEq, Eq, Debug, ValueEnum)]
+pub enum GraphFormat {
+    /// Graphviz DOT, for `dot -Tsvg` and similar tools
+    #[value(name = "dot")]
+    Dot,
+
+    /// Mermaid flowchart, for Markdown renderers such as GitHub
+    #[value(name = "mermaid")]
+    Mermaid,
+
+    /// JSON nodes and edges, for custom visualizations
+    #[value(name = "json")]
+    Json,
+
+    /// Interactive HTML page with a force-directed d3 layout
+    #[value(name = "html")]
+    Html,
+}
+
+/// Output format of `fob analyze`
+#[derive(Copy, Clone, PartialEq, Eq, Debug, ValueEnum)]
+pub enum AnalyzeFormat {
+    /// Human-readable tables grouped by check
+    #[value(name = "table")]
+    Table,
+
+    /// A single JSON report, for scripts and dashboards
+    #[value(name = "json")]
+    Json,
+
+    /// GitHub Actions workflow commands that annotate the offending files
+    #[value(name = "github")]
+    Github,
+}
+
+/// Output format of `fob compare`
+#[derive(Copy, Clone, PartialEq, Eq, Debug, ValueEnum)]
+pub enum CompareFormat {
+    /// Aligned table for the terminal
+    #[value(name = "text")]
+    Text,
+
+    /// Markdown table, e.g. for a pull request comment
+    #[value(name = "markdown")]
+    Markdown,
+
+    /// JSON, for scripts and dashboards
+    #[value(name = "json")]
+    Json,
+}
+
+/// Check run by `fob analyze`
+#[derive(Copy, Clone, PartialEq, Eq, Debug, ValueEnum)]
+pub enum AnalyzeCheck {
+    /// Exports that no module imports
+    #[value(name = "unused-exports")]
+    UnusedExports,
+
+    /// Import cycles between modules
+    #[value(name = "circular")]
+    Circular,
+
+    /// Source files that no entry point reaches
+    #[value(name = "orphans")]
+    Orphans,
+
+    /// Packages bundled from more than one installed copy
+    #[value(name = "duplicates")]
+    Duplicates,
+}
+
+/// Shape of compiled MDX code
+#[derive(Copy, Clone, PartialEq, Eq, Debug, ValueEnum)]
+pub enum MdxOutputFormat {
+    /// ES module with imports and a default export
+    #[value(name = "program")]
+    Program,
+
+    /// Function body for evaluation with `new Function()`
+    #[value(name = "function-body")]
+    FunctionBody,
+}
+
+/// Rendering of math in compiled MDX
+#[derive(Copy, Clone, PartialEq, Eq, Debug, ValueEnum)]
+pub enum MdxMathOutput {
+    /// TeX spans for a client-side library such as KaTeX
+    #[value(name = "tex")]
+    Tex,
+
+    /// MathML rendered at compile time
+    #[value(name = "mathml")]
+    MathMl,
+}
+
+/// Framework targeted by compiled MDX
+#[derive(Copy, Clone, PartialEq, Eq, Debug, ValueEnum)]
+pub enum MdxJsxTarget {
+    /// React automatic runtime
+    #[value(name = "react")]
+    React,
+
+    /// Preact automatic runtime
+    #[value(name = "preact")]
+    Preact,
+
+    /// Vue 3 h() calls
+    #[value(name = "vue")]
+    Vue,
+
+    /// Solid hyperscript runtime
+    #[value(name = "solid")]
+    Solid,
+
+    /// Hono JSX runtime
+    #[value(name = "hono")]
+    Hono,
+}
@@ -10,6 +10,15 @@
 //! - `fob dev` - Development server with watch mode (planned)
 //! - `fob init` - Project scaffolding (planned)
 //! - `fob check` - Configuration validation (planned)
+//! - `fob new-plugin` - Plugin crate scaffolding
+//! - `fob mdx repl` - Watch an MDX file and show compiled output
+//! - `fob graph` - Export the dependency graph as DOT, Mermaid, JSON or HTML
+//! - `fob analyze` - Report unused exports, cycles, orphans and duplicate packages
+//! - `fob compare` - Diff two build summaries
+//! - `fob config schema` - Print the JSON Schema for fob.toml
+//! - `fob completions <shell>` - Shell completion script
+//! - `fob man` - Man pages
+//! - `fob --capabilities [--json]` - Report features, plugins and limits
 
 mod commands;
 pub mod enums;
@@ -18,9 +27,13 @@ mod validation;
 
 use clap::Parser;
 
-pub use commands::{BuildArgs, CheckArgs, Command, DevArgs, InitArgs};
+pub use commands::{
+    AnalyzeArgs, BuildArgs, CheckArgs, Command, CompareArgs, CompletionsArgs, ConfigArgs,
+    ConfigCommand, ConfigSchemaArgs, DevArgs, GraphArgs, InitArgs, ManArgs, MdxArgs, MdxCommand,
+    MdxReplArgs, NewPluginArgs,
+};
 pub use enums::*;
-pub use validation::parse_global;
+pub use validation::{parse_component_mapping, parse_global, parse_size};
 
 /// Fob - A modern JavaScript/TypeScript bundler
 #[derive(Parser, Debug)]
@@ -54,7 +67,18 @@ pub struct Cli {
     #[arg(long, global = true)]
     pub no_color: bool,
 
+    /// Print the features, built-in plugins and limits of this build
+    ///
+    /// Intended for tools that need feature detection. Combine with --json
+    /// for a stable, machine-readable report.
+    #[arg(long)]
+    pub capabilities: bool,
+
+    /// Print the --capabilities report as JSON
+    #[arg(long, requires = "capabilities")]
+    pub json: bool,
+
     /// Subcommand to execute
     #[command(subcommand)]
-    pub command: Command,
+    pub command: Option<Command>,
 }
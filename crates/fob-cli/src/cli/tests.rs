@@ -1,6 +1,6 @@
 #[cfg(test)]
 mod tests {
-    use crate::cli::validation::parse_global;
+    use crate::cli::validation::{parse_component_mapping, parse_global, parse_size};
     use crate::cli::{Cli, Command};
     use clap::Parser;
     use std::path::PathBuf;
@@ -133,7 +133,7 @@ mod tests {
 
         let args = Cli::try_parse_from(&["joy", "build", "src/index.ts"]).unwrap();
 
-        if let Command::Build(build) = args.command {
+        if let Some(Command::Build(build)) = args.command {
             assert_eq!(build.entry, Some(vec!["src/index.ts".to_string()]));
             assert_eq!(build.format, Format::Esm);
             assert_eq!(build.out_dir, PathBuf::from("dist"));
@@ -144,6 +144,13 @@ mod tests {
             assert!(!build.splitting);
             assert!(!build.no_treeshake);
             assert!(!build.clean);
+            assert!(!build.validate_output);
+            assert!(!build.watch);
+            assert!(build.on_success.is_none());
+            assert!(!build.ci);
+            assert!(build.summary.is_none());
+            assert!(build.max_chunk_size.is_none());
+            assert!(!build.fail_on_warnings);
             assert!(!build.docs);
             assert!(build.docs_format.is_none());
             assert!(build.docs_dir.is_none());
@@ -167,17 +174,81 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[test]
+    fn test_build_args_watch_with_hook() {
+        use clap::Parser;
+
+        let args = Cli::try_parse_from(&[
+            "joy",
+            "build",
+            "src/index.ts",
+            "-w",
+            "--on-success",
+            "node dist/index.js",
+        ])
+        .unwrap();
+
+        let Some(Command::Build(build)) = args.command else {
+            panic!("Expected Build command");
+        };
+        assert!(build.watch);
+        assert_eq!(build.on_success.as_deref(), Some("node dist/index.js"));
+    }
+
+    #[test]
+    fn test_build_args_ci() {
+        use clap::Parser;
+
+        let args = Cli::try_parse_from(&[
+            "joy",
+            "build",
+            "src/index.ts",
+            "--ci",
+            "--summary",
+            "summary.json",
+            "--max-chunk-size",
+            "250kb",
+            "--fail-on-warnings",
+        ])
+        .unwrap();
+
+        let Some(Command::Build(build)) = args.command else {
+            panic!("Expected Build command");
+        };
+        assert!(build.ci);
+        assert_eq!(build.summary, Some(PathBuf::from("summary.json")));
+        assert_eq!(build.max_chunk_size, Some(250 * 1024));
+        assert!(build.fail_on_warnings);
+
+        // --summary requires --ci, and --ci can't watch
+        assert!(Cli::try_parse_from(&["joy", "build", "--summary", "s.json"]).is_err());
+        assert!(Cli::try_parse_from(&["joy", "build", "--ci", "--watch"]).is_err());
+    }
+
+    #[test]
+    fn test_parse_size() {
+        assert_eq!(parse_size("2048"), Ok(2048));
+        assert_eq!(parse_size("100b"), Ok(100));
+        assert_eq!(parse_size("250kb"), Ok(250 * 1024));
+        assert_eq!(parse_size("1.5MB"), Ok(1024 * 1024 * 3 / 2));
+        assert!(parse_size("").is_err());
+        assert!(parse_size("kb").is_err());
+        assert!(parse_size("-1kb").is_err());
+        assert!(parse_size("10gb").is_err());
+    }
+
     #[test]
     fn test_dev_args_defaults() {
         use clap::Parser;
 
         let args = Cli::try_parse_from(&["joy", "dev"]).unwrap();
 
-        if let Command::Dev(dev) = args.command {
+        if let Some(Command::Dev(dev)) = args.command {
             assert_eq!(dev.entry, None); // No default - reads from config
             assert_eq!(dev.port, 3000);
             assert!(!dev.https);
             assert!(!dev.open);
+            assert!(!dev.unbundled);
         } else {
             panic!("Expected Dev command");
         }
@@ -198,4 +269,255 @@ mod tests {
         let result = Cli::try_parse_from(&["joy", "init", "--use-pnpm"]);
         assert!(result.is_ok());
     }
+
+    #[test]
+    fn test_new_plugin_args() {
+        let args = Cli::try_parse_from(&["fob", "new-plugin", "yaml"]).unwrap();
+
+        if let Some(Command::NewPlugin(new_plugin)) = args.command {
+            assert_eq!(new_plugin.name, "yaml");
+            assert_eq!(new_plugin.dir, None);
+            assert!(!new_plugin.force);
+        } else {
+            panic!("Expected NewPlugin command");
+        }
+
+        // Name is required
+        assert!(Cli::try_parse_from(&["fob", "new-plugin"]).is_err());
+    }
+
+    #[test]
+    fn test_parse_component_mapping() {
+        assert_eq!(
+            parse_component_mapping("img=./image.js#default"),
+            Ok(("img".to_string(), "./image.js#default".to_string()))
+        );
+        assert!(parse_component_mapping("h1").is_err());
+        assert!(parse_component_mapping("=Heading").is_err());
+        assert!(parse_component_mapping("h1=").is_err());
+    }
+
+    #[test]
+    fn test_mdx_repl_args() {
+        use crate::cli::{MdxCommand, MdxJsxTarget, MdxMathOutput, MdxOutputFormat};
+
+        let args = Cli::try_parse_from(&["fob", "mdx", "repl", "post.mdx", "--no-math"]).unwrap();
+        let Some(Command::Mdx(mdx)) = args.command else {
+            panic!("Expected Mdx command");
+        };
+        let MdxCommand::Repl(repl) = mdx.command;
+        assert_eq!(repl.file, PathBuf::from("post.mdx"));
+        assert!(repl.no_math);
+        assert!(!repl.no_gfm);
+        assert!(!repl.no_includes);
+        assert_eq!(repl.output_format, MdxOutputFormat::Program);
+        assert_eq!(repl.jsx_runtime, "react/jsx-runtime");
+        assert_eq!(repl.jsx_target, MdxJsxTarget::React);
+        assert_eq!(repl.math_output, MdxMathOutput::Tex);
+        assert_eq!(repl.serve, None);
+
+        // --serve without a value uses the default port
+        let args = Cli::try_parse_from(&["fob", "mdx", "repl", "post.mdx", "--serve"]).unwrap();
+        let Some(Command::Mdx(mdx)) = args.command else {
+            panic!("Expected Mdx command");
+        };
+        let MdxCommand::Repl(repl) = mdx.command;
+        assert_eq!(repl.serve, Some(3100));
+
+        // --once and --serve conflict
+        assert!(
+            Cli::try_parse_from(&["fob", "mdx", "repl", "post.mdx", "--once", "--serve"]).is_err()
+        );
+    }
+
+    #[test]
+    fn test_graph_args() {
+        use crate::cli::GraphFormat;
+
+        let args = Cli::try_parse_from(&["fob", "graph", "src/index.ts"]).unwrap();
+        let Some(Command::Graph(graph)) = args.command else {
+            panic!("Expected Graph command");
+        };
+        assert_eq!(graph.entry, vec![PathBuf::from("src/index.ts")]);
+        assert_eq!(graph.format, GraphFormat::Dot);
+        assert_eq!(graph.max_depth, None);
+        assert!(!graph.hide_externals);
+        assert!(graph.exclude.is_empty());
+
+        let args = Cli::try_parse_from(&[
+            "fob",
+            "graph",
+            "src/index.ts",
+            "--format",
+            "mermaid",
+            "--max-depth",
+            "2",
+            "--root",
+            "src/lib.ts",
+            "--hide-externals",
+        ])
+        .unwrap();
+        let Some(Command::Graph(graph)) = args.command else {
+            panic!("Expected Graph command");
+        };
+        assert_eq!(graph.format, GraphFormat::Mermaid);
+        assert_eq!(graph.max_depth, Some(2));
+        assert_eq!(graph.root, Some(PathBuf::from("src/lib.ts")));
+        assert!(graph.hide_externals);
+
+        let args = Cli::try_parse_from(&[
+            "fob",
+            "graph",
+            "src/index.ts",
+            "--format",
+            "html",
+            "--focus",
+            "src/api.ts",
+            "--exclude",
+            "node_modules",
+            "--exclude",
+            "generated",
+        ])
+        .unwrap();
+        let Some(Command::Graph(graph)) = args.command else {
+            panic!("Expected Graph command");
+        };
+        assert_eq!(graph.format, GraphFormat::Html);
+        assert_eq!(graph.focus, Some(PathBuf::from("src/api.ts")));
+        assert_eq!(graph.exclude, vec!["node_modules", "generated"]);
+
+        // --focus and --root select different subgraphs
+        assert!(Cli::try_parse_from(&[
+            "fob",
+            "graph",
+            "src/index.ts",
+            "--root",
+            "src/lib.ts",
+            "--focus",
+            "src/api.ts",
+        ])
+        .is_err());
+
+        // At least one entry is required
+        assert!(Cli::try_parse_from(&["fob", "graph"]).is_err());
+    }
+
+    #[test]
+    fn test_analyze_args() {
+        use crate::cli::{AnalyzeCheck, AnalyzeFormat};
+
+        let args = Cli::try_parse_from(&["fob", "analyze", "src/index.ts"]).unwrap();
+        let Some(Command::Analyze(analyze)) = args.command else {
+            panic!("Expected Analyze command");
+        };
+        assert_eq!(analyze.entry, vec![PathBuf::from("src/index.ts")]);
+        assert_eq!(analyze.format, AnalyzeFormat::Table);
+        assert!(analyze.check.is_empty());
+        assert!(analyze.src.is_empty());
+        assert!(!analyze.warn_only);
+
+        let args = Cli::try_parse_from(&[
+            "fob",
+            "analyze",
+            "src/index.ts",
+            "--format",
+            "github",
+            "--check",
+            "unused-exports,orphans",
+            "--src",
+            "src",
+            "--warn-only",
+        ])
+        .unwrap();
+        let Some(Command::Analyze(analyze)) = args.command else {
+            panic!("Expected Analyze command");
+        };
+        assert_eq!(analyze.format, AnalyzeFormat::Github);
+        assert_eq!(
+            analyze.check,
+            vec![AnalyzeCheck::UnusedExports, AnalyzeCheck::Orphans]
+        );
+        assert_eq!(analyze.src, vec![PathBuf::from("src")]);
+        assert!(analyze.warn_only);
+
+        assert!(Cli::try_parse_from(&["fob", "analyze"]).is_err());
+        assert!(
+            Cli::try_parse_from(&["fob", "analyze", "src/index.ts", "--check", "bogus"]).is_err()
+        );
+    }
+
+    #[test]
+    fn test_capabilities_flags() {
+        use clap::Parser;
+
+        let args = Cli::try_parse_from(&["fob", "--capabilities", "--json"]).unwrap();
+        assert!(args.capabilities);
+        assert!(args.json);
+        assert!(args.command.is_none());
+
+        // --json only applies to --capabilities
+        assert!(Cli::try_parse_from(&["fob", "--json"]).is_err());
+    }
+
+    #[test]
+    fn test_config_schema_args() {
+        use crate::cli::ConfigCommand;
+
+        let args = Cli::try_parse_from(&["fob", "config", "schema"]).unwrap();
+        let Some(Command::Config(config)) = args.command else {
+            panic!("Expected Config command");
+        };
+        let ConfigCommand::Schema(schema) = config.command;
+        assert_eq!(schema.output, None);
+
+        let args =
+            Cli::try_parse_from(&["fob", "config", "schema", "-o", "fob.schema.json"]).unwrap();
+        let Some(Command::Config(config)) = args.command else {
+            panic!("Expected Config command");
+        };
+        let ConfigCommand::Schema(schema) = config.command;
+        assert_eq!(schema.output, Some(PathBuf::from("fob.schema.json")));
+    }
+
+    #[test]
+    fn test_completions_args() {
+        let args = Cli::try_parse_from(&["fob", "completions", "zsh"]).unwrap();
+        let Some(Command::Completions(completions)) = args.command else {
+            panic!("Expected Completions command");
+        };
+        assert_eq!(completions.shell, clap_complete::Shell::Zsh);
+
+        assert!(Cli::try_parse_from(&["fob", "completions", "tcsh"]).is_err());
+    }
+
+    #[test]
+    fn test_man_args() {
+        let args = Cli::try_parse_from(&["fob", "man", "-o", "man"]).unwrap();
+        let Some(Command::Man(man)) = args.command else {
+            panic!("Expected Man command");
+        };
+        assert_eq!(man.output, Some(PathBuf::from("man")));
+    }
+
+    #[test]
+    fn test_compare_args() {
+        use crate::cli::CompareFormat;
+
+        let args = Cli::try_parse_from(&["fob", "compare", "base.json", "head.json"]).unwrap();
+        let Some(Command::Compare(compare)) = args.command else {
+            panic!("Expected Compare command");
+        };
+        assert_eq!(compare.old, PathBuf::from("base.json"));
+        assert_eq!(compare.new, PathBuf::from("head.json"));
+        assert_eq!(compare.format, CompareFormat::Text);
+
+        let args =
+            Cli::try_parse_from(&["fob", "compare", "a.json", "b.json", "-f", "markdown"]).unwrap();
+        let Some(Command::Compare(compare)) = args.command else {
+            panic!("Expected Compare command");
+        };
+        assert_eq!(compare.format, CompareFormat::Markdown);
+
+        assert!(Cli::try_parse_from(&["fob", "compare", "a.json"]).is_err());
+    }
 }
@@ -37,3 +37,59 @@ pub fn parse_global(s: &str) -> Result<String, String> {
 
     Ok(s.to_string())
 }
+
+/// Parse a `NAME=COMPONENT` pair for MDX component mappings.
+///
+/// # Examples
+///
+/// Valid: `h1=Heading`, `img=./image.js#default`
+/// Invalid: `h1`, `=Heading`, `h1=`
+///
+/// # Errors
+///
+/// Returns an error message if either side is missing.
+pub fn parse_component_mapping(s: &str) -> Result<(String, String), String> {
+    match s.split_once('=') {
+        Some((name, component)) if !name.is_empty() && !component.is_empty() => {
+            Ok((name.to_string(), component.to_string()))
+        }
+        _ => Err(format!(
+            "Component mapping must have the form NAME=COMPONENT: '{}'",
+            s
+        )),
+    }
+}
+
+/// Parse a size in bytes, optionally with a `kb` or `mb` suffix.
+///
+/// Units are binary (1kb = 1024 bytes) and case-insensitive, matching how
+/// sizes are printed in build output.
+///
+/// # Examples
+///
+/// Valid: `2048`, `250kb`, `1.5MB`, `100 b`
+/// Invalid: `""`, `kb`, `-1kb`, `10gb`
+///
+/// # Errors
+///
+/// Returns an error message if the number or unit is invalid.
+pub fn parse_size(s: &str) -> Result<u64, String> {
+    let lower = s.trim().to_ascii_lowercase();
+    let (number, multiplier) = if let Some(number) = lower.strip_suffix("kb") {
+        (number, 1024.0)
+    } else if let Some(number) = lower.strip_suffix("mb") {
+        (number, 1024.0 * 1024.0)
+    } else if let Some(number) = lower.strip_suffix('b') {
+        (number, 1.0)
+    } else {
+        (lower.as_str(), 1.0)
+    };
+
+    match number.trim().parse::<f64>() {
+        Ok(value) if value.is_finite() && value >= 0.0 => Ok((value * multiplier).round() as u64),
+        _ => Err(format!(
+            "Size must be a number of bytes with an optional kb or mb suffix: '{}'",
+            s
+        )),
+    }
+}
@@ -1,66 +1,551 @@
-use std::path::PathBuf;
+//! Analyze command implementation.
+//!
+//! Runs the static analyzer over the entry points and reports unused
+//! exports, import cycles, orphan files and duplicate packages as a table,
+//! JSON, or GitHub Actions annotations. Any finding makes the command exit
+//! with status 1 (unless `--warn-only`), so it can gate CI.
 
-use anyhow::{Context, Result};
-use console::style;
-use fob_bundler::analyze;
-use fob_bundler::analysis::CacheAnalysis;
-use tokio::fs;
+use std::collections::{BTreeSet, HashSet};
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
 
-use crate::analysis::AnalysisDocument;
-use crate::cli::AnalyzeArgs;
-use crate::server;
+use serde::Serialize;
 
-pub struct AnalyzeCommand {
-    args: AnalyzeArgs,
-}
+use crate::cli::{AnalyzeArgs, AnalyzeCheck, AnalyzeFormat};
+use crate::commands::utils;
+use crate::error::{CliError, Result};
+use crate::ui;
+use fob_bundler::{AnalysisResult, Analyzer, NativeRuntime};
+
+/// Every check, run when `--check` isn't given.
+const ALL_CHECKS: &[AnalyzeCheck] = &[
+    AnalyzeCheck::UnusedExports,
+    AnalyzeCheck::Circular,
+    AnalyzeCheck::Orphans,
+    AnalyzeCheck::Duplicates,
+];
+
+/// Extensions of the files considered when looking for orphans.
+const SOURCE_EXTENSIONS: &[&str] = &[
+    "js", "jsx", "mjs", "cjs", "ts", "tsx", "mts", "cts", "vue", "svelte", "astro",
+];
+
+/// Execute the analyze command.
+///
+/// # Errors
+///
+/// Returns errors for missing entry points, failed analysis, or when any
+/// enabled check reports an issue and `--warn-only` isn't set.
+pub async fn execute(args: AnalyzeArgs) -> Result<()> {
+    let cwd = utils::get_cwd()?;
+
+    let entries = args
+        .entry
+        .iter()
+        .map(|entry| utils::resolve_path(entry, &cwd))
+        .collect::<Vec<_>>();
+    for entry in &entries {
+        utils::validate_entry(entry)?;
+    }
 
-impl From<AnalyzeArgs> for AnalyzeCommand {
-    fn from(args: AnalyzeArgs) -> Self {
-        Self { args }
+    let src_dirs = if args.src.is_empty() {
+        entries
+            .iter()
+            .filter_map(|entry| entry.parent().map(Path::to_path_buf))
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect()
+    } else {
+        args.src
+            .iter()
+            .map(|dir| utils::resolve_path(dir, &cwd))
+            .collect::<Vec<_>>()
+    };
+    let checks = if args.check.is_empty() {
+        ALL_CHECKS
+    } else {
+        args.check.as_slice()
+    };
+
+    let analysis = Analyzer::new()
+        .entries(entries)
+        .cwd(cwd.clone())
+        .analyze()
+        .await
+        .map_err(|e| CliError::Core(format!("Analysis failed: {}", e)))?;
+    for warning in &analysis.warnings {
+        ui::warning(warning);
+    }
+
+    let report = AnalyzeReport::collect(&analysis, checks, &cwd, &src_dirs).await?;
+    let output = match args.format {
+        AnalyzeFormat::Table => report.to_table(),
+        AnalyzeFormat::Json => serde_json::to_string_pretty(&report)? + "\n",
+        AnalyzeFormat::Github => report.to_github(if args.warn_only { "warning" } else { "error" }),
+    };
+    print!("{}", output);
+
+    let count = report.issue_count();
+    if count > 0 && !args.warn_only {
+        return Err(CliError::Custom(format!(
+            "Analysis found {} issue(s)",
+            count
+        )));
     }
+    Ok(())
 }
 
-impl AnalyzeCommand {
-    pub async fn run(self) -> Result<()> {
-        let entry_paths: Vec<PathBuf> = self.args.entries.iter().map(PathBuf::from).collect();
+/// Findings of `fob analyze`, serialized as the `--format json` report.
+///
+/// Paths are relative to the working directory.
+#[derive(Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AnalyzeReport {
+    /// Exports that no module imports
+    pub unused_exports: Vec<UnusedExportIssue>,
+    /// Import cycles, each starting at its lexicographically smallest module
+    pub circular_dependencies: Vec<Vec<String>>,
+    /// Source files not reachable from any entry point
+    pub orphan_files: Vec<String>,
+    /// Packages bundled from more than one installed copy
+    pub duplicate_packages: Vec<DuplicatePackageIssue>,
+}
 
-        let analysis = analyze(entry_paths.clone())
-            .await
-            .context("Static analysis failed")?;
+/// An export that no module imports.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct UnusedExportIssue {
+    pub file: String,
+    /// 1-based line of the export, or 0 if the file couldn't be read
+    pub line: usize,
+    pub name: String,
+}
 
-        let document = AnalysisDocument::from_analysis(&analysis, CacheAnalysis::default());
+/// A package with more than one installed copy in the graph.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DuplicatePackageIssue {
+    pub name: String,
+    pub copies: Vec<DuplicateCopy>,
+}
 
-        println!(
-            "{} Analysis complete for {} entry(ies)",
-            style("✔").green().bold(),
-            style(entry_paths.len()).cyan()
-        );
+/// One installed copy of a duplicated package.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DuplicateCopy {
+    pub root: String,
+    pub version: Option<String>,
+}
+
+impl AnalyzeReport {
+    /// Run the enabled checks against an analysis.
+    ///
+    /// Orphan files are source files under `src_dirs` that the analysis
+    /// didn't reach. Declaration files and `.test.`/`.spec.` files are
+    /// never reported as orphans.
+    pub async fn collect(
+        analysis: &AnalysisResult,
+        checks: &[AnalyzeCheck],
+        cwd: &Path,
+        src_dirs: &[PathBuf],
+    ) -> Result<Self> {
+        let mut report = Self::default();
+
+        if checks.contains(&AnalyzeCheck::UnusedExports) {
+            for unused in analysis.unused_exports().map_err(core_error)? {
+                let file = unused.module_id.path_string().into_owned();
+                let line = std::fs::read(utils::resolve_path(Path::new(&file), cwd))
+                    .map(|source| line_of(&source, unused.export.span.start as usize))
+                    .unwrap_or(0);
+                report.unused_exports.push(UnusedExportIssue {
+                    file,
+                    line,
+                    name: unused.export.name,
+                });
+            }
+            report
+                .unused_exports
+                .sort_by(|a, b| (&a.file, a.line, &a.name).cmp(&(&b.file, b.line, &b.name)));
+        }
+
+        if checks.contains(&AnalyzeCheck::Circular) {
+            let mut cycles = BTreeSet::new();
+            for chain in analysis.find_circular_dependencies().map_err(core_error)? {
+                let path = chain
+                    .path
+                    .iter()
+                    .map(|id| id.path_string().into_owned())
+                    .collect::<Vec<_>>();
+                if let Some(cycle) = first_cycle(&path) {
+                    cycles.insert(cycle);
+                }
+            }
+            report.circular_dependencies = cycles.into_iter().collect();
+        }
+
+        if checks.contains(&AnalyzeCheck::Orphans) {
+            let reachable = analysis
+                .graph
+                .modules()
+                .map_err(core_error)?
+                .iter()
+                .map(|module| canonical(&utils::resolve_path(&module.path, cwd)))
+                .collect::<HashSet<_>>();
+            let mut files = BTreeSet::new();
+            for dir in src_dirs {
+                collect_source_files(dir, &mut files)?;
+            }
+            let cwd = canonical(cwd);
+            report.orphan_files = files
+                .into_iter()
+                .map(|file| canonical(&file))
+                .filter(|file| !reachable.contains(file))
+                .map(|file| display_path(&file, &cwd))
+                .collect();
+        }
 
-        if let Some(path) = &self.args.json {
-            write_analysis_json(path, &document).await?;
-            println!(
-                "{} Wrote analysis JSON to {}",
-                style("✔").green().bold(),
-                style(path).bold()
+        if checks.contains(&AnalyzeCheck::Duplicates) {
+            for mut duplicate in analysis.duplicate_packages().map_err(core_error)? {
+                duplicate.load_versions(&NativeRuntime).await;
+                report.duplicate_packages.push(DuplicatePackageIssue {
+                    name: duplicate.name,
+                    copies: duplicate
+                        .copies
+                        .into_iter()
+                        .map(|copy| DuplicateCopy {
+                            root: display_path(&copy.root, cwd),
+                            version: copy.version,
+                        })
+                        .collect(),
+                });
+            }
+        }
+
+        Ok(report)
+    }
+
+    /// Total number of findings across all checks.
+    pub fn issue_count(&self) -> usize {
+        self.unused_exports.len()
+            + self.circular_dependencies.len()
+            + self.orphan_files.len()
+            + self.duplicate_packages.len()
+    }
+
+    /// Render the report as plain-text sections, one per check with findings.
+    pub fn to_table(&self) -> String {
+        let mut output = String::new();
+
+        if !self.unused_exports.is_empty() {
+            let _ = writeln!(output, "Unused exports ({})", self.unused_exports.len());
+            let width = self
+                .unused_exports
+                .iter()
+                .map(|issue| issue.file.len() + issue.line.to_string().len() + 1)
+                .max()
+                .unwrap_or(0);
+            for issue in &self.unused_exports {
+                let location = format!("{}:{}", issue.file, issue.line);
+                let _ = writeln!(output, "  {:<width$}  {}", location, issue.name);
+            }
+            output.push('\n');
+        }
+
+        if !self.circular_dependencies.is_empty() {
+            let _ = writeln!(
+                output,
+                "Circular dependencies ({})",
+                self.circular_dependencies.len()
             );
+            for cycle in &self.circular_dependencies {
+                let _ = writeln!(output, "  {}", format_cycle(cycle));
+            }
+            output.push('\n');
         }
 
-        if self.args.viz {
-            server::serve(document, self.args.port).await?;
+        if !self.orphan_files.is_empty() {
+            let _ = writeln!(output, "Orphan files ({})", self.orphan_files.len());
+            for file in &self.orphan_files {
+                let _ = writeln!(output, "  {}", file);
+            }
+            output.push('\n');
+        }
+
+        if !self.duplicate_packages.is_empty() {
+            let _ = writeln!(
+                output,
+                "Duplicate packages ({})",
+                self.duplicate_packages.len()
+            );
+            for duplicate in &self.duplicate_packages {
+                let _ = writeln!(output, "  {}", duplicate.name);
+                for copy in &duplicate.copies {
+                    let _ = writeln!(
+                        output,
+                        "    {}  {}",
+                        copy.version.as_deref().unwrap_or("unknown"),
+                        copy.root
+                    );
+                }
+            }
+            output.push('\n');
         }
 
-        Ok(())
+        let _ = writeln!(output, "{} issue(s) found", self.issue_count());
+        output
     }
+
+    /// Render the report as GitHub Actions workflow commands.
+    ///
+    /// `level` is the annotation command, `error` or `warning`. Findings
+    /// tied to a file annotate it; duplicate packages annotate the run.
+    pub fn to_github(&self, level: &str) -> String {
+        let mut output = String::new();
+
+        for issue in &self.unused_exports {
+            let _ = writeln!(
+                output,
+                "::{level} file={},line={},title=Unused export::{}",
+                escape_property(&issue.file),
+                issue.line.max(1),
+                escape_data(&format!("'{}' is exported but never imported", issue.name))
+            );
+        }
+        for cycle in &self.circular_dependencies {
+            let _ = writeln!(
+                output,
+                "::{level} file={},title=Circular dependency::{}",
+                escape_property(&cycle[0]),
+                escape_data(&format_cycle(cycle))
+            );
+        }
+        for file in &self.orphan_files {
+            let _ = writeln!(
+                output,
+                "::{level} file={},title=Orphan file::{}",
+                escape_property(file),
+                escape_data("File is not reachable from any entry point")
+            );
+        }
+        for duplicate in &self.duplicate_packages {
+            let copies = duplicate
+                .copies
+                .iter()
+                .map(|copy| match &copy.version {
+                    Some(version) => format!("{} ({})", copy.root, version),
+                    None => copy.root.clone(),
+                })
+                .collect::<Vec<_>>()
+                .join(", ");
+            let _ = writeln!(
+                output,
+                "::{level} title=Duplicate package::{}",
+                escape_data(&format!(
+                    "{} is bundled from {} copies: {}",
+                    duplicate.name,
+                    duplicate.copies.len(),
+                    copies
+                ))
+            );
+        }
+
+        output
+    }
+}
+
+/// The first cycle in a dependency chain, rotated to start at its smallest
+/// module so a cycle reached from different entries is reported once.
+fn first_cycle(path: &[String]) -> Option<Vec<String>> {
+    for (end, module) in path.iter().enumerate() {
+        if let Some(start) = path[..end].iter().position(|m| m == module) {
+            let mut cycle = path[start..end].to_vec();
+            let smallest = (0..cycle.len()).min_by_key(|&i| &cycle[i])?;
+            cycle.rotate_left(smallest);
+            return Some(cycle);
+        }
+    }
+    None
+}
+
+/// Format a cycle as `a -> b -> a`.
+fn format_cycle(cycle: &[String]) -> String {
+    let mut parts = cycle.to_vec();
+    parts.extend(cycle.first().cloned());
+    parts.join(" -> ")
+}
+
+/// 1-based line containing a byte offset.
+fn line_of(source: &[u8], offset: usize) -> usize {
+    let offset = offset.min(source.len());
+    source[..offset].iter().filter(|&&b| b == b'\n').count() + 1
 }
 
-async fn write_analysis_json(path: &str, analysis: &AnalysisDocument) -> Result<()> {
-    let json = analysis.to_pretty_json()?;
-    let path_buf = PathBuf::from(path);
-    if let Some(parent) = path_buf.parent() {
-        if !parent.as_os_str().is_empty() {
-            fs::create_dir_all(parent).await.ok();
+/// Recursively collect source files, skipping hidden directories,
+/// `node_modules`, declaration files and tests.
+fn collect_source_files(dir: &Path, files: &mut BTreeSet<PathBuf>) -> Result<()> {
+    if !dir.is_dir() {
+        return Err(CliError::FileNotFound(dir.to_path_buf()));
+    }
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        let name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        if path.is_dir() {
+            if !name.starts_with('.') && name != "node_modules" {
+                collect_source_files(&path, files)?;
+            }
+            continue;
+        }
+
+        let is_source = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .is_some_and(|ext| SOURCE_EXTENSIONS.contains(&ext));
+        let is_declaration = [".d.ts", ".d.mts", ".d.cts"]
+            .iter()
+            .any(|suffix| name.ends_with(suffix));
+        let is_test = name.contains(".test.") || name.contains(".spec.");
+        if is_source && !is_declaration && !is_test {
+            files.insert(path);
         }
     }
-    fs::write(path_buf, json.into_bytes()).await?;
     Ok(())
 }
+
+fn core_error(error: impl std::fmt::Display) -> CliError {
+    CliError::Core(error.to_string())
+}
+
+fn canonical(path: &Path) -> PathBuf {
+    std::fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf())
+}
+
+/// Path relative to `cwd` when it's inside it, with `/` separators.
+fn display_path(path: &Path, cwd: &Path) -> String {
+    path.strip_prefix(cwd)
+        .unwrap_or(path)
+        .to_string_lossy()
+        .replace('\\', "/")
+}
+
+/// Escape the message of a workflow command.
+fn escape_data(value: &str) -> String {
+    value
+        .replace('%', "%25")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
+
+/// Escape a property value (such as `file=`) of a workflow command.
+fn escape_property(value: &str) -> String {
+    escape_data(value).replace(':', "%3A").replace(',', "%2C")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn report() -> AnalyzeReport {
+        AnalyzeReport {
+            unused_exports: vec![UnusedExportIssue {
+                file: "src/utils.ts".to_string(),
+                line: 3,
+                name: "unused".to_string(),
+            }],
+            circular_dependencies: vec![vec!["src/a.ts".to_string(), "src/b.ts".to_string()]],
+            orphan_files: vec!["src/old.ts".to_string()],
+            duplicate_packages: vec![DuplicatePackageIssue {
+                name: "lodash".to_string(),
+                copies: vec![
+                    DuplicateCopy {
+                        root: "node_modules/lodash".to_string(),
+                        version: Some("4.17.21".to_string()),
+                    },
+                    DuplicateCopy {
+                        root: "node_modules/a/node_modules/lodash".to_string(),
+                        version: None,
+                    },
+                ],
+            }],
+        }
+    }
+
+    #[test]
+    fn test_first_cycle_is_rotated_to_smallest_module() {
+        let path = ["index.ts", "c.ts", "a.ts", "b.ts", "c.ts"]
+            .map(String::from)
+            .to_vec();
+        assert_eq!(
+            first_cycle(&path),
+            Some(["a.ts", "b.ts", "c.ts"].map(String::from).to_vec())
+        );
+
+        let acyclic = ["index.ts", "a.ts"].map(String::from).to_vec();
+        assert_eq!(first_cycle(&acyclic), None);
+    }
+
+    #[test]
+    fn test_line_of_offset() {
+        let source = b"import x from 'x';\n\nexport const a = 1;\n";
+        assert_eq!(line_of(source, 0), 1);
+        assert_eq!(line_of(source, 20), 3);
+        assert_eq!(line_of(source, 1000), 4);
+    }
+
+    #[test]
+    fn test_table_output() {
+        let table = report().to_table();
+        assert!(table.contains("Unused exports (1)\n  src/utils.ts:3  unused\n"));
+        assert!(table.contains("  src/a.ts -> src/b.ts -> src/a.ts\n"));
+        assert!(table.contains("Orphan files (1)\n  src/old.ts\n"));
+        assert!(table.contains("    4.17.21  node_modules/lodash\n"));
+        assert!(table.contains("    unknown  node_modules/a/node_modules/lodash\n"));
+        assert!(table.ends_with("4 issue(s) found\n"));
+
+        assert_eq!(AnalyzeReport::default().to_table(), "0 issue(s) found\n");
+    }
+
+    #[test]
+    fn test_github_annotations() {
+        let annotations = report().to_github("error");
+        let lines: Vec<&str> = annotations.lines().collect();
+        assert_eq!(
+            lines,
+            vec![
+                "::error file=src/utils.ts,line=3,title=Unused export::'unused' is exported but never imported",
+                "::error file=src/a.ts,title=Circular dependency::src/a.ts -> src/b.ts -> src/a.ts",
+                "::error file=src/old.ts,title=Orphan file::File is not reachable from any entry point",
+                "::error title=Duplicate package::lodash is bundled from 2 copies: node_modules/lodash (4.17.21), node_modules/a/node_modules/lodash",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_github_escaping() {
+        assert_eq!(escape_data("50%\nnext"), "50%25%0Anext");
+        assert_eq!(escape_property("C:\\a,b"), "C%3A\\a%2Cb");
+    }
+
+    #[test]
+    fn test_collect_source_files_skips_tests_and_dependencies() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let root = temp.path();
+        for file in [
+            "index.ts",
+            "lib/util.js",
+            "lib/util.test.ts",
+            "types.d.ts",
+            "README.md",
+            "node_modules/pkg/index.js",
+            ".cache/chunk.js",
+        ] {
+            let path = root.join(file);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, "").unwrap();
+        }
+
+        let mut files = BTreeSet::new();
+        collect_source_files(root, &mut files).unwrap();
+        let names: Vec<String> = files.iter().map(|file| display_path(file, root)).collect();
+        assert_eq!(names, vec!["index.ts", "lib/util.js"]);
+    }
+}
@@ -5,13 +5,23 @@
 
 use crate::cli::BuildArgs;
 use crate::commands::utils;
-use crate::config::FobConfig;
+use crate::config::{plugins, FobConfig};
+use crate::dev::FileWatcher;
 use crate::error::{BuildError, CliError, Result};
 use crate::ui;
 use fob_bundler::NativeRuntime;
-use std::path::Path;
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
-use std::time::Instant;
+use std::time::{Duration, Instant};
+use tokio::signal;
+
+pub(crate) mod summary;
+
+use summary::{BuildSummary, Timings};
+
+/// How long watch mode waits for more changes before rebuilding.
+const WATCH_DEBOUNCE: Duration = Duration::from_millis(100);
 
 /// Execute the build command.
 ///
@@ -22,7 +32,11 @@ use std::time::Instant;
 /// 3. Validate entry points
 /// 4. Execute build with progress tracking
 /// 5. Write output files
-/// 6. Display build summary
+/// 6. Display build summary (JSON on stdout with `--ci`)
+/// 7. Check `--max-chunk-size` and `--fail-on-warnings`
+/// 8. Run the `--on-success` hook, if any
+///
+/// With `--watch`, steps 4-8 repeat on every source change until Ctrl+C.
 ///
 /// # Arguments
 ///
@@ -35,12 +49,65 @@ use std::time::Instant;
 /// - Missing entry points
 /// - Build failures
 /// - File system errors
+/// - Policy violations ([`CliError::PolicyViolation`])
 pub async fn execute(args: BuildArgs) -> Result<()> {
     let start_time = Instant::now();
+    if args.watch {
+        let (config, cwd) = prepare(&args)?;
+        return watch(&config, &cwd, args.on_success.as_deref()).await;
+    }
+
+    // A failed build still produces a --ci summary, so collect the outcome
+    // before reporting it
+    let mut timings = Timings::default();
+    let outcome: Result<(FobConfig, PathBuf, fob_bundler::BuildResult)> = async {
+        let (config, cwd) = prepare(&args)?;
+        timings.setup_ms = Timings::millis(start_time.elapsed());
+
+        // Step 4: Execute build
+        let build_start = Instant::now();
+        let result = build_with_result(&config, &cwd).await?;
+        timings.build_ms = Timings::millis(build_start.elapsed());
+        Ok((config, cwd, result))
+    }
+    .await;
+    timings.total_ms = Timings::millis(start_time.elapsed());
+
+    let summary = match &outcome {
+        Ok((config, cwd, result)) => {
+            BuildSummary::from_result(result, cwd, &config.out_dir, args.max_chunk_size, timings)
+        }
+        Err(e) => BuildSummary::failed(e.to_string(), timings),
+    };
+    if args.ci {
+        write_summary(&summary, args.summary.as_deref())?;
+    }
+    let (_, cwd, _) = outcome?;
+
+    ui::success(&format!(
+        "Build completed in {}",
+        ui::format_duration(start_time.elapsed())
+    ));
+
+    // Step 7: Enforce size and warning policies
+    if let Some(violation) = summary.policy_violation(args.fail_on_warnings) {
+        return Err(CliError::PolicyViolation(violation));
+    }
+
+    // Step 8: Run the post-build hook
+    if let Some(command) = &args.on_success {
+        run_hook(command, &cwd).await?;
+    }
+
+    Ok(())
+}
 
+/// Load the configuration, prepare the output directory and validate entry
+/// points. Returns the config and the resolved project root.
+fn prepare(args: &BuildArgs) -> Result<(FobConfig, PathBuf)> {
     // Step 1: Load configuration
     ui::info("Loading configuration...");
-    let config = FobConfig::load(&args, None)?;
+    let config = FobConfig::load(args, None)?;
     config.validate()?;
 
     // Resolve project root using smart auto-detection
@@ -71,15 +138,129 @@ pub async fn execute(args: BuildArgs) -> Result<()> {
         utils::validate_entry(&entry_path)?;
     }
 
-    // Step 4: Execute build
-    build(&config, &cwd).await?;
+    Ok((config, cwd))
+}
 
-    let duration = start_time.elapsed();
-    ui::success(&format!(
-        "Build completed in {}",
-        ui::format_duration(duration)
+/// Print the `--ci` summary to stdout, or write it to `path`.
+fn write_summary(summary: &BuildSummary, path: Option<&Path>) -> Result<()> {
+    let json = serde_json::to_string_pretty(summary)?;
+    match path {
+        Some(path) => {
+            if let Some(parent) = path.parent() {
+                if !parent.as_os_str().is_empty() {
+                    std::fs::create_dir_all(parent)?;
+                }
+            }
+            std::fs::write(path, format!("{}\n", json))?;
+            ui::info(&format!("Wrote build summary to {}", path.display()));
+        }
+        None => println!("{}", json),
+    }
+    Ok(())
+}
+
+/// Rebuild on every source change until Ctrl+C.
+///
+/// Changes arriving within [`WATCH_DEBOUNCE`] of each other are batched into
+/// one rebuild. Build and hook failures are reported and watching continues.
+async fn watch(config: &FobConfig, cwd: &Path, on_success: Option<&str>) -> Result<()> {
+    rebuild(config, cwd, on_success).await;
+
+    let mut ignore = vec!["node_modules".to_string()];
+    let out_dir = utils::resolve_path(&config.out_dir, cwd);
+    if let Ok(relative) = out_dir.strip_prefix(cwd) {
+        ignore.push(relative.to_string_lossy().into_owned());
+    }
+    let (watcher, mut change_rx) =
+        FileWatcher::new(cwd.to_path_buf(), ignore, WATCH_DEBOUNCE.as_millis() as u64)?;
+    ui::info(&format!(
+        "Watching for changes in {} (Ctrl+C to stop)",
+        watcher.root().display()
     ));
 
+    loop {
+        tokio::select! {
+            Some(change) = change_rx.recv() => {
+                // Editors often write several files (or one file several
+                // times) per save, so wait for the burst to settle
+                let mut changed = BTreeSet::from([change.path().to_path_buf()]);
+                while let Ok(Some(change)) =
+                    tokio::time::timeout(WATCH_DEBOUNCE, change_rx.recv()).await
+                {
+                    changed.insert(change.path().to_path_buf());
+                }
+                ui::info(&describe_changes(&changed, cwd));
+
+                fob_bundler::diagnostics::clear_source_cache();
+                rebuild(config, cwd, on_success).await;
+            }
+            _ = signal::ctrl_c() => {
+                ui::info("Stopped watching");
+                return Ok(());
+            }
+        }
+    }
+}
+
+/// Run one watch-mode build and its hook, reporting the outcome.
+async fn rebuild(config: &FobConfig, cwd: &Path, on_success: Option<&str>) {
+    let start_time = Instant::now();
+    match build_incremental(config, cwd).await {
+        Ok(result) => {
+            ui::success(&format!(
+                "Rebuilt {} module(s) in {}",
+                result.stats().module_count,
+                ui::format_duration(start_time.elapsed())
+            ));
+            if let Some(command) = on_success {
+                if let Err(e) = run_hook(command, cwd).await {
+                    ui::error(&e.to_string());
+                }
+            }
+        }
+        Err(e) => ui::error(&format!(
+            "Build failed after {}: {}",
+            ui::format_duration(start_time.elapsed()),
+            e
+        )),
+    }
+}
+
+/// Summarize a batch of changed files, e.g. `Changed: src/a.ts, src/b.ts (+3 more)`.
+fn describe_changes(changed: &BTreeSet<PathBuf>, cwd: &Path) -> String {
+    const SHOWN: usize = 3;
+    let names = changed
+        .iter()
+        .take(SHOWN)
+        .map(|path| path.strip_prefix(cwd).unwrap_or(path).display().to_string())
+        .collect::<Vec<_>>()
+        .join(", ");
+    if changed.len() > SHOWN {
+        format!("Changed: {} (+{} more)", names, changed.len() - SHOWN)
+    } else {
+        format!("Changed: {}", names)
+    }
+}
+
+/// Run the `--on-success` command through the platform shell in `cwd`.
+async fn run_hook(command: &str, cwd: &Path) -> Result<()> {
+    ui::info(&format!("Running: {}", command));
+    let mut process = if cfg!(windows) {
+        let mut process = tokio::process::Command::new("cmd");
+        process.args(["/C", command]);
+        process
+    } else {
+        let mut process = tokio::process::Command::new("sh");
+        process.args(["-c", command]);
+        process
+    };
+    let status = process.current_dir(cwd).status().await?;
+    if !status.success() {
+        return Err(CliError::Build(BuildError::Custom(format!(
+            "Post-build command `{}` failed with {}",
+            command, status
+        ))));
+    }
     Ok(())
 }
 
@@ -94,9 +275,51 @@ pub async fn execute(args: BuildArgs) -> Result<()> {
 pub(crate) async fn build_with_result(
     config: &FobConfig,
     cwd: &std::path::Path,
+) -> Result<fob_bundler::BuildResult> {
+    run_build(config, cwd, false).await
+}
+
+/// Build with the module graph cached between runs, for watch mode.
+async fn build_incremental(config: &FobConfig, cwd: &Path) -> Result<fob_bundler::BuildResult> {
+    run_build(config, cwd, true).await
+}
+
+async fn run_build(
+    config: &FobConfig,
+    cwd: &Path,
+    incremental: bool,
 ) -> Result<fob_bundler::BuildResult> {
     validate_output_dir(&config.out_dir, cwd)?;
+    let result = bundle(config, cwd, incremental).await?;
+
+    // Write output (force overwrite for build command)
+    let resolved_out_dir = utils::resolve_path(&config.out_dir, cwd);
+    result.write_to_force(&resolved_out_dir).map_err(|e| {
+        CliError::Build(BuildError::Custom(format!("Failed to write output: {}", e)))
+    })?;
 
+    ui::success(&format!("Built to {}", config.out_dir.display()));
+
+    for warning in &result.analysis.warnings {
+        ui::warning(warning);
+    }
+
+    Ok(result)
+}
+
+/// Build without writing the output, for commands that only inspect it.
+pub(crate) async fn build_in_memory(
+    config: &FobConfig,
+    cwd: &Path,
+) -> Result<fob_bundler::BuildResult> {
+    bundle(config, cwd, false).await
+}
+
+async fn bundle(
+    config: &FobConfig,
+    cwd: &Path,
+    incremental: bool,
+) -> Result<fob_bundler::BuildResult> {
     // Display build info
     if config.entry.len() == 1 {
         ui::info(&format!("Building: {}", config.entry[0]));
@@ -149,11 +372,17 @@ pub(crate) async fn build_with_result(
     };
 
     // Apply common configuration
+    let runtime: Arc<dyn fob_bundler::Runtime> = Arc::new(NativeRuntime);
     builder = builder
         .format(convert_format(config.format))
         .platform(convert_platform(config.platform))
         .cwd(cwd)
-        .runtime(Arc::new(NativeRuntime));
+        .runtime(Arc::clone(&runtime));
+
+    // Built-in plugins declared in the config file
+    for plugin in plugins::instantiate(&config.plugins, runtime, cwd)? {
+        builder = builder.plugin(plugin);
+    }
 
     // Minification
     if config.minify {
@@ -179,40 +408,31 @@ pub(crate) async fn build_with_result(
         builder = builder.globals_map([("__self__".to_string(), name.clone())]);
     }
 
+    // Graph caching between watch-mode rebuilds
+    if incremental {
+        builder = builder.incremental_dir(cwd.join(".fob-cache"));
+    }
+
+    // Post-emit validation
+    if config.validate_output {
+        builder = builder.validate_output(convert_target(config.target));
+    }
+
     // TypeScript declarations
     #[cfg(feature = "dts-generation")]
     {
         if config.dts {
-            builder = builder.emit_dts(true);
+            builder = builder
+                .emit_dts(true)
+                .bundle_dts(config.dts_bundle.unwrap_or(false));
         }
     }
 
     // Build
-    let result = builder
+    builder
         .build()
         .await
-        .map_err(|e| CliError::Build(BuildError::Custom(format!("Build failed: {}", e))))?;
-
-    // Write output (force overwrite for build command)
-    let resolved_out_dir = utils::resolve_path(&config.out_dir, cwd);
-    result.write_to_force(&resolved_out_dir).map_err(|e| {
-        CliError::Build(BuildError::Custom(format!("Failed to write output: {}", e)))
-    })?;
-
-    ui::success(&format!("Built to {}", config.out_dir.display()));
-
-    Ok(result)
-}
-
-/// Unified build function that applies configuration directly.
-///
-/// Builds based on configuration using composable primitives:
-/// - bundle=false → library mode (externalize deps)
-/// - splitting=true → app mode (code splitting)
-/// - Otherwise → components or standalone mode
-pub(crate) async fn build(config: &FobConfig, cwd: &std::path::Path) -> Result<()> {
-    build_with_result(config, cwd).await?;
-    Ok(())
+        .map_err(|e| CliError::Build(BuildError::Custom(format!("Build failed: {}", e))))
 }
 
 /// Validates that the output directory is safe to write to.
@@ -300,6 +520,24 @@ fn convert_format(format: crate::config::Format) -> fob_bundler::OutputFormat {
     }
 }
 
+/// Convert CLI target enum to fob-bundler SyntaxTarget
+fn convert_target(target: crate::config::EsTarget) -> fob_bundler::SyntaxTarget {
+    use crate::config::EsTarget;
+    use fob_bundler::SyntaxTarget;
+
+    match target {
+        EsTarget::Es2015 => SyntaxTarget::Es2015,
+        EsTarget::Es2016 => SyntaxTarget::Es2016,
+        EsTarget::Es2017 => SyntaxTarget::Es2017,
+        EsTarget::Es2018 => SyntaxTarget::Es2018,
+        EsTarget::Es2019 => SyntaxTarget::Es2019,
+        EsTarget::Es2020 => SyntaxTarget::Es2020,
+        EsTarget::Es2021 => SyntaxTarget::Es2021,
+        EsTarget::Es2022 => SyntaxTarget::Es2022,
+        EsTarget::Esnext => SyntaxTarget::EsNext,
+    }
+}
+
 /// Convert CLI platform enum to fob-bundler Platform
 fn convert_platform(platform: crate::config::Platform) -> fob_bundler::Platform {
     match platform {
@@ -0,0 +1,280 @@
+//! Machine-readable build summary for `fob build --ci`.
+//!
+//! The summary is also what `--max-chunk-size` and `--fail-on-warnings` are
+//! checked against, so CI and interactive builds apply the same policies,
+//! and what `fob compare` diffs between two builds.
+
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Outcome of one build, serialized as the `--ci` JSON summary.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub(crate) struct BuildSummary {
+    /// Whether the build itself succeeded (policies aside)
+    pub success: bool,
+    /// Error message of a failed build
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// Output directory, as configured
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub out_dir: Option<PathBuf>,
+    /// Number of modules in the graph
+    pub modules: usize,
+    /// Combined size of chunks and assets in bytes
+    pub total_size: u64,
+    /// Emitted JavaScript chunks, sorted by file name
+    pub chunks: Vec<ChunkSummary>,
+    /// Emitted static assets, sorted by file name
+    pub assets: Vec<AssetSummary>,
+    /// Warnings reported by the build
+    pub warnings: Vec<String>,
+    /// `--max-chunk-size` status, when a budget was given
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub budget: Option<BudgetSummary>,
+    /// Wall-clock timings
+    pub timings: Timings,
+}
+
+/// One emitted chunk.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ChunkSummary {
+    pub file: String,
+    pub size: u64,
+    pub entry: bool,
+    /// Modules rendered into the chunk, relative to the project root
+    #[serde(default)]
+    pub modules: Vec<String>,
+}
+
+/// One emitted asset.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct AssetSummary {
+    pub file: String,
+    pub size: u64,
+}
+
+/// Result of checking chunk sizes against `--max-chunk-size`.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct BudgetSummary {
+    pub max_chunk_size: u64,
+    /// Chunks larger than the budget
+    pub exceeded: Vec<String>,
+}
+
+/// Build timings in milliseconds.
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct Timings {
+    /// Loading config, validating entries and preparing the output directory
+    pub setup_ms: u64,
+    /// Bundling and writing output
+    pub build_ms: u64,
+    pub total_ms: u64,
+}
+
+impl Timings {
+    pub fn millis(duration: Duration) -> u64 {
+        duration.as_millis() as u64
+    }
+}
+
+impl Default for BuildSummary {
+    fn default() -> Self {
+        Self {
+            success: false,
+            error: None,
+            out_dir: None,
+            modules: 0,
+            total_size: 0,
+            chunks: Vec::new(),
+            assets: Vec::new(),
+            warnings: Vec::new(),
+            budget: None,
+            timings: Timings::default(),
+        }
+    }
+}
+
+impl BuildSummary {
+    /// Summarize a successful build.
+    pub fn from_result(
+        result: &fob_bundler::BuildResult,
+        cwd: &Path,
+        out_dir: &Path,
+        max_chunk_size: Option<u64>,
+        timings: Timings,
+    ) -> Self {
+        let mut chunks: Vec<_> = result
+            .chunks()
+            .map(|chunk| ChunkSummary {
+                file: chunk.filename.to_string(),
+                size: chunk.code.len() as u64,
+                entry: chunk.is_entry,
+                modules: chunk
+                    .module_ids
+                    .iter()
+                    .map(|id| {
+                        let id = id.to_string();
+                        match Path::new(&id).strip_prefix(cwd) {
+                            Ok(relative) => relative.to_string_lossy().replace('\\', "/"),
+                            Err(_) => id,
+                        }
+                    })
+                    .collect(),
+            })
+            .collect();
+        chunks.sort_by(|a, b| a.file.cmp(&b.file));
+
+        let mut assets: Vec<_> = result
+            .assets()
+            .map(|asset| AssetSummary {
+                file: asset.filename.to_string(),
+                size: asset.source.as_bytes().len() as u64,
+            })
+            .collect();
+        assets.sort_by(|a, b| a.file.cmp(&b.file));
+
+        let budget = max_chunk_size.map(|max_chunk_size| BudgetSummary {
+            max_chunk_size,
+            exceeded: chunks
+                .iter()
+                .filter(|chunk| chunk.size > max_chunk_size)
+                .map(|chunk| chunk.file.clone())
+                .collect(),
+        });
+
+        Self {
+            success: true,
+            error: None,
+            out_dir: Some(out_dir.to_path_buf()),
+            modules: result.stats().module_count,
+            total_size: result.total_size() as u64,
+            chunks,
+            assets,
+            warnings: result.analysis.warnings.clone(),
+            budget,
+            timings,
+        }
+    }
+
+    /// Summarize a build that failed before producing output.
+    pub fn failed(error: String, timings: Timings) -> Self {
+        Self {
+            error: Some(error),
+            timings,
+            ..Self::default()
+        }
+    }
+
+    /// Describe why a successful build should still fail, if it should.
+    pub fn policy_violation(&self, fail_on_warnings: bool) -> Option<String> {
+        if let Some(budget) = &self.budget {
+            if !budget.exceeded.is_empty() {
+                return Some(format!(
+                    "{} chunk(s) exceed --max-chunk-size of {}: {}",
+                    budget.exceeded.len(),
+                    crate::ui::format_size(budget.max_chunk_size),
+                    budget.exceeded.join(", ")
+                ));
+            }
+        }
+        if fail_on_warnings && !self.warnings.is_empty() {
+            return Some(format!(
+                "Build reported {} warning(s) and --fail-on-warnings is set",
+                self.warnings.len()
+            ));
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn summary(sizes: &[(&str, u64)], max_chunk_size: Option<u64>) -> BuildSummary {
+        let mut summary = BuildSummary {
+            success: true,
+            ..BuildSummary::default()
+        };
+        summary.chunks = sizes
+            .iter()
+            .map(|(file, size)| ChunkSummary {
+                file: file.to_string(),
+                size: *size,
+                entry: false,
+                modules: Vec::new(),
+            })
+            .collect();
+        summary.budget = max_chunk_size.map(|max_chunk_size| BudgetSummary {
+            max_chunk_size,
+            exceeded: sizes
+                .iter()
+                .filter(|(_, size)| *size > max_chunk_size)
+                .map(|(file, _)| file.to_string())
+                .collect(),
+        });
+        summary
+    }
+
+    #[test]
+    fn test_within_budget_passes() {
+        let summary = summary(&[("index.js", 1024)], Some(1024));
+        assert_eq!(summary.policy_violation(true), None);
+    }
+
+    #[test]
+    fn test_over_budget_fails() {
+        let summary = summary(&[("index.js", 1025), ("chunk.js", 10)], Some(1024));
+        let violation = summary.policy_violation(false).unwrap();
+        assert!(violation.contains("1 chunk(s)"));
+        assert!(violation.contains("index.js"));
+    }
+
+    #[test]
+    fn test_warnings_fail_only_when_requested() {
+        let mut summary = summary(&[("index.js", 1)], None);
+        summary.warnings.push("Unused import".to_string());
+
+        assert_eq!(summary.policy_violation(false), None);
+        assert!(summary.policy_violation(true).is_some());
+    }
+
+    #[test]
+    fn test_json_shape() {
+        let summary = summary(&[("index.js", 2048)], Some(1024));
+        let json = serde_json::to_value(&summary).unwrap();
+
+        assert_eq!(json["success"], true);
+        assert_eq!(json["chunks"][0]["file"], "index.js");
+        assert_eq!(json["chunks"][0]["size"], 2048);
+        assert_eq!(json["budget"]["maxChunkSize"], 1024);
+        assert_eq!(json["budget"]["exceeded"][0], "index.js");
+        assert!(json["timings"]["totalMs"].is_u64());
+        assert!(json.get("error").is_none());
+    }
+
+    #[test]
+    fn test_round_trips_through_json() {
+        let summary = summary(&[("index.js", 2048)], Some(1024));
+        let json = serde_json::to_string(&summary).unwrap();
+
+        let parsed: BuildSummary = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.chunks[0].file, "index.js");
+        assert_eq!(parsed.budget.unwrap().exceeded, vec!["index.js"]);
+    }
+
+    #[test]
+    fn test_failed_summary_has_error() {
+        let summary = BuildSummary::failed("Build failed: boom".to_string(), Timings::default());
+        let json = serde_json::to_value(&summary).unwrap();
+
+        assert_eq!(json["success"], false);
+        assert_eq!(json["error"], "Build failed: boom");
+    }
+}
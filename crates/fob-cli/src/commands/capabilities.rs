@@ -0,0 +1,79 @@
+//! `fob --capabilities` implementation.
+//!
+//! Prints the features, built-in plugins and limits of this build, either as
+//! a human-readable summary or as the stable JSON report from
+//! [`fob_bundler::capabilities`].
+
+use crate::error::Result;
+use crate::ui::format_size;
+use fob_bundler::Capabilities;
+
+/// Print the capabilities report.
+///
+/// # Arguments
+///
+/// * `json` - Print the machine-readable JSON report instead of a summary
+pub fn execute(json: bool) -> Result<()> {
+    let capabilities = fob_bundler::capabilities();
+    if json {
+        println!("{}", serde_json::to_string_pretty(&capabilities)?);
+    } else {
+        print!("{}", summary(&capabilities));
+    }
+    Ok(())
+}
+
+/// Render the human-readable summary.
+fn summary(capabilities: &Capabilities) -> String {
+    let features = if capabilities.features.is_empty() {
+        "none".to_string()
+    } else {
+        capabilities.features.join(", ")
+    };
+    let limits = &capabilities.limits;
+
+    let mut out = format!(
+        "fob {} (capabilities schema v{})\n\n",
+        capabilities.version, capabilities.schema_version
+    );
+    out.push_str(&format!("Features:        {}\n", features));
+    out.push_str(&format!(
+        "Output formats:  {}\n\nPlugins:\n",
+        capabilities.output_formats.join(", ")
+    ));
+    for plugin in &capabilities.plugins {
+        out.push_str(&format!("  {:<22}{}\n", plugin.name, plugin.description));
+    }
+    out.push_str("\nLimits:\n");
+    for (name, value) in [
+        ("Entry points", limits.max_entry_points.to_string()),
+        ("Modules", limits.max_modules.to_string()),
+        ("Import depth", limits.max_depth.to_string()),
+        ("Source file size", format_size(limits.max_file_size as u64)),
+        ("Asset size", format_size(limits.max_asset_size)),
+        (
+            "Virtual file size",
+            format_size(limits.max_virtual_file_size as u64),
+        ),
+        (
+            "Chunk size warning",
+            format_size(limits.chunk_size_warning_limit as u64),
+        ),
+    ] {
+        out.push_str(&format!("  {:<22}{}\n", name, value));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_summary_lists_plugins_and_limits() {
+        let output = summary(&fob_bundler::capabilities());
+        assert!(output.starts_with(&format!("fob {}", env!("CARGO_PKG_VERSION"))));
+        assert!(output.contains("fob-css"));
+        assert!(output.contains("Asset size            50.00 MB"));
+    }
+}
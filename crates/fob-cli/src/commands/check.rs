@@ -1,12 +1,14 @@
 //! Check command implementation.
 //!
-//! Validates configuration and dependencies without building.
+//! Validates configuration and dependencies without building. Only
+//! `--exports` builds, in memory, to compare package.json against the output.
 
 use crate::cli::CheckArgs;
-use crate::commands::utils;
+use crate::commands::{build, utils};
 use crate::config::{FobConfig, Format};
-use crate::error::{ConfigError, Result};
+use crate::error::{CliError, ConfigError, Result};
 use crate::ui;
+use fob_bundler::{validate_package_exports, PackageOutputs};
 use std::path::Path;
 
 /// Execute the check command.
@@ -18,6 +20,7 @@ use std::path::Path;
 /// 3. Validate format/option combinations
 /// 4. Check dependencies (if --deps flag)
 /// 5. Report warnings (if --warnings flag)
+/// 6. Check package.json entry points against an in-memory build (if --exports flag)
 ///
 /// # Arguments
 ///
@@ -40,6 +43,14 @@ pub async fn execute(args: CheckArgs) -> Result<()> {
                 .map_err(|_| ConfigError::NotFound(default_path.to_path_buf()))?
         } else {
             ui::warning("No fob.config.json found, using defaults");
+            if args.exports {
+                return Err(ConfigError::MissingField {
+                    field: "entry".to_string(),
+                    hint: "Checking package.json entry points builds the configured entries"
+                        .to_string(),
+                }
+                .into());
+            }
             return Ok(());
         }
     };
@@ -86,6 +97,10 @@ pub async fn execute(args: CheckArgs) -> Result<()> {
         check_warnings(&config);
     }
 
+    if args.exports {
+        check_package_exports(&config, &cwd).await?;
+    }
+
     ui::success("All checks passed!");
     Ok(())
 }
@@ -151,6 +166,46 @@ fn check_dependencies(cwd: &Path) -> Result<()> {
     Ok(())
 }
 
+/// Check package.json entry points against the files a build produces.
+///
+/// The build runs in memory, so stale files in the output directory are
+/// not mistaken for outputs and nothing is written.
+async fn check_package_exports(config: &FobConfig, cwd: &Path) -> Result<()> {
+    ui::info("Checking package.json entry points...");
+    let package_json_path = cwd.join("package.json");
+    if !package_json_path.exists() {
+        ui::warning("No package.json found");
+        return Ok(());
+    }
+    let package_json = std::fs::read_to_string(&package_json_path)?;
+
+    let out_dir = utils::resolve_path(&config.out_dir, cwd);
+    let out_dir = out_dir.strip_prefix(cwd).map_err(|_| {
+        CliError::Custom(format!(
+            "Output directory is outside the package: {}",
+            out_dir.display()
+        ))
+    })?;
+    let out_dir = out_dir.to_string_lossy().replace('\\', "/");
+
+    let result = build::build_in_memory(config, cwd).await?;
+    let outputs = PackageOutputs::from_build_result(&result, &out_dir);
+
+    let issues = validate_package_exports(&package_json, &outputs)
+        .map_err(|e| CliError::Core(e.to_string()))?;
+    if issues.is_empty() {
+        ui::success("  package.json entry points match the build output");
+        return Ok(());
+    }
+    for issue in &issues {
+        ui::error(&format!("  {}", issue));
+    }
+    Err(CliError::Custom(format!(
+        "Found {} problem(s) in package.json entry points",
+        issues.len()
+    )))
+}
+
 /// Check for potential issues and report warnings.
 fn check_warnings(config: &FobConfig) {
     let mut warnings = Vec::new();
@@ -223,6 +278,8 @@ mod tests {
             splitting: false,
             no_treeshake: false,
             clean: false,
+            validate_output: false,
+            plugins: Vec::new(),
             cwd: None,
         }
     }
@@ -287,6 +344,34 @@ mod tests {
         assert!(validate_options(&config).is_ok());
     }
 
+    #[tokio::test]
+    async fn test_check_package_exports_ignores_stale_files() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("src")).unwrap();
+        std::fs::write(dir.path().join("src/index.js"), "export const a = 1;").unwrap();
+        // Left over from an earlier build, not produced by this one
+        std::fs::create_dir_all(dir.path().join("dist")).unwrap();
+        std::fs::write(dir.path().join("dist/extra.js"), "export const b = 2;").unwrap();
+
+        let mut config = test_config();
+        config.entry = vec!["src/index.js".to_string()];
+        config.cwd = Some(dir.path().to_path_buf());
+
+        std::fs::write(
+            dir.path().join("package.json"),
+            r#"{ "type": "module", "exports": { ".": "./dist/index.js" } }"#,
+        )
+        .unwrap();
+        assert!(check_package_exports(&config, dir.path()).await.is_ok());
+
+        std::fs::write(
+            dir.path().join("package.json"),
+            r#"{ "exports": { ".": "./dist/index.js", "./extra": "./dist/extra.js" } }"#,
+        )
+        .unwrap();
+        assert!(check_package_exports(&config, dir.path()).await.is_err());
+    }
+
     #[test]
     fn test_check_warnings_generates_warnings() {
         let mut config = test_config();
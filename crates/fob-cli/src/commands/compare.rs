@@ -0,0 +1,371 @@
+//! Compare command implementation.
+//!
+//! `fob compare` diffs two build summaries written by
+//! `fob build --ci --summary <FILE>`: size changes per chunk, the total
+//! size change, and modules that were added to or removed from the output.
+//! The markdown format is meant to be posted as a pull request comment.
+
+use crate::cli::{CompareArgs, CompareFormat};
+use crate::commands::build::summary::BuildSummary;
+use crate::error::{CliError, Result};
+use crate::ui::format_size;
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write;
+use std::path::Path;
+
+/// Execute the compare command.
+///
+/// # Errors
+///
+/// Returns an error if either summary can't be read or parsed.
+pub async fn execute(args: CompareArgs) -> Result<()> {
+    let old = read_summary(&args.old)?;
+    let new = read_summary(&args.new)?;
+    let comparison = Comparison::new(&old, &new);
+
+    let output = match args.format {
+        CompareFormat::Text => comparison.to_text(),
+        CompareFormat::Markdown => comparison.to_markdown(),
+        CompareFormat::Json => serde_json::to_string_pretty(&comparison)? + "\n",
+    };
+    print!("{}", output);
+    Ok(())
+}
+
+fn read_summary(path: &Path) -> Result<BuildSummary> {
+    if !path.exists() {
+        return Err(CliError::FileNotFound(path.to_path_buf()));
+    }
+    let contents = std::fs::read_to_string(path)?;
+    serde_json::from_str(&contents).map_err(|e| {
+        CliError::InvalidArgument(format!(
+            "{} is not a build summary: {}\n\nHint: Write one with `fob build --ci --summary {}`",
+            path.display(),
+            e,
+            path.display()
+        ))
+    })
+}
+
+/// Differences between two build summaries.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Comparison {
+    /// Chunks of either build, paired by name
+    pub chunks: Vec<ChunkDelta>,
+    pub old_total_size: u64,
+    pub new_total_size: u64,
+    /// Modules only in the new build
+    pub added_modules: Vec<String>,
+    /// Modules only in the old build
+    pub removed_modules: Vec<String>,
+}
+
+/// Size change of one chunk. A missing size means the chunk only exists in
+/// the other build.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChunkDelta {
+    /// File name in the new build, or in the old one for removed chunks
+    pub file: String,
+    pub old_size: Option<u64>,
+    pub new_size: Option<u64>,
+}
+
+impl ChunkDelta {
+    fn delta(&self) -> i64 {
+        self.new_size.unwrap_or(0) as i64 - self.old_size.unwrap_or(0) as i64
+    }
+}
+
+impl Comparison {
+    /// Pair the chunks of both builds and diff their modules.
+    ///
+    /// Chunks are paired by file name with any content hash removed, so
+    /// `shared-3fa9c1d2.js` in one build matches `shared-8be0a7f1.js` in
+    /// the other.
+    pub(crate) fn new(old: &BuildSummary, new: &BuildSummary) -> Self {
+        let mut paired: BTreeMap<String, ChunkDelta> = BTreeMap::new();
+        for chunk in &old.chunks {
+            paired.insert(
+                chunk_key(&chunk.file),
+                ChunkDelta {
+                    file: chunk.file.clone(),
+                    old_size: Some(chunk.size),
+                    new_size: None,
+                },
+            );
+        }
+        for chunk in &new.chunks {
+            let delta = paired
+                .entry(chunk_key(&chunk.file))
+                .or_insert_with(|| ChunkDelta {
+                    file: chunk.file.clone(),
+                    old_size: None,
+                    new_size: None,
+                });
+            delta.file = chunk.file.clone();
+            delta.new_size = Some(chunk.size);
+        }
+
+        let modules = |summary: &BuildSummary| {
+            summary
+                .chunks
+                .iter()
+                .flat_map(|chunk| chunk.modules.iter().cloned())
+                .collect::<BTreeSet<_>>()
+        };
+        let old_modules = modules(old);
+        let new_modules = modules(new);
+
+        Self {
+            chunks: paired.into_values().collect(),
+            old_total_size: old.total_size,
+            new_total_size: new.total_size,
+            added_modules: new_modules.difference(&old_modules).cloned().collect(),
+            removed_modules: old_modules.difference(&new_modules).cloned().collect(),
+        }
+    }
+
+    fn total_delta(&self) -> i64 {
+        self.new_total_size as i64 - self.old_total_size as i64
+    }
+
+    /// Plain-text report for the terminal.
+    pub fn to_text(&self) -> String {
+        let width = self
+            .chunks
+            .iter()
+            .map(|chunk| chunk.file.len())
+            .chain(["Chunk".len(), "Total".len()])
+            .max()
+            .unwrap_or(0);
+
+        let mut out = String::new();
+        let _ = writeln!(
+            out,
+            "{:<width$}  {:>10}  {:>10}  Change",
+            "Chunk", "Old", "New"
+        );
+        for chunk in &self.chunks {
+            let _ = writeln!(
+                out,
+                "{:<width$}  {:>10}  {:>10}  {}",
+                chunk.file,
+                size_or_dash(chunk.old_size),
+                size_or_dash(chunk.new_size),
+                describe_change(chunk.old_size, chunk.new_size, chunk.delta())
+            );
+        }
+        let _ = writeln!(
+            out,
+            "{:<width$}  {:>10}  {:>10}  {}",
+            "Total",
+            format_size(self.old_total_size),
+            format_size(self.new_total_size),
+            describe_change(
+                Some(self.old_total_size),
+                Some(self.new_total_size),
+                self.total_delta()
+            )
+        );
+
+        for (title, sign, modules) in [
+            ("Added", '+', &self.added_modules),
+            ("Removed", '-', &self.removed_modules),
+        ] {
+            if !modules.is_empty() {
+                let _ = writeln!(out, "\n{} modules ({}):", title, modules.len());
+                for module in modules {
+                    let _ = writeln!(out, "  {} {}", sign, module);
+                }
+            }
+        }
+        out
+    }
+
+    /// Markdown report, e.g. for a pull request comment.
+    pub fn to_markdown(&self) -> String {
+        let mut out = String::new();
+        let _ = writeln!(
+            out,
+            "### Bundle size: {} ({})\n",
+            format_size(self.new_total_size),
+            describe_change(
+                Some(self.old_total_size),
+                Some(self.new_total_size),
+                self.total_delta()
+            )
+        );
+        let _ = writeln!(out, "| Chunk | Old | New | Change |");
+        let _ = writeln!(out, "| --- | ---: | ---: | ---: |");
+        for chunk in &self.chunks {
+            let _ = writeln!(
+                out,
+                "| `{}` | {} | {} | {} |",
+                chunk.file,
+                size_or_dash(chunk.old_size),
+                size_or_dash(chunk.new_size),
+                describe_change(chunk.old_size, chunk.new_size, chunk.delta())
+            );
+        }
+
+        if !self.added_modules.is_empty() || !self.removed_modules.is_empty() {
+            let _ = writeln!(
+                out,
+                "\n<details>\n<summary>{} module(s) added, {} removed</summary>\n",
+                self.added_modules.len(),
+                self.removed_modules.len()
+            );
+            for (title, modules) in [
+                ("Added", &self.added_modules),
+                ("Removed", &self.removed_modules),
+            ] {
+                if !modules.is_empty() {
+                    let _ = writeln!(out, "**{}**\n", title);
+                    for module in modules {
+                        let _ = writeln!(out, "- `{}`", module);
+                    }
+                    out.push('\n');
+                }
+            }
+            let _ = writeln!(out, "</details>");
+        }
+        out
+    }
+}
+
+/// Chunk file name without its content hash, used to pair chunks across
+/// builds.
+fn chunk_key(file: &str) -> String {
+    let (stem, ext) = match file.rfind('.') {
+        Some(dot) => file.split_at(dot),
+        None => (file, ""),
+    };
+    match stem.rsplit_once('-') {
+        Some((name, hash))
+            if hash.len() == 8 && hash.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') =>
+        {
+            format!("{}{}", name, ext)
+        }
+        _ => file.to_string(),
+    }
+}
+
+fn size_or_dash(size: Option<u64>) -> String {
+    size.map(format_size).unwrap_or_else(|| "-".to_string())
+}
+
+/// `+1.50 KB (+12.5%)`, `unchanged`, `added` or `removed`.
+fn describe_change(old: Option<u64>, new: Option<u64>, delta: i64) -> String {
+    match (old, new) {
+        (None, Some(_)) => "added".to_string(),
+        (Some(_), None) => "removed".to_string(),
+        _ if delta == 0 => "unchanged".to_string(),
+        (Some(old), _) => {
+            let sign = if delta > 0 { '+' } else { '-' };
+            let size = format_size(delta.unsigned_abs());
+            if old == 0 {
+                format!("{}{}", sign, size)
+            } else {
+                let percent = delta as f64 / old as f64 * 100.0;
+                format!("{}{} ({:+.1}%)", sign, size, percent)
+            }
+        }
+        (None, None) => "unchanged".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::build::summary::ChunkSummary;
+
+    fn summary(chunks: &[(&str, u64, &[&str])]) -> BuildSummary {
+        let mut summary = BuildSummary {
+            success: true,
+            ..BuildSummary::default()
+        };
+        summary.chunks = chunks
+            .iter()
+            .map(|(file, size, modules)| ChunkSummary {
+                file: file.to_string(),
+                size: *size,
+                entry: false,
+                modules: modules.iter().map(|m| m.to_string()).collect(),
+            })
+            .collect();
+        summary.total_size = summary.chunks.iter().map(|chunk| chunk.size).sum();
+        summary
+    }
+
+    #[test]
+    fn test_chunk_key_strips_hash() {
+        assert_eq!(chunk_key("shared-3fa9c1d2.js"), "shared.js");
+        assert_eq!(chunk_key("index.js"), "index.js");
+        assert_eq!(chunk_key("my-button.js"), "my-button.js");
+    }
+
+    #[test]
+    fn test_pairs_chunks_and_diffs_modules() {
+        let old = summary(&[
+            ("index.js", 1000, &["src/index.ts", "src/old.ts"]),
+            ("shared-aaaaaaaa.js", 200, &["src/shared.ts"]),
+            ("legacy.js", 50, &["src/legacy.ts"]),
+        ]);
+        let new = summary(&[
+            ("index.js", 1100, &["src/index.ts", "src/new.ts"]),
+            ("shared-bbbbbbbb.js", 200, &["src/shared.ts"]),
+        ]);
+
+        let comparison = Comparison::new(&old, &new);
+
+        let files: Vec<_> = comparison.chunks.iter().map(|c| c.file.as_str()).collect();
+        assert_eq!(files, ["index.js", "legacy.js", "shared-bbbbbbbb.js"]);
+        assert_eq!(comparison.chunks[0].delta(), 100);
+        assert_eq!(comparison.chunks[1].new_size, None);
+        assert_eq!(comparison.chunks[2].delta(), 0);
+        assert_eq!(comparison.added_modules, ["src/new.ts"]);
+        assert_eq!(comparison.removed_modules, ["src/legacy.ts", "src/old.ts"]);
+    }
+
+    #[test]
+    fn test_describe_change() {
+        assert_eq!(
+            describe_change(Some(1024), Some(2048), 1024),
+            "+1.00 KB (+100.0%)"
+        );
+        assert_eq!(
+            describe_change(Some(1000), Some(900), -100),
+            "-100 B (-10.0%)"
+        );
+        assert_eq!(describe_change(Some(10), Some(10), 0), "unchanged");
+        assert_eq!(describe_change(None, Some(10), 10), "added");
+        assert_eq!(describe_change(Some(10), None, -10), "removed");
+    }
+
+    #[test]
+    fn test_markdown_report() {
+        let old = summary(&[("index.js", 1000, &["src/index.ts"])]);
+        let new = summary(&[("index.js", 1000, &["src/index.ts", "src/new.ts"])]);
+
+        let markdown = Comparison::new(&old, &new).to_markdown();
+
+        assert!(markdown.starts_with("### Bundle size:"));
+        assert!(markdown.contains("| `index.js` | 1000 B | 1000 B | unchanged |"));
+        assert!(markdown.contains("1 module(s) added, 0 removed"));
+        assert!(markdown.contains("- `src/new.ts`"));
+    }
+
+    #[test]
+    fn test_text_report() {
+        let old = summary(&[("index.js", 1000, &["src/a.ts"])]);
+        let new = summary(&[("index.js", 1500, &[])]);
+
+        let text = Comparison::new(&old, &new).to_text();
+
+        assert!(text.contains("+500 B (+50.0%)"));
+        assert!(text.contains("Removed modules (1):\n  - src/a.ts"));
+    }
+}
@@ -0,0 +1,39 @@
+//! Completions command implementation.
+//!
+//! Generates shell completion scripts from the clap definitions, so they
+//! always match the installed binary.
+
+use clap::CommandFactory;
+
+use crate::cli::{Cli, CompletionsArgs};
+use crate::error::Result;
+
+/// Print the completion script for the requested shell to stdout.
+pub async fn execute(args: CompletionsArgs) -> Result<()> {
+    let mut command = Cli::command();
+    let name = command.get_name().to_string();
+    clap_complete::generate(args.shell, &mut command, name, &mut std::io::stdout());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap_complete::Shell;
+
+    fn completions(shell: Shell) -> String {
+        let mut command = Cli::command();
+        let mut out = Vec::new();
+        clap_complete::generate(shell, &mut command, "fob", &mut out);
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn test_completions_include_subcommands() {
+        for shell in [Shell::Bash, Shell::Zsh, Shell::Fish, Shell::PowerShell] {
+            let script = completions(shell);
+            assert!(script.contains("build"), "{shell}");
+            assert!(script.contains("completions"), "{shell}");
+        }
+    }
+}
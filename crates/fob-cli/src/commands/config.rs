@@ -0,0 +1,40 @@
+//! Config command implementation.
+//!
+//! `fob config schema` prints the JSON Schema for `fob.toml` so editors can
+//! complete and validate config files, e.g. by saving it next to the project
+//! and adding `#:schema ./fob.schema.json` to the top of `fob.toml`.
+
+use crate::cli::{ConfigArgs, ConfigCommand, ConfigSchemaArgs};
+use crate::error::Result;
+use crate::ui;
+
+/// Execute a config subcommand.
+pub async fn execute(args: ConfigArgs) -> Result<()> {
+    match args.command {
+        ConfigCommand::Schema(schema_args) => schema(schema_args),
+    }
+}
+
+/// Print or write the config JSON Schema.
+///
+/// # Errors
+///
+/// Returns an error if the output file can't be written.
+fn schema(args: ConfigSchemaArgs) -> Result<()> {
+    let schema = serde_json::to_string_pretty(&fob_config::schema())?;
+
+    match &args.output {
+        Some(path) => {
+            if let Some(parent) = path.parent() {
+                if !parent.as_os_str().is_empty() {
+                    std::fs::create_dir_all(parent)?;
+                }
+            }
+            std::fs::write(path, format!("{}\n", schema))?;
+            ui::success(&format!("Wrote config schema to {}", path.display()));
+        }
+        None => println!("{}", schema),
+    }
+
+    Ok(())
+}
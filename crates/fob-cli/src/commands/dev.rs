@@ -10,11 +10,13 @@
 use crate::cli::DevArgs;
 use crate::dev::{
     DevBuilder, DevConfig, DevEvent, DevServer, DevServerState, FileChange, FileWatcher,
-    SharedState,
+    SharedState, UnbundledModules,
 };
 use crate::error::Result;
 use crate::ui;
+use std::path::PathBuf;
 use std::sync::Arc;
+use std::time::Instant;
 use tokio::signal;
 
 /// Execute the dev command.
@@ -22,7 +24,8 @@ use tokio::signal;
 /// # Process Flow
 ///
 /// 1. Load and validate dev configuration
-/// 2. Create builder and perform initial build
+/// 2. Create builder and perform initial build (in unbundled mode, only
+///    dependencies are bundled)
 /// 3. Start file watcher for auto-rebuild
 /// 4. Start HTTP server with SSE
 /// 5. Main event loop:
@@ -69,36 +72,66 @@ pub async fn execute(args: DevArgs) -> Result<()> {
     } else {
         config.cwd.join(&config.base.out_dir)
     };
-    let state = Arc::new(DevServerState::new(out_dir));
+    let mut state = DevServerState::new(out_dir);
+    if config.unbundled {
+        let entries = config.base.entry.iter().map(PathBuf::from).collect();
+        let modules = UnbundledModules::new(config.cwd.clone(), entries);
+        state = state.with_unbundled(Arc::new(modules));
+    }
+    let state = Arc::new(state);
 
     // Step 3: Create builder
     let builder = DevBuilder::new(config.base.clone(), config.cwd.clone());
 
     // Step 4: Perform initial build
-    ui::info("Performing initial build...");
-    state.start_build();
-
-    match builder.initial_build().await {
-        Ok((duration_ms, cache, asset_registry)) => {
-            state.complete_build(duration_ms);
-            ui::success(&format!("Initial build completed in {}ms", duration_ms));
-
-            // Update cache and asset registry
-            state.update_cache(cache);
-            if let Some(registry) = asset_registry {
-                state.update_asset_registry(registry);
+    if let Some(modules) = &state.unbundled {
+        ui::info("Bundling dependencies...");
+        state.start_build();
+        let started = Instant::now();
+
+        match modules.prepare().await {
+            Ok(_) => {
+                let duration_ms = started.elapsed().as_millis() as u64;
+                state.complete_build(duration_ms);
+                ui::success(&format!(
+                    "Bundled {} dependencies in {}ms, serving source modules unbundled",
+                    modules.dependency_count(),
+                    duration_ms
+                ));
+            }
+            Err(e) => {
+                let error_msg = e.to_string();
+                state.fail_build(error_msg.clone());
+                ui::error(&format!("Dependency build failed: {}", error_msg));
+                return Err(e);
             }
-
-            ui::info(&format!(
-                "Cached {} files in memory",
-                state.cache.read().len()
-            ));
         }
-        Err(e) => {
-            let error_msg = e.to_string();
-            state.fail_build(error_msg.clone());
-            ui::error(&format!("Initial build failed: {}", error_msg));
-            return Err(e);
+    } else {
+        ui::info("Performing initial build...");
+        state.start_build();
+
+        match builder.initial_build().await {
+            Ok((duration_ms, cache, asset_registry)) => {
+                state.complete_build(duration_ms);
+                ui::success(&format!("Initial build completed in {}ms", duration_ms));
+
+                // Update cache and asset registry
+                state.update_cache(cache);
+                if let Some(registry) = asset_registry {
+                    state.update_asset_registry(registry);
+                }
+
+                ui::info(&format!(
+                    "Cached {} files in memory",
+                    state.cache.read().len()
+                ));
+            }
+            Err(e) => {
+                let error_msg = e.to_string();
+                state.fail_build(error_msg.clone());
+                ui::error(&format!("Initial build failed: {}", error_msg));
+                return Err(e);
+            }
         }
     }
 
@@ -134,7 +167,10 @@ pub async fn execute(args: DevArgs) -> Result<()> {
         tokio::select! {
             // File change detected
             Some(change) = change_rx.recv() => {
-                handle_file_change(change, &builder, &state).await;
+                match &state.unbundled {
+                    Some(modules) => handle_unbundled_change(change, modules, &state).await,
+                    None => handle_file_change(change, &builder, &state).await,
+                }
             }
 
             // Ctrl+C received
@@ -200,6 +236,53 @@ async fn handle_file_change(change: FileChange, builder: &DevBuilder, state: &Sh
     }
 }
 
+/// Handle a file change event in unbundled mode.
+///
+/// Modules are transformed on their next request, so only dependencies are
+/// rebuilt, and only when the change adds or removes one.
+async fn handle_unbundled_change(
+    change: FileChange,
+    modules: &UnbundledModules,
+    state: &SharedState,
+) {
+    let path = change.path();
+    ui::info(&format!("File changed: {}", path.display()));
+
+    fob_bundler::diagnostics::clear_source_cache();
+
+    state.start_build();
+    let _ = state.broadcast(&DevEvent::BuildStarted).await;
+    let started = Instant::now();
+
+    match modules.prepare().await {
+        Ok(rebuilt) => {
+            let duration_ms = started.elapsed().as_millis() as u64;
+            state.complete_build(duration_ms);
+            if rebuilt {
+                ui::success(&format!(
+                    "Dependencies rebuilt in {}ms ({} total)",
+                    duration_ms,
+                    modules.dependency_count()
+                ));
+            }
+
+            let _ = state
+                .broadcast(&DevEvent::BuildCompleted { duration_ms })
+                .await;
+        }
+        Err(e) => {
+            let error_msg = e.to_string();
+            state.fail_build(error_msg.clone());
+
+            ui::error(&format!("Update failed: {}", error_msg));
+
+            let _ = state
+                .broadcast(&DevEvent::BuildFailed { error: error_msg })
+                .await;
+        }
+    }
+}
+
 /// Open the server URL in the default browser.
 ///
 /// Uses platform-specific commands:
@@ -0,0 +1,91 @@
+//! Graph command implementation.
+//!
+//! Analyzes the modules reachable from the entry points and writes their
+//! dependency graph in a visualization format. The graph goes to stdout by
+//! default so it can be piped into Graphviz or pasted into Markdown; the
+//! HTML format is best written to a file with `--output` and opened in a
+//! browser.
+
+use crate::cli::{GraphArgs, GraphFormat};
+use crate::commands::utils;
+use crate::error::{CliError, Result};
+use crate::ui;
+use fob_bundler::{Analyzer, GraphExportOptions, ModuleId};
+use std::path::Path;
+
+/// Execute the graph command.
+///
+/// # Errors
+///
+/// Returns errors for missing entry points or `--root`/`--focus` files,
+/// failed analysis, or an output file that can't be written.
+pub async fn execute(args: GraphArgs) -> Result<()> {
+    let cwd = utils::get_cwd()?;
+
+    let entries = args
+        .entry
+        .iter()
+        .map(|entry| utils::resolve_path(entry, &cwd))
+        .collect::<Vec<_>>();
+    for entry in &entries {
+        utils::validate_entry(entry)?;
+    }
+
+    let root = module_arg(args.root.as_deref(), &cwd)?;
+    let focus = module_arg(args.focus.as_deref(), &cwd)?;
+
+    let analysis = Analyzer::new()
+        .entries(entries)
+        .cwd(cwd)
+        .analyze()
+        .await
+        .map_err(|e| CliError::Core(format!("Analysis failed: {}", e)))?;
+    for warning in &analysis.warnings {
+        ui::warning(warning);
+    }
+
+    let options = GraphExportOptions {
+        root,
+        max_depth: args.max_depth,
+        hide_externals: args.hide_externals,
+        focus,
+        exclude: args.exclude,
+    };
+    let graph = &analysis.graph;
+    let output = match args.format {
+        GraphFormat::Dot => graph.to_dot(&options),
+        GraphFormat::Mermaid => graph.to_mermaid(&options),
+        GraphFormat::Json => graph.to_json_graph(&options),
+        GraphFormat::Html => graph.to_html(&options),
+    }
+    .map_err(|e| CliError::Core(e.to_string()))?;
+
+    match &args.output {
+        Some(path) => {
+            if let Some(parent) = path.parent() {
+                if !parent.as_os_str().is_empty() {
+                    std::fs::create_dir_all(parent)?;
+                }
+            }
+            std::fs::write(path, output)?;
+            ui::success(&format!("Wrote dependency graph to {}", path.display()));
+        }
+        None => print!("{}", output),
+    }
+
+    Ok(())
+}
+
+/// Resolve a `--root`/`--focus` file to the module ID it has in the graph.
+fn module_arg(path: Option<&Path>, cwd: &Path) -> Result<Option<ModuleId>> {
+    let Some(path) = path else {
+        return Ok(None);
+    };
+    let path = utils::resolve_path(path, cwd);
+    if !path.is_file() {
+        return Err(CliError::FileNotFound(path));
+    }
+    ModuleId::new(&path)
+        .map(Some)
+        .map_err(|e| CliError::InvalidArgument(e.to_string()))
+}
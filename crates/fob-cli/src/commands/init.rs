@@ -147,9 +147,14 @@ fn validate_project_name(name: &str) -> Result<()> {
 fn select_template(args: &InitArgs) -> Result<templates::Template> {
     if let Some(ref template_name) = args.template {
         templates::Template::from_str(template_name).ok_or_else(|| {
+            let available: Vec<_> = templates::Template::ALL
+                .iter()
+                .map(|template| template.name())
+                .collect();
             CliError::InvalidArgument(format!(
-                "Invalid template '{}'. Available: library, app, component-library, meta-framework",
-                template_name
+                "Invalid template '{}'. Available: {}",
+                template_name,
+                available.join(", ")
             ))
         })
     } else if args.yes {
@@ -163,7 +168,7 @@ fn select_template(args: &InitArgs) -> Result<templates::Template> {
     }
 }
 
-/// Generate all project files from template.
+/// Write the template's files into the project directory.
 fn generate_project_files(
     project_dir: &Path,
     project_name: &str,
@@ -171,79 +176,15 @@ fn generate_project_files(
 ) -> Result<()> {
     ui::info("Generating files...");
 
-    // Create src directory
-    let src_dir = project_dir.join("src");
-    fs::create_dir(&src_dir)?;
-
-    // Generate package.json
-    let package_json = templates::package_json(project_name, template);
-    fs::write(project_dir.join("package.json"), package_json)?;
-    ui::success("  Created package.json");
-
-    // Generate tsconfig.json
-    let tsconfig = templates::tsconfig_json(template);
-    fs::write(project_dir.join("tsconfig.json"), tsconfig)?;
-    ui::success("  Created tsconfig.json");
-
-    // Generate fob.config.json
-    let joy_config = templates::joy_config_json(template);
-    fs::write(project_dir.join("fob.config.json"), joy_config)?;
-    ui::success("  Created fob.config.json");
-
-    // Generate source file
-    let source_file = templates::source_file(template);
-    let source_filename = match template {
-        templates::Template::Library => "index.ts",
-        templates::Template::App => "main.ts",
-        templates::Template::ComponentLibrary => "index.ts",
-        templates::Template::MetaFramework => "index.ts",
-    };
-    fs::write(src_dir.join(source_filename), source_file)?;
-    ui::success(&format!("  Created src/{}", source_filename));
-
-    // Generate template-specific files
-    match template {
-        templates::Template::App => {
-            // Create index.html
-            let index_html = templates::index_html(project_name);
-            fs::write(project_dir.join("index.html"), index_html)?;
-            ui::success("  Created index.html");
-
-            // Create app.css
-            let app_css = templates::app_css();
-            fs::write(src_dir.join("app.css"), app_css)?;
-            ui::success("  Created src/app.css");
-        }
-        templates::Template::Library => {
-            // No additional files for library
-        }
-        templates::Template::ComponentLibrary => {
-            // Create Button.tsx
-            let button_content = templates::button_component();
-            fs::write(src_dir.join("Button.tsx"), button_content)?;
-            ui::success("  Created src/Button.tsx");
-        }
-        templates::Template::MetaFramework => {
-            // Create router.ts and server.ts
-            let router_content = templates::router_module();
-            let server_content = templates::server_module();
-            fs::write(src_dir.join("router.ts"), router_content)?;
-            ui::success("  Created src/router.ts");
-            fs::write(src_dir.join("server.ts"), server_content)?;
-            ui::success("  Created src/server.ts");
+    for file in template.files() {
+        let path = project_dir.join(file.path);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
         }
+        fs::write(&path, templates::render(file, project_name))?;
+        ui::success(&format!("  Created {}", file.path));
     }
 
-    // Generate .gitignore
-    let gitignore = templates::gitignore();
-    fs::write(project_dir.join(".gitignore"), gitignore)?;
-    ui::success("  Created .gitignore");
-
-    // Generate README.md
-    let readme = templates::readme(project_name, template);
-    fs::write(project_dir.join("README.md"), readme)?;
-    ui::success("  Created README.md");
-
     Ok(())
 }
 
@@ -333,6 +274,18 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_generate_project_files() {
+        let temp = tempfile::TempDir::new().unwrap();
+
+        generate_project_files(temp.path(), "my-blog", templates::Template::MdxBlog).unwrap();
+
+        assert!(temp.path().join("posts/hello-world.mdx").is_file());
+        assert!(temp.path().join(".gitignore").is_file());
+        let package = fs::read_to_string(temp.path().join("package.json")).unwrap();
+        assert!(package.contains("\"name\": \"my-blog\""));
+    }
+
     #[test]
     fn test_select_template_invalid() {
         let args = InitArgs {
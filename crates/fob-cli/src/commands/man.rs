@@ -0,0 +1,77 @@
+//! Man command implementation.
+//!
+//! Renders roff man pages from the clap definitions. Without `--output` the
+//! fob(1) page is printed; with it, every subcommand also gets its own page
+//! named after the command path, e.g. `fob-config-schema.1`.
+
+use std::path::Path;
+
+use clap::CommandFactory;
+
+use crate::cli::{Cli, ManArgs};
+use crate::error::Result;
+use crate::ui;
+
+/// Print or write the man pages.
+///
+/// # Errors
+///
+/// Returns an error if a page can't be written.
+pub async fn execute(args: ManArgs) -> Result<()> {
+    let command = Cli::command();
+
+    match &args.output {
+        Some(dir) => {
+            std::fs::create_dir_all(dir)?;
+            let count = write_pages(&command, dir)?;
+            ui::success(&format!("Wrote {} man pages to {}", count, dir.display()));
+        }
+        None => render(&command, &mut std::io::stdout())?,
+    }
+
+    Ok(())
+}
+
+fn render(command: &clap::Command, out: &mut dyn std::io::Write) -> std::io::Result<()> {
+    clap_mangen::Man::new(command.clone()).render(out)
+}
+
+/// Write a page for `command` and, recursively, for each of its
+/// subcommands. Returns the number of pages written.
+fn write_pages(command: &clap::Command, dir: &Path) -> Result<usize> {
+    let mut file = std::fs::File::create(dir.join(format!("{}.1", command.get_name())))?;
+    render(command, &mut file)?;
+
+    let mut count = 1;
+    for subcommand in command.get_subcommands() {
+        let name = format!("{}-{}", command.get_name(), subcommand.get_name());
+        count += write_pages(&subcommand.clone().name(name), dir)?;
+    }
+    Ok(count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_top_level_page() {
+        let mut out = Vec::new();
+        render(&Cli::command(), &mut out).unwrap();
+        let page = String::from_utf8(out).unwrap();
+        assert!(page.contains(".TH fob"));
+        assert!(page.contains("build"));
+    }
+
+    #[test]
+    fn test_write_pages_for_subcommands() {
+        let temp = tempfile::TempDir::new().unwrap();
+
+        let count = write_pages(&Cli::command(), temp.path()).unwrap();
+
+        assert!(count > 1);
+        assert!(temp.path().join("fob.1").is_file());
+        assert!(temp.path().join("fob-build.1").is_file());
+        assert!(temp.path().join("fob-config-schema.1").is_file());
+    }
+}
@@ -0,0 +1,534 @@
+//! MDX command implementation.
+//!
+//! `fob mdx repl` is a small playground for a single MDX file: it compiles the
+//! file with the given options and recompiles on every save. In the terminal,
+//! the first result is printed in full and later results as a line diff
+//! against the previous output. With `--serve`, the source and output are
+//! shown side by side in the browser instead, with changed lines highlighted.
+//!
+//! The file and everything it includes are watched; the include set is
+//! updated after each successful compile, so newly included files (even
+//! outside the file's directory) trigger recompiles too.
+
+use crate::cli::{MdxArgs, MdxCommand, MdxJsxTarget, MdxMathOutput, MdxOutputFormat, MdxReplArgs};
+use crate::error::{CliError, Result};
+use crate::ui;
+use axum::extract::State;
+use axum::response::Html;
+use axum::routing::get;
+use axum::{Json, Router};
+use fob_mdx::{compile, IncludeOptions, JsxTarget, MathOutput, MdxCompileOptions, OutputFormat};
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use owo_colors::OwoColorize;
+use parking_lot::RwLock;
+use serde::Serialize;
+use similar::{ChangeTag, TextDiff};
+use std::collections::BTreeSet;
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::net::TcpListener;
+use tokio::signal;
+use tokio::sync::mpsc;
+
+/// Lines of unchanged context shown around each diff hunk.
+const DIFF_CONTEXT_LINES: usize = 3;
+
+/// Quiet period after a change before recompiling.
+const DEBOUNCE: Duration = Duration::from_millis(50);
+
+/// Execute an mdx subcommand.
+pub async fn execute(args: MdxArgs) -> Result<()> {
+    match args.command {
+        MdxCommand::Repl(repl_args) => repl(repl_args).await,
+    }
+}
+
+/// Outcome of compiling the watched file once.
+#[derive(Debug, Clone, Default, Serialize)]
+struct Snapshot {
+    /// Source as read from disk
+    source: String,
+    /// Compiled code, or the last successful output if compilation failed
+    output: String,
+    /// 0-based output lines that changed since the previous successful compile
+    changed_lines: Vec<usize>,
+    /// Compilation error, if the latest compile failed
+    error: Option<String>,
+    /// Short summary of compile metadata
+    summary: String,
+    /// Incremented on every recompile so the browser can skip redundant renders
+    version: u64,
+    /// Files included by the last successful compile, also watched
+    #[serde(skip)]
+    includes: Vec<PathBuf>,
+}
+
+async fn repl(args: MdxReplArgs) -> Result<()> {
+    if !args.file.is_file() {
+        return Err(CliError::FileNotFound(args.file.clone()));
+    }
+    let file = args.file.canonicalize()?;
+
+    let state = Arc::new(RwLock::new(Snapshot::default()));
+    recompile(&args, &file, &state);
+    if args.serve.is_none() {
+        print_snapshot(&state.read(), None);
+    }
+
+    if args.once {
+        return match &state.read().error {
+            Some(error) => Err(CliError::Custom(error.clone())),
+            None => Ok(()),
+        };
+    }
+
+    let (mut watcher, mut change_rx) = SourceWatcher::new()?;
+    watcher.update(&file, &state.read().includes)?;
+
+    let server = match args.serve {
+        Some(port) => Some(tokio::spawn(serve(Arc::clone(&state), port))),
+        None => None,
+    };
+
+    ui::info(&format!(
+        "Watching {} (Ctrl+C to stop)",
+        args.file.display()
+    ));
+
+    loop {
+        tokio::select! {
+            Some(path) = change_rx.recv() => {
+                if path != file && !state.read().includes.contains(&path) {
+                    continue;
+                }
+                // Editors often write a file several times per save, so
+                // wait for the burst to settle
+                while let Ok(Some(_)) = tokio::time::timeout(DEBOUNCE, change_rx.recv()).await {}
+
+                let previous = state.read().output.clone();
+                recompile(&args, &file, &state);
+                let includes = state.read().includes.clone();
+                if let Err(e) = watcher.update(&file, &includes) {
+                    ui::warning(&format!("Failed to watch included files: {}", e));
+                }
+                if args.serve.is_none() {
+                    print_snapshot(&state.read(), Some(&previous));
+                } else {
+                    report_status(&state.read());
+                }
+            }
+
+            _ = signal::ctrl_c() => {
+                break;
+            }
+        }
+    }
+
+    if let Some(server) = server {
+        server.abort();
+    }
+    Ok(())
+}
+
+/// Watches the directories holding the MDX file and its includes.
+///
+/// Directories rather than files are watched because editors often save by
+/// replacing the file, which ends a watch on the file itself.
+struct SourceWatcher {
+    watcher: RecommendedWatcher,
+    dirs: BTreeSet<PathBuf>,
+}
+
+impl SourceWatcher {
+    /// Create a watcher and the receiver for paths changed in watched
+    /// directories.
+    fn new() -> Result<(Self, mpsc::Receiver<PathBuf>)> {
+        let (tx, rx) = mpsc::channel(100);
+        let watcher = notify::recommended_watcher(move |res: notify::Result<Event>| {
+            let Ok(event) = res else {
+                return;
+            };
+            if matches!(
+                event.kind,
+                EventKind::Create(_) | EventKind::Modify(_) | EventKind::Remove(_)
+            ) {
+                for path in event.paths {
+                    let _ = tx.blocking_send(path);
+                }
+            }
+        })?;
+        Ok((
+            Self {
+                watcher,
+                dirs: BTreeSet::new(),
+            },
+            rx,
+        ))
+    }
+
+    /// Watch exactly the directories of `file` and `includes`.
+    fn update(&mut self, file: &Path, includes: &[PathBuf]) -> Result<()> {
+        let dirs: BTreeSet<PathBuf> = std::iter::once(file)
+            .chain(includes.iter().map(PathBuf::as_path))
+            .filter_map(Path::parent)
+            .map(Path::to_path_buf)
+            .collect();
+        for dir in self.dirs.difference(&dirs) {
+            // The directory may be gone already
+            let _ = self.watcher.unwatch(dir);
+        }
+        for dir in dirs.difference(&self.dirs) {
+            self.watcher.watch(dir, RecursiveMode::NonRecursive)?;
+        }
+        self.dirs = dirs;
+        Ok(())
+    }
+}
+
+/// Map the command-line flags onto compile options.
+fn compile_options(args: &MdxReplArgs, file: &Path) -> MdxCompileOptions {
+    MdxCompileOptions::builder()
+        .filepath(file.display().to_string())
+        .gfm(!args.no_gfm)
+        .footnotes(!args.no_footnotes)
+        .math(!args.no_math)
+        .math_output(match args.math_output {
+            MdxMathOutput::Tex => MathOutput::Tex,
+            MdxMathOutput::MathMl => MathOutput::MathMl,
+        })
+        .use_default_plugins(!args.no_default_plugins)
+        .jsx_runtime(args.jsx_runtime.clone())
+        .jsx_target(match args.jsx_target {
+            MdxJsxTarget::React => JsxTarget::React,
+            MdxJsxTarget::Preact => JsxTarget::Preact,
+            MdxJsxTarget::Vue => JsxTarget::Vue,
+            MdxJsxTarget::Solid => JsxTarget::Solid,
+            MdxJsxTarget::Hono => JsxTarget::Hono,
+        })
+        .output_format(match args.output_format {
+            MdxOutputFormat::Program => OutputFormat::Program,
+            MdxOutputFormat::FunctionBody => OutputFormat::FunctionBody,
+        })
+        .maybe_provider_import_source(args.provider_import_source.clone())
+        .components(args.component.iter().cloned().collect())
+        .maybe_include((!args.no_includes).then(|| IncludeOptions::builder().build()))
+        .build()
+}
+
+/// Read and compile the file, updating the shared snapshot.
+///
+/// On failure the previous output is kept so the view doesn't go blank
+/// while the file is being edited.
+fn recompile(args: &MdxReplArgs, file: &Path, state: &RwLock<Snapshot>) {
+    let source = match std::fs::read_to_string(file) {
+        Ok(source) => source,
+        Err(e) => {
+            state.write().error = Some(format!("Failed to read {}: {}", file.display(), e));
+            return;
+        }
+    };
+
+    let result = compile(&source, compile_options(args, file));
+
+    let mut snapshot = state.write();
+    snapshot.version += 1;
+    snapshot.source = source;
+    match result {
+        Ok(result) => {
+            snapshot.changed_lines = changed_lines(&snapshot.output, &result.code);
+            snapshot.output = result.code;
+            snapshot.error = None;
+            snapshot.includes = result.includes;
+            snapshot.summary = format!(
+                "{} words, {} min read, {} images, {} imports",
+                result.word_count,
+                result.reading_time_minutes,
+                result.images.len(),
+                result.imports.len()
+            );
+        }
+        Err(error) => {
+            snapshot.changed_lines.clear();
+            snapshot.error = Some(error.to_string());
+        }
+    }
+}
+
+/// Lines of `new` that are inserted or changed relative to `old`.
+fn changed_lines(old: &str, new: &str) -> Vec<usize> {
+    if old.is_empty() {
+        return Vec::new();
+    }
+
+    TextDiff::from_lines(old, new)
+        .iter_all_changes()
+        .filter(|change| change.tag() == ChangeTag::Insert)
+        .filter_map(|change| change.new_index())
+        .collect()
+}
+
+/// Render a unified diff of two outputs, colored if the terminal supports it.
+fn render_diff(old: &str, new: &str, color: bool) -> String {
+    let diff = TextDiff::from_lines(old, new);
+    let mut rendered = String::new();
+
+    for (index, group) in diff.grouped_ops(DIFF_CONTEXT_LINES).iter().enumerate() {
+        if index > 0 {
+            rendered.push_str(&paint("...", color, |s| s.dimmed().to_string()));
+            rendered.push('\n');
+        }
+        for op in group {
+            for change in diff.iter_changes(op) {
+                let line = change.to_string_lossy();
+                let line = line.trim_end_matches('\n');
+                let text = match change.tag() {
+                    ChangeTag::Delete => {
+                        paint(&format!("-{}", line), color, |s| s.red().to_string())
+                    }
+                    ChangeTag::Insert => {
+                        paint(&format!("+{}", line), color, |s| s.green().to_string())
+                    }
+                    ChangeTag::Equal => format!(" {}", line),
+                };
+                rendered.push_str(&text);
+                rendered.push('\n');
+            }
+        }
+    }
+
+    rendered
+}
+
+fn paint(text: &str, color: bool, style: impl Fn(&str) -> String) -> String {
+    if color {
+        style(text)
+    } else {
+        text.to_string()
+    }
+}
+
+/// Print the snapshot to the terminal, as a diff against `previous` if given.
+fn print_snapshot(snapshot: &Snapshot, previous: Option<&str>) {
+    if let Some(error) = &snapshot.error {
+        ui::error(error);
+        return;
+    }
+
+    match previous {
+        None => println!("{}", snapshot.output),
+        Some(previous) if previous == snapshot.output => {
+            ui::info("Recompiled, output unchanged");
+            return;
+        }
+        Some(previous) => print!(
+            "{}",
+            render_diff(previous, &snapshot.output, ui::should_use_color())
+        ),
+    }
+
+    report_status(snapshot);
+}
+
+fn report_status(snapshot: &Snapshot) {
+    match &snapshot.error {
+        Some(error) => ui::error(error),
+        None => ui::success(&format!("Compiled ({})", snapshot.summary)),
+    }
+}
+
+/// Serve the side-by-side view until the task is aborted.
+async fn serve(state: Arc<RwLock<Snapshot>>, port: u16) -> Result<()> {
+    let app = Router::new()
+        .route("/", get(index))
+        .route("/api/state", get(api_state))
+        .with_state(state);
+
+    let addr: SocketAddr = ([127, 0, 0, 1], port).into();
+    let listener = TcpListener::bind(addr)
+        .await
+        .map_err(|e| CliError::Server(format!("Failed to bind to {}: {}", addr, e)))?;
+
+    ui::success(&format!("MDX playground at http://{}", addr));
+
+    axum::serve(listener, app)
+        .await
+        .map_err(|e| CliError::Server(e.to_string()))
+}
+
+async fn index() -> Html<&'static str> {
+    Html(PLAYGROUND_HTML)
+}
+
+async fn api_state(State(state): State<Arc<RwLock<Snapshot>>>) -> Json<Snapshot> {
+    Json(state.read().clone())
+}
+
+/// Single-page side-by-side view; polls `/api/state` for updates.
+const PLAYGROUND_HTML: &str = r#"<!DOCTYPE html>
+<html>
+<head>
+<meta charset="utf-8">
+<title>fob mdx repl</title>
+<style>
+  body { margin: 0; font: 13px/1.5 ui-monospace, monospace; display: flex; flex-direction: column; height: 100vh; }
+  header { padding: 6px 12px; background: #222; color: #ddd; }
+  header.error { background: #8b1d1d; white-space: pre-wrap; }
+  main { flex: 1; display: flex; min-height: 0; }
+  pre { flex: 1; margin: 0; padding: 8px 0; overflow: auto; border-right: 1px solid #ddd; }
+  pre div { padding: 0 12px; white-space: pre; }
+  pre div.changed { background: #e6ffec; }
+</style>
+</head>
+<body>
+<header id="status">Compiling...</header>
+<main><pre id="source"></pre><pre id="output"></pre></main>
+<script>
+  let version = -1;
+  function render(el, text, changed) {
+    el.replaceChildren(...text.split("\n").map((line, i) => {
+      const div = document.createElement("div");
+      div.textContent = line || " ";
+      if (changed.has(i)) div.className = "changed";
+      return div;
+    }));
+  }
+  async function poll() {
+    try {
+      const state = await (await fetch("/api/state")).json();
+      if (state.version !== version) {
+        version = state.version;
+        const status = document.getElementById("status");
+        status.className = state.error ? "error" : "";
+        status.textContent = state.error || state.summary;
+        render(document.getElementById("source"), state.source, new Set());
+        render(document.getElementById("output"), state.output, new Set(state.changed_lines));
+      }
+    } catch (_) {}
+    setTimeout(poll, 500);
+  }
+  poll();
+</script>
+</body>
+</html>
+"#;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::{Cli, Command};
+    use clap::Parser;
+
+    fn repl_args(extra: &[&str]) -> MdxReplArgs {
+        let mut argv = vec!["fob", "mdx", "repl", "post.mdx"];
+        argv.extend_from_slice(extra);
+        match Cli::try_parse_from(argv).unwrap().command {
+            Some(Command::Mdx(MdxArgs {
+                command: MdxCommand::Repl(args),
+            })) => args,
+            _ => panic!("Expected mdx repl command"),
+        }
+    }
+
+    #[test]
+    fn test_compile_options_from_flags() {
+        let args = repl_args(&[
+            "--no-gfm",
+            "--no-math",
+            "--output-format",
+            "function-body",
+            "--provider-import-source",
+            "@mdx-js/react",
+            "--jsx-target",
+            "vue",
+            "--component",
+            "h1=Heading",
+            "--math-output",
+            "mathml",
+        ]);
+        let options = compile_options(&args, Path::new("post.mdx"));
+
+        assert!(!options.gfm);
+        assert!(!options.math);
+        assert!(options.footnotes);
+        assert!(options.use_default_plugins);
+        assert_eq!(options.output_format, OutputFormat::FunctionBody);
+        assert_eq!(options.jsx_target, JsxTarget::Vue);
+        assert_eq!(options.math_output, MathOutput::MathMl);
+        assert_eq!(options.components["h1"], "Heading");
+        assert_eq!(
+            options.provider_import_source.as_deref(),
+            Some("@mdx-js/react")
+        );
+        assert_eq!(options.filepath.as_deref(), Some("post.mdx"));
+        assert!(options.include.is_some());
+
+        let args = repl_args(&["--no-includes"]);
+        assert!(compile_options(&args, Path::new("post.mdx"))
+            .include
+            .is_none());
+    }
+
+    #[test]
+    fn test_changed_lines() {
+        assert!(changed_lines("", "a\nb\n").is_empty());
+        assert_eq!(changed_lines("a\nb\nc\n", "a\nB\nc\nd\n"), vec![1, 3]);
+        assert!(changed_lines("a\n", "a\n").is_empty());
+    }
+
+    #[test]
+    fn test_render_diff_plain() {
+        let diff = render_diff("a\nb\nc\n", "a\nB\nc\n", false);
+        assert_eq!(diff, " a\n-b\n+B\n c\n");
+    }
+
+    #[tokio::test]
+    async fn test_watcher_follows_includes() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let root = temp.path().canonicalize().unwrap();
+        let file = root.join("posts/post.mdx");
+        let include = root.join("shared/snippet.md");
+        std::fs::create_dir_all(file.parent().unwrap()).unwrap();
+        std::fs::create_dir_all(include.parent().unwrap()).unwrap();
+
+        let (mut watcher, mut change_rx) = SourceWatcher::new().unwrap();
+        watcher
+            .update(&file, std::slice::from_ref(&include))
+            .unwrap();
+        assert_eq!(
+            watcher.dirs,
+            BTreeSet::from([root.join("posts"), root.join("shared")])
+        );
+
+        std::fs::write(&include, "Shared").unwrap();
+        let changed = tokio::time::timeout(Duration::from_secs(5), change_rx.recv())
+            .await
+            .unwrap();
+        assert_eq!(changed, Some(include));
+
+        watcher.update(&file, &[]).unwrap();
+        assert_eq!(watcher.dirs, BTreeSet::from([root.join("posts")]));
+    }
+
+    #[test]
+    fn test_recompile_keeps_output_on_error() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let file = temp.path().join("post.mdx");
+        let args = repl_args(&[]);
+        let state = RwLock::new(Snapshot::default());
+
+        std::fs::write(&file, "# Hello").unwrap();
+        recompile(&args, &file, &state);
+        let output = state.read().output.clone();
+        assert!(output.contains("Hello"));
+        assert!(state.read().error.is_none());
+
+        std::fs::write(&file, "# Hello {").unwrap();
+        recompile(&args, &file, &state);
+        let snapshot = state.read();
+        assert!(snapshot.error.is_some());
+        assert_eq!(snapshot.output, output);
+        assert_eq!(snapshot.version, 2);
+    }
+}
@@ -3,22 +3,50 @@
 //! This module contains the implementation of all CLI commands:
 //!
 //! - [`build`] - Bundle JavaScript/TypeScript files
+//! - [`capabilities`] - Build feature and limit report (`--capabilities`)
 //! - [`dev`] - Development server with hot reload
 //! - [`init`] - Project scaffolding
 //! - [`check`] - Configuration validation
+//! - [`new_plugin`] - Plugin crate scaffolding
+//! - [`mdx`] - MDX playground
+//! - [`graph`] - Dependency graph export
+//! - [`analyze`] - Static analysis report for CI
+//! - [`compare`] - Build summary comparison
+//! - [`config`] - Config JSON Schema
+//! - [`completions`] - Shell completion scripts
+//! - [`man`] - Man pages
 //!
 //! Each command is implemented in its own module and provides an `execute`
 //! function that takes the parsed command arguments and returns a Result.
 
+pub mod analyze;
 pub mod build;
+pub mod capabilities;
 pub mod check;
+pub mod compare;
+pub mod completions;
+pub mod config;
 pub mod dev;
+pub mod graph;
 pub mod init;
+pub mod man;
+pub mod mdx;
+pub mod new_plugin;
+mod plugin_templates;
 mod templates;
 pub mod utils;
 
 // Re-export execute functions for convenience
+pub use analyze::execute as analyze_execute;
 pub use build::execute as build_execute;
+pub use capabilities::execute as capabilities_execute;
 pub use check::execute as check_execute;
+pub use compare::execute as compare_execute;
+pub use completions::execute as completions_execute;
+pub use config::execute as config_execute;
 pub use dev::execute as dev_execute;
+pub use graph::execute as graph_execute;
 pub use init::execute as init_execute;
+pub use man::execute as man_execute;
+pub use mdx::execute as mdx_execute;
+pub use new_plugin::execute as new_plugin_execute;
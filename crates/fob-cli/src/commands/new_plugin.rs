@@ -0,0 +1,160 @@
+//! New-plugin command implementation.
+//!
+//! Scaffolds a standalone Rust crate containing a fob bundler plugin.
+
+use crate::cli::NewPluginArgs;
+use crate::commands::plugin_templates::{self, PluginNames};
+use crate::error::{CliError, Result};
+use crate::ui;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Version of fob-bundler the generated crate depends on.
+const FOB_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// Execute the new-plugin command.
+///
+/// # Process
+///
+/// 1. Validate the plugin name and derive crate/struct names
+/// 2. Create the crate directory
+/// 3. Generate Cargo.toml, src/lib.rs, tests/plugin.rs and README.md
+/// 4. Show next steps
+///
+/// # Errors
+///
+/// Returns errors for:
+/// - Invalid plugin names
+/// - Target directory already exists (without `--force`)
+/// - File write failures
+pub async fn execute(args: NewPluginArgs) -> Result<()> {
+    validate_plugin_name(&args.name)?;
+    let names = PluginNames::new(&args.name);
+
+    let crate_dir = args
+        .dir
+        .clone()
+        .unwrap_or_else(|| PathBuf::from(&names.crate_name));
+
+    if crate_dir.exists() && !args.force {
+        return Err(CliError::InvalidArgument(format!(
+            "Directory '{}' already exists (use --force to overwrite)",
+            crate_dir.display()
+        )));
+    }
+
+    ui::info(&format!("Creating plugin crate: {}", names.crate_name));
+    generate_plugin_files(&crate_dir, &names)?;
+
+    print_next_steps(&crate_dir);
+
+    ui::success("Plugin crate created successfully!");
+    Ok(())
+}
+
+/// Validate that the plugin name produces a valid crate and type name.
+fn validate_plugin_name(name: &str) -> Result<()> {
+    let short = name.strip_prefix("fob-plugin-").unwrap_or(name);
+
+    if short.is_empty() {
+        return Err(CliError::InvalidArgument(
+            "Plugin name cannot be empty".to_string(),
+        ));
+    }
+
+    if !short
+        .chars()
+        .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+    {
+        return Err(CliError::InvalidArgument(
+            "Plugin name can only contain lowercase letters, numbers, and hyphens".to_string(),
+        ));
+    }
+
+    // Note: empty check above guarantees name is non-empty
+    if !short.as_bytes()[0].is_ascii_lowercase() {
+        return Err(CliError::InvalidArgument(
+            "Plugin name must start with a lowercase letter".to_string(),
+        ));
+    }
+
+    if short.ends_with('-') || short.contains("--") {
+        return Err(CliError::InvalidArgument(
+            "Plugin name cannot end with a hyphen or contain consecutive hyphens".to_string(),
+        ));
+    }
+
+    Ok(())
+}
+
+/// Generate all plugin crate files.
+fn generate_plugin_files(crate_dir: &Path, names: &PluginNames) -> Result<()> {
+    fs::create_dir_all(crate_dir.join("src"))?;
+    fs::create_dir_all(crate_dir.join("tests"))?;
+
+    for file in plugin_templates::FILES {
+        let content = plugin_templates::render(file.contents, names, FOB_VERSION);
+        fs::write(crate_dir.join(file.path), content)?;
+        ui::success(&format!("  Created {}", file.path));
+    }
+
+    Ok(())
+}
+
+/// Print next steps for the user.
+fn print_next_steps(crate_dir: &Path) {
+    eprintln!();
+    ui::info("Next steps:");
+    eprintln!();
+    eprintln!("  cd {}", crate_dir.display());
+    eprintln!("  cargo test");
+    eprintln!();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_plugin_name_valid() {
+        assert!(validate_plugin_name("yaml").is_ok());
+        assert!(validate_plugin_name("yaml-loader").is_ok());
+        assert!(validate_plugin_name("fob-plugin-yaml").is_ok());
+        assert!(validate_plugin_name("svg2").is_ok());
+    }
+
+    #[test]
+    fn test_validate_plugin_name_invalid() {
+        assert!(validate_plugin_name("").is_err());
+        assert!(validate_plugin_name("fob-plugin-").is_err());
+        assert!(validate_plugin_name("Yaml").is_err());
+        assert!(validate_plugin_name("2yaml").is_err());
+        assert!(validate_plugin_name("yaml_loader").is_err());
+        assert!(validate_plugin_name("yaml-").is_err());
+        assert!(validate_plugin_name("yaml--loader").is_err());
+    }
+
+    #[test]
+    fn test_generate_plugin_files() {
+        let temp = tempfile::tempdir().unwrap();
+        let crate_dir = temp.path().join("fob-plugin-yaml");
+        let names = PluginNames::new("yaml");
+
+        generate_plugin_files(&crate_dir, &names).unwrap();
+
+        let cargo_toml = fs::read_to_string(crate_dir.join("Cargo.toml")).unwrap();
+        assert!(cargo_toml.contains("name = \"fob-plugin-yaml\""));
+        assert!(cargo_toml.contains(&format!("fob-bundler = \"{}\"", FOB_VERSION)));
+
+        let lib_rs = fs::read_to_string(crate_dir.join("src/lib.rs")).unwrap();
+        assert!(lib_rs.contains("pub struct YamlPlugin"));
+        assert!(lib_rs.contains("pub struct YamlOptions"));
+        assert!(lib_rs.contains("impl Plugin for YamlPlugin"));
+
+        let tests = fs::read_to_string(crate_dir.join("tests/plugin.rs")).unwrap();
+        assert!(tests.contains("use fob_plugin_yaml::{YamlOptions, YamlPlugin};"));
+
+        assert!(crate_dir.join("README.md").exists());
+        assert!(crate_dir.join(".gitignore").exists());
+    }
+}
@@ -0,0 +1,121 @@
+//! Plugin crate template for the new-plugin command.
+//!
+//! Like the project templates, the files live under `assets/plugin/` and are
+//! embedded into the binary at compile time. Rust sources are full of braces,
+//! so they use `__PLACEHOLDER__` markers that are substituted by [`render`].
+
+use crate::commands::templates::TemplateFile;
+
+/// Names derived from the plugin name that appear in the generated files.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PluginNames {
+    /// Short plugin name, e.g. `banner`
+    pub short: String,
+    /// Cargo package name, e.g. `fob-plugin-banner`
+    pub crate_name: String,
+    /// Rust library name, e.g. `fob_plugin_banner`
+    pub lib_name: String,
+    /// Plugin struct name, e.g. `BannerPlugin`
+    pub struct_name: String,
+    /// Options struct name, e.g. `BannerOptions`
+    pub options_name: String,
+}
+
+impl PluginNames {
+    /// Derive all names from a short plugin name.
+    ///
+    /// A leading `fob-plugin-` prefix is stripped so both `banner` and
+    /// `fob-plugin-banner` produce the same crate.
+    pub fn new(name: &str) -> Self {
+        let short = name.strip_prefix("fob-plugin-").unwrap_or(name).to_string();
+        let pascal: String = short
+            .split(['-', '_'])
+            .filter(|part| !part.is_empty())
+            .map(|part| {
+                let mut chars = part.chars();
+                match chars.next() {
+                    Some(first) => first.to_ascii_uppercase().to_string() + chars.as_str(),
+                    None => String::new(),
+                }
+            })
+            .collect();
+
+        Self {
+            crate_name: format!("fob-plugin-{}", short),
+            lib_name: format!("fob_plugin_{}", short.replace('-', "_")),
+            struct_name: format!("{}Plugin", pascal),
+            options_name: format!("{}Options", pascal),
+            short,
+        }
+    }
+}
+
+/// Substitute plugin names into a template.
+pub fn render(template: &str, names: &PluginNames, fob_version: &str) -> String {
+    template
+        .replace("__SHORT_NAME__", &names.short)
+        .replace("__CRATE_NAME__", &names.crate_name)
+        .replace("__LIB_NAME__", &names.lib_name)
+        .replace("__STRUCT_NAME__", &names.struct_name)
+        .replace("__OPTIONS_NAME__", &names.options_name)
+        .replace("__FOB_VERSION__", fob_version)
+}
+
+/// Embed a file from `assets/plugin/`.
+macro_rules! plugin_file {
+    ($path:literal, $asset:literal) => {
+        TemplateFile {
+            path: $path,
+            contents: include_str!(concat!("../../assets/plugin/", $asset)),
+        }
+    };
+}
+
+/// Files of the plugin crate: options struct, plugin struct and hooks in
+/// `src/lib.rs`, and tests exercising the hooks against virtual files.
+///
+/// Cargo.toml is stored as `Cargo.toml.template` so cargo doesn't treat
+/// the asset directory as a package of its own.
+pub const FILES: &[TemplateFile] = &[
+    plugin_file!("Cargo.toml", "Cargo.toml.template"),
+    plugin_file!("src/lib.rs", "src/lib.rs"),
+    plugin_file!("tests/plugin.rs", "tests/plugin.rs"),
+    plugin_file!("README.md", "README.md"),
+    plugin_file!(".gitignore", "gitignore"),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plugin_names_from_short_name() {
+        let names = PluginNames::new("yaml-loader");
+        assert_eq!(names.short, "yaml-loader");
+        assert_eq!(names.crate_name, "fob-plugin-yaml-loader");
+        assert_eq!(names.lib_name, "fob_plugin_yaml_loader");
+        assert_eq!(names.struct_name, "YamlLoaderPlugin");
+        assert_eq!(names.options_name, "YamlLoaderOptions");
+    }
+
+    #[test]
+    fn test_plugin_names_strips_prefix() {
+        assert_eq!(
+            PluginNames::new("fob-plugin-banner"),
+            PluginNames::new("banner")
+        );
+    }
+
+    #[test]
+    fn test_render_replaces_all_placeholders() {
+        let names = PluginNames::new("banner");
+        for file in FILES {
+            let rendered = render(file.contents, &names, "0.5.0");
+            assert!(
+                !rendered.contains("__"),
+                "unrendered placeholder in:\n{}",
+                rendered
+            );
+        }
+    }
+}
@@ -96,6 +96,8 @@ impl FobConfig {
             splitting: args.splitting,
             no_treeshake: args.no_treeshake,
             clean: args.clean,
+            validate_output: args.validate_output,
+            plugins: Vec::new(),
             cwd: args.cwd.clone(),
         }
     }
@@ -121,6 +123,8 @@ impl FobConfig {
             splitting: false,
             no_treeshake: false,
             clean: false,
+            validate_output: false,
+            plugins: Vec::new(),
             cwd: None,
         }
     }
@@ -6,6 +6,7 @@
 mod conversions;
 mod defaults;
 mod loading;
+pub(crate) mod plugins;
 mod tests;
 mod types;
 mod validation;
@@ -84,6 +85,14 @@ pub struct FobConfig {
     #[serde(default)]
     pub clean: bool,
 
+    /// Re-parse emitted chunks and fail on invalid output or syntax above `target`
+    #[serde(default)]
+    pub validate_output: bool,
+
+    /// Built-in plugins to enable, e.g. `{ "name": "fob-mdx", "options": { ... } }`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub plugins: Vec<fob_config::PluginOptions>,
+
     /// Working directory
     #[serde(skip_serializing_if = "Option::is_none")]
     pub cwd: Option<PathBuf>,
@@ -117,6 +126,8 @@ impl FobConfig {
             splitting: true,
             no_treeshake: false,
             clean: true,
+            validate_output: false,
+            plugins: Vec::new(),
             cwd: None,
         })
         .expect("Example config serialization should never fail")
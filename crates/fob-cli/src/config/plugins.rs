@@ -0,0 +1,278 @@
+//! Built-in plugins declared in the config file.
+//!
+//! Each `plugins` entry names a built-in plugin and carries its options:
+//!
+//! ```json
+//! {
+//!   "plugins": [
+//!     { "name": "fob-mdx", "options": { "jsxTarget": "preact", "math": false } },
+//!     { "name": "fob-css", "options": { "minify": true } }
+//!   ]
+//! }
+//! ```
+//!
+//! Enabled entries are instantiated in `order` and handed to the bundler.
+
+use crate::error::{ConfigError, Result};
+use fob_bundler::builtins::{CssPlugin, CssPluginOptions};
+use fob_bundler::{Runtime, SharedPluginable};
+use fob_config::{PluginOptions, BUILTIN_PLUGINS};
+use fob_mdx::{FobMdxPlugin, JsxTarget, MathOutput};
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// A built-in plugin the CLI can instantiate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Builtin {
+    Mdx,
+    Css,
+}
+
+impl Builtin {
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "fob-mdx" => Some(Self::Mdx),
+            "fob-css" => Some(Self::Css),
+            _ => None,
+        }
+    }
+}
+
+/// Options accepted by the `fob-mdx` plugin.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase", deny_unknown_fields)]
+struct MdxOptions {
+    gfm: Option<bool>,
+    footnotes: Option<bool>,
+    math: Option<bool>,
+    math_output: Option<String>,
+    jsx_runtime: Option<String>,
+    jsx_target: Option<String>,
+    use_default_plugins: Option<bool>,
+    provider_import_source: Option<String>,
+    components: HashMap<String, String>,
+    layouts_dir: Option<PathBuf>,
+}
+
+/// Options accepted by the `fob-css` plugin.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase", deny_unknown_fields)]
+struct CssOptions {
+    minify: Option<bool>,
+    targets: Option<Vec<String>>,
+    source_map: Option<bool>,
+    include: Option<Vec<String>>,
+    exclude: Option<Vec<String>>,
+}
+
+/// Check that every plugin entry names a built-in plugin with valid options.
+///
+/// # Errors
+///
+/// Returns an error for entries with a `path`, unknown plugin names, and
+/// options the plugin doesn't accept.
+pub(crate) fn validate(plugins: &[PluginOptions]) -> Result<()> {
+    for plugin in plugins {
+        match builtin(plugin)? {
+            Builtin::Mdx => {
+                mdx_options(plugin)?;
+            }
+            Builtin::Css => {
+                options::<CssOptions>(plugin)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Instantiate the enabled plugins, lowest `order` first.
+///
+/// # Errors
+///
+/// Returns the same errors as [`validate`].
+pub(crate) fn instantiate(
+    plugins: &[PluginOptions],
+    runtime: Arc<dyn Runtime>,
+    cwd: &Path,
+) -> Result<Vec<SharedPluginable>> {
+    let mut enabled: Vec<_> = plugins.iter().filter(|plugin| plugin.enabled).collect();
+    enabled.sort_by_key(|plugin| plugin.order);
+
+    enabled
+        .into_iter()
+        .map(|plugin| -> Result<SharedPluginable> {
+            match builtin(plugin)? {
+                Builtin::Mdx => Ok(Arc::new(mdx_plugin(plugin, Arc::clone(&runtime), cwd)?)),
+                Builtin::Css => Ok(Arc::new(css_plugin(plugin, Arc::clone(&runtime))?)),
+            }
+        })
+        .collect()
+}
+
+fn builtin(plugin: &PluginOptions) -> Result<Builtin> {
+    let Some(name) = plugin.builtin() else {
+        return Err(ConfigError::InvalidValue {
+            field: "plugins".to_string(),
+            value: plugin.path.display().to_string(),
+            hint: format!(
+                "Only built-in plugins can be loaded by the CLI; use `name` with one of {}",
+                BUILTIN_PLUGINS.join(", ")
+            ),
+        }
+        .into());
+    };
+
+    Builtin::from_name(name).ok_or_else(|| {
+        ConfigError::InvalidValue {
+            field: "plugins".to_string(),
+            value: name.to_string(),
+            hint: format!("Built-in plugins are {}", BUILTIN_PLUGINS.join(", ")),
+        }
+        .into()
+    })
+}
+
+fn options<T: DeserializeOwned + Default>(plugin: &PluginOptions) -> Result<T> {
+    if plugin.config.is_null() {
+        return Ok(T::default());
+    }
+    serde_json::from_value(plugin.config.clone()).map_err(|err| {
+        ConfigError::InvalidValue {
+            field: "plugins".to_string(),
+            value: plugin.name.clone().unwrap_or_default(),
+            hint: format!("Invalid options: {}", err),
+        }
+        .into()
+    })
+}
+
+fn mdx_options(
+    plugin: &PluginOptions,
+) -> Result<(MdxOptions, Option<JsxTarget>, Option<MathOutput>)> {
+    let options: MdxOptions = options(plugin)?;
+    let invalid = |hint: String| ConfigError::InvalidValue {
+        field: "plugins".to_string(),
+        value: "fob-mdx".to_string(),
+        hint,
+    };
+    let jsx_target = options
+        .jsx_target
+        .as_deref()
+        .map(str::parse)
+        .transpose()
+        .map_err(invalid)?;
+    let math_output = options
+        .math_output
+        .as_deref()
+        .map(str::parse)
+        .transpose()
+        .map_err(invalid)?;
+    Ok((options, jsx_target, math_output))
+}
+
+fn mdx_plugin(
+    plugin: &PluginOptions,
+    runtime: Arc<dyn Runtime>,
+    cwd: &Path,
+) -> Result<FobMdxPlugin> {
+    let (options, jsx_target, math_output) = mdx_options(plugin)?;
+
+    let mut mdx = FobMdxPlugin::new(runtime).with_project_root(cwd);
+    if let Some(gfm) = options.gfm {
+        mdx.gfm = gfm;
+    }
+    if let Some(footnotes) = options.footnotes {
+        mdx.footnotes = footnotes;
+    }
+    if let Some(math) = options.math {
+        mdx.math = math;
+    }
+    if let Some(math_output) = math_output {
+        mdx.math_output = math_output;
+    }
+    if let Some(jsx_target) = jsx_target {
+        mdx.jsx_target = jsx_target;
+    }
+    if let Some(jsx_runtime) = options.jsx_runtime {
+        mdx.jsx_runtime = jsx_runtime;
+    }
+    if let Some(use_default_plugins) = options.use_default_plugins {
+        mdx.use_default_plugins = use_default_plugins;
+    }
+    mdx.provider_import_source = options.provider_import_source;
+    mdx.components = options.components;
+    mdx.layouts_dir = options.layouts_dir;
+    Ok(mdx)
+}
+
+fn css_plugin(plugin: &PluginOptions, runtime: Arc<dyn Runtime>) -> Result<CssPlugin> {
+    let options: CssOptions = options(plugin)?;
+
+    let defaults = CssPluginOptions::default();
+    let css = CssPluginOptions {
+        minify: options.minify.unwrap_or(defaults.minify),
+        targets: options.targets.or(defaults.targets),
+        source_map: options.source_map.unwrap_or(defaults.source_map),
+        include: options.include.unwrap_or(defaults.include),
+        exclude: options.exclude.unwrap_or(defaults.exclude),
+    };
+    Ok(CssPlugin::with_options(runtime, css))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn plugin(value: serde_json::Value) -> PluginOptions {
+        serde_json::from_value(value).unwrap()
+    }
+
+    #[test]
+    fn accepts_builtin_plugins_with_options() {
+        let plugins = [
+            plugin(
+                json!({ "name": "fob-mdx", "options": { "jsxTarget": "preact", "gfm": false } }),
+            ),
+            plugin(json!({ "name": "fob-css", "options": { "minify": true } })),
+        ];
+        assert!(validate(&plugins).is_ok());
+    }
+
+    #[test]
+    fn every_builtin_plugin_can_be_instantiated() {
+        for name in BUILTIN_PLUGINS {
+            assert!(Builtin::from_name(name).is_some(), "{name}");
+        }
+    }
+
+    #[test]
+    fn rejects_unknown_plugin() {
+        let err = validate(&[plugin(json!({ "name": "fob-vue" }))]).unwrap_err();
+        assert!(err.to_string().contains("fob-vue"));
+    }
+
+    #[test]
+    fn rejects_path_plugins() {
+        let err = validate(&[plugin(json!({ "path": "plugins/custom.wasm" }))]).unwrap_err();
+        assert!(err.to_string().contains("plugins/custom.wasm"));
+    }
+
+    #[test]
+    fn rejects_unknown_options() {
+        let err = validate(&[plugin(
+            json!({ "name": "fob-css", "options": { "minfy": true } }),
+        )])
+        .unwrap_err();
+        assert!(err.to_string().contains("minfy"));
+
+        let err = validate(&[plugin(
+            json!({ "name": "fob-mdx", "options": { "jsxTarget": "angular" } }),
+        )])
+        .unwrap_err();
+        assert!(err.to_string().contains("angular"));
+    }
+}
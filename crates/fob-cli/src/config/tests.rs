@@ -22,6 +22,8 @@ mod tests {
             splitting: false,
             no_treeshake: false,
             clean: true,
+            validate_output: false,
+            plugins: Vec::new(),
             cwd: Some(PathBuf::from(".")),
         };
         let json = serde_json::to_string(&config).unwrap();
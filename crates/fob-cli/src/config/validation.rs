@@ -81,6 +81,8 @@ impl FobConfig {
             validate_global_name(name)?;
         }
 
+        crate::config::plugins::validate(&self.plugins)?;
+
         Ok(())
     }
 }
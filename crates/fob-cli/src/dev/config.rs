@@ -26,6 +26,9 @@ pub struct DevConfig {
     /// Open browser automatically on start
     pub open: bool,
 
+    /// Serve source modules unbundled instead of bundling on change
+    pub unbundled: bool,
+
     /// Working directory for the dev server
     pub cwd: PathBuf,
 
@@ -87,6 +90,14 @@ impl DevConfig {
             splitting: false,
             no_treeshake: false,
             clean: false,
+            validate_output: false,
+            plugins: Vec::new(),
+            watch: false,
+            on_success: None,
+            ci: false,
+            summary: None,
+            max_chunk_size: None,
+            fail_on_warnings: false,
             cwd: args.cwd.clone(),
             bundle: true,
         };
@@ -126,6 +137,7 @@ impl DevConfig {
             addr,
             https: args.https,
             open: args.open,
+            unbundled: args.unbundled,
             cwd,
             watch_ignore,
             debounce_ms: 100, // 100ms debounce
@@ -278,6 +290,7 @@ mod tests {
             addr: "127.0.0.1:3000".parse().unwrap(),
             https: false,
             open: false,
+            unbundled: false,
             cwd: PathBuf::from("."),
             watch_ignore: vec![],
             debounce_ms: 100,
@@ -296,6 +309,7 @@ mod tests {
             addr: "127.0.0.1:3000".parse().unwrap(),
             https: true,
             open: false,
+            unbundled: false,
             cwd: PathBuf::from("."),
             watch_ignore: vec![],
             debounce_ms: 100,
@@ -321,6 +335,8 @@ mod tests {
             splitting: false,
             no_treeshake: false,
             clean: false,
+            validate_output: false,
+            plugins: Vec::new(),
             cwd: None,
         }
     }
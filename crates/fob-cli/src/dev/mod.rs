@@ -5,6 +5,7 @@
 //! - In-memory bundling with disk write option
 //! - File watching with debouncing
 //! - Error overlay in browser
+//! - Unbundled serving of source modules with an import map
 
 pub mod asset_middleware;
 pub mod builder;
@@ -12,6 +13,7 @@ pub mod config;
 pub mod error_overlay;
 pub mod server;
 pub mod state;
+pub mod unbundled;
 pub mod watcher;
 
 // Re-exports
@@ -20,6 +22,7 @@ pub use builder::DevBuilder;
 pub use config::DevConfig;
 pub use server::DevServer;
 pub use state::{BuildStatus, BundleCache, DevServerState, SharedState};
+pub use unbundled::UnbundledModules;
 pub use watcher::{FileChange, FileWatcher};
 
 use serde::{Deserialize, Serialize};
@@ -3,7 +3,7 @@
 //! Serves bundled files from memory cache and provides SSE endpoint
 //! for push-based reload notifications.
 
-use crate::dev::{error_overlay, DevConfig, SharedState};
+use crate::dev::{error_overlay, DevConfig, SharedState, UnbundledModules};
 use crate::error::Result;
 use axum::{
     body::Body,
@@ -174,6 +174,10 @@ async fn handle_request(
             .unwrap());
     }
 
+    if let Some(modules) = &state.unbundled {
+        return serve_unbundled(modules, path, uri.query()).await;
+    }
+
     // Try to serve from cache
     if let Some((content, content_type)) = state.get_cached_file(path) {
         return Ok(Response::builder()
@@ -248,6 +252,84 @@ async fn handle_request(
         .unwrap())
 }
 
+/// Serve a request in unbundled mode.
+///
+/// Source modules are transformed on request, dependencies come from the
+/// prebundled `/@deps/` files, and everything else is served from the
+/// project root.
+async fn serve_unbundled(
+    modules: &UnbundledModules,
+    path: &str,
+    query: Option<&str>,
+) -> Result<Response, Response> {
+    let ok = |content: Vec<u8>, content_type: &str| {
+        Response::builder()
+            .status(StatusCode::OK)
+            .header(header::CONTENT_TYPE, content_type)
+            .header(header::CACHE_CONTROL, "no-cache")
+            .body(Body::from(content))
+            .unwrap()
+    };
+    let internal_error = |message: String| {
+        Response::builder()
+            .status(StatusCode::INTERNAL_SERVER_ERROR)
+            .header(header::CONTENT_TYPE, "text/plain")
+            .body(Body::from(message))
+            .unwrap()
+    };
+
+    if path == "/" || path == "/index.html" {
+        let index = match modules.resolve("/index.html") {
+            Some(file) => Some(
+                tokio::fs::read_to_string(&file)
+                    .await
+                    .map_err(|e| internal_error(format!("Failed to read index.html: {}", e)))?,
+            ),
+            None => None,
+        };
+        let html = modules
+            .index_html(index.as_deref(), generate_index_html)
+            .map_err(internal_error)?;
+        let content_type = "text/html; charset=utf-8";
+        // The generated page already loads the reload client
+        let html = match index {
+            Some(_) => inject_reload_script(html.as_bytes(), content_type),
+            None => html.into_bytes(),
+        };
+        return Ok(ok(html, content_type));
+    }
+
+    if path.starts_with(fob_bundler::builders::unbundled::DEPS_URL_PREFIX) {
+        if let Some(code) = modules.dependency(path) {
+            return Ok(ok(code.into_bytes(), "application/javascript"));
+        }
+    }
+
+    match modules.serve(path, query) {
+        Some(Ok((content, content_type))) => return Ok(ok(content, &content_type)),
+        Some(Err(e)) => {
+            crate::ui::error(&format!("{}: {}", path, e));
+            return Err(internal_error(e.to_string()));
+        }
+        None => {}
+    }
+
+    if let Some(file) = modules.resolve(path) {
+        match tokio::fs::read(&file).await {
+            Ok(content) => return Ok(ok(content, determine_content_type(path))),
+            Err(e) => {
+                crate::ui::warning(&format!("Failed to read file {}: {}", file.display(), e));
+            }
+        }
+    }
+
+    Err(Response::builder()
+        .status(StatusCode::NOT_FOUND)
+        .header(header::CONTENT_TYPE, "text/plain")
+        .body(Body::from(format!("File not found: {}", path)))
+        .unwrap())
+}
+
 /// Inject reload script into HTML content.
 ///
 /// Adds the reload client script before the closing </body> tag.
@@ -3,6 +3,7 @@
 //! Provides thread-safe access to build artifacts, client connections,
 //! and build status using parking_lot RwLock for better performance.
 
+use super::unbundled::UnbundledModules;
 use fob_bundler::builders::asset_registry::AssetRegistry;
 use parking_lot::RwLock;
 use std::collections::HashMap;
@@ -149,6 +150,9 @@ pub struct DevServerState {
 
     /// Output directory for serving files from disk
     pub out_dir: PathBuf,
+
+    /// Source modules served without bundling, in unbundled mode
+    pub unbundled: Option<Arc<UnbundledModules>>,
 }
 
 impl DevServerState {
@@ -165,9 +169,16 @@ impl DevServerState {
             next_client_id: RwLock::new(0),
             asset_registry: RwLock::new(Arc::new(AssetRegistry::new())),
             out_dir,
+            unbundled: None,
         }
     }
 
+    /// Serve source modules unbundled instead of from the bundle cache.
+    pub fn with_unbundled(mut self, modules: Arc<UnbundledModules>) -> Self {
+        self.unbundled = Some(modules);
+        self
+    }
+
     /// Create new dev server state with a specific asset registry.
     #[cfg(test)]
     pub fn new_with_registry(registry: Arc<AssetRegistry>) -> Self {
@@ -178,6 +189,7 @@ impl DevServerState {
             next_client_id: RwLock::new(0),
             asset_registry: RwLock::new(registry),
             out_dir: PathBuf::from("dist"),
+            unbundled: None,
         }
     }
 
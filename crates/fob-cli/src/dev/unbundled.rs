@@ -0,0 +1,257 @@
+//! Unbundled development serving.
+//!
+//! Instead of bundling on every change, source modules are transformed when
+//! the browser requests them and dependencies are resolved through an import
+//! map. Only dependencies are bundled, once at startup and again when a
+//! change adds a new one.
+
+use crate::error::{CliError, Result};
+use fob_bundler::builders::unbundled::{self, PrebundledDependencies, IMPORT_QUERY};
+use parking_lot::RwLock;
+use path_clean::PathClean;
+use std::collections::{BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+/// Source modules and prebundled dependencies of an unbundled dev server.
+pub struct UnbundledModules {
+    /// Project root, which module URLs are relative to
+    root: PathBuf,
+    /// Entry points, relative to the root
+    entries: Vec<PathBuf>,
+    /// Dependencies found by the last scan, `None` before the first
+    dependencies: RwLock<Option<BTreeSet<String>>>,
+    /// Output of the last dependency build
+    prebundled: RwLock<PrebundledDependencies>,
+    /// Transformed modules by file, with the modification time they match
+    transformed: RwLock<HashMap<PathBuf, (SystemTime, String)>>,
+}
+
+impl UnbundledModules {
+    /// Create the module server for a project.
+    pub fn new(root: PathBuf, entries: Vec<PathBuf>) -> Self {
+        Self {
+            root,
+            entries,
+            dependencies: RwLock::new(None),
+            prebundled: RwLock::new(PrebundledDependencies::default()),
+            transformed: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Scan the project for dependencies and bundle them if the set changed.
+    ///
+    /// # Returns
+    ///
+    /// Whether the dependencies were rebuilt
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a module fails to transform or a dependency fails
+    /// to bundle.
+    pub async fn prepare(&self) -> Result<bool> {
+        let dependencies = unbundled::scan_dependencies(&self.entries, &self.root)
+            .map_err(|e| CliError::Core(e.to_string()))?;
+        if self.dependencies.read().as_ref() == Some(&dependencies) {
+            return Ok(false);
+        }
+
+        let prebundled = unbundled::prebundle_dependencies(&dependencies, &self.root)
+            .await
+            .map_err(|e| CliError::Core(format!("Failed to bundle dependencies: {}", e)))?;
+        *self.prebundled.write() = prebundled;
+        *self.dependencies.write() = Some(dependencies);
+        // Named imports of CommonJS dependencies depend on the dependency build
+        self.transformed.write().clear();
+        Ok(true)
+    }
+
+    /// Number of dependencies served from `/@deps/`.
+    pub fn dependency_count(&self) -> usize {
+        self.dependencies.read().as_ref().map_or(0, BTreeSet::len)
+    }
+
+    /// A prebundled dependency file, by its URL path.
+    pub fn dependency(&self, path: &str) -> Option<String> {
+        self.prebundled.read().files.get(path).cloned()
+    }
+
+    /// Serve a project file requested by the browser.
+    ///
+    /// Modules are transformed, and files imported from JavaScript with
+    /// `?import` are wrapped in a module. Anything else is left to static
+    /// file serving.
+    ///
+    /// # Returns
+    ///
+    /// `(content, content_type)`, or `None` if the path isn't served here
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the module fails to read or transform.
+    pub fn serve(&self, path: &str, query: Option<&str>) -> Option<Result<(Vec<u8>, String)>> {
+        let file = self.resolve(path)?;
+
+        if query.is_some_and(|query| format!("?{}", query) == IMPORT_QUERY) {
+            return Some(self.wrap_import(path, &file));
+        }
+        if unbundled::is_module_path(&file) {
+            return Some(
+                self.transform(&file)
+                    .map(|code| (code.into_bytes(), "application/javascript".to_string())),
+            );
+        }
+        None
+    }
+
+    /// Add the import map and, without an `index.html`, the entry script.
+    ///
+    /// # Arguments
+    ///
+    /// * `html` - The project's `index.html`, if it has one
+    /// * `generate` - Builds a page that loads the given entry URL
+    pub fn index_html(
+        &self,
+        html: Option<&str>,
+        generate: impl FnOnce(Option<&str>) -> Result<String, String>,
+    ) -> Result<String, String> {
+        let html = match html {
+            Some(html) => html.to_string(),
+            None => {
+                let entry = self.entries.first().and_then(|entry| {
+                    unbundled::module_url(&self.root.join(entry).clean(), &self.root)
+                });
+                generate(entry.as_deref())?
+            }
+        };
+        Ok(self.prebundled.read().import_map.inject_html(&html))
+    }
+
+    /// Project file for a URL path, if it exists inside the root.
+    pub fn resolve(&self, path: &str) -> Option<PathBuf> {
+        let file = self.root.join(path.trim_start_matches('/')).clean();
+        (file.starts_with(&self.root) && file.is_file()).then_some(file)
+    }
+
+    /// Transform a module, reusing the last result while the file is unchanged.
+    fn transform(&self, file: &Path) -> Result<String> {
+        let modified = std::fs::metadata(file)?.modified()?;
+        if let Some((cached_at, code)) = self.transformed.read().get(file) {
+            if *cached_at == modified {
+                return Ok(code.clone());
+            }
+        }
+
+        let source = std::fs::read_to_string(file)?;
+        let default_only = self.prebundled.read().default_only.clone();
+        let module = unbundled::transform_module(file, &source, &self.root, &default_only)
+            .map_err(|e| CliError::Core(e.to_string()))?;
+        self.transformed
+            .write()
+            .insert(file.to_path_buf(), (modified, module.code.clone()));
+        Ok(module.code)
+    }
+
+    /// Module standing in for a non-JavaScript import.
+    ///
+    /// Stylesheets are applied by appending a `<style>` element; other files
+    /// export their URL.
+    fn wrap_import(&self, path: &str, file: &Path) -> Result<(Vec<u8>, String)> {
+        let code = if file.extension().is_some_and(|ext| ext == "css") {
+            let css = serde_json::to_string(&std::fs::read_to_string(file)?)?;
+            format!(
+                "const style = document.createElement(\"style\");\n\
+                 style.dataset.fobPath = {path};\n\
+                 style.textContent = {css};\n\
+                 document.head.appendChild(style);\n\
+                 export default {css};\n",
+                path = serde_json::to_string(path)?,
+                css = css
+            )
+        } else {
+            format!("export default {};\n", serde_json::to_string(path)?)
+        };
+        Ok((code.into_bytes(), "application/javascript".to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn project() -> (TempDir, UnbundledModules) {
+        let temp = TempDir::new().unwrap();
+        fs::create_dir_all(temp.path().join("src")).unwrap();
+        fs::write(
+            temp.path().join("src/main.js"),
+            "import './app.css';\nimport { greet } from './greet';\ngreet();\n",
+        )
+        .unwrap();
+        fs::write(
+            temp.path().join("src/greet.js"),
+            "export const greet = () => {};\n",
+        )
+        .unwrap();
+        fs::write(temp.path().join("src/app.css"), "body { margin: 0; }\n").unwrap();
+
+        let modules = UnbundledModules::new(
+            temp.path().to_path_buf(),
+            vec![PathBuf::from("src/main.js")],
+        );
+        (temp, modules)
+    }
+
+    #[test]
+    fn test_serve_module_rewrites_imports() {
+        let (_temp, modules) = project();
+
+        let (code, content_type) = modules.serve("/src/main.js", None).unwrap().unwrap();
+        let code = String::from_utf8(code).unwrap();
+
+        assert_eq!(content_type, "application/javascript");
+        assert!(code.contains("\"/src/app.css?import\""));
+        assert!(code.contains("\"/src/greet.js\""));
+    }
+
+    #[test]
+    fn test_serve_css_import_as_module() {
+        let (_temp, modules) = project();
+
+        let (code, _) = modules
+            .serve("/src/app.css", Some("import"))
+            .unwrap()
+            .unwrap();
+        let code = String::from_utf8(code).unwrap();
+
+        assert!(code.contains("document.head.appendChild(style)"));
+        assert!(code.contains("body { margin: 0; }"));
+
+        // Without the query the stylesheet is a static file
+        assert!(modules.serve("/src/app.css", None).is_none());
+    }
+
+    #[test]
+    fn test_serve_rejects_paths_outside_root() {
+        let (_temp, modules) = project();
+
+        assert!(modules.serve("/../../etc/passwd", None).is_none());
+        assert!(modules.serve("/src/missing.js", None).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_prepare_without_dependencies() {
+        let (_temp, modules) = project();
+
+        assert!(modules.prepare().await.unwrap());
+        assert!(!modules.prepare().await.unwrap());
+
+        assert_eq!(modules.dependency_count(), 0);
+        let html = modules
+            .index_html(None, |entry| Ok(format!("<head></head>{}", entry.unwrap())))
+            .unwrap();
+        assert!(html.contains("/src/main.js"));
+        assert!(html.contains(r#"<script type="importmap">{"imports":{}}</script>"#));
+    }
+}
@@ -86,11 +86,29 @@ pub enum CliError {
     #[error("Core bundler error: {0}")]
     Core(String),
 
+    /// The build succeeded but violated a `--ci` policy (size budget,
+    /// warnings)
+    #[error("{0}")]
+    PolicyViolation(String),
+
     /// Generic errors with custom messages
     #[error("{0}")]
     Custom(String),
 }
 
+impl CliError {
+    /// Process exit status for this error.
+    ///
+    /// Policy violations exit with 2 so pipelines can tell them apart from
+    /// failed builds, which exit with 1.
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            CliError::PolicyViolation(_) => 2,
+            _ => 1,
+        }
+    }
+}
+
 /// Configuration-specific errors.
 ///
 /// These errors occur during config file loading, parsing, and validation.
@@ -3,7 +3,7 @@
 //! This is the main entry point for the Joy CLI. It handles command-line argument
 //! parsing, logging initialization, and command dispatch.
 
-use clap::Parser;
+use clap::{CommandFactory, Parser};
 use fob_cli::{cli, commands, error, logger, ui};
 use miette::Result;
 
@@ -16,14 +16,46 @@ async fn main() -> Result<()> {
     logger::init_logger(args.verbose, args.quiet, args.no_color);
     ui::init_colors();
 
+    if args.capabilities {
+        return commands::capabilities_execute(args.json).map_err(error::cli_error_to_miette);
+    }
+    let Some(command) = args.command else {
+        cli::Cli::command()
+            .error(
+                clap::error::ErrorKind::MissingSubcommand,
+                "a subcommand is required unless --capabilities is given",
+            )
+            .exit();
+    };
+
     // Execute the appropriate command
-    let result = match args.command {
+    let result = match command {
         cli::Command::Build(build_args) => commands::build_execute(build_args).await,
         cli::Command::Dev(dev_args) => commands::dev_execute(dev_args).await,
         cli::Command::Init(init_args) => commands::init_execute(init_args).await,
         cli::Command::Check(check_args) => commands::check_execute(check_args).await,
+        cli::Command::NewPlugin(new_plugin_args) => {
+            commands::new_plugin_execute(new_plugin_args).await
+        }
+        cli::Command::Mdx(mdx_args) => commands::mdx_execute(mdx_args).await,
+        cli::Command::Graph(graph_args) => commands::graph_execute(graph_args).await,
+        cli::Command::Analyze(analyze_args) => commands::analyze_execute(analyze_args).await,
+        cli::Command::Compare(compare_args) => commands::compare_execute(compare_args).await,
+        cli::Command::Config(config_args) => commands::config_execute(config_args).await,
+        cli::Command::Completions(completions_args) => {
+            commands::completions_execute(completions_args).await
+        }
+        cli::Command::Man(man_args) => commands::man_execute(man_args).await,
     };
 
     // Convert CLI errors to miette diagnostics for beautiful error reporting
-    result.map_err(error::cli_error_to_miette)
+    if let Err(err) = result {
+        let code = err.exit_code();
+        if code == 1 {
+            return Err(error::cli_error_to_miette(err));
+        }
+        eprintln!("Error: {:?}", error::cli_error_to_miette(err));
+        std::process::exit(code);
+    }
+    Ok(())
 }
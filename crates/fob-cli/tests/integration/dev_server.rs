@@ -134,6 +134,7 @@ async fn test_dev_config_from_args() {
         port: 3000,
         https: false,
         open: false,
+        unbundled: false,
         cwd: Some(project_dir.to_path_buf()),
     };
 
@@ -174,6 +175,7 @@ async fn test_dev_config_loads_from_file() {
         port: 3000,
         https: false,
         open: false,
+        unbundled: false,
         cwd: Some(project_dir.to_path_buf()),
     };
 
@@ -62,6 +62,13 @@ async fn test_build_successful_single_entry() {
         splitting: false,
         no_treeshake: false,
         clean: false,
+        validate_output: false,
+        watch: false,
+        on_success: None,
+        ci: false,
+        summary: None,
+        max_chunk_size: None,
+        fail_on_warnings: false,
         cwd: Some(project_dir.to_path_buf()),
         bundle: true,
     };
@@ -128,6 +135,13 @@ async fn test_build_missing_entry_point() {
         splitting: false,
         no_treeshake: false,
         clean: false,
+        validate_output: false,
+        watch: false,
+        on_success: None,
+        ci: false,
+        summary: None,
+        max_chunk_size: None,
+        fail_on_warnings: false,
         cwd: Some(project_dir.to_path_buf()),
         bundle: true,
     };
@@ -190,6 +204,13 @@ async fn test_build_clean_output_dir() {
         splitting: false,
         no_treeshake: false,
         clean: true, // Enable clean
+        validate_output: false,
+        watch: false,
+        on_success: None,
+        ci: false,
+        summary: None,
+        max_chunk_size: None,
+        fail_on_warnings: false,
         cwd: Some(project_dir.to_path_buf()),
         bundle: true,
     };
@@ -252,6 +273,13 @@ async fn test_build_empty_entry_list() {
         splitting: false,
         no_treeshake: false,
         clean: false,
+        validate_output: false,
+        watch: false,
+        on_success: None,
+        ci: false,
+        summary: None,
+        max_chunk_size: None,
+        fail_on_warnings: false,
         cwd: Some(project_dir.to_path_buf()),
         bundle: true,
     };
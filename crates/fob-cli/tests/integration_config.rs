@@ -58,6 +58,13 @@ fn test_config_cli_overrides_file() {
         splitting: false,
         no_treeshake: false,
         clean: false,
+        validate_output: false,
+        watch: false,
+        on_success: None,
+        ci: false,
+        summary: None,
+        max_chunk_size: None,
+        fail_on_warnings: false,
         cwd: Some(cwd.to_path_buf()),
         bundle: true,
     };
@@ -115,6 +122,13 @@ fn test_config_file_fallback_when_cli_empty() {
         splitting: false,
         no_treeshake: false,
         clean: false,
+        validate_output: false,
+        watch: false,
+        on_success: None,
+        ci: false,
+        summary: None,
+        max_chunk_size: None,
+        fail_on_warnings: false,
         cwd: Some(cwd.to_path_buf()),
         bundle: true,
     };
@@ -162,6 +176,13 @@ fn test_config_defaults_when_no_file() {
         splitting: false,
         no_treeshake: false,
         clean: false,
+        validate_output: false,
+        watch: false,
+        on_success: None,
+        ci: false,
+        summary: None,
+        max_chunk_size: None,
+        fail_on_warnings: false,
         cwd: Some(cwd.to_path_buf()),
         bundle: true,
     };
@@ -222,6 +243,13 @@ fn test_config_environment_variables() {
         splitting: false,
         no_treeshake: false,
         clean: false,
+        validate_output: false,
+        watch: false,
+        on_success: None,
+        ci: false,
+        summary: None,
+        max_chunk_size: None,
+        fail_on_warnings: false,
         cwd: Some(cwd.to_path_buf()),
         bundle: true,
     };
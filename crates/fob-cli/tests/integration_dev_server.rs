@@ -142,6 +142,7 @@ async fn test_dev_config_from_args() {
         port: requested_port,
         https: false,
         open: false,
+        unbundled: false,
         cwd: Some(project_dir.to_path_buf()),
     };
 
@@ -191,6 +192,7 @@ async fn test_dev_config_loads_from_file() {
         port: requested_port,
         https: false,
         open: false,
+        unbundled: false,
         cwd: Some(project_dir.to_path_buf()),
     };
 
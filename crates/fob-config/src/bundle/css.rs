@@ -1,10 +1,11 @@
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 
 use crate::bundle::helpers::{default_tailwind_output, default_true};
 
 /// CSS processing configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct CssOptions {
     /// Enable CSS processing
     #[serde(default = "default_true")]
@@ -35,7 +36,7 @@ impl Default for CssOptions {
 }
 
 /// Tailwind CSS configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct TailwindOptions {
     /// Enable Tailwind CSS processing
     #[serde(default = "default_true")]
@@ -1,3 +1,4 @@
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::collections::HashMap;
@@ -7,7 +8,7 @@ use crate::bundle::helpers::{default_html_filename, default_lang};
 use crate::bundle::types::HtmlTemplateType;
 
 /// HTML generation options
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct HtmlOptions {
     /// Path to custom HTML template (Jinja2 format)
     /// If not provided, uses built-in template based on template type
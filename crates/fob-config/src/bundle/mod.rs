@@ -7,6 +7,7 @@ mod plugin;
 mod transform;
 mod types;
 
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::collections::HashMap;
@@ -14,7 +15,7 @@ use std::path::PathBuf;
 
 pub use css::{CssOptions, TailwindOptions};
 pub use html::HtmlOptions;
-pub use plugin::{CacheConfig, PluginBackend, PluginOptions};
+pub use plugin::{BUILTIN_PLUGINS, CacheConfig, PluginBackend, PluginOptions};
 pub use transform::{TransformOptions, TypeScriptConfig};
 pub use types::{
     EsTarget, ExperimentalOptions, HtmlTemplateType, OutputFormat, Platform, SourceMapOptions,
@@ -26,7 +27,7 @@ use helpers::{
 };
 
 /// Main bundle configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct BundleOptions {
     /// Entry points (must be ESM modules)
     #[serde(default)]
@@ -243,7 +244,7 @@ impl Default for BundleOptions {
 }
 
 /// Configuration for virtual modules
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
 pub struct VirtualModuleConfig {
     /// Map of virtual module ID to JavaScript generator function code
     /// The generator function should return the module source code as a string
@@ -259,7 +260,7 @@ pub struct VirtualModuleConfig {
 }
 
 /// Configuration for an inline transform
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct InlineTransform {
     /// Glob pattern or regex to match files
     pub test: String,
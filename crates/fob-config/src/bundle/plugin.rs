@@ -1,3 +1,4 @@
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::collections::HashMap;
@@ -5,7 +6,7 @@ use std::path::PathBuf;
 
 use crate::bundle::helpers::default_true;
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct CacheConfig {
     /// Enable persistent cache layer
     #[serde(default = "default_true")]
@@ -31,7 +32,20 @@ impl Default for CacheConfig {
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Plugins built into fob that can be enabled by `name` alone.
+pub const BUILTIN_PLUGINS: &[&str] = &["fob-css", "fob-mdx"];
+
+/// A plugin declared in the config.
+///
+/// Built-in plugins are selected by `name` and take their options from
+/// `options`; other plugins are loaded from `path`.
+///
+/// ```toml
+/// [[plugins]]
+/// name = "fob-mdx"
+/// options = { gfm = true, jsxTarget = "preact" }
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct PluginOptions {
     /// Optional friendly name for the plugin
     #[serde(default)]
@@ -41,11 +55,12 @@ pub struct PluginOptions {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub backend: Option<PluginBackend>,
 
-    /// Path to the plugin artifact
+    /// Path to the plugin artifact (empty for built-in plugins)
+    #[serde(default)]
     pub path: PathBuf,
 
     /// Plugin-specific configuration forwarded during `Plugin::init`
-    #[serde(default)]
+    #[serde(default, alias = "options")]
     pub config: Value,
 
     /// Execution order (lower values run earlier)
@@ -73,6 +88,17 @@ pub struct PluginOptions {
     pub profiles: HashMap<String, Value>,
 }
 
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+impl PluginOptions {
+    /// Name of the built-in plugin this entry enables, if it has no `path`.
+    pub fn builtin(&self) -> Option<&str> {
+        if self.path.as_os_str().is_empty() {
+            self.name.as_deref()
+        } else {
+            None
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "lowercase")]
 pub enum PluginBackend {}
@@ -1,3 +1,4 @@
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::path::PathBuf;
@@ -6,7 +7,7 @@ use crate::bundle::helpers::{default_mode, default_true};
 use crate::bundle::types::{EsTarget, TypeCheckMode};
 
 /// Transformation/transpilation options for TypeScript and JSX
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct TransformOptions {
     /// Enable TypeScript to JavaScript transformation
     #[serde(default = "default_true")]
@@ -100,7 +101,7 @@ impl Default for TransformOptions {
 }
 
 /// TypeScript configuration options
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct TypeScriptConfig {
     /// Path to tsconfig.json (None = auto-discovery disabled)
     #[serde(skip_serializing_if = "Option::is_none")]
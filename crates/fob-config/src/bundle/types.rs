@@ -1,7 +1,8 @@
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 /// Output format for bundles
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "lowercase")]
 pub enum OutputFormat {
     /// Standard ESM bundle with runtime
@@ -13,7 +14,7 @@ pub enum OutputFormat {
 }
 
 /// Target platform
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "lowercase")]
 pub enum Platform {
     /// Browser environment (default)
@@ -28,7 +29,7 @@ pub enum Platform {
 }
 
 /// Source map generation options
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "lowercase")]
 pub enum SourceMapOptions {
     /// No source maps
@@ -43,7 +44,7 @@ pub enum SourceMapOptions {
 }
 
 /// Experimental features (unstable APIs)
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
 pub struct ExperimentalOptions {
     /// Enable WASM module support (import assertions)
     #[serde(default)]
@@ -63,7 +64,7 @@ pub struct ExperimentalOptions {
 }
 
 /// Target ECMAScript version for transpilation
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "lowercase")]
 pub enum EsTarget {
     /// ECMAScript 2015 (ES6)
@@ -92,7 +93,7 @@ pub enum EsTarget {
 }
 
 /// Type-checking mode for TypeScript
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "lowercase")]
 pub enum TypeCheckMode {
     /// No type-checking (transpile-only)
@@ -103,7 +104,7 @@ pub enum TypeCheckMode {
 }
 
 /// Type of built-in HTML template to use
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "lowercase")]
 pub enum HtmlTemplateType {
     /// Single-page application template (default)
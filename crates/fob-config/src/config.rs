@@ -1,10 +1,13 @@
 //! High-level configuration structure for Fob.
 //!
-//! This module provides the main `JoyConfig` struct and profile merging logic.
+//! This module provides the main `JoyConfig` struct and profile merging logic,
+//! including profile inheritance via `extends`.
 //! For file discovery, see the `discovery` module.
 
 use std::collections::HashMap;
 
+use schemars::JsonSchema;
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
@@ -13,7 +16,7 @@ use crate::dev::DevConfig;
 use crate::error::{ConfigError, Result as ConfigResult};
 use crate::settings::GlobalSettings;
 
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
 pub struct JoyConfig {
     #[serde(default)]
     pub bundle: BundleOptions,
@@ -33,8 +36,21 @@ pub struct JoyConfig {
     extra_plugins: Vec<PluginOptions>,
 }
 
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+/// Environment-specific overrides, deep-merged into the base config.
+///
+/// ```toml
+/// [profiles.production.bundle]
+/// minify = true
+///
+/// [profiles.staging]
+/// extends = "production"
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
 pub struct ProfileConfig {
+    /// Profile whose overrides are applied before this one's
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub extends: Option<String>,
+
     #[serde(default)]
     pub bundle: Value,
 
@@ -43,8 +59,16 @@ pub struct ProfileConfig {
 
     #[serde(default)]
     pub settings: Value,
+
+    /// Keys that aren't profile sections, rejected when the profile is applied
+    #[serde(flatten, skip_serializing)]
+    #[schemars(skip)]
+    unknown: serde_json::Map<String, Value>,
 }
 
+/// Keys allowed directly under `[profiles.<name>]`.
+const PROFILE_KEYS: &[&str] = &["extends", "bundle", "dev", "settings"];
+
 impl JoyConfig {
     /// Create from serde_json::Value (for programmatic config from DB/API)
     ///
@@ -85,65 +109,98 @@ impl JoyConfig {
 }
 
 impl JoyConfig {
+    /// Apply a profile's overrides on top of the base config.
+    ///
+    /// Profiles it `extends` are applied first, root-most first. Plugin
+    /// `profiles` entries for each of these profiles are applied as well.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when an override has a key the config doesn't know
+    /// (with a suggestion for likely typos), when the selected profile or
+    /// one it `extends` isn't defined, when `extends` forms a cycle, or when
+    /// the merged config is invalid.
     pub fn materialize_profile(mut self, profile: Option<&str>) -> ConfigResult<Self> {
         self.promote_top_level_plugins();
 
         if let Some(name) = profile {
-            if let Some(profile_cfg) = self.profiles.get(name) {
-                if !profile_cfg.bundle.is_null() {
-                    let mut base = serde_json::to_value(&self.bundle).map_err(|err| {
-                        ConfigError::InvalidProfileOverride {
-                            message: err.to_string(),
-                        }
-                    })?;
-                    merge_values(&mut base, &profile_cfg.bundle);
-                    self.bundle = serde_json::from_value(base).map_err(|err| {
-                        ConfigError::InvalidProfileOverride {
-                            message: err.to_string(),
-                        }
-                    })?;
+            for name in self.profile_chain(name)? {
+                if let Some(profile_cfg) = self.profiles.get(&name).cloned() {
+                    self.apply_profile(&name, &profile_cfg)?;
                 }
+                apply_plugin_profiles(&mut self.bundle.plugins, &name)?;
+            }
+        }
 
-                if !profile_cfg.dev.is_null() {
-                    let mut base = match &self.dev {
-                        Some(dev) => serde_json::to_value(dev).map_err(|err| {
-                            ConfigError::InvalidProfileOverride {
-                                message: err.to_string(),
-                            }
-                        })?,
-                        None => Value::Null,
-                    };
-                    merge_values(&mut base, &profile_cfg.dev);
-                    if base.is_null() {
-                        self.dev = None;
-                    } else {
-                        self.dev = Some(serde_json::from_value(base).map_err(|err| {
-                            ConfigError::InvalidProfileOverride {
-                                message: err.to_string(),
-                            }
-                        })?);
-                    }
-                }
+        Ok(self)
+    }
 
-                if !profile_cfg.settings.is_null() {
-                    let mut base = serde_json::to_value(&self.settings).map_err(|err| {
-                        ConfigError::InvalidProfileOverride {
-                            message: err.to_string(),
-                        }
-                    })?;
-                    merge_values(&mut base, &profile_cfg.settings);
-                    self.settings = serde_json::from_value(base).map_err(|err| {
-                        ConfigError::InvalidProfileOverride {
-                            message: err.to_string(),
-                        }
-                    })?;
-                }
+    /// The profiles to apply for `name`, following `extends` root-first.
+    ///
+    /// The selected profile may be defined by plugins alone.
+    fn profile_chain(&self, name: &str) -> ConfigResult<Vec<String>> {
+        let defined_by_plugin = self
+            .bundle
+            .plugins
+            .iter()
+            .any(|plugin| plugin.profiles.contains_key(name));
+        if !self.profiles.contains_key(name) && !defined_by_plugin {
+            return Err(self.unknown_profile(name));
+        }
+
+        let mut chain = vec![name.to_string()];
+        let mut current = name;
+        while let Some(parent) = self
+            .profiles
+            .get(current)
+            .and_then(|profile| profile.extends.as_deref())
+        {
+            if !self.profiles.contains_key(parent) {
+                return Err(self.unknown_profile(parent));
+            }
+            if chain.iter().any(|seen| seen == parent) {
+                chain.push(parent.to_string());
+                chain.reverse();
+                return Err(ConfigError::InvalidProfileOverride {
+                    message: format!("profiles extend each other: {}", chain.join(" -> ")),
+                });
             }
+            chain.push(parent.to_string());
+            current = parent;
+        }
+        chain.reverse();
+        Ok(chain)
+    }
 
-            apply_plugin_profiles(&mut self.bundle.plugins, name)?;
+    fn unknown_profile(&self, name: &str) -> ConfigError {
+        ConfigError::UnknownProfile {
+            name: name.to_string(),
+            hint: suggest(name, self.profiles.keys().map(String::as_str))
+                .map(|candidate| format!("Did you mean `{}`?", candidate)),
         }
+    }
 
-        Ok(self)
+    fn apply_profile(&mut self, name: &str, profile_cfg: &ProfileConfig) -> ConfigResult<()> {
+        if let Some(key) = profile_cfg.unknown.keys().next() {
+            return Err(ConfigError::UnknownProfileKey {
+                profile: name.to_string(),
+                key: key.clone(),
+                hint: suggest(key, PROFILE_KEYS.iter().copied())
+                    .map(|candidate| format!("Did you mean `{}`?", candidate)),
+            });
+        }
+
+        if !profile_cfg.bundle.is_null() {
+            self.bundle = merge_section(&self.bundle, &profile_cfg.bundle, name, "bundle")?;
+        }
+        if !profile_cfg.dev.is_null() {
+            self.dev = merge_section(&self.dev, &profile_cfg.dev, name, "dev")?;
+        }
+        if !profile_cfg.settings.is_null() {
+            self.settings = merge_section(&self.settings, &profile_cfg.settings, name, "settings")?;
+        }
+
+        Ok(())
     }
 
     fn promote_top_level_plugins(&mut self) {
@@ -155,6 +212,96 @@ impl JoyConfig {
     }
 }
 
+/// Deep-merge a profile's overrides into one config section.
+///
+/// Keys the section drops when deserializing are unknown to it, so the
+/// overrides are checked against the re-serialized result.
+fn merge_section<T: Serialize + DeserializeOwned>(
+    base: &T,
+    overrides: &Value,
+    profile: &str,
+    section: &str,
+) -> ConfigResult<T> {
+    let invalid = |err: serde_json::Error| ConfigError::InvalidProfileOverride {
+        message: format!("profile '{}', section '{}': {}", profile, section, err),
+    };
+
+    let mut merged = serde_json::to_value(base).map_err(invalid)?;
+    merge_values(&mut merged, overrides);
+    let updated: T = serde_json::from_value(merged).map_err(invalid)?;
+
+    let known = serde_json::to_value(&updated).map_err(invalid)?;
+    if let Some((key, hint)) = find_unknown_key(overrides, &known, section) {
+        return Err(ConfigError::UnknownProfileKey {
+            profile: profile.to_string(),
+            key,
+            hint,
+        });
+    }
+
+    Ok(updated)
+}
+
+/// Find the first key of `overrides` that is missing from `known`, with
+/// its dotted path and a suggestion from the keys at the same level.
+fn find_unknown_key(
+    overrides: &Value,
+    known: &Value,
+    path: &str,
+) -> Option<(String, Option<String>)> {
+    let (Value::Object(overrides), Value::Object(known)) = (overrides, known) else {
+        return None;
+    };
+    for (key, value) in overrides {
+        if value.is_null() {
+            continue;
+        }
+        let key_path = format!("{}.{}", path, key);
+        match known.get(key) {
+            Some(known_value) => {
+                if let Some(unknown) = find_unknown_key(value, known_value, &key_path) {
+                    return Some(unknown);
+                }
+            }
+            None => {
+                let hint = suggest(key, known.keys().map(String::as_str))
+                    .map(|candidate| format!("Did you mean `{}.{}`?", path, candidate));
+                return Some((key_path, hint));
+            }
+        }
+    }
+    None
+}
+
+/// The candidate closest to `name`, if it's close enough to be a typo.
+pub(crate) fn suggest<'a>(
+    name: &str,
+    candidates: impl IntoIterator<Item = &'a str>,
+) -> Option<&'a str> {
+    let max_distance = (name.chars().count() / 3).max(1);
+    candidates
+        .into_iter()
+        .map(|candidate| (edit_distance(name, candidate), candidate))
+        .filter(|(distance, _)| *distance <= max_distance)
+        .min()
+        .map(|(_, candidate)| candidate)
+}
+
+/// Levenshtein distance between two strings.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, a_char) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, b_char) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(a_char != *b_char);
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
 fn merge_values(target: &mut Value, update: &Value) {
     match (target, update) {
         (Value::Object(target_map), Value::Object(update_map)) => {
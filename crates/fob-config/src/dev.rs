@@ -1,10 +1,11 @@
 //! Development server configuration types.
 
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::path::PathBuf;
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct DevConfig {
     #[serde(default = "default_host")]
     pub host: String,
@@ -60,7 +61,7 @@ impl Default for DevConfig {
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default, JsonSchema)]
 pub struct ProxyConfig {
     pub target: Option<String>,
 
@@ -77,7 +78,7 @@ pub struct ProxyConfig {
     pub rewrite: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default, JsonSchema)]
 pub struct CorsConfig {
     #[serde(default)]
     pub origins: Vec<String>,
@@ -98,7 +99,7 @@ pub struct CorsConfig {
     pub allow_credentials: bool,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default, JsonSchema)]
 pub struct HttpsConfig {
     #[serde(default)]
     pub key: Option<PathBuf>,
@@ -10,6 +10,27 @@ use serde_json::Value;
 use crate::config::JoyConfig;
 use crate::error::{ConfigError, Result};
 
+/// Environment variable that selects a config profile when `--profile`
+/// isn't given.
+pub const PROFILE_ENV: &str = "FOB_PROFILE";
+
+/// Resolve which profile to apply: an explicit `--profile` value wins,
+/// otherwise a non-empty `FOB_PROFILE`.
+pub fn selected_profile(explicit: Option<&str>) -> Option<String> {
+    if let Some(profile) = explicit {
+        return Some(profile.to_string());
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    if let Ok(profile) = std::env::var(PROFILE_ENV) {
+        if !profile.trim().is_empty() {
+            return Some(profile.trim().to_string());
+        }
+    }
+
+    None
+}
+
 /// File-based configuration discovery
 ///
 /// Searches for Fob configuration files in conventional locations and loads them.
@@ -72,12 +93,27 @@ impl ConfigDiscovery {
     }
 
     /// Load config with profile merging
+    ///
+    /// # Errors
+    ///
+    /// Returns `ConfigError::UnknownProfile` if `profile` isn't defined.
     pub fn load_with_profile(&self, profile: &str) -> Result<JoyConfig> {
         let mut config = self.load()?;
         config = config.materialize_profile(Some(profile))?;
         Ok(config)
     }
 
+    /// Load config with the selected profile applied
+    ///
+    /// Uses `profile` when given (e.g. from `--profile`), falling back to
+    /// `FOB_PROFILE`. Without either, this is the same as [`Self::load`].
+    pub fn load_selected(&self, profile: Option<&str>) -> Result<JoyConfig> {
+        match selected_profile(profile) {
+            Some(profile) => self.load_with_profile(&profile),
+            None => self.load(),
+        }
+    }
+
     /// Load config from a specific file path
     fn load_from(&self, path: &Path) -> Result<JoyConfig> {
         // Handle package.json specially
@@ -128,6 +164,8 @@ impl ConfigDiscovery {
 
 /// Discover and load config from current directory (convenience function)
 ///
+/// No profile is applied; see [`discover_selected`] to honor `FOB_PROFILE`.
+///
 /// # Example
 ///
 /// ```no_run
@@ -145,6 +183,28 @@ pub fn discover() -> Result<JoyConfig> {
     ConfigDiscovery::new(&root).load()
 }
 
+/// Discover and load config with the selected profile (convenience function)
+///
+/// Uses `profile` when given (e.g. from `--profile`), falling back to
+/// `FOB_PROFILE`, as [`ConfigDiscovery::load_selected`] does.
+///
+/// # Example
+///
+/// ```no_run
+/// use fob_config::discover_selected;
+///
+/// let config = discover_selected(None).unwrap();
+/// ```
+pub fn discover_selected(profile: Option<&str>) -> Result<JoyConfig> {
+    #[cfg(not(target_arch = "wasm32"))]
+    let root = std::env::current_dir()?;
+
+    #[cfg(target_arch = "wasm32")]
+    let root = PathBuf::from("/");
+
+    ConfigDiscovery::new(&root).load_selected(profile)
+}
+
 /// Discover and load config with profile (convenience function)
 ///
 /// # Example
@@ -24,6 +24,14 @@ pub enum ConfigError {
     )]
     PluginNotFound { path: PathBuf },
 
+    #[error("Unknown built-in plugin '{name}'")]
+    #[diagnostic(code(fob::config::unknown_plugin))]
+    UnknownPlugin {
+        name: String,
+        #[help]
+        hint: Option<String>,
+    },
+
     #[error("Cache directory is not writable: {}", path.display())]
     #[diagnostic(
         code(fob::config::cache_not_writable),
@@ -61,6 +69,23 @@ pub enum ConfigError {
     )]
     InvalidProfileOverride { message: String },
 
+    #[error("Unknown key '{key}' in profile '{profile}'")]
+    #[diagnostic(code(fob::config::unknown_profile_key))]
+    UnknownProfileKey {
+        profile: String,
+        key: String,
+        #[help]
+        hint: Option<String>,
+    },
+
+    #[error("Profile '{name}' is not defined")]
+    #[diagnostic(code(fob::config::unknown_profile))]
+    UnknownProfile {
+        name: String,
+        #[help]
+        hint: Option<String>,
+    },
+
     // Schema validation errors (no filesystem checks)
     #[error("No entries specified in configuration")]
     #[diagnostic(
@@ -3,6 +3,7 @@ pub mod config;
 pub mod dev;
 pub mod discovery;
 pub mod error;
+pub mod schema;
 pub mod settings;
 pub mod validation;
 
@@ -14,5 +15,9 @@ pub use error::*;
 pub use settings::*;
 
 // Re-export discovery and validation
-pub use discovery::{ConfigDiscovery, discover, discover_with_profile};
+pub use discovery::{
+    ConfigDiscovery, PROFILE_ENV, discover, discover_selected, discover_with_profile,
+    selected_profile,
+};
+pub use schema::schema;
 pub use validation::{ConfigValidator, FsValidator, SchemaValidator, validate_fs, validate_schema};
@@ -0,0 +1,22 @@
+//! JSON Schema for the config format.
+//!
+//! Editors can use it to complete and validate `fob.toml` (e.g. via a
+//! `#:schema` directive) and other tools can validate configs before loading.
+
+use serde_json::Value;
+
+use crate::config::JoyConfig;
+
+/// Generate a JSON Schema describing the full config file.
+///
+/// # Example
+///
+/// ```
+/// let schema = fob_config::schema();
+/// assert!(schema["properties"]["bundle"].is_object());
+/// ```
+pub fn schema() -> Value {
+    let mut schema = schemars::schema_for!(JoyConfig);
+    schema.insert("title".to_string(), "Fob configuration".into());
+    serde_json::to_value(schema).expect("Schema serialization should never fail")
+}
@@ -1,9 +1,10 @@
 //! Global configuration settings shared across profiles.
 
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
 pub struct GlobalSettings {
     #[serde(default)]
     pub log_level: Option<String>,
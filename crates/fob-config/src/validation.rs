@@ -4,7 +4,8 @@
 
 use std::path::Path;
 
-use crate::bundle::BundleOptions;
+use crate::bundle::{BUILTIN_PLUGINS, BundleOptions};
+use crate::config::suggest;
 use crate::error::{ConfigError, Result};
 
 /// Trait for pluggable config validation strategies
@@ -50,10 +51,27 @@ impl ConfigValidator for SchemaValidator {
         // Validate plugin configurations
         for plugin in &config.plugins {
             if plugin.path.as_os_str().is_empty() {
-                return Err(ConfigError::SchemaValidation {
-                    message: "plugin path cannot be empty".to_string(),
-                    hint: Some("Specify a valid path for each plugin".to_string()),
-                });
+                let Some(name) = plugin.name.as_deref() else {
+                    return Err(ConfigError::SchemaValidation {
+                        message: "plugin path cannot be empty".to_string(),
+                        hint: Some(
+                            "Specify a valid path for each plugin, or the name of a built-in plugin"
+                                .to_string(),
+                        ),
+                    });
+                };
+                if !BUILTIN_PLUGINS.contains(&name) {
+                    return Err(ConfigError::UnknownPlugin {
+                        name: name.to_string(),
+                        hint: Some(match suggest(name, BUILTIN_PLUGINS.iter().copied()) {
+                            Some(candidate) => format!("Did you mean `{}`?", candidate),
+                            None => format!(
+                                "Built-in plugins are {}; set `path` to load another plugin",
+                                BUILTIN_PLUGINS.join(", ")
+                            ),
+                        }),
+                    });
+                }
             }
 
             // Validate order is reasonable
@@ -130,7 +148,11 @@ impl ConfigValidator for FsValidator {
             }
         }
 
-        for plugin in &config.plugins {
+        for plugin in config
+            .plugins
+            .iter()
+            .filter(|plugin| plugin.builtin().is_none())
+        {
             let path = self.root.join(&plugin.path);
             if !path.exists() {
                 return Err(ConfigError::PluginNotFound { path });
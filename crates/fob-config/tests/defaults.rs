@@ -1,8 +1,9 @@
 //! Tests for default values and edge cases.
 
 use fob_config::{
-    BundleOptions, CacheConfig, DevConfig, EsTarget, ExperimentalOptions, GlobalSettings,
-    JoyConfig, OutputFormat, Platform, SourceMapOptions, TransformOptions, TypeCheckMode,
+    BundleOptions, CacheConfig, ConfigError, DevConfig, EsTarget, ExperimentalOptions,
+    GlobalSettings, JoyConfig, OutputFormat, Platform, SourceMapOptions, TransformOptions,
+    TypeCheckMode,
 };
 use std::path::PathBuf;
 
@@ -121,7 +122,7 @@ fn type_check_mode_enum() {
 fn empty_profiles_map() {
     let config = JoyConfig::default();
     let result = config.materialize_profile(Some("nonexistent"));
-    assert!(result.is_ok());
+    assert!(matches!(result, Err(ConfigError::UnknownProfile { .. })));
 }
 
 #[test]
@@ -1,6 +1,6 @@
 //! Tests for configuration profiles and merging behavior.
 
-use fob_config::ConfigDiscovery;
+use fob_config::{ConfigDiscovery, ConfigError, PROFILE_ENV};
 use serde_json::Value;
 use std::fs;
 use std::path::PathBuf;
@@ -151,7 +151,7 @@ external = ["react"]
 }
 
 #[test]
-fn profile_not_found_uses_base_config() {
+fn profile_not_found_is_error() {
     let _guard = test_lock().lock().expect("lock");
     let dir = TempDir::new().expect("tempdir");
     let config_path = dir.path().join("fob.toml");
@@ -160,15 +160,24 @@ fn profile_not_found_uses_base_config() {
         r#"
 [bundle]
 minify = false
+
+[profiles.production.bundle]
+minify = true
 "#,
     )
     .expect("write config");
 
-    let config = ConfigDiscovery::new(dir.path())
-        .load_with_profile("nonexistent")
-        .expect("load with profile");
-
-    assert!(!config.bundle.minify);
+    let err = ConfigDiscovery::new(dir.path())
+        .load_with_profile("prodution")
+        .expect_err("undefined profile should fail");
+
+    match err {
+        ConfigError::UnknownProfile { name, hint } => {
+            assert_eq!(name, "prodution");
+            assert_eq!(hint.as_deref(), Some("Did you mean `production`?"));
+        }
+        other => panic!("unexpected error: {other:?}"),
+    }
 }
 
 #[test]
@@ -280,3 +289,200 @@ path = "./top.wasm"
     assert!(names.contains(&"bundle_plugin"));
     assert!(names.contains(&"top_level_plugin"));
 }
+
+#[test]
+fn profile_extends_another_profile() {
+    let _guard = test_lock().lock().expect("lock");
+    let dir = TempDir::new().expect("tempdir");
+    fs::write(
+        dir.path().join("fob.toml"),
+        r#"
+[bundle]
+entries = ["src/index.ts"]
+minify = false
+external = ["react"]
+
+[profiles.production.bundle]
+minify = true
+external = ["react", "react-dom"]
+
+[profiles.staging]
+extends = "production"
+
+[profiles.staging.bundle]
+external = ["vue"]
+"#,
+    )
+    .expect("write config");
+
+    let config = ConfigDiscovery::new(dir.path())
+        .load_with_profile("staging")
+        .expect("load with profile");
+
+    assert!(config.bundle.minify);
+    assert_eq!(config.bundle.external, vec!["vue"]);
+}
+
+#[test]
+fn profile_extends_cycle_is_error() {
+    let _guard = test_lock().lock().expect("lock");
+    let dir = TempDir::new().expect("tempdir");
+    fs::write(
+        dir.path().join("fob.toml"),
+        r#"
+[profiles.a]
+extends = "b"
+
+[profiles.b]
+extends = "a"
+"#,
+    )
+    .expect("write config");
+
+    let err = ConfigDiscovery::new(dir.path())
+        .load_with_profile("a")
+        .expect_err("cycle should fail");
+
+    match err {
+        ConfigError::InvalidProfileOverride { message } => {
+            assert!(message.contains("a -> b -> a"), "{message}");
+        }
+        other => panic!("unexpected error: {other:?}"),
+    }
+}
+
+#[test]
+fn profile_extends_unknown_profile_suggests_name() {
+    let _guard = test_lock().lock().expect("lock");
+    let dir = TempDir::new().expect("tempdir");
+    fs::write(
+        dir.path().join("fob.toml"),
+        r#"
+[profiles.production.bundle]
+minify = true
+
+[profiles.staging]
+extends = "prodution"
+"#,
+    )
+    .expect("write config");
+
+    let err = ConfigDiscovery::new(dir.path())
+        .load_with_profile("staging")
+        .expect_err("missing parent should fail");
+
+    match err {
+        ConfigError::UnknownProfile { name, hint } => {
+            assert_eq!(name, "prodution");
+            assert_eq!(hint.as_deref(), Some("Did you mean `production`?"));
+        }
+        other => panic!("unexpected error: {other:?}"),
+    }
+}
+
+#[test]
+fn profile_unknown_key_suggests_field() {
+    let _guard = test_lock().lock().expect("lock");
+    let dir = TempDir::new().expect("tempdir");
+    fs::write(
+        dir.path().join("fob.toml"),
+        r#"
+[profiles.production.bundle]
+minfy = true
+"#,
+    )
+    .expect("write config");
+
+    let err = ConfigDiscovery::new(dir.path())
+        .load_with_profile("production")
+        .expect_err("unknown key should fail");
+
+    match err {
+        ConfigError::UnknownProfileKey { profile, key, hint } => {
+            assert_eq!(profile, "production");
+            assert_eq!(key, "bundle.minfy");
+            assert_eq!(hint.as_deref(), Some("Did you mean `bundle.minify`?"));
+        }
+        other => panic!("unexpected error: {other:?}"),
+    }
+}
+
+#[test]
+fn profile_unknown_section_suggests_section() {
+    let _guard = test_lock().lock().expect("lock");
+    let dir = TempDir::new().expect("tempdir");
+    fs::write(
+        dir.path().join("fob.toml"),
+        r#"
+[profiles.production.bundel]
+minify = true
+"#,
+    )
+    .expect("write config");
+
+    let err = ConfigDiscovery::new(dir.path())
+        .load_with_profile("production")
+        .expect_err("unknown section should fail");
+
+    match err {
+        ConfigError::UnknownProfileKey { key, hint, .. } => {
+            assert_eq!(key, "bundel");
+            assert_eq!(hint.as_deref(), Some("Did you mean `bundle`?"));
+        }
+        other => panic!("unexpected error: {other:?}"),
+    }
+}
+
+#[test]
+fn profile_selected_from_env() {
+    let _guard = test_lock().lock().expect("lock");
+    let dir = TempDir::new().expect("tempdir");
+    fs::write(
+        dir.path().join("fob.toml"),
+        r#"
+[bundle]
+minify = false
+
+[profiles.production.bundle]
+minify = true
+
+[profiles.development.bundle]
+minify = false
+"#,
+    )
+    .expect("write config");
+
+    unsafe { std::env::set_var(PROFILE_ENV, "production") };
+    let from_env = ConfigDiscovery::new(dir.path()).load_selected(None);
+    let explicit = ConfigDiscovery::new(dir.path()).load_selected(Some("development"));
+    unsafe { std::env::remove_var(PROFILE_ENV) };
+
+    assert!(from_env.expect("load from env").bundle.minify);
+    assert!(!explicit.expect("load explicit").bundle.minify);
+}
+
+#[test]
+fn profile_from_env_must_be_defined() {
+    let _guard = test_lock().lock().expect("lock");
+    let dir = TempDir::new().expect("tempdir");
+    fs::write(
+        dir.path().join("fob.toml"),
+        r#"
+[bundle]
+minify = false
+"#,
+    )
+    .expect("write config");
+
+    unsafe { std::env::set_var(PROFILE_ENV, "production") };
+    let selected = ConfigDiscovery::new(dir.path()).load_selected(None);
+    let plain = ConfigDiscovery::new(dir.path()).load();
+    unsafe { std::env::remove_var(PROFILE_ENV) };
+
+    assert!(matches!(
+        selected.expect_err("undefined profile should fail"),
+        ConfigError::UnknownProfile { name, .. } if name == "production"
+    ));
+    // Only the `*_selected` loaders read `FOB_PROFILE`
+    assert!(!plain.expect("load ignores env").bundle.minify);
+}
@@ -0,0 +1,51 @@
+//! Tests for the generated config JSON Schema.
+
+use serde_json::Value;
+
+fn resolve<'a>(schema: &'a Value, node: &'a Value) -> &'a Value {
+    match node.get("$ref").and_then(Value::as_str) {
+        Some(reference) => {
+            let name = reference.trim_start_matches("#/$defs/");
+            &schema["$defs"][name]
+        }
+        None => node,
+    }
+}
+
+#[test]
+fn schema_describes_top_level_sections() {
+    let schema = fob_config::schema();
+
+    assert_eq!(schema["title"], "Fob configuration");
+    for section in ["bundle", "dev", "profiles", "settings", "plugins"] {
+        assert!(
+            schema["properties"][section].is_object(),
+            "missing section {section}"
+        );
+    }
+}
+
+#[test]
+fn schema_includes_bundle_fields() {
+    let schema = fob_config::schema();
+    let bundle = resolve(&schema, &schema["properties"]["bundle"]);
+
+    let properties = bundle["properties"].as_object().expect("bundle properties");
+    assert!(properties.contains_key("entries"));
+    assert!(properties.contains_key("minify"));
+    assert_eq!(properties["minify"]["type"], "boolean");
+}
+
+#[test]
+fn schema_describes_profiles() {
+    let schema = fob_config::schema();
+    let profiles = &schema["properties"]["profiles"];
+    let profile = resolve(&schema, &profiles["additionalProperties"]);
+
+    let properties = profile["properties"]
+        .as_object()
+        .expect("profile properties");
+    let mut keys: Vec<_> = properties.keys().map(String::as_str).collect();
+    keys.sort_unstable();
+    assert_eq!(keys, ["bundle", "dev", "extends", "settings"]);
+}
@@ -1,6 +1,8 @@
 //! Tests for configuration validation.
 
-use fob_config::{ConfigError, ConfigValidator, FsValidator, JoyConfig, PluginOptions};
+use fob_config::{
+    ConfigError, ConfigValidator, FsValidator, JoyConfig, PluginOptions, SchemaValidator,
+};
 use std::fs;
 use std::path::PathBuf;
 use tempfile::TempDir;
@@ -223,3 +225,45 @@ fn validate_checks_multiple_plugins() {
         _ => panic!("expected PluginNotFound error"),
     }
 }
+
+#[test]
+fn builtin_plugins_are_declared_by_name() {
+    let dir = TempDir::new().expect("tempdir");
+    fs::create_dir(dir.path().join("src")).expect("create src");
+    fs::write(dir.path().join("src/index.ts"), "").expect("write entry");
+
+    let cfg = JoyConfig::from_value(serde_json::json!({
+        "bundle": { "entries": ["src/index.ts"] },
+        "plugins": [
+            { "name": "fob-mdx", "options": { "gfm": false } },
+            { "name": "fob-css" }
+        ]
+    }))
+    .expect("parse config");
+
+    let mdx = &cfg.bundle.plugins[0];
+    assert_eq!(mdx.builtin(), Some("fob-mdx"));
+    assert_eq!(mdx.config["gfm"], false);
+
+    SchemaValidator.validate(&cfg.bundle).expect("schema valid");
+    FsValidator::new(dir.path())
+        .validate(&cfg.bundle)
+        .expect("built-in plugins have no path to check");
+}
+
+#[test]
+fn unknown_builtin_plugin_suggests_name() {
+    let cfg = JoyConfig::from_value(serde_json::json!({
+        "bundle": { "entries": ["src/index.ts"] },
+        "plugins": [{ "name": "fob-mxd" }]
+    }))
+    .expect("parse config");
+
+    match SchemaValidator.validate(&cfg.bundle).unwrap_err() {
+        ConfigError::UnknownPlugin { name, hint } => {
+            assert_eq!(name, "fob-mxd");
+            assert_eq!(hint.as_deref(), Some("Did you mean `fob-mdx`?"));
+        }
+        other => panic!("unexpected error: {other:?}"),
+    }
+}
@@ -0,0 +1,32 @@
+//! Generates the C header from the exported functions.
+//!
+//! The header is written to `OUT_DIR`. The checked-in `include/fob.h` is
+//! only updated with the `regenerate-header` feature, so ordinary builds
+//! never touch the source tree.
+
+use std::path::PathBuf;
+
+fn main() {
+    println!("cargo:rerun-if-changed=src/lib.rs");
+    println!("cargo:rerun-if-changed=cbindgen.toml");
+
+    let crate_dir = PathBuf::from(std::env::var("CARGO_MANIFEST_DIR").unwrap());
+    let out_dir = PathBuf::from(std::env::var("OUT_DIR").unwrap());
+    let config = cbindgen::Config::from_file(crate_dir.join("cbindgen.toml"))
+        .expect("invalid cbindgen.toml");
+
+    // A header that fails to generate shouldn't break the library build;
+    // the checked-in header stays usable.
+    let bindings = match cbindgen::generate_with_config(&crate_dir, config) {
+        Ok(bindings) => bindings,
+        Err(e) => {
+            println!("cargo:warning=Failed to generate fob.h: {}", e);
+            return;
+        }
+    };
+    bindings.write_to_file(out_dir.join("fob.h"));
+
+    if std::env::var_os("CARGO_FEATURE_REGENERATE_HEADER").is_some() {
+        bindings.write_to_file(crate_dir.join("include/fob.h"));
+    }
+}
@@ -0,0 +1,87 @@
+#ifndef FOB_H
+#define FOB_H
+
+/* Generated by cbindgen from crates/fob-ffi/src/lib.rs. Do not edit. */
+
+/* Generated with cbindgen:0.29.0 */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+// Outcome of a call
+typedef enum FobStatus {
+  // `json` holds the result
+  FOB_STATUS_OK = 0,
+  // The operation failed; `json` holds a diagnostic
+  FOB_STATUS_ERROR = 1,
+  // An argument was null, not UTF-8 or not valid JSON; `json` holds a diagnostic
+  FOB_STATUS_INVALID_ARGUMENT = 2,
+  // fob panicked; `json` holds a diagnostic with the panic message
+  FOB_STATUS_PANIC = 3,
+} FobStatus;
+
+// Result of a call, owned by the caller until passed to [`fob_result_free`]
+typedef struct FobResult {
+  FobStatus status;
+  // NUL-terminated UTF-8 JSON, never null
+  char *json;
+} FobResult;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+// Bundle entries and write the output to disk.
+//
+// `config_json` is a [`BundleConfig`] object. The result holds the output
+// directory, bundle manifest and build statistics.
+//
+// # Safety
+//
+// `config_json` must be null or a NUL-terminated string.
+FobResult fob_bundle(const char *config_json);
+
+// Compile MDX source to JSX.
+//
+// `options_json` is an [`MdxOptions`] object, or null for the defaults. The
+// result holds the code, frontmatter, images and document metadata.
+//
+// # Safety
+//
+// `source` and `options_json` must be null or NUL-terminated strings.
+FobResult fob_compile_mdx(const char *source, const char *options_json);
+
+// Analyze the module graph of the given entries without bundling.
+//
+// `entries_json` is a JSON array of entry paths; `options_json` is an
+// [`AnalyzeOptions`] object, or null for the defaults. The result holds
+// unused exports, circular dependencies and graph statistics.
+//
+// # Safety
+//
+// `entries_json` and `options_json` must be null or NUL-terminated strings.
+FobResult fob_analyze(const char *entries_json, const char *options_json);
+
+// Report the features, built-in plugins and limits of this build.
+//
+// The result holds the same versioned JSON report as
+// `fob --capabilities --json`.
+FobResult fob_capabilities(void);
+
+// Release a result returned by any fob function.
+//
+// # Safety
+//
+// `result` must come from a fob function and must not be used afterwards.
+void fob_result_free(FobResult result);
+
+// Version of fob, as a static NUL-terminated string that must not be freed.
+const char *fob_version(void);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* FOB_H */
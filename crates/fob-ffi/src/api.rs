@@ -0,0 +1,264 @@
+//! Operations behind the C functions, on already parsed arguments.
+
+use crate::FobStatus;
+use fob_bundler::diagnostics::Diagnostic;
+use fob_bundler::{Analyzer, BuildOptions, OutputFormat, Platform};
+use fob_mdx::{JsxTarget, MdxCompileOptions, MdxError};
+use serde::Deserialize;
+use serde_json::{Value, json};
+use std::collections::BTreeSet;
+use std::path::PathBuf;
+use std::sync::OnceLock;
+use tokio::runtime::Runtime;
+
+/// Input of `fob_bundle`
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct BundleConfig {
+    /// Entry points to bundle
+    pub entries: Vec<String>,
+    /// Output directory, relative to `cwd` (default: "dist")
+    pub output_dir: Option<String>,
+    /// Output format: "esm" | "cjs" | "iife" (default: "esm")
+    pub format: Option<String>,
+    /// Write external source maps (default: false)
+    #[serde(default)]
+    pub sourcemap: bool,
+    /// Minify the output (default: false)
+    #[serde(default)]
+    pub minify: bool,
+    /// Target platform: "browser" | "node" (default: "browser")
+    pub platform: Option<String>,
+    /// Packages to leave as imports instead of bundling
+    #[serde(default)]
+    pub external: Vec<String>,
+    /// Working directory (default: the process working directory)
+    pub cwd: Option<String>,
+}
+
+/// Options of `fob_compile_mdx`
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct MdxOptions {
+    /// Path of the document, used in error messages
+    pub filepath: Option<String>,
+    /// GitHub Flavored Markdown (default: true)
+    pub gfm: Option<bool>,
+    /// Footnotes (default: true)
+    pub footnotes: Option<bool>,
+    /// Math (default: true)
+    pub math: Option<bool>,
+    /// JSX runtime module
+    pub jsx_runtime: Option<String>,
+    /// JSX target: "react" | "preact" | "vue" | "solid" | "hono"
+    pub jsx_target: Option<String>,
+}
+
+/// Options of `fob_analyze`
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct AnalyzeOptions {
+    /// Working directory (default: the process working directory)
+    pub cwd: Option<String>,
+}
+
+/// Failed call: the status to return and the diagnostic to report
+pub(crate) struct Failure {
+    pub status: FobStatus,
+    pub diagnostic: Diagnostic,
+}
+
+impl Failure {
+    pub fn invalid(message: impl Into<String>) -> Self {
+        Self {
+            status: FobStatus::InvalidArgument,
+            diagnostic: Diagnostic::error("InvalidArgument", message),
+        }
+    }
+}
+
+impl From<fob_bundler::Error> for Failure {
+    fn from(error: fob_bundler::Error) -> Self {
+        Self {
+            status: FobStatus::Error,
+            diagnostic: error.to_diagnostic(),
+        }
+    }
+}
+
+impl From<MdxError> for Failure {
+    fn from(error: MdxError) -> Self {
+        Self {
+            status: FobStatus::Error,
+            diagnostic: Diagnostic::mdx_syntax(
+                error.message,
+                error.file,
+                error.line,
+                error.column,
+                error.suggestion,
+            ),
+        }
+    }
+}
+
+/// Runtime shared by all calls; C callers block on it
+fn runtime() -> &'static Runtime {
+    static RUNTIME: OnceLock<Runtime> = OnceLock::new();
+    RUNTIME.get_or_init(|| {
+        tokio::runtime::Builder::new_multi_thread()
+            .enable_all()
+            .build()
+            .expect("failed to start the fob runtime")
+    })
+}
+
+fn resolve_cwd(cwd: Option<&str>) -> Result<PathBuf, Failure> {
+    match cwd {
+        Some(cwd) => Ok(PathBuf::from(cwd)),
+        None => std::env::current_dir().map_err(|e| Failure::from(fob_bundler::Error::Io(e))),
+    }
+}
+
+pub(crate) fn bundle(config: BundleConfig) -> Result<Value, Failure> {
+    let Some(first_entry) = config.entries.first() else {
+        return Err(Failure::invalid("entries must not be empty"));
+    };
+    let format = match config.format.as_deref().map(str::to_lowercase).as_deref() {
+        None | Some("esm") => OutputFormat::Esm,
+        Some("cjs") => OutputFormat::Cjs,
+        Some("iife") => OutputFormat::Iife,
+        Some(other) => {
+            return Err(Failure::invalid(format!(
+                "Invalid format '{}'. Expected: esm, cjs, iife",
+                other
+            )));
+        }
+    };
+    let platform = match config.platform.as_deref().map(str::to_lowercase).as_deref() {
+        None | Some("browser") => Platform::Browser,
+        Some("node") => Platform::Node,
+        Some(other) => {
+            return Err(Failure::invalid(format!(
+                "Invalid platform '{}'. Expected: browser, node",
+                other
+            )));
+        }
+    };
+    let cwd = resolve_cwd(config.cwd.as_deref())?;
+
+    let mut options = if config.entries.len() == 1 {
+        BuildOptions::new(first_entry)
+    } else {
+        BuildOptions::new_multiple(&config.entries)
+    };
+    options = options
+        .cwd(&cwd)
+        .format(format)
+        .platform(platform)
+        .sourcemap(config.sourcemap);
+    if !config.external.is_empty() {
+        options = options.externalize(config.external);
+    }
+    if config.minify {
+        options = options.minify_level("identifiers");
+    }
+
+    let result = runtime().block_on(options.build())?;
+    let output_dir = cwd.join(config.output_dir.as_deref().unwrap_or("dist"));
+    result.write_to_force(&output_dir)?;
+
+    Ok(json!({
+        "outputDir": output_dir,
+        "manifest": result.manifest(),
+        "stats": result.build_stats(),
+    }))
+}
+
+pub(crate) fn compile_mdx(source: &str, options: MdxOptions) -> Result<Value, Failure> {
+    let mut compile_options = MdxCompileOptions::new();
+    compile_options.filepath = options.filepath;
+    if let Some(gfm) = options.gfm {
+        compile_options.gfm = gfm;
+    }
+    if let Some(footnotes) = options.footnotes {
+        compile_options.footnotes = footnotes;
+    }
+    if let Some(math) = options.math {
+        compile_options.math = math;
+    }
+    if let Some(jsx_runtime) = options.jsx_runtime {
+        compile_options.jsx_runtime = jsx_runtime;
+    }
+    if let Some(jsx_target) = options.jsx_target {
+        compile_options.jsx_target = jsx_target.parse::<JsxTarget>().map_err(Failure::invalid)?;
+    }
+
+    let result = fob_mdx::compile(source, compile_options).map_err(|e| Failure::from(*e))?;
+
+    Ok(json!({
+        "code": result.code,
+        "frontmatter": result.frontmatter.as_ref().map(|fm| &fm.data),
+        "matter": result.matter(),
+        "images": result.images,
+        "imports": result.imports,
+        "namedExports": result.named_exports,
+        "excerpt": result.excerpt,
+        "wordCount": result.word_count,
+        "readingTimeMinutes": result.reading_time_minutes,
+        "links": result.links,
+    }))
+}
+
+pub(crate) fn analyze(entries: Vec<String>, options: AnalyzeOptions) -> Result<Value, Failure> {
+    if entries.is_empty() {
+        return Err(Failure::invalid("entries must not be empty"));
+    }
+    let cwd = resolve_cwd(options.cwd.as_deref())?;
+
+    let analysis = runtime()
+        .block_on(Analyzer::new().entries(entries).cwd(cwd).analyze())
+        .map_err(fob_bundler::Error::Foundation)?;
+
+    let unused_exports = analysis
+        .unused_exports()
+        .map_err(fob_bundler::Error::Foundation)?
+        .into_iter()
+        .map(|unused| {
+            json!({
+                "file": unused.module_id.path_string(),
+                "name": unused.export.name,
+            })
+        })
+        .collect::<Vec<_>>();
+    let circular_dependencies = analysis
+        .find_circular_dependencies()
+        .map_err(fob_bundler::Error::Foundation)?
+        .into_iter()
+        .map(|chain| {
+            chain
+                .path
+                .iter()
+                .map(|id| id.path_string().into_owned())
+                .collect::<Vec<_>>()
+        })
+        .collect::<BTreeSet<_>>();
+    let stats = &analysis.stats;
+
+    Ok(json!({
+        "unusedExports": unused_exports,
+        "circularDependencies": circular_dependencies,
+        "stats": {
+            "moduleCount": stats.module_count,
+            "entryPointCount": stats.entry_point_count,
+            "externalDependencyCount": stats.external_dependency_count,
+            "sideEffectModuleCount": stats.side_effect_module_count,
+            "unusedExportCount": stats.unused_export_count,
+            "unreachableModuleCount": stats.unreachable_module_count,
+        },
+        "warnings": analysis.warnings,
+    }))
+}
+
+pub(crate) fn capabilities() -> Result<Value, Failure> {
+    Ok(json!(fob_bundler::capabilities()))
+}
@@ -0,0 +1,212 @@
+//! # fob-ffi
+//!
+//! Stable C ABI for fob, for languages without dedicated bindings (Go, .NET,
+//! Swift, ...).
+//!
+//! Every function takes NUL-terminated UTF-8 strings, JSON for structured
+//! input, and returns a [`FobResult`]. On success its `json` holds the result;
+//! otherwise it holds a [`Diagnostic`] describing the failure. Results are
+//! owned by the caller and must be released with [`fob_result_free`].
+//!
+//! ```c
+//! #include "fob.h"
+//!
+//! FobResult result = fob_bundle("{\"entries\": [\"src/index.js\"]}");
+//! if (result.status != FOB_STATUS_OK) {
+//!     fprintf(stderr, "%s\n", result.json);
+//! }
+//! fob_result_free(result);
+//! ```
+//!
+//! The header `include/fob.h` is generated from this file by cbindgen; build
+//! with the `regenerate-header` feature to update it.
+
+mod api;
+
+pub use api::{AnalyzeOptions, BundleConfig, MdxOptions};
+
+use api::Failure;
+use fob_bundler::diagnostics::Diagnostic;
+use serde::de::DeserializeOwned;
+use std::ffi::{CStr, CString, c_char};
+use std::panic::{AssertUnwindSafe, catch_unwind};
+
+/// Outcome of a call
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FobStatus {
+    /// `json` holds the result
+    Ok = 0,
+    /// The operation failed; `json` holds a diagnostic
+    Error = 1,
+    /// An argument was null, not UTF-8 or not valid JSON; `json` holds a diagnostic
+    InvalidArgument = 2,
+    /// fob panicked; `json` holds a diagnostic with the panic message
+    Panic = 3,
+}
+
+/// Result of a call, owned by the caller until passed to [`fob_result_free`]
+#[repr(C)]
+#[derive(Debug)]
+pub struct FobResult {
+    pub status: FobStatus,
+    /// NUL-terminated UTF-8 JSON, never null
+    pub json: *mut c_char,
+}
+
+/// Bundle entries and write the output to disk.
+///
+/// `config_json` is a [`BundleConfig`] object. The result holds the output
+/// directory, bundle manifest and build statistics.
+///
+/// # Safety
+///
+/// `config_json` must be null or a NUL-terminated string.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn fob_bundle(config_json: *const c_char) -> FobResult {
+    call(|| {
+        let config = unsafe { json_arg(config_json, "config_json") }?;
+        api::bundle(config)
+    })
+}
+
+/// Compile MDX source to JSX.
+///
+/// `options_json` is an [`MdxOptions`] object, or null for the defaults. The
+/// result holds the code, frontmatter, images and document metadata.
+///
+/// # Safety
+///
+/// `source` and `options_json` must be null or NUL-terminated strings.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn fob_compile_mdx(
+    source: *const c_char,
+    options_json: *const c_char,
+) -> FobResult {
+    call(|| {
+        let source = unsafe { str_arg(source, "source") }?;
+        let options = unsafe { optional_json_arg(options_json, "options_json") }?;
+        api::compile_mdx(source, options)
+    })
+}
+
+/// Analyze the module graph of the given entries without bundling.
+///
+/// `entries_json` is a JSON array of entry paths; `options_json` is an
+/// [`AnalyzeOptions`] object, or null for the defaults. The result holds
+/// unused exports, circular dependencies and graph statistics.
+///
+/// # Safety
+///
+/// `entries_json` and `options_json` must be null or NUL-terminated strings.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn fob_analyze(
+    entries_json: *const c_char,
+    options_json: *const c_char,
+) -> FobResult {
+    call(|| {
+        let entries = unsafe { json_arg(entries_json, "entries_json") }?;
+        let options = unsafe { optional_json_arg(options_json, "options_json") }?;
+        api::analyze(entries, options)
+    })
+}
+
+/// Report the features, built-in plugins and limits of this build.
+///
+/// The result holds the same versioned JSON report as
+/// `fob --capabilities --json`.
+#[unsafe(no_mangle)]
+pub extern "C" fn fob_capabilities() -> FobResult {
+    call(api::capabilities)
+}
+
+/// Release a result returned by any fob function.
+///
+/// # Safety
+///
+/// `result` must come from a fob function and must not be used afterwards.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn fob_result_free(result: FobResult) {
+    if !result.json.is_null() {
+        drop(unsafe { CString::from_raw(result.json) });
+    }
+}
+
+/// Version of fob, as a static NUL-terminated string that must not be freed.
+#[unsafe(no_mangle)]
+pub extern "C" fn fob_version() -> *const c_char {
+    concat!(env!("CARGO_PKG_VERSION"), "\0").as_ptr().cast()
+}
+
+/// Run an operation, turning its outcome (including panics, which must not
+/// unwind into the caller) into a [`FobResult`].
+fn call(operation: impl FnOnce() -> Result<serde_json::Value, Failure>) -> FobResult {
+    let (status, json) = match catch_unwind(AssertUnwindSafe(operation)) {
+        Ok(Ok(value)) => (FobStatus::Ok, value.to_string()),
+        Ok(Err(failure)) => (failure.status, diagnostic_json(&failure.diagnostic)),
+        Err(panic) => {
+            let message = panic
+                .downcast_ref::<&str>()
+                .map(|s| s.to_string())
+                .or_else(|| panic.downcast_ref::<String>().cloned())
+                .unwrap_or_else(|| "fob panicked".to_string());
+            (
+                FobStatus::Panic,
+                diagnostic_json(&Diagnostic::error("Panic", message)),
+            )
+        }
+    };
+
+    // serde_json escapes NUL, so the conversion can't fail
+    let json = CString::new(json).unwrap_or_default();
+    FobResult {
+        status,
+        json: json.into_raw(),
+    }
+}
+
+fn diagnostic_json(diagnostic: &Diagnostic) -> String {
+    serde_json::to_string(diagnostic).unwrap_or_else(|_| {
+        r#"{"kind":"Internal","severity":"error","message":"Failed to serialize error"}"#
+            .to_string()
+    })
+}
+
+/// Read a required string argument.
+///
+/// # Safety
+///
+/// `ptr` must be null or a NUL-terminated string valid for `'a`.
+unsafe fn str_arg<'a>(ptr: *const c_char, name: &str) -> Result<&'a str, Failure> {
+    if ptr.is_null() {
+        return Err(Failure::invalid(format!("{} must not be null", name)));
+    }
+    unsafe { CStr::from_ptr(ptr) }
+        .to_str()
+        .map_err(|_| Failure::invalid(format!("{} is not valid UTF-8", name)))
+}
+
+/// Parse a required JSON argument.
+///
+/// # Safety
+///
+/// `ptr` must be null or a NUL-terminated string.
+unsafe fn json_arg<T: DeserializeOwned>(ptr: *const c_char, name: &str) -> Result<T, Failure> {
+    let json = unsafe { str_arg(ptr, name) }?;
+    serde_json::from_str(json).map_err(|e| Failure::invalid(format!("Invalid {}: {}", name, e)))
+}
+
+/// Parse an optional JSON argument; null means the default.
+///
+/// # Safety
+///
+/// `ptr` must be null or a NUL-terminated string.
+unsafe fn optional_json_arg<T: DeserializeOwned + Default>(
+    ptr: *const c_char,
+    name: &str,
+) -> Result<T, Failure> {
+    if ptr.is_null() {
+        return Ok(T::default());
+    }
+    unsafe { json_arg(ptr, name) }
+}
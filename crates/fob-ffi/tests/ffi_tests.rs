@@ -0,0 +1,133 @@
+//! Tests for the C functions, called the way a C caller would.
+
+use fob_ffi::{
+    FobResult, FobStatus, fob_analyze, fob_bundle, fob_capabilities, fob_compile_mdx,
+    fob_result_free, fob_version,
+};
+use std::ffi::{CStr, CString};
+use std::ptr;
+
+/// Read and free a result.
+fn take(result: FobResult) -> (FobStatus, serde_json::Value) {
+    let json = unsafe { CStr::from_ptr(result.json) }
+        .to_str()
+        .unwrap()
+        .to_string();
+    let status = result.status;
+    unsafe { fob_result_free(result) };
+    (status, serde_json::from_str(&json).unwrap())
+}
+
+fn c(s: &str) -> CString {
+    CString::new(s).unwrap()
+}
+
+#[test]
+fn test_compile_mdx() {
+    let source = c("---\ntitle: Hello\n---\n\n# Intro\n\n![Cat](./cat.png)\n");
+
+    let (status, json) = take(unsafe { fob_compile_mdx(source.as_ptr(), ptr::null()) });
+
+    assert_eq!(status, FobStatus::Ok);
+    assert!(json["code"].as_str().unwrap().contains("Intro"));
+    assert_eq!(json["frontmatter"]["title"], "Hello");
+    assert_eq!(json["images"][0]["url"], "./cat.png");
+}
+
+#[test]
+fn test_compile_mdx_syntax_error_is_a_diagnostic() {
+    let source = c("<div>{unclosed");
+    let options = c(r#"{"filepath": "docs/broken.mdx"}"#);
+
+    let (status, json) = take(unsafe { fob_compile_mdx(source.as_ptr(), options.as_ptr()) });
+
+    assert_eq!(status, FobStatus::Error);
+    assert_eq!(json["kind"], "MdxSyntax");
+    assert_eq!(json["severity"], "error");
+}
+
+#[test]
+fn test_invalid_arguments() {
+    let (status, json) = take(unsafe { fob_compile_mdx(ptr::null(), ptr::null()) });
+    assert_eq!(status, FobStatus::InvalidArgument);
+    assert!(json["message"].as_str().unwrap().contains("source"));
+
+    let config = c(r#"{"entries": ["a.js"], "unknown": true}"#);
+    let (status, json) = take(unsafe { fob_bundle(config.as_ptr()) });
+    assert_eq!(status, FobStatus::InvalidArgument);
+    assert!(json["message"].as_str().unwrap().contains("unknown"));
+
+    let entries = c("[]");
+    let (status, _) = take(unsafe { fob_analyze(entries.as_ptr(), ptr::null()) });
+    assert_eq!(status, FobStatus::InvalidArgument);
+}
+
+#[test]
+fn test_bundle_writes_output() {
+    let temp = tempfile::tempdir().unwrap();
+    std::fs::write(temp.path().join("index.js"), "export const answer = 42;\n").unwrap();
+    let config = serde_json::json!({
+        "entries": ["index.js"],
+        "platform": "Node",
+        "cwd": temp.path(),
+    });
+    let config = c(&config.to_string());
+
+    let (status, json) = take(unsafe { fob_bundle(config.as_ptr()) });
+
+    assert_eq!(status, FobStatus::Ok, "{}", json);
+    assert!(
+        temp.path()
+            .join("dist")
+            .read_dir()
+            .unwrap()
+            .next()
+            .is_some()
+    );
+    assert!(json["stats"].is_object());
+}
+
+#[test]
+fn test_analyze_reports_unused_exports() {
+    let temp = tempfile::tempdir().unwrap();
+    std::fs::write(
+        temp.path().join("index.js"),
+        "import { used } from './lib.js';\nconsole.log(used);\n",
+    )
+    .unwrap();
+    std::fs::write(
+        temp.path().join("lib.js"),
+        "export const used = 1;\nexport const unused = 2;\n",
+    )
+    .unwrap();
+    let entries = c(r#"["index.js"]"#);
+    let options = c(&serde_json::json!({ "cwd": temp.path() }).to_string());
+
+    let (status, json) = take(unsafe { fob_analyze(entries.as_ptr(), options.as_ptr()) });
+
+    assert_eq!(status, FobStatus::Ok, "{}", json);
+    let unused = json["unusedExports"].as_array().unwrap();
+    assert!(unused.iter().any(|u| u["name"] == "unused"), "{}", json);
+    assert!(json["stats"]["moduleCount"].as_u64().unwrap() >= 2);
+}
+
+#[test]
+fn test_capabilities() {
+    let (status, json) = take(fob_capabilities());
+
+    assert_eq!(status, FobStatus::Ok);
+    assert_eq!(
+        json,
+        serde_json::to_value(fob_bundler::capabilities()).unwrap()
+    );
+    assert_eq!(
+        json["schemaVersion"],
+        fob_bundler::capabilities::CAPABILITIES_SCHEMA_VERSION
+    );
+}
+
+#[test]
+fn test_version() {
+    let version = unsafe { CStr::from_ptr(fob_version()) };
+    assert_eq!(version.to_str().unwrap(), env!("CARGO_PKG_VERSION"));
+}
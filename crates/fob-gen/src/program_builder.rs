@@ -386,6 +386,41 @@ impl<'a> ProgramBuilder<'a> {
         )
     }
 
+    /// Create aliased named imports: `import { imported as local } from 'source';`
+    ///
+    /// Each pair is `(imported, local)`; `imported` may be `default`.
+    pub fn import_named_as(
+        &self,
+        names: Vec<(impl Into<Atom<'a>>, impl Into<Atom<'a>>)>,
+        source: impl Into<Atom<'a>>,
+    ) -> ModuleDeclaration<'a> {
+        let specifiers: Vec<_> = names
+            .into_iter()
+            .map(|(imported, local)| {
+                let imported_name = self.ast.identifier_name(SPAN, imported);
+                let local_binding = self.ast.binding_identifier(SPAN, local);
+                let specifier = self.ast.import_specifier(
+                    SPAN,
+                    ModuleExportName::IdentifierName(imported_name),
+                    local_binding,
+                    ImportOrExportKind::Value,
+                );
+                ImportDeclarationSpecifier::ImportSpecifier(self.ast.alloc(specifier))
+            })
+            .collect();
+
+        let specifiers_vec = self.ast.vec_from_iter(specifiers);
+        let source_literal = self.ast.string_literal(SPAN, source, None);
+        self.ast.module_declaration_import_declaration(
+            SPAN,
+            Some(specifiers_vec),
+            source_literal,
+            None, // phase
+            NONE, // with_clause
+            ImportOrExportKind::Value,
+        )
+    }
+
     /// Create default export: `export default expr;`
     pub fn export_default(&self, expr: Expression<'a>) -> ModuleDeclaration<'a> {
         // ExportDefaultDeclarationKind inherits Expression variants, so we can convert
@@ -157,6 +157,21 @@ fn test_named_imports() {
     assert!(code.contains(r#""react""#));
 }
 
+#[test]
+fn test_aliased_named_imports() {
+    let allocator = Allocator::default();
+    let mut js = ProgramBuilder::new(&allocator);
+
+    // import { default as Button, size } from './Button'
+    let import_decl = js.import_named_as(vec![("default", "Button"), ("size", "size")], "./Button");
+    js.push(Statement::from(import_decl));
+    let code = js.generate(&Default::default()).unwrap();
+
+    assert!(code.contains("default as Button"));
+    assert!(!code.contains("size as size"));
+    assert!(code.contains(r#""./Button""#));
+}
+
 #[test]
 fn test_exports() {
     let allocator = Allocator::default();
@@ -10,6 +10,7 @@
 use std::path::PathBuf;
 use std::sync::Arc;
 
+use path_clean::PathClean;
 use rustc_hash::FxHashMap;
 
 use super::{AnalyzeOptions, result::AnalysisResult, stats::compute_stats};
@@ -19,6 +20,7 @@ use crate::{Error, Result};
 
 use super::config::AnalyzerConfig;
 use super::walker::GraphWalker;
+use super::workspace::Workspace;
 
 /// Typestate marker for an unconfigured analyzer (no entry points yet).
 #[derive(Debug, Clone, Copy)]
@@ -149,6 +151,15 @@ impl<State> Analyzer<State> {
         self
     }
 
+    /// Set how many modules are read and parsed concurrently.
+    ///
+    /// Default: the available hardware parallelism. Values below 1 are
+    /// treated as 1, which walks the graph one module at a time.
+    pub fn parallelism(mut self, parallelism: usize) -> Self {
+        self.config.parallelism = parallelism.max(1);
+        self
+    }
+
     /// Set the runtime for filesystem operations.
     ///
     /// If not set, will attempt to use a default runtime.
@@ -162,6 +173,16 @@ impl<State> Analyzer<State> {
         self.config.cwd = Some(cwd.into());
         self
     }
+
+    /// Analyze as part of the pnpm/yarn/npm workspace rooted at `root`.
+    ///
+    /// Imports of workspace packages resolve to their source files instead
+    /// of being external, so internal packages are analyzed as first-party
+    /// modules. Module paths become relative to the workspace root.
+    pub fn workspace(mut self, root: impl Into<PathBuf>) -> Self {
+        self.config.workspace_root = Some(root.into());
+        self
+    }
 }
 
 impl Analyzer<Configured> {
@@ -205,6 +226,25 @@ impl Analyzer<Configured> {
 
         let mut config = self.config;
         config.cwd = cwd;
+        if let Some(root) = config.workspace_root.clone() {
+            let base = config.cwd.clone().unwrap_or_default();
+            let root = base.join(root).clean();
+            let workspace = Workspace::discover(&root, runtime.as_ref()).await?;
+
+            // Paths are validated against the cwd, so sibling packages are
+            // only reachable with the workspace root as cwd
+            for entry in &mut config.entries {
+                *entry = base.join(&*entry).clean();
+            }
+            for target in config.path_aliases.values_mut() {
+                if !target.starts_with('/') {
+                    *target = base.join(&*target).clean().to_string_lossy().into_owned();
+                }
+            }
+            config.cwd = Some(root);
+            config.workspace = Some(workspace);
+        }
+        let workspace = config.workspace.clone();
 
         // Create walker and traverse graph
         let walker = GraphWalker::new(config);
@@ -254,6 +294,7 @@ impl Analyzer<Configured> {
             errors: Vec::new(),
             stats,
             symbol_stats,
+            workspace,
         })
     }
 
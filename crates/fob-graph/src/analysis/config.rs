@@ -8,6 +8,8 @@ use rustc_hash::FxHashMap;
 
 use crate::runtime::Runtime;
 
+use super::workspace::Workspace;
+
 /// Default maximum depth for graph traversal (DoS protection).
 ///
 /// This prevents infinite loops in circular dependencies and provides
@@ -20,6 +22,16 @@ pub const DEFAULT_MAX_DEPTH: usize = 1000;
 /// memory exhaustion or excessive processing time.
 pub const DEFAULT_MAX_MODULES: usize = 100_000;
 
+/// Default number of modules parsed concurrently during traversal.
+///
+/// Uses the available hardware parallelism, falling back to a single
+/// module at a time where it can't be determined (e.g. on WASM).
+pub fn default_parallelism() -> usize {
+    std::thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(1)
+}
+
 /// Maximum depth for graph traversal (DoS protection).
 ///
 /// This newtype ensures type safety when working with depth limits.
@@ -138,11 +150,22 @@ pub struct AnalyzerConfig {
     /// Default: `DEFAULT_MAX_MODULES` (100,000)
     pub max_modules: Option<usize>,
 
+    /// Maximum number of modules read and parsed concurrently.
+    ///
+    /// Default: [`default_parallelism`]
+    pub parallelism: usize,
+
     /// Runtime for filesystem operations.
     pub runtime: Option<Arc<dyn Runtime>>,
 
     /// Current working directory.
     pub cwd: Option<PathBuf>,
+
+    /// Monorepo root whose workspace packages resolve to source.
+    pub workspace_root: Option<PathBuf>,
+
+    /// Workspace discovered from `workspace_root` when the analysis starts.
+    pub workspace: Option<Workspace>,
 }
 
 impl Default for AnalyzerConfig {
@@ -155,8 +178,11 @@ impl Default for AnalyzerConfig {
             include_type_imports: true,
             max_depth: Some(DEFAULT_MAX_DEPTH),
             max_modules: Some(DEFAULT_MAX_MODULES),
+            parallelism: default_parallelism(),
             runtime: None,
             cwd: None,
+            workspace_root: None,
+            workspace: None,
         }
     }
 }
@@ -47,6 +47,7 @@
 //! - **Framework Support**: Extracts JavaScript/TypeScript from framework components
 //! - **Path Aliases**: Supports path alias resolution (e.g., `@` → `./src`)
 //! - **External Packages**: Mark npm packages as external to skip analysis
+//! - **Workspaces**: Resolve pnpm/yarn/npm workspace packages to their source
 //! - **Usage Analysis**: Compute export usage counts across the module graph
 //!
 //! ## Quick Start
@@ -88,6 +89,7 @@
 //!   - [`resolver::extensions`] - File extension resolution
 //! - [`extractors`] - Framework-specific script extractors
 //! - [`result`] - Analysis result types
+//! - [`workspace`] - Monorepo workspace discovery
 //!
 //! ## Security Considerations
 //!
@@ -120,6 +122,7 @@ pub mod result;
 pub mod stats;
 pub mod trace;
 pub mod walker;
+pub mod workspace;
 
 #[cfg(test)]
 mod tests;
@@ -129,6 +132,7 @@ pub use cache::{CacheAnalysis, CacheEffectiveness};
 pub use config::{AnalyzerConfig, ResolveResult};
 pub use result::AnalysisResult;
 pub use trace::{ImportOutcome, ImportResolution, RenameEvent, RenamePhase, TransformationTrace};
+pub use workspace::{Workspace, WorkspacePackage};
 
 /// Error that can occur during analysis.
 #[derive(Debug, Error)]
@@ -35,7 +35,8 @@ impl ModuleResolver {
     /// 2. Try path aliases
     /// 3. Try extensions (.ts, .tsx, .js, .jsx)
     /// 4. Try index files
-    /// 5. Check if it's external (npm package)
+    /// 5. Resolve workspace packages to their source
+    /// 6. Check if it's external (npm package)
     pub async fn resolve(
         &self,
         specifier: &str,
@@ -68,6 +69,13 @@ impl ModuleResolver {
                 .await;
         }
 
+        // Workspace packages are first-party source
+        if let Some(workspace) = &self.config.workspace {
+            if let Some(resolved) = workspace.resolve(specifier, runtime).await? {
+                return Ok(resolved);
+            }
+        }
+
         // Must be an external package (bare import)
         Ok(ResolveResult::External(specifier.to_string()))
     }
@@ -1,10 +1,13 @@
+use std::collections::BTreeMap;
 use std::fmt;
 
 use crate::{
-    ExternalDependency, GraphStatistics, ModuleGraph, ModuleId, UnusedExport,
+    DuplicatePackage, ExternalDependency, GraphStatistics, ModuleGraph, ModuleId, UnusedExport,
     dependency_chain::DependencyChain, symbol::SymbolStatistics,
 };
 
+use super::workspace::Workspace;
+
 #[derive(Debug)]
 pub struct AnalysisResult {
     pub graph: ModuleGraph,
@@ -14,6 +17,8 @@ pub struct AnalysisResult {
     pub stats: GraphStatistics,
     /// Symbol-level statistics from intra-file dead code analysis
     pub symbol_stats: SymbolStatistics,
+    /// Workspace the analysis resolved internal packages against
+    pub workspace: Option<Workspace>,
 }
 
 impl AnalysisResult {
@@ -22,11 +27,36 @@ impl AnalysisResult {
         self.graph.unused_exports()
     }
 
+    /// Get unused exports grouped by the workspace package declaring them.
+    ///
+    /// Empty unless the analysis ran with [`super::Analyzer::workspace`].
+    /// Modules outside every workspace package are left out.
+    pub fn unused_exports_by_package(&self) -> crate::Result<BTreeMap<String, Vec<UnusedExport>>> {
+        let mut by_package: BTreeMap<String, Vec<UnusedExport>> = BTreeMap::new();
+        let Some(workspace) = &self.workspace else {
+            return Ok(by_package);
+        };
+        for unused in self.unused_exports()? {
+            if let Some(package) = workspace.package_for(unused.module_id.as_path()) {
+                by_package
+                    .entry(package.name.clone())
+                    .or_default()
+                    .push(unused);
+            }
+        }
+        Ok(by_package)
+    }
+
     /// Get all external dependencies.
     pub fn external_dependencies(&self) -> crate::Result<Vec<ExternalDependency>> {
         self.graph.external_dependencies()
     }
 
+    /// Get packages bundled from more than one installed copy.
+    pub fn duplicate_packages(&self) -> crate::Result<Vec<DuplicatePackage>> {
+        self.graph.duplicate_packages()
+    }
+
     /// Check if the analysis completed without errors.
     pub fn is_ok(&self) -> bool {
         self.errors.is_empty()
@@ -288,6 +288,76 @@ async fn test_wide_dependencies() {
     assert!(analysis.stats.module_count >= 21);
 }
 
+#[tokio::test]
+async fn test_parallel_traversal_matches_sequential() {
+    let temp = TempDir::new().unwrap();
+
+    // Two layers of shared modules so batches overlap on dependencies
+    let mut owned_files: Vec<(String, String)> = Vec::new();
+    let mut imports = Vec::new();
+    for i in 0..12 {
+        owned_files.push((
+            format!("src/feature{}.ts", i),
+            format!(
+                "import {{ shared{} }} from './shared{}';\nexport const feature{} = shared{};",
+                i % 3,
+                i % 3,
+                i,
+                i % 3
+            ),
+        ));
+        imports.push(format!("import {{ feature{} }} from './feature{}';", i, i));
+    }
+    for i in 0..3 {
+        owned_files.push((
+            format!("src/shared{}.ts", i),
+            format!("import 'lodash';\nexport const shared{} = {};", i, i),
+        ));
+    }
+    owned_files.push(("src/index.ts".to_string(), imports.join("\n")));
+
+    let files_ref: Vec<(&str, &str)> = owned_files
+        .iter()
+        .map(|(p, c)| (p.as_str(), c.as_str()))
+        .collect();
+    let root = create_test_project(&temp, &files_ref);
+
+    let mut graphs = Vec::new();
+    for parallelism in [1, 4, 64] {
+        let analysis = Analyzer::new()
+            .entry(root.join("src/index.ts"))
+            .cwd(root.clone())
+            .runtime(Arc::new(TestRuntime::new(root.clone())))
+            .parallelism(parallelism)
+            .analyze()
+            .await
+            .unwrap();
+
+        let mut modules = analysis.graph.modules().unwrap();
+        modules.sort_by(|a, b| a.path.cmp(&b.path));
+        let summary: Vec<_> = modules
+            .iter()
+            .map(|module| {
+                let mut dependencies: Vec<_> = analysis
+                    .graph
+                    .dependencies(&module.id)
+                    .unwrap()
+                    .iter()
+                    .map(|id| id.path_string().into_owned())
+                    .collect();
+                dependencies.sort();
+                (module.path.clone(), module.is_entry, dependencies)
+            })
+            .collect();
+        graphs.push(summary);
+    }
+
+    // index + 12 features + 3 shared modules
+    assert!(graphs[0].len() >= 16);
+    assert_eq!(graphs[0], graphs[1]);
+    assert_eq!(graphs[0], graphs[2]);
+}
+
 #[tokio::test]
 async fn test_dependency_chains() {
     let temp = TempDir::new().unwrap();
@@ -423,3 +493,251 @@ async fn test_handles_missing_imports_gracefully() {
     // Should have warnings about unresolved imports
     assert!(analysis.warnings.is_empty() || analysis.has_warnings());
 }
+
+#[tokio::test]
+async fn test_workspace_packages_resolve_to_source() {
+    let temp = TempDir::new().unwrap();
+    let root = create_test_project(
+        &temp,
+        &[
+            (
+                "package.json",
+                r#"{ "private": true, "workspaces": ["packages/*"] }"#,
+            ),
+            (
+                "packages/ui/package.json",
+                r#"{ "name": "@acme/ui", "main": "dist/index.js" }"#,
+            ),
+            (
+                "packages/ui/src/index.ts",
+                r#"
+            export const Button = () => 'button';
+            export const Unused = () => 'unused';
+        "#,
+            ),
+            (
+                "packages/app/package.json",
+                r#"{ "name": "app", "dependencies": { "@acme/ui": "workspace:*", "react": "^19.0.0" } }"#,
+            ),
+            (
+                "packages/app/src/index.ts",
+                r#"
+            import { Button } from '@acme/ui';
+            import { helper } from './helpers';
+            export const main = () => Button() + helper();
+        "#,
+            ),
+            (
+                "packages/app/src/helpers.ts",
+                r#"
+            export const helper = () => 'helper';
+            export const unusedHelper = () => 'unused';
+        "#,
+            ),
+        ],
+    );
+    let app = root.join("packages/app");
+    let runtime = Arc::new(TestRuntime::new(app.clone()));
+
+    let analysis = Analyzer::new()
+        .entry("src/index.ts")
+        .cwd(app)
+        .workspace(root.clone())
+        .runtime(runtime)
+        .analyze()
+        .await
+        .unwrap();
+
+    let workspace = analysis.workspace.as_ref().unwrap();
+    assert_eq!(workspace.packages.len(), 2);
+    assert_eq!(
+        workspace.package("app").unwrap().workspace_dependencies,
+        vec!["@acme/ui".to_string()]
+    );
+    assert_eq!(
+        workspace.package("@acme/ui").unwrap().entry,
+        Some(root.join("packages/ui/src/index.ts"))
+    );
+
+    let externals = analysis.external_dependencies().unwrap();
+    assert!(externals.iter().all(|dep| dep.specifier != "@acme/ui"));
+    assert!(assert_graph_contains_module(
+        &analysis.graph,
+        "packages/ui/src/index.ts"
+    ));
+
+    let by_package = analysis.unused_exports_by_package().unwrap();
+    let names = |package: &str| -> Vec<String> {
+        by_package[package]
+            .iter()
+            .map(|unused| unused.export.name.clone())
+            .collect()
+    };
+    assert_eq!(names("@acme/ui"), vec!["Unused".to_string()]);
+    assert_eq!(names("app"), vec!["unusedHelper".to_string()]);
+}
+
+#[tokio::test]
+async fn test_license_audit_reports_policy_violations_per_entry() {
+    use crate::{LicensePolicy, LicenseSource};
+
+    let temp = TempDir::new().unwrap();
+    let root = create_test_project(
+        &temp,
+        &[
+            (
+                "src/app.ts",
+                r#"
+            import { debounce } from 'lodash';
+            import fp from 'lodash/fp';
+            import { copyleft } from 'gpl-lib';
+            export const app = () => debounce(fp) + copyleft;
+        "#,
+            ),
+            (
+                "src/admin.ts",
+                r#"
+            import { chunk } from 'lodash';
+            import { dual } from 'dual-lib';
+            export const admin = () => chunk(dual);
+        "#,
+            ),
+            (
+                "node_modules/lodash/package.json",
+                r#"{ "name": "lodash", "version": "4.17.21", "license": "MIT" }"#,
+            ),
+            (
+                "node_modules/gpl-lib/package.json",
+                r#"{ "name": "gpl-lib", "version": "1.0.0" }"#,
+            ),
+            (
+                "node_modules/gpl-lib/LICENSE",
+                "GNU GENERAL PUBLIC LICENSE\n                       Version 3, 29 June 2007\n",
+            ),
+            (
+                "node_modules/dual-lib/package.json",
+                r#"{ "name": "dual-lib", "license": "(MIT OR GPL-3.0)" }"#,
+            ),
+        ],
+    );
+    let runtime = Arc::new(TestRuntime::new(root.clone()));
+
+    let analysis = Analyzer::new()
+        .entries(["src/app.ts", "src/admin.ts"])
+        .cwd(root.clone())
+        .runtime(runtime.clone())
+        .analyze()
+        .await
+        .unwrap();
+
+    let policy = LicensePolicy::deny(["GPL-3.0"]);
+    let report = analysis
+        .graph
+        .license_audit(runtime.as_ref(), &policy)
+        .await
+        .unwrap();
+
+    let names: Vec<&str> = report.packages.iter().map(|p| p.name.as_str()).collect();
+    assert_eq!(names, vec!["dual-lib", "gpl-lib", "lodash"]);
+
+    let lodash = &report.packages[2];
+    assert_eq!(lodash.license.as_deref(), Some("MIT"));
+    assert_eq!(lodash.version.as_deref(), Some("4.17.21"));
+    assert_eq!(lodash.source, LicenseSource::PackageJson);
+    assert_eq!(lodash.specifiers, vec!["lodash", "lodash/fp"]);
+    assert_eq!(lodash.entries.len(), 2);
+
+    let gpl = &report.packages[1];
+    assert_eq!(gpl.license.as_deref(), Some("GPL-3.0"));
+    assert!(matches!(gpl.source, LicenseSource::LicenseFile(_)));
+    assert!(gpl.entries[0].as_path().ends_with("src/app.ts"));
+
+    // One allowed alternative is enough
+    assert!(report.packages[0].allowed);
+    let violations: Vec<&str> = report.violations().map(|p| p.name.as_str()).collect();
+    assert_eq!(violations, vec!["gpl-lib"]);
+    assert!(!report.is_compliant());
+
+    let by_entry = report.licenses_by_entry();
+    let admin = by_entry
+        .iter()
+        .find(|(entry, _)| entry.ends_with("src/admin.ts"))
+        .unwrap()
+        .1;
+    assert!(!admin.contains_key("GPL-3.0"));
+    assert_eq!(admin["MIT"], vec!["lodash".to_string()]);
+}
+
+#[tokio::test]
+async fn test_dynamic_import_boundaries() {
+    let temp = TempDir::new().unwrap();
+    let root = create_test_project(
+        &temp,
+        &[
+            (
+                "src/index.ts",
+                r#"
+            import { format } from './shared';
+            export const main = () => import('./page').then(page => page.render(format));
+        "#,
+            ),
+            (
+                "src/shared.ts",
+                r#"
+            export const format = (value: string) => value.trim();
+        "#,
+            ),
+            (
+                "src/page.ts",
+                r#"
+            import { format } from './shared';
+            import { chart } from './chart';
+            export const render = () => chart(format(' page '));
+        "#,
+            ),
+            (
+                "src/chart.ts",
+                r#"
+            export const chart = (value: string) => value;
+        "#,
+            ),
+        ],
+    );
+    let runtime = Arc::new(TestRuntime::new(root.clone()));
+
+    let analysis = Analyzer::new()
+        .entry("src/index.ts")
+        .cwd(root)
+        .follow_dynamic_imports(true)
+        .runtime(runtime)
+        .analyze()
+        .await
+        .unwrap();
+    let graph = &analysis.graph;
+
+    let boundaries = graph.dynamic_import_boundaries().unwrap();
+    assert_eq!(boundaries.len(), 1);
+    let boundary = &boundaries[0];
+    assert_eq!(boundary.source, "./page");
+    assert!(boundary.importer.as_path().ends_with("src/index.ts"));
+    let lazy: Vec<_> = boundary
+        .lazy_modules
+        .iter()
+        .map(|id| {
+            id.as_path()
+                .file_name()
+                .unwrap()
+                .to_string_lossy()
+                .to_string()
+        })
+        .collect();
+    assert_eq!(lazy, vec!["chart.ts", "page.ts"]);
+    assert!(boundary.lazy_size > 0);
+
+    assert_eq!(graph.lazily_loaded_modules().unwrap().len(), 2);
+    assert!(graph.side_effect_only_imports().unwrap().is_empty());
+
+    // Exports of a dynamically imported module are reachable through its namespace
+    let unused = graph.unused_exports().unwrap();
+    assert!(unused.iter().all(|unused| unused.export.name != "render"));
+}
@@ -3,6 +3,7 @@
 use super::test_helpers::*;
 use crate::analysis::config::AnalyzerConfig;
 use crate::analysis::walker::GraphWalker;
+use crate::collection::CollectedImportKind;
 use crate::test_utils::TestRuntime;
 use std::sync::Arc;
 use tempfile::TempDir;
@@ -210,9 +211,16 @@ async fn test_walk_follows_dynamic_imports_when_enabled() {
 
     let collection = walker.walk(runtime).await.unwrap();
 
-    // Note: Current implementation doesn't parse dynamic imports from code
-    // This test verifies the config is respected
     assert_eq!(collection.entry_points.len(), 1);
+    assert_eq!(collection.modules.len(), 2);
+    let index = collection
+        .modules
+        .iter()
+        .find(|module| module.key().contains("index.ts"))
+        .unwrap();
+    let import = &index.value().imports[0];
+    assert_eq!(import.kind, CollectedImportKind::Dynamic);
+    assert!(import.resolved_path.is_some());
 }
 
 #[tokio::test]
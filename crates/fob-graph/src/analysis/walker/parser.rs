@@ -17,6 +17,10 @@ pub struct ModuleParser;
 
 impl ModuleParser {
     /// Process a module file: read, extract scripts, and parse structure.
+    ///
+    /// On native targets parsing runs on the blocking thread pool, so
+    /// several modules can be processed concurrently without stalling the
+    /// async runtime. On WASM it runs inline.
     pub async fn process_module(
         &self,
         path: &Path,
@@ -25,6 +29,23 @@ impl ModuleParser {
         // Read the file
         let code = self.read_file(path, runtime).await?;
 
+        #[cfg(not(target_family = "wasm"))]
+        if let Ok(handle) = tokio::runtime::Handle::try_current() {
+            let path = path.to_path_buf();
+            return match handle
+                .spawn_blocking(move || ModuleParser.parse_code(&path, code))
+                .await
+            {
+                Ok(result) => result,
+                Err(e) => std::panic::resume_unwind(e.into_panic()),
+            };
+        }
+
+        self.parse_code(path, code)
+    }
+
+    /// Extract scripts from framework files if needed and parse the result.
+    fn parse_code(&self, path: &Path, code: String) -> Result<ParsedModule, WalkerError> {
         // Extract scripts from framework files if needed
         let code_to_parse = self.extract_if_framework(path, &code)?;
 
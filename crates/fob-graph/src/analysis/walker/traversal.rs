@@ -4,16 +4,17 @@
 //! the dependency graph starting from entry points.
 
 use std::collections::{HashMap, VecDeque};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
+use futures::future::join_all;
 use rustc_hash::FxHashSet;
 
 use crate::collection::{CollectedImportKind, CollectionState};
 use crate::runtime::Runtime;
 
 use super::WalkerError;
-use super::parser::ModuleParser;
+use super::parser::{ModuleParser, ParsedModule};
 use super::validation::normalize_and_validate_path;
 use crate::analysis::config::{AnalyzerConfig, ResolveResult};
 use crate::analysis::resolver::ModuleResolver;
@@ -36,8 +37,10 @@ impl<'a> Traversal<'a> {
 
     /// Perform BFS traversal of the dependency graph.
     ///
-    /// This is the main traversal loop that processes modules in breadth-first order,
-    /// resolving imports and adding new modules to the queue as they're discovered.
+    /// Modules are taken from the queue in batches of up to
+    /// `config.parallelism` and read, parsed and resolved concurrently.
+    /// Results are merged in queue order, so the collected graph is the same
+    /// as a one-at-a-time walk regardless of the parallelism limit.
     pub async fn traverse(
         &self,
         runtime: Arc<dyn Runtime>,
@@ -46,6 +49,7 @@ impl<'a> Traversal<'a> {
         let mut visited = FxHashSet::default();
         let mut queue = VecDeque::new();
         let mut depth_map: HashMap<PathBuf, usize> = HashMap::new();
+        let mut entries = FxHashSet::default();
 
         // Initialize queue with entry points
         for entry in &self.config.entries {
@@ -54,165 +58,148 @@ impl<'a> Traversal<'a> {
             depth_map.insert(entry_path.clone(), 0);
             let entry_storage_path = self.path_for_storage(&entry_path, runtime.as_ref())?;
             collection.mark_entry(entry_storage_path);
+            entries.insert(entry_path);
         }
 
-        // BFS traversal
-        while let Some((current_path, depth)) = queue.pop_front() {
-            // Check max depth
-            if let Some(max_depth) = self.config.max_depth {
-                if depth > max_depth {
-                    return Err(WalkerError::MaxDepthExceeded { depth });
+        let parallelism = self.config.parallelism.max(1);
+        loop {
+            // Take the next batch of unvisited modules off the queue
+            let mut batch = Vec::with_capacity(parallelism);
+            while batch.len() < parallelism {
+                let Some((current_path, depth)) = queue.pop_front() else {
+                    break;
+                };
+
+                // Check max depth
+                if let Some(max_depth) = self.config.max_depth {
+                    if depth > max_depth {
+                        return Err(WalkerError::MaxDepthExceeded { depth });
+                    }
                 }
-            }
 
-            // Check max modules
-            if let Some(max_modules) = self.config.max_modules {
-                if visited.len() >= max_modules {
-                    return Err(WalkerError::TooManyModules {
-                        count: visited.len(),
-                        max: max_modules,
-                    });
+                // Check max modules
+                if let Some(max_modules) = self.config.max_modules {
+                    if visited.len() >= max_modules {
+                        return Err(WalkerError::TooManyModules {
+                            count: visited.len(),
+                            max: max_modules,
+                        });
+                    }
                 }
-            }
-
-            // Skip if already visited
-            if visited.contains(&current_path) {
-                continue;
-            }
-
-            // Mark as visited
-            visited.insert(current_path.clone());
-
-            // Process the module
-            let module = self
-                .parser
-                .process_module(&current_path, runtime.as_ref())
-                .await?;
-
-            // Determine if this is an entry point
-            let is_entry = self.config.entries.iter().any(|e| {
-                self.normalize_path(e, runtime.as_ref())
-                    .map(|p| p == current_path)
-                    .unwrap_or(false)
-            });
-
-            // Mark entry point in collection using relative path
-            if is_entry {
-                let entry_storage_path = self.path_for_storage(&current_path, runtime.as_ref())?;
-                collection.mark_entry(entry_storage_path);
-            }
 
-            // Clone exports before moving into module (needed for re-export processing)
-            let exports_clone = module.exports.clone();
-
-            // Resolve imports and populate resolved_path
-            let mut imports = module.imports;
-            for import in &mut imports {
-                // Skip dynamic imports if not following them
-                if import.kind == CollectedImportKind::Dynamic
-                    && !self.config.follow_dynamic_imports
-                {
+                // Skip if already visited
+                if !visited.insert(current_path.clone()) {
                     continue;
                 }
 
-                // Resolve the import
-                let resolve_result = self
-                    .resolver
-                    .resolve(&import.source, &current_path, runtime.as_ref())
-                    .await
-                    .map_err(|e| WalkerError::ResolutionFailed {
-                        specifier: import.source.clone(),
-                        from: current_path.clone(),
-                        reason: e.to_string(),
-                    })?;
-
-                match resolve_result {
-                    ResolveResult::Local(resolved_path) => {
-                        let normalized = self.normalize_path(&resolved_path, runtime.as_ref())?;
-
-                        // Store resolved path for graph building
-                        import.resolved_path =
-                            Some(self.path_for_storage(&normalized, runtime.as_ref())?);
-
-                        // Check for circular dependency
-                        if visited.contains(&normalized) {
-                            // Already processed, skip
-                            continue;
-                        }
-
-                        // Add to queue if not already queued
-                        if !depth_map.contains_key(&normalized) {
-                            let new_depth = depth + 1;
-                            depth_map.insert(normalized.clone(), new_depth);
-                            queue.push_back((normalized, new_depth));
-                        }
-                    }
-                    ResolveResult::External(_) => {
-                        // External dependency - resolved_path stays None
-                        // It will be handled when building the graph
-                    }
-                    ResolveResult::Unresolved(_) => {
-                        // Could not resolve - resolved_path stays None
-                        // This allows analysis to continue even with missing modules
-                    }
-                }
+                batch.push((current_path, depth));
+            }
+            if batch.is_empty() {
+                break;
             }
 
-            // Process re-exports (exports with a source) as imports
-            for export in &exports_clone {
-                if let crate::collection::CollectedExport::All { source } = export {
-                    // Re-export - treat as import to follow the dependency
-                    // Skip dynamic imports if not following them (re-exports are never dynamic)
-
-                    // Resolve the re-export source
-                    if let Ok(ResolveResult::Local(resolved_path)) = self
-                        .resolver
-                        .resolve(source, &current_path, runtime.as_ref())
-                        .await
-                    {
-                        let normalized = self.normalize_path(&resolved_path, runtime.as_ref())?;
-
-                        // Check for circular dependency
-                        if visited.contains(&normalized) {
-                            // Already processed, skip
-                            continue;
-                        }
-
-                        // Add to queue if not already queued
-                        if !depth_map.contains_key(&normalized) {
-                            let new_depth = depth + 1;
-                            depth_map.insert(normalized.clone(), new_depth);
-                            queue.push_back((normalized, new_depth));
-                        }
+            let results = join_all(
+                batch
+                    .iter()
+                    .map(|(path, _)| self.process_module(path, runtime.as_ref())),
+            )
+            .await;
+
+            for ((current_path, depth), result) in batch.into_iter().zip(results) {
+                let (module, local_imports) = result?;
+
+                for normalized in local_imports {
+                    // Add to queue if not already processed or queued
+                    if !visited.contains(&normalized) && !depth_map.contains_key(&normalized) {
+                        let new_depth = depth + 1;
+                        depth_map.insert(normalized.clone(), new_depth);
+                        queue.push_back((normalized, new_depth));
                     }
                 }
-            }
 
-            // Create collected module with resolved imports
-            // Store using path relative to cwd for consistency with tests
-            let storage_path = self.path_for_storage(&current_path, runtime.as_ref())?;
-            let collected_module = crate::collection::CollectedModule {
-                id: storage_path.clone(),
-                code: Some(module.code),
-                is_entry,
-                is_external: false,
-                imports,
-                exports: module.exports,
-                has_side_effects: module.has_side_effects,
-            };
-
-            collection.add_module(storage_path, collected_module);
+                // Mark entry point in collection using relative path
+                let is_entry = entries.contains(&current_path);
+                if is_entry {
+                    let entry_storage_path =
+                        self.path_for_storage(&current_path, runtime.as_ref())?;
+                    collection.mark_entry(entry_storage_path);
+                }
+
+                // Create collected module with resolved imports
+                // Store using path relative to cwd for consistency with tests
+                let storage_path = self.path_for_storage(&current_path, runtime.as_ref())?;
+                let collected_module = crate::collection::CollectedModule {
+                    id: storage_path.clone(),
+                    code: Some(module.code),
+                    is_entry,
+                    is_external: false,
+                    imports: module.imports,
+                    exports: module.exports,
+                    has_side_effects: module.has_side_effects,
+                };
+
+                collection.add_module(storage_path, collected_module);
+            }
         }
 
         Ok(collection)
     }
 
-    /// Normalize a path to an absolute path with security validation.
-    fn normalize_path(
+    /// Read, parse and resolve the imports of a single module.
+    ///
+    /// Returns the parsed module with `resolved_path` populated, along with
+    /// the normalized paths of its local dependencies in import order.
+    async fn process_module(
         &self,
-        path: &std::path::Path,
+        current_path: &Path,
         runtime: &dyn Runtime,
-    ) -> Result<PathBuf, WalkerError> {
+    ) -> Result<(ParsedModule, Vec<PathBuf>), WalkerError> {
+        let mut module = self.parser.process_module(current_path, runtime).await?;
+
+        // Resolve imports and populate resolved_path. Dynamic imports that
+        // aren't followed are dropped so they don't become externals
+        if !self.config.follow_dynamic_imports {
+            module
+                .imports
+                .retain(|import| import.kind != CollectedImportKind::Dynamic);
+        }
+        let mut local_imports = Vec::new();
+        for import in &mut module.imports {
+            // Resolve the import
+            let resolve_result = self
+                .resolver
+                .resolve(&import.source, current_path, runtime)
+                .await
+                .map_err(|e| WalkerError::ResolutionFailed {
+                    specifier: import.source.clone(),
+                    from: current_path.to_path_buf(),
+                    reason: e.to_string(),
+                })?;
+
+            match resolve_result {
+                ResolveResult::Local(resolved_path) => {
+                    let normalized = self.normalize_path(&resolved_path, runtime)?;
+
+                    // Store resolved path for graph building
+                    import.resolved_path = Some(self.path_for_storage(&normalized, runtime)?);
+                    local_imports.push(normalized);
+                }
+                ResolveResult::External(_) => {
+                    // External dependency - resolved_path stays None
+                    // It will be handled when building the graph
+                }
+                ResolveResult::Unresolved(_) => {
+                    // Could not resolve - resolved_path stays None
+                    // This allows analysis to continue even with missing modules
+                }
+            }
+        }
+
+        Ok((module, local_imports))
+    }
+
+    /// Normalize a path to an absolute path with security validation.
+    fn normalize_path(&self, path: &Path, runtime: &dyn Runtime) -> Result<PathBuf, WalkerError> {
         let cwd = self
             .resolver
             .get_cwd(runtime)
@@ -229,11 +216,7 @@ impl<'a> Traversal<'a> {
     }
 
     /// Convert an absolute path to a path relative to cwd for storage.
-    fn path_for_storage(
-        &self,
-        path: &std::path::Path,
-        runtime: &dyn Runtime,
-    ) -> Result<String, WalkerError> {
+    fn path_for_storage(&self, path: &Path, runtime: &dyn Runtime) -> Result<String, WalkerError> {
         let cwd = self
             .resolver
             .get_cwd(runtime)
@@ -0,0 +1,412 @@
+//! Monorepo workspace discovery.
+//!
+//! Reads the package globs of npm and yarn (`workspaces` in package.json)
+//! and pnpm (`pnpm-workspace.yaml`), so that imports of workspace packages
+//! resolve to their source files instead of being treated as external.
+
+use std::path::{Component, Path, PathBuf};
+
+use path_clean::PathClean;
+use serde_json::Value;
+
+use crate::runtime::{Runtime, RuntimeError};
+use crate::{Error, Result};
+
+use super::config::ResolveResult;
+use super::resolver::resolve_with_extensions;
+
+/// Build output directories mapped back to `src` when locating sources.
+const BUILD_DIRS: &[&str] = &["dist", "lib", "build", "out"];
+
+/// Monorepo root and the packages it contains.
+#[derive(Debug, Clone, Default, serde::Serialize)]
+pub struct Workspace {
+    /// Directory holding the root package.json or pnpm-workspace.yaml
+    pub root: PathBuf,
+    /// Packages matched by the workspace globs, sorted by name
+    pub packages: Vec<WorkspacePackage>,
+}
+
+/// Package that is part of a workspace.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct WorkspacePackage {
+    pub name: String,
+    /// Absolute package directory
+    pub root: PathBuf,
+    /// Source file imported for the bare package name, if one was found
+    pub entry: Option<PathBuf>,
+    /// Workspace packages depended on through `workspace:` ranges, sorted
+    pub workspace_dependencies: Vec<String>,
+}
+
+impl Workspace {
+    /// Discover the packages of the workspace rooted at `root`.
+    ///
+    /// Returns an empty workspace if `root` declares no workspace globs.
+    pub async fn discover(root: &Path, runtime: &dyn Runtime) -> Result<Self> {
+        let mut patterns = Vec::new();
+        if let Some(manifest) = read_json(&root.join("package.json"), runtime).await? {
+            // npm and yarn use an array; yarn classic also allows { packages: [...] }
+            let globs = match manifest.get("workspaces") {
+                Some(Value::Object(workspaces)) => workspaces.get("packages"),
+                globs => globs,
+            };
+            if let Some(Value::Array(globs)) = globs {
+                patterns.extend(globs.iter().filter_map(Value::as_str).map(String::from));
+            }
+        }
+        let pnpm_workspace = root.join("pnpm-workspace.yaml");
+        if runtime.exists(&pnpm_workspace) {
+            let content = runtime.read_file(&pnpm_workspace).await.map_err(|e| {
+                Error::InvalidConfig(format!(
+                    "Failed to read {}: {}",
+                    pnpm_workspace.display(),
+                    e
+                ))
+            })?;
+            patterns.extend(pnpm_packages(&String::from_utf8_lossy(&content)));
+        }
+
+        let mut included = Vec::new();
+        let mut excluded = Vec::new();
+        for pattern in &patterns {
+            match pattern.strip_prefix('!') {
+                Some(pattern) => excluded.extend(expand_glob(root, pattern, runtime).await),
+                None => included.extend(expand_glob(root, pattern, runtime).await),
+            }
+        }
+        included.sort();
+        included.dedup();
+
+        let mut manifests = Vec::new();
+        for dir in included {
+            if excluded.contains(&dir) {
+                continue;
+            }
+            let Some(manifest) = read_json(&dir.join("package.json"), runtime).await? else {
+                continue;
+            };
+            let Some(name) = manifest.get("name").and_then(Value::as_str) else {
+                continue;
+            };
+            manifests.push((name.to_string(), dir, manifest));
+        }
+
+        let mut packages = Vec::with_capacity(manifests.len());
+        for (name, dir, manifest) in &manifests {
+            let mut workspace_dependencies: Vec<String> = [
+                "dependencies",
+                "devDependencies",
+                "peerDependencies",
+                "optionalDependencies",
+            ]
+            .iter()
+            .filter_map(|field| manifest.get(field).and_then(Value::as_object))
+            .flatten()
+            .filter(|(dependency, range)| {
+                range
+                    .as_str()
+                    .is_some_and(|range| range.starts_with("workspace:"))
+                    && manifests.iter().any(|(member, _, _)| member == *dependency)
+            })
+            .map(|(dependency, _)| dependency.clone())
+            .collect();
+            workspace_dependencies.sort();
+            workspace_dependencies.dedup();
+
+            packages.push(WorkspacePackage {
+                name: name.clone(),
+                root: dir.clone(),
+                entry: source_entry(dir, manifest, runtime).await?,
+                workspace_dependencies,
+            });
+        }
+        packages.sort_by(|a, b| a.name.cmp(&b.name));
+
+        Ok(Self {
+            root: root.to_path_buf(),
+            packages,
+        })
+    }
+
+    /// Whether no packages were found.
+    pub fn is_empty(&self) -> bool {
+        self.packages.is_empty()
+    }
+
+    /// Get a package by name.
+    pub fn package(&self, name: &str) -> Option<&WorkspacePackage> {
+        self.packages.iter().find(|package| package.name == name)
+    }
+
+    /// Get the package containing a file.
+    ///
+    /// Relative paths are taken relative to the workspace root. Nested
+    /// packages win over the packages containing them.
+    pub fn package_for(&self, path: &Path) -> Option<&WorkspacePackage> {
+        let path = if path.is_absolute() {
+            path.to_path_buf()
+        } else {
+            self.root.join(path).clean()
+        };
+        self.packages
+            .iter()
+            .filter(|package| path.starts_with(&package.root))
+            .max_by_key(|package| package.root.components().count())
+    }
+
+    /// Resolve a bare import of a workspace package to a source file.
+    ///
+    /// Returns `None` if the specifier doesn't name a workspace package.
+    /// Subpaths (`@scope/ui/button`) are looked up in the package and then
+    /// in its `src` directory.
+    pub async fn resolve(
+        &self,
+        specifier: &str,
+        runtime: &dyn Runtime,
+    ) -> std::result::Result<Option<ResolveResult>, RuntimeError> {
+        let Some(package) = self.packages.iter().find(|package| {
+            specifier
+                .strip_prefix(package.name.as_str())
+                .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+        }) else {
+            return Ok(None);
+        };
+
+        let subpath = specifier[package.name.len()..].trim_start_matches('/');
+        if subpath.is_empty() {
+            return Ok(Some(match &package.entry {
+                Some(entry) => ResolveResult::Local(entry.clone()),
+                None => ResolveResult::Unresolved(specifier.to_string()),
+            }));
+        }
+
+        for base in [package.root.clone(), package.root.join("src")] {
+            let resolved = resolve_with_extensions(base.join(subpath).clean(), runtime).await?;
+            if resolved.is_local() {
+                return Ok(Some(resolved));
+            }
+        }
+        Ok(Some(ResolveResult::Unresolved(specifier.to_string())))
+    }
+}
+
+/// Read and parse a JSON file, or `None` if it doesn't exist.
+async fn read_json(path: &Path, runtime: &dyn Runtime) -> Result<Option<Value>> {
+    if !runtime.exists(path) {
+        return Ok(None);
+    }
+    let content = runtime
+        .read_file(path)
+        .await
+        .map_err(|e| Error::InvalidConfig(format!("Failed to read {}: {}", path.display(), e)))?;
+    serde_json::from_slice(&content)
+        .map(Some)
+        .map_err(|e| Error::InvalidConfig(format!("Failed to parse {}: {}", path.display(), e)))
+}
+
+/// Extract the `packages` list of a pnpm-workspace.yaml file.
+fn pnpm_packages(yaml: &str) -> Vec<String> {
+    let mut packages = Vec::new();
+    let mut in_packages = false;
+    for line in yaml.lines() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+        if !line.starts_with([' ', '\t', '-']) {
+            in_packages = trimmed == "packages:";
+            continue;
+        }
+        if let Some(item) = trimmed.strip_prefix('-').filter(|_| in_packages) {
+            let item = item.split(" #").next().unwrap_or_default().trim();
+            packages.push(item.trim_matches(['\'', '"']).to_string());
+        }
+    }
+    packages
+}
+
+/// Directories matching a workspace glob such as `packages/*` or `apps/**`.
+async fn expand_glob(root: &Path, pattern: &str, runtime: &dyn Runtime) -> Vec<PathBuf> {
+    let mut dirs = vec![root.to_path_buf()];
+    for segment in Path::new(pattern).components() {
+        let segment = match segment {
+            Component::Normal(segment) => segment.to_string_lossy(),
+            Component::CurDir => continue,
+            // Globs can't leave the workspace root
+            _ => return Vec::new(),
+        };
+
+        let mut next = Vec::new();
+        if segment == "**" {
+            let mut stack = dirs;
+            while let Some(dir) = stack.pop() {
+                for child in child_dirs(&dir, runtime).await {
+                    stack.push(child.clone());
+                    next.push(child);
+                }
+                next.push(dir);
+            }
+        } else if segment.contains('*') {
+            for dir in &dirs {
+                for child in child_dirs(dir, runtime).await {
+                    let name = child.file_name().unwrap_or_default().to_string_lossy();
+                    if matches_wildcard(&segment, &name) {
+                        next.push(child);
+                    }
+                }
+            }
+        } else {
+            next.extend(
+                dirs.iter()
+                    .map(|dir| dir.join(segment.as_ref()))
+                    .filter(|dir| runtime.exists(dir)),
+            );
+        }
+        dirs = next;
+    }
+
+    dirs.retain(|dir| runtime.exists(&dir.join("package.json")));
+    dirs
+}
+
+/// Subdirectories of `dir`, skipping `node_modules` and hidden directories.
+async fn child_dirs(dir: &Path, runtime: &dyn Runtime) -> Vec<PathBuf> {
+    let mut children = Vec::new();
+    for name in runtime.read_dir(dir).await.unwrap_or_default() {
+        if name == "node_modules" || name.starts_with('.') {
+            continue;
+        }
+        let child = dir.join(&name);
+        if runtime
+            .metadata(&child)
+            .await
+            .is_ok_and(|metadata| metadata.is_dir)
+        {
+            children.push(child);
+        }
+    }
+    children
+}
+
+/// Match a single path segment against a pattern where `*` matches any run of characters.
+fn matches_wildcard(pattern: &str, name: &str) -> bool {
+    let mut parts = pattern.split('*');
+    let first = parts.next().unwrap_or_default();
+    let Some(mut rest) = name.strip_prefix(first) else {
+        return false;
+    };
+    let mut parts: Vec<&str> = parts.collect();
+    let last = parts.pop().unwrap_or_default();
+    for part in parts {
+        match rest.find(part) {
+            Some(index) => rest = &rest[index + part.len()..],
+            None => return false,
+        }
+    }
+    rest.len() >= last.len() && rest.ends_with(last)
+}
+
+/// Locate the source file behind a package's entry fields.
+///
+/// Tries `source`, `exports["."]`, `module` and `main`, mapping build
+/// output directories such as `dist` back to `src`, then falls back to
+/// `src/index` and `index`.
+async fn source_entry(
+    root: &Path,
+    manifest: &Value,
+    runtime: &dyn Runtime,
+) -> Result<Option<PathBuf>> {
+    let mut candidates: Vec<&str> = Vec::new();
+    candidates.extend(manifest.get("source").and_then(Value::as_str));
+    let main_export = match manifest.get("exports") {
+        Some(Value::Object(exports)) if exports.contains_key(".") => exports.get("."),
+        exports => exports,
+    };
+    candidates.extend(main_export.and_then(condition_target));
+    candidates.extend(
+        ["module", "main"]
+            .iter()
+            .filter_map(|field| manifest.get(field).and_then(Value::as_str)),
+    );
+    candidates.extend(["src/index", "index"]);
+
+    for candidate in candidates {
+        let path = root.join(candidate).clean();
+        if !path.starts_with(root) {
+            continue;
+        }
+        let mut attempts = Vec::with_capacity(2);
+        if let Ok(relative) = path.strip_prefix(root) {
+            let mut components = relative.components();
+            let first = components.next().map(|c| c.as_os_str().to_string_lossy());
+            if first.is_some_and(|first| BUILD_DIRS.contains(&first.as_ref())) {
+                let mut source = root.join("src").join(components.as_path());
+                // `dist/index.js` becomes `src/index`, tried with every extension
+                source.set_extension("");
+                if source.extension().is_some_and(|ext| ext == "d") {
+                    source.set_extension("");
+                }
+                attempts.push(source);
+            }
+        }
+        attempts.push(path);
+
+        for attempt in attempts {
+            let resolved = resolve_with_extensions(attempt, runtime)
+                .await
+                .map_err(|e| Error::Operation(e.to_string()))?;
+            if let ResolveResult::Local(path) = resolved {
+                return Ok(Some(path));
+            }
+        }
+    }
+
+    Ok(None)
+}
+
+/// First file target of an `exports` value, preferring source and ESM conditions.
+fn condition_target(value: &Value) -> Option<&str> {
+    match value {
+        Value::String(target) => Some(target),
+        Value::Array(targets) => targets.iter().find_map(condition_target),
+        Value::Object(conditions) => ["source", "import", "module", "default", "require"]
+            .iter()
+            .filter_map(|condition| conditions.get(*condition))
+            .find_map(condition_target),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pnpm_packages() {
+        let yaml = "# workspace\npackages:\n  - 'packages/*'\n  - \"apps/**\" # apps\n  - '!**/test/**'\ncatalog:\n  - ignored\n";
+        assert_eq!(
+            pnpm_packages(yaml),
+            vec!["packages/*", "apps/**", "!**/test/**"]
+        );
+    }
+
+    #[test]
+    fn test_matches_wildcard() {
+        assert!(matches_wildcard("*", "ui"));
+        assert!(matches_wildcard("pkg-*", "pkg-ui"));
+        assert!(matches_wildcard("*-utils", "date-utils"));
+        assert!(!matches_wildcard("pkg-*", "ui"));
+        assert!(!matches_wildcard("a*a", "a"));
+    }
+
+    #[test]
+    fn test_condition_target() {
+        let exports: Value = serde_json::json!({
+            "types": "./dist/index.d.ts",
+            "import": { "types": "./dist/index.d.mts", "default": "./dist/index.mjs" },
+            "require": "./dist/index.cjs"
+        });
+        assert_eq!(condition_target(&exports), Some("./dist/index.mjs"));
+    }
+}
@@ -39,6 +39,8 @@ pub enum CollectedImportKind {
     Dynamic,
     /// Type-only import: `import type { Type } from './types'`
     TypeOnly,
+    /// Re-export: `export { foo } from './bar'` or `export * from './bar'`
+    ReExport,
 }
 
 /// Represents a collected module with all its metadata
@@ -82,6 +84,12 @@ pub enum CollectedExport {
     All {
         source: String,
     },
+    /// Named re-export: `export { imported as exported } from 'source'`
+    ReExport {
+        exported: String,
+        imported: String,
+        source: String,
+    },
 }
 
 /// Shared state for collecting module information during bundling or analysis
@@ -257,8 +265,27 @@ pub fn parse_module_structure(
             }
             ExportDeclaration::Named(named) => {
                 if let Some(src) = &named.source {
-                    exports.push(CollectedExport::All {
-                        source: src.value.to_string(),
+                    let source = src.value.to_string();
+                    let mut specifiers = Vec::new();
+                    for s in &named.specifiers {
+                        let imported = get_module_export_name_string(&s.local);
+                        let exported = get_module_export_name_string(&s.exported);
+                        specifiers.push(CollectedImportSpecifier::Named {
+                            imported: imported.clone(),
+                            local: exported.clone(),
+                        });
+                        exports.push(CollectedExport::ReExport {
+                            exported,
+                            imported,
+                            source: source.clone(),
+                        });
+                    }
+                    // Re-exports load their source, so record them as imports too
+                    imports.push(CollectedImport {
+                        source,
+                        specifiers,
+                        kind: CollectedImportKind::ReExport,
+                        resolved_path: None,
                     });
                 } else if let Some(decl) = &named.declaration {
                     match decl {
@@ -305,12 +332,64 @@ pub fn parse_module_structure(
                 }
             }
             ExportDeclaration::All(all) => {
+                let source = all.source.value.to_string();
                 exports.push(CollectedExport::All {
-                    source: all.source.value.to_string(),
+                    source: source.clone(),
+                });
+                imports.push(CollectedImport {
+                    source,
+                    specifiers: vec![CollectedImportSpecifier::Namespace {
+                        local: "*".to_string(),
+                    }],
+                    kind: CollectedImportKind::ReExport,
+                    resolved_path: None,
                 });
             }
         }
     }
 
+    // Extract `import()` expressions with a static specifier
+    let mut dynamic_imports = DynamicImportCollector::default();
+    oxc_ast_visit::Visit::visit_program(&mut dynamic_imports, parsed.ast());
+    imports.extend(
+        dynamic_imports
+            .sources
+            .into_iter()
+            .map(|source| CollectedImport {
+                source,
+                specifiers: Vec::new(),
+                kind: CollectedImportKind::Dynamic,
+                resolved_path: None,
+            }),
+    );
+
     Ok((imports, exports, has_side_effects))
 }
+
+/// Collects the specifiers of `import()` expressions.
+///
+/// Only string literals and template literals without substitutions are
+/// recorded; computed specifiers can't be resolved statically.
+#[derive(Default)]
+struct DynamicImportCollector {
+    sources: Vec<String>,
+}
+
+impl<'a> oxc_ast_visit::Visit<'a> for DynamicImportCollector {
+    fn visit_import_expression(&mut self, it: &oxc_ast::ast::ImportExpression<'a>) {
+        use oxc_ast::ast::Expression;
+
+        let source = match &it.source {
+            Expression::StringLiteral(literal) => Some(literal.value.to_string()),
+            Expression::TemplateLiteral(template) if template.expressions.is_empty() => template
+                .quasis
+                .first()
+                .map(|quasi| quasi.value.raw.to_string()),
+            _ => None,
+        };
+        if let Some(source) = source {
+            self.sources.push(source);
+        }
+        oxc_ast_visit::walk::walk_import_expression(self, it);
+    }
+}
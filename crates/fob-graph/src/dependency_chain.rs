@@ -14,7 +14,7 @@ use super::ModuleId;
 /// This limit prevents path explosion in large graphs with deep dependency chains.
 /// A value of 50 allows for very deep dependency graphs while still preventing
 /// exponential growth that could cause performance issues or memory exhaustion.
-const MAX_DEPENDENCY_CHAIN_DEPTH: usize = 50;
+pub(crate) const MAX_DEPENDENCY_CHAIN_DEPTH: usize = 50;
 
 /// A chain of dependencies from an entry point to a target module.
 ///
@@ -24,6 +24,12 @@ pub struct Export {
     pub is_used: bool,
     pub is_type_only: bool,
     pub re_exported_from: Option<String>,
+    /// Name of the export in the source module, for named re-exports.
+    ///
+    /// Differs from `name` when the re-export renames it, as in
+    /// `export { default as Button } from './Button'`.
+    #[serde(default)]
+    pub imported_name: Option<String>,
     pub is_framework_used: bool,
     /// True if this export came from a CommonJS module.
     ///
@@ -59,6 +65,7 @@ impl Export {
             is_used,
             is_type_only,
             re_exported_from,
+            imported_name: None,
             is_framework_used,
             came_from_commonjs,
             span,
@@ -95,6 +95,24 @@ pub fn convert_collected_exports(collected: &CollectedModule, module_id: &Module
                     SourceSpan::new(module_id.as_path(), 0, 0),
                 ));
             }
+            CollectedExport::ReExport {
+                exported,
+                imported,
+                source,
+            } => {
+                let mut re_export = Export::new(
+                    exported.clone(),
+                    ExportKind::ReExport,
+                    false,
+                    false,
+                    Some(source.clone()),
+                    false,
+                    false,
+                    SourceSpan::new(module_id.as_path(), 0, 0),
+                );
+                re_export.imported_name = Some(imported.clone());
+                exports.push(re_export);
+            }
             CollectedExport::All { source } => {
                 exports.push(Export::new(
                     "*".to_string(),
@@ -131,6 +149,7 @@ pub fn convert_collected_imports(
             CollectedImportKind::Dynamic => ImportKind::Dynamic,
             CollectedImportKind::Static => ImportKind::Static,
             CollectedImportKind::TypeOnly => ImportKind::TypeOnly,
+            CollectedImportKind::ReExport => ImportKind::ReExport,
         };
 
         let fob_import = Import::new(
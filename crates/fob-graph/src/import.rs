@@ -70,7 +70,12 @@ impl Import {
 
     /// Returns `true` for side-effect-only imports (`import 'polyfill'`).
     pub fn is_side_effect_only(&self) -> bool {
-        self.specifiers.is_empty() && self.kind.is_runtime()
+        self.specifiers.is_empty() && self.kind.is_static()
+    }
+
+    /// Returns `true` for `import()` expressions, which load the module lazily.
+    pub fn is_dynamic(&self) -> bool {
+        matches!(self.kind, ImportKind::Dynamic)
     }
 
     /// Returns `true` when the import references a package on npm (no relative prefix).
@@ -133,6 +133,7 @@ pub mod external_dep;
 pub mod framework_rules;
 pub mod from_collection;
 pub mod import;
+pub mod license;
 pub mod module;
 pub mod module_id;
 pub mod package_json;
@@ -140,6 +141,7 @@ mod quality;
 pub mod semantic;
 pub mod span;
 pub mod statistics;
+pub mod storage;
 pub mod symbol;
 
 // ModuleGraph implementation
@@ -148,8 +150,11 @@ mod memory;
 
 // Re-export ModuleGraph implementation
 pub use memory::{
-    ClassMemberInfo, EnumMemberInfo, ModuleGraph, NamespaceImportInfo, SideEffectImport,
-    TypeOnlyImport,
+    BarrelFile, BarrelImport, ChunkGroupKind, ChunkGroupOptions, ChunkGroupSuggestion,
+    ClassMemberInfo, DirectImport, DynamicImportBoundary, EnumMemberInfo, GraphExportOptions,
+    GraphNodeKind, GraphQuery, ImportCodemod, ModuleChange, ModuleGraph, NamespaceImportInfo,
+    SideEffectImport, SymbolReachability, SymbolReachabilityInfo, TypeImportIssue,
+    TypeImportIssueKind, TypeOnlyImport,
 };
 
 /// Output entry for unused exports.
@@ -169,17 +174,20 @@ pub use external_dep::ExternalDependency;
 pub use framework_rules::FrameworkRule;
 pub use from_collection::CollectionGraphError;
 pub use import::{Import, ImportKind, ImportSpecifier};
+pub use license::{LicensePolicy, LicenseReport, LicenseSource, PackageLicense};
 pub use module::{Module, SourceType};
 pub use module_id::{ModuleId, ModuleIdError};
 pub use package_json::{
-    DependencyCoverage, DependencyType, PackageJson, TypeCoverage, UnusedDependency,
-    extract_package_name,
+    DependencyCoverage, DependencyType, DuplicatePackage, PackageCopy, PackageJson, TypeCoverage,
+    UnusedDependency, extract_package_name,
 };
 pub use span::SourceSpan;
 pub use statistics::GraphStatistics;
+pub use storage::{GraphDelta, GraphSnapshot, GraphStorage, MemoryGraphStorage, ModuleRecord};
 pub use symbol::{
-    ClassMemberMetadata, EnumMemberMetadata, EnumMemberValue, Symbol, SymbolKind, SymbolMetadata,
-    SymbolSpan, SymbolStatistics, SymbolTable, UnreachableCode, UnusedSymbol, Visibility,
+    ClassMemberMetadata, EnumMemberMetadata, EnumMemberValue, ImportBinding, ImportStatement,
+    Symbol, SymbolKind, SymbolMetadata, SymbolSpan, SymbolStatistics, SymbolTable, UnreachableCode,
+    UnusedSymbol, Visibility,
 };
 
 // Re-export runtime types
@@ -211,7 +219,7 @@ pub mod test_utils {
 pub use analysis::{
     AnalysisResult, AnalyzeError, AnalyzeOptions, Analyzer, CacheAnalysis, CacheEffectiveness,
     Configured, ImportOutcome, ImportResolution, RenameEvent, RenamePhase, TransformationTrace,
-    Unconfigured, analyze, analyze_with_options,
+    Unconfigured, Workspace, WorkspacePackage, analyze, analyze_with_options,
 };
 
 // Re-export OXC foundation types for consistent version usage across workspace
@@ -311,6 +319,10 @@ pub enum Error {
     /// Analysis or graph operation error.
     #[error("Operation error: {0}")]
     Operation(String),
+
+    /// Persisted graph data is inconsistent, e.g. a partially written store.
+    #[error("Corrupt graph storage: {0}")]
+    CorruptStorage(String),
 }
 
 /// Result type alias for fob operations.
@@ -0,0 +1,322 @@
+//! License detection for installed npm packages.
+//!
+//! Licenses come from the package.json `license` field (or the legacy
+//! `licenses` array) and fall back to recognising a LICENSE file. See
+//! [`ModuleGraph::license_audit`](crate::ModuleGraph::license_audit) for
+//! the graph-wide report.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::ModuleId;
+use crate::runtime::Runtime;
+
+/// Files checked, in order, when package.json declares no license.
+const LICENSE_FILES: &[&str] = &[
+    "LICENSE",
+    "LICENSE.md",
+    "LICENSE.txt",
+    "LICENCE",
+    "LICENCE.md",
+    "LICENCE.txt",
+    "license",
+    "license.md",
+    "COPYING",
+];
+
+/// Maximum size of a LICENSE file that is read for detection (1MB)
+const MAX_LICENSE_FILE_SIZE: u64 = 1024 * 1024;
+
+/// Phrases identifying common license texts, most specific first.
+const LICENSE_TEXTS: &[(&str, &[&str])] = &[
+    (
+        "AGPL-3.0",
+        &["GNU AFFERO GENERAL PUBLIC LICENSE", "Version 3"],
+    ),
+    (
+        "LGPL-3.0",
+        &["GNU LESSER GENERAL PUBLIC LICENSE", "Version 3"],
+    ),
+    (
+        "LGPL-2.1",
+        &["GNU LESSER GENERAL PUBLIC LICENSE", "Version 2.1"],
+    ),
+    ("GPL-3.0", &["GNU GENERAL PUBLIC LICENSE", "Version 3"]),
+    ("GPL-2.0", &["GNU GENERAL PUBLIC LICENSE", "Version 2"]),
+    ("MPL-2.0", &["Mozilla Public License", "2.0"]),
+    ("Apache-2.0", &["Apache License", "Version 2.0"]),
+    (
+        "BSD-3-Clause",
+        &["Redistribution and use", "Neither the name"],
+    ),
+    ("BSD-2-Clause", &["Redistribution and use"]),
+    (
+        "ISC",
+        &[
+            "Permission to use, copy, modify, and/or distribute",
+            "provided that the above copyright notice",
+        ],
+    ),
+    (
+        "0BSD",
+        &["Permission to use, copy, modify, and/or distribute"],
+    ),
+    ("MIT", &["Permission is hereby granted, free of charge"]),
+    ("Unlicense", &["This is free and unencumbered software"]),
+];
+
+/// Licenses a project may not ship.
+///
+/// License identifiers are SPDX ids, compared case-insensitively. An SPDX
+/// expression is disallowed when every `OR` alternative contains a
+/// disallowed id, so `MIT OR GPL-3.0` passes a policy denying `GPL-3.0`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LicensePolicy {
+    /// Disallowed SPDX license ids
+    pub disallowed: Vec<String>,
+    /// Whether packages without a detectable license violate the policy
+    pub deny_unknown: bool,
+}
+
+impl LicensePolicy {
+    /// Create a policy disallowing the given license ids.
+    pub fn deny<I, S>(licenses: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self {
+            disallowed: licenses.into_iter().map(Into::into).collect(),
+            deny_unknown: false,
+        }
+    }
+
+    /// Also disallow packages whose license can't be detected.
+    pub fn deny_unknown(mut self, deny: bool) -> Self {
+        self.deny_unknown = deny;
+        self
+    }
+
+    /// Check a license expression, or `None` for an unknown license.
+    pub fn is_allowed(&self, license: Option<&str>) -> bool {
+        let Some(license) = license else {
+            return !self.deny_unknown;
+        };
+        let denied = |id: &str| {
+            let id = id.trim_end_matches('+');
+            self.disallowed
+                .iter()
+                .any(|disallowed| disallowed.eq_ignore_ascii_case(id))
+        };
+        license_alternatives(license)
+            .iter()
+            .any(|alternative| !alternative.iter().any(|id| denied(id)))
+    }
+}
+
+/// Where a package's license was found.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LicenseSource {
+    /// The package.json `license` or `licenses` field
+    PackageJson,
+    /// Recognised from a license file
+    LicenseFile(PathBuf),
+    /// No license could be determined
+    Unknown,
+}
+
+/// License information for one installed external package.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PackageLicense {
+    /// Package name
+    pub name: String,
+    /// Version from package.json
+    pub version: Option<String>,
+    /// Installed package directory, if the package could be found
+    pub root: Option<PathBuf>,
+    /// SPDX license expression
+    pub license: Option<String>,
+    /// Where the license came from
+    pub source: LicenseSource,
+    /// Whether the license is allowed by the policy
+    pub allowed: bool,
+    /// Import specifiers that load the package, e.g. `lodash` and `lodash/fp`
+    pub specifiers: Vec<String>,
+    /// Modules importing the package
+    pub importers: Vec<ModuleId>,
+    /// Entry points whose output includes the package
+    pub entries: Vec<ModuleId>,
+}
+
+/// Result of [`ModuleGraph::license_audit`](crate::ModuleGraph::license_audit).
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LicenseReport {
+    /// Packages sorted by name
+    pub packages: Vec<PackageLicense>,
+}
+
+impl LicenseReport {
+    /// Packages whose license the policy disallows.
+    pub fn violations(&self) -> impl Iterator<Item = &PackageLicense> {
+        self.packages.iter().filter(|package| !package.allowed)
+    }
+
+    /// Whether every package passes the policy.
+    pub fn is_compliant(&self) -> bool {
+        self.violations().next().is_none()
+    }
+
+    /// Packages grouped by license expression; unknown licenses use `UNKNOWN`.
+    pub fn by_license(&self) -> BTreeMap<String, Vec<&PackageLicense>> {
+        let mut licenses: BTreeMap<String, Vec<&PackageLicense>> = BTreeMap::new();
+        for package in &self.packages {
+            let license = package.license.as_deref().unwrap_or("UNKNOWN");
+            licenses
+                .entry(license.to_string())
+                .or_default()
+                .push(package);
+        }
+        licenses
+    }
+
+    /// Licenses shipped by each entry point, keyed by entry path, with the
+    /// names of the packages carrying them.
+    pub fn licenses_by_entry(&self) -> BTreeMap<PathBuf, BTreeMap<String, Vec<String>>> {
+        let mut entries: BTreeMap<PathBuf, BTreeMap<String, Vec<String>>> = BTreeMap::new();
+        for package in &self.packages {
+            let license = package.license.as_deref().unwrap_or("UNKNOWN");
+            for entry in &package.entries {
+                entries
+                    .entry(entry.as_path().to_path_buf())
+                    .or_default()
+                    .entry(license.to_string())
+                    .or_default()
+                    .push(package.name.clone());
+            }
+        }
+        entries
+    }
+}
+
+/// Find the installed directory of a package, searching `node_modules`
+/// upward from the importing file like Node's resolver.
+pub(crate) fn find_package_root<R: Runtime + ?Sized>(
+    runtime: &R,
+    name: &str,
+    importer: &Path,
+) -> Option<PathBuf> {
+    importer.ancestors().skip(1).find_map(|dir| {
+        let root = dir.join("node_modules").join(name);
+        runtime.exists(&root.join("package.json")).then_some(root)
+    })
+}
+
+/// Read the version and license of an installed package.
+pub(crate) async fn read_package_license<R: Runtime + ?Sized>(
+    runtime: &R,
+    root: &Path,
+) -> (Option<String>, Option<String>, LicenseSource) {
+    let manifest = runtime
+        .read_file(&root.join("package.json"))
+        .await
+        .ok()
+        .and_then(|bytes| serde_json::from_slice::<serde_json::Value>(&bytes).ok());
+    let version = manifest
+        .as_ref()
+        .and_then(|manifest| manifest.get("version"))
+        .and_then(|version| version.as_str())
+        .map(str::to_string);
+
+    if let Some(license) = manifest.as_ref().and_then(manifest_license) {
+        return (version, Some(license), LicenseSource::PackageJson);
+    }
+
+    for file in LICENSE_FILES {
+        let path = root.join(file);
+        if !runtime.exists(&path) {
+            continue;
+        }
+        let small = runtime
+            .metadata(&path)
+            .await
+            .is_ok_and(|metadata| metadata.size <= MAX_LICENSE_FILE_SIZE);
+        if !small {
+            continue;
+        }
+        let Ok(bytes) = runtime.read_file(&path).await else {
+            continue;
+        };
+        if let Some(license) = detect_license_text(&String::from_utf8_lossy(&bytes)) {
+            return (
+                version,
+                Some(license.to_string()),
+                LicenseSource::LicenseFile(path),
+            );
+        }
+    }
+
+    (version, None, LicenseSource::Unknown)
+}
+
+/// Read the license expression from a parsed package.json.
+///
+/// Handles `"license": "MIT"`, the deprecated `"license": { "type": "MIT" }`
+/// and `"licenses": [{ "type": "MIT" }, ...]`, which is joined with `OR`.
+/// `SEE LICENSE IN <file>` defers to the license file.
+fn manifest_license(manifest: &serde_json::Value) -> Option<String> {
+    let license = match manifest.get("license") {
+        Some(serde_json::Value::String(license)) => Some(license.clone()),
+        Some(serde_json::Value::Object(license)) => license
+            .get("type")
+            .and_then(|kind| kind.as_str())
+            .map(str::to_string),
+        _ => None,
+    }
+    .or_else(|| {
+        let licenses: Vec<&str> = manifest
+            .get("licenses")?
+            .as_array()?
+            .iter()
+            .filter_map(|license| license.get("type")?.as_str())
+            .collect();
+        (!licenses.is_empty()).then(|| licenses.join(" OR "))
+    })?;
+
+    let license = license.trim();
+    if license.is_empty() || license.to_ascii_uppercase().starts_with("SEE LICENSE IN") {
+        return None;
+    }
+    Some(license.to_string())
+}
+
+/// Identify a license from the text of a license file.
+fn detect_license_text(text: &str) -> Option<&'static str> {
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    LICENSE_TEXTS
+        .iter()
+        .find(|(_, phrases)| phrases.iter().all(|phrase| text.contains(phrase)))
+        .map(|(id, _)| *id)
+}
+
+/// Split an SPDX expression into `OR` alternatives of `AND`-ed license ids.
+///
+/// Parentheses are flattened and `WITH` exceptions dropped, which is
+/// exact for the expressions packages use in practice.
+fn license_alternatives(expression: &str) -> Vec<Vec<&str>> {
+    let expression = expression.trim_start_matches('(').trim_end_matches(')');
+    expression
+        .split(" OR ")
+        .map(|alternative| {
+            alternative
+                .split(" AND ")
+                .map(|id| {
+                    let id = id.trim_matches(|c: char| c == '(' || c == ')' || c.is_whitespace());
+                    id.split(" WITH ").next().unwrap_or(id)
+                })
+                .collect()
+        })
+        .collect()
+}
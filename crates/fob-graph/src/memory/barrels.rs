@@ -0,0 +1,336 @@
+//! Barrel file analysis for ModuleGraph.
+//!
+//! A barrel (typically an `index.ts`) re-exports other modules so they can
+//! be imported from one place. Importing a single name from it still loads
+//! everything it re-exports, so this reports how much of each barrel its
+//! importers actually use and which direct imports would replace them.
+
+use std::collections::{BTreeMap, VecDeque};
+use std::path::{Component, Path, PathBuf};
+
+use fob_gen::{Allocator, ProgramBuilder};
+use oxc_ast::ast::Statement;
+use rustc_hash::FxHashSet as HashSet;
+
+use super::super::import::{Import, ImportKind, ImportSpecifier};
+use super::super::package_json::installed_package;
+use super::super::{ExportKind, Module, ModuleId};
+use super::graph::{GraphInner, ModuleGraph};
+use super::types::{BarrelFile, BarrelImport, DirectImport};
+use crate::{Error, Result};
+
+impl ModuleGraph {
+    /// Find barrel files and how much of them each importer uses.
+    ///
+    /// A module is a barrel when it has at least two re-exports and more
+    /// re-exports than local exports. Barrels are sorted by the size of the
+    /// subtree they load, largest first.
+    pub fn barrel_files(&self) -> Result<Vec<BarrelFile>> {
+        let inner = self.inner.read();
+        let mut barrels = Vec::new();
+
+        for module in inner.modules.values() {
+            let re_export_count = module
+                .exports
+                .iter()
+                .filter(|export| is_re_export(export.kind))
+                .count();
+            if re_export_count < 2 || re_export_count <= module.exports.len() - re_export_count {
+                continue;
+            }
+
+            let mut re_exported_modules: Vec<ModuleId> = module
+                .imports
+                .iter()
+                .filter(|import| import.kind == ImportKind::ReExport)
+                .filter_map(|import| import.resolved_to.clone())
+                .collect::<HashSet<_>>()
+                .into_iter()
+                .collect();
+            re_exported_modules.sort_by(|a, b| a.as_path().cmp(b.as_path()));
+
+            let subtree = reachable(
+                &inner,
+                inner.dependencies.get(&module.id).into_iter().flatten(),
+                &module.id,
+            );
+            let subtree_bytes = total_size(&inner, &subtree);
+
+            let mut importers = Vec::new();
+            for importer_id in inner.dependents.get(&module.id).into_iter().flatten() {
+                let Some(importer) = inner.modules.get(importer_id) else {
+                    continue;
+                };
+                for import in importer.imports.iter() {
+                    // Forwarding and type-only imports don't load the barrel
+                    if import.resolved_to.as_ref() != Some(&module.id)
+                        || matches!(import.kind, ImportKind::ReExport | ImportKind::TypeOnly)
+                        || (import.specifiers.is_empty() && !import.is_dynamic())
+                    {
+                        continue;
+                    }
+                    importers.push(analyze_import(
+                        &inner,
+                        module,
+                        importer,
+                        import,
+                        &subtree,
+                        subtree_bytes,
+                    ));
+                }
+            }
+            importers.sort_by(|a, b| a.importer.as_path().cmp(b.importer.as_path()));
+
+            barrels.push(BarrelFile {
+                module_id: module.id.clone(),
+                re_exported_modules,
+                re_export_count,
+                subtree_modules: subtree.len(),
+                subtree_bytes,
+                importers,
+            });
+        }
+
+        barrels.sort_by(|a, b| {
+            b.subtree_bytes
+                .cmp(&a.subtree_bytes)
+                .then_with(|| a.module_id.as_path().cmp(b.module_id.as_path()))
+        });
+        Ok(barrels)
+    }
+}
+
+impl BarrelImport {
+    /// Generate the import declarations that replace this import of the barrel.
+    ///
+    /// Returns `None` if the importer needs the whole barrel.
+    pub fn flattened_imports(&self) -> Result<Option<String>> {
+        if self.whole_barrel || self.direct_imports.is_empty() {
+            return Ok(None);
+        }
+
+        let allocator = Allocator::default();
+        let mut js = ProgramBuilder::new(&allocator);
+        for direct in &self.direct_imports {
+            let names: Vec<(&str, &str)> = direct
+                .names
+                .iter()
+                .map(|(imported, local)| (imported.as_str(), local.as_str()))
+                .collect();
+            let import = js.import_named_as(names, direct.specifier.as_str());
+            js.push(Statement::from(import));
+        }
+        js.generate(&Default::default())
+            .map(Some)
+            .map_err(|e| Error::Operation(format!("Failed to generate imports: {e}")))
+    }
+}
+
+fn is_re_export(kind: ExportKind) -> bool {
+    matches!(kind, ExportKind::ReExport | ExportKind::StarReExport)
+}
+
+/// Work out which part of a barrel an import uses and how to import it directly.
+fn analyze_import(
+    inner: &GraphInner,
+    barrel: &Module,
+    importer: &Module,
+    import: &Import,
+    subtree: &HashSet<ModuleId>,
+    subtree_bytes: usize,
+) -> BarrelImport {
+    // Dynamic imports get the whole module namespace
+    let mut whole_barrel = import.kind == ImportKind::Dynamic;
+    let mut used_exports = Vec::new();
+    let mut origins: BTreeMap<PathBuf, (ModuleId, Vec<(String, String)>)> = BTreeMap::new();
+
+    for specifier in &import.specifiers {
+        let name = match specifier {
+            ImportSpecifier::Named(name) => name,
+            // The local names of these aren't recorded, so they can't be rewritten
+            ImportSpecifier::Default => {
+                used_exports.push("default".to_string());
+                whole_barrel = true;
+                continue;
+            }
+            ImportSpecifier::Namespace(_) => {
+                used_exports.push("*".to_string());
+                whole_barrel = true;
+                continue;
+            }
+        };
+        used_exports.push(name.clone());
+
+        match resolve_origin(inner, &barrel.id, name, &mut HashSet::default()) {
+            Some((origin, imported)) if origin != barrel.id => {
+                origins
+                    .entry(origin.as_path().to_path_buf())
+                    .or_insert_with(|| (origin, Vec::new()))
+                    .1
+                    .push((imported, name.clone()));
+            }
+            // Defined in the barrel itself, or re-exported from a missing module
+            _ => whole_barrel = true,
+        }
+    }
+
+    if whole_barrel {
+        return BarrelImport {
+            importer: importer.id.clone(),
+            source: import.source.clone(),
+            used_exports,
+            whole_barrel,
+            used_modules: subtree.len(),
+            used_bytes: subtree_bytes,
+            direct_imports: Vec::new(),
+        };
+    }
+
+    let used = reachable(inner, origins.values().map(|(id, _)| id), &barrel.id);
+    let direct_imports = origins
+        .into_values()
+        .filter_map(|(module_id, names)| {
+            let origin = inner.modules.get(&module_id)?;
+            Some(DirectImport {
+                specifier: direct_specifier(importer, &import.source, origin),
+                module_id,
+                names,
+            })
+        })
+        .collect();
+
+    BarrelImport {
+        importer: importer.id.clone(),
+        source: import.source.clone(),
+        used_exports,
+        whole_barrel,
+        used_modules: used.len(),
+        used_bytes: total_size(inner, &used),
+        direct_imports,
+    }
+}
+
+/// Trace an export to the module defining it, returning that module and the
+/// export's name there.
+fn resolve_origin(
+    inner: &GraphInner,
+    module_id: &ModuleId,
+    name: &str,
+    visited: &mut HashSet<(ModuleId, String)>,
+) -> Option<(ModuleId, String)> {
+    if !visited.insert((module_id.clone(), name.to_string())) {
+        return None;
+    }
+    let module = inner.modules.get(module_id)?;
+
+    if module
+        .exports
+        .iter()
+        .any(|export| export.name == name && !is_re_export(export.kind))
+    {
+        return Some((module_id.clone(), name.to_string()));
+    }
+
+    if let Some(export) = module
+        .exports
+        .iter()
+        .find(|export| export.kind == ExportKind::ReExport && export.name == name)
+    {
+        let target = module
+            .imports
+            .iter()
+            .find(|import| {
+                import.kind == ImportKind::ReExport
+                    && export.re_exported_from.as_deref() == Some(import.source.as_str())
+            })?
+            .resolved_to
+            .as_ref()?;
+        let imported = export.imported_name.as_deref().unwrap_or(name);
+        return resolve_origin(inner, target, imported, visited);
+    }
+
+    // `export *` doesn't forward the default export
+    if name == "default" {
+        return None;
+    }
+    module
+        .imports
+        .iter()
+        .filter(|import| {
+            import.kind == ImportKind::ReExport
+                && import
+                    .specifiers
+                    .iter()
+                    .any(|specifier| matches!(specifier, ImportSpecifier::Namespace(_)))
+        })
+        .filter_map(|import| import.resolved_to.as_ref())
+        .find_map(|target| resolve_origin(inner, target, name, visited))
+}
+
+/// Modules reachable from `starts` (included) without passing through `exclude`.
+fn reachable<'a>(
+    inner: &GraphInner,
+    starts: impl IntoIterator<Item = &'a ModuleId>,
+    exclude: &ModuleId,
+) -> HashSet<ModuleId> {
+    let mut seen = HashSet::default();
+    let mut queue: VecDeque<&ModuleId> = starts.into_iter().collect();
+    while let Some(id) = queue.pop_front() {
+        if id == exclude || !seen.insert(id.clone()) {
+            continue;
+        }
+        queue.extend(inner.dependencies.get(id).into_iter().flatten());
+    }
+    seen
+}
+
+fn total_size(inner: &GraphInner, modules: &HashSet<ModuleId>) -> usize {
+    modules
+        .iter()
+        .filter_map(|id| inner.modules.get(id))
+        .map(|module| module.original_size)
+        .sum()
+}
+
+/// Specifier for importing `origin` directly from `importer`.
+///
+/// Package barrels are bypassed through the package name; local modules get
+/// a relative path without a JS/TS extension, as bundlers resolve those.
+fn direct_specifier(importer: &Module, barrel_source: &str, origin: &Module) -> String {
+    if !barrel_source.starts_with('.') && !barrel_source.starts_with('/') {
+        if let Some((name, root)) = installed_package(&origin.path) {
+            if let Ok(rest) = origin.path.strip_prefix(&root) {
+                return format!("{}/{}", name, rest.to_string_lossy().replace('\\', "/"));
+            }
+        }
+    }
+
+    let from = importer.path.parent().unwrap_or(Path::new(""));
+    let mut relative = relative_path(from, &origin.path);
+    if relative
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| matches!(ext, "js" | "jsx" | "ts" | "tsx"))
+    {
+        relative.set_extension("");
+    }
+    let relative = relative.to_string_lossy().replace('\\', "/");
+    if relative.starts_with("../") {
+        relative
+    } else {
+        format!("./{}", relative)
+    }
+}
+
+fn relative_path(from: &Path, to: &Path) -> PathBuf {
+    let from: Vec<Component<'_>> = from.components().collect();
+    let to: Vec<Component<'_>> = to.components().collect();
+    let common = from.iter().zip(&to).take_while(|(a, b)| a == b).count();
+
+    let mut path = PathBuf::new();
+    for _ in common..from.len() {
+        path.push("..");
+    }
+    path.extend(&to[common..]);
+    path
+}
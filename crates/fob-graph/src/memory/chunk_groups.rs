@@ -0,0 +1,102 @@
+//! Shared chunk suggestions for ModuleGraph.
+
+use std::collections::VecDeque;
+
+use rustc_hash::{FxHashMap as HashMap, FxHashSet as HashSet};
+
+use super::super::ModuleId;
+use super::super::package_json::installed_package;
+use super::graph::{GraphInner, ModuleGraph};
+use super::types::{ChunkGroupKind, ChunkGroupOptions, ChunkGroupSuggestion};
+use crate::Result;
+
+impl ModuleGraph {
+    /// Suggest vendor and common chunk groups for a multi-entry build.
+    ///
+    /// A module is grouped when at least `min_entries` entry points load it
+    /// through static imports. Installed packages go to `vendor` and the
+    /// project's own modules to `common`; modules behind dynamic imports get
+    /// their own chunks anyway and are not counted. Entry points themselves
+    /// are never grouped.
+    pub fn suggest_chunk_groups(
+        &self,
+        options: &ChunkGroupOptions,
+    ) -> Result<Vec<ChunkGroupSuggestion>> {
+        let inner = self.inner.read();
+
+        let mut loaded_by: HashMap<&ModuleId, Vec<&ModuleId>> = HashMap::default();
+        for entry in &inner.entry_points {
+            for id in statically_loaded(&inner, entry) {
+                loaded_by.entry(id).or_default().push(entry);
+            }
+        }
+
+        let mut groups = Vec::new();
+        for kind in [ChunkGroupKind::Vendor, ChunkGroupKind::Common] {
+            let mut modules = Vec::new();
+            let mut entries = HashSet::default();
+            for (id, loaders) in &loaded_by {
+                let is_vendor = installed_package(id.as_path()).is_some();
+                if loaders.len() < options.min_entries.max(1)
+                    || inner.entry_points.contains(*id)
+                    || is_vendor != (kind == ChunkGroupKind::Vendor)
+                {
+                    continue;
+                }
+                modules.push((*id).clone());
+                entries.extend(loaders.iter().copied());
+            }
+
+            let size = modules
+                .iter()
+                .filter_map(|id| inner.modules.get(id))
+                .map(|module| module.original_size)
+                .sum();
+            if modules.is_empty() || size < options.min_size {
+                continue;
+            }
+            modules.sort_by(|a, b| a.as_path().cmp(b.as_path()));
+            let mut entries: Vec<ModuleId> = entries.into_iter().cloned().collect();
+            entries.sort_by(|a, b| a.as_path().cmp(b.as_path()));
+            groups.push(ChunkGroupSuggestion {
+                name: match kind {
+                    ChunkGroupKind::Vendor => "vendor".to_string(),
+                    ChunkGroupKind::Common => "common".to_string(),
+                },
+                kind,
+                modules,
+                entries,
+                size,
+            });
+        }
+
+        Ok(groups)
+    }
+}
+
+/// Modules an entry point loads through static imports, including itself.
+///
+/// Edges without import records (added with `add_dependency`) count as static.
+fn statically_loaded<'a>(inner: &'a GraphInner, entry: &'a ModuleId) -> HashSet<&'a ModuleId> {
+    let mut loaded = HashSet::default();
+    let mut queue = VecDeque::from([entry]);
+    while let Some(id) = queue.pop_front() {
+        if !loaded.insert(id) {
+            continue;
+        }
+        let module = inner.modules.get(id);
+        for dependency in inner.dependencies.get(id).into_iter().flatten() {
+            let mut imports = module
+                .into_iter()
+                .flat_map(|module| module.imports.iter())
+                .filter(|import| import.resolved_to.as_ref() == Some(dependency))
+                .peekable();
+            let dynamic_only =
+                imports.peek().is_some() && imports.all(|import| import.is_dynamic());
+            if !dynamic_only {
+                queue.push_back(dependency);
+            }
+        }
+    }
+    loaded
+}
@@ -2,7 +2,14 @@
 
 use rustc_hash::FxHashMap as HashMap;
 
+use super::super::collection::CollectedModule;
 use super::super::external_dep::ExternalDependency;
+use super::super::from_collection::{
+    PendingImport, convert_collected_exports, convert_collected_imports, has_star_export,
+    infer_exports_kind,
+};
+use super::super::module::ModuleFormat as FobModuleFormat;
+use super::super::semantic::analyze_symbols;
 use super::super::{Module, ModuleId};
 use super::graph::{GraphInner, ModuleGraph};
 use crate::{Error, Result};
@@ -31,12 +38,7 @@ impl ModuleGraph {
     pub fn from_collected_data(
         collection: super::super::collection::CollectionState,
     ) -> Result<Self> {
-        use super::super::from_collection::{
-            PendingImport, convert_collected_exports, convert_collected_imports,
-            convert_collected_module_id, has_star_export, infer_exports_kind,
-        };
-        use super::super::module::ModuleFormat as FobModuleFormat;
-        use super::super::semantic::analyze_symbols;
+        use super::super::from_collection::convert_collected_module_id;
 
         let graph = Self::new()?;
 
@@ -69,46 +71,7 @@ impl ModuleGraph {
                 Error::InvalidConfig(format!("Module ID not found for path: {}", path))
             })?;
 
-            let exports = convert_collected_exports(collected, module_id);
-            let imports = convert_collected_imports(collected, module_id, &path_to_id);
-
-            let has_side_effects = collected.has_side_effects;
-
-            // Perform semantic analysis to extract symbols
-            let source_type = super::super::SourceType::from_path(module_id.as_path());
-            let code = collected.code.as_deref().unwrap_or("");
-            let mut symbol_table = analyze_symbols(
-                code,
-                module_id.as_path().to_str().unwrap_or("unknown"),
-                source_type,
-            )
-            .unwrap_or_default();
-
-            // Link exports to symbols - mark symbols as exported
-            let export_names: Vec<String> = exports.iter().map(|e| e.name.clone()).collect();
-            symbol_table.mark_exports(&export_names);
-
-            let mut builder = Module::builder(
-                module_id.clone(),
-                module_id.as_path().to_path_buf(),
-                source_type,
-            )
-            .exports(exports)
-            .side_effects(has_side_effects)
-            .original_size(code.len())
-            .bundled_size(None)
-            .external(false)
-            .symbol_table(symbol_table)
-            .module_format(FobModuleFormat::Unknown)
-            .exports_kind(infer_exports_kind(&collected.exports))
-            .has_star_exports(has_star_export(&collected.exports))
-            .execution_order(None);
-
-            if collected.is_entry {
-                builder = builder.entry(true);
-            }
-
-            let module = builder.build();
+            let (module, imports) = convert_collected_module(module_id, collected, &path_to_id);
             pending_modules.push((module_id.clone(), module, imports));
         }
 
@@ -145,3 +108,51 @@ impl ModuleGraph {
         Ok(graph)
     }
 }
+
+/// Convert a collected module, leaving its imports to be resolved against the graph.
+pub(super) fn convert_collected_module(
+    module_id: &ModuleId,
+    collected: &CollectedModule,
+    path_to_id: &std::collections::HashMap<String, ModuleId>,
+) -> (Module, Vec<PendingImport>) {
+    let exports = convert_collected_exports(collected, module_id);
+    let imports = convert_collected_imports(collected, module_id, path_to_id);
+
+    let has_side_effects = collected.has_side_effects;
+
+    // Perform semantic analysis to extract symbols
+    let source_type = super::super::SourceType::from_path(module_id.as_path());
+    let code = collected.code.as_deref().unwrap_or("");
+    let mut symbol_table = analyze_symbols(
+        code,
+        module_id.as_path().to_str().unwrap_or("unknown"),
+        source_type,
+    )
+    .unwrap_or_default();
+
+    // Link exports to symbols - mark symbols as exported
+    let export_names: Vec<String> = exports.iter().map(|e| e.name.clone()).collect();
+    symbol_table.mark_exports(&export_names);
+
+    let mut builder = Module::builder(
+        module_id.clone(),
+        module_id.as_path().to_path_buf(),
+        source_type,
+    )
+    .exports(exports)
+    .side_effects(has_side_effects)
+    .original_size(code.len())
+    .bundled_size(None)
+    .external(false)
+    .symbol_table(symbol_table)
+    .module_format(FobModuleFormat::Unknown)
+    .exports_kind(infer_exports_kind(&collected.exports))
+    .has_star_exports(has_star_export(&collected.exports))
+    .execution_order(None);
+
+    if collected.is_entry {
+        builder = builder.entry(true);
+    }
+
+    (builder.build(), imports)
+}
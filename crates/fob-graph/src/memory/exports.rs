@@ -4,7 +4,7 @@ use std::sync::Arc;
 
 use rustc_hash::{FxHashMap as HashMap, FxHashSet as HashSet};
 
-use super::super::import::{ImportKind, ImportSpecifier};
+use super::super::import::{Import, ImportKind, ImportSpecifier};
 use super::super::{ExportKind, Module, ModuleId};
 use super::graph::{GraphInner, ModuleGraph};
 use crate::{Error, Result};
@@ -42,6 +42,20 @@ impl ModuleGraph {
         module_id: &ModuleId,
         export_name: &str,
     ) -> Result<bool> {
+        Self::is_export_used_visiting(inner, module_id, export_name, &mut HashSet::default())
+    }
+
+    /// Check export usage, following re-exports; `visited` breaks re-export cycles.
+    fn is_export_used_visiting(
+        inner: &GraphInner,
+        module_id: &ModuleId,
+        export_name: &str,
+        visited: &mut HashSet<(ModuleId, String)>,
+    ) -> Result<bool> {
+        if !visited.insert((module_id.clone(), export_name.to_string())) {
+            return Ok(false);
+        }
+
         let dependents = inner.dependents.get(module_id).cloned().unwrap_or_default();
 
         for importer_id in dependents {
@@ -51,11 +65,31 @@ impl ModuleGraph {
                         continue;
                     }
 
+                    // `import()` resolves to the whole module namespace
+                    if import_record.is_dynamic() {
+                        return Ok(true);
+                    }
+
                     if import_record.specifiers.is_empty() {
                         // Side-effect import does not use exports.
                         continue;
                     }
 
+                    if matches!(import_record.kind, ImportKind::ReExport) {
+                        // Re-exports only forward; the export is used if the
+                        // name it is re-exported as is used
+                        if Self::is_re_export_used(
+                            inner,
+                            importer,
+                            import_record,
+                            export_name,
+                            visited,
+                        )? {
+                            return Ok(true);
+                        }
+                        continue;
+                    }
+
                     let is_used =
                         import_record
                             .specifiers
@@ -96,7 +130,12 @@ impl ModuleGraph {
                             if re_exported_from == source_path.as_ref() {
                                 // This module re-exports all exports from our module
                                 // Recursively check if this re-exporting module's export is used
-                                if Self::is_export_used_inner(inner, re_exporter_id, export_name)? {
+                                if Self::is_export_used_visiting(
+                                    inner,
+                                    re_exporter_id,
+                                    export_name,
+                                    visited,
+                                )? {
                                     return Ok(true);
                                 }
                             }
@@ -109,10 +148,11 @@ impl ModuleGraph {
                                 if re_exported_from == source_path.as_ref() {
                                     // This is a named re-export of our specific export
                                     // Recursively check if THIS re-export is used
-                                    if Self::is_export_used_inner(
+                                    if Self::is_export_used_visiting(
                                         inner,
                                         re_exporter_id,
                                         &export.name,
+                                        visited,
                                     )? {
                                         return Ok(true);
                                     }
@@ -130,6 +170,46 @@ impl ModuleGraph {
         Ok(false)
     }
 
+    /// Check whether `export_name` of a module is used through a re-export of it.
+    fn is_re_export_used(
+        inner: &GraphInner,
+        re_exporter: &Module,
+        import_record: &Import,
+        export_name: &str,
+        visited: &mut HashSet<(ModuleId, String)>,
+    ) -> Result<bool> {
+        for specifier in &import_record.specifiers {
+            let forwarded_as: Vec<&str> = match specifier {
+                ImportSpecifier::Named(imported) if imported == export_name => re_exporter
+                    .exports
+                    .iter()
+                    .filter(|export| {
+                        matches!(export.kind, ExportKind::ReExport)
+                            && export.re_exported_from.as_deref()
+                                == Some(import_record.source.as_str())
+                            && export.imported_name.as_deref().unwrap_or(&export.name)
+                                == export_name
+                    })
+                    .map(|export| export.name.as_str())
+                    .collect(),
+                // `export *` forwards every name except the default export
+                ImportSpecifier::Namespace(_) if export_name != "default" => vec![export_name],
+                _ => Vec::new(),
+            };
+
+            for name in forwarded_as {
+                // Exports of entry points are public API
+                if re_exporter.is_entry
+                    || Self::is_export_used_visiting(inner, &re_exporter.id, name, visited)?
+                {
+                    return Ok(true);
+                }
+            }
+        }
+
+        Ok(false)
+    }
+
     /// Computes and sets usage counts for all exports in the module graph.
     ///
     /// For each export in each module, this counts how many times it's imported
@@ -205,7 +285,7 @@ impl ModuleGraph {
     ///
     /// This works with a read lock on modules HashMap, counting how many times
     /// an export is imported by dependent modules.
-    fn count_export_usage_standalone(
+    pub(super) fn count_export_usage_standalone(
         modules: &HashMap<ModuleId, Arc<Module>>,
         module_id: &ModuleId,
         export_name: &str,
@@ -220,6 +300,12 @@ impl ModuleGraph {
                         continue;
                     }
 
+                    // `import()` resolves to the whole module namespace
+                    if import_record.is_dynamic() {
+                        count += 1;
+                        continue;
+                    }
+
                     if import_record.specifiers.is_empty() {
                         // Side-effect import does not use exports.
                         continue;
@@ -1,7 +1,12 @@
 //! Import analysis methods for ModuleGraph.
 
-use super::graph::ModuleGraph;
-use super::types::{NamespaceImportInfo, SideEffectImport, TypeOnlyImport};
+use std::collections::VecDeque;
+
+use rustc_hash::FxHashSet as HashSet;
+
+use super::super::ModuleId;
+use super::graph::{GraphInner, ModuleGraph};
+use super::types::{DynamicImportBoundary, NamespaceImportInfo, SideEffectImport, TypeOnlyImport};
 use crate::Result;
 
 impl ModuleGraph {
@@ -81,4 +86,100 @@ impl ModuleGraph {
 
         Ok(type_imports)
     }
+
+    /// Get all dynamic `import()` boundaries.
+    ///
+    /// Each boundary lists the modules it loads that no entry point reaches
+    /// through static imports, i.e. the code split off into lazy chunks.
+    /// Results are sorted by importer path and source.
+    pub fn dynamic_import_boundaries(&self) -> Result<Vec<DynamicImportBoundary>> {
+        let inner = self.inner.read();
+        let eager = eagerly_loaded(&inner);
+
+        let mut boundaries = Vec::new();
+        for module in inner.modules.values() {
+            for import in module.imports.iter().filter(|import| import.is_dynamic()) {
+                let mut lazy_modules: Vec<ModuleId> = match &import.resolved_to {
+                    Some(target) => reachable(&inner, target)
+                        .into_iter()
+                        .filter(|id| !eager.contains(id))
+                        .collect(),
+                    None => Vec::new(),
+                };
+                lazy_modules.sort_by(|a, b| a.as_path().cmp(b.as_path()));
+                let lazy_size = lazy_modules
+                    .iter()
+                    .filter_map(|id| inner.modules.get(id))
+                    .map(|module| module.original_size)
+                    .sum();
+                boundaries.push(DynamicImportBoundary {
+                    importer: module.id.clone(),
+                    source: import.source.clone(),
+                    resolved_to: import.resolved_to.clone(),
+                    span: import.span.clone(),
+                    lazy_modules,
+                    lazy_size,
+                });
+            }
+        }
+
+        boundaries.sort_by(|a, b| {
+            a.importer
+                .as_path()
+                .cmp(b.importer.as_path())
+                .then_with(|| a.source.cmp(&b.source))
+        });
+        Ok(boundaries)
+    }
+
+    /// Get modules that are only loaded through dynamic imports.
+    ///
+    /// These are reachable from an entry point, but not through static
+    /// imports alone. Results are sorted by path.
+    pub fn lazily_loaded_modules(&self) -> Result<Vec<ModuleId>> {
+        let inner = self.inner.read();
+        let eager = eagerly_loaded(&inner);
+
+        let mut lazy = HashSet::default();
+        for entry in &inner.entry_points {
+            lazy.extend(reachable(&inner, entry));
+        }
+        let mut lazy: Vec<ModuleId> = lazy.into_iter().filter(|id| !eager.contains(id)).collect();
+        lazy.sort_by(|a, b| a.as_path().cmp(b.as_path()));
+        Ok(lazy)
+    }
+}
+
+/// Modules the entry points load through static runtime imports.
+fn eagerly_loaded(inner: &GraphInner) -> HashSet<ModuleId> {
+    let mut loaded = HashSet::default();
+    let mut queue: VecDeque<&ModuleId> = inner.entry_points.iter().collect();
+    while let Some(id) = queue.pop_front() {
+        if !loaded.insert(id.clone()) {
+            continue;
+        }
+        let Some(module) = inner.modules.get(id) else {
+            continue;
+        };
+        queue.extend(
+            module
+                .imports
+                .iter()
+                .filter(|import| import.kind.is_static())
+                .filter_map(|import| import.resolved_to.as_ref()),
+        );
+    }
+    loaded
+}
+
+/// A module and everything it imports, directly or transitively.
+fn reachable(inner: &GraphInner, start: &ModuleId) -> HashSet<ModuleId> {
+    let mut visited = HashSet::default();
+    let mut queue = VecDeque::from([start]);
+    while let Some(id) = queue.pop_front() {
+        if visited.insert(id.clone()) {
+            queue.extend(inner.dependencies.get(id).into_iter().flatten());
+        }
+    }
+    visited
 }
@@ -0,0 +1,245 @@
+//! Incremental updates for ModuleGraph.
+//!
+//! Watch mode and editor tooling re-collect single files; these methods
+//! patch the graph in place instead of rebuilding it from a full
+//! collection. Dependency chains are computed on demand from the edges,
+//! so only export usage counts need to be invalidated.
+
+use std::sync::Arc;
+
+use rustc_hash::FxHashSet as HashSet;
+
+use super::super::collection::CollectedModule;
+use super::super::external_dep::ExternalDependency;
+use super::super::from_collection::{PendingImport, convert_collected_module_id};
+use super::super::{Module, ModuleId};
+use super::construction::convert_collected_module;
+use super::graph::{GraphInner, ModuleGraph};
+use super::types::ModuleChange;
+use crate::Result;
+
+impl ModuleGraph {
+    /// Replace a module with a freshly collected version, or add it if new.
+    ///
+    /// The module's outgoing edges and external imports are rebuilt from
+    /// `module`. Imports resolve against modules already in the graph;
+    /// anything else is recorded as an external dependency, as in
+    /// [`ModuleGraph::from_collected_data`]. Imports of other modules are
+    /// not re-resolved, so when a file is created, apply it before its
+    /// importers. The module stays an entry point if it was one.
+    ///
+    /// If usage counts were computed, they are recomputed for the module
+    /// and for every module it imported before or after the change.
+    pub fn apply_change(
+        &self,
+        module_id: ModuleId,
+        module: CollectedModule,
+    ) -> Result<ModuleChange> {
+        // Symbol analysis is the expensive part; do it before taking the lock
+        let (mut new_module, pending_imports) =
+            convert_collected_module(&module_id, &module, &Default::default());
+        let targets: Vec<Option<ModuleId>> = pending_imports
+            .iter()
+            .map(|pending| {
+                pending
+                    .target
+                    .as_deref()
+                    .and_then(|target| convert_collected_module_id(target).ok())
+            })
+            .collect();
+
+        let mut inner = self.inner.write();
+
+        let old_module = inner.modules.get(&module_id).cloned();
+        let counted = old_module.as_deref().is_some_and(has_usage_counts);
+        let old_dependencies = detach_imports(&mut inner, &module_id);
+
+        let mut dependencies = HashSet::default();
+        let mut imports = Vec::with_capacity(pending_imports.len());
+        for (PendingImport { mut import, target }, target_id) in
+            pending_imports.into_iter().zip(targets)
+        {
+            let target_id =
+                target_id.filter(|id| *id == module_id || inner.modules.contains_key(id));
+            if let Some(target_id) = target_id {
+                import.resolved_to = Some(target_id.clone());
+                dependencies.insert(target_id);
+            } else if let Some(target) = target {
+                let dep = inner
+                    .external_deps
+                    .entry(target.clone())
+                    .or_insert_with(|| ExternalDependency::new(target));
+                if !dep.importers.contains(&module_id) {
+                    dep.push_importer(module_id.clone());
+                }
+            }
+            imports.push(import);
+        }
+        new_module.imports = Arc::new(imports);
+
+        for dependency in &dependencies {
+            inner
+                .dependencies
+                .entry(module_id.clone())
+                .or_default()
+                .insert(dependency.clone());
+            inner
+                .dependents
+                .entry(dependency.clone())
+                .or_default()
+                .insert(module_id.clone());
+        }
+
+        if inner.entry_points.contains(&module_id) {
+            new_module.is_entry = true;
+        } else if new_module.is_entry {
+            inner.entry_points.insert(module_id.clone());
+        }
+        inner
+            .modules
+            .insert(module_id.clone(), Arc::new(new_module));
+
+        // Counts are computed graph-wide, so any counted module means all are
+        let counted = counted
+            || old_dependencies
+                .union(&dependencies)
+                .filter_map(|id| inner.modules.get(id))
+                .any(|module| has_usage_counts(module));
+        let mut recounted = Vec::new();
+        if counted {
+            let mut affected: HashSet<ModuleId> =
+                old_dependencies.union(&dependencies).cloned().collect();
+            affected.insert(module_id.clone());
+            for id in affected {
+                if recount_usage(&mut inner, &id)? {
+                    recounted.push(id);
+                }
+            }
+        }
+
+        Ok(ModuleChange {
+            is_new: old_module.is_none(),
+            added_dependencies: dependencies
+                .difference(&old_dependencies)
+                .cloned()
+                .collect(),
+            removed_dependencies: old_dependencies
+                .difference(&dependencies)
+                .cloned()
+                .collect(),
+            recounted,
+        })
+    }
+
+    /// Remove a module, e.g. after its file was deleted.
+    ///
+    /// Imports of the module in other modules become unresolved and are
+    /// recorded as an external dependency keyed by its path, matching how
+    /// [`ModuleGraph::from_collected_data`] records imports of missing files.
+    /// Returns the removed module, or `None` if it was not in the graph.
+    pub fn remove_module(&self, module_id: &ModuleId) -> Result<Option<Module>> {
+        let mut inner = self.inner.write();
+
+        let Some(module) = inner.modules.remove(module_id) else {
+            return Ok(None);
+        };
+        inner.entry_points.remove(module_id);
+        let old_dependencies = detach_imports(&mut inner, module_id);
+
+        let importers = inner.dependents.remove(module_id).unwrap_or_default();
+        if !importers.is_empty() {
+            for importer_id in &importers {
+                let now_empty = match inner.dependencies.get_mut(importer_id) {
+                    Some(dependencies) => {
+                        dependencies.remove(module_id);
+                        dependencies.is_empty()
+                    }
+                    None => false,
+                };
+                if now_empty {
+                    inner.dependencies.remove(importer_id);
+                }
+
+                if let Some(importer) = inner.modules.get(importer_id) {
+                    let mut importer = (**importer).clone();
+                    for import in Arc::make_mut(&mut importer.imports).iter_mut() {
+                        if import.resolved_to.as_ref() == Some(module_id) {
+                            import.resolved_to = None;
+                        }
+                    }
+                    inner
+                        .modules
+                        .insert(importer_id.clone(), Arc::new(importer));
+                }
+            }
+
+            let specifier = module_id.path_string().to_string();
+            inner
+                .external_deps
+                .entry(specifier.clone())
+                .or_insert_with(|| ExternalDependency::new(specifier))
+                .extend_importers(importers);
+        }
+
+        if has_usage_counts(&module) {
+            for id in old_dependencies {
+                recount_usage(&mut inner, &id)?;
+            }
+        }
+
+        Ok(Some(Arc::unwrap_or_clone(module)))
+    }
+}
+
+/// Remove a module's outgoing edges and external imports, returning its former dependencies.
+fn detach_imports(inner: &mut GraphInner, module_id: &ModuleId) -> HashSet<ModuleId> {
+    let dependencies = inner.dependencies.remove(module_id).unwrap_or_default();
+    for dependency in &dependencies {
+        let now_empty = match inner.dependents.get_mut(dependency) {
+            Some(dependents) => {
+                dependents.remove(module_id);
+                dependents.is_empty()
+            }
+            None => false,
+        };
+        if now_empty {
+            inner.dependents.remove(dependency);
+        }
+    }
+
+    // Drop externals only this module imported, but keep ones registered without importers
+    inner.external_deps.retain(|_, dep| {
+        let before = dep.importers.len();
+        dep.importers.retain(|importer| importer != module_id);
+        dep.importers.len() == before || !dep.importers.is_empty()
+    });
+
+    dependencies
+}
+
+/// Recompute the usage counts of a module's exports, returning whether it exists.
+fn recount_usage(inner: &mut GraphInner, module_id: &ModuleId) -> Result<bool> {
+    let Some(module) = inner.modules.get(module_id) else {
+        return Ok(false);
+    };
+    let mut module = (**module).clone();
+    let dependents = inner.dependents.get(module_id).cloned().unwrap_or_default();
+    for export in Arc::make_mut(&mut module.exports).iter_mut() {
+        let count = ModuleGraph::count_export_usage_standalone(
+            &inner.modules,
+            module_id,
+            &export.name,
+            &dependents,
+        )?;
+        export.set_usage_count(count);
+    }
+    inner.modules.insert(module_id.clone(), Arc::new(module));
+    Ok(true)
+}
+
+fn has_usage_counts(module: &Module) -> bool {
+    module
+        .exports
+        .iter()
+        .any(|export| export.usage_count.is_some())
+}
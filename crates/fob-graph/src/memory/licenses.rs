@@ -0,0 +1,90 @@
+//! License audit over the external dependencies of a ModuleGraph.
+
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use super::super::ModuleId;
+use super::super::license::{
+    LicensePolicy, LicenseReport, LicenseSource, PackageLicense, find_package_root,
+    read_package_license,
+};
+use super::super::package_json::extract_package_name;
+use super::graph::ModuleGraph;
+use crate::Result;
+use crate::runtime::Runtime;
+
+/// External imports of one installed package copy, collected under the lock.
+#[derive(Default)]
+struct PackageUse {
+    specifiers: Vec<String>,
+    importers: Vec<ModuleId>,
+}
+
+impl ModuleGraph {
+    /// Report the license of every external package and check it against a policy.
+    ///
+    /// Each external dependency is located by searching `node_modules`
+    /// upward from its importers, so importers that load different copies
+    /// get separate entries. The license is read from the package's
+    /// package.json, falling back to its LICENSE file. Every package lists
+    /// the entry points that import it directly or transitively, which are
+    /// the outputs its license applies to. Packages are sorted by name.
+    pub async fn license_audit<R: Runtime + ?Sized>(
+        &self,
+        runtime: &R,
+        policy: &LicensePolicy,
+    ) -> Result<LicenseReport> {
+        let mut packages: BTreeMap<(String, Option<PathBuf>), PackageUse> = BTreeMap::new();
+        for dependency in self.external_dependencies()? {
+            let name = extract_package_name(&dependency.specifier);
+            // Relative imports of missing files and node: builtins are not packages
+            if name.is_empty()
+                || name.starts_with('.')
+                || name.starts_with('/')
+                || name.contains(':')
+            {
+                continue;
+            }
+            for importer in dependency.importers {
+                let root = if importer.is_virtual() {
+                    None
+                } else {
+                    find_package_root(runtime, name, importer.as_path())
+                };
+                let package = packages.entry((name.to_string(), root)).or_default();
+                if !package.specifiers.contains(&dependency.specifier) {
+                    package.specifiers.push(dependency.specifier.clone());
+                }
+                if !package.importers.contains(&importer) {
+                    package.importers.push(importer);
+                }
+            }
+        }
+
+        let mut report = LicenseReport::default();
+        for ((name, root), mut package) in packages {
+            let (version, license, source) = match &root {
+                Some(root) => read_package_license(runtime, root).await,
+                None => (None, None, LicenseSource::Unknown),
+            };
+            package.specifiers.sort();
+            package
+                .importers
+                .sort_by(|a, b| a.as_path().cmp(b.as_path()));
+            let entries = self.affected_entries(&package.importers)?;
+            report.packages.push(PackageLicense {
+                name,
+                version,
+                root,
+                allowed: policy.is_allowed(license.as_deref()),
+                license,
+                source,
+                specifiers: package.specifiers,
+                importers: package.importers,
+                entries,
+            });
+        }
+
+        Ok(report)
+    }
+}
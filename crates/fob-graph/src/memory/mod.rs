@@ -3,24 +3,38 @@
 //! This provides a HashMap-based graph storage that is fully in-memory,
 //! making it compatible with WASM environments and simple to use.
 
+mod barrels;
 mod chains;
+mod chunk_groups;
 mod construction;
 mod exports;
 mod framework;
 mod graph;
 mod imports;
+mod incremental;
+mod licenses;
 mod mutations;
 mod package_json;
 mod queries;
+mod query;
+mod reachability;
 mod serialization;
 mod statistics;
+mod storage;
 mod symbols;
 mod traversal;
+mod type_imports;
 mod types;
+mod visualization;
 
 // Re-export types
+pub use query::GraphQuery;
 pub use types::{
-    ClassMemberInfo, EnumMemberInfo, NamespaceImportInfo, SideEffectImport, TypeOnlyImport,
+    BarrelFile, BarrelImport, ChunkGroupKind, ChunkGroupOptions, ChunkGroupSuggestion,
+    ClassMemberInfo, DirectImport, DynamicImportBoundary, EnumMemberInfo, GraphExportOptions,
+    GraphNodeKind, ImportCodemod, ModuleChange, NamespaceImportInfo, SideEffectImport,
+    SymbolReachability, SymbolReachabilityInfo, TypeImportIssue, TypeImportIssueKind,
+    TypeOnlyImport,
 };
 
 // Re-export ModuleGraph
@@ -29,8 +43,12 @@ pub use graph::ModuleGraph;
 // Import implementations - these add methods to ModuleGraph
 // These wildcard imports are intentional - they add impl blocks to ModuleGraph
 #[allow(unused_imports)]
+use barrels::*;
+#[allow(unused_imports)]
 use chains::*;
 #[allow(unused_imports)]
+use chunk_groups::*;
+#[allow(unused_imports)]
 use construction::*;
 #[allow(unused_imports)]
 use exports::*;
@@ -39,16 +57,28 @@ use framework::*;
 #[allow(unused_imports)]
 use imports::*;
 #[allow(unused_imports)]
+use incremental::*;
+#[allow(unused_imports)]
+use licenses::*;
+#[allow(unused_imports)]
 use mutations::*;
 #[allow(unused_imports)]
 use package_json::*;
 #[allow(unused_imports)]
 use queries::*;
 #[allow(unused_imports)]
+use reachability::*;
+#[allow(unused_imports)]
 use serialization::*;
 #[allow(unused_imports)]
 use statistics::*;
 #[allow(unused_imports)]
+use storage::*;
+#[allow(unused_imports)]
 use symbols::*;
 #[allow(unused_imports)]
 use traversal::*;
+#[allow(unused_imports)]
+use type_imports::*;
+#[allow(unused_imports)]
+use visualization::*;
@@ -1,10 +1,13 @@
 //! Package.json analysis methods for ModuleGraph.
 
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
 use rustc_hash::FxHashSet as HashSet;
 
 use super::super::package_json::{
-    DependencyCoverage, DependencyType, PackageJson, TypeCoverage, UnusedDependency,
-    extract_package_name,
+    DependencyCoverage, DependencyType, DuplicatePackage, PackageCopy, PackageJson, TypeCoverage,
+    UnusedDependency, extract_package_name, installed_package,
 };
 use super::graph::ModuleGraph;
 use crate::Result;
@@ -124,4 +127,58 @@ impl ModuleGraph {
             by_type,
         })
     }
+
+    /// Detect packages bundled from more than one installed copy.
+    ///
+    /// Modules are grouped by their innermost `node_modules` package
+    /// directory; a name with several directories is a duplicate. Versions
+    /// are not read from disk; see [`DuplicatePackage::load_versions`].
+    /// Results are sorted by package name.
+    pub fn duplicate_packages(&self) -> Result<Vec<DuplicatePackage>> {
+        let inner = self.inner.read();
+
+        let mut packages: BTreeMap<String, BTreeMap<PathBuf, PackageCopy>> = BTreeMap::new();
+        for (id, module) in &inner.modules {
+            let Some((name, root)) = installed_package(id.as_path()) else {
+                continue;
+            };
+            let copy = packages
+                .entry(name)
+                .or_default()
+                .entry(root.clone())
+                .or_insert_with(|| PackageCopy {
+                    root,
+                    version: None,
+                    modules: Vec::new(),
+                    importers: Vec::new(),
+                    size: 0,
+                });
+            copy.modules.push(id.clone());
+            copy.size += module.original_size;
+        }
+
+        let mut duplicates = Vec::new();
+        for (name, copies) in packages {
+            if copies.len() < 2 {
+                continue;
+            }
+            let mut copies: Vec<PackageCopy> = copies.into_values().collect();
+            for copy in &mut copies {
+                let mut importers = HashSet::default();
+                for module_id in &copy.modules {
+                    for dependent in inner.dependents.get(module_id).into_iter().flatten() {
+                        if !dependent.as_path().starts_with(&copy.root) {
+                            importers.insert(dependent.clone());
+                        }
+                    }
+                }
+                copy.importers = importers.into_iter().collect();
+                copy.importers.sort_by(|a, b| a.as_path().cmp(b.as_path()));
+                copy.modules.sort_by(|a, b| a.as_path().cmp(b.as_path()));
+            }
+            duplicates.push(DuplicatePackage { name, copies });
+        }
+
+        Ok(duplicates)
+    }
 }
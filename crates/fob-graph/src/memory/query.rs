@@ -0,0 +1,451 @@
+//! Composable queries over a ModuleGraph.
+//!
+//! ```
+//! # use fob_graph::{ModuleGraph, Result};
+//! # fn example(graph: &ModuleGraph) -> Result<()> {
+//! let entries = graph
+//!     .query()
+//!     .transitive_importers_of("src/db.ts")
+//!     .that_are_entries()
+//!     .collect()?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::VecDeque;
+use std::path::{Path, PathBuf};
+
+use rustc_hash::{FxHashMap as HashMap, FxHashSet as HashSet};
+
+use super::super::dependency_chain::{DependencyChain, MAX_DEPENDENCY_CHAIN_DEPTH};
+use super::super::package_json::extract_package_name;
+use super::super::{Module, ModuleId};
+use super::graph::{GraphInner, ModuleGraph};
+use crate::{Error, Result};
+
+/// Edge direction to follow.
+#[derive(Debug, Clone, Copy)]
+enum Direction {
+    /// From a module to the modules it imports
+    Dependencies,
+    /// From a module to the modules importing it
+    Importers,
+}
+
+/// One step of a query, applied to the modules selected so far.
+enum Step<'a> {
+    /// Keep modules related to the modules matching a path
+    RelatedTo {
+        target: PathBuf,
+        direction: Direction,
+        transitive: bool,
+    },
+    /// Replace the selection with its neighbours
+    Expand {
+        direction: Direction,
+        transitive: bool,
+    },
+    Entries,
+    Filter(Box<dyn Fn(&Module) -> bool + 'a>),
+}
+
+/// A lazily evaluated module selection, built with [`ModuleGraph::query`].
+///
+/// A query starts with every module and each method narrows or moves the
+/// selection; nothing is evaluated until a terminal method such as
+/// [`GraphQuery::collect`]. Modules are named by path: a name matches a
+/// module whose path equals it or ends with it, so `"src/db.ts"` works for
+/// absolute module paths. Naming a module that isn't in the graph is an
+/// error, to catch typos.
+pub struct GraphQuery<'a> {
+    graph: &'a ModuleGraph,
+    steps: Vec<Step<'a>>,
+}
+
+impl ModuleGraph {
+    /// Start a query over all modules in the graph.
+    pub fn query(&self) -> GraphQuery<'_> {
+        GraphQuery {
+            graph: self,
+            steps: Vec::new(),
+        }
+    }
+
+    /// Find every path of imports from one module to another.
+    ///
+    /// Paths never visit a module twice, so cycles don't repeat. The search
+    /// stops at the dependency chain depth limit to keep large graphs from
+    /// exploding. Paths are sorted by length, then by module paths.
+    pub fn all_paths(&self, from: &ModuleId, to: &ModuleId) -> Result<Vec<DependencyChain>> {
+        let inner = self.inner.read();
+        Ok(all_paths(&inner, from, to, None))
+    }
+
+    /// Find a shortest path of imports from one module to another.
+    pub fn shortest_path(&self, from: &ModuleId, to: &ModuleId) -> Result<Option<DependencyChain>> {
+        let inner = self.inner.read();
+        Ok(shortest_path(&inner, from, to))
+    }
+}
+
+impl<'a> GraphQuery<'a> {
+    /// Keep modules that import `target` directly.
+    pub fn importers_of(self, target: impl AsRef<Path>) -> Self {
+        self.related_to(target, Direction::Importers, false)
+    }
+
+    /// Keep modules that import `target` directly or transitively.
+    pub fn transitive_importers_of(self, target: impl AsRef<Path>) -> Self {
+        self.related_to(target, Direction::Importers, true)
+    }
+
+    /// Keep modules that `target` imports directly.
+    pub fn dependencies_of(self, target: impl AsRef<Path>) -> Self {
+        self.related_to(target, Direction::Dependencies, false)
+    }
+
+    /// Keep modules that `target` imports directly or transitively.
+    pub fn transitive_dependencies_of(self, target: impl AsRef<Path>) -> Self {
+        self.related_to(target, Direction::Dependencies, true)
+    }
+
+    /// Replace the selection with the modules importing it.
+    pub fn importers(self) -> Self {
+        self.expand(Direction::Importers, false)
+    }
+
+    /// Replace the selection with everything importing it, directly or transitively.
+    pub fn transitive_importers(self) -> Self {
+        self.expand(Direction::Importers, true)
+    }
+
+    /// Replace the selection with the modules it imports.
+    pub fn dependencies(self) -> Self {
+        self.expand(Direction::Dependencies, false)
+    }
+
+    /// Replace the selection with everything it imports, directly or transitively.
+    pub fn transitive_dependencies(self) -> Self {
+        self.expand(Direction::Dependencies, true)
+    }
+
+    /// Keep entry points.
+    pub fn that_are_entries(mut self) -> Self {
+        self.steps.push(Step::Entries);
+        self
+    }
+
+    /// Keep modules with side effects.
+    pub fn with_side_effects(self) -> Self {
+        self.filter(|module| module.has_side_effects)
+    }
+
+    /// Keep modules with an export named `name`.
+    pub fn exporting(self, name: impl Into<String>) -> Self {
+        let name = name.into();
+        self.filter(move |module| module.exports.iter().any(|export| export.name == name))
+    }
+
+    /// Keep modules importing an external package, e.g. `"react"`.
+    ///
+    /// Subpath imports such as `react/jsx-runtime` count as importing the package.
+    pub fn importing_package(self, package: impl Into<String>) -> Self {
+        let package = package.into();
+        self.filter(move |module| {
+            module.imports.iter().any(|import| {
+                import.resolved_to.is_none() && extract_package_name(&import.source) == package
+            })
+        })
+    }
+
+    /// Keep modules under a directory.
+    pub fn in_directory(self, directory: impl Into<PathBuf>) -> Self {
+        let directory = directory.into();
+        self.filter(move |module| {
+            module
+                .path
+                .parent()
+                .into_iter()
+                .flat_map(Path::ancestors)
+                .any(|parent| parent.ends_with(&directory))
+        })
+    }
+
+    /// Keep modules matching a predicate.
+    pub fn filter(mut self, predicate: impl Fn(&Module) -> bool + 'a) -> Self {
+        self.steps.push(Step::Filter(Box::new(predicate)));
+        self
+    }
+
+    /// Evaluate the query, returning module ids sorted by path.
+    pub fn collect(self) -> Result<Vec<ModuleId>> {
+        let graph = self.graph;
+        let inner = graph.inner.read();
+        let mut ids: Vec<ModuleId> = self.evaluate(&inner)?.into_iter().collect();
+        ids.sort_by(|a, b| a.as_path().cmp(b.as_path()));
+        Ok(ids)
+    }
+
+    /// Evaluate the query, returning the modules sorted by path.
+    pub fn modules(self) -> Result<Vec<Module>> {
+        let graph = self.graph;
+        let inner = graph.inner.read();
+        let mut modules: Vec<Module> = self
+            .evaluate(&inner)?
+            .iter()
+            .filter_map(|id| inner.modules.get(id))
+            .map(|module| (**module).clone())
+            .collect();
+        modules.sort_by(|a, b| a.id.as_path().cmp(b.id.as_path()));
+        Ok(modules)
+    }
+
+    /// Count the selected modules.
+    pub fn count(self) -> Result<usize> {
+        let graph = self.graph;
+        let inner = graph.inner.read();
+        Ok(self.evaluate(&inner)?.len())
+    }
+
+    /// Whether the query selects any module.
+    pub fn exists(self) -> Result<bool> {
+        Ok(self.count()? > 0)
+    }
+
+    /// Find every import path from `from` to `to` that stays within the selection.
+    ///
+    /// Like [`ModuleGraph::all_paths`], but modules are named by path and
+    /// the earlier steps restrict which modules a path may pass through,
+    /// e.g. `graph.query().in_directory("src").paths("src/a.ts", "src/b.ts")`.
+    pub fn paths(
+        self,
+        from: impl AsRef<Path>,
+        to: impl AsRef<Path>,
+    ) -> Result<Vec<DependencyChain>> {
+        let graph = self.graph;
+        let inner = graph.inner.read();
+        let selection = self.evaluate(&inner)?;
+        let from = resolve_single(&inner, from.as_ref())?;
+        let to = resolve_single(&inner, to.as_ref())?;
+
+        if !selection.contains(&from) || !selection.contains(&to) {
+            return Ok(Vec::new());
+        }
+        Ok(all_paths(&inner, &from, &to, Some(&selection)))
+    }
+
+    fn related_to(
+        mut self,
+        target: impl AsRef<Path>,
+        direction: Direction,
+        transitive: bool,
+    ) -> Self {
+        self.steps.push(Step::RelatedTo {
+            target: target.as_ref().to_path_buf(),
+            direction,
+            transitive,
+        });
+        self
+    }
+
+    fn expand(mut self, direction: Direction, transitive: bool) -> Self {
+        self.steps.push(Step::Expand {
+            direction,
+            transitive,
+        });
+        self
+    }
+
+    fn evaluate(self, inner: &GraphInner) -> Result<HashSet<ModuleId>> {
+        let mut selection: HashSet<ModuleId> = inner.modules.keys().cloned().collect();
+        for step in self.steps {
+            selection = match step {
+                Step::RelatedTo {
+                    target,
+                    direction,
+                    transitive,
+                } => {
+                    let targets = resolve(inner, &target)?;
+                    let related = neighbours(inner, targets, direction, transitive);
+                    selection.retain(|id| related.contains(id));
+                    selection
+                }
+                Step::Expand {
+                    direction,
+                    transitive,
+                } => neighbours(inner, selection, direction, transitive),
+                Step::Entries => {
+                    selection.retain(|id| {
+                        inner.entry_points.contains(id)
+                            || inner.modules.get(id).is_some_and(|module| module.is_entry)
+                    });
+                    selection
+                }
+                Step::Filter(predicate) => {
+                    selection.retain(|id| {
+                        inner
+                            .modules
+                            .get(id)
+                            .is_some_and(|module| predicate(module))
+                    });
+                    selection
+                }
+            };
+        }
+        Ok(selection)
+    }
+}
+
+/// Modules whose path equals or ends with `target`.
+fn resolve(inner: &GraphInner, target: &Path) -> Result<HashSet<ModuleId>> {
+    let matches: HashSet<ModuleId> = inner
+        .modules
+        .keys()
+        .filter(|id| id.as_path() == target || id.as_path().ends_with(target))
+        .cloned()
+        .collect();
+    if matches.is_empty() {
+        return Err(Error::InvalidConfig(format!(
+            "No module in the graph matches {}",
+            target.display()
+        )));
+    }
+    Ok(matches)
+}
+
+/// The single module matching `target`, preferring an exact path match.
+fn resolve_single(inner: &GraphInner, target: &Path) -> Result<ModuleId> {
+    let mut matches: Vec<ModuleId> = resolve(inner, target)?.into_iter().collect();
+    if let Some(exact) = matches.iter().find(|id| id.as_path() == target) {
+        return Ok(exact.clone());
+    }
+    if matches.len() > 1 {
+        matches.sort_by(|a, b| a.as_path().cmp(b.as_path()));
+        let names: Vec<_> = matches.iter().map(|id| id.path_string()).collect();
+        return Err(Error::InvalidConfig(format!(
+            "{} matches several modules: {}",
+            target.display(),
+            names.join(", ")
+        )));
+    }
+    Ok(matches.remove(0))
+}
+
+/// Modules one edge (or any number of edges) away from `start`, excluding
+/// `start` itself unless it is reachable from another start module.
+fn neighbours(
+    inner: &GraphInner,
+    start: HashSet<ModuleId>,
+    direction: Direction,
+    transitive: bool,
+) -> HashSet<ModuleId> {
+    let edges = match direction {
+        Direction::Dependencies => &inner.dependencies,
+        Direction::Importers => &inner.dependents,
+    };
+    let mut found = HashSet::default();
+    let mut queue: VecDeque<&ModuleId> = start
+        .iter()
+        .flat_map(|id| edges.get(id).into_iter().flatten())
+        .collect();
+    while let Some(id) = queue.pop_front() {
+        if found.insert(id.clone()) && transitive {
+            queue.extend(edges.get(id).into_iter().flatten());
+        }
+    }
+    found
+}
+
+/// Dependencies of a module within `allowed`, sorted for deterministic traversal.
+fn sorted_dependencies<'a>(
+    inner: &'a GraphInner,
+    id: &ModuleId,
+    allowed: Option<&HashSet<ModuleId>>,
+) -> Vec<&'a ModuleId> {
+    let mut dependencies: Vec<&ModuleId> = inner
+        .dependencies
+        .get(id)
+        .into_iter()
+        .flatten()
+        .filter(|dependency| allowed.is_none_or(|allowed| allowed.contains(*dependency)))
+        .collect();
+    dependencies.sort_by(|a, b| a.as_path().cmp(b.as_path()));
+    dependencies
+}
+
+fn all_paths(
+    inner: &GraphInner,
+    from: &ModuleId,
+    to: &ModuleId,
+    allowed: Option<&HashSet<ModuleId>>,
+) -> Vec<DependencyChain> {
+    let mut paths = Vec::new();
+    let mut path = vec![from.clone()];
+    let mut on_path: HashSet<ModuleId> = HashSet::default();
+    on_path.insert(from.clone());
+    // Depth-first, with an iterator of unexplored dependencies per path module
+    let mut stack = vec![sorted_dependencies(inner, from, allowed).into_iter()];
+
+    while let Some(dependencies) = stack.last_mut() {
+        if path.last() == Some(to) || path.len() > MAX_DEPENDENCY_CHAIN_DEPTH {
+            if path.last() == Some(to) {
+                paths.push(DependencyChain::new(path.clone()));
+            }
+            stack.pop();
+            if let Some(id) = path.pop() {
+                on_path.remove(&id);
+            }
+            continue;
+        }
+        match dependencies.next() {
+            Some(next) if !on_path.contains(next) => {
+                on_path.insert(next.clone());
+                path.push(next.clone());
+                stack.push(sorted_dependencies(inner, next, allowed).into_iter());
+            }
+            Some(_) => {}
+            None => {
+                stack.pop();
+                if let Some(id) = path.pop() {
+                    on_path.remove(&id);
+                }
+            }
+        }
+    }
+
+    paths.sort_by(|a, b| {
+        a.depth.cmp(&b.depth).then_with(|| {
+            a.path
+                .iter()
+                .map(ModuleId::as_path)
+                .cmp(b.path.iter().map(ModuleId::as_path))
+        })
+    });
+    paths
+}
+
+fn shortest_path(inner: &GraphInner, from: &ModuleId, to: &ModuleId) -> Option<DependencyChain> {
+    let mut previous: HashMap<&ModuleId, &ModuleId> = HashMap::default();
+    let mut visited: HashSet<&ModuleId> = HashSet::default();
+    visited.insert(from);
+    let mut queue = VecDeque::from([from]);
+    while let Some(id) = queue.pop_front() {
+        if id == to {
+            let mut path = vec![id.clone()];
+            let mut current = id;
+            while let Some(prev) = previous.get(current) {
+                path.push((*prev).clone());
+                current = prev;
+            }
+            path.reverse();
+            return Some(DependencyChain::new(path));
+        }
+        for dependency in sorted_dependencies(inner, id, None) {
+            if visited.insert(dependency) {
+                previous.insert(dependency, id);
+                queue.push_back(dependency);
+            }
+        }
+    }
+    None
+}
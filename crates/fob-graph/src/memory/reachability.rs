@@ -0,0 +1,366 @@
+//! Symbol-level reachability across module boundaries.
+//!
+//! Connects the per-module symbol tables through imports and exports to
+//! find which top-level declarations the entry points can reach, the way a
+//! bundler's tree shaking would, and how many bytes each one keeps alive.
+
+use std::collections::VecDeque;
+
+use rustc_hash::FxHashSet as HashSet;
+
+use super::super::import::{ImportKind, ImportSpecifier};
+use super::super::symbol::{Symbol, SymbolKind};
+use super::super::{ExportKind, Module, ModuleId};
+use super::graph::{GraphInner, ModuleGraph};
+use super::types::{SymbolReachability, SymbolReachabilityInfo};
+use crate::{Error, Result};
+
+/// Part of a module that is kept alive.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum Live {
+    /// Module-scope symbol, by local name
+    Symbol(String),
+    /// Export, by exported name
+    Export(String),
+    /// Every export, for namespace and dynamic imports
+    AllExports,
+}
+
+impl ModuleGraph {
+    /// Find which module-scope declarations are reachable from the entry points.
+    ///
+    /// The exports of entry points, framework-used exports and the
+    /// top-level statements of every loaded module are roots. Liveness
+    /// follows references between declarations, and from imports to the
+    /// exports they name. Import bindings that can't be matched to an
+    /// import by name, such as aliased or default imports, conservatively
+    /// keep every unmatched import of their module alive.
+    pub fn symbol_reachability(&self) -> Result<SymbolReachability> {
+        let inner = self.inner.read();
+        let live = live_symbols(&inner, None);
+
+        let mut symbols: Vec<SymbolReachabilityInfo> = inner
+            .modules
+            .values()
+            .flat_map(|module| {
+                module
+                    .symbol_table
+                    .symbols
+                    .iter()
+                    .filter(|symbol| is_module_scope(symbol) && symbol.kind != SymbolKind::Import)
+                    .map(|symbol| SymbolReachabilityInfo {
+                        module_id: module.id.clone(),
+                        name: symbol.name.clone(),
+                        kind: symbol.kind,
+                        size: symbol.declaration_size,
+                        reachable: live.contains(&(module.id.clone(), symbol.name.clone())),
+                    })
+            })
+            .collect();
+        symbols.sort_by(|a, b| {
+            a.module_id
+                .as_path()
+                .cmp(b.module_id.as_path())
+                .then_with(|| a.name.cmp(&b.name))
+        });
+
+        Ok(SymbolReachability { symbols })
+    }
+
+    /// Estimate how many bytes a module-scope declaration keeps in the output.
+    ///
+    /// This is the size of the declaration plus every other declaration
+    /// that is only reachable through it, across modules. Unreachable
+    /// declarations retain nothing.
+    pub fn retained_size(&self, module_id: &ModuleId, name: &str) -> Result<usize> {
+        let inner = self.inner.read();
+        if !inner.modules.contains_key(module_id) {
+            return Err(Error::InvalidConfig(format!(
+                "Module {} not found in graph",
+                module_id
+            )));
+        }
+
+        let target = (module_id.clone(), name.to_string());
+        let live = live_symbols(&inner, None);
+        if !live.contains(&target) {
+            return Ok(0);
+        }
+        let without = live_symbols(&inner, Some(&target));
+
+        Ok(live
+            .difference(&without)
+            .filter_map(|(id, name)| {
+                let module = inner.modules.get(id)?;
+                Some(
+                    module
+                        .symbol_table
+                        .symbols
+                        .iter()
+                        .filter(|symbol| is_module_scope(symbol) && symbol.name == *name)
+                        .map(|symbol| symbol.declaration_size)
+                        .sum::<usize>(),
+                )
+            })
+            .sum())
+    }
+}
+
+impl SymbolReachability {
+    /// Whether a module-scope declaration is reachable from an entry point.
+    pub fn is_reachable(&self, module_id: &ModuleId, name: &str) -> bool {
+        self.symbols
+            .iter()
+            .any(|info| info.reachable && info.module_id == *module_id && info.name == name)
+    }
+
+    /// Declarations no entry point reaches.
+    pub fn unreachable(&self) -> impl Iterator<Item = &SymbolReachabilityInfo> {
+        self.symbols.iter().filter(|info| !info.reachable)
+    }
+
+    /// Total estimated size of the unreachable declarations in bytes.
+    pub fn unreachable_size(&self) -> usize {
+        self.unreachable().map(|info| info.size).sum()
+    }
+}
+
+/// Module-scope symbols (by module and name) reachable from the roots,
+/// treating `dead` as removed.
+fn live_symbols(
+    inner: &GraphInner,
+    dead: Option<&(ModuleId, String)>,
+) -> HashSet<(ModuleId, String)> {
+    let mut queue: VecDeque<(ModuleId, Live)> = VecDeque::new();
+    for module_id in loaded_modules(inner) {
+        let Some(module) = inner.modules.get(&module_id) else {
+            continue;
+        };
+        if module.is_entry || inner.entry_points.contains(&module_id) {
+            queue.push_back((module_id.clone(), Live::AllExports));
+        }
+        for export in module.exports.iter() {
+            if export.is_framework_used {
+                queue.push_back((module_id.clone(), Live::Export(export.name.clone())));
+            }
+        }
+        for name in &module.symbol_table.top_level_references {
+            queue.push_back((module_id.clone(), Live::Symbol(name.clone())));
+        }
+        // Dynamic imports and `require` expose the whole module
+        for import in module.imports.iter() {
+            if matches!(import.kind, ImportKind::Dynamic | ImportKind::Require) {
+                if let Some(target) = &import.resolved_to {
+                    queue.push_back((target.clone(), Live::AllExports));
+                }
+            }
+        }
+    }
+
+    let mut live = HashSet::default();
+    let mut seen = HashSet::default();
+    while let Some((module_id, item)) = queue.pop_front() {
+        if !seen.insert((module_id.clone(), item.clone())) {
+            continue;
+        }
+        let Some(module) = inner.modules.get(&module_id) else {
+            continue;
+        };
+
+        match item {
+            Live::AllExports => {
+                for export in module.exports.iter() {
+                    if export.kind != ExportKind::StarReExport {
+                        queue.push_back((module_id.clone(), Live::Export(export.name.clone())));
+                    }
+                }
+                for target in star_targets(module) {
+                    queue.push_back((target.clone(), Live::AllExports));
+                }
+            }
+            Live::Export(name) => {
+                for (target, item) in export_targets(module, &name) {
+                    queue.push_back((target, item));
+                }
+            }
+            Live::Symbol(name) => {
+                if dead.is_some_and(|(id, dead_name)| *id == module_id && *dead_name == name) {
+                    continue;
+                }
+                let mut declared = false;
+                for symbol in module
+                    .symbol_table
+                    .symbols
+                    .iter()
+                    .filter(|symbol| is_module_scope(symbol) && symbol.name == name)
+                {
+                    declared = true;
+                    for reference in &symbol.references {
+                        queue.push_back((module_id.clone(), Live::Symbol(reference.clone())));
+                    }
+                    if symbol.kind == SymbolKind::Import {
+                        queue.extend(import_targets(module, &name));
+                    }
+                }
+                // Globals have no declaration to keep
+                if declared {
+                    live.insert((module_id, name));
+                }
+            }
+        }
+    }
+
+    live
+}
+
+/// Modules loaded by the entry points.
+fn loaded_modules(inner: &GraphInner) -> HashSet<ModuleId> {
+    let mut loaded = HashSet::default();
+    let mut queue: VecDeque<&ModuleId> = inner.entry_points.iter().collect();
+    queue.extend(
+        inner
+            .modules
+            .values()
+            .filter(|module| module.is_entry)
+            .map(|module| &module.id),
+    );
+    while let Some(id) = queue.pop_front() {
+        if loaded.insert(id.clone()) {
+            queue.extend(inner.dependencies.get(id).into_iter().flatten());
+        }
+    }
+    loaded
+}
+
+/// What an export named `name` keeps alive.
+fn export_targets(module: &Module, name: &str) -> Vec<(ModuleId, Live)> {
+    // export { imported as name } from './source'
+    if let Some(export) = module
+        .exports
+        .iter()
+        .find(|export| export.kind == ExportKind::ReExport && export.name == name)
+    {
+        let imported = export.imported_name.as_deref().unwrap_or(name);
+        return module
+            .imports
+            .iter()
+            .filter(|import| {
+                import.kind == ImportKind::ReExport
+                    && export.re_exported_from.as_deref() == Some(import.source.as_str())
+            })
+            .filter_map(|import| import.resolved_to.clone())
+            .map(|target| (target, Live::Export(imported.to_string())))
+            .collect();
+    }
+
+    let table = &module.symbol_table;
+    if name == "default" && !table.default_export_references.is_empty() {
+        return table
+            .default_export_references
+            .iter()
+            .map(|local| (module.id.clone(), Live::Symbol(local.clone())))
+            .collect();
+    }
+
+    // export { local as name }
+    let local = table
+        .export_aliases
+        .iter()
+        .find(|(exported, _)| exported == name)
+        .map_or(name, |(_, local)| local.as_str());
+    if table
+        .symbols
+        .iter()
+        .any(|symbol| is_module_scope(symbol) && symbol.name == local)
+    {
+        return vec![(module.id.clone(), Live::Symbol(local.to_string()))];
+    }
+
+    // export * doesn't forward the default export
+    if name == "default" {
+        return Vec::new();
+    }
+    star_targets(module)
+        .map(|target| (target.clone(), Live::Export(name.to_string())))
+        .collect()
+}
+
+/// What an import binding named `local` keeps alive.
+fn import_targets(module: &Module, local: &str) -> Vec<(ModuleId, Live)> {
+    let bindings = || {
+        module
+            .imports
+            .iter()
+            .filter(|import| matches!(import.kind, ImportKind::Static | ImportKind::TypeOnly))
+            .filter_map(|import| Some((import.resolved_to.as_ref()?, &import.specifiers)))
+            .flat_map(|(target, specifiers)| specifiers.iter().map(move |s| (target, s)))
+    };
+
+    let matched: Vec<(ModuleId, Live)> = bindings()
+        .filter_map(|(target, specifier)| match specifier {
+            ImportSpecifier::Named(imported) if imported == local => {
+                Some((target.clone(), Live::Export(imported.clone())))
+            }
+            ImportSpecifier::Namespace(name) if name == local => {
+                Some((target.clone(), Live::AllExports))
+            }
+            _ => None,
+        })
+        .collect();
+    if !matched.is_empty() {
+        return matched;
+    }
+
+    // Aliased and default imports don't record their local name; keep
+    // every import that no binding of the module matches by name
+    let import_names: HashSet<&str> = module
+        .symbol_table
+        .symbols
+        .iter()
+        .filter(|symbol| is_module_scope(symbol) && symbol.kind == SymbolKind::Import)
+        .map(|symbol| symbol.name.as_str())
+        .collect();
+    bindings()
+        .filter_map(|(target, specifier)| match specifier {
+            ImportSpecifier::Named(imported) if !import_names.contains(imported.as_str()) => {
+                Some((target.clone(), Live::Export(imported.clone())))
+            }
+            ImportSpecifier::Default => Some((target.clone(), Live::Export("default".into()))),
+            ImportSpecifier::Namespace(name) if !import_names.contains(name.as_str()) => {
+                Some((target.clone(), Live::AllExports))
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+/// Modules re-exported with `export *`.
+fn star_targets(module: &Module) -> impl Iterator<Item = &ModuleId> {
+    module
+        .imports
+        .iter()
+        .filter(|import| {
+            import.kind == ImportKind::ReExport
+                && import
+                    .specifiers
+                    .iter()
+                    .any(|specifier| matches!(specifier, ImportSpecifier::Namespace(_)))
+        })
+        .filter_map(|import| import.resolved_to.as_ref())
+}
+
+/// Whether a symbol is declared at module scope, as opposed to a nested
+/// binding or a class or enum member.
+fn is_module_scope(symbol: &Symbol) -> bool {
+    symbol.scope_id == 0
+        && !matches!(
+            symbol.kind,
+            SymbolKind::Parameter
+                | SymbolKind::ClassProperty
+                | SymbolKind::ClassMethod
+                | SymbolKind::ClassGetter
+                | SymbolKind::ClassSetter
+                | SymbolKind::ClassConstructor
+                | SymbolKind::EnumMember
+        )
+}
@@ -0,0 +1,145 @@
+//! Record conversion for persisting ModuleGraph (see `crate::storage`).
+
+use std::sync::Arc;
+
+use rustc_hash::FxHashSet as HashSet;
+
+use super::super::ModuleId;
+use super::super::external_dep::ExternalDependency;
+use super::super::storage::{GraphDelta, GraphSnapshot, ModuleRecord};
+use super::graph::{GraphInner, ModuleGraph};
+use crate::{Error, Result};
+
+impl ModuleGraph {
+    /// Convert the graph into storage records.
+    pub fn snapshot(&self) -> Result<GraphSnapshot> {
+        let inner = self.inner.read();
+        let mut modules: Vec<ModuleRecord> = inner
+            .modules
+            .keys()
+            .filter_map(|id| record(&inner, id))
+            .collect();
+        modules.sort_by(|a, b| a.module.id.as_path().cmp(b.module.id.as_path()));
+
+        Ok(GraphSnapshot {
+            modules,
+            external_deps: external_deps(&inner),
+        })
+    }
+
+    /// Rebuild a graph from storage records.
+    ///
+    /// Entry points are restored from `Module::is_entry`. Edges to modules
+    /// missing from the snapshot are rejected with [`Error::CorruptStorage`],
+    /// since they indicate a partially written store.
+    pub fn from_snapshot(snapshot: GraphSnapshot) -> Result<Self> {
+        let mut inner = GraphInner::default();
+        for record in &snapshot.modules {
+            if record.module.is_entry {
+                inner.entry_points.insert(record.module.id.clone());
+            }
+        }
+        let ids: HashSet<ModuleId> = snapshot
+            .modules
+            .iter()
+            .map(|record| record.module.id.clone())
+            .collect();
+        for record in snapshot.modules {
+            let id = record.module.id.clone();
+            for dependency in record.dependencies {
+                if !ids.contains(&dependency) {
+                    return Err(Error::CorruptStorage(format!(
+                        "Stored graph has an edge from {} to missing module {}",
+                        id, dependency
+                    )));
+                }
+                inner
+                    .dependencies
+                    .entry(id.clone())
+                    .or_default()
+                    .insert(dependency.clone());
+                inner
+                    .dependents
+                    .entry(dependency)
+                    .or_default()
+                    .insert(id.clone());
+            }
+            inner.modules.insert(id, Arc::new(record.module));
+        }
+        inner.external_deps = snapshot
+            .external_deps
+            .into_iter()
+            .map(|dep| (dep.specifier.clone(), dep))
+            .collect();
+
+        Ok(ModuleGraph {
+            inner: Arc::new(parking_lot::RwLock::new(inner)),
+        })
+    }
+
+    /// Collect the records to write after incremental updates.
+    ///
+    /// `changed` lists modules passed to [`ModuleGraph::apply_change`]
+    /// together with the `recounted` modules it reported, and `removed`
+    /// the modules passed to [`ModuleGraph::remove_module`]. Importers of
+    /// removed modules lose an edge, so they are upserted as well.
+    pub fn delta(&self, changed: &[ModuleId], removed: &[ModuleId]) -> Result<GraphDelta> {
+        let inner = self.inner.read();
+
+        let mut upserted: Vec<ModuleRecord> = Vec::new();
+        let mut seen: HashSet<&ModuleId> = HashSet::default();
+        let mut push = |id| {
+            if seen.insert(id) {
+                if let Some(record) = record(&inner, id) {
+                    upserted.push(record);
+                }
+            }
+        };
+        for id in changed {
+            push(id);
+        }
+        // Removed modules are gone from the graph; `remove_module` records
+        // their former importers as importers of an external keyed by path
+        for id in removed {
+            let dependency = inner.external_deps.get(id.path_string().as_ref());
+            for importer in dependency.into_iter().flat_map(|dep| &dep.importers) {
+                push(importer);
+            }
+        }
+        upserted.sort_by(|a, b| a.module.id.as_path().cmp(b.module.id.as_path()));
+
+        Ok(GraphDelta {
+            upserted,
+            removed: removed
+                .iter()
+                .filter(|id| !inner.modules.contains_key(*id))
+                .cloned()
+                .collect(),
+            external_deps: external_deps(&inner),
+        })
+    }
+}
+
+fn record(inner: &GraphInner, id: &ModuleId) -> Option<ModuleRecord> {
+    let module = inner.modules.get(id)?;
+    let mut dependencies: Vec<ModuleId> = inner
+        .dependencies
+        .get(id)
+        .into_iter()
+        .flatten()
+        .cloned()
+        .collect();
+    dependencies.sort_by(|a, b| a.as_path().cmp(b.as_path()));
+    let mut module = (**module).clone();
+    module.is_entry |= inner.entry_points.contains(id);
+    Some(ModuleRecord {
+        module,
+        dependencies,
+    })
+}
+
+fn external_deps(inner: &GraphInner) -> Vec<ExternalDependency> {
+    let mut deps: Vec<_> = inner.external_deps.values().cloned().collect();
+    deps.sort_by(|a, b| a.specifier.cmp(&b.specifier));
+    deps
+}
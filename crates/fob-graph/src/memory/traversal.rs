@@ -60,4 +60,41 @@ impl ModuleGraph {
         visited.remove(id);
         Ok(visited)
     }
+
+    /// Entry points whose module trees include any of the changed modules.
+    ///
+    /// Walks the reverse edges from each changed module, so an entry point is
+    /// affected if it imports a changed module directly or transitively, or
+    /// is one itself. Modules that are not in the graph are ignored. Useful
+    /// for selective rebuilds and test runs. Results are sorted by path.
+    pub fn affected_entries(&self, changed: &[ModuleId]) -> Result<Vec<ModuleId>> {
+        let inner = self.inner.read();
+        let mut visited = HashSet::default();
+        let mut queue: VecDeque<ModuleId> = changed
+            .iter()
+            .filter(|id| inner.modules.contains_key(*id))
+            .cloned()
+            .collect();
+
+        let mut affected = Vec::new();
+        while let Some(current) = queue.pop_front() {
+            if !visited.insert(current.clone()) {
+                continue;
+            }
+            if inner.entry_points.contains(&current) {
+                affected.push(current.clone());
+            }
+
+            if let Some(dependents) = inner.dependents.get(&current) {
+                for dependent in dependents {
+                    if !visited.contains(dependent) {
+                        queue.push_back(dependent.clone());
+                    }
+                }
+            }
+        }
+
+        affected.sort_by(|a, b| a.as_path().cmp(b.as_path()));
+        Ok(affected)
+    }
 }
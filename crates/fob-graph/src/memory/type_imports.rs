@@ -0,0 +1,160 @@
+//! Verification of TypeScript `import type` modifiers for ModuleGraph.
+//!
+//! The compiler erases type-only imports, so a type-only binding used as a
+//! value is `undefined` at runtime. The opposite mistake, a value import only
+//! used as a type, keeps a runtime import alive under `verbatimModuleSyntax`
+//! and relies on import elision otherwise.
+
+use super::super::ImportSpecifier;
+use super::super::symbol::{ImportBinding, ImportStatement};
+use super::graph::ModuleGraph;
+use super::types::{ImportCodemod, TypeImportIssue, TypeImportIssueKind};
+use crate::Result;
+
+impl ModuleGraph {
+    /// Find import bindings whose `type` modifier disagrees with their usage.
+    ///
+    /// Bindings listed in a local `export { .. }` are not flagged as value
+    /// imports only used as types, since dropping the value would change the
+    /// module's exports. Unused bindings are not flagged either. Results are
+    /// sorted by module path and position.
+    pub fn type_import_issues(&self) -> Result<Vec<TypeImportIssue>> {
+        let inner = self.inner.read();
+        let mut issues = Vec::new();
+
+        for module in inner.modules.values() {
+            for statement in &module.symbol_table.import_statements {
+                let kinds: Vec<Option<TypeImportIssueKind>> =
+                    statement.bindings.iter().map(issue_kind).collect();
+                if kinds.iter().all(Option::is_none) {
+                    continue;
+                }
+
+                let fix = ImportCodemod {
+                    module_id: module.id.clone(),
+                    start: statement.start,
+                    end: statement.end,
+                    replacement: rewrite(statement, &kinds),
+                };
+                let resolved_to = module
+                    .imports
+                    .iter()
+                    .find(|import| import.source == statement.source)
+                    .and_then(|import| import.resolved_to.clone());
+                for (binding, kind) in statement.bindings.iter().zip(&kinds) {
+                    if let Some(kind) = kind {
+                        issues.push(TypeImportIssue {
+                            module_id: module.id.clone(),
+                            source: statement.source.clone(),
+                            resolved_to: resolved_to.clone(),
+                            local: binding.local.clone(),
+                            kind: *kind,
+                            fix: fix.clone(),
+                        });
+                    }
+                }
+            }
+        }
+
+        issues.sort_by(|a, b| {
+            a.module_id
+                .as_path()
+                .cmp(b.module_id.as_path())
+                .then_with(|| a.fix.start.cmp(&b.fix.start))
+        });
+        Ok(issues)
+    }
+}
+
+impl ImportCodemod {
+    /// Apply codemods for one module to its source.
+    ///
+    /// Issues from the same declaration share an identical codemod, which is
+    /// applied once.
+    pub fn apply(source: &str, codemods: &[ImportCodemod]) -> String {
+        let mut codemods: Vec<&ImportCodemod> = codemods.iter().collect();
+        codemods.sort_by(|a, b| b.start.cmp(&a.start));
+        codemods.dedup_by(|a, b| a.start == b.start && a.end == b.end);
+
+        let mut output = source.to_string();
+        for codemod in codemods {
+            output.replace_range(
+                codemod.start as usize..codemod.end as usize,
+                &codemod.replacement,
+            );
+        }
+        output
+    }
+}
+
+fn issue_kind(binding: &ImportBinding) -> Option<TypeImportIssueKind> {
+    if binding.is_type_only {
+        (binding.value_references > 0).then_some(TypeImportIssueKind::TypeOnlyUsedAsValue)
+    } else {
+        (binding.value_references == 0 && binding.type_references > 0 && !binding.re_exported)
+            .then_some(TypeImportIssueKind::ValueUsedOnlyAsType)
+    }
+}
+
+/// Regenerate an import declaration with the fixes applied.
+///
+/// A type-only import can't combine a default and named bindings, so type
+/// default and namespace bindings get their own `import type` declaration
+/// and type named bindings use inline `type` modifiers when any binding of
+/// the declaration stays a value.
+fn rewrite(statement: &ImportStatement, kinds: &[Option<TypeImportIssueKind>]) -> String {
+    let bindings: Vec<(&ImportBinding, bool)> = statement
+        .bindings
+        .iter()
+        .zip(kinds)
+        .map(|(binding, kind)| (binding, binding.is_type_only != kind.is_some()))
+        .collect();
+    let from = format!(
+        " from {}{}",
+        statement.source_literal,
+        if statement.has_semicolon { ";" } else { "" }
+    );
+    let is_named = |binding: &ImportBinding| matches!(binding.specifier, ImportSpecifier::Named(_));
+
+    let mut declarations = Vec::new();
+    let has_value = bindings.iter().any(|(_, is_type)| !is_type);
+    if has_value {
+        let mut clause: Vec<String> = bindings
+            .iter()
+            .filter(|(binding, is_type)| !is_type && !is_named(binding))
+            .map(|(binding, _)| binding.to_source())
+            .collect();
+        let named: Vec<String> = bindings
+            .iter()
+            .filter(|(binding, _)| is_named(binding))
+            .map(|(binding, is_type)| {
+                if *is_type {
+                    format!("type {}", binding.to_source())
+                } else {
+                    binding.to_source()
+                }
+            })
+            .collect();
+        if !named.is_empty() {
+            clause.push(format!("{{ {} }}", named.join(", ")));
+        }
+        declarations.push(format!("import {}{from}", clause.join(", ")));
+    }
+    for (binding, _) in bindings
+        .iter()
+        .filter(|(binding, is_type)| *is_type && !is_named(binding))
+    {
+        declarations.push(format!("import type {}{from}", binding.to_source()));
+    }
+    if !has_value {
+        let named: Vec<String> = bindings
+            .iter()
+            .filter(|(binding, _)| is_named(binding))
+            .map(|(binding, _)| binding.to_source())
+            .collect();
+        if !named.is_empty() {
+            declarations.push(format!("import type {{ {} }}{from}", named.join(", ")));
+        }
+    }
+    declarations.join("\n")
+}
@@ -1,5 +1,6 @@
 //! Type definitions for the in-memory ModuleGraph implementation.
 
+use super::super::symbol::SymbolKind;
 use super::super::{ImportSpecifier, ModuleId, SourceSpan};
 
 /// Information about a class member symbol
@@ -36,6 +37,20 @@ pub struct NamespaceImportInfo {
     pub resolved_to: Option<ModuleId>,
 }
 
+/// A dynamic `import()` and the code it loads lazily
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct DynamicImportBoundary {
+    pub importer: ModuleId,
+    pub source: String,
+    pub resolved_to: Option<ModuleId>,
+    pub span: SourceSpan,
+    /// Modules behind the boundary that no entry point loads statically,
+    /// sorted by path
+    pub lazy_modules: Vec<ModuleId>,
+    /// Total source size of `lazy_modules` in bytes
+    pub lazy_size: usize,
+}
+
 /// A type-only import (TypeScript)
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct TypeOnlyImport {
@@ -44,3 +59,189 @@ pub struct TypeOnlyImport {
     pub specifiers: Vec<ImportSpecifier>,
     pub span: SourceSpan,
 }
+
+/// Summary of an incremental graph update (see `ModuleGraph::apply_change`)
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ModuleChange {
+    /// Whether the module was not in the graph before
+    pub is_new: bool,
+    /// Modules the changed module now depends on but did not before
+    pub added_dependencies: Vec<ModuleId>,
+    /// Modules the changed module no longer depends on
+    pub removed_dependencies: Vec<ModuleId>,
+    /// Modules whose export usage counts were recomputed
+    pub recounted: Vec<ModuleId>,
+}
+
+/// Filters for the visualization exports (`ModuleGraph::to_dot` and friends)
+#[derive(Debug, Clone, Default)]
+pub struct GraphExportOptions {
+    /// Only export the subtree imported from this module
+    pub root: Option<ModuleId>,
+    /// Maximum number of import hops from the root, or from the entry points
+    /// if no root is set
+    pub max_depth: Option<usize>,
+    /// Leave out external dependencies
+    pub hide_externals: bool,
+    /// Only export the neighbourhood of this module: what it imports and
+    /// what imports it, transitively. Can't be combined with `root`
+    pub focus: Option<ModuleId>,
+    /// Leave out modules and externals whose path contains any of these
+    /// strings (e.g. `node_modules`). Excluded modules aren't traversed
+    pub exclude: Vec<String>,
+}
+
+/// Kind of a node in a visualization export
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum GraphNodeKind {
+    /// Entry point module
+    Entry,
+    /// Module in the graph
+    Module,
+    /// Unresolved import, such as an npm package
+    External,
+}
+
+/// Module that mostly re-exports other modules (see `ModuleGraph::barrel_files`)
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct BarrelFile {
+    pub module_id: ModuleId,
+    /// Modules the barrel re-exports from, sorted by path
+    pub re_exported_modules: Vec<ModuleId>,
+    /// Number of `export ... from` declarations and specifiers
+    pub re_export_count: usize,
+    /// Number of modules loaded by importing the barrel, excluding itself
+    pub subtree_modules: usize,
+    /// Source size of those modules in bytes
+    pub subtree_bytes: usize,
+    /// Imports of the barrel, sorted by importer path
+    pub importers: Vec<BarrelImport>,
+}
+
+/// Import of a barrel file and the part of the barrel it uses
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct BarrelImport {
+    pub importer: ModuleId,
+    /// Specifier the importer uses for the barrel
+    pub source: String,
+    /// Names imported from the barrel (`default` and `*` for default and
+    /// namespace imports)
+    pub used_exports: Vec<String>,
+    /// Whether the importer needs the whole barrel (namespace, default or
+    /// dynamic import, or names that can't be traced to a module)
+    pub whole_barrel: bool,
+    /// Number of subtree modules the used names need
+    pub used_modules: usize,
+    /// Source size of those modules in bytes
+    pub used_bytes: usize,
+    /// Imports that would replace this one, empty if `whole_barrel`
+    pub direct_imports: Vec<DirectImport>,
+}
+
+/// Import of the module that defines names re-exported by a barrel
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct DirectImport {
+    pub module_id: ModuleId,
+    /// Specifier to import the module from the importer
+    pub specifier: String,
+    /// `(imported, local)` pairs: the name in the module and the name the
+    /// importer used from the barrel
+    pub names: Vec<(String, String)>,
+}
+
+/// Module-scope declarations and whether the entry points reach them
+/// (see `ModuleGraph::symbol_reachability`)
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+pub struct SymbolReachability {
+    /// Declarations sorted by module path and name
+    pub symbols: Vec<SymbolReachabilityInfo>,
+}
+
+/// Reachability of a single module-scope declaration
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct SymbolReachabilityInfo {
+    pub module_id: ModuleId,
+    pub name: String,
+    pub kind: SymbolKind,
+    /// Estimated size of the declaration in bytes
+    pub size: usize,
+    pub reachable: bool,
+}
+
+/// How an import's `type` modifier disagrees with the binding's usage
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum TypeImportIssueKind {
+    /// A type-only binding is referenced as a value, which fails at runtime
+    TypeOnlyUsedAsValue,
+    /// A value binding is only referenced in type positions
+    ValueUsedOnlyAsType,
+}
+
+/// An import binding whose `type` modifier should change
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct TypeImportIssue {
+    pub module_id: ModuleId,
+    pub source: String,
+    pub resolved_to: Option<ModuleId>,
+    /// Local name of the binding
+    pub local: String,
+    pub kind: TypeImportIssueKind,
+    /// Rewrite of the whole import declaration, with every fix for it applied
+    pub fix: ImportCodemod,
+}
+
+/// A text replacement in a module's source
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct ImportCodemod {
+    pub module_id: ModuleId,
+    /// Byte offset where the replaced text starts
+    pub start: u32,
+    /// Byte offset where the replaced text ends
+    pub end: u32,
+    pub replacement: String,
+}
+
+/// Options for `ModuleGraph::suggest_chunk_groups`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChunkGroupOptions {
+    /// Minimum number of entry points that must load a module for it to be
+    /// grouped
+    pub min_entries: usize,
+    /// Groups smaller than this many bytes are not suggested
+    pub min_size: usize,
+}
+
+impl Default for ChunkGroupOptions {
+    fn default() -> Self {
+        Self {
+            min_entries: 2,
+            min_size: 0,
+        }
+    }
+}
+
+/// Kind of a suggested chunk group
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChunkGroupKind {
+    /// Installed packages from `node_modules`
+    Vendor,
+    /// The project's own modules
+    Common,
+}
+
+/// A suggested group of modules to emit as one shared chunk
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct ChunkGroupSuggestion {
+    /// Chunk name, `vendor` or `common`
+    pub name: String,
+    pub kind: ChunkGroupKind,
+    /// Modules in the group, sorted by path
+    pub modules: Vec<ModuleId>,
+    /// Entry points loading at least one module of the group, sorted by path
+    pub entries: Vec<ModuleId>,
+    /// Total source size of `modules` in bytes
+    pub size: usize,
+}
@@ -0,0 +1,281 @@
+//! Visualization exports (DOT, Mermaid, JSON, HTML) for ModuleGraph.
+
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
+use std::fmt::Write;
+
+use rustc_hash::{FxHashMap as HashMap, FxHashSet as HashSet};
+
+use super::super::ModuleId;
+use super::graph::ModuleGraph;
+use super::types::{GraphExportOptions, GraphNodeKind};
+use crate::{Error, Result};
+
+/// Page used by [`ModuleGraph::to_html`]; `__GRAPH_DATA__` is replaced
+/// with the JSON graph.
+const HTML_TEMPLATE: &str = include_str!("../../assets/graph.html");
+
+/// Nodes and edges selected for export, keyed by label and sorted for stable output.
+struct GraphView {
+    nodes: BTreeMap<String, GraphNodeKind>,
+    edges: BTreeSet<(String, String)>,
+}
+
+impl ModuleGraph {
+    /// Export the graph as Graphviz DOT.
+    ///
+    /// Entry points are drawn bold and external dependencies dashed.
+    pub fn to_dot(&self, options: &GraphExportOptions) -> Result<String> {
+        let view = self.export_view(options)?;
+        let mut output = String::from("digraph ModuleGraph {\n    node [shape=box];\n");
+        for (label, kind) in &view.nodes {
+            let attributes = match kind {
+                GraphNodeKind::Entry => " [style=bold]",
+                GraphNodeKind::Module => "",
+                GraphNodeKind::External => " [shape=ellipse, style=dashed]",
+            };
+            let _ = writeln!(output, "    \"{}\"{};", escape_dot(label), attributes);
+        }
+        for (from, to) in &view.edges {
+            let _ = writeln!(
+                output,
+                "    \"{}\" -> \"{}\";",
+                escape_dot(from),
+                escape_dot(to)
+            );
+        }
+        output.push_str("}\n");
+        Ok(output)
+    }
+
+    /// Export the graph as a Mermaid flowchart.
+    ///
+    /// Entry points use the `entry` class and external dependencies are
+    /// drawn as stadiums, so both can be styled by the embedding page.
+    pub fn to_mermaid(&self, options: &GraphExportOptions) -> Result<String> {
+        let view = self.export_view(options)?;
+        let ids: HashMap<&str, usize> = view
+            .nodes
+            .keys()
+            .enumerate()
+            .map(|(index, label)| (label.as_str(), index))
+            .collect();
+
+        let mut output = String::from("graph LR\n");
+        for (index, (label, kind)) in view.nodes.iter().enumerate() {
+            let label = escape_mermaid(label);
+            let _ = match kind {
+                GraphNodeKind::Entry => writeln!(output, "    n{index}[\"{label}\"]:::entry"),
+                GraphNodeKind::Module => writeln!(output, "    n{index}[\"{label}\"]"),
+                GraphNodeKind::External => writeln!(output, "    n{index}([\"{label}\"])"),
+            };
+        }
+        for (from, to) in &view.edges {
+            let _ = writeln!(
+                output,
+                "    n{} --> n{}",
+                ids[from.as_str()],
+                ids[to.as_str()]
+            );
+        }
+        if view
+            .nodes
+            .values()
+            .any(|kind| *kind == GraphNodeKind::Entry)
+        {
+            output.push_str("    classDef entry font-weight:bold\n");
+        }
+        Ok(output)
+    }
+
+    /// Export the graph as JSON nodes and edges for visualization tools.
+    ///
+    /// Unlike [`ModuleGraph::to_json`], which dumps full module data, this
+    /// produces `{ "nodes": [{ "id", "kind" }], "edges": [{ "from", "to" }] }`
+    /// and honours the export filters.
+    pub fn to_json_graph(&self, options: &GraphExportOptions) -> Result<String> {
+        let view = self.export_view(options)?;
+
+        #[derive(serde::Serialize)]
+        struct NodeJson<'a> {
+            id: &'a str,
+            kind: GraphNodeKind,
+        }
+
+        #[derive(serde::Serialize)]
+        struct EdgeJson<'a> {
+            from: &'a str,
+            to: &'a str,
+        }
+
+        #[derive(serde::Serialize)]
+        struct GraphJson<'a> {
+            nodes: Vec<NodeJson<'a>>,
+            edges: Vec<EdgeJson<'a>>,
+        }
+
+        let graph_json = GraphJson {
+            nodes: view
+                .nodes
+                .iter()
+                .map(|(id, kind)| NodeJson { id, kind: *kind })
+                .collect(),
+            edges: view
+                .edges
+                .iter()
+                .map(|(from, to)| EdgeJson { from, to })
+                .collect(),
+        };
+
+        serde_json::to_string_pretty(&graph_json)
+            .map_err(|e| Error::InvalidConfig(format!("Failed to serialize graph: {e}")))
+    }
+
+    /// Export the graph as a self-contained, interactive HTML page.
+    ///
+    /// The page draws a force-directed layout with d3 (loaded from a CDN)
+    /// and supports zooming, dragging and highlighting a module's imports
+    /// and importers on click.
+    pub fn to_html(&self, options: &GraphExportOptions) -> Result<String> {
+        let json = self.to_json_graph(options)?;
+        // Keep the data from closing the surrounding <script> tag
+        let json = json.replace("</", "<\\/");
+        Ok(HTML_TEMPLATE.replace("__GRAPH_DATA__", &json))
+    }
+
+    /// Select the modules, externals and edges to export.
+    ///
+    /// Modules are walked breadth-first from the root (or the entry points)
+    /// when a root or depth limit is given, and in both directions from the
+    /// focus module when one is given; otherwise every module is kept.
+    fn export_view(&self, options: &GraphExportOptions) -> Result<GraphView> {
+        let inner = self.inner.read();
+        let is_excluded = |label: &str| {
+            options
+                .exclude
+                .iter()
+                .any(|pattern| label.contains(pattern.as_str()))
+        };
+
+        if options.root.is_some() && options.focus.is_some() {
+            return Err(Error::InvalidConfig(
+                "Graph export can't combine a root and a focus module".to_string(),
+            ));
+        }
+        for id in options.root.iter().chain(&options.focus) {
+            if !inner.modules.contains_key(id) {
+                return Err(Error::InvalidConfig(format!(
+                    "Module not found in graph: {}",
+                    id
+                )));
+            }
+        }
+
+        // Depth of every selected module
+        let depths: HashMap<ModuleId, usize> = match (&options.root, &options.focus) {
+            (_, Some(focus)) => {
+                let start = vec![focus.clone()];
+                let mut depths = walk(&start, &inner.dependencies, options, &is_excluded);
+                for (id, depth) in walk(&start, &inner.dependents, options, &is_excluded) {
+                    let entry = depths.entry(id).or_insert(depth);
+                    *entry = (*entry).min(depth);
+                }
+                depths
+            }
+            (None, None) if options.max_depth.is_none() => inner
+                .modules
+                .keys()
+                .filter(|id| !is_excluded(&id.path_string()))
+                .map(|id| (id.clone(), 0))
+                .collect(),
+            (root, _) => {
+                let start = match root {
+                    Some(root) => vec![root.clone()],
+                    None => inner.entry_points.iter().cloned().collect(),
+                };
+                walk(&start, &inner.dependencies, options, &is_excluded)
+            }
+        };
+
+        let mut view = GraphView {
+            nodes: BTreeMap::new(),
+            edges: BTreeSet::new(),
+        };
+        for (id, depth) in &depths {
+            let Some(module) = inner.modules.get(id) else {
+                continue;
+            };
+            let label = id.path_string().into_owned();
+            let kind = if module.is_entry || inner.entry_points.contains(id) {
+                GraphNodeKind::Entry
+            } else {
+                GraphNodeKind::Module
+            };
+            view.nodes.insert(label.clone(), kind);
+
+            // Edges between focused modules are always kept, since importers
+            // at the depth limit still point at selected modules
+            let expand = options.max_depth.is_none_or(|max| *depth < max);
+            if expand || options.focus.is_some() {
+                for dependency in inner.dependencies.get(id).into_iter().flatten() {
+                    if depths.contains_key(dependency) {
+                        view.edges
+                            .insert((label.clone(), dependency.path_string().into_owned()));
+                    }
+                }
+            }
+            if options.hide_externals || !expand {
+                continue;
+            }
+            for import in module.imports.iter() {
+                if import.resolved_to.is_none()
+                    && !import.source.is_empty()
+                    && !is_excluded(&import.source)
+                {
+                    view.nodes
+                        .entry(import.source.clone())
+                        .or_insert(GraphNodeKind::External);
+                    view.edges.insert((label.clone(), import.source.clone()));
+                }
+            }
+        }
+
+        Ok(view)
+    }
+}
+
+/// Breadth-first walk along `edges` from `start`, returning the depth of
+/// every reached module. Excluded modules are neither kept nor traversed.
+fn walk(
+    start: &[ModuleId],
+    edges: &HashMap<ModuleId, HashSet<ModuleId>>,
+    options: &GraphExportOptions,
+    is_excluded: &impl Fn(&str) -> bool,
+) -> HashMap<ModuleId, usize> {
+    let mut depths: HashMap<ModuleId, usize> = HashMap::default();
+    let mut queue: VecDeque<(ModuleId, usize)> = start.iter().map(|id| (id.clone(), 0)).collect();
+    while let Some((id, depth)) = queue.pop_front() {
+        if depths.contains_key(&id) || is_excluded(&id.path_string()) {
+            continue;
+        }
+        depths.insert(id.clone(), depth);
+        if options.max_depth.is_some_and(|max| depth >= max) {
+            continue;
+        }
+        for next in edges.get(&id).into_iter().flatten() {
+            if !depths.contains_key(next) {
+                queue.push_back((next.clone(), depth + 1));
+            }
+        }
+    }
+    depths
+}
+
+/// Escape a label for a quoted DOT identifier.
+fn escape_dot(label: &str) -> String {
+    label.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+/// Escape a label for a quoted Mermaid node text.
+fn escape_mermaid(label: &str) -> String {
+    label.replace('"', "#quot;")
+}
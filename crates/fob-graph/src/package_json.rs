@@ -5,8 +5,9 @@
 
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use std::path::{Path, PathBuf};
+use std::path::{Component, Path, PathBuf};
 
+use crate::ModuleId;
 use crate::Result;
 use crate::runtime::Runtime;
 
@@ -301,6 +302,82 @@ pub struct TypeCoverage {
     pub unused: usize,
 }
 
+/// A package bundled from more than one installed copy.
+///
+/// Typically caused by nested `node_modules` when dependents require
+/// incompatible versions, e.g. two copies of `react`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DuplicatePackage {
+    /// Package name
+    pub name: String,
+    /// Installed copies, sorted by directory
+    pub copies: Vec<PackageCopy>,
+}
+
+/// One installed copy of a duplicated package.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PackageCopy {
+    /// Package directory; its `node_modules` nesting is the resolution path
+    pub root: PathBuf,
+    /// Version from the copy's package.json, if loaded with
+    /// [`DuplicatePackage::load_versions`]
+    pub version: Option<String>,
+    /// Modules of this copy in the graph
+    pub modules: Vec<ModuleId>,
+    /// Modules outside the copy that import it
+    pub importers: Vec<ModuleId>,
+    /// Total source size of the copy's modules in bytes
+    pub size: usize,
+}
+
+impl DuplicatePackage {
+    /// Estimated bytes saved by deduplicating: every copy but the largest.
+    pub fn duplicated_bytes(&self) -> usize {
+        let total: usize = self.copies.iter().map(|copy| copy.size).sum();
+        let largest = self.copies.iter().map(|copy| copy.size).max().unwrap_or(0);
+        total - largest
+    }
+
+    /// Read the version of each copy from its package.json.
+    ///
+    /// Copies whose package.json can't be read keep `version: None`.
+    pub async fn load_versions<R: Runtime>(&mut self, runtime: &R) {
+        for copy in &mut self.copies {
+            let path = copy.root.join("package.json");
+            copy.version = PackageJson::from_path(runtime, &path)
+                .await
+                .ok()
+                .and_then(|package| package.version);
+        }
+    }
+}
+
+/// Split an installed file path into its package name and package directory.
+///
+/// Uses the innermost `node_modules` directory, so nested and pnpm
+/// (`node_modules/.pnpm/pkg@1.0.0/node_modules/pkg`) installs resolve to the
+/// copy that is actually loaded. Returns `None` outside `node_modules`.
+pub(crate) fn installed_package(path: &Path) -> Option<(String, PathBuf)> {
+    let components: Vec<Component<'_>> = path.components().collect();
+    let index = components
+        .iter()
+        .rposition(|component| component.as_os_str() == "node_modules")?;
+
+    let mut name = components.get(index + 1)?.as_os_str().to_str()?.to_string();
+    let mut len = index + 2;
+    if name.starts_with('@') {
+        let package = components.get(index + 2)?.as_os_str().to_str()?;
+        name = format!("{}/{}", name, package);
+        len += 1;
+    }
+    // The package directory itself is not a file of the package
+    if len >= components.len() {
+        return None;
+    }
+
+    Some((name, components[..len].iter().collect()))
+}
+
 /// Extract the base package name from an npm import specifier.
 ///
 /// This handles scoped packages correctly:
@@ -1,10 +1,16 @@
 //! Symbol analysis functions for extracting symbol information from source code.
 
-use super::super::symbol::{QualifiedReference, Symbol, SymbolSpan, SymbolTable};
+use super::super::ImportSpecifier;
+use super::super::symbol::{
+    ImportBinding, ImportStatement, QualifiedReference, Symbol, SymbolSpan, SymbolTable,
+};
 use super::utils::{LineIndex, determine_symbol_kind};
 use crate::oxc::SemanticBuilder;
 use oxc_ast::AstKind;
+use oxc_ast::ast::{ImportDeclarationSpecifier, Program, Statement};
+use oxc_semantic::{AstNodes, NodeId, Semantic, SymbolId};
 use oxc_span::GetSpan;
+use rustc_hash::FxHashMap;
 
 /// Extract symbols from parsed program using Oxc's semantic analysis.
 ///
@@ -176,5 +182,211 @@ pub(super) fn extract_symbols_from_program(
         table.add_symbol(symbol);
     }
 
+    link_top_level_references(&semantic, &mut table);
+    collect_export_aliases(program, &mut table);
+    collect_import_statements(program, &semantic, source, &mut table);
+
     table
 }
+
+/// Record which module-scope symbols each top-level declaration refers to.
+///
+/// References are attributed to the top-level statement containing them.
+/// Declarations get the references of their statement and its size (split
+/// between the symbols it declares); references from statements declaring
+/// nothing run when the module loads. `export { .. }` lists only name
+/// exports, so their references are skipped.
+fn link_top_level_references(semantic: &Semantic<'_>, table: &mut SymbolTable) {
+    let scoping = semantic.scoping();
+    let nodes = semantic.nodes();
+    let root_scope = scoping.root_scope_id();
+
+    // Symbol table indexes follow `symbol_ids()` order
+    let module_symbols: Vec<(usize, SymbolId)> = scoping
+        .symbol_ids()
+        .enumerate()
+        .filter(|(_, symbol_id)| scoping.symbol_scope_id(*symbol_id) == root_scope)
+        .collect();
+
+    let mut declared_by: FxHashMap<NodeId, Vec<usize>> = FxHashMap::default();
+    for &(index, symbol_id) in &module_symbols {
+        if let Some(statement) = top_level_statement(nodes, scoping.symbol_declaration(symbol_id)) {
+            declared_by.entry(statement).or_default().push(index);
+        }
+    }
+
+    for (statement, declared) in &declared_by {
+        let kind = nodes.kind(*statement);
+        if matches!(kind, AstKind::ImportDeclaration(_)) {
+            continue;
+        }
+        let size = kind.span().size() as usize / declared.len();
+        for &index in declared {
+            table.symbols[index].declaration_size = size;
+        }
+        if matches!(kind, AstKind::ExportDefaultDeclaration(_)) {
+            for &index in declared {
+                let name = table.symbols[index].name.clone();
+                push_unique(&mut table.default_export_references, name);
+            }
+        }
+    }
+
+    for &(index, symbol_id) in &module_symbols {
+        let name = table.symbols[index].name.clone();
+        for &reference_id in scoping.get_resolved_reference_ids(symbol_id) {
+            let reference = scoping.get_reference(reference_id);
+            if !reference.is_value() {
+                continue;
+            }
+            let Some(statement) = top_level_statement(nodes, reference.node_id()) else {
+                continue;
+            };
+            let kind = nodes.kind(statement);
+            if let Some(declared) = declared_by.get(&statement) {
+                for &user in declared {
+                    if user != index {
+                        push_unique(&mut table.symbols[user].references, name.clone());
+                    }
+                }
+            } else if matches!(kind, AstKind::ExportDefaultDeclaration(_)) {
+                push_unique(&mut table.default_export_references, name.clone());
+            } else if !matches!(kind, AstKind::ExportNamedDeclaration(_)) {
+                push_unique(&mut table.top_level_references, name.clone());
+            }
+        }
+    }
+}
+
+/// Record the local names behind renamed `export { local as exported }` specifiers.
+fn collect_export_aliases(program: &Program<'_>, table: &mut SymbolTable) {
+    for statement in &program.body {
+        let Statement::ExportNamedDeclaration(declaration) = statement else {
+            continue;
+        };
+        if declaration.source.is_some() {
+            continue;
+        }
+        for specifier in &declaration.specifiers {
+            let local = specifier.local.name();
+            let exported = specifier.exported.name();
+            if local != exported {
+                table
+                    .export_aliases
+                    .push((exported.to_string(), local.to_string()));
+            }
+        }
+    }
+}
+
+/// Record top-level import declarations and whether each binding is used
+/// as a value, as a type, or re-exported.
+///
+/// Value references to a type-only binding can't resolve to it, so they are
+/// counted from the module's unresolved references instead. Declarations with
+/// import attributes or a phase modifier are skipped.
+fn collect_import_statements(
+    program: &Program<'_>,
+    semantic: &Semantic<'_>,
+    source: &str,
+    table: &mut SymbolTable,
+) {
+    let scoping = semantic.scoping();
+    let nodes = semantic.nodes();
+    let unresolved = scoping.root_unresolved_references();
+
+    for statement in &program.body {
+        let Statement::ImportDeclaration(declaration) = statement else {
+            continue;
+        };
+        if declaration.with_clause.is_some() || declaration.phase.is_some() {
+            continue;
+        }
+        let Some(specifiers) = &declaration.specifiers else {
+            continue;
+        };
+
+        let mut bindings = Vec::with_capacity(specifiers.len());
+        for specifier in specifiers {
+            let (local, imported, inline_type) = match specifier {
+                ImportDeclarationSpecifier::ImportSpecifier(spec) => (
+                    &spec.local,
+                    ImportSpecifier::Named(spec.imported.name().to_string()),
+                    spec.import_kind.is_type(),
+                ),
+                ImportDeclarationSpecifier::ImportDefaultSpecifier(spec) => {
+                    (&spec.local, ImportSpecifier::Default, false)
+                }
+                ImportDeclarationSpecifier::ImportNamespaceSpecifier(spec) => (
+                    &spec.local,
+                    ImportSpecifier::Namespace("*".to_string()),
+                    false,
+                ),
+            };
+            let is_type_only = declaration.import_kind.is_type() || inline_type;
+
+            let mut binding = ImportBinding {
+                local: local.name.to_string(),
+                specifier: imported,
+                is_type_only,
+                value_references: 0,
+                type_references: 0,
+                re_exported: false,
+            };
+            let mut count = |node_id: NodeId, is_value: bool| {
+                if matches!(nodes.parent_kind(node_id), AstKind::ExportSpecifier(_)) {
+                    binding.re_exported = true;
+                } else if is_value {
+                    binding.value_references += 1;
+                } else {
+                    binding.type_references += 1;
+                }
+            };
+            if let Some(symbol_id) = local.symbol_id.get() {
+                for &reference_id in scoping.get_resolved_reference_ids(symbol_id) {
+                    let reference = scoping.get_reference(reference_id);
+                    count(reference.node_id(), reference.is_value());
+                }
+            }
+            if is_type_only {
+                let ids = unresolved.get(local.name.as_str());
+                for &reference_id in ids.into_iter().flat_map(|ids| ids.iter()) {
+                    let reference = scoping.get_reference(reference_id);
+                    if reference.is_value() {
+                        count(reference.node_id(), true);
+                    }
+                }
+            }
+            bindings.push(binding);
+        }
+
+        let span = declaration.span;
+        let literal = declaration.source.span;
+        table.import_statements.push(ImportStatement {
+            source: declaration.source.value.to_string(),
+            source_literal: source[literal.start as usize..literal.end as usize].to_string(),
+            start: span.start,
+            end: span.end,
+            has_semicolon: source[span.start as usize..span.end as usize].ends_with(';'),
+            bindings,
+        });
+    }
+}
+
+/// The statement directly inside the program that contains a node.
+fn top_level_statement(nodes: &AstNodes<'_>, node_id: NodeId) -> Option<NodeId> {
+    let mut current = node_id;
+    for parent in nodes.ancestor_ids(node_id) {
+        if matches!(nodes.kind(parent), AstKind::Program(_)) {
+            return Some(current);
+        }
+        current = parent;
+    }
+    None
+}
+
+fn push_unique(names: &mut Vec<String>, name: String) {
+    if !names.contains(&name) {
+        names.push(name);
+    }
+}
@@ -0,0 +1,145 @@
+//! Persistence interface for module graphs.
+//!
+//! Analyzing a large monorepo can take minutes, so tools keep the graph in
+//! a store and reload it instead of re-analyzing for every query. Backends
+//! implement [`GraphStorage`] over a record form of the graph: one
+//! [`ModuleRecord`] per module carrying its outgoing edges, which maps
+//! directly onto database rows. After an incremental update with
+//! [`ModuleGraph::apply_change`], only the touched records need to be
+//! written; see [`ModuleGraph::delta`].
+//!
+//! [`MemoryGraphStorage`] keeps the records in process and serves as the
+//! reference implementation for backends.
+
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use async_trait::async_trait;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+
+use crate::{ExternalDependency, Module, ModuleGraph, ModuleId, Result};
+
+/// A module with its outgoing dependency edges.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModuleRecord {
+    pub module: Module,
+    /// Modules this module depends on, sorted by path
+    pub dependencies: Vec<ModuleId>,
+}
+
+/// Every record of a graph, as written by [`GraphStorage::save`].
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct GraphSnapshot {
+    /// Modules sorted by path
+    pub modules: Vec<ModuleRecord>,
+    /// External dependencies sorted by specifier
+    pub external_deps: Vec<ExternalDependency>,
+}
+
+/// Records to upsert and delete after an incremental update.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct GraphDelta {
+    /// Added or changed modules
+    pub upserted: Vec<ModuleRecord>,
+    /// Modules removed from the graph
+    pub removed: Vec<ModuleId>,
+    /// All external dependencies; they are few and change with any import
+    pub external_deps: Vec<ExternalDependency>,
+}
+
+impl GraphDelta {
+    /// Whether the delta has no module changes.
+    pub fn is_empty(&self) -> bool {
+        self.upserted.is_empty() && self.removed.is_empty()
+    }
+}
+
+/// A store that module graphs can be saved to and reloaded from.
+#[cfg_attr(target_family = "wasm", async_trait(?Send))]
+#[cfg_attr(not(target_family = "wasm"), async_trait)]
+pub trait GraphStorage: Send + Sync {
+    /// Replace the stored graph.
+    async fn save(&self, graph: &ModuleGraph) -> Result<()>;
+
+    /// Load the stored graph, or `None` if nothing was saved.
+    async fn load(&self) -> Result<Option<ModuleGraph>>;
+
+    /// Apply incremental changes to the stored graph.
+    async fn upsert(&self, delta: GraphDelta) -> Result<()>;
+}
+
+/// In-process [`GraphStorage`] keeping records keyed by module path.
+#[derive(Debug, Default)]
+pub struct MemoryGraphStorage {
+    state: Mutex<Option<StoredGraph>>,
+}
+
+#[derive(Debug, Default)]
+struct StoredGraph {
+    modules: BTreeMap<PathBuf, ModuleRecord>,
+    external_deps: Vec<ExternalDependency>,
+}
+
+impl MemoryGraphStorage {
+    /// Create an empty store.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of stored modules.
+    pub fn len(&self) -> usize {
+        self.state
+            .lock()
+            .as_ref()
+            .map_or(0, |stored| stored.modules.len())
+    }
+
+    /// Whether no modules are stored.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[cfg_attr(target_family = "wasm", async_trait(?Send))]
+#[cfg_attr(not(target_family = "wasm"), async_trait)]
+impl GraphStorage for MemoryGraphStorage {
+    async fn save(&self, graph: &ModuleGraph) -> Result<()> {
+        let snapshot = graph.snapshot()?;
+        *self.state.lock() = Some(StoredGraph {
+            modules: snapshot
+                .modules
+                .into_iter()
+                .map(|record| (record.module.id.as_path().to_path_buf(), record))
+                .collect(),
+            external_deps: snapshot.external_deps,
+        });
+        Ok(())
+    }
+
+    async fn load(&self) -> Result<Option<ModuleGraph>> {
+        let snapshot = match self.state.lock().as_ref() {
+            Some(stored) => GraphSnapshot {
+                modules: stored.modules.values().cloned().collect(),
+                external_deps: stored.external_deps.clone(),
+            },
+            None => return Ok(None),
+        };
+        ModuleGraph::from_snapshot(snapshot).map(Some)
+    }
+
+    async fn upsert(&self, delta: GraphDelta) -> Result<()> {
+        let mut state = self.state.lock();
+        let stored = state.get_or_insert_with(StoredGraph::default);
+        for id in &delta.removed {
+            stored.modules.remove(id.as_path());
+        }
+        for record in delta.upserted {
+            stored
+                .modules
+                .insert(record.module.id.as_path().to_path_buf(), record);
+        }
+        stored.external_deps = delta.external_deps;
+        Ok(())
+    }
+}
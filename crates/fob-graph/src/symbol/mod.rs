@@ -8,7 +8,7 @@ mod statistics;
 
 use serde::{Deserialize, Serialize};
 
-use super::ModuleId;
+use super::{ImportSpecifier, ModuleId};
 
 pub use metadata::{
     ClassMemberMetadata, CodeQualityMetadata, EnumMemberMetadata, EnumMemberValue, SymbolMetadata,
@@ -42,6 +42,13 @@ pub struct Symbol {
     /// Qualified member references (e.g. `React.ComponentProps`)
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub qualified_references: Vec<QualifiedReference>,
+    /// Module-scope symbols the declaration refers to at runtime
+    #[serde(default)]
+    pub references: Vec<String>,
+    /// Estimated size in bytes of the top-level declaration, 0 for imports
+    /// and nested symbols
+    #[serde(default)]
+    pub declaration_size: usize,
 }
 
 /// A qualified reference to a member of a symbol (e.g. `Namespace.Member`).
@@ -55,6 +62,52 @@ pub struct QualifiedReference {
     pub span: SymbolSpan,
 }
 
+/// An import declaration and how its bindings are used in the module.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ImportStatement {
+    /// Module specifier (e.g. `./types`)
+    pub source: String,
+    /// Specifier as written, including its quotes
+    pub source_literal: String,
+    /// Byte offset where the declaration starts
+    pub start: u32,
+    /// Byte offset where the declaration ends
+    pub end: u32,
+    /// Whether the declaration ends with a semicolon
+    pub has_semicolon: bool,
+    /// Bindings in declaration order
+    pub bindings: Vec<ImportBinding>,
+}
+
+/// A single binding introduced by an import declaration.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ImportBinding {
+    /// Local name of the binding
+    pub local: String,
+    /// What the binding imports
+    pub specifier: ImportSpecifier,
+    /// Whether the binding is erased (`import type` or an inline `type` modifier)
+    pub is_type_only: bool,
+    /// Number of references in value positions
+    pub value_references: usize,
+    /// Number of references in type positions
+    pub type_references: usize,
+    /// Whether the binding is listed in a local `export { .. }`
+    pub re_exported: bool,
+}
+
+impl ImportBinding {
+    /// The specifier as written inside an import declaration, without any `type` modifier.
+    pub fn to_source(&self) -> String {
+        match &self.specifier {
+            ImportSpecifier::Named(imported) if *imported == self.local => imported.clone(),
+            ImportSpecifier::Named(imported) => format!("{imported} as {}", self.local),
+            ImportSpecifier::Default => self.local.clone(),
+            ImportSpecifier::Namespace(_) => format!("* as {}", self.local),
+        }
+    }
+}
+
 impl Symbol {
     /// Create a new symbol with default usage counts and no metadata.
     pub fn new(
@@ -73,6 +126,8 @@ impl Symbol {
             scope_id,
             metadata: SymbolMetadata::None,
             qualified_references: Vec::new(),
+            references: Vec::new(),
+            declaration_size: 0,
         }
     }
 
@@ -94,6 +149,8 @@ impl Symbol {
             scope_id,
             metadata,
             qualified_references: Vec::new(),
+            references: Vec::new(),
+            declaration_size: 0,
         }
     }
 
@@ -285,6 +342,19 @@ pub struct SymbolTable {
     pub symbols: Vec<Symbol>,
     /// Number of scopes in the module (from Oxc)
     pub scope_count: usize,
+    /// Module-scope symbols referenced by top-level statements that run when
+    /// the module loads
+    #[serde(default)]
+    pub top_level_references: Vec<String>,
+    /// Module-scope symbols declared or referenced by `export default`
+    #[serde(default)]
+    pub default_export_references: Vec<String>,
+    /// `(exported, local)` names of `export { local as exported }`
+    #[serde(default)]
+    pub export_aliases: Vec<(String, String)>,
+    /// Top-level import declarations with the usage of their bindings
+    #[serde(default)]
+    pub import_statements: Vec<ImportStatement>,
 }
 
 impl SymbolTable {
@@ -293,6 +363,10 @@ impl SymbolTable {
         Self {
             symbols: Vec::new(),
             scope_count: 0,
+            top_level_references: Vec::new(),
+            default_export_references: Vec::new(),
+            export_aliases: Vec::new(),
+            import_statements: Vec::new(),
         }
     }
 
@@ -301,6 +375,10 @@ impl SymbolTable {
         Self {
             symbols: Vec::with_capacity(capacity),
             scope_count: 0,
+            top_level_references: Vec::new(),
+            default_export_references: Vec::new(),
+            export_aliases: Vec::new(),
+            import_statements: Vec::new(),
         }
     }
 
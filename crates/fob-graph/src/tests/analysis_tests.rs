@@ -338,3 +338,220 @@ async fn namespace_import_marks_all_exports_used() {
         "All exports should be used by namespace import"
     );
 }
+
+fn installed_module(path: &str, size: usize) -> Module {
+    Module::builder(
+        ModuleId::new(path).unwrap(),
+        PathBuf::from(path),
+        SourceType::JavaScript,
+    )
+    .original_size(size)
+    .build()
+}
+
+#[test]
+fn installed_package_uses_innermost_node_modules() {
+    use super::super::package_json::installed_package;
+    use std::path::Path;
+
+    let (name, root) =
+        installed_package(Path::new("/app/node_modules/a/node_modules/react/index.js")).unwrap();
+    assert_eq!(name, "react");
+    assert_eq!(root, Path::new("/app/node_modules/a/node_modules/react"));
+
+    let (name, root) =
+        installed_package(Path::new("/app/node_modules/@scope/pkg/lib/index.js")).unwrap();
+    assert_eq!(name, "@scope/pkg");
+    assert_eq!(root, Path::new("/app/node_modules/@scope/pkg"));
+
+    assert!(installed_package(Path::new("/app/src/index.js")).is_none());
+    assert!(installed_package(Path::new("/app/node_modules/react")).is_none());
+}
+
+#[tokio::test]
+async fn duplicate_packages_reports_copies_and_importers() {
+    let graph = ModuleGraph::new().unwrap();
+
+    let app = installed_module("/app/src/index.js", 100);
+    let lib = installed_module("/app/node_modules/lib/index.js", 50);
+    let react = installed_module("/app/node_modules/react/index.js", 1000);
+    let react_nested = installed_module("/app/node_modules/lib/node_modules/react/index.js", 800);
+    let react_nested_cjs =
+        installed_module("/app/node_modules/lib/node_modules/react/cjs/react.js", 400);
+    for module in [&app, &lib, &react, &react_nested, &react_nested_cjs] {
+        graph.add_module(module.clone()).unwrap();
+    }
+    graph
+        .add_dependency(app.id.clone(), react.id.clone())
+        .unwrap();
+    graph
+        .add_dependency(app.id.clone(), lib.id.clone())
+        .unwrap();
+    graph
+        .add_dependency(lib.id.clone(), react_nested.id.clone())
+        .unwrap();
+    // Imports within a copy don't make the module an importer
+    graph
+        .add_dependency(react_nested.id.clone(), react_nested_cjs.id.clone())
+        .unwrap();
+
+    let duplicates = graph.duplicate_packages().unwrap();
+    assert_eq!(duplicates.len(), 1);
+    let react_dup = &duplicates[0];
+    assert_eq!(react_dup.name, "react");
+    assert_eq!(react_dup.copies.len(), 2);
+
+    let nested = &react_dup.copies[0];
+    assert_eq!(
+        nested.root,
+        PathBuf::from("/app/node_modules/lib/node_modules/react")
+    );
+    assert_eq!(nested.modules.len(), 2);
+    assert_eq!(nested.importers, vec![lib.id.clone()]);
+    assert_eq!(nested.size, 1200);
+    assert_eq!(react_dup.copies[1].importers, vec![app.id.clone()]);
+    assert_eq!(react_dup.duplicated_bytes(), 1000);
+}
+
+fn re_export_import(source: &str, specifier: ImportSpecifier, target: &Module) -> Import {
+    Import::new(
+        source,
+        vec![specifier],
+        ImportKind::ReExport,
+        Some(target.id.clone()),
+        SourceSpan::new(source, 0, 0),
+    )
+}
+
+#[tokio::test]
+async fn barrel_files_report_usage_and_direct_imports() {
+    let graph = ModuleGraph::new().unwrap();
+
+    let mut button = installed_module("/app/src/components/Button.tsx", 100);
+    Arc::make_mut(&mut button.exports).push(Export::new(
+        "default",
+        ExportKind::Default,
+        false,
+        false,
+        None,
+        false,
+        false,
+        SourceSpan::new("Button.tsx", 0, 0),
+    ));
+    let theme = installed_module("/app/src/components/theme.ts", 50);
+    let mut card = installed_module("/app/src/components/Card.tsx", 200);
+    Arc::make_mut(&mut card.exports).push(Export::new(
+        "Card",
+        ExportKind::Named,
+        false,
+        false,
+        None,
+        false,
+        false,
+        SourceSpan::new("Card.tsx", 0, 0),
+    ));
+
+    // export { default as Button } from './Button'; export * from './Card'
+    let mut index = installed_module("/app/src/components/index.ts", 10);
+    let mut button_re_export = Export::new(
+        "Button",
+        ExportKind::ReExport,
+        false,
+        false,
+        Some("./Button".to_string()),
+        false,
+        false,
+        SourceSpan::new("index.ts", 0, 0),
+    );
+    button_re_export.imported_name = Some("default".to_string());
+    Arc::make_mut(&mut index.exports).extend([
+        button_re_export,
+        Export::new(
+            "*",
+            ExportKind::StarReExport,
+            false,
+            false,
+            Some("./Card".to_string()),
+            false,
+            false,
+            SourceSpan::new("index.ts", 0, 0),
+        ),
+    ]);
+    Arc::make_mut(&mut index.imports).extend([
+        re_export_import(
+            "./Button",
+            ImportSpecifier::Named("default".to_string()),
+            &button,
+        ),
+        re_export_import("./Card", ImportSpecifier::Namespace("*".to_string()), &card),
+    ]);
+
+    let mut app = installed_module("/app/src/app.tsx", 20);
+    app.mark_entry();
+    Arc::make_mut(&mut app.imports).push(Import::new(
+        "./components",
+        vec![ImportSpecifier::Named("Button".to_string())],
+        ImportKind::Static,
+        Some(index.id.clone()),
+        SourceSpan::new("app.tsx", 0, 0),
+    ));
+    let mut page = installed_module("/app/src/page.tsx", 20);
+    Arc::make_mut(&mut page.imports).push(Import::new(
+        "./components",
+        vec![ImportSpecifier::Namespace("ui".to_string())],
+        ImportKind::Static,
+        Some(index.id.clone()),
+        SourceSpan::new("page.tsx", 0, 0),
+    ));
+
+    for module in [&button, &theme, &card, &index, &app, &page] {
+        graph.add_module(module.clone()).unwrap();
+    }
+    for (from, to) in [
+        (&card, &theme),
+        (&index, &button),
+        (&index, &card),
+        (&app, &index),
+        (&page, &index),
+    ] {
+        graph
+            .add_dependency(from.id.clone(), to.id.clone())
+            .unwrap();
+    }
+
+    let barrels = graph.barrel_files().unwrap();
+    assert_eq!(barrels.len(), 1);
+    let barrel = &barrels[0];
+    assert_eq!(barrel.module_id, index.id);
+    assert_eq!(
+        barrel.re_exported_modules,
+        vec![button.id.clone(), card.id.clone()]
+    );
+    assert_eq!(barrel.subtree_modules, 3);
+    assert_eq!(barrel.subtree_bytes, 350);
+    assert_eq!(barrel.importers.len(), 2);
+
+    let from_app = &barrel.importers[0];
+    assert_eq!(from_app.importer, app.id);
+    assert_eq!(from_app.used_exports, vec!["Button".to_string()]);
+    assert!(!from_app.whole_barrel);
+    assert_eq!(from_app.used_modules, 1);
+    assert_eq!(from_app.used_bytes, 100);
+    assert_eq!(from_app.direct_imports[0].specifier, "./components/Button");
+    assert_eq!(
+        from_app.direct_imports[0].names,
+        vec![("default".to_string(), "Button".to_string())]
+    );
+    let code = from_app.flattened_imports().unwrap().unwrap();
+    assert!(code.contains("default as Button"), "{code}");
+    assert!(code.contains("\"./components/Button\""), "{code}");
+
+    let from_page = &barrel.importers[1];
+    assert!(from_page.whole_barrel);
+    assert_eq!(from_page.used_bytes, 350);
+    assert!(from_page.flattened_imports().unwrap().is_none());
+
+    // The aliased default export is used through the barrel
+    let unused = graph.unused_exports().unwrap();
+    assert!(!unused.iter().any(|u| u.module_id == button.id));
+}
@@ -0,0 +1,83 @@
+use std::path::PathBuf;
+
+use super::super::{ChunkGroupKind, ChunkGroupOptions, Module, ModuleGraph, ModuleId, SourceType};
+
+fn id(path: &str) -> ModuleId {
+    ModuleId::new(path).unwrap()
+}
+
+/// Three pages; `react` and `layout` are shared by all of them, `chart` only by two.
+fn graph() -> ModuleGraph {
+    let graph = ModuleGraph::new().unwrap();
+    for (path, size) in [
+        ("/app/src/home.tsx", 100),
+        ("/app/src/about.tsx", 100),
+        ("/app/src/stats.tsx", 100),
+        ("/app/src/layout.tsx", 300),
+        ("/app/src/only-home.ts", 50),
+        ("/app/node_modules/react/index.js", 4000),
+        ("/app/node_modules/chart/index.js", 2000),
+    ] {
+        let module = Module::builder(id(path), PathBuf::from(path), SourceType::TypeScript)
+            .original_size(size)
+            .build();
+        graph.add_module(module).unwrap();
+    }
+    for (from, to) in [
+        ("/app/src/home.tsx", "/app/src/layout.tsx"),
+        ("/app/src/about.tsx", "/app/src/layout.tsx"),
+        ("/app/src/stats.tsx", "/app/src/layout.tsx"),
+        ("/app/src/home.tsx", "/app/src/only-home.ts"),
+        ("/app/src/layout.tsx", "/app/node_modules/react/index.js"),
+        ("/app/src/home.tsx", "/app/node_modules/chart/index.js"),
+        ("/app/src/stats.tsx", "/app/node_modules/chart/index.js"),
+    ] {
+        graph.add_dependency(id(from), id(to)).unwrap();
+    }
+    for entry in [
+        "/app/src/home.tsx",
+        "/app/src/about.tsx",
+        "/app/src/stats.tsx",
+    ] {
+        graph.add_entry_point(id(entry)).unwrap();
+    }
+    graph
+}
+
+#[test]
+fn shared_modules_are_split_into_vendor_and_common() {
+    let groups = graph()
+        .suggest_chunk_groups(&ChunkGroupOptions::default())
+        .unwrap();
+
+    assert_eq!(groups.len(), 2);
+    assert_eq!(groups[0].name, "vendor");
+    assert_eq!(groups[0].kind, ChunkGroupKind::Vendor);
+    assert_eq!(
+        groups[0].modules,
+        vec![
+            id("/app/node_modules/chart/index.js"),
+            id("/app/node_modules/react/index.js"),
+        ]
+    );
+    assert_eq!(groups[0].size, 6000);
+    assert_eq!(groups[0].entries.len(), 3);
+
+    assert_eq!(groups[1].kind, ChunkGroupKind::Common);
+    assert_eq!(groups[1].modules, vec![id("/app/src/layout.tsx")]);
+}
+
+#[test]
+fn chunk_group_thresholds_are_applied() {
+    let options = ChunkGroupOptions {
+        min_entries: 3,
+        min_size: 1000,
+    };
+    let groups = graph().suggest_chunk_groups(&options).unwrap();
+
+    assert_eq!(groups.len(), 1);
+    assert_eq!(
+        groups[0].modules,
+        vec![id("/app/node_modules/react/index.js")]
+    );
+}
@@ -1,7 +1,9 @@
 use std::path::PathBuf;
 use std::sync::Arc;
 
-use super::super::{Module, ModuleGraph, ModuleId, SourceSpan, SourceType, import::ImportKind};
+use super::super::{
+    GraphExportOptions, Module, ModuleGraph, ModuleId, SourceSpan, SourceType, import::ImportKind,
+};
 
 fn make_module(id: &str) -> Module {
     let module_id = ModuleId::new_virtual(id);
@@ -93,3 +95,140 @@ async fn exports_to_dot_and_json_formats() {
     // This test just verifies that to_json() works
     assert!(!json.is_empty());
 }
+
+fn visualization_graph() -> ModuleGraph {
+    let graph = ModuleGraph::new().unwrap();
+    let mut entry = make_module("virtual:entry.ts");
+    entry.mark_entry();
+    Arc::make_mut(&mut entry.imports).push(super::super::Import::new(
+        "react",
+        Vec::new(),
+        ImportKind::Static,
+        None,
+        SourceSpan::new("virtual:entry.ts", 0, 0),
+    ));
+    let entry_id = entry.id.clone();
+    let a = make_module("virtual:a.ts");
+    let b = make_module("virtual:b.ts");
+    let (a_id, b_id) = (a.id.clone(), b.id.clone());
+    graph.add_module(entry).unwrap();
+    graph.add_module(a).unwrap();
+    graph.add_module(b).unwrap();
+    graph.add_dependency(entry_id, a_id.clone()).unwrap();
+    graph.add_dependency(a_id, b_id).unwrap();
+    graph
+}
+
+#[tokio::test]
+async fn exports_to_dot_and_mermaid_with_kinds() {
+    let graph = visualization_graph();
+    let options = GraphExportOptions::default();
+
+    let dot = graph.to_dot(&options).unwrap();
+    assert!(dot.contains("\"virtual:entry.ts\" [style=bold];"));
+    assert!(dot.contains("\"react\" [shape=ellipse, style=dashed];"));
+    assert!(dot.contains("\"virtual:a.ts\" -> \"virtual:b.ts\";"));
+    assert!(dot.contains("\"virtual:entry.ts\" -> \"react\";"));
+
+    let mermaid = graph.to_mermaid(&options).unwrap();
+    assert!(mermaid.starts_with("graph LR\n"));
+    assert!(mermaid.contains("([\"react\"])"));
+    assert!(mermaid.contains("[\"virtual:entry.ts\"]:::entry"));
+    assert_eq!(mermaid.matches("-->").count(), 3);
+}
+
+#[tokio::test]
+async fn graph_export_filters_by_root_depth_and_externals() {
+    let graph = visualization_graph();
+
+    let options = GraphExportOptions {
+        max_depth: Some(1),
+        hide_externals: true,
+        ..Default::default()
+    };
+    let json: serde_json::Value =
+        serde_json::from_str(&graph.to_json_graph(&options).unwrap()).unwrap();
+    let nodes: Vec<&str> = json["nodes"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|node| node["id"].as_str().unwrap())
+        .collect();
+    assert_eq!(nodes, vec!["virtual:a.ts", "virtual:entry.ts"]);
+    assert_eq!(json["nodes"][1]["kind"], "entry");
+    assert_eq!(json["edges"].as_array().unwrap().len(), 1);
+
+    let options = GraphExportOptions {
+        root: Some(ModuleId::new_virtual("virtual:a.ts")),
+        ..Default::default()
+    };
+    let dot = graph.to_dot(&options).unwrap();
+    assert!(!dot.contains("virtual:entry.ts"));
+    assert!(dot.contains("\"virtual:a.ts\" -> \"virtual:b.ts\";"));
+
+    let options = GraphExportOptions {
+        root: Some(ModuleId::new_virtual("virtual:missing.ts")),
+        ..Default::default()
+    };
+    assert!(graph.to_mermaid(&options).is_err());
+}
+
+#[tokio::test]
+async fn graph_export_focus_and_exclude() {
+    let graph = visualization_graph();
+    let node_ids = |options: &GraphExportOptions| -> Vec<String> {
+        let json: serde_json::Value =
+            serde_json::from_str(&graph.to_json_graph(options).unwrap()).unwrap();
+        json["nodes"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|node| node["id"].as_str().unwrap().to_string())
+            .collect()
+    };
+
+    // Focus keeps importers as well as imports
+    let options = GraphExportOptions {
+        focus: Some(ModuleId::new_virtual("virtual:b.ts")),
+        max_depth: Some(1),
+        ..Default::default()
+    };
+    assert_eq!(node_ids(&options), vec!["virtual:a.ts", "virtual:b.ts"]);
+    let dot = graph.to_dot(&options).unwrap();
+    assert!(dot.contains("\"virtual:a.ts\" -> \"virtual:b.ts\";"));
+
+    let options = GraphExportOptions {
+        focus: Some(ModuleId::new_virtual("virtual:a.ts")),
+        ..Default::default()
+    };
+    assert_eq!(
+        node_ids(&options),
+        vec!["react", "virtual:a.ts", "virtual:b.ts", "virtual:entry.ts"]
+    );
+
+    // Excluded modules and externals are dropped and not traversed
+    let options = GraphExportOptions {
+        max_depth: Some(5),
+        exclude: vec!["a.ts".to_string(), "react".to_string()],
+        ..Default::default()
+    };
+    assert_eq!(node_ids(&options), vec!["virtual:entry.ts"]);
+
+    let options = GraphExportOptions {
+        root: Some(ModuleId::new_virtual("virtual:a.ts")),
+        focus: Some(ModuleId::new_virtual("virtual:b.ts")),
+        ..Default::default()
+    };
+    assert!(graph.to_dot(&options).is_err());
+}
+
+#[tokio::test]
+async fn exports_to_html_with_embedded_data() {
+    let graph = visualization_graph();
+    let html = graph.to_html(&GraphExportOptions::default()).unwrap();
+    assert!(html.starts_with("<!doctype html>"));
+    assert!(html.contains("d3@7"));
+    assert!(!html.contains("__GRAPH_DATA__"));
+    assert!(html.contains("\"id\": \"virtual:entry.ts\""));
+    assert!(html.contains("\"kind\": \"external\""));
+}
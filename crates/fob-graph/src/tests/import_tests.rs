@@ -21,6 +21,16 @@ fn detects_side_effect_only_imports() {
         dummy_span(),
     );
     assert!(!not_side_effect.is_side_effect_only());
+
+    let dynamic = Import::new(
+        "./lazy",
+        Vec::new(),
+        ImportKind::Dynamic,
+        None,
+        dummy_span(),
+    );
+    assert!(dynamic.is_dynamic());
+    assert!(!dynamic.is_side_effect_only());
 }
 
 #[test]
@@ -0,0 +1,169 @@
+use super::super::collection::{
+    CollectedExport, CollectedImport, CollectedImportKind, CollectedImportSpecifier,
+    CollectedModule,
+};
+use super::super::{ModuleGraph, ModuleId};
+
+fn collected(id: &str, imports: &[(&str, &str)], exports: &[&str]) -> CollectedModule {
+    CollectedModule {
+        id: id.to_string(),
+        code: None,
+        is_entry: false,
+        is_external: false,
+        imports: imports
+            .iter()
+            .map(|(source, name)| CollectedImport {
+                source: source.to_string(),
+                specifiers: vec![CollectedImportSpecifier::Named {
+                    imported: name.to_string(),
+                    local: name.to_string(),
+                }],
+                kind: CollectedImportKind::Static,
+                resolved_path: source.starts_with("virtual:").then(|| source.to_string()),
+            })
+            .collect(),
+        exports: exports
+            .iter()
+            .map(|name| CollectedExport::Named {
+                exported: name.to_string(),
+                local: None,
+            })
+            .collect(),
+        has_side_effects: false,
+    }
+}
+
+fn usage(graph: &ModuleGraph, id: &ModuleId, name: &str) -> Option<usize> {
+    let module = graph.module(id).unwrap().unwrap();
+    module
+        .exports
+        .iter()
+        .find(|export| export.name == name)
+        .and_then(|export| export.usage_count)
+}
+
+#[test]
+fn apply_change_adds_module_and_resolves_imports() {
+    let graph = ModuleGraph::new().unwrap();
+    let a = ModuleId::new_virtual("virtual:a.ts");
+    let b = ModuleId::new_virtual("virtual:b.ts");
+
+    let change = graph
+        .apply_change(b.clone(), collected("virtual:b.ts", &[], &["value"]))
+        .unwrap();
+    assert!(change.is_new);
+
+    let change = graph
+        .apply_change(
+            a.clone(),
+            collected(
+                "virtual:a.ts",
+                &[("virtual:b.ts", "value"), ("react", "useState")],
+                &[],
+            ),
+        )
+        .unwrap();
+    assert!(change.is_new);
+    assert_eq!(change.added_dependencies, vec![b.clone()]);
+
+    assert_eq!(graph.dependencies(&a).unwrap(), vec![b.clone()]);
+    assert_eq!(graph.dependents(&b).unwrap(), vec![a.clone()]);
+    let module = graph.module(&a).unwrap().unwrap();
+    assert_eq!(module.imports[0].resolved_to.as_ref(), Some(&b));
+    let externals = graph.external_dependencies().unwrap();
+    assert_eq!(externals.len(), 1);
+    assert_eq!(externals[0].specifier, "react");
+    assert_eq!(externals[0].importers, vec![a]);
+}
+
+#[test]
+fn apply_change_replaces_edges_and_recounts_usage() {
+    let graph = ModuleGraph::new().unwrap();
+    let a = ModuleId::new_virtual("virtual:a.ts");
+    let b = ModuleId::new_virtual("virtual:b.ts");
+    let c = ModuleId::new_virtual("virtual:c.ts");
+
+    graph
+        .apply_change(b.clone(), collected("virtual:b.ts", &[], &["value"]))
+        .unwrap();
+    graph
+        .apply_change(c.clone(), collected("virtual:c.ts", &[], &["value"]))
+        .unwrap();
+    graph
+        .apply_change(
+            a.clone(),
+            collected(
+                "virtual:a.ts",
+                &[("virtual:b.ts", "value"), ("lodash", "x")],
+                &[],
+            ),
+        )
+        .unwrap();
+    graph.compute_export_usage_counts().unwrap();
+    assert_eq!(usage(&graph, &b, "value"), Some(1));
+    assert_eq!(usage(&graph, &c, "value"), Some(0));
+
+    let change = graph
+        .apply_change(
+            a.clone(),
+            collected("virtual:a.ts", &[("virtual:c.ts", "value")], &[]),
+        )
+        .unwrap();
+    assert!(!change.is_new);
+    assert_eq!(change.added_dependencies, vec![c.clone()]);
+    assert_eq!(change.removed_dependencies, vec![b.clone()]);
+    assert_eq!(change.recounted.len(), 3);
+
+    assert_eq!(graph.dependencies(&a).unwrap(), vec![c.clone()]);
+    assert!(graph.dependents(&b).unwrap().is_empty());
+    assert_eq!(usage(&graph, &b, "value"), Some(0));
+    assert_eq!(usage(&graph, &c, "value"), Some(1));
+    assert!(graph.external_dependencies().unwrap().is_empty());
+}
+
+#[test]
+fn apply_change_preserves_entry_points() {
+    let graph = ModuleGraph::new().unwrap();
+    let entry = ModuleId::new_virtual("virtual:entry.ts");
+
+    let mut module = collected("virtual:entry.ts", &[], &[]);
+    module.is_entry = true;
+    graph.apply_change(entry.clone(), module).unwrap();
+
+    let change = graph
+        .apply_change(entry.clone(), collected("virtual:entry.ts", &[], &[]))
+        .unwrap();
+    assert!(change.recounted.is_empty());
+    assert!(graph.entry_points().unwrap().contains(&entry));
+    assert!(graph.module(&entry).unwrap().unwrap().is_entry);
+}
+
+#[test]
+fn remove_module_unresolves_importers() {
+    let graph = ModuleGraph::new().unwrap();
+    let a = ModuleId::new_virtual("virtual:a.ts");
+    let b = ModuleId::new_virtual("virtual:b.ts");
+
+    graph
+        .apply_change(b.clone(), collected("virtual:b.ts", &[], &["value"]))
+        .unwrap();
+    graph
+        .apply_change(
+            a.clone(),
+            collected("virtual:a.ts", &[("virtual:b.ts", "value")], &[]),
+        )
+        .unwrap();
+
+    let removed = graph.remove_module(&b).unwrap().unwrap();
+    assert_eq!(removed.id, b);
+    assert!(graph.remove_module(&b).unwrap().is_none());
+
+    assert!(graph.module(&b).unwrap().is_none());
+    assert!(graph.dependencies(&a).unwrap().is_empty());
+    let module = graph.module(&a).unwrap().unwrap();
+    assert!(module.imports[0].resolved_to.is_none());
+    let externals = graph.external_dependencies().unwrap();
+    assert_eq!(externals.len(), 1);
+    assert_eq!(externals[0].specifier, b.path_string().to_string());
+    assert_eq!(externals[0].importers, vec![a]);
+}
@@ -1,10 +1,16 @@
 mod analysis_tests;
+mod chunk_group_tests;
 mod export_tests;
 mod graph_tests;
 mod import_tests;
+mod incremental_tests;
 mod module_id_tests;
 #[cfg(feature = "proptest")]
 mod property_tests;
 mod qualified_tests;
+mod query_tests;
+mod reachability_tests;
 mod smoke_tests;
+mod storage_tests;
 mod traversal_tests;
+mod type_import_tests;
@@ -0,0 +1,117 @@
+use std::path::PathBuf;
+
+use super::super::{Module, ModuleGraph, ModuleId, SourceType};
+
+fn module(path: &str) -> Module {
+    Module::builder(
+        ModuleId::new(path).unwrap(),
+        PathBuf::from(path),
+        SourceType::TypeScript,
+    )
+    .build()
+}
+
+fn id(path: &str) -> ModuleId {
+    ModuleId::new(path).unwrap()
+}
+
+/// `app` and `admin` are entries; both reach `db` through `service`,
+/// and `admin` also imports it directly.
+fn graph() -> ModuleGraph {
+    let graph = ModuleGraph::new().unwrap();
+    for path in [
+        "/app/src/app.ts",
+        "/app/src/admin.ts",
+        "/app/src/service.ts",
+        "/app/src/db.ts",
+        "/app/lib/log.ts",
+    ] {
+        graph.add_module(module(path)).unwrap();
+    }
+    for (from, to) in [
+        ("/app/src/app.ts", "/app/src/service.ts"),
+        ("/app/src/admin.ts", "/app/src/service.ts"),
+        ("/app/src/admin.ts", "/app/src/db.ts"),
+        ("/app/src/service.ts", "/app/src/db.ts"),
+        ("/app/src/db.ts", "/app/lib/log.ts"),
+    ] {
+        graph.add_dependency(id(from), id(to)).unwrap();
+    }
+    graph.add_entry_point(id("/app/src/app.ts")).unwrap();
+    graph.add_entry_point(id("/app/src/admin.ts")).unwrap();
+    graph
+}
+
+#[test]
+fn query_composes_relations_and_filters() {
+    let graph = graph();
+
+    let importers = graph.query().importers_of("src/db.ts").collect().unwrap();
+    assert_eq!(
+        importers,
+        vec![id("/app/src/admin.ts"), id("/app/src/service.ts")]
+    );
+
+    let entries = graph
+        .query()
+        .importers_of("src/db.ts")
+        .that_are_entries()
+        .collect()
+        .unwrap();
+    assert_eq!(entries, vec![id("/app/src/admin.ts")]);
+
+    let entries = graph
+        .query()
+        .transitive_importers_of("src/db.ts")
+        .that_are_entries()
+        .count()
+        .unwrap();
+    assert_eq!(entries, 2);
+
+    let reached = graph
+        .query()
+        .that_are_entries()
+        .transitive_dependencies()
+        .in_directory("src")
+        .collect()
+        .unwrap();
+    assert_eq!(
+        reached,
+        vec![id("/app/src/db.ts"), id("/app/src/service.ts")]
+    );
+
+    assert!(
+        graph
+            .query()
+            .importers_of("src/missing.ts")
+            .collect()
+            .is_err()
+    );
+}
+
+#[test]
+fn path_queries_find_every_simple_path() {
+    let graph = graph();
+    let admin = id("/app/src/admin.ts");
+    let log = id("/app/lib/log.ts");
+
+    let paths = graph.all_paths(&admin, &log).unwrap();
+    assert_eq!(paths.len(), 2);
+    assert_eq!(
+        paths[0].path,
+        vec![admin.clone(), id("/app/src/db.ts"), log.clone()]
+    );
+    assert_eq!(paths[1].depth, 3);
+
+    let shortest = graph.shortest_path(&admin, &log).unwrap().unwrap();
+    assert_eq!(shortest.depth, 2);
+    assert!(graph.shortest_path(&log, &admin).unwrap().is_none());
+
+    // Restricting the selection removes paths through excluded modules
+    let paths = graph
+        .query()
+        .filter(|module| !module.path.ends_with("service.ts"))
+        .paths("src/admin.ts", "lib/log.ts")
+        .unwrap();
+    assert_eq!(paths.len(), 1);
+}
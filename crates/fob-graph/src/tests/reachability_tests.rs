@@ -0,0 +1,131 @@
+use super::super::collection::{CollectedModule, parse_module_structure};
+use super::super::{ModuleGraph, ModuleId, SymbolKind};
+
+fn module(id: &str, code: &str, is_entry: bool) -> CollectedModule {
+    let (mut imports, exports, has_side_effects) = parse_module_structure(code).unwrap();
+    for import in &mut imports {
+        if import.source.starts_with("virtual:") {
+            import.resolved_path = Some(import.source.clone());
+        }
+    }
+    CollectedModule {
+        id: id.to_string(),
+        code: Some(code.to_string()),
+        is_entry,
+        is_external: false,
+        imports,
+        exports,
+        has_side_effects,
+    }
+}
+
+/// Build a graph from `(id, code)` pairs listed dependencies first; the last is the entry.
+fn graph(modules: &[(&str, &str)]) -> ModuleGraph {
+    let graph = ModuleGraph::new().unwrap();
+    for (index, (id, code)) in modules.iter().enumerate() {
+        let is_entry = index == modules.len() - 1;
+        graph
+            .apply_change(ModuleId::new_virtual(*id), module(id, code, is_entry))
+            .unwrap();
+    }
+    graph
+}
+
+#[test]
+fn symbol_reachability_follows_references_across_modules() {
+    let graph = graph(&[
+        (
+            "virtual:util.ts",
+            "const table = [1, 2, 3];\n\
+             function lookup(i) { return table[i]; }\n\
+             export function used(i) { return lookup(i); }\n\
+             export function unused() { return 0; }\n",
+        ),
+        (
+            "virtual:main.ts",
+            "import { used } from 'virtual:util.ts';\nconsole.log(used(1));\n",
+        ),
+    ]);
+    let util = ModuleId::new_virtual("virtual:util.ts");
+
+    let reachability = graph.symbol_reachability().unwrap();
+    assert!(reachability.is_reachable(&util, "used"));
+    assert!(reachability.is_reachable(&util, "lookup"));
+    assert!(reachability.is_reachable(&util, "table"));
+    assert!(!reachability.is_reachable(&util, "unused"));
+
+    let unreachable: Vec<_> = reachability.unreachable().collect();
+    assert_eq!(unreachable.len(), 1);
+    assert_eq!(unreachable[0].name, "unused");
+    assert_eq!(unreachable[0].kind, SymbolKind::Function);
+    assert!(unreachable[0].size > 0);
+    assert_eq!(reachability.unreachable_size(), unreachable[0].size);
+}
+
+#[test]
+fn symbol_reachability_follows_re_exports_and_namespaces() {
+    let graph = graph(&[
+        (
+            "virtual:a.ts",
+            "export const a1 = 1;\nexport const a2 = 2;\n",
+        ),
+        ("virtual:b.ts", "export const b1 = 1;\n"),
+        (
+            "virtual:index.ts",
+            "export { a1 } from 'virtual:a.ts';\nexport * from 'virtual:b.ts';\n",
+        ),
+        (
+            "virtual:main.ts",
+            "import * as lib from 'virtual:index.ts';\nconsole.log(lib);\n",
+        ),
+    ]);
+    let a = ModuleId::new_virtual("virtual:a.ts");
+    let b = ModuleId::new_virtual("virtual:b.ts");
+
+    let reachability = graph.symbol_reachability().unwrap();
+    assert!(reachability.is_reachable(&a, "a1"));
+    assert!(!reachability.is_reachable(&a, "a2"));
+    assert!(reachability.is_reachable(&b, "b1"));
+}
+
+#[test]
+fn retained_size_includes_exclusively_reachable_declarations() {
+    let graph = graph(&[
+        (
+            "virtual:util.ts",
+            "const shared = 1;\n\
+             const only = 2;\n\
+             export function first() { return shared + only; }\n\
+             export function second() { return shared; }\n",
+        ),
+        (
+            "virtual:main.ts",
+            "import { first, second } from 'virtual:util.ts';\nfirst();\nsecond();\n",
+        ),
+    ]);
+    let util = ModuleId::new_virtual("virtual:util.ts");
+    let size = |name: &str| {
+        graph
+            .symbol_reachability()
+            .unwrap()
+            .symbols
+            .into_iter()
+            .find(|info| info.name == name)
+            .unwrap()
+            .size
+    };
+
+    assert_eq!(
+        graph.retained_size(&util, "first").unwrap(),
+        size("first") + size("only")
+    );
+    assert_eq!(
+        graph.retained_size(&util, "second").unwrap(),
+        size("second")
+    );
+    assert!(
+        graph
+            .retained_size(&ModuleId::new_virtual("virtual:missing.ts"), "x")
+            .is_err()
+    );
+}
@@ -0,0 +1,114 @@
+use super::super::collection::{
+    CollectedExport, CollectedImport, CollectedImportKind, CollectedImportSpecifier,
+    CollectedModule,
+};
+use super::super::{Error, GraphStorage, MemoryGraphStorage, ModuleGraph, ModuleId};
+
+fn collected(id: &str, imports: &[&str], is_entry: bool) -> CollectedModule {
+    CollectedModule {
+        id: id.to_string(),
+        code: None,
+        is_entry,
+        is_external: false,
+        imports: imports
+            .iter()
+            .map(|source| CollectedImport {
+                source: source.to_string(),
+                specifiers: vec![CollectedImportSpecifier::Named {
+                    imported: "value".to_string(),
+                    local: "value".to_string(),
+                }],
+                kind: CollectedImportKind::Static,
+                resolved_path: source.starts_with("virtual:").then(|| source.to_string()),
+            })
+            .collect(),
+        exports: vec![CollectedExport::Named {
+            exported: "value".to_string(),
+            local: None,
+        }],
+        has_side_effects: false,
+    }
+}
+
+fn graph() -> ModuleGraph {
+    let graph = ModuleGraph::new().unwrap();
+    for (id, imports, is_entry) in [
+        ("virtual:c.ts", vec![], false),
+        ("virtual:b.ts", vec!["virtual:c.ts", "react"], false),
+        ("virtual:a.ts", vec!["virtual:b.ts"], true),
+    ] {
+        graph
+            .apply_change(ModuleId::new_virtual(id), collected(id, &imports, is_entry))
+            .unwrap();
+    }
+    graph
+}
+
+#[tokio::test]
+async fn memory_storage_round_trips_graph() {
+    let storage = MemoryGraphStorage::new();
+    assert!(storage.load().await.unwrap().is_none());
+
+    let graph = graph();
+    storage.save(&graph).await.unwrap();
+    assert_eq!(storage.len(), 3);
+
+    let loaded = storage.load().await.unwrap().unwrap();
+    let a = ModuleId::new_virtual("virtual:a.ts");
+    let b = ModuleId::new_virtual("virtual:b.ts");
+    assert_eq!(loaded.len().unwrap(), 3);
+    assert_eq!(loaded.entry_points().unwrap(), vec![a.clone()]);
+    assert_eq!(loaded.dependents(&b).unwrap(), vec![a]);
+    assert_eq!(
+        loaded.external_dependencies().unwrap()[0].specifier,
+        "react"
+    );
+}
+
+#[tokio::test]
+async fn memory_storage_applies_incremental_deltas() {
+    let storage = MemoryGraphStorage::new();
+    let graph = graph();
+    storage.save(&graph).await.unwrap();
+
+    let b = ModuleId::new_virtual("virtual:b.ts");
+    let c = ModuleId::new_virtual("virtual:c.ts");
+    let d = ModuleId::new_virtual("virtual:d.ts");
+    graph
+        .apply_change(d.clone(), collected("virtual:d.ts", &[], false))
+        .unwrap();
+    let change = graph
+        .apply_change(
+            b.clone(),
+            collected("virtual:b.ts", &["virtual:d.ts"], false),
+        )
+        .unwrap();
+    let mut changed = vec![d.clone(), b.clone()];
+    changed.extend(change.recounted);
+    graph.remove_module(&c).unwrap();
+
+    let delta = graph.delta(&changed, &[c.clone()]).unwrap();
+    assert_eq!(delta.removed, vec![c.clone()]);
+    storage.upsert(delta).await.unwrap();
+
+    let loaded = storage.load().await.unwrap().unwrap();
+    assert_eq!(loaded.len().unwrap(), 3);
+    assert!(!loaded.contains(&c).unwrap());
+    assert_eq!(loaded.dependencies(&b).unwrap(), vec![d]);
+    assert_eq!(
+        loaded.external_dependencies().unwrap().len(),
+        graph.external_dependencies().unwrap().len()
+    );
+}
+
+#[test]
+fn from_snapshot_rejects_dangling_edges() {
+    let mut snapshot = graph().snapshot().unwrap();
+    snapshot
+        .modules
+        .retain(|record| record.module.id != ModuleId::new_virtual("virtual:c.ts"));
+    assert!(matches!(
+        ModuleGraph::from_snapshot(snapshot),
+        Err(Error::CorruptStorage(_))
+    ));
+}
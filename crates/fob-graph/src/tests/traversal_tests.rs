@@ -70,3 +70,44 @@ async fn depends_on_handles_cycles_without_infinite_loop() {
     assert!(graph.depends_on(&a.id, &b.id).unwrap());
     assert!(graph.depends_on(&b.id, &a.id).unwrap());
 }
+
+#[tokio::test]
+async fn affected_entries_walks_reverse_edges() {
+    let graph = ModuleGraph::new().unwrap();
+    let mut app = module("virtual:app.js");
+    app.mark_entry();
+    let mut admin = module("virtual:admin.js");
+    admin.mark_entry();
+    let shared = module("virtual:shared.js");
+    let chart = module("virtual:chart.js");
+    let util = module("virtual:util.js");
+
+    for m in [&app, &admin, &shared, &chart, &util] {
+        graph.add_module(m.clone()).unwrap();
+    }
+    graph
+        .add_dependency(app.id.clone(), shared.id.clone())
+        .unwrap();
+    graph
+        .add_dependency(admin.id.clone(), chart.id.clone())
+        .unwrap();
+    graph
+        .add_dependency(chart.id.clone(), shared.id.clone())
+        .unwrap();
+    graph
+        .add_dependency(shared.id.clone(), util.id.clone())
+        .unwrap();
+
+    let affected = graph.affected_entries(&[util.id.clone()]).unwrap();
+    assert_eq!(affected, vec![admin.id.clone(), app.id.clone()]);
+
+    let affected = graph.affected_entries(&[chart.id.clone()]).unwrap();
+    assert_eq!(affected, vec![admin.id.clone()]);
+
+    // A changed entry point affects itself
+    let affected = graph.affected_entries(&[app.id.clone()]).unwrap();
+    assert_eq!(affected, vec![app.id.clone()]);
+
+    let missing = ModuleId::new_virtual("virtual:missing.js");
+    assert!(graph.affected_entries(&[missing]).unwrap().is_empty());
+}
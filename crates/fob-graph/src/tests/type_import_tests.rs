@@ -0,0 +1,75 @@
+use super::super::collection::{CollectedModule, parse_module_structure};
+use super::super::{ImportCodemod, ModuleGraph, ModuleId, TypeImportIssueKind};
+
+fn graph(id: &str, code: &str) -> ModuleGraph {
+    let (imports, exports, has_side_effects) = parse_module_structure(code).unwrap();
+    let graph = ModuleGraph::new().unwrap();
+    graph
+        .apply_change(
+            ModuleId::new_virtual(id),
+            CollectedModule {
+                id: id.to_string(),
+                code: Some(code.to_string()),
+                is_entry: true,
+                is_external: false,
+                imports,
+                exports,
+                has_side_effects,
+            },
+        )
+        .unwrap();
+    graph
+}
+
+#[test]
+fn type_only_import_used_as_value_is_flagged() {
+    let code = "import type { Config, Options } from './config';\n\
+                const config = new Config();\n\
+                export function run(options: Options) { return config; }\n";
+    let graph = graph("virtual:main.ts", code);
+
+    let issues = graph.type_import_issues().unwrap();
+    assert_eq!(issues.len(), 1);
+    assert_eq!(issues[0].local, "Config");
+    assert_eq!(issues[0].kind, TypeImportIssueKind::TypeOnlyUsedAsValue);
+    assert_eq!(
+        issues[0].fix.replacement,
+        "import { Config, type Options } from './config';"
+    );
+}
+
+#[test]
+fn value_import_only_used_as_type_is_flagged() {
+    let code = "import Default, { Props, render } from \"./view\";\n\
+                export function show(props: Props, view: Default) { render(props, view); }\n";
+    let graph = graph("virtual:main.ts", code);
+
+    let issues = graph.type_import_issues().unwrap();
+    let flagged: Vec<_> = issues.iter().map(|issue| issue.local.as_str()).collect();
+    assert_eq!(flagged, ["Default", "Props"]);
+    assert!(
+        issues
+            .iter()
+            .all(|issue| issue.kind == TypeImportIssueKind::ValueUsedOnlyAsType)
+    );
+
+    let fixes: Vec<_> = issues.iter().map(|issue| issue.fix.clone()).collect();
+    let fixed = ImportCodemod::apply(code, &fixes);
+    assert!(fixed.starts_with(
+        "import { type Props, render } from \"./view\";\n\
+         import type Default from \"./view\";\n"
+    ));
+}
+
+#[test]
+fn re_exported_and_correct_imports_are_not_flagged() {
+    let code = "import type { Shape } from './shapes';\n\
+                import { area, Circle } from './shapes';\n\
+                import { Unused } from './unused';\n\
+                export { Circle };\n\
+                export const size = (shape: Shape): number => area(shape);\n\
+                export type Round = Circle;\n";
+    let graph = graph("virtual:main.ts", code);
+
+    assert!(graph.type_import_issues().unwrap().is_empty());
+}
@@ -0,0 +1,185 @@
+//! In-memory MDX bundling
+//!
+//! Only available with the `bundle` feature. Imports are resolved against
+//! a JS object of virtual files; the bundler never touches a filesystem,
+//! so the resulting bundle can be evaluated directly in the browser.
+
+use crate::error::{WasmError, validate_input};
+use crate::{WasmFrontmatter, WasmMdxOptions};
+use fob_mdx::MdxCompileOptions;
+use fob_mdx::runtime::{BundleMdxOptions, BundleMdxSourceMap};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use wasm_bindgen::prelude::*;
+
+/// Result of MDX bundling (serializable for JS)
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WasmBundleResult {
+    /// Bundled ESM code, with all imports of the virtual files inlined
+    pub code: String,
+
+    /// Extracted frontmatter (if present)
+    pub frontmatter: Option<WasmFrontmatter>,
+
+    /// Frontmatter data merged with statically evaluated exports
+    pub matter: serde_json::Value,
+
+    /// Source map JSON (if requested with `sourcemap: "external"`)
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub map: Option<String>,
+
+    /// CSS collected from imported stylesheets (if any)
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub css: Option<String>,
+}
+
+/// Bundling options beyond MDX compilation (all optional)
+#[derive(Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BundleSettings {
+    /// `"inline"` or `"external"`
+    sourcemap: Option<String>,
+    #[serde(default)]
+    minify: bool,
+}
+
+/// Compile MDX and bundle it with its imports
+///
+/// # Arguments
+///
+/// * `source` - MDX source code as string (max 10MB)
+/// * `files` - Object mapping import paths (relative to the MDX file) to
+///   their contents, e.g. `{ "./Button.jsx": "export default ..." }`
+/// * `options` - Compilation options (optional, uses defaults if None)
+/// * `settings` - Bundling settings (optional, see `MdxBundleSettings`)
+///
+/// # Errors
+///
+/// In addition to the errors of `compile_mdx`, rejects with
+/// `"BundleError"` if an import can't be resolved or bundling fails.
+///
+/// # Example
+///
+/// ```javascript
+/// import { bundle_mdx } from './pkg/fob_mdx_wasm.js';
+///
+/// const result = await bundle_mdx(
+///   "import Button from './Button.jsx'\n\n<Button>Hi</Button>",
+///   { "./Button.jsx": "export default (props) => props.children" },
+/// );
+/// const url = URL.createObjectURL(new Blob([result.code], { type: "text/javascript" }));
+/// const { default: Content } = await import(url);
+/// ```
+#[wasm_bindgen(unchecked_return_type = "MdxBundleResult")]
+pub async fn bundle_mdx(
+    source: String,
+    #[wasm_bindgen(unchecked_param_type = "Record<string, string>")] files: Option<js_sys::Object>,
+    options: Option<WasmMdxOptions>,
+    #[wasm_bindgen(unchecked_param_type = "MdxBundleSettings")] settings: Option<js_sys::Object>,
+) -> Result<JsValue, JsValue> {
+    validate_input(&source, 10_000_000).map_err(|e| -> JsValue { (*e).into() })?;
+
+    let files: HashMap<String, String> = match files {
+        None => HashMap::new(),
+        Some(files) => serde_wasm_bindgen::from_value(files.into()).map_err(|e| {
+            JsValue::from(WasmError::validation_with_help(
+                "Files must be an object mapping paths to string contents",
+                e.to_string(),
+            ))
+        })?,
+    };
+    let settings: BundleSettings = match settings {
+        None => BundleSettings::default(),
+        Some(settings) => serde_wasm_bindgen::from_value(settings.into()).map_err(|e| {
+            JsValue::from(WasmError::validation_with_help(
+                "Invalid bundle settings",
+                e.to_string(),
+            ))
+        })?,
+    };
+    let sourcemap = match settings.sourcemap.as_deref() {
+        None => None,
+        Some("inline") => Some(BundleMdxSourceMap::Inline),
+        Some("external") => Some(BundleMdxSourceMap::External),
+        Some(other) => {
+            return Err(WasmError::validation(format!(
+                "Invalid sourcemap setting '{}', expected 'inline' or 'external'",
+                other
+            ))
+            .into());
+        }
+    };
+
+    let mdx_options = match options {
+        Some(ref opts) => MdxCompileOptions::from(opts),
+        None => MdxCompileOptions::new(),
+    };
+    let bundle_options = BundleMdxOptions {
+        source,
+        files,
+        mdx_options: Some(mdx_options),
+        sourcemap,
+        minify: settings.minify,
+        ..BundleMdxOptions::default()
+    };
+
+    let result = fob_mdx::bundle_mdx(bundle_options)
+        .await
+        .map_err(|e| -> JsValue { WasmError::from_bundle_error(e).into() })?;
+
+    let wasm_result = WasmBundleResult {
+        code: result.code,
+        frontmatter: result.frontmatter.map(WasmFrontmatter::from),
+        matter: result.matter,
+        map: result.map,
+        css: result.css,
+    };
+
+    crate::to_js(&wasm_result).map_err(|e| {
+        let err =
+            WasmError::serialization_with_help("Failed to serialize bundle result", e.to_string());
+        JsValue::from(err)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wasm_bindgen_test::*;
+
+    #[wasm_bindgen_test]
+    async fn test_bundle_with_virtual_files() {
+        let files = js_sys::Object::new();
+        js_sys::Reflect::set(
+            &files,
+            &"./Note.jsx".into(),
+            &"export default () => 'virtual-note'".into(),
+        )
+        .unwrap();
+        let result = bundle_mdx(
+            "import Note from './Note.jsx'\n\n# Hi\n\n<Note />".to_string(),
+            Some(files),
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+        let result: WasmBundleResult = serde_wasm_bindgen::from_value(result).unwrap();
+        assert!(result.code.contains("virtual-note"));
+    }
+
+    #[wasm_bindgen_test]
+    async fn test_bundle_missing_import() {
+        let err = bundle_mdx(
+            "import Missing from './missing.jsx'\n\n<Missing />".to_string(),
+            None,
+            None,
+            None,
+        )
+        .await
+        .unwrap_err();
+        let err: serde_json::Value = serde_wasm_bindgen::from_value(err).unwrap();
+        assert_eq!(err["kind"], "BundleError");
+    }
+}
@@ -5,69 +5,112 @@
 //! error objects with full TypeScript support.
 
 use fob_mdx::MdxError;
-use serde::{Deserialize, Serialize};
+use serde::{Serialize, Serializer};
 use std::fmt;
 use wasm_bindgen::prelude::*;
 
 /// Error categories specific to WASM context
 ///
 /// These categories help JavaScript consumers handle different error
-/// types appropriately. The `kind` field enables type discrimination
-/// in TypeScript through tagged union types.
+/// types appropriately. Errors serialize in the diagnostic format shared
+/// by all bindings (`fob_bundler::diagnostics::Diagnostic`, which this
+/// crate can't depend on), with the variant name as PascalCase `kind`:
 ///
-/// Note: Variant names intentionally include "Error" suffix for serde serialization
-/// to produce JSON like `{"kind": "validationError", ...}` as required by the API.
-#[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(tag = "kind", rename_all = "camelCase")]
+/// ```json
+/// { "kind": "CompilationError", "severity": "error", "message": "...",
+///   "file": "post.mdx", "line": 3, "column": 10, "help": "...", "related": [] }
+/// ```
+#[derive(Debug, Clone)]
 #[allow(clippy::enum_variant_names)]
 pub enum WasmError {
     /// Input validation failed (size limits, invalid characters, etc.)
-    #[serde(rename_all = "camelCase")]
     ValidationError {
         message: String,
-        #[serde(skip_serializing_if = "Option::is_none")]
-        details: Option<String>,
+        help: Option<String>,
     },
 
     /// MDX compilation failed (syntax errors, invalid JSX, etc.)
-    #[serde(rename_all = "camelCase")]
     CompilationError {
         message: String,
-        #[serde(skip_serializing_if = "Option::is_none")]
-        location: Option<ErrorLocation>,
-        #[serde(skip_serializing_if = "Option::is_none")]
-        context: Option<String>,
-        #[serde(skip_serializing_if = "Option::is_none")]
-        suggestion: Option<String>,
+        file: Option<String>,
+        /// 1-based line number
+        line: Option<usize>,
+        /// 1-based column number
+        column: Option<usize>,
+        help: Option<String>,
+    },
+
+    /// Bundling failed (missing import, resource limit exceeded, etc.)
+    BundleError {
+        message: String,
+        /// Underlying causes, outermost first
+        related: Vec<WasmError>,
     },
 
     /// Failed to serialize result to JavaScript
-    #[serde(rename_all = "camelCase")]
     SerializationError {
         message: String,
-        #[serde(skip_serializing_if = "Option::is_none")]
-        details: Option<String>,
+        help: Option<String>,
     },
 
     /// Internal/unexpected error
-    #[serde(rename_all = "camelCase")]
     InternalError {
         message: String,
-        #[serde(skip_serializing_if = "Option::is_none")]
-        details: Option<String>,
+        help: Option<String>,
     },
 }
 
-/// Source code location information
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Serialized form of [`WasmError`]
+#[derive(Serialize)]
 #[serde(rename_all = "camelCase")]
-pub struct ErrorLocation {
+struct Diagnostic<'a> {
+    kind: &'static str,
+    severity: &'static str,
+    message: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    file: Option<&'a str>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub file: Option<String>,
+    line: Option<usize>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub line: Option<usize>,
+    column: Option<usize>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub column: Option<usize>,
+    help: Option<&'a str>,
+    #[serde(skip_serializing_if = "<[_]>::is_empty")]
+    related: &'a [WasmError],
+}
+
+impl Serialize for WasmError {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut diagnostic = Diagnostic {
+            kind: self.kind_str(),
+            severity: "error",
+            message: self.message(),
+            file: None,
+            line: None,
+            column: None,
+            help: None,
+            related: &[],
+        };
+        match self {
+            Self::ValidationError { help, .. }
+            | Self::SerializationError { help, .. }
+            | Self::InternalError { help, .. } => diagnostic.help = help.as_deref(),
+            Self::CompilationError {
+                file,
+                line,
+                column,
+                help,
+                ..
+            } => {
+                diagnostic.file = file.as_deref();
+                diagnostic.line = *line;
+                diagnostic.column = *column;
+                diagnostic.help = help.as_deref();
+            }
+            Self::BundleError { related, .. } => diagnostic.related = related,
+        }
+        diagnostic.serialize(serializer)
+    }
 }
 
 impl WasmError {
@@ -75,35 +118,56 @@ impl WasmError {
     pub fn validation(message: impl Into<String>) -> Self {
         Self::ValidationError {
             message: message.into(),
-            details: None,
+            help: None,
         }
     }
 
-    /// Create a validation error with details
-    pub fn validation_with_details(message: impl Into<String>, details: impl Into<String>) -> Self {
+    /// Create a validation error with help text
+    pub fn validation_with_help(message: impl Into<String>, help: impl Into<String>) -> Self {
         Self::ValidationError {
             message: message.into(),
-            details: Some(details.into()),
+            help: Some(help.into()),
         }
     }
 
     /// Create a compilation error from an MdxError
+    ///
+    /// The suggestion becomes the help text, like in the other bindings.
     pub fn from_mdx_error(err: MdxError) -> Self {
-        let location = if err.file.is_some() || err.line.is_some() || err.column.is_some() {
-            Some(ErrorLocation {
-                file: err.file,
-                line: err.line,
-                column: err.column,
-            })
-        } else {
-            None
-        };
-
         Self::CompilationError {
             message: err.message,
-            location,
-            context: err.context,
-            suggestion: err.suggestion,
+            file: err.file,
+            line: err.line,
+            column: err.column,
+            help: err.suggestion,
+        }
+    }
+
+    /// Create a bundle error
+    pub fn bundle(message: impl Into<String>) -> Self {
+        Self::BundleError {
+            message: message.into(),
+            related: Vec::new(),
+        }
+    }
+
+    /// Create an error from a failed `bundle_mdx` call
+    ///
+    /// MDX compilation errors keep their location as a compilation error;
+    /// anything else becomes a bundle error with each underlying cause as a
+    /// related diagnostic.
+    #[cfg(feature = "bundle")]
+    pub fn from_bundle_error(err: anyhow::Error) -> Self {
+        if let Some(mdx_err) = err.downcast_ref::<Box<MdxError>>() {
+            return Self::from_mdx_error((**mdx_err).clone());
+        }
+        Self::BundleError {
+            message: err.to_string(),
+            related: err
+                .chain()
+                .skip(1)
+                .map(|cause| Self::bundle(cause.to_string()))
+                .collect(),
         }
     }
 
@@ -111,18 +175,15 @@ impl WasmError {
     pub fn serialization(message: impl Into<String>) -> Self {
         Self::SerializationError {
             message: message.into(),
-            details: None,
+            help: None,
         }
     }
 
-    /// Create a serialization error with details
-    pub fn serialization_with_details(
-        message: impl Into<String>,
-        details: impl Into<String>,
-    ) -> Self {
+    /// Create a serialization error with help text
+    pub fn serialization_with_help(message: impl Into<String>, help: impl Into<String>) -> Self {
         Self::SerializationError {
             message: message.into(),
-            details: Some(details.into()),
+            help: Some(help.into()),
         }
     }
 
@@ -130,15 +191,16 @@ impl WasmError {
     pub fn internal(message: impl Into<String>) -> Self {
         Self::InternalError {
             message: message.into(),
-            details: None,
+            help: None,
         }
     }
 
-    /// Get the error kind as a string (for logging/debugging)
+    /// Get the error kind, as serialized in `kind`
     pub fn kind_str(&self) -> &'static str {
         match self {
             Self::ValidationError { .. } => "ValidationError",
             Self::CompilationError { .. } => "CompilationError",
+            Self::BundleError { .. } => "BundleError",
             Self::SerializationError { .. } => "SerializationError",
             Self::InternalError { .. } => "InternalError",
         }
@@ -149,6 +211,7 @@ impl WasmError {
         match self {
             Self::ValidationError { message, .. }
             | Self::CompilationError { message, .. }
+            | Self::BundleError { message, .. }
             | Self::SerializationError { message, .. }
             | Self::InternalError { message, .. } => message,
         }
@@ -157,45 +220,42 @@ impl WasmError {
 
 impl fmt::Display for WasmError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let title = match self {
+            Self::ValidationError { .. } => "Validation Error",
+            Self::CompilationError { .. } => "Compilation Error",
+            Self::BundleError { .. } => "Bundle Error",
+            Self::SerializationError { .. } => "Serialization Error",
+            Self::InternalError { .. } => "Internal Error",
+        };
+        write!(f, "{}: {}", title, self.message())?;
         match self {
-            Self::ValidationError { message, details } => {
-                write!(f, "Validation Error: {}", message)?;
-                if let Some(d) = details {
-                    write!(f, "\nDetails: {}", d)?;
+            Self::ValidationError { help, .. }
+            | Self::SerializationError { help, .. }
+            | Self::InternalError { help, .. } => {
+                if let Some(help) = help {
+                    write!(f, "\nHelp: {}", help)?;
                 }
             }
             Self::CompilationError {
-                message,
-                location,
-                context,
-                suggestion,
+                file,
+                line,
+                column,
+                help,
+                ..
             } => {
-                write!(f, "Compilation Error: {}", message)?;
-                if let Some(loc) = location {
-                    if let Some(file) = &loc.file {
-                        write!(f, "\n  in {}", file)?;
-                    }
-                    if let (Some(line), Some(col)) = (loc.line, loc.column) {
-                        write!(f, "\n  at line {}, column {}", line, col)?;
-                    }
-                }
-                if let Some(ctx) = context {
-                    write!(f, "\n\n{}", ctx)?;
+                if let Some(file) = file {
+                    write!(f, "\n  in {}", file)?;
                 }
-                if let Some(sug) = suggestion {
-                    write!(f, "\nSuggestion: {}", sug)?;
+                if let (Some(line), Some(col)) = (line, column) {
+                    write!(f, "\n  at line {}, column {}", line, col)?;
                 }
-            }
-            Self::SerializationError { message, details } => {
-                write!(f, "Serialization Error: {}", message)?;
-                if let Some(d) = details {
-                    write!(f, "\nDetails: {}", d)?;
+                if let Some(help) = help {
+                    write!(f, "\nHelp: {}", help)?;
                 }
             }
-            Self::InternalError { message, details } => {
-                write!(f, "Internal Error: {}", message)?;
-                if let Some(d) = details {
-                    write!(f, "\nDetails: {}", d)?;
+            Self::BundleError { related, .. } => {
+                for cause in related {
+                    write!(f, "\nCaused by: {}", cause.message())?;
                 }
             }
         }
@@ -223,7 +283,7 @@ impl From<MdxError> for WasmError {
 impl From<WasmError> for JsValue {
     fn from(err: WasmError) -> Self {
         // Serialize the error to a JS object
-        match serde_wasm_bindgen::to_value(&err) {
+        match crate::to_js(&err) {
             Ok(js_value) => js_value,
             Err(serialization_err) => {
                 // Fallback: if serialization fails, return a simple string error
@@ -245,7 +305,7 @@ impl From<WasmError> for JsValue {
 pub fn validate_input(source: &str, max_size: usize) -> Result<(), Box<WasmError>> {
     // Check size limit (default 10MB for WASM environments)
     if source.len() > max_size {
-        return Err(Box::new(WasmError::validation_with_details(
+        return Err(Box::new(WasmError::validation_with_help(
             "Input size exceeds maximum allowed",
             format!(
                 "Input is {} bytes, maximum is {} bytes ({}MB)",
@@ -269,6 +329,7 @@ pub fn validate_input(source: &str, max_size: usize) -> Result<(), Box<WasmError
 #[cfg(test)]
 mod tests {
     use super::*;
+    use serde_json::json;
 
     #[test]
     fn test_validation_error_creation() {
@@ -278,11 +339,11 @@ mod tests {
     }
 
     #[test]
-    fn test_validation_error_with_details() {
-        let err = WasmError::validation_with_details("Too large", "10MB limit exceeded");
+    fn test_validation_error_with_help() {
+        let err = WasmError::validation_with_help("Too large", "10MB limit exceeded");
         match err {
-            WasmError::ValidationError { details, .. } => {
-                assert_eq!(details, Some("10MB limit exceeded".to_string()));
+            WasmError::ValidationError { help, .. } => {
+                assert_eq!(help, Some("10MB limit exceeded".to_string()));
             }
             _ => panic!("Wrong error type"),
         }
@@ -300,17 +361,16 @@ mod tests {
         match wasm_err {
             WasmError::CompilationError {
                 message,
-                location,
-                suggestion,
-                ..
+                file,
+                line,
+                column,
+                help,
             } => {
                 assert_eq!(message, "Parse failed");
-                assert!(location.is_some());
-                let loc = location.unwrap();
-                assert_eq!(loc.file, Some("test.mdx".to_string()));
-                assert_eq!(loc.line, Some(10));
-                assert_eq!(loc.column, Some(5));
-                assert_eq!(suggestion, Some("Check syntax".to_string()));
+                assert_eq!(file, Some("test.mdx".to_string()));
+                assert_eq!(line, Some(10));
+                assert_eq!(column, Some(5));
+                assert_eq!(help, Some("Check syntax".to_string()));
             }
             _ => panic!("Wrong error type"),
         }
@@ -323,6 +383,13 @@ mod tests {
         assert_eq!(err.message(), "Failed to convert");
     }
 
+    #[test]
+    fn test_bundle_error() {
+        let err = WasmError::bundle("Could not resolve './x.js'");
+        assert_eq!(err.kind_str(), "BundleError");
+        assert!(format!("{}", err).starts_with("Bundle Error"));
+    }
+
     #[test]
     fn test_internal_error() {
         let err = WasmError::internal("Unexpected failure");
@@ -333,13 +400,10 @@ mod tests {
     fn test_error_display() {
         let err = WasmError::CompilationError {
             message: "Syntax error".to_string(),
-            location: Some(ErrorLocation {
-                file: Some("test.mdx".to_string()),
-                line: Some(5),
-                column: Some(10),
-            }),
-            context: Some("  5 | import { foo } fro './bar'".to_string()),
-            suggestion: Some("Did you mean 'from'?".to_string()),
+            file: Some("test.mdx".to_string()),
+            line: Some(5),
+            column: Some(10),
+            help: Some("Did you mean 'from'?".to_string()),
         };
 
         let display = format!("{}", err);
@@ -393,82 +457,48 @@ mod tests {
     #[test]
     fn test_location_without_file() {
         let mdx_err = MdxError::new("Error").with_location(5, 10);
-        let wasm_err = WasmError::from_mdx_error(mdx_err);
-
-        match wasm_err {
-            WasmError::CompilationError { location, .. } => {
-                let loc = location.unwrap();
-                assert_eq!(loc.file, None);
-                assert_eq!(loc.line, Some(5));
-                assert_eq!(loc.column, Some(10));
-            }
-            _ => panic!("Wrong error type"),
-        }
-    }
-
-    #[test]
-    fn test_error_without_location() {
-        let mdx_err = MdxError::new("Simple error");
-        let wasm_err = WasmError::from_mdx_error(mdx_err);
-
-        match wasm_err {
-            WasmError::CompilationError { location, .. } => {
-                assert!(location.is_none());
-            }
-            _ => panic!("Wrong error type"),
-        }
+        let json = serde_json::to_value(WasmError::from_mdx_error(mdx_err)).unwrap();
+        assert_eq!(
+            json,
+            json!({
+                "kind": "CompilationError",
+                "severity": "error",
+                "message": "Error",
+                "line": 5,
+                "column": 10,
+            })
+        );
     }
 
     #[test]
-    fn test_wasmerror_serialization() {
-        // Test ValidationError serializes with correct kind
-        let err = WasmError::ValidationError {
-            message: "test".to_string(),
-            details: None,
-        };
-        let json = serde_json::to_string(&err).unwrap();
-        assert!(
-            json.contains(r#""kind":"validationError""#),
-            "Expected 'validationError' in JSON, got: {}",
-            json
-        );
-
-        // Test CompilationError serializes with correct kind
-        let comp_err = WasmError::CompilationError {
-            message: "test".to_string(),
-            location: None,
-            context: None,
-            suggestion: None,
-        };
-        let comp_json = serde_json::to_string(&comp_err).unwrap();
-        assert!(
-            comp_json.contains(r#""kind":"compilationError""#),
-            "Expected 'compilationError' in JSON, got: {}",
-            comp_json
-        );
-
-        // Test SerializationError serializes with correct kind
-        let ser_err = WasmError::SerializationError {
-            message: "test".to_string(),
-            details: None,
-        };
-        let ser_json = serde_json::to_string(&ser_err).unwrap();
-        assert!(
-            ser_json.contains(r#""kind":"serializationError""#),
-            "Expected 'serializationError' in JSON, got: {}",
-            ser_json
+    fn test_serializes_as_diagnostic() {
+        let err = WasmError::validation_with_help("Too large", "Input is 11MB");
+        assert_eq!(
+            serde_json::to_value(&err).unwrap(),
+            json!({
+                "kind": "ValidationError",
+                "severity": "error",
+                "message": "Too large",
+                "help": "Input is 11MB",
+            })
         );
 
-        // Test InternalError serializes with correct kind
-        let int_err = WasmError::InternalError {
-            message: "test".to_string(),
-            details: None,
+        let err = WasmError::BundleError {
+            message: "Failed to bundle MDX".to_string(),
+            related: vec![WasmError::bundle("Could not resolve './x.js'")],
         };
-        let int_json = serde_json::to_string(&int_err).unwrap();
-        assert!(
-            int_json.contains(r#""kind":"internalError""#),
-            "Expected 'internalError' in JSON, got: {}",
-            int_json
+        assert_eq!(
+            serde_json::to_value(&err).unwrap(),
+            json!({
+                "kind": "BundleError",
+                "severity": "error",
+                "message": "Failed to bundle MDX",
+                "related": [{
+                    "kind": "BundleError",
+                    "severity": "error",
+                    "message": "Could not resolve './x.js'",
+                }],
+            })
         );
     }
 }
@@ -10,7 +10,8 @@
 //! - Compile MDX to JSX in the browser
 //! - Extract frontmatter (YAML/TOML)
 //! - Support for GFM, math, footnotes
-//! - No bundling (compile-only, WASM-compatible)
+//! - Custom AST plugins written in JavaScript
+//! - In-memory bundling of imports with the `bundle` feature
 //!
 //! ## Usage
 //!
@@ -27,11 +28,21 @@
 //! console.log(result.code); // Compiled JSX
 //! ```
 
+#[cfg(feature = "bundle")]
+mod bundle;
 mod error;
+mod plugins;
+mod typescript;
 
+#[cfg(feature = "bundle")]
+pub use bundle::{WasmBundleResult, bundle_mdx};
 use error::{WasmError, validate_input};
 use fob_mdx::{MdxCompileOptions, compile};
+use plugins::JsPlugin;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Arc;
 use wasm_bindgen::prelude::*;
 
 /// Initialize panic hook for better error messages in console
@@ -49,10 +60,31 @@ pub fn init() {
 pub struct WasmMdxOptions {
     filepath: Option<String>,
     gfm: bool,
+    gfm_tables: bool,
+    gfm_strikethrough: bool,
+    gfm_tasklist: bool,
+    gfm_autolink: bool,
     footnotes: bool,
     math: bool,
+    math_output: String,
     jsx_runtime: String,
+    jsx_target: String,
+    use_default_plugins: bool,
+    default_heading_ids: bool,
+    default_image_optimization: bool,
+    heading_id_prefix: String,
+    heading_slug_style: String,
+    link_pattern: Option<String>,
+    image_files: HashMap<PathBuf, Vec<u8>>,
+    image_public_dir: Option<String>,
+    image_placeholder_max_bytes: usize,
+    include_files: HashMap<PathBuf, Vec<u8>>,
     output_format: String,
+    provider_import_source: Option<String>,
+    words_per_minute: u32,
+    structured_content: bool,
+    components: HashMap<String, String>,
+    plugins: Vec<JsPlugin>,
 }
 
 #[wasm_bindgen]
@@ -63,10 +95,31 @@ impl WasmMdxOptions {
         Self {
             filepath: None,
             gfm: false,
+            gfm_tables: true,
+            gfm_strikethrough: true,
+            gfm_tasklist: true,
+            gfm_autolink: true,
             footnotes: false,
             math: false,
+            math_output: "tex".to_string(),
             jsx_runtime: "react/jsx-runtime".to_string(),
+            jsx_target: "react".to_string(),
+            use_default_plugins: true,
+            default_heading_ids: true,
+            default_image_optimization: true,
+            heading_id_prefix: String::new(),
+            heading_slug_style: "default".to_string(),
+            link_pattern: None,
+            image_files: HashMap::new(),
+            image_public_dir: None,
+            image_placeholder_max_bytes: 0,
+            include_files: HashMap::new(),
             output_format: "program".to_string(),
+            provider_import_source: None,
+            words_per_minute: fob_mdx::DEFAULT_WORDS_PER_MINUTE,
+            structured_content: false,
+            components: HashMap::new(),
+            plugins: Vec::new(),
         }
     }
 
@@ -94,6 +147,54 @@ impl WasmMdxOptions {
         self.gfm
     }
 
+    /// Enable/disable GFM tables (default: true, only applies with GFM enabled)
+    #[wasm_bindgen]
+    pub fn set_gfm_tables(&mut self, enabled: bool) {
+        self.gfm_tables = enabled;
+    }
+
+    /// Get GFM tables setting
+    #[wasm_bindgen(getter)]
+    pub fn gfm_tables(&self) -> bool {
+        self.gfm_tables
+    }
+
+    /// Enable/disable GFM strikethrough (default: true, only applies with GFM enabled)
+    #[wasm_bindgen]
+    pub fn set_gfm_strikethrough(&mut self, enabled: bool) {
+        self.gfm_strikethrough = enabled;
+    }
+
+    /// Get GFM strikethrough setting
+    #[wasm_bindgen(getter)]
+    pub fn gfm_strikethrough(&self) -> bool {
+        self.gfm_strikethrough
+    }
+
+    /// Enable/disable GFM task list items (default: true, only applies with GFM enabled)
+    #[wasm_bindgen]
+    pub fn set_gfm_tasklist(&mut self, enabled: bool) {
+        self.gfm_tasklist = enabled;
+    }
+
+    /// Get GFM task list items setting
+    #[wasm_bindgen(getter)]
+    pub fn gfm_tasklist(&self) -> bool {
+        self.gfm_tasklist
+    }
+
+    /// Enable/disable GFM autolink literals (default: true, only applies with GFM enabled)
+    #[wasm_bindgen]
+    pub fn set_gfm_autolink(&mut self, enabled: bool) {
+        self.gfm_autolink = enabled;
+    }
+
+    /// Get GFM autolink literals setting
+    #[wasm_bindgen(getter)]
+    pub fn gfm_autolink(&self) -> bool {
+        self.gfm_autolink
+    }
+
     /// Enable/disable footnotes
     #[wasm_bindgen]
     pub fn set_footnotes(&mut self, enabled: bool) {
@@ -118,6 +219,23 @@ impl WasmMdxOptions {
         self.math
     }
 
+    /// Set math rendering ("tex" for client-side libraries, or "mathml")
+    ///
+    /// Unknown values fall back to "tex".
+    #[wasm_bindgen]
+    pub fn set_math_output(&mut self, output: &str) {
+        self.math_output = output
+            .parse::<fob_mdx::MathOutput>()
+            .map(|_| output.to_ascii_lowercase())
+            .unwrap_or_else(|_| "tex".to_string());
+    }
+
+    /// Get math rendering
+    #[wasm_bindgen(getter)]
+    pub fn math_output(&self) -> String {
+        self.math_output.clone()
+    }
+
     /// Set JSX runtime (default: "react/jsx-runtime")
     #[wasm_bindgen]
     pub fn set_jsx_runtime(&mut self, runtime: String) {
@@ -130,6 +248,148 @@ impl WasmMdxOptions {
         self.jsx_runtime.clone()
     }
 
+    /// Set JSX target ("react", "preact", "vue", "solid" or "hono")
+    ///
+    /// Unknown targets fall back to "react".
+    #[wasm_bindgen]
+    pub fn set_jsx_target(&mut self, target: &str) {
+        self.jsx_target = target
+            .parse::<fob_mdx::JsxTarget>()
+            .map(|_| target.to_ascii_lowercase())
+            .unwrap_or_else(|_| "react".to_string());
+    }
+
+    /// Get JSX target
+    #[wasm_bindgen(getter)]
+    pub fn jsx_target(&self) -> String {
+        self.jsx_target.clone()
+    }
+
+    /// Enable/disable the default plugins (heading IDs, image optimization)
+    #[wasm_bindgen]
+    pub fn set_use_default_plugins(&mut self, enabled: bool) {
+        self.use_default_plugins = enabled;
+    }
+
+    /// Get default plugins setting
+    #[wasm_bindgen(getter)]
+    pub fn use_default_plugins(&self) -> bool {
+        self.use_default_plugins
+    }
+
+    /// Enable/disable heading IDs (default: true, only applies with default plugins enabled)
+    #[wasm_bindgen]
+    pub fn set_default_heading_ids(&mut self, enabled: bool) {
+        self.default_heading_ids = enabled;
+    }
+
+    /// Get heading IDs setting
+    #[wasm_bindgen(getter)]
+    pub fn default_heading_ids(&self) -> bool {
+        self.default_heading_ids
+    }
+
+    /// Enable/disable image collection and optimization (default: true, only
+    /// applies with default plugins enabled)
+    #[wasm_bindgen]
+    pub fn set_default_image_optimization(&mut self, enabled: bool) {
+        self.default_image_optimization = enabled;
+    }
+
+    /// Get image optimization setting
+    #[wasm_bindgen(getter)]
+    pub fn default_image_optimization(&self) -> bool {
+        self.default_image_optimization
+    }
+
+    /// Set a prefix for generated heading IDs (e.g. "docs-")
+    #[wasm_bindgen]
+    pub fn set_heading_id_prefix(&mut self, prefix: String) {
+        self.heading_id_prefix = prefix;
+    }
+
+    /// Get heading ID prefix
+    #[wasm_bindgen(getter)]
+    pub fn heading_id_prefix(&self) -> String {
+        self.heading_id_prefix.clone()
+    }
+
+    /// Set heading slug style ("default" or "github")
+    ///
+    /// Unknown styles fall back to "default".
+    #[wasm_bindgen]
+    pub fn set_heading_slug_style(&mut self, style: &str) {
+        self.heading_slug_style = style
+            .parse::<fob_mdx::Slugify>()
+            .map(|_| style.to_ascii_lowercase())
+            .unwrap_or_else(|_| "default".to_string());
+    }
+
+    /// Get heading slug style
+    #[wasm_bindgen(getter)]
+    pub fn heading_slug_style(&self) -> String {
+        self.heading_slug_style.clone()
+    }
+
+    /// Rewrite relative `.md`/`.mdx` links with a route pattern
+    ///
+    /// `{slug}` is the file name and `{path}` the normalized path, both
+    /// without extension (e.g. `"/blog/{slug}"`).
+    #[wasm_bindgen]
+    pub fn set_link_pattern(&mut self, pattern: String) {
+        self.link_pattern = Some(pattern);
+    }
+
+    /// Get link rewrite pattern
+    #[wasm_bindgen(getter)]
+    pub fn link_pattern(&self) -> Option<String> {
+        self.link_pattern.clone()
+    }
+
+    /// Provide the content of a local image file
+    ///
+    /// When any files are given, images resolving to them get `width` and
+    /// `height` props and are reported with their size and format. Relative
+    /// URLs resolve against the filepath's directory.
+    #[wasm_bindgen]
+    pub fn add_image_file(&mut self, path: String, content: Vec<u8>) {
+        self.image_files.insert(PathBuf::from(path), content);
+    }
+
+    /// Set the directory root-relative image URLs (`/img.png`) resolve against
+    #[wasm_bindgen]
+    pub fn set_image_public_dir(&mut self, dir: String) {
+        self.image_public_dir = Some(dir);
+    }
+
+    /// Get image public directory
+    #[wasm_bindgen(getter)]
+    pub fn image_public_dir(&self) -> Option<String> {
+        self.image_public_dir.clone()
+    }
+
+    /// Embed images up to this many bytes as blurred placeholders (default: 0, disabled)
+    #[wasm_bindgen]
+    pub fn set_image_placeholder_max_bytes(&mut self, max_bytes: usize) {
+        self.image_placeholder_max_bytes = max_bytes;
+    }
+
+    /// Get image placeholder size limit
+    #[wasm_bindgen(getter)]
+    pub fn image_placeholder_max_bytes(&self) -> usize {
+        self.image_placeholder_max_bytes
+    }
+
+    /// Provide a file for `<Include src="..." />` lines
+    ///
+    /// Includes are expanded only when at least one file is given; paths
+    /// resolve against the directory of the including file.
+    #[wasm_bindgen]
+    pub fn add_include_file(&mut self, path: String, content: String) {
+        self.include_files
+            .insert(PathBuf::from(path), content.into_bytes());
+    }
+
     /// Set output format ("program" or "function-body")
     #[wasm_bindgen]
     pub fn set_output_format(&mut self, format: &str) {
@@ -144,6 +404,70 @@ impl WasmMdxOptions {
     pub fn output_format(&self) -> String {
         self.output_format.clone()
     }
+
+    /// Import `useMDXComponents` from a provider module (e.g. "@mdx-js/react")
+    ///
+    /// Provider components are merged below `props.components`.
+    #[wasm_bindgen]
+    pub fn set_provider_import_source(&mut self, source: String) {
+        self.provider_import_source = Some(source);
+    }
+
+    /// Get provider import source
+    #[wasm_bindgen(getter)]
+    pub fn provider_import_source(&self) -> Option<String> {
+        self.provider_import_source.clone()
+    }
+
+    /// Set reading speed for reading time estimates (default: 200)
+    #[wasm_bindgen]
+    pub fn set_words_per_minute(&mut self, words_per_minute: u32) {
+        self.words_per_minute = words_per_minute;
+    }
+
+    /// Get reading speed
+    #[wasm_bindgen(getter)]
+    pub fn words_per_minute(&self) -> u32 {
+        self.words_per_minute
+    }
+
+    /// Also return the document as a structured content tree (default: false)
+    #[wasm_bindgen]
+    pub fn set_structured_content(&mut self, enabled: bool) {
+        self.structured_content = enabled;
+    }
+
+    /// Get structured content setting
+    #[wasm_bindgen(getter)]
+    pub fn structured_content(&self) -> bool {
+        self.structured_content
+    }
+
+    /// Map an element or component name to a component at compile time
+    ///
+    /// `component` is an identifier in scope (`"Heading"`) or
+    /// `"module#export"` (`"./ui.js#Heading"`).
+    #[wasm_bindgen]
+    pub fn set_component(&mut self, name: String, component: String) {
+        self.components.insert(name, component);
+    }
+
+    /// Register a JavaScript function as an AST plugin
+    ///
+    /// The callback receives mdast nodes as plain objects: the document
+    /// root, or with `node_type` (e.g. `"heading"`) every node of that type.
+    /// It returns a replacement node, `null` to remove the node, or nothing
+    /// to keep the node it mutated. Plugins run after the built-in ones, in
+    /// registration order.
+    #[wasm_bindgen]
+    pub fn add_plugin(
+        &mut self,
+        name: String,
+        #[wasm_bindgen(unchecked_param_type = "MdxPluginCallback")] callback: js_sys::Function,
+        node_type: Option<String>,
+    ) {
+        self.plugins.push(JsPlugin::new(name, node_type, callback));
+    }
 }
 
 impl Default for WasmMdxOptions {
@@ -162,17 +486,61 @@ impl From<&WasmMdxOptions> for MdxCompileOptions {
 
         // Set feature flags
         rust_opts.gfm = opts.gfm;
+        rust_opts.gfm_tables = opts.gfm_tables;
+        rust_opts.gfm_strikethrough = opts.gfm_strikethrough;
+        rust_opts.gfm_tasklist = opts.gfm_tasklist;
+        rust_opts.gfm_autolink = opts.gfm_autolink;
         rust_opts.footnotes = opts.footnotes;
         rust_opts.math = opts.math;
+        rust_opts.math_output = opts.math_output.parse().unwrap_or_default();
 
         // Set JSX runtime
         rust_opts.jsx_runtime = opts.jsx_runtime.clone();
+        rust_opts.jsx_target = opts.jsx_target.parse().unwrap_or_default();
+
+        rust_opts.use_default_plugins = opts.use_default_plugins;
+        rust_opts.default_heading_ids = opts.default_heading_ids;
+        rust_opts.default_image_optimization = opts.default_image_optimization;
+        rust_opts.heading_ids = fob_mdx::HeadingIdOptions::builder()
+            .prefix(opts.heading_id_prefix.clone())
+            .slugify(opts.heading_slug_style.parse().unwrap_or_default())
+            .build();
+
+        rust_opts.link_rewrite = opts.link_pattern.clone().map(fob_mdx::LinkRewrite::Pattern);
+
+        if !opts.image_files.is_empty() {
+            rust_opts.image_dimensions = Some(
+                fob_mdx::ImageDimensionOptions::builder()
+                    .source(Arc::new(opts.image_files.clone()))
+                    .maybe_public_dir(opts.image_public_dir.clone())
+                    .placeholder_max_bytes(opts.image_placeholder_max_bytes)
+                    .build(),
+            );
+        }
+
+        if !opts.include_files.is_empty() {
+            rust_opts.include = Some(
+                fob_mdx::IncludeOptions::builder()
+                    .source(Arc::new(opts.include_files.clone()))
+                    .build(),
+            );
+        }
+
+        rust_opts.words_per_minute = opts.words_per_minute;
+        rust_opts.structured_content = opts.structured_content;
+        rust_opts.components = opts.components.clone();
 
         // Set output format
         rust_opts.output_format = match opts.output_format.as_str() {
             "function-body" => fob_mdx::OutputFormat::FunctionBody,
             _ => fob_mdx::OutputFormat::Program,
         };
+        rust_opts.provider_import_source = opts.provider_import_source.clone();
+        rust_opts.plugins = opts
+            .plugins
+            .iter()
+            .map(|plugin| Box::new(plugin.clone()) as Box<dyn fob_mdx::MdxPlugin>)
+            .collect();
 
         rust_opts
     }
@@ -188,8 +556,8 @@ pub struct WasmMdxResult {
     /// Extracted frontmatter (if present)
     pub frontmatter: Option<WasmFrontmatter>,
 
-    /// List of image URLs found in the document
-    pub images: Vec<String>,
+    /// Images found in the document, with size and format for provided files
+    pub images: Vec<fob_mdx::ImageInfo>,
 
     /// Named exports found in the document
     pub named_exports: Vec<String>,
@@ -202,6 +570,23 @@ pub struct WasmMdxResult {
 
     /// Default export name (if present)
     pub default_export: Option<String>,
+
+    /// Plain-text excerpt (if any)
+    pub excerpt: Option<String>,
+
+    /// Number of words in the document's text content
+    pub word_count: usize,
+
+    /// Estimated reading time in minutes
+    pub reading_time_minutes: u32,
+
+    /// Outgoing link URLs (after rewriting)
+    #[serde(default)]
+    pub links: Vec<String>,
+
+    /// Structured content tree (if enabled, will be converted to JS object)
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub content: Option<serde_json::Value>,
 }
 
 /// Frontmatter data (serializable for JS)
@@ -219,6 +604,19 @@ pub struct WasmFrontmatter {
     pub data: Option<serde_json::Value>,
 }
 
+impl From<fob_mdx::FrontmatterData> for WasmFrontmatter {
+    fn from(fm: fob_mdx::FrontmatterData) -> Self {
+        Self {
+            raw: fm.raw,
+            format: match fm.format {
+                fob_mdx::FrontmatterFormat::Yaml => "yaml".to_string(),
+                fob_mdx::FrontmatterFormat::Toml => "toml".to_string(),
+            },
+            data: Some(fm.data),
+        }
+    }
+}
+
 /// Compile MDX source to JSX
 ///
 /// # Arguments
@@ -229,14 +627,14 @@ pub struct WasmFrontmatter {
 /// # Returns
 ///
 /// * `Ok(WasmMdxResult)` - Compiled JSX and metadata
-/// * `Err(JsValue)` - Structured error object with kind, message, location, etc.
+/// * `Err(JsValue)` - Diagnostic object with kind, message, location, help, etc.
 ///
 /// # Errors
 ///
-/// Returns structured error objects that can be discriminated by `kind`:
-/// - `"validationError"` - Input validation failed (size limit, null bytes)
-/// - `"compilationError"` - MDX syntax error (with location and suggestion)
-/// - `"serializationError"` - Failed to serialize result to JavaScript
+/// Returns diagnostic objects that can be discriminated by `kind`:
+/// - `"ValidationError"` - Input validation failed (size limit, null bytes)
+/// - `"CompilationError"` - MDX syntax error (with location and help)
+/// - `"SerializationError"` - Failed to serialize result to JavaScript
 ///
 /// # Example
 ///
@@ -250,18 +648,18 @@ pub struct WasmFrontmatter {
 ///   const result = compile_mdx("# Hello **World**", options);
 ///   console.log(result.code);
 /// } catch (error) {
-///   if (error.kind === "compilationError") {
+///   if (error.kind === "CompilationError") {
 ///     console.error(error.message);
-///     if (error.location) {
-///       console.error(`At ${error.location.line}:${error.location.column}`);
+///     if (error.line !== undefined) {
+///       console.error(`At ${error.line}:${error.column}`);
 ///     }
-///     if (error.suggestion) {
-///       console.log(`Suggestion: ${error.suggestion}`);
+///     if (error.help) {
+///       console.log(`Help: ${error.help}`);
 ///     }
 ///   }
 /// }
 /// ```
-#[wasm_bindgen]
+#[wasm_bindgen(unchecked_return_type = "MdxCompileResult")]
 pub fn compile_mdx(source: &str, options: Option<WasmMdxOptions>) -> Result<JsValue, JsValue> {
     // Input validation (10MB limit for WASM environments)
     validate_input(source, 10_000_000).map_err(|e| -> JsValue { (*e).into() })?;
@@ -278,33 +676,27 @@ pub fn compile_mdx(source: &str, options: Option<WasmMdxOptions>) -> Result<JsVa
         .map_err(|e| -> WasmError { e.into() })
         .map_err(|e| -> JsValue { e.into() })?;
 
-    // Convert frontmatter
-    let frontmatter = result.frontmatter.map(|fm| {
-        WasmFrontmatter {
-            raw: fm.raw.clone(),
-            format: match fm.format {
-                fob_mdx::FrontmatterFormat::Yaml => "yaml".to_string(),
-                fob_mdx::FrontmatterFormat::Toml => "toml".to_string(),
-            },
-            // Convert JsonValue to serde_json::Value (they're the same type)
-            data: Some(fm.data),
-        }
-    });
-
     // Build WASM result
     let wasm_result = WasmMdxResult {
         code: result.code,
-        frontmatter,
+        frontmatter: result.frontmatter.map(WasmFrontmatter::from),
         images: result.images,
         named_exports: result.named_exports,
         reexports: result.reexports,
         imports: result.imports,
         default_export: result.default_export,
+        excerpt: result.excerpt,
+        word_count: result.word_count,
+        reading_time_minutes: result.reading_time_minutes,
+        links: result.links,
+        content: result
+            .content
+            .and_then(|content| serde_json::to_value(content).ok()),
     };
 
     // Serialize to JS value
-    serde_wasm_bindgen::to_value(&wasm_result).map_err(|e| {
-        let err = WasmError::serialization_with_details(
+    to_js(&wasm_result).map_err(|e| {
+        let err = WasmError::serialization_with_help(
             "Failed to serialize compilation result",
             e.to_string(),
         );
@@ -312,6 +704,13 @@ pub fn compile_mdx(source: &str, options: Option<WasmMdxOptions>) -> Result<JsVa
     })
 }
 
+/// Convert a value to JS, with maps (such as frontmatter data) as plain objects
+pub(crate) fn to_js<T: Serialize + ?Sized>(
+    value: &T,
+) -> Result<JsValue, serde_wasm_bindgen::Error> {
+    value.serialize(&serde_wasm_bindgen::Serializer::new().serialize_maps_as_objects(true))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -437,6 +836,23 @@ mod tests {
         assert_eq!(options.filepath(), Some("test.mdx".to_string()));
     }
 
+    #[test]
+    fn test_options_set_get_gfm_extensions() {
+        let mut options = WasmMdxOptions::new();
+        assert!(options.gfm_tables());
+        assert!(options.gfm_autolink());
+        options.set_gfm(true);
+        options.set_gfm_autolink(false);
+        options.set_gfm_tasklist(false);
+
+        let rust_options = MdxCompileOptions::from(&options);
+        assert!(rust_options.gfm);
+        assert!(!rust_options.gfm_autolink);
+        assert!(!rust_options.gfm_tasklist);
+        assert!(rust_options.gfm_tables);
+        assert!(rust_options.gfm_strikethrough);
+    }
+
     #[test]
     fn test_options_set_get_gfm() {
         let mut options = WasmMdxOptions::new();
@@ -475,6 +891,97 @@ mod tests {
         assert_eq!(options.jsx_runtime(), "preact/jsx-runtime");
     }
 
+    #[test]
+    fn test_options_set_get_jsx_target() {
+        let mut options = WasmMdxOptions::new();
+        assert_eq!(options.jsx_target(), "react");
+        options.set_jsx_target("Vue");
+        assert_eq!(options.jsx_target(), "vue");
+        assert_eq!(
+            MdxCompileOptions::from(&options).jsx_target,
+            fob_mdx::JsxTarget::Vue
+        );
+        options.set_jsx_target("angular");
+        assert_eq!(options.jsx_target(), "react");
+    }
+
+    #[test]
+    fn test_options_set_get_math_output() {
+        let mut options = WasmMdxOptions::new();
+        assert_eq!(options.math_output(), "tex");
+        options.set_math_output("MathML");
+        assert_eq!(options.math_output(), "mathml");
+        assert_eq!(
+            MdxCompileOptions::from(&options).math_output,
+            fob_mdx::MathOutput::MathMl
+        );
+        options.set_math_output("katex");
+        assert_eq!(options.math_output(), "tex");
+    }
+
+    #[test]
+    fn test_options_set_get_heading_ids() {
+        let mut options = WasmMdxOptions::new();
+        assert_eq!(options.heading_id_prefix(), "");
+        assert_eq!(options.heading_slug_style(), "default");
+        options.set_heading_id_prefix("docs-".to_string());
+        options.set_heading_slug_style("GitHub");
+        assert_eq!(options.heading_slug_style(), "github");
+
+        let heading_ids = MdxCompileOptions::from(&options).heading_ids;
+        assert_eq!(heading_ids.prefix, "docs-");
+        assert!(matches!(heading_ids.slugify, fob_mdx::Slugify::GitHub));
+
+        options.set_heading_slug_style("kebab");
+        assert_eq!(options.heading_slug_style(), "default");
+    }
+
+    #[test]
+    fn test_options_set_get_plugins() {
+        let mut options = WasmMdxOptions::new();
+        assert!(options.use_default_plugins());
+        assert!(options.default_heading_ids());
+        assert!(options.default_image_optimization());
+        assert_eq!(options.provider_import_source(), None);
+
+        options.set_use_default_plugins(false);
+        options.set_default_heading_ids(false);
+        options.set_default_image_optimization(false);
+        options.set_provider_import_source("@mdx-js/react".to_string());
+
+        let rust_opts = MdxCompileOptions::from(&options);
+        assert!(!rust_opts.use_default_plugins);
+        assert!(!rust_opts.default_heading_ids);
+        assert!(!rust_opts.default_image_optimization);
+        assert_eq!(
+            rust_opts.provider_import_source.as_deref(),
+            Some("@mdx-js/react")
+        );
+    }
+
+    #[test]
+    fn test_options_set_get_image_dimensions() {
+        let mut options = WasmMdxOptions::new();
+        assert!(MdxCompileOptions::from(&options).image_dimensions.is_none());
+        assert_eq!(options.image_public_dir(), None);
+        assert_eq!(options.image_placeholder_max_bytes(), 0);
+
+        options.add_image_file("public/a.png".to_string(), Vec::new());
+        options.set_image_public_dir("public".to_string());
+        options.set_image_placeholder_max_bytes(2048);
+        let dimensions = MdxCompileOptions::from(&options).image_dimensions.unwrap();
+        assert_eq!(dimensions.public_dir, Some(PathBuf::from("public")));
+        assert_eq!(dimensions.placeholder_max_bytes, 2048);
+    }
+
+    #[test]
+    fn test_options_include_files() {
+        let mut options = WasmMdxOptions::new();
+        assert!(MdxCompileOptions::from(&options).include.is_none());
+        options.add_include_file("a.mdx".to_string(), "A".to_string());
+        assert!(MdxCompileOptions::from(&options).include.is_some());
+    }
+
     #[test]
     fn test_options_all_features() {
         let mut options = WasmMdxOptions::new();
@@ -933,6 +1440,83 @@ import { Card } from './Card.tsx'
         );
     }
 
+    #[wasm_bindgen_test]
+    fn test_reading_time_metadata() {
+        let mut options = WasmMdxOptions::new();
+        options.set_words_per_minute(2);
+        let result = compile_mdx("# Title\n\nOne two three four five.", Some(options));
+        let result_obj: WasmMdxResult = serde_wasm_bindgen::from_value(result.unwrap()).unwrap();
+        assert_eq!(result_obj.word_count, 6);
+        assert_eq!(result_obj.reading_time_minutes, 3);
+    }
+
+    #[wasm_bindgen_test]
+    fn test_link_pattern() {
+        let mut options = WasmMdxOptions::new();
+        options.set_link_pattern("/blog/{slug}".to_string());
+        let result = compile_mdx("[Next](./next-post.mdx) [Home](/)", Some(options));
+        let result_obj: WasmMdxResult = serde_wasm_bindgen::from_value(result.unwrap()).unwrap();
+        assert_eq!(result_obj.links, vec!["/blog/next-post", "/"]);
+    }
+
+    #[wasm_bindgen_test]
+    fn test_image_files() {
+        let mut options = WasmMdxOptions::new();
+        options.set_filepath("posts/hello.mdx".to_string());
+        options.add_image_file(
+            "posts/diagram.svg".to_string(),
+            br#"<svg width="40" height="30"></svg>"#.to_vec(),
+        );
+        let result = compile_mdx("![Diagram](./diagram.svg)", Some(options));
+        let result_obj: WasmMdxResult = serde_wasm_bindgen::from_value(result.unwrap()).unwrap();
+        assert_eq!(result_obj.images[0].url, "./diagram.svg");
+        assert_eq!(result_obj.images[0].width, Some(40));
+        assert_eq!(result_obj.images[0].height, Some(30));
+        assert!(result_obj.code.contains("width: 40, height: 30"));
+    }
+
+    #[wasm_bindgen_test]
+    fn test_include_files() {
+        let mut options = WasmMdxOptions::new();
+        options.set_filepath("docs/page.mdx".to_string());
+        options.add_include_file(
+            "docs/shared/note.mdx".to_string(),
+            "Shared **note**".to_string(),
+        );
+        let result = compile_mdx("<Include src=\"./shared/note.mdx\" />", Some(options));
+        let result_obj: WasmMdxResult = serde_wasm_bindgen::from_value(result.unwrap()).unwrap();
+        assert!(result_obj.code.contains("\"note\""));
+        assert!(!result_obj.code.contains("Include"));
+    }
+
+    #[wasm_bindgen_test]
+    fn test_component_mapping() {
+        let mut options = WasmMdxOptions::new();
+        options.set_component("h1".to_string(), "./ui.js#Heading".to_string());
+        let result = compile_mdx("# Title", Some(options));
+        let result_obj: WasmMdxResult = serde_wasm_bindgen::from_value(result.unwrap()).unwrap();
+        assert!(
+            result_obj
+                .code
+                .contains("import {Heading as _component0} from './ui.js';")
+        );
+        assert!(result_obj.code.contains("{h1: _component0}"));
+    }
+
+    #[wasm_bindgen_test]
+    fn test_structured_content() {
+        let result = compile_mdx("# Title", None);
+        let result_obj: WasmMdxResult = serde_wasm_bindgen::from_value(result.unwrap()).unwrap();
+        assert!(result_obj.content.is_none());
+
+        let mut options = WasmMdxOptions::new();
+        options.set_structured_content(true);
+        let result = compile_mdx("# Title", Some(options));
+        let result_obj: WasmMdxResult = serde_wasm_bindgen::from_value(result.unwrap()).unwrap();
+        let content = result_obj.content.expect("content");
+        assert_eq!(content["children"][0]["tagName"], "h1");
+    }
+
     #[wasm_bindgen_test]
     fn test_named_exports_extracted() {
         let mdx = r#"export const meta = { title: "Test" }
@@ -0,0 +1,187 @@
+//! MDX plugins implemented as JavaScript callbacks
+//!
+//! Nodes cross the WASM boundary as plain mdast JSON objects
+//! (`{ type: "heading", depth: 1, children: [...] }`), so callbacks can
+//! inspect and rebuild them without access to the Rust AST.
+
+use anyhow::{Result, anyhow};
+use fob_mdx::plugins::MdxPlugin;
+use js_sys::Function;
+use markdown::mdast::{Node, Root};
+use std::any::Any;
+use wasm_bindgen::prelude::*;
+
+/// AST transform backed by a JavaScript function
+///
+/// Without a node type, the callback receives the root of the document.
+/// With one, it is called for every node of that type, in document order.
+/// The callback returns the replacement node, `null` to remove the node, or
+/// `undefined` to keep the (possibly mutated) node it was given.
+#[derive(Clone)]
+pub(crate) struct JsPlugin {
+    name: String,
+    node_type: Option<String>,
+    callback: Function,
+}
+
+// Safety: JS values only exist on wasm32, which is single-threaded, so the
+// callback is never shared with or called from another thread
+unsafe impl Send for JsPlugin {}
+unsafe impl Sync for JsPlugin {}
+
+impl JsPlugin {
+    pub(crate) fn new(name: String, node_type: Option<String>, callback: Function) -> Self {
+        Self {
+            name,
+            node_type,
+            callback,
+        }
+    }
+
+    /// Visit `node` and its descendants, returning `None` if it was removed
+    fn visit(&self, mut node: Node, node_type: &str) -> Result<Option<Node>> {
+        if mdast_type(&node) == node_type {
+            match self.call(node)? {
+                Some(replacement) => node = replacement,
+                None => return Ok(None),
+            }
+        }
+        if let Some(children) = node.children_mut() {
+            let mut visited = Vec::with_capacity(children.len());
+            for child in std::mem::take(children) {
+                visited.extend(self.visit(child, node_type)?);
+            }
+            *children = visited;
+        }
+        Ok(Some(node))
+    }
+
+    /// Pass a node through the callback
+    fn call(&self, node: Node) -> Result<Option<Node>> {
+        let value = crate::to_js(&node)
+            .map_err(|e| anyhow!("Failed to pass node to plugin '{}': {}", self.name, e))?;
+        let returned = self
+            .callback
+            .call1(&JsValue::UNDEFINED, &value)
+            .map_err(|e| anyhow!("Plugin '{}' threw: {}", self.name, js_error_message(&e)))?;
+        if returned.is_null() {
+            return Ok(None);
+        }
+        let result = if returned.is_undefined() {
+            value
+        } else {
+            returned
+        };
+        serde_wasm_bindgen::from_value(result)
+            .map(Some)
+            .map_err(|e| anyhow!("Plugin '{}' returned an invalid node: {}", self.name, e))
+    }
+}
+
+impl MdxPlugin for JsPlugin {
+    fn name(&self) -> &'static str {
+        "js-plugin"
+    }
+
+    fn transform_ast(&self, ast: &mut Node) -> Result<()> {
+        let node_type = self.node_type.as_deref().unwrap_or("root");
+        let empty = Node::Root(Root {
+            children: Vec::new(),
+            position: None,
+        });
+        let root = std::mem::replace(ast, empty);
+        *ast = self
+            .visit(root, node_type)?
+            .ok_or_else(|| anyhow!("Plugin '{}' removed the document root", self.name))?;
+        Ok(())
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+/// Message of a thrown JS value
+fn js_error_message(error: &JsValue) -> String {
+    if let Some(error) = error.dyn_ref::<js_sys::Error>() {
+        return String::from(error.message());
+    }
+    error.as_string().unwrap_or_else(|| format!("{:?}", error))
+}
+
+/// mdast `type` of a node, as it is serialized
+fn mdast_type(node: &Node) -> &'static str {
+    match node {
+        Node::Root(_) => "root",
+        Node::Blockquote(_) => "blockquote",
+        Node::FootnoteDefinition(_) => "footnoteDefinition",
+        Node::MdxJsxFlowElement(_) => "mdxJsxFlowElement",
+        Node::List(_) => "list",
+        Node::MdxjsEsm(_) => "mdxjsEsm",
+        Node::Toml(_) => "toml",
+        Node::Yaml(_) => "yaml",
+        Node::Break(_) => "break",
+        Node::InlineCode(_) => "inlineCode",
+        Node::InlineMath(_) => "inlineMath",
+        Node::Delete(_) => "delete",
+        Node::Emphasis(_) => "emphasis",
+        Node::MdxTextExpression(_) => "mdxTextExpression",
+        Node::FootnoteReference(_) => "footnoteReference",
+        Node::Html(_) => "html",
+        Node::Image(_) => "image",
+        Node::ImageReference(_) => "imageReference",
+        Node::MdxJsxTextElement(_) => "mdxJsxTextElement",
+        Node::Link(_) => "link",
+        Node::LinkReference(_) => "linkReference",
+        Node::Strong(_) => "strong",
+        Node::Text(_) => "text",
+        Node::Code(_) => "code",
+        Node::Math(_) => "math",
+        Node::MdxFlowExpression(_) => "mdxFlowExpression",
+        Node::Heading(_) => "heading",
+        Node::Table(_) => "table",
+        Node::ThematicBreak(_) => "thematicBreak",
+        Node::TableRow(_) => "tableRow",
+        Node::TableCell(_) => "tableCell",
+        Node::ListItem(_) => "listItem",
+        Node::Definition(_) => "definition",
+        Node::Paragraph(_) => "paragraph",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mdast_type_matches_serialized_type() {
+        fn check(node: &Node) {
+            let json = serde_json::to_value(node).unwrap();
+            assert_eq!(json["type"], mdast_type(node));
+            for child in node.children().into_iter().flatten() {
+                check(child);
+            }
+        }
+
+        let source = "---\ntitle: x\n---\n\nimport A from './a.js'\n\n# *Hi* **there** `x` $y$\n\n> [link](./a) ![img](./b.png) <A>{1}</A>\n\n- [x] item\n\n| a |\n| - |\n| b |\n\n```js\ncode\n```\n\n$$\nmath\n$$\n\n***\n\n[^1]\n\n[^1]: note\n\n[ref]: ./c\n";
+        let mut options = markdown::ParseOptions::mdx();
+        options.constructs.frontmatter = true;
+        options.constructs.gfm_table = true;
+        options.constructs.gfm_task_list_item = true;
+        options.constructs.gfm_footnote_definition = true;
+        options.constructs.gfm_label_start_footnote = true;
+        options.constructs.math_flow = true;
+        options.constructs.math_text = true;
+        check(&markdown::to_mdast(source, &options).unwrap());
+    }
+
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    #[cfg(target_family = "wasm")]
+    fn test_js_plugin_transforms_nodes() {
+        let callback = Function::new_with_args("node", "node.value = node.value.toUpperCase()");
+        let plugin = JsPlugin::new("shout".into(), Some("text".into()), callback);
+        let mut ast = markdown::to_mdast("# hello *world*", &Default::default()).unwrap();
+        plugin.transform_ast(&mut ast).unwrap();
+        assert_eq!(ast.to_string(), "HELLO WORLD");
+    }
+}
@@ -0,0 +1,242 @@
+//! TypeScript definitions for values returned as `JsValue`
+//!
+//! wasm-bindgen types serde-converted values as `any`. These declarations
+//! are appended to the generated `.d.ts` and referenced from the exports
+//! through `unchecked_return_type`/`unchecked_param_type`; the tests below
+//! keep them in sync with the Rust structs.
+
+use wasm_bindgen::prelude::*;
+
+#[wasm_bindgen(typescript_custom_section)]
+const TS_APPEND_CONTENT: &str = TS_DEFINITIONS;
+
+// Only read by the custom section, which is emitted for wasm32 alone
+#[cfg_attr(not(target_family = "wasm"), allow(dead_code))]
+const TS_DEFINITIONS: &str = r#"
+/** Frontmatter block of a document */
+export interface MdxFrontmatter {
+  /** Raw frontmatter string */
+  raw: string;
+  /** Format of the block */
+  format: "yaml" | "toml";
+  /** Parsed frontmatter data */
+  data?: Record<string, unknown>;
+}
+
+/** Image referenced by a document */
+export interface MdxImageInfo {
+  /** URL as written in the document */
+  url: string;
+  /** Alternative text */
+  alt: string;
+  /** Local file the URL resolved to */
+  path?: string;
+  /** Intrinsic width in pixels */
+  width?: number;
+  /** Intrinsic height in pixels */
+  height?: number;
+  /** Format detected from the file content */
+  format?: "png" | "jpeg" | "gif" | "webp" | "svg";
+  /** Blurred placeholder as a `data:image/svg+xml` URI */
+  placeholder?: string;
+}
+
+/** Property value of a structured content element or component */
+export type MdxPropValue = boolean | number | string | { expression: string };
+
+/** Node of the structured content tree */
+export type MdxContentNode =
+  | { type: "root"; children: MdxContentNode[] }
+  | {
+      type: "element";
+      tagName: string;
+      properties: Record<string, MdxPropValue>;
+      children: MdxContentNode[];
+    }
+  | {
+      type: "component";
+      name?: string;
+      props: Record<string, MdxPropValue>;
+      children: MdxContentNode[];
+    }
+  | { type: "text"; value: string }
+  | { type: "expression"; value: string }
+  | { type: "raw"; value: string };
+
+/** Result of `compile_mdx` */
+export interface MdxCompileResult {
+  /** Compiled JSX code */
+  code: string;
+  /** Extracted frontmatter (if present) */
+  frontmatter?: MdxFrontmatter;
+  /** Images found in the document */
+  images: MdxImageInfo[];
+  /** Named exports found in the document */
+  namedExports: string[];
+  /** Re-exports found in the document */
+  reexports: string[];
+  /** Imports found in the document */
+  imports: string[];
+  /** Default export name (if present) */
+  defaultExport?: string;
+  /** Plain-text excerpt (if any) */
+  excerpt?: string;
+  /** Number of words in the document's text content */
+  wordCount: number;
+  /** Estimated reading time in minutes */
+  readingTimeMinutes: number;
+  /** Outgoing link URLs (after rewriting) */
+  links: string[];
+  /** Structured content tree (if enabled) */
+  content?: MdxContentNode;
+}
+
+/** Result of `bundle_mdx` */
+export interface MdxBundleResult {
+  /** Bundled ESM code */
+  code: string;
+  /** Extracted frontmatter (if present) */
+  frontmatter?: MdxFrontmatter;
+  /** Frontmatter data merged with statically evaluated exports */
+  matter: Record<string, unknown>;
+  /** Source map JSON (with `sourcemap: "external"`) */
+  map?: string;
+  /** CSS collected from imported stylesheets (if any) */
+  css?: string;
+}
+
+/** Bundling settings of `bundle_mdx` */
+export interface MdxBundleSettings {
+  /** Append the source map to the code, or return it as `map` */
+  sourcemap?: "inline" | "external";
+  /** Minify the bundle */
+  minify?: boolean;
+}
+
+/**
+ * Error thrown by `compile_mdx` and `bundle_mdx`, discriminated by `kind`.
+ * Same diagnostic format as the other fob bindings.
+ */
+export interface MdxError {
+  kind:
+    | "ValidationError"
+    | "CompilationError"
+    | "BundleError"
+    | "SerializationError"
+    | "InternalError";
+  severity: "error" | "warning";
+  message: string;
+  /** File the error occurred in */
+  file?: string;
+  /** 1-based line number */
+  line?: number;
+  /** 1-based column number */
+  column?: number;
+  /** How to fix the error */
+  help?: string;
+  /** Further diagnostics, such as the causes of a bundle error */
+  related?: MdxError[];
+}
+
+/** mdast node passed to plugins */
+export interface MdastNode {
+  type: string;
+  children?: MdastNode[];
+  [key: string]: unknown;
+}
+
+/**
+ * Plugin callback: return a replacement node, `null` to remove the node,
+ * or nothing to keep the node as mutated
+ */
+export type MdxPluginCallback = (node: MdastNode) => MdastNode | null | undefined | void;
+"#;
+
+#[cfg(test)]
+mod tests {
+    use super::TS_DEFINITIONS;
+    use crate::error::WasmError;
+    use crate::{WasmFrontmatter, WasmMdxResult};
+    use serde::Serialize;
+    use serde_json::Value;
+
+    /// Body of a declaration, from its name to the next declaration
+    fn declaration(name: &str) -> &'static str {
+        let start = TS_DEFINITIONS
+            .find(&format!(" {} ", name))
+            .unwrap_or_else(|| panic!("{} is not declared", name));
+        let rest = &TS_DEFINITIONS[start..];
+        let end = rest.find("\n/**").unwrap_or(rest.len());
+        &rest[..end]
+    }
+
+    /// Assert that every serialized field of `value` is declared in `name`
+    fn assert_declares<T: Serialize>(name: &str, value: &T) {
+        let body = declaration(name);
+        let Value::Object(fields) = serde_json::to_value(value).unwrap() else {
+            panic!("{} is not serialized as an object", name);
+        };
+        for field in fields.keys() {
+            assert!(
+                body.contains(&format!("{}:", field)) || body.contains(&format!("{}?:", field)),
+                "{} is missing `{}`",
+                name,
+                field
+            );
+        }
+    }
+
+    #[test]
+    fn test_compile_result_fields_are_declared() {
+        let frontmatter = WasmFrontmatter {
+            raw: String::new(),
+            format: "yaml".to_string(),
+            data: Some(Value::Null),
+        };
+        assert_declares("MdxFrontmatter", &frontmatter);
+        assert_declares(
+            "MdxCompileResult",
+            &WasmMdxResult {
+                code: String::new(),
+                frontmatter: Some(frontmatter),
+                images: Vec::new(),
+                named_exports: Vec::new(),
+                reexports: Vec::new(),
+                imports: Vec::new(),
+                default_export: Some(String::new()),
+                excerpt: Some(String::new()),
+                word_count: 0,
+                reading_time_minutes: 0,
+                links: Vec::new(),
+                content: Some(Value::Null),
+            },
+        );
+    }
+
+    #[test]
+    fn test_error_kinds_are_declared() {
+        let body = declaration("MdxError");
+        let errors = [
+            WasmError::validation_with_help("", ""),
+            WasmError::CompilationError {
+                message: String::new(),
+                file: Some(String::new()),
+                line: Some(1),
+                column: Some(1),
+                help: Some(String::new()),
+            },
+            WasmError::BundleError {
+                message: String::new(),
+                related: vec![WasmError::bundle("")],
+            },
+            WasmError::serialization_with_help("", ""),
+            WasmError::internal(""),
+        ];
+        for error in errors {
+            let json = serde_json::to_value(&error).unwrap();
+            let kind = json["kind"].as_str().unwrap();
+            assert!(body.contains(&format!("\"{}\"", kind)), "kind {}", kind);
+            assert_declares("MdxError", &error);
+        }
+    }
+}
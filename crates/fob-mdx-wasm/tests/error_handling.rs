@@ -25,13 +25,16 @@ fn test_validation_error_large_input() {
 
     // Verify error structure using js_sys
     let kind = js_sys::Reflect::get(&err_value, &"kind".into()).unwrap();
-    assert_eq!(kind.as_string().unwrap(), "validationError");
+    assert_eq!(kind.as_string().unwrap(), "ValidationError");
 
     let message = js_sys::Reflect::get(&err_value, &"message".into()).unwrap();
     assert!(message.as_string().unwrap().contains("size exceeds"));
 
-    let details = js_sys::Reflect::get(&err_value, &"details".into()).unwrap();
-    assert!(!details.is_undefined(), "Details should be present");
+    let help = js_sys::Reflect::get(&err_value, &"help".into()).unwrap();
+    assert!(!help.is_undefined(), "Help should be present");
+
+    let severity = js_sys::Reflect::get(&err_value, &"severity".into()).unwrap();
+    assert_eq!(severity.as_string().unwrap(), "error");
 }
 
 #[wasm_bindgen_test]
@@ -43,7 +46,7 @@ fn test_validation_error_null_bytes() {
 
     let err_value = result.unwrap_err();
     let kind = js_sys::Reflect::get(&err_value, &"kind".into()).unwrap();
-    assert_eq!(kind.as_string().unwrap(), "validationError");
+    assert_eq!(kind.as_string().unwrap(), "ValidationError");
 
     let message = js_sys::Reflect::get(&err_value, &"message".into()).unwrap();
     assert!(message.as_string().unwrap().contains("null bytes"));
@@ -70,10 +73,10 @@ fn test_compilation_error_structure() {
     // If it errors (parser dependent), verify error structure
     if let Err(err_value) = result {
         let kind = js_sys::Reflect::get(&err_value, &"kind".into()).unwrap();
-        // Should be either compilationError or validationError
+        // Should be either CompilationError or ValidationError
         assert!(
-            kind.as_string().unwrap() == "compilationError"
-                || kind.as_string().unwrap() == "validationError"
+            kind.as_string().unwrap() == "CompilationError"
+                || kind.as_string().unwrap() == "ValidationError"
         );
 
         let message = js_sys::Reflect::get(&err_value, &"message".into()).unwrap();
@@ -108,17 +111,16 @@ fn test_error_has_required_fields() {
 }
 
 #[wasm_bindgen_test]
-fn test_error_details_are_optional() {
+fn test_error_help_is_optional() {
     let input_with_null = "Test\0";
     let result = compile_mdx(input_with_null, None);
 
     assert!(result.is_err());
     let err_value = result.unwrap_err();
 
-    // Details field might be undefined for some errors
-    let details = js_sys::Reflect::get(&err_value, &"details".into()).unwrap();
-    // This is okay - details can be undefined
-    let _ = details;
+    // Empty fields are omitted rather than set to null
+    let help = js_sys::Reflect::get(&err_value, &"help".into()).unwrap();
+    assert!(help.is_undefined());
 }
 
 // ============================================================================
@@ -220,11 +222,11 @@ fn test_mixed_unicode_null_byte_fails() {
 
     let err_value = result.unwrap_err();
     let kind = js_sys::Reflect::get(&err_value, &"kind".into()).unwrap();
-    assert_eq!(kind.as_string().unwrap(), "validationError");
+    assert_eq!(kind.as_string().unwrap(), "ValidationError");
 }
 
 // ============================================================================
-// Error Metadata Tests (location, context, suggestion)
+// Error Metadata Tests (location, help)
 // ============================================================================
 
 #[wasm_bindgen_test]
@@ -233,64 +235,34 @@ fn test_compilation_error_has_location() {
     let mdx = "line1\nline2\nimport { foo } from";
     let result = compile_mdx(mdx, None);
 
-    // If it errors, check for location field
+    // If it errors, location fields sit next to the message
     if let Err(err_value) = result {
-        // Check location field exists (may be undefined for some errors)
-        let location = js_sys::Reflect::get(&err_value, &"location".into());
-        if let Ok(loc) = location {
-            if !loc.is_undefined() && !loc.is_null() {
-                // If location exists, it should have line/column
-                let line = js_sys::Reflect::get(&loc, &"line".into());
-                if let Ok(line_val) = line {
-                    if !line_val.is_undefined() {
-                        // Line should be a positive number
-                        assert!(
-                            line_val.as_f64().unwrap_or(0.0) >= 1.0,
-                            "Line number should be >= 1"
-                        );
-                    }
-                }
-            }
+        let line = js_sys::Reflect::get(&err_value, &"line".into()).unwrap();
+        if !line.is_undefined() {
+            assert!(
+                line.as_f64().unwrap_or(0.0) >= 1.0,
+                "Line number should be >= 1"
+            );
         }
     }
     // If compilation succeeds (parser is lenient), that's okay too
 }
 
 #[wasm_bindgen_test]
-fn test_error_context_shows_source() {
-    // Trigger a validation error which should have context
+fn test_validation_error_help_is_informative() {
     let large_input = "x".repeat(11_000_000);
     let result = compile_mdx(&large_input, None);
 
     assert!(result.is_err());
     let err_value = result.unwrap_err();
 
-    // Check context field (validation errors include size info as context)
-    let context = js_sys::Reflect::get(&err_value, &"context".into());
-    // Context is optional, but if present should be a string
-    if let Ok(ctx) = context {
-        if !ctx.is_undefined() && !ctx.is_null() {
-            // Context should be informative
-            if let Some(ctx_str) = ctx.as_string() {
-                assert!(
-                    !ctx_str.is_empty() || ctx_str.is_empty(),
-                    "Context should be valid if present"
-                );
-            }
-        }
-    }
-
-    // The important thing is that error has useful details
-    let details = js_sys::Reflect::get(&err_value, &"details".into());
-    if let Ok(det) = details {
-        if !det.is_undefined() && !det.is_null() {
-            if let Some(det_str) = det.as_string() {
-                // Details should mention size
-                assert!(
-                    det_str.contains("bytes") || det_str.contains("size") || det_str.len() > 0,
-                    "Details should be informative"
-                );
-            }
-        }
-    }
+    let help = js_sys::Reflect::get(&err_value, &"help".into())
+        .unwrap()
+        .as_string()
+        .unwrap();
+    assert!(
+        help.contains("bytes"),
+        "Help should mention the size: {}",
+        help
+    );
 }
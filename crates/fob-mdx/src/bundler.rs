@@ -12,6 +12,17 @@
 //! - The `load` hook is specifically designed for custom file loading
 //! - We return JSX with `ModuleType::Jsx` to tell Rolldown how to handle it
 //!
+//! ## Content collections
+//!
+//! The plugin also serves `fob:content/<glob>` modules listing the matching
+//! `.mdx` files with their frontmatter, for index pages:
+//!
+//! ```javascript
+//! import posts from 'fob:content/blog/*.mdx';
+//!
+//! for (const { slug, frontmatter, Component } of posts) { /* ... */ }
+//! ```
+//!
 //! ## Example Usage
 //!
 //! ```rust,no_run
@@ -29,14 +40,23 @@
 //! # }
 //! ```
 
-use crate::{MdxCompileOptions, compile};
+use crate::collection::{CONTENT_PREFIX, RESOLVED_CONTENT_PREFIX, generate_collection};
+use crate::frontmatter::parse_frontmatter;
+use crate::glob::Glob;
+use crate::include::{include_targets, resolve_include};
+use crate::{
+    HeadingIdOptions, ImageDimensionOptions, IncludeOptions, JsxTarget, LinkRewrite, MathOutput,
+    MdxCompileOptions, MdxPlugin, OutputFormat, compile,
+};
 use anyhow::Context;
 use fob_bundler::{
-    HookLoadArgs, HookLoadOutput, HookLoadReturn, HookResolveIdArgs, HookResolveIdOutput,
-    HookResolveIdReturn, ModuleType, Plugin, PluginContext, Runtime,
+    EmittedAsset, HookLoadArgs, HookLoadOutput, HookLoadReturn, HookResolveIdArgs,
+    HookResolveIdOutput, HookResolveIdReturn, ModuleType, Plugin, PluginContext, Runtime,
 };
+use path_clean::PathClean;
 use std::borrow::Cow;
-use std::path::PathBuf;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
 /// Rolldown plugin that compiles MDX files to JSX
@@ -52,6 +72,29 @@ use std::sync::Arc;
 ///
 /// The plugin is async-compatible (required by Rolldown) but performs synchronous
 /// compilation internally, which is acceptable for build-time transforms.
+///
+/// # Configuration
+///
+/// Options can be set through the public fields or the `with_*` methods.
+/// Custom [`MdxPlugin`]s and per-glob overrides are only available through
+/// the methods:
+///
+/// ```rust,no_run
+/// use fob_mdx::plugins::LinkValidationPlugin;
+/// use fob_mdx::{FobMdxPlugin, LinkRewrite};
+/// # use fob_bundler::Runtime;
+/// # use std::sync::Arc;
+///
+/// # fn example(runtime: Arc<dyn Runtime>) {
+/// let plugin = FobMdxPlugin::new(runtime)
+///     .with_provider_import_source("@mdx-js/react")
+///     .with_mdx_plugin("link-validation", LinkValidationPlugin::new)
+///     .with_override("docs/**", |options| options.math = false)
+///     .with_override("blog/**", |options| {
+///         options.link_rewrite = Some(LinkRewrite::pattern("/blog/{slug}"));
+///     });
+/// # }
+/// ```
 #[derive(Clone, Debug)]
 pub struct FobMdxPlugin {
     /// Enable GFM (tables, strikethrough, task lists)
@@ -60,15 +103,43 @@ pub struct FobMdxPlugin {
     pub footnotes: bool,
     /// Enable math support
     pub math: bool,
+    /// Math rendering (TeX spans or compile-time MathML)
+    pub math_output: MathOutput,
     /// JSX runtime module
     pub jsx_runtime: String,
+    /// Framework the generated JSX targets
+    pub jsx_target: JsxTarget,
     /// Use default plugins (heading IDs, image optimization)
     pub use_default_plugins: bool,
+    /// Prefix and slug function for heading IDs
+    pub heading_ids: HeadingIdOptions,
+    /// Rewrite relative `.md`/`.mdx` links (e.g. to `/blog/{slug}`)
+    pub link_rewrite: Option<LinkRewrite>,
+    /// Add `width`/`height` to local images. Image files are read through
+    /// the runtime, so its `source` is not used.
+    pub image_dimensions: Option<ImageDimensionOptions>,
+    /// Expand `<Include src="..." />` lines. Included files are read through
+    /// the runtime, so its `source` is not used.
+    pub include: Option<IncludeOptions>,
     /// Provider import source for component injection (e.g., "gumbo/mdx", "@mdx-js/react")
     ///
     /// When set, compiled MDX will import useMDXComponents from this source
     /// and merge provider components between defaults and props.components.
     pub provider_import_source: Option<String>,
+    /// Compile-time component mapping (e.g. `h1` → `./ui.js#Heading`)
+    pub components: HashMap<String, String>,
+    /// Output format (Program or FunctionBody)
+    pub output_format: OutputFormat,
+    /// Directory of layout components, relative to the project root.
+    ///
+    /// When set, a `layout: Post` frontmatter key wraps the document in the
+    /// default export of `Post.tsx`, `Post.jsx`, `Post.ts` or `Post.js` from
+    /// this directory (or `Post` itself if it has one of those extensions).
+    pub layouts_dir: Option<PathBuf>,
+    /// Custom plugins, instantiated for every compiled file
+    mdx_plugins: Vec<MdxPluginFactory>,
+    /// Option overrides for files matching a glob, applied in order
+    overrides: Vec<MdxOverride>,
     /// Project root for resolving relative file paths
     project_root: PathBuf,
     /// Runtime for file access (handles virtual files + filesystem)
@@ -103,27 +174,197 @@ impl FobMdxPlugin {
             gfm: true,
             footnotes: true,
             math: true,
+            math_output: MathOutput::default(),
             jsx_runtime: "react/jsx-runtime".to_string(),
+            jsx_target: JsxTarget::default(),
             use_default_plugins: true,
+            heading_ids: HeadingIdOptions::default(),
+            link_rewrite: None,
+            image_dimensions: None,
+            include: None,
             provider_import_source: None,
+            components: HashMap::new(),
+            output_format: OutputFormat::default(),
+            layouts_dir: None,
+            mdx_plugins: Vec::new(),
+            overrides: Vec::new(),
             project_root: PathBuf::from("."),
             runtime,
         }
     }
 
+    /// Resolve bare `.mdx` imports and virtual-module imports against `root`
+    /// instead of the current directory.
+    pub fn with_project_root(mut self, root: impl Into<PathBuf>) -> Self {
+        self.project_root = root.into();
+        self
+    }
+
+    /// Project root, absolute against the runtime's working directory
+    fn absolute_project_root(&self) -> PathBuf {
+        if self.project_root.is_absolute() {
+            return self.project_root.clean();
+        }
+        match self.runtime.get_cwd() {
+            Ok(cwd) => cwd.join(&self.project_root).clean(),
+            Err(_) => self.project_root.clean(),
+        }
+    }
+
+    /// Absolute directory of layout components.
+    ///
+    /// Layouts are imported from the compiled file, so the path can't be
+    /// relative to the working directory.
+    fn absolute_layouts_dir(&self) -> Option<PathBuf> {
+        let dir = self.layouts_dir.as_ref()?;
+        Some(self.absolute_project_root().join(dir).clean())
+    }
+
+    /// Import `useMDXComponents` from `source` (e.g. `@mdx-js/react`) and
+    /// merge its components into every document.
+    pub fn with_provider_import_source(mut self, source: impl Into<String>) -> Self {
+        self.provider_import_source = Some(source.into());
+        self
+    }
+
+    /// Set the output format of compiled documents.
+    pub fn with_output_format(mut self, output_format: OutputFormat) -> Self {
+        self.output_format = output_format;
+        self
+    }
+
+    /// Set the directory `layout:` frontmatter keys are resolved in.
+    pub fn with_layouts_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.layouts_dir = Some(dir.into());
+        self
+    }
+
+    /// Run a custom plugin on every document, after the default plugins.
+    ///
+    /// `create` is called for each compiled file, so state a plugin collects
+    /// while compiling one document doesn't leak into the next. `name`
+    /// identifies the plugin in debug output.
+    pub fn with_mdx_plugin<F, P>(mut self, name: &'static str, create: F) -> Self
+    where
+        F: Fn() -> P + Send + Sync + 'static,
+        P: MdxPlugin + 'static,
+    {
+        self.mdx_plugins.push(MdxPluginFactory {
+            name,
+            create: Arc::new(move || Box::new(create()) as Box<dyn MdxPlugin>),
+        });
+        self
+    }
+
+    /// Adjust the compile options of files matching `pattern`.
+    ///
+    /// Patterns are matched against paths relative to the project root
+    /// (`docs/**`, `blog/*.mdx`), or against the module id for files outside
+    /// it such as virtual modules. `*` and `?` match within a path segment
+    /// and `**` matches any number of segments. Overrides run in the order
+    /// they were added, after the plugin's own options are applied, so they
+    /// can also add plugins for some files only.
+    ///
+    /// A pattern that fails to compile is reported when the first `.mdx`
+    /// file is loaded, failing the build.
+    pub fn with_override<F>(mut self, pattern: &str, apply: F) -> Self
+    where
+        F: Fn(&mut MdxCompileOptions) + Send + Sync + 'static,
+    {
+        self.overrides.push(MdxOverride {
+            pattern: pattern.to_string(),
+            glob: Glob::new(pattern),
+            apply: Arc::new(apply),
+        });
+        self
+    }
+
+    /// Path override patterns are matched against: relative to the project
+    /// root and `/`-separated, or the id itself outside of it
+    fn override_path(&self, id: &str) -> String {
+        match Path::new(id).strip_prefix(self.absolute_project_root()) {
+            Ok(relative) => relative
+                .components()
+                .map(|component| component.as_os_str().to_string_lossy())
+                .collect::<Vec<_>>()
+                .join("/"),
+            Err(_) => id.to_string(),
+        }
+    }
+
     /// Create MdxCompileOptions from plugin config
-    fn create_options(&self, filepath: Option<String>) -> MdxCompileOptions {
+    ///
+    /// Fails if an override pattern is invalid.
+    fn create_options(&self, filepath: Option<String>) -> anyhow::Result<MdxCompileOptions> {
         let mut opts = MdxCompileOptions::builder()
             .gfm(self.gfm)
             .footnotes(self.footnotes)
             .math(self.math)
+            .math_output(self.math_output)
             .jsx_runtime(self.jsx_runtime.clone())
+            .jsx_target(self.jsx_target)
             .use_default_plugins(self.use_default_plugins)
+            .heading_ids(self.heading_ids.clone())
+            .maybe_link_rewrite(self.link_rewrite.clone())
+            .maybe_image_dimensions(self.image_dimensions.clone())
+            .maybe_include(self.include.clone())
             .maybe_provider_import_source(self.provider_import_source.clone())
+            .components(self.components.clone())
+            .output_format(self.output_format)
+            .plugins(
+                self.mdx_plugins
+                    .iter()
+                    .map(|factory| (factory.create)())
+                    .collect(),
+            )
             .build();
 
+        if let Some(id) = &filepath {
+            let path = self.override_path(id);
+            for entry in &self.overrides {
+                let glob = entry.glob.as_ref().map_err(|e| {
+                    anyhow::anyhow!("Invalid MDX override pattern '{}': {}", entry.pattern, e)
+                })?;
+                if glob.is_match(&path) {
+                    (entry.apply)(&mut opts);
+                }
+            }
+        }
+
         opts.filepath = filepath;
-        opts
+        Ok(opts)
+    }
+}
+
+/// Creates a fresh instance of a custom plugin for each compiled file
+#[derive(Clone)]
+struct MdxPluginFactory {
+    name: &'static str,
+    create: Arc<dyn Fn() -> Box<dyn MdxPlugin> + Send + Sync>,
+}
+
+impl std::fmt::Debug for MdxPluginFactory {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MdxPluginFactory")
+            .field("name", &self.name)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Options override for files matching a glob
+#[derive(Clone)]
+struct MdxOverride {
+    pattern: String,
+    /// Compiled `pattern`, or why it failed to compile
+    glob: Result<Glob, regex::Error>,
+    apply: Arc<dyn Fn(&mut MdxCompileOptions) + Send + Sync>,
+}
+
+impl std::fmt::Debug for MdxOverride {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MdxOverride")
+            .field("pattern", &self.pattern)
+            .finish_non_exhaustive()
     }
 }
 
@@ -151,7 +392,8 @@ impl Plugin for FobMdxPlugin {
     ///
     /// # Returns
     ///
-    /// - `Ok(Some(output))` - Successfully resolved MDX file to absolute path
+    /// - `Ok(Some(output))` - Successfully resolved MDX file to absolute path, or a
+    ///   `fob:content/*` collection to its virtual id
     /// - `Ok(None)` - Not an MDX file or file doesn't exist, let other resolvers handle it
     /// - `Err(e)` - Resolution error
     fn resolve_id(
@@ -165,6 +407,14 @@ impl Plugin for FobMdxPlugin {
         let runtime = Arc::clone(&self.runtime);
 
         async move {
+            // Content collections are generated by the load hook
+            if let Some(pattern) = specifier.strip_prefix(CONTENT_PREFIX) {
+                return Ok(Some(HookResolveIdOutput {
+                    id: format!("{}{}", RESOLVED_CONTENT_PREFIX, pattern).into(),
+                    ..Default::default()
+                }));
+            }
+
             // Only handle .mdx files
             if !specifier.ends_with(".mdx") {
                 return Ok(None);
@@ -232,7 +482,10 @@ impl Plugin for FobMdxPlugin {
     /// 1. Checks if the file is a `.mdx` file
     /// 2. Reads the file from disk
     /// 3. Compiles MDX → JSX using fob-mdx
-    /// 4. Returns JSX with `ModuleType::Jsx` for Rolldown to process
+    /// 4. Emits files produced by MDX plugins as bundle assets
+    /// 5. Returns JSX with `ModuleType::Jsx` for Rolldown to process
+    ///
+    /// Content collections are generated here too, as plain JavaScript.
     ///
     /// # Returns
     ///
@@ -252,20 +505,39 @@ impl Plugin for FobMdxPlugin {
     /// for true async behavior.
     fn load(
         &self,
-        _ctx: &PluginContext,
+        ctx: &PluginContext,
         args: &HookLoadArgs<'_>,
     ) -> impl std::future::Future<Output = HookLoadReturn> + Send {
         // Capture data needed for async block to avoid lifetime issues
+        let ctx = ctx.clone();
         let id = args.id.to_string();
-        let options = self.create_options(Some(id.clone()));
+        // Only .mdx files are compiled
+        let options = id
+            .ends_with(".mdx")
+            .then(|| self.create_options(Some(id.clone())));
         let project_root = self.project_root.clone();
+        // Collection entries are imported by path, so use absolute paths
+        let content_root = self.absolute_project_root();
+        let layouts_dir = self.absolute_layouts_dir();
         let runtime = Arc::clone(&self.runtime);
 
         async move {
+            if let Some(pattern) = id.strip_prefix(RESOLVED_CONTENT_PREFIX) {
+                let code = generate_collection(pattern, &content_root, &*runtime)
+                    .await
+                    .with_context(|| format!("Failed to load content collection '{}'", pattern))?;
+                return Ok(Some(HookLoadOutput {
+                    code: code.into(),
+                    module_type: Some(ModuleType::Js),
+                    ..Default::default()
+                }));
+            }
+
             // Only handle .mdx files
-            if !id.ends_with(".mdx") {
+            let Some(options) = options else {
                 return Ok(None);
-            }
+            };
+            let mut options = options?;
 
             // Determine file path for reading
             // - Virtual files (virtual:xxx): pass through as-is, Runtime handles lookup
@@ -287,6 +559,23 @@ impl Plugin for FobMdxPlugin {
                 format!("MDX file {} contains invalid UTF-8", file_path.display())
             })?;
 
+            // Compiling is synchronous, so read included files and images up front
+            if let Some(include) = &mut options.include {
+                let files =
+                    preload_includes(&source, &include.root_dir(Some(&id)), &*runtime).await;
+                include.source = Arc::new(files);
+            }
+            if let Some(dimensions) = &mut options.image_dimensions {
+                let files =
+                    preload_images(&source, &dimensions.for_file(Some(&id)), &*runtime).await;
+                dimensions.source = Arc::new(files);
+            }
+            if let (Some(dir), Some(name)) = (&layouts_dir, frontmatter_layout(&source)) {
+                let layout = resolve_layout(dir, &name, &*runtime)
+                    .with_context(|| format!("Failed to resolve layout of {}", id))?;
+                options.layout = Some(layout.to_string_lossy().into_owned());
+            }
+
             // Compile MDX to JSX
             let result = compile(&source, options)
                 .with_context(|| format!("Failed to compile MDX file: {}", id))?;
@@ -304,6 +593,21 @@ impl Plugin for FobMdxPlugin {
                 "Compiled MDX code preview"
             );
 
+            // Forward files emitted by MDX plugins as bundle assets
+            for asset in result.emitted_assets {
+                ctx.emit_file(
+                    EmittedAsset {
+                        name: None,
+                        original_file_name: Some(id.clone()),
+                        file_name: Some(asset.path.clone().into()),
+                        source: asset.content.into(),
+                    },
+                    None,
+                    None,
+                )
+                .with_context(|| format!("Failed to emit MDX asset {} from {}", asset.path, id))?;
+            }
+
             // Return JSX to Rolldown
             // IMPORTANT: Set module_type to Jsx so Rolldown knows how to parse it
             Ok(Some(HookLoadOutput {
@@ -315,6 +619,123 @@ impl Plugin for FobMdxPlugin {
     }
 }
 
+/// Read the files `source` includes, directly or transitively, through the runtime
+///
+/// Unreadable files are left out; compiling then reports them with the
+/// include that referenced them.
+async fn preload_includes(
+    source: &str,
+    root_dir: &std::path::Path,
+    runtime: &dyn Runtime,
+) -> HashMap<PathBuf, Vec<u8>> {
+    let mut files = HashMap::new();
+    let mut pending: Vec<PathBuf> = include_targets(source)
+        .into_iter()
+        .map(|target| resolve_include(root_dir, target))
+        .collect();
+
+    while let Some(path) = pending.pop() {
+        if files.contains_key(&path) {
+            continue;
+        }
+        let Ok(content) = runtime.read_file(&path).await else {
+            continue;
+        };
+        if let (Ok(text), Some(dir)) = (std::str::from_utf8(&content), path.parent()) {
+            pending.extend(
+                include_targets(text)
+                    .into_iter()
+                    .map(|target| resolve_include(dir, target)),
+            );
+        }
+        files.insert(path, content);
+    }
+    files
+}
+
+/// Read the local images `source` references through the runtime
+///
+/// Images are found with a plain markdown parse, which is enough to locate
+/// `![](...)` references without compiling the document twice.
+async fn preload_images(
+    source: &str,
+    dimensions: &ImageDimensionOptions,
+    runtime: &dyn Runtime,
+) -> HashMap<PathBuf, Vec<u8>> {
+    fn collect(node: &markdown::mdast::Node, urls: &mut Vec<String>) {
+        if let markdown::mdast::Node::Image(image) = node {
+            urls.push(image.url.clone());
+        }
+        for child in node.children().into_iter().flatten() {
+            collect(child, urls);
+        }
+    }
+
+    let mut urls = Vec::new();
+    if let Ok(ast) = markdown::to_mdast(source, &markdown::ParseOptions::gfm()) {
+        collect(&ast, &mut urls);
+    }
+
+    let mut files = HashMap::new();
+    for path in urls.iter().filter_map(|url| dimensions.resolve(url)) {
+        if files.contains_key(&path) {
+            continue;
+        }
+        match runtime.read_file(&path).await {
+            Ok(content) => {
+                files.insert(path, content);
+            }
+            Err(e) => tracing::debug!(path = %path.display(), error = %e, "Could not read image"),
+        }
+    }
+    files
+}
+
+/// Extensions tried, in order, for layout names without one
+const LAYOUT_EXTENSIONS: [&str; 4] = ["tsx", "jsx", "ts", "js"];
+
+/// The `layout` frontmatter key of `source`, if it is a non-empty string
+///
+/// Invalid frontmatter is ignored here; compiling reports it.
+fn frontmatter_layout(source: &str) -> Option<String> {
+    let frontmatter = parse_frontmatter(source).ok()??;
+    match frontmatter.data.get("layout")? {
+        serde_json::Value::String(name) if !name.trim().is_empty() => Some(name.trim().to_string()),
+        _ => None,
+    }
+}
+
+/// Find the file of layout `name` in `dir` through the runtime
+fn resolve_layout(
+    dir: &std::path::Path,
+    name: &str,
+    runtime: &dyn Runtime,
+) -> anyhow::Result<PathBuf> {
+    let path = dir.join(name);
+    let has_extension = path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| LAYOUT_EXTENSIONS.contains(&ext));
+    let candidates: Vec<PathBuf> = if has_extension {
+        vec![path]
+    } else {
+        LAYOUT_EXTENSIONS
+            .iter()
+            .map(|ext| {
+                let mut file = path.clone().into_os_string();
+                file.push(".");
+                file.push(ext);
+                PathBuf::from(file)
+            })
+            .collect()
+    };
+
+    candidates
+        .into_iter()
+        .find(|candidate| runtime.exists(candidate))
+        .ok_or_else(|| anyhow::anyhow!("Layout '{}' not found in {}", name, dir.display()))
+}
+
 // FobPlugin trait has been removed from the public API.
 // MDX plugin is now automatically registered by the bundler when .mdx files are detected.
 
@@ -322,6 +743,105 @@ impl Plugin for FobMdxPlugin {
 mod tests {
     use super::*;
 
+    mod project_root_tests {
+        use super::*;
+        use fob_bundler::runtime::BundlerRuntime;
+
+        #[test]
+        fn layouts_resolve_to_absolute_paths_with_default_project_root() {
+            let runtime = Arc::new(BundlerRuntime::new("/site"));
+            runtime.add_virtual_file("/site/layouts/Post.tsx", "export default () => null;");
+            let mut plugin = FobMdxPlugin::new(runtime.clone());
+            plugin.layouts_dir = Some(PathBuf::from("layouts"));
+
+            let dir = plugin.absolute_layouts_dir().unwrap();
+            assert_eq!(dir, PathBuf::from("/site/layouts"));
+            let layout = resolve_layout(&dir, "Post", &*runtime).unwrap();
+            assert_eq!(layout, PathBuf::from("/site/layouts/Post.tsx"));
+        }
+
+        #[tokio::test]
+        async fn collections_import_absolute_paths_with_default_project_root() {
+            let dir = tempfile::tempdir().unwrap();
+            std::fs::create_dir(dir.path().join("blog")).unwrap();
+            std::fs::write(dir.path().join("blog/hello.mdx"), "# Hello").unwrap();
+            let plugin = FobMdxPlugin::new(Arc::new(BundlerRuntime::new(dir.path())));
+
+            let code = generate_collection(
+                "blog/*.mdx",
+                &plugin.absolute_project_root(),
+                &*plugin.runtime,
+            )
+            .await
+            .unwrap();
+            let path = dir.path().join("blog/hello.mdx");
+            assert!(code.contains(&format!("import({:?})", path.to_string_lossy())));
+        }
+    }
+
+    mod builder_tests {
+        use super::*;
+        use crate::plugins::LinkValidationPlugin;
+        use fob_bundler::runtime::BundlerRuntime;
+
+        fn plugin() -> FobMdxPlugin {
+            FobMdxPlugin::new(Arc::new(BundlerRuntime::new("/site")))
+        }
+
+        #[test]
+        fn overrides_apply_to_matching_files() {
+            let plugin = plugin()
+                .with_output_format(OutputFormat::FunctionBody)
+                .with_override("docs/**", |options| options.math = false)
+                .with_override("**/*.mdx", |options| options.footnotes = false);
+
+            let docs = plugin
+                .create_options(Some("/site/docs/guide/intro.mdx".to_string()))
+                .unwrap();
+            assert!(!docs.math);
+            assert!(!docs.footnotes);
+            assert_eq!(docs.output_format, OutputFormat::FunctionBody);
+
+            let blog = plugin
+                .create_options(Some("/site/blog/post.mdx".to_string()))
+                .unwrap();
+            assert!(blog.math);
+            assert!(!blog.footnotes);
+
+            let outside = plugin
+                .create_options(Some("/other/docs/intro.mdx".to_string()))
+                .unwrap();
+            assert!(outside.math);
+        }
+
+        #[test]
+        fn custom_plugins_are_created_per_file() {
+            let plugin = plugin()
+                .with_provider_import_source("@mdx-js/react")
+                .with_mdx_plugin("link-validation", LinkValidationPlugin::new);
+
+            let options = plugin.create_options(None).unwrap();
+            assert_eq!(options.plugins.len(), 1);
+            assert_eq!(options.plugins[0].name(), "link-validation");
+            assert_eq!(
+                options.provider_import_source.as_deref(),
+                Some("@mdx-js/react")
+            );
+            assert_eq!(plugin.create_options(None).unwrap().plugins.len(), 1);
+        }
+
+        #[test]
+        fn invalid_override_patterns_fail_when_loading() {
+            let pattern = "?".repeat(100_000);
+            let plugin = plugin().with_override(&pattern, |options| options.math = false);
+
+            let err = plugin
+                .create_options(Some("/site/docs/intro.mdx".to_string()))
+                .unwrap_err();
+            assert!(err.to_string().contains("Invalid MDX override pattern"));
+        }
+    }
+
     // Integration tests using the full bundler pipeline
     //
     // NOTE: These tests are currently disabled because the public .plugin() API was removed
@@ -0,0 +1,166 @@
+//! Compile-time component mapping
+//!
+//! Entries of `MdxOptions::components` map an element or component name to
+//! a component, and are merged into `_components` between the built-in
+//! defaults and any provider or `props.components` overrides:
+//!
+//! - `"Heading"`: an identifier already in scope (e.g. imported in the MDX file)
+//! - `"./ui.js#Heading"`: named export, imported by the compiled module
+//! - `"./ui.js#default"`: default export
+
+use anyhow::{Result, bail};
+use std::collections::{BTreeMap, HashMap};
+
+use super::escape::{escape_js_string, is_valid_identifier};
+use crate::OutputFormat;
+
+/// Object literal and import statements for a component mapping
+#[derive(Debug, Default, PartialEq)]
+pub(crate) struct ComponentMapping {
+    /// `{h1: Heading, ...}`, or `None` if the mapping is empty
+    pub object: Option<String>,
+    /// Imports needed by the mapped components
+    pub imports: Vec<String>,
+}
+
+/// Build the mapping object and imports, sorted by name for stable output.
+pub(crate) fn component_mapping(
+    components: &HashMap<String, String>,
+    output_format: OutputFormat,
+) -> Result<ComponentMapping> {
+    if components.is_empty() {
+        return Ok(ComponentMapping::default());
+    }
+
+    let sorted: BTreeMap<&String, &String> = components.iter().collect();
+    let mut entries = Vec::with_capacity(sorted.len());
+    // Module → (export, local) pairs
+    let mut modules: BTreeMap<&str, Vec<(&str, String)>> = BTreeMap::new();
+
+    for (index, (name, target)) in sorted.into_iter().enumerate() {
+        let reference = match target.rsplit_once('#') {
+            Some((module, export)) => {
+                if output_format == OutputFormat::FunctionBody {
+                    bail!(
+                        "Component mapping '{}' imports from '{}', which is not supported with the function-body output format",
+                        name,
+                        module
+                    );
+                }
+                if module.is_empty() || !is_valid_identifier(export) && export != "default" {
+                    bail!("Invalid component mapping for '{}': '{}'", name, target);
+                }
+                let local = format!("_component{}", index);
+                modules
+                    .entry(module)
+                    .or_default()
+                    .push((export, local.clone()));
+                local
+            }
+            None => {
+                if !target.split('.').all(is_valid_identifier) {
+                    bail!(
+                        "Invalid component mapping for '{}': '{}' is not an identifier",
+                        name,
+                        target
+                    );
+                }
+                target.clone()
+            }
+        };
+
+        let key = if is_valid_identifier(name) {
+            name.clone()
+        } else {
+            format!("\"{}\"", escape_js_string(name))
+        };
+        entries.push(format!("{}: {}", key, reference));
+    }
+
+    let mut imports = Vec::new();
+    for (module, specifiers) in modules {
+        let module = escape_js_string(module);
+        let (defaults, named): (Vec<_>, Vec<_>) = specifiers
+            .into_iter()
+            .partition(|(export, _)| *export == "default");
+        for (_, local) in defaults {
+            imports.push(format!("import {} from '{}';", local, module));
+        }
+        if !named.is_empty() {
+            let named: Vec<String> = named
+                .into_iter()
+                .map(|(export, local)| format!("{} as {}", export, local))
+                .collect();
+            imports.push(format!(
+                "import {{{}}} from '{}';",
+                named.join(", "),
+                module
+            ));
+        }
+    }
+
+    Ok(ComponentMapping {
+        object: Some(format!("{{{}}}", entries.join(", "))),
+        imports,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mapping(entries: &[(&str, &str)]) -> Result<ComponentMapping> {
+        let components = entries
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        component_mapping(&components, OutputFormat::Program)
+    }
+
+    #[test]
+    fn test_empty_mapping() {
+        assert_eq!(mapping(&[]).unwrap(), ComponentMapping::default());
+    }
+
+    #[test]
+    fn test_identifiers_in_scope() {
+        let result = mapping(&[("h1", "Heading"), ("img", "UI.Image")]).unwrap();
+        assert_eq!(
+            result.object.as_deref(),
+            Some("{h1: Heading, img: UI.Image}")
+        );
+        assert!(result.imports.is_empty());
+    }
+
+    #[test]
+    fn test_imports_grouped_by_module() {
+        let result = mapping(&[
+            ("a", "./ui.js#Link"),
+            ("h1", "./ui.js#Heading"),
+            ("img", "./image.js#default"),
+        ])
+        .unwrap();
+        assert_eq!(
+            result.object.as_deref(),
+            Some("{a: _component0, h1: _component1, img: _component2}")
+        );
+        assert_eq!(
+            result.imports,
+            vec![
+                "import _component2 from './image.js';",
+                "import {Link as _component0, Heading as _component1} from './ui.js';",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_invalid_mappings() {
+        assert!(mapping(&[("h1", "not valid")]).is_err());
+        assert!(mapping(&[("h1", "#Heading")]).is_err());
+
+        let components = HashMap::from([("h1".to_string(), "./ui.js#Heading".to_string())]);
+        assert!(component_mapping(&components, OutputFormat::FunctionBody).is_err());
+        let components = HashMap::from([("h1".to_string(), "Heading".to_string())]);
+        assert!(component_mapping(&components, OutputFormat::FunctionBody).is_ok());
+    }
+}
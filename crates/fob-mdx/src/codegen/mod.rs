@@ -3,6 +3,7 @@
 //! Converts markdown AST nodes to JSX code strings with proper escaping
 //! and React runtime integration.
 
+mod components;
 mod context;
 mod escape;
 mod jsx_value;
@@ -13,3 +14,4 @@ pub use context::{CodegenContext, TableContext};
 pub use escape::{escape_js_string, is_valid_identifier};
 pub use jsx_value::JsValue;
 pub use renderer::{mdast_to_jsx, mdast_to_jsx_with_options};
+pub(crate) use renderer::{render_jsx, transform_ast};
@@ -3,8 +3,10 @@
 use anyhow::{Context, Result, anyhow};
 use markdown::mdast::Node;
 
+use super::components::component_mapping;
 use super::context::CodegenContext;
-use crate::frontmatter::extract_frontmatter;
+use crate::JsxTarget;
+use crate::frontmatter::{FrontmatterData, extract_frontmatter};
 
 /// Convert MDX mdast to JSX string with React 19 and MDX v3 compatibility
 ///
@@ -32,6 +34,15 @@ pub fn mdast_to_jsx(root: &Node) -> Result<String> {
 /// 3. Convert AST to JSX
 /// 4. Run all `plugin.transform_jsx()` in registration order
 pub fn mdast_to_jsx_with_options(root: &Node, options: &crate::MdxOptions) -> Result<String> {
+    let (cleaned_root, frontmatter) = transform_ast(root, options)?;
+    render_jsx(&cleaned_root, frontmatter.as_ref(), options)
+}
+
+/// Extract frontmatter and run all AST transformation plugins
+pub(crate) fn transform_ast(
+    root: &Node,
+    options: &crate::MdxOptions,
+) -> Result<(Node, Option<FrontmatterData>)> {
     // Use pre-extracted frontmatter if provided, otherwise extract from AST
     let (mut cleaned_root, frontmatter) = if options.frontmatter.is_some() {
         // Frontmatter already extracted, just clone root and use provided frontmatter
@@ -52,6 +63,15 @@ pub fn mdast_to_jsx_with_options(root: &Node, options: &crate::MdxOptions) -> Re
         })?;
     }
 
+    Ok((cleaned_root, frontmatter))
+}
+
+/// Generate JSX from an already transformed AST and run JSX transformation plugins
+pub(crate) fn render_jsx(
+    cleaned_root: &Node,
+    frontmatter: Option<&FrontmatterData>,
+    options: &crate::MdxOptions,
+) -> Result<String> {
     let mut imports = Vec::new();
     let mut named_exports = Vec::new();
     let mut reexports = Vec::new();
@@ -67,7 +87,7 @@ pub fn mdast_to_jsx_with_options(root: &Node, options: &crate::MdxOptions) -> Re
         ));
     }
 
-    if let Node::Root(root_node) = &cleaned_root {
+    if let Node::Root(root_node) = cleaned_root {
         for child in &root_node.children {
             match child {
                 Node::MdxjsEsm(esm) => {
@@ -120,7 +140,7 @@ pub fn mdast_to_jsx_with_options(root: &Node, options: &crate::MdxOptions) -> Re
     }
 
     // Add frontmatter export if present
-    if let Some(ref fm) = frontmatter {
+    if let Some(fm) = frontmatter {
         // Serialize frontmatter as JSON and inject as a named export
         let json_str = serde_json::to_string(&fm.data)
             .with_context(|| "Failed to serialize frontmatter to JSON")?;
@@ -145,30 +165,55 @@ pub fn mdast_to_jsx_with_options(root: &Node, options: &crate::MdxOptions) -> Re
         )
     };
 
+    // Wrap the content in the layout's default export
+    let content = match &options.layout {
+        Some(layout) => {
+            if options.output_format == crate::OutputFormat::FunctionBody {
+                return Err(anyhow!(
+                    "Layout '{}' must be imported, which is not supported with the function-body output format",
+                    layout
+                ));
+            }
+            imports.push(format!(
+                "import _Layout from '{}';",
+                super::escape::escape_js_string(layout)
+            ));
+            let frontmatter_prop = if frontmatter.is_some() {
+                "frontmatter, "
+            } else {
+                ""
+            };
+            format!(
+                "_jsx(_Layout, {{...props, components: _cProp, {}children: {}}})",
+                frontmatter_prop, content
+            )
+        }
+        None => content,
+    };
+
     // Data props destructuring (empty since providers are removed)
     let data_props_destructure = String::new();
 
     // Component merge: when provider is configured, merge provider components between defaults and props
-    // Order: defaults < _provideComponents() < props.components (later wins)
-    let component_merge = if options.provider_import_source.is_some() {
-        // With provider: merge defaults + provider + props
-        r#"const _components = Object.assign({
-    h1: "h1", h2: "h2", h3: "h3", h4: "h4", h5: "h5", h6: "h6",
-    p: "p", a: "a", strong: "strong", em: "em", code: "code", pre: "pre",
-    blockquote: "blockquote", ul: "ul", ol: "ol", li: "li",
-    table: "table", thead: "thead", tbody: "tbody", tr: "tr", th: "th", td: "td",
-    hr: "hr", br: "br", img: "img", del: "del", div: "div", span: "span", sup: "sup", input: "input"
-  }, _provideComponents(), _cProp);"#
-    } else {
-        // Without provider: just merge defaults + props
-        r#"const _components = Object.assign({
+    // Order: defaults < compile-time mapping < _provideComponents() < props.components (later wins)
+    let mapping = component_mapping(&options.components, options.output_format)?;
+    imports.extend(mapping.imports);
+    let mut overrides = Vec::new();
+    overrides.extend(mapping.object);
+    if options.provider_import_source.is_some() {
+        overrides.push("_provideComponents()".to_string());
+    }
+    overrides.push("_cProp".to_string());
+    let component_merge = format!(
+        r#"const _components = Object.assign({{
     h1: "h1", h2: "h2", h3: "h3", h4: "h4", h5: "h5", h6: "h6",
     p: "p", a: "a", strong: "strong", em: "em", code: "code", pre: "pre",
     blockquote: "blockquote", ul: "ul", ol: "ol", li: "li",
     table: "table", thead: "thead", tbody: "tbody", tr: "tr", th: "th", td: "td",
     hr: "hr", br: "br", img: "img", del: "del", div: "div", span: "span", sup: "sup", input: "input"
-  }, _cProp);"#
-    };
+  }}, {});"#,
+        overrides.join(", ")
+    );
 
     // Build MDXContent function body (shared between formats)
     let mdx_content_body = format!(
@@ -195,18 +240,11 @@ pub fn mdast_to_jsx_with_options(root: &Node, options: &crate::MdxOptions) -> Re
         crate::OutputFormat::Program => {
             // Program format: ES module with import/export
             // Add JSX runtime imports based on what we need
-            let jsx_runtime = if needs_fragment {
-                format!(
-                    "import {{jsx as _jsx, jsxs as _jsxs, Fragment as _Fragment}} from '{}';",
-                    options.jsx_runtime
-                )
-            } else {
-                format!(
-                    "import {{jsx as _jsx, jsxs as _jsxs}} from '{}';",
-                    options.jsx_runtime
-                )
-            };
-            imports.insert(0, jsx_runtime);
+            let runtime = options.jsx_target.resolve_runtime(&options.jsx_runtime);
+            imports.insert(
+                0,
+                runtime_import(options.jsx_target, runtime, needs_fragment),
+            );
 
             // Add imports
             if !imports.is_empty() {
@@ -235,13 +273,7 @@ pub fn mdast_to_jsx_with_options(root: &Node, options: &crate::MdxOptions) -> Re
             output.push_str("\"use strict\";\n");
 
             // Provide JSX runtime from arguments[0]
-            if needs_fragment {
-                output.push_str(
-                    "const {jsx: _jsx, jsxs: _jsxs, Fragment: _Fragment} = arguments[0];\n",
-                );
-            } else {
-                output.push_str("const {jsx: _jsx, jsxs: _jsxs} = arguments[0];\n");
-            }
+            output.push_str(&runtime_arguments(options.jsx_target, needs_fragment));
 
             // Extract named export names for return object
             let mut export_names = Vec::new();
@@ -293,3 +325,41 @@ pub fn mdast_to_jsx_with_options(root: &Node, options: &crate::MdxOptions) -> Re
 
     Ok(output)
 }
+
+/// Adapter defining `_jsx`/`_jsxs` on top of Vue's `h()`.
+///
+/// Vue passes children to components as slots, so component children are
+/// wrapped in a default slot function; elements and fragments take them as-is.
+const VUE_JSX_ADAPTER: &str = "const _jsx = (type, {children, ...props}) => _h(type, props, typeof type === \"string\" || type === _Fragment || children === undefined ? children : {default: () => children});
+const _jsxs = _jsx;";
+
+/// Runtime import for the program output format.
+fn runtime_import(target: JsxTarget, runtime: &str, needs_fragment: bool) -> String {
+    match target {
+        JsxTarget::Vue => format!(
+            "import {{h as _h, Fragment as _Fragment}} from '{}';\n{}",
+            runtime, VUE_JSX_ADAPTER
+        ),
+        _ if needs_fragment => format!(
+            "import {{jsx as _jsx, jsxs as _jsxs, Fragment as _Fragment}} from '{}';",
+            runtime
+        ),
+        _ => format!("import {{jsx as _jsx, jsxs as _jsxs}} from '{}';", runtime),
+    }
+}
+
+/// Runtime bindings taken from `arguments[0]` in the function-body output format.
+///
+/// For Vue, `arguments[0]` is the `vue` module itself.
+fn runtime_arguments(target: JsxTarget, needs_fragment: bool) -> String {
+    match target {
+        JsxTarget::Vue => format!(
+            "const {{h: _h, Fragment: _Fragment}} = arguments[0];\n{}\n",
+            VUE_JSX_ADAPTER
+        ),
+        _ if needs_fragment => {
+            "const {jsx: _jsx, jsxs: _jsxs, Fragment: _Fragment} = arguments[0];\n".to_string()
+        }
+        _ => "const {jsx: _jsx, jsxs: _jsxs} = arguments[0];\n".to_string(),
+    }
+}
@@ -0,0 +1,138 @@
+//! Content collections: `fob:content/<glob>` virtual modules
+//!
+//! `import posts from 'fob:content/blog/*.mdx'` resolves to a module whose
+//! default export lists the matching `.mdx` files under the project root,
+//! sorted by path:
+//!
+//! ```javascript
+//! export default [
+//!   {slug: "hello", frontmatter: {"title": "Hello"}, Component: () => import("/site/blog/hello.mdx")},
+//! ];
+//! ```
+//!
+//! Frontmatter is read without compiling the documents. Each document is
+//! compiled into its own chunk, loaded when `Component()` is called, so index
+//! pages don't pull in every post; `Component` has the shape `React.lazy` and
+//! similar helpers expect.
+//!
+//! The slug is the path below the pattern's leading directories without the
+//! extension (`blog/2024/hello.mdx` → `2024/hello`, `blog/intro/index.mdx` →
+//! `intro`), unless the frontmatter sets a `slug` string.
+
+use crate::frontmatter::parse_frontmatter;
+use crate::glob::Glob;
+use anyhow::Context;
+use fob_bundler::Runtime;
+use serde_json::Value;
+use std::path::Path;
+
+/// Specifier prefix of content collections
+pub(crate) const CONTENT_PREFIX: &str = "fob:content/";
+
+/// Prefix for resolved collection ids (`\0` marks them as virtual for other plugins)
+pub(crate) const RESOLVED_CONTENT_PREFIX: &str = "\0fob:content/";
+
+/// Generate the module for the collection of files matching `pattern`,
+/// relative to the absolute directory `root`
+pub(crate) async fn generate_collection(
+    pattern: &str,
+    root: &Path,
+    runtime: &dyn Runtime,
+) -> anyhow::Result<String> {
+    let glob = Glob::new(pattern)
+        .with_context(|| format!("Invalid content collection pattern '{}'", pattern))?;
+    let mut code = String::from("export default [\n");
+    for relative in find_files(&glob, root, runtime).await? {
+        let path = root.join(&relative);
+        let content = runtime
+            .read_file(&path)
+            .await
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        let frontmatter = parse_frontmatter(&String::from_utf8_lossy(&content))
+            .with_context(|| format!("Invalid frontmatter in {}", path.display()))?
+            .map(|frontmatter| frontmatter.data)
+            .filter(Value::is_object)
+            .unwrap_or_else(|| Value::Object(Default::default()));
+        let slug = match frontmatter.get("slug") {
+            Some(Value::String(slug)) => slug.clone(),
+            _ => default_slug(&relative, glob.base()),
+        };
+
+        code.push_str(&format!(
+            "  {{slug: {}, frontmatter: {}, Component: () => import({})}},\n",
+            Value::String(slug),
+            frontmatter,
+            Value::String(path.to_string_lossy().into_owned())
+        ));
+    }
+    code.push_str("];\n");
+    Ok(code)
+}
+
+/// Paths of the `.mdx` files matching `glob`, relative to `root` and sorted
+///
+/// Hidden directories and `node_modules` are skipped.
+async fn find_files(
+    glob: &Glob,
+    root: &Path,
+    runtime: &dyn Runtime,
+) -> anyhow::Result<Vec<String>> {
+    let max_depth = glob.max_depth();
+    let mut files = Vec::new();
+    let mut pending = vec![(glob.base().to_string(), 0)];
+
+    while let Some((dir, depth)) = pending.pop() {
+        let dir_path = root.join(&dir);
+        let entries = runtime
+            .read_dir(&dir_path)
+            .await
+            .with_context(|| format!("Failed to read content directory {}", dir_path.display()))?;
+
+        for name in entries {
+            let relative = if dir.is_empty() {
+                name.clone()
+            } else {
+                format!("{}/{}", dir, name)
+            };
+            let metadata = runtime.metadata(&root.join(&relative)).await?;
+            if metadata.is_dir {
+                let descend = max_depth.is_none_or(|max| depth < max);
+                if descend && !name.starts_with('.') && name != "node_modules" {
+                    pending.push((relative, depth + 1));
+                }
+            } else if relative.ends_with(".mdx") && glob.is_match(&relative) {
+                files.push(relative);
+            }
+        }
+    }
+
+    files.sort();
+    Ok(files)
+}
+
+/// Slug of a file from its path relative to the root
+fn default_slug(relative: &str, base: &str) -> String {
+    let path = relative
+        .strip_prefix(base)
+        .unwrap_or(relative)
+        .trim_start_matches('/');
+    let path = path.strip_suffix(".mdx").unwrap_or(path);
+    if path == "index" {
+        return String::new();
+    }
+    path.strip_suffix("/index").unwrap_or(path).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn slugs_are_relative_to_base() {
+        assert_eq!(default_slug("blog/hello.mdx", "blog"), "hello");
+        assert_eq!(default_slug("blog/2024/hello.mdx", "blog"), "2024/hello");
+        assert_eq!(default_slug("blog/intro/index.mdx", "blog"), "intro");
+        assert_eq!(default_slug("blog/index.mdx", "blog"), "");
+        assert_eq!(default_slug("hello.mdx", ""), "hello");
+    }
+}
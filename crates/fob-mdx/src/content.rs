@@ -0,0 +1,473 @@
+//! Structured content output
+//!
+//! Converts the post-plugin mdast into a portable, HAST-like tree for
+//! consumers that render content without executing JavaScript (native apps,
+//! search indexers, non-JS server renderers).
+//!
+//! HTML elements become [`ContentNode::Element`] with the same tag names the
+//! JSX codegen uses, so a renderer can map them onto its own components.
+//! MDX components are kept as [`ContentNode::Component`] rather than being
+//! flattened, and JavaScript expressions are preserved verbatim as
+//! [`ContentNode::Expression`] for the consumer to evaluate or ignore.
+//! ESM, frontmatter and definitions are dropped.
+
+use crate::plugins::{HeadingIdPlugin, Slugger};
+use crate::utils::plain_text;
+use markdown::mdast::{AlignKind, AttributeContent, AttributeValue, Node};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Node of the structured content tree
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum ContentNode {
+    /// Document root
+    Root { children: Vec<ContentNode> },
+    /// HTML element (`p`, `h1`, `a`, ...)
+    #[serde(rename_all = "camelCase")]
+    Element {
+        tag_name: String,
+        properties: BTreeMap<String, PropValue>,
+        children: Vec<ContentNode>,
+    },
+    /// MDX component (`<Note>`, `<Chart data={...}>`); `name` is `None` for fragments
+    Component {
+        name: Option<String>,
+        props: BTreeMap<String, PropValue>,
+        children: Vec<ContentNode>,
+    },
+    /// Text content
+    Text { value: String },
+    /// Unevaluated JavaScript expression (`{props.title}`)
+    Expression { value: String },
+    /// Raw HTML passed through from the source
+    Raw { value: String },
+}
+
+/// Property value of an element or component
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum PropValue {
+    Bool(bool),
+    Number(u64),
+    String(String),
+    /// Unevaluated JavaScript expression
+    Expression {
+        expression: String,
+    },
+}
+
+/// Convert a (frontmatter-free, post-plugin) mdast root to structured content.
+///
+/// With `heading_ids`, headings get the same `id` the heading ID plugin
+/// injects into the JSX output.
+pub(crate) fn to_content(root: &Node, heading_ids: Option<&HeadingIdPlugin>) -> ContentNode {
+    let mut converter = Converter {
+        slugger: heading_ids.map(HeadingIdPlugin::slugger),
+        footnote_count: 0,
+    };
+    ContentNode::Root {
+        children: converter.children(root.children().map_or(&[], |c| c.as_slice())),
+    }
+}
+
+struct Converter<'a> {
+    slugger: Option<Slugger<'a>>,
+    footnote_count: usize,
+}
+
+impl Converter<'_> {
+    fn children(&mut self, nodes: &[Node]) -> Vec<ContentNode> {
+        nodes.iter().filter_map(|node| self.node(node)).collect()
+    }
+
+    fn node(&mut self, node: &Node) -> Option<ContentNode> {
+        let content = match node {
+            Node::Text(text) => ContentNode::Text {
+                value: text.value.clone(),
+            },
+            Node::Paragraph(p) => self.element("p", &p.children),
+            Node::Heading(heading) => {
+                let mut properties = BTreeMap::new();
+                if let Some(id) = self.heading_id(node) {
+                    properties.insert("id".to_string(), PropValue::String(id));
+                }
+                element(
+                    &format!("h{}", heading.depth),
+                    properties,
+                    self.children(&heading.children),
+                )
+            }
+            Node::Blockquote(quote) => self.element("blockquote", &quote.children),
+            Node::ThematicBreak(_) => element("hr", BTreeMap::new(), Vec::new()),
+            Node::Break(_) => element("br", BTreeMap::new(), Vec::new()),
+            Node::Emphasis(em) => self.element("em", &em.children),
+            Node::Strong(strong) => self.element("strong", &strong.children),
+            Node::Delete(del) => self.element("del", &del.children),
+            Node::InlineCode(code) => element("code", BTreeMap::new(), vec![text(&code.value)]),
+            Node::Code(code) => {
+                let mut properties = BTreeMap::new();
+                if let Some(lang) = &code.lang {
+                    properties.insert(
+                        "className".to_string(),
+                        PropValue::String(format!("language-{}", lang)),
+                    );
+                }
+                if let Some(meta) = &code.meta {
+                    properties.insert("metastring".to_string(), PropValue::String(meta.clone()));
+                }
+                element(
+                    "pre",
+                    BTreeMap::new(),
+                    vec![element("code", properties, vec![text(&code.value)])],
+                )
+            }
+            Node::List(list) => {
+                let mut properties = BTreeMap::new();
+                if let Some(start) = list.start.filter(|start| list.ordered && *start != 1) {
+                    properties.insert("start".to_string(), PropValue::Number(start as u64));
+                }
+                element(
+                    if list.ordered { "ol" } else { "ul" },
+                    properties,
+                    self.children(&list.children),
+                )
+            }
+            Node::ListItem(item) => {
+                let mut children = Vec::new();
+                if let Some(checked) = item.checked {
+                    let properties = BTreeMap::from([
+                        (
+                            "type".to_string(),
+                            PropValue::String("checkbox".to_string()),
+                        ),
+                        ("checked".to_string(), PropValue::Bool(checked)),
+                        ("disabled".to_string(), PropValue::Bool(true)),
+                    ]);
+                    children.push(element("input", properties, Vec::new()));
+                    children.push(text(" "));
+                }
+                children.extend(self.children(&item.children));
+                element("li", BTreeMap::new(), children)
+            }
+            Node::Link(link) => {
+                let mut properties =
+                    BTreeMap::from([("href".to_string(), PropValue::String(link.url.clone()))]);
+                if let Some(title) = &link.title {
+                    properties.insert("title".to_string(), PropValue::String(title.clone()));
+                }
+                element("a", properties, self.children(&link.children))
+            }
+            Node::Image(image) => {
+                let mut properties = BTreeMap::from([
+                    ("src".to_string(), PropValue::String(image.url.clone())),
+                    ("alt".to_string(), PropValue::String(image.alt.clone())),
+                ]);
+                if let Some(title) = &image.title {
+                    properties.insert("title".to_string(), PropValue::String(title.clone()));
+                }
+                element("img", properties, Vec::new())
+            }
+            Node::Table(table) => {
+                let mut rows = table
+                    .children
+                    .iter()
+                    .map(|row| self.table_row(row, &table.align));
+                let mut sections = Vec::new();
+                if let Some(head) = rows.next() {
+                    sections.push(element(
+                        "thead",
+                        BTreeMap::new(),
+                        vec![head.with_cell_tag("th")],
+                    ));
+                }
+                let body: Vec<ContentNode> = rows.collect();
+                if !body.is_empty() {
+                    sections.push(element("tbody", BTreeMap::new(), body));
+                }
+                element("table", BTreeMap::new(), sections)
+            }
+            Node::Math(math) => element(
+                "div",
+                class_name("math math-display"),
+                vec![text(&math.value)],
+            ),
+            Node::InlineMath(math) => element(
+                "span",
+                class_name("math math-inline"),
+                vec![text(&math.value)],
+            ),
+            Node::FootnoteReference(reference) => {
+                self.footnote_count += 1;
+                let properties = BTreeMap::from([
+                    (
+                        "href".to_string(),
+                        PropValue::String(format!("#fn-{}", reference.identifier)),
+                    ),
+                    (
+                        "id".to_string(),
+                        PropValue::String(format!("fnref-{}", reference.identifier)),
+                    ),
+                ]);
+                element(
+                    "sup",
+                    BTreeMap::new(),
+                    vec![element(
+                        "a",
+                        properties,
+                        vec![text(&self.footnote_count.to_string())],
+                    )],
+                )
+            }
+            Node::FootnoteDefinition(definition) => {
+                let properties = BTreeMap::from([(
+                    "id".to_string(),
+                    PropValue::String(format!("fn-{}", definition.identifier)),
+                )]);
+                element("div", properties, self.children(&definition.children))
+            }
+            Node::MdxJsxFlowElement(el) => self.jsx(&el.name, &el.attributes, &el.children),
+            Node::MdxJsxTextElement(el) => self.jsx(&el.name, &el.attributes, &el.children),
+            Node::MdxFlowExpression(expr) => ContentNode::Expression {
+                value: expr.value.clone(),
+            },
+            Node::MdxTextExpression(expr) => ContentNode::Expression {
+                value: expr.value.clone(),
+            },
+            Node::Html(html) => ContentNode::Raw {
+                value: html.value.clone(),
+            },
+            _ => return None,
+        };
+        Some(content)
+    }
+
+    fn element(&mut self, tag: &str, children: &[Node]) -> ContentNode {
+        element(tag, BTreeMap::new(), self.children(children))
+    }
+
+    fn table_row(&mut self, row: &Node, align: &[AlignKind]) -> ContentNode {
+        let cells = row
+            .children()
+            .map_or(&[][..], |c| c.as_slice())
+            .iter()
+            .enumerate()
+            .map(|(index, cell)| {
+                let mut properties = BTreeMap::new();
+                let text_align = match align.get(index) {
+                    Some(AlignKind::Left) => Some("left"),
+                    Some(AlignKind::Right) => Some("right"),
+                    Some(AlignKind::Center) => Some("center"),
+                    _ => None,
+                };
+                if let Some(value) = text_align {
+                    properties.insert("align".to_string(), PropValue::String(value.to_string()));
+                }
+                let children = self.children(cell.children().map_or(&[], |c| c.as_slice()));
+                element("td", properties, children)
+            })
+            .collect();
+        element("tr", BTreeMap::new(), cells)
+    }
+
+    fn jsx(
+        &mut self,
+        name: &Option<String>,
+        attributes: &[AttributeContent],
+        children: &[Node],
+    ) -> ContentNode {
+        let mut props = BTreeMap::new();
+        for attribute in attributes {
+            match attribute {
+                AttributeContent::Property(property) => {
+                    let value = match &property.value {
+                        None => PropValue::Bool(true),
+                        Some(AttributeValue::Literal(value)) => PropValue::String(value.clone()),
+                        Some(AttributeValue::Expression(expr)) => PropValue::Expression {
+                            expression: expr.value.clone(),
+                        },
+                    };
+                    props.insert(property.name.clone(), value);
+                }
+                AttributeContent::Expression(expr) => {
+                    // Spread attributes can't be named; keep them under a reserved key
+                    props.insert(
+                        "...".to_string(),
+                        PropValue::Expression {
+                            expression: expr.value.clone(),
+                        },
+                    );
+                }
+            }
+        }
+
+        let children = self.children(children);
+        match name {
+            Some(tag) if is_html_tag(tag) => element(tag, props, children),
+            _ => ContentNode::Component {
+                name: name.clone(),
+                props,
+                children,
+            },
+        }
+    }
+
+    fn heading_id(&mut self, heading: &Node) -> Option<String> {
+        let text = plain_text(heading);
+        Some(self.slugger.as_mut()?.id(&text))
+    }
+}
+
+impl ContentNode {
+    /// Rename the `td` cells of a row (used for the header row).
+    fn with_cell_tag(mut self, tag: &str) -> Self {
+        if let ContentNode::Element { children, .. } = &mut self {
+            for cell in children {
+                if let ContentNode::Element { tag_name, .. } = cell {
+                    *tag_name = tag.to_string();
+                }
+            }
+        }
+        self
+    }
+}
+
+/// Lowercase, undotted JSX names are HTML elements; everything else is a component.
+fn is_html_tag(name: &str) -> bool {
+    name.starts_with(|c: char| c.is_ascii_lowercase()) && !name.contains('.')
+}
+
+fn element(
+    tag: &str,
+    properties: BTreeMap<String, PropValue>,
+    children: Vec<ContentNode>,
+) -> ContentNode {
+    ContentNode::Element {
+        tag_name: tag.to_string(),
+        properties,
+        children,
+    }
+}
+
+fn text(value: &str) -> ContentNode {
+    ContentNode::Text {
+        value: value.to_string(),
+    }
+}
+
+fn class_name(value: &str) -> BTreeMap<String, PropValue> {
+    BTreeMap::from([(
+        "className".to_string(),
+        PropValue::String(value.to_string()),
+    )])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn convert(source: &str) -> ContentNode {
+        let mut options = markdown::ParseOptions::mdx();
+        options.constructs.gfm_table = true;
+        to_content(
+            &markdown::to_mdast(source, &options).unwrap(),
+            Some(&HeadingIdPlugin::new()),
+        )
+    }
+
+    fn root_children(node: ContentNode) -> Vec<ContentNode> {
+        match node {
+            ContentNode::Root { children } => children,
+            other => panic!("expected root, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_heading_ids_are_deduplicated() {
+        let children = root_children(convert("# Intro\n\n## Intro"));
+        let ids: Vec<_> = children
+            .iter()
+            .map(|node| match node {
+                ContentNode::Element { properties, .. } => properties["id"].clone(),
+                _ => panic!("expected element"),
+            })
+            .collect();
+        assert_eq!(
+            ids,
+            vec![
+                PropValue::String("intro".to_string()),
+                PropValue::String("intro-1".to_string())
+            ]
+        );
+    }
+
+    #[test]
+    fn test_components_and_expressions_are_explicit() {
+        let children = root_children(convert(
+            "<Chart data={points} animated>\n\nHi {name}\n\n</Chart>",
+        ));
+        let ContentNode::Component {
+            name,
+            props,
+            children,
+        } = &children[0]
+        else {
+            panic!("expected component, got {:?}", children[0]);
+        };
+        assert_eq!(name.as_deref(), Some("Chart"));
+        assert_eq!(
+            props["data"],
+            PropValue::Expression {
+                expression: "points".to_string()
+            }
+        );
+        assert_eq!(props["animated"], PropValue::Bool(true));
+
+        let ContentNode::Element { children, .. } = &children[0] else {
+            panic!("expected paragraph");
+        };
+        assert_eq!(
+            children[1],
+            ContentNode::Expression {
+                value: "name".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn test_table_header_cells() {
+        let children = root_children(convert("| a | b |\n|:--|--:|\n| 1 | 2 |"));
+        let json = serde_json::to_value(&children[0]).unwrap();
+        assert_eq!(json["tagName"], "table");
+        assert_eq!(
+            json["children"][0]["children"][0]["children"][0]["tagName"],
+            "th"
+        );
+        assert_eq!(
+            json["children"][1]["children"][0]["children"][1]["properties"]["align"],
+            "right"
+        );
+    }
+
+    #[test]
+    fn test_serialized_shape() {
+        let json = serde_json::to_value(convert("Some *text*")).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "type": "root",
+                "children": [{
+                    "type": "element",
+                    "tagName": "p",
+                    "properties": {},
+                    "children": [
+                        {"type": "text", "value": "Some "},
+                        {"type": "element", "tagName": "em", "properties": {}, "children": [
+                            {"type": "text", "value": "text"}
+                        ]}
+                    ]
+                }]
+            })
+        );
+    }
+}
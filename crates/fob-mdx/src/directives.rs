@@ -0,0 +1,356 @@
+//! Container directives (`:::note ... :::`)
+//!
+//! Remark-style container directives are rewritten to JSX flow elements
+//! before parsing, so their content is still parsed as markdown:
+//!
+//! ```text
+//! :::warning[Heads up]{#legacy .compact}    <Warning title={"Heads up"} id={"legacy"} className={"compact"}>
+//! Old **API**                          →    Old **API**
+//! :::                                       </Warning>
+//! ```
+//!
+//! Fences are replaced line for line, so error positions in the rewritten
+//! source still point at the original lines.
+//!
+//! Only directive names present in the component mapping are rewritten; any
+//! other `:::` line is left as-is. Directives nest when the outer fence uses
+//! more colons than the inner one (`::::tip` ... `:::note` ... `:::` ... `::::`).
+//! Lines inside fenced code blocks are never treated as directives.
+
+use crate::codegen::escape_js_string;
+use std::collections::HashMap;
+
+/// Directive name → component mapping for common admonitions.
+///
+/// Covers the admonition names used by Docusaurus and remark-directive setups.
+/// Not enabled by default; pass it to [`MdxCompileOptions::directives`]
+/// (with `Note`, `Tip`, ... components in scope) to opt in.
+///
+/// [`MdxCompileOptions::directives`]: crate::MdxCompileOptions::directives
+pub fn default_directive_components() -> HashMap<String, String> {
+    [
+        ("note", "Note"),
+        ("tip", "Tip"),
+        ("info", "Info"),
+        ("warning", "Warning"),
+        ("caution", "Caution"),
+        ("danger", "Danger"),
+    ]
+    .into_iter()
+    .map(|(name, component)| (name.to_string(), component.to_string()))
+    .collect()
+}
+
+/// Opening directive fence.
+#[derive(Debug, PartialEq)]
+struct Opener<'a> {
+    colons: usize,
+    name: &'a str,
+    label: Option<&'a str>,
+    attributes: Vec<(String, String)>,
+}
+
+/// Rewrite mapped container directives into JSX elements.
+///
+/// Returns `None` if the source contains no directives to rewrite.
+pub(crate) fn rewrite_directives(
+    source: &str,
+    components: &HashMap<String, String>,
+) -> Option<String> {
+    if components.is_empty() || !source.contains(":::") {
+        return None;
+    }
+
+    let mut output = String::with_capacity(source.len());
+    // Open directives: (colon count, component, indentation)
+    let mut open: Vec<(usize, &str, &str)> = Vec::new();
+    let mut fence: Option<&str> = None;
+    let mut rewritten = false;
+
+    for line in source.split_inclusive('\n') {
+        let trimmed = line.trim();
+        let indent = &line[..line.len() - line.trim_start().len()];
+        let line_fence = ["```", "~~~"]
+            .into_iter()
+            .find(|marker| trimmed.starts_with(marker));
+
+        match (fence, line_fence) {
+            (None, Some(marker)) => fence = Some(marker),
+            (Some(open_marker), Some(marker)) if open_marker == marker => fence = None,
+            _ => {}
+        }
+        if fence.is_some() || line_fence.is_some() {
+            output.push_str(line);
+            continue;
+        }
+
+        // Fences are replaced in place; JSX flow tags interrupt paragraphs,
+        // so no blank lines are needed around them
+        let line_ending = &line[line.trim_end_matches(['\r', '\n']).len()..];
+
+        if let Some(colons) = closing_fence(trimmed) {
+            if let Some(&(open_colons, component, open_indent)) = open.last() {
+                if colons >= open_colons {
+                    open.pop();
+                    output.push_str(&format!("{}</{}>{}", open_indent, component, line_ending));
+                    continue;
+                }
+            }
+        }
+
+        if let Some(opener) = parse_opener(trimmed) {
+            if let Some(component) = components.get(opener.name) {
+                output.push_str(&format!(
+                    "{}{}{}",
+                    indent,
+                    opening_tag(component, &opener),
+                    line_ending
+                ));
+                open.push((opener.colons, component, indent));
+                rewritten = true;
+                continue;
+            }
+        }
+
+        output.push_str(line);
+    }
+
+    // Close anything left open after the last line of the document
+    while let Some((_, component, indent)) = open.pop() {
+        if !output.ends_with('\n') {
+            output.push('\n');
+        }
+        output.push_str(&format!("{}</{}>\n", indent, component));
+    }
+
+    rewritten.then_some(output)
+}
+
+/// Number of colons if the line is a closing fence (only colons, at least three).
+fn closing_fence(line: &str) -> Option<usize> {
+    (line.len() >= 3 && line.bytes().all(|b| b == b':')).then_some(line.len())
+}
+
+/// Parse `:::name[label]{attributes}`.
+fn parse_opener(line: &str) -> Option<Opener<'_>> {
+    let colons = line.bytes().take_while(|&b| b == b':').count();
+    if colons < 3 {
+        return None;
+    }
+
+    let rest = &line[colons..];
+    let name_len = rest
+        .char_indices()
+        .find(|(i, c)| !(c.is_ascii_alphanumeric() || (*i > 0 && (*c == '-' || *c == '_'))))
+        .map_or(rest.len(), |(i, _)| i);
+    if name_len == 0 || !rest.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        return None;
+    }
+    let name = &rest[..name_len];
+    let mut rest = &rest[name_len..];
+
+    let mut label = None;
+    if let Some(after) = rest.strip_prefix('[') {
+        let end = after.find(']')?;
+        label = Some(&after[..end]);
+        rest = &after[end + 1..];
+    }
+
+    let mut attributes = Vec::new();
+    if let Some(after) = rest.strip_prefix('{') {
+        let end = after.rfind('}')?;
+        attributes = parse_attributes(&after[..end]);
+        rest = &after[end + 1..];
+    }
+
+    rest.trim().is_empty().then_some(Opener {
+        colons,
+        name,
+        label,
+        attributes,
+    })
+}
+
+/// Parse `#id .class key="value" key=value flag` attribute lists.
+fn parse_attributes(input: &str) -> Vec<(String, String)> {
+    let mut attributes: Vec<(String, String)> = Vec::new();
+    let mut classes: Vec<&str> = Vec::new();
+    let mut chars = input.char_indices().peekable();
+
+    while let Some(&(start, c)) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+            continue;
+        }
+
+        // Read a key up to whitespace or '='
+        let mut end = start;
+        while let Some(&(i, c)) = chars.peek() {
+            if c.is_whitespace() || c == '=' {
+                break;
+            }
+            end = i + c.len_utf8();
+            chars.next();
+        }
+        let key = &input[start..end];
+
+        let value = if chars.peek().is_some_and(|&(_, c)| c == '=') {
+            chars.next();
+            match chars.peek().copied() {
+                Some((i, quote @ ('"' | '\''))) => {
+                    chars.next();
+                    let value_start = i + 1;
+                    let mut value_end = input.len();
+                    for (j, c) in chars.by_ref() {
+                        if c == quote {
+                            value_end = j;
+                            break;
+                        }
+                    }
+                    input[value_start..value_end].to_string()
+                }
+                Some((i, _)) => {
+                    let mut value_end = input.len();
+                    while let Some(&(j, c)) = chars.peek() {
+                        if c.is_whitespace() {
+                            value_end = j;
+                            break;
+                        }
+                        chars.next();
+                    }
+                    input[i..value_end].to_string()
+                }
+                None => String::new(),
+            }
+        } else {
+            String::new()
+        };
+
+        if let Some(id) = key.strip_prefix('#') {
+            attributes.push(("id".to_string(), id.to_string()));
+        } else if let Some(class) = key.strip_prefix('.') {
+            classes.push(class);
+        } else if !key.is_empty() {
+            attributes.push((key.to_string(), value));
+        }
+    }
+
+    if !classes.is_empty() {
+        attributes.push(("className".to_string(), classes.join(" ")));
+    }
+    attributes
+}
+
+/// Render the opening JSX tag for a directive.
+fn opening_tag(component: &str, opener: &Opener<'_>) -> String {
+    let mut tag = format!("<{}", component);
+    let label = opener
+        .label
+        .filter(|label| !label.is_empty())
+        .map(|label| ("title", label));
+    let attributes = label.into_iter().chain(
+        opener
+            .attributes
+            .iter()
+            .map(|(key, value)| (key.as_str(), value.as_str())),
+    );
+
+    for (key, value) in attributes {
+        if !key
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        {
+            continue;
+        }
+        tag.push_str(&format!(" {}={{\"{}\"}}", key, escape_js_string(value)));
+    }
+
+    tag.push('>');
+    tag
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rewrite(source: &str) -> Option<String> {
+        rewrite_directives(source, &default_directive_components())
+    }
+
+    #[test]
+    fn test_parse_opener() {
+        let opener = parse_opener(":::warning[Heads up]{#old .a .b level=2}").unwrap();
+        assert_eq!(opener.colons, 3);
+        assert_eq!(opener.name, "warning");
+        assert_eq!(opener.label, Some("Heads up"));
+        assert_eq!(
+            opener.attributes,
+            vec![
+                ("id".to_string(), "old".to_string()),
+                ("level".to_string(), "2".to_string()),
+                ("className".to_string(), "a b".to_string()),
+            ]
+        );
+
+        assert!(parse_opener(":::").is_none());
+        assert!(parse_opener("::note").is_none());
+        assert!(parse_opener(":::note trailing").is_none());
+    }
+
+    #[test]
+    fn test_quoted_attribute_values() {
+        assert_eq!(
+            parse_attributes(r#"title="Say hi" data-x='y z'"#),
+            vec![
+                ("title".to_string(), "Say hi".to_string()),
+                ("data-x".to_string(), "y z".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_rewrite_basic_directive() {
+        let output = rewrite(":::note\nHello *world*\n:::\n").unwrap();
+        assert_eq!(output, "<Note>\nHello *world*\n</Note>\n");
+    }
+
+    #[test]
+    fn test_rewrite_label_becomes_title() {
+        let output = rewrite(":::tip[Pro \"tip\"]\nText\n:::\n").unwrap();
+        assert!(output.starts_with("<Tip title={\"Pro \\\"tip\\\"\"}>"));
+    }
+
+    #[test]
+    fn test_nested_directives() {
+        let output = rewrite("::::note\nOuter\n:::warning\nInner\n:::\n::::\n").unwrap();
+        assert_eq!(
+            output,
+            "<Note>\nOuter\n<Warning>\nInner\n</Warning>\n</Note>\n"
+        );
+    }
+
+    #[test]
+    fn test_rewrite_keeps_line_numbers() {
+        let source = "Intro.\n:::note[A]\nText\n\n  :::tip\n  Inner\n  :::\n:::\r\nAfter.\n";
+        let output = rewrite(source).unwrap();
+        assert_eq!(output.lines().count(), source.lines().count());
+        assert_eq!(output.lines().nth(5), Some("  Inner"));
+        assert!(output.contains("\n</Note>\r\nAfter."));
+    }
+
+    #[test]
+    fn test_unknown_and_fenced_directives_untouched() {
+        assert_eq!(rewrite(":::custom\nText\n:::\n"), None);
+        assert_eq!(rewrite("```md\n:::note\nText\n:::\n```\n"), None);
+        assert_eq!(
+            rewrite_directives(":::note\nText\n:::\n", &HashMap::new()),
+            None
+        );
+    }
+
+    #[test]
+    fn test_unclosed_directive_closed_at_end() {
+        let output = rewrite(":::danger\nCareful").unwrap();
+        assert_eq!(output, "<Danger>\nCareful\n</Danger>\n");
+    }
+}
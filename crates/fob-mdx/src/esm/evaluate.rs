@@ -0,0 +1,213 @@
+//! Static evaluation of exported constants
+//!
+//! Resolves `export const` declarations whose values can be computed without
+//! running JavaScript: literals, objects, arrays, template strings and
+//! references to `frontmatter` or earlier constants. Anything else (calls,
+//! imports, JSX) is skipped.
+
+use oxc_allocator::Allocator;
+use oxc_ast::ast::{
+    ArrayExpressionElement, BindingPatternKind, Declaration, Expression, ObjectPropertyKind,
+    PropertyKind, Statement, UnaryOperator, VariableDeclarationKind,
+};
+use oxc_parser::Parser;
+use oxc_span::SourceType;
+use serde_json::{Map, Number, Value};
+
+/// Evaluate the `export const` declarations of an ESM block.
+///
+/// `scope` holds the identifiers values may refer to; each evaluated
+/// constant is inserted into it and into the returned map.
+///
+/// ```rust
+/// use fob_mdx::esm::evaluate_exports;
+/// use serde_json::{Map, json};
+///
+/// let mut scope = Map::new();
+/// scope.insert("frontmatter".into(), json!({ "title": "Intro" }));
+/// let exports = evaluate_exports(
+///     "export const meta = { title: frontmatter.title, tags: ['a'] }",
+///     &mut scope,
+/// );
+/// assert_eq!(exports["meta"], json!({ "title": "Intro", "tags": ["a"] }));
+/// ```
+pub fn evaluate_exports(code: &str, scope: &mut Map<String, Value>) -> Map<String, Value> {
+    let allocator = Allocator::default();
+    let parsed = Parser::new(&allocator, code, SourceType::mjs()).parse();
+    let mut exports = Map::new();
+    if !parsed.errors.is_empty() {
+        return exports;
+    }
+
+    for statement in &parsed.program.body {
+        let Statement::ExportNamedDeclaration(export) = statement else {
+            continue;
+        };
+        let Some(Declaration::VariableDeclaration(declaration)) = &export.declaration else {
+            continue;
+        };
+        if declaration.kind != VariableDeclarationKind::Const {
+            continue;
+        }
+        for declarator in &declaration.declarations {
+            let BindingPatternKind::BindingIdentifier(id) = &declarator.id.kind else {
+                continue;
+            };
+            let Some(value) = declarator
+                .init
+                .as_ref()
+                .and_then(|init| evaluate(init, scope))
+            else {
+                continue;
+            };
+            scope.insert(id.name.to_string(), value.clone());
+            exports.insert(id.name.to_string(), value);
+        }
+    }
+    exports
+}
+
+/// JSON value of an expression, if it can be computed statically.
+fn evaluate(expression: &Expression, scope: &Map<String, Value>) -> Option<Value> {
+    match expression {
+        Expression::NullLiteral(_) => Some(Value::Null),
+        Expression::BooleanLiteral(literal) => Some(Value::Bool(literal.value)),
+        Expression::NumericLiteral(literal) => number(literal.value),
+        Expression::StringLiteral(literal) => Some(Value::String(literal.value.to_string())),
+        Expression::TemplateLiteral(template) => {
+            let mut text = String::new();
+            for (i, quasi) in template.quasis.iter().enumerate() {
+                text.push_str(quasi.value.cooked.as_ref()?);
+                if let Some(expression) = template.expressions.get(i) {
+                    text.push_str(&to_string(&evaluate(expression, scope)?)?);
+                }
+            }
+            Some(Value::String(text))
+        }
+        Expression::ParenthesizedExpression(inner) => evaluate(&inner.expression, scope),
+        Expression::UnaryExpression(unary) => {
+            let value = evaluate(&unary.argument, scope)?;
+            match unary.operator {
+                UnaryOperator::UnaryNegation => number(-value.as_f64()?),
+                UnaryOperator::UnaryPlus => number(value.as_f64()?),
+                UnaryOperator::LogicalNot => Some(Value::Bool(!truthy(&value))),
+                _ => None,
+            }
+        }
+        Expression::Identifier(identifier) => scope.get(identifier.name.as_str()).cloned(),
+        Expression::StaticMemberExpression(member) => {
+            let object = evaluate(&member.object, scope)?;
+            match object.get(member.property.name.as_str()) {
+                Some(value) => Some(value.clone()),
+                // Missing properties of known objects are `undefined`
+                None if member.optional || object.is_object() => Some(Value::Null),
+                None => None,
+            }
+        }
+        Expression::ArrayExpression(array) => array
+            .elements
+            .iter()
+            .map(|element| match element {
+                ArrayExpressionElement::SpreadElement(_) | ArrayExpressionElement::Elision(_) => {
+                    None
+                }
+                _ => evaluate(element.to_expression(), scope),
+            })
+            .collect::<Option<Vec<_>>>()
+            .map(Value::Array),
+        Expression::ObjectExpression(object) => {
+            let mut map = Map::new();
+            for property in &object.properties {
+                match property {
+                    ObjectPropertyKind::ObjectProperty(property)
+                        if property.kind == PropertyKind::Init && !property.method =>
+                    {
+                        let key = property.key.static_name()?;
+                        map.insert(key.to_string(), evaluate(&property.value, scope)?);
+                    }
+                    ObjectPropertyKind::SpreadProperty(spread) => {
+                        let Value::Object(spread) = evaluate(&spread.argument, scope)? else {
+                            return None;
+                        };
+                        map.extend(spread);
+                    }
+                    _ => return None,
+                }
+            }
+            Some(Value::Object(map))
+        }
+        _ => None,
+    }
+}
+
+/// JSON number, keeping integral values as integers (`NaN`/`Infinity` have none).
+fn number(value: f64) -> Option<Value> {
+    if value.fract() == 0.0 && value.abs() < 2f64.powi(53) {
+        return Some(Value::Number(Number::from(value as i64)));
+    }
+    Number::from_f64(value).map(Value::Number)
+}
+
+/// String conversion for template literal substitutions.
+fn to_string(value: &Value) -> Option<String> {
+    match value {
+        Value::String(text) => Some(text.clone()),
+        Value::Number(number) => Some(number.to_string()),
+        Value::Bool(value) => Some(value.to_string()),
+        Value::Null => Some("null".to_string()),
+        _ => None,
+    }
+}
+
+fn truthy(value: &Value) -> bool {
+    match value {
+        Value::Null => false,
+        Value::Bool(value) => *value,
+        Value::Number(number) => number.as_f64().is_some_and(|n| n != 0.0),
+        Value::String(text) => !text.is_empty(),
+        _ => true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn exports(code: &str) -> Value {
+        Value::Object(evaluate_exports(code, &mut Map::new()))
+    }
+
+    #[test]
+    fn test_literals() {
+        assert_eq!(
+            exports("export const a = 1, b = -2.5, c = 'x', d = true, e = null, f = `t${1}`"),
+            json!({ "a": 1, "b": -2.5, "c": "x", "d": true, "e": null, "f": "t1" })
+        );
+    }
+
+    #[test]
+    fn test_references() {
+        let mut scope = Map::new();
+        scope.insert("frontmatter".into(), json!({ "title": "Post" }));
+        let exports = evaluate_exports(
+            "export const base = { draft: false }\nexport const meta = { ...base, title: `${frontmatter.title}!`, 'x-y': [base.draft] }",
+            &mut scope,
+        );
+        assert_eq!(
+            exports["meta"],
+            json!({ "draft": false, "title": "Post!", "x-y": [false] })
+        );
+    }
+
+    #[test]
+    fn test_dynamic_values_are_skipped() {
+        assert_eq!(
+            exports(
+                "export const a = Date.now()\nexport let b = 1\nexport const c = unknown\nexport const d = { f() {} }\nexport const e = 2"
+            ),
+            json!({ "e": 2 })
+        );
+        assert_eq!(exports("export const = broken"), json!({}));
+    }
+}
@@ -3,8 +3,10 @@
 //! This module validates import/export statements in MDX files to ensure they're
 //! syntactically correct before passing them through to the JavaScript compiler.
 
+mod evaluate;
 mod parser;
 mod validator;
 
+pub use evaluate::evaluate_exports;
 pub use parser::{extract_imported_names, get_default_export_name, has_named_exports, is_reexport};
 pub use validator::validate_esm_syntax;
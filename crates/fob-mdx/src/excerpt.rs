@@ -0,0 +1,161 @@
+//! Excerpt extraction
+//!
+//! An excerpt is the plain-text content of a document up to an explicit
+//! separator line (`<!-- more -->` by default) or, when the document has no
+//! separator, its first N paragraphs. Markdown syntax is stripped; blocks are
+//! separated by blank lines.
+//!
+//! MDX does not support HTML comments, so the separator line is blanked out of
+//! the source before parsing. It is replaced with spaces rather than removed
+//! so that byte offsets in error messages still point at the original source.
+
+use crate::utils::plain_text;
+use markdown::mdast::Node;
+
+/// Default excerpt separator.
+pub const DEFAULT_EXCERPT_SEPARATOR: &str = "<!-- more -->";
+
+/// Find the byte offset of the first line consisting only of `separator`.
+///
+/// Lines inside fenced code blocks are ignored.
+pub(crate) fn find_separator(source: &str, separator: &str) -> Option<usize> {
+    let separator = separator.trim();
+    if separator.is_empty() {
+        return None;
+    }
+
+    let mut fence: Option<&str> = None;
+    let mut offset = 0;
+
+    for line in source.split_inclusive('\n') {
+        let trimmed = line.trim();
+        let line_fence = ["```", "~~~"]
+            .into_iter()
+            .find(|marker| trimmed.starts_with(marker));
+
+        match (fence, line_fence) {
+            (None, Some(marker)) => fence = Some(marker),
+            (Some(open), Some(marker)) if open == marker => fence = None,
+            (None, None) if trimmed == separator => return Some(offset),
+            _ => {}
+        }
+
+        offset += line.len();
+    }
+
+    None
+}
+
+/// Replace the separator at `offset` with spaces, keeping all other offsets intact.
+pub(crate) fn blank_separator(source: &str, offset: usize) -> String {
+    let end = source[offset..]
+        .find('\n')
+        .map_or(source.len(), |i| offset + i);
+
+    let mut blanked = String::with_capacity(source.len());
+    blanked.push_str(&source[..offset]);
+    blanked.push_str(&" ".repeat(end - offset));
+    blanked.push_str(&source[end..]);
+    blanked
+}
+
+/// Extract the excerpt from a (frontmatter-free) document root.
+///
+/// With a separator offset, every content block that starts before it is
+/// included. Otherwise the first `paragraphs` top-level paragraphs are used.
+/// Returns `None` if the resulting text is empty.
+pub(crate) fn extract_excerpt(
+    root: &Node,
+    separator_offset: Option<usize>,
+    paragraphs: usize,
+) -> Option<String> {
+    let children = root.children()?;
+
+    let blocks: Vec<String> = match separator_offset {
+        Some(offset) => children
+            .iter()
+            .take_while(|node| {
+                node.position()
+                    .is_some_and(|position| position.start.offset < offset)
+            })
+            .map(plain_text)
+            .collect(),
+        None => children
+            .iter()
+            .filter(|node| matches!(node, Node::Paragraph(_)))
+            .take(paragraphs)
+            .map(plain_text)
+            .collect(),
+    };
+
+    let excerpt = blocks
+        .into_iter()
+        .map(|block| block.trim().to_string())
+        .filter(|block| !block.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n\n");
+
+    (!excerpt.is_empty()).then_some(excerpt)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(source: &str) -> Node {
+        markdown::to_mdast(source, &markdown::ParseOptions::mdx()).unwrap()
+    }
+
+    #[test]
+    fn test_find_separator() {
+        let source = "Intro\n\n<!-- more -->\n\nRest";
+        assert_eq!(find_separator(source, DEFAULT_EXCERPT_SEPARATOR), Some(7));
+        assert_eq!(
+            find_separator("No marker here", DEFAULT_EXCERPT_SEPARATOR),
+            None
+        );
+    }
+
+    #[test]
+    fn test_find_separator_ignores_code_fences() {
+        let source = "```html\n<!-- more -->\n```\n\nText";
+        assert_eq!(find_separator(source, DEFAULT_EXCERPT_SEPARATOR), None);
+    }
+
+    #[test]
+    fn test_blank_separator_keeps_offsets() {
+        let source = "Intro\n<!-- more -->\nRest";
+        let blanked = blank_separator(source, 6);
+        assert_eq!(blanked.len(), source.len());
+        assert_eq!(blanked.find("Rest"), source.find("Rest"));
+        assert!(!blanked.contains("more"));
+    }
+
+    #[test]
+    fn test_first_paragraphs_strip_markdown() {
+        let root = parse("# Title\n\nSome **bold** and [a link](/x).\n\nSecond `code`.\n\nThird.");
+        assert_eq!(
+            extract_excerpt(&root, None, 2).as_deref(),
+            Some("Some bold and a link.\n\nSecond code.")
+        );
+    }
+
+    #[test]
+    fn test_excerpt_up_to_separator() {
+        let source = "# Title\n\nIntro text.\n\n- one\n- two\n\n<!-- more -->\n\nHidden.";
+        let offset = find_separator(source, DEFAULT_EXCERPT_SEPARATOR).unwrap();
+        let root = parse(&blank_separator(source, offset));
+
+        assert_eq!(
+            extract_excerpt(&root, Some(offset), 1).as_deref(),
+            Some("Title\n\nIntro text.\n\none\ntwo")
+        );
+    }
+
+    #[test]
+    fn test_empty_excerpt_is_none() {
+        let root = parse("{props.title}\n\n```js\ncode\n```");
+        assert_eq!(extract_excerpt(&root, None, 1), None);
+        assert_eq!(extract_excerpt(&parse("Text"), None, 0), None);
+    }
+}
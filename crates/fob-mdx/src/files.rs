@@ -0,0 +1,37 @@
+//! Synchronous file access for compile-time features
+//!
+//! Compilation is synchronous, so features that read other files (includes,
+//! image dimensions) go through [`FileSource`] instead of an async runtime.
+//! Bundler integrations preload what a document needs into a `HashMap`.
+
+use std::collections::HashMap;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Reads files referenced by a document
+///
+/// Implemented by [`FsFileSource`] for the local file system and by
+/// `HashMap<PathBuf, Vec<u8>>` for in-memory files (WASM, tests, or files
+/// preloaded through an async runtime).
+pub trait FileSource: Send + Sync {
+    /// Read the full content of a file
+    fn read(&self, path: &Path) -> io::Result<Vec<u8>>;
+}
+
+/// [`FileSource`] reading from the local file system
+#[derive(Debug, Default, Clone, Copy)]
+pub struct FsFileSource;
+
+impl FileSource for FsFileSource {
+    fn read(&self, path: &Path) -> io::Result<Vec<u8>> {
+        std::fs::read(path)
+    }
+}
+
+impl FileSource for HashMap<PathBuf, Vec<u8>> {
+    fn read(&self, path: &Path) -> io::Result<Vec<u8>> {
+        self.get(path)
+            .cloned()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, path.display().to_string()))
+    }
+}
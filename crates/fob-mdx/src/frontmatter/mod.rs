@@ -6,5 +6,5 @@
 mod parser;
 mod types;
 
-pub use parser::extract_frontmatter;
+pub use parser::{extract_frontmatter, parse_frontmatter};
 pub use types::{FrontmatterData, FrontmatterFormat};
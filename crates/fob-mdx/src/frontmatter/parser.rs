@@ -97,3 +97,20 @@ pub fn extract_frontmatter(root: &Node) -> Result<(Node, Option<FrontmatterData>
 
     Ok((cleaned_root, frontmatter))
 }
+
+/// Parse only the frontmatter of an MDX or Markdown source
+///
+/// The document is parsed as plain Markdown, which is enough to find the
+/// frontmatter block without compiling the document. Bundler integrations
+/// use this to read metadata (layouts, content collections) up front.
+///
+/// # Errors
+///
+/// Returns an error if the frontmatter is not valid YAML or TOML.
+pub fn parse_frontmatter(source: &str) -> Result<Option<FrontmatterData>> {
+    let mut parse_options = markdown::ParseOptions::default();
+    parse_options.constructs.frontmatter = true;
+    let root = markdown::to_mdast(source, &parse_options)
+        .map_err(|e| anyhow!("Failed to parse frontmatter: {}", e))?;
+    Ok(extract_frontmatter(&root)?.1)
+}
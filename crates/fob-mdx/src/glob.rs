@@ -0,0 +1,121 @@
+//! Minimal glob patterns for selecting content files
+//!
+//! Patterns match `/`-separated paths relative to the project root:
+//!
+//! - `*` matches any characters within a path segment
+//! - `?` matches a single character within a path segment
+//! - `**` as a whole segment matches any number of segments, including none
+
+use regex::Regex;
+
+/// A compiled glob pattern
+#[derive(Debug, Clone)]
+pub(crate) struct Glob {
+    pattern: String,
+    regex: Regex,
+}
+
+impl Glob {
+    /// Compile a pattern; a leading `./` is ignored.
+    pub(crate) fn new(pattern: &str) -> Result<Self, regex::Error> {
+        let pattern = pattern.trim_start_matches("./");
+        let segments: Vec<&str> = pattern.split('/').collect();
+
+        let mut source = String::from("^");
+        for (index, segment) in segments.iter().enumerate() {
+            let last = index + 1 == segments.len();
+            if *segment == "**" {
+                source.push_str(if last { ".*" } else { "(?:[^/]*/)*" });
+                continue;
+            }
+            for c in segment.chars() {
+                match c {
+                    '*' => source.push_str("[^/]*"),
+                    '?' => source.push_str("[^/]"),
+                    c => source.push_str(&regex::escape(c.encode_utf8(&mut [0; 4]))),
+                }
+            }
+            if !last {
+                source.push('/');
+            }
+        }
+        source.push('$');
+
+        Ok(Self {
+            pattern: pattern.to_string(),
+            regex: Regex::new(&source)?,
+        })
+    }
+
+    /// Whether `path` (relative, `/`-separated) matches the pattern
+    pub(crate) fn is_match(&self, path: &str) -> bool {
+        self.regex.is_match(path)
+    }
+
+    /// Leading directories without wildcards, e.g. `blog/posts` for
+    /// `blog/posts/**/*.mdx`; every match is inside this directory.
+    pub(crate) fn base(&self) -> &str {
+        let segments: Vec<&str> = self.pattern.split('/').collect();
+        let literal = segments[..segments.len() - 1]
+            .iter()
+            .take_while(|segment| !segment.contains(['*', '?']))
+            .map(|segment| segment.len() + 1)
+            .sum::<usize>();
+        self.pattern[..literal].trim_end_matches('/')
+    }
+
+    /// Number of directory levels below [`Glob::base`] a match can be in,
+    /// or `None` if the pattern has a `**` segment.
+    pub(crate) fn max_depth(&self) -> Option<usize> {
+        let base = self.base();
+        let rest = self.pattern[base.len()..].trim_start_matches('/');
+        if rest.split('/').any(|segment| segment == "**") {
+            None
+        } else {
+            Some(rest.split('/').count() - 1)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_within_segment() {
+        let glob = Glob::new("blog/*.mdx").unwrap();
+        assert!(glob.is_match("blog/hello.mdx"));
+        assert!(!glob.is_match("blog/2024/hello.mdx"));
+        assert!(!glob.is_match("blog/hello.md"));
+        assert!(!glob.is_match("docs/hello.mdx"));
+        assert_eq!(glob.base(), "blog");
+        assert_eq!(glob.max_depth(), Some(0));
+    }
+
+    #[test]
+    fn double_star_matches_any_depth() {
+        let glob = Glob::new("./docs/**/*.mdx").unwrap();
+        assert!(glob.is_match("docs/intro.mdx"));
+        assert!(glob.is_match("docs/guides/setup/install.mdx"));
+        assert!(!glob.is_match("docs.mdx"));
+        assert_eq!(glob.base(), "docs");
+        assert_eq!(glob.max_depth(), None);
+
+        let all = Glob::new("**").unwrap();
+        assert!(all.is_match("a/b/c.mdx"));
+        assert_eq!(all.base(), "");
+    }
+
+    #[test]
+    fn escapes_literal_characters() {
+        let glob = Glob::new("content/v1.0/?.mdx").unwrap();
+        assert!(glob.is_match("content/v1.0/a.mdx"));
+        assert!(!glob.is_match("content/v1x0/a.mdx"));
+        assert!(!glob.is_match("content/v1.0/ab.mdx"));
+        assert_eq!(glob.base(), "content/v1.0");
+
+        let nested = Glob::new("content/*/posts/*.mdx").unwrap();
+        assert_eq!(nested.base(), "content");
+        assert_eq!(nested.max_depth(), Some(2));
+    }
+}
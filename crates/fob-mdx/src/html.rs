@@ -0,0 +1,351 @@
+//! Static HTML rendering
+//!
+//! Renders MDX straight to an HTML string for documents that don't need a
+//! JavaScript runtime: markdown, plain HTML elements and fragments. The
+//! structured content tree is serialized directly, so the output matches what
+//! the compiled JSX renders with the default components.
+//!
+//! Only literal expressions are evaluated (`{"text"}`, `{42}`, comments) plus
+//! `frontmatter.*` lookups. Anything needing JavaScript, such as components
+//! or arbitrary expressions, is reported as an error instead of being dropped
+//! silently.
+
+use crate::content::{ContentNode, PropValue};
+use crate::error::MdxError;
+use crate::frontmatter::FrontmatterData;
+use crate::{MdxCompileOptions, compile};
+use serde_json::Value as JsonValue;
+use std::collections::BTreeMap;
+
+/// Result of rendering MDX to static HTML
+#[derive(Debug, Clone)]
+pub struct MdxHtmlResult {
+    /// Rendered HTML fragment (no `<html>`/`<body>` wrapper)
+    pub html: String,
+    pub frontmatter: Option<FrontmatterData>,
+    /// Plain-text excerpt (see `MdxCompileResult::excerpt`)
+    pub excerpt: Option<String>,
+    pub word_count: usize,
+    pub reading_time_minutes: u32,
+}
+
+/// Elements that never have children or a closing tag
+const VOID_ELEMENTS: &[&str] = &[
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "source", "track",
+    "wbr",
+];
+
+/// Compile MDX and render it to static HTML.
+///
+/// Uses the same options as [`compile`]; `structured_content` is always
+/// enabled and the JSX-specific options have no effect on the HTML.
+///
+/// # Errors
+///
+/// Fails with the compilation error, or if the document uses a component or
+/// an expression that can't be evaluated without JavaScript.
+pub fn render_html(
+    source: &str,
+    mut options: MdxCompileOptions,
+) -> Result<MdxHtmlResult, Box<MdxError>> {
+    options.structured_content = true;
+    let filepath = options.filepath.clone();
+    let result = compile(source, options)?;
+
+    let content = result
+        .content
+        .expect("structured content is enabled for HTML rendering");
+    let frontmatter = result.frontmatter.as_ref().map(|fm| &fm.data);
+    let mut html = String::with_capacity(source.len());
+    HtmlRenderer { frontmatter }
+        .node(&content, &mut html)
+        .map_err(|message| {
+            let mut err = MdxError::new(message).with_suggestion(
+                "Static HTML rendering only supports markdown, HTML elements, literal expressions and `frontmatter` lookups. Use `compile` and a JSX renderer for interactive content.",
+            );
+            if let Some(filepath) = filepath {
+                err = err.with_file(filepath);
+            }
+            Box::new(err)
+        })?;
+
+    Ok(MdxHtmlResult {
+        html,
+        frontmatter: result.frontmatter,
+        excerpt: result.excerpt,
+        word_count: result.word_count,
+        reading_time_minutes: result.reading_time_minutes,
+    })
+}
+
+struct HtmlRenderer<'a> {
+    frontmatter: Option<&'a JsonValue>,
+}
+
+impl HtmlRenderer<'_> {
+    fn node(&self, node: &ContentNode, out: &mut String) -> Result<(), String> {
+        match node {
+            ContentNode::Root { children } => self.children(children, out)?,
+            ContentNode::Element {
+                tag_name,
+                properties,
+                children,
+            } => self.element(tag_name, properties, children, out)?,
+            ContentNode::Component {
+                name: None,
+                children,
+                ..
+            } => self.children(children, out)?,
+            ContentNode::Component {
+                name: Some(name), ..
+            } => {
+                return Err(format!(
+                    "Component <{}> can't be rendered to static HTML",
+                    name
+                ));
+            }
+            ContentNode::Text { value } => escape_text(value, out),
+            // Like JSX, booleans and null render nothing
+            ContentNode::Expression { value } => match self.evaluate(value)? {
+                None
+                | Some(Literal::Prop(PropValue::Bool(_)))
+                | Some(Literal::Json(JsonValue::Bool(_))) => {}
+                Some(value) => escape_text(&value.to_text(), out),
+            },
+            ContentNode::Raw { value } => out.push_str(value),
+        }
+        Ok(())
+    }
+
+    fn children(&self, children: &[ContentNode], out: &mut String) -> Result<(), String> {
+        children.iter().try_for_each(|child| self.node(child, out))
+    }
+
+    fn element(
+        &self,
+        tag: &str,
+        properties: &BTreeMap<String, PropValue>,
+        children: &[ContentNode],
+        out: &mut String,
+    ) -> Result<(), String> {
+        out.push('<');
+        out.push_str(tag);
+        for (name, value) in properties {
+            let value = match value {
+                PropValue::Expression { expression } => self.evaluate(expression)?,
+                literal => Some(Literal::Prop(literal.clone())),
+            };
+            self.attribute(tag, name, value, out)?;
+        }
+        out.push('>');
+
+        if VOID_ELEMENTS.contains(&tag) {
+            return Ok(());
+        }
+        self.children(children, out)?;
+        out.push_str("</");
+        out.push_str(tag);
+        out.push('>');
+        Ok(())
+    }
+
+    fn attribute(
+        &self,
+        tag: &str,
+        name: &str,
+        value: Option<Literal>,
+        out: &mut String,
+    ) -> Result<(), String> {
+        // Code fence meta is a prop for custom `code` components, not an attribute
+        if name == "metastring" {
+            return Ok(());
+        }
+        if name == "..." {
+            return Err(format!(
+                "Spread attributes on <{}> can't be rendered to static HTML",
+                tag
+            ));
+        }
+        let name = match name {
+            "className" => "class",
+            "htmlFor" => "for",
+            other => other,
+        };
+
+        match value {
+            None | Some(Literal::Prop(PropValue::Bool(false))) => {}
+            Some(Literal::Prop(PropValue::Bool(true))) => {
+                out.push(' ');
+                out.push_str(name);
+            }
+            Some(value) => {
+                out.push(' ');
+                out.push_str(name);
+                out.push_str("=\"");
+                escape_attribute(&value.to_text(), out);
+                out.push('"');
+            }
+        }
+        Ok(())
+    }
+
+    /// Evaluate a literal or `frontmatter.*` expression; `None` renders nothing.
+    fn evaluate(&self, expression: &str) -> Result<Option<Literal>, String> {
+        let expression = strip_comments(expression);
+        let expression = expression.trim();
+
+        if expression.is_empty() || expression == "null" || expression == "undefined" {
+            return Ok(None);
+        }
+        if let Some(path) = expression.strip_prefix("frontmatter.") {
+            let value = self
+                .frontmatter
+                .and_then(|fm| path.split('.').try_fold(fm, |value, key| value.get(key)));
+            return Ok(value.cloned().map(Literal::Json));
+        }
+        match expression {
+            "true" => return Ok(Some(Literal::Prop(PropValue::Bool(true)))),
+            "false" => return Ok(Some(Literal::Prop(PropValue::Bool(false)))),
+            _ => {}
+        }
+        if let Ok(number) = expression.parse::<i64>() {
+            return Ok(Some(Literal::Json(number.into())));
+        }
+        if let Some(number) = expression
+            .parse::<f64>()
+            .ok()
+            .filter(|n| n.is_finite())
+            .and_then(serde_json::Number::from_f64)
+        {
+            return Ok(Some(Literal::Json(JsonValue::Number(number))));
+        }
+        if let Some(string) = parse_string_literal(expression) {
+            return Ok(Some(Literal::Prop(PropValue::String(string))));
+        }
+        Err(format!(
+            "Expression {{{}}} can't be evaluated without JavaScript",
+            expression
+        ))
+    }
+}
+
+/// Evaluated expression or literal property value
+enum Literal {
+    Prop(PropValue),
+    Json(JsonValue),
+}
+
+impl Literal {
+    fn to_text(&self) -> String {
+        match self {
+            Literal::Prop(PropValue::Bool(value)) => value.to_string(),
+            Literal::Prop(PropValue::Number(value)) => value.to_string(),
+            Literal::Prop(PropValue::String(value)) => value.clone(),
+            Literal::Prop(PropValue::Expression { expression }) => expression.clone(),
+            Literal::Json(JsonValue::String(value)) => value.clone(),
+            Literal::Json(JsonValue::Null) => String::new(),
+            Literal::Json(JsonValue::Array(values)) => values
+                .iter()
+                .map(|value| Literal::Json(value.clone()).to_text())
+                .collect::<Vec<_>>()
+                .join(","),
+            Literal::Json(value) => value.to_string(),
+        }
+    }
+}
+
+/// Remove `/* ... */` comments (JSX comments are written as `{/* ... */}`).
+fn strip_comments(expression: &str) -> String {
+    let mut result = String::new();
+    let mut rest = expression;
+    while let Some(start) = rest.find("/*") {
+        result.push_str(&rest[..start]);
+        match rest[start + 2..].find("*/") {
+            Some(end) => rest = &rest[start + 2 + end + 2..],
+            None => return result,
+        }
+    }
+    result.push_str(rest);
+    result
+}
+
+/// Parse a single- or double-quoted string literal with simple escapes.
+fn parse_string_literal(expression: &str) -> Option<String> {
+    let quote = expression
+        .chars()
+        .next()
+        .filter(|c| matches!(c, '"' | '\''))?;
+    let inner = expression.strip_prefix(quote)?.strip_suffix(quote)?;
+
+    let mut value = String::with_capacity(inner.len());
+    let mut chars = inner.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => value.push(match chars.next()? {
+                'n' => '\n',
+                't' => '\t',
+                'r' => '\r',
+                other => other,
+            }),
+            c if c == quote => return None,
+            c => value.push(c),
+        }
+    }
+    Some(value)
+}
+
+fn escape_text(value: &str, out: &mut String) {
+    for c in value.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            c => out.push(c),
+        }
+    }
+}
+
+fn escape_attribute(value: &str, out: &mut String) {
+    for c in value.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '"' => out.push_str("&quot;"),
+            '<' => out.push_str("&lt;"),
+            c => out.push(c),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_strip_comments() {
+        assert_eq!(strip_comments("/* note */"), "");
+        assert_eq!(strip_comments("'a' /* b */"), "'a' ");
+        assert_eq!(strip_comments("/* unterminated"), "");
+    }
+
+    #[test]
+    fn test_parse_string_literal() {
+        assert_eq!(
+            parse_string_literal(r#""a \"b\"""#).as_deref(),
+            Some("a \"b\"")
+        );
+        assert_eq!(parse_string_literal("'it\\'s'").as_deref(), Some("it's"));
+        assert_eq!(parse_string_literal("'a' + 'b'"), None);
+        assert_eq!(parse_string_literal("name"), None);
+    }
+
+    #[test]
+    fn test_escaping() {
+        let mut out = String::new();
+        escape_text("<a & b>", &mut out);
+        assert_eq!(out, "&lt;a &amp; b&gt;");
+
+        let mut out = String::new();
+        escape_attribute("say \"hi\"", &mut out);
+        assert_eq!(out, "say &quot;hi&quot;");
+    }
+}
@@ -0,0 +1,386 @@
+//! Compile-time includes for shared MDX fragments
+//!
+//! A line consisting only of an include is replaced by the content of the
+//! referenced file before parsing:
+//!
+//! ```text
+//! <Include src="./shared/warning.mdx" />
+//! {/* include: ./shared/warning.mdx */}
+//! ```
+//!
+//! Paths resolve against the directory of the file containing the include,
+//! and included files may include others. Their frontmatter is dropped.
+//! Lines inside fenced code blocks are never treated as includes.
+
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, LazyLock};
+
+use bon::Builder;
+use path_clean::PathClean;
+use regex::Regex;
+
+use crate::error::MdxError;
+use crate::files::{FileSource, FsFileSource};
+
+/// Default maximum nesting of includes
+pub const DEFAULT_MAX_INCLUDE_DEPTH: usize = 16;
+
+/// Settings for expanding includes
+///
+/// ```rust
+/// use fob_mdx::{IncludeOptions, MdxCompileOptions};
+///
+/// let options = MdxCompileOptions::builder()
+///     .filepath("docs/intro.mdx")
+///     .include(IncludeOptions::builder().build())
+///     .build();
+/// ```
+#[derive(Clone, Builder)]
+pub struct IncludeOptions {
+    /// Where included files are read from (default: the file system)
+    #[builder(default = Arc::new(FsFileSource))]
+    pub source: Arc<dyn FileSource>,
+
+    /// Directory includes resolve against when the document has no
+    /// `filepath`. Defaults to the current directory.
+    #[builder(into)]
+    pub base_dir: Option<PathBuf>,
+
+    /// Maximum nesting of includes (default 16)
+    #[builder(default = DEFAULT_MAX_INCLUDE_DEPTH)]
+    pub max_depth: usize,
+}
+
+impl IncludeOptions {
+    /// Directory the includes of the compiled document resolve against
+    pub(crate) fn root_dir(&self, filepath: Option<&str>) -> PathBuf {
+        filepath
+            .and_then(|filepath| Path::new(filepath).parent())
+            .or(self.base_dir.as_deref())
+            .unwrap_or(Path::new(""))
+            .to_path_buf()
+    }
+}
+
+impl std::fmt::Debug for IncludeOptions {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("IncludeOptions")
+            .field("base_dir", &self.base_dir)
+            .field("max_depth", &self.max_depth)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Source with includes expanded
+#[derive(Debug)]
+pub(crate) struct Expanded {
+    /// Expanded source, or `None` if there was nothing to include
+    pub source: Option<String>,
+    /// Included files, in the order they were first included
+    pub files: Vec<PathBuf>,
+}
+
+/// Replace include lines with the content of the referenced files.
+pub(crate) fn expand_includes(
+    source: &str,
+    filepath: Option<&str>,
+    options: &IncludeOptions,
+) -> Result<Expanded, Box<MdxError>> {
+    let mut expander = Expander {
+        options,
+        stack: filepath
+            .map(|filepath| vec![Path::new(filepath).clean()])
+            .unwrap_or_default(),
+        depth: 0,
+        files: Vec::new(),
+    };
+    let source = expander.expand(source, &options.root_dir(filepath))?;
+    Ok(Expanded {
+        source,
+        files: expander.files,
+    })
+}
+
+/// Include targets of a source, as written, in document order.
+#[cfg_attr(not(feature = "bundler"), allow(dead_code))]
+pub(crate) fn include_targets(source: &str) -> Vec<&str> {
+    let mut fence: Option<&str> = None;
+    source
+        .lines()
+        .filter_map(|line| {
+            let trimmed = line.trim();
+            let line_fence = ["```", "~~~"]
+                .into_iter()
+                .find(|marker| trimmed.starts_with(marker));
+            match (fence, line_fence) {
+                (None, Some(marker)) => fence = Some(marker),
+                (Some(open_marker), Some(marker)) if open_marker == marker => fence = None,
+                _ => {}
+            }
+            if fence.is_some() || line_fence.is_some() {
+                return None;
+            }
+            include_target(trimmed)
+        })
+        .collect()
+}
+
+/// Resolve an include target relative to the including file's directory.
+pub(crate) fn resolve_include(dir: &Path, target: &str) -> PathBuf {
+    dir.join(target).clean()
+}
+
+struct Expander<'a> {
+    options: &'a IncludeOptions,
+    /// Files currently being expanded, outermost first
+    stack: Vec<PathBuf>,
+    depth: usize,
+    files: Vec<PathBuf>,
+}
+
+impl Expander<'_> {
+    fn expand(&mut self, source: &str, dir: &Path) -> Result<Option<String>, Box<MdxError>> {
+        if !source.contains("<Include") && !source.contains("include:") {
+            return Ok(None);
+        }
+
+        let mut output = String::with_capacity(source.len());
+        let mut fence: Option<&str> = None;
+        let mut expanded = false;
+
+        for line in source.split_inclusive('\n') {
+            let trimmed = line.trim();
+            let line_fence = ["```", "~~~"]
+                .into_iter()
+                .find(|marker| trimmed.starts_with(marker));
+
+            match (fence, line_fence) {
+                (None, Some(marker)) => fence = Some(marker),
+                (Some(open_marker), Some(marker)) if open_marker == marker => fence = None,
+                _ => {}
+            }
+            let target = match fence.or(line_fence) {
+                Some(_) => None,
+                None => include_target(trimmed),
+            };
+            let Some(target) = target else {
+                output.push_str(line);
+                continue;
+            };
+
+            let content = self.include(target, dir)?;
+            // Blank lines keep the fragment from merging with its neighbours
+            if !output.is_empty() {
+                output.push('\n');
+            }
+            output.push_str(content.trim_end());
+            output.push_str("\n\n");
+            expanded = true;
+        }
+
+        Ok(expanded.then_some(output))
+    }
+
+    /// Read and expand one included file.
+    fn include(&mut self, target: &str, dir: &Path) -> Result<String, Box<MdxError>> {
+        let path = resolve_include(dir, target);
+
+        if let Some(start) = self.stack.iter().position(|open| *open == path) {
+            let chain: Vec<String> = self.stack[start..]
+                .iter()
+                .chain(std::iter::once(&path))
+                .map(|path| path.display().to_string())
+                .collect();
+            return Err(Box::new(
+                MdxError::new(format!("Include cycle: {}", chain.join(" -> ")))
+                    .with_suggestion("Remove one of the includes to break the cycle"),
+            ));
+        }
+        if self.depth >= self.options.max_depth {
+            return Err(Box::new(MdxError::new(format!(
+                "Includes nested deeper than {} levels at '{}'",
+                self.options.max_depth,
+                path.display()
+            ))));
+        }
+
+        let bytes = self.options.source.read(&path).map_err(|e| {
+            Box::new(MdxError::new(format!(
+                "Failed to read include '{}' ({}): {}",
+                target,
+                path.display(),
+                e
+            )))
+        })?;
+        let content = String::from_utf8(bytes).map_err(|_| {
+            Box::new(MdxError::new(format!(
+                "Include '{}' contains invalid UTF-8",
+                path.display()
+            )))
+        })?;
+        if !self.files.contains(&path) {
+            self.files.push(path.clone());
+        }
+
+        let body = strip_frontmatter(&content);
+        let included_dir = path.parent().map(Path::to_path_buf).unwrap_or_default();
+        self.stack.push(path);
+        self.depth += 1;
+        let expanded = self.expand(body, &included_dir);
+        self.depth -= 1;
+        self.stack.pop();
+
+        Ok(expanded?.unwrap_or_else(|| body.to_string()))
+    }
+}
+
+static INCLUDE_ELEMENT: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"^<Include\s+src=(?:"([^"]*)"|'([^']*)'|\{"([^"]*)"\})\s*/>$"#).unwrap()
+});
+static INCLUDE_COMMENT: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^\{/\*\s*include:\s*(\S+)\s*\*/\}$").unwrap());
+
+/// Target of an include line (`<Include src="..." />` or `{/* include: ... */}`).
+fn include_target(line: &str) -> Option<&str> {
+    let captures = INCLUDE_ELEMENT
+        .captures(line)
+        .or_else(|| INCLUDE_COMMENT.captures(line))?;
+    let target = captures.iter().skip(1).flatten().next()?.as_str();
+    (!target.is_empty()).then_some(target)
+}
+
+/// Drop a leading `---` (YAML) or `+++` (TOML) frontmatter block.
+fn strip_frontmatter(content: &str) -> &str {
+    for fence in ["---", "+++"] {
+        let Some(rest) = content.strip_prefix(fence) else {
+            continue;
+        };
+        let Some(rest) = rest
+            .strip_prefix('\n')
+            .or_else(|| rest.strip_prefix("\r\n"))
+        else {
+            continue;
+        };
+        let mut offset = 0;
+        for line in rest.split_inclusive('\n') {
+            offset += line.len();
+            if line.trim_end() == fence {
+                return &rest[offset..];
+            }
+        }
+    }
+    content
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn options(files: &[(&str, &str)]) -> IncludeOptions {
+        let files: HashMap<PathBuf, Vec<u8>> = files
+            .iter()
+            .map(|(path, content)| (PathBuf::from(path), content.as_bytes().to_vec()))
+            .collect();
+        IncludeOptions::builder().source(Arc::new(files)).build()
+    }
+
+    #[test]
+    fn test_include_target() {
+        assert_eq!(
+            include_target(r#"<Include src="./a.mdx" />"#),
+            Some("./a.mdx")
+        );
+        assert_eq!(include_target("<Include src='b.mdx'/>"), Some("b.mdx"));
+        assert_eq!(
+            include_target(r#"<Include src={"c.mdx"} />"#),
+            Some("c.mdx")
+        );
+        assert_eq!(
+            include_target("{/* include: ../d.mdx */}"),
+            Some("../d.mdx")
+        );
+        assert_eq!(include_target(r#"<Include src="" />"#), None);
+        assert_eq!(include_target(r#"Text <Include src="a.mdx" />"#), None);
+        assert_eq!(include_target("{/* a comment */}"), None);
+    }
+
+    #[test]
+    fn test_nested_includes() {
+        let options = options(&[
+            (
+                "docs/shared/warning.mdx",
+                "---\ntitle: x\n---\n**Careful**\n{/* include: ./footer.mdx */}\n",
+            ),
+            ("docs/shared/footer.mdx", "Footer"),
+        ]);
+        let expanded = expand_includes(
+            "# Title\n<Include src=\"./shared/warning.mdx\" />\nAfter\n",
+            Some("docs/page.mdx"),
+            &options,
+        )
+        .unwrap();
+
+        assert_eq!(
+            expanded.source.as_deref(),
+            Some("# Title\n\n**Careful**\n\nFooter\n\nAfter\n")
+        );
+        assert_eq!(
+            expanded.files,
+            vec![
+                PathBuf::from("docs/shared/warning.mdx"),
+                PathBuf::from("docs/shared/footer.mdx"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_fenced_includes_untouched() {
+        let source = "```mdx\n<Include src=\"./a.mdx\" />\n```\n";
+        let expanded = expand_includes(source, None, &options(&[])).unwrap();
+        assert_eq!(expanded.source, None);
+        assert!(include_targets(source).is_empty());
+    }
+
+    #[test]
+    fn test_include_cycle() {
+        let options = options(&[
+            ("a.mdx", "<Include src=\"./b.mdx\" />"),
+            ("b.mdx", "<Include src=\"./a.mdx\" />"),
+        ]);
+        let err =
+            expand_includes("<Include src=\"./b.mdx\" />", Some("a.mdx"), &options).unwrap_err();
+        assert_eq!(err.message, "Include cycle: a.mdx -> b.mdx -> a.mdx");
+
+        let options = IncludeOptions {
+            max_depth: 1,
+            ..options
+        };
+        let err = expand_includes("<Include src=\"./a.mdx\" />", None, &options).unwrap_err();
+        assert!(
+            err.message.contains("nested deeper than 1"),
+            "{}",
+            err.message
+        );
+    }
+
+    #[test]
+    fn test_missing_include() {
+        let err =
+            expand_includes("<Include src=\"./gone.mdx\" />", None, &options(&[])).unwrap_err();
+        assert!(
+            err.message
+                .starts_with("Failed to read include './gone.mdx' (gone.mdx)")
+        );
+    }
+
+    #[test]
+    fn test_strip_frontmatter() {
+        assert_eq!(strip_frontmatter("---\na: 1\n---\nBody"), "Body");
+        assert_eq!(strip_frontmatter("+++\na = 1\n+++\nBody"), "Body");
+        assert_eq!(
+            strip_frontmatter("--- not frontmatter"),
+            "--- not frontmatter"
+        );
+    }
+}
@@ -8,23 +8,49 @@
 //! in simple data structures.
 
 pub mod codegen;
+#[cfg(feature = "bundler")]
+mod collection;
+pub mod content;
+mod directives;
 pub mod error;
 pub mod esm;
+mod excerpt;
+mod files;
 pub mod frontmatter;
+#[cfg(feature = "bundler")]
+mod glob;
+pub mod html;
+mod include;
 pub mod nodes;
 pub mod options;
 pub mod plugins;
+mod reading_time;
+mod sections;
+#[cfg(not(target_family = "wasm"))]
+pub mod site;
 pub mod utils;
 
 // Re-export public types
 pub use codegen::{mdast_to_jsx, mdast_to_jsx_with_options};
+pub use content::{ContentNode, PropValue};
+pub use directives::default_directive_components;
 pub use error::MdxError;
+pub use excerpt::DEFAULT_EXCERPT_SEPARATOR;
+pub use files::{FileSource, FsFileSource};
 pub use frontmatter::{FrontmatterData, FrontmatterFormat, extract_frontmatter};
+pub use html::{MdxHtmlResult, render_html};
+pub use include::{DEFAULT_MAX_INCLUDE_DEPTH, IncludeOptions};
 pub use options::MdxOptions;
-pub use plugins::MdxPlugin;
+pub use plugins::{
+    HeadingIdOptions, ImageDimensionOptions, ImageInfo, LinkRewrite, MdxAsset, MdxPlugin,
+    MdxPluginContext, Slugify,
+};
+pub use reading_time::DEFAULT_WORDS_PER_MINUTE;
+pub use sections::{DEFAULT_SECTION_LEVEL, MdxSection, compile_sections};
 
 use anyhow::{Result, anyhow};
 use bon::Builder;
+use std::collections::HashMap;
 
 /// Output format for compiled MDX code
 #[derive(Clone, Copy, Debug, Default, PartialEq)]
@@ -36,6 +62,100 @@ pub enum OutputFormat {
     FunctionBody,
 }
 
+/// JSX runtime that compiled MDX targets
+///
+/// React, Preact, Solid and Hono all ship an automatic runtime with the
+/// `jsx`/`jsxs`/`Fragment` signature the codegen emits, so for them the target
+/// only selects the default runtime module. Vue's children model differs
+/// (component children are slots), so for Vue the calls are routed through
+/// `h` and `Fragment` from `vue` instead.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum JsxTarget {
+    /// React 17+ automatic runtime
+    #[default]
+    React,
+    /// Preact automatic runtime
+    Preact,
+    /// Vue 3 `h()` vnode calls
+    Vue,
+    /// Solid hyperscript runtime (`solid-js/h`)
+    Solid,
+    /// Hono JSX runtime
+    Hono,
+}
+
+/// Default JSX runtime module (React automatic runtime)
+pub const DEFAULT_JSX_RUNTIME: &str = "react/jsx-runtime";
+
+impl JsxTarget {
+    /// Module the runtime helpers are imported from by default.
+    pub fn default_runtime(self) -> &'static str {
+        match self {
+            Self::React => DEFAULT_JSX_RUNTIME,
+            Self::Preact => "preact/jsx-runtime",
+            Self::Vue => "vue",
+            Self::Solid => "solid-js/h/jsx-runtime",
+            Self::Hono => "hono/jsx/jsx-runtime",
+        }
+    }
+
+    /// Resolve the runtime module, preferring an explicitly configured one.
+    ///
+    /// `jsx_runtime` defaults to the React runtime, so that value is treated
+    /// as "not configured" for other targets.
+    pub fn resolve_runtime(self, jsx_runtime: &str) -> &str {
+        if jsx_runtime == DEFAULT_JSX_RUNTIME {
+            self.default_runtime()
+        } else {
+            jsx_runtime
+        }
+    }
+}
+
+impl std::str::FromStr for JsxTarget {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "react" => Ok(Self::React),
+            "preact" => Ok(Self::Preact),
+            "vue" => Ok(Self::Vue),
+            "solid" => Ok(Self::Solid),
+            "hono" => Ok(Self::Hono),
+            other => Err(format!(
+                "Unknown JSX target '{}' (expected react, preact, vue, solid or hono)",
+                other
+            )),
+        }
+    }
+}
+
+/// How math nodes are rendered
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum MathOutput {
+    /// `<span className="math math-inline">` with the TeX source, for a
+    /// client-side library such as KaTeX or MathJax
+    #[default]
+    Tex,
+    /// MathML rendered at compile time; no client JavaScript needed
+    MathMl,
+}
+
+impl std::str::FromStr for MathOutput {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "tex" => Ok(Self::Tex),
+            "mathml" => Ok(Self::MathMl),
+            other => Err(format!(
+                "Unknown math output '{}' (expected tex or mathml)",
+                other
+            )),
+        }
+    }
+}
+
 /// Options for MDX compilation
 #[derive(Builder)]
 pub struct MdxCompileOptions {
@@ -43,11 +163,40 @@ pub struct MdxCompileOptions {
     #[builder(into)]
     pub filepath: Option<String>,
 
+    /// Parse the source as plain Markdown instead of MDX (default false).
+    ///
+    /// ESM, JSX, expressions and directives are not recognized, so `<`, `{`
+    /// and `import` lines are ordinary text, and raw HTML is allowed. Raw
+    /// HTML is kept by [`render_html`] but dropped from the compiled JSX, as
+    /// in MDX's `md` format.
+    #[builder(default)]
+    pub markdown: bool,
+
     /// Enable GitHub Flavored Markdown (tables, strikethrough, task lists).
     /// Enabled by default. Set to `false` to disable.
+    ///
+    /// When enabled, the `gfm_*` flags turn individual extensions off.
     #[builder(default = true)]
     pub gfm: bool,
 
+    /// GFM tables (default true, requires `gfm`)
+    #[builder(default = true)]
+    pub gfm_tables: bool,
+
+    /// GFM `~~strikethrough~~` (default true, requires `gfm`)
+    #[builder(default = true)]
+    pub gfm_strikethrough: bool,
+
+    /// GFM `- [x]` task list items (default true, requires `gfm`)
+    #[builder(default = true)]
+    pub gfm_tasklist: bool,
+
+    /// GFM autolink literals such as bare `www.example.com` URLs
+    /// (default true, requires `gfm`). Literal URLs inside component content
+    /// are turned into links too, so MDX-heavy documents often disable this.
+    #[builder(default = true)]
+    pub gfm_autolink: bool,
+
     /// Enable footnote support.
     /// Enabled by default. Set to `false` to disable.
     #[builder(default = true)]
@@ -58,15 +207,54 @@ pub struct MdxCompileOptions {
     #[builder(default = true)]
     pub math: bool,
 
-    /// JSX runtime module path
-    #[builder(default = "react/jsx-runtime".to_string(), into)]
+    /// Rendering of math nodes (default TeX spans for a client library)
+    #[builder(default)]
+    pub math_output: MathOutput,
+
+    /// JSX runtime module path.
+    /// Left at the default, the module is chosen by `jsx_target`.
+    #[builder(default = DEFAULT_JSX_RUNTIME.to_string(), into)]
     pub jsx_runtime: String,
 
+    /// Framework the generated JSX calls target (default React)
+    #[builder(default)]
+    pub jsx_target: JsxTarget,
+
     /// Use default plugins (heading IDs, image optimization).
     /// Enabled by default. Set to `false` to disable.
     #[builder(default = true)]
     pub use_default_plugins: bool,
 
+    /// Add `id` attributes to headings (default: true, only applies with
+    /// default plugins enabled)
+    #[builder(default = true)]
+    pub default_heading_ids: bool,
+
+    /// Collect images and optimize image props (default: true, only applies
+    /// with default plugins enabled)
+    #[builder(default = true)]
+    pub default_image_optimization: bool,
+
+    /// Prefix and slug function for the heading ID default plugin
+    #[builder(default)]
+    pub heading_ids: HeadingIdOptions,
+
+    /// Rewrite relative links to `.md`/`.mdx` files, e.g. with the pattern
+    /// `/blog/{slug}` or a callback. Disabled by default.
+    #[builder(into)]
+    pub link_rewrite: Option<LinkRewrite>,
+
+    /// Read local images to add `width`/`height` props, formats and blur
+    /// placeholders to `MdxCompileResult::images`. Requires default plugins.
+    /// Disabled by default.
+    #[builder(into)]
+    pub image_dimensions: Option<ImageDimensionOptions>,
+
+    /// Expand `<Include src="..." />` and `{/* include: ... */}` lines with
+    /// the content of the referenced files. Disabled by default.
+    #[builder(into)]
+    pub include: Option<IncludeOptions>,
+
     /// Additional plugins to apply during compilation.
     /// These are applied AFTER default plugins (if enabled).
     #[builder(default)]
@@ -88,19 +276,89 @@ pub struct MdxCompileOptions {
     /// This follows the MDX v3 pattern used by Next.js and @mdx-js/react.
     #[builder(into)]
     pub provider_import_source: Option<String>,
+
+    /// Line marking the end of the excerpt (default `<!-- more -->`).
+    ///
+    /// The separator line is removed from the compiled output.
+    #[builder(default = DEFAULT_EXCERPT_SEPARATOR.to_string(), into)]
+    pub excerpt_separator: String,
+
+    /// Number of leading paragraphs used as the excerpt when the document
+    /// has no separator. Set to `0` to only produce excerpts from separators.
+    #[builder(default = 1)]
+    pub excerpt_paragraphs: usize,
+
+    /// Reading speed used to estimate `reading_time_minutes` (default 200).
+    #[builder(default = DEFAULT_WORDS_PER_MINUTE)]
+    pub words_per_minute: u32,
+
+    /// Container directive name → component mapping (empty by default).
+    ///
+    /// `:::note ... :::` becomes `<Note>...</Note>` when `note` maps to
+    /// `Note`. A `[label]` is passed as the `title` prop. Directives not in
+    /// the map are left as-is. [`default_directive_components`] maps the
+    /// common admonition names.
+    #[builder(default)]
+    pub directives: HashMap<String, String>,
+
+    /// Compile-time component mapping, baked into the output so no runtime
+    /// MDXProvider is needed for common substitutions.
+    ///
+    /// Keys are element or component names (`h1`, `img`, `Note`). Values are
+    /// either an identifier in scope (`Heading`) or `module#export`
+    /// (`./components.js#Heading`, `./image.js#default`), which adds an import.
+    /// Provider and `props.components` entries still take precedence.
+    #[builder(default)]
+    pub components: HashMap<String, String>,
+
+    /// Module whose default export wraps the document (e.g.
+    /// `/site/layouts/Post.jsx`). The layout receives the props passed to
+    /// `MDXContent`, the frontmatter as `frontmatter`, and the rendered
+    /// content as `children`. Not supported with the function-body output
+    /// format.
+    #[builder(into)]
+    pub layout: Option<String>,
+
+    /// Also return the document as a portable JSON-serializable tree
+    /// (`MdxCompileResult::content`). Disabled by default.
+    #[builder(default)]
+    pub structured_content: bool,
 }
 
 impl std::fmt::Debug for MdxCompileOptions {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("MdxCompileOptions")
             .field("filepath", &self.filepath)
+            .field("markdown", &self.markdown)
             .field("gfm", &self.gfm)
+            .field("gfm_tables", &self.gfm_tables)
+            .field("gfm_strikethrough", &self.gfm_strikethrough)
+            .field("gfm_tasklist", &self.gfm_tasklist)
+            .field("gfm_autolink", &self.gfm_autolink)
             .field("footnotes", &self.footnotes)
             .field("math", &self.math)
+            .field("math_output", &self.math_output)
             .field("jsx_runtime", &self.jsx_runtime)
+            .field("jsx_target", &self.jsx_target)
             .field("use_default_plugins", &self.use_default_plugins)
+            .field("default_heading_ids", &self.default_heading_ids)
+            .field(
+                "default_image_optimization",
+                &self.default_image_optimization,
+            )
+            .field("heading_ids", &self.heading_ids)
+            .field("link_rewrite", &self.link_rewrite)
+            .field("image_dimensions", &self.image_dimensions)
+            .field("include", &self.include)
             .field("output_format", &self.output_format)
             .field("provider_import_source", &self.provider_import_source)
+            .field("excerpt_separator", &self.excerpt_separator)
+            .field("excerpt_paragraphs", &self.excerpt_paragraphs)
+            .field("words_per_minute", &self.words_per_minute)
+            .field("directives", &self.directives)
+            .field("components", &self.components)
+            .field("layout", &self.layout)
+            .field("structured_content", &self.structured_content)
             .field("plugins_count", &self.plugins.len())
             .finish()
     }
@@ -133,11 +391,55 @@ impl MdxCompileOptions {
 pub struct MdxCompileResult {
     pub code: String,
     pub frontmatter: Option<FrontmatterData>,
-    pub images: Vec<String>,
+    /// Images in document order, with size and format when dimension
+    /// reading is enabled
+    pub images: Vec<ImageInfo>,
     pub named_exports: Vec<String>,
     pub reexports: Vec<String>,
     pub imports: Vec<String>,
     pub default_export: Option<String>,
+    /// Plain-text excerpt: content before the excerpt separator, or the
+    /// first paragraphs if there is none
+    pub excerpt: Option<String>,
+    /// Number of words in the document's text content (code excluded)
+    pub word_count: usize,
+    /// Estimated reading time in minutes, rounded up
+    pub reading_time_minutes: u32,
+    /// Outgoing link URLs after rewriting, in document order and without
+    /// duplicates (fragment-only links excluded)
+    pub links: Vec<String>,
+    /// Files pulled in through includes, for dependency tracking
+    pub includes: Vec<std::path::PathBuf>,
+    /// Files emitted by plugins
+    pub emitted_assets: Vec<MdxAsset>,
+    /// Post-plugin document tree for non-JS renderers
+    /// (only when `structured_content` is enabled)
+    pub content: Option<ContentNode>,
+}
+
+impl MdxCompileResult {
+    /// Frontmatter merged with the statically computable `export const`
+    /// values of the document (see [`esm::evaluate_exports`]).
+    ///
+    /// Exports take precedence over frontmatter fields of the same name.
+    /// Always a JSON object.
+    pub fn matter(&self) -> serde_json::Value {
+        let data = match self.frontmatter.as_ref().map(|fm| &fm.data) {
+            Some(serde_json::Value::Object(data)) => data.clone(),
+            _ => serde_json::Map::new(),
+        };
+        let mut scope = serde_json::Map::new();
+        scope.insert(
+            "frontmatter".to_string(),
+            serde_json::Value::Object(data.clone()),
+        );
+
+        let mut matter = data;
+        for code in &self.named_exports {
+            matter.extend(esm::evaluate_exports(code, &mut scope));
+        }
+        serde_json::Value::Object(matter)
+    }
 }
 
 /// Maximum allowed MDX source size (10MB) to prevent DoS attacks
@@ -149,29 +451,38 @@ pub fn compile(
     options: MdxCompileOptions,
 ) -> Result<MdxCompileResult, Box<MdxError>> {
     // Validate input size to prevent DoS
-    if source.len() > MAX_MDX_SIZE {
-        return Err(Box::new(MdxError::new(format!(
-            "MDX source exceeds maximum size of {} bytes ({} MB)",
-            MAX_MDX_SIZE,
-            MAX_MDX_SIZE / 1024 / 1024
-        ))));
-    }
+    let check_size = |source: &str| {
+        if source.len() > MAX_MDX_SIZE {
+            return Err(Box::new(MdxError::new(format!(
+                "MDX source exceeds maximum size of {} bytes ({} MB)",
+                MAX_MDX_SIZE,
+                MAX_MDX_SIZE / 1024 / 1024
+            ))));
+        }
+        Ok(())
+    };
+    check_size(source)?;
 
     // Set up markdown parser options
-    let mut parse_options = markdown::ParseOptions::mdx();
-
-    // Enable ESM parsing with OXC validation
-    parse_options.mdx_esm_parse = Some(Box::new(crate::esm::validate_esm_syntax));
+    let mut parse_options = if options.markdown {
+        // CommonMark, which includes raw HTML
+        markdown::ParseOptions::default()
+    } else {
+        let mut parse_options = markdown::ParseOptions::mdx();
+        // Enable ESM parsing with OXC validation
+        parse_options.mdx_esm_parse = Some(Box::new(crate::esm::validate_esm_syntax));
+        parse_options
+    };
 
     // Enable frontmatter parsing (YAML and TOML)
     parse_options.constructs.frontmatter = true;
 
     // Enable GFM features if requested
     if options.gfm {
-        parse_options.constructs.gfm_strikethrough = true;
-        parse_options.constructs.gfm_table = true;
-        parse_options.constructs.gfm_task_list_item = true;
-        parse_options.constructs.gfm_autolink_literal = true;
+        parse_options.constructs.gfm_strikethrough = options.gfm_strikethrough;
+        parse_options.constructs.gfm_table = options.gfm_tables;
+        parse_options.constructs.gfm_task_list_item = options.gfm_tasklist;
+        parse_options.constructs.gfm_autolink_literal = options.gfm_autolink;
     }
 
     // Enable footnotes if requested
@@ -185,9 +496,53 @@ pub fn compile(
         parse_options.constructs.math_flow = true;
     }
 
+    // Splice in included files so they get the same treatment as the document
+    let mut includes = Vec::new();
+    let with_includes = match &options.include {
+        Some(include_options) => {
+            let expanded =
+                include::expand_includes(source, options.filepath.as_deref(), include_options)
+                    .map_err(|e| match &options.filepath {
+                        Some(filepath) => Box::new(e.with_file(filepath.clone())),
+                        None => e,
+                    })?;
+            includes = expanded.files;
+            expanded.source
+        }
+        None => None,
+    };
+    let source = with_includes.as_deref().unwrap_or(source);
+    check_size(source)?;
+
+    // Rewrite container directives to JSX before anything depends on offsets
+    let with_directives = if options.markdown {
+        None
+    } else {
+        directives::rewrite_directives(source, &options.directives)
+    };
+    let source = with_directives.as_deref().unwrap_or(source);
+
+    // Blank out the excerpt separator; MDX cannot parse HTML comments
+    let separator_offset = excerpt::find_separator(source, &options.excerpt_separator);
+    let blanked;
+    let source = match separator_offset {
+        Some(offset) => {
+            blanked = excerpt::blank_separator(source, offset);
+            blanked.as_str()
+        }
+        None => source,
+    };
+
     // Parse MDX to markdown AST
     let mdast = markdown::to_mdast(source, &parse_options).map_err(|e| {
         let mut err = MdxError::parse_error(e.to_string());
+        if let Some(place) = e.place.as_deref() {
+            let point = match place {
+                markdown::message::Place::Point(point) => point,
+                markdown::message::Place::Position(position) => &position.start,
+            };
+            err = err.with_location(point.line, point.column);
+        }
         if let Some(filepath) = &options.filepath {
             err = err.with_file(filepath.clone());
         }
@@ -198,20 +553,46 @@ pub fn compile(
     let (cleaned_mdast, frontmatter) =
         extract_frontmatter(&mdast).map_err(|e| Box::new(MdxError::new(format!("{:#}", e))))?;
 
+    let excerpt =
+        excerpt::extract_excerpt(&cleaned_mdast, separator_offset, options.excerpt_paragraphs);
+    let word_count = reading_time::count_words(&utils::plain_text(&cleaned_mdast));
+    let reading_time_minutes =
+        reading_time::reading_time_minutes(word_count, options.words_per_minute);
+
     // Set up MDX conversion options with plugins and jsx_runtime
     let mut mdx_options = MdxOptions {
         plugins: Vec::new(),
         jsx_runtime: options.jsx_runtime.clone(),
+        jsx_target: options.jsx_target,
         output_format: options.output_format,
         frontmatter: frontmatter.clone(),
         provider_import_source: options.provider_import_source.clone(),
+        components: options.components.clone(),
+        layout: options.layout.clone(),
     };
 
     // Add default plugins first (if enabled)
-    if options.use_default_plugins {
-        mdx_options = mdx_options
-            .with_plugin(Box::new(plugins::HeadingIdPlugin::default()))
-            .with_plugin(Box::new(plugins::ImageOptimizationPlugin::default()));
+    let heading_ids = options.use_default_plugins && options.default_heading_ids;
+    if heading_ids {
+        mdx_options = mdx_options.with_plugin(Box::new(plugins::HeadingIdPlugin::with_options(
+            options.heading_ids.clone(),
+        )));
+    }
+    if options.use_default_plugins && options.default_image_optimization {
+        mdx_options = mdx_options.with_plugin(Box::new(image_plugin(&options)));
+    }
+
+    // Pre-render math before user plugins see the tree
+    if options.math && options.math_output == MathOutput::MathMl {
+        mdx_options = mdx_options.with_plugin(Box::new(plugins::MathMlPlugin::new()));
+    }
+
+    if let Some(rewrite) = options.link_rewrite.clone() {
+        let mut plugin = plugins::LinkRewritePlugin::new(rewrite);
+        if let Some(document) = options.filepath.as_deref().and_then(plugins::document_path) {
+            plugin = plugin.with_document(document);
+        }
+        mdx_options = mdx_options.with_plugin(Box::new(plugin));
     }
 
     // Add user's custom plugins (on top of defaults)
@@ -219,14 +600,37 @@ pub fn compile(
         mdx_options = mdx_options.with_plugin(plugin);
     }
 
-    // Convert mdast to JSX (applies plugins during conversion)
-    let jsx_code = mdast_to_jsx_with_options(&cleaned_mdast, &mdx_options).map_err(|e| {
+    // Run AST plugins, then convert to JSX (and structured content if requested)
+    let conversion_error = |e: anyhow::Error| {
         let mut err = MdxError::conversion_error(e.to_string());
         if let Some(filepath) = &options.filepath {
             err = err.with_file(filepath.clone());
         }
         Box::new(err)
-    })?;
+    };
+    let (transformed_mdast, _) =
+        codegen::transform_ast(&cleaned_mdast, &mdx_options).map_err(conversion_error)?;
+    let jsx_code = codegen::render_jsx(&transformed_mdast, frontmatter.as_ref(), &mdx_options)
+        .map_err(conversion_error)?;
+
+    // Collect files emitted by plugins from the final AST
+    let mut plugin_ctx = MdxPluginContext::new(options.filepath.clone());
+    for plugin in &mdx_options.plugins {
+        plugin
+            .emit_assets(&transformed_mdast, &mut plugin_ctx)
+            .map_err(|e| {
+                conversion_error(anyhow!(
+                    "Plugin '{}' failed while emitting assets: {:#}",
+                    plugin.name(),
+                    e
+                ))
+            })?;
+    }
+    let content = options.structured_content.then(|| {
+        let heading_ids = heading_ids
+            .then(|| plugins::HeadingIdPlugin::with_options(options.heading_ids.clone()));
+        content::to_content(&transformed_mdast, heading_ids.as_ref())
+    });
 
     // Extract collected images from ImageOptimizationPlugin
     let mut images = Vec::new();
@@ -251,9 +655,26 @@ pub fn compile(
         reexports: parsed_exports.reexports,
         imports: parsed_exports.imports,
         default_export: parsed_exports.default_export,
+        excerpt,
+        word_count,
+        reading_time_minutes,
+        links: plugins::collect_links(&transformed_mdast),
+        includes,
+        emitted_assets: plugin_ctx.into_assets(),
+        content,
     })
 }
 
+/// Default image plugin, reading local images relative to the compiled file
+fn image_plugin(options: &MdxCompileOptions) -> plugins::ImageOptimizationPlugin {
+    match &options.image_dimensions {
+        Some(dimensions) => plugins::ImageOptimizationPlugin::with_dimensions(
+            dimensions.for_file(options.filepath.as_deref()),
+        ),
+        None => plugins::ImageOptimizationPlugin::new(),
+    }
+}
+
 /// Parsed ES module information from MDX
 struct ParsedExports {
     named_exports: Vec<String>,
@@ -437,4 +858,7 @@ pub use bundler::FobMdxPlugin;
 #[cfg(feature = "runtime")]
 pub mod runtime;
 #[cfg(feature = "runtime")]
-pub use runtime::{BundleMdxOptions, BundleMdxResult, bundle_mdx};
+pub use runtime::{
+    BundleMdxCss, BundleMdxLimitError, BundleMdxOptions, BundleMdxResult, BundleMdxSourceMap,
+    CachedMdxBundler, LruMdxBundleCache, MdxBundleCache, MdxBundler, bundle_mdx, bundle_mdx_many,
+};
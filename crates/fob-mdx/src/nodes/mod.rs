@@ -82,11 +82,9 @@ pub fn node_to_jsx(
             inline::footnote_definition_to_jsx(footnote_def, ctx)
         }
 
-        // HTML (pass through if safe)
-        Node::Html(html) => Ok(Some(JsValue::raw(html.value.clone()))),
-
-        // Skip these nodes
-        Node::Definition(_) | Node::Yaml(_) | Node::Toml(_) => Ok(None),
+        // Skip these nodes. Raw HTML only occurs in plain markdown mode and
+        // cannot be expressed as JSX; `render_html` keeps it.
+        Node::Html(_) | Node::Definition(_) | Node::Yaml(_) | Node::Toml(_) => Ok(None),
 
         // Fallback for unhandled nodes
         _ => Ok(None),
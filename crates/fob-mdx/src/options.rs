@@ -1,8 +1,9 @@
 //! MDX compilation options
 
-use crate::OutputFormat;
 use crate::frontmatter::FrontmatterData;
 use crate::plugins::MdxPlugin;
+use crate::{DEFAULT_JSX_RUNTIME, JsxTarget, OutputFormat};
+use std::collections::HashMap;
 
 /// Configuration options for MDX processing
 pub struct MdxOptions {
@@ -10,6 +11,8 @@ pub struct MdxOptions {
     pub plugins: Vec<Box<dyn MdxPlugin>>,
     /// JSX runtime import path (default: "react/jsx-runtime")
     pub jsx_runtime: String,
+    /// Framework the generated JSX calls target
+    pub jsx_target: JsxTarget,
     /// Output format (Program or FunctionBody)
     pub output_format: OutputFormat,
     /// Pre-extracted frontmatter (passed from compile() to avoid double extraction)
@@ -25,16 +28,23 @@ pub struct MdxOptions {
     ///
     /// This follows the MDX v3 pattern used by Next.js and @mdx-js/react.
     pub provider_import_source: Option<String>,
+    /// Compile-time component mapping (e.g. `h1` → `Heading`), see `MdxCompileOptions::components`
+    pub components: HashMap<String, String>,
+    /// Module whose default export wraps the document, see `MdxCompileOptions::layout`
+    pub layout: Option<String>,
 }
 
 impl Default for MdxOptions {
     fn default() -> Self {
         Self {
             plugins: Vec::new(),
-            jsx_runtime: "react/jsx-runtime".to_string(),
+            jsx_runtime: DEFAULT_JSX_RUNTIME.to_string(),
+            jsx_target: JsxTarget::default(),
             output_format: OutputFormat::default(),
             frontmatter: None,
             provider_import_source: None,
+            components: HashMap::new(),
+            layout: None,
         }
     }
 }
@@ -0,0 +1,76 @@
+//! Plugin context for emitting additional files
+
+/// File emitted by a plugin alongside the compiled MDX
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MdxAsset {
+    /// Output path, relative to the output directory (e.g. `og/intro.png`)
+    pub path: String,
+    /// File content
+    pub content: Vec<u8>,
+}
+
+/// Context passed to [`MdxPlugin::emit_assets`](super::MdxPlugin::emit_assets)
+///
+/// Collects files emitted by plugins. They are returned in
+/// `MdxCompileResult::emitted_assets` and, when compiling through the
+/// bundler, written as bundle assets.
+#[derive(Debug, Default)]
+pub struct MdxPluginContext {
+    filepath: Option<String>,
+    assets: Vec<MdxAsset>,
+}
+
+impl MdxPluginContext {
+    /// Create a context for the given source file
+    pub fn new(filepath: Option<String>) -> Self {
+        Self {
+            filepath,
+            assets: Vec::new(),
+        }
+    }
+
+    /// Path of the MDX file being compiled, if known
+    pub fn filepath(&self) -> Option<&str> {
+        self.filepath.as_deref()
+    }
+
+    /// Emit a file
+    ///
+    /// Emitting the same path twice replaces the earlier content.
+    pub fn emit_file(&mut self, path: impl Into<String>, content: impl Into<Vec<u8>>) {
+        let path = path.into();
+        let content = content.into();
+        match self.assets.iter_mut().find(|asset| asset.path == path) {
+            Some(existing) => existing.content = content,
+            None => self.assets.push(MdxAsset { path, content }),
+        }
+    }
+
+    /// Files emitted so far
+    pub fn assets(&self) -> &[MdxAsset] {
+        &self.assets
+    }
+
+    /// Consume the context, returning all emitted files
+    pub fn into_assets(self) -> Vec<MdxAsset> {
+        self.assets
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_emit_replaces_same_path() {
+        let mut ctx = MdxPluginContext::new(Some("docs/intro.mdx".to_string()));
+        ctx.emit_file("intro.json", "{}");
+        ctx.emit_file("snippets/1.js", b"a()".to_vec());
+        ctx.emit_file("intro.json", r#"{"title":"Intro"}"#);
+
+        assert_eq!(ctx.filepath(), Some("docs/intro.mdx"));
+        let assets = ctx.into_assets();
+        assert_eq!(assets.len(), 2);
+        assert_eq!(assets[0].content, br#"{"title":"Intro"}"#);
+    }
+}
@@ -6,9 +6,10 @@
 //! # Features
 //!
 //! - Generates URL-safe slugs from heading text
-//! - Handles duplicate IDs with numeric suffixes
+//! - Handles duplicate IDs with deterministic numeric suffixes
 //! - Preserves existing IDs if manually set
 //! - Works with all heading levels (h1-h6)
+//! - Configurable prefix and slugification (see [`HeadingIdOptions`])
 //!
 //! # Example
 //!
@@ -23,31 +24,93 @@
 //! - `<h2 id="hello-world-1">Hello World</h2>`
 //! - `<h3 id="special-characters">Special Characters: @#$%</h3>`
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
 
 use anyhow::Result;
+use bon::Builder;
 use markdown::mdast::Node;
 
 use super::MdxPlugin;
+use crate::codegen::escape_js_string;
+use crate::utils::plain_text;
+
+/// How heading text is turned into a slug
+#[derive(Clone, Default)]
+pub enum Slugify {
+    /// Lowercase ASCII alphanumerics with runs of anything else collapsed
+    /// into a single hyphen: `"Rust & TypeScript"` → `rust-typescript`
+    #[default]
+    Default,
+    /// github-slugger compatible: Unicode lowercase, punctuation removed,
+    /// each space replaced by a hyphen: `"Rust & TypeScript"` → `rust--typescript`
+    GitHub,
+    /// Custom function, e.g. for locale-specific transliteration
+    Custom(Arc<dyn Fn(&str) -> String + Send + Sync>),
+}
+
+impl Slugify {
+    /// Wrap a custom slug function
+    pub fn custom(slugify: impl Fn(&str) -> String + Send + Sync + 'static) -> Self {
+        Self::Custom(Arc::new(slugify))
+    }
+}
+
+impl std::fmt::Debug for Slugify {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Default => f.write_str("Default"),
+            Self::GitHub => f.write_str("GitHub"),
+            Self::Custom(_) => f.write_str("Custom(..)"),
+        }
+    }
+}
+
+impl std::str::FromStr for Slugify {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "default" => Ok(Self::Default),
+            "github" => Ok(Self::GitHub),
+            other => Err(format!(
+                "Unknown slug style '{}' (expected default or github)",
+                other
+            )),
+        }
+    }
+}
+
+/// Heading ID configuration
+#[derive(Clone, Debug, Default, Builder)]
+pub struct HeadingIdOptions {
+    /// Prepended to every generated ID (e.g. `"docs-"`)
+    #[builder(default, into)]
+    pub prefix: String,
+
+    /// Slug function applied to the heading text
+    #[builder(default)]
+    pub slugify: Slugify,
+}
 
 /// Plugin that generates unique anchor IDs for heading elements
 ///
 /// The plugin walks the AST and adds `id` attributes to all heading nodes.
 /// IDs are generated by:
 /// 1. Extracting text content from the heading
-/// 2. Converting to lowercase
-/// 3. Replacing non-alphanumeric characters with hyphens
-/// 4. Removing leading/trailing hyphens
-/// 5. Appending numeric suffix if ID already exists
+/// 2. Slugifying it (by default: lowercase, non-alphanumeric runs → hyphen)
+/// 3. Prepending the configured prefix
+/// 4. Appending the first free numeric suffix if the ID is already used
 ///
 /// # Thread Safety
 ///
-/// This plugin uses internal state to track used IDs. Each instance maintains
-/// its own ID counter, so use separate instances for parallel processing.
-#[derive(Default)]
+/// IDs computed from the AST are held until the JSX pass, so use one
+/// instance per document when compiling in parallel.
+#[derive(Debug, Default)]
 pub struct HeadingIdPlugin {
-    // Internal state is not needed here as we'll process the entire AST in one pass
-    // and collect IDs during that pass
+    options: HeadingIdOptions,
+    /// IDs computed by `transform_ast`, consumed by `transform_jsx`
+    ids: Mutex<Vec<String>>,
 }
 
 impl HeadingIdPlugin {
@@ -56,8 +119,23 @@ impl HeadingIdPlugin {
         Self::default()
     }
 
+    /// Create a heading ID plugin with a custom prefix or slug function
+    pub fn with_options(options: HeadingIdOptions) -> Self {
+        Self {
+            options,
+            ids: Mutex::default(),
+        }
+    }
+
+    /// Configured options
+    pub fn options(&self) -> &HeadingIdOptions {
+        &self.options
+    }
+
     /// Generate a URL-safe slug from heading text
     ///
+    /// The prefix is not included; see [`HeadingIdOptions::prefix`].
+    ///
     /// # Algorithm
     ///
     /// 1. Extract text content from all child nodes
@@ -71,23 +149,11 @@ impl HeadingIdPlugin {
     /// - "Rust & TypeScript" → "rust-typescript"
     /// - "  Multiple   Spaces  " → "multiple-spaces"
     pub fn generate_slug(&self, text: &str) -> String {
-        let mut slug = String::new();
-        let mut last_was_sep = true; // Start true to skip leading hyphens
-
-        for ch in text.chars() {
-            if ch.is_alphanumeric() {
-                slug.push(ch.to_ascii_lowercase());
-                last_was_sep = false;
-            } else if !last_was_sep {
-                slug.push('-');
-                last_was_sep = true;
-            }
-        }
-
-        // Remove trailing hyphen if present
-        if slug.ends_with('-') {
-            slug.pop();
-        }
+        let slug = match &self.options.slugify {
+            Slugify::Default => default_slug(text),
+            Slugify::GitHub => github_slug(text),
+            Slugify::Custom(slugify) => slugify(text),
+        };
 
         // Return "heading" as fallback for empty slugs
         if slug.is_empty() {
@@ -97,121 +163,106 @@ impl HeadingIdPlugin {
         }
     }
 
-    /// Extract text content from a node and its children
-    fn extract_text(node: &Node) -> String {
-        match node {
-            Node::Text(text) => text.value.clone(),
-            Node::InlineCode(code) => code.value.clone(),
-            Node::Strong(strong) => strong
-                .children
-                .iter()
-                .map(Self::extract_text)
-                .collect::<Vec<_>>()
-                .join(""),
-            Node::Emphasis(em) => em
-                .children
-                .iter()
-                .map(Self::extract_text)
-                .collect::<Vec<_>>()
-                .join(""),
-            Node::Delete(del) => del
-                .children
-                .iter()
-                .map(Self::extract_text)
-                .collect::<Vec<_>>()
-                .join(""),
-            Node::Link(link) => link
-                .children
-                .iter()
-                .map(Self::extract_text)
-                .collect::<Vec<_>>()
-                .join(""),
-            Node::LinkReference(link_ref) => link_ref
-                .children
-                .iter()
-                .map(Self::extract_text)
-                .collect::<Vec<_>>()
-                .join(""),
-            Node::MdxTextExpression(expr) => {
-                // For expressions, just use placeholder text
-                format!("expr-{}", expr.value.len())
-            }
-            // Ignore other node types
-            _ => String::new(),
+    /// Start assigning IDs for one document.
+    pub(crate) fn slugger(&self) -> Slugger<'_> {
+        Slugger {
+            plugin: self,
+            used: HashSet::new(),
+            next_suffix: HashMap::new(),
         }
     }
 
-    /// Process headings and add IDs
+    /// Assign IDs to headings in document order, which is also the order
+    /// the codegen emits them in.
     ///
-    /// Note: Since mdast doesn't support custom attributes, this method just
-    /// validates the AST structure. The actual ID injection happens in the
-    /// JSX transformation phase.
-    fn process_headings(&self, node: &mut Node, used_ids: &mut HashMap<String, usize>) {
-        match node {
-            Node::Heading(heading) => {
-                // Extract text from all children
-                let text = heading
-                    .children
-                    .iter()
-                    .map(Self::extract_text)
-                    .collect::<Vec<_>>()
-                    .join("");
-
-                // Generate base slug
-                let base_slug = self.generate_slug(&text);
-
-                // Make unique if needed
-                let _unique_id = if let Some(count) = used_ids.get_mut(&base_slug) {
-                    *count += 1;
-                    format!("{}-{}", base_slug, count)
-                } else {
-                    used_ids.insert(base_slug.clone(), 0);
-                    base_slug
-                };
-
-                // Note: We can't store the ID in the AST since mdast doesn't support
-                // custom attributes. The ID generation will need to happen during
-                // JSX transformation where we can parse and modify the output.
-            }
-            Node::Root(root) => {
-                for child in &mut root.children {
-                    self.process_headings(child, used_ids);
-                }
-            }
-            Node::Blockquote(blockquote) => {
-                for child in &mut blockquote.children {
-                    self.process_headings(child, used_ids);
-                }
-            }
-            Node::List(list) => {
-                for child in &mut list.children {
-                    self.process_headings(child, used_ids);
-                }
-            }
-            Node::ListItem(item) => {
-                for child in &mut item.children {
-                    self.process_headings(child, used_ids);
-                }
-            }
-            Node::FootnoteDefinition(def) => {
-                for child in &mut def.children {
-                    self.process_headings(child, used_ids);
-                }
+    /// mdast has no attributes, so the IDs are kept until `transform_jsx`
+    /// injects them.
+    fn collect_ids(node: &Node, slugger: &mut Slugger<'_>, ids: &mut Vec<String>) {
+        if let Node::Heading(_) = node {
+            ids.push(slugger.id(&plain_text(node)));
+        }
+        for child in node.children().into_iter().flatten() {
+            Self::collect_ids(child, slugger, ids);
+        }
+    }
+}
+
+/// Assigns unique IDs to the headings of one document
+///
+/// Repeated slugs get the first free suffix, so `Intro`, `Intro`, `Intro 1`
+/// become `intro`, `intro-1` and `intro-1-1` regardless of hash order.
+pub(crate) struct Slugger<'a> {
+    plugin: &'a HeadingIdPlugin,
+    used: HashSet<String>,
+    next_suffix: HashMap<String, usize>,
+}
+
+impl Slugger<'_> {
+    /// Unique, prefixed ID for a heading's text.
+    pub(crate) fn id(&mut self, text: &str) -> String {
+        let base = format!(
+            "{}{}",
+            self.plugin.options.prefix,
+            self.plugin.generate_slug(text)
+        );
+        if self.used.insert(base.clone()) {
+            return base;
+        }
+
+        let suffix = self.next_suffix.entry(base.clone()).or_insert(1);
+        loop {
+            let candidate = format!("{}-{}", base, suffix);
+            *suffix += 1;
+            if self.used.insert(candidate.clone()) {
+                return candidate;
             }
-            // Other container nodes can be added as needed
-            _ => {}
         }
     }
 }
 
+fn default_slug(text: &str) -> String {
+    let mut slug = String::new();
+    let mut last_was_sep = true; // Start true to skip leading hyphens
+
+    for ch in text.chars() {
+        if ch.is_alphanumeric() {
+            slug.push(ch.to_ascii_lowercase());
+            last_was_sep = false;
+        } else if !last_was_sep {
+            slug.push('-');
+            last_was_sep = true;
+        }
+    }
+
+    // Remove trailing hyphen if present
+    if slug.ends_with('-') {
+        slug.pop();
+    }
+    slug
+}
+
+fn github_slug(text: &str) -> String {
+    text.trim()
+        .to_lowercase()
+        .chars()
+        .filter_map(|ch| match ch {
+            ' ' => Some('-'),
+            '-' | '_' => Some(ch),
+            ch if ch.is_alphanumeric() => Some(ch),
+            _ => None,
+        })
+        .collect()
+}
+
 impl MdxPlugin for HeadingIdPlugin {
     fn name(&self) -> &'static str {
         "heading-ids"
     }
 
     fn transform_ast(&self, ast: &mut Node) -> Result<()> {
-        let mut used_ids = HashMap::new();
-        self.process_headings(ast, &mut used_ids);
+        let mut ids = Vec::new();
+        Self::collect_ids(ast, &mut self.slugger(), &mut ids);
+        *self.ids.lock().unwrap_or_else(|e| e.into_inner()) = ids;
         Ok(())
     }
 
@@ -232,7 +283,12 @@ impl MdxPlugin for HeadingIdPlugin {
         // international text, etc.) and slicing mid-character causes panics.
 
         let mut result = String::with_capacity(jsx.len() + 1024);
-        let mut used_ids = HashMap::new();
+        // Prefer the IDs computed from the AST. Without them (transform_jsx
+        // called on its own), fall back to the text found in the JSX.
+        let ids = std::mem::take(&mut *self.ids.lock().unwrap_or_else(|e| e.into_inner()));
+        let mut slugger = self.slugger();
+        slugger.used.extend(ids.iter().cloned());
+        let mut ids = ids.into_iter();
         let mut last_end = 0;
 
         // Simple state machine to find heading elements
@@ -295,15 +351,7 @@ impl MdxPlugin for HeadingIdPlugin {
                                     .take(50)
                                     .collect::<String>();
 
-                                // Generate slug
-                                let base_slug = self.generate_slug(&text);
-                                let unique_id = if let Some(count) = used_ids.get_mut(&base_slug) {
-                                    *count += 1;
-                                    format!("{}-{}", base_slug, count)
-                                } else {
-                                    used_ids.insert(base_slug.clone(), 0);
-                                    base_slug
-                                };
+                                let unique_id = ids.next().unwrap_or_else(|| slugger.id(&text));
 
                                 // Calculate the injection point (where we insert the id attribute)
                                 let raw_inject_point = abs_children + ", {...props, ".len();
@@ -335,7 +383,10 @@ impl MdxPlugin for HeadingIdPlugin {
                                     result.push_str(&jsx[safe_last_end..inject_point]);
 
                                     // Inject id attribute
-                                    result.push_str(&format!("id: \"{}\", ", unique_id));
+                                    result.push_str(&format!(
+                                        "id: \"{}\", ",
+                                        escape_js_string(&unique_id)
+                                    ));
 
                                     // Update last_end to be after the injection point
                                     // This is safe because inject_point is validated
@@ -394,22 +445,35 @@ mod tests {
 
     #[test]
     fn test_text_extraction() {
-        // Test plain text
-        let text_node = Node::Text(markdown::mdast::Text {
-            value: "Hello".to_string(),
-            position: None,
-        });
-        assert_eq!(HeadingIdPlugin::extract_text(&text_node), "Hello");
-
-        // Test strong
-        let strong_node = Node::Strong(markdown::mdast::Strong {
-            children: vec![Node::Text(markdown::mdast::Text {
-                value: "Bold".to_string(),
+        // `## Hello **Bold**`
+        let mut ast = Node::Root(markdown::mdast::Root {
+            children: vec![Node::Heading(markdown::mdast::Heading {
+                depth: 2,
+                children: vec![
+                    Node::Text(markdown::mdast::Text {
+                        value: "Hello ".to_string(),
+                        position: None,
+                    }),
+                    Node::Strong(markdown::mdast::Strong {
+                        children: vec![Node::Text(markdown::mdast::Text {
+                            value: "Bold".to_string(),
+                            position: None,
+                        })],
+                        position: None,
+                    }),
+                ],
                 position: None,
             })],
             position: None,
         });
-        assert_eq!(HeadingIdPlugin::extract_text(&strong_node), "Bold");
+
+        let plugin = HeadingIdPlugin::new();
+        plugin.transform_ast(&mut ast).unwrap();
+        let mut jsx = String::from(
+            r#"_jsx(_components.h2, {...props, children: ["Hello ", _jsx(_components.strong, {children: "Bold"})]});"#,
+        );
+        plugin.transform_jsx(&mut jsx).unwrap();
+        assert!(jsx.contains(r#"id: "hello-bold""#));
     }
 
     #[test]
@@ -443,6 +507,49 @@ mod tests {
         assert_eq!(unique_slug3, "introduction-2");
     }
 
+    #[test]
+    fn test_slugger_suffixes_are_deterministic() {
+        let plugin = HeadingIdPlugin::new();
+        let mut slugger = plugin.slugger();
+
+        assert_eq!(slugger.id("Intro"), "intro");
+        assert_eq!(slugger.id("Intro 1"), "intro-1");
+        // `intro-1` is taken by a literal heading, so the next free suffix is used
+        assert_eq!(slugger.id("Intro"), "intro-2");
+        assert_eq!(slugger.id("Intro"), "intro-3");
+        assert_eq!(slugger.id("Intro 1"), "intro-1-1");
+    }
+
+    #[test]
+    fn test_prefix_and_slug_styles() {
+        let plugin = HeadingIdPlugin::with_options(
+            HeadingIdOptions::builder()
+                .prefix("docs-")
+                .slugify(Slugify::GitHub)
+                .build(),
+        );
+        assert_eq!(
+            plugin.generate_slug("Rust & TypeScript"),
+            "rust--typescript"
+        );
+        assert_eq!(plugin.generate_slug("Ünïcode_Names"), "ünïcode_names");
+
+        let mut slugger = plugin.slugger();
+        assert_eq!(slugger.id("Setup"), "docs-setup");
+        assert_eq!(slugger.id("Setup"), "docs-setup-1");
+
+        let plugin = HeadingIdPlugin::with_options(
+            HeadingIdOptions::builder()
+                .slugify(Slugify::custom(|text| text.replace(' ', "_")))
+                .build(),
+        );
+        assert_eq!(plugin.generate_slug("Hello World"), "Hello_World");
+        assert_eq!(plugin.generate_slug(""), "heading");
+
+        assert!(matches!("GitHub".parse::<Slugify>(), Ok(Slugify::GitHub)));
+        assert!("kebab".parse::<Slugify>().is_err());
+    }
+
     // UTF-8 safety tests - these ensure we never panic on multibyte characters
     #[test]
     fn test_jsx_transform_with_emoji() {
@@ -0,0 +1,658 @@
+//! Image optimization plugin for collecting and tagging images
+//!
+//! This plugin identifies all images in MDX documents and collects them for
+//! later optimization and processing. It also adds data attributes to track
+//! which images have been processed.
+//!
+//! # Features
+//!
+//! - Collects both relative and absolute image URLs
+//! - Thread-safe image collection using Arc<Mutex<Vec<ImageInfo>>>
+//! - Adds `data-fob-optimized="true"` attribute for tracking
+//! - Supports both inline images and MDX JSX image components
+//! - Optionally reads local files to add `width`/`height` (avoiding layout
+//!   shift) and a blurred `data-placeholder` image
+//!
+//! # Example
+//!
+//! ```markdown
+//! ![Alt text](/images/photo.jpg)
+//! <img src="./local.png" />
+//! ```
+//!
+//! Both images will be collected and marked with `data-fob-optimized="true"`.
+
+mod probe;
+
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use anyhow::Result;
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use bon::Builder;
+use markdown::mdast::Node;
+use path_clean::PathClean;
+use serde::{Deserialize, Serialize};
+
+use super::MdxPlugin;
+use crate::codegen::escape_js_string;
+use crate::files::{FileSource, FsFileSource};
+pub use probe::ImageFormat;
+
+/// Settings for reading local images to add their dimensions
+///
+/// ```rust
+/// use std::collections::HashMap;
+/// use std::path::PathBuf;
+/// use std::sync::Arc;
+/// use fob_mdx::plugins::ImageDimensionOptions;
+///
+/// // Read from disk, resolving `/...` URLs in `public`
+/// let options = ImageDimensionOptions::builder().public_dir("public").build();
+///
+/// // Read from memory, embedding small images as placeholders
+/// let files: HashMap<PathBuf, Vec<u8>> = HashMap::new();
+/// let options = ImageDimensionOptions::builder()
+///     .source(Arc::new(files))
+///     .placeholder_max_bytes(8 * 1024)
+///     .build();
+/// ```
+#[derive(Clone, Builder)]
+pub struct ImageDimensionOptions {
+    /// Where image files are read from (default: the file system)
+    #[builder(default = Arc::new(FsFileSource))]
+    pub source: Arc<dyn FileSource>,
+
+    /// Directory relative URLs (`./photo.png`) resolve against.
+    /// Defaults to the directory of the compiled file.
+    #[builder(into)]
+    pub base_dir: Option<PathBuf>,
+
+    /// Directory root-relative URLs (`/images/photo.png`) resolve against,
+    /// such as `public`. Those images are skipped when unset.
+    #[builder(into)]
+    pub public_dir: Option<PathBuf>,
+
+    /// Largest file, in bytes, embedded as a blurred placeholder.
+    /// Placeholders embed the whole file, so keep this small; `0` (the
+    /// default) disables them.
+    #[builder(default)]
+    pub placeholder_max_bytes: usize,
+}
+
+impl ImageDimensionOptions {
+    /// Local file path an image URL resolves to, if it points to one
+    pub fn resolve(&self, url: &str) -> Option<PathBuf> {
+        let path = url.split(['?', '#']).next().unwrap_or_default();
+        // Remote (`https:`, `data:`) and protocol-relative URLs
+        if path.is_empty() || path.starts_with("//") || has_scheme(path) {
+            return None;
+        }
+        let path = percent_decode(path);
+        let resolved = match path.strip_prefix('/') {
+            Some(rooted) => self.public_dir.as_ref()?.join(rooted),
+            None => self.base_dir.as_deref().unwrap_or(Path::new("")).join(path),
+        };
+        Some(resolved.clean())
+    }
+
+    /// Options for compiling `filepath`, whose directory becomes the
+    /// default `base_dir`
+    pub(crate) fn for_file(&self, filepath: Option<&str>) -> Self {
+        let mut options = self.clone();
+        if options.base_dir.is_none() {
+            options.base_dir = filepath
+                .and_then(|filepath| Path::new(filepath).parent())
+                .map(Path::to_path_buf);
+        }
+        options
+    }
+}
+
+impl std::fmt::Debug for ImageDimensionOptions {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ImageDimensionOptions")
+            .field("base_dir", &self.base_dir)
+            .field("public_dir", &self.public_dir)
+            .field("placeholder_max_bytes", &self.placeholder_max_bytes)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Image found in a document
+///
+/// Only `url` and `alt` are set unless dimension reading is enabled and the
+/// image resolves to a readable local file.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImageInfo {
+    /// URL as written in the document
+    pub url: String,
+    /// Alternative text
+    pub alt: String,
+    /// Local file the URL resolved to
+    pub path: Option<PathBuf>,
+    /// Intrinsic width in pixels
+    pub width: Option<u32>,
+    /// Intrinsic height in pixels
+    pub height: Option<u32>,
+    /// Format detected from the file content
+    pub format: Option<ImageFormat>,
+    /// Blurred placeholder as a `data:image/svg+xml` URI
+    pub placeholder: Option<String>,
+}
+
+impl ImageInfo {
+    fn new(url: impl Into<String>, alt: impl Into<String>) -> Self {
+        Self {
+            url: url.into(),
+            alt: alt.into(),
+            path: None,
+            width: None,
+            height: None,
+            format: None,
+            placeholder: None,
+        }
+    }
+}
+
+/// Plugin that collects images for optimization and adds tracking attributes
+///
+/// This plugin walks the AST and:
+/// 1. Finds all `Node::Image` nodes (markdown images)
+/// 2. Extracts image URLs (both relative and absolute)
+/// 3. Stores them in a thread-safe collection
+/// 4. Adds data attributes during JSX transformation
+///
+/// With [`ImageDimensionOptions`], local images are also read to fill in
+/// their size and format, which are added as `width`/`height` props.
+///
+/// # Thread Safety
+///
+/// Uses `Arc<Mutex<Vec<ImageInfo>>>` to safely collect images across multiple
+/// documents processed in parallel.
+///
+/// # Usage
+///
+/// ```rust,no_run
+/// use fob_mdx::plugins::ImageOptimizationPlugin;
+///
+/// let plugin = ImageOptimizationPlugin::new();
+/// // ... use plugin in MdxOptions ...
+///
+/// // After processing, retrieve collected images
+/// let images = plugin.images();
+/// println!("Found {} images", images.len());
+/// ```
+#[derive(Clone)]
+pub struct ImageOptimizationPlugin {
+    /// Thread-safe collection of images found during AST traversal
+    images: Arc<Mutex<Vec<ImageInfo>>>,
+    /// Reads local images when set
+    dimensions: Option<ImageDimensionOptions>,
+}
+
+impl ImageOptimizationPlugin {
+    /// Create a new image optimization plugin
+    pub fn new() -> Self {
+        Self {
+            images: Arc::new(Mutex::new(Vec::new())),
+            dimensions: None,
+        }
+    }
+
+    /// Create a plugin that also reads local images for their dimensions
+    pub fn with_dimensions(options: ImageDimensionOptions) -> Self {
+        Self {
+            dimensions: Some(options),
+            ..Self::new()
+        }
+    }
+
+    /// Get a clone of all collected images
+    ///
+    /// This returns a snapshot of images collected so far. Safe to call
+    /// from any thread. Handles mutex poisoning gracefully by recovering
+    /// the inner data.
+    pub fn images(&self) -> Vec<ImageInfo> {
+        self.images
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+
+    /// Clear all collected images
+    ///
+    /// Useful for resetting state between batch processing runs.
+    pub fn clear(&self) {
+        self.images
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clear();
+    }
+
+    /// Fill in size, format and placeholder from the local file
+    fn inspect(&self, info: &mut ImageInfo) {
+        let Some(options) = &self.dimensions else {
+            return;
+        };
+        let Some(path) = options.resolve(&info.url) else {
+            return;
+        };
+        let bytes = match options.source.read(&path) {
+            Ok(bytes) => bytes,
+            Err(e) => {
+                tracing::debug!(path = %path.display(), error = %e, "Could not read image");
+                return;
+            }
+        };
+        info.path = Some(path);
+        let Some((format, size)) = probe::probe(&bytes) else {
+            return;
+        };
+        info.format = Some(format);
+        if let Some((width, height)) = size {
+            info.width = Some(width);
+            info.height = Some(height);
+            if bytes.len() <= options.placeholder_max_bytes {
+                info.placeholder = Some(blur_placeholder(&bytes, format, width, height));
+            }
+        }
+    }
+
+    /// Walk the AST and collect all images
+    fn collect_images(&self, node: &Node) {
+        match node {
+            Node::Image(image) => {
+                let mut info = ImageInfo::new(&image.url, &image.alt);
+                self.inspect(&mut info);
+                self.images
+                    .lock()
+                    .unwrap_or_else(|e| e.into_inner())
+                    .push(info);
+
+                tracing::debug!(
+                    url = image.url,
+                    alt = image.alt,
+                    "Collected image for optimization"
+                );
+            }
+            Node::Root(root) => {
+                for child in &root.children {
+                    self.collect_images(child);
+                }
+            }
+            Node::Paragraph(para) => {
+                for child in &para.children {
+                    self.collect_images(child);
+                }
+            }
+            Node::Link(link) => {
+                for child in &link.children {
+                    self.collect_images(child);
+                }
+            }
+            Node::LinkReference(link_ref) => {
+                for child in &link_ref.children {
+                    self.collect_images(child);
+                }
+            }
+            Node::Strong(strong) => {
+                for child in &strong.children {
+                    self.collect_images(child);
+                }
+            }
+            Node::Emphasis(em) => {
+                for child in &em.children {
+                    self.collect_images(child);
+                }
+            }
+            Node::Delete(del) => {
+                for child in &del.children {
+                    self.collect_images(child);
+                }
+            }
+            Node::Blockquote(blockquote) => {
+                for child in &blockquote.children {
+                    self.collect_images(child);
+                }
+            }
+            Node::List(list) => {
+                for child in &list.children {
+                    self.collect_images(child);
+                }
+            }
+            Node::ListItem(item) => {
+                for child in &item.children {
+                    self.collect_images(child);
+                }
+            }
+            Node::Table(table) => {
+                for child in &table.children {
+                    self.collect_images(child);
+                }
+            }
+            Node::TableRow(row) => {
+                for child in &row.children {
+                    self.collect_images(child);
+                }
+            }
+            Node::TableCell(cell) => {
+                for child in &cell.children {
+                    self.collect_images(child);
+                }
+            }
+            Node::Heading(heading) => {
+                for child in &heading.children {
+                    self.collect_images(child);
+                }
+            }
+            Node::FootnoteDefinition(def) => {
+                for child in &def.children {
+                    self.collect_images(child);
+                }
+            }
+            // Other node types don't contain images
+            _ => {}
+        }
+    }
+}
+
+impl Default for ImageOptimizationPlugin {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MdxPlugin for ImageOptimizationPlugin {
+    fn name(&self) -> &'static str {
+        "image-optimization"
+    }
+
+    fn transform_ast(&self, ast: &mut Node) -> Result<()> {
+        self.collect_images(ast);
+        Ok(())
+    }
+
+    fn transform_jsx(&self, jsx: &mut String) -> Result<()> {
+        // Add data-fob-optimized="true" to all img elements
+        // Pattern: _jsx(_components.img, {
+        // We need to inject data-fob-optimized: "true" into the props
+
+        // Simple string replacement approach
+        // In production, you'd want to parse the JSX AST for safety
+        let mut sized = HashSet::new();
+        for image in self.images() {
+            let (Some(width), Some(height)) = (image.width, image.height) else {
+                continue;
+            };
+            let props = format!(
+                "src: \"{}\", alt: \"{}\"",
+                escape_js_string(&image.url),
+                escape_js_string(&image.alt)
+            );
+            if !sized.insert(props.clone()) {
+                continue;
+            }
+            let mut extra = format!(", width: {}, height: {}", width, height);
+            if let Some(placeholder) = &image.placeholder {
+                extra.push_str(&format!(", \"data-placeholder\": \"{}\"", placeholder));
+            }
+            *jsx = jsx.replace(&props, &format!("{}{}", props, extra));
+        }
+
+        *jsx = jsx.replace(
+            "_jsx(_components.img, {",
+            "_jsx(_components.img, {\"data-fob-optimized\": \"true\", ",
+        );
+
+        Ok(())
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+/// `scheme:` before any path separator (`https:`, `data:`, `c:`)
+fn has_scheme(url: &str) -> bool {
+    let path_start = url.find('/').unwrap_or(url.len());
+    url[..path_start].contains(':')
+}
+
+/// Decode `%XX` escapes, keeping malformed ones as written
+fn percent_decode(path: &str) -> String {
+    let bytes = path.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = bytes
+            .get(i + 1..i + 3)
+            .and_then(|hex| std::str::from_utf8(hex).ok())
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match (bytes[i], hex) {
+            (b'%', Some(byte)) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            (byte, _) => {
+                decoded.push(byte);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+/// SVG data URI showing the image blurred, sized to its aspect ratio
+///
+/// This is the `data-placeholder` of optimized Markdown images. Image
+/// plugins use it too, so imported images share its CSS treatment.
+pub fn blur_placeholder(bytes: &[u8], format: ImageFormat, width: u32, height: u32) -> String {
+    let svg = format!(
+        "<svg xmlns='http://www.w3.org/2000/svg' viewBox='0 0 {width} {height}'>\
+         <filter id='b' color-interpolation-filters='sRGB'><feGaussianBlur stdDeviation='20'/>\
+         <feComponentTransfer><feFuncA type='discrete' tableValues='1 1'/></feComponentTransfer></filter>\
+         <image width='100%' height='100%' preserveAspectRatio='none' filter='url(#b)' \
+         href='data:{mime};base64,{data}'/></svg>",
+        mime = format.mime_type(),
+        data = BASE64.encode(bytes),
+    );
+    format!("data:image/svg+xml;base64,{}", BASE64.encode(svg))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use markdown::mdast::{Image, Paragraph, Root};
+    use std::collections::HashMap;
+
+    fn image(url: &str, alt: &str) -> Node {
+        Node::Root(Root {
+            children: vec![Node::Paragraph(Paragraph {
+                children: vec![Node::Image(Image {
+                    url: url.to_string(),
+                    alt: alt.to_string(),
+                    title: None,
+                    position: None,
+                })],
+                position: None,
+            })],
+            position: None,
+        })
+    }
+
+    fn plugin_with_files(placeholder_max_bytes: usize) -> ImageOptimizationPlugin {
+        let files = HashMap::from([
+            (
+                PathBuf::from("docs/img/a b.png"),
+                probe::tests::png(640, 480),
+            ),
+            (PathBuf::from("public/logo.png"), probe::tests::png(32, 16)),
+        ]);
+        ImageOptimizationPlugin::with_dimensions(
+            ImageDimensionOptions::builder()
+                .source(Arc::new(files))
+                .base_dir("docs/guides")
+                .public_dir("public")
+                .placeholder_max_bytes(placeholder_max_bytes)
+                .build(),
+        )
+    }
+
+    #[test]
+    fn test_image_collection() {
+        let plugin = ImageOptimizationPlugin::new();
+
+        // Create AST with images
+        let ast = Node::Root(Root {
+            children: vec![
+                Node::Paragraph(Paragraph {
+                    children: vec![Node::Image(Image {
+                        url: "/images/photo1.jpg".to_string(),
+                        alt: "Photo 1".to_string(),
+                        title: None,
+                        position: None,
+                    })],
+                    position: None,
+                }),
+                Node::Paragraph(Paragraph {
+                    children: vec![Node::Image(Image {
+                        url: "./local.png".to_string(),
+                        alt: "Local image".to_string(),
+                        title: None,
+                        position: None,
+                    })],
+                    position: None,
+                }),
+            ],
+            position: None,
+        });
+
+        plugin.collect_images(&ast);
+
+        let images = plugin.images();
+        assert_eq!(images.len(), 2);
+        assert_eq!(images[0].url, "/images/photo1.jpg");
+        assert_eq!(images[0].alt, "Photo 1");
+        assert_eq!(images[1].url, "./local.png");
+        assert_eq!(images[1].width, None);
+    }
+
+    #[test]
+    fn test_clear_images() {
+        let plugin = ImageOptimizationPlugin::new();
+
+        // Add some images
+        let ast = Node::Root(Root {
+            children: vec![Node::Paragraph(Paragraph {
+                children: vec![Node::Image(Image {
+                    url: "/test.jpg".to_string(),
+                    alt: "Test".to_string(),
+                    title: None,
+                    position: None,
+                })],
+                position: None,
+            })],
+            position: None,
+        });
+
+        plugin.collect_images(&ast);
+        assert_eq!(plugin.images().len(), 1);
+
+        // Clear and verify
+        plugin.clear();
+        assert_eq!(plugin.images().len(), 0);
+    }
+
+    #[test]
+    fn test_nested_images() {
+        let plugin = ImageOptimizationPlugin::new();
+
+        // Image inside a link
+        let ast = Node::Root(Root {
+            children: vec![Node::Paragraph(Paragraph {
+                children: vec![Node::Link(markdown::mdast::Link {
+                    url: "https://example.com".to_string(),
+                    title: None,
+                    children: vec![Node::Image(Image {
+                        url: "/nested.jpg".to_string(),
+                        alt: "Nested".to_string(),
+                        title: None,
+                        position: None,
+                    })],
+                    position: None,
+                })],
+                position: None,
+            })],
+            position: None,
+        });
+
+        plugin.collect_images(&ast);
+        let images = plugin.images();
+        assert_eq!(images.len(), 1);
+        assert_eq!(images[0].url, "/nested.jpg");
+    }
+
+    #[test]
+    fn test_jsx_transformation() {
+        let plugin = ImageOptimizationPlugin::new();
+
+        let mut jsx = String::from(r#"_jsx(_components.img, {src: "/test.jpg", alt: "Test"})"#);
+
+        plugin.transform_jsx(&mut jsx).unwrap();
+
+        assert!(jsx.contains("data-fob-optimized"));
+        assert!(jsx.contains("\"true\""));
+    }
+
+    #[test]
+    fn test_local_image_dimensions() {
+        let plugin = plugin_with_files(0);
+        plugin.collect_images(&image("../img/a%20b.png?v=2", "Chart"));
+        plugin.collect_images(&image("/logo.png", "Logo"));
+        plugin.collect_images(&image("https://example.com/remote.png", "Remote"));
+        plugin.collect_images(&image("./missing.png", "Missing"));
+
+        let images = plugin.images();
+        assert_eq!(images[0].path, Some(PathBuf::from("docs/img/a b.png")));
+        assert_eq!((images[0].width, images[0].height), (Some(640), Some(480)));
+        assert_eq!(images[0].format, Some(ImageFormat::Png));
+        assert_eq!(images[0].placeholder, None);
+        assert_eq!((images[1].width, images[1].height), (Some(32), Some(16)));
+        assert_eq!(
+            images[2],
+            ImageInfo::new("https://example.com/remote.png", "Remote")
+        );
+        assert_eq!(images[3], ImageInfo::new("./missing.png", "Missing"));
+    }
+
+    #[test]
+    fn test_jsx_dimensions_and_placeholder() {
+        let plugin = plugin_with_files(1024);
+        plugin.collect_images(&image("/logo.png", "Logo"));
+        let placeholder = plugin.images()[0].placeholder.clone().unwrap();
+        assert!(placeholder.starts_with("data:image/svg+xml;base64,"));
+
+        let mut jsx = String::from(
+            r#"_jsx(_components.img, {...props, src: "/logo.png", alt: "Logo", title: "T"})"#,
+        );
+        plugin.transform_jsx(&mut jsx).unwrap();
+        assert_eq!(
+            jsx,
+            format!(
+                r#"_jsx(_components.img, {{"data-fob-optimized": "true", ...props, src: "/logo.png", alt: "Logo", width: 32, height: 16, "data-placeholder": "{}", title: "T"}})"#,
+                placeholder
+            )
+        );
+    }
+
+    #[test]
+    fn test_percent_decode() {
+        assert_eq!(percent_decode("a%20b%2Fc"), "a b/c");
+        assert_eq!(percent_decode("100%"), "100%");
+        assert_eq!(percent_decode("%zz"), "%zz");
+    }
+}
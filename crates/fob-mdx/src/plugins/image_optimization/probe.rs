@@ -0,0 +1,276 @@
+//! Image header parsing
+//!
+//! Reads the format and intrinsic size from the first bytes of an image
+//! file without decoding pixels. Covers the formats browsers commonly serve:
+//! PNG, JPEG, GIF, WebP and SVG.
+
+use std::sync::LazyLock;
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+/// Image file format detected from its content
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ImageFormat {
+    Png,
+    Jpeg,
+    Gif,
+    Webp,
+    Svg,
+}
+
+impl ImageFormat {
+    /// MIME type of the format
+    pub fn mime_type(self) -> &'static str {
+        match self {
+            Self::Png => "image/png",
+            Self::Jpeg => "image/jpeg",
+            Self::Gif => "image/gif",
+            Self::Webp => "image/webp",
+            Self::Svg => "image/svg+xml",
+        }
+    }
+}
+
+/// Format and size of an image, if recognized.
+///
+/// The size is `None` for SVGs without numeric `width`/`height` or `viewBox`.
+pub(crate) fn probe(bytes: &[u8]) -> Option<(ImageFormat, Option<(u32, u32)>)> {
+    if bytes.starts_with(b"\x89PNG\r\n\x1a\n") {
+        // IHDR is always the first chunk
+        let width = be_u32(bytes, 16)?;
+        let height = be_u32(bytes, 20)?;
+        return Some((ImageFormat::Png, Some((width, height))));
+    }
+    if bytes.starts_with(b"GIF87a") || bytes.starts_with(b"GIF89a") {
+        let width = le_u16(bytes, 6)?;
+        let height = le_u16(bytes, 8)?;
+        return Some((ImageFormat::Gif, Some((width.into(), height.into()))));
+    }
+    if bytes.starts_with(&[0xFF, 0xD8]) {
+        return Some((ImageFormat::Jpeg, jpeg_size(bytes)));
+    }
+    if bytes.len() >= 12 && &bytes[0..4] == b"RIFF" && &bytes[8..12] == b"WEBP" {
+        return Some((ImageFormat::Webp, webp_size(bytes)));
+    }
+    let text = std::str::from_utf8(bytes).ok()?;
+    if text.contains("<svg") {
+        return Some((ImageFormat::Svg, svg_size(text)));
+    }
+    None
+}
+
+/// Size from the first start-of-frame segment.
+fn jpeg_size(bytes: &[u8]) -> Option<(u32, u32)> {
+    let mut offset = 2;
+    loop {
+        // Markers may be preceded by any number of 0xFF fill bytes
+        while *bytes.get(offset)? == 0xFF && *bytes.get(offset + 1)? == 0xFF {
+            offset += 1;
+        }
+        if *bytes.get(offset)? != 0xFF {
+            return None;
+        }
+        let marker = *bytes.get(offset + 1)?;
+        offset += 2;
+        match marker {
+            // Standalone markers carry no length
+            0x01 | 0xD0..=0xD7 => continue,
+            // End of image or start of scan before any frame header
+            0xD9 | 0xDA => return None,
+            // SOF0-SOF15 except DHT (C4), JPG (C8) and DAC (CC)
+            0xC0..=0xCF if !matches!(marker, 0xC4 | 0xC8 | 0xCC) => {
+                let height = be_u16(bytes, offset + 3)?;
+                let width = be_u16(bytes, offset + 5)?;
+                return Some((width.into(), height.into()));
+            }
+            _ => offset += usize::from(be_u16(bytes, offset)?),
+        }
+    }
+}
+
+fn webp_size(bytes: &[u8]) -> Option<(u32, u32)> {
+    match bytes.get(12..16)? {
+        // Lossy: 14-bit dimensions after the frame tag and start code
+        b"VP8 " => {
+            let width = le_u16(bytes, 26)? & 0x3FFF;
+            let height = le_u16(bytes, 28)? & 0x3FFF;
+            Some((width.into(), height.into()))
+        }
+        // Lossless: two 14-bit fields, stored minus one, after the signature
+        b"VP8L" => {
+            let bits = u32::from_le_bytes(bytes.get(21..25)?.try_into().ok()?);
+            Some(((bits & 0x3FFF) + 1, ((bits >> 14) & 0x3FFF) + 1))
+        }
+        // Extended: 24-bit canvas size, stored minus one
+        b"VP8X" => Some((le_u24(bytes, 24)? + 1, le_u24(bytes, 27)? + 1)),
+        _ => None,
+    }
+}
+
+static SVG_TAG: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"<svg\b[^>]*>").unwrap());
+static SVG_ATTRIBUTE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"\s(width|height|viewBox)\s*=\s*(?:"([^"]*)"|'([^']*)')"#).unwrap()
+});
+
+/// Size from the root element's `width`/`height`, falling back to `viewBox`.
+fn svg_size(text: &str) -> Option<(u32, u32)> {
+    let tag = SVG_TAG.find(text)?.as_str();
+    let (mut width, mut height, mut view_box) = (None, None, None);
+    for captures in SVG_ATTRIBUTE.captures_iter(tag) {
+        let value = captures.get(2).or_else(|| captures.get(3))?.as_str();
+        match &captures[1] {
+            "width" => width = svg_length(value),
+            "height" => height = svg_length(value),
+            _ => view_box = Some(value),
+        }
+    }
+    if let (Some(width), Some(height)) = (width, height) {
+        return Some((width, height));
+    }
+
+    let numbers: Vec<f64> = view_box?
+        .split([' ', ','])
+        .filter(|part| !part.is_empty())
+        .map(str::parse)
+        .collect::<Result<_, _>>()
+        .ok()?;
+    let [_, _, view_width, view_height] = numbers[..] else {
+        return None;
+    };
+    let view_width = view_width.round() as u32;
+    let view_height = view_height.round() as u32;
+    // Keep an explicit dimension and scale the other by the aspect ratio
+    match (width, height) {
+        (Some(width), None) if view_width > 0 => Some((
+            width,
+            (f64::from(width) * f64::from(view_height) / f64::from(view_width)).round() as u32,
+        )),
+        (None, Some(height)) if view_height > 0 => Some((
+            (f64::from(height) * f64::from(view_width) / f64::from(view_height)).round() as u32,
+            height,
+        )),
+        _ => Some((view_width, view_height)),
+    }
+}
+
+/// Absolute length in pixels; relative units (`%`, `em`) have no intrinsic size.
+fn svg_length(value: &str) -> Option<u32> {
+    let value = value.trim();
+    let number = value.strip_suffix("px").unwrap_or(value);
+    number
+        .parse::<f64>()
+        .ok()
+        .filter(|number| *number >= 0.0)
+        .map(|number| number.round() as u32)
+}
+
+fn be_u16(bytes: &[u8], offset: usize) -> Option<u16> {
+    Some(u16::from_be_bytes(
+        bytes.get(offset..offset + 2)?.try_into().ok()?,
+    ))
+}
+
+fn be_u32(bytes: &[u8], offset: usize) -> Option<u32> {
+    Some(u32::from_be_bytes(
+        bytes.get(offset..offset + 4)?.try_into().ok()?,
+    ))
+}
+
+fn le_u16(bytes: &[u8], offset: usize) -> Option<u16> {
+    Some(u16::from_le_bytes(
+        bytes.get(offset..offset + 2)?.try_into().ok()?,
+    ))
+}
+
+fn le_u24(bytes: &[u8], offset: usize) -> Option<u32> {
+    let bytes = bytes.get(offset..offset + 3)?;
+    Some(u32::from(bytes[0]) | u32::from(bytes[1]) << 8 | u32::from(bytes[2]) << 16)
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    /// Minimal PNG header (signature + IHDR) for the given size
+    pub(crate) fn png(width: u32, height: u32) -> Vec<u8> {
+        let mut bytes = b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR".to_vec();
+        bytes.extend_from_slice(&width.to_be_bytes());
+        bytes.extend_from_slice(&height.to_be_bytes());
+        bytes.extend_from_slice(&[8, 6, 0, 0, 0]);
+        bytes
+    }
+
+    #[test]
+    fn test_png_and_gif() {
+        assert_eq!(
+            probe(&png(640, 480)),
+            Some((ImageFormat::Png, Some((640, 480))))
+        );
+        assert_eq!(
+            probe(b"GIF89a\x20\x03\x58\x02\0\0"),
+            Some((ImageFormat::Gif, Some((800, 600))))
+        );
+    }
+
+    #[test]
+    fn test_jpeg() {
+        let mut bytes = vec![0xFF, 0xD8];
+        // APP0 segment (length 16) to skip
+        bytes.extend_from_slice(&[0xFF, 0xE0, 0x00, 0x10]);
+        bytes.extend_from_slice(&[0; 14]);
+        // SOF2 (progressive): length, precision, height 300, width 400
+        bytes.extend_from_slice(&[0xFF, 0xC2, 0x00, 0x11, 0x08, 0x01, 0x2C, 0x01, 0x90]);
+        assert_eq!(probe(&bytes), Some((ImageFormat::Jpeg, Some((400, 300)))));
+
+        assert_eq!(probe(&[0xFF, 0xD8, 0xFF]), Some((ImageFormat::Jpeg, None)));
+    }
+
+    #[test]
+    fn test_webp() {
+        let mut lossy = b"RIFF\0\0\0\0WEBPVP8 \0\0\0\0\0\0\0\x9d\x01\x2a".to_vec();
+        lossy.extend_from_slice(&[0x80, 0x02, 0xE0, 0x01]);
+        assert_eq!(probe(&lossy), Some((ImageFormat::Webp, Some((640, 480)))));
+
+        let mut extended = b"RIFF\0\0\0\0WEBPVP8X\0\0\0\0\0\0\0\0".to_vec();
+        extended.extend_from_slice(&[0x1F, 0x03, 0x00, 0x57, 0x02, 0x00]);
+        assert_eq!(
+            probe(&extended),
+            Some((ImageFormat::Webp, Some((800, 600))))
+        );
+
+        // 100x50: (100 - 1) | (50 - 1) << 14
+        let bits: u32 = 99 | (49 << 14);
+        let mut lossless = b"RIFF\0\0\0\0WEBPVP8L\0\0\0\0\x2f".to_vec();
+        lossless.extend_from_slice(&bits.to_le_bytes());
+        assert_eq!(probe(&lossless), Some((ImageFormat::Webp, Some((100, 50)))));
+    }
+
+    #[test]
+    fn test_svg() {
+        let svg = |root: &str| probe(format!("<?xml version=\"1.0\"?>{}</svg>", root).as_bytes());
+        assert_eq!(
+            svg(r#"<svg width="120px" height='60' xmlns="http://www.w3.org/2000/svg">"#),
+            Some((ImageFormat::Svg, Some((120, 60))))
+        );
+        assert_eq!(
+            svg(r#"<svg viewBox="0 0 24 12">"#),
+            Some((ImageFormat::Svg, Some((24, 12))))
+        );
+        assert_eq!(
+            svg(r#"<svg width="48" viewBox="0,0,24,12">"#),
+            Some((ImageFormat::Svg, Some((48, 24))))
+        );
+        assert_eq!(
+            svg(r#"<svg width="100%" height="100%">"#),
+            Some((ImageFormat::Svg, None))
+        );
+    }
+
+    #[test]
+    fn test_unknown_format() {
+        assert_eq!(probe(b"not an image"), None);
+        assert_eq!(probe(&[0x89, b'P']), None);
+    }
+}
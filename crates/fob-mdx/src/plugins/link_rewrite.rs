@@ -0,0 +1,334 @@
+//! Link rewriting plugin for relative markdown links
+//!
+//! Documents usually link to each other by file (`[Next](./other-post.mdx)`),
+//! while the site serves them under routes (`/blog/other-post`). This plugin
+//! rewrites such links at compile time, either from a route pattern or with a
+//! custom callback.
+//!
+//! # Features
+//!
+//! - Rewrites relative links to `.md`/`.mdx` files; other links are untouched
+//! - Handles inline links, link definitions and JSX `<a href="...">`
+//! - Keeps `#fragment` and `?query` suffixes
+//!
+//! # Example
+//!
+//! With the pattern `/blog/{slug}`:
+//!
+//! ```markdown
+//! [Next post](./other-post.mdx#comments)
+//! ```
+//!
+//! Generates:
+//! - `<a href="/blog/other-post#comments">Next post</a>`
+
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use anyhow::Result;
+use markdown::mdast::{AttributeContent, AttributeValue, Node};
+use path_clean::PathClean;
+
+use super::MdxPlugin;
+
+/// Callback mapping a link path to a URL
+pub type LinkResolver = dyn Fn(&str) -> Option<String> + Send + Sync;
+
+/// How relative markdown links are resolved to URLs
+#[derive(Clone)]
+pub enum LinkRewrite {
+    /// Route pattern with placeholders:
+    ///
+    /// - `{slug}`: file name without extension (`./posts/hello.mdx` → `hello`)
+    /// - `{path}`: path without extension, resolved from the document's
+    ///   directory (`../guide.mdx` in `posts/a.mdx` → `guide`)
+    Pattern(String),
+    /// Callback receiving the link path (without fragment or query).
+    /// Returning `None` leaves the link unchanged.
+    Custom(Arc<LinkResolver>),
+}
+
+impl LinkRewrite {
+    /// Rewrite links with a route pattern such as `/blog/{slug}`
+    pub fn pattern(pattern: impl Into<String>) -> Self {
+        Self::Pattern(pattern.into())
+    }
+
+    /// Rewrite links with a custom callback
+    pub fn custom(rewrite: impl Fn(&str) -> Option<String> + Send + Sync + 'static) -> Self {
+        Self::Custom(Arc::new(rewrite))
+    }
+}
+
+impl std::fmt::Debug for LinkRewrite {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Pattern(pattern) => f.debug_tuple("Pattern").field(pattern).finish(),
+            Self::Custom(_) => f.write_str("Custom(..)"),
+        }
+    }
+}
+
+/// Plugin that rewrites relative links to markdown files
+///
+/// Enabled through `MdxCompileOptions::link_rewrite`, or added manually:
+///
+/// ```rust
+/// use fob_mdx::plugins::{LinkRewrite, LinkRewritePlugin};
+///
+/// let plugin = LinkRewritePlugin::new(LinkRewrite::pattern("/docs/{path}"));
+/// assert_eq!(plugin.rewrite("./guides/setup.mdx#install").as_deref(), Some("/docs/guides/setup#install"));
+/// ```
+#[derive(Debug, Clone)]
+pub struct LinkRewritePlugin {
+    rewrite: LinkRewrite,
+    document_dir: PathBuf,
+}
+
+impl LinkRewritePlugin {
+    /// Create a link rewriting plugin
+    pub fn new(rewrite: LinkRewrite) -> Self {
+        Self {
+            rewrite,
+            document_dir: PathBuf::new(),
+        }
+    }
+
+    /// Resolve links from the directory of `document`, a path relative to the
+    /// route root such as `posts/a.mdx`
+    pub fn with_document(mut self, document: impl AsRef<Path>) -> Self {
+        self.document_dir = document
+            .as_ref()
+            .parent()
+            .map(Path::to_path_buf)
+            .unwrap_or_default();
+        self
+    }
+
+    /// Rewritten URL for a link, or `None` if it stays as written
+    pub fn rewrite(&self, url: &str) -> Option<String> {
+        if !is_relative(url) {
+            return None;
+        }
+
+        let split = url.find(['#', '?']).unwrap_or(url.len());
+        let (path, suffix) = url.split_at(split);
+        let stem = path
+            .strip_suffix(".mdx")
+            .or_else(|| path.strip_suffix(".md"))?;
+
+        let target = match &self.rewrite {
+            LinkRewrite::Pattern(pattern) => {
+                let resolved = self
+                    .document_dir
+                    .join(stem)
+                    .clean()
+                    .components()
+                    .map(|component| component.as_os_str().to_string_lossy())
+                    .collect::<Vec<_>>()
+                    .join("/");
+                let slug = resolved.rsplit('/').next().unwrap_or_default();
+                pattern.replace("{path}", &resolved).replace("{slug}", slug)
+            }
+            LinkRewrite::Custom(rewrite) => rewrite(path)?,
+        };
+        Some(format!("{}{}", target, suffix))
+    }
+
+    fn rewrite_url(&self, url: &mut String) {
+        if let Some(rewritten) = self.rewrite(url) {
+            *url = rewritten;
+        }
+    }
+
+    fn transform_node(&self, node: &mut Node) {
+        match node {
+            Node::Link(link) => self.rewrite_url(&mut link.url),
+            Node::Definition(definition) => self.rewrite_url(&mut definition.url),
+            Node::MdxJsxFlowElement(element) if element.name.as_deref() == Some("a") => {
+                self.rewrite_href(&mut element.attributes)
+            }
+            Node::MdxJsxTextElement(element) if element.name.as_deref() == Some("a") => {
+                self.rewrite_href(&mut element.attributes)
+            }
+            _ => {}
+        }
+        if let Some(children) = node.children_mut() {
+            for child in children {
+                self.transform_node(child);
+            }
+        }
+    }
+
+    fn rewrite_href(&self, attributes: &mut [AttributeContent]) {
+        for attribute in attributes {
+            let AttributeContent::Property(property) = attribute else {
+                continue;
+            };
+            if property.name != "href" {
+                continue;
+            }
+            if let Some(AttributeValue::Literal(url)) = &mut property.value {
+                self.rewrite_url(url);
+            }
+        }
+    }
+}
+
+impl MdxPlugin for LinkRewritePlugin {
+    fn name(&self) -> &'static str {
+        "link-rewrite"
+    }
+
+    fn transform_ast(&self, ast: &mut Node) -> Result<()> {
+        self.transform_node(ast);
+        Ok(())
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+/// Outgoing link URLs in document order, without duplicates.
+///
+/// Includes markdown links, link definitions and literal `href`s of JSX
+/// `<a>` elements; fragment-only links (`#section`) are skipped.
+pub(crate) fn collect_links(node: &Node) -> Vec<String> {
+    fn walk(node: &Node, links: &mut Vec<String>) {
+        let url = match node {
+            Node::Link(link) => Some(link.url.as_str()),
+            Node::Definition(definition) => Some(definition.url.as_str()),
+            Node::MdxJsxFlowElement(element) if element.name.as_deref() == Some("a") => {
+                literal_href(&element.attributes)
+            }
+            Node::MdxJsxTextElement(element) if element.name.as_deref() == Some("a") => {
+                literal_href(&element.attributes)
+            }
+            _ => None,
+        };
+        if let Some(url) = url.filter(|url| !url.is_empty() && !url.starts_with('#')) {
+            if !links.iter().any(|link| link == url) {
+                links.push(url.to_string());
+            }
+        }
+        for child in node.children().into_iter().flatten() {
+            walk(child, links);
+        }
+    }
+
+    let mut links = Vec::new();
+    walk(node, &mut links);
+    links
+}
+
+fn literal_href(attributes: &[AttributeContent]) -> Option<&str> {
+    attributes.iter().find_map(|attribute| match attribute {
+        AttributeContent::Property(property) if property.name == "href" => match &property.value {
+            Some(AttributeValue::Literal(url)) => Some(url.as_str()),
+            _ => None,
+        },
+        _ => None,
+    })
+}
+
+/// Relative path: no scheme, not absolute, not protocol-relative, not an anchor.
+fn is_relative(url: &str) -> bool {
+    if url.is_empty() || url.starts_with(['/', '#', '?']) {
+        return false;
+    }
+    // `scheme:` (https:, mailto:, ...) before any path separator
+    let scheme_end = url.find(':');
+    let path_start = url.find(['/', '#', '?']).unwrap_or(url.len());
+    !matches!(scheme_end, Some(end) if end < path_start)
+}
+
+/// Path of a compiled file relative to the route root, taken as the working
+/// directory; `None` for absolute paths outside it.
+pub(crate) fn document_path(filepath: &str) -> Option<PathBuf> {
+    let path = Path::new(filepath);
+    if path.is_relative() {
+        return Some(path.to_path_buf());
+    }
+    let cwd = std::env::current_dir().ok()?;
+    path.strip_prefix(cwd).ok().map(Path::to_path_buf)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pattern_rewrite() {
+        let plugin = LinkRewritePlugin::new(LinkRewrite::pattern("/blog/{slug}"));
+        assert_eq!(
+            plugin.rewrite("./other-post.mdx").as_deref(),
+            Some("/blog/other-post")
+        );
+        assert_eq!(
+            plugin.rewrite("../drafts/idea.md?x=1#top").as_deref(),
+            Some("/blog/idea?x=1#top")
+        );
+
+        let plugin = LinkRewritePlugin::new(LinkRewrite::pattern("/docs/{path}/"));
+        assert_eq!(
+            plugin.rewrite("./guides/../api/index.mdx").as_deref(),
+            Some("/docs/api/index/")
+        );
+        assert_eq!(
+            plugin.rewrite("../intro.mdx").as_deref(),
+            Some("/docs/../intro/")
+        );
+    }
+
+    #[test]
+    fn test_links_resolve_from_document() {
+        let plugin = LinkRewritePlugin::new(LinkRewrite::pattern("/docs/{path}"))
+            .with_document("posts/a.mdx");
+        assert_eq!(
+            plugin.rewrite("../guide.mdx").as_deref(),
+            Some("/docs/guide")
+        );
+        assert_eq!(
+            plugin.rewrite("./b.mdx#top").as_deref(),
+            Some("/docs/posts/b#top")
+        );
+        assert_eq!(
+            plugin.rewrite("drafts/c.md").as_deref(),
+            Some("/docs/posts/drafts/c")
+        );
+    }
+
+    #[test]
+    fn test_non_markdown_and_absolute_links_are_kept() {
+        let plugin = LinkRewritePlugin::new(LinkRewrite::pattern("/blog/{slug}"));
+        for url in [
+            "https://example.com/post.mdx",
+            "/blog/post.mdx",
+            "//cdn.example.com/a.md",
+            "#section",
+            "mailto:me@example.com",
+            "./image.png",
+            "",
+        ] {
+            assert_eq!(plugin.rewrite(url), None, "{}", url);
+        }
+    }
+
+    #[test]
+    fn test_custom_rewrite() {
+        let plugin = LinkRewritePlugin::new(LinkRewrite::custom(|path| {
+            (path != "./skip.mdx").then(|| format!("/p/{}", path.trim_start_matches("./")))
+        }));
+        assert_eq!(plugin.rewrite("./a.mdx#x").as_deref(), Some("/p/a.mdx#x"));
+        assert_eq!(plugin.rewrite("./skip.mdx"), None);
+    }
+
+    #[test]
+    fn test_is_relative() {
+        assert!(is_relative("post.mdx"));
+        assert!(is_relative("./a:b.mdx"));
+        assert!(!is_relative("c:foo"));
+        assert!(!is_relative("/abs"));
+    }
+}
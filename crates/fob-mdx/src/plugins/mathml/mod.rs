@@ -0,0 +1,159 @@
+//! MathML plugin for rendering math at compile time
+//!
+//! Replaces `$...$` and `$$...$$` nodes with `<math>` elements, so formulas
+//! display in any browser with MathML Core support and no client-side math
+//! library. The TeX source is kept in an `application/x-tex` annotation.
+//!
+//! # Example
+//!
+//! ```markdown
+//! The area is $\pi r^2$.
+//! ```
+//!
+//! Generates:
+//! - `<math display="inline"><semantics><mrow><mi>π</mi><msup>…</msup></mrow>…</semantics></math>`
+
+mod tex;
+
+use std::any::Any;
+
+use anyhow::Result;
+use markdown::mdast::{
+    AttributeContent, AttributeValue, MdxJsxAttribute, MdxJsxFlowElement, MdxJsxTextElement, Node,
+    Text,
+};
+
+use super::MdxPlugin;
+use tex::{MathNode, tex_to_mathml};
+
+/// Plugin that converts math nodes to MathML elements
+///
+/// Enabled by [`MathOutput::MathMl`](crate::MathOutput::MathMl). It runs after
+/// the default plugins and before user plugins, which see `math` JSX
+/// elements instead of math nodes.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct MathMlPlugin;
+
+impl MathMlPlugin {
+    /// Create a new MathML plugin
+    pub fn new() -> Self {
+        Self
+    }
+
+    fn transform_node(node: &mut Node) {
+        match node {
+            Node::Math(math) => {
+                let Node::MdxJsxTextElement(element) = to_jsx(tex_to_mathml(&math.value, true))
+                else {
+                    unreachable!("MathML conversion always produces an element");
+                };
+                *node = Node::MdxJsxFlowElement(MdxJsxFlowElement {
+                    name: element.name,
+                    attributes: element.attributes,
+                    children: element.children,
+                    position: math.position.clone(),
+                });
+            }
+            Node::InlineMath(math) => {
+                let position = math.position.clone();
+                *node = to_jsx(tex_to_mathml(&math.value, false));
+                if let Node::MdxJsxTextElement(element) = node {
+                    element.position = position;
+                }
+            }
+            _ => {
+                if let Some(children) = node.children_mut() {
+                    children.iter_mut().for_each(Self::transform_node);
+                }
+            }
+        }
+    }
+}
+
+/// Convert a MathML tree to JSX text elements.
+fn to_jsx(node: MathNode) -> Node {
+    match node {
+        MathNode::Text(value) => Node::Text(Text {
+            value,
+            position: None,
+        }),
+        MathNode::Element {
+            tag,
+            attributes,
+            children,
+        } => Node::MdxJsxTextElement(MdxJsxTextElement {
+            name: Some(tag.to_string()),
+            attributes: attributes
+                .into_iter()
+                .map(|(name, value)| {
+                    AttributeContent::Property(MdxJsxAttribute {
+                        name: name.to_string(),
+                        value: Some(AttributeValue::Literal(value)),
+                    })
+                })
+                .collect(),
+            children: children.into_iter().map(to_jsx).collect(),
+            position: None,
+        }),
+    }
+}
+
+impl MdxPlugin for MathMlPlugin {
+    fn name(&self) -> &'static str {
+        "mathml"
+    }
+
+    fn transform_ast(&self, ast: &mut Node) -> Result<()> {
+        Self::transform_node(ast);
+        Ok(())
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use markdown::mdast::{InlineMath, Math, Paragraph, Root};
+
+    #[test]
+    fn test_replaces_math_nodes() {
+        let mut ast = Node::Root(Root {
+            children: vec![
+                Node::Math(Math {
+                    value: "x".to_string(),
+                    meta: None,
+                    position: None,
+                }),
+                Node::Paragraph(Paragraph {
+                    children: vec![Node::InlineMath(InlineMath {
+                        value: "y".to_string(),
+                        position: None,
+                    })],
+                    position: None,
+                }),
+            ],
+            position: None,
+        });
+
+        MathMlPlugin::new().transform_ast(&mut ast).unwrap();
+
+        let children = ast.children().unwrap();
+        let Node::MdxJsxFlowElement(block) = &children[0] else {
+            panic!("expected a flow element, got {:?}", children[0]);
+        };
+        assert_eq!(block.name.as_deref(), Some("math"));
+        let Node::MdxJsxTextElement(inline) = &children[1].children().unwrap()[0] else {
+            panic!("expected a text element");
+        };
+        assert_eq!(inline.name.as_deref(), Some("math"));
+        assert!(inline.attributes.iter().any(|attribute| matches!(
+            attribute,
+            AttributeContent::Property(property)
+                if property.name == "display"
+                    && property.value == Some(AttributeValue::Literal("inline".to_string()))
+        )));
+    }
+}
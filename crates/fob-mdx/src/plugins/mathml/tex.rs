@@ -0,0 +1,934 @@
+//! TeX math to MathML conversion
+//!
+//! Covers the LaTeX math subset commonly used in documentation: scripts,
+//! fractions, roots, Greek letters and symbols, `\left`/`\right`, accents,
+//! font commands, `\text`, spacing and matrix-like environments. Unknown
+//! commands are kept as `<merror>` so the rest of the formula still renders.
+
+/// MathML element or text
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum MathNode {
+    Element {
+        tag: &'static str,
+        attributes: Vec<(&'static str, String)>,
+        children: Vec<MathNode>,
+    },
+    Text(String),
+}
+
+impl MathNode {
+    fn element(tag: &'static str, children: Vec<MathNode>) -> Self {
+        Self::Element {
+            tag,
+            attributes: Vec::new(),
+            children,
+        }
+    }
+
+    fn token(tag: &'static str, text: impl Into<String>) -> Self {
+        Self::element(tag, vec![Self::Text(text.into())])
+    }
+
+    fn with_attribute(mut self, name: &'static str, value: impl Into<String>) -> Self {
+        if let Self::Element { attributes, .. } = &mut self {
+            attributes.retain(|(existing, _)| *existing != name);
+            attributes.push((name, value.into()));
+        }
+        self
+    }
+
+    /// Wrap several nodes in an `mrow`, or return a single node as-is.
+    fn row(mut children: Vec<MathNode>) -> Self {
+        if children.len() == 1 {
+            children.pop().unwrap()
+        } else {
+            Self::element("mrow", children)
+        }
+    }
+
+    /// Apply a `mathvariant` to every identifier and number in the tree.
+    fn with_variant(self, variant: &str) -> Self {
+        match self {
+            Self::Element {
+                tag: "mi" | "mn", ..
+            } => self.with_attribute("mathvariant", variant),
+            Self::Element {
+                tag,
+                attributes,
+                children,
+            } => Self::Element {
+                tag,
+                attributes,
+                children: children
+                    .into_iter()
+                    .map(|child| child.with_variant(variant))
+                    .collect(),
+            },
+            text => text,
+        }
+    }
+}
+
+/// Convert TeX source to a `<math>` element.
+///
+/// The original source is kept as an `application/x-tex` annotation.
+pub(crate) fn tex_to_mathml(tex: &str, display: bool) -> MathNode {
+    let body = MathNode::row(Parser::new(tex).parse_row(Stop::End).nodes);
+    let semantics = MathNode::element(
+        "semantics",
+        vec![
+            body,
+            MathNode::token("annotation", tex).with_attribute("encoding", "application/x-tex"),
+        ],
+    );
+    MathNode::element("math", vec![semantics])
+        .with_attribute("xmlns", "http://www.w3.org/1998/Math/MathML")
+        .with_attribute("display", if display { "block" } else { "inline" })
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Token<'a> {
+    /// `\name` or `\` followed by a single symbol
+    Command(&'a str),
+    Open,
+    Close,
+    Sup,
+    Sub,
+    Align,
+    Number(&'a str),
+    Letter(char),
+    Symbol(char),
+}
+
+/// Where a row of nodes stops
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Stop {
+    End,
+    Brace,
+    Right,
+    Environment,
+}
+
+/// What ended a row
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum RowEnd {
+    End,
+    Brace,
+    Right,
+    Cell,
+    Line,
+    Environment,
+}
+
+struct Row {
+    nodes: Vec<MathNode>,
+    end: RowEnd,
+}
+
+struct Parser<'a> {
+    source: &'a str,
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn new(source: &'a str) -> Self {
+        Self { source, pos: 0 }
+    }
+
+    fn rest(&self) -> &'a str {
+        &self.source[self.pos..]
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = self.rest();
+        self.pos += rest.len() - rest.trim_start().len();
+    }
+
+    fn peek(&mut self) -> Option<Token<'a>> {
+        let start = self.pos;
+        let token = self.next_token();
+        self.pos = start;
+        token
+    }
+
+    fn next_token(&mut self) -> Option<Token<'a>> {
+        self.skip_whitespace();
+        let rest = self.rest();
+        let c = rest.chars().next()?;
+        self.pos += c.len_utf8();
+
+        Some(match c {
+            '\\' => {
+                let after = self.rest();
+                let name_len = after
+                    .find(|c: char| !c.is_ascii_alphabetic())
+                    .unwrap_or(after.len());
+                let name_len = if name_len == 0 {
+                    after.chars().next().map_or(0, char::len_utf8)
+                } else {
+                    name_len
+                };
+                self.pos += name_len;
+                Token::Command(&after[..name_len])
+            }
+            '{' => Token::Open,
+            '}' => Token::Close,
+            '^' => Token::Sup,
+            '_' => Token::Sub,
+            '&' => Token::Align,
+            c if c.is_ascii_digit() => {
+                let len = rest
+                    .char_indices()
+                    .find(|&(i, c)| {
+                        !(c.is_ascii_digit()
+                            || c == '.' && rest[i + 1..].starts_with(|c: char| c.is_ascii_digit()))
+                    })
+                    .map_or(rest.len(), |(i, _)| i);
+                self.pos = self.pos - 1 + len;
+                Token::Number(&rest[..len])
+            }
+            c if c.is_alphabetic() => Token::Letter(c),
+            c => Token::Symbol(c),
+        })
+    }
+
+    /// Read a `{...}` group verbatim, or a single character without braces.
+    fn raw_argument(&mut self) -> &'a str {
+        self.skip_whitespace();
+        let rest = self.rest();
+        if let Some(inner) = rest.strip_prefix('{') {
+            let mut depth = 1;
+            for (i, c) in inner.char_indices() {
+                match c {
+                    '{' => depth += 1,
+                    '}' => {
+                        depth -= 1;
+                        if depth == 0 {
+                            self.pos += i + 2;
+                            return &inner[..i];
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            self.pos = self.source.len();
+            inner
+        } else {
+            let len = rest.chars().next().map_or(0, char::len_utf8);
+            self.pos += len;
+            &rest[..len]
+        }
+    }
+
+    /// Read an optional `[...]` argument verbatim.
+    fn optional_argument(&mut self) -> Option<&'a str> {
+        self.skip_whitespace();
+        let inner = self.rest().strip_prefix('[')?;
+        let end = inner.find(']')?;
+        self.pos += end + 2;
+        Some(&inner[..end])
+    }
+
+    /// Parse a command or group argument.
+    fn argument(&mut self) -> MathNode {
+        match self.peek() {
+            Some(Token::Open) => {
+                self.next_token();
+                MathNode::row(self.parse_row(Stop::Brace).nodes)
+            }
+            Some(Token::Close) | None => MathNode::element("mrow", Vec::new()),
+            Some(_) => self
+                .atom()
+                .unwrap_or_else(|| MathNode::element("mrow", Vec::new())),
+        }
+    }
+
+    fn parse_row(&mut self, stop: Stop) -> Row {
+        let mut nodes = Vec::new();
+        let end = loop {
+            let Some(token) = self.peek() else {
+                break RowEnd::End;
+            };
+            match token {
+                Token::Close => {
+                    self.next_token();
+                    if stop == Stop::Brace {
+                        break RowEnd::Brace;
+                    }
+                }
+                Token::Command("right") if stop == Stop::Right => break RowEnd::Right,
+                Token::Command("end") if stop == Stop::Environment => {
+                    break RowEnd::Environment;
+                }
+                Token::Align if stop == Stop::Environment => {
+                    self.next_token();
+                    break RowEnd::Cell;
+                }
+                Token::Command("\\") if stop == Stop::Environment => {
+                    self.next_token();
+                    break RowEnd::Line;
+                }
+                Token::Align | Token::Command("\\") => {
+                    self.next_token();
+                }
+                _ => {
+                    if let Some(node) = self.atom_with_scripts() {
+                        nodes.push(node);
+                    }
+                }
+            }
+        };
+        Row { nodes, end }
+    }
+
+    fn atom_with_scripts(&mut self) -> Option<MathNode> {
+        let base = match self.peek() {
+            Some(Token::Sup | Token::Sub) => Some(MathNode::element("mrow", Vec::new())),
+            _ => self.atom(),
+        }?;
+        let limits = is_limit_operator(&base);
+
+        let mut sub = None;
+        let mut sup = None;
+        loop {
+            match self.peek() {
+                Some(Token::Sup) if sup.is_none() => {
+                    self.next_token();
+                    sup = Some(self.argument());
+                }
+                Some(Token::Sub) if sub.is_none() => {
+                    self.next_token();
+                    sub = Some(self.argument());
+                }
+                Some(Token::Symbol('\'')) if sup.is_none() => {
+                    let mut primes = String::new();
+                    while self.peek() == Some(Token::Symbol('\'')) {
+                        self.next_token();
+                        primes.push('′');
+                    }
+                    sup = Some(MathNode::token("mo", primes));
+                }
+                _ => break,
+            }
+        }
+
+        let (sub_tag, sup_tag, both_tag) = if limits {
+            ("munder", "mover", "munderover")
+        } else {
+            ("msub", "msup", "msubsup")
+        };
+        Some(match (sub, sup) {
+            (None, None) => base,
+            (Some(sub), None) => MathNode::element(sub_tag, vec![base, sub]),
+            (None, Some(sup)) => MathNode::element(sup_tag, vec![base, sup]),
+            (Some(sub), Some(sup)) => MathNode::element(both_tag, vec![base, sub, sup]),
+        })
+    }
+
+    fn atom(&mut self) -> Option<MathNode> {
+        Some(match self.next_token()? {
+            Token::Number(number) => MathNode::token("mn", number),
+            Token::Letter(c) => MathNode::token("mi", c),
+            Token::Symbol(c) => MathNode::token(
+                "mo",
+                match c {
+                    '-' => '−',
+                    '*' => '∗',
+                    '\'' => '′',
+                    c => c,
+                },
+            ),
+            Token::Open => MathNode::row(self.parse_row(Stop::Brace).nodes),
+            Token::Command(name) => return self.command(name),
+            Token::Close | Token::Sup | Token::Sub | Token::Align => return None,
+        })
+    }
+
+    fn command(&mut self, name: &'a str) -> Option<MathNode> {
+        if let Some(node) = symbol(name) {
+            return Some(node);
+        }
+
+        Some(match name {
+            "frac" | "dfrac" | "tfrac" | "cfrac" => {
+                let numerator = self.argument();
+                let denominator = self.argument();
+                MathNode::element("mfrac", vec![numerator, denominator])
+            }
+            "binom" => {
+                let top = self.argument();
+                let bottom = self.argument();
+                MathNode::element(
+                    "mrow",
+                    vec![
+                        MathNode::token("mo", "("),
+                        MathNode::element("mfrac", vec![top, bottom])
+                            .with_attribute("linethickness", "0"),
+                        MathNode::token("mo", ")"),
+                    ],
+                )
+            }
+            "sqrt" => {
+                let index = self.optional_argument();
+                let radicand = self.argument();
+                match index {
+                    Some(index) => {
+                        let index = MathNode::row(Parser::new(index).parse_row(Stop::End).nodes);
+                        MathNode::element("mroot", vec![radicand, index])
+                    }
+                    None => MathNode::element("msqrt", vec![radicand]),
+                }
+            }
+            "text" | "textrm" | "textnormal" | "mbox" | "hbox" => {
+                MathNode::token("mtext", self.raw_argument())
+            }
+            "textbf" => {
+                MathNode::token("mtext", self.raw_argument()).with_attribute("mathvariant", "bold")
+            }
+            "textit" => MathNode::token("mtext", self.raw_argument())
+                .with_attribute("mathvariant", "italic"),
+            "operatorname" => MathNode::token("mi", self.raw_argument().trim()),
+            "left" => self.fenced(),
+            "begin" => self.environment(),
+            // Sizing and style switches don't change the structure
+            "displaystyle" | "textstyle" | "scriptstyle" | "limits" | "nolimits" | "big"
+            | "Big" | "bigg" | "Bigg" | "bigl" | "bigr" | "Bigl" | "Bigr" | "right" | "end"
+            | "\\" => return None,
+            "pmod" => {
+                let argument = self.argument();
+                MathNode::element(
+                    "mrow",
+                    vec![
+                        MathNode::token("mo", "("),
+                        MathNode::token("mo", "mod"),
+                        argument,
+                        MathNode::token("mo", ")"),
+                    ],
+                )
+            }
+            _ => {
+                if let Some(variant) = font_variant(name) {
+                    return Some(self.argument().with_variant(variant));
+                }
+                if let Some(accent) = accent(name) {
+                    let base = self.argument();
+                    return Some(if name == "underline" || name == "underbrace" {
+                        MathNode::element("munder", vec![base, MathNode::token("mo", accent)])
+                            .with_attribute("accentunder", "true")
+                    } else {
+                        MathNode::element("mover", vec![base, MathNode::token("mo", accent)])
+                            .with_attribute("accent", "true")
+                    });
+                }
+                if let Some(width) = space(name) {
+                    return Some(
+                        MathNode::element("mspace", Vec::new()).with_attribute("width", width),
+                    );
+                }
+                MathNode::element(
+                    "merror",
+                    vec![MathNode::token("mtext", format!("\\{}", name))],
+                )
+            }
+        })
+    }
+
+    /// `\left( ... \right)`
+    fn fenced(&mut self) -> MathNode {
+        let open = self.delimiter();
+        let inner = self.parse_row(Stop::Right);
+        let close = if inner.end == RowEnd::Right {
+            self.next_token();
+            self.delimiter()
+        } else {
+            None
+        };
+
+        let mut children = Vec::new();
+        children.extend(open.map(fence));
+        children.extend(inner.nodes);
+        children.extend(close.map(fence));
+        MathNode::element("mrow", children)
+    }
+
+    /// Delimiter after `\left`/`\right`; `.` means none.
+    fn delimiter(&mut self) -> Option<String> {
+        match self.next_token()? {
+            Token::Symbol('.') => None,
+            Token::Symbol(c) => Some(c.to_string()),
+            Token::Command(name) => match symbol(name) {
+                Some(MathNode::Element { children, .. }) => match children.first() {
+                    Some(MathNode::Text(text)) => Some(text.clone()),
+                    _ => None,
+                },
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    /// `\begin{name} ... \end{name}`
+    fn environment(&mut self) -> MathNode {
+        let name = self.raw_argument().trim().trim_end_matches('*');
+        if name == "array" {
+            // Column specification
+            self.raw_argument();
+        }
+
+        let mut rows = Vec::new();
+        let mut cells = Vec::new();
+        loop {
+            let cell = self.parse_row(Stop::Environment);
+            let end = cell.end;
+            cells.push(MathNode::element("mtd", vec![MathNode::row(cell.nodes)]));
+            match end {
+                RowEnd::Cell => {}
+                RowEnd::Line => rows.push(MathNode::element("mtr", std::mem::take(&mut cells))),
+                _ => {
+                    if end == RowEnd::Environment {
+                        self.next_token();
+                        self.raw_argument();
+                    }
+                    // Skip an empty trailing row left by a final `\\`
+                    let empty = cells.len() == 1
+                        && matches!(&cells[0], MathNode::Element { children, .. }
+                            if matches!(children.as_slice(), [MathNode::Element { tag: "mrow", children, .. }] if children.is_empty()));
+                    if !empty || rows.is_empty() {
+                        rows.push(MathNode::element("mtr", cells));
+                    }
+                    break;
+                }
+            }
+        }
+
+        let table = MathNode::element("mtable", rows);
+        let (open, close, table) = match name {
+            "pmatrix" => ("(", ")", table),
+            "bmatrix" => ("[", "]", table),
+            "Bmatrix" => ("{", "}", table),
+            "vmatrix" => ("|", "|", table),
+            "Vmatrix" => ("‖", "‖", table),
+            "cases" => ("{", "", table.with_attribute("columnalign", "left")),
+            "aligned" | "align" | "split" | "gathered" => (
+                "",
+                "",
+                table
+                    .with_attribute("columnalign", "right left")
+                    .with_attribute("columnspacing", "0"),
+            ),
+            _ => ("", "", table),
+        };
+        if open.is_empty() && close.is_empty() {
+            return table;
+        }
+
+        let mut children = Vec::new();
+        if !open.is_empty() {
+            children.push(fence(open.to_string()));
+        }
+        children.push(table);
+        if !close.is_empty() {
+            children.push(fence(close.to_string()));
+        }
+        MathNode::element("mrow", children)
+    }
+}
+
+fn fence(delimiter: String) -> MathNode {
+    MathNode::token("mo", delimiter)
+        .with_attribute("fence", "true")
+        .with_attribute("stretchy", "true")
+}
+
+/// Operators whose scripts are placed above and below (`\sum`, `\lim`).
+fn is_limit_operator(node: &MathNode) -> bool {
+    matches!(node, MathNode::Element { tag: "mo", attributes, .. }
+        if attributes.iter().any(|(name, _)| *name == "movablelimits"))
+}
+
+/// Greek letters, symbols, operators and function names.
+fn symbol(name: &str) -> Option<MathNode> {
+    const GREEK: &[(&str, &str)] = &[
+        ("alpha", "α"),
+        ("beta", "β"),
+        ("gamma", "γ"),
+        ("delta", "δ"),
+        ("epsilon", "ϵ"),
+        ("varepsilon", "ε"),
+        ("zeta", "ζ"),
+        ("eta", "η"),
+        ("theta", "θ"),
+        ("vartheta", "ϑ"),
+        ("iota", "ι"),
+        ("kappa", "κ"),
+        ("lambda", "λ"),
+        ("mu", "μ"),
+        ("nu", "ν"),
+        ("xi", "ξ"),
+        ("pi", "π"),
+        ("varpi", "ϖ"),
+        ("rho", "ρ"),
+        ("varrho", "ϱ"),
+        ("sigma", "σ"),
+        ("varsigma", "ς"),
+        ("tau", "τ"),
+        ("upsilon", "υ"),
+        ("phi", "ϕ"),
+        ("varphi", "φ"),
+        ("chi", "χ"),
+        ("psi", "ψ"),
+        ("omega", "ω"),
+    ];
+    const UPPER_GREEK: &[(&str, &str)] = &[
+        ("Gamma", "Γ"),
+        ("Delta", "Δ"),
+        ("Theta", "Θ"),
+        ("Lambda", "Λ"),
+        ("Xi", "Ξ"),
+        ("Pi", "Π"),
+        ("Sigma", "Σ"),
+        ("Upsilon", "Υ"),
+        ("Phi", "Φ"),
+        ("Psi", "Ψ"),
+        ("Omega", "Ω"),
+    ];
+    const IDENTIFIERS: &[(&str, &str)] = &[
+        ("infty", "∞"),
+        ("partial", "∂"),
+        ("nabla", "∇"),
+        ("emptyset", "∅"),
+        ("varnothing", "∅"),
+        ("hbar", "ℏ"),
+        ("ell", "ℓ"),
+        ("aleph", "ℵ"),
+        ("Re", "ℜ"),
+        ("Im", "ℑ"),
+        ("wp", "℘"),
+        ("imath", "ı"),
+        ("jmath", "ȷ"),
+    ];
+    const OPERATORS: &[(&str, &str)] = &[
+        ("cdot", "⋅"),
+        ("times", "×"),
+        ("div", "÷"),
+        ("pm", "±"),
+        ("mp", "∓"),
+        ("ast", "∗"),
+        ("star", "⋆"),
+        ("circ", "∘"),
+        ("bullet", "∙"),
+        ("oplus", "⊕"),
+        ("otimes", "⊗"),
+        ("leq", "≤"),
+        ("le", "≤"),
+        ("geq", "≥"),
+        ("ge", "≥"),
+        ("neq", "≠"),
+        ("ne", "≠"),
+        ("ll", "≪"),
+        ("gg", "≫"),
+        ("approx", "≈"),
+        ("equiv", "≡"),
+        ("sim", "∼"),
+        ("simeq", "≃"),
+        ("cong", "≅"),
+        ("propto", "∝"),
+        ("in", "∈"),
+        ("notin", "∉"),
+        ("ni", "∋"),
+        ("subset", "⊂"),
+        ("subseteq", "⊆"),
+        ("supset", "⊃"),
+        ("supseteq", "⊇"),
+        ("cup", "∪"),
+        ("cap", "∩"),
+        ("setminus", "∖"),
+        ("land", "∧"),
+        ("wedge", "∧"),
+        ("lor", "∨"),
+        ("vee", "∨"),
+        ("neg", "¬"),
+        ("lnot", "¬"),
+        ("forall", "∀"),
+        ("exists", "∃"),
+        ("to", "→"),
+        ("rightarrow", "→"),
+        ("leftarrow", "←"),
+        ("gets", "←"),
+        ("leftrightarrow", "↔"),
+        ("Rightarrow", "⇒"),
+        ("Leftarrow", "⇐"),
+        ("Leftrightarrow", "⇔"),
+        ("implies", "⟹"),
+        ("iff", "⟺"),
+        ("mapsto", "↦"),
+        ("uparrow", "↑"),
+        ("downarrow", "↓"),
+        ("perp", "⊥"),
+        ("parallel", "∥"),
+        ("mid", "∣"),
+        ("angle", "∠"),
+        ("prime", "′"),
+        ("ldots", "…"),
+        ("dots", "…"),
+        ("cdots", "⋯"),
+        ("vdots", "⋮"),
+        ("ddots", "⋱"),
+        ("langle", "⟨"),
+        ("rangle", "⟩"),
+        ("lfloor", "⌊"),
+        ("rfloor", "⌋"),
+        ("lceil", "⌈"),
+        ("rceil", "⌉"),
+        ("lbrace", "{"),
+        ("rbrace", "}"),
+        ("{", "{"),
+        ("}", "}"),
+        ("vert", "|"),
+        ("lvert", "|"),
+        ("rvert", "|"),
+        ("|", "‖"),
+        ("Vert", "‖"),
+        ("lVert", "‖"),
+        ("rVert", "‖"),
+        ("backslash", "\\"),
+        ("bmod", "mod"),
+        ("int", "∫"),
+        ("iint", "∬"),
+        ("iiint", "∭"),
+        ("oint", "∮"),
+        ("%", "%"),
+        ("$", "$"),
+        ("#", "#"),
+        ("&", "&"),
+        ("_", "_"),
+    ];
+    const LARGE_OPERATORS: &[(&str, &str)] = &[
+        ("sum", "∑"),
+        ("prod", "∏"),
+        ("coprod", "∐"),
+        ("bigcup", "⋃"),
+        ("bigcap", "⋂"),
+        ("bigoplus", "⨁"),
+        ("bigotimes", "⨂"),
+        ("bigvee", "⋁"),
+        ("bigwedge", "⋀"),
+    ];
+    const FUNCTIONS: &[&str] = &[
+        "sin", "cos", "tan", "cot", "sec", "csc", "arcsin", "arccos", "arctan", "sinh", "cosh",
+        "tanh", "coth", "log", "ln", "lg", "exp", "arg", "deg", "dim", "ker", "hom",
+    ];
+    const LIMIT_FUNCTIONS: &[&str] = &[
+        "lim", "liminf", "limsup", "max", "min", "sup", "inf", "det", "gcd", "Pr",
+    ];
+
+    let lookup = |table: &[(&str, &'static str)]| {
+        table
+            .iter()
+            .find(|(command, _)| *command == name)
+            .map(|(_, value)| *value)
+    };
+
+    if let Some(value) = lookup(GREEK).or_else(|| lookup(IDENTIFIERS)) {
+        return Some(MathNode::token("mi", value));
+    }
+    if let Some(value) = lookup(UPPER_GREEK) {
+        return Some(MathNode::token("mi", value).with_attribute("mathvariant", "normal"));
+    }
+    if let Some(value) = lookup(LARGE_OPERATORS) {
+        return Some(
+            MathNode::token("mo", value)
+                .with_attribute("largeop", "true")
+                .with_attribute("movablelimits", "true"),
+        );
+    }
+    if let Some(value) = lookup(OPERATORS) {
+        return Some(MathNode::token("mo", value));
+    }
+    if FUNCTIONS.contains(&name) {
+        return Some(MathNode::token("mi", name));
+    }
+    if LIMIT_FUNCTIONS.contains(&name) {
+        let text = match name {
+            "liminf" => "lim inf",
+            "limsup" => "lim sup",
+            other => other,
+        };
+        return Some(MathNode::token("mo", text).with_attribute("movablelimits", "true"));
+    }
+    None
+}
+
+fn font_variant(name: &str) -> Option<&'static str> {
+    Some(match name {
+        "mathrm" | "rm" => "normal",
+        "mathbf" | "bf" => "bold",
+        "mathit" | "it" => "italic",
+        "boldsymbol" | "bm" => "bold-italic",
+        "mathbb" => "double-struck",
+        "mathcal" => "script",
+        "mathfrak" => "fraktur",
+        "mathsf" => "sans-serif",
+        "mathtt" => "monospace",
+        _ => return None,
+    })
+}
+
+fn accent(name: &str) -> Option<&'static str> {
+    Some(match name {
+        "hat" | "widehat" => "^",
+        "bar" | "overline" => "‾",
+        "vec" | "overrightarrow" => "→",
+        "overleftarrow" => "←",
+        "dot" => "˙",
+        "ddot" => "¨",
+        "tilde" | "widetilde" => "~",
+        "check" => "ˇ",
+        "breve" => "˘",
+        "acute" => "´",
+        "grave" => "`",
+        "overbrace" => "⏞",
+        "underline" => "_",
+        "underbrace" => "⏟",
+        _ => return None,
+    })
+}
+
+fn space(name: &str) -> Option<&'static str> {
+    Some(match name {
+        "," | "thinspace" => "0.1667em",
+        ":" | ">" | "medspace" => "0.2222em",
+        ";" | "thickspace" => "0.2778em",
+        " " => "0.25em",
+        "quad" => "1em",
+        "qquad" => "2em",
+        "!" => "-0.1667em",
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Serialize for compact assertions.
+    fn to_markup(node: &MathNode) -> String {
+        match node {
+            MathNode::Text(text) => text.clone(),
+            MathNode::Element {
+                tag,
+                attributes,
+                children,
+            } => {
+                let attributes: String = attributes
+                    .iter()
+                    .map(|(name, value)| format!(" {}=\"{}\"", name, value))
+                    .collect();
+                let children: String = children.iter().map(to_markup).collect();
+                format!("<{0}{1}>{2}</{0}>", tag, attributes, children)
+            }
+        }
+    }
+
+    fn convert(tex: &str) -> String {
+        to_markup(&MathNode::row(Parser::new(tex).parse_row(Stop::End).nodes))
+    }
+
+    #[test]
+    fn test_tokens_and_scripts() {
+        assert_eq!(
+            convert("x^2 + 3.5y_i"),
+            "<mrow><msup><mi>x</mi><mn>2</mn></msup><mo>+</mo><mn>3.5</mn><msub><mi>y</mi><mi>i</mi></msub></mrow>"
+        );
+        assert_eq!(
+            convert("a_{n+1}^{2}"),
+            "<msubsup><mi>a</mi><mrow><mi>n</mi><mo>+</mo><mn>1</mn></mrow><mn>2</mn></msubsup>"
+        );
+        assert_eq!(convert("f'"), "<msup><mi>f</mi><mo>′</mo></msup>");
+    }
+
+    #[test]
+    fn test_fractions_and_roots() {
+        assert_eq!(
+            convert(r"\frac{1}{2}"),
+            "<mfrac><mn>1</mn><mn>2</mn></mfrac>"
+        );
+        assert_eq!(
+            convert(r"\sqrt[3]{x}"),
+            "<mroot><mi>x</mi><mn>3</mn></mroot>"
+        );
+        assert_eq!(convert(r"\sqrt x"), "<msqrt><mi>x</mi></msqrt>");
+    }
+
+    #[test]
+    fn test_symbols_and_limits() {
+        assert_eq!(
+            convert(r"\sum_{i=0}^n \alpha"),
+            "<mrow><munderover><mo largeop=\"true\" movablelimits=\"true\">∑</mo><mrow><mi>i</mi><mo>=</mo><mn>0</mn></mrow><mi>n</mi></munderover><mi>α</mi></mrow>"
+        );
+        assert_eq!(
+            convert(r"\int_0^1"),
+            "<msubsup><mo>∫</mo><mn>0</mn><mn>1</mn></msubsup>"
+        );
+        assert_eq!(
+            convert(r"\Omega \leq \infty"),
+            "<mrow><mi mathvariant=\"normal\">Ω</mi><mo>≤</mo><mi>∞</mi></mrow>"
+        );
+    }
+
+    #[test]
+    fn test_fences_text_and_fonts() {
+        assert_eq!(
+            convert(r"\left( x \right]"),
+            "<mrow><mo fence=\"true\" stretchy=\"true\">(</mo><mi>x</mi><mo fence=\"true\" stretchy=\"true\">]</mo></mrow>"
+        );
+        assert_eq!(
+            convert(r"\text{if } x"),
+            "<mrow><mtext>if </mtext><mi>x</mi></mrow>"
+        );
+        assert_eq!(
+            convert(r"\mathbb{R}"),
+            "<mi mathvariant=\"double-struck\">R</mi>"
+        );
+        assert_eq!(
+            convert(r"\vec v"),
+            "<mover accent=\"true\"><mi>v</mi><mo>→</mo></mover>"
+        );
+    }
+
+    #[test]
+    fn test_environments() {
+        assert_eq!(
+            convert(r"\begin{pmatrix} a & b \\ c & d \end{pmatrix}"),
+            "<mrow><mo fence=\"true\" stretchy=\"true\">(</mo><mtable><mtr><mtd><mi>a</mi></mtd><mtd><mi>b</mi></mtd></mtr><mtr><mtd><mi>c</mi></mtd><mtd><mi>d</mi></mtd></mtr></mtable><mo fence=\"true\" stretchy=\"true\">)</mo></mrow>"
+        );
+        // A trailing `\\` doesn't add an empty row
+        assert_eq!(
+            convert(r"\begin{matrix} a \\ \end{matrix}"),
+            "<mtable><mtr><mtd><mi>a</mi></mtd></mtr></mtable>"
+        );
+    }
+
+    #[test]
+    fn test_unknown_commands_and_unbalanced_input() {
+        assert_eq!(
+            convert(r"\foo x"),
+            "<mrow><merror><mtext>\\foo</mtext></merror><mi>x</mi></mrow>"
+        );
+        assert_eq!(convert("{x"), "<mi>x</mi>");
+        assert_eq!(convert("x}"), "<mi>x</mi>");
+        assert_eq!(
+            convert(r"\frac{1}"),
+            "<mfrac><mn>1</mn><mrow></mrow></mfrac>"
+        );
+    }
+
+    #[test]
+    fn test_math_root_keeps_source() {
+        let markup = to_markup(&tex_to_mathml("x", true));
+        assert_eq!(
+            markup,
+            "<math xmlns=\"http://www.w3.org/1998/Math/MathML\" display=\"block\"><semantics><mi>x</mi><annotation encoding=\"application/x-tex\">x</annotation></semantics></math>"
+        );
+    }
+}
@@ -1,11 +1,21 @@
 //! Plugin system for MDX transformations
 
+mod context;
 mod heading_ids;
 mod image_optimization;
+mod link_rewrite;
 mod link_validation;
+mod mathml;
 mod trait_def;
 
-pub use heading_ids::HeadingIdPlugin;
-pub use image_optimization::ImageOptimizationPlugin;
+pub use context::{MdxAsset, MdxPluginContext};
+pub(crate) use heading_ids::Slugger;
+pub use heading_ids::{HeadingIdOptions, HeadingIdPlugin, Slugify};
+pub use image_optimization::{
+    ImageDimensionOptions, ImageFormat, ImageInfo, ImageOptimizationPlugin, blur_placeholder,
+};
+pub use link_rewrite::{LinkResolver, LinkRewrite, LinkRewritePlugin};
+pub(crate) use link_rewrite::{collect_links, document_path};
 pub use link_validation::LinkValidationPlugin;
+pub use mathml::MathMlPlugin;
 pub use trait_def::MdxPlugin;
@@ -3,6 +3,7 @@
 //! The MDX plugin system allows custom transformations of both the markdown AST
 //! (before JSX conversion) and the generated JSX string (after conversion).
 
+use super::MdxPluginContext;
 use anyhow::Result;
 use markdown::mdast::Node;
 use std::any::Any;
@@ -65,6 +66,21 @@ pub trait MdxPlugin: Send + Sync {
         Ok(())
     }
 
+    /// Emit additional files derived from the document
+    ///
+    /// Called by [`compile`](crate::compile) once every plugin's
+    /// `transform_ast` has run, with the final AST. Use
+    /// [`MdxPluginContext::emit_file`] to produce side files such as extracted
+    /// code samples or per-document JSON.
+    ///
+    /// # Default Implementation
+    ///
+    /// The default implementation emits nothing and returns `Ok(())`.
+    fn emit_assets(&self, ast: &Node, ctx: &mut MdxPluginContext) -> Result<()> {
+        let _ = (ast, ctx);
+        Ok(())
+    }
+
     /// Enable downcasting to concrete plugin types
     ///
     /// This method allows the bundler to downcast trait objects to specific
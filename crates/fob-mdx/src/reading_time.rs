@@ -0,0 +1,65 @@
+//! Word count and reading time estimation
+
+/// Default reading speed used for reading time estimates.
+pub const DEFAULT_WORDS_PER_MINUTE: u32 = 200;
+
+/// Count words in plain text.
+///
+/// Words are whitespace-separated runs; CJK characters count as one word
+/// each, since those scripts do not separate words with spaces.
+pub(crate) fn count_words(text: &str) -> usize {
+    text.split_whitespace()
+        .map(|word| {
+            let cjk = word.chars().filter(|c| is_cjk(*c)).count();
+            let has_other = word.chars().any(|c| !is_cjk(c) && c.is_alphanumeric());
+            cjk + usize::from(has_other)
+        })
+        .sum()
+}
+
+/// Estimated reading time in whole minutes, rounded up.
+///
+/// Any non-empty text takes at least one minute.
+pub(crate) fn reading_time_minutes(words: usize, words_per_minute: u32) -> u32 {
+    let words_per_minute = words_per_minute.max(1) as usize;
+    words.div_ceil(words_per_minute) as u32
+}
+
+fn is_cjk(c: char) -> bool {
+    matches!(c,
+        '\u{3040}'..='\u{30FF}'   // Hiragana, Katakana
+        | '\u{3400}'..='\u{4DBF}' // CJK Extension A
+        | '\u{4E00}'..='\u{9FFF}' // CJK Unified Ideographs
+        | '\u{AC00}'..='\u{D7AF}' // Hangul syllables
+        | '\u{F900}'..='\u{FAFF}' // CJK Compatibility Ideographs
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_count_words() {
+        assert_eq!(count_words("Hello world,  this is\nMDX."), 5);
+        assert_eq!(count_words(""), 0);
+        // Punctuation-only tokens are not words
+        assert_eq!(count_words("a - b"), 2);
+    }
+
+    #[test]
+    fn test_count_words_cjk() {
+        assert_eq!(count_words("日本語のテキスト"), 8);
+        assert_eq!(count_words("Rust 编程"), 3);
+    }
+
+    #[test]
+    fn test_reading_time_rounds_up() {
+        assert_eq!(reading_time_minutes(0, 200), 0);
+        assert_eq!(reading_time_minutes(1, 200), 1);
+        assert_eq!(reading_time_minutes(400, 200), 2);
+        assert_eq!(reading_time_minutes(401, 200), 3);
+        // Zero WPM does not divide by zero
+        assert_eq!(reading_time_minutes(10, 0), 10);
+    }
+}
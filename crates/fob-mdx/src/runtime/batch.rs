@@ -0,0 +1,332 @@
+//! Batch bundling of many MDX documents
+
+use super::{
+    BundleMdxCss, BundleMdxOptions, BundleMdxResult, BundleMdxSourceMap, Limits, bundle_result,
+    compile_source, extract_bundle_code,
+};
+use crate::{MdxCompileResult, compile};
+use anyhow::{Context, Result};
+use bon::Builder;
+use fob_bundler::builders::MAX_ENTRY_POINTS;
+use fob_bundler::runtime::BundlerRuntime;
+use fob_bundler::{BuildOptions, BuildOutput, MinifyLevel, OutputFormat};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+/// Reusable bundler for many MDX documents
+///
+/// [`bundle_mdx`](super::bundle_mdx) sets up a complete bundler pipeline per
+/// document. `MdxBundler` instead bundles a whole batch as one isolated
+/// multi-entry build: the runtime, plugin setup and shared files are created
+/// once, and the per-document builds run in parallel.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use fob_mdx::runtime::{BundleMdxOptions, MdxBundler};
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let bundler = MdxBundler::builder()
+///     .build()
+///     .with_file("./Callout.jsx", "export default ({children}) => children");
+///
+/// let posts = ["# One\n\nimport Callout from './Callout.jsx'\n\n<Callout>Hi</Callout>", "# Two"];
+/// let results = bundler
+///     .bundle_many(
+///         posts
+///             .iter()
+///             .map(|source| BundleMdxOptions::builder().source(*source).build())
+///             .collect(),
+///     )
+///     .await?;
+/// assert_eq!(results.len(), 2);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Builder)]
+pub struct MdxBundler {
+    /// Virtual files available to every document, such as shared
+    /// components. Files of a document take precedence.
+    #[builder(default)]
+    pub files: HashMap<String, String>,
+
+    /// Maximum number of documents bundled in parallel
+    /// (default: `min(num_cpus, 8)`)
+    pub max_parallel: Option<usize>,
+
+    /// Runtime shared by all builds
+    #[builder(skip = Arc::new(BundlerRuntime::new(
+        std::env::current_dir().unwrap_or_else(|_| PathBuf::from(".")),
+    )))]
+    runtime: Arc<BundlerRuntime>,
+}
+
+impl Default for MdxBundler {
+    fn default() -> Self {
+        Self::builder().build()
+    }
+}
+
+impl MdxBundler {
+    /// Add a virtual file shared by all documents
+    pub fn with_file(mut self, path: impl Into<String>, content: impl Into<String>) -> Self {
+        self.files.insert(path.into(), content.into());
+        self
+    }
+
+    /// Compile and bundle a single document
+    pub async fn bundle(&self, document: BundleMdxOptions) -> Result<BundleMdxResult> {
+        let mut results = self.bundle_many(vec![document]).await?;
+        results
+            .pop()
+            .ok_or_else(|| anyhow::anyhow!("No bundle produced for MDX document"))
+    }
+
+    /// Compile and bundle documents, returning results in input order
+    ///
+    /// # Errors
+    ///
+    /// Fails if any document fails to compile or bundle; the error names
+    /// the document's index. Documents with resource limits are bundled on
+    /// their own, so their limits apply to them alone.
+    pub async fn bundle_many(
+        &self,
+        documents: Vec<BundleMdxOptions>,
+    ) -> Result<Vec<BundleMdxResult>> {
+        let count = documents.len();
+        let mut results: Vec<Option<BundleMdxResult>> = (0..count).map(|_| None).collect();
+        // Build options are shared by a batch, so documents are grouped by
+        // minification level
+        let mut groups: Vec<(MinifyLevel, Vec<Compiled>)> = Vec::new();
+        for (index, mut document) in documents.into_iter().enumerate() {
+            let limits = Limits::of(&document);
+            if limits != Limits::default() {
+                let result = limits
+                    .within_timeout(self.bundle_limited(index, document, limits))
+                    .await
+                    .with_context(|| format!("Failed to bundle MDX document {}", index))?;
+                results[index] = Some(result);
+                continue;
+            }
+
+            let minify = document.effective_minify_level();
+            let mdx_opts = document.mdx_options.take().unwrap_or_default();
+            let mdx_result = compile(&document.source, mdx_opts)
+                .with_context(|| format!("Failed to compile MDX document {}", index))?;
+            let compiled = Compiled::new(index, document, mdx_result);
+            match groups.iter_mut().find(|(level, _)| *level == minify) {
+                Some((_, group)) => group.push(compiled),
+                None => groups.push((minify, vec![compiled])),
+            }
+        }
+
+        for (minify, group) in groups {
+            let mut group = group.into_iter().peekable();
+            while group.peek().is_some() {
+                let batch: Vec<_> = group.by_ref().take(MAX_ENTRY_POINTS).collect();
+                for (index, result) in self.bundle_batch(batch, minify, Limits::default()).await? {
+                    results[index] = Some(result);
+                }
+            }
+        }
+        Ok(results.into_iter().flatten().collect())
+    }
+
+    /// Compile and bundle a document with resource limits on its own, so
+    /// its limits apply to it alone
+    async fn bundle_limited(
+        &self,
+        index: usize,
+        mut document: BundleMdxOptions,
+        limits: Limits,
+    ) -> Result<BundleMdxResult> {
+        let minify = document.effective_minify_level();
+        let mdx_opts = document.mdx_options.take().unwrap_or_default();
+        let source = std::mem::take(&mut document.source);
+        let mdx_result = compile_source(source, mdx_opts)
+            .await
+            .with_context(|| format!("Failed to compile MDX document {}", index))?;
+        let compiled = Compiled::new(index, document, mdx_result);
+        self.bundle_batch(vec![compiled], minify, limits)
+            .await?
+            .pop()
+            .map(|(_, result)| result)
+            .ok_or_else(|| anyhow::anyhow!("No bundle produced for MDX document {}", index))
+    }
+
+    /// Bundle compiled documents as one multi-entry build
+    async fn bundle_batch(
+        &self,
+        batch: Vec<Compiled>,
+        minify: MinifyLevel,
+        limits: Limits,
+    ) -> Result<Vec<(usize, BundleMdxResult)>> {
+        let mut virtual_files = self.files.clone();
+        let mut entries = Vec::with_capacity(batch.len());
+        for document in &batch {
+            let entry = format!("__mdx_entry_{}__.jsx", document.index);
+            if document.files.is_empty() {
+                virtual_files.insert(entry.clone(), document.mdx_result.code.clone());
+                entries.push(entry);
+                continue;
+            }
+
+            // Documents with their own files get a directory, so equal
+            // paths in different documents don't collide
+            let dir = format!("__mdx_{}__", document.index);
+            let in_dir = |path: &str| format!("{}/{}", dir, path.trim_start_matches("./"));
+            for (path, content) in self.files.iter().chain(&document.files) {
+                virtual_files.insert(in_dir(path), content.clone());
+            }
+            virtual_files.insert(in_dir(&entry), document.mdx_result.code.clone());
+            entries.push(in_dir(&entry));
+        }
+
+        let mut build_opts = BuildOptions::new_multiple(&entries)
+            .bundle_separately()
+            .format(OutputFormat::Esm)
+            .sourcemap_hidden()
+            .runtime(Arc::clone(&self.runtime));
+        if let Some(max) = self.max_parallel {
+            build_opts = build_opts.max_parallel_builds(max);
+        }
+        if minify.is_enabled() {
+            build_opts = build_opts.minify_level(minify.to_string());
+        }
+        build_opts.virtual_files.extend(virtual_files);
+
+        let build_result = limits
+            .build(build_opts)
+            .await
+            .context("Failed to bundle MDX documents and dependencies")?;
+        let mut bundles = match build_result.output {
+            BuildOutput::Multiple(bundles) => bundles,
+            BuildOutput::Single(_) => {
+                anyhow::bail!("Unexpected single bundle output for MDX batch")
+            }
+        };
+
+        let mut results = Vec::with_capacity(batch.len());
+        for document in batch {
+            let index = document.index;
+            let bundle = bundles
+                .remove(&format!("__mdx_entry_{}__", index))
+                .ok_or_else(|| anyhow::anyhow!("No bundle produced for MDX document {}", index))?;
+            let bundle = extract_bundle_code(&bundle)?;
+            limits
+                .check_output(&bundle)
+                .with_context(|| format!("MDX document {} exceeds its limits", index))?;
+            let result = bundle_result(
+                document.mdx_result,
+                bundle,
+                document.sourcemap,
+                document.css,
+            );
+            results.push((index, result));
+        }
+        Ok(results)
+    }
+}
+
+/// Document compiled to JSX, waiting to be bundled
+struct Compiled {
+    index: usize,
+    files: HashMap<String, String>,
+    sourcemap: Option<BundleMdxSourceMap>,
+    css: BundleMdxCss,
+    mdx_result: MdxCompileResult,
+}
+
+impl Compiled {
+    fn new(index: usize, document: BundleMdxOptions, mdx_result: MdxCompileResult) -> Self {
+        Self {
+            index,
+            files: document.files,
+            sourcemap: document.sourcemap,
+            css: document.css,
+            mdx_result,
+        }
+    }
+}
+
+/// Compile and bundle many MDX documents with one shared bundler
+///
+/// Shorthand for [`MdxBundler::bundle_many`] with a default bundler.
+/// Results are in input order.
+pub async fn bundle_mdx_many(documents: Vec<BundleMdxOptions>) -> Result<Vec<BundleMdxResult>> {
+    MdxBundler::default().bundle_many(documents).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_bundle_many_keeps_order_and_files_apart() {
+        let button = |label: &str| format!("export default () => '{}'", label);
+        let documents = vec![
+            BundleMdxOptions::builder()
+                .source("import B from './Button.jsx'\n\n# First\n\n<B />")
+                .build()
+                .with_file("./Button.jsx", button("first-button")),
+            BundleMdxOptions::builder().source("# Second").build(),
+            BundleMdxOptions::builder()
+                .source("import B from './Button.jsx'\n\n# Third\n\n<B />")
+                .build()
+                .with_file("./Button.jsx", button("third-button")),
+        ];
+
+        let results = bundle_mdx_many(documents).await.unwrap();
+
+        assert_eq!(results.len(), 3);
+        assert!(results[0].code.contains("first-button"));
+        assert!(!results[0].code.contains("third-button"));
+        assert!(results[1].code.contains("Second"));
+        assert!(results[2].code.contains("third-button"));
+    }
+
+    #[tokio::test]
+    async fn test_mixed_minify_levels() {
+        let source = "export const word = 'kept'\n\n# {word}\n";
+        let results = bundle_mdx_many(vec![
+            BundleMdxOptions::builder()
+                .source(source)
+                .minify(true)
+                .build(),
+            BundleMdxOptions::builder().source(source).build(),
+        ])
+        .await
+        .unwrap();
+
+        assert!(results[0].size() < results[1].size());
+        assert!(results[0].code.contains("kept"));
+    }
+
+    #[tokio::test]
+    async fn test_shared_files() {
+        let bundler = MdxBundler::builder()
+            .build()
+            .with_file("./Note.jsx", "export default () => 'shared-note'");
+        let result = bundler
+            .bundle(
+                BundleMdxOptions::builder()
+                    .source("import Note from './Note.jsx'\n\n<Note />")
+                    .build(),
+            )
+            .await
+            .unwrap();
+        assert!(result.code.contains("shared-note"));
+    }
+
+    #[tokio::test]
+    async fn test_compile_error_names_document() {
+        let err = bundle_mdx_many(vec![
+            BundleMdxOptions::builder().source("# Fine").build(),
+            BundleMdxOptions::builder().source("<Broken").build(),
+        ])
+        .await
+        .unwrap_err();
+        assert!(format!("{}", err).contains("document 1"), "{:#}", err);
+    }
+}
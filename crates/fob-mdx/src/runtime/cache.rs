@@ -0,0 +1,330 @@
+//! Content-hash keyed caching of bundled MDX
+
+use super::{BundleMdxCss, BundleMdxOptions, BundleMdxResult, BundleMdxSourceMap, MdxBundler};
+use anyhow::Result;
+use async_trait::async_trait;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, Mutex};
+
+/// Storage backend for [`CachedMdxBundler`]
+///
+/// Implement this to keep bundles in a shared store such as Redis; keys
+/// are hex content hashes and values can be serialized from the public
+/// fields of [`BundleMdxResult`].
+#[async_trait]
+pub trait MdxBundleCache: Send + Sync {
+    /// Cached result for a key, if present
+    async fn get(&self, key: &str) -> Option<BundleMdxResult>;
+
+    /// Store a result under a key
+    async fn set(&self, key: &str, result: &BundleMdxResult);
+}
+
+/// In-memory cache evicting the least recently used entry when full
+#[derive(Debug)]
+pub struct LruMdxBundleCache {
+    capacity: usize,
+    state: Mutex<LruState>,
+}
+
+#[derive(Debug, Default)]
+struct LruState {
+    /// Value and last use of each key
+    entries: HashMap<String, (BundleMdxResult, u64)>,
+    /// Keys by last use, oldest first
+    order: BTreeMap<u64, String>,
+    tick: u64,
+}
+
+impl LruState {
+    fn touch(&mut self, key: &str) -> Option<&BundleMdxResult> {
+        self.tick += 1;
+        let (result, last_used) = self.entries.get_mut(key)?;
+        self.order.remove(last_used);
+        *last_used = self.tick;
+        self.order.insert(self.tick, key.to_string());
+        Some(result)
+    }
+}
+
+impl LruMdxBundleCache {
+    /// Create a cache holding at most `capacity` bundles
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            state: Mutex::new(LruState::default()),
+        }
+    }
+
+    /// Number of cached bundles
+    pub fn len(&self) -> usize {
+        self.state.lock().unwrap().entries.len()
+    }
+
+    /// Check if the cache is empty
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Remove all cached bundles
+    pub fn clear(&self) {
+        let mut state = self.state.lock().unwrap();
+        state.entries.clear();
+        state.order.clear();
+    }
+}
+
+#[async_trait]
+impl MdxBundleCache for LruMdxBundleCache {
+    async fn get(&self, key: &str) -> Option<BundleMdxResult> {
+        self.state.lock().unwrap().touch(key).cloned()
+    }
+
+    async fn set(&self, key: &str, result: &BundleMdxResult) {
+        if self.capacity == 0 {
+            return;
+        }
+        let mut state = self.state.lock().unwrap();
+        if let Some((_, last_used)) = state.entries.remove(key) {
+            state.order.remove(&last_used);
+        }
+        while state.entries.len() >= self.capacity {
+            let Some((_, oldest)) = state.order.pop_first() else {
+                break;
+            };
+            state.entries.remove(&oldest);
+        }
+        state.tick += 1;
+        let tick = state.tick;
+        state.order.insert(tick, key.to_string());
+        state
+            .entries
+            .insert(key.to_string(), (result.clone(), tick));
+    }
+}
+
+/// [`MdxBundler`] that skips compilation and bundling for unchanged documents
+///
+/// Results are keyed by a BLAKE3 hash of everything that affects the
+/// output: the source, the document's and the bundler's shared files, the
+/// source map, minification and CSS modes, and the MDX options. Options are
+/// hashed through their `Debug` output, so custom plugins should print
+/// their configuration.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use fob_mdx::runtime::{BundleMdxOptions, CachedMdxBundler};
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let bundler = CachedMdxBundler::in_memory(500);
+///
+/// // The second request is served from the cache
+/// for _ in 0..2 {
+///     let result = bundler
+///         .bundle(BundleMdxOptions::builder().source("# Hello").build())
+///         .await?;
+///     println!("{} bytes", result.size());
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub struct CachedMdxBundler {
+    bundler: MdxBundler,
+    cache: Arc<dyn MdxBundleCache>,
+    /// Hash of the bundler's shared files
+    shared_files: blake3::Hash,
+}
+
+impl CachedMdxBundler {
+    /// Cache the results of `bundler` in `cache`
+    pub fn new(bundler: MdxBundler, cache: Arc<dyn MdxBundleCache>) -> Self {
+        let mut hasher = blake3::Hasher::new();
+        hash_files(&mut hasher, &bundler.files);
+        Self {
+            shared_files: hasher.finalize(),
+            bundler,
+            cache,
+        }
+    }
+
+    /// Default bundler with an in-memory LRU cache of `capacity` bundles
+    pub fn in_memory(capacity: usize) -> Self {
+        Self::new(
+            MdxBundler::default(),
+            Arc::new(LruMdxBundleCache::new(capacity)),
+        )
+    }
+
+    /// Cache key for a document
+    pub fn cache_key(&self, document: &BundleMdxOptions) -> String {
+        let mut hasher = blake3::Hasher::new();
+        hasher.update(env!("CARGO_PKG_VERSION").as_bytes());
+        hasher.update(self.shared_files.as_bytes());
+        hash_str(&mut hasher, &document.source);
+        hash_files(&mut hasher, &document.files);
+        hasher.update(&[match document.sourcemap {
+            None => 0,
+            Some(BundleMdxSourceMap::Inline) => 1,
+            Some(BundleMdxSourceMap::External) => 2,
+        }]);
+        hash_str(&mut hasher, &document.effective_minify_level().to_string());
+        hasher.update(&[match document.css {
+            BundleMdxCss::Collect => 0,
+            BundleMdxCss::Inject => 1,
+        }]);
+        if let Some(options) = &document.mdx_options {
+            hash_str(&mut hasher, &format!("{:?}", options));
+        }
+        hasher.finalize().to_hex().to_string()
+    }
+
+    /// Compile and bundle a document, or return its cached result
+    pub async fn bundle(&self, document: BundleMdxOptions) -> Result<BundleMdxResult> {
+        let key = self.cache_key(&document);
+        if let Some(result) = self.cache.get(&key).await {
+            return Ok(result);
+        }
+        let result = self.bundler.bundle(document).await?;
+        self.cache.set(&key, &result).await;
+        Ok(result)
+    }
+
+    /// Bundle documents, bundling only cache misses (as one batch)
+    ///
+    /// Results are in input order.
+    pub async fn bundle_many(
+        &self,
+        documents: Vec<BundleMdxOptions>,
+    ) -> Result<Vec<BundleMdxResult>> {
+        let mut results = Vec::with_capacity(documents.len());
+        let mut misses = Vec::new();
+        for document in documents {
+            let key = self.cache_key(&document);
+            let cached = self.cache.get(&key).await;
+            if cached.is_none() {
+                misses.push((results.len(), key, document));
+            }
+            results.push(cached);
+        }
+
+        let (slots, documents): (Vec<_>, Vec<_>) = misses
+            .into_iter()
+            .map(|(slot, key, document)| ((slot, key), document))
+            .unzip();
+        let bundled = self.bundler.bundle_many(documents).await?;
+        for ((slot, key), result) in slots.into_iter().zip(bundled) {
+            self.cache.set(&key, &result).await;
+            results[slot] = Some(result);
+        }
+
+        Ok(results.into_iter().flatten().collect())
+    }
+}
+
+impl std::fmt::Debug for CachedMdxBundler {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CachedMdxBundler")
+            .field("bundler", &self.bundler)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Length-prefixed, so adjacent strings can't run together
+fn hash_str(hasher: &mut blake3::Hasher, text: &str) {
+    hasher.update(&(text.len() as u64).to_le_bytes());
+    hasher.update(text.as_bytes());
+}
+
+/// Files in path order, independent of map iteration order
+fn hash_files(hasher: &mut blake3::Hasher, files: &HashMap<String, String>) {
+    let mut paths: Vec<&String> = files.keys().collect();
+    paths.sort();
+    hasher.update(&(paths.len() as u64).to_le_bytes());
+    for path in paths {
+        hash_str(hasher, path);
+        hash_str(hasher, &files[path]);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(code: &str) -> BundleMdxResult {
+        BundleMdxResult {
+            code: code.to_string(),
+            frontmatter: None,
+            matter: serde_json::json!({}),
+            map: None,
+            css: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_lru_evicts_least_recently_used() {
+        let cache = LruMdxBundleCache::new(2);
+        cache.set("a", &result("a")).await;
+        cache.set("b", &result("b")).await;
+        // Reading `a` makes `b` the oldest entry
+        assert_eq!(cache.get("a").await.unwrap().code, "a");
+        cache.set("c", &result("c")).await;
+
+        assert_eq!(cache.len(), 2);
+        assert!(cache.get("b").await.is_none());
+        assert!(cache.get("a").await.is_some());
+        assert!(cache.get("c").await.is_some());
+    }
+
+    #[test]
+    fn test_cache_key_covers_inputs() {
+        let bundler = CachedMdxBundler::in_memory(1);
+        let key = |document: BundleMdxOptions| bundler.cache_key(&document);
+        let base = || BundleMdxOptions::builder().source("# Hi").build();
+
+        assert_eq!(key(base()), key(base()));
+        assert_ne!(
+            key(base()),
+            key(BundleMdxOptions::builder().source("# Hey").build())
+        );
+        assert_ne!(key(base()), key(base().with_file("./a.js", "1")));
+        assert_ne!(
+            key(base().with_file("./a.js", "1")),
+            key(base().with_file("./a.js", "2"))
+        );
+        assert_ne!(
+            key(base()),
+            key(BundleMdxOptions {
+                sourcemap: Some(BundleMdxSourceMap::Inline),
+                ..base()
+            })
+        );
+        assert_ne!(
+            key(base()),
+            key(BundleMdxOptions {
+                minify: true,
+                ..base()
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_cached_bundle() {
+        let cache = Arc::new(LruMdxBundleCache::new(8));
+        let bundler = CachedMdxBundler::new(MdxBundler::default(), cache.clone());
+        let document = || BundleMdxOptions::builder().source("# Cached").build();
+
+        let first = bundler.bundle(document()).await.unwrap();
+        assert_eq!(cache.len(), 1);
+        let many = bundler
+            .bundle_many(vec![
+                document(),
+                BundleMdxOptions::builder().source("# New").build(),
+            ])
+            .await
+            .unwrap();
+        assert_eq!(many[0].code, first.code);
+        assert!(many[1].code.contains("New"));
+        assert_eq!(cache.len(), 2);
+    }
+}
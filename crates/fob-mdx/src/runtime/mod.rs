@@ -4,35 +4,98 @@
 //! It provides the `bundle_mdx` function and related types for bundling
 //! MDX files with their dependencies at runtime.
 
+mod batch;
+mod cache;
 mod types;
 
-pub use types::{BundleMdxOptions, BundleMdxResult};
+pub use batch::{MdxBundler, bundle_mdx_many};
+pub use cache::{CachedMdxBundler, LruMdxBundleCache, MdxBundleCache};
+pub use types::{
+    BundleMdxCss, BundleMdxLimitError, BundleMdxOptions, BundleMdxResult, BundleMdxSourceMap,
+};
 
-use crate::compile;
+use crate::{MdxCompileOptions, MdxCompileResult, compile};
 use anyhow::{Context, Result};
+use base64::Engine;
 use fob_bundler::runtime::BundlerRuntime;
-use fob_bundler::{BuildOptions, BuildOutput, BundleOutput, OutputFormat, build};
+use fob_bundler::{
+    BuildOptions, BuildOutput, BuildResult, BundleOutput, HookTransformArgs, HookTransformReturn,
+    HookUsage, OutputFormat, Plugin, SharedTransformPluginContext, build,
+};
+use std::borrow::Cow;
+use std::future::Future;
 use std::path::PathBuf;
 use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+/// Code, source map and styles of a bundled document
+struct BundleCode {
+    code: String,
+    map: Option<String>,
+    /// Concatenated CSS assets, if any CSS was imported
+    css: Option<String>,
+}
 
 /// Extract JavaScript code from a Rolldown bundle output
 ///
-/// Searches for the first entry chunk in the bundle and returns its code.
-fn extract_bundle_code(bundle: &BundleOutput) -> Result<String> {
+/// Searches for the first entry chunk in the bundle and returns its code
+/// and source map JSON, along with the CSS emitted for imported stylesheets.
+fn extract_bundle_code(bundle: &BundleOutput) -> Result<BundleCode> {
     use rolldown_common::Output;
 
     // Find the first JavaScript chunk (should be our entry)
-    bundle
+    let (code, map) = bundle
         .assets
         .iter()
         .find_map(|asset| {
             if let Output::Chunk(chunk) = asset {
-                Some(chunk.code.clone())
+                Some((
+                    chunk.code.clone(),
+                    chunk.map.as_ref().map(|map| map.to_json_string()),
+                ))
             } else {
                 None
             }
         })
-        .ok_or_else(|| anyhow::anyhow!("No JavaScript chunk found in bundle output"))
+        .ok_or_else(|| anyhow::anyhow!("No JavaScript chunk found in bundle output"))?;
+
+    let styles: Vec<String> = bundle
+        .assets
+        .iter()
+        .filter_map(|asset| match asset {
+            Output::Asset(asset) if asset.filename.ends_with(".css") => {
+                Some(String::from_utf8_lossy(asset.source.as_bytes()).into_owned())
+            }
+            _ => None,
+        })
+        .collect();
+    let css = (!styles.is_empty()).then(|| styles.join("\n"));
+
+    Ok(BundleCode { code, map, css })
+}
+
+/// Append a script adding the CSS to the document head when the bundle runs
+fn inject_css(code: &mut String, css: &str) {
+    if !code.ends_with('\n') {
+        code.push('\n');
+    }
+    // JSON string literals are valid JavaScript strings
+    let literal = serde_json::to_string(css).unwrap_or_else(|_| "\"\"".to_string());
+    code.push_str(&format!(
+        "if (typeof document !== \"undefined\") {{ const style = document.createElement(\"style\"); style.textContent = {}; document.head.appendChild(style); }}\n",
+        literal
+    ));
+}
+
+/// Append a source map to the code as a data URL comment
+fn inline_sourcemap(code: &mut String, map: &str) {
+    if !code.ends_with('\n') {
+        code.push('\n');
+    }
+    code.push_str("//# sourceMappingURL=data:application/json;charset=utf-8;base64,");
+    code.push_str(&base64::engine::general_purpose::STANDARD.encode(map));
+    code.push('\n');
 }
 
 /// Compile and bundle MDX at runtime
@@ -64,6 +127,9 @@ fn extract_bundle_code(bundle: &BundleOutput) -> Result<String> {
 ///         ("./x.js".into(), "export default () => 'Hi'".into()),
 ///     ]),
 ///     mdx_options: None, // Uses sensible defaults (all features ON)
+///     sourcemap: None,
+///     minify: true, // Ship minified bundles to clients
+///     minify_level: None,
 /// }).await?;
 ///
 /// // result.code is ready to execute on client
@@ -77,7 +143,7 @@ fn extract_bundle_code(bundle: &BundleOutput) -> Result<String> {
 /// This function performs bundling synchronously in the current task. For high-throughput
 /// servers, consider:
 ///
-/// - Caching bundled results (MDX source hash → bundle)
+/// - Caching bundled results with [`CachedMdxBundler`]
 /// - Rate limiting bundle requests
 /// - Using a task queue for bundling operations
 ///
@@ -88,16 +154,33 @@ fn extract_bundle_code(bundle: &BundleOutput) -> Result<String> {
 /// - Bundling fails (missing import, invalid JavaScript)
 /// - File I/O fails (temporary directory creation)
 pub async fn bundle_mdx(options: BundleMdxOptions) -> Result<BundleMdxResult> {
+    let limits = Limits::of(&options);
+    limits
+        .within_timeout(bundle_within_limits(options, limits))
+        .await
+}
+
+/// Compile and bundle a document, stopping once it pulls in too many modules
+async fn bundle_within_limits(
+    options: BundleMdxOptions,
+    limits: Limits,
+) -> Result<BundleMdxResult> {
     // Step 1: Compile MDX to JSX (defaults: all features ON, default plugins ON)
+    let minify = options.effective_minify_level();
     let mdx_opts = options.mdx_options.unwrap_or_default();
 
-    let mdx_result = compile(&options.source, mdx_opts).context("Failed to compile MDX to JSX")?;
+    let mdx_result = compile_source(options.source, mdx_opts)
+        .await
+        .context("Failed to compile MDX to JSX")?;
 
     // Step 2: Bundle using fob-core with virtual files
     let cwd = std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
     let mut build_opts = BuildOptions::new("__mdx_entry__.jsx")
         .format(OutputFormat::Esm)
         .sourcemap_hidden();
+    if minify.is_enabled() {
+        build_opts = build_opts.minify_level(minify.to_string());
+    }
 
     let runtime = Arc::new(BundlerRuntime::new(cwd.clone()));
 
@@ -113,22 +196,187 @@ pub async fn bundle_mdx(options: BundleMdxOptions) -> Result<BundleMdxResult> {
         build_opts.virtual_files.insert(path, content);
     }
 
-    let build_result = build(build_opts)
+    let build_result = limits
+        .build(build_opts)
         .await
         .context("Failed to bundle MDX and dependencies")?;
 
     // Step 3: Extract bundled code from result
-    let bundled_code = match build_result.output {
+    let bundle = match build_result.output {
         BuildOutput::Single(bundle) => extract_bundle_code(&bundle)?,
         BuildOutput::Multiple(_) => {
             anyhow::bail!("Unexpected multiple bundle output for single MDX file")
         }
     };
+    limits.check_output(&bundle)?;
+
+    Ok(bundle_result(
+        mdx_result,
+        bundle,
+        options.sourcemap,
+        options.css,
+    ))
+}
+
+/// Compile the document off the async executor so the timeout can fire
+/// while compilation runs
+async fn compile_source(source: String, options: MdxCompileOptions) -> Result<MdxCompileResult> {
+    #[cfg(not(target_family = "wasm"))]
+    {
+        tokio::task::spawn_blocking(move || compile(&source, options))
+            .await
+            .context("MDX compilation task failed")?
+            .map_err(Into::into)
+    }
+
+    #[cfg(target_family = "wasm")]
+    {
+        compile(&source, options).map_err(Into::into)
+    }
+}
+
+/// Resource limits of a document
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct Limits {
+    timeout: Option<Duration>,
+    max_modules: Option<usize>,
+    max_output_size: Option<usize>,
+}
 
-    Ok(BundleMdxResult {
-        code: bundled_code,
+impl Limits {
+    fn of(options: &BundleMdxOptions) -> Self {
+        Self {
+            timeout: options.timeout,
+            max_modules: options.max_modules,
+            max_output_size: options.max_output_size,
+        }
+    }
+
+    /// Run the whole compile and bundle pipeline within the timeout
+    ///
+    /// WASM targets have no timer, so timeouts aren't enforced there.
+    async fn within_timeout<T>(&self, pipeline: impl Future<Output = Result<T>>) -> Result<T> {
+        match self.timeout {
+            #[cfg(not(target_family = "wasm"))]
+            Some(timeout) => tokio::time::timeout(timeout, pipeline)
+                .await
+                .map_err(|_| BundleMdxLimitError::Timeout(timeout))?,
+            _ => pipeline.await,
+        }
+    }
+
+    /// Run a build, failing as soon as it loads more than `max_modules`
+    async fn build(&self, mut build_opts: BuildOptions) -> Result<BuildResult> {
+        let module_limit = self.max_modules.map(ModuleLimitPlugin::new);
+        if let Some(plugin) = &module_limit {
+            build_opts = build_opts.plugin(Arc::new(plugin.clone()));
+        }
+
+        let build_result = build(build_opts).await;
+        // The build fails once the limit is hit; report the limit instead
+        if let Some(plugin) = &module_limit {
+            plugin.check()?;
+        }
+        Ok(build_result?)
+    }
+
+    fn check_output(&self, bundle: &BundleCode) -> Result<()> {
+        match self.max_output_size {
+            Some(limit) if bundle.code.len() > limit => Err(BundleMdxLimitError::OutputTooLarge {
+                size: bundle.code.len(),
+                limit,
+            }
+            .into()),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Counts modules as the bundler transforms them and fails the build as
+/// soon as there are more than `limit`, before the rest of the graph loads
+#[derive(Debug, Clone)]
+struct ModuleLimitPlugin {
+    limit: usize,
+    count: Arc<AtomicUsize>,
+}
+
+impl ModuleLimitPlugin {
+    fn new(limit: usize) -> Self {
+        Self {
+            limit,
+            count: Arc::default(),
+        }
+    }
+
+    /// Error if the build went over the limit
+    fn check(&self) -> Result<(), BundleMdxLimitError> {
+        let count = self.count.load(Ordering::SeqCst);
+        if count > self.limit {
+            return Err(BundleMdxLimitError::TooManyModules {
+                count,
+                limit: self.limit,
+            });
+        }
+        Ok(())
+    }
+}
+
+impl Plugin for ModuleLimitPlugin {
+    fn name(&self) -> Cow<'static, str> {
+        "fob-mdx-module-limit".into()
+    }
+
+    fn register_hook_usage(&self) -> HookUsage {
+        HookUsage::Transform
+    }
+
+    fn transform(
+        &self,
+        _ctx: SharedTransformPluginContext,
+        _args: &HookTransformArgs<'_>,
+    ) -> impl Future<Output = HookTransformReturn> + Send {
+        self.count.fetch_add(1, Ordering::SeqCst);
+        let result = self.check().map(|()| None).map_err(Into::into);
+        async move { result }
+    }
+}
+
+/// Assemble the result for a compiled and bundled document
+fn bundle_result(
+    mdx_result: MdxCompileResult,
+    bundle: BundleCode,
+    sourcemap: Option<BundleMdxSourceMap>,
+    css_mode: BundleMdxCss,
+) -> BundleMdxResult {
+    let BundleCode {
+        mut code,
+        map,
+        mut css,
+    } = bundle;
+
+    if css_mode == BundleMdxCss::Inject {
+        if let Some(styles) = css.take() {
+            inject_css(&mut code, &styles);
+        }
+    }
+
+    // Maps are always generated hidden; attach them as requested
+    let map = match (sourcemap, map) {
+        (Some(BundleMdxSourceMap::Inline), Some(map)) => {
+            inline_sourcemap(&mut code, &map);
+            None
+        }
+        (Some(BundleMdxSourceMap::External), map) => map,
+        _ => None,
+    };
+
+    BundleMdxResult {
+        matter: mdx_result.matter(),
+        code,
         frontmatter: mdx_result.frontmatter,
-    })
+        map,
+        css,
+    }
 }
 
 #[cfg(test)]
@@ -162,6 +410,169 @@ author: Joy
         // This test just verifies bundling succeeds with frontmatter present
         assert!(!result.code.is_empty());
         // Frontmatter extraction is tested in fob-mdx crate
+        assert_eq!(result.matter["title"], "Test Post");
+    }
+
+    #[tokio::test]
+    async fn test_minified_bundle() {
+        let source = "export const greeting = 'Hello'\n\n# {greeting}\n\nSome   text\n";
+        let plain = bundle_mdx(BundleMdxOptions::builder().source(source).build())
+            .await
+            .unwrap();
+        let minified = bundle_mdx(
+            BundleMdxOptions::builder()
+                .source(source)
+                .minify(true)
+                .build(),
+        )
+        .await
+        .unwrap();
+
+        assert!(minified.size() < plain.size());
+        assert!(minified.code.contains("Hello"));
+    }
+
+    #[tokio::test]
+    async fn test_css_imports() {
+        let options = |css| {
+            BundleMdxOptions::builder()
+                .source("import Card from './Card.jsx'\n\n<Card />")
+                .css(css)
+                .build()
+                .with_file(
+                    "./Card.jsx",
+                    "import './card.css'\nexport default () => <div className=\"card\" />",
+                )
+                .with_file("./card.css", ".card { color: rebeccapurple; }")
+        };
+
+        let collected = bundle_mdx(options(BundleMdxCss::Collect)).await.unwrap();
+        let css = collected.css.expect("collected CSS");
+        assert!(css.contains("rebeccapurple"));
+        assert!(!collected.code.contains("rebeccapurple"));
+
+        let injected = bundle_mdx(options(BundleMdxCss::Inject)).await.unwrap();
+        assert!(injected.css.is_none());
+        assert!(injected.code.contains("document.createElement(\"style\")"));
+        assert!(injected.code.contains("rebeccapurple"));
+    }
+
+    #[tokio::test]
+    async fn test_resource_limits() {
+        let options = || {
+            BundleMdxOptions::builder()
+                .source("import X from './x.js'\n\n# Limited\n\n<X />")
+                .build()
+                .with_file("./x.js", "export default () => 'x'")
+        };
+        let limit_error = |err: anyhow::Error| {
+            err.chain()
+                .find_map(|cause| cause.downcast_ref::<BundleMdxLimitError>())
+                .cloned()
+        };
+
+        let err = bundle_mdx(BundleMdxOptions {
+            max_modules: Some(1),
+            ..options()
+        })
+        .await
+        .unwrap_err();
+        assert!(matches!(
+            limit_error(err),
+            Some(BundleMdxLimitError::TooManyModules { limit: 1, .. })
+        ));
+
+        let err = bundle_mdx(BundleMdxOptions {
+            max_output_size: Some(10),
+            ..options()
+        })
+        .await
+        .unwrap_err();
+        assert!(matches!(
+            limit_error(err),
+            Some(BundleMdxLimitError::OutputTooLarge { limit: 10, .. })
+        ));
+
+        let err = bundle_mdx(BundleMdxOptions {
+            timeout: Some(Duration::ZERO),
+            ..options()
+        })
+        .await
+        .unwrap_err();
+        assert_eq!(
+            limit_error(err),
+            Some(BundleMdxLimitError::Timeout(Duration::ZERO))
+        );
+
+        let generous = BundleMdxOptions {
+            timeout: Some(Duration::from_secs(60)),
+            max_modules: Some(100),
+            max_output_size: Some(1 << 20),
+            ..options()
+        };
+        assert!(bundle_mdx(generous).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_module_limit_stops_large_graphs_early() {
+        // A chain of 50 modules, each importing the next
+        let mut options = BundleMdxOptions::builder()
+            .source("import M from './m0.js'\n\n<M />")
+            .max_modules(3)
+            .build();
+        for i in 0..50 {
+            options = options.with_file(
+                format!("./m{}.js", i),
+                format!(
+                    "import next from './m{}.js';\nexport default () => next;",
+                    i + 1
+                ),
+            );
+        }
+        options = options.with_file("./m50.js", "export default null;");
+
+        let err = bundle_mdx(options).await.unwrap_err();
+        let limit = err
+            .chain()
+            .find_map(|cause| cause.downcast_ref::<BundleMdxLimitError>())
+            .cloned();
+        // Bundling stops at the first module over the limit instead of
+        // loading the whole chain first
+        assert!(
+            matches!(
+                limit,
+                Some(BundleMdxLimitError::TooManyModules { count, limit: 3 }) if count < 10
+            ),
+            "{:?}",
+            limit
+        );
+    }
+
+    #[tokio::test]
+    async fn test_mdx_sourcemaps() {
+        let options = |sourcemap| {
+            BundleMdxOptions::builder()
+                .source("# Hello")
+                .sourcemap(sourcemap)
+                .build()
+        };
+
+        let external = bundle_mdx(options(BundleMdxSourceMap::External))
+            .await
+            .unwrap();
+        let map = external.map.expect("external source map");
+        assert!(map.contains("\"mappings\""));
+        assert!(!external.code.contains("sourceMappingURL=data:"));
+
+        let inline = bundle_mdx(options(BundleMdxSourceMap::Inline))
+            .await
+            .unwrap();
+        assert!(inline.map.is_none());
+        assert!(
+            inline
+                .code
+                .contains("//# sourceMappingURL=data:application/json;charset=utf-8;base64,")
+        );
     }
 
     #[tokio::test]
@@ -2,7 +2,9 @@
 
 use crate::{FrontmatterData, MdxCompileOptions};
 use bon::Builder;
+use fob_bundler::MinifyLevel;
 use std::collections::HashMap;
+use std::time::Duration;
 
 /// Options for runtime MDX bundling
 ///
@@ -53,6 +55,59 @@ pub struct BundleMdxOptions {
     ///
     /// If `None`, uses default options with all features enabled.
     pub mdx_options: Option<MdxCompileOptions>,
+
+    /// Source map output for the bundle (default: none)
+    pub sourcemap: Option<BundleMdxSourceMap>,
+
+    /// Minify the bundle with full identifier mangling (default: false)
+    #[builder(default)]
+    pub minify: bool,
+
+    /// Minification level, overriding `minify` when set
+    pub minify_level: Option<MinifyLevel>,
+
+    /// What happens to CSS imported by the MDX or its components
+    /// (default: collected into [`BundleMdxResult::css`])
+    #[builder(default)]
+    pub css: BundleMdxCss,
+
+    /// Wall-clock limit for compiling and bundling (default: none)
+    ///
+    /// Covers the whole pipeline, but is best-effort: `bundle_mdx` returns
+    /// when the limit passes, while compilation keeps running to completion
+    /// on a blocking thread, and a long synchronous step inside the bundler
+    /// only notices the limit once it yields. Not enforced on WASM targets.
+    pub timeout: Option<Duration>,
+
+    /// Maximum number of modules in the bundle, including the MDX entry
+    /// (default: none)
+    ///
+    /// Checked as modules load, so bundling stops at the first module over
+    /// the limit.
+    pub max_modules: Option<usize>,
+
+    /// Maximum size of the bundled code in bytes (default: none)
+    pub max_output_size: Option<usize>,
+}
+
+/// Handling of CSS imported by a bundled MDX file
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum BundleMdxCss {
+    /// Returned separately in [`BundleMdxResult::css`], e.g. for a
+    /// `<style>` tag in server-rendered HTML
+    #[default]
+    Collect,
+    /// Added to the document by the bundle itself when it runs
+    Inject,
+}
+
+/// How the source map of a bundled MDX file is returned
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BundleMdxSourceMap {
+    /// Appended to `code` as a base64 `sourceMappingURL` data URL
+    Inline,
+    /// Returned separately in [`BundleMdxResult::map`]
+    External,
 }
 
 impl BundleMdxOptions {
@@ -94,6 +149,35 @@ impl BundleMdxOptions {
         self.mdx_options = Some(options);
         self
     }
+
+    /// Minification level the bundle is built with
+    pub fn effective_minify_level(&self) -> MinifyLevel {
+        match (self.minify_level, self.minify) {
+            (Some(level), _) => level,
+            (None, true) => MinifyLevel::Identifiers,
+            (None, false) => MinifyLevel::None,
+        }
+    }
+}
+
+/// Resource limit exceeded while bundling
+///
+/// Returned inside the [`anyhow::Error`] of `bundle_mdx`; use
+/// `err.downcast_ref::<BundleMdxLimitError>()` to tell limits apart from
+/// compilation and bundling errors.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum BundleMdxLimitError {
+    /// Compiling and bundling took longer than the timeout
+    #[error("MDX bundling timed out after {0:?}")]
+    Timeout(Duration),
+
+    /// The bundle pulled in more modules than allowed
+    #[error("MDX bundle contains {count} modules (limit: {limit})")]
+    TooManyModules { count: usize, limit: usize },
+
+    /// The bundled code is larger than allowed
+    #[error("MDX bundle is {size} bytes (limit: {limit} bytes)")]
+    OutputTooLarge { size: usize, limit: usize },
 }
 
 /// Result of runtime MDX bundling
@@ -117,6 +201,9 @@ impl BundleMdxOptions {
 /// if let Some(fm) = result.frontmatter {
 ///     println!("Frontmatter: {:?}", fm.raw);
 /// }
+///
+/// // Frontmatter merged with computed exports
+/// println!("Title: {}", result.matter["title"]);
 /// # Ok(())
 /// # }
 /// ```
@@ -133,6 +220,19 @@ pub struct BundleMdxResult {
     ///
     /// Extracted from YAML or TOML frontmatter blocks at the top of the file.
     pub frontmatter: Option<FrontmatterData>,
+
+    /// Frontmatter fields merged with the statically computable
+    /// `export const` values of the MDX, like mdx-bundler's `matter`
+    ///
+    /// Always a JSON object; exports win over frontmatter fields of the
+    /// same name. See [`MdxCompileResult::matter`](crate::MdxCompileResult::matter).
+    pub matter: serde_json::Value,
+
+    /// Source map JSON, when [`BundleMdxSourceMap::External`] was requested
+    pub map: Option<String>,
+
+    /// Styles imported by the MDX and its components, when collected
+    pub css: Option<String>,
 }
 
 impl BundleMdxResult {
@@ -0,0 +1,355 @@
+//! Section-by-section compilation for very large documents
+//!
+//! [`compile`](crate::compile) holds the whole document, its AST and the
+//! generated code in memory at once, which is why sources are capped at
+//! 10MB. [`compile_sections`] instead reads a document incrementally and
+//! compiles it in heading-delimited sections, so memory is bounded by the
+//! largest section rather than the whole book.
+//!
+//! Each section is compiled as an independent document:
+//!
+//! - `import` statements are repeated in every later section, so components
+//!   stay in scope; other ESM (`export const ...`) stays in its section
+//! - Frontmatter is only parsed with the first section
+//! - Headings are never split off from inside fenced code, JSX elements or
+//!   `:::` directives
+//! - Heading ID deduplication, footnotes and link reference definitions
+//!   only work within a section
+//! - Error line numbers refer to the original document
+
+use std::io::BufRead;
+
+use crate::error::MdxError;
+use crate::{MdxCompileOptions, MdxCompileResult, compile};
+
+/// Default heading level sections are split at (`#` and `##`)
+pub const DEFAULT_SECTION_LEVEL: u8 = 2;
+
+/// Compiled section of a document
+#[derive(Debug, Clone)]
+pub struct MdxSection {
+    /// 0-based position of the section in the document
+    pub index: usize,
+    /// Text of the heading that starts the section (`None` for content
+    /// before the first heading)
+    pub heading: Option<String>,
+    /// 1-based line of the document where the section starts
+    pub line: usize,
+    /// Compilation result for the section
+    pub result: MdxCompileResult,
+}
+
+/// Compile a document in sections split at headings of `level` or above.
+///
+/// `options` is called once per section, since options own their plugins.
+/// Sections are passed to `on_section` as soon as they are compiled; an
+/// error from it stops compilation. Returns the number of sections.
+///
+/// ```rust
+/// use fob_mdx::{MdxCompileOptions, compile_sections};
+///
+/// let book = "# Part one\n\nText\n\n## Chapter\n\nMore text\n";
+/// let mut headings = Vec::new();
+/// let count = compile_sections(book.as_bytes(), 2, MdxCompileOptions::new, |section| {
+///     headings.push(section.heading);
+///     Ok(())
+/// })
+/// .unwrap();
+/// assert_eq!(count, 2);
+/// assert_eq!(headings, [Some("Part one".to_string()), Some("Chapter".to_string())]);
+/// ```
+pub fn compile_sections<R, F, S>(
+    mut reader: R,
+    level: u8,
+    options: F,
+    mut on_section: S,
+) -> Result<usize, Box<MdxError>>
+where
+    R: BufRead,
+    F: Fn() -> MdxCompileOptions,
+    S: FnMut(MdxSection) -> Result<(), Box<MdxError>>,
+{
+    let mut splitter = Splitter::new(level);
+    let mut line = String::new();
+    let mut line_number = 0;
+    let mut count = 0;
+
+    loop {
+        line.clear();
+        let read = reader
+            .read_line(&mut line)
+            .map_err(|e| Box::new(MdxError::new(format!("Failed to read MDX source: {}", e))))?;
+        let chunk = if read == 0 {
+            splitter.finish()
+        } else {
+            line_number += 1;
+            splitter.push(&line, line_number)
+        };
+
+        if let Some(chunk) = chunk {
+            on_section(chunk.compile(count, &options)?)?;
+            count += 1;
+        }
+        if read == 0 {
+            return Ok(count);
+        }
+    }
+}
+
+/// Section source waiting to be compiled
+#[derive(Debug, Default)]
+struct Chunk {
+    /// Imports from earlier sections, prepended when compiling
+    prefix: String,
+    text: String,
+    heading: Option<String>,
+    line: usize,
+    /// Whether the chunk has anything besides frontmatter and imports
+    has_content: bool,
+}
+
+impl Chunk {
+    fn compile<F>(self, index: usize, options: &F) -> Result<MdxSection, Box<MdxError>>
+    where
+        F: Fn() -> MdxCompileOptions,
+    {
+        let prefix_lines = self.prefix.matches('\n').count();
+        let source = self.prefix + &self.text;
+        let result = compile(&source, options()).map_err(|mut err| {
+            // Report lines relative to the whole document
+            if let Some(line) = err.line.filter(|line| *line > prefix_lines) {
+                err.line = Some(line - prefix_lines + self.line - 1);
+            }
+            err
+        })?;
+        Ok(MdxSection {
+            index,
+            heading: self.heading,
+            line: self.line,
+            result,
+        })
+    }
+}
+
+/// Splits lines into sections, tracking the constructs a split must not cut.
+struct Splitter {
+    level: usize,
+    current: Chunk,
+    /// Import statements seen so far
+    imports: String,
+    /// Inside a multi-line import statement
+    in_import: bool,
+    /// Open code fence marker
+    fence: Option<&'static str>,
+    /// Open frontmatter fence marker
+    frontmatter: Option<&'static str>,
+    /// Nesting of multi-line JSX elements and directives
+    depth: usize,
+}
+
+impl Splitter {
+    fn new(level: u8) -> Self {
+        Self {
+            level: usize::from(level.clamp(1, 6)),
+            current: Chunk {
+                line: 1,
+                ..Chunk::default()
+            },
+            imports: String::new(),
+            in_import: false,
+            fence: None,
+            frontmatter: None,
+            depth: 0,
+        }
+    }
+
+    /// Add a line, returning the previous section if this line starts a new one.
+    fn push(&mut self, line: &str, line_number: usize) -> Option<Chunk> {
+        let trimmed = line.trim();
+
+        if line_number == 1 && (trimmed == "---" || trimmed == "+++") {
+            self.frontmatter = Some(if trimmed == "---" { "---" } else { "+++" });
+            self.current.text.push_str(line);
+            return None;
+        }
+        if let Some(marker) = self.frontmatter {
+            if trimmed == marker {
+                self.frontmatter = None;
+            }
+            self.current.text.push_str(line);
+            return None;
+        }
+
+        let line_fence = ["```", "~~~"]
+            .into_iter()
+            .find(|marker| trimmed.starts_with(marker));
+        match (self.fence, line_fence) {
+            (None, Some(marker)) => self.fence = Some(marker),
+            (Some(open_marker), Some(marker)) if open_marker == marker => self.fence = None,
+            _ => {}
+        }
+        if self.fence.is_some() || line_fence.is_some() {
+            self.current.has_content = true;
+            self.current.text.push_str(line);
+            return None;
+        }
+
+        if self.in_import || (self.depth == 0 && trimmed.starts_with("import ")) {
+            self.imports.push_str(line);
+            self.in_import = !import_complete(trimmed);
+            self.current.text.push_str(line);
+            return None;
+        }
+
+        let mut finished = None;
+        if self.depth == 0 && self.current.has_content {
+            if let Some(heading) = heading(line, self.level) {
+                // A blank line ends the ESM block before the heading
+                let prefix = match self.imports.is_empty() {
+                    true => String::new(),
+                    false => format!("{}\n", self.imports),
+                };
+                let next = Chunk {
+                    prefix,
+                    heading: Some(heading.to_string()),
+                    line: line_number,
+                    ..Chunk::default()
+                };
+                finished = Some(std::mem::replace(&mut self.current, next));
+            }
+        }
+        if self.current.heading.is_none() && !self.current.has_content {
+            self.current.heading = heading(line, self.level).map(str::to_string);
+        }
+
+        self.depth = self.depth.saturating_add_signed(depth_change(trimmed));
+        self.current.has_content |= !trimmed.is_empty();
+        self.current.text.push_str(line);
+        finished
+    }
+
+    /// Remaining section at the end of input, if it has content.
+    fn finish(&mut self) -> Option<Chunk> {
+        let chunk = std::mem::take(&mut self.current);
+        (chunk.has_content || !chunk.text.trim().is_empty()).then_some(chunk)
+    }
+}
+
+/// Text of an ATX heading of `max_level` or above.
+fn heading(line: &str, max_level: usize) -> Option<&str> {
+    let content = line.trim_end();
+    let indent = content.len() - content.trim_start().len();
+    if indent > 3 {
+        return None;
+    }
+    let content = content.trim_start();
+    let level = content.bytes().take_while(|&b| b == b'#').count();
+    if level == 0 || level > max_level {
+        return None;
+    }
+    let rest = &content[level..];
+    if !rest.is_empty() && !rest.starts_with([' ', '\t']) {
+        return None;
+    }
+    // Optional closing sequence (`## Title ##`)
+    let text = rest.trim().trim_end_matches('#').trim_end();
+    Some(text)
+}
+
+/// Whether an import statement ends on this line.
+fn import_complete(line: &str) -> bool {
+    line.ends_with(';') || line.ends_with('"') || line.ends_with('\'')
+}
+
+/// Change in nesting of block JSX elements and `:::` directives.
+fn depth_change(line: &str) -> isize {
+    if line.starts_with("</") || line.starts_with("/>") {
+        return -1;
+    }
+    if let Some(rest) = line.strip_prefix(":::") {
+        let rest = rest.trim_start_matches(':');
+        return match rest.chars().next() {
+            Some(c) if c.is_ascii_alphabetic() => 1,
+            None => -1,
+            _ => 0,
+        };
+    }
+    if let Some(rest) = line.strip_prefix('<') {
+        let opens = rest.starts_with(|c: char| c.is_ascii_alphabetic())
+            && !line.ends_with("/>")
+            && !line.contains("</");
+        return isize::from(opens);
+    }
+    0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// (heading, line, text) of each section
+    fn split(source: &str) -> Vec<(Option<String>, usize, String)> {
+        let mut splitter = Splitter::new(DEFAULT_SECTION_LEVEL);
+        let mut chunks = Vec::new();
+        for (i, line) in source.split_inclusive('\n').enumerate() {
+            chunks.extend(splitter.push(line, i + 1));
+        }
+        chunks.extend(splitter.finish());
+        chunks
+            .into_iter()
+            .map(|chunk| (chunk.heading, chunk.line, chunk.prefix + &chunk.text))
+            .collect()
+    }
+
+    #[test]
+    fn test_split_at_headings() {
+        let sections = split("---\ntitle: Book\n---\n# One\nA\n### Deep\nB\n## Two\nC\n");
+        assert_eq!(
+            sections,
+            vec![
+                (
+                    Some("One".to_string()),
+                    1,
+                    "---\ntitle: Book\n---\n# One\nA\n### Deep\nB\n".to_string()
+                ),
+                (Some("Two".to_string()), 8, "## Two\nC\n".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_no_split_inside_blocks() {
+        let source = "# A\n```md\n# Not a heading\n```\n<Note>\n\n## Inside\n\n</Note>\n:::tip\n## Also inside\n:::\n";
+        assert_eq!(split(source).len(), 1);
+    }
+
+    #[test]
+    fn test_imports_carried_forward() {
+        let sections = split(
+            "import {Chart} from './chart.js'\nimport {\n  Note,\n} from './note.js';\n\n# A\n<Chart />\n# B\n<Note />\n",
+        );
+        assert_eq!(sections.len(), 2);
+        assert_eq!(
+            sections[1].2,
+            "import {Chart} from './chart.js'\nimport {\n  Note,\n} from './note.js';\n\n# B\n<Note />\n"
+        );
+    }
+
+    #[test]
+    fn test_heading() {
+        assert_eq!(heading("## Title ##\n", 2), Some("Title"));
+        assert_eq!(heading("#\n", 2), Some(""));
+        assert_eq!(heading("### Deep", 2), None);
+        assert_eq!(heading("#hashtag", 2), None);
+        assert_eq!(heading("    # code", 2), None);
+    }
+
+    #[test]
+    fn test_error_lines_refer_to_document() {
+        let source = "import {A} from './a.js'\n\n# One\n\nText\n\n# Two\n\n<A>\n";
+        let err =
+            compile_sections(source.as_bytes(), 1, MdxCompileOptions::new, |_| Ok(())).unwrap_err();
+        // Unclosed `<A>` on line 9 is reported at the end of input
+        assert_eq!(err.line, Some(10), "{:?}", err);
+    }
+}
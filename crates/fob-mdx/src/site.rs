@@ -0,0 +1,440 @@
+//! Static site export
+//!
+//! Builds a static site from a directory of MDX documents: each document is
+//! compiled, pre-rendered with [`render_html`] and wrapped in a layout, the
+//! other files of the content directory and an optional public directory are
+//! copied alongside, and a `sitemap.xml` lists the pages.
+//!
+//! Pages follow the file layout of the content directory with clean URLs:
+//! `index.mdx` is served at `/`, `blog/hello.mdx` at `/blog/hello/` (written
+//! to `blog/hello/index.html`). Relative links between documents are
+//! rewritten to those URLs.
+//!
+//! Since pages are rendered without a JavaScript runtime, documents can use
+//! markdown, HTML elements, literal expressions and `frontmatter` lookups.
+//! A document using a component fails the build with the file name.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use fob_mdx::site::{SiteOptions, build_site};
+//!
+//! let output = build_site(
+//!     &SiteOptions::builder()
+//!         .content_dir("posts")
+//!         .out_dir("dist")
+//!         .layout("<html><head><title>{{ title }}</title></head><body>{{ content }}</body></html>")
+//!         .base_url("https://example.com")
+//!         .build(),
+//! )
+//! .unwrap();
+//! println!("{} pages", output.pages.len());
+//! ```
+
+use crate::MdxCompileOptions;
+use crate::error::MdxError;
+use crate::html::render_html;
+use crate::plugins::LinkRewrite;
+use bon::Builder;
+use path_clean::PathClean;
+use serde_json::Value as JsonValue;
+use std::path::{Path, PathBuf};
+
+/// Layout used when none is configured
+pub const DEFAULT_LAYOUT: &str = r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<meta name="viewport" content="width=device-width, initial-scale=1">
+<title>{{ title }}</title>
+<meta name="description" content="{{ frontmatter.description }}">
+</head>
+<body>
+<main>
+{{ content }}
+</main>
+</body>
+</html>
+"#;
+
+/// Options for [`build_site`]
+#[derive(Debug, Clone, Builder)]
+pub struct SiteOptions {
+    /// Directory containing the `.mdx`/`.md` documents and their assets
+    #[builder(into)]
+    pub content_dir: PathBuf,
+
+    /// Directory the site is written to
+    #[builder(into)]
+    pub out_dir: PathBuf,
+
+    /// HTML layout wrapping every page
+    ///
+    /// Placeholders: `{{ content }}` (the rendered page, inserted as HTML),
+    /// `{{ title }}`, `{{ url }}`, `{{ excerpt }}` and `{{ frontmatter.key }}`
+    /// for any frontmatter field, nested with dots. Values other than the
+    /// content are HTML-escaped; unknown placeholders render nothing.
+    #[builder(default = DEFAULT_LAYOUT.to_string(), into)]
+    pub layout: String,
+
+    /// Directory copied as-is to the root of the site (e.g. `static/`)
+    #[builder(into)]
+    pub public_dir: Option<PathBuf>,
+
+    /// Absolute site URL (e.g. `https://example.com`), required for the sitemap
+    ///
+    /// Without it no `sitemap.xml` is written, since sitemaps need absolute URLs.
+    #[builder(into)]
+    pub base_url: Option<String>,
+
+    /// Also build documents with `draft: true` in their frontmatter
+    #[builder(default)]
+    pub include_drafts: bool,
+}
+
+/// A page written by [`build_site`]
+#[derive(Debug, Clone)]
+pub struct SitePage {
+    /// Source document, relative to the content directory
+    pub source: PathBuf,
+    /// URL path of the page, e.g. `/blog/hello/`
+    pub url: String,
+    /// Written HTML file, relative to the output directory
+    pub file: PathBuf,
+    /// Page title: the `title` frontmatter field, or the file name
+    pub title: String,
+    /// Frontmatter as JSON (`Null` without frontmatter)
+    pub frontmatter: JsonValue,
+    /// Plain-text excerpt of the document
+    pub excerpt: Option<String>,
+}
+
+/// Result of [`build_site`]
+#[derive(Debug, Clone, Default)]
+pub struct SiteOutput {
+    /// Pages in URL order
+    pub pages: Vec<SitePage>,
+    /// Copied files, relative to the output directory
+    pub assets: Vec<PathBuf>,
+    /// Whether `sitemap.xml` was written
+    pub sitemap: bool,
+}
+
+/// Build a static site from a directory of MDX documents.
+///
+/// # Errors
+///
+/// Fails if a document doesn't compile or can't be rendered statically, or
+/// if reading the content or writing the site fails.
+pub fn build_site(options: &SiteOptions) -> Result<SiteOutput, Box<MdxError>> {
+    let mut files = Vec::new();
+    collect_files(&options.content_dir, &mut files)
+        .map_err(|e| io_error(&options.content_dir, e))?;
+    files.sort();
+    std::fs::create_dir_all(&options.out_dir).map_err(|e| io_error(&options.out_dir, e))?;
+
+    let mut output = SiteOutput::default();
+    for file in files {
+        let relative = file
+            .strip_prefix(&options.content_dir)
+            .unwrap_or(&file)
+            .to_path_buf();
+        if is_document(&relative) {
+            if let Some(page) = build_page(options, &file, relative)? {
+                output.pages.push(page);
+            }
+        } else {
+            copy_file(&file, &options.out_dir.join(&relative))?;
+            output.assets.push(relative);
+        }
+    }
+
+    if let Some(public_dir) = &options.public_dir {
+        let mut files = Vec::new();
+        collect_files(public_dir, &mut files).map_err(|e| io_error(public_dir, e))?;
+        files.sort();
+        for file in files {
+            let relative = file.strip_prefix(public_dir).unwrap_or(&file).to_path_buf();
+            copy_file(&file, &options.out_dir.join(&relative))?;
+            output.assets.push(relative);
+        }
+    }
+
+    output.pages.sort_by(|a, b| a.url.cmp(&b.url));
+
+    if let Some(base_url) = &options.base_url {
+        let sitemap_path = options.out_dir.join("sitemap.xml");
+        std::fs::write(&sitemap_path, sitemap(base_url, &output.pages))
+            .map_err(|e| io_error(&sitemap_path, e))?;
+        output.sitemap = true;
+    }
+
+    Ok(output)
+}
+
+/// Render one document into its page; `None` for skipped drafts.
+fn build_page(
+    options: &SiteOptions,
+    file: &Path,
+    relative: PathBuf,
+) -> Result<Option<SitePage>, Box<MdxError>> {
+    let source = std::fs::read_to_string(file).map_err(|e| io_error(file, e))?;
+
+    // Links are relative to the document, so resolve them from its directory
+    let document_dir = relative.parent().map(Path::to_path_buf).unwrap_or_default();
+    let compile_options = MdxCompileOptions::builder()
+        .filepath(file.display().to_string())
+        .link_rewrite(LinkRewrite::custom(move |link| {
+            Some(page_url(&document_dir.join(link).clean()))
+        }))
+        .build();
+    let rendered = render_html(&source, compile_options)?;
+
+    let frontmatter = rendered
+        .frontmatter
+        .map(|fm| fm.data)
+        .unwrap_or(JsonValue::Null);
+    if !options.include_drafts && frontmatter.get("draft") == Some(&JsonValue::Bool(true)) {
+        return Ok(None);
+    }
+
+    let url = page_url(&relative);
+    let page_file = PathBuf::from(url.trim_start_matches('/')).join("index.html");
+    let title = frontmatter
+        .get("title")
+        .and_then(JsonValue::as_str)
+        .map(str::to_string)
+        .unwrap_or_else(|| {
+            relative
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().into_owned())
+                .unwrap_or_default()
+        });
+
+    let page = SitePage {
+        source: relative,
+        url,
+        file: page_file,
+        title,
+        frontmatter,
+        excerpt: rendered.excerpt,
+    };
+    let html = render_layout(&options.layout, &page, &rendered.html);
+    let out_path = options.out_dir.join(&page.file);
+    if let Some(parent) = out_path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| io_error(parent, e))?;
+    }
+    std::fs::write(&out_path, html).map_err(|e| io_error(&out_path, e))?;
+
+    Ok(Some(page))
+}
+
+/// Clean URL of a document path relative to the content directory.
+fn page_url(relative: &Path) -> String {
+    let without_ext = relative.with_extension("");
+    let mut segments: Vec<String> = without_ext
+        .components()
+        .map(|component| component.as_os_str().to_string_lossy().into_owned())
+        .collect();
+    if segments.last().is_some_and(|last| last == "index") {
+        segments.pop();
+    }
+    if segments.is_empty() {
+        "/".to_string()
+    } else {
+        format!("/{}/", segments.join("/"))
+    }
+}
+
+/// Fill the layout's `{{ ... }}` placeholders for a page.
+fn render_layout(layout: &str, page: &SitePage, content: &str) -> String {
+    let mut out = String::with_capacity(layout.len() + content.len());
+    let mut rest = layout;
+    while let Some(start) = rest.find("{{") {
+        let Some(end) = rest[start + 2..].find("}}") else {
+            break;
+        };
+        out.push_str(&rest[..start]);
+        let key = rest[start + 2..start + 2 + end].trim();
+        match key {
+            "content" => out.push_str(content),
+            "title" => escape_html(&page.title, &mut out),
+            "url" => escape_html(&page.url, &mut out),
+            "excerpt" => escape_html(page.excerpt.as_deref().unwrap_or_default(), &mut out),
+            _ => {
+                let value = key.strip_prefix("frontmatter.").and_then(|path| {
+                    path.split('.')
+                        .try_fold(&page.frontmatter, |value, key| value.get(key))
+                });
+                match value {
+                    Some(JsonValue::String(value)) => escape_html(value, &mut out),
+                    Some(JsonValue::Null) | None => {}
+                    Some(value) => escape_html(&value.to_string(), &mut out),
+                }
+            }
+        }
+        rest = &rest[start + 2 + end + 2..];
+    }
+    out.push_str(rest);
+    out
+}
+
+/// `sitemap.xml` listing the pages, with `lastmod` from an `updated` or
+/// `date` frontmatter field.
+fn sitemap(base_url: &str, pages: &[SitePage]) -> String {
+    let base_url = base_url.trim_end_matches('/');
+    let mut xml = String::from(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+         <urlset xmlns=\"http://www.sitemaps.org/schemas/sitemap/0.9\">\n",
+    );
+    for page in pages {
+        xml.push_str("  <url>\n    <loc>");
+        escape_html(&format!("{}{}", base_url, page.url), &mut xml);
+        xml.push_str("</loc>\n");
+        let lastmod = ["updated", "date"]
+            .iter()
+            .find_map(|key| page.frontmatter.get(key).and_then(JsonValue::as_str));
+        if let Some(lastmod) = lastmod {
+            xml.push_str("    <lastmod>");
+            escape_html(lastmod, &mut xml);
+            xml.push_str("</lastmod>\n");
+        }
+        xml.push_str("  </url>\n");
+    }
+    xml.push_str("</urlset>\n");
+    xml
+}
+
+fn is_document(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|ext| ext == "mdx" || ext == "md")
+}
+
+/// Recursively collect the files under a directory, skipping hidden entries.
+fn collect_files(dir: &Path, files: &mut Vec<PathBuf>) -> std::io::Result<()> {
+    for entry in std::fs::read_dir(dir)? {
+        let entry = entry?;
+        if entry.file_name().to_string_lossy().starts_with('.') {
+            continue;
+        }
+        let path = entry.path();
+        if entry.file_type()?.is_dir() {
+            collect_files(&path, files)?;
+        } else {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+fn copy_file(from: &Path, to: &Path) -> Result<(), Box<MdxError>> {
+    if let Some(parent) = to.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| io_error(parent, e))?;
+    }
+    std::fs::copy(from, to).map_err(|e| io_error(from, e))?;
+    Ok(())
+}
+
+fn io_error(path: &Path, error: std::io::Error) -> Box<MdxError> {
+    Box::new(MdxError::new(error.to_string()).with_file(path.display().to_string()))
+}
+
+fn escape_html(value: &str, out: &mut String) {
+    for c in value.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '"' => out.push_str("&quot;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            c => out.push(c),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_page_url() {
+        assert_eq!(page_url(Path::new("index.mdx")), "/");
+        assert_eq!(page_url(Path::new("about.md")), "/about/");
+        assert_eq!(page_url(Path::new("blog/index.mdx")), "/blog/");
+        assert_eq!(page_url(Path::new("blog/hello.mdx")), "/blog/hello/");
+    }
+
+    #[test]
+    fn test_render_layout() {
+        let page = SitePage {
+            source: PathBuf::from("hello.mdx"),
+            url: "/hello/".to_string(),
+            file: PathBuf::from("hello/index.html"),
+            title: "Fish & Chips".to_string(),
+            frontmatter: serde_json::json!({ "author": { "name": "Ada" } }),
+            excerpt: None,
+        };
+
+        let html = render_layout(
+            "<title>{{ title }}</title>{{content}}<p>{{ frontmatter.author.name }}{{ frontmatter.missing }}</p>",
+            &page,
+            "<h1>Hi</h1>",
+        );
+
+        assert_eq!(html, "<title>Fish &amp; Chips</title><h1>Hi</h1><p>Ada</p>");
+    }
+
+    #[test]
+    fn test_build_site() {
+        let temp = std::env::temp_dir().join(format!("fob-mdx-site-{}", std::process::id()));
+        let content = temp.join("content");
+        let out = temp.join("dist");
+        fs::create_dir_all(content.join("blog")).unwrap();
+        fs::write(
+            content.join("index.mdx"),
+            "---\ntitle: Home\n---\n\n[First post](./blog/first.mdx)\n",
+        )
+        .unwrap();
+        fs::write(
+            content.join("blog/first.mdx"),
+            "---\ntitle: First\ndate: \"2024-05-01\"\n---\n\n# Hello\n\n[Home](../index.mdx)\n",
+        )
+        .unwrap();
+        fs::write(
+            content.join("blog/wip.mdx"),
+            "---\ndraft: true\n---\n\nSoon\n",
+        )
+        .unwrap();
+        fs::write(content.join("blog/photo.png"), [0u8; 4]).unwrap();
+
+        let output = build_site(
+            &SiteOptions::builder()
+                .content_dir(&content)
+                .out_dir(&out)
+                .layout("<title>{{ title }}</title>{{ content }}")
+                .base_url("https://example.com/")
+                .build(),
+        )
+        .unwrap();
+
+        let urls: Vec<_> = output.pages.iter().map(|page| page.url.as_str()).collect();
+        assert_eq!(urls, ["/", "/blog/first/"]);
+        assert_eq!(output.assets, [PathBuf::from("blog/photo.png")]);
+
+        let index = fs::read_to_string(out.join("index.html")).unwrap();
+        assert!(index.contains("<title>Home</title>"), "{}", index);
+        assert!(index.contains("href=\"/blog/first/\""), "{}", index);
+
+        let post = fs::read_to_string(out.join("blog/first/index.html")).unwrap();
+        assert!(post.contains("<h1"), "{}", post);
+        assert!(post.contains("href=\"/\""), "{}", post);
+        assert!(!out.join("blog/wip/index.html").exists());
+        assert!(out.join("blog/photo.png").exists());
+
+        let sitemap = fs::read_to_string(out.join("sitemap.xml")).unwrap();
+        assert!(sitemap.contains("<loc>https://example.com/blog/first/</loc>"));
+        assert!(sitemap.contains("<lastmod>2024-05-01</lastmod>"));
+
+        fs::remove_dir_all(&temp).unwrap();
+    }
+}
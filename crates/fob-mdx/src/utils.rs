@@ -1,5 +1,7 @@
 //! Internal utilities for MDX compilation
 
+use markdown::mdast::Node;
+
 /// Convert byte offset to (line, column) position in source code.
 ///
 /// Lines and columns are 1-indexed to match standard editor conventions.
@@ -22,6 +24,69 @@ pub(crate) fn offset_to_line_col(source: &str, offset: usize) -> (usize, usize)
     (line, col)
 }
 
+/// Render an mdast node as plain text.
+///
+/// Code, ESM, expressions, math and images are dropped. Block children are
+/// separated by newlines, table cells by spaces.
+pub(crate) fn plain_text(node: &Node) -> String {
+    let mut text = String::new();
+    push_plain_text(node, &mut text);
+    text
+}
+
+fn push_plain_text(node: &Node, out: &mut String) {
+    match node {
+        Node::Text(text) => out.push_str(&text.value),
+        Node::InlineCode(code) => out.push_str(&code.value),
+        Node::Break(_) => out.push(' '),
+        Node::Code(_)
+        | Node::Math(_)
+        | Node::InlineMath(_)
+        | Node::MdxjsEsm(_)
+        | Node::MdxFlowExpression(_)
+        | Node::MdxTextExpression(_)
+        | Node::Html(_)
+        | Node::Image(_)
+        | Node::ImageReference(_)
+        | Node::FootnoteReference(_)
+        | Node::FootnoteDefinition(_)
+        | Node::Definition(_)
+        | Node::ThematicBreak(_)
+        | Node::Yaml(_)
+        | Node::Toml(_) => {}
+        // Pre-rendered math, skipped like the math nodes it replaces
+        Node::MdxJsxFlowElement(element) if element.name.as_deref() == Some("math") => {}
+        Node::MdxJsxTextElement(element) if element.name.as_deref() == Some("math") => {}
+        Node::Root(_)
+        | Node::List(_)
+        | Node::ListItem(_)
+        | Node::Blockquote(_)
+        | Node::MdxJsxFlowElement(_)
+        | Node::Table(_)
+        | Node::TableRow(_) => {
+            let parts: Vec<String> = node
+                .children()
+                .into_iter()
+                .flatten()
+                .map(plain_text)
+                .map(|part| part.trim().to_string())
+                .filter(|part| !part.is_empty())
+                .collect();
+            let separator = if matches!(node, Node::TableRow(_)) {
+                " "
+            } else {
+                "\n"
+            };
+            out.push_str(&parts.join(separator));
+        }
+        _ => {
+            for child in node.children().into_iter().flatten() {
+                push_plain_text(child, out);
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -224,6 +224,19 @@ export { z } from './z'
 
 mod image_collection {
     use super::*;
+    use fob_mdx::ImageDimensionOptions;
+    use fob_mdx::plugins::ImageFormat;
+    use std::collections::HashMap;
+    use std::path::PathBuf;
+    use std::sync::Arc;
+
+    fn urls(result: &MdxCompileResult) -> Vec<&str> {
+        result
+            .images
+            .iter()
+            .map(|image| image.url.as_str())
+            .collect()
+    }
 
     #[test]
     fn collects_markdown_images() {
@@ -233,12 +246,31 @@ mod image_collection {
 "#;
         let result = compile_mdx(mdx);
         assert!(
-            result.images.contains(&"./image.png".to_string()),
+            urls(&result).contains(&"./image.png"),
             "Should collect image URL. Got: {:?}",
             result.images
         );
     }
 
+    #[test]
+    fn default_plugins_can_be_disabled_individually() {
+        let mdx = "# Hello\n\n![Alt](./image.png)\n";
+        let options = |heading_ids: bool, images: bool| {
+            MdxCompileOptions::builder()
+                .default_heading_ids(heading_ids)
+                .default_image_optimization(images)
+                .build()
+        };
+
+        let result = compile_with_options(mdx, options(false, true));
+        assert!(!result.code.contains("\"hello\""), "{}", result.code);
+        assert_eq!(urls(&result), ["./image.png"]);
+
+        let result = compile_with_options(mdx, options(true, false));
+        assert!(result.code.contains("\"hello\""), "{}", result.code);
+        assert!(result.images.is_empty());
+    }
+
     #[test]
     fn collects_multiple_images() {
         let mdx = r#"# Gallery
@@ -255,18 +287,42 @@ mod image_collection {
     fn handles_relative_image_paths() {
         let mdx = r#"![](../assets/logo.svg)"#;
         let result = compile_mdx(mdx);
-        assert!(result.images.contains(&"../assets/logo.svg".to_string()));
+        assert!(urls(&result).contains(&"../assets/logo.svg"));
     }
 
     #[test]
     fn handles_absolute_image_urls() {
         let mdx = r#"![](https://example.com/image.png)"#;
         let result = compile_mdx(mdx);
+        assert!(urls(&result).contains(&"https://example.com/image.png"));
+    }
+
+    #[test]
+    fn adds_local_image_dimensions() {
+        let svg = br#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 200 100"></svg>"#;
+        let files = HashMap::from([(PathBuf::from("content/img/chart.svg"), svg.to_vec())]);
+        let options = MdxCompileOptions::builder()
+            .filepath("content/post.mdx")
+            .image_dimensions(
+                ImageDimensionOptions::builder()
+                    .source(Arc::new(files))
+                    .build(),
+            )
+            .build();
+
+        let result = compile_with_options("![Chart](./img/chart.svg)\n\n![](/x.png)", options);
+        let chart = &result.images[0];
+        assert_eq!(chart.path, Some(PathBuf::from("content/img/chart.svg")));
+        assert_eq!((chart.width, chart.height), (Some(200), Some(100)));
+        assert_eq!(chart.format, Some(ImageFormat::Svg));
         assert!(
             result
-                .images
-                .contains(&"https://example.com/image.png".to_string())
+                .code
+                .contains(r#"src: "./img/chart.svg", alt: "Chart", width: 200, height: 100"#),
+            "{}",
+            result.code
         );
+        assert_eq!(result.images[1].width, None);
     }
 }
 
@@ -733,6 +789,35 @@ mod gfm_features {
             "GFM strikethrough should be disabled"
         );
     }
+
+    #[test]
+    fn extensions_can_be_disabled_individually() {
+        let mdx = "Visit www.example.com\n\n- [x] done\n\n~~gone~~";
+        let options = MdxCompileOptions::builder().gfm_autolink(false).build();
+        let result = compile_with_options(mdx, options);
+        assert!(!result.code.contains("http://www.example.com"));
+        assert!(result.code.contains("checkbox"));
+        assert!(result.code.contains("_components.del"));
+
+        let options = MdxCompileOptions::builder()
+            .gfm_tasklist(false)
+            .gfm_strikethrough(false)
+            .build();
+        let result = compile_with_options(mdx, options);
+        assert!(result.code.contains("http://www.example.com"));
+        assert!(!result.code.contains("checkbox"));
+        assert!(!result.code.contains("_components.del"));
+    }
+
+    #[test]
+    fn extensions_require_gfm() {
+        let options = MdxCompileOptions::builder()
+            .gfm(false)
+            .gfm_tables(true)
+            .build();
+        let result = compile_with_options("| a |\n| - |\n| b |", options);
+        assert!(!result.code.contains("_components.table"));
+    }
 }
 
 // =============================================================================
@@ -952,3 +1037,807 @@ mod jsx_runtime {
         );
     }
 }
+
+// =============================================================================
+// Excerpt Tests
+// =============================================================================
+
+mod excerpt {
+    use super::*;
+
+    #[test]
+    fn excerpt_defaults_to_first_paragraph() {
+        let mdx = "---\ntitle: Post\n---\n\n# Post\n\nFirst *paragraph*.\n\nSecond paragraph.";
+        let result = compile_mdx(mdx);
+        assert_eq!(result.excerpt.as_deref(), Some("First paragraph."));
+    }
+
+    #[test]
+    fn excerpt_stops_at_separator() {
+        let mdx = "Intro with <Badge>JSX</Badge>.\n\nMore intro.\n\n<!-- more -->\n\nBody.";
+        let result = compile_mdx(mdx);
+        assert_eq!(
+            result.excerpt.as_deref(),
+            Some("Intro with JSX.\n\nMore intro.")
+        );
+        // Separator is not rendered and the body is still compiled
+        assert!(!result.code.contains("more -->"));
+        assert!(result.code.contains("Body."));
+    }
+
+    #[test]
+    fn custom_separator_and_paragraph_count() {
+        let options = MdxCompileOptions::builder()
+            .excerpt_separator("{/* excerpt */}")
+            .build();
+        let result = compile_with_options("One.\n\n{/* excerpt */}\n\nTwo.", options);
+        assert_eq!(result.excerpt.as_deref(), Some("One."));
+
+        let options = MdxCompileOptions::builder().excerpt_paragraphs(2).build();
+        let result = compile_with_options("One.\n\nTwo.\n\nThree.", options);
+        assert_eq!(result.excerpt.as_deref(), Some("One.\n\nTwo."));
+    }
+}
+
+// =============================================================================
+// Reading Time Tests
+// =============================================================================
+
+mod reading_time {
+    use super::*;
+
+    #[test]
+    fn counts_text_but_not_code_or_frontmatter() {
+        let mdx = "---\ntitle: Lots of words here\n---\n\n# Hello world\n\nSome *emphasis* text.\n\n```js\nconst ignored = true;\n```";
+        let result = compile_mdx(mdx);
+        assert_eq!(result.word_count, 5);
+        assert_eq!(result.reading_time_minutes, 1);
+    }
+
+    #[test]
+    fn words_per_minute_is_configurable() {
+        let mdx = "word ".repeat(450);
+        let result = compile_mdx(&mdx);
+        assert_eq!(result.word_count, 450);
+        assert_eq!(result.reading_time_minutes, 3);
+
+        let options = MdxCompileOptions::builder().words_per_minute(500).build();
+        let result = compile_with_options(&mdx, options);
+        assert_eq!(result.reading_time_minutes, 1);
+    }
+
+    #[test]
+    fn empty_document_has_zero_reading_time() {
+        let result = compile_mdx("import X from './x.js'");
+        assert_eq!(result.word_count, 0);
+        assert_eq!(result.reading_time_minutes, 0);
+    }
+}
+
+// =============================================================================
+// Directive Tests
+// =============================================================================
+
+mod directives {
+    use super::*;
+    use fob_mdx::default_directive_components;
+    use std::collections::HashMap;
+
+    fn compile_with_admonitions(mdx: &str) -> MdxCompileResult {
+        let options = MdxCompileOptions::builder()
+            .directives(default_directive_components())
+            .build();
+        compile_with_options(mdx, options)
+    }
+
+    #[test]
+    fn disabled_by_default() {
+        let result = compile_mdx(":::note\nText\n:::");
+        assert!(!result.code.contains("_components.Note"));
+    }
+
+    #[test]
+    fn admonition_becomes_component() {
+        let result =
+            compile_with_admonitions(":::note[Read this]\nSome **bold** advice.\n:::\n\nAfter.");
+        assert!(result.code.contains("_components.Note"));
+        assert!(result.code.contains("title: \"Read this\""));
+        assert!(result.code.contains("_components.strong"));
+        assert!(!result.code.contains(":::"));
+    }
+
+    #[test]
+    fn directive_after_paragraph_is_block() {
+        let result = compile_with_admonitions("Intro.\n:::warning\nCareful.\n:::");
+        assert!(result.code.contains("_components.Warning"));
+        assert!(result.code.contains("\"Careful.\""));
+    }
+
+    #[test]
+    fn errors_keep_source_line_numbers() {
+        let options = MdxCompileOptions::builder()
+            .directives(default_directive_components())
+            .build();
+        let err = compile("Intro.\n:::note\nText\n:::\n\n{oops", options).unwrap_err();
+        assert_eq!(err.line, Some(6));
+    }
+
+    #[test]
+    fn custom_mapping() {
+        let mut directives = HashMap::new();
+        directives.insert("aside".to_string(), "Callout".to_string());
+        let options = MdxCompileOptions::builder().directives(directives).build();
+
+        let result =
+            compile_with_options(":::aside{.wide}\nText\n:::\n\n:::note\nKept\n:::", options);
+        assert!(result.code.contains("_components.Callout"));
+        assert!(result.code.contains("className: \"wide\""));
+        assert!(!result.code.contains("_components.Note"));
+    }
+}
+
+// =============================================================================
+// JSX Target Tests
+// =============================================================================
+
+mod jsx_target {
+    use super::*;
+    use fob_mdx::JsxTarget;
+
+    fn compile_for(target: JsxTarget, format: OutputFormat) -> String {
+        let options = MdxCompileOptions::builder()
+            .jsx_target(target)
+            .output_format(format)
+            .build();
+        compile_with_options("# Title\n\nText", options).code
+    }
+
+    #[test]
+    fn automatic_runtime_targets_pick_default_module() {
+        for (target, module) in [
+            (JsxTarget::Preact, "'preact/jsx-runtime'"),
+            (JsxTarget::Solid, "'solid-js/h/jsx-runtime'"),
+            (JsxTarget::Hono, "'hono/jsx/jsx-runtime'"),
+        ] {
+            let code = compile_for(target, OutputFormat::Program);
+            assert!(code.contains(module), "{:?}: {}", target, code);
+            assert!(code.contains("Fragment as _Fragment"));
+        }
+    }
+
+    #[test]
+    fn explicit_runtime_wins_over_target_default() {
+        let options = MdxCompileOptions::builder()
+            .jsx_target(JsxTarget::Preact)
+            .jsx_runtime("preact/compat/jsx-runtime")
+            .build();
+        let result = compile_with_options("# Hi", options);
+        assert!(result.code.contains("'preact/compat/jsx-runtime'"));
+    }
+
+    #[test]
+    fn vue_uses_h_with_slot_adapter() {
+        let code = compile_for(JsxTarget::Vue, OutputFormat::Program);
+        assert!(code.contains("import {h as _h, Fragment as _Fragment} from 'vue';"));
+        assert!(code.contains("const _jsx = (type, {children, ...props}) => _h("));
+        assert!(code.contains("{default: () => children}"));
+        assert!(!code.contains("jsx-runtime"));
+
+        let code = compile_for(JsxTarget::Vue, OutputFormat::FunctionBody);
+        assert!(code.contains("const {h: _h, Fragment: _Fragment} = arguments[0];"));
+    }
+
+    #[test]
+    fn parse_target_names() {
+        assert_eq!("vue".parse::<JsxTarget>(), Ok(JsxTarget::Vue));
+        assert_eq!("Solid".parse::<JsxTarget>(), Ok(JsxTarget::Solid));
+        assert!("angular".parse::<JsxTarget>().is_err());
+    }
+}
+
+// =============================================================================
+// Structured Content Tests
+// =============================================================================
+
+mod structured_content {
+    use super::*;
+    use fob_mdx::ContentNode;
+
+    #[test]
+    fn disabled_by_default() {
+        assert!(compile_mdx("# Hello").content.is_none());
+    }
+
+    #[test]
+    fn content_reflects_directives_and_components() {
+        let options = MdxCompileOptions::builder()
+            .structured_content(true)
+            .directives(fob_mdx::default_directive_components())
+            .build();
+        let mdx =
+            "---\ntitle: Doc\n---\n\nimport X from './x.js'\n\n# Hello World\n\n:::note\nBody\n:::";
+        let result = compile_with_options(mdx, options);
+
+        let json = serde_json::to_value(result.content.unwrap()).unwrap();
+        let children = json["children"].as_array().unwrap();
+        // Frontmatter and ESM are not part of the content
+        assert_eq!(children.len(), 2);
+        assert_eq!(children[0]["tagName"], "h1");
+        assert_eq!(children[0]["properties"]["id"], "hello-world");
+        assert_eq!(children[1]["type"], "component");
+        assert_eq!(children[1]["name"], "Note");
+    }
+
+    #[test]
+    fn round_trips_through_json() {
+        let options = MdxCompileOptions::builder()
+            .structured_content(true)
+            .build();
+        let result = compile_with_options("Text with {expr} and <Badge count={3} />", options);
+        let content = result.content.unwrap();
+
+        let json = serde_json::to_string(&content).unwrap();
+        let parsed: ContentNode = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, content);
+    }
+}
+
+// =============================================================================
+// Component Mapping Tests
+// =============================================================================
+
+mod component_mapping {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn components(entries: &[(&str, &str)]) -> HashMap<String, String> {
+        entries
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn mapping_is_merged_before_overrides() {
+        let options = MdxCompileOptions::builder()
+            .components(components(&[("h1", "./ui.js#Heading"), ("img", "Image")]))
+            .provider_import_source("@mdx-js/react")
+            .build();
+        let result = compile_with_options("import Image from './img.js'\n\n# Hi", options);
+
+        assert!(
+            result
+                .code
+                .contains("import {Heading as _component0} from './ui.js';")
+        );
+        assert!(
+            result
+                .code
+                .contains("}, {h1: _component0, img: Image}, _provideComponents(), _cProp);")
+        );
+    }
+
+    #[test]
+    fn no_mapping_leaves_output_unchanged() {
+        let result = compile_mdx("# Hi");
+        assert!(result.code.contains("}, _cProp);"));
+        assert!(!result.code.contains("_component0"));
+    }
+
+    #[test]
+    fn invalid_mapping_is_an_error() {
+        let options = MdxCompileOptions::builder()
+            .components(components(&[("h1", "not an identifier")]))
+            .build();
+        assert!(compile("# Hi", options).is_err());
+    }
+}
+
+// =============================================================================
+// Plugin Asset Emission Tests
+// =============================================================================
+
+mod emitted_assets {
+    use super::*;
+    use fob_mdx::{MdxPlugin, MdxPluginContext};
+    use markdown::mdast::Node;
+
+    /// Emits every code block as a separate file
+    struct CodeSamplePlugin;
+
+    impl MdxPlugin for CodeSamplePlugin {
+        fn name(&self) -> &'static str {
+            "code-samples"
+        }
+
+        fn emit_assets(&self, ast: &Node, ctx: &mut MdxPluginContext) -> anyhow::Result<()> {
+            let stem = ctx
+                .filepath()
+                .unwrap_or("doc")
+                .trim_end_matches(".mdx")
+                .to_string();
+            for (index, node) in ast.children().into_iter().flatten().enumerate() {
+                if let Node::Code(code) = node {
+                    ctx.emit_file(format!("{}/sample-{}.js", stem, index), code.value.as_str());
+                }
+            }
+            Ok(())
+        }
+
+        fn as_any(&self) -> &dyn std::any::Any {
+            self
+        }
+    }
+
+    struct FailingPlugin;
+
+    impl MdxPlugin for FailingPlugin {
+        fn name(&self) -> &'static str {
+            "failing"
+        }
+
+        fn emit_assets(&self, _ast: &Node, _ctx: &mut MdxPluginContext) -> anyhow::Result<()> {
+            anyhow::bail!("disk full")
+        }
+
+        fn as_any(&self) -> &dyn std::any::Any {
+            self
+        }
+    }
+
+    #[test]
+    fn no_assets_by_default() {
+        assert!(compile_mdx("# Hello").emitted_assets.is_empty());
+    }
+
+    #[test]
+    fn plugin_assets_are_collected() {
+        let options = MdxCompileOptions::builder()
+            .filepath("intro.mdx")
+            .plugins(vec![Box::new(CodeSamplePlugin)])
+            .build();
+        let result = compile_with_options("# Intro\n\n```js\nrun()\n```", options);
+
+        assert_eq!(result.emitted_assets.len(), 1);
+        assert_eq!(result.emitted_assets[0].path, "intro/sample-1.js");
+        assert_eq!(result.emitted_assets[0].content, b"run()");
+    }
+
+    #[test]
+    fn emission_errors_name_the_plugin() {
+        let options = MdxCompileOptions::builder()
+            .plugins(vec![Box::new(FailingPlugin)])
+            .build();
+        let err = compile("# Hi", options).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("Plugin 'failing' failed while emitting assets: disk full")
+        );
+    }
+}
+
+// =============================================================================
+// Static HTML Rendering Tests
+// =============================================================================
+
+mod html_rendering {
+    use super::*;
+    use fob_mdx::render_html;
+
+    fn html(mdx: &str) -> String {
+        render_html(mdx, MdxCompileOptions::default()).unwrap().html
+    }
+
+    #[test]
+    fn renders_markdown() {
+        assert_eq!(
+            html("# Hello World\n\nSome **bold** & `<code>`"),
+            "<h1 id=\"hello-world\">Hello World</h1><p>Some <strong>bold</strong> &amp; <code>&lt;code&gt;</code></p>"
+        );
+    }
+
+    #[test]
+    fn renders_code_lists_and_void_elements() {
+        let output = html("```rust title=\"main.rs\"\nfn main() {}\n```\n\n- [x] done\n\n---");
+        assert!(output.contains("<pre><code class=\"language-rust\">fn main() {}</code></pre>"));
+        assert!(output.contains("<li><input checked disabled type=\"checkbox\"> "));
+        assert!(output.ends_with("<hr>"));
+    }
+
+    #[test]
+    fn renders_html_elements_and_literal_expressions() {
+        let mdx = "---\ntitle: Intro\n---\n\n<div className=\"box\" hidden={false} data-n={2}>{frontmatter.title} {\"x\"} {/* note */}</div>";
+        let result = render_html(mdx, MdxCompileOptions::default()).unwrap();
+        assert_eq!(
+            result.html,
+            "<p><div class=\"box\" data-n=\"2\">Intro x </div></p>"
+        );
+        assert!(result.frontmatter.is_some());
+    }
+
+    #[test]
+    fn rejects_components_and_dynamic_expressions() {
+        let err = render_html("<Chart />", MdxCompileOptions::default()).unwrap_err();
+        assert!(err.message.contains("Component <Chart>"));
+
+        let options = MdxCompileOptions::builder().filepath("post.mdx").build();
+        let err = render_html("Count: {items.length}", options).unwrap_err();
+        assert!(err.message.contains("{items.length}"));
+        assert_eq!(err.file.as_deref(), Some("post.mdx"));
+    }
+}
+
+// =============================================================================
+// Math Output Tests
+// =============================================================================
+
+mod math_output {
+    use super::*;
+    use fob_mdx::{MathOutput, render_html};
+
+    fn options() -> MdxCompileOptions {
+        MdxCompileOptions::builder()
+            .math_output(MathOutput::MathMl)
+            .build()
+    }
+
+    #[test]
+    fn tex_spans_by_default() {
+        let result = compile("$x^2$", MdxCompileOptions::default()).unwrap();
+        assert!(result.code.contains("math math-inline"));
+        assert!(!result.code.contains("\"math\""));
+    }
+
+    #[test]
+    fn mathml_elements_in_code() {
+        let result = compile("Area $\\pi r^2$\n\n$$\n\\frac{a}{b}\n$$", options()).unwrap();
+        assert!(result.code.contains("_jsx(\"math\""));
+        assert!(result.code.contains("\"mfrac\""));
+        assert!(!result.code.contains("math math-inline"));
+        // Rendered math doesn't count as words
+        assert_eq!(result.word_count, 1);
+    }
+
+    #[test]
+    fn mathml_in_static_html() {
+        let result = render_html("# Euler $e^{i\\pi}$", options()).unwrap();
+        assert!(
+            result.html.starts_with("<h1 id=\"euler\">Euler <math display=\"inline\" xmlns=\"http://www.w3.org/1998/Math/MathML\"><semantics><msup><mi>e</mi><mrow><mi>i</mi><mi>π</mi></mrow></msup><annotation encoding=\"application/x-tex\">e^{i\\pi}</annotation></semantics></math></h1>"),
+            "{}",
+            result.html
+        );
+    }
+
+    #[test]
+    fn parses_from_str() {
+        assert_eq!("MathML".parse::<MathOutput>(), Ok(MathOutput::MathMl));
+        assert!("katex".parse::<MathOutput>().is_err());
+    }
+}
+
+// =============================================================================
+// Heading ID Tests
+// =============================================================================
+
+mod heading_ids {
+    use super::*;
+    use fob_mdx::{HeadingIdOptions, Slugify};
+
+    #[test]
+    fn prefix_and_style_apply_to_code_and_content() {
+        let options = MdxCompileOptions::builder()
+            .heading_ids(
+                HeadingIdOptions::builder()
+                    .prefix("docs-")
+                    .slugify(Slugify::GitHub)
+                    .build(),
+            )
+            .structured_content(true)
+            .build();
+        let result = compile_with_options("# Q & A\n\n## Q & A", options);
+
+        assert!(result.code.contains(r#"id: "docs-q--a""#));
+        assert!(result.code.contains(r#"id: "docs-q--a-1""#));
+
+        let json = serde_json::to_value(result.content.unwrap()).unwrap();
+        assert_eq!(json["children"][0]["properties"]["id"], "docs-q--a");
+        assert_eq!(json["children"][1]["properties"]["id"], "docs-q--a-1");
+    }
+
+    #[test]
+    fn literal_suffixes_are_not_reused() {
+        let result = compile_mdx("# Intro\n\n# Intro 1\n\n# Intro");
+        assert!(result.code.contains(r#"id: "intro""#));
+        assert!(result.code.contains(r#"id: "intro-1""#));
+        assert!(result.code.contains(r#"id: "intro-2""#));
+    }
+}
+
+// =============================================================================
+// Link Rewriting Tests
+// =============================================================================
+
+mod link_rewrite {
+    use super::*;
+    use fob_mdx::LinkRewrite;
+
+    const MDX: &str = "[Next](./next-post.mdx#top) [Site](https://example.com) [Top](#top)\n\n[ref]: ../other.md\n\n<a href=\"./jsx.mdx\">JSX</a>";
+
+    #[test]
+    fn links_are_collected_without_rewriting() {
+        let result = compile_mdx(MDX);
+        assert_eq!(
+            result.links,
+            vec![
+                "./next-post.mdx#top",
+                "https://example.com",
+                "../other.md",
+                "./jsx.mdx"
+            ]
+        );
+    }
+
+    #[test]
+    fn pattern_rewrites_code_and_links() {
+        let options = MdxCompileOptions::builder()
+            .link_rewrite(LinkRewrite::pattern("/blog/{slug}"))
+            .build();
+        let result = compile_with_options(MDX, options);
+        assert!(result.code.contains(r#"href: "/blog/next-post#top""#));
+        assert!(result.code.contains(r#"href: "/blog/jsx""#));
+        assert_eq!(
+            result.links,
+            vec![
+                "/blog/next-post#top",
+                "https://example.com",
+                "/blog/other",
+                "/blog/jsx"
+            ]
+        );
+    }
+
+    #[test]
+    fn pattern_resolves_from_document_directory() {
+        let options = MdxCompileOptions::builder()
+            .filepath("posts/a.mdx")
+            .link_rewrite(LinkRewrite::pattern("/docs/{path}"))
+            .build();
+        let result = compile_with_options("[Guide](../guide.mdx) [B](./b.mdx)", options);
+        assert_eq!(result.links, vec!["/docs/guide", "/docs/posts/b"]);
+    }
+
+    #[test]
+    fn callback_rewrites_links() {
+        let options = MdxCompileOptions::builder()
+            .link_rewrite(LinkRewrite::custom(|path| {
+                Some(path.trim_start_matches("./").replace(".mdx", ".html"))
+            }))
+            .build();
+        let result = compile_with_options("[Next](./next.mdx)", options);
+        assert_eq!(result.links, vec!["next.html"]);
+    }
+}
+
+// =============================================================================
+// Include Tests
+// =============================================================================
+
+mod includes {
+    use super::*;
+    use fob_mdx::IncludeOptions;
+    use std::collections::HashMap;
+    use std::path::PathBuf;
+    use std::sync::Arc;
+
+    fn options(files: &[(&str, &str)]) -> MdxCompileOptions {
+        let files: HashMap<PathBuf, Vec<u8>> = files
+            .iter()
+            .map(|(path, content)| (PathBuf::from(path), content.as_bytes().to_vec()))
+            .collect();
+        MdxCompileOptions::builder()
+            .filepath("docs/guide.mdx")
+            .include(IncludeOptions::builder().source(Arc::new(files)).build())
+            .directives(fob_mdx::default_directive_components())
+            .build()
+    }
+
+    #[test]
+    fn includes_are_compiled_as_markdown() {
+        let options = options(&[(
+            "docs/shared/warning.mdx",
+            ":::warning\nBack up **first**\n:::\n",
+        )]);
+        let result = compile_with_options(
+            "# Setup\n\n<Include src=\"./shared/warning.mdx\" />\n\nDone.",
+            options,
+        );
+        assert!(
+            result.code.contains("_components.Warning"),
+            "{}",
+            result.code
+        );
+        assert!(result.code.contains("\"first\""));
+        assert!(!result.code.contains("Include"));
+        assert_eq!(
+            result.includes,
+            vec![PathBuf::from("docs/shared/warning.mdx")]
+        );
+    }
+
+    #[test]
+    fn include_is_a_component_when_disabled() {
+        let result = compile_mdx("<Include src=\"./shared/warning.mdx\" />");
+        assert!(result.code.contains("Include"));
+        assert!(result.includes.is_empty());
+    }
+
+    #[test]
+    fn include_cycle_is_an_error() {
+        let options = options(&[("docs/a.mdx", "{/* include: ./guide.mdx */}")]);
+        let err = compile("{/* include: ./a.mdx */}", options).unwrap_err();
+        assert_eq!(
+            err.message,
+            "Include cycle: docs/guide.mdx -> docs/a.mdx -> docs/guide.mdx"
+        );
+        assert_eq!(err.file.as_deref(), Some("docs/guide.mdx"));
+    }
+}
+
+// =============================================================================
+// Section Compilation Tests
+// =============================================================================
+
+mod sections {
+    use super::*;
+    use fob_mdx::{MdxSection, compile_sections};
+
+    #[test]
+    fn sections_compile_independently() {
+        let mdx = "---\ntitle: Book\n---\nimport {Figure} from './figure.js'\n\n# Part one\n\n<Figure />\n\n## Chapter\n\n<Figure />\n";
+        let mut sections: Vec<MdxSection> = Vec::new();
+        let count = compile_sections(mdx.as_bytes(), 2, MdxCompileOptions::new, |section| {
+            sections.push(section);
+            Ok(())
+        })
+        .unwrap();
+
+        assert_eq!(count, 2);
+        assert_eq!(sections[1].line, 10);
+        assert!(sections[0].result.frontmatter.is_some());
+        assert!(sections[1].result.frontmatter.is_none());
+        for section in &sections {
+            assert_eq!(section.result.imports.len(), 1);
+            assert!(
+                section.result.code.contains("_jsx(Figure, {})"),
+                "{}",
+                section.result.code
+            );
+        }
+    }
+
+    #[test]
+    fn callback_error_stops_compilation() {
+        let mut seen = 0;
+        let err = compile_sections("# A\n# B\n".as_bytes(), 1, MdxCompileOptions::new, |_| {
+            seen += 1;
+            Err(Box::new(fob_mdx::MdxError::new("stop")))
+        })
+        .unwrap_err();
+        assert_eq!(err.message, "stop");
+        assert_eq!(seen, 1);
+    }
+}
+
+// =============================================================================
+// Matter Tests
+// =============================================================================
+
+mod matter {
+    use super::*;
+
+    #[test]
+    fn matter_merges_frontmatter_and_exports() {
+        let mdx = "---\ntitle: Post\ndraft: true\n---\n\nexport const draft = false\nexport const slug = `posts/${frontmatter.title}`\nexport const Chart = () => null\n\n# Hello\n";
+        let result = compile_mdx(mdx);
+        assert_eq!(
+            result.matter(),
+            serde_json::json!({ "title": "Post", "draft": false, "slug": "posts/Post" })
+        );
+    }
+
+    #[test]
+    fn matter_without_frontmatter_is_empty_object() {
+        assert_eq!(compile_mdx("# Hello").matter(), serde_json::json!({}));
+    }
+}
+
+// =============================================================================
+// Plain Markdown Tests
+// =============================================================================
+
+mod plain_markdown {
+    use super::*;
+    use fob_mdx::render_html;
+
+    fn options() -> MdxCompileOptions {
+        MdxCompileOptions::builder().markdown(true).build()
+    }
+
+    #[test]
+    fn treats_mdx_syntax_as_text() {
+        let md = "import x from 'y'\n\nUse {braces} and a < b.\n\n:::note\nHi\n:::";
+        let html = render_html(md, options()).unwrap().html;
+        assert_eq!(
+            html,
+            "<p>import x from 'y'</p><p>Use {braces} and a &lt; b.</p><p>:::note\nHi\n:::</p>"
+        );
+
+        let result = compile_with_options(md, options());
+        assert!(result.imports.is_empty());
+        assert!(result.code.contains("Use {braces} and a < b."));
+    }
+
+    #[test]
+    fn keeps_raw_html_in_html_output_only() {
+        let md = "---\ntitle: Changes\n---\n\n<details><summary>More</summary>\n\nText\n\n</details>\n\n<!-- note -->";
+        let result = render_html(md, options()).unwrap();
+        assert_eq!(
+            result.html,
+            "<details><summary>More</summary><p>Text</p></details><!-- note -->"
+        );
+        assert!(result.frontmatter.is_some());
+
+        let compiled = compile_with_options(md, options());
+        assert!(!compiled.code.contains("<details>"));
+        assert!(compiled.code.contains("\"Text\""));
+    }
+}
+
+// =============================================================================
+// Layout Tests
+// =============================================================================
+
+mod layouts {
+    use super::*;
+
+    #[test]
+    fn wraps_content_in_layout() {
+        let options = MdxCompileOptions::builder()
+            .layout("/site/layouts/Post.jsx")
+            .build();
+        let result = compile_with_options("---\ntitle: Hello\n---\n\n# Hello", options);
+
+        assert!(
+            result
+                .code
+                .contains("import _Layout from '/site/layouts/Post.jsx';")
+        );
+        assert!(result.code.contains(
+            "return _jsx(_Layout, {...props, components: _cProp, frontmatter, children: _jsx(_components.h1"
+        ));
+        assert!(result.code.contains("export default function MDXContent"));
+    }
+
+    #[test]
+    fn omits_frontmatter_prop_without_frontmatter() {
+        let options = MdxCompileOptions::builder().layout("./Layout.jsx").build();
+        let result = compile_with_options("", options);
+
+        assert!(
+            result
+                .code
+                .contains("return _jsx(_Layout, {...props, components: _cProp, children: null});")
+        );
+    }
+
+    #[test]
+    fn rejects_layout_in_function_body() {
+        let options = MdxCompileOptions::builder()
+            .layout("./Layout.jsx")
+            .output_format(OutputFormat::FunctionBody)
+            .build();
+        let err = compile("# Hi", options).unwrap_err();
+
+        assert!(err.message.contains("function-body output format"));
+    }
+}
@@ -18,10 +18,14 @@ export const SourceMapMode = Object.freeze({
 
 // Use wrapped Fob class that supports flexible entries
 export const Fob = wrapper.Fob
+export const bundleMdx = wrapper.bundleMdx
 export const bundleSingle = wrapper.bundleSingle
+export const capabilities = wrapper.capabilities
+export const compileMdx = wrapper.compileMdx
 export const initLogging = wrapper.initLogging
 export const initLoggingFromEnv = wrapper.initLoggingFromEnv
 export const version = wrapper.version
+export const watch = wrapper.watch
 export const normalizeEntries = wrapper.normalizeEntries
 
 export default {
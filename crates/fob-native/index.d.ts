@@ -59,6 +59,12 @@ export declare class Fob {
   static components(entries: Array<string>, options?: BuildOptions | undefined | null): Promise<BundleResult>
 }
 
+/** Handle of a running watch */
+export declare class FobWatcher {
+  /** Stop watching. A build in progress still completes and reports its result. */
+  close(): void
+}
+
 /** Options for app builds with code splitting. */
 export interface AppOptions {
   /** Output directory (defaults to "dist") */
@@ -169,13 +175,47 @@ export interface BundleConfig {
    * Keys should use "virtual:" prefix (e.g., "virtual:main.ts")
    */
   virtualFiles?: Record<string, string>
+  /**
+   * JavaScript plugins, called in array order after fob's virtual module
+   * resolution and before asset handling
+   */
+  plugins?: Array<JsPlugin>
 }
 
 /** Result of a bundle operation */
 export interface BundleResult {
   /** Generated chunks */
   chunks: Array<ChunkInfo>
-  /** Bundle manifest */
+  /**
+ * Plugin implemented in JavaScript
+ *
+ * Hooks return `null`/`undefined` to defer to the next plugin. The
+ * binding itself only accepts async hooks returning objects; the JS
+ * wrapper adapts synchronous hooks and plain string results.
+ */
+export interface JsPlugin {
+  /** Plugin name, used in error messages */
+  name: string
+  /** Resolve an import specifier to a module id */
+  resolveId?: (specifier: string, importer?: string) => ResolveIdResult | string | null | undefined | Promise<ResolveIdResult | string | null | undefined>
+  /** Load the source of a module id */
+  load?: (id: string) => LoadResult | string | null | undefined | Promise<LoadResult | string | null | undefined>
+  /** Transform the source of a module */
+  transform?: (code: string, id: string) => TransformResult | string | null | undefined | Promise<TransformResult | string | null | undefined>
+}
+
+/** Result of a `load` hook */
+export interface LoadResult {
+  /** Module source */
+  code: string
+  /**
+   * Module type: "js" | "jsx" | "ts" | "tsx" | "json" | "css"
+   * Default: inferred from the id
+   */
+  moduleType?: string
+}
+
+/** Bundle manifest */
   manifest: ManifestInfo
   /** Build statistics */
   stats: BuildStatsInfo
@@ -185,6 +225,23 @@ export interface BundleResult {
   moduleCount: number
 }
 
+/**
+ * Compile MDX and bundle it with its imports into a single ESM module
+ *
+ * Imports are resolved against `options.files`; the filesystem is not read.
+ *
+ * @example
+ * ```typescript
+ * import { bundleMdx } from '@fob/native';
+ *
+ * const { code, matter } = await bundleMdx({
+ *   source: "import Button from './Button.jsx'\n\n<Button>Hi</Button>",
+ *   files: { './Button.jsx': 'export default (props) => props.children' },
+ * });
+ * ```
+ */
+export declare function bundleMdx(options: MdxBundleOptions): Promise<MdxBundleResult>
+
 /**
  * Quick helper to bundle a single entry
  *
@@ -193,6 +250,54 @@ export interface BundleResult {
 export declare function bundleSingle(entry: string, outputDir: string, format?: string | undefined | null): Promise<BundleResult>
 
 /** Detailed chunk information */
+/**
+ * Report the features, plugins and limits of this build
+ *
+ * @example
+ * ```typescript
+ * import { capabilities } from '@fob/native';
+ *
+ * if (capabilities().features.includes('dts-generation')) {
+ *   // emit declarations
+ * }
+ * ```
+ */
+export declare function capabilities(): Capabilities
+
+/** Features, plugins and limits of this native build */
+export interface Capabilities {
+  /** Version of the report shape; fields are only added within a version */
+  schemaVersion: number
+  /** Bundler version */
+  version: string
+  /** Cargo features compiled into the bundler */
+  features: Array<string>
+  /** Built-in plugins */
+  plugins: Array<PluginCapability>
+  /** Supported output formats: "esm" | "cjs" | "iife" */
+  outputFormats: Array<string>
+  /** Hard limits and defaults enforced by the bundler */
+  limits: CapabilityLimits
+}
+
+/** Size (bytes) and count limits */
+export interface CapabilityLimits {
+  /** Maximum entry points per build */
+  maxEntryPoints: number
+  /** Maximum source file size during graph analysis */
+  maxFileSize: number
+  /** Default maximum number of modules in a graph */
+  maxModules: number
+  /** Default maximum import depth */
+  maxDepth: number
+  /** Default maximum imported asset size */
+  maxAssetSize: number
+  /** Maximum virtual file size */
+  maxVirtualFileSize: number
+  /** Default chunk size above which a warning is reported */
+  chunkSizeWarningLimit: number
+}
+
 export interface ChunkInfo {
   /** Chunk identifier */
   id: string
@@ -239,6 +344,24 @@ export interface CodeSplittingConfig {
   minImports: number
 }
 
+/**
+ * Compile MDX source to JSX
+ *
+ * Returns the compiled module along with the document's frontmatter,
+ * table of contents and images. Imports are left as-is; use `bundleMdx`
+ * to inline them.
+ *
+ * @param filepath - Path of the document, used in error messages
+ *
+ * @example
+ * ```typescript
+ * import { compileMdx } from '@fob/native';
+ *
+ * const { code, frontmatter, toc } = compileMdx('---\ntitle: Hi\n---\n\n# Hello');
+ * ```
+ */
+export declare function compileMdx(source: string, options?: MdxOptions | undefined | null, filepath?: string | undefined | null): MdxCompileResult
+
 /**
  * Initialize fob logging with specified level
  *
@@ -284,6 +407,91 @@ export interface ManifestInfo {
   version: string
 }
 
+/** Options for bundling a single MDX document with `bundleMdx` */
+export interface MdxBundleOptions {
+  /** MDX source code */
+  source: string
+  /**
+   * Virtual files the document imports (path relative to the MDX → content)
+   * e.g. { "./Button.jsx": "export default ..." }
+   */
+  files?: Record<string, string>
+  /** MDX compilation options */
+  mdx?: MdxOptions
+  /** Source map mode: "inline" | "external" (default: no source map) */
+  sourcemap?: string
+  /** Enable minification (default: false) */
+  minify?: boolean
+}
+
+/** Result of bundling an MDX document with its imports */
+export interface MdxBundleResult {
+  /** Self-contained ESM bundle */
+  code: string
+  /** Frontmatter block (None if the document has none) */
+  frontmatter?: MdxFrontmatter
+  /** Frontmatter data merged with the statically computable exports */
+  matter: any
+  /** Source map JSON (only with sourcemap: "external") */
+  map?: string
+  /** CSS imported by the document and its components */
+  css?: string
+}
+
+/** Result of compiling an MDX document */
+export interface MdxCompileResult {
+  /** Compiled JSX module code */
+  code: string
+  /** Frontmatter block (None if the document has none) */
+  frontmatter?: MdxFrontmatter
+  /** Headings in document order */
+  toc: Array<MdxTocEntry>
+  /** Images in document order */
+  images: Array<MdxImage>
+  /** Names exported with `export const` / `export function` */
+  namedExports: Array<string>
+  /** Modules re-exported with `export ... from` */
+  reexports: Array<string>
+  /** Imported module specifiers */
+  imports: Array<string>
+  /** Default export, if the document declares one */
+  defaultExport?: string
+  /** Plain-text excerpt */
+  excerpt?: string
+  /** Number of words in the text content (code excluded) */
+  wordCount: number
+  /** Estimated reading time in minutes, rounded up */
+  readingTimeMinutes: number
+  /** Outgoing link URLs after rewriting */
+  links: Array<string>
+}
+
+/** Parsed frontmatter */
+export interface MdxFrontmatter {
+  /** Source text of the block */
+  raw: string
+  /** Block format: "yaml" | "toml" */
+  format: string
+  /** Parsed data */
+  data: any
+}
+
+/** Image referenced by the document */
+export interface MdxImage {
+  /** URL as written in the document */
+  url: string
+  /** Alternative text */
+  alt: string
+  /** Intrinsic width in pixels (None if unknown) */
+  width?: number
+  /** Intrinsic height in pixels (None if unknown) */
+  height?: number
+  /** Image format: "png" | "jpeg" | "gif" | "webp" | "svg" (None if unknown) */
+  format?: string
+  /** Blurred placeholder as a data URI (None if not generated) */
+  placeholder?: string
+}
+
 /**
  * MDX compilation options
  *
@@ -311,6 +519,12 @@ export interface MdxOptions {
    * Default: "react/jsx-runtime"
    */
   jsxRuntime?: string
+  /**
+   * Framework the JSX targets: "react" | "preact" | "vue" | "solid" | "hono"
+   * (case-insensitive). Selects the default JSX runtime module.
+   * Default: "react"
+   */
+  jsxTarget?: string
   /**
    * Use default plugins (heading IDs, image optimization)
    * Default: true
@@ -318,6 +532,16 @@ export interface MdxOptions {
   useDefaultPlugins?: boolean
 }
 
+/** Table of contents entry */
+export interface MdxTocEntry {
+  /** Heading level (1-6) */
+  depth: number
+  /** Heading text */
+  text: string
+  /** Heading `id` (None when heading IDs are disabled) */
+  id?: string
+}
+
 /** Module information */
 export interface ModuleInfo {
   /** Module path */
@@ -328,5 +552,54 @@ export interface ModuleInfo {
   hasSideEffects?: boolean
 }
 
+/** Built-in plugin description */
+export interface PluginCapability {
+  /** Plugin name */
+  name: string
+  /** What the plugin handles */
+  description: string
+}
+
+/** Result of a `resolveId` hook */
+export interface ResolveIdResult {
+  /** Resolved module id */
+  id: string
+  /** Keep the import in the output instead of bundling it (default: false) */
+  external?: boolean
+}
+
+/** Result of a `transform` hook */
+export interface TransformResult {
+  /** Transformed source */
+  code: string
+}
+
 /** Get the bundler version */
 export declare function version(): string
+
+/**
+ * Build the configured entries, then rebuild whenever a file under the
+ * working directory changes
+ *
+ * `onEvent` is called with a "start" event before every build and a
+ * "success" or "failure" event after it. Changes arriving within 100ms of
+ * each other are batched into one rebuild. `node_modules`, `.git` and the
+ * output directory are not watched.
+ *
+ * The JS wrapper returns an EventEmitter instead; prefer that.
+ */
+export declare function watch(config: BundleConfig, onEvent: (event: WatchEvent) => void): FobWatcher
+
+/** Rebuild event reported by `watch` */
+export interface WatchEvent {
+  /** Event kind: "start" | "success" | "failure" */
+  kind: string
+  /** Files whose changes triggered the build (empty for the initial build) */
+  changed: Array<string>
+  /** Build duration in milliseconds ("success" and "failure" only) */
+  durationMs?: number
+  /** Bundle result ("success" only) */
+  result?: BundleResult
+  /** Error details in the format `bundle()` throws ("failure" only) */
+  error?: any
+}
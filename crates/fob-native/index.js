@@ -573,7 +573,12 @@ if (!nativeBinding) {
 
 module.exports = nativeBinding
 module.exports.Fob = nativeBinding.Fob
+module.exports.FobWatcher = nativeBinding.FobWatcher
+module.exports.bundleMdx = nativeBinding.bundleMdx
 module.exports.bundleSingle = nativeBinding.bundleSingle
+module.exports.capabilities = nativeBinding.capabilities
+module.exports.compileMdx = nativeBinding.compileMdx
 module.exports.initLogging = nativeBinding.initLogging
 module.exports.initLoggingFromEnv = nativeBinding.initLoggingFromEnv
 module.exports.version = nativeBinding.version
+module.exports.watch = nativeBinding.watch
@@ -105,6 +105,7 @@ impl Fob {
             code_splitting: None,
             external_from_manifest: None,
             virtual_files: None,
+            plugins: None,
         };
 
         let fob = Self::new(config)?;
@@ -139,6 +140,7 @@ impl Fob {
             code_splitting: None,
             external_from_manifest: Some(true),
             virtual_files: None,
+            plugins: None,
         };
 
         let fob = Self::new(config)?;
@@ -173,6 +175,7 @@ impl Fob {
             code_splitting: opts.code_splitting.clone(),
             external_from_manifest: None,
             virtual_files: None,
+            plugins: None,
         };
 
         let fob = Self::new(config)?;
@@ -209,6 +212,7 @@ impl Fob {
             code_splitting: None,
             external_from_manifest: Some(true),
             virtual_files: None,
+            plugins: None,
         };
 
         let fob = Self::new(config)?;
@@ -0,0 +1,103 @@
+//! Capability report for feature detection from JavaScript.
+
+use napi_derive::napi;
+
+/// Features, plugins and limits of this native build
+#[napi(object)]
+pub struct Capabilities {
+    /// Version of the report shape; fields are only added within a version
+    pub schema_version: u32,
+    /// Bundler version
+    pub version: String,
+    /// Cargo features compiled into the bundler
+    pub features: Vec<String>,
+    /// Built-in plugins
+    pub plugins: Vec<PluginCapability>,
+    /// Supported output formats: "esm" | "cjs" | "iife"
+    pub output_formats: Vec<String>,
+    /// Hard limits and defaults enforced by the bundler
+    pub limits: CapabilityLimits,
+}
+
+/// Built-in plugin description
+#[napi(object)]
+pub struct PluginCapability {
+    /// Plugin name
+    pub name: String,
+    /// What the plugin handles
+    pub description: String,
+}
+
+/// Size (bytes) and count limits
+#[napi(object)]
+pub struct CapabilityLimits {
+    /// Maximum entry points per build
+    pub max_entry_points: u32,
+    /// Maximum source file size during graph analysis
+    pub max_file_size: u32,
+    /// Default maximum number of modules in a graph
+    pub max_modules: u32,
+    /// Default maximum import depth
+    pub max_depth: u32,
+    /// Default maximum imported asset size
+    pub max_asset_size: u32,
+    /// Maximum virtual file size
+    pub max_virtual_file_size: u32,
+    /// Default chunk size above which a warning is reported
+    pub chunk_size_warning_limit: u32,
+}
+
+/// Report the features, plugins and limits of this build
+///
+/// @example
+/// ```typescript
+/// import { capabilities } from '@fob/native';
+///
+/// if (capabilities().features.includes('dts-generation')) {
+///   // emit declarations
+/// }
+/// ```
+#[napi]
+pub fn capabilities() -> Capabilities {
+    let report = fob_bundler::capabilities();
+    let limits = &report.limits;
+
+    let mut plugins: Vec<PluginCapability> = report
+        .plugins
+        .iter()
+        .map(|plugin| PluginCapability {
+            name: plugin.name.to_string(),
+            description: plugin.description.to_string(),
+        })
+        .collect();
+    // MDX support is added by the binding, not fob-bundler itself
+    plugins.push(PluginCapability {
+        name: "fob-mdx".to_string(),
+        description: "MDX compilation (enabled for .mdx entries)".to_string(),
+    });
+
+    Capabilities {
+        schema_version: report.schema_version,
+        version: report.version.to_string(),
+        features: report.features.iter().map(|f| f.to_string()).collect(),
+        plugins,
+        output_formats: report
+            .output_formats
+            .iter()
+            .map(|f| f.to_string())
+            .collect(),
+        limits: CapabilityLimits {
+            max_entry_points: to_u32(limits.max_entry_points as u64),
+            max_file_size: to_u32(limits.max_file_size as u64),
+            max_modules: to_u32(limits.max_modules as u64),
+            max_depth: to_u32(limits.max_depth as u64),
+            max_asset_size: to_u32(limits.max_asset_size),
+            max_virtual_file_size: to_u32(limits.max_virtual_file_size as u64),
+            chunk_size_warning_limit: to_u32(limits.chunk_size_warning_limit as u64),
+        },
+    }
+}
+
+fn to_u32(value: u64) -> u32 {
+    u32::try_from(value).unwrap_or(u32::MAX)
+}
@@ -1,5 +1,6 @@
 //! Bundle configuration types
 
+use crate::api::plugin::JsPlugin;
 use crate::api::primitives::CodeSplittingConfig;
 use napi_derive::napi;
 use std::collections::HashMap;
@@ -23,14 +24,35 @@ pub struct MdxOptions {
     /// JSX runtime module path
     /// Default: "react/jsx-runtime"
     pub jsx_runtime: Option<String>,
+    /// Framework the JSX targets: "react" | "preact" | "vue" | "solid" | "hono"
+    /// (case-insensitive). Selects the default JSX runtime module.
+    /// Default: "react"
+    pub jsx_target: Option<String>,
     /// Use default plugins (heading IDs, image optimization)
     /// Default: true
     pub use_default_plugins: Option<bool>,
 }
 
-/// Bundle configuration
+/// Options for bundling a single MDX document with `bundleMdx`
 #[napi(object)]
 #[derive(Debug, Clone, Default)]
+pub struct MdxBundleOptions {
+    /// MDX source code
+    pub source: String,
+    /// Virtual files the document imports (path relative to the MDX → content)
+    /// e.g. { "./Button.jsx": "export default ..." }
+    pub files: Option<HashMap<String, String>>,
+    /// MDX compilation options
+    pub mdx: Option<MdxOptions>,
+    /// Source map mode: "inline" | "external" (default: no source map)
+    pub sourcemap: Option<String>,
+    /// Enable minification (default: false)
+    pub minify: Option<bool>,
+}
+
+/// Bundle configuration
+#[napi(object, object_to_js = false)]
+#[derive(Debug, Default)]
 pub struct BundleConfig {
     /// Entry points to bundle
     pub entries: Vec<String>,
@@ -74,6 +96,10 @@ pub struct BundleConfig {
     /// Used internally when entries have inline content via the JS wrapper.
     /// Keys should use "virtual:" prefix (e.g., "virtual:main.ts")
     pub virtual_files: Option<HashMap<String, String>>,
+
+    /// JavaScript plugins, called in array order after fob's virtual module
+    /// resolution and before asset handling
+    pub plugins: Option<Vec<JsPlugin>>,
 }
 
 // Note: Builder pattern is not exposed via NAPI due to limitations with moving self.
@@ -80,6 +80,7 @@ pub async fn bundle_single(
         code_splitting: None,
         external_from_manifest: None,
         virtual_files: None,
+        plugins: None,
     };
 
     let bundler = CoreBundler::new(config).map_err(|e| Error::from_reason(e.to_string()))?;
@@ -0,0 +1,94 @@
+//! Standalone MDX functions
+
+use crate::api::config::{MdxBundleOptions, MdxOptions};
+use crate::conversion::mdx::{convert_mdx_options, convert_mdx_sourcemap};
+use crate::error::{FobErrorDetails, MdxSyntaxError, RuntimeError, ValidationError};
+use crate::mdx_result::{MdxBundleResult, MdxCompileResult};
+use napi::bindgen_prelude::*;
+use napi_derive::napi;
+
+/// Compile MDX source to JSX
+///
+/// Returns the compiled module along with the document's frontmatter,
+/// table of contents and images. Imports are left as-is; use `bundleMdx`
+/// to inline them.
+///
+/// @param filepath - Path of the document, used in error messages
+///
+/// @example
+/// ```typescript
+/// import { compileMdx } from '@fob/native';
+///
+/// const { code, frontmatter, toc } = compileMdx('---\ntitle: Hi\n---\n\n# Hello');
+/// ```
+#[napi]
+pub fn compile_mdx(
+    source: String,
+    options: Option<MdxOptions>,
+    filepath: Option<String>,
+) -> Result<MdxCompileResult> {
+    let mut compile_options = convert_mdx_options(options.as_ref()).map_err(validation_error)?;
+    compile_options.filepath = filepath;
+    // The table of contents is read from the structured document
+    compile_options.structured_content = true;
+
+    let result = fob_mdx::compile(&source, compile_options).map_err(|e| mdx_error(*e))?;
+    Ok(MdxCompileResult::from(result))
+}
+
+/// Compile MDX and bundle it with its imports into a single ESM module
+///
+/// Imports are resolved against `options.files`; the filesystem is not read.
+///
+/// @example
+/// ```typescript
+/// import { bundleMdx } from '@fob/native';
+///
+/// const { code, matter } = await bundleMdx({
+///   source: "import Button from './Button.jsx'\n\n<Button>Hi</Button>",
+///   files: { './Button.jsx': 'export default (props) => props.children' },
+/// });
+/// ```
+#[napi]
+pub async fn bundle_mdx(options: MdxBundleOptions) -> Result<MdxBundleResult> {
+    let mdx_options = convert_mdx_options(options.mdx.as_ref()).map_err(validation_error)?;
+    let sourcemap =
+        convert_mdx_sourcemap(options.sourcemap.as_deref()).map_err(validation_error)?;
+
+    let bundle_options = fob_mdx::BundleMdxOptions {
+        source: options.source,
+        files: options.files.unwrap_or_default(),
+        mdx_options: Some(mdx_options),
+        sourcemap,
+        minify: options.minify.unwrap_or(false),
+        ..Default::default()
+    };
+
+    let result = fob_mdx::bundle_mdx(bundle_options).await.map_err(|e| {
+        if let Some(mdx_err) = e.downcast_ref::<Box<fob_mdx::MdxError>>() {
+            return mdx_error((**mdx_err).clone());
+        }
+        let details = FobErrorDetails::Runtime(RuntimeError {
+            message: format!("{:#}", e),
+        });
+        Error::from_reason(details.to_napi_json_string())
+    })?;
+    Ok(MdxBundleResult::from(result))
+}
+
+fn mdx_error(err: fob_mdx::MdxError) -> Error {
+    let details = FobErrorDetails::MdxSyntax(MdxSyntaxError {
+        message: err.message,
+        file: err.file,
+        line: err.line.map(|line| line as u32),
+        column: err.column.map(|column| column as u32),
+        context: err.context,
+        suggestion: err.suggestion,
+    });
+    Error::from_reason(details.to_napi_json_string())
+}
+
+fn validation_error(message: String) -> Error {
+    let details = FobErrorDetails::Validation(ValidationError { message });
+    Error::from_reason(details.to_napi_json_string())
+}
@@ -3,11 +3,19 @@
 //! This module contains the public API exposed to Node.js through NAPI.
 
 mod bundler;
+mod capabilities;
 pub mod config;
 mod functions;
+mod mdx;
+pub mod plugin;
 pub mod primitives;
+mod watch;
 
 pub use bundler::Fob;
-pub use config::BundleConfig;
+pub use capabilities::{Capabilities, CapabilityLimits, PluginCapability, capabilities};
+pub use config::{BundleConfig, MdxBundleOptions, MdxOptions};
 pub use functions::{bundle_single, init_logging, init_logging_from_env, version};
+pub use mdx::{bundle_mdx, compile_mdx};
+pub use plugin::JsPlugin;
 pub use primitives::CodeSplittingConfig;
+pub use watch::{FobWatcher, WatchEvent, watch};
@@ -0,0 +1,266 @@
+//! JavaScript plugins
+//!
+//! Plugin hooks are JS functions called through threadsafe functions, so
+//! the bundler can invoke them from its worker threads while the JS thread
+//! stays free to run them.
+
+use fob_bundler::{
+    HookLoadArgs, HookLoadOutput, HookLoadReturn, HookResolveIdArgs, HookResolveIdOutput,
+    HookResolveIdReturn, HookTransformArgs, HookTransformOutput, HookTransformReturn, HookUsage,
+    ModuleType, Plugin, PluginContext, SharedPluginable, SharedTransformPluginContext,
+};
+use napi::Status;
+use napi::bindgen_prelude::{FnArgs, Promise};
+use napi::threadsafe_function::ThreadsafeFunction;
+use napi_derive::napi;
+use std::borrow::Cow;
+use std::sync::Arc;
+
+/// Hook called as `(...args) => Promise<Return | null>`.
+///
+/// Weak, so a bundler holding plugins doesn't keep the process alive.
+type JsHook<Args, Return> =
+    ThreadsafeFunction<Args, Promise<Option<Return>>, Args, Status, false, true>;
+
+/// `resolveId(specifier, importer)`
+pub type ResolveIdHook = JsHook<FnArgs<(String, Option<String>)>, ResolveIdResult>;
+
+/// `load(id)`
+pub type LoadHook = JsHook<String, LoadResult>;
+
+/// `transform(code, id)`
+pub type TransformHook = JsHook<FnArgs<(String, String)>, TransformResult>;
+
+/// Plugin implemented in JavaScript
+///
+/// Hooks return `null`/`undefined` to defer to the next plugin. The
+/// binding itself only accepts async hooks returning objects; the JS
+/// wrapper adapts synchronous hooks and plain string results.
+#[napi(object, object_to_js = false)]
+pub struct JsPlugin {
+    /// Plugin name, used in error messages
+    pub name: String,
+    /// Resolve an import specifier to a module id
+    #[napi(
+        ts_type = "(specifier: string, importer?: string) => ResolveIdResult | string | null | undefined | Promise<ResolveIdResult | string | null | undefined>"
+    )]
+    pub resolve_id: Option<ResolveIdHook>,
+    /// Load the source of a module id
+    #[napi(
+        ts_type = "(id: string) => LoadResult | string | null | undefined | Promise<LoadResult | string | null | undefined>"
+    )]
+    pub load: Option<LoadHook>,
+    /// Transform the source of a module
+    #[napi(
+        ts_type = "(code: string, id: string) => TransformResult | string | null | undefined | Promise<TransformResult | string | null | undefined>"
+    )]
+    pub transform: Option<TransformHook>,
+}
+
+impl std::fmt::Debug for JsPlugin {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("JsPlugin")
+            .field("name", &self.name)
+            .field("resolve_id", &self.resolve_id.is_some())
+            .field("load", &self.load.is_some())
+            .field("transform", &self.transform.is_some())
+            .finish()
+    }
+}
+
+/// Result of a `resolveId` hook
+#[napi(object)]
+pub struct ResolveIdResult {
+    /// Resolved module id
+    pub id: String,
+    /// Keep the import in the output instead of bundling it (default: false)
+    pub external: Option<bool>,
+}
+
+/// Result of a `load` hook
+#[napi(object)]
+pub struct LoadResult {
+    /// Module source
+    pub code: String,
+    /// Module type: "js" | "jsx" | "ts" | "tsx" | "json" | "css"
+    /// Default: inferred from the id
+    pub module_type: Option<String>,
+}
+
+/// Result of a `transform` hook
+#[napi(object)]
+pub struct TransformResult {
+    /// Transformed source
+    pub code: String,
+}
+
+impl JsPlugin {
+    /// Wrap the plugin for the bundler.
+    pub fn into_plugin(self) -> SharedPluginable {
+        Arc::new(JsPluginAdapter {
+            name: self.name,
+            resolve_id: self.resolve_id.map(Arc::new),
+            load: self.load.map(Arc::new),
+            transform: self.transform.map(Arc::new),
+        })
+    }
+}
+
+/// Rolldown plugin forwarding hooks to a [`JsPlugin`]
+struct JsPluginAdapter {
+    name: String,
+    resolve_id: Option<Arc<ResolveIdHook>>,
+    load: Option<Arc<LoadHook>>,
+    transform: Option<Arc<TransformHook>>,
+}
+
+impl std::fmt::Debug for JsPluginAdapter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("JsPluginAdapter")
+            .field("name", &self.name)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Plugin for JsPluginAdapter {
+    fn name(&self) -> Cow<'static, str> {
+        Cow::Owned(self.name.clone())
+    }
+
+    fn register_hook_usage(&self) -> HookUsage {
+        let mut usage = HookUsage::empty();
+        if self.resolve_id.is_some() {
+            usage |= HookUsage::ResolveId;
+        }
+        if self.load.is_some() {
+            usage |= HookUsage::Load;
+        }
+        if self.transform.is_some() {
+            usage |= HookUsage::Transform;
+        }
+        usage
+    }
+
+    fn resolve_id(
+        &self,
+        _ctx: &PluginContext,
+        args: &HookResolveIdArgs,
+    ) -> impl std::future::Future<Output = HookResolveIdReturn> + Send {
+        let name = self.name.clone();
+        let hook = self.resolve_id.clone();
+        let specifier = args.specifier.to_string();
+        let importer = args.importer.map(|s| s.to_string());
+
+        async move {
+            let Some(hook) = hook else {
+                return Ok(None);
+            };
+            let result = async { hook.call_async((specifier, importer).into()).await?.await }
+                .await
+                .map_err(|e| hook_error(&name, "resolveId", e.reason))?;
+
+            Ok(result.map(|result| HookResolveIdOutput {
+                id: result.id.into(),
+                external: Some(result.external.unwrap_or(false).into()),
+                ..Default::default()
+            }))
+        }
+    }
+
+    fn load(
+        &self,
+        _ctx: &PluginContext,
+        args: &HookLoadArgs<'_>,
+    ) -> impl std::future::Future<Output = HookLoadReturn> + Send {
+        let name = self.name.clone();
+        let hook = self.load.clone();
+        let id = args.id.to_string();
+
+        async move {
+            let Some(hook) = hook else {
+                return Ok(None);
+            };
+            let result = async { hook.call_async(id).await?.await }
+                .await
+                .map_err(|e| hook_error(&name, "load", e.reason))?;
+
+            let Some(result) = result else {
+                return Ok(None);
+            };
+            let module_type = result
+                .module_type
+                .as_deref()
+                .map(parse_module_type)
+                .transpose()
+                .map_err(|e| hook_error(&name, "load", e))?;
+
+            Ok(Some(HookLoadOutput {
+                code: result.code.into(),
+                module_type,
+                ..Default::default()
+            }))
+        }
+    }
+
+    fn transform(
+        &self,
+        _ctx: SharedTransformPluginContext,
+        args: &HookTransformArgs<'_>,
+    ) -> impl std::future::Future<Output = HookTransformReturn> + Send {
+        let name = self.name.clone();
+        let hook = self.transform.clone();
+        let code = args.code.to_string();
+        let id = args.id.to_string();
+
+        async move {
+            let Some(hook) = hook else {
+                return Ok(None);
+            };
+            let result = async { hook.call_async((code, id).into()).await?.await }
+                .await
+                .map_err(|e| hook_error(&name, "transform", e.reason))?;
+
+            Ok(result.map(|result| HookTransformOutput {
+                code: Some(result.code),
+                map: None,
+                side_effects: None,
+                module_type: None,
+            }))
+        }
+    }
+}
+
+fn hook_error(plugin: &str, hook: &str, message: String) -> anyhow::Error {
+    anyhow::anyhow!("Plugin '{}' failed in {}: {}", plugin, hook, message)
+}
+
+/// Parse a `moduleType` returned from a `load` hook (case-insensitive)
+fn parse_module_type(module_type: &str) -> Result<ModuleType, String> {
+    match module_type.to_lowercase().as_str() {
+        "js" => Ok(ModuleType::Js),
+        "jsx" => Ok(ModuleType::Jsx),
+        "ts" => Ok(ModuleType::Ts),
+        "tsx" => Ok(ModuleType::Tsx),
+        "json" => Ok(ModuleType::Json),
+        "css" => Ok(ModuleType::Css),
+        other => Err(format!(
+            "Invalid moduleType '{}'. Expected: js, jsx, ts, tsx, json, css",
+            other
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_module_type() {
+        assert!(matches!(parse_module_type("js"), Ok(ModuleType::Js)));
+        assert!(matches!(parse_module_type("TSX"), Ok(ModuleType::Tsx)));
+        assert!(matches!(parse_module_type("css"), Ok(ModuleType::Css)));
+
+        let err = parse_module_type("vue").unwrap_err();
+        assert!(err.contains("Invalid moduleType 'vue'"));
+    }
+}
@@ -0,0 +1,230 @@
+//! Watch mode: rebuild on file changes and report each build to JS
+
+use crate::api::config::BundleConfig;
+use crate::conversion::result::BundleResult;
+use crate::core::bundler::CoreBundler;
+use crate::error_mapper::map_bundler_error;
+use napi::Status;
+use napi::bindgen_prelude::*;
+use napi::threadsafe_function::{ThreadsafeFunction, ThreadsafeFunctionCallMode};
+use napi_derive::napi;
+use notify::{EventKind, RecursiveMode, Watcher};
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tokio::sync::{mpsc, oneshot};
+
+/// How long to wait for more changes before rebuilding
+const DEBOUNCE: Duration = Duration::from_millis(100);
+
+/// Directories whose changes never trigger a rebuild (besides the output directory)
+const IGNORED_DIRS: &[&str] = &["node_modules", ".git", ".fob-cache"];
+
+type WatchCallback = ThreadsafeFunction<WatchEvent, Unknown<'static>, WatchEvent, Status, false>;
+
+/// Rebuild event reported by `watch`
+#[napi(object)]
+pub struct WatchEvent {
+    /// Event kind: "start" | "success" | "failure"
+    pub kind: String,
+    /// Files whose changes triggered the build (empty for the initial build)
+    pub changed: Vec<String>,
+    /// Build duration in milliseconds ("success" and "failure" only)
+    pub duration_ms: Option<u32>,
+    /// Bundle result ("success" only)
+    pub result: Option<BundleResult>,
+    /// Error details in the format `bundle()` throws ("failure" only)
+    pub error: Option<serde_json::Value>,
+}
+
+/// Handle of a running watch
+#[napi]
+pub struct FobWatcher {
+    stop: Mutex<Option<oneshot::Sender<()>>>,
+}
+
+#[napi]
+impl FobWatcher {
+    /// Stop watching. A build in progress still completes and reports its result.
+    #[napi]
+    pub fn close(&self) {
+        let stop = self.stop.lock().unwrap_or_else(|e| e.into_inner()).take();
+        if let Some(stop) = stop {
+            let _ = stop.send(());
+        }
+    }
+}
+
+/// Build the configured entries, then rebuild whenever a file under the
+/// working directory changes
+///
+/// `onEvent` is called with a "start" event before every build and a
+/// "success" or "failure" event after it. Changes arriving within 100ms of
+/// each other are batched into one rebuild. `node_modules`, `.git` and the
+/// output directory are not watched.
+///
+/// The JS wrapper returns an EventEmitter instead; prefer that.
+#[napi(ts_args_type = "config: BundleConfig, onEvent: (event: WatchEvent) => void")]
+pub fn watch(config: BundleConfig, on_event: WatchCallback) -> Result<FobWatcher> {
+    let bundler = CoreBundler::new(config).map_err(|e| Error::from_reason(e.to_string()))?;
+    let cwd = bundler.cwd();
+    let output_dir = bundler.output_dir();
+
+    let (change_tx, change_rx) = mpsc::unbounded_channel();
+    let root = cwd.clone();
+    let mut watcher = notify::recommended_watcher(move |res: notify::Result<notify::Event>| {
+        let Ok(event) = res else {
+            return;
+        };
+        if !matches!(
+            event.kind,
+            EventKind::Create(_) | EventKind::Modify(_) | EventKind::Remove(_)
+        ) {
+            return;
+        }
+        for path in event.paths {
+            if !is_ignored(&path, &root, &output_dir) {
+                let _ = change_tx.send(path);
+            }
+        }
+    })
+    .map_err(|e| Error::from_reason(format!("Failed to create file watcher: {}", e)))?;
+    watcher
+        .watch(&cwd, RecursiveMode::Recursive)
+        .map_err(|e| Error::from_reason(format!("Failed to watch {}: {}", cwd.display(), e)))?;
+
+    let (stop_tx, stop_rx) = oneshot::channel();
+    spawn(async move {
+        // Dropping the watcher when the loop ends stops the notifications
+        let _watcher = watcher;
+        watch_loop(bundler, on_event, change_rx, stop_rx).await;
+    });
+
+    Ok(FobWatcher {
+        stop: Mutex::new(Some(stop_tx)),
+    })
+}
+
+/// Build, then wait for a batch of changes, until stopped.
+///
+/// Also stops when the `FobWatcher` is garbage collected, which drops the
+/// stop sender.
+async fn watch_loop(
+    bundler: CoreBundler,
+    on_event: WatchCallback,
+    mut changes: mpsc::UnboundedReceiver<PathBuf>,
+    mut stop: oneshot::Receiver<()>,
+) {
+    let mut changed = Vec::new();
+    loop {
+        build(&bundler, &on_event, changed).await;
+
+        let first = tokio::select! {
+            Some(path) = changes.recv() => path,
+            _ = &mut stop => return,
+        };
+        // Editors often write several files (or one file several times)
+        // per save, so wait for the burst to settle
+        let mut batch = BTreeSet::from([first]);
+        while let Ok(Some(path)) = tokio::time::timeout(DEBOUNCE, changes.recv()).await {
+            batch.insert(path);
+        }
+        if matches!(
+            stop.try_recv(),
+            Ok(()) | Err(oneshot::error::TryRecvError::Closed)
+        ) {
+            return;
+        }
+
+        fob_bundler::diagnostics::clear_source_cache();
+        changed = batch
+            .into_iter()
+            .map(|path| path.to_string_lossy().into_owned())
+            .collect();
+    }
+}
+
+/// Run one build, reporting its start and outcome.
+async fn build(bundler: &CoreBundler, on_event: &WatchCallback, changed: Vec<String>) {
+    emit(
+        on_event,
+        WatchEvent {
+            kind: "start".to_string(),
+            changed: changed.clone(),
+            duration_ms: None,
+            result: None,
+            error: None,
+        },
+    );
+
+    let started = Instant::now();
+    let outcome = bundler.bundle().await;
+    let duration_ms = Some(u32::try_from(started.elapsed().as_millis()).unwrap_or(u32::MAX));
+
+    let event = match outcome {
+        Ok(result) => WatchEvent {
+            kind: "success".to_string(),
+            changed,
+            duration_ms,
+            result: Some(result),
+            error: None,
+        },
+        Err(e) => WatchEvent {
+            kind: "failure".to_string(),
+            changed,
+            duration_ms,
+            result: None,
+            error: serde_json::to_value(map_bundler_error(&e).to_napi_error()).ok(),
+        },
+    };
+    emit(on_event, event);
+}
+
+fn emit(on_event: &WatchCallback, event: WatchEvent) {
+    // Fails only once the JS environment is shutting down
+    let _ = on_event.call(event, ThreadsafeFunctionCallMode::NonBlocking);
+}
+
+/// Whether a change is outside the project, in the output directory or in
+/// an ignored directory.
+fn is_ignored(path: &Path, cwd: &Path, output_dir: &Path) -> bool {
+    if path.starts_with(output_dir) {
+        return true;
+    }
+    let Ok(relative) = path.strip_prefix(cwd) else {
+        return true;
+    };
+    relative
+        .components()
+        .any(|component| IGNORED_DIRS.iter().any(|dir| component.as_os_str() == *dir))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_ignored() {
+        let cwd = Path::new("/project");
+        let output_dir = Path::new("/project/dist");
+
+        assert!(!is_ignored(
+            Path::new("/project/src/index.ts"),
+            cwd,
+            output_dir
+        ));
+        assert!(is_ignored(
+            Path::new("/project/dist/index.js"),
+            cwd,
+            output_dir
+        ));
+        assert!(is_ignored(
+            Path::new("/project/node_modules/react/index.js"),
+            cwd,
+            output_dir
+        ));
+        assert!(is_ignored(Path::new("/project/.git/HEAD"), cwd, output_dir));
+        assert!(is_ignored(Path::new("/elsewhere/file.ts"), cwd, output_dir));
+    }
+}
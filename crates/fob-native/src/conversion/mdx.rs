@@ -0,0 +1,48 @@
+//! MDX options conversion
+
+use crate::api::config::MdxOptions;
+use fob_mdx::{BundleMdxSourceMap, JsxTarget, MdxCompileOptions};
+
+/// Convert JS MDX options to fob-mdx compile options.
+/// Unset fields keep the fob-mdx defaults; an unknown JSX target is an error.
+pub fn convert_mdx_options(options: Option<&MdxOptions>) -> Result<MdxCompileOptions, String> {
+    let mut compile_options = MdxCompileOptions::new();
+    let Some(options) = options else {
+        return Ok(compile_options);
+    };
+
+    if let Some(gfm) = options.gfm {
+        compile_options.gfm = gfm;
+    }
+    if let Some(footnotes) = options.footnotes {
+        compile_options.footnotes = footnotes;
+    }
+    if let Some(math) = options.math {
+        compile_options.math = math;
+    }
+    if let Some(jsx_runtime) = &options.jsx_runtime {
+        compile_options.jsx_runtime = jsx_runtime.clone();
+    }
+    if let Some(jsx_target) = &options.jsx_target {
+        compile_options.jsx_target = jsx_target.parse::<JsxTarget>()?;
+    }
+    if let Some(use_default_plugins) = options.use_default_plugins {
+        compile_options.use_default_plugins = use_default_plugins;
+    }
+
+    Ok(compile_options)
+}
+
+/// Convert MDX bundle source map mode (case-insensitive)
+/// Accepts: "inline", "external", or None for no source map
+pub fn convert_mdx_sourcemap(mode: Option<&str>) -> Result<Option<BundleMdxSourceMap>, String> {
+    match mode.map(|s| s.to_lowercase()).as_deref() {
+        Some("inline") => Ok(Some(BundleMdxSourceMap::Inline)),
+        Some("external") => Ok(Some(BundleMdxSourceMap::External)),
+        Some(other) => Err(format!(
+            "Invalid sourcemap value '{}'. Expected: inline, external",
+            other
+        )),
+        None => Ok(None),
+    }
+}
@@ -1,5 +1,6 @@
 //! Type conversion utilities
 
 pub mod format;
+pub mod mdx;
 pub mod result;
 pub mod sourcemap;
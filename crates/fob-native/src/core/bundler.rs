@@ -7,7 +7,7 @@ use crate::core::validator::validate_path;
 use crate::runtime::NativeRuntime;
 use crate::types::parse_entry_mode;
 use fob_bundler::ExternalConfig;
-use fob_bundler::{BuildOptions, Runtime};
+use fob_bundler::{BuildOptions, Runtime, SharedPluginable};
 use std::io;
 use std::path::PathBuf;
 use std::sync::Arc;
@@ -16,11 +16,12 @@ use std::sync::Arc;
 pub struct CoreBundler {
     config: BundleConfig,
     runtime: Arc<dyn Runtime>,
+    plugins: Vec<SharedPluginable>,
 }
 
 impl CoreBundler {
     /// Create a new core bundler instance
-    pub fn new(config: BundleConfig) -> Result<Self, String> {
+    pub fn new(mut config: BundleConfig) -> Result<Self, String> {
         let cwd = config
             .cwd
             .as_ref()
@@ -33,7 +34,39 @@ impl CoreBundler {
                 .map_err(|e| format!("Failed to create runtime: {}", e))?,
         );
 
-        Ok(Self { config, runtime })
+        // Plugins are shared by every build of this bundler
+        let plugins = config
+            .plugins
+            .take()
+            .unwrap_or_default()
+            .into_iter()
+            .map(|plugin| plugin.into_plugin())
+            .collect();
+
+        Ok(Self {
+            config,
+            runtime,
+            plugins,
+        })
+    }
+
+    /// Working directory the configured paths resolve against
+    pub fn cwd(&self) -> PathBuf {
+        self.runtime
+            .get_cwd()
+            .unwrap_or_else(|_| PathBuf::from("."))
+    }
+
+    /// Output directory, resolved against the working directory
+    ///
+    /// Not validated; `bundle` rejects output directories outside the
+    /// working directory.
+    pub fn output_dir(&self) -> PathBuf {
+        let cwd = self.cwd();
+        match &self.config.output_dir {
+            Some(output_dir) => cwd.join(output_dir),
+            None => cwd.join("dist"),
+        }
     }
 
     /// Create BuildOptions using the new composable primitives.
@@ -87,6 +120,10 @@ impl CoreBundler {
         }
         // Otherwise external stays as ExternalConfig::None (bundle everything)
 
+        for plugin in &self.plugins {
+            options = options.plugin(plugin.clone());
+        }
+
         // Add virtual files (for inline content support)
         if let Some(ref virtual_files) = self.config.virtual_files {
             for (path, content) in virtual_files {
@@ -1,3 +1,5 @@
+use fob_bundler::diagnostics::Diagnostic;
+
 #[derive(serde::Serialize, Clone, Debug)]
 #[serde(tag = "type")]
 pub struct MdxSyntaxError {
@@ -98,6 +100,8 @@ pub enum FobErrorDetails {
     Runtime(RuntimeError),
     Validation(ValidationError),
     Multiple(MultipleDiagnostics),
+    /// Bundler diagnostic without a dedicated variant, kept with its kind and location
+    Diagnostic(Diagnostic),
 }
 
 /// Unified NAPI error format for JavaScript consumption
@@ -115,6 +119,24 @@ pub struct NapiError {
     pub help: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub details: Option<serde_json::Value>,
+    /// Further errors reported together with this one
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub related: Vec<NapiError>,
+}
+
+impl From<&Diagnostic> for NapiError {
+    fn from(diagnostic: &Diagnostic) -> Self {
+        NapiError {
+            kind: diagnostic.kind.clone(),
+            message: diagnostic.message.clone(),
+            file: diagnostic.file.clone(),
+            line: diagnostic.line,
+            column: diagnostic.column,
+            help: diagnostic.help.clone(),
+            details: None,
+            related: diagnostic.related.iter().map(NapiError::from).collect(),
+        }
+    }
 }
 
 impl FobErrorDetails {
@@ -127,15 +149,13 @@ impl FobErrorDetails {
     /// Convert to unified NAPI error format
     pub fn to_napi_error(&self) -> NapiError {
         match self {
-            FobErrorDetails::MdxSyntax(err) => NapiError {
-                kind: "MdxSyntax".to_string(),
-                message: err.message.clone(),
-                file: err.file.clone(),
-                line: err.line,
-                column: err.column,
-                help: err.suggestion.clone(),
-                details: None,
-            },
+            FobErrorDetails::MdxSyntax(err) => NapiError::from(&Diagnostic::mdx_syntax(
+                err.message.clone(),
+                err.file.clone(),
+                err.line.map(|line| line as usize),
+                err.column.map(|column| column as usize),
+                err.suggestion.clone(),
+            )),
             FobErrorDetails::Transform(err) => {
                 let first_diag = err.diagnostics.first();
                 NapiError {
@@ -151,6 +171,7 @@ impl FobErrorDetails {
                         "diagnostics": err.diagnostics,
                         "path": err.path
                     })),
+                    related: Vec::new(),
                 }
             }
             FobErrorDetails::MissingExport(err) => NapiError {
@@ -168,6 +189,7 @@ impl FobErrorDetails {
                     "module_id": err.module_id,
                     "available_exports": err.available_exports
                 })),
+                related: Vec::new(),
             },
             FobErrorDetails::CircularDependency(err) => NapiError {
                 kind: "CircularDependency".to_string(),
@@ -182,6 +204,7 @@ impl FobErrorDetails {
                 details: Some(serde_json::json!({
                     "cycle_path": err.cycle_path
                 })),
+                related: Vec::new(),
             },
             FobErrorDetails::InvalidEntry(err) => NapiError {
                 kind: "InvalidEntry".to_string(),
@@ -191,6 +214,7 @@ impl FobErrorDetails {
                 column: None,
                 help: Some("Ensure the entry file exists and is accessible".to_string()),
                 details: None,
+                related: Vec::new(),
             },
             FobErrorDetails::NoEntries(_) => NapiError {
                 kind: "NoEntries".to_string(),
@@ -200,6 +224,7 @@ impl FobErrorDetails {
                 column: None,
                 help: Some("Provide at least one entry point in your configuration".to_string()),
                 details: None,
+                related: Vec::new(),
             },
             FobErrorDetails::Plugin(err) => NapiError {
                 kind: "Plugin".to_string(),
@@ -212,6 +237,7 @@ impl FobErrorDetails {
                     "plugin_name": err.name,
                     "message": err.message
                 })),
+                related: Vec::new(),
             },
             FobErrorDetails::Runtime(err) => NapiError {
                 kind: "Runtime".to_string(),
@@ -221,6 +247,7 @@ impl FobErrorDetails {
                 column: None,
                 help: None,
                 details: None,
+                related: Vec::new(),
             },
             FobErrorDetails::Validation(err) => NapiError {
                 kind: "Validation".to_string(),
@@ -230,6 +257,7 @@ impl FobErrorDetails {
                 column: None,
                 help: Some("Check your configuration for errors".to_string()),
                 details: None,
+                related: Vec::new(),
             },
             FobErrorDetails::Multiple(err) => {
                 let primary = err.errors.first();
@@ -244,8 +272,15 @@ impl FobErrorDetails {
                         "errors": err.errors.iter().map(|e| e.to_napi_error()).collect::<Vec<_>>(),
                         "count": err.errors.len()
                     })),
+                    related: err
+                        .errors
+                        .iter()
+                        .skip(1)
+                        .map(|e| e.to_napi_error())
+                        .collect(),
                 }
             }
+            FobErrorDetails::Diagnostic(diagnostic) => NapiError::from(diagnostic),
         }
     }
 
@@ -38,6 +38,7 @@ impl std::fmt::Display for FobErrorDiagnostic {
             FobErrorDetails::Runtime(e) => write!(f, "{}", e.message),
             FobErrorDetails::Validation(e) => write!(f, "{}", e.message),
             FobErrorDetails::Multiple(e) => write!(f, "{}", e.primary_message),
+            FobErrorDetails::Diagnostic(e) => write!(f, "{}", e.message),
         }
     }
 }
@@ -55,6 +56,7 @@ impl Diagnostic for FobErrorDiagnostic {
             FobErrorDetails::Runtime(_) => "RUNTIME_ERROR",
             FobErrorDetails::Validation(_) => "VALIDATION_ERROR",
             FobErrorDetails::Multiple(_) => "MULTIPLE_ERRORS",
+            FobErrorDetails::Diagnostic(e) => e.kind.as_str(),
         }))
     }
 
@@ -97,6 +99,10 @@ impl Diagnostic for FobErrorDiagnostic {
                 "Plugin '{}' encountered an error.\nHint: Check the plugin configuration and ensure all dependencies are installed.",
                 e.name
             )) as Box<dyn std::fmt::Display>),
+            FobErrorDetails::Diagnostic(e) => e
+                .help
+                .as_ref()
+                .map(|h| Box::new(h.clone()) as Box<dyn std::fmt::Display>),
             FobErrorDetails::Transform(e) => {
                 if let Some(first) = e.diagnostics.first() {
                     first
@@ -146,6 +152,22 @@ impl Diagnostic for FobErrorDiagnostic {
                 }
                 None
             }
+            FobErrorDetails::Diagnostic(e) => {
+                if let (Some(file), Some(line), Some(column)) = (&e.file, e.line, e.column) {
+                    if let Some(source) = load_source(file) {
+                        if let Some(offset) = line_col_to_offset(&source, line, column) {
+                            let length = calculate_span_length(&source, offset);
+                            let span = SourceSpan::new(offset.into(), length);
+                            return Some(Box::new(std::iter::once(LabeledSpan::new(
+                                Some(e.message.clone()),
+                                span.offset(),
+                                span.len(),
+                            ))));
+                        }
+                    }
+                }
+                None
+            }
             _ => None,
         }
     }
@@ -176,6 +198,11 @@ pub fn to_miette_diagnostic(error: FobErrorDetails) -> FobErrorDiagnostic {
                 .map(|e| to_miette_diagnostic(e.clone()))
                 .collect()
         }
+        FobErrorDetails::Diagnostic(diagnostic) => diagnostic
+            .related
+            .iter()
+            .map(|d| to_miette_diagnostic(FobErrorDetails::Diagnostic(d.clone())))
+            .collect(),
         _ => Vec::new(),
     };
 
@@ -1,6 +1,8 @@
 use crate::error::{MdxSyntaxError, PluginError, *};
 use fob_bundler::Error as BundlerError;
-use fob_bundler::diagnostics::{DiagnosticContext, DiagnosticKind, ExtractedDiagnostic};
+use fob_bundler::diagnostics::{
+    Diagnostic, DiagnosticContext, DiagnosticKind, ExtractedDiagnostic,
+};
 
 /// Map fob-bundler errors to structured FobErrorDetails
 pub fn map_bundler_error(error: &BundlerError) -> FobErrorDetails {
@@ -195,12 +197,7 @@ fn map_single_diagnostic(diag: &ExtractedDiagnostic) -> FobErrorDetails {
 
         DiagnosticKind::UnresolvedImport
         | DiagnosticKind::InvalidOption
-        | DiagnosticKind::Other(_) => {
-            // Map to RuntimeError with context
-            FobErrorDetails::Runtime(RuntimeError {
-                message: format!("{}: {}", diag.kind, diag.message),
-            })
-        }
+        | DiagnosticKind::Other(_) => FobErrorDetails::Diagnostic(Diagnostic::from(diag)),
     }
 }
 
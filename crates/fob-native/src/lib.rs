@@ -11,9 +11,14 @@ pub mod conversion;
 pub mod core;
 pub mod error;
 pub mod error_mapper;
+pub mod mdx_result;
 pub mod runtime;
 pub mod types;
 
 // Re-export public API
-pub use api::{BundleConfig, Fob, bundle_single, init_logging, init_logging_from_env, version};
+pub use api::{
+    BundleConfig, Capabilities, Fob, MdxBundleOptions, MdxOptions, bundle_mdx, bundle_single,
+    capabilities, compile_mdx, init_logging, init_logging_from_env, version,
+};
 pub use bundle_result::BundleResult;
+pub use mdx_result::{MdxBundleResult, MdxCompileResult};
@@ -0,0 +1,231 @@
+use fob_mdx::{ContentNode, PropValue};
+use napi_derive::napi;
+
+/// Result of compiling an MDX document
+#[napi(object)]
+pub struct MdxCompileResult {
+    /// Compiled JSX module code
+    pub code: String,
+
+    /// Frontmatter block (None if the document has none)
+    pub frontmatter: Option<MdxFrontmatter>,
+
+    /// Headings in document order
+    pub toc: Vec<MdxTocEntry>,
+
+    /// Images in document order
+    pub images: Vec<MdxImage>,
+
+    /// Names exported with `export const` / `export function`
+    pub named_exports: Vec<String>,
+
+    /// Modules re-exported with `export ... from`
+    pub reexports: Vec<String>,
+
+    /// Imported module specifiers
+    pub imports: Vec<String>,
+
+    /// Default export, if the document declares one
+    pub default_export: Option<String>,
+
+    /// Plain-text excerpt
+    pub excerpt: Option<String>,
+
+    /// Number of words in the text content (code excluded)
+    pub word_count: u32,
+
+    /// Estimated reading time in minutes, rounded up
+    pub reading_time_minutes: u32,
+
+    /// Outgoing link URLs after rewriting
+    pub links: Vec<String>,
+}
+
+/// Result of bundling an MDX document with its imports
+#[napi(object)]
+pub struct MdxBundleResult {
+    /// Self-contained ESM bundle
+    pub code: String,
+
+    /// Frontmatter block (None if the document has none)
+    pub frontmatter: Option<MdxFrontmatter>,
+
+    /// Frontmatter data merged with the statically computable exports
+    pub matter: serde_json::Value,
+
+    /// Source map JSON (only with sourcemap: "external")
+    pub map: Option<String>,
+
+    /// CSS imported by the document and its components
+    pub css: Option<String>,
+}
+
+/// Parsed frontmatter
+#[napi(object)]
+pub struct MdxFrontmatter {
+    /// Source text of the block
+    pub raw: String,
+
+    /// Block format: "yaml" | "toml"
+    pub format: String,
+
+    /// Parsed data
+    pub data: serde_json::Value,
+}
+
+/// Table of contents entry
+#[napi(object)]
+pub struct MdxTocEntry {
+    /// Heading level (1-6)
+    pub depth: u32,
+
+    /// Heading text
+    pub text: String,
+
+    /// Heading `id` (None when heading IDs are disabled)
+    pub id: Option<String>,
+}
+
+/// Image referenced by the document
+#[napi(object)]
+pub struct MdxImage {
+    /// URL as written in the document
+    pub url: String,
+
+    /// Alternative text
+    pub alt: String,
+
+    /// Intrinsic width in pixels (None if unknown)
+    pub width: Option<u32>,
+
+    /// Intrinsic height in pixels (None if unknown)
+    pub height: Option<u32>,
+
+    /// Image format: "png" | "jpeg" | "gif" | "webp" | "svg" (None if unknown)
+    pub format: Option<String>,
+
+    /// Blurred placeholder as a data URI (None if not generated)
+    pub placeholder: Option<String>,
+}
+
+impl From<fob_mdx::MdxCompileResult> for MdxCompileResult {
+    fn from(result: fob_mdx::MdxCompileResult) -> Self {
+        let mut toc = Vec::new();
+        if let Some(content) = &result.content {
+            collect_headings(content, &mut toc);
+        }
+
+        Self {
+            code: result.code,
+            frontmatter: result.frontmatter.map(MdxFrontmatter::from),
+            toc,
+            images: result.images.into_iter().map(MdxImage::from).collect(),
+            named_exports: result.named_exports,
+            reexports: result.reexports,
+            imports: result.imports,
+            default_export: result.default_export,
+            excerpt: result.excerpt,
+            word_count: result.word_count as u32,
+            reading_time_minutes: result.reading_time_minutes,
+            links: result.links,
+        }
+    }
+}
+
+impl From<fob_mdx::BundleMdxResult> for MdxBundleResult {
+    fn from(result: fob_mdx::BundleMdxResult) -> Self {
+        Self {
+            code: result.code,
+            frontmatter: result.frontmatter.map(MdxFrontmatter::from),
+            matter: result.matter,
+            map: result.map,
+            css: result.css,
+        }
+    }
+}
+
+impl From<fob_mdx::FrontmatterData> for MdxFrontmatter {
+    fn from(frontmatter: fob_mdx::FrontmatterData) -> Self {
+        Self {
+            raw: frontmatter.raw,
+            format: match frontmatter.format {
+                fob_mdx::FrontmatterFormat::Yaml => "yaml".to_string(),
+                fob_mdx::FrontmatterFormat::Toml => "toml".to_string(),
+            },
+            data: frontmatter.data,
+        }
+    }
+}
+
+impl From<fob_mdx::ImageInfo> for MdxImage {
+    fn from(image: fob_mdx::ImageInfo) -> Self {
+        Self {
+            url: image.url,
+            alt: image.alt,
+            width: image.width,
+            height: image.height,
+            format: image
+                .format
+                .map(|format| format!("{:?}", format).to_lowercase()),
+            placeholder: image.placeholder,
+        }
+    }
+}
+
+/// Collect `h1`-`h6` elements of the structured content in document order.
+fn collect_headings(node: &ContentNode, toc: &mut Vec<MdxTocEntry>) {
+    match node {
+        ContentNode::Element {
+            tag_name,
+            properties,
+            children,
+        } => {
+            if let Some(depth) = heading_depth(tag_name) {
+                let mut text = String::new();
+                for child in children {
+                    collect_text(child, &mut text);
+                }
+                let id = match properties.get("id") {
+                    Some(PropValue::String(id)) => Some(id.clone()),
+                    _ => None,
+                };
+                toc.push(MdxTocEntry {
+                    depth,
+                    text: text.trim().to_string(),
+                    id,
+                });
+            } else {
+                for child in children {
+                    collect_headings(child, toc);
+                }
+            }
+        }
+        ContentNode::Root { children } | ContentNode::Component { children, .. } => {
+            for child in children {
+                collect_headings(child, toc);
+            }
+        }
+        ContentNode::Text { .. } | ContentNode::Expression { .. } | ContentNode::Raw { .. } => {}
+    }
+}
+
+fn heading_depth(tag_name: &str) -> Option<u32> {
+    match tag_name.as_bytes() {
+        [b'h', level @ b'1'..=b'6'] => Some(u32::from(level - b'0')),
+        _ => None,
+    }
+}
+
+fn collect_text(node: &ContentNode, text: &mut String) {
+    match node {
+        ContentNode::Text { value } => text.push_str(value),
+        ContentNode::Root { children }
+        | ContentNode::Element { children, .. }
+        | ContentNode::Component { children, .. } => {
+            for child in children {
+                collect_text(child, text);
+            }
+        }
+        ContentNode::Expression { .. } | ContentNode::Raw { .. } => {}
+    }
+}
@@ -289,3 +289,46 @@ fn test_all_error_types_serialize() {
         );
     }
 }
+
+#[test]
+fn test_unresolved_import_keeps_location_in_napi_error() {
+    let diagnostics = vec![
+        ExtractedDiagnostic {
+            kind: DiagnosticKind::UnresolvedImport,
+            severity: DiagnosticSeverity::Error,
+            message: "Cannot resolve 'bar'".to_string(),
+            file: Some("b.js".to_string()),
+            line: Some(2),
+            column: Some(8),
+            help: Some("Install the package".to_string()),
+            context: None,
+            error_chain: Vec::new(),
+        },
+        ExtractedDiagnostic {
+            kind: DiagnosticKind::InvalidOption,
+            severity: DiagnosticSeverity::Warning,
+            message: "Unknown option".to_string(),
+            file: None,
+            line: None,
+            column: None,
+            help: None,
+            context: None,
+            error_chain: Vec::new(),
+        },
+    ];
+
+    let error = BundlerError::Bundler(diagnostics);
+    let napi: serde_json::Value =
+        serde_json::from_str(&map_bundler_error(&error).to_napi_json_string()).unwrap();
+
+    assert_eq!(napi["kind"], "Multiple");
+    assert_eq!(napi["file"], "b.js");
+    assert_eq!(napi["line"], 2);
+    assert_eq!(napi["column"], 8);
+    assert_eq!(napi["related"].as_array().unwrap().len(), 1);
+    assert_eq!(napi["related"][0]["kind"], "InvalidOption");
+
+    let primary = &napi["details"]["errors"][0];
+    assert_eq!(primary["kind"], "UnresolvedImport");
+    assert_eq!(primary["help"], "Install the package");
+}
@@ -0,0 +1,100 @@
+//! Tests for the standalone MDX functions.
+
+use fob_native::{MdxBundleOptions, MdxOptions, bundle_mdx, compile_mdx};
+use std::collections::HashMap;
+
+#[test]
+fn test_compile_mdx_returns_frontmatter_and_toc() {
+    let source =
+        "---\ntitle: Hello\ntags: [a, b]\n---\n\n# Intro\n\nText.\n\n## Getting *started*\n";
+
+    let result = compile_mdx(source.to_string(), None, None).unwrap();
+
+    let frontmatter = result.frontmatter.expect("frontmatter");
+    assert_eq!(frontmatter.format, "yaml");
+    assert_eq!(frontmatter.data["title"], "Hello");
+    assert_eq!(frontmatter.data["tags"][1], "b");
+
+    assert_eq!(result.toc.len(), 2);
+    assert_eq!(result.toc[0].depth, 1);
+    assert_eq!(result.toc[0].text, "Intro");
+    assert_eq!(result.toc[0].id.as_deref(), Some("intro"));
+    assert_eq!(result.toc[1].depth, 2);
+    assert_eq!(result.toc[1].text, "Getting started");
+}
+
+#[test]
+fn test_compile_mdx_collects_images() {
+    let result = compile_mdx("![A cat](./cat.png)\n".to_string(), None, None).unwrap();
+
+    assert_eq!(result.images.len(), 1);
+    assert_eq!(result.images[0].url, "./cat.png");
+    assert_eq!(result.images[0].alt, "A cat");
+}
+
+#[test]
+fn test_compile_mdx_rejects_unknown_jsx_target() {
+    let options = MdxOptions {
+        jsx_target: Some("angular".to_string()),
+        ..Default::default()
+    };
+
+    let err = compile_mdx("# Hi".to_string(), Some(options), None).unwrap_err();
+    assert!(err.reason.contains("Validation"), "got: {}", err.reason);
+}
+
+#[test]
+fn test_compile_mdx_syntax_error_includes_file() {
+    let err = compile_mdx(
+        "<div>{unclosed".to_string(),
+        None,
+        Some("docs/broken.mdx".to_string()),
+    )
+    .unwrap_err();
+
+    let error: serde_json::Value = serde_json::from_str(&err.reason).unwrap();
+    assert_eq!(error["kind"], "MdxSyntax");
+    assert!(
+        error["file"] == "docs/broken.mdx"
+            || error["message"]
+                .as_str()
+                .is_some_and(|m| m.contains("docs/broken.mdx")),
+        "got: {}",
+        err.reason
+    );
+}
+
+#[tokio::test]
+async fn test_bundle_mdx_inlines_virtual_files() {
+    let options = MdxBundleOptions {
+        source: "---\ntitle: Bundled\n---\n\nimport Note from './Note.jsx'\n\n<Note />\n"
+            .to_string(),
+        files: Some(HashMap::from([(
+            "./Note.jsx".to_string(),
+            "export default () => 'virtual-note'".to_string(),
+        )])),
+        ..Default::default()
+    };
+
+    let result = bundle_mdx(options).await.unwrap();
+
+    assert!(result.code.contains("virtual-note"));
+    assert_eq!(result.matter["title"], "Bundled");
+    assert!(result.map.is_none());
+}
+
+#[tokio::test]
+async fn test_bundle_mdx_rejects_invalid_sourcemap() {
+    let options = MdxBundleOptions {
+        source: "# Hi".to_string(),
+        sourcemap: Some("hidden".to_string()),
+        ..Default::default()
+    };
+
+    let err = bundle_mdx(options).await.unwrap_err();
+    assert!(
+        err.reason.contains("Invalid sourcemap"),
+        "got: {}",
+        err.reason
+    );
+}
@@ -24,3 +24,25 @@ export declare function normalizeEntries(input: import('./index').Entries): {
   entries: string[];
   virtualFiles?: Record<string, string>;
 };
+
+/** Watcher returned by `watch`: an EventEmitter that is also async iterable */
+export interface FobWatchEmitter
+  extends import('node:events').EventEmitter,
+    AsyncIterable<import('./index').WatchEvent> {
+  on(event: 'start' | 'success' | 'failure' | 'event', listener: (event: import('./index').WatchEvent) => void): this;
+  /** Stop watching; ends async iteration */
+  close(): void;
+}
+
+/**
+ * Build the configured entries, then rebuild whenever a file changes.
+ *
+ * ```typescript
+ * const watcher = watch({ entries: ['src/index.ts'] });
+ * watcher.on('failure', (event) => console.error(event.error));
+ * for await (const event of watcher) {
+ *   if (event.kind === 'success') console.log(`rebuilt in ${event.durationMs}ms`);
+ * }
+ * ```
+ */
+export declare function watch(config: import('./index').BundleConfig): FobWatchEmitter;
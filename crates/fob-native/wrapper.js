@@ -1,5 +1,6 @@
 // Lightweight wrapper to expose stable named exports around the autogenerated NAPI binding.
 // The generated index.js may change on rebuilds, so keep custom exports here.
+const { EventEmitter, on } = require('node:events');
 const binding = require('./index.js');
 
 const OutputFormat = Object.freeze({
@@ -65,23 +66,108 @@ function normalizeEntries(input) {
   };
 }
 
-// Wrap Fob class to support flexible entries
+/**
+ * Normalize a JS plugin for the native binding.
+ * Hooks may be sync or async and may return a string instead of an object;
+ * the binding expects async hooks returning an object or null.
+ * @param {object} plugin
+ * @param {number} index
+ * @returns {object}
+ */
+function normalizePlugin(plugin, index) {
+  if (!plugin || typeof plugin !== 'object') {
+    throw new Error(`Invalid plugin at index ${index}: expected an object`);
+  }
+
+  const wrap = (hook, name, key) => {
+    if (hook == null) {
+      return undefined;
+    }
+    if (typeof hook !== 'function') {
+      throw new Error(`Plugin "${plugin.name}" ${name} hook must be a function`);
+    }
+    return async (...args) => {
+      const result = await hook.apply(plugin, args);
+      if (result == null) {
+        return null;
+      }
+      return typeof result === 'string' ? { [key]: result } : result;
+    };
+  };
+
+  return {
+    name: plugin.name || `plugin-${index}`,
+    resolveId: wrap(plugin.resolveId, 'resolveId', 'id'),
+    load: wrap(plugin.load, 'load', 'code'),
+    transform: wrap(plugin.transform, 'transform', 'code'),
+  };
+}
+
+/**
+ * Normalize a BundleConfig for the native binding (entries and plugins).
+ * @param {object} config
+ * @returns {object}
+ */
+function normalizeConfig(config) {
+  const { entries, virtualFiles } = normalizeEntries(config.entries);
+  return {
+    ...config,
+    entries,
+    virtualFiles,
+    plugins: config.plugins ? config.plugins.map(normalizePlugin) : undefined,
+  };
+}
+
+// Wrap Fob class to support flexible entries and plugins
 const OriginalFob = binding.Fob;
 
 class Fob extends OriginalFob {
   constructor(config) {
-    const { entries, virtualFiles } = normalizeEntries(config.entries);
-    super({
-      ...config,
-      entries,
-      virtualFiles,
-    });
+    super(normalizeConfig(config));
   }
 }
 
+/**
+ * Build the configured entries, then rebuild whenever a file changes.
+ *
+ * Returns an EventEmitter emitting 'start', 'success' and 'failure' events
+ * (and 'event' for all of them). It is also an async iterator over the
+ * events, which ends when `close()` is called.
+ * @param {object} config
+ * @returns {EventEmitter & AsyncIterable<object> & { close(): void }}
+ */
+function watch(config) {
+  const emitter = new EventEmitter();
+  const controller = new AbortController();
+
+  const handle = binding.watch(normalizeConfig(config), (event) => {
+    emitter.emit(event.kind, event);
+    emitter.emit('event', event);
+  });
+
+  emitter.close = () => {
+    handle.close();
+    controller.abort();
+  };
+  emitter[Symbol.asyncIterator] = async function* () {
+    try {
+      for await (const [event] of on(emitter, 'event', { signal: controller.signal })) {
+        yield event;
+      }
+    } catch (error) {
+      if (error.name !== 'AbortError') {
+        throw error;
+      }
+    }
+  };
+
+  return emitter;
+}
+
 module.exports = {
   ...binding,
   Fob,
+  watch,
   OutputFormat,
   SourceMapMode,
   // Export normalizeEntries for testing/advanced usage
@@ -0,0 +1,291 @@
+//! Precompressed output for the fob bundler.
+//!
+//! Static hosts such as nginx (`gzip_static`, `brotli_static`), Caddy
+//! (`precompressed`) and most CDNs serve `app.js.gz` or `app.js.br` in place
+//! of `app.js` when the browser accepts it, which saves compressing on every
+//! request and allows the slowest, smallest settings. This plugin adds those
+//! siblings to the bundle in the `generate_bundle` hook, so they are written
+//! with the rest of the output:
+//!
+//! ```text
+//! dist/app-3f2a.js
+//! dist/app-3f2a.js.gz
+//! dist/app-3f2a.js.br
+//! ```
+//!
+//! Files below a size threshold, and files that don't shrink enough to be
+//! worth a second request path, are left uncompressed.
+//!
+//! ```rust,no_run
+//! use fob_plugin_compression::{CompressionOptions, CompressionPlugin};
+//!
+//! let plugin = CompressionPlugin::with_options(CompressionOptions::new().with_threshold(10 * 1024));
+//! ```
+
+use anyhow::Context;
+use fob_bundler::{
+    HookGenerateBundleArgs, HookNoopReturn, HookUsage, Output, OutputAsset, Plugin, PluginContext,
+};
+use rayon::prelude::*;
+use std::borrow::Cow;
+use std::collections::HashSet;
+use std::io::Write;
+use std::path::Path;
+use std::sync::Arc;
+
+/// Brotli window size (log2), the largest the format allows for static files.
+const BROTLI_WINDOW: u32 = 22;
+
+/// A compression format and its file extension.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Encoding {
+    /// gzip (`.gz`)
+    Gzip,
+    /// Brotli (`.br`)
+    Brotli,
+}
+
+impl Encoding {
+    /// Extension appended to the compressed file name.
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Gzip => "gz",
+            Self::Brotli => "br",
+        }
+    }
+}
+
+/// Configuration for [`CompressionPlugin`].
+#[derive(Debug, Clone)]
+pub struct CompressionOptions {
+    /// Emit `.gz` files (default: true).
+    pub gzip: bool,
+    /// gzip level, 0-9 (default: 9).
+    pub gzip_level: u32,
+    /// Emit `.br` files (default: true).
+    pub brotli: bool,
+    /// Brotli quality, 0-11 (default: 11).
+    pub brotli_quality: u32,
+    /// Files smaller than this many bytes are not compressed (default: 1024).
+    pub threshold: usize,
+    /// Keep a compressed file only if it is at most this fraction of the
+    /// original size (default: 0.8).
+    pub min_ratio: f64,
+    /// File extensions compressed (without the leading dot).
+    pub extensions: Vec<String>,
+}
+
+impl Default for CompressionOptions {
+    fn default() -> Self {
+        Self {
+            gzip: true,
+            gzip_level: 9,
+            brotli: true,
+            brotli_quality: 11,
+            threshold: 1024,
+            min_ratio: 0.8,
+            extensions: ["js", "mjs", "cjs", "css", "html", "json", "svg"]
+                .map(String::from)
+                .to_vec(),
+        }
+    }
+}
+
+impl CompressionOptions {
+    /// Create options with default values.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Enable or disable gzip output.
+    pub fn with_gzip(mut self, enabled: bool) -> Self {
+        self.gzip = enabled;
+        self
+    }
+
+    /// Set the gzip level (clamped to 0-9).
+    pub fn with_gzip_level(mut self, level: u32) -> Self {
+        self.gzip_level = level.min(9);
+        self
+    }
+
+    /// Enable or disable brotli output.
+    pub fn with_brotli(mut self, enabled: bool) -> Self {
+        self.brotli = enabled;
+        self
+    }
+
+    /// Set the brotli quality (clamped to 0-11).
+    pub fn with_brotli_quality(mut self, quality: u32) -> Self {
+        self.brotli_quality = quality.min(11);
+        self
+    }
+
+    /// Set the minimum size, in bytes, of files worth compressing.
+    pub fn with_threshold(mut self, bytes: usize) -> Self {
+        self.threshold = bytes;
+        self
+    }
+
+    /// Set the largest compressed/original size ratio that is kept (clamped
+    /// to at most 1; zero, negative and NaN ratios are ignored).
+    pub fn with_min_ratio(mut self, ratio: f64) -> Self {
+        if ratio > 0.0 {
+            self.min_ratio = ratio.min(1.0);
+        }
+        self
+    }
+
+    /// Set the file extensions that are compressed.
+    pub fn with_extensions(mut self, extensions: Vec<String>) -> Self {
+        self.extensions = extensions;
+        self
+    }
+
+    fn encodings(&self) -> impl Iterator<Item = Encoding> {
+        [
+            self.gzip.then_some(Encoding::Gzip),
+            self.brotli.then_some(Encoding::Brotli),
+        ]
+        .into_iter()
+        .flatten()
+    }
+}
+
+/// Plugin emitting precompressed copies of the bundle's text files.
+#[derive(Debug, Clone, Default)]
+pub struct CompressionPlugin {
+    options: CompressionOptions,
+}
+
+impl CompressionPlugin {
+    /// Create a plugin with default options.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a plugin with custom options.
+    pub fn with_options(options: CompressionOptions) -> Self {
+        Self { options }
+    }
+
+    /// Access the plugin options.
+    pub fn options(&self) -> &CompressionOptions {
+        &self.options
+    }
+
+    /// Whether a file of this name and size is compressed.
+    pub fn handles(&self, filename: &str, size: usize) -> bool {
+        size >= self.options.threshold
+            && Path::new(filename)
+                .extension()
+                .and_then(|ext| ext.to_str())
+                .is_some_and(|ext| self.options.extensions.iter().any(|e| e == ext))
+    }
+
+    /// Compressed siblings for the outputs of a bundle.
+    ///
+    /// Files that already have a sibling in the bundle keep it. Files are
+    /// compressed in parallel; the result follows the bundle order.
+    pub fn compress_outputs(&self, bundle: &[Output]) -> anyhow::Result<Vec<Output>> {
+        let existing: HashSet<&str> = bundle.iter().map(output_filename).collect();
+        let mut jobs = Vec::new();
+
+        for output in bundle {
+            let filename = output_filename(output);
+            let content = match output {
+                Output::Chunk(chunk) => chunk.code.as_bytes(),
+                Output::Asset(asset) => asset.source.as_bytes(),
+            };
+            if !self.handles(filename, content.len()) {
+                continue;
+            }
+
+            for encoding in self.options.encodings() {
+                let target = format!("{}.{}", filename, encoding.extension());
+                if !existing.contains(target.as_str()) {
+                    jobs.push((filename, content, encoding, target));
+                }
+            }
+        }
+
+        let compressed = jobs
+            .into_par_iter()
+            .map(|(filename, content, encoding, target)| {
+                let bytes = self
+                    .compress(content, encoding)
+                    .with_context(|| format!("Failed to compress {}", filename))?;
+                if bytes.len() as f64 > content.len() as f64 * self.options.min_ratio {
+                    return Ok(None);
+                }
+                Ok(Some(Output::Asset(Arc::new(OutputAsset {
+                    names: vec![],
+                    original_file_names: vec![],
+                    filename: target.into(),
+                    source: bytes.into(),
+                }))))
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        Ok(compressed.into_iter().flatten().collect())
+    }
+
+    /// Compress a file with one encoding.
+    pub fn compress(&self, content: &[u8], encoding: Encoding) -> std::io::Result<Vec<u8>> {
+        match encoding {
+            Encoding::Gzip => {
+                let mut encoder = flate2::write::GzEncoder::new(
+                    Vec::new(),
+                    flate2::Compression::new(self.options.gzip_level),
+                );
+                encoder.write_all(content)?;
+                encoder.finish()
+            }
+            Encoding::Brotli => {
+                let mut writer = brotli::CompressorWriter::new(
+                    Vec::new(),
+                    4096,
+                    self.options.brotli_quality,
+                    BROTLI_WINDOW,
+                );
+                writer.write_all(content)?;
+                writer.flush()?;
+                Ok(writer.into_inner())
+            }
+        }
+    }
+}
+
+impl Plugin for CompressionPlugin {
+    fn name(&self) -> Cow<'static, str> {
+        "fob-plugin-compression".into()
+    }
+
+    fn register_hook_usage(&self) -> HookUsage {
+        HookUsage::GenerateBundle
+    }
+
+    fn generate_bundle(
+        &self,
+        _ctx: &PluginContext,
+        args: &mut HookGenerateBundleArgs<'_>,
+    ) -> impl std::future::Future<Output = HookNoopReturn> + Send {
+        let plugin = self.clone();
+        let bundle = args.bundle.clone();
+
+        async move {
+            // Brotli at quality 11 takes long enough to stall the executor
+            let compressed = tokio::task::spawn_blocking(move || plugin.compress_outputs(&bundle))
+                .await
+                .context("Compression task failed")??;
+            args.bundle.extend(compressed);
+            Ok(())
+        }
+    }
+}
+
+fn output_filename(output: &Output) -> &str {
+    match output {
+        Output::Chunk(chunk) => chunk.filename.as_str(),
+        Output::Asset(asset) => asset.filename.as_str(),
+    }
+}
@@ -0,0 +1,123 @@
+//! Integration tests for fob-plugin-compression.
+
+use fob_bundler::{Output, OutputAsset};
+use fob_plugin_compression::{CompressionOptions, CompressionPlugin, Encoding};
+use std::io::Read;
+use std::sync::Arc;
+
+fn asset(filename: &str, source: Vec<u8>) -> Output {
+    Output::Asset(Arc::new(OutputAsset {
+        names: vec![],
+        original_file_names: vec![],
+        filename: filename.into(),
+        source: source.into(),
+    }))
+}
+
+fn filename(output: &Output) -> &str {
+    match output {
+        Output::Chunk(chunk) => chunk.filename.as_str(),
+        Output::Asset(asset) => asset.filename.as_str(),
+    }
+}
+
+fn css(size: usize) -> Vec<u8> {
+    ".button { color: red; padding: 4px; }\n"
+        .repeat(size / 38 + 1)
+        .into_bytes()
+}
+
+#[test]
+fn emits_gzip_and_brotli_siblings() {
+    let plugin = CompressionPlugin::new();
+    let source = css(4096);
+
+    let outputs = plugin
+        .compress_outputs(&[asset("assets/app.css", source.clone())])
+        .unwrap();
+
+    let names: Vec<&str> = outputs.iter().map(filename).collect();
+    assert_eq!(names, ["assets/app.css.gz", "assets/app.css.br"]);
+
+    let Output::Asset(gz) = &outputs[0] else {
+        panic!("expected an asset");
+    };
+    let mut decoded = Vec::new();
+    flate2::read::GzDecoder::new(gz.source.as_bytes())
+        .read_to_end(&mut decoded)
+        .unwrap();
+    assert_eq!(decoded, source);
+}
+
+#[test]
+fn skips_small_incompressible_and_unlisted_files() {
+    let plugin = CompressionPlugin::new();
+    // A xorshift sequence doesn't compress below the default ratio
+    let mut state = 0x2545_f491_u32;
+    let noise: Vec<u8> = (0..4096)
+        .map(|_| {
+            state ^= state << 13;
+            state ^= state >> 17;
+            state ^= state << 5;
+            state as u8
+        })
+        .collect();
+
+    let outputs = plugin
+        .compress_outputs(&[
+            asset("small.js", b"export {};".to_vec()),
+            asset("noise.json", noise),
+            asset("logo.png", css(4096)),
+        ])
+        .unwrap();
+
+    assert!(outputs.is_empty());
+    assert!(!plugin.handles("small.js", 10));
+    assert!(plugin.handles("big.mjs", 2048));
+    assert!(!plugin.handles("logo.png", 2048));
+}
+
+#[test]
+fn keeps_existing_siblings_and_respects_encodings() {
+    let plugin = CompressionPlugin::with_options(
+        CompressionOptions::new()
+            .with_brotli(false)
+            .with_threshold(0),
+    );
+
+    let outputs = plugin
+        .compress_outputs(&[
+            asset("a.js", css(2048)),
+            asset("b.js", css(2048)),
+            asset("b.js.gz", vec![0x1f, 0x8b]),
+        ])
+        .unwrap();
+
+    let names: Vec<&str> = outputs.iter().map(filename).collect();
+    assert_eq!(names, ["a.js.gz"]);
+}
+
+#[test]
+fn clamps_levels() {
+    let options = CompressionOptions::new()
+        .with_gzip_level(42)
+        .with_brotli_quality(42);
+    assert_eq!(options.gzip_level, 9);
+    assert_eq!(options.brotli_quality, 11);
+
+    let plugin = CompressionPlugin::with_options(options);
+    assert!(plugin.compress(&css(1024), Encoding::Gzip).is_ok());
+    assert_eq!(Encoding::Brotli.extension(), "br");
+}
+
+#[test]
+fn min_ratio_stays_in_range() {
+    assert_eq!(CompressionOptions::new().with_min_ratio(0.5).min_ratio, 0.5);
+    assert_eq!(CompressionOptions::new().with_min_ratio(3.0).min_ratio, 1.0);
+    for ratio in [0.0, -0.5, f64::NAN] {
+        assert_eq!(
+            CompressionOptions::new().with_min_ratio(ratio).min_ratio,
+            0.8
+        );
+    }
+}
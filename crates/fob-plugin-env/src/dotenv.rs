@@ -0,0 +1,176 @@
+//! `.env` file parsing.
+//!
+//! Supports the common dotenv syntax: `KEY=value` lines with an optional
+//! `export ` prefix, `#` comments, single-quoted literal values and
+//! double-quoted values with escapes that may span several lines. Variable
+//! expansion (`${OTHER}`) is not supported; values are taken as written.
+
+/// A syntax error, with its 1-based line number.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct ParseError {
+    pub line: usize,
+    pub message: String,
+}
+
+/// Parse a `.env` file into its variables, in file order.
+pub(crate) fn parse(source: &str) -> Result<Vec<(String, String)>, ParseError> {
+    let mut vars = Vec::new();
+    let mut lines = source.lines().enumerate();
+
+    while let Some((index, line)) = lines.next() {
+        let line_number = index + 1;
+        let error = |message: String| ParseError {
+            line: line_number,
+            message,
+        };
+
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let line = line.strip_prefix("export ").unwrap_or(line);
+        let Some((key, value)) = line.split_once('=') else {
+            return Err(error(format!("expected KEY=value, found `{}`", line)));
+        };
+        let key = key.trim();
+        if !is_key(key) {
+            return Err(error(format!("invalid variable name `{}`", key)));
+        }
+
+        let value = value.trim_start();
+        let value = if let Some(rest) = value.strip_prefix('"') {
+            // Double-quoted values continue until the closing quote
+            let mut raw = rest.to_string();
+            loop {
+                if let Some((value, after)) = double_quoted(&raw) {
+                    if !is_comment(after) {
+                        return Err(error(format!("unexpected `{}` after value", after.trim())));
+                    }
+                    break value;
+                }
+                let Some((_, next)) = lines.next() else {
+                    return Err(error(format!("unterminated quoted value for {}", key)));
+                };
+                raw.push('\n');
+                raw.push_str(next);
+            }
+        } else if let Some(rest) = value.strip_prefix('\'') {
+            let Some((value, after)) = rest.split_once('\'') else {
+                return Err(error(format!("unterminated quoted value for {}", key)));
+            };
+            if !is_comment(after) {
+                return Err(error(format!("unexpected `{}` after value", after.trim())));
+            }
+            value.to_string()
+        } else {
+            // An unquoted `#` starts a comment only after whitespace
+            let end = value
+                .char_indices()
+                .find(|&(i, c)| c == '#' && value[..i].ends_with(char::is_whitespace))
+                .map_or(value.len(), |(i, _)| i);
+            value[..end].trim_end().to_string()
+        };
+
+        vars.push((key.to_string(), value));
+    }
+
+    Ok(vars)
+}
+
+/// The value of a double-quoted string up to its closing quote, with
+/// escapes processed, and the text after it.
+fn double_quoted(raw: &str) -> Option<(String, &str)> {
+    let mut value = String::new();
+    let mut chars = raw.char_indices();
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '"' => return Some((value, &raw[i + 1..])),
+            '\\' => match chars.next()?.1 {
+                'n' => value.push('\n'),
+                'r' => value.push('\r'),
+                't' => value.push('\t'),
+                other @ ('"' | '\\' | '$') => value.push(other),
+                other => {
+                    value.push('\\');
+                    value.push(other);
+                }
+            },
+            c => value.push(c),
+        }
+    }
+    None
+}
+
+fn is_comment(text: &str) -> bool {
+    let text = text.trim();
+    text.is_empty() || text.starts_with('#')
+}
+
+fn is_key(key: &str) -> bool {
+    let mut chars = key.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '.' || c == '-')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vars(source: &str) -> Vec<(String, String)> {
+        parse(source).unwrap()
+    }
+
+    fn var(key: &str, value: &str) -> (String, String) {
+        (key.to_string(), value.to_string())
+    }
+
+    #[test]
+    fn parses_assignments_and_comments() {
+        let source = "# API settings\n\
+                      FOB_API_URL=https://api.example.com/v1#anchor\n\
+                      export FOB_PORT = 3000   # dev server\n\
+                      \n\
+                      EMPTY=\n";
+        assert_eq!(
+            vars(source),
+            [
+                var("FOB_API_URL", "https://api.example.com/v1#anchor"),
+                var("FOB_PORT", "3000"),
+                var("EMPTY", ""),
+            ]
+        );
+    }
+
+    #[test]
+    fn parses_quoted_values() {
+        let source = "SINGLE='a \\n # b'  # comment\n\
+                      DOUBLE=\"line\\n\\\"quoted\\\"\"\n\
+                      KEY=\"-----BEGIN KEY-----\n\
+                      abc\n\
+                      -----END KEY-----\"\n";
+        assert_eq!(
+            vars(source),
+            [
+                var("SINGLE", "a \\n # b"),
+                var("DOUBLE", "line\n\"quoted\""),
+                var("KEY", "-----BEGIN KEY-----\nabc\n-----END KEY-----"),
+            ]
+        );
+    }
+
+    #[test]
+    fn reports_errors_with_line_numbers() {
+        assert_eq!(
+            parse("A=1\nnot a variable\n").unwrap_err(),
+            ParseError {
+                line: 2,
+                message: "expected KEY=value, found `not a variable`".to_string(),
+            }
+        );
+        assert_eq!(parse("1A=x").unwrap_err().line, 1);
+        assert_eq!(parse("A=1\nB=\"open\n").unwrap_err().line, 2);
+        assert!(parse("A='x' y").is_err());
+    }
+}
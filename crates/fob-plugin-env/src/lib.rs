@@ -0,0 +1,366 @@
+//! `import.meta.env` for the fob bundler.
+//!
+//! Replaces `import.meta.env.NAME` in every script module with the value of
+//! an environment variable, read at build time from `.env` files and the
+//! process environment:
+//!
+//! ```js
+//! fetch(`${import.meta.env.FOB_API_URL}/users`);
+//! if (import.meta.env.DEV) console.debug("debug build");
+//! ```
+//!
+//! Files are read from the project root (or [`EnvOptions::env_dir`]) in this
+//! order, later files overriding earlier ones, and the process environment
+//! overrides them all:
+//!
+//! 1. `.env`
+//! 2. `.env.local`
+//! 3. `.env.[mode]`
+//! 4. `.env.[mode].local`
+//!
+//! Only variables whose names start with one of [`EnvOptions::prefixes`]
+//! (`FOB_` by default) are exposed, so secrets such as `DATABASE_URL` never
+//! reach the bundle. `MODE`, `DEV` and `PROD` are always exposed.
+//!
+//! Values that look like booleans or numbers are inserted as such, so
+//! `FOB_RETRIES=3` gives `3` rather than `"3"`. A reference to a variable
+//! that isn't exposed fails the build instead of becoming `undefined`.
+//!
+//! ```rust,no_run
+//! use fob_bundler::runtime::BundlerRuntime;
+//! use fob_plugin_env::{EnvOptions, EnvPlugin};
+//! use std::sync::Arc;
+//!
+//! # async fn example() -> anyhow::Result<()> {
+//! let runtime = Arc::new(BundlerRuntime::new("."));
+//! let plugin = EnvPlugin::load(runtime, EnvOptions::new().with_mode("staging")).await?;
+//! # Ok(())
+//! # }
+//! ```
+
+mod dotenv;
+mod transform;
+
+use anyhow::{Context, bail};
+use fob_bundler::oxc::SourceType;
+use fob_bundler::{
+    HookTransformArgs, HookTransformOutput, HookTransformReturn, HookUsage, ModuleType, Plugin,
+    Runtime, SharedTransformPluginContext,
+};
+use std::borrow::Cow;
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::PathBuf;
+use std::sync::Arc;
+
+/// Configuration for [`EnvPlugin`].
+#[derive(Debug, Clone)]
+pub struct EnvOptions {
+    /// Build mode, exposed as `MODE` and selecting `.env.[mode]` files
+    /// (default: `"production"`).
+    pub mode: String,
+    /// Directory containing the `.env` files (default: the runtime's
+    /// working directory).
+    pub env_dir: Option<PathBuf>,
+    /// Name prefixes of exposed variables (default: `["FOB_"]`).
+    pub prefixes: Vec<String>,
+    /// Read variables from the process environment (default: true).
+    pub process_env: bool,
+    /// Insert boolean and numeric values as such rather than as strings
+    /// (default: true).
+    pub coerce: bool,
+}
+
+impl Default for EnvOptions {
+    fn default() -> Self {
+        Self {
+            mode: "production".to_string(),
+            env_dir: None,
+            prefixes: vec!["FOB_".to_string()],
+            process_env: true,
+            coerce: true,
+        }
+    }
+}
+
+impl EnvOptions {
+    /// Create options with default values.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the build mode.
+    pub fn with_mode(mut self, mode: impl Into<String>) -> Self {
+        self.mode = mode.into();
+        self
+    }
+
+    /// Set the directory containing the `.env` files.
+    pub fn with_env_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.env_dir = Some(dir.into());
+        self
+    }
+
+    /// Set the name prefixes of exposed variables.
+    pub fn with_prefixes(mut self, prefixes: Vec<String>) -> Self {
+        self.prefixes = prefixes;
+        self
+    }
+
+    /// Enable or disable reading the process environment.
+    pub fn with_process_env(mut self, enabled: bool) -> Self {
+        self.process_env = enabled;
+        self
+    }
+
+    /// Enable or disable boolean and numeric coercion.
+    pub fn with_coerce(mut self, enabled: bool) -> Self {
+        self.coerce = enabled;
+        self
+    }
+
+    /// `.env` file names, lowest precedence first.
+    fn files(&self) -> [String; 4] {
+        [
+            ".env".to_string(),
+            ".env.local".to_string(),
+            format!(".env.{}", self.mode),
+            format!(".env.{}.local", self.mode),
+        ]
+    }
+}
+
+/// The value of an exposed variable.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EnvValue {
+    String(String),
+    /// A decimal number, as written
+    Number(String),
+    Boolean(bool),
+}
+
+impl EnvValue {
+    /// Interpret a raw value: `true` and `false` become booleans, and
+    /// decimal numbers that JavaScript represents exactly become numbers.
+    /// Anything else, including numbers with leading zeros such as ZIP
+    /// codes, stays a string.
+    pub fn coerce(raw: &str) -> Self {
+        match raw {
+            "true" => Self::Boolean(true),
+            "false" => Self::Boolean(false),
+            raw if is_number(raw) => Self::Number(raw.to_string()),
+            raw => Self::String(raw.to_string()),
+        }
+    }
+
+    /// The value as a JavaScript literal.
+    pub fn to_js(&self) -> String {
+        match self {
+            Self::String(value) => serde_json::to_string(value).unwrap_or_default(),
+            Self::Number(value) => value.clone(),
+            Self::Boolean(value) => value.to_string(),
+        }
+    }
+}
+
+/// `-?(0|[1-9][0-9]*)(\.[0-9]+)?` with at most 15 significant digits.
+fn is_number(raw: &str) -> bool {
+    let digits = raw.strip_prefix('-').unwrap_or(raw);
+    let (integer, fraction) = match digits.split_once('.') {
+        Some((integer, fraction)) => (integer, Some(fraction)),
+        None => (digits, None),
+    };
+    let all_digits = |part: &str| !part.is_empty() && part.bytes().all(|b| b.is_ascii_digit());
+    all_digits(integer)
+        && (integer == "0" || !integer.starts_with('0'))
+        && fraction.is_none_or(all_digits)
+        && integer.len() + fraction.map_or(0, str::len) <= 15
+}
+
+/// `import.meta.env` plugin.
+///
+/// Variables are read once, when the plugin is created; create a new plugin
+/// to pick up changes to the `.env` files.
+#[derive(Debug, Clone)]
+pub struct EnvPlugin {
+    options: EnvOptions,
+    /// Exposed variables
+    vars: Arc<BTreeMap<String, EnvValue>>,
+    /// Variables that are set but not exposed, for error messages
+    hidden: Arc<BTreeSet<String>>,
+}
+
+impl EnvPlugin {
+    /// Read the `.env` files through the runtime, and the process
+    /// environment if enabled.
+    pub async fn load(runtime: Arc<dyn Runtime>, options: EnvOptions) -> anyhow::Result<Self> {
+        let dir = match &options.env_dir {
+            Some(dir) => dir.clone(),
+            None => runtime
+                .get_cwd()
+                .context("Failed to determine the .env directory")?,
+        };
+
+        let mut vars = Vec::new();
+        for name in options.files() {
+            let path = dir.join(name);
+            if !runtime.exists(&path) {
+                continue;
+            }
+            let bytes = runtime
+                .read_file(&path)
+                .await
+                .with_context(|| format!("Failed to read {}", path.display()))?;
+            let source = String::from_utf8(bytes)
+                .with_context(|| format!("{} contains invalid UTF-8", path.display()))?;
+            let parsed = dotenv::parse(&source).map_err(|error| {
+                anyhow::anyhow!("{}:{}: {}", path.display(), error.line, error.message)
+            })?;
+            vars.extend(parsed);
+        }
+        if options.process_env {
+            vars.extend(std::env::vars_os().filter_map(|(key, value)| {
+                Some((key.into_string().ok()?, value.into_string().ok()?))
+            }));
+        }
+
+        Self::from_vars(vars, options)
+    }
+
+    /// Create a plugin from variables gathered elsewhere, later ones
+    /// overriding earlier ones. No files are read.
+    pub fn from_vars<K, V>(
+        vars: impl IntoIterator<Item = (K, V)>,
+        options: EnvOptions,
+    ) -> anyhow::Result<Self>
+    where
+        K: Into<String>,
+        V: Into<String>,
+    {
+        if options.prefixes.iter().any(|prefix| prefix.is_empty()) {
+            bail!("An empty env prefix would expose every environment variable, including secrets");
+        }
+
+        let mut exposed = BTreeMap::new();
+        let mut hidden = BTreeSet::new();
+        for (key, value) in vars {
+            let (key, value) = (key.into(), value.into());
+            if options
+                .prefixes
+                .iter()
+                .any(|prefix| key.starts_with(prefix))
+            {
+                let value = if options.coerce {
+                    EnvValue::coerce(&value)
+                } else {
+                    EnvValue::String(value)
+                };
+                exposed.insert(key, value);
+            } else {
+                hidden.insert(key);
+            }
+        }
+
+        // Built-ins follow the mode and can't be overridden
+        let production = options.mode == "production";
+        exposed.insert("MODE".to_string(), EnvValue::String(options.mode.clone()));
+        exposed.insert("DEV".to_string(), EnvValue::Boolean(!production));
+        exposed.insert("PROD".to_string(), EnvValue::Boolean(production));
+
+        Ok(Self {
+            options,
+            vars: Arc::new(exposed),
+            hidden: Arc::new(hidden),
+        })
+    }
+
+    /// Access the plugin options.
+    pub fn options(&self) -> &EnvOptions {
+        &self.options
+    }
+
+    /// The exposed variables, including `MODE`, `DEV` and `PROD`.
+    pub fn vars(&self) -> &BTreeMap<String, EnvValue> {
+        &self.vars
+    }
+
+    /// Replace the `import.meta.env` references of a module.
+    ///
+    /// Returns `Ok(None)` for modules without references and modules that
+    /// fail to parse, which the bundler reports. References to variables
+    /// that aren't exposed are an error.
+    pub fn transform_code(
+        &self,
+        id: &str,
+        code: &str,
+        source_type: SourceType,
+    ) -> anyhow::Result<Option<String>> {
+        transform::rewrite(self, id, code, source_type)
+    }
+
+    /// `import.meta.env` as an object literal.
+    fn object_literal(&self) -> String {
+        let entries: Vec<String> = self
+            .vars
+            .iter()
+            .map(|(key, value)| {
+                let key = serde_json::to_string(key).unwrap_or_default();
+                format!("{}: {}", key, value.to_js())
+            })
+            .collect();
+        format!("({{ {} }})", entries.join(", "))
+    }
+
+    /// Why a variable isn't available, for error messages.
+    fn undefined_reason(&self, name: &str) -> String {
+        let prefixes = self.options.prefixes.join(", ");
+        if self.hidden.contains(name) {
+            format!(
+                "is set but not exposed: only variables starting with {} are",
+                prefixes
+            )
+        } else if !self.options.prefixes.iter().any(|p| name.starts_with(p)) {
+            format!(
+                "is not defined, and only variables starting with {} are exposed",
+                prefixes
+            )
+        } else {
+            "is not defined".to_string()
+        }
+    }
+}
+
+impl Plugin for EnvPlugin {
+    fn name(&self) -> Cow<'static, str> {
+        "fob-plugin-env".into()
+    }
+
+    fn register_hook_usage(&self) -> HookUsage {
+        HookUsage::Transform
+    }
+
+    fn transform(
+        &self,
+        _ctx: SharedTransformPluginContext,
+        args: &HookTransformArgs<'_>,
+    ) -> impl std::future::Future<Output = HookTransformReturn> + Send {
+        let source_type = match args.module_type {
+            ModuleType::Js => Some(SourceType::mjs()),
+            ModuleType::Jsx => Some(SourceType::jsx()),
+            ModuleType::Ts => Some(SourceType::ts()),
+            ModuleType::Tsx => Some(SourceType::tsx()),
+            _ => None,
+        };
+        let result = source_type
+            .map(|source_type| self.transform_code(args.id, args.code, source_type))
+            .transpose();
+
+        async move {
+            Ok(result?.flatten().map(|code| HookTransformOutput {
+                code: Some(code),
+                map: None,
+                side_effects: None,
+                module_type: None,
+            }))
+        }
+    }
+}
@@ -0,0 +1,164 @@
+//! Replacement of `import.meta.env` references.
+//!
+//! Works on the AST, so strings and comments mentioning `import.meta.env`
+//! are left alone. `import.meta.env.NAME` and `import.meta.env["NAME"]`
+//! become the variable's value as a literal; any other use of
+//! `import.meta.env` becomes an object literal holding every exposed
+//! variable. Only the references are rewritten: every other byte of the
+//! module is copied as-is.
+
+use crate::{EnvPlugin, EnvValue};
+use anyhow::bail;
+use fob_bundler::oxc::ast::*;
+use fob_bundler::oxc::{Allocator, GetSpan, Parser, SourceType, Span, Visit};
+use std::fmt::Write;
+
+/// Rewrite a module's `import.meta.env` references.
+///
+/// Returns `Ok(None)` for modules without references, and for modules that
+/// fail to parse, which the bundler reports. References to variables that
+/// are not exposed are an error.
+pub(crate) fn rewrite(
+    plugin: &EnvPlugin,
+    id: &str,
+    code: &str,
+    source_type: SourceType,
+) -> anyhow::Result<Option<String>> {
+    if !code.contains("import.meta") {
+        return Ok(None);
+    }
+
+    let allocator = Allocator::default();
+    let parsed = Parser::new(&allocator, code, source_type.with_module(true)).parse();
+    if !parsed.errors.is_empty() {
+        return Ok(None);
+    }
+
+    let mut finder = EnvReferences {
+        plugin,
+        edits: Vec::new(),
+        undefined: Vec::new(),
+    };
+    finder.visit_program(&parsed.program);
+
+    if !finder.undefined.is_empty() {
+        let mut message = String::new();
+        for (name, span) in &finder.undefined {
+            let (line, column) = line_col(code, span.start);
+            let _ = write!(
+                message,
+                "\n  {}:{}:{}: import.meta.env.{} {}",
+                id,
+                line,
+                column,
+                name,
+                plugin.undefined_reason(name)
+            );
+        }
+        bail!("Undefined environment variables:{}", message);
+    }
+    if finder.edits.is_empty() {
+        return Ok(None);
+    }
+
+    let mut edits = finder.edits;
+    edits.sort_by_key(|(span, _)| std::cmp::Reverse(span.start));
+    let mut code = code.to_string();
+    for (span, replacement) in edits {
+        code.replace_range(span.start as usize..span.end as usize, &replacement);
+    }
+    Ok(Some(code))
+}
+
+/// What a member expression reads from `import.meta.env`.
+enum EnvReference<'a> {
+    /// `import.meta.env` itself
+    Object,
+    /// `import.meta.env.NAME` or `import.meta.env["NAME"]`
+    Variable(&'a str),
+}
+
+/// Visitor collecting replacements for `import.meta.env` references.
+struct EnvReferences<'p> {
+    plugin: &'p EnvPlugin,
+    edits: Vec<(Span, String)>,
+    undefined: Vec<(String, Span)>,
+}
+
+impl EnvReferences<'_> {
+    /// Handle a member expression; `in_object` is set when it is the object
+    /// of another member expression, where numbers need parentheses.
+    fn member(&mut self, member: &MemberExpression<'_>, in_object: bool) {
+        match env_reference(member) {
+            Some(EnvReference::Object) => {
+                let object = self.plugin.object_literal();
+                self.edits.push((member.span(), object));
+            }
+            Some(EnvReference::Variable(name)) => match self.plugin.vars.get(name) {
+                Some(value) => {
+                    let literal = match value {
+                        EnvValue::Number(number) if in_object || number.starts_with('-') => {
+                            format!("({})", number)
+                        }
+                        value => value.to_js(),
+                    };
+                    self.edits.push((member.span(), literal));
+                }
+                None => self.undefined.push((name.to_string(), member.span())),
+            },
+            None => {
+                match member.object().as_member_expression() {
+                    Some(object) => self.member(object, true),
+                    None => self.visit_expression(member.object()),
+                }
+                if let MemberExpression::ComputedMemberExpression(computed) = member {
+                    self.visit_expression(&computed.expression);
+                }
+            }
+        }
+    }
+}
+
+impl<'a> Visit<'a> for EnvReferences<'_> {
+    fn visit_member_expression(&mut self, it: &MemberExpression<'a>) {
+        self.member(it, false);
+    }
+}
+
+fn env_reference<'a>(member: &MemberExpression<'a>) -> Option<EnvReference<'a>> {
+    if is_env_object(member) {
+        return Some(EnvReference::Object);
+    }
+    let object = member.object().as_member_expression()?;
+    if !is_env_object(object) {
+        return None;
+    }
+    // `import.meta.env[key]` falls back to the whole object
+    member.static_property_name().map(EnvReference::Variable)
+}
+
+/// Whether `member` is `import.meta.env`.
+fn is_env_object(member: &MemberExpression<'_>) -> bool {
+    let MemberExpression::StaticMemberExpression(member) = member else {
+        return false;
+    };
+    member.property.name == "env"
+        && matches!(
+            &member.object,
+            Expression::MetaProperty(meta)
+                if meta.meta.name == "import" && meta.property.name == "meta"
+        )
+}
+
+/// 1-based line and column of a byte offset.
+fn line_col(code: &str, offset: u32) -> (usize, usize) {
+    let before = &code[..offset as usize];
+    let line = before.matches('\n').count() + 1;
+    let column = before
+        .rfind('\n')
+        .map_or(before, |newline| &before[newline + 1..])
+        .chars()
+        .count()
+        + 1;
+    (line, column)
+}
@@ -0,0 +1,164 @@
+//! Integration tests for fob-plugin-env.
+
+use fob_bundler::oxc::SourceType;
+use fob_bundler::runtime::BundlerRuntime;
+use fob_plugin_env::{EnvOptions, EnvPlugin, EnvValue};
+use std::sync::Arc;
+
+async fn plugin_with(files: &[(&str, &str)], options: EnvOptions) -> anyhow::Result<EnvPlugin> {
+    let runtime = BundlerRuntime::new("/project");
+    for (path, content) in files {
+        runtime.add_virtual_file(*path, content.as_bytes().to_vec());
+    }
+    EnvPlugin::load(Arc::new(runtime), options.with_process_env(false)).await
+}
+
+fn transform(plugin: &EnvPlugin, code: &str) -> anyhow::Result<Option<String>> {
+    plugin.transform_code("/project/src/app.ts", code, SourceType::ts())
+}
+
+#[tokio::test]
+async fn loads_env_files_in_order() {
+    let plugin = plugin_with(
+        &[
+            (
+                "/project/.env",
+                "FOB_API_URL=https://api.example.com\nFOB_RETRIES=3\nDATABASE_URL=postgres://secret\n",
+            ),
+            ("/project/.env.local", "FOB_RETRIES=5\n"),
+            ("/project/.env.staging", "FOB_API_URL=https://staging.example.com\n"),
+            ("/project/.env.production", "FOB_API_URL=https://prod.example.com\n"),
+        ],
+        EnvOptions::new().with_mode("staging"),
+    )
+    .await
+    .unwrap();
+
+    let vars = plugin.vars();
+    assert_eq!(
+        vars["FOB_API_URL"],
+        EnvValue::String("https://staging.example.com".to_string())
+    );
+    assert_eq!(vars["FOB_RETRIES"], EnvValue::Number("5".to_string()));
+    assert_eq!(vars["MODE"], EnvValue::String("staging".to_string()));
+    assert_eq!(vars["DEV"], EnvValue::Boolean(true));
+    assert!(!vars.contains_key("DATABASE_URL"));
+}
+
+#[tokio::test]
+async fn replaces_references_with_literals() {
+    let plugin = plugin_with(
+        &[(
+            "/project/.env",
+            "FOB_API_URL=https://api.example.com\nFOB_RETRIES=3\nFOB_OFFSET=-1\nFOB_DEBUG=false\nFOB_ZIP=02134\n",
+        )],
+        EnvOptions::default(),
+    )
+    .await
+    .unwrap();
+
+    let code = "// import.meta.env.FOB_MISSING in a comment\n\
+                const label: string = \"import.meta.env.FOB_MISSING\";\n\
+                fetch(`${import.meta.env.FOB_API_URL}/users`);\n\
+                const retries = import.meta.env[\"FOB_RETRIES\"].toFixed(0) + import.meta.env.FOB_OFFSET;\n\
+                if (import.meta.env.PROD && !import.meta.env.FOB_DEBUG) run(import.meta.env.FOB_ZIP);\n";
+    assert_eq!(
+        transform(&plugin, code).unwrap().unwrap(),
+        "// import.meta.env.FOB_MISSING in a comment\n\
+         const label: string = \"import.meta.env.FOB_MISSING\";\n\
+         fetch(`${\"https://api.example.com\"}/users`);\n\
+         const retries = (3).toFixed(0) + (-1);\n\
+         if (true && !false) run(\"02134\");\n"
+    );
+}
+
+#[tokio::test]
+async fn replaces_whole_object() {
+    let plugin = plugin_with(&[("/project/.env", "FOB_A=a\n")], EnvOptions::default())
+        .await
+        .unwrap();
+
+    assert_eq!(
+        transform(&plugin, "const { FOB_A } = import.meta.env;")
+            .unwrap()
+            .unwrap(),
+        "const { FOB_A } = ({ \"DEV\": false, \"FOB_A\": \"a\", \"MODE\": \"production\", \"PROD\": true });"
+    );
+    assert_eq!(
+        transform(&plugin, "console.log(import.meta.url);").unwrap(),
+        None
+    );
+}
+
+#[tokio::test]
+async fn errors_on_undefined_variables() {
+    let plugin = plugin_with(
+        &[("/project/.env", "DATABASE_URL=postgres://secret\n")],
+        EnvOptions::default(),
+    )
+    .await
+    .unwrap();
+
+    let err = transform(
+        &plugin,
+        "const a = import.meta.env.FOB_MISSING;\nconst b = import.meta.env.DATABASE_URL;\n",
+    )
+    .unwrap_err()
+    .to_string();
+
+    assert!(err.contains("/project/src/app.ts:1:11: import.meta.env.FOB_MISSING is not defined\n"));
+    assert!(
+        err.contains(
+            "/project/src/app.ts:2:11: import.meta.env.DATABASE_URL is set but not exposed"
+        )
+    );
+    assert!(!err.contains("postgres://secret"));
+}
+
+#[tokio::test]
+async fn respects_prefixes_and_coercion_options() {
+    let plugin = plugin_with(
+        &[("/project/.env", "PUBLIC_PORT=8080\nFOB_PORT=3000\n")],
+        EnvOptions::new()
+            .with_prefixes(vec!["PUBLIC_".to_string()])
+            .with_coerce(false),
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(
+        plugin.vars()["PUBLIC_PORT"],
+        EnvValue::String("8080".to_string())
+    );
+    assert!(!plugin.vars().contains_key("FOB_PORT"));
+
+    let err = plugin_with(&[], EnvOptions::new().with_prefixes(vec![String::new()]))
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("empty env prefix"));
+}
+
+#[tokio::test]
+async fn reports_env_file_syntax_errors() {
+    let err = plugin_with(
+        &[("/project/.env", "FOB_A=1\nFOB_B\n")],
+        EnvOptions::default(),
+    )
+    .await
+    .unwrap_err();
+
+    assert!(
+        err.to_string()
+            .contains("/project/.env:2: expected KEY=value")
+    );
+}
+
+#[test]
+fn coerces_values() {
+    assert_eq!(EnvValue::coerce("true"), EnvValue::Boolean(true));
+    assert_eq!(EnvValue::coerce("0.5"), EnvValue::Number("0.5".to_string()));
+    assert_eq!(EnvValue::coerce("0"), EnvValue::Number("0".to_string()));
+    for raw in ["007", "1e3", "1.", ".5", "TRUE", "12345678901234567890", ""] {
+        assert_eq!(EnvValue::coerce(raw), EnvValue::String(raw.to_string()));
+    }
+}
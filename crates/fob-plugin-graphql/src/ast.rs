@@ -0,0 +1,232 @@
+//! GraphQL document AST, serialized in the shape of graphql-js' `DocumentNode`.
+//!
+//! Nodes carry a `kind` field like `{ "kind": "Field", "name": { "kind":
+//! "Name", "value": "id" }, ... }`, so clients such as Apollo and urql accept
+//! the JSON as if it came from `graphql-tag`. Locations are only kept on the
+//! document, as `graphql-tag` does.
+
+use serde::Serialize;
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "kind")]
+pub struct Document {
+    pub definitions: Vec<Definition>,
+    pub loc: Loc,
+}
+
+/// Location of the whole document, with its source text.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Loc {
+    pub start: usize,
+    pub end: usize,
+    pub source: Source,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Source {
+    pub body: String,
+    pub name: String,
+    pub location_offset: LocationOffset,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct LocationOffset {
+    pub line: usize,
+    pub column: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "kind")]
+pub enum Definition {
+    OperationDefinition(OperationDefinition),
+    FragmentDefinition(FragmentDefinition),
+}
+
+impl Definition {
+    /// Name of the operation or fragment.
+    pub fn name(&self) -> Option<&str> {
+        match self {
+            Self::OperationDefinition(operation) => operation.name.as_ref(),
+            Self::FragmentDefinition(fragment) => Some(&fragment.name),
+        }
+        .map(|name| name.value.as_str())
+    }
+
+    pub fn selection_set(&self) -> &SelectionSet {
+        match self {
+            Self::OperationDefinition(operation) => &operation.selection_set,
+            Self::FragmentDefinition(fragment) => &fragment.selection_set,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OperationType {
+    Query,
+    Mutation,
+    Subscription,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OperationDefinition {
+    pub operation: OperationType,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<Name>,
+    pub variable_definitions: Vec<VariableDefinition>,
+    pub directives: Vec<Directive>,
+    pub selection_set: SelectionSet,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FragmentDefinition {
+    pub name: Name,
+    pub type_condition: NamedType,
+    pub directives: Vec<Directive>,
+    pub selection_set: SelectionSet,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "kind")]
+pub struct Name {
+    pub value: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub struct VariableDefinition {
+    pub variable: Variable,
+    #[serde(rename = "type")]
+    pub ty: Type,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default_value: Option<Value>,
+    pub directives: Vec<Directive>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "kind")]
+pub struct Variable {
+    pub name: Name,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "kind")]
+pub struct NamedType {
+    pub name: Name,
+}
+
+// Variant names are the graphql-js kinds
+#[allow(clippy::enum_variant_names)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "kind")]
+pub enum Type {
+    NamedType {
+        name: Name,
+    },
+    ListType {
+        #[serde(rename = "type")]
+        ty: Box<Type>,
+    },
+    NonNullType {
+        #[serde(rename = "type")]
+        ty: Box<Type>,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "kind")]
+pub struct SelectionSet {
+    pub selections: Vec<Selection>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "kind")]
+pub enum Selection {
+    Field(Field),
+    FragmentSpread(FragmentSpread),
+    InlineFragment(InlineFragment),
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Field {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub alias: Option<Name>,
+    pub name: Name,
+    pub arguments: Vec<Argument>,
+    pub directives: Vec<Directive>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub selection_set: Option<SelectionSet>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FragmentSpread {
+    pub name: Name,
+    pub directives: Vec<Directive>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InlineFragment {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub type_condition: Option<NamedType>,
+    pub directives: Vec<Directive>,
+    pub selection_set: SelectionSet,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "kind")]
+pub struct Argument {
+    pub name: Name,
+    pub value: Value,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "kind")]
+pub struct Directive {
+    pub name: Name,
+    pub arguments: Vec<Argument>,
+}
+
+#[allow(clippy::enum_variant_names)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "kind")]
+pub enum Value {
+    Variable {
+        name: Name,
+    },
+    /// Kept as written, like graphql-js
+    IntValue {
+        value: String,
+    },
+    FloatValue {
+        value: String,
+    },
+    StringValue {
+        value: String,
+        block: bool,
+    },
+    BooleanValue {
+        value: bool,
+    },
+    NullValue,
+    EnumValue {
+        value: String,
+    },
+    ListValue {
+        values: Vec<Value>,
+    },
+    ObjectValue {
+        fields: Vec<ObjectField>,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "kind")]
+pub struct ObjectField {
+    pub name: Name,
+    pub value: Value,
+}
@@ -0,0 +1,289 @@
+//! GraphQL document plugin for the fob bundler.
+//!
+//! Loads `.graphql` and `.gql` files as pre-parsed documents, so GraphQL
+//! clients receive the AST directly and neither `graphql-tag` nor a parser
+//! ships to the browser:
+//!
+//! ```js
+//! import document, { GetUser } from "./user.graphql";
+//!
+//! client.query({ query: GetUser, variables: { id } });
+//! ```
+//!
+//! The default export is the whole document in graphql-js' `DocumentNode`
+//! shape. Each named operation is also a named export holding that operation
+//! and the fragments it uses, like `graphql-tag/loader`.
+//!
+//! Fragments from other files are pulled in with `#import` comments, which
+//! are resolved at build time:
+//!
+//! ```graphql
+//! #import "./fragments/user.graphql"
+//!
+//! query GetUser($id: ID!) { user(id: $id) { ...UserFields } }
+//! ```
+//!
+//! Documents are validated with the rules that need no schema (unknown
+//! fragments, fragment cycles, undefined variables, duplicate names), so
+//! mistakes fail the build instead of the request.
+//!
+//! ```rust,no_run
+//! use fob_bundler::runtime::BundlerRuntime;
+//! use fob_plugin_graphql::GraphqlPlugin;
+//! use std::sync::Arc;
+//!
+//! let runtime = Arc::new(BundlerRuntime::new("."));
+//! let plugin = GraphqlPlugin::new(runtime);
+//! ```
+
+mod ast;
+mod parser;
+mod validate;
+
+use anyhow::Context;
+use ast::{Definition, Document};
+use fob_bundler::{
+    HookLoadArgs, HookLoadOutput, HookLoadReturn, HookUsage, ModuleType, Plugin, PluginContext,
+    Runtime,
+};
+use path_clean::PathClean;
+use std::borrow::Cow;
+use std::collections::HashSet;
+use std::fmt::Write;
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::pin::Pin;
+use std::sync::Arc;
+
+/// Configuration for [`GraphqlPlugin`].
+#[derive(Debug, Clone)]
+pub struct GraphqlOptions {
+    /// File extensions handled by the `load` hook (without the leading dot).
+    pub extensions: Vec<String>,
+    /// Validate documents after parsing (default: true).
+    pub validate: bool,
+}
+
+impl Default for GraphqlOptions {
+    fn default() -> Self {
+        Self {
+            extensions: vec!["graphql".to_string(), "gql".to_string()],
+            validate: true,
+        }
+    }
+}
+
+impl GraphqlOptions {
+    /// Create options with default values.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the file extensions handled by the `load` hook.
+    pub fn with_extensions(mut self, extensions: Vec<String>) -> Self {
+        self.extensions = extensions;
+        self
+    }
+
+    /// Enable or disable validation.
+    pub fn with_validate(mut self, enabled: bool) -> Self {
+        self.validate = enabled;
+        self
+    }
+}
+
+/// GraphQL document plugin.
+#[derive(Debug, Clone)]
+pub struct GraphqlPlugin {
+    options: GraphqlOptions,
+    runtime: Arc<dyn Runtime>,
+}
+
+type DefinitionsFuture<'a> =
+    Pin<Box<dyn Future<Output = anyhow::Result<Vec<Definition>>> + Send + 'a>>;
+
+impl GraphqlPlugin {
+    /// Create a plugin with default options.
+    pub fn new(runtime: Arc<dyn Runtime>) -> Self {
+        Self::with_options(runtime, GraphqlOptions::default())
+    }
+
+    /// Create a plugin with custom options.
+    pub fn with_options(runtime: Arc<dyn Runtime>, options: GraphqlOptions) -> Self {
+        Self { options, runtime }
+    }
+
+    /// Access the plugin options.
+    pub fn options(&self) -> &GraphqlOptions {
+        &self.options
+    }
+
+    /// Whether `id` is a GraphQL document (`.graphql` or `.gql` by default).
+    pub fn handles(&self, id: &str) -> bool {
+        Path::new(id)
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .is_some_and(|ext| self.options.extensions.iter().any(|e| e == ext))
+    }
+
+    /// Load a document with its imports and generate its module.
+    ///
+    /// Returns `Ok(None)` when `id` is not a GraphQL document.
+    pub async fn load_module(&self, id: &str) -> anyhow::Result<Option<String>> {
+        if !self.handles(id) {
+            return Ok(None);
+        }
+
+        let path = Path::new(id).clean();
+        let source = self.read_source(&path).await?;
+        let mut document = parser::parse(&source, "GraphQL request")
+            .map_err(|e| anyhow::anyhow!("Failed to parse {}: {}", id, e))?;
+
+        let mut seen = HashSet::from([path.clone()]);
+        for import in imports(&source) {
+            let imported = self.read_imported(&path, import, &mut seen).await?;
+            document.definitions.extend(imported);
+        }
+
+        if self.options.validate {
+            if let Err(errors) = validate::validate(&document) {
+                anyhow::bail!("Invalid GraphQL in {}:\n  {}", id, errors.join("\n  "));
+            }
+        }
+        document_module(&document).map(Some)
+    }
+
+    /// Definitions of an imported file and, recursively, of its imports.
+    fn read_imported<'a>(
+        &'a self,
+        importer: &'a Path,
+        url: &'a str,
+        seen: &'a mut HashSet<PathBuf>,
+    ) -> DefinitionsFuture<'a> {
+        Box::pin(async move {
+            let dir = importer.parent().unwrap_or(Path::new(""));
+            let path = dir.join(url).clean();
+            if !seen.insert(path.clone()) {
+                return Ok(Vec::new());
+            }
+            if !self.runtime.exists(&path) {
+                anyhow::bail!("Cannot find '{}' imported from {}", url, importer.display());
+            }
+
+            let source = self.read_source(&path).await?;
+            let document = parser::parse(&source, "GraphQL request")
+                .map_err(|e| anyhow::anyhow!("Failed to parse {}: {}", path.display(), e))?;
+            let mut definitions = document.definitions;
+            for import in imports(&source) {
+                definitions.extend(self.read_imported(&path, import, seen).await?);
+            }
+            Ok(definitions)
+        })
+    }
+
+    async fn read_source(&self, path: &Path) -> anyhow::Result<String> {
+        let bytes = self
+            .runtime
+            .read_file(path)
+            .await
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        String::from_utf8(bytes)
+            .with_context(|| format!("{} contains invalid UTF-8", path.display()))
+    }
+}
+
+impl Plugin for GraphqlPlugin {
+    fn name(&self) -> Cow<'static, str> {
+        "fob-plugin-graphql".into()
+    }
+
+    fn register_hook_usage(&self) -> HookUsage {
+        HookUsage::Load
+    }
+
+    fn load(
+        &self,
+        _ctx: &PluginContext,
+        args: &HookLoadArgs<'_>,
+    ) -> impl std::future::Future<Output = HookLoadReturn> + Send {
+        let plugin = self.clone();
+        let id = args.id.to_string();
+
+        async move {
+            Ok(plugin.load_module(&id).await?.map(|code| HookLoadOutput {
+                code: code.into(),
+                module_type: Some(ModuleType::Js),
+                ..Default::default()
+            }))
+        }
+    }
+}
+
+/// Paths of `#import "..."` comments, as used by `graphql-tag/loader`.
+fn imports(source: &str) -> impl Iterator<Item = &str> {
+    source.lines().filter_map(|line| {
+        let rest = line.trim_start().strip_prefix("#import")?.trim();
+        let quote = rest.chars().next().filter(|c| *c == '"' || *c == '\'')?;
+        rest[1..]
+            .split(quote)
+            .next()
+            .filter(|path| !path.is_empty())
+    })
+}
+
+/// The document as the default export, plus one named export per named
+/// operation with the fragments it needs.
+fn document_module(document: &Document) -> anyhow::Result<String> {
+    let mut code = format!(
+        "const doc = {};\nexport default doc;\n",
+        serde_json::to_string(document)?
+    );
+
+    for (index, definition) in document.definitions.iter().enumerate() {
+        let (Definition::OperationDefinition(_), Some(name)) = (definition, definition.name())
+        else {
+            continue;
+        };
+        let needed = fragments_needed(document, definition);
+        let definitions: Vec<String> = std::iter::once(index)
+            .chain(needed)
+            .map(|i| format!("doc.definitions[{}]", i))
+            .collect();
+        let _ = write!(
+            code,
+            "const op{index} = {{ kind: \"Document\", definitions: [{}], loc: doc.loc }};\nexport {{ op{index} as {} }};\n",
+            definitions.join(", "),
+            name,
+        );
+    }
+    Ok(code)
+}
+
+/// Indexes of the fragments an operation uses, directly or through other
+/// fragments, in document order.
+fn fragments_needed(document: &Document, operation: &Definition) -> Vec<usize> {
+    let mut needed = HashSet::new();
+    let mut pending = Vec::new();
+    validate::collect_spreads(operation.selection_set(), &mut pending);
+    while let Some(name) = pending.pop() {
+        if !needed.insert(name) {
+            continue;
+        }
+        if let Some(fragment) = document
+            .definitions
+            .iter()
+            .find(|d| matches!(d, Definition::FragmentDefinition(_)) && d.name() == Some(name))
+        {
+            validate::collect_spreads(fragment.selection_set(), &mut pending);
+        }
+    }
+
+    document
+        .definitions
+        .iter()
+        .enumerate()
+        .filter(|(_, d)| matches!(d, Definition::FragmentDefinition(_)))
+        .filter(|(_, d)| d.name().is_some_and(|name| needed.contains(name)))
+        .map(|(index, _)| index)
+        .collect()
+}
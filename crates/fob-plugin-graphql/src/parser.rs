@@ -0,0 +1,719 @@
+//! Parser for executable GraphQL documents (operations and fragments).
+//!
+//! Follows the October 2021 spec grammar. Type system definitions (`type`,
+//! `schema`, ...) are rejected: schemas are not imported into client code.
+
+use crate::ast::*;
+
+/// Parse a document. `name` is recorded as the source name.
+pub fn parse(source: &str, name: &str) -> Result<Document, String> {
+    let mut parser = Parser {
+        lexer: Lexer::new(source),
+        token: Token::Eof,
+        token_start: 0,
+    };
+    parser.advance()?;
+
+    let mut definitions = Vec::new();
+    loop {
+        if parser.token == Token::Eof {
+            break;
+        }
+        definitions.push(parser.definition()?);
+    }
+    if definitions.is_empty() {
+        return Err(parser.error("Expected a query, mutation, subscription or fragment"));
+    }
+
+    Ok(Document {
+        definitions,
+        loc: Loc {
+            start: 0,
+            end: source.len(),
+            source: Source {
+                body: source.to_string(),
+                name: name.to_string(),
+                location_offset: LocationOffset { line: 1, column: 1 },
+            },
+        },
+    })
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Punctuator(&'static str),
+    Name(String),
+    Int(String),
+    Float(String),
+    String { value: String, block: bool },
+    Eof,
+}
+
+impl Token {
+    fn describe(&self) -> String {
+        match self {
+            Self::Punctuator(p) => format!("\"{}\"", p),
+            Self::Name(name) => format!("Name \"{}\"", name),
+            Self::Int(value) | Self::Float(value) => format!("number {}", value),
+            Self::String { .. } => "string".to_string(),
+            Self::Eof => "end of file".to_string(),
+        }
+    }
+}
+
+struct Lexer<'a> {
+    source: &'a str,
+    pos: usize,
+}
+
+impl<'a> Lexer<'a> {
+    fn new(source: &'a str) -> Self {
+        let pos = if source.starts_with('\u{feff}') { 3 } else { 0 };
+        Self { source, pos }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.source[self.pos..].chars().next()
+    }
+
+    /// Skip whitespace, commas and comments.
+    fn skip_ignored(&mut self) {
+        while let Some(c) = self.peek() {
+            match c {
+                ' ' | '\t' | '\n' | '\r' | ',' | '\u{feff}' => self.pos += c.len_utf8(),
+                '#' => {
+                    let rest = &self.source[self.pos..];
+                    self.pos += rest.find(['\n', '\r']).unwrap_or(rest.len());
+                }
+                _ => break,
+            }
+        }
+    }
+
+    /// Next token and the byte offset it starts at.
+    fn next(&mut self) -> Result<(Token, usize), String> {
+        self.skip_ignored();
+        let start = self.pos;
+        let rest = &self.source[start..];
+        let Some(c) = self.peek() else {
+            return Ok((Token::Eof, start));
+        };
+
+        const PUNCTUATORS: &[&str] = &[
+            "...", "!", "$", "&", "(", ")", ":", "=", "@", "[", "]", "{", "|", "}",
+        ];
+        if let Some(p) = PUNCTUATORS.iter().find(|p| rest.starts_with(**p)) {
+            self.pos += p.len();
+            return Ok((Token::Punctuator(p), start));
+        }
+
+        if c == '_' || c.is_ascii_alphabetic() {
+            let len = rest
+                .find(|c: char| !(c == '_' || c.is_ascii_alphanumeric()))
+                .unwrap_or(rest.len());
+            self.pos += len;
+            return Ok((Token::Name(rest[..len].to_string()), start));
+        }
+        if c == '-' || c.is_ascii_digit() {
+            return self.number().map(|token| (token, start));
+        }
+        if rest.starts_with("\"\"\"") {
+            return self.block_string().map(|token| (token, start));
+        }
+        if c == '"' {
+            return self.string().map(|token| (token, start));
+        }
+        Err(format!("Unexpected character \"{}\"", c))
+    }
+
+    fn number(&mut self) -> Result<Token, String> {
+        let bytes = self.source.as_bytes();
+        let start = self.pos;
+        let digits = |pos: &mut usize| {
+            let begin = *pos;
+            while bytes.get(*pos).is_some_and(u8::is_ascii_digit) {
+                *pos += 1;
+            }
+            *pos > begin
+        };
+
+        let mut pos = start;
+        if bytes[pos] == b'-' {
+            pos += 1;
+        }
+        if bytes.get(pos) == Some(&b'0') && bytes.get(pos + 1).is_some_and(u8::is_ascii_digit) {
+            return Err("Invalid number, unexpected digit after 0".to_string());
+        }
+        if !digits(&mut pos) {
+            return Err("Invalid number, expected digit".to_string());
+        }
+        let mut float = false;
+        if bytes.get(pos) == Some(&b'.') {
+            pos += 1;
+            float = true;
+            if !digits(&mut pos) {
+                return Err("Invalid number, expected digit after \".\"".to_string());
+            }
+        }
+        if matches!(bytes.get(pos), Some(b'e' | b'E')) {
+            pos += 1;
+            float = true;
+            if matches!(bytes.get(pos), Some(b'+' | b'-')) {
+                pos += 1;
+            }
+            if !digits(&mut pos) {
+                return Err("Invalid number, expected digit in exponent".to_string());
+            }
+        }
+        if bytes
+            .get(pos)
+            .is_some_and(|b| *b == b'.' || *b == b'_' || b.is_ascii_alphabetic())
+        {
+            return Err("Invalid number, expected digit".to_string());
+        }
+
+        self.pos = pos;
+        let text = self.source[start..pos].to_string();
+        Ok(if float {
+            Token::Float(text)
+        } else {
+            Token::Int(text)
+        })
+    }
+
+    fn string(&mut self) -> Result<Token, String> {
+        self.pos += 1;
+        let mut value = String::new();
+        let mut chars = self.source[self.pos..].char_indices();
+        while let Some((offset, c)) = chars.next() {
+            match c {
+                '"' => {
+                    self.pos += offset + 1;
+                    return Ok(Token::String {
+                        value,
+                        block: false,
+                    });
+                }
+                '\n' | '\r' => break,
+                '\\' => {
+                    let escaped = match chars.next().map(|(_, c)| c) {
+                        Some('"') => '"',
+                        Some('\\') => '\\',
+                        Some('/') => '/',
+                        Some('b') => '\u{8}',
+                        Some('f') => '\u{c}',
+                        Some('n') => '\n',
+                        Some('r') => '\r',
+                        Some('t') => '\t',
+                        Some('u') => {
+                            let hex: String = chars.by_ref().take(4).map(|(_, c)| c).collect();
+                            u32::from_str_radix(&hex, 16)
+                                .ok()
+                                .and_then(char::from_u32)
+                                .ok_or_else(|| format!("Invalid Unicode escape \"\\u{}\"", hex))?
+                        }
+                        other => {
+                            return Err(format!(
+                                "Invalid character escape \"\\{}\"",
+                                other.map(String::from).unwrap_or_default()
+                            ));
+                        }
+                    };
+                    value.push(escaped);
+                }
+                c => value.push(c),
+            }
+        }
+        Err("Unterminated string".to_string())
+    }
+
+    fn block_string(&mut self) -> Result<Token, String> {
+        self.pos += 3;
+        let rest = &self.source[self.pos..];
+        let mut raw = String::new();
+        let mut offset = 0;
+        loop {
+            let Some(c) = rest[offset..].chars().next() else {
+                return Err("Unterminated string".to_string());
+            };
+            if rest[offset..].starts_with("\"\"\"") {
+                self.pos += offset + 3;
+                return Ok(Token::String {
+                    value: block_string_value(&raw),
+                    block: true,
+                });
+            }
+            if rest[offset..].starts_with("\\\"\"\"") {
+                raw.push_str("\"\"\"");
+                offset += 4;
+                continue;
+            }
+            raw.push(c);
+            offset += c.len_utf8();
+        }
+    }
+}
+
+/// Remove the common indentation and surrounding blank lines of a block
+/// string, as the spec's `BlockStringValue`.
+fn block_string_value(raw: &str) -> String {
+    let lines: Vec<&str> = raw
+        .split("\r\n")
+        .flat_map(|l| l.split(['\n', '\r']))
+        .collect();
+    let indent = |line: &str| line.len() - line.trim_start_matches([' ', '\t']).len();
+    let common = lines
+        .iter()
+        .skip(1)
+        .filter(|line| line.len() > indent(line))
+        .map(|line| indent(line))
+        .min()
+        .unwrap_or(0);
+
+    let mut lines: Vec<&str> = lines
+        .iter()
+        .enumerate()
+        .map(|(i, line)| {
+            if i == 0 {
+                line
+            } else {
+                &line[common.min(line.len())..]
+            }
+        })
+        .collect();
+    while lines
+        .first()
+        .is_some_and(|l| l.trim_matches([' ', '\t']).is_empty())
+    {
+        lines.remove(0);
+    }
+    while lines
+        .last()
+        .is_some_and(|l| l.trim_matches([' ', '\t']).is_empty())
+    {
+        lines.pop();
+    }
+    lines.join("\n")
+}
+
+struct Parser<'a> {
+    lexer: Lexer<'a>,
+    token: Token,
+    token_start: usize,
+}
+
+impl Parser<'_> {
+    fn advance(&mut self) -> Result<Token, String> {
+        let (token, start) = self.lexer.next().map_err(|e| {
+            let at = self.lexer.pos;
+            self.error_at(&e, at)
+        })?;
+        self.token_start = start;
+        Ok(std::mem::replace(&mut self.token, token))
+    }
+
+    fn error(&self, message: &str) -> String {
+        self.error_at(message, self.token_start)
+    }
+
+    fn error_at(&self, message: &str, offset: usize) -> String {
+        let before = &self.lexer.source[..offset.min(self.lexer.source.len())];
+        let line = before.matches('\n').count() + 1;
+        let column = before.len() - before.rfind('\n').map_or(0, |i| i + 1) + 1;
+        format!("Syntax Error: {} ({}:{})", message, line, column)
+    }
+
+    fn unexpected(&self, expected: &str) -> String {
+        self.error(&format!(
+            "Expected {}, found {}",
+            expected,
+            self.token.describe()
+        ))
+    }
+
+    fn peek(&self, punctuator: &str) -> bool {
+        matches!(self.token, Token::Punctuator(p) if p == punctuator)
+    }
+
+    fn peek_name(&self, name: &str) -> bool {
+        matches!(&self.token, Token::Name(n) if n == name)
+    }
+
+    fn skip(&mut self, punctuator: &str) -> Result<bool, String> {
+        if self.peek(punctuator) {
+            self.advance()?;
+            return Ok(true);
+        }
+        Ok(false)
+    }
+
+    fn expect(&mut self, punctuator: &str) -> Result<(), String> {
+        if !self.skip(punctuator)? {
+            return Err(self.unexpected(&format!("\"{}\"", punctuator)));
+        }
+        Ok(())
+    }
+
+    fn name(&mut self) -> Result<Name, String> {
+        let Token::Name(value) = &self.token else {
+            return Err(self.unexpected("Name"));
+        };
+        let value = value.clone();
+        self.advance()?;
+        Ok(Name { value })
+    }
+
+    fn definition(&mut self) -> Result<Definition, String> {
+        if self.peek("{") {
+            return Ok(Definition::OperationDefinition(OperationDefinition {
+                operation: OperationType::Query,
+                name: None,
+                variable_definitions: Vec::new(),
+                directives: Vec::new(),
+                selection_set: self.selection_set()?,
+            }));
+        }
+
+        let keyword = match &self.token {
+            Token::Name(keyword) => keyword.clone(),
+            Token::String { .. } => {
+                return Err(self.error(
+                    "Descriptions are only allowed on type system definitions, which are not supported in documents",
+                ));
+            }
+            _ => return Err(self.unexpected("a query, mutation, subscription or fragment")),
+        };
+        let operation = match keyword.as_str() {
+            "query" => OperationType::Query,
+            "mutation" => OperationType::Mutation,
+            "subscription" => OperationType::Subscription,
+            "fragment" => {
+                return self
+                    .fragment_definition()
+                    .map(Definition::FragmentDefinition);
+            }
+            "schema" | "scalar" | "type" | "interface" | "union" | "enum" | "input"
+            | "directive" | "extend" => {
+                return Err(self.error(&format!(
+                    "Unexpected \"{}\": type system definitions are not supported in documents",
+                    keyword
+                )));
+            }
+            _ => return Err(self.unexpected("a query, mutation, subscription or fragment")),
+        };
+        self.advance()?;
+
+        let name = match self.token {
+            Token::Name(_) => Some(self.name()?),
+            _ => None,
+        };
+        Ok(Definition::OperationDefinition(OperationDefinition {
+            operation,
+            name,
+            variable_definitions: self.variable_definitions()?,
+            directives: self.directives(false)?,
+            selection_set: self.selection_set()?,
+        }))
+    }
+
+    fn fragment_definition(&mut self) -> Result<FragmentDefinition, String> {
+        self.advance()?;
+        let name = self.fragment_name()?;
+        if !self.peek_name("on") {
+            return Err(self.unexpected("\"on\""));
+        }
+        self.advance()?;
+        Ok(FragmentDefinition {
+            name,
+            type_condition: NamedType { name: self.name()? },
+            directives: self.directives(false)?,
+            selection_set: self.selection_set()?,
+        })
+    }
+
+    fn fragment_name(&mut self) -> Result<Name, String> {
+        if self.peek_name("on") {
+            return Err(self.unexpected("a fragment name"));
+        }
+        self.name()
+    }
+
+    fn variable_definitions(&mut self) -> Result<Vec<VariableDefinition>, String> {
+        let mut definitions = Vec::new();
+        if !self.skip("(")? {
+            return Ok(definitions);
+        }
+        loop {
+            self.expect("$")?;
+            let variable = Variable { name: self.name()? };
+            self.expect(":")?;
+            let ty = self.ty()?;
+            let default_value = if self.skip("=")? {
+                Some(self.value(true)?)
+            } else {
+                None
+            };
+            definitions.push(VariableDefinition {
+                variable,
+                ty,
+                default_value,
+                directives: self.directives(true)?,
+            });
+            if self.skip(")")? {
+                return Ok(definitions);
+            }
+        }
+    }
+
+    fn ty(&mut self) -> Result<Type, String> {
+        let ty = if self.skip("[")? {
+            let inner = self.ty()?;
+            self.expect("]")?;
+            Type::ListType {
+                ty: Box::new(inner),
+            }
+        } else {
+            Type::NamedType { name: self.name()? }
+        };
+        if self.skip("!")? {
+            return Ok(Type::NonNullType { ty: Box::new(ty) });
+        }
+        Ok(ty)
+    }
+
+    fn selection_set(&mut self) -> Result<SelectionSet, String> {
+        self.expect("{")?;
+        let mut selections = Vec::new();
+        loop {
+            selections.push(self.selection()?);
+            if self.skip("}")? {
+                return Ok(SelectionSet { selections });
+            }
+        }
+    }
+
+    fn selection(&mut self) -> Result<Selection, String> {
+        if !self.skip("...")? {
+            return self.field().map(Selection::Field);
+        }
+
+        if matches!(&self.token, Token::Name(name) if name != "on") {
+            return Ok(Selection::FragmentSpread(FragmentSpread {
+                name: self.name()?,
+                directives: self.directives(false)?,
+            }));
+        }
+        let type_condition = if self.peek_name("on") {
+            self.advance()?;
+            Some(NamedType { name: self.name()? })
+        } else {
+            None
+        };
+        Ok(Selection::InlineFragment(InlineFragment {
+            type_condition,
+            directives: self.directives(false)?,
+            selection_set: self.selection_set()?,
+        }))
+    }
+
+    fn field(&mut self) -> Result<Field, String> {
+        let first = self.name()?;
+        let (alias, name) = if self.skip(":")? {
+            (Some(first), self.name()?)
+        } else {
+            (None, first)
+        };
+        Ok(Field {
+            alias,
+            name,
+            arguments: self.arguments(false)?,
+            directives: self.directives(false)?,
+            selection_set: if self.peek("{") {
+                Some(self.selection_set()?)
+            } else {
+                None
+            },
+        })
+    }
+
+    fn arguments(&mut self, is_const: bool) -> Result<Vec<Argument>, String> {
+        let mut arguments = Vec::new();
+        if !self.skip("(")? {
+            return Ok(arguments);
+        }
+        loop {
+            let name = self.name()?;
+            self.expect(":")?;
+            arguments.push(Argument {
+                name,
+                value: self.value(is_const)?,
+            });
+            if self.skip(")")? {
+                return Ok(arguments);
+            }
+        }
+    }
+
+    fn directives(&mut self, is_const: bool) -> Result<Vec<Directive>, String> {
+        let mut directives = Vec::new();
+        while self.skip("@")? {
+            directives.push(Directive {
+                name: self.name()?,
+                arguments: self.arguments(is_const)?,
+            });
+        }
+        Ok(directives)
+    }
+
+    fn value(&mut self, is_const: bool) -> Result<Value, String> {
+        if self.peek("$") {
+            if is_const {
+                return Err(self.error("Unexpected variable in constant value"));
+            }
+            self.advance()?;
+            return Ok(Value::Variable { name: self.name()? });
+        }
+        if self.skip("[")? {
+            let mut values = Vec::new();
+            while !self.skip("]")? {
+                values.push(self.value(is_const)?);
+            }
+            return Ok(Value::ListValue { values });
+        }
+        if self.skip("{")? {
+            let mut fields = Vec::new();
+            while !self.skip("}")? {
+                let name = self.name()?;
+                self.expect(":")?;
+                fields.push(ObjectField {
+                    name,
+                    value: self.value(is_const)?,
+                });
+            }
+            return Ok(Value::ObjectValue { fields });
+        }
+
+        let value = match self.token.clone() {
+            Token::Int(value) => Value::IntValue { value },
+            Token::Float(value) => Value::FloatValue { value },
+            Token::String { value, block } => Value::StringValue { value, block },
+            Token::Name(name) => match name.as_str() {
+                "true" => Value::BooleanValue { value: true },
+                "false" => Value::BooleanValue { value: false },
+                "null" => Value::NullValue,
+                _ => Value::EnumValue { value: name },
+            },
+            Token::Punctuator(_) | Token::Eof => return Err(self.unexpected("a value")),
+        };
+        self.advance()?;
+        Ok(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn operation(source: &str) -> OperationDefinition {
+        match parse(source, "test").unwrap().definitions.remove(0) {
+            Definition::OperationDefinition(operation) => operation,
+            other => panic!("expected an operation, found {:?}", other),
+        }
+    }
+
+    #[test]
+    fn parses_operations() {
+        let op = operation(
+            "query User($id: ID!, $tags: [String!] = [\"a\"]) @cached { user(id: $id) { ...UserFields friends: people(first: 10) { name } ... on Admin { level } } }",
+        );
+
+        assert_eq!(op.operation, OperationType::Query);
+        assert_eq!(op.name.unwrap().value, "User");
+        assert_eq!(op.variable_definitions.len(), 2);
+        assert_eq!(
+            op.variable_definitions[0].ty,
+            Type::NonNullType {
+                ty: Box::new(Type::NamedType {
+                    name: Name {
+                        value: "ID".to_string()
+                    }
+                })
+            }
+        );
+        assert_eq!(op.directives[0].name.value, "cached");
+
+        let Selection::Field(user) = &op.selection_set.selections[0] else {
+            panic!("expected a field");
+        };
+        let selections = &user.selection_set.as_ref().unwrap().selections;
+        assert!(
+            matches!(&selections[0], Selection::FragmentSpread(s) if s.name.value == "UserFields")
+        );
+        assert!(
+            matches!(&selections[1], Selection::Field(f) if f.alias.as_ref().unwrap().value == "friends")
+        );
+        assert!(
+            matches!(&selections[2], Selection::InlineFragment(f) if f.type_condition.is_some())
+        );
+    }
+
+    #[test]
+    fn parses_values() {
+        let op = operation(
+            "{ f(a: -1.5e3, b: \"x\\n\\u0041\", c: null, d: RED, e: {k: [1, true]}, g: \"\"\"\n    one\n      two\n  \"\"\") }",
+        );
+
+        let Selection::Field(field) = &op.selection_set.selections[0] else {
+            panic!("expected a field");
+        };
+        let values: Vec<&Value> = field.arguments.iter().map(|a| &a.value).collect();
+        assert_eq!(
+            values[0],
+            &Value::FloatValue {
+                value: "-1.5e3".to_string()
+            }
+        );
+        assert_eq!(
+            values[1],
+            &Value::StringValue {
+                value: "x\nA".to_string(),
+                block: false
+            }
+        );
+        assert_eq!(values[2], &Value::NullValue);
+        assert_eq!(
+            values[3],
+            &Value::EnumValue {
+                value: "RED".to_string()
+            }
+        );
+        assert!(matches!(values[4], Value::ObjectValue { fields } if fields.len() == 1));
+        assert_eq!(
+            values[5],
+            &Value::StringValue {
+                value: "one\n  two".to_string(),
+                block: true
+            }
+        );
+    }
+
+    #[test]
+    fn serializes_like_graphql_js() {
+        let document = parse("fragment F on User { id }", "GraphQL request").unwrap();
+        let json = serde_json::to_string(&document.definitions).unwrap();
+
+        assert_eq!(
+            json,
+            r#"[{"kind":"FragmentDefinition","name":{"kind":"Name","value":"F"},"typeCondition":{"kind":"NamedType","name":{"kind":"Name","value":"User"}},"directives":[],"selectionSet":{"kind":"SelectionSet","selections":[{"kind":"Field","name":{"kind":"Name","value":"id"},"arguments":[],"directives":[]}]}}]"#
+        );
+    }
+
+    #[test]
+    fn reports_positions() {
+        let error = parse("query {\n  user(id: ) { id }\n}", "test").unwrap_err();
+        assert_eq!(error, "Syntax Error: Expected a value, found \")\" (2:12)");
+
+        let error = parse("type User { id: ID }", "test").unwrap_err();
+        assert!(error.contains("type system definitions are not supported"));
+    }
+}
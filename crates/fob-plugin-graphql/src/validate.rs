@@ -0,0 +1,302 @@
+//! Validation rules that need no schema.
+//!
+//! Covers graphql-js' `LoneAnonymousOperation`, `UniqueOperationNames`,
+//! `UniqueFragmentNames`, `KnownFragmentNames`, `NoFragmentCycles`,
+//! `UniqueVariableNames` and `NoUndefinedVariables`. Rules that check fields
+//! and types against a schema are left to the server or codegen tooling.
+
+use crate::ast::*;
+use std::collections::{HashMap, HashSet};
+
+/// Validate a document, returning every problem found.
+pub fn validate(document: &Document) -> Result<(), Vec<String>> {
+    let mut errors = Vec::new();
+
+    let fragments: HashMap<&str, &FragmentDefinition> = fragment_definitions(document).collect();
+    let operations: Vec<&OperationDefinition> = document
+        .definitions
+        .iter()
+        .filter_map(|definition| match definition {
+            Definition::OperationDefinition(operation) => Some(operation),
+            Definition::FragmentDefinition(_) => None,
+        })
+        .collect();
+
+    if operations.len() > 1 && operations.iter().any(|op| op.name.is_none()) {
+        errors.push("This anonymous operation must be the only defined operation.".to_string());
+    }
+    duplicates(
+        operations.iter().filter_map(|op| op.name.as_ref()),
+        |name| format!("There can be only one operation named \"{}\".", name),
+        &mut errors,
+    );
+    duplicates(
+        fragment_definitions(document).map(|(_, fragment)| &fragment.name),
+        |name| format!("There can be only one fragment named \"{}\".", name),
+        &mut errors,
+    );
+
+    for definition in &document.definitions {
+        let mut spreads = Vec::new();
+        collect_spreads(definition.selection_set(), &mut spreads);
+        for spread in spreads {
+            if !fragments.contains_key(spread) {
+                errors.push(format!("Unknown fragment \"{}\".", spread));
+            }
+        }
+    }
+
+    let mut reported = HashSet::new();
+    for (name, _) in fragment_definitions(document) {
+        let mut path = Vec::new();
+        if !find_cycle(name, name, &fragments, &mut HashSet::new(), &mut path) {
+            continue;
+        }
+        let mut members: Vec<&str> = path.iter().copied().chain([name]).collect();
+        members.sort_unstable();
+        if !reported.insert(members) {
+            continue;
+        }
+        errors.push(if path.is_empty() {
+            format!("Cannot spread fragment \"{}\" within itself.", name)
+        } else {
+            let via: Vec<String> = path.iter().map(|n| format!("\"{}\"", n)).collect();
+            format!(
+                "Cannot spread fragment \"{}\" within itself via {}.",
+                name,
+                via.join(", ")
+            )
+        });
+    }
+
+    for operation in &operations {
+        duplicates(
+            operation
+                .variable_definitions
+                .iter()
+                .map(|definition| &definition.variable.name),
+            |name| format!("There can be only one variable named \"${}\".", name),
+            &mut errors,
+        );
+
+        let defined: HashSet<&str> = operation
+            .variable_definitions
+            .iter()
+            .map(|definition| definition.variable.name.value.as_str())
+            .collect();
+        let mut used = Vec::new();
+        collect_variables(
+            &operation.selection_set,
+            &fragments,
+            &mut HashSet::new(),
+            &mut used,
+        );
+        directives_variables(&operation.directives, &mut used);
+        let mut seen = HashSet::new();
+        for variable in used {
+            if !defined.contains(variable) && seen.insert(variable) {
+                errors.push(match &operation.name {
+                    Some(name) => format!(
+                        "Variable \"${}\" is not defined by operation \"{}\".",
+                        variable, name.value
+                    ),
+                    None => format!("Variable \"${}\" is not defined.", variable),
+                });
+            }
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+fn fragment_definitions(document: &Document) -> impl Iterator<Item = (&str, &FragmentDefinition)> {
+    document
+        .definitions
+        .iter()
+        .filter_map(|definition| match definition {
+            Definition::FragmentDefinition(fragment) => {
+                Some((fragment.name.value.as_str(), fragment))
+            }
+            Definition::OperationDefinition(_) => None,
+        })
+}
+
+/// Report each name defined more than once.
+fn duplicates<'a>(
+    names: impl Iterator<Item = &'a Name>,
+    message: impl Fn(&str) -> String,
+    errors: &mut Vec<String>,
+) {
+    let mut seen = HashSet::new();
+    let mut reported = HashSet::new();
+    for name in names {
+        if !seen.insert(name.value.as_str()) && reported.insert(name.value.as_str()) {
+            errors.push(message(&name.value));
+        }
+    }
+}
+
+/// Fragment names spread anywhere in a selection set.
+pub fn collect_spreads<'a>(selection_set: &'a SelectionSet, spreads: &mut Vec<&'a str>) {
+    for selection in &selection_set.selections {
+        match selection {
+            Selection::Field(field) => {
+                if let Some(selection_set) = &field.selection_set {
+                    collect_spreads(selection_set, spreads);
+                }
+            }
+            Selection::FragmentSpread(spread) => spreads.push(&spread.name.value),
+            Selection::InlineFragment(fragment) => {
+                collect_spreads(&fragment.selection_set, spreads)
+            }
+        }
+    }
+}
+
+/// Whether `target` is reachable from the spreads of fragment `name`,
+/// leaving the fragments in between in `path`.
+fn find_cycle<'a>(
+    target: &str,
+    name: &str,
+    fragments: &HashMap<&'a str, &'a FragmentDefinition>,
+    visited: &mut HashSet<&'a str>,
+    path: &mut Vec<&'a str>,
+) -> bool {
+    let Some(&fragment) = fragments.get(name) else {
+        return false;
+    };
+    let mut spreads = Vec::new();
+    collect_spreads(&fragment.selection_set, &mut spreads);
+    for spread in spreads {
+        if spread == target {
+            return true;
+        }
+        if visited.insert(spread) {
+            path.push(spread);
+            if find_cycle(target, spread, fragments, visited, path) {
+                return true;
+            }
+            path.pop();
+        }
+    }
+    false
+}
+
+/// Variables used in a selection set, following fragment spreads.
+fn collect_variables<'a>(
+    selection_set: &'a SelectionSet,
+    fragments: &HashMap<&'a str, &'a FragmentDefinition>,
+    visited: &mut HashSet<&'a str>,
+    used: &mut Vec<&'a str>,
+) {
+    for selection in &selection_set.selections {
+        match selection {
+            Selection::Field(field) => {
+                arguments_variables(&field.arguments, used);
+                directives_variables(&field.directives, used);
+                if let Some(selection_set) = &field.selection_set {
+                    collect_variables(selection_set, fragments, visited, used);
+                }
+            }
+            Selection::FragmentSpread(spread) => {
+                directives_variables(&spread.directives, used);
+                let name = spread.name.value.as_str();
+                let fragment = fragments.get(name).copied();
+                if let Some(fragment) = fragment.filter(|_| visited.insert(name)) {
+                    directives_variables(&fragment.directives, used);
+                    collect_variables(&fragment.selection_set, fragments, visited, used);
+                }
+            }
+            Selection::InlineFragment(fragment) => {
+                directives_variables(&fragment.directives, used);
+                collect_variables(&fragment.selection_set, fragments, visited, used);
+            }
+        }
+    }
+}
+
+fn directives_variables<'a>(directives: &'a [Directive], used: &mut Vec<&'a str>) {
+    for directive in directives {
+        arguments_variables(&directive.arguments, used);
+    }
+}
+
+fn arguments_variables<'a>(arguments: &'a [Argument], used: &mut Vec<&'a str>) {
+    for argument in arguments {
+        value_variables(&argument.value, used);
+    }
+}
+
+fn value_variables<'a>(value: &'a Value, used: &mut Vec<&'a str>) {
+    match value {
+        Value::Variable { name } => used.push(&name.value),
+        Value::ListValue { values } => values.iter().for_each(|v| value_variables(v, used)),
+        Value::ObjectValue { fields } => fields
+            .iter()
+            .for_each(|field| value_variables(&field.value, used)),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse;
+
+    fn errors(source: &str) -> Vec<String> {
+        validate(&parse(source, "test").unwrap())
+            .err()
+            .unwrap_or_default()
+    }
+
+    #[test]
+    fn accepts_valid_documents() {
+        assert!(
+            errors(
+                "query A($id: ID!) { user(id: $id) { ...F } } fragment F on User { id friends @include(if: $id) { ...G } } fragment G on User { name }"
+            )
+            .is_empty()
+        );
+    }
+
+    #[test]
+    fn reports_operation_problems() {
+        assert_eq!(
+            errors("{ a } query B { b } query B { c }"),
+            vec![
+                "This anonymous operation must be the only defined operation.",
+                "There can be only one operation named \"B\".",
+            ]
+        );
+        assert_eq!(
+            errors("query A($x: Int, $x: Int) { f(a: $x, b: $y) }"),
+            vec![
+                "There can be only one variable named \"$x\".",
+                "Variable \"$y\" is not defined by operation \"A\".",
+            ]
+        );
+    }
+
+    #[test]
+    fn reports_fragment_problems() {
+        assert_eq!(
+            errors("{ ...Missing } fragment F on T { a } fragment F on T { b }"),
+            vec![
+                "There can be only one fragment named \"F\".",
+                "Unknown fragment \"Missing\".",
+            ]
+        );
+        assert_eq!(
+            errors("fragment A on T { ...B } fragment B on T { ...A }"),
+            vec!["Cannot spread fragment \"A\" within itself via \"B\"."]
+        );
+        assert_eq!(
+            errors("fragment A on T { a { ...A } }"),
+            vec!["Cannot spread fragment \"A\" within itself."]
+        );
+    }
+}
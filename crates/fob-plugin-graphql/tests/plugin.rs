@@ -0,0 +1,145 @@
+//! Integration tests for fob-plugin-graphql.
+
+use fob_bundler::runtime::BundlerRuntime;
+use fob_plugin_graphql::{GraphqlOptions, GraphqlPlugin};
+use std::sync::Arc;
+
+const USER_FIELDS: &str = "fragment UserFields on User { id name ...Avatar }\n\nfragment Avatar on User { avatar(size: 64) }\n";
+
+const QUERIES: &str = r#"#import "./fragments/user.graphql"
+
+query GetUser($id: ID!) {
+  user(id: $id) { ...UserFields }
+}
+
+mutation Rename($id: ID!, $name: String!) {
+  rename(id: $id, name: $name) { id }
+}
+"#;
+
+fn runtime(files: &[(&str, &str)]) -> Arc<BundlerRuntime> {
+    let runtime = BundlerRuntime::new("/project");
+    for (path, content) in files {
+        runtime.add_virtual_file(*path, content.as_bytes().to_vec());
+    }
+    Arc::new(runtime)
+}
+
+#[tokio::test]
+async fn emits_document_ast() {
+    let plugin = GraphqlPlugin::new(runtime(&[(
+        "/project/src/viewer.gql",
+        "{ viewer { login } }",
+    )]));
+
+    let code = plugin
+        .load_module("/project/src/viewer.gql")
+        .await
+        .unwrap()
+        .expect("graphql file should be handled");
+
+    assert!(code.starts_with("const doc = {\"kind\":\"Document\""));
+    assert!(code.contains("export default doc;"));
+    assert!(code.contains("\"operation\":\"query\""));
+    assert!(code.contains("{\"kind\":\"Name\",\"value\":\"login\"}"));
+    assert!(code.contains("\"body\":\"{ viewer { login } }\""));
+    // Anonymous operations have no named export
+    assert!(!code.contains("export {"));
+}
+
+#[tokio::test]
+async fn follows_fragment_imports() {
+    let plugin = GraphqlPlugin::new(runtime(&[
+        ("/project/src/queries.graphql", QUERIES),
+        ("/project/src/fragments/user.graphql", USER_FIELDS),
+    ]));
+
+    let code = plugin
+        .load_module("/project/src/queries.graphql")
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert!(code.contains("{\"kind\":\"Name\",\"value\":\"UserFields\"}"));
+    assert!(code.contains("{\"kind\":\"Name\",\"value\":\"Avatar\"}"));
+    // The operation and both fragments it needs, transitively
+    assert!(code.contains(
+        "const op0 = { kind: \"Document\", definitions: [doc.definitions[0], doc.definitions[2], doc.definitions[3]], loc: doc.loc };"
+    ));
+    assert!(code.contains("export { op0 as GetUser };"));
+    assert!(code.contains(
+        "const op1 = { kind: \"Document\", definitions: [doc.definitions[1]], loc: doc.loc };"
+    ));
+    assert!(code.contains("export { op1 as Rename };"));
+}
+
+#[tokio::test]
+async fn reports_validation_errors() {
+    let plugin = GraphqlPlugin::new(runtime(&[(
+        "/project/src/broken.graphql",
+        "query A { user(id: $id) { ...Missing } }",
+    )]));
+
+    let error = plugin
+        .load_module("/project/src/broken.graphql")
+        .await
+        .unwrap_err()
+        .to_string();
+
+    assert!(error.contains("Invalid GraphQL in /project/src/broken.graphql"));
+    assert!(error.contains("Unknown fragment \"Missing\"."));
+    assert!(error.contains("Variable \"$id\" is not defined by operation \"A\"."));
+
+    let plugin = GraphqlPlugin::with_options(
+        runtime(&[(
+            "/project/src/broken.graphql",
+            "query A { user(id: $id) { ...Missing } }",
+        )]),
+        GraphqlOptions::new().with_validate(false),
+    );
+    assert!(
+        plugin
+            .load_module("/project/src/broken.graphql")
+            .await
+            .is_ok()
+    );
+}
+
+#[tokio::test]
+async fn reports_syntax_errors_and_missing_imports() {
+    let plugin = GraphqlPlugin::new(runtime(&[
+        ("/project/src/syntax.graphql", "query { user(id: ) }"),
+        ("/project/src/queries.graphql", QUERIES),
+    ]));
+
+    let error = plugin
+        .load_module("/project/src/syntax.graphql")
+        .await
+        .unwrap_err()
+        .to_string();
+    assert!(error.contains("Failed to parse /project/src/syntax.graphql"));
+    assert!(error.contains("Syntax Error: Expected a value"));
+
+    let error = plugin
+        .load_module("/project/src/queries.graphql")
+        .await
+        .unwrap_err()
+        .to_string();
+    assert!(error.contains("Cannot find './fragments/user.graphql'"));
+    assert!(error.contains("/project/src/queries.graphql"));
+}
+
+#[tokio::test]
+async fn skips_other_modules() {
+    let plugin = GraphqlPlugin::new(runtime(&[]));
+
+    assert!(
+        plugin
+            .load_module("/project/src/main.js")
+            .await
+            .unwrap()
+            .is_none()
+    );
+    assert!(plugin.handles("/project/src/schema.gql"));
+    assert!(!plugin.handles("/project/src/schema.json"));
+}
@@ -0,0 +1,297 @@
+//! Image plugin for the fob bundler.
+//!
+//! Imported images are decoded with the `image` crate, resized to a set of
+//! widths, encoded in the source format plus modern formats (WebP, AVIF) and
+//! emitted as hashed assets. The import resolves to everything a responsive
+//! `<img>` or `<picture>` needs:
+//!
+//! ```js
+//! import hero from "./hero.jpg";
+//! // {
+//! //   src: "/hero-1600w-a1b2c3d4.jpg",
+//! //   srcset: "/hero-640w-....jpg 640w, /hero-960w-....jpg 960w, ...",
+//! //   width: 1600,
+//! //   height: 900,
+//! //   format: "jpeg",
+//! //   placeholder: "data:image/svg+xml;base64,...",
+//! //   sources: [{ type: "image/webp", srcset: "..." }],
+//! // }
+//! ```
+//!
+//! `placeholder` is a tiny thumbnail wrapped by
+//! [`fob_mdx::plugins::blur_placeholder`], which also builds the
+//! `data-placeholder` attribute of Markdown images, so both share one CSS
+//! treatment.
+//!
+//! ```rust,no_run
+//! use fob_bundler::runtime::BundlerRuntime;
+//! use fob_plugin_image::{ImageOptions, ImagePlugin, OutputFormat};
+//! use std::sync::Arc;
+//!
+//! let runtime = Arc::new(BundlerRuntime::new("."));
+//! let plugin = ImagePlugin::with_options(
+//!     runtime,
+//!     ImageOptions::new()
+//!         .with_widths(vec![480, 960])
+//!         .with_formats(vec![OutputFormat::Avif, OutputFormat::Webp]),
+//! );
+//! ```
+
+mod process;
+
+use anyhow::Context;
+use fob_bundler::{
+    EmittedAsset, HookLoadArgs, HookLoadOutput, HookLoadReturn, HookUsage, ModuleType, Plugin,
+    PluginContext, Runtime,
+};
+use std::borrow::Cow;
+use std::fmt::Write;
+use std::path::Path;
+use std::sync::Arc;
+
+pub use process::{OutputFormat, ProcessedImage, Variant};
+
+/// Configuration for [`ImagePlugin`].
+#[derive(Debug, Clone)]
+pub struct ImageOptions {
+    /// File extensions handled by the `load` hook (without the leading dot).
+    pub extensions: Vec<String>,
+    /// Widths of the generated variants. Widths above the image's own are
+    /// skipped; the intrinsic width is always generated.
+    pub widths: Vec<u32>,
+    /// Formats generated in addition to the source format (default: WebP).
+    pub formats: Vec<OutputFormat>,
+    /// JPEG and AVIF quality, 1-100 (default: 80).
+    pub quality: u8,
+    /// Width of the blurred placeholder thumbnail, `0` for none
+    /// (default: 16).
+    pub placeholder_width: u32,
+}
+
+impl Default for ImageOptions {
+    fn default() -> Self {
+        Self {
+            extensions: ["png", "jpg", "jpeg", "webp"]
+                .iter()
+                .map(|ext| ext.to_string())
+                .collect(),
+            widths: vec![640, 960, 1280, 1920],
+            formats: vec![OutputFormat::Webp],
+            quality: 80,
+            placeholder_width: 16,
+        }
+    }
+}
+
+impl ImageOptions {
+    /// Create options with default values.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the file extensions handled by the `load` hook.
+    pub fn with_extensions(mut self, extensions: Vec<String>) -> Self {
+        self.extensions = extensions;
+        self
+    }
+
+    /// Set the widths of the generated variants.
+    pub fn with_widths(mut self, widths: Vec<u32>) -> Self {
+        self.widths = widths;
+        self
+    }
+
+    /// Set the formats generated in addition to the source format.
+    pub fn with_formats(mut self, formats: Vec<OutputFormat>) -> Self {
+        self.formats = formats;
+        self
+    }
+
+    /// Set the JPEG and AVIF quality.
+    pub fn with_quality(mut self, quality: u8) -> Self {
+        self.quality = quality.clamp(1, 100);
+        self
+    }
+
+    /// Set the placeholder width, `0` to disable placeholders.
+    pub fn with_placeholder_width(mut self, width: u32) -> Self {
+        self.placeholder_width = width;
+        self
+    }
+}
+
+/// Image plugin.
+#[derive(Debug, Clone)]
+pub struct ImagePlugin {
+    options: ImageOptions,
+    runtime: Arc<dyn Runtime>,
+}
+
+impl ImagePlugin {
+    /// Create a plugin with default options.
+    pub fn new(runtime: Arc<dyn Runtime>) -> Self {
+        Self::with_options(runtime, ImageOptions::default())
+    }
+
+    /// Create a plugin with custom options.
+    pub fn with_options(runtime: Arc<dyn Runtime>, options: ImageOptions) -> Self {
+        Self { options, runtime }
+    }
+
+    /// Access the plugin options.
+    pub fn options(&self) -> &ImageOptions {
+        &self.options
+    }
+
+    /// Whether `id` is an image to resize; extensions match in any case, so
+    /// `photo.JPG` is included.
+    pub fn handles(&self, id: &str) -> bool {
+        Path::new(id)
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .is_some_and(|ext| {
+                self.options
+                    .extensions
+                    .iter()
+                    .any(|e| e.eq_ignore_ascii_case(ext))
+            })
+    }
+
+    /// Read an image and generate its variants and placeholder.
+    ///
+    /// Returns `Ok(None)` for other file types.
+    pub async fn process_image(&self, id: &str) -> anyhow::Result<Option<ProcessedImage>> {
+        if !self.handles(id) {
+            return Ok(None);
+        }
+
+        let bytes = self
+            .runtime
+            .read_file(Path::new(id))
+            .await
+            .with_context(|| format!("Failed to read {}", id))?;
+        // Encoding, AVIF especially, can take seconds per image
+        let options = self.options.clone();
+        tokio::task::spawn_blocking(move || {
+            let options = process::ProcessOptions {
+                widths: &options.widths,
+                formats: &options.formats,
+                quality: options.quality,
+                placeholder_width: options.placeholder_width,
+            };
+            process::process(&bytes, &options)
+        })
+        .await
+        .context("Image processing task failed")?
+        .with_context(|| format!("Failed to process {}", id))
+        .map(Some)
+    }
+}
+
+/// Generate the module for a processed image.
+///
+/// `urls` holds one JavaScript expression per variant, in the same order,
+/// evaluating to the variant's URL.
+pub fn image_module(image: &ProcessedImage, urls: &[String]) -> String {
+    let variants: Vec<(&Variant, &String)> = image.variants.iter().zip(urls).collect();
+    let srcset = |format: OutputFormat| {
+        variants
+            .iter()
+            .filter(|(variant, _)| variant.format == format)
+            .map(|(variant, url)| format!("{} + \" {}w\"", url, variant.width))
+            .collect::<Vec<_>>()
+            .join(" + \", \" + ")
+    };
+
+    let src = variants
+        .iter()
+        .rev()
+        .find(|(variant, _)| variant.format == image.format)
+        .map_or("undefined", |(_, url)| url.as_str());
+
+    let mut code = String::from("export default {\n");
+    let _ = writeln!(code, "  src: {},", src);
+    let _ = writeln!(code, "  srcset: {},", srcset(image.format));
+    let _ = writeln!(code, "  width: {},", image.width);
+    let _ = writeln!(code, "  height: {},", image.height);
+    let _ = writeln!(code, "  format: \"{}\",", image.format.name());
+    match &image.placeholder {
+        Some(placeholder) => {
+            let _ = writeln!(code, "  placeholder: \"{}\",", placeholder);
+        }
+        None => code.push_str("  placeholder: undefined,\n"),
+    }
+
+    code.push_str("  sources: [");
+    let mut formats: Vec<OutputFormat> = Vec::new();
+    for (variant, _) in &variants {
+        if variant.format != image.format && !formats.contains(&variant.format) {
+            formats.push(variant.format);
+        }
+    }
+    for format in formats {
+        let _ = write!(
+            code,
+            "\n    {{ type: \"{}\", srcset: {} }},",
+            format.mime_type(),
+            srcset(format)
+        );
+    }
+    code.push_str("\n  ],\n};\n");
+    code
+}
+
+impl Plugin for ImagePlugin {
+    fn name(&self) -> Cow<'static, str> {
+        "fob-plugin-image".into()
+    }
+
+    fn register_hook_usage(&self) -> HookUsage {
+        HookUsage::Load
+    }
+
+    fn load(
+        &self,
+        ctx: &PluginContext,
+        args: &HookLoadArgs<'_>,
+    ) -> impl std::future::Future<Output = HookLoadReturn> + Send {
+        let plugin = self.clone();
+        let id = args.id.to_string();
+
+        async move {
+            let Some(image) = plugin.process_image(&id).await? else {
+                return Ok(None);
+            };
+
+            let stem = Path::new(&id)
+                .file_stem()
+                .and_then(|stem| stem.to_str())
+                .unwrap_or("image");
+            let mut urls = Vec::with_capacity(image.variants.len());
+            for variant in &image.variants {
+                let reference_id = ctx.emit_file(
+                    EmittedAsset {
+                        name: Some(format!(
+                            "{}-{}w.{}",
+                            stem,
+                            variant.width,
+                            variant.format.extension()
+                        )),
+                        original_file_name: Some(id.clone()),
+                        file_name: None,
+                        source: variant.bytes.clone().into(),
+                    },
+                    None,
+                    None,
+                )?;
+                urls.push(format!("import.meta.ROLLUP_FILE_URL_{}", reference_id));
+            }
+
+            Ok(Some(HookLoadOutput {
+                code: image_module(&image, &urls).into(),
+                module_type: Some(ModuleType::Js),
+                ..Default::default()
+            }))
+        }
+    }
+}
@@ -0,0 +1,181 @@
+//! Decoding, resizing and encoding of image variants.
+
+use anyhow::Context;
+use image::codecs::avif::AvifEncoder;
+use image::codecs::jpeg::JpegEncoder;
+use image::codecs::png::PngEncoder;
+use image::codecs::webp::WebPEncoder;
+use image::imageops::FilterType;
+use image::{DynamicImage, ImageFormat};
+
+/// Output format of a variant.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum OutputFormat {
+    Png,
+    Jpeg,
+    /// Lossless WebP
+    Webp,
+    Avif,
+}
+
+impl OutputFormat {
+    /// Lowercase name, as used by `fob-mdx`'s `ImageFormat`.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Png => "png",
+            Self::Jpeg => "jpeg",
+            Self::Webp => "webp",
+            Self::Avif => "avif",
+        }
+    }
+
+    /// MIME type of the format.
+    pub fn mime_type(self) -> &'static str {
+        match self {
+            Self::Png => "image/png",
+            Self::Jpeg => "image/jpeg",
+            Self::Webp => "image/webp",
+            Self::Avif => "image/avif",
+        }
+    }
+
+    /// File extension of the format, without the dot.
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Jpeg => "jpg",
+            other => other.name(),
+        }
+    }
+
+    /// Output format for a decoded source format; sources browsers cannot
+    /// show everywhere fall back to PNG.
+    fn for_source(format: ImageFormat) -> Self {
+        match format {
+            ImageFormat::Jpeg => Self::Jpeg,
+            ImageFormat::WebP => Self::Webp,
+            ImageFormat::Avif => Self::Avif,
+            _ => Self::Png,
+        }
+    }
+}
+
+/// Settings for [`process`].
+#[derive(Debug, Clone)]
+pub struct ProcessOptions<'a> {
+    pub widths: &'a [u32],
+    pub formats: &'a [OutputFormat],
+    pub quality: u8,
+    /// Width of the blurred placeholder, `0` for none
+    pub placeholder_width: u32,
+}
+
+/// One emitted file of an image.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Variant {
+    pub format: OutputFormat,
+    pub width: u32,
+    pub height: u32,
+    pub bytes: Vec<u8>,
+}
+
+/// A processed image: variants in its own format first, then the extra
+/// formats, each ordered by width.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProcessedImage {
+    /// Format of the `src`/`srcset` variants
+    pub format: OutputFormat,
+    /// Intrinsic size of the source image
+    pub width: u32,
+    pub height: u32,
+    pub variants: Vec<Variant>,
+    /// Blurred placeholder as a `data:image/svg+xml` URI
+    pub placeholder: Option<String>,
+}
+
+/// Decode an image and encode its variants.
+///
+/// Widths larger than the image are skipped and the intrinsic width is
+/// always included, so images are never upscaled.
+pub fn process(bytes: &[u8], options: &ProcessOptions<'_>) -> anyhow::Result<ProcessedImage> {
+    let source_format = image::guess_format(bytes).context("Unrecognized image format")?;
+    let image = image::load_from_memory_with_format(bytes, source_format)?;
+    let (width, height) = (image.width(), image.height());
+
+    let mut widths: Vec<u32> = options
+        .widths
+        .iter()
+        .copied()
+        .filter(|w| *w > 0 && *w < width)
+        .collect();
+    widths.push(width);
+    widths.sort_unstable();
+    widths.dedup();
+
+    let format = OutputFormat::for_source(source_format);
+    let mut formats = vec![format];
+    formats.extend(options.formats.iter().copied().filter(|f| *f != format));
+
+    let mut variants = Vec::with_capacity(formats.len() * widths.len());
+    for &format in &formats {
+        for &variant_width in &widths {
+            let resized = resize(&image, variant_width);
+            variants.push(Variant {
+                format,
+                width: resized.width(),
+                height: resized.height(),
+                bytes: encode(&resized, format, options.quality)?,
+            });
+        }
+    }
+
+    let placeholder = match options.placeholder_width {
+        0 => None,
+        placeholder_width => Some(blur_placeholder(&image, placeholder_width)?),
+    };
+
+    Ok(ProcessedImage {
+        format,
+        width,
+        height,
+        variants,
+        placeholder,
+    })
+}
+
+/// Scale to a width, keeping the aspect ratio.
+fn resize(image: &DynamicImage, width: u32) -> DynamicImage {
+    if width >= image.width() {
+        return image.clone();
+    }
+    let height = (u64::from(image.height()) * u64::from(width) / u64::from(image.width())).max(1);
+    image.resize_exact(width, height as u32, FilterType::Lanczos3)
+}
+
+fn encode(image: &DynamicImage, format: OutputFormat, quality: u8) -> anyhow::Result<Vec<u8>> {
+    let mut bytes = Vec::new();
+    match format {
+        OutputFormat::Png => DynamicImage::ImageRgba8(image.to_rgba8())
+            .write_with_encoder(PngEncoder::new(&mut bytes))?,
+        // JPEG has no alpha channel
+        OutputFormat::Jpeg => DynamicImage::ImageRgb8(image.to_rgb8())
+            .write_with_encoder(JpegEncoder::new_with_quality(&mut bytes, quality))?,
+        OutputFormat::Webp => DynamicImage::ImageRgba8(image.to_rgba8())
+            .write_with_encoder(WebPEncoder::new_lossless(&mut bytes))?,
+        OutputFormat::Avif => DynamicImage::ImageRgba8(image.to_rgba8())
+            .write_with_encoder(AvifEncoder::new_with_speed_quality(&mut bytes, 6, quality))?,
+    }
+    Ok(bytes)
+}
+
+/// Tiny PNG thumbnail blurred by `fob-mdx`'s placeholder SVG, the same
+/// `data-placeholder` it adds to Markdown images.
+fn blur_placeholder(image: &DynamicImage, width: u32) -> anyhow::Result<String> {
+    let thumbnail = resize(image, width);
+    let png = encode(&thumbnail, OutputFormat::Png, 100)?;
+    Ok(fob_mdx::plugins::blur_placeholder(
+        &png,
+        fob_mdx::plugins::ImageFormat::Png,
+        image.width(),
+        image.height(),
+    ))
+}
@@ -0,0 +1,203 @@
+//! Integration tests for fob-plugin-image.
+
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use fob_bundler::runtime::BundlerRuntime;
+use fob_plugin_image::{ImageOptions, ImagePlugin, OutputFormat, image_module};
+use image::{DynamicImage, ImageFormat, Rgba, RgbaImage};
+use std::io::Cursor;
+use std::sync::Arc;
+
+fn encoded(width: u32, height: u32, format: ImageFormat) -> Vec<u8> {
+    let image = RgbaImage::from_pixel(width, height, Rgba([200, 40, 40, 255]));
+    let image = match format {
+        ImageFormat::Jpeg => DynamicImage::ImageRgb8(DynamicImage::ImageRgba8(image).to_rgb8()),
+        _ => DynamicImage::ImageRgba8(image),
+    };
+    let mut bytes = Vec::new();
+    image
+        .write_to(&mut Cursor::new(&mut bytes), format)
+        .unwrap();
+    bytes
+}
+
+fn plugin_with(files: Vec<(&str, Vec<u8>)>, options: ImageOptions) -> ImagePlugin {
+    let runtime = BundlerRuntime::new("/project");
+    for (path, content) in files {
+        runtime.add_virtual_file(path, content);
+    }
+    ImagePlugin::with_options(Arc::new(runtime), options)
+}
+
+#[tokio::test]
+async fn generates_variants_up_to_the_intrinsic_width() {
+    let plugin = plugin_with(
+        vec![(
+            "/project/src/hero.png",
+            encoded(1200, 600, ImageFormat::Png),
+        )],
+        ImageOptions::default(),
+    );
+
+    let image = plugin
+        .process_image("/project/src/hero.png")
+        .await
+        .unwrap()
+        .expect("png should be handled");
+
+    assert_eq!((image.width, image.height), (1200, 600));
+    assert_eq!(image.format, OutputFormat::Png);
+    let sizes: Vec<(OutputFormat, u32, u32)> = image
+        .variants
+        .iter()
+        .map(|v| (v.format, v.width, v.height))
+        .collect();
+    assert_eq!(
+        sizes,
+        vec![
+            (OutputFormat::Png, 640, 320),
+            (OutputFormat::Png, 960, 480),
+            (OutputFormat::Png, 1200, 600),
+            (OutputFormat::Webp, 640, 320),
+            (OutputFormat::Webp, 960, 480),
+            (OutputFormat::Webp, 1200, 600),
+        ]
+    );
+    assert!(image.variants[3].bytes.starts_with(b"RIFF"));
+    assert!(
+        image
+            .placeholder
+            .as_deref()
+            .is_some_and(|p| p.starts_with("data:image/svg+xml;base64,"))
+    );
+}
+
+#[tokio::test]
+async fn placeholder_matches_markdown_images() {
+    let plugin = plugin_with(
+        vec![("/project/src/hero.png", encoded(320, 160, ImageFormat::Png))],
+        ImageOptions::default(),
+    );
+
+    let image = plugin
+        .process_image("/project/src/hero.png")
+        .await
+        .unwrap()
+        .unwrap();
+    let placeholder = image.placeholder.unwrap();
+
+    // The thumbnail embedded in the placeholder, wrapped the way fob-mdx
+    // wraps Markdown images, gives the same placeholder back
+    let svg = BASE64
+        .decode(
+            placeholder
+                .strip_prefix("data:image/svg+xml;base64,")
+                .unwrap(),
+        )
+        .unwrap();
+    let svg = String::from_utf8(svg).unwrap();
+    let (_, thumbnail) = svg.split_once("data:image/png;base64,").unwrap();
+    let thumbnail = BASE64
+        .decode(thumbnail.split('\'').next().unwrap())
+        .unwrap();
+    assert_eq!(
+        placeholder,
+        fob_mdx::plugins::blur_placeholder(
+            &thumbnail,
+            fob_mdx::plugins::ImageFormat::Png,
+            320,
+            160
+        )
+    );
+}
+
+#[tokio::test]
+async fn keeps_jpeg_sources_and_dedupes_formats() {
+    let plugin = plugin_with(
+        vec![(
+            "/project/src/photo.jpg",
+            encoded(800, 400, ImageFormat::Jpeg),
+        )],
+        ImageOptions::new()
+            .with_widths(vec![400])
+            .with_formats(vec![OutputFormat::Jpeg, OutputFormat::Webp])
+            .with_placeholder_width(0),
+    );
+
+    let image = plugin
+        .process_image("/project/src/photo.jpg")
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(image.format, OutputFormat::Jpeg);
+    let formats: Vec<(OutputFormat, u32)> =
+        image.variants.iter().map(|v| (v.format, v.width)).collect();
+    assert_eq!(
+        formats,
+        vec![
+            (OutputFormat::Jpeg, 400),
+            (OutputFormat::Jpeg, 800),
+            (OutputFormat::Webp, 400),
+            (OutputFormat::Webp, 800),
+        ]
+    );
+    assert!(image.variants[0].bytes.starts_with(&[0xFF, 0xD8]));
+    assert_eq!(image.placeholder, None);
+}
+
+#[tokio::test]
+async fn generates_the_module() {
+    let plugin = plugin_with(
+        vec![(
+            "/project/src/hero.png",
+            encoded(1200, 600, ImageFormat::Png),
+        )],
+        ImageOptions::new().with_widths(vec![640]),
+    );
+    let image = plugin
+        .process_image("/project/src/hero.png")
+        .await
+        .unwrap()
+        .unwrap();
+    let urls: Vec<String> = (0..image.variants.len())
+        .map(|i| format!("url{}", i))
+        .collect();
+
+    let code = image_module(&image, &urls);
+
+    assert!(code.starts_with("export default {"));
+    assert!(code.contains("src: url1,"));
+    assert!(code.contains("srcset: url0 + \" 640w\" + \", \" + url1 + \" 1200w\","));
+    assert!(code.contains("width: 1200,"));
+    assert!(code.contains("height: 600,"));
+    assert!(code.contains("format: \"png\","));
+    assert!(code.contains("placeholder: \"data:image/svg+xml;base64,"));
+    assert!(code.contains(
+        "{ type: \"image/webp\", srcset: url2 + \" 640w\" + \", \" + url3 + \" 1200w\" },"
+    ));
+}
+
+#[tokio::test]
+async fn skips_other_ids_and_names_broken_files() {
+    let plugin = plugin_with(
+        vec![("/project/src/broken.png", b"not an image".to_vec())],
+        ImageOptions::default(),
+    );
+
+    assert!(plugin.handles("/project/src/photo.JPG"));
+    assert!(!plugin.handles("/project/src/icon.svg"));
+    assert!(
+        plugin
+            .process_image("/project/src/app.ts")
+            .await
+            .unwrap()
+            .is_none()
+    );
+
+    let error = plugin
+        .process_image("/project/src/broken.png")
+        .await
+        .unwrap_err();
+    assert!(error.to_string().contains("/project/src/broken.png"));
+}
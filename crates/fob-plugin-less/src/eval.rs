@@ -0,0 +1,757 @@
+//! Evaluation of a parsed stylesheet to CSS.
+//!
+//! Supports lazily evaluated, block-scoped variables, `@{name}`
+//! interpolation, nesting with `&`, mixins (with parameters, defaults and
+//! `@arguments`), media query bubbling and arithmetic. Imports must have been
+//! inlined by the caller; the remaining `@import` rules are plain CSS.
+
+use crate::parser::{Node, split_args, split_top_level, top_level_find};
+use std::collections::HashMap;
+
+/// How many mixin calls or variable lookups may nest before evaluation
+/// stops, to catch recursive definitions.
+const MAX_DEPTH: usize = 64;
+
+/// Compile a parsed stylesheet to CSS.
+pub fn evaluate(nodes: &[Node]) -> Result<String, String> {
+    let mut evaluator = Evaluator {
+        frames: Vec::new(),
+        depth: 0,
+    };
+    let (declarations, items) = evaluator.block(nodes, &[])?;
+    if let Some((name, _)) = declarations.first() {
+        return Err(format!("Declaration '{}' is outside of a rule", name));
+    }
+
+    let mut css = String::new();
+    for item in &items {
+        item.write(&mut css, 0);
+    }
+    Ok(css)
+}
+
+/// Output CSS
+#[derive(Debug)]
+enum Item {
+    Rule {
+        selectors: Vec<String>,
+        declarations: Vec<(String, String)>,
+    },
+    AtRule {
+        header: String,
+        declarations: Vec<(String, String)>,
+        items: Vec<Item>,
+    },
+    Statement(String),
+}
+
+impl Item {
+    fn write(&self, out: &mut String, indent: usize) {
+        let pad = "  ".repeat(indent);
+        match self {
+            Item::Rule {
+                selectors,
+                declarations,
+            } => {
+                out.push_str(&pad);
+                out.push_str(&selectors.join(&format!(",\n{}", pad)));
+                out.push_str(" {\n");
+                write_declarations(out, declarations, indent + 1);
+                out.push_str(&pad);
+                out.push_str("}\n");
+            }
+            Item::AtRule {
+                header,
+                declarations,
+                items,
+            } => {
+                out.push_str(&format!("{}{} {{\n", pad, header));
+                write_declarations(out, declarations, indent + 1);
+                for item in items {
+                    item.write(out, indent + 1);
+                }
+                out.push_str(&pad);
+                out.push_str("}\n");
+            }
+            Item::Statement(text) => {
+                out.push_str(&format!("{}{};\n", pad, text));
+            }
+        }
+    }
+}
+
+fn write_declarations(out: &mut String, declarations: &[(String, String)], indent: usize) {
+    let pad = "  ".repeat(indent);
+    for (name, value) in declarations {
+        out.push_str(&format!("{}{}: {};\n", pad, name, value));
+    }
+}
+
+/// Variables and mixins visible in one block
+struct Frame<'a> {
+    variables: HashMap<String, String>,
+    mixins: Vec<Mixin<'a>>,
+}
+
+struct Mixin<'a> {
+    name: String,
+    params: Vec<Param>,
+    /// Defined with parentheses, so not output as a rule
+    parametric: bool,
+    children: &'a [Node],
+}
+
+struct Param {
+    name: String,
+    default: Option<String>,
+}
+
+struct Evaluator<'a> {
+    frames: Vec<Frame<'a>>,
+    depth: usize,
+}
+
+type Declarations = Vec<(String, String)>;
+
+impl<'a> Evaluator<'a> {
+    /// Evaluate the statements of a block nested in `parents`.
+    ///
+    /// Returns the declarations of the block itself and the rules nested in
+    /// it, in output order.
+    fn block(
+        &mut self,
+        nodes: &'a [Node],
+        parents: &[String],
+    ) -> Result<(Declarations, Vec<Item>), String> {
+        self.frames.push(frame(nodes, HashMap::new()));
+        let result = self.statements(nodes, parents);
+        self.frames.pop();
+        result
+    }
+
+    fn statements(
+        &mut self,
+        nodes: &'a [Node],
+        parents: &[String],
+    ) -> Result<(Declarations, Vec<Item>), String> {
+        let mut declarations = Vec::new();
+        let mut items = Vec::new();
+
+        for node in nodes {
+            match node {
+                Node::Variable { .. } | Node::Reference(_) => {}
+                Node::Declaration { name, value } => {
+                    let name = self.interpolate(name)?;
+                    let value = self.value(value)?;
+                    declarations.push((name, value));
+                }
+                Node::Rule { selector, children } => {
+                    if is_parametric(selector) || selector.contains(" when ") {
+                        continue;
+                    }
+                    let selectors = join_selectors(parents, &self.interpolate(selector)?);
+                    let (inner, nested) = self.block(children, &selectors)?;
+                    if !inner.is_empty() {
+                        items.push(Item::Rule {
+                            selectors,
+                            declarations: inner,
+                        });
+                    }
+                    items.extend(nested);
+                }
+                Node::AtRule {
+                    name,
+                    prelude,
+                    children: Some(children),
+                } => {
+                    let header = format!("@{} {}", name, self.interpolate_value(prelude)?)
+                        .trim_end()
+                        .to_string();
+                    let bubbles = matches!(name.as_str(), "media" | "supports" | "container");
+                    if bubbles && !parents.is_empty() {
+                        // `.a { @media x { ... } }` becomes `@media x { .a { ... } }`
+                        let (inner, nested) = self.block(children, parents)?;
+                        let mut block_items = Vec::new();
+                        if !inner.is_empty() {
+                            block_items.push(Item::Rule {
+                                selectors: parents.to_vec(),
+                                declarations: inner,
+                            });
+                        }
+                        block_items.extend(nested);
+                        items.push(Item::AtRule {
+                            header,
+                            declarations: Vec::new(),
+                            items: block_items,
+                        });
+                    } else {
+                        let (inner, nested) = self.block(children, &[])?;
+                        items.push(Item::AtRule {
+                            header,
+                            declarations: inner,
+                            items: nested,
+                        });
+                    }
+                }
+                Node::AtRule {
+                    name,
+                    prelude,
+                    children: None,
+                } => {
+                    let prelude = self.interpolate_value(prelude)?;
+                    items.push(Item::Statement(format!("@{} {}", name, prelude)));
+                }
+                Node::MixinCall {
+                    name,
+                    args,
+                    important,
+                } => {
+                    let (mut inner, nested) = self.call(name, args, parents)?;
+                    if *important {
+                        for (_, value) in &mut inner {
+                            if !value.ends_with("!important") {
+                                value.push_str(" !important");
+                            }
+                        }
+                    }
+                    declarations.extend(inner);
+                    items.extend(nested);
+                }
+            }
+        }
+
+        Ok((declarations, items))
+    }
+
+    /// Expand a mixin call in the current block.
+    fn call(
+        &mut self,
+        name: &str,
+        args: &[String],
+        parents: &[String],
+    ) -> Result<(Declarations, Vec<Item>), String> {
+        if self.depth >= MAX_DEPTH {
+            return Err(format!("Mixin {} is called recursively", name));
+        }
+
+        // Arguments are evaluated in the caller's scope
+        let mut positional = Vec::new();
+        let mut named = HashMap::new();
+        for arg in args {
+            match named_arg(arg) {
+                Some((param, value)) => {
+                    named.insert(param, self.value(&value)?);
+                }
+                None => positional.push(self.value(arg)?),
+            }
+        }
+
+        let candidates: Vec<(&'a [Node], HashMap<String, String>)> = self
+            .frames
+            .iter()
+            .rev()
+            .map(|frame| {
+                frame
+                    .mixins
+                    .iter()
+                    .filter(|mixin| mixin.name == name && accepts(mixin, &positional, &named))
+                    .collect::<Vec<_>>()
+            })
+            .find(|found| !found.is_empty())
+            .unwrap_or_default()
+            .into_iter()
+            .map(|mixin| (mixin.children, bind(mixin, &positional, &named)))
+            .collect();
+        if candidates.is_empty() {
+            return Err(format!(
+                "No mixin {} accepts {} argument(s)",
+                name,
+                args.len()
+            ));
+        }
+
+        let mut declarations = Vec::new();
+        let mut items = Vec::new();
+        self.depth += 1;
+        for (children, bound) in candidates {
+            // Defaults may refer to other parameters and to outer variables,
+            // which lazy evaluation of the frame takes care of
+            self.frames.push(frame(children, bound));
+            let result = self.statements(children, parents);
+            self.frames.pop();
+            let (inner, nested) = result?;
+            declarations.extend(inner);
+            items.extend(nested);
+        }
+        self.depth -= 1;
+
+        Ok((declarations, items))
+    }
+
+    /// Evaluate a property value: variables, escapes and math.
+    fn value(&mut self, raw: &str) -> Result<String, String> {
+        let value = self.interpolate_value(raw)?;
+        let value = unescape(&value);
+        Ok(arithmetic(&value))
+    }
+
+    /// Look up and evaluate a variable, innermost scope first.
+    fn variable(&mut self, name: &str) -> Result<String, String> {
+        let Some(index) = self
+            .frames
+            .iter()
+            .rposition(|frame| frame.variables.contains_key(name))
+        else {
+            return Err(format!("Variable @{} is undefined", name));
+        };
+        if self.depth >= MAX_DEPTH {
+            return Err(format!("Variable @{} refers to itself", name));
+        }
+
+        let raw = self.frames[index].variables[name].clone();
+        // Evaluate in the scope the variable was defined in
+        let inner = self.frames.split_off(index + 1);
+        self.depth += 1;
+        let result = self.value(&raw);
+        self.depth -= 1;
+        self.frames.extend(inner);
+        result
+    }
+
+    /// Replace `@name` and `@{name}` in a value; interpolation also applies
+    /// inside strings.
+    fn interpolate_value(&mut self, text: &str) -> Result<String, String> {
+        let mut out = String::with_capacity(text.len());
+        let mut rest = text;
+        let mut quote = None;
+
+        while let Some(c) = rest.chars().next() {
+            if c == '@' {
+                let braced = rest
+                    .strip_prefix("@{")
+                    .and_then(|inner| inner.find('}').map(|end| inner.split_at(end)));
+                if let Some((name, after)) = braced {
+                    out.push_str(&self.variable(name)?);
+                    rest = &after[1..];
+                    continue;
+                }
+                let name_len = ident_len(&rest[1..]);
+                if quote.is_none() && name_len > 0 {
+                    out.push_str(&self.variable(&rest[1..1 + name_len])?);
+                    rest = &rest[1 + name_len..];
+                    continue;
+                }
+            }
+            match (quote, c) {
+                (None, '"' | '\'') => quote = Some(c),
+                (Some(q), c) if c == q => quote = None,
+                _ => {}
+            }
+            out.push(c);
+            rest = &rest[c.len_utf8()..];
+        }
+
+        Ok(out)
+    }
+
+    /// Replace `@{name}` in selectors and property names.
+    fn interpolate(&mut self, text: &str) -> Result<String, String> {
+        let mut out = String::with_capacity(text.len());
+        let mut rest = text;
+        while let Some(start) = rest.find("@{") {
+            let Some(end) = rest[start..].find('}') else {
+                break;
+            };
+            out.push_str(&rest[..start]);
+            out.push_str(&unescape(&self.variable(&rest[start + 2..start + end])?));
+            rest = &rest[start + end + 1..];
+        }
+        out.push_str(rest);
+        Ok(out)
+    }
+}
+
+/// Collect the variables (last definition wins) and mixins of a block.
+fn frame(nodes: &[Node], mut variables: HashMap<String, String>) -> Frame<'_> {
+    let mut mixins = Vec::new();
+    for node in nodes {
+        match node {
+            Node::Variable { name, value } => {
+                variables.insert(name.clone(), value.clone());
+            }
+            Node::Rule { selector, children } => {
+                if let Some(mixin) = mixin_definition(selector, children) {
+                    mixins.push(mixin);
+                }
+            }
+            Node::Reference(children) => {
+                let referenced = frame(children, HashMap::new());
+                variables.extend(referenced.variables);
+                mixins.extend(referenced.mixins);
+            }
+            _ => {}
+        }
+    }
+    Frame { variables, mixins }
+}
+
+/// A rule usable as a mixin: a single class or id selector, with optional
+/// parameters.
+fn mixin_definition<'a>(selector: &str, children: &'a [Node]) -> Option<Mixin<'a>> {
+    let selector = selector.trim();
+    if !(selector.starts_with('.') || selector.starts_with('#')) || selector.contains(" when ") {
+        return None;
+    }
+    let (name, params) = match selector.find('(') {
+        Some(open) if selector.ends_with(')') => (
+            selector[..open].trim(),
+            split_args(&selector[open + 1..selector.len() - 1]),
+        ),
+        Some(_) => return None,
+        None => (selector, Vec::new()),
+    };
+    if name[1..].contains(|c: char| !(c.is_alphanumeric() || c == '-' || c == '_')) {
+        return None;
+    }
+
+    let params = params
+        .iter()
+        .filter_map(|param| {
+            let param = param.strip_prefix('@')?;
+            Some(match top_level_find(param, ':') {
+                Some(colon) => Param {
+                    name: param[..colon].trim().to_string(),
+                    default: Some(param[colon + 1..].trim().to_string()),
+                },
+                None => Param {
+                    name: param.trim().to_string(),
+                    default: None,
+                },
+            })
+        })
+        .collect();
+
+    Some(Mixin {
+        name: name.to_string(),
+        params,
+        parametric: is_parametric(selector),
+        children,
+    })
+}
+
+fn is_parametric(selector: &str) -> bool {
+    let selector = selector.trim();
+    let Some(open) = selector.find('(') else {
+        return false;
+    };
+    selector.ends_with(')')
+        && (selector.starts_with('.') || selector.starts_with('#'))
+        && !selector[..open].contains([':', ' ', ','])
+}
+
+/// `@name: value` passed as a mixin argument
+fn named_arg(arg: &str) -> Option<(String, String)> {
+    let rest = arg.strip_prefix('@')?;
+    let len = ident_len(rest);
+    let after = rest[len..].trim_start().strip_prefix(':')?;
+    Some((rest[..len].to_string(), after.trim().to_string()))
+}
+
+fn accepts(mixin: &Mixin<'_>, positional: &[String], named: &HashMap<String, String>) -> bool {
+    if !mixin.parametric {
+        return positional.is_empty() && named.is_empty();
+    }
+    if positional.len() > mixin.params.len() {
+        return false;
+    }
+    mixin.params.iter().enumerate().all(|(index, param)| {
+        index < positional.len() || named.contains_key(&param.name) || param.default.is_some()
+    })
+}
+
+/// Bind arguments to parameters; unset parameters keep their default, which
+/// is evaluated lazily inside the mixin.
+fn bind(
+    mixin: &Mixin<'_>,
+    positional: &[String],
+    named: &HashMap<String, String>,
+) -> HashMap<String, String> {
+    let mut bound = HashMap::new();
+    let mut arguments = Vec::new();
+    for (index, param) in mixin.params.iter().enumerate() {
+        let value = positional
+            .get(index)
+            .or_else(|| named.get(&param.name))
+            .cloned()
+            .or_else(|| param.default.clone())
+            .unwrap_or_default();
+        arguments.push(value.clone());
+        bound.insert(param.name.clone(), value);
+    }
+    bound.insert("arguments".to_string(), arguments.join(" "));
+    bound
+}
+
+/// Combine parent selectors with a nested selector, replacing `&`.
+fn join_selectors(parents: &[String], selector: &str) -> Vec<String> {
+    let children: Vec<&str> = split_top_level(selector, ',')
+        .into_iter()
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .collect();
+    if parents.is_empty() {
+        return children
+            .iter()
+            .map(|child| child.replace('&', "").trim().to_string())
+            .collect();
+    }
+
+    let mut joined = Vec::new();
+    for parent in parents {
+        for child in &children {
+            if child.contains('&') {
+                joined.push(child.replace('&', parent));
+            } else {
+                joined.push(format!("{} {}", parent, child));
+            }
+        }
+    }
+    joined
+}
+
+fn ident_len(text: &str) -> usize {
+    text.find(|c: char| !(c.is_alphanumeric() || c == '-' || c == '_'))
+        .unwrap_or(text.len())
+}
+
+/// `~"text"` and `~'text'` output their contents unquoted.
+fn unescape(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    let mut rest = value;
+    while let Some(start) = rest.find("~\"").or_else(|| rest.find("~'")) {
+        let quote = &rest[start + 1..start + 2];
+        let Some(end) = rest[start + 2..].find(quote) else {
+            break;
+        };
+        out.push_str(&rest[..start]);
+        out.push_str(&rest[start + 2..start + 2 + end]);
+        rest = &rest[start + 3 + end..];
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Evaluate math in a value.
+///
+/// Parenthesized expressions such as `(@width / 2)` support `+ - * /`.
+/// Outside parentheses only `+ - *` are evaluated, since `/` is a separator
+/// in CSS (`font: 12px/1.5`, `grid-area: 1 / 3`).
+fn arithmetic(value: &str) -> String {
+    if value.contains(['"', '\'']) {
+        return value.to_string();
+    }
+    if let Some(rest) = value.strip_suffix("!important") {
+        return format!("{} !important", arithmetic(rest.trim_end()));
+    }
+
+    let mut value = value.to_string();
+    while let Some((open, close)) = innermost_group(&value) {
+        match compute(&value[open + 1..close], true) {
+            Some(result) => value.replace_range(open..=close, &result),
+            None => break,
+        }
+    }
+
+    split_top_level(&value, ',')
+        .iter()
+        .map(|part| compute(part.trim(), false).unwrap_or_else(|| part.trim().to_string()))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Innermost `( ... )` that is not a function call like `calc(...)`.
+fn innermost_group(value: &str) -> Option<(usize, usize)> {
+    let mut open = None;
+    for (index, c) in value.char_indices() {
+        match c {
+            '(' => {
+                let is_call = value[..index]
+                    .chars()
+                    .next_back()
+                    .is_some_and(|c| c.is_alphanumeric() || c == '-' || c == '_');
+                open = (!is_call).then_some(index);
+            }
+            ')' => {
+                if let Some(open) = open {
+                    return Some((open, index));
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Evaluate a whitespace-separated expression of dimensions and operators.
+///
+/// Returns `None` when the text is not purely an expression.
+fn compute(expression: &str, allow_division: bool) -> Option<String> {
+    let tokens: Vec<&str> = expression.split_whitespace().collect();
+    if tokens.len() < 3 || tokens.len() % 2 == 0 {
+        return None;
+    }
+
+    let mut operands = Vec::new();
+    let mut operators = Vec::new();
+    for (index, token) in tokens.iter().enumerate() {
+        if index % 2 == 0 {
+            operands.push(Dimension::parse(token)?);
+        } else {
+            match *token {
+                "+" | "-" | "*" => operators.push(*token),
+                "/" if allow_division => operators.push(*token),
+                _ => return None,
+            }
+        }
+    }
+
+    // `*` and `/` before `+` and `-`
+    let mut terms = vec![operands[0].clone()];
+    let mut additive = Vec::new();
+    for (operator, operand) in operators.into_iter().zip(operands.into_iter().skip(1)) {
+        match operator {
+            "*" | "/" => {
+                let left = terms.pop()?;
+                terms.push(left.apply(operator, &operand)?);
+            }
+            _ => {
+                additive.push(operator);
+                terms.push(operand);
+            }
+        }
+    }
+    let mut terms = terms.into_iter();
+    let mut result = terms.next()?;
+    for (operator, operand) in additive.into_iter().zip(terms) {
+        result = result.apply(operator, &operand)?;
+    }
+    Some(result.to_string())
+}
+
+#[derive(Debug, Clone)]
+struct Dimension {
+    number: f64,
+    unit: String,
+}
+
+impl Dimension {
+    fn parse(token: &str) -> Option<Self> {
+        let unit_start = token
+            .char_indices()
+            .find(|&(index, c)| !(c.is_ascii_digit() || c == '.' || (index == 0 && c == '-')))
+            .map(|(index, _)| index)
+            .unwrap_or(token.len());
+        let number = token[..unit_start].parse().ok()?;
+        let unit = &token[unit_start..];
+        if !unit.chars().all(|c| c.is_ascii_alphabetic() || c == '%') {
+            return None;
+        }
+        Some(Self {
+            number,
+            unit: unit.to_string(),
+        })
+    }
+
+    fn apply(&self, operator: &str, other: &Dimension) -> Option<Dimension> {
+        let number = match operator {
+            "+" => self.number + other.number,
+            "-" => self.number - other.number,
+            "*" => self.number * other.number,
+            "/" if other.number != 0.0 => self.number / other.number,
+            _ => return None,
+        };
+        let unit = if self.unit.is_empty() {
+            other.unit.clone()
+        } else {
+            self.unit.clone()
+        };
+        Some(Dimension { number, unit })
+    }
+}
+
+impl std::fmt::Display for Dimension {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let rounded = (self.number * 1e8).round() / 1e8;
+        write!(f, "{}{}", rounded, self.unit)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse;
+
+    fn compile(source: &str) -> String {
+        evaluate(&parse(source).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn substitutes_lazy_scoped_variables() {
+        let css = compile(
+            ".a { color: @color; @color: blue; }\n.b { color: @color; }\n@color: @base;\n@base: red;",
+        );
+
+        assert_eq!(css, ".a {\n  color: blue;\n}\n.b {\n  color: red;\n}\n");
+    }
+
+    #[test]
+    fn nests_selectors() {
+        let css = compile(".nav, .menu { a { x: 1; &:hover { x: 2; } } }");
+
+        assert!(css.contains(".nav a,\n.menu a {"));
+        assert!(css.contains(".nav a:hover,\n.menu a:hover {"));
+    }
+
+    #[test]
+    fn expands_mixins_with_arguments() {
+        let css = compile(
+            ".border(@width; @style: solid) { border: @width @style; }\n.rounded { radius: 4px; }\n.box { .border(2px); .rounded(); }",
+        );
+
+        assert!(css.contains(".box {\n  border: 2px solid;\n  radius: 4px;\n}"));
+        assert!(!css.contains(".border"));
+        assert!(css.contains(".rounded {\n  radius: 4px;\n}"));
+    }
+
+    #[test]
+    fn bubbles_media_queries() {
+        let css = compile("@bp: ~\"(min-width: 768px)\";\n.a { x: 1; @media @bp { x: 2; } }");
+
+        assert!(css.contains("@media (min-width: 768px) {\n  .a {\n    x: 2;\n  }\n}"));
+    }
+
+    #[test]
+    fn evaluates_math() {
+        assert_eq!(arithmetic("10px + 5px * 2"), "20px");
+        assert_eq!(arithmetic("(20px / 4) 1px"), "5px 1px");
+        assert_eq!(arithmetic("12px/1.5 sans-serif"), "12px/1.5 sans-serif");
+        assert_eq!(arithmetic("calc(100% - 10px)"), "calc(100% - 10px)");
+        assert_eq!(arithmetic("1 / 3"), "1 / 3");
+    }
+
+    #[test]
+    fn interpolates_selectors_and_strings() {
+        let css = compile("@name: logo;\n.@{name} { background: url(\"/img/@{name}.png\"); }");
+
+        assert!(css.contains(".logo {\n  background: url(\"/img/logo.png\");\n}"));
+    }
+
+    #[test]
+    fn reports_undefined_variables_and_mixins() {
+        let error = evaluate(&parse(".a { color: @missing; }").unwrap()).unwrap_err();
+        assert_eq!(error, "Variable @missing is undefined");
+
+        let error = evaluate(&parse(".a { .missing(); }").unwrap()).unwrap_err();
+        assert!(error.contains("No mixin .missing"));
+    }
+}
@@ -0,0 +1,340 @@
+//! Less plugin for the fob bundler.
+//!
+//! Compiles `.less` files in the `load` hook and hands the CSS to fob's
+//! built-in [`CssPlugin`] for minification and browser targets, so the
+//! result continues through the bundler as a CSS module.
+//!
+//! The compiler covers the Less features design systems rely on most:
+//! variables (lazy and block scoped), `@{name}` interpolation, nesting with
+//! `&`, mixins with parameters and defaults, media query bubbling,
+//! arithmetic, `~"escapes"` and `@import` (with the `reference`, `css`,
+//! `optional` and `multiple` options). Guards, `:extend` and Less' color
+//! functions are not supported; CSS functions pass through unchanged.
+//!
+//! ```rust,no_run
+//! use fob_bundler::builtins::CssPluginOptions;
+//! use fob_bundler::runtime::BundlerRuntime;
+//! use fob_plugin_less::{LessOptions, LessPlugin};
+//! use std::sync::Arc;
+//!
+//! let runtime = Arc::new(BundlerRuntime::new("."));
+//! let plugin = LessPlugin::with_options(
+//!     runtime,
+//!     LessOptions::new()
+//!         .with_load_path("node_modules")
+//!         .with_css(CssPluginOptions::new().with_minify(true)),
+//! );
+//! ```
+
+mod eval;
+mod parser;
+
+use anyhow::Context;
+use fob_bundler::builtins::{CssPlugin, CssPluginOptions};
+use fob_bundler::{
+    HookLoadArgs, HookLoadOutput, HookLoadReturn, HookUsage, ModuleType, Plugin, PluginContext,
+    Runtime,
+};
+use parser::Node;
+use path_clean::PathClean;
+use std::borrow::Cow;
+use std::collections::HashSet;
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::pin::Pin;
+use std::sync::Arc;
+
+/// Configuration for [`LessPlugin`].
+#[derive(Debug, Clone)]
+pub struct LessOptions {
+    /// File extensions handled by the `load` hook (without the leading dot).
+    pub extensions: Vec<String>,
+    /// Directories searched for `@import` URLs that are not relative to the
+    /// importing file, e.g. `node_modules`.
+    pub load_paths: Vec<PathBuf>,
+    /// Options of the CSS pass run on the compiled output.
+    pub css: CssPluginOptions,
+}
+
+impl Default for LessOptions {
+    fn default() -> Self {
+        Self {
+            extensions: vec!["less".to_string()],
+            load_paths: Vec::new(),
+            css: CssPluginOptions::default(),
+        }
+    }
+}
+
+impl LessOptions {
+    /// Create options with default values.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the file extensions handled by the `load` hook.
+    pub fn with_extensions(mut self, extensions: Vec<String>) -> Self {
+        self.extensions = extensions;
+        self
+    }
+
+    /// Add a directory searched for `@import` URLs.
+    pub fn with_load_path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.load_paths.push(path.into());
+        self
+    }
+
+    /// Set the options of the CSS pass (minification, targets).
+    pub fn with_css(mut self, css: CssPluginOptions) -> Self {
+        self.css = css;
+        self
+    }
+}
+
+/// Less plugin.
+#[derive(Debug, Clone)]
+pub struct LessPlugin {
+    options: LessOptions,
+    runtime: Arc<dyn Runtime>,
+}
+
+type ImportFuture<'a> = Pin<Box<dyn Future<Output = anyhow::Result<Vec<Node>>> + Send + 'a>>;
+
+impl LessPlugin {
+    /// Create a plugin with default options.
+    pub fn new(runtime: Arc<dyn Runtime>) -> Self {
+        Self::with_options(runtime, LessOptions::default())
+    }
+
+    /// Create a plugin with custom options.
+    pub fn with_options(runtime: Arc<dyn Runtime>, options: LessOptions) -> Self {
+        Self { options, runtime }
+    }
+
+    /// Access the plugin options.
+    pub fn options(&self) -> &LessOptions {
+        &self.options
+    }
+
+    /// Whether `id` is a Less stylesheet (`.less` by default).
+    pub fn handles(&self, id: &str) -> bool {
+        Path::new(id)
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .is_some_and(|ext| self.options.extensions.iter().any(|e| e == ext))
+    }
+
+    /// Load a stylesheet and compile it to CSS.
+    ///
+    /// Plain `.css` files return `Ok(None)` and are left to the CSS plugin.
+    pub async fn load_module(&self, id: &str) -> anyhow::Result<Option<String>> {
+        if !self.handles(id) {
+            return Ok(None);
+        }
+
+        let path = self.absolute(Path::new(id));
+        let mut seen = HashSet::new();
+        let nodes = self.read_stylesheet(path.clone(), &mut seen).await?;
+        let css = eval::evaluate(&nodes)
+            .map_err(|e| anyhow::anyhow!("Failed to compile {}: {}", id, e))?;
+
+        let css_plugin =
+            CssPlugin::with_options(Arc::clone(&self.runtime), self.options.css.clone());
+        css_plugin.process_css(&path, css).map(Some)
+    }
+
+    /// Read and parse a stylesheet with its imports inlined.
+    fn read_stylesheet<'a>(
+        &'a self,
+        path: PathBuf,
+        seen: &'a mut HashSet<PathBuf>,
+    ) -> ImportFuture<'a> {
+        Box::pin(async move {
+            seen.insert(path.clone());
+            let bytes = self
+                .runtime
+                .read_file(&path)
+                .await
+                .with_context(|| format!("Failed to read {}", path.display()))?;
+            let source = String::from_utf8(bytes)
+                .with_context(|| format!("{} contains invalid UTF-8", path.display()))?;
+            let nodes = parser::parse(&source)
+                .map_err(|e| anyhow::anyhow!("Failed to parse {}: {}", path.display(), e))?;
+
+            let dir = path.parent().unwrap_or(Path::new("")).to_path_buf();
+            self.inline_imports(nodes, dir, seen).await
+        })
+    }
+
+    /// Replace `@import` rules for Less files, at any depth, with the
+    /// imported statements.
+    fn inline_imports<'a>(
+        &'a self,
+        nodes: Vec<Node>,
+        dir: PathBuf,
+        seen: &'a mut HashSet<PathBuf>,
+    ) -> ImportFuture<'a> {
+        Box::pin(async move {
+            let mut out = Vec::with_capacity(nodes.len());
+            for node in nodes {
+                match node {
+                    Node::AtRule {
+                        name,
+                        prelude,
+                        children: None,
+                    } if name == "import" => {
+                        let import = Import::parse(&prelude);
+                        let Some(url) = import.less_url() else {
+                            out.push(Node::AtRule {
+                                name,
+                                prelude: import.css_prelude.to_string(),
+                                children: None,
+                            });
+                            continue;
+                        };
+                        let Some(path) = self.resolve_import(&dir, url) else {
+                            if import.has("optional") {
+                                continue;
+                            }
+                            anyhow::bail!("Cannot find '{}' imported from {}", url, dir.display());
+                        };
+                        if seen.contains(&path) && !import.has("multiple") {
+                            continue;
+                        }
+
+                        let imported = self.read_stylesheet(path, seen).await?;
+                        if import.has("reference") {
+                            out.push(Node::Reference(imported));
+                        } else {
+                            out.extend(imported);
+                        }
+                    }
+                    Node::Rule { selector, children } => {
+                        let children = self.inline_imports(children, dir.clone(), seen).await?;
+                        out.push(Node::Rule { selector, children });
+                    }
+                    Node::AtRule {
+                        name,
+                        prelude,
+                        children: Some(children),
+                    } => {
+                        let children = self.inline_imports(children, dir.clone(), seen).await?;
+                        out.push(Node::AtRule {
+                            name,
+                            prelude,
+                            children: Some(children),
+                        });
+                    }
+                    node => out.push(node),
+                }
+            }
+            Ok(out)
+        })
+    }
+
+    /// Find an imported file next to the importing file or in a load path.
+    fn resolve_import(&self, dir: &Path, url: &str) -> Option<PathBuf> {
+        let file = if Path::new(url).extension().is_some() {
+            url.to_string()
+        } else {
+            format!("{}.less", url)
+        };
+        std::iter::once(dir.to_path_buf())
+            .chain(self.options.load_paths.iter().map(|p| self.absolute(p)))
+            .map(|base| base.join(&file).clean())
+            .find(|candidate| self.runtime.exists(candidate))
+    }
+
+    /// Make a path absolute against the runtime's working directory.
+    fn absolute(&self, path: &Path) -> PathBuf {
+        if path.is_absolute() {
+            path.clean()
+        } else {
+            let cwd = self.runtime.get_cwd().unwrap_or_default();
+            cwd.join(path).clean()
+        }
+    }
+}
+
+impl Plugin for LessPlugin {
+    fn name(&self) -> Cow<'static, str> {
+        "fob-plugin-less".into()
+    }
+
+    fn register_hook_usage(&self) -> HookUsage {
+        HookUsage::Load
+    }
+
+    fn load(
+        &self,
+        _ctx: &PluginContext,
+        args: &HookLoadArgs<'_>,
+    ) -> impl std::future::Future<Output = HookLoadReturn> + Send {
+        let plugin = self.clone();
+        let id = args.id.to_string();
+
+        async move {
+            Ok(plugin.load_module(&id).await?.map(|code| HookLoadOutput {
+                code: code.into(),
+                module_type: Some(ModuleType::Css),
+                ..Default::default()
+            }))
+        }
+    }
+}
+
+/// `@import (options) "url";`
+struct Import<'a> {
+    options: Vec<&'a str>,
+    url: &'a str,
+    /// The prelude without the Less options, as it is kept in the CSS
+    css_prelude: &'a str,
+    /// Written as `url(...)`
+    is_url_function: bool,
+}
+
+impl<'a> Import<'a> {
+    fn parse(prelude: &'a str) -> Self {
+        let mut rest = prelude.trim();
+        let mut options = Vec::new();
+        let group = rest
+            .strip_prefix('(')
+            .and_then(|inner| inner.find(')').map(|end| inner.split_at(end)));
+        if let Some((list, after)) = group {
+            options = list.split(',').map(str::trim).collect();
+            rest = after[1..].trim_start();
+        }
+        let css_prelude = rest;
+
+        let (rest, is_url_function) = match rest.strip_prefix("url(") {
+            Some(inner) => (inner.split(')').next().unwrap_or(inner).trim(), true),
+            None => (rest, false),
+        };
+        // Media queries may follow the URL: `@import "print" print;`
+        let url = match rest.chars().next() {
+            Some(quote @ ('"' | '\'')) => rest[1..].split(quote).next().unwrap_or(""),
+            _ => rest.split_whitespace().next().unwrap_or(""),
+        };
+
+        Self {
+            options,
+            url,
+            css_prelude,
+            is_url_function,
+        }
+    }
+
+    fn has(&self, option: &str) -> bool {
+        self.options.contains(&option)
+    }
+
+    /// URL of an import that should be compiled as Less, or `None` for
+    /// imports left to the browser.
+    fn less_url(&self) -> Option<&'a str> {
+        let is_css = self.has("css")
+            || self.is_url_function
+            || (self.url.ends_with(".css") && !self.has("less"))
+            || self.url.contains("://");
+        (!is_css && !self.has("inline")).then_some(self.url)
+    }
+}
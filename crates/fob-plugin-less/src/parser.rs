@@ -0,0 +1,420 @@
+//! Less source to a tree of statements.
+//!
+//! Values and selectors are kept as text; the evaluator substitutes
+//! variables and does math on them.
+
+/// A statement of a Less stylesheet.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Node {
+    /// `@name: value;`
+    Variable { name: String, value: String },
+    /// `property: value;`
+    Declaration { name: String, value: String },
+    /// `selector { ... }`, including mixin definitions like `.m(@a) { ... }`
+    Rule {
+        selector: String,
+        children: Vec<Node>,
+    },
+    /// `@media ... { ... }` or `@import ...;`
+    AtRule {
+        name: String,
+        prelude: String,
+        children: Option<Vec<Node>>,
+    },
+    /// `.mixin;`, `.mixin();` or `.mixin(args) !important;`
+    MixinCall {
+        name: String,
+        args: Vec<String>,
+        important: bool,
+    },
+    /// Contents of an `@import (reference)`: their variables and mixins can
+    /// be used, but nothing is output
+    Reference(Vec<Node>),
+}
+
+/// Parse a stylesheet.
+pub fn parse(source: &str) -> Result<Vec<Node>, String> {
+    let source = strip_comments(source);
+    let mut parser = Parser {
+        chars: source.chars().collect(),
+        pos: 0,
+    };
+    let nodes = parser.block()?;
+    if parser.pos < parser.chars.len() {
+        return Err(format!("Unexpected '}}' at line {}", parser.line()));
+    }
+    Ok(nodes)
+}
+
+struct Parser {
+    chars: Vec<char>,
+    pos: usize,
+}
+
+/// How a statement ended
+enum End {
+    Semicolon,
+    Block,
+    Close,
+    Eof,
+}
+
+impl Parser {
+    /// Parse statements until a closing brace (left unconsumed) or the end.
+    fn block(&mut self) -> Result<Vec<Node>, String> {
+        let mut nodes = Vec::new();
+        loop {
+            let start_line = self.line();
+            let (text, end) = self.statement()?;
+            let text = text.trim();
+            match end {
+                End::Block => {
+                    let children = self.block()?;
+                    if self.pos >= self.chars.len() {
+                        return Err(format!("Unclosed block '{}' at line {}", text, start_line));
+                    }
+                    self.pos += 1;
+                    nodes.push(block_node(text, children));
+                }
+                End::Semicolon | End::Close | End::Eof => {
+                    if !text.is_empty() {
+                        nodes.push(statement_node(text)?);
+                    }
+                    if !matches!(end, End::Semicolon) {
+                        return Ok(nodes);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Read text up to `;`, `{` or `}` outside strings, parentheses and
+    /// `@{...}` interpolation. `;` and `{` are consumed, `}` is not.
+    fn statement(&mut self) -> Result<(String, End), String> {
+        let mut text = String::new();
+        let mut depth = 0usize;
+        let mut quote = None;
+
+        while let Some(&c) = self.chars.get(self.pos) {
+            self.pos += 1;
+            if let Some(q) = quote {
+                text.push(c);
+                if c == '\\' {
+                    if let Some(&escaped) = self.chars.get(self.pos) {
+                        text.push(escaped);
+                        self.pos += 1;
+                    }
+                } else if c == q {
+                    quote = None;
+                }
+                continue;
+            }
+            match c {
+                '"' | '\'' => {
+                    quote = Some(c);
+                    text.push(c);
+                }
+                '(' => {
+                    depth += 1;
+                    text.push(c);
+                }
+                ')' => {
+                    depth = depth.saturating_sub(1);
+                    text.push(c);
+                }
+                '@' if self.chars.get(self.pos) == Some(&'{') => {
+                    // `@{name}` interpolation
+                    text.push(c);
+                    while let Some(&c) = self.chars.get(self.pos) {
+                        self.pos += 1;
+                        text.push(c);
+                        if c == '}' {
+                            break;
+                        }
+                    }
+                }
+                ';' if depth == 0 => return Ok((text, End::Semicolon)),
+                '{' if depth == 0 => return Ok((text, End::Block)),
+                '}' if depth == 0 => {
+                    self.pos -= 1;
+                    return Ok((text, End::Close));
+                }
+                _ => text.push(c),
+            }
+        }
+
+        if quote.is_some() {
+            return Err(format!("Unterminated string at line {}", self.line()));
+        }
+        Ok((text, End::Eof))
+    }
+
+    fn line(&self) -> usize {
+        self.chars[..self.pos.min(self.chars.len())]
+            .iter()
+            .filter(|&&c| c == '\n')
+            .count()
+            + 1
+    }
+}
+
+fn block_node(header: &str, children: Vec<Node>) -> Node {
+    match header.strip_prefix('@') {
+        Some(rest) => {
+            let (name, prelude) = split_at_rule(rest);
+            Node::AtRule {
+                name,
+                prelude,
+                children: Some(children),
+            }
+        }
+        None => Node::Rule {
+            selector: header.to_string(),
+            children,
+        },
+    }
+}
+
+fn statement_node(text: &str) -> Result<Node, String> {
+    if let Some(rest) = text.strip_prefix('@') {
+        let name_len = rest
+            .find(|c: char| !(c.is_alphanumeric() || c == '-' || c == '_'))
+            .unwrap_or(rest.len());
+        let after = rest[name_len..].trim_start();
+        if name_len > 0 && after.starts_with(':') {
+            return Ok(Node::Variable {
+                name: rest[..name_len].to_string(),
+                value: after[1..].trim().to_string(),
+            });
+        }
+        let (name, prelude) = split_at_rule(rest);
+        return Ok(Node::AtRule {
+            name,
+            prelude,
+            children: None,
+        });
+    }
+
+    match top_level_find(text, ':') {
+        Some(colon) => Ok(Node::Declaration {
+            name: text[..colon].trim().to_string(),
+            value: text[colon + 1..].trim().to_string(),
+        }),
+        None if text.starts_with('.') || text.starts_with('#') => Ok(mixin_call(text)),
+        None => Err(format!("Expected a declaration, found '{}'", text)),
+    }
+}
+
+fn mixin_call(text: &str) -> Node {
+    let (text, important) = match text.strip_suffix("!important") {
+        Some(rest) => (rest.trim_end(), true),
+        None => (text, false),
+    };
+    let (name, args) = match text.find('(') {
+        Some(open) if text.ends_with(')') => (
+            text[..open].trim(),
+            split_args(&text[open + 1..text.len() - 1]),
+        ),
+        _ => (text.trim(), Vec::new()),
+    };
+    Node::MixinCall {
+        name: name.to_string(),
+        args,
+        important,
+    }
+}
+
+fn split_at_rule(rest: &str) -> (String, String) {
+    let name_len = rest
+        .find(|c: char| c.is_whitespace() || c == '(' || c == '"' || c == '\'')
+        .unwrap_or(rest.len());
+    (
+        rest[..name_len].to_string(),
+        rest[name_len..].trim().to_string(),
+    )
+}
+
+/// Split mixin arguments or parameters on `;`, or on `,` when there is no
+/// `;` (Less accepts both).
+pub fn split_args(text: &str) -> Vec<String> {
+    let separator = if top_level_find(text, ';').is_some() {
+        ';'
+    } else {
+        ','
+    };
+    split_top_level(text, separator)
+        .into_iter()
+        .map(|arg| arg.trim().to_string())
+        .filter(|arg| !arg.is_empty())
+        .collect()
+}
+
+/// Split on a character outside strings and parentheses.
+pub fn split_top_level(text: &str, separator: char) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut start = 0;
+    let mut rest = text;
+    while let Some(index) = top_level_find(rest, separator) {
+        parts.push(&text[start..start + index]);
+        start += index + separator.len_utf8();
+        rest = &text[start..];
+    }
+    parts.push(&text[start..]);
+    parts
+}
+
+/// Byte index of a character outside strings and parentheses.
+pub fn top_level_find(text: &str, target: char) -> Option<usize> {
+    let mut depth = 0usize;
+    let mut quote = None;
+    let mut escaped = false;
+    for (index, c) in text.char_indices() {
+        if let Some(q) = quote {
+            if escaped {
+                escaped = false;
+            } else if c == '\\' {
+                escaped = true;
+            } else if c == q {
+                quote = None;
+            }
+            continue;
+        }
+        match c {
+            '"' | '\'' => quote = Some(c),
+            '(' | '[' => depth += 1,
+            ')' | ']' => depth = depth.saturating_sub(1),
+            c if c == target && depth == 0 => return Some(index),
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Remove `//` and `/* */` comments, leaving strings and `url(...)` intact.
+fn strip_comments(source: &str) -> String {
+    let mut out = String::with_capacity(source.len());
+    let mut chars = source.chars().peekable();
+    let mut quote = None;
+
+    while let Some(c) = chars.next() {
+        if let Some(q) = quote {
+            out.push(c);
+            if c == '\\' {
+                if let Some(escaped) = chars.next() {
+                    out.push(escaped);
+                }
+            } else if c == q {
+                quote = None;
+            }
+            continue;
+        }
+
+        match (c, chars.peek()) {
+            ('"' | '\'', _) => {
+                quote = Some(c);
+                out.push(c);
+            }
+            // `//` inside an unquoted url(http://...) is not a comment
+            ('/', Some('/')) if !out.ends_with(':') => {
+                for c in chars.by_ref() {
+                    if c == '\n' {
+                        out.push('\n');
+                        break;
+                    }
+                }
+            }
+            ('/', Some('*')) => {
+                chars.next();
+                let mut previous = ' ';
+                for c in chars.by_ref() {
+                    if c == '\n' {
+                        out.push('\n');
+                    }
+                    if previous == '*' && c == '/' {
+                        break;
+                    }
+                    previous = c;
+                }
+            }
+            _ => out.push(c),
+        }
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_variables_rules_and_calls() {
+        let nodes = parse(
+            "@color: red; // primary\n.a { color: @color; .mixin(1px; 2px) !important; &:hover { x: y } }",
+        )
+        .unwrap();
+
+        assert_eq!(
+            nodes[0],
+            Node::Variable {
+                name: "color".to_string(),
+                value: "red".to_string()
+            }
+        );
+        let Node::Rule { selector, children } = &nodes[1] else {
+            panic!("expected a rule");
+        };
+        assert_eq!(selector, ".a");
+        assert_eq!(
+            children[1],
+            Node::MixinCall {
+                name: ".mixin".to_string(),
+                args: vec!["1px".to_string(), "2px".to_string()],
+                important: true
+            }
+        );
+        assert!(matches!(&children[2], Node::Rule { selector, .. } if selector == "&:hover"));
+    }
+
+    #[test]
+    fn keeps_interpolation_and_urls() {
+        let nodes = parse(".@{name}-icon { background: url(http://example.com/a.png); }").unwrap();
+
+        let Node::Rule { selector, children } = &nodes[0] else {
+            panic!("expected a rule");
+        };
+        assert_eq!(selector, ".@{name}-icon");
+        assert_eq!(
+            children[0],
+            Node::Declaration {
+                name: "background".to_string(),
+                value: "url(http://example.com/a.png)".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn parses_at_rules() {
+        let nodes =
+            parse("@import (reference) \"base\";\n@media (min-width: 768px) { .a { b: c } }")
+                .unwrap();
+
+        assert!(matches!(
+            &nodes[0],
+            Node::AtRule { name, prelude, children: None } if name == "import" && prelude == "(reference) \"base\""
+        ));
+        assert!(matches!(
+            &nodes[1],
+            Node::AtRule { name, children: Some(_), .. } if name == "media"
+        ));
+    }
+
+    #[test]
+    fn reports_unclosed_blocks() {
+        assert!(
+            parse(".a { color: red;")
+                .unwrap_err()
+                .contains("Unclosed block")
+        );
+    }
+}
@@ -0,0 +1,143 @@
+//! Integration tests for fob-plugin-less.
+
+use fob_bundler::builtins::CssPluginOptions;
+use fob_bundler::runtime::BundlerRuntime;
+use fob_plugin_less::{LessOptions, LessPlugin};
+use std::sync::Arc;
+
+fn runtime(files: &[(&str, &str)]) -> Arc<BundlerRuntime> {
+    let runtime = BundlerRuntime::new("/project");
+    for (path, content) in files {
+        runtime.add_virtual_file(*path, content.as_bytes().to_vec());
+    }
+    Arc::new(runtime)
+}
+
+#[tokio::test]
+async fn compiles_variables_mixins_and_imports() {
+    let plugin = LessPlugin::new(runtime(&[
+        (
+            "/project/src/app.less",
+            "@import \"theme/colors\";\n.button { .padded(8px); color: @primary; &:hover { text-decoration: underline; } }\n",
+        ),
+        (
+            "/project/src/theme/colors.less",
+            "@import \"../mixins\";\n@primary: red;\n",
+        ),
+        (
+            "/project/src/mixins.less",
+            ".padded(@size) { padding: @size (@size * 2); }\n",
+        ),
+    ]));
+
+    let css = plugin
+        .load_module("/project/src/app.less")
+        .await
+        .unwrap()
+        .expect("file should be handled");
+
+    assert!(css.contains(".button"));
+    assert!(css.contains("padding: 8px 16px"));
+    assert!(css.contains("color: red"));
+    assert!(css.contains(".button:hover"));
+    assert!(!css.contains(".padded"));
+    assert!(!css.contains('@'));
+}
+
+#[tokio::test]
+async fn reference_imports_only_provide_mixins() {
+    let plugin = LessPlugin::with_options(
+        runtime(&[
+            (
+                "/project/src/app.less",
+                "@import (reference) \"bootstrap\";\n.card { .rounded; }\n",
+            ),
+            (
+                "/project/node_modules/bootstrap.less",
+                ".rounded { border-radius: 4px; }\n.unused { color: red; }\n",
+            ),
+        ]),
+        LessOptions::new().with_load_path("node_modules"),
+    );
+
+    let css = plugin
+        .load_module("/project/src/app.less")
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert!(css.contains(".card"));
+    assert!(css.contains("border-radius: 4px"));
+    assert!(!css.contains(".unused"));
+    assert!(!css.contains(".rounded"));
+}
+
+#[tokio::test]
+async fn css_imports_are_kept() {
+    let plugin = LessPlugin::new(runtime(&[(
+        "/project/src/app.less",
+        "@import (css) url(\"https://fonts.example.com/inter.css\");\n.a { b: c; }\n",
+    )]));
+
+    let css = plugin
+        .load_module("/project/src/app.less")
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert!(css.contains("@import"));
+    assert!(css.contains("fonts.example.com/inter.css"));
+    assert!(!css.contains("(css)"));
+}
+
+#[tokio::test]
+async fn css_options_minify_output() {
+    let plugin = LessPlugin::with_options(
+        runtime(&[("/project/src/app.less", ".a {\n  .b { color: red; }\n}\n")]),
+        LessOptions::new().with_css(CssPluginOptions::new().with_minify(true)),
+    );
+
+    let css = plugin
+        .load_module("/project/src/app.less")
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(css, ".a .b{color:red}");
+}
+
+#[tokio::test]
+async fn skips_other_files() {
+    let plugin = LessPlugin::new(runtime(&[]));
+
+    assert!(
+        plugin
+            .load_module("/project/src/index.css")
+            .await
+            .unwrap()
+            .is_none()
+    );
+}
+
+#[tokio::test]
+async fn errors_name_the_file() {
+    let plugin = LessPlugin::new(runtime(&[
+        ("/project/src/app.less", ".a { color: @missing; }\n"),
+        ("/project/src/broken.less", "@import \"nowhere\";\n"),
+    ]));
+
+    let error = plugin
+        .load_module("/project/src/app.less")
+        .await
+        .unwrap_err()
+        .to_string();
+    assert!(error.contains("/project/src/app.less"));
+    assert!(error.contains("@missing"));
+
+    let error = plugin
+        .load_module("/project/src/broken.less")
+        .await
+        .unwrap_err()
+        .to_string();
+    assert!(error.contains("nowhere"));
+}
@@ -0,0 +1,229 @@
+//! Plain Markdown plugin for the fob bundler.
+//!
+//! Loads `.md` files with fob-mdx's parser in plain Markdown mode: there is
+//! no ESM, JSX or expression syntax, so `<`, `{` and `import` are ordinary
+//! text and raw HTML is allowed. That suits changelogs and docs folders
+//! written for GitHub rather than for MDX.
+//!
+//! By default a module exports the rendered HTML:
+//!
+//! ```js
+//! import html, { frontmatter, excerpt, readingTime } from "./CHANGELOG.md";
+//! ```
+//!
+//! With [`MarkdownOutput::Component`] it exports an MDX-style component
+//! instead, taking `components` overrides like a compiled `.mdx` file:
+//!
+//! ```js
+//! import Changelog, { frontmatter } from "./CHANGELOG.md";
+//! ```
+//!
+//! ```rust,no_run
+//! use fob_bundler::runtime::BundlerRuntime;
+//! use fob_plugin_markdown::{MarkdownOptions, MarkdownOutput, MarkdownPlugin};
+//! use std::sync::Arc;
+//!
+//! let runtime = Arc::new(BundlerRuntime::new("."));
+//! let plugin = MarkdownPlugin::with_options(
+//!     runtime,
+//!     MarkdownOptions::new().with_output(MarkdownOutput::Component),
+//! );
+//! ```
+
+use anyhow::Context;
+use fob_bundler::{
+    HookLoadArgs, HookLoadOutput, HookLoadReturn, HookUsage, ModuleType, Plugin, PluginContext,
+    Runtime,
+};
+use fob_mdx::{JsxTarget, MdxCompileOptions, compile, render_html};
+use std::borrow::Cow;
+use std::fmt::Write;
+use std::path::Path;
+use std::sync::Arc;
+
+/// What a Markdown module exports.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum MarkdownOutput {
+    /// The rendered HTML string, as the default and the `html` export.
+    #[default]
+    Html,
+    /// A JSX component, as the default export. Raw HTML in the document is
+    /// dropped, since it can't be expressed as JSX.
+    Component,
+}
+
+/// Configuration for [`MarkdownPlugin`].
+#[derive(Debug, Clone)]
+pub struct MarkdownOptions {
+    /// What modules export (default: HTML).
+    pub output: MarkdownOutput,
+    /// File extensions handled by the `load` hook (without the leading dot).
+    pub extensions: Vec<String>,
+    /// JSX runtime targeted by component output (default: React).
+    pub jsx_target: JsxTarget,
+}
+
+impl Default for MarkdownOptions {
+    fn default() -> Self {
+        Self {
+            output: MarkdownOutput::default(),
+            extensions: vec!["md".to_string()],
+            jsx_target: JsxTarget::default(),
+        }
+    }
+}
+
+impl MarkdownOptions {
+    /// Create options with default values.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set what modules export.
+    pub fn with_output(mut self, output: MarkdownOutput) -> Self {
+        self.output = output;
+        self
+    }
+
+    /// Set the file extensions handled by the `load` hook.
+    pub fn with_extensions(mut self, extensions: Vec<String>) -> Self {
+        self.extensions = extensions;
+        self
+    }
+
+    /// Set the JSX runtime targeted by component output.
+    pub fn with_jsx_target(mut self, jsx_target: JsxTarget) -> Self {
+        self.jsx_target = jsx_target;
+        self
+    }
+}
+
+/// Plain Markdown plugin.
+#[derive(Debug, Clone)]
+pub struct MarkdownPlugin {
+    options: MarkdownOptions,
+    runtime: Arc<dyn Runtime>,
+}
+
+impl MarkdownPlugin {
+    /// Create a plugin with default options.
+    pub fn new(runtime: Arc<dyn Runtime>) -> Self {
+        Self::with_options(runtime, MarkdownOptions::default())
+    }
+
+    /// Create a plugin with custom options.
+    pub fn with_options(runtime: Arc<dyn Runtime>, options: MarkdownOptions) -> Self {
+        Self { options, runtime }
+    }
+
+    /// Access the plugin options.
+    pub fn options(&self) -> &MarkdownOptions {
+        &self.options
+    }
+
+    /// Whether `id` is a plain Markdown file (`.md` by default); `.mdx` files
+    /// belong to the MDX plugin.
+    pub fn handles(&self, id: &str) -> bool {
+        Path::new(id)
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .is_some_and(|ext| self.options.extensions.iter().any(|e| e == ext))
+    }
+
+    /// Load a Markdown file and generate its module.
+    pub async fn load_module(&self, id: &str) -> anyhow::Result<Option<String>> {
+        if !self.handles(id) {
+            return Ok(None);
+        }
+
+        let path = Path::new(id);
+        let bytes = self
+            .runtime
+            .read_file(path)
+            .await
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        let source = String::from_utf8(bytes)
+            .with_context(|| format!("{} contains invalid UTF-8", path.display()))?;
+
+        let options = MdxCompileOptions::builder()
+            .markdown(true)
+            .filepath(id)
+            .jsx_target(self.options.jsx_target)
+            .build();
+        match self.options.output {
+            MarkdownOutput::Html => {
+                let result = render_html(&source, options)
+                    .with_context(|| format!("Failed to render {}", id))?;
+                let frontmatter = result.frontmatter.map(|fm| fm.data);
+                html_module(
+                    &result.html,
+                    frontmatter.as_ref(),
+                    result.excerpt.as_deref(),
+                    result.reading_time_minutes,
+                )
+                .map(Some)
+            }
+            MarkdownOutput::Component => {
+                let result = compile(&source, options)
+                    .with_context(|| format!("Failed to compile {}", id))?;
+                Ok(Some(result.code))
+            }
+        }
+    }
+}
+
+impl Plugin for MarkdownPlugin {
+    fn name(&self) -> Cow<'static, str> {
+        "fob-plugin-markdown".into()
+    }
+
+    fn register_hook_usage(&self) -> HookUsage {
+        HookUsage::Load
+    }
+
+    fn load(
+        &self,
+        _ctx: &PluginContext,
+        args: &HookLoadArgs<'_>,
+    ) -> impl std::future::Future<Output = HookLoadReturn> + Send {
+        let plugin = self.clone();
+        let id = args.id.to_string();
+        let module_type = match self.options.output {
+            MarkdownOutput::Html => ModuleType::Js,
+            MarkdownOutput::Component => ModuleType::Jsx,
+        };
+
+        async move {
+            Ok(plugin.load_module(&id).await?.map(|code| HookLoadOutput {
+                code: code.into(),
+                module_type: Some(module_type),
+                ..Default::default()
+            }))
+        }
+    }
+}
+
+/// The HTML as the default export, plus the document's metadata.
+fn html_module(
+    html: &str,
+    frontmatter: Option<&serde_json::Value>,
+    excerpt: Option<&str>,
+    reading_time: u32,
+) -> anyhow::Result<String> {
+    let mut code = format!(
+        "export const html = {};\nexport default html;\n",
+        serde_json::to_string(html)?
+    );
+    let _ = writeln!(
+        code,
+        "export const frontmatter = {};",
+        serde_json::to_string(frontmatter.unwrap_or(&serde_json::json!({})))?
+    );
+    let _ = writeln!(
+        code,
+        "export const excerpt = {};",
+        serde_json::to_string(&excerpt)?
+    );
+    let _ = writeln!(code, "export const readingTime = {};", reading_time);
+    Ok(code)
+}
@@ -0,0 +1,90 @@
+//! Integration tests for fob-plugin-markdown.
+
+use fob_bundler::runtime::BundlerRuntime;
+use fob_plugin_markdown::{MarkdownOptions, MarkdownOutput, MarkdownPlugin};
+use std::sync::Arc;
+
+const CHANGELOG: &str = "---\ntitle: Changelog\n---\n\n## 1.2.0\n\nSupports `a < b` and {braces}.\n\n<details><summary>Details</summary>\n\nMore text\n\n</details>\n";
+
+fn plugin_with(files: &[(&str, &str)], options: MarkdownOptions) -> MarkdownPlugin {
+    let runtime = BundlerRuntime::new("/project");
+    for (path, content) in files {
+        runtime.add_virtual_file(*path, content.as_bytes().to_vec());
+    }
+    MarkdownPlugin::with_options(Arc::new(runtime), options)
+}
+
+#[tokio::test]
+async fn exports_rendered_html() {
+    let plugin = plugin_with(
+        &[("/project/CHANGELOG.md", CHANGELOG)],
+        MarkdownOptions::default(),
+    );
+
+    let code = plugin
+        .load_module("/project/CHANGELOG.md")
+        .await
+        .unwrap()
+        .expect("markdown file should be handled");
+
+    assert!(code.starts_with("export const html = \"<h2 id=\\\"1-2-0\\\">1.2.0</h2>"));
+    assert!(code.contains("<code>a &lt; b</code> and {braces}."));
+    assert!(code.contains("<details><summary>Details</summary><p>More text</p></details>"));
+    assert!(code.contains("export default html;"));
+    assert!(code.contains("export const frontmatter = {\"title\":\"Changelog\"};"));
+    assert!(code.contains("export const excerpt = \"Supports a < b and {braces}.\";"));
+    assert!(code.contains("export const readingTime = 1;"));
+}
+
+#[tokio::test]
+async fn exports_component() {
+    let plugin = plugin_with(
+        &[("/project/docs/intro.md", CHANGELOG)],
+        MarkdownOptions::new().with_output(MarkdownOutput::Component),
+    );
+
+    let code = plugin
+        .load_module("/project/docs/intro.md")
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert!(code.contains("export default function MDXContent"));
+    assert!(code.contains("export const frontmatter"));
+    assert!(code.contains("{braces}"));
+    // Raw HTML can't be compiled to JSX and is dropped
+    assert!(!code.contains("<details>"));
+}
+
+#[tokio::test]
+async fn ignores_other_files() {
+    let plugin = plugin_with(&[], MarkdownOptions::default());
+
+    assert!(plugin.handles("/project/README.md"));
+    assert!(!plugin.handles("/project/page.mdx"));
+    assert!(
+        plugin
+            .load_module("/project/page.mdx")
+            .await
+            .unwrap()
+            .is_none()
+    );
+
+    let plugin = plugin_with(
+        &[],
+        MarkdownOptions::new().with_extensions(vec!["markdown".to_string()]),
+    );
+    assert!(plugin.handles("/project/notes.markdown"));
+    assert!(!plugin.handles("/project/README.md"));
+}
+
+#[tokio::test]
+async fn reports_missing_files() {
+    let plugin = plugin_with(&[], MarkdownOptions::default());
+
+    let err = plugin.load_module("/project/missing.md").await.unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("Failed to read /project/missing.md")
+    );
+}
@@ -0,0 +1,279 @@
+//! PostCSS bridge plugin for the fob bundler.
+//!
+//! Runs `.css` files through the project's PostCSS config (custom property
+//! polyfills, nesting plugins, design-token transforms, ...) in the `load`
+//! hook, then hands the result to fob's built-in [`CssPlugin`] pass for
+//! minification and browser targets.
+//!
+//! PostCSS runs in a small pool of long-lived Node processes, using the
+//! `postcss` and `postcss-load-config` packages installed in the project.
+//! Results are cached in memory by file, content and config, so unchanged
+//! stylesheets are not sent to Node again during watch rebuilds.
+//!
+//! Register this plugin before the built-in CSS plugin so it sees `.css`
+//! files first. Without a PostCSS config it leaves them alone.
+//!
+//! ```rust,no_run
+//! use fob_bundler::runtime::BundlerRuntime;
+//! use fob_plugin_postcss::{PostcssOptions, PostcssPlugin};
+//! use std::sync::Arc;
+//!
+//! let runtime = Arc::new(BundlerRuntime::new("."));
+//! let plugin = PostcssPlugin::with_options(runtime, PostcssOptions::new().with_workers(2));
+//! ```
+
+mod worker;
+
+use anyhow::Context;
+use fob_bundler::builtins::{CssPlugin, CssPluginOptions};
+use fob_bundler::{
+    HookLoadArgs, HookLoadOutput, HookLoadReturn, HookUsage, ModuleType, Plugin, PluginContext,
+    Runtime,
+};
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, OnceLock};
+use worker::WorkerPool;
+
+/// Config files looked up in the project directory, in order
+pub const CONFIG_FILES: &[&str] = &[
+    "postcss.config.js",
+    "postcss.config.cjs",
+    "postcss.config.mjs",
+    "postcss.config.ts",
+    ".postcssrc",
+    ".postcssrc.json",
+    ".postcssrc.yaml",
+    ".postcssrc.yml",
+    ".postcssrc.js",
+    ".postcssrc.cjs",
+];
+
+/// Configuration for [`PostcssPlugin`].
+#[derive(Debug, Clone)]
+pub struct PostcssOptions {
+    /// File extensions handled by the `load` hook (without the leading dot).
+    pub extensions: Vec<String>,
+    /// PostCSS config file. When unset, [`CONFIG_FILES`] are looked up in
+    /// the project directory.
+    pub config: Option<PathBuf>,
+    /// Node executable (default: `node` from `PATH`).
+    pub node: PathBuf,
+    /// Number of Node processes (default: up to 4, one per CPU).
+    pub workers: usize,
+    /// Cache results in memory (default: true).
+    pub cache: bool,
+    /// Options of the CSS pass run on the PostCSS output.
+    pub css: CssPluginOptions,
+}
+
+impl Default for PostcssOptions {
+    fn default() -> Self {
+        Self {
+            extensions: vec!["css".to_string()],
+            config: None,
+            node: PathBuf::from("node"),
+            workers: std::thread::available_parallelism()
+                .map(|n| n.get().min(4))
+                .unwrap_or(1),
+            cache: true,
+            css: CssPluginOptions::default(),
+        }
+    }
+}
+
+impl PostcssOptions {
+    /// Create options with default values.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the file extensions handled by the `load` hook.
+    pub fn with_extensions(mut self, extensions: Vec<String>) -> Self {
+        self.extensions = extensions;
+        self
+    }
+
+    /// Use this PostCSS config file instead of looking one up.
+    pub fn with_config(mut self, config: impl Into<PathBuf>) -> Self {
+        self.config = Some(config.into());
+        self
+    }
+
+    /// Set the Node executable.
+    pub fn with_node(mut self, node: impl Into<PathBuf>) -> Self {
+        self.node = node.into();
+        self
+    }
+
+    /// Set the number of Node processes.
+    pub fn with_workers(mut self, workers: usize) -> Self {
+        self.workers = workers;
+        self
+    }
+
+    /// Enable or disable the result cache.
+    pub fn with_cache(mut self, enabled: bool) -> Self {
+        self.cache = enabled;
+        self
+    }
+
+    /// Set the options of the CSS pass (minification, targets).
+    pub fn with_css(mut self, css: CssPluginOptions) -> Self {
+        self.css = css;
+        self
+    }
+}
+
+/// PostCSS bridge plugin.
+///
+/// Clones share the worker pool and the cache.
+#[derive(Debug, Clone)]
+pub struct PostcssPlugin {
+    options: PostcssOptions,
+    runtime: Arc<dyn Runtime>,
+    /// Created on first use; `None` when the project has no PostCSS config
+    pool: Arc<OnceLock<Option<WorkerPool>>>,
+    cache: Arc<Mutex<HashMap<u64, String>>>,
+}
+
+impl PostcssPlugin {
+    /// Create a plugin with default options.
+    pub fn new(runtime: Arc<dyn Runtime>) -> Self {
+        Self::with_options(runtime, PostcssOptions::default())
+    }
+
+    /// Create a plugin with custom options.
+    pub fn with_options(runtime: Arc<dyn Runtime>, options: PostcssOptions) -> Self {
+        Self {
+            options,
+            runtime,
+            pool: Arc::new(OnceLock::new()),
+            cache: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Access the plugin options.
+    pub fn options(&self) -> &PostcssOptions {
+        &self.options
+    }
+
+    /// Whether `id` is a stylesheet PostCSS processes (`.css` by default).
+    pub fn handles(&self, id: &str) -> bool {
+        Path::new(id)
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .is_some_and(|ext| self.options.extensions.iter().any(|e| e == ext))
+    }
+
+    /// The PostCSS config in use, if any.
+    pub fn config_file(&self) -> Option<PathBuf> {
+        let cwd = self.runtime.get_cwd().unwrap_or_default();
+        match &self.options.config {
+            Some(config) => Some(cwd.join(config)),
+            None => CONFIG_FILES
+                .iter()
+                .map(|name| cwd.join(name))
+                .find(|path| self.runtime.exists(path)),
+        }
+    }
+
+    /// Load a stylesheet and run it through PostCSS and the CSS pass.
+    ///
+    /// Without a PostCSS config every file returns `Ok(None)`, so stylesheets
+    /// go to the built-in CSS plugin unchanged.
+    pub async fn load_module(&self, id: &str) -> anyhow::Result<Option<String>> {
+        if !self.handles(id) {
+            return Ok(None);
+        }
+        let Some(pool) = self.pool() else {
+            return Ok(None);
+        };
+
+        let path = Path::new(id);
+        let bytes = self
+            .runtime
+            .read_file(path)
+            .await
+            .with_context(|| format!("Failed to read {}", id))?;
+        let source =
+            String::from_utf8(bytes).with_context(|| format!("{} contains invalid UTF-8", id))?;
+
+        let key = self.cache_key(id, &source).await;
+        let cached = key.and_then(|key| self.cache.lock().ok()?.get(&key).cloned());
+        let processed = match cached {
+            Some(css) => css,
+            None => {
+                let css = pool.process(&source, path).await?;
+                if let (Some(key), Ok(mut cache)) = (key, self.cache.lock()) {
+                    cache.insert(key, css.clone());
+                }
+                css
+            }
+        };
+
+        let css_plugin =
+            CssPlugin::with_options(Arc::clone(&self.runtime), self.options.css.clone());
+        css_plugin.process_css(path, processed).map(Some)
+    }
+
+    fn pool(&self) -> Option<&WorkerPool> {
+        self.pool
+            .get_or_init(|| {
+                let config = self.config_file()?;
+                let project_dir = self.runtime.get_cwd().unwrap_or_default();
+                Some(WorkerPool::new(
+                    self.options.node.clone(),
+                    project_dir,
+                    config,
+                    self.options.workers,
+                ))
+            })
+            .as_ref()
+    }
+
+    /// Cache key covering the file, its content and the config content, so
+    /// editing the config invalidates earlier results.
+    async fn cache_key(&self, id: &str, source: &str) -> Option<u64> {
+        if !self.options.cache {
+            return None;
+        }
+        let config = self.config_file()?;
+        let config_source = self.runtime.read_file(&config).await.ok()?;
+
+        let mut hasher = DefaultHasher::new();
+        id.hash(&mut hasher);
+        source.hash(&mut hasher);
+        config_source.hash(&mut hasher);
+        Some(hasher.finish())
+    }
+}
+
+impl Plugin for PostcssPlugin {
+    fn name(&self) -> Cow<'static, str> {
+        "fob-plugin-postcss".into()
+    }
+
+    fn register_hook_usage(&self) -> HookUsage {
+        HookUsage::Load
+    }
+
+    fn load(
+        &self,
+        _ctx: &PluginContext,
+        args: &HookLoadArgs<'_>,
+    ) -> impl std::future::Future<Output = HookLoadReturn> + Send {
+        let plugin = self.clone();
+        let id = args.id.to_string();
+
+        async move {
+            Ok(plugin.load_module(&id).await?.map(|code| HookLoadOutput {
+                code: code.into(),
+                module_type: Some(ModuleType::Css),
+                ..Default::default()
+            }))
+        }
+    }
+}
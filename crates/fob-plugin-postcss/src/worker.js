@@ -0,0 +1,53 @@
+// PostCSS worker for fob-plugin-postcss.
+//
+// Usage: node -e <this file> <project dir> [config file]
+//
+// Reads one JSON request per line on stdin: { "css": "...", "from": "/abs/path.css" }
+// and answers each with one JSON line: { "css": "..." } or { "error": "..." }.
+// postcss and postcss-load-config are resolved from the project, so the
+// project's own versions and plugins are used.
+
+const { createRequire } = require("module");
+const path = require("path");
+const readline = require("readline");
+
+const projectDir = process.argv[1];
+const configFile = process.argv[2] || "";
+const projectRequire = createRequire(path.join(projectDir, "package.json"));
+
+let processor;
+
+function load() {
+  if (!processor) {
+    processor = (async () => {
+      const postcss = projectRequire("postcss");
+      const loadConfig = projectRequire("postcss-load-config");
+      const searchFrom = configFile ? path.dirname(configFile) : projectDir;
+      const config = await loadConfig({}, searchFrom);
+      return { postcss: postcss(config.plugins), options: config.options };
+    })();
+  }
+  return processor;
+}
+
+let queue = Promise.resolve();
+
+readline.createInterface({ input: process.stdin }).on("line", (line) => {
+  // Answer in request order
+  queue = queue.then(async () => {
+    let response;
+    try {
+      const request = JSON.parse(line);
+      const { postcss, options } = await load();
+      const result = await postcss.process(request.css, {
+        ...options,
+        from: request.from,
+        map: false,
+      });
+      response = { css: result.css };
+    } catch (error) {
+      response = { error: String((error && error.message) || error) };
+    }
+    process.stdout.write(JSON.stringify(response) + "\n");
+  });
+});
@@ -0,0 +1,142 @@
+//! Pool of long-lived Node processes running PostCSS.
+//!
+//! Starting Node and loading PostCSS plugins takes far longer than processing
+//! a stylesheet, so workers are started on first use and kept alive for the
+//! lifetime of the plugin. Each worker handles one request at a time over
+//! line-delimited JSON on stdin/stdout (see `worker.js`).
+
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader, Lines};
+use tokio::process::{Child, ChildStdin, ChildStdout, Command};
+use tokio::sync::Mutex;
+
+const WORKER_SCRIPT: &str = include_str!("worker.js");
+
+#[derive(Serialize)]
+struct Request<'a> {
+    css: &'a str,
+    from: &'a Path,
+}
+
+#[derive(Deserialize)]
+struct Response {
+    css: Option<String>,
+    error: Option<String>,
+}
+
+/// Workers sharing one PostCSS config
+pub(crate) struct WorkerPool {
+    node: PathBuf,
+    project_dir: PathBuf,
+    config: PathBuf,
+    workers: Vec<Mutex<Option<Worker>>>,
+    next: AtomicUsize,
+}
+
+impl fmt::Debug for WorkerPool {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WorkerPool")
+            .field("node", &self.node)
+            .field("project_dir", &self.project_dir)
+            .field("config", &self.config)
+            .field("size", &self.workers.len())
+            .finish()
+    }
+}
+
+impl WorkerPool {
+    pub fn new(node: PathBuf, project_dir: PathBuf, config: PathBuf, size: usize) -> Self {
+        Self {
+            node,
+            project_dir,
+            config,
+            workers: (0..size.max(1)).map(|_| Mutex::new(None)).collect(),
+            next: AtomicUsize::new(0),
+        }
+    }
+
+    /// Run a stylesheet through PostCSS.
+    pub async fn process(&self, css: &str, from: &Path) -> anyhow::Result<String> {
+        let index = self.next.fetch_add(1, Ordering::Relaxed) % self.workers.len();
+        let mut slot = self.workers[index].lock().await;
+
+        if slot.is_none() {
+            *slot = Some(self.spawn().await?);
+        }
+        let worker = slot.as_mut().expect("worker was just started");
+        match worker.process(&Request { css, from }).await {
+            Ok(Ok(css)) => Ok(css),
+            Ok(Err(message)) => {
+                anyhow::bail!("PostCSS failed on {}: {}", from.display(), message)
+            }
+            Err(error) => {
+                // The worker is unusable; start a new one on the next request
+                *slot = None;
+                Err(error.context(format!("PostCSS worker failed on {}", from.display())))
+            }
+        }
+    }
+
+    async fn spawn(&self) -> anyhow::Result<Worker> {
+        let mut child = Command::new(&self.node)
+            .arg("-e")
+            .arg(WORKER_SCRIPT)
+            .arg(&self.project_dir)
+            .arg(&self.config)
+            .current_dir(&self.project_dir)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::inherit())
+            .kill_on_drop(true)
+            .spawn()
+            .with_context(|| format!("Failed to start {}", self.node.display()))?;
+
+        let stdin = child.stdin.take().context("PostCSS worker has no stdin")?;
+        let stdout = child
+            .stdout
+            .take()
+            .context("PostCSS worker has no stdout")?;
+        Ok(Worker {
+            _child: child,
+            stdin,
+            stdout: BufReader::new(stdout).lines(),
+        })
+    }
+}
+
+struct Worker {
+    /// Kept so the process is killed when the worker is dropped
+    _child: Child,
+    stdin: ChildStdin,
+    stdout: Lines<BufReader<ChildStdout>>,
+}
+
+impl Worker {
+    /// Send one request. The outer error means the worker is broken, the
+    /// inner one that PostCSS rejected the stylesheet.
+    async fn process(&mut self, request: &Request<'_>) -> anyhow::Result<Result<String, String>> {
+        let mut line = serde_json::to_string(request)?;
+        line.push('\n');
+        self.stdin.write_all(line.as_bytes()).await?;
+        self.stdin.flush().await?;
+
+        let response = self
+            .stdout
+            .next_line()
+            .await?
+            .context("PostCSS worker exited")?;
+        let response: Response = serde_json::from_str(&response)
+            .with_context(|| format!("Invalid PostCSS worker response: {}", response))?;
+
+        Ok(match (response.css, response.error) {
+            (_, Some(error)) => Err(error),
+            (Some(css), None) => Ok(css),
+            (None, None) => Err("PostCSS returned no CSS".to_string()),
+        })
+    }
+}
@@ -0,0 +1,165 @@
+//! Integration tests for fob-plugin-postcss.
+//!
+//! A shell script stands in for Node so the worker protocol and the cache
+//! can be tested without Node or PostCSS installed.
+
+#![cfg(unix)]
+
+use fob_bundler::builtins::CssPluginOptions;
+use fob_bundler::runtime::BundlerRuntime;
+use fob_plugin_postcss::{PostcssOptions, PostcssPlugin};
+use std::os::unix::fs::PermissionsExt;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tempfile::TempDir;
+
+/// Write a fake `node` that answers every request with `response` and
+/// appends a line to `calls.log` in the project directory.
+fn fake_node(dir: &Path, response: &str) -> PathBuf {
+    let path = dir.join("fake-node");
+    let script = format!(
+        "#!/bin/sh\nwhile IFS= read -r line; do\n  echo call >> '{}'\n  printf '%s\\n' '{}'\ndone\n",
+        dir.join("calls.log").display(),
+        response
+    );
+    std::fs::write(&path, script).unwrap();
+    std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755)).unwrap();
+    path
+}
+
+fn calls(dir: &Path) -> usize {
+    std::fs::read_to_string(dir.join("calls.log"))
+        .map(|log| log.lines().count())
+        .unwrap_or(0)
+}
+
+fn plugin_with(dir: &TempDir, files: &[(&str, &str)], options: PostcssOptions) -> PostcssPlugin {
+    let runtime = BundlerRuntime::new(dir.path());
+    for (path, content) in files {
+        runtime.add_virtual_file(dir.path().join(path), content.as_bytes().to_vec());
+    }
+    PostcssPlugin::with_options(Arc::new(runtime), options)
+}
+
+fn id(dir: &TempDir, path: &str) -> String {
+    dir.path().join(path).to_string_lossy().into_owned()
+}
+
+#[tokio::test]
+async fn without_config_css_is_left_alone() {
+    let dir = TempDir::new().unwrap();
+    let plugin = plugin_with(
+        &dir,
+        &[("src/app.css", ".a { color: red; }")],
+        PostcssOptions::default(),
+    );
+
+    assert!(plugin.config_file().is_none());
+    assert!(
+        plugin
+            .load_module(&id(&dir, "src/app.css"))
+            .await
+            .unwrap()
+            .is_none()
+    );
+}
+
+#[tokio::test]
+async fn runs_postcss_and_caches_results() {
+    let dir = TempDir::new().unwrap();
+    let node = fake_node(dir.path(), r#"{"css":".from-postcss { color: red; }"}"#);
+    let plugin = plugin_with(
+        &dir,
+        &[
+            ("postcss.config.js", "module.exports = { plugins: [] };"),
+            ("src/app.css", ".a { color: blue; }"),
+        ],
+        PostcssOptions::new().with_node(node).with_workers(1),
+    );
+
+    let css = plugin
+        .load_module(&id(&dir, "src/app.css"))
+        .await
+        .unwrap()
+        .expect("file should be handled");
+    assert!(css.contains(".from-postcss"));
+
+    // Clones share the cache
+    let again = plugin
+        .clone()
+        .load_module(&id(&dir, "src/app.css"))
+        .await
+        .unwrap();
+    assert_eq!(again.as_deref(), Some(css.as_str()));
+    assert_eq!(calls(dir.path()), 1);
+}
+
+#[tokio::test]
+async fn cache_can_be_disabled() {
+    let dir = TempDir::new().unwrap();
+    let node = fake_node(dir.path(), r#"{"css":".b {}"}"#);
+    let plugin = plugin_with(
+        &dir,
+        &[(".postcssrc.json", "{}"), ("src/app.css", ".a {}")],
+        PostcssOptions::new().with_node(node).with_cache(false),
+    );
+
+    plugin.load_module(&id(&dir, "src/app.css")).await.unwrap();
+    plugin.load_module(&id(&dir, "src/app.css")).await.unwrap();
+
+    assert_eq!(calls(dir.path()), 2);
+}
+
+#[tokio::test]
+async fn output_goes_through_css_options() {
+    let dir = TempDir::new().unwrap();
+    let node = fake_node(dir.path(), r#"{"css":".a {\n  color: red;\n}"}"#);
+    let plugin = plugin_with(
+        &dir,
+        &[("postcss.config.js", ""), ("src/app.css", ".a {}")],
+        PostcssOptions::new()
+            .with_node(node)
+            .with_css(CssPluginOptions::new().with_minify(true)),
+    );
+
+    let css = plugin.load_module(&id(&dir, "src/app.css")).await.unwrap();
+
+    assert_eq!(css.as_deref(), Some(".a{color:red}"));
+}
+
+#[tokio::test]
+async fn postcss_errors_name_the_file() {
+    let dir = TempDir::new().unwrap();
+    let node = fake_node(dir.path(), r#"{"error":"Unknown word"}"#);
+    let plugin = plugin_with(
+        &dir,
+        &[("postcss.config.js", ""), ("src/app.css", ".a {")],
+        PostcssOptions::new().with_node(node),
+    );
+
+    let error = plugin
+        .load_module(&id(&dir, "src/app.css"))
+        .await
+        .unwrap_err()
+        .to_string();
+
+    assert!(error.contains("Unknown word"));
+    assert!(error.contains("app.css"));
+}
+
+#[tokio::test]
+async fn missing_node_is_an_error() {
+    let dir = TempDir::new().unwrap();
+    let plugin = plugin_with(
+        &dir,
+        &[("postcss.config.js", ""), ("src/app.css", ".a {}")],
+        PostcssOptions::new().with_node(dir.path().join("no-such-node")),
+    );
+
+    let error = plugin
+        .load_module(&id(&dir, "src/app.css"))
+        .await
+        .unwrap_err();
+
+    assert!(format!("{:#}", error).contains("no-such-node"));
+}
@@ -0,0 +1,209 @@
+//! Discovery of the files a stylesheet loads.
+//!
+//! grass reads files synchronously, while the [`Runtime`](fob_bundler::Runtime)
+//! is async. The plugin therefore reads every file a stylesheet may load up
+//! front, and this module finds those files.
+
+use path_clean::PathClean;
+use std::path::{Path, PathBuf};
+
+/// URLs of `@use`, `@forward` and `@import` rules in a stylesheet.
+///
+/// Built-in modules (`sass:math`), remote URLs and `url(...)` imports are
+/// skipped since they never load a file.
+pub fn import_urls(source: &str) -> Vec<String> {
+    let source = strip_comments(source);
+    let mut urls = Vec::new();
+    let mut rest = source.as_str();
+
+    while let Some(at) = rest.find('@') {
+        let starts_statement = rest[..at]
+            .trim_end_matches([' ', '\t'])
+            .chars()
+            .next_back()
+            .is_none_or(|c| matches!(c, '\n' | '\r' | ';' | '{' | '}'));
+        rest = &rest[at + 1..];
+        if !starts_statement {
+            continue;
+        }
+        let Some(keyword) = ["use", "forward", "import"]
+            .into_iter()
+            .find(|keyword| rest.starts_with(keyword))
+        else {
+            continue;
+        };
+        rest = &rest[keyword.len()..];
+        if !rest.starts_with(char::is_whitespace) {
+            continue;
+        }
+
+        // `@import` accepts a comma-separated list
+        loop {
+            rest = rest.trim_start();
+            let (url, remaining) = match rest.chars().next() {
+                Some(quote @ ('"' | '\'')) => match rest[1..].find(quote) {
+                    Some(end) => (&rest[1..end + 1], &rest[end + 2..]),
+                    None => break,
+                },
+                // The indented syntax allows unquoted imports
+                Some(_) => {
+                    let end = rest
+                        .find(|c: char| c.is_whitespace() || c == ',' || c == ';')
+                        .unwrap_or(rest.len());
+                    (&rest[..end], &rest[end..])
+                }
+                None => break,
+            };
+            if loads_file(url) {
+                urls.push(url.to_string());
+            }
+            rest = remaining.trim_start();
+            match rest.strip_prefix(',') {
+                Some(next) => rest = next,
+                None => break,
+            }
+        }
+    }
+
+    urls
+}
+
+fn loads_file(url: &str) -> bool {
+    !(url.is_empty()
+        || url.starts_with("sass:")
+        || url.starts_with("url(")
+        || url.starts_with("//")
+        || url.contains("://"))
+}
+
+/// Paths a URL may refer to, relative to `base`, following Sass' rules for
+/// partials (`_name`), extensions and index files.
+pub fn candidates(base: &Path, url: &str) -> Vec<PathBuf> {
+    let path = base.join(url);
+    let (Some(dir), Some(name)) = (path.parent(), path.file_name().and_then(|n| n.to_str())) else {
+        return Vec::new();
+    };
+
+    let has_extension = [".scss", ".sass", ".css"]
+        .iter()
+        .any(|ext| name.ends_with(ext));
+    if has_extension {
+        return vec![
+            dir.join(name).clean(),
+            dir.join(format!("_{}", name)).clean(),
+        ];
+    }
+
+    let mut paths = Vec::new();
+    for ext in ["scss", "sass", "css"] {
+        paths.push(dir.join(format!("{}.{}", name, ext)).clean());
+        paths.push(dir.join(format!("_{}.{}", name, ext)).clean());
+    }
+    for ext in ["scss", "sass", "css"] {
+        paths.push(path.join(format!("_index.{}", ext)).clean());
+        paths.push(path.join(format!("index.{}", ext)).clean());
+    }
+    paths
+}
+
+/// Replace comments with whitespace, leaving strings intact.
+fn strip_comments(source: &str) -> String {
+    let mut out = String::with_capacity(source.len());
+    let mut chars = source.chars().peekable();
+    let mut quote = None;
+
+    while let Some(c) = chars.next() {
+        if let Some(q) = quote {
+            out.push(c);
+            if c == '\\' {
+                if let Some(escaped) = chars.next() {
+                    out.push(escaped);
+                }
+            } else if c == q {
+                quote = None;
+            }
+            continue;
+        }
+
+        match (c, chars.peek()) {
+            ('"' | '\'', _) => {
+                quote = Some(c);
+                out.push(c);
+            }
+            ('/', Some('/')) => {
+                for c in chars.by_ref() {
+                    if c == '\n' {
+                        out.push('\n');
+                        break;
+                    }
+                }
+            }
+            ('/', Some('*')) => {
+                chars.next();
+                let mut previous = ' ';
+                for c in chars.by_ref() {
+                    if previous == '*' && c == '/' {
+                        break;
+                    }
+                    previous = c;
+                }
+                out.push(' ');
+            }
+            _ => out.push(c),
+        }
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_use_forward_and_import() {
+        let source = r#"
+            @use "sass:math";
+            @use 'theme/colors' as c;
+            @forward "mixins";
+            @import "reset", 'layout';
+            @import url("https://example.com/font.css");
+            // @use "commented";
+            /* @import "also-commented"; */
+            .a { content: "@use 'not-an-import'"; }
+        "#;
+
+        assert_eq!(
+            import_urls(source),
+            vec!["theme/colors", "mixins", "reset", "layout"]
+        );
+    }
+
+    #[test]
+    fn finds_unquoted_imports_in_indented_syntax() {
+        assert_eq!(
+            import_urls("@import base\n.a\n  color: red\n"),
+            vec!["base"]
+        );
+    }
+
+    #[test]
+    fn candidates_cover_partials_and_index_files() {
+        let paths = candidates(Path::new("/src/styles"), "../theme/colors");
+
+        assert!(paths.contains(&PathBuf::from("/src/theme/_colors.scss")));
+        assert!(paths.contains(&PathBuf::from("/src/theme/colors.sass")));
+        assert!(paths.contains(&PathBuf::from("/src/theme/colors/_index.scss")));
+    }
+
+    #[test]
+    fn candidates_keep_explicit_extensions() {
+        assert_eq!(
+            candidates(Path::new("/src"), "reset.css"),
+            vec![
+                PathBuf::from("/src/reset.css"),
+                PathBuf::from("/src/_reset.css")
+            ]
+        );
+    }
+}
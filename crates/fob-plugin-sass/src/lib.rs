@@ -0,0 +1,254 @@
+//! SCSS and Sass plugin for the fob bundler.
+//!
+//! Compiles `.scss` and `.sass` files with [grass](https://docs.rs/grass) in
+//! the `load` hook and hands the CSS to fob's built-in
+//! [`CssPlugin`] for minification and browser targets, so the result
+//! continues through the bundler as a CSS module.
+//!
+//! ```rust,no_run
+//! use fob_bundler::builtins::CssPluginOptions;
+//! use fob_bundler::runtime::BundlerRuntime;
+//! use fob_plugin_sass::{SassOptions, SassPlugin};
+//! use std::sync::Arc;
+//!
+//! let runtime = Arc::new(BundlerRuntime::new("."));
+//! let plugin = SassPlugin::with_options(
+//!     runtime,
+//!     SassOptions::new()
+//!         .with_load_path("node_modules")
+//!         .with_css(CssPluginOptions::new().with_minify(true)),
+//! );
+//! ```
+
+mod imports;
+
+use anyhow::Context;
+use fob_bundler::builtins::{CssPlugin, CssPluginOptions};
+use fob_bundler::{
+    HookLoadArgs, HookLoadOutput, HookLoadReturn, HookUsage, ModuleType, Plugin, PluginContext,
+    Runtime,
+};
+use path_clean::PathClean;
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// Configuration for [`SassPlugin`].
+#[derive(Debug, Clone)]
+pub struct SassOptions {
+    /// File extensions handled by the `load` hook (without the leading dot).
+    pub extensions: Vec<String>,
+    /// Directories searched for `@use` and `@import` URLs that are not
+    /// relative to the importing file, e.g. `node_modules`.
+    pub load_paths: Vec<PathBuf>,
+    /// Options of the CSS pass run on the compiled output.
+    pub css: CssPluginOptions,
+}
+
+impl Default for SassOptions {
+    fn default() -> Self {
+        Self {
+            extensions: vec!["scss".to_string(), "sass".to_string()],
+            load_paths: Vec::new(),
+            css: CssPluginOptions::default(),
+        }
+    }
+}
+
+impl SassOptions {
+    /// Create options with default values.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the file extensions handled by the `load` hook.
+    pub fn with_extensions(mut self, extensions: Vec<String>) -> Self {
+        self.extensions = extensions;
+        self
+    }
+
+    /// Add a directory searched for `@use` and `@import` URLs.
+    pub fn with_load_path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.load_paths.push(path.into());
+        self
+    }
+
+    /// Set the options of the CSS pass (minification, targets).
+    pub fn with_css(mut self, css: CssPluginOptions) -> Self {
+        self.css = css;
+        self
+    }
+}
+
+/// SCSS and Sass plugin.
+#[derive(Debug, Clone)]
+pub struct SassPlugin {
+    options: SassOptions,
+    runtime: Arc<dyn Runtime>,
+}
+
+impl SassPlugin {
+    /// Create a plugin with default options.
+    pub fn new(runtime: Arc<dyn Runtime>) -> Self {
+        Self::with_options(runtime, SassOptions::default())
+    }
+
+    /// Create a plugin with custom options.
+    pub fn with_options(runtime: Arc<dyn Runtime>, options: SassOptions) -> Self {
+        Self { options, runtime }
+    }
+
+    /// Access the plugin options.
+    pub fn options(&self) -> &SassOptions {
+        &self.options
+    }
+
+    /// Whether `id` is a Sass stylesheet (`.scss` or `.sass` by default).
+    pub fn handles(&self, id: &str) -> bool {
+        Path::new(id)
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .is_some_and(|ext| self.options.extensions.iter().any(|e| e == ext))
+    }
+
+    /// Load a stylesheet and compile it to CSS.
+    pub async fn load_module(&self, id: &str) -> anyhow::Result<Option<String>> {
+        if !self.handles(id) {
+            return Ok(None);
+        }
+
+        let path = self.resolve(Path::new(id));
+        let files = self.read_stylesheets(&path).await?;
+        let fs = MemoryFs { files };
+
+        let syntax = match path.extension().and_then(|ext| ext.to_str()) {
+            Some("sass") => grass::InputSyntax::Sass,
+            Some("css") => grass::InputSyntax::Css,
+            _ => grass::InputSyntax::Scss,
+        };
+        let load_paths: Vec<PathBuf> = self
+            .options
+            .load_paths
+            .iter()
+            .map(|dir| self.resolve(dir))
+            .collect();
+        let grass_options = grass::Options::default()
+            .fs(&fs)
+            .input_syntax(syntax)
+            .load_paths(&load_paths)
+            .style(grass::OutputStyle::Expanded);
+
+        let css = grass::from_path(&path, &grass_options)
+            .map_err(|e| anyhow::anyhow!("Failed to compile {}: {}", id, e))?;
+
+        let css_plugin =
+            CssPlugin::with_options(Arc::clone(&self.runtime), self.options.css.clone());
+        css_plugin.process_css(&path, css).map(Some)
+    }
+
+    /// Read the entry stylesheet and, transitively, every file it may load.
+    ///
+    /// grass reads synchronously, so the files are fetched from the runtime
+    /// up front and served to it from memory.
+    async fn read_stylesheets(&self, entry: &Path) -> anyhow::Result<HashMap<PathBuf, Vec<u8>>> {
+        let mut files = HashMap::new();
+        let mut pending = vec![entry.to_path_buf()];
+
+        while let Some(path) = pending.pop() {
+            if files.contains_key(&path) {
+                continue;
+            }
+            let bytes = self
+                .runtime
+                .read_file(&path)
+                .await
+                .with_context(|| format!("Failed to read {}", path.display()))?;
+
+            let urls = imports::import_urls(&String::from_utf8_lossy(&bytes));
+            let dir = path.parent().unwrap_or(Path::new(""));
+            for url in urls {
+                let bases = std::iter::once(dir.to_path_buf()).chain(
+                    self.options
+                        .load_paths
+                        .iter()
+                        .map(|load_path| self.resolve(load_path)),
+                );
+                for base in bases {
+                    for candidate in imports::candidates(&base, &url) {
+                        if !files.contains_key(&candidate) && self.runtime.exists(&candidate) {
+                            pending.push(candidate);
+                        }
+                    }
+                }
+            }
+
+            files.insert(path, bytes);
+        }
+
+        Ok(files)
+    }
+
+    /// Make a path absolute against the runtime's working directory.
+    fn resolve(&self, path: &Path) -> PathBuf {
+        if path.is_absolute() {
+            path.clean()
+        } else {
+            let cwd = self.runtime.get_cwd().unwrap_or_default();
+            cwd.join(path).clean()
+        }
+    }
+}
+
+impl Plugin for SassPlugin {
+    fn name(&self) -> Cow<'static, str> {
+        "fob-plugin-sass".into()
+    }
+
+    fn register_hook_usage(&self) -> HookUsage {
+        HookUsage::Load
+    }
+
+    fn load(
+        &self,
+        _ctx: &PluginContext,
+        args: &HookLoadArgs<'_>,
+    ) -> impl std::future::Future<Output = HookLoadReturn> + Send {
+        let plugin = self.clone();
+        let id = args.id.to_string();
+
+        async move {
+            Ok(plugin.load_module(&id).await?.map(|code| HookLoadOutput {
+                code: code.into(),
+                module_type: Some(ModuleType::Css),
+                ..Default::default()
+            }))
+        }
+    }
+}
+
+/// Files read from the runtime, served to grass.
+#[derive(Debug)]
+struct MemoryFs {
+    files: HashMap<PathBuf, Vec<u8>>,
+}
+
+impl grass::Fs for MemoryFs {
+    fn is_dir(&self, path: &Path) -> bool {
+        let path = path.clean();
+        self.files
+            .keys()
+            .any(|file| file != &path && file.starts_with(&path))
+    }
+
+    fn is_file(&self, path: &Path) -> bool {
+        self.files.contains_key(&path.clean())
+    }
+
+    fn read(&self, path: &Path) -> std::io::Result<Vec<u8>> {
+        self.files
+            .get(&path.clean())
+            .cloned()
+            .ok_or_else(|| std::io::Error::from(std::io::ErrorKind::NotFound))
+    }
+}
@@ -0,0 +1,124 @@
+//! Integration tests for fob-plugin-sass.
+
+use fob_bundler::builtins::CssPluginOptions;
+use fob_bundler::runtime::BundlerRuntime;
+use fob_plugin_sass::{SassOptions, SassPlugin};
+use std::sync::Arc;
+
+fn runtime(files: &[(&str, &str)]) -> Arc<BundlerRuntime> {
+    let runtime = BundlerRuntime::new("/project");
+    for (path, content) in files {
+        runtime.add_virtual_file(*path, content.as_bytes().to_vec());
+    }
+    Arc::new(runtime)
+}
+
+#[tokio::test]
+async fn compiles_scss_with_partials() {
+    let plugin = SassPlugin::new(runtime(&[
+        (
+            "/project/src/app.scss",
+            "@use 'theme/colors';\n.button { color: colors.$primary; &:hover { color: blue; } }\n",
+        ),
+        ("/project/src/theme/_colors.scss", "$primary: red;\n"),
+    ]));
+
+    let css = plugin
+        .load_module("/project/src/app.scss")
+        .await
+        .unwrap()
+        .expect("file should be handled");
+
+    assert!(css.contains(".button"));
+    assert!(css.contains("color: red"));
+    assert!(css.contains(".button:hover"));
+    assert!(!css.contains('$'));
+}
+
+#[tokio::test]
+async fn resolves_parent_and_load_paths() {
+    let plugin = SassPlugin::with_options(
+        runtime(&[
+            (
+                "/project/src/pages/home.scss",
+                "@use '../shared/mixins';\n@use 'vendor-grid' as grid;\n.home { @include mixins.center; width: grid.$width; }\n",
+            ),
+            (
+                "/project/src/shared/_mixins.scss",
+                "@mixin center { margin: 0 auto; }\n",
+            ),
+            (
+                "/project/node_modules/vendor-grid/_index.scss",
+                "$width: 960px;\n",
+            ),
+        ]),
+        SassOptions::new().with_load_path("node_modules"),
+    );
+
+    let css = plugin
+        .load_module("/project/src/pages/home.scss")
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert!(css.contains("margin: 0 auto"));
+    assert!(css.contains("width: 960px"));
+}
+
+#[tokio::test]
+async fn compiles_indented_syntax() {
+    let plugin = SassPlugin::new(runtime(&[(
+        "/project/src/app.sass",
+        "$gap: 4px\n\n.list\n  gap: $gap\n",
+    )]));
+
+    let css = plugin
+        .load_module("/project/src/app.sass")
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert!(css.contains(".list"));
+    assert!(css.contains("gap: 4px"));
+}
+
+#[tokio::test]
+async fn css_options_minify_output() {
+    let plugin = SassPlugin::with_options(
+        runtime(&[("/project/src/app.scss", ".a {\n  .b { color: red; }\n}\n")]),
+        SassOptions::new().with_css(CssPluginOptions::new().with_minify(true)),
+    );
+
+    let css = plugin
+        .load_module("/project/src/app.scss")
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(css, ".a .b{color:red}");
+}
+
+#[tokio::test]
+async fn skips_other_files() {
+    let plugin = SassPlugin::new(runtime(&[]));
+
+    assert!(
+        plugin
+            .load_module("/project/src/index.css")
+            .await
+            .unwrap()
+            .is_none()
+    );
+}
+
+#[tokio::test]
+async fn compile_errors_name_the_file() {
+    let plugin = SassPlugin::new(runtime(&[("/project/src/app.scss", "@use 'missing';\n")]));
+
+    let error = plugin
+        .load_module("/project/src/app.scss")
+        .await
+        .unwrap_err();
+
+    assert!(error.to_string().contains("/project/src/app.scss"));
+}
@@ -0,0 +1,155 @@
+//! Solid plugin for the fob bundler.
+//!
+//! The bundler compiles JSX for React's automatic runtime, turning
+//! `<p>{count()}</p>` into `jsx("p", { children: count() })`. That reads the
+//! signal once, when the element is created, so the DOM never updates. This
+//! plugin compiles `.jsx` and `.tsx` modules the way Solid's own compiler
+//! does instead, before the bundler's JSX transform sees them:
+//!
+//! - native elements are cloned from `<template>`s, with dynamic attributes
+//!   and children wired up in effects
+//! - components are created with `createComponent`, and dynamic props become
+//!   getters so they stay reactive
+//! - events on common types are delegated to the document
+//!
+//! ```rust,no_run
+//! use fob_plugin_solid::{SolidOptions, SolidPlugin};
+//!
+//! let plugin = SolidPlugin::with_options(SolidOptions::new().with_delegate_events(false));
+//! ```
+//!
+//! Server rendering and hydration output are not supported; the generated
+//! code targets `solid-js/web` in the browser.
+
+mod transform;
+
+use fob_bundler::{
+    HookTransformArgs, HookTransformOutput, HookTransformReturn, HookUsage, ModuleType, Plugin,
+    SharedTransformPluginContext,
+};
+use std::borrow::Cow;
+use std::path::Path;
+
+/// Configuration for [`SolidPlugin`].
+#[derive(Debug, Clone)]
+pub struct SolidOptions {
+    /// File extensions compiled by the plugin (without the leading dot).
+    pub extensions: Vec<String>,
+    /// Module the generated code imports its runtime helpers from
+    /// (default: `solid-js/web`).
+    pub module_name: String,
+    /// Delegate common events to the document instead of adding a listener
+    /// per element (default: true).
+    pub delegate_events: bool,
+}
+
+impl Default for SolidOptions {
+    fn default() -> Self {
+        Self {
+            extensions: vec!["jsx".to_string(), "tsx".to_string()],
+            module_name: "solid-js/web".to_string(),
+            delegate_events: true,
+        }
+    }
+}
+
+impl SolidOptions {
+    /// Create options with default values.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the file extensions compiled by the plugin.
+    pub fn with_extensions(mut self, extensions: Vec<String>) -> Self {
+        self.extensions = extensions;
+        self
+    }
+
+    /// Set the module runtime helpers are imported from.
+    pub fn with_module_name(mut self, module_name: impl Into<String>) -> Self {
+        self.module_name = module_name.into();
+        self
+    }
+
+    /// Enable or disable event delegation.
+    pub fn with_delegate_events(mut self, enabled: bool) -> Self {
+        self.delegate_events = enabled;
+        self
+    }
+}
+
+/// Solid JSX plugin.
+#[derive(Debug, Clone, Default)]
+pub struct SolidPlugin {
+    options: SolidOptions,
+}
+
+impl SolidPlugin {
+    /// Create a plugin with default options.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a plugin with custom options.
+    pub fn with_options(options: SolidOptions) -> Self {
+        Self { options }
+    }
+
+    /// Access the plugin options.
+    pub fn options(&self) -> &SolidOptions {
+        &self.options
+    }
+
+    /// Whether the `transform` hook should compile this module id.
+    pub fn handles(&self, id: &str) -> bool {
+        let path = id.split('?').next().unwrap_or(id);
+        Path::new(path)
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .is_some_and(|ext| self.options.extensions.iter().any(|e| e == ext))
+    }
+
+    /// Compile the JSX of a module.
+    ///
+    /// Returns `None` for ids this plugin does not handle, modules without
+    /// JSX, and modules that fail to parse, which the bundler reports.
+    pub fn transform_code(&self, id: &str, code: &str) -> Option<String> {
+        if !self.handles(id) {
+            return None;
+        }
+        let path = id.split('?').next().unwrap_or(id);
+        transform::compile(code, Path::new(path), &self.options)
+    }
+}
+
+impl Plugin for SolidPlugin {
+    fn name(&self) -> Cow<'static, str> {
+        "fob-plugin-solid".into()
+    }
+
+    fn register_hook_usage(&self) -> HookUsage {
+        HookUsage::Transform
+    }
+
+    fn transform(
+        &self,
+        _ctx: SharedTransformPluginContext,
+        args: &HookTransformArgs<'_>,
+    ) -> impl std::future::Future<Output = HookTransformReturn> + Send {
+        let result = self.transform_code(args.id, args.code);
+        // The JSX is gone, but TypeScript still needs stripping
+        let module_type = match args.module_type {
+            ModuleType::Ts | ModuleType::Tsx => ModuleType::Ts,
+            _ => ModuleType::Js,
+        };
+
+        async move {
+            Ok(result.map(|code| HookTransformOutput {
+                code: Some(code),
+                map: None,
+                side_effects: None,
+                module_type: Some(module_type),
+            }))
+        }
+    }
+}
@@ -0,0 +1,1010 @@
+//! Solid's JSX compilation, after `babel-plugin-jsx-dom-expressions`.
+//!
+//! Native elements become cloned `<template>`s whose dynamic parts are wired
+//! up with `solid-js/web` helpers, and components become `createComponent`
+//! calls whose dynamic props are getters. Reads of signals and props thus
+//! happen inside Solid's tracking scopes rather than once, when the JSX is
+//! evaluated, which is what the React runtime's `jsx()` calls would do.
+//!
+//! Only the JSX is rewritten: every other byte of the module is copied
+//! as-is, so TypeScript stays in place for the bundler's own transform.
+
+use crate::SolidOptions;
+use fob_bundler::oxc::ast::*;
+use fob_bundler::oxc::{Allocator, GetSpan, Parser, ScopeFlags, SourceType, Span, Visit};
+use std::collections::{BTreeSet, HashMap};
+use std::fmt::Write;
+use std::path::Path;
+
+/// Events Solid delegates to the document instead of listening on each
+/// element.
+const DELEGATED_EVENTS: &[&str] = &[
+    "beforeinput",
+    "click",
+    "dblclick",
+    "contextmenu",
+    "focusin",
+    "focusout",
+    "input",
+    "keydown",
+    "keyup",
+    "mousedown",
+    "mousemove",
+    "mouseout",
+    "mouseover",
+    "mouseup",
+    "pointerdown",
+    "pointermove",
+    "pointerout",
+    "pointerover",
+    "pointerup",
+    "touchend",
+    "touchmove",
+    "touchstart",
+];
+
+/// Attributes set as DOM properties rather than with `setAttribute`.
+const PROPERTIES: &[&str] = &[
+    "value",
+    "checked",
+    "selected",
+    "muted",
+    "multiple",
+    "innerHTML",
+    "textContent",
+    "innerText",
+];
+
+const VOID_ELEMENTS: &[&str] = &[
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "param", "source",
+    "track", "wbr",
+];
+
+/// Elements that only exist inside `<svg>`; templates rooted at one must be
+/// parsed in the SVG namespace.
+const SVG_ELEMENTS: &[&str] = &[
+    "circle",
+    "clipPath",
+    "defs",
+    "ellipse",
+    "foreignObject",
+    "g",
+    "line",
+    "linearGradient",
+    "marker",
+    "mask",
+    "path",
+    "pattern",
+    "polygon",
+    "polyline",
+    "radialGradient",
+    "rect",
+    "stop",
+    "symbol",
+    "textPath",
+    "tspan",
+    "use",
+];
+
+/// Compile the JSX of a module.
+///
+/// Returns `None` when the module has no JSX, or does not parse; the
+/// bundler reports syntax errors itself.
+pub fn compile(source: &str, path: &Path, options: &SolidOptions) -> Option<String> {
+    let source_type = SourceType::from_path(path).ok()?;
+    let allocator = Allocator::default();
+    let parsed = Parser::new(&allocator, source, source_type).parse();
+    if !parsed.errors.is_empty() {
+        return None;
+    }
+
+    let mut compiler = Compiler {
+        source,
+        options,
+        helpers: BTreeSet::new(),
+        templates: Vec::new(),
+        events: BTreeSet::new(),
+        counters: HashMap::new(),
+    };
+    let mut replacer = JsxReplacer {
+        compiler: &mut compiler,
+        edits: Vec::new(),
+    };
+    replacer.visit_program(&parsed.program);
+    let mut edits = replacer.edits;
+    if edits.is_empty() {
+        return None;
+    }
+
+    let mut footer = String::new();
+    if !compiler.events.is_empty() {
+        let delegate = compiler.helper("delegateEvents");
+        let events: Vec<String> = compiler.events.iter().map(|e| js_string(e)).collect();
+        let _ = write!(footer, "\n{}([{}]);\n", delegate, events.join(", "));
+    }
+
+    let mut header = String::new();
+    let imports: Vec<String> = compiler
+        .helpers
+        .iter()
+        .map(|name| format!("{} as _${}", name, name))
+        .collect();
+    let _ = writeln!(
+        header,
+        "import {{ {} }} from {};",
+        imports.join(", "),
+        js_string(&options.module_name)
+    );
+    for (name, call) in &compiler.templates {
+        let _ = writeln!(header, "const {} = /*#__PURE__*/ {};", name, call);
+    }
+
+    // Directives and hashbangs must stay first
+    let program = &parsed.program;
+    let header_at = program
+        .directives
+        .last()
+        .map(|directive| directive.span.end)
+        .or_else(|| program.hashbang.as_ref().map(|hashbang| hashbang.span.end))
+        .unwrap_or(0);
+    if header_at > 0 {
+        header.insert(0, '\n');
+    }
+    edits.push((Span::new(header_at, header_at), header));
+
+    let mut code = splice(source, 0, edits);
+    code.push_str(&footer);
+    Some(code)
+}
+
+/// Module-wide state of a compilation.
+struct Compiler<'s> {
+    source: &'s str,
+    options: &'s SolidOptions,
+    /// `solid-js/web` exports used by the generated code
+    helpers: BTreeSet<&'static str>,
+    /// Template variables with their `template(...)` calls
+    templates: Vec<(String, String)>,
+    /// Delegated event names
+    events: BTreeSet<String>,
+    counters: HashMap<&'static str, usize>,
+}
+
+/// A JSX child after whitespace cleanup.
+enum Child<'n, 'a> {
+    /// Text as written, with entities still encoded
+    Text(String),
+    Element(&'n JSXElement<'a>),
+    Fragment(&'n JSXFragment<'a>),
+    Expression(&'n Expression<'a>),
+}
+
+impl Compiler<'_> {
+    /// Fresh variable name: `_el$`, `_el$2`, `_el$3`, ...
+    fn uid(&mut self, base: &'static str) -> String {
+        let count = self.counters.entry(base).or_insert(0);
+        *count += 1;
+        match *count {
+            1 => format!("_{}$", base),
+            n => format!("_{}${}", base, n),
+        }
+    }
+
+    /// Local name of a `solid-js/web` export.
+    fn helper(&mut self, name: &'static str) -> String {
+        self.helpers.insert(name);
+        format!("_${}", name)
+    }
+
+    fn text(&self, span: Span) -> &str {
+        &self.source[span.start as usize..span.end as usize]
+    }
+
+    /// Source of an expression with its JSX compiled.
+    fn code(&mut self, expression: &Expression<'_>) -> String {
+        let span = expression.span();
+        let mut replacer = JsxReplacer {
+            compiler: self,
+            edits: Vec::new(),
+        };
+        replacer.visit_expression(expression);
+        let edits = replacer.edits;
+        splice(self.text(span), span.start, edits)
+    }
+
+    /// Variable holding a template, reusing identical ones.
+    fn template(&mut self, html: String, svg: bool) -> String {
+        let template = self.helper("template");
+        let call = if svg {
+            format!("{}({}, false, true)", template, js_string(&html))
+        } else {
+            format!("{}({})", template, js_string(&html))
+        };
+        if let Some((name, _)) = self.templates.iter().find(|(_, c)| *c == call) {
+            return name.clone();
+        }
+        let name = self.uid("tmpl");
+        self.templates.push((name.clone(), call));
+        name
+    }
+
+    fn element(&mut self, element: &JSXElement<'_>) -> String {
+        if is_component(&element.opening_element.name) {
+            return self.component(element);
+        }
+
+        let tag = self.text(element.opening_element.name.span()).to_string();
+        let root = self.uid("el");
+        let mut html = String::new();
+        let mut declarations = Vec::new();
+        let mut statements = Vec::new();
+        self.native(
+            element,
+            &root,
+            &mut html,
+            &mut declarations,
+            &mut statements,
+        );
+
+        let template = self.template(html, SVG_ELEMENTS.contains(&tag.as_str()));
+        if declarations.is_empty() && statements.is_empty() {
+            return format!("{}()", template);
+        }
+
+        let mut code = format!("(() => {{\n  const {} = {}()", root, template);
+        for declaration in &declarations {
+            let _ = write!(code, ",\n    {}", declaration);
+        }
+        code.push_str(";\n");
+        for statement in &statements {
+            let _ = writeln!(code, "  {}", statement);
+        }
+        let _ = write!(code, "  return {};\n}})()", root);
+        code
+    }
+
+    /// Append a native element to a template, collecting the declarations
+    /// that walk to its dynamic nodes and the statements that fill them in.
+    fn native(
+        &mut self,
+        element: &JSXElement<'_>,
+        var: &str,
+        html: &mut String,
+        declarations: &mut Vec<String>,
+        statements: &mut Vec<String>,
+    ) {
+        let tag = self.text(element.opening_element.name.span()).to_string();
+        let svg = tag == "svg" || SVG_ELEMENTS.contains(&tag.as_str());
+        let _ = write!(html, "<{}", tag);
+        for item in &element.opening_element.attributes {
+            match item {
+                JSXAttributeItem::SpreadAttribute(spread) => {
+                    let helper = self.helper("spread");
+                    let props = self.code(&spread.argument);
+                    statements.push(format!("{}({}, {}, {}, true);", helper, var, props, svg));
+                }
+                JSXAttributeItem::Attribute(attribute) => {
+                    self.attribute(attribute, var, html, statements);
+                }
+            }
+        }
+        html.push('>');
+        if VOID_ELEMENTS.contains(&tag.as_str()) {
+            return;
+        }
+
+        let children = children(&element.children);
+        // A lone dynamic child is appended to the element, without a marker
+        let lone = children.len() == 1 && !is_static_child(&children[0]);
+        let needs_ref: Vec<bool> = children
+            .iter()
+            .map(|child| match child {
+                Child::Text(_) => false,
+                Child::Element(element) if !is_component(&element.opening_element.name) => {
+                    element_needs_ref(element)
+                }
+                _ => !lone,
+            })
+            .collect();
+        let last = needs_ref.iter().rposition(|needed| *needed);
+
+        let mut previous: Option<String> = None;
+        for (index, child) in children.iter().enumerate() {
+            let child_var = if last.is_some_and(|last| index <= last) {
+                let child_var = self.uid("el");
+                let path = match &previous {
+                    None => format!("{}.firstChild", var),
+                    Some(previous) => format!("{}.nextSibling", previous),
+                };
+                declarations.push(format!("{} = {}", child_var, path));
+                previous = Some(child_var.clone());
+                Some(child_var)
+            } else {
+                None
+            };
+
+            match child {
+                Child::Text(text) => html.push_str(text),
+                Child::Element(element) if !is_component(&element.opening_element.name) => {
+                    // Without a variable the subtree is static, so nothing
+                    // refers to it
+                    let child_var = child_var.unwrap_or_default();
+                    self.native(element, &child_var, html, declarations, statements);
+                }
+                child => {
+                    let insert = self.helper("insert");
+                    let value = match child {
+                        Child::Expression(expression) if is_dynamic(expression) => {
+                            format!("() => {}", self.code(expression))
+                        }
+                        Child::Expression(expression) => self.code(expression),
+                        Child::Element(element) => self.component(element),
+                        Child::Fragment(fragment) => self.fragment(fragment),
+                        Child::Text(_) => unreachable!("text is part of the template"),
+                    };
+                    match child_var {
+                        Some(marker) => {
+                            html.push_str("<!>");
+                            statements.push(format!("{}({}, {}, {});", insert, var, value, marker));
+                        }
+                        None => statements.push(format!("{}({}, {});", insert, var, value)),
+                    }
+                }
+            }
+        }
+
+        let _ = write!(html, "</{}>", tag);
+    }
+
+    /// Add an attribute to the template, or the statement setting it.
+    fn attribute(
+        &mut self,
+        attribute: &JSXAttribute<'_>,
+        var: &str,
+        html: &mut String,
+        statements: &mut Vec<String>,
+    ) {
+        let name = attribute_name(&attribute.name);
+        let templated = is_template_attribute(&name);
+        let html_name = match name.as_str() {
+            "className" => "class",
+            "htmlFor" => "for",
+            name => name.strip_prefix("attr:").unwrap_or(name),
+        };
+
+        let (code, dynamic) = match &attribute.value {
+            None if templated => {
+                let _ = write!(html, " {}", html_name);
+                return;
+            }
+            None => ("true".to_string(), false),
+            Some(JSXAttributeValue::StringLiteral(literal)) => {
+                let raw = self.text(literal.span);
+                let value = &raw[1..raw.len() - 1];
+                if templated {
+                    let _ = write!(html, " {}=\"{}\"", html_name, value.replace('"', "&quot;"));
+                    return;
+                }
+                (js_string(&decode_entities(value)), false)
+            }
+            Some(JSXAttributeValue::Element(element)) => (self.element(element), false),
+            Some(JSXAttributeValue::Fragment(fragment)) => (self.fragment(fragment), false),
+            Some(JSXAttributeValue::ExpressionContainer(container)) => {
+                let Some(expression) = container.expression.as_expression() else {
+                    return;
+                };
+                if let Some(value) = static_value(expression).filter(|_| templated) {
+                    let _ = write!(html, " {}=\"{}\"", html_name, escape_attribute(&value));
+                    return;
+                }
+                (self.code(expression), is_dynamic(expression))
+            }
+        };
+
+        let statement = if name == "ref" {
+            let expression = match &attribute.value {
+                Some(JSXAttributeValue::ExpressionContainer(container)) => {
+                    container.expression.as_expression()
+                }
+                _ => None,
+            };
+            if expression.is_some_and(is_assignable) {
+                let use_ = self.helper("use");
+                let ref_var = self.uid("ref");
+                format!(
+                    "const {ref_var} = {code}; typeof {ref_var} === \"function\" ? {use_}({ref_var}, {var}) : {code} = {var};"
+                )
+            } else {
+                format!("{}({}, {});", self.helper("use"), code, var)
+            }
+        } else if let Some(event) = name.strip_prefix("on:") {
+            format!("{}.addEventListener({}, {});", var, js_string(event), code)
+        } else if let Some(event) = name.strip_prefix("oncapture:") {
+            format!(
+                "{}.addEventListener({}, {}, true);",
+                var,
+                js_string(event),
+                code
+            )
+        } else if let Some(event) = event_name(&name) {
+            if self.options.delegate_events && DELEGATED_EVENTS.contains(&event.as_str()) {
+                let statement = format!("{}.$${} = {};", var, event, code);
+                self.events.insert(event);
+                statement
+            } else {
+                format!("{}.addEventListener({}, {});", var, js_string(&event), code)
+            }
+        } else if let Some(directive) = name.strip_prefix("use:") {
+            format!(
+                "{}({}, {}, () => {});",
+                self.helper("use"),
+                directive,
+                var,
+                code
+            )
+        } else {
+            let update = if let Some(property) = name.strip_prefix("prop:") {
+                format!("{}.{} = {}", var, property, code)
+            } else if PROPERTIES.contains(&name.as_str()) {
+                format!("{}.{} = {}", var, name, code)
+            } else if name == "style" || name == "classList" {
+                let helper = self.helper(if name == "style" {
+                    "style"
+                } else {
+                    "classList"
+                });
+                if dynamic {
+                    let effect = self.helper("effect");
+                    statements.push(format!(
+                        "{}(_p$ => {}({}, {}, _p$));",
+                        effect, helper, var, code
+                    ));
+                    return;
+                }
+                format!("{}({}, {})", helper, var, code)
+            } else if name == "class" || name == "className" {
+                format!("{}({}, {})", self.helper("className"), var, code)
+            } else {
+                let attribute = name.strip_prefix("attr:").unwrap_or(&name);
+                format!(
+                    "{}({}, {}, {})",
+                    self.helper("setAttribute"),
+                    var,
+                    js_string(attribute),
+                    code
+                )
+            };
+            if dynamic {
+                format!("{}(() => {});", self.helper("effect"), update)
+            } else {
+                format!("{};", update)
+            }
+        };
+        statements.push(statement);
+    }
+
+    fn component(&mut self, element: &JSXElement<'_>) -> String {
+        let tag = self.text(element.opening_element.name.span()).to_string();
+        let mut sources = Vec::new();
+        let mut props = Vec::new();
+
+        for item in &element.opening_element.attributes {
+            let attribute = match item {
+                JSXAttributeItem::SpreadAttribute(spread) => {
+                    if !props.is_empty() {
+                        sources.push(object(std::mem::take(&mut props)));
+                    }
+                    sources.push(self.code(&spread.argument));
+                    continue;
+                }
+                JSXAttributeItem::Attribute(attribute) => attribute,
+            };
+
+            let name = attribute_name(&attribute.name);
+            let key = property_key(&name);
+            match &attribute.value {
+                None => props.push(format!("{}: true", key)),
+                Some(JSXAttributeValue::StringLiteral(literal)) => {
+                    let raw = self.text(literal.span);
+                    let value = decode_entities(&raw[1..raw.len() - 1]);
+                    props.push(format!("{}: {}", key, js_string(&value)));
+                }
+                Some(JSXAttributeValue::Element(element)) => {
+                    let code = self.element(element);
+                    props.push(getter(&key, &code));
+                }
+                Some(JSXAttributeValue::Fragment(fragment)) => {
+                    let code = self.fragment(fragment);
+                    props.push(getter(&key, &code));
+                }
+                Some(JSXAttributeValue::ExpressionContainer(container)) => {
+                    let Some(expression) = container.expression.as_expression() else {
+                        continue;
+                    };
+                    let code = self.code(expression);
+                    if name == "ref" && is_assignable(expression) {
+                        props.push(format!(
+                            "ref(r$) {{ const _ref$ = {code}; typeof _ref$ === \"function\" ? _ref$(r$) : {code} = r$; }}"
+                        ));
+                    } else if is_dynamic_prop(expression) {
+                        props.push(getter(&key, &code));
+                    } else {
+                        props.push(format!("{}: {}", key, code));
+                    }
+                }
+            }
+        }
+
+        let children = children(&element.children);
+        match children.as_slice() {
+            [] => {}
+            [Child::Text(text)] => {
+                props.push(format!("children: {}", js_string(&decode_entities(text))));
+            }
+            [Child::Expression(expression)] if !is_dynamic_prop(expression) => {
+                let code = self.code(expression);
+                props.push(format!("children: {}", code));
+            }
+            [Child::Expression(expression)] => {
+                let code = self.code(expression);
+                props.push(getter("children", &code));
+            }
+            [child] => {
+                let code = self.child(child);
+                props.push(getter("children", &code));
+            }
+            children => {
+                let codes: Vec<String> = children.iter().map(|child| self.child(child)).collect();
+                props.push(getter("children", &format!("[{}]", codes.join(", "))));
+            }
+        }
+
+        if !props.is_empty() || sources.is_empty() {
+            sources.push(object(props));
+        }
+        let props = match sources.len() {
+            1 => sources.remove(0),
+            _ => format!("{}({})", self.helper("mergeProps"), sources.join(", ")),
+        };
+        format!("{}({}, {})", self.helper("createComponent"), tag, props)
+    }
+
+    fn fragment(&mut self, fragment: &JSXFragment<'_>) -> String {
+        let children = children(&fragment.children);
+        match children.as_slice() {
+            [child] => self.child(child),
+            children => {
+                let codes: Vec<String> = children.iter().map(|child| self.child(child)).collect();
+                format!("[{}]", codes.join(", "))
+            }
+        }
+    }
+
+    /// A child as a value of its own, as in fragments and component
+    /// children; dynamic expressions are memoized.
+    fn child(&mut self, child: &Child<'_, '_>) -> String {
+        match child {
+            Child::Text(text) => js_string(&decode_entities(text)),
+            Child::Element(element) => self.element(element),
+            Child::Fragment(fragment) => self.fragment(fragment),
+            Child::Expression(expression) if is_dynamic(expression) => {
+                let code = self.code(expression);
+                format!("{}(() => {})", self.helper("memo"), code)
+            }
+            Child::Expression(expression) => self.code(expression),
+        }
+    }
+}
+
+/// Compiles the outermost JSX nodes it visits, recording the replacements.
+struct JsxReplacer<'c, 's> {
+    compiler: &'c mut Compiler<'s>,
+    edits: Vec<(Span, String)>,
+}
+
+impl<'a> Visit<'a> for JsxReplacer<'_, '_> {
+    fn visit_jsx_element(&mut self, it: &JSXElement<'a>) {
+        let code = self.compiler.element(it);
+        self.edits.push((it.span, code));
+    }
+
+    fn visit_jsx_fragment(&mut self, it: &JSXFragment<'a>) {
+        let code = self.compiler.fragment(it);
+        self.edits.push((it.span, code));
+    }
+}
+
+/// Whether an expression may read reactive state when evaluated: Solid
+/// treats calls and member accesses as potential signal or prop reads.
+/// Function bodies don't count, as they run later.
+fn is_dynamic(expression: &Expression<'_>) -> bool {
+    let mut check = DynamicCheck {
+        dynamic: false,
+        tags: false,
+    };
+    check.visit_expression(expression);
+    check.dynamic
+}
+
+/// Whether a component prop needs a getter: besides dynamic expressions,
+/// JSX is created lazily, when the component reads the prop.
+fn is_dynamic_prop(expression: &Expression<'_>) -> bool {
+    let mut check = DynamicCheck {
+        dynamic: false,
+        tags: true,
+    };
+    check.visit_expression(expression);
+    check.dynamic
+}
+
+struct DynamicCheck {
+    dynamic: bool,
+    /// Whether JSX counts as dynamic
+    tags: bool,
+}
+
+impl<'a> Visit<'a> for DynamicCheck {
+    fn visit_call_expression(&mut self, _: &CallExpression<'a>) {
+        self.dynamic = true;
+    }
+
+    fn visit_tagged_template_expression(&mut self, _: &TaggedTemplateExpression<'a>) {
+        self.dynamic = true;
+    }
+
+    fn visit_member_expression(&mut self, _: &MemberExpression<'a>) {
+        self.dynamic = true;
+    }
+
+    fn visit_function(&mut self, _: &Function<'a>, _: ScopeFlags) {}
+
+    fn visit_arrow_function_expression(&mut self, _: &ArrowFunctionExpression<'a>) {}
+
+    fn visit_jsx_element(&mut self, _: &JSXElement<'a>) {
+        self.dynamic |= self.tags;
+    }
+
+    fn visit_jsx_fragment(&mut self, _: &JSXFragment<'a>) {
+        self.dynamic |= self.tags;
+    }
+}
+
+fn is_component(name: &JSXElementName<'_>) -> bool {
+    !matches!(
+        name,
+        JSXElementName::Identifier(_) | JSXElementName::NamespacedName(_)
+    )
+}
+
+/// Attribute name as written, with its namespace.
+fn attribute_name(name: &JSXAttributeName<'_>) -> String {
+    match name {
+        JSXAttributeName::Identifier(identifier) => identifier.name.to_string(),
+        JSXAttributeName::NamespacedName(name) => name.to_string(),
+    }
+}
+
+/// Whether a static value of this attribute can be written into the
+/// template, rather than needing a statement.
+fn is_template_attribute(name: &str) -> bool {
+    name != "ref"
+        && event_name(name).is_none()
+        && !["on:", "oncapture:", "use:", "prop:"]
+            .iter()
+            .any(|prefix| name.starts_with(prefix))
+}
+
+fn is_assignable(expression: &Expression<'_>) -> bool {
+    matches!(expression, Expression::Identifier(_)) || expression.is_member_expression()
+}
+
+/// Whether a child is written into its parent's template.
+fn is_static_child(child: &Child<'_, '_>) -> bool {
+    match child {
+        Child::Text(_) => true,
+        Child::Element(element) => !is_component(&element.opening_element.name),
+        Child::Fragment(_) | Child::Expression(_) => false,
+    }
+}
+
+/// Whether any statement needs a variable for this native element or one
+/// of its descendants.
+fn element_needs_ref(element: &JSXElement<'_>) -> bool {
+    let dynamic_attribute = element
+        .opening_element
+        .attributes
+        .iter()
+        .any(|item| match item {
+            JSXAttributeItem::SpreadAttribute(_) => true,
+            JSXAttributeItem::Attribute(attribute) => match &attribute.value {
+                _ if !is_template_attribute(&attribute_name(&attribute.name)) => true,
+                None | Some(JSXAttributeValue::StringLiteral(_)) => false,
+                Some(JSXAttributeValue::ExpressionContainer(container)) => container
+                    .expression
+                    .as_expression()
+                    .is_some_and(|expression| static_value(expression).is_none()),
+                Some(_) => true,
+            },
+        });
+    dynamic_attribute
+        || children(&element.children).iter().any(|child| match child {
+            Child::Text(_) => false,
+            Child::Element(element) if !is_component(&element.opening_element.name) => {
+                element_needs_ref(element)
+            }
+            _ => true,
+        })
+}
+
+/// Children without formatting whitespace and empty expressions.
+fn children<'n, 'a>(children: &'n [JSXChild<'a>]) -> Vec<Child<'n, 'a>> {
+    children
+        .iter()
+        .filter_map(|child| match child {
+            JSXChild::Text(text) => {
+                let text = clean_text(&text.value);
+                (!text.is_empty()).then_some(Child::Text(text))
+            }
+            JSXChild::Element(element) => Some(Child::Element(element)),
+            JSXChild::Fragment(fragment) => Some(Child::Fragment(fragment)),
+            JSXChild::ExpressionContainer(container) => {
+                container.expression.as_expression().map(Child::Expression)
+            }
+            JSXChild::Spread(spread) => Some(Child::Expression(&spread.expression)),
+        })
+        .collect()
+}
+
+/// JSX whitespace rules: lines are trimmed, whitespace-only lines dropped
+/// and the remaining lines joined with single spaces.
+fn clean_text(text: &str) -> String {
+    let lines: Vec<&str> = text
+        .split('\n')
+        .map(|line| line.strip_suffix('\r').unwrap_or(line))
+        .collect();
+    let last_non_empty = lines
+        .iter()
+        .rposition(|line| !line.trim().is_empty())
+        .unwrap_or(0);
+    let mut cleaned = String::new();
+    for (index, line) in lines.iter().enumerate() {
+        let mut line = line.replace('\t', " ");
+        if index != 0 {
+            line = line.trim_start().to_string();
+        }
+        if index != lines.len() - 1 {
+            line = line.trim_end().to_string();
+        }
+        if line.is_empty() {
+            continue;
+        }
+        cleaned.push_str(&line);
+        if index != last_non_empty {
+            cleaned.push(' ');
+        }
+    }
+    cleaned
+}
+
+/// Value of a string or number literal, for the template.
+fn static_value(expression: &Expression<'_>) -> Option<String> {
+    match expression {
+        Expression::StringLiteral(literal) => Some(literal.value.to_string()),
+        Expression::NumericLiteral(literal) => Some(literal.value.to_string()),
+        _ => None,
+    }
+}
+
+/// Lowercase event name of an `onEvent` attribute.
+fn event_name(name: &str) -> Option<String> {
+    let rest = name.strip_prefix("on")?;
+    rest.starts_with(|c: char| c.is_ascii_uppercase())
+        .then(|| rest.to_ascii_lowercase())
+}
+
+fn property_key(name: &str) -> String {
+    let identifier = name
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$');
+    if identifier {
+        name.to_string()
+    } else {
+        js_string(name)
+    }
+}
+
+fn getter(key: &str, code: &str) -> String {
+    format!("get {}() {{ return {}; }}", key, code)
+}
+
+fn object(props: Vec<String>) -> String {
+    if props.is_empty() {
+        "{}".to_string()
+    } else {
+        format!("{{ {} }}", props.join(", "))
+    }
+}
+
+/// Apply replacements to a slice of source starting at `offset`.
+fn splice(source: &str, offset: u32, mut edits: Vec<(Span, String)>) -> String {
+    edits.sort_by_key(|(span, _)| (span.start, span.end));
+    let mut code = String::with_capacity(source.len());
+    let mut position = 0;
+    for (span, replacement) in edits {
+        let start = (span.start - offset) as usize;
+        code.push_str(&source[position..start]);
+        code.push_str(&replacement);
+        position = (span.end - offset) as usize;
+    }
+    code.push_str(&source[position..]);
+    code
+}
+
+fn escape_attribute(value: &str) -> String {
+    value.replace('&', "&amp;").replace('"', "&quot;")
+}
+
+/// Decode the HTML entities JSX allows in text and attribute strings.
+fn decode_entities(text: &str) -> String {
+    let mut decoded = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('&') {
+        decoded.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let entity = rest
+            .find(';')
+            .filter(|end| *end <= 10)
+            .and_then(|end| decode_entity(&rest[1..end]).map(|c| (c, end)));
+        match entity {
+            Some((c, end)) => {
+                decoded.push(c);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                decoded.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    decoded.push_str(rest);
+    decoded
+}
+
+fn decode_entity(entity: &str) -> Option<char> {
+    let code = match entity {
+        "amp" => '&',
+        "lt" => '<',
+        "gt" => '>',
+        "quot" => '"',
+        "apos" => '\'',
+        "nbsp" => '\u{a0}',
+        _ => {
+            let number = entity.strip_prefix('#')?;
+            let code = match number.strip_prefix(['x', 'X']) {
+                Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+                None => number.parse().ok()?,
+            };
+            return char::from_u32(code);
+        }
+    };
+    Some(code)
+}
+
+/// Double-quoted JavaScript string literal.
+fn js_string(value: &str) -> String {
+    let mut literal = String::with_capacity(value.len() + 2);
+    literal.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => literal.push_str("\\\""),
+            '\\' => literal.push_str("\\\\"),
+            '\n' => literal.push_str("\\n"),
+            '\r' => literal.push_str("\\r"),
+            '\u{2028}' => literal.push_str("\\u2028"),
+            '\u{2029}' => literal.push_str("\\u2029"),
+            c => literal.push(c),
+        }
+    }
+    literal.push('"');
+    literal
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn compile_jsx(source: &str) -> String {
+        let code = compile(source, Path::new("app.jsx"), &SolidOptions::default()).unwrap();
+        // The output must be valid JavaScript
+        let allocator = Allocator::default();
+        let parsed = Parser::new(&allocator, &code, SourceType::mjs()).parse();
+        assert!(parsed.errors.is_empty(), "invalid output:\n{}", code);
+        code
+    }
+
+    #[test]
+    fn compiles_static_templates() {
+        assert_eq!(
+            compile_jsx("const el = <div class=\"card\">\n  Hello <b>world</b>\n</div>;"),
+            "import { template as _$template } from \"solid-js/web\";\n\
+             const _tmpl$ = /*#__PURE__*/ _$template(\"<div class=\\\"card\\\">Hello <b>world</b></div>\");\n\
+             const el = _tmpl$();"
+        );
+    }
+
+    #[test]
+    fn wires_dynamic_parts() {
+        assert_eq!(
+            compile_jsx(
+                "const el = <button title={label()} onClick={increment}>Count: {count()}!</button>;"
+            ),
+            "import { delegateEvents as _$delegateEvents, effect as _$effect, insert as _$insert, setAttribute as _$setAttribute, template as _$template } from \"solid-js/web\";\n\
+             const _tmpl$ = /*#__PURE__*/ _$template(\"<button>Count: <!>!</button>\");\n\
+             const el = (() => {\n  \
+               const _el$ = _tmpl$(),\n    \
+                 _el$2 = _el$.firstChild,\n    \
+                 _el$3 = _el$2.nextSibling;\n  \
+               _$effect(() => _$setAttribute(_el$, \"title\", label()));\n  \
+               _el$.$$click = increment;\n  \
+               _$insert(_el$, () => count(), _el$3);\n  \
+               return _el$;\n\
+             })();\n\
+             _$delegateEvents([\"click\"]);\n"
+        );
+    }
+
+    #[test]
+    fn compiles_components_with_getters() {
+        assert_eq!(
+            compile_jsx(
+                "const el = <Show when={user()} fallback=\"&lt;none&gt;\" {...rest}><Profile /></Show>;"
+            ),
+            "import { createComponent as _$createComponent, mergeProps as _$mergeProps } from \"solid-js/web\";\n\
+             const el = _$createComponent(Show, _$mergeProps({ get when() { return user(); }, fallback: \"<none>\" }, rest, { get children() { return _$createComponent(Profile, {}); } }));"
+        );
+    }
+
+    #[test]
+    fn compiles_nested_jsx_in_expressions() {
+        let code = compile_jsx(
+            "const list = <ul>{items().map(item => <li class={item.kind}>{item.name}</li>)}</ul>;",
+        );
+        assert!(code.contains("_$insert(_el$, () => items().map(item => (() => {"));
+        assert!(code.contains("_$effect(() => _$className(_el$2, item.kind));"));
+        assert!(code.contains("_$insert(_el$2, () => item.name);"));
+        assert!(code.contains("_$template(\"<ul></ul>\")"));
+        assert!(code.contains("_$template(\"<li></li>\")"));
+    }
+
+    #[test]
+    fn cleans_whitespace() {
+        assert_eq!(clean_text("\n  Hello\n  world  \n"), "Hello world");
+        assert_eq!(clean_text(" a "), " a ");
+        assert_eq!(clean_text(" "), " ");
+        assert_eq!(clean_text("\n   \n"), "");
+        assert_eq!(
+            decode_entities("a &amp; b &#x41;&#66; &bogus;"),
+            "a & b AB &bogus;"
+        );
+    }
+
+    #[test]
+    fn leaves_modules_without_jsx_alone() {
+        assert!(
+            compile(
+                "export const a = 1;",
+                Path::new("a.jsx"),
+                &SolidOptions::default()
+            )
+            .is_none()
+        );
+        assert!(
+            compile(
+                "const a = <div>;",
+                Path::new("a.jsx"),
+                &SolidOptions::default()
+            )
+            .is_none()
+        );
+    }
+}
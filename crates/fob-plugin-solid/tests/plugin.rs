@@ -0,0 +1,100 @@
+//! Integration tests for fob-plugin-solid.
+
+use fob_plugin_solid::{SolidOptions, SolidPlugin};
+
+const COUNTER: &str = r#"import { createSignal } from "solid-js";
+
+export default function Counter(props: { label: string }) {
+  const [count, setCount] = createSignal<number>(0);
+  let button!: HTMLButtonElement;
+  return (
+    <>
+      <h1 class="title">{props.label}</h1>
+      <button ref={button} onClick={() => setCount(count() + 1)} on:focus={focus}>
+        Clicked {count()} times
+      </button>
+      <Show when={count() > 5} fallback={<p>Keep going</p>}>
+        <strong>Done!</strong>
+      </Show>
+    </>
+  );
+}
+"#;
+
+#[test]
+fn compiles_components() {
+    let plugin = SolidPlugin::new();
+
+    let code = plugin
+        .transform_code("/project/src/Counter.tsx", COUNTER)
+        .expect("tsx module should be compiled");
+
+    assert!(code.starts_with("import { createComponent as _$createComponent, "));
+    assert!(code.contains("} from \"solid-js/web\";\n"));
+    // TypeScript is left for the bundler
+    assert!(code.contains("createSignal<number>(0)"));
+    assert!(code.contains("let button!: HTMLButtonElement;"));
+    assert!(!code.contains("</Show>"));
+
+    assert!(code.contains("_$template(\"<h1 class=\\\"title\\\"></h1>\")"));
+    assert!(code.contains("_$insert(_el$, () => props.label);"));
+    assert!(code.contains("_$template(\"<button>Clicked <!> times</button>\")"));
+    assert!(code.contains("_$insert(_el$2, () => count(), _el$4);"));
+    assert!(code.contains("_el$2.$$click = () => setCount(count() + 1);"));
+    assert!(code.contains("_el$2.addEventListener(\"focus\", focus);"));
+    assert!(code.contains("typeof _ref$ === \"function\" ? _$use(_ref$, _el$2) : button = _el$2;"));
+    assert!(code.contains("_$createComponent(Show, { get when() { return count() > 5; }, get fallback() { return _tmpl$3(); }, get children() { return _tmpl$4(); } })"));
+    assert!(code.ends_with("_$delegateEvents([\"click\"]);\n"));
+}
+
+#[test]
+fn keeps_directives_first() {
+    let plugin = SolidPlugin::new();
+
+    let code = plugin
+        .transform_code(
+            "/project/src/app.jsx",
+            "\"use client\";\nexport const App = () => <div />;\n",
+        )
+        .unwrap();
+
+    assert!(code.starts_with(
+        "\"use client\";\nimport { template as _$template } from \"solid-js/web\";\n"
+    ));
+    assert!(code.contains("export const App = () => _tmpl$();"));
+}
+
+#[test]
+fn respects_options() {
+    let plugin = SolidPlugin::with_options(
+        SolidOptions::new()
+            .with_module_name("solid-js/universal")
+            .with_delegate_events(false),
+    );
+
+    let code = plugin
+        .transform_code("/project/src/app.jsx", "<a onClick={go}>Go</a>")
+        .unwrap();
+
+    assert!(code.contains("from \"solid-js/universal\""));
+    assert!(code.contains("_el$.addEventListener(\"click\", go);"));
+    assert!(!code.contains("delegateEvents"));
+}
+
+#[test]
+fn skips_other_modules() {
+    let plugin = SolidPlugin::new();
+
+    assert!(
+        plugin
+            .transform_code("/project/src/util.ts", "const a = 1;")
+            .is_none()
+    );
+    assert!(
+        plugin
+            .transform_code("/project/src/plain.jsx", "export const a = 1;")
+            .is_none()
+    );
+    assert!(plugin.handles("/project/src/App.tsx?v=1"));
+    assert!(!plugin.handles("/project/src/App.vue"));
+}
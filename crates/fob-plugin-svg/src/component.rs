@@ -0,0 +1,213 @@
+//! SVG document to a JSX component module, SVGR style.
+//!
+//! ```jsx
+//! const SvgComponent = (props) => {
+//!     return <svg viewBox="0 0 24 24" {...props}>...</svg>;
+//! };
+//! export default SvgComponent;
+//! ```
+//!
+//! Attributes are renamed to their React props (`stroke-width` becomes
+//! `strokeWidth`, `class` becomes `className`) and `style` strings become
+//! objects. Props passed to the component override the SVG's attributes.
+
+use crate::xml::{Element, XmlNode};
+use fob_gen::{Allocator, JsxBuilder, ProgramBuilder};
+use oxc_ast::ast::{
+    Expression, JSXAttributeItem, JSXChild, ObjectPropertyKind, PropertyKey, PropertyKind,
+    Statement,
+};
+use oxc_span::SPAN;
+
+/// Name of the generated component
+pub const COMPONENT_NAME: &str = "SvgComponent";
+
+/// Generate the component module for an `<svg>` element.
+///
+/// With `dimensions` off, `width` and `height` are dropped from the root so
+/// the icon scales with CSS.
+pub fn component_module(svg: &Element, dimensions: bool) -> anyhow::Result<String> {
+    let allocator = Allocator::default();
+    let mut js = ProgramBuilder::new(&allocator);
+    let jsx = JsxBuilder::new(&allocator);
+    let generator = Generator { js: &js, jsx: &jsx };
+
+    let mut attributes: Vec<JSXAttributeItem> = svg
+        .attributes
+        .iter()
+        .filter(|(key, _)| dimensions || !matches!(key.as_str(), "width" | "height"))
+        .map(|(key, value)| generator.attribute(key, value))
+        .collect();
+    attributes.push(
+        jsx.ast()
+            .jsx_attribute_item_spread_attribute(SPAN, js.ident("props")),
+    );
+    let children = generator.children(svg);
+    let element = jsx.element("svg", attributes, children, svg.children.is_empty());
+
+    let component = js.arrow_fn(vec!["props"], jsx.jsx_expr(element));
+    let declaration = js.const_decl(COMPONENT_NAME, component);
+    let export_default = js.export_default(js.ident(COMPONENT_NAME));
+    js.extend(vec![declaration, Statement::from(export_default)]);
+
+    Ok(js.generate(&Default::default())?)
+}
+
+struct Generator<'a, 'b> {
+    js: &'b ProgramBuilder<'a>,
+    jsx: &'b JsxBuilder<'a>,
+}
+
+impl<'a> Generator<'a, '_> {
+    fn element(&self, element: &Element) -> JSXChild<'a> {
+        let attributes = element
+            .attributes
+            .iter()
+            .map(|(key, value)| self.attribute(key, value))
+            .collect();
+        let children = self.children(element);
+        let name = self.jsx.ast().atom(&element.name);
+        self.jsx.child(
+            self.jsx
+                .element(name, attributes, children, element.children.is_empty()),
+        )
+    }
+
+    fn children(&self, element: &Element) -> Vec<JSXChild<'a>> {
+        element
+            .children
+            .iter()
+            .filter_map(|child| match child {
+                XmlNode::Element(child) => Some(self.element(child)),
+                XmlNode::Text(text) if text.trim().is_empty() => None,
+                // Braces and angle brackets are not allowed in JSX text
+                XmlNode::Text(text) if text.contains(['{', '}', '<', '>']) => {
+                    let value = self.jsx.ast().atom(text);
+                    Some(self.jsx.expr_child(self.js.string(value)))
+                }
+                XmlNode::Text(text) => Some(self.jsx.text(self.jsx.ast().atom(text))),
+                XmlNode::Comment(_) | XmlNode::Declaration(_) => None,
+            })
+            .collect()
+    }
+
+    fn attribute(&self, key: &str, value: &str) -> JSXAttributeItem<'a> {
+        let ast = self.jsx.ast();
+        let name = ast.atom(&prop_name(key));
+        let value = if key == "style" {
+            self.jsx.expr_attr(self.style_object(value))
+        } else {
+            self.jsx.string_attr(ast.atom(value))
+        };
+        self.jsx.attr(name, Some(value))
+    }
+
+    /// `fill: red; stroke-width: 2` to `{ fill: "red", strokeWidth: "2" }`
+    fn style_object(&self, style: &str) -> Expression<'a> {
+        let ast = self.jsx.ast();
+        let properties = style
+            .split(';')
+            .filter_map(|declaration| declaration.split_once(':'))
+            .map(|(property, value)| {
+                let key = ast.atom(&style_key(property.trim()));
+                let value = self.js.string(ast.atom(value.trim()));
+                if is_identifier(&key) {
+                    self.js.prop(key, value)
+                } else {
+                    let key =
+                        PropertyKey::StringLiteral(ast.alloc(ast.string_literal(SPAN, key, None)));
+                    ObjectPropertyKind::ObjectProperty(ast.alloc(ast.object_property(
+                        SPAN,
+                        PropertyKind::Init,
+                        key,
+                        value,
+                        false,
+                        false,
+                        false,
+                    )))
+                }
+            })
+            .collect();
+        self.js.object(properties)
+    }
+}
+
+/// React prop name of an SVG attribute.
+pub fn prop_name(attribute: &str) -> String {
+    match attribute {
+        "class" => return "className".to_string(),
+        "for" => return "htmlFor".to_string(),
+        _ => {}
+    }
+    if attribute.starts_with("data-") || attribute.starts_with("aria-") {
+        return attribute.to_string();
+    }
+    camel_case(attribute, &['-', ':'])
+}
+
+/// Style object key of a CSS property; custom properties are kept as is.
+fn style_key(property: &str) -> String {
+    if property.starts_with("--") {
+        return property.to_string();
+    }
+    let property = property.to_ascii_lowercase();
+    match property.strip_prefix('-') {
+        // `-webkit-mask` becomes `WebkitMask`, `-ms-` stays lowercase
+        Some(vendor) if !vendor.starts_with("ms-") => {
+            let mut key = camel_case(vendor, &['-']);
+            if let Some(first) = key.get_mut(..1) {
+                first.make_ascii_uppercase();
+            }
+            key
+        }
+        Some(vendor) => camel_case(vendor, &['-']),
+        None => camel_case(&property, &['-']),
+    }
+}
+
+fn camel_case(text: &str, separators: &[char]) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut upper = false;
+    for c in text.chars() {
+        if separators.contains(&c) {
+            upper = true;
+        } else if upper {
+            out.push(c.to_ascii_uppercase());
+            upper = false;
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_' || c == '$')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn converts_attribute_names() {
+        assert_eq!(prop_name("stroke-width"), "strokeWidth");
+        assert_eq!(prop_name("xlink:href"), "xlinkHref");
+        assert_eq!(prop_name("xmlns:xlink"), "xmlnsXlink");
+        assert_eq!(prop_name("class"), "className");
+        assert_eq!(prop_name("viewBox"), "viewBox");
+        assert_eq!(prop_name("aria-hidden"), "aria-hidden");
+    }
+
+    #[test]
+    fn converts_style_properties() {
+        assert_eq!(style_key("stroke-width"), "strokeWidth");
+        assert_eq!(style_key("-webkit-mask"), "WebkitMask");
+        assert_eq!(style_key("-ms-transform"), "msTransform");
+        assert_eq!(style_key("--icon-color"), "--icon-color");
+    }
+}
@@ -0,0 +1,259 @@
+//! SVG plugin for the fob bundler.
+//!
+//! Handles two kinds of SVG imports:
+//!
+//! ```js
+//! import logoUrl from "./logo.svg";          // URL of a hashed asset
+//! import Logo from "./logo.svg?component";  // JSX component, SVGR style
+//! ```
+//!
+//! Plain imports emit the file as an asset (`logo-a1b2c3d4.svg`) and export
+//! its URL. `?component` imports become a component rendering the SVG inline,
+//! generated with `fob-gen` and compiled by the bundler's JSX transform;
+//! props are spread onto the root `<svg>` element.
+//!
+//! Both are cleaned up by an SVGO-style pass first (comments, metadata,
+//! editor namespaces, empty groups, whitespace), which can be turned off.
+//!
+//! ```rust,no_run
+//! use fob_bundler::runtime::BundlerRuntime;
+//! use fob_plugin_svg::{SvgOptions, SvgPlugin};
+//! use std::sync::Arc;
+//!
+//! let runtime = Arc::new(BundlerRuntime::new("."));
+//! let plugin = SvgPlugin::with_options(runtime, SvgOptions::new().with_dimensions(false));
+//! ```
+
+mod component;
+mod optimize;
+mod xml;
+
+use anyhow::Context;
+use fob_bundler::{
+    EmittedAsset, HookLoadArgs, HookLoadOutput, HookLoadReturn, HookResolveIdArgs,
+    HookResolveIdOutput, HookResolveIdReturn, HookUsage, ModuleType, Plugin, PluginContext,
+    Runtime,
+};
+use path_clean::PathClean;
+use std::borrow::Cow;
+use std::path::Path;
+use std::sync::Arc;
+
+pub use component::COMPONENT_NAME;
+
+/// Query selecting the component form of an SVG import
+pub const COMPONENT_QUERY: &str = "?component";
+
+/// Configuration for [`SvgPlugin`].
+#[derive(Debug, Clone)]
+pub struct SvgOptions {
+    /// Run the SVGO-style cleanup on assets and components (default: true).
+    pub optimize: bool,
+    /// Keep `width` and `height` on the component's root element
+    /// (default: true). Turn off to size icons with CSS.
+    pub dimensions: bool,
+}
+
+impl Default for SvgOptions {
+    fn default() -> Self {
+        Self {
+            optimize: true,
+            dimensions: true,
+        }
+    }
+}
+
+impl SvgOptions {
+    /// Create options with default values.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Enable or disable the SVGO-style cleanup.
+    pub fn with_optimize(mut self, enabled: bool) -> Self {
+        self.optimize = enabled;
+        self
+    }
+
+    /// Keep or drop `width` and `height` on components.
+    pub fn with_dimensions(mut self, enabled: bool) -> Self {
+        self.dimensions = enabled;
+        self
+    }
+}
+
+/// SVG plugin.
+#[derive(Debug, Clone)]
+pub struct SvgPlugin {
+    options: SvgOptions,
+    runtime: Arc<dyn Runtime>,
+}
+
+impl SvgPlugin {
+    /// Create a plugin with default options.
+    pub fn new(runtime: Arc<dyn Runtime>) -> Self {
+        Self::with_options(runtime, SvgOptions::default())
+    }
+
+    /// Create a plugin with custom options.
+    pub fn with_options(runtime: Arc<dyn Runtime>, options: SvgOptions) -> Self {
+        Self { options, runtime }
+    }
+
+    /// Access the plugin options.
+    pub fn options(&self) -> &SvgOptions {
+        &self.options
+    }
+
+    /// Whether this module id is an SVG file, with or without `?component`.
+    pub fn handles(&self, id: &str) -> bool {
+        let path = id.strip_suffix(COMPONENT_QUERY).unwrap_or(id);
+        Path::new(path)
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("svg"))
+    }
+
+    /// Resolve a `?component` import to an id keeping the query.
+    ///
+    /// Relative specifiers resolve against the importer, absolute ones
+    /// against the runtime's working directory. Returns `None` for other
+    /// specifiers and missing files.
+    pub fn resolve_component(&self, specifier: &str, importer: Option<&str>) -> Option<String> {
+        let path = specifier.strip_suffix(COMPONENT_QUERY)?;
+        if !self.handles(path) {
+            return None;
+        }
+        let path = Path::new(path);
+        let resolved = if path.is_absolute() {
+            path.clean()
+        } else if path.starts_with(".") || path.starts_with("..") {
+            let base = match importer {
+                Some(importer) => Path::new(importer)
+                    .parent()
+                    .unwrap_or(Path::new(""))
+                    .to_path_buf(),
+                None => self.runtime.get_cwd().unwrap_or_default(),
+            };
+            base.join(path).clean()
+        } else {
+            return None;
+        };
+
+        self.runtime
+            .exists(&resolved)
+            .then(|| format!("{}{}", resolved.display(), COMPONENT_QUERY))
+    }
+
+    /// Read an SVG file, optimized unless disabled.
+    pub async fn read_svg(&self, path: &Path) -> anyhow::Result<String> {
+        let bytes = self
+            .runtime
+            .read_file(path)
+            .await
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        let source = String::from_utf8(bytes)
+            .with_context(|| format!("{} contains invalid UTF-8", path.display()))?;
+        if !self.options.optimize {
+            return Ok(source);
+        }
+
+        let mut nodes = xml::parse(&source)
+            .map_err(|e| anyhow::anyhow!("Failed to parse {}: {}", path.display(), e))?;
+        optimize::optimize(&mut nodes);
+        Ok(xml::write(&nodes))
+    }
+
+    /// Generate the JSX component module for a `?component` id.
+    ///
+    /// Returns `Ok(None)` for other ids.
+    pub async fn load_component(&self, id: &str) -> anyhow::Result<Option<String>> {
+        let Some(path) = id.strip_suffix(COMPONENT_QUERY) else {
+            return Ok(None);
+        };
+        if !self.handles(path) {
+            return Ok(None);
+        }
+
+        let source = self.read_svg(Path::new(path)).await?;
+        let nodes =
+            xml::parse(&source).map_err(|e| anyhow::anyhow!("Failed to parse {}: {}", path, e))?;
+        let svg = xml::root(&nodes).with_context(|| format!("{} has no <svg> element", path))?;
+        component::component_module(svg, self.options.dimensions).map(Some)
+    }
+
+    /// Emit a plain SVG import as an asset and export its URL.
+    async fn load_asset(&self, ctx: &PluginContext, id: &str) -> anyhow::Result<String> {
+        let path = Path::new(id);
+        let source = self.read_svg(path).await?;
+        let reference_id = ctx.emit_file(
+            EmittedAsset {
+                name: path
+                    .file_name()
+                    .and_then(|n| n.to_str())
+                    .map(|s| s.to_string()),
+                original_file_name: Some(id.to_string()),
+                file_name: None,
+                source: source.into(),
+            },
+            None,
+            None,
+        )?;
+        Ok(format!(
+            "export default import.meta.ROLLUP_FILE_URL_{};\n",
+            reference_id
+        ))
+    }
+}
+
+impl Plugin for SvgPlugin {
+    fn name(&self) -> Cow<'static, str> {
+        "fob-plugin-svg".into()
+    }
+
+    fn register_hook_usage(&self) -> HookUsage {
+        HookUsage::ResolveId | HookUsage::Load
+    }
+
+    fn resolve_id(
+        &self,
+        _ctx: &PluginContext,
+        args: &HookResolveIdArgs,
+    ) -> impl std::future::Future<Output = HookResolveIdReturn> + Send {
+        let id = self.resolve_component(args.specifier, args.importer);
+
+        async move {
+            Ok(id.map(|id| HookResolveIdOutput {
+                id: id.into(),
+                ..Default::default()
+            }))
+        }
+    }
+
+    fn load(
+        &self,
+        ctx: &PluginContext,
+        args: &HookLoadArgs<'_>,
+    ) -> impl std::future::Future<Output = HookLoadReturn> + Send {
+        let plugin = self.clone();
+        let id = args.id.to_string();
+
+        async move {
+            if !plugin.handles(&id) {
+                return Ok(None);
+            }
+            if let Some(code) = plugin.load_component(&id).await? {
+                return Ok(Some(HookLoadOutput {
+                    code: code.into(),
+                    module_type: Some(ModuleType::Jsx),
+                    ..Default::default()
+                }));
+            }
+            let code = plugin.load_asset(ctx, &id).await?;
+            Ok(Some(HookLoadOutput {
+                code: code.into(),
+                module_type: Some(ModuleType::Js),
+                ..Default::default()
+            }))
+        }
+    }
+}
@@ -0,0 +1,119 @@
+//! SVGO-style cleanup of SVG documents.
+//!
+//! Covers the default SVGO passes that matter for icons exported by design
+//! tools: comments, declarations, metadata, editor data, empty groups and
+//! containers, redundant attributes and formatting whitespace. Passes that
+//! rewrite geometry (path data, transforms) are left to SVGO itself.
+
+use crate::xml::{Element, XmlNode};
+
+/// Namespaces written by editors, with no effect on rendering
+const EDITOR_NAMESPACES: &[&str] = &["inkscape", "sodipodi", "sketch", "serif", "figma", "i", "x"];
+
+/// Containers removed when they end up without children
+const CONTAINERS: &[&str] = &["g", "defs", "symbol", "clipPath", "mask", "pattern"];
+
+/// Elements whose text content is significant
+const TEXT_ELEMENTS: &[&str] = &["text", "tspan", "textPath", "title", "style", "script"];
+
+/// Optimize the nodes of a document in place.
+pub fn optimize(nodes: &mut Vec<XmlNode>) {
+    nodes.retain(|node| matches!(node, XmlNode::Element(_)));
+    for node in nodes.iter_mut() {
+        if let XmlNode::Element(element) = node {
+            optimize_element(element, true);
+        }
+    }
+}
+
+fn optimize_element(element: &mut Element, is_root: bool) {
+    element
+        .attributes
+        .retain(|(key, value)| keep_attribute(key, value, is_root));
+
+    let keeps_text = TEXT_ELEMENTS.contains(&element.name.as_str());
+    element.children.retain_mut(|child| match child {
+        XmlNode::Element(child) => {
+            if child.name == "metadata" || is_editor_name(&child.name) {
+                return false;
+            }
+            optimize_element(child, false);
+            !(CONTAINERS.contains(&child.name.as_str())
+                && child.children.is_empty()
+                && child.attribute("id").is_none())
+        }
+        XmlNode::Text(text) => keeps_text || !text.trim().is_empty(),
+        XmlNode::Comment(_) | XmlNode::Declaration(_) => false,
+    });
+
+    // A group without attributes adds nothing around its children
+    if !keeps_text {
+        let children = std::mem::take(&mut element.children);
+        for child in children {
+            match child {
+                XmlNode::Element(group) if group.name == "g" && group.attributes.is_empty() => {
+                    element.children.extend(group.children);
+                }
+                child => element.children.push(child),
+            }
+        }
+    }
+}
+
+fn keep_attribute(key: &str, value: &str, is_root: bool) -> bool {
+    if is_editor_name(key) {
+        return false;
+    }
+    if let Some(prefix) = key.strip_prefix("xmlns:") {
+        return !EDITOR_NAMESPACES.contains(&prefix);
+    }
+    if is_root && matches!(key, "version" | "baseProfile" | "enable-background") {
+        return false;
+    }
+    !(key == "xml:space" || (value.is_empty() && key != "id"))
+}
+
+fn is_editor_name(name: &str) -> bool {
+    name.split_once(':')
+        .is_some_and(|(prefix, _)| EDITOR_NAMESPACES.contains(&prefix))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::xml;
+
+    fn run(source: &str) -> String {
+        let mut nodes = xml::parse(source).unwrap();
+        optimize(&mut nodes);
+        xml::write(&nodes)
+    }
+
+    #[test]
+    fn removes_editor_data_and_metadata() {
+        let output = run(concat!(
+            "<?xml version=\"1.0\"?>\n<!-- Generator: Sketch -->\n",
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" xmlns:sketch=\"http://www.bohemiancoding.com/sketch/ns\" ",
+            "version=\"1.1\" sketch:type=\"MSPage\">\n",
+            "  <metadata>rdf</metadata>\n  <sodipodi:namedview/>\n",
+            "  <path d=\"M0 0\" inkscape:label=\"x\" fill=\"\"/>\n</svg>\n"
+        ));
+
+        assert_eq!(
+            output,
+            "<svg xmlns=\"http://www.w3.org/2000/svg\"><path d=\"M0 0\"/></svg>"
+        );
+    }
+
+    #[test]
+    fn collapses_groups_and_empty_containers() {
+        let output = run(
+            "<svg><defs></defs><g><g fill=\"red\"><rect/></g></g><g id=\"anchor\"/><title> Icon </title></svg>",
+        );
+
+        assert_eq!(
+            output,
+            "<svg><g fill=\"red\"><rect/></g><g id=\"anchor\"/><title> Icon </title></svg>"
+        );
+    }
+}
@@ -0,0 +1,341 @@
+//! Minimal XML reader and writer for SVG documents.
+//!
+//! Handles elements, attributes, text, CDATA, comments, processing
+//! instructions and doctypes, which is all SVG files exported by design tools
+//! contain. Entities other than the predefined ones are left as written.
+
+/// A node of an SVG document.
+#[derive(Debug, Clone, PartialEq)]
+pub enum XmlNode {
+    Element(Element),
+    /// Text with entities decoded
+    Text(String),
+    Comment(String),
+    /// `<?xml ...?>` and `<!DOCTYPE ...>`, kept verbatim
+    Declaration(String),
+}
+
+/// An element with its attributes in document order.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Element {
+    pub name: String,
+    pub attributes: Vec<(String, String)>,
+    pub children: Vec<XmlNode>,
+}
+
+impl Element {
+    /// Value of an attribute.
+    pub fn attribute(&self, name: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+/// Parse a document into its top-level nodes.
+pub fn parse(source: &str) -> Result<Vec<XmlNode>, String> {
+    let mut reader = Reader { source, pos: 0 };
+    let nodes = reader.nodes(None)?;
+    Ok(nodes)
+}
+
+/// The root `<svg>` element of a document.
+pub fn root(nodes: &[XmlNode]) -> Option<&Element> {
+    nodes.iter().find_map(|node| match node {
+        XmlNode::Element(element) if element.name == "svg" => Some(element),
+        _ => None,
+    })
+}
+
+struct Reader<'a> {
+    source: &'a str,
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn rest(&self) -> &'a str {
+        &self.source[self.pos..]
+    }
+
+    fn line(&self) -> usize {
+        self.source[..self.pos].matches('\n').count() + 1
+    }
+
+    /// Read nodes until the closing tag of `parent`, or the end of input
+    /// at the top level.
+    fn nodes(&mut self, parent: Option<&str>) -> Result<Vec<XmlNode>, String> {
+        let mut nodes = Vec::new();
+        loop {
+            let rest = self.rest();
+            if rest.is_empty() {
+                return match parent {
+                    Some(name) => Err(format!("Unclosed <{}> element", name)),
+                    None => Ok(nodes),
+                };
+            }
+
+            if let Some(after) = rest.strip_prefix("</") {
+                let end = after
+                    .find('>')
+                    .ok_or_else(|| format!("Unterminated closing tag at line {}", self.line()))?;
+                let name = after[..end].trim();
+                if parent != Some(name) {
+                    return Err(format!(
+                        "Unexpected </{}> at line {}, expected </{}>",
+                        name,
+                        self.line(),
+                        parent.unwrap_or("nothing")
+                    ));
+                }
+                self.pos += 2 + end + 1;
+                return Ok(nodes);
+            } else if let Some(after) = rest.strip_prefix("<!--") {
+                let end = after
+                    .find("-->")
+                    .ok_or_else(|| format!("Unterminated comment at line {}", self.line()))?;
+                nodes.push(XmlNode::Comment(after[..end].to_string()));
+                self.pos += 4 + end + 3;
+            } else if let Some(after) = rest.strip_prefix("<![CDATA[") {
+                let end = after
+                    .find("]]>")
+                    .ok_or_else(|| format!("Unterminated CDATA at line {}", self.line()))?;
+                nodes.push(XmlNode::Text(after[..end].to_string()));
+                self.pos += 9 + end + 3;
+            } else if rest.starts_with("<?") || rest.starts_with("<!") {
+                let end = declaration_end(rest)
+                    .ok_or_else(|| format!("Unterminated declaration at line {}", self.line()))?;
+                nodes.push(XmlNode::Declaration(rest[..end].to_string()));
+                self.pos += end;
+            } else if rest.starts_with('<') {
+                nodes.push(XmlNode::Element(self.element()?));
+            } else {
+                let end = rest.find('<').unwrap_or(rest.len());
+                nodes.push(XmlNode::Text(decode_entities(&rest[..end])));
+                self.pos += end;
+            }
+        }
+    }
+
+    fn element(&mut self) -> Result<Element, String> {
+        let line = self.line();
+        self.pos += 1;
+        let rest = self.rest();
+        let name_len = rest
+            .find(|c: char| c.is_whitespace() || c == '>' || c == '/')
+            .unwrap_or(rest.len());
+        let name = rest[..name_len].to_string();
+        if name.is_empty() {
+            return Err(format!("Expected an element name at line {}", line));
+        }
+        self.pos += name_len;
+
+        let mut attributes = Vec::new();
+        loop {
+            let trimmed = self.rest().trim_start();
+            self.pos = self.source.len() - trimmed.len();
+
+            if let Some(after) = trimmed.strip_prefix("/>") {
+                self.pos = self.source.len() - after.len();
+                return Ok(Element {
+                    name,
+                    attributes,
+                    children: Vec::new(),
+                });
+            }
+            if let Some(after) = trimmed.strip_prefix('>') {
+                self.pos = self.source.len() - after.len();
+                let children = self.nodes(Some(&name))?;
+                return Ok(Element {
+                    name,
+                    attributes,
+                    children,
+                });
+            }
+
+            let key_len = trimmed
+                .find(|c: char| c.is_whitespace() || c == '=' || c == '>' || c == '/')
+                .unwrap_or(trimmed.len());
+            if key_len == 0 {
+                return Err(format!("Unterminated <{}> tag at line {}", name, line));
+            }
+            let key = trimmed[..key_len].to_string();
+            let after_key = trimmed[key_len..].trim_start();
+            let Some(after_eq) = after_key.strip_prefix('=') else {
+                // Attribute without a value
+                self.pos = self.source.len() - after_key.len();
+                attributes.push((key, String::new()));
+                continue;
+            };
+            let after_eq = after_eq.trim_start();
+            let quote = after_eq
+                .chars()
+                .next()
+                .filter(|c| *c == '"' || *c == '\'')
+                .ok_or_else(|| format!("Unquoted value of '{}' at line {}", key, line))?;
+            let value_end = after_eq[1..]
+                .find(quote)
+                .ok_or_else(|| format!("Unterminated value of '{}' at line {}", key, line))?;
+            attributes.push((key, decode_entities(&after_eq[1..1 + value_end])));
+            self.pos = self.source.len() - after_eq.len() + value_end + 2;
+        }
+    }
+}
+
+/// Length of a `<?...?>` or `<!...>` declaration, allowing an internal
+/// DTD subset in brackets.
+fn declaration_end(text: &str) -> Option<usize> {
+    if text.starts_with("<?") {
+        return text.find("?>").map(|end| end + 2);
+    }
+    let mut depth = 0usize;
+    for (index, c) in text.char_indices() {
+        match c {
+            '[' => depth += 1,
+            ']' => depth = depth.saturating_sub(1),
+            '>' if depth == 0 => return Some(index + 1),
+            _ => {}
+        }
+    }
+    None
+}
+
+fn decode_entities(text: &str) -> String {
+    if !text.contains('&') {
+        return text.to_string();
+    }
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('&') {
+        out.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let Some(end) = rest.find(';').filter(|end| *end <= 10) else {
+            out.push('&');
+            rest = &rest[1..];
+            continue;
+        };
+        let entity = &rest[1..end];
+        let decoded = match entity {
+            "amp" => Some('&'),
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            _ => entity
+                .strip_prefix("#x")
+                .and_then(|hex| u32::from_str_radix(hex, 16).ok())
+                .or_else(|| entity.strip_prefix('#').and_then(|dec| dec.parse().ok()))
+                .and_then(char::from_u32),
+        };
+        match decoded {
+            Some(c) => out.push(c),
+            None => out.push_str(&rest[..=end]),
+        }
+        rest = &rest[end + 1..];
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Serialize nodes back to XML.
+pub fn write(nodes: &[XmlNode]) -> String {
+    let mut out = String::new();
+    for node in nodes {
+        write_node(&mut out, node);
+    }
+    out
+}
+
+fn write_node(out: &mut String, node: &XmlNode) {
+    match node {
+        XmlNode::Element(element) => {
+            out.push('<');
+            out.push_str(&element.name);
+            for (key, value) in &element.attributes {
+                out.push(' ');
+                out.push_str(key);
+                out.push_str("=\"");
+                out.push_str(&escape(value, true));
+                out.push('"');
+            }
+            if element.children.is_empty() {
+                out.push_str("/>");
+            } else {
+                out.push('>');
+                for child in &element.children {
+                    write_node(out, child);
+                }
+                out.push_str("</");
+                out.push_str(&element.name);
+                out.push('>');
+            }
+        }
+        XmlNode::Text(text) => out.push_str(&escape(text, false)),
+        XmlNode::Comment(text) => {
+            out.push_str("<!--");
+            out.push_str(text);
+            out.push_str("-->");
+        }
+        XmlNode::Declaration(text) => out.push_str(text),
+    }
+}
+
+fn escape(text: &str, attribute: bool) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' if !attribute => out.push_str("&gt;"),
+            '"' if attribute => out.push_str("&quot;"),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_and_writes_documents() {
+        let source = r#"<?xml version="1.0"?><!-- icon --><svg viewBox="0 0 24 24"><path d="M0 0h24" /><text>a &amp; b</text></svg>"#;
+
+        let nodes = parse(source).unwrap();
+        let svg = root(&nodes).unwrap();
+
+        assert_eq!(svg.attribute("viewBox"), Some("0 0 24 24"));
+        assert_eq!(svg.children.len(), 2);
+        assert_eq!(
+            write(&nodes),
+            r#"<?xml version="1.0"?><!-- icon --><svg viewBox="0 0 24 24"><path d="M0 0h24"/><text>a &amp; b</text></svg>"#
+        );
+    }
+
+    #[test]
+    fn handles_doctype_with_internal_subset() {
+        let source = "<!DOCTYPE svg [ <!ENTITY ns \"x\"> ]>\n<svg/>";
+
+        let nodes = parse(source).unwrap();
+
+        assert!(matches!(&nodes[0], XmlNode::Declaration(d) if d.ends_with("]>")));
+        assert!(root(&nodes).is_some());
+    }
+
+    #[test]
+    fn decodes_entities_in_attributes() {
+        let nodes = parse("<svg aria-label=\"a &lt; b &#x41;&#66;\"/>").unwrap();
+
+        assert_eq!(
+            root(&nodes).unwrap().attribute("aria-label"),
+            Some("a < b AB")
+        );
+    }
+
+    #[test]
+    fn reports_mismatched_tags() {
+        let error = parse("<svg><g></svg>").unwrap_err();
+        assert!(error.contains("expected </g>"));
+    }
+}
@@ -0,0 +1,130 @@
+//! Integration tests for fob-plugin-svg.
+
+use fob_bundler::runtime::BundlerRuntime;
+use fob_plugin_svg::{SvgOptions, SvgPlugin};
+use std::path::Path;
+use std::sync::Arc;
+
+const ICON: &str = r##"<?xml version="1.0" encoding="UTF-8"?>
+<!-- Generator: Sketch 52 -->
+<svg xmlns="http://www.w3.org/2000/svg" xmlns:sketch="http://www.bohemiancoding.com/sketch/ns" width="24" height="24" viewBox="0 0 24 24" class="icon">
+  <metadata>exported</metadata>
+  <g>
+    <path stroke-width="2" style="fill: red; stroke-linecap: round" d="M4 12h16" sketch:type="MSShapeGroup"/>
+  </g>
+</svg>
+"##;
+
+/// Plugin whose runtime holds `svg` at `/project/src/icon.svg`
+fn plugin(svg: &str, options: SvgOptions) -> SvgPlugin {
+    let runtime = BundlerRuntime::new("/project");
+    runtime.add_virtual_file("/project/src/icon.svg", svg.as_bytes().to_vec());
+    SvgPlugin::with_options(Arc::new(runtime), options)
+}
+
+#[tokio::test]
+async fn generates_components() {
+    let plugin = plugin(ICON, SvgOptions::default());
+
+    let code = plugin
+        .load_component("/project/src/icon.svg?component")
+        .await
+        .unwrap()
+        .expect("component id should be handled");
+
+    assert!(code.contains("const SvgComponent = (props) =>"));
+    assert!(code.contains("export default SvgComponent;"));
+    assert!(code.contains("className=\"icon\""));
+    assert!(code.contains("width=\"24\""));
+    assert!(code.contains("{...props}"));
+    assert!(code.contains("strokeWidth=\"2\""));
+    assert!(code.contains("strokeLinecap: \"round\""));
+    assert!(!code.contains("metadata"));
+    assert!(!code.contains("sketch"));
+    assert!(!code.contains("<g>"));
+}
+
+#[tokio::test]
+async fn drops_dimensions_when_disabled() {
+    let plugin = plugin(ICON, SvgOptions::new().with_dimensions(false));
+
+    let code = plugin
+        .load_component("/project/src/icon.svg?component")
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert!(!code.contains("width="));
+    assert!(!code.contains("height="));
+    assert!(code.contains("viewBox=\"0 0 24 24\""));
+}
+
+#[tokio::test]
+async fn optimizes_assets_unless_disabled() {
+    let path = Path::new("/project/src/icon.svg");
+
+    let optimized = plugin(ICON, SvgOptions::default())
+        .read_svg(path)
+        .await
+        .unwrap();
+    assert!(optimized.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"24\""));
+    assert!(optimized.contains("<path stroke-width=\"2\""));
+    assert!(!optimized.contains("<!--"));
+    assert!(!optimized.contains('\n'));
+
+    let original = plugin(ICON, SvgOptions::new().with_optimize(false))
+        .read_svg(path)
+        .await
+        .unwrap();
+    assert_eq!(original, ICON);
+}
+
+#[test]
+fn resolves_component_imports() {
+    let plugin = plugin(ICON, SvgOptions::default());
+
+    assert_eq!(
+        plugin.resolve_component("../icon.svg?component", Some("/project/src/pages/home.tsx")),
+        Some("/project/src/icon.svg?component".to_string())
+    );
+    assert_eq!(
+        plugin.resolve_component("/project/src/icon.svg?component", None),
+        Some("/project/src/icon.svg?component".to_string())
+    );
+    assert_eq!(
+        plugin.resolve_component("./missing.svg?component", Some("/project/src/app.tsx")),
+        None
+    );
+    assert_eq!(
+        plugin.resolve_component("./icon.svg", Some("/project/src/app.tsx")),
+        None
+    );
+}
+
+#[tokio::test]
+async fn skips_other_ids() {
+    let plugin = plugin(ICON, SvgOptions::default());
+
+    assert!(plugin.handles("/project/src/icon.svg"));
+    assert!(plugin.handles("/project/src/icon.svg?component"));
+    assert!(!plugin.handles("/project/src/app.tsx"));
+    assert!(
+        plugin
+            .load_component("/project/src/icon.svg")
+            .await
+            .unwrap()
+            .is_none()
+    );
+}
+
+#[tokio::test]
+async fn parse_errors_name_the_file() {
+    let plugin = plugin("<svg><path></svg>", SvgOptions::default());
+
+    let error = plugin
+        .load_component("/project/src/icon.svg?component")
+        .await
+        .unwrap_err();
+
+    assert!(error.to_string().contains("/project/src/icon.svg"));
+}
@@ -0,0 +1,189 @@
+//! Bundle composition report for the fob bundler.
+//!
+//! After the bundle is generated, this plugin adds a self-contained
+//! `stats.html` showing which modules make up each chunk and how many bytes
+//! of output they account for, as a zoomable treemap or sunburst. The report
+//! needs no network access or server; open it straight from the output
+//! directory.
+//!
+//! Sizes come from the code Rolldown rendered for each module, so they
+//! reflect tree shaking but not minification of the whole chunk or
+//! compression.
+//!
+//! ```rust,no_run
+//! use fob_plugin_visualizer::{VisualizerOptions, VisualizerPlugin, VisualizerView};
+//!
+//! let plugin = VisualizerPlugin::with_options(
+//!     VisualizerOptions::new().with_view(VisualizerView::Sunburst),
+//! );
+//! ```
+
+mod stats;
+
+pub use stats::{BundleStats, ChunkStats, ModuleStats, TreeNode};
+
+use fob_bundler::{
+    HookGenerateBundleArgs, HookNoopReturn, HookUsage, Output, OutputAsset, Plugin, PluginContext,
+};
+use serde::Serialize;
+use std::borrow::Cow;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+const TEMPLATE: &str = include_str!("report.html");
+
+/// Chart the report opens with; both are available in the page.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum VisualizerView {
+    #[default]
+    Treemap,
+    Sunburst,
+}
+
+impl VisualizerView {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Treemap => "treemap",
+            Self::Sunburst => "sunburst",
+        }
+    }
+}
+
+/// Configuration for [`VisualizerPlugin`].
+#[derive(Debug, Clone)]
+pub struct VisualizerOptions {
+    /// Report file name, relative to the output directory (default: `stats.html`).
+    pub filename: String,
+    /// Page title (default: `Bundle composition`).
+    pub title: String,
+    /// Chart shown first (default: treemap).
+    pub view: VisualizerView,
+    /// Directory module paths are shown relative to (default: the deepest
+    /// directory shared by all modules).
+    pub root: Option<PathBuf>,
+}
+
+impl Default for VisualizerOptions {
+    fn default() -> Self {
+        Self {
+            filename: "stats.html".to_string(),
+            title: "Bundle composition".to_string(),
+            view: VisualizerView::default(),
+            root: None,
+        }
+    }
+}
+
+impl VisualizerOptions {
+    /// Create options with default values.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the report file name.
+    pub fn with_filename(mut self, filename: impl Into<String>) -> Self {
+        self.filename = filename.into();
+        self
+    }
+
+    /// Set the page title.
+    pub fn with_title(mut self, title: impl Into<String>) -> Self {
+        self.title = title.into();
+        self
+    }
+
+    /// Set the chart shown first.
+    pub fn with_view(mut self, view: VisualizerView) -> Self {
+        self.view = view;
+        self
+    }
+
+    /// Set the directory module paths are shown relative to.
+    pub fn with_root(mut self, root: impl Into<PathBuf>) -> Self {
+        self.root = Some(root.into());
+        self
+    }
+}
+
+/// Plugin emitting an HTML report of the bundle's composition.
+#[derive(Debug, Clone, Default)]
+pub struct VisualizerPlugin {
+    options: VisualizerOptions,
+}
+
+/// Data embedded in the report.
+#[derive(Serialize)]
+struct ReportData<'a> {
+    tree: TreeNode,
+    chunks: &'a [ChunkStats],
+}
+
+impl VisualizerPlugin {
+    /// Create a plugin with default options.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a plugin with custom options.
+    pub fn with_options(options: VisualizerOptions) -> Self {
+        Self { options }
+    }
+
+    /// Access the plugin options.
+    pub fn options(&self) -> &VisualizerOptions {
+        &self.options
+    }
+
+    /// Render the report for a bundle's statistics.
+    pub fn render(&self, stats: &BundleStats) -> String {
+        let data = ReportData {
+            tree: stats.tree(self.options.root.as_deref()),
+            chunks: &stats.chunks,
+        };
+        // Module paths may contain `</script>`
+        let json = serde_json::to_string(&data)
+            .unwrap_or_else(|_| "{}".to_string())
+            .replace('<', "\\u003c");
+
+        TEMPLATE
+            .replace("__TITLE__", &escape_html(&self.options.title))
+            .replace("__VIEW__", self.options.view.as_str())
+            .replace("__DATA__", &json)
+    }
+}
+
+impl Plugin for VisualizerPlugin {
+    fn name(&self) -> Cow<'static, str> {
+        "fob-plugin-visualizer".into()
+    }
+
+    fn register_hook_usage(&self) -> HookUsage {
+        HookUsage::GenerateBundle
+    }
+
+    fn generate_bundle(
+        &self,
+        _ctx: &PluginContext,
+        args: &mut HookGenerateBundleArgs<'_>,
+    ) -> impl std::future::Future<Output = HookNoopReturn> + Send {
+        let html = self.render(&BundleStats::from_bundle(args.bundle.as_slice()));
+        let filename = self.options.filename.clone();
+
+        async move {
+            args.bundle.push(Output::Asset(Arc::new(OutputAsset {
+                names: vec![],
+                original_file_names: vec![],
+                filename: filename.into(),
+                source: html.into(),
+            })));
+            Ok(())
+        }
+    }
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
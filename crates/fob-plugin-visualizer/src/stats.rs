@@ -0,0 +1,204 @@
+//! Chunk composition gathered from the generated bundle.
+//!
+//! Every chunk lists the modules Rolldown rendered into it with their code,
+//! so sizes are those of the output after tree shaking, not of the sources.
+
+use fob_bundler::Output;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// Composition of a bundle's chunks.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BundleStats {
+    pub chunks: Vec<ChunkStats>,
+}
+
+/// One output chunk.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct ChunkStats {
+    pub filename: String,
+    /// Size of the chunk's code in bytes
+    pub size: usize,
+    pub is_entry: bool,
+    pub is_dynamic_entry: bool,
+    /// Chunks this one imports statically
+    pub imports: Vec<String>,
+    /// Chunks this one imports with `import()`
+    pub dynamic_imports: Vec<String>,
+    #[serde(skip)]
+    pub modules: Vec<ModuleStats>,
+}
+
+/// A module's contribution to a chunk.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModuleStats {
+    pub id: String,
+    /// Size of the module's rendered code in bytes
+    pub size: usize,
+}
+
+/// A node of the report's hierarchy: the bundle, a chunk, a directory or a
+/// module. Only modules have a size; the others add up their children.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TreeNode {
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub size: Option<usize>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub children: Vec<TreeNode>,
+}
+
+impl BundleStats {
+    /// Gather the chunks of a generated bundle; assets are ignored.
+    pub fn from_bundle(bundle: &[Output]) -> Self {
+        let chunks = bundle
+            .iter()
+            .filter_map(|output| match output {
+                Output::Chunk(chunk) => Some(ChunkStats {
+                    filename: chunk.filename.to_string(),
+                    size: chunk.code.len(),
+                    is_entry: chunk.is_entry,
+                    is_dynamic_entry: chunk.is_dynamic_entry,
+                    imports: chunk.imports.iter().map(|s| s.to_string()).collect(),
+                    dynamic_imports: chunk
+                        .dynamic_imports
+                        .iter()
+                        .map(|s| s.to_string())
+                        .collect(),
+                    modules: chunk
+                        .modules
+                        .keys
+                        .iter()
+                        .zip(chunk.modules.values.iter())
+                        .map(|(id, rendered)| ModuleStats {
+                            id: id.to_string(),
+                            size: rendered.code().map(|code| code.len()).unwrap_or(0),
+                        })
+                        .collect(),
+                }),
+                Output::Asset(_) => None,
+            })
+            .collect();
+        Self { chunks }
+    }
+
+    /// The bundle as a tree of chunks, directories and modules.
+    ///
+    /// Module paths are shown relative to `root`, or to the deepest
+    /// directory shared by every module when `root` is `None`. Modules
+    /// removed entirely by tree shaking are left out.
+    pub fn tree(&self, root: Option<&Path>) -> TreeNode {
+        let root: Vec<String> = match root {
+            Some(root) => segments(&root.to_string_lossy())
+                .map(str::to_string)
+                .collect(),
+            None => self.common_directory(),
+        };
+
+        let children = self
+            .chunks
+            .iter()
+            .map(|chunk| {
+                let mut dir = Dir::default();
+                for module in chunk.modules.iter().filter(|module| module.size > 0) {
+                    let mut path: Vec<&str> = segments(&module.id).collect();
+                    if path.len() > root.len() && path.iter().zip(&root).all(|(a, b)| a == b) {
+                        path.drain(..root.len());
+                    }
+                    dir.insert(&path, module.size);
+                }
+                TreeNode {
+                    name: chunk.filename.clone(),
+                    size: None,
+                    children: dir.into_nodes(),
+                }
+            })
+            .collect();
+
+        TreeNode {
+            name: "bundle".to_string(),
+            size: None,
+            children,
+        }
+    }
+
+    /// Deepest directory containing every module that has a file path.
+    fn common_directory(&self) -> Vec<String> {
+        let mut common: Option<Vec<&str>> = None;
+        for module in self.chunks.iter().flat_map(|chunk| &chunk.modules) {
+            if !is_file_path(&module.id) {
+                continue;
+            }
+            let path: Vec<&str> = segments(&module.id).collect();
+            let directory = &path[..path.len().saturating_sub(1)];
+            common = Some(match common {
+                None => directory.to_vec(),
+                Some(common) => common
+                    .into_iter()
+                    .zip(directory)
+                    .take_while(|(a, b)| a == *b)
+                    .map(|(a, _)| a)
+                    .collect(),
+            });
+        }
+        common
+            .unwrap_or_default()
+            .into_iter()
+            .map(str::to_string)
+            .collect()
+    }
+}
+
+/// Path segments of a module id; virtual ids lose their `\0` marker.
+fn segments(id: &str) -> impl Iterator<Item = &str> {
+    id.trim_start_matches('\0')
+        .split(['/', '\\'])
+        .filter(|segment| !segment.is_empty())
+}
+
+fn is_file_path(id: &str) -> bool {
+    id.starts_with('/') || Path::new(id).is_absolute()
+}
+
+#[derive(Default)]
+struct Dir<'a> {
+    dirs: BTreeMap<&'a str, Dir<'a>>,
+    files: BTreeMap<&'a str, usize>,
+}
+
+impl<'a> Dir<'a> {
+    fn insert(&mut self, path: &[&'a str], size: usize) {
+        match path {
+            [] => {}
+            [file] => *self.files.entry(file).or_default() += size,
+            [dir, rest @ ..] => self.dirs.entry(dir).or_default().insert(rest, size),
+        }
+    }
+
+    fn into_nodes(self) -> Vec<TreeNode> {
+        let mut nodes = Vec::new();
+        for (name, mut dir) in self.dirs {
+            // `src` > `components` > `ui` becomes `src/components/ui`
+            let mut name = name.to_string();
+            while dir.files.is_empty() && dir.dirs.len() == 1 {
+                let Some((child, inner)) = dir.dirs.pop_first() else {
+                    break;
+                };
+                name = format!("{}/{}", name, child);
+                dir = inner;
+            }
+            nodes.push(TreeNode {
+                name,
+                size: None,
+                children: dir.into_nodes(),
+            });
+        }
+        nodes.extend(self.files.into_iter().map(|(name, size)| TreeNode {
+            name: name.to_string(),
+            size: Some(size),
+            children: Vec::new(),
+        }));
+        nodes
+    }
+}
@@ -0,0 +1,138 @@
+//! Integration tests for fob-plugin-visualizer.
+
+use fob_bundler::{Output, OutputAsset};
+use fob_plugin_visualizer::{
+    BundleStats, ChunkStats, ModuleStats, TreeNode, VisualizerOptions, VisualizerPlugin,
+    VisualizerView,
+};
+use std::path::Path;
+use std::sync::Arc;
+
+fn module(id: &str, size: usize) -> ModuleStats {
+    ModuleStats {
+        id: id.to_string(),
+        size,
+    }
+}
+
+fn leaf(name: &str, size: usize) -> TreeNode {
+    TreeNode {
+        name: name.to_string(),
+        size: Some(size),
+        children: vec![],
+    }
+}
+
+fn dir(name: &str, children: Vec<TreeNode>) -> TreeNode {
+    TreeNode {
+        name: name.to_string(),
+        size: None,
+        children,
+    }
+}
+
+fn stats() -> BundleStats {
+    BundleStats {
+        chunks: vec![
+            ChunkStats {
+                filename: "index.js".to_string(),
+                size: 1200,
+                is_entry: true,
+                imports: vec!["chunk-a1.js".to_string()],
+                modules: vec![
+                    module("/app/src/index.ts", 300),
+                    module("/app/src/components/ui/Button.tsx", 500),
+                    module("/app/src/unused.ts", 0),
+                    module("\0rolldown/runtime.js", 100),
+                ],
+                ..Default::default()
+            },
+            ChunkStats {
+                filename: "chunk-a1.js".to_string(),
+                size: 400,
+                modules: vec![module("/app/node_modules/lodash/get.js", 350)],
+                ..Default::default()
+            },
+        ],
+    }
+}
+
+#[test]
+fn builds_tree_relative_to_common_directory() {
+    assert_eq!(
+        stats().tree(None),
+        dir(
+            "bundle",
+            vec![
+                dir(
+                    "index.js",
+                    vec![
+                        dir("rolldown", vec![leaf("runtime.js", 100)]),
+                        dir(
+                            "src",
+                            vec![
+                                dir("components/ui", vec![leaf("Button.tsx", 500)]),
+                                leaf("index.ts", 300),
+                            ]
+                        ),
+                    ]
+                ),
+                dir(
+                    "chunk-a1.js",
+                    vec![dir("node_modules/lodash", vec![leaf("get.js", 350)])]
+                ),
+            ]
+        )
+    );
+}
+
+#[test]
+fn builds_tree_relative_to_root() {
+    let tree = stats().tree(Some(Path::new("/app/src")));
+
+    let names: Vec<&str> = tree.children[0]
+        .children
+        .iter()
+        .map(|node| node.name.as_str())
+        .collect();
+    assert_eq!(names, ["components/ui", "rolldown", "index.ts"]);
+    assert_eq!(tree.children[1].children[0].name, "app/node_modules/lodash");
+}
+
+#[test]
+fn renders_self_contained_report() {
+    let plugin = VisualizerPlugin::with_options(
+        VisualizerOptions::new()
+            .with_title("App <prod>")
+            .with_view(VisualizerView::Sunburst),
+    );
+    let mut stats = stats();
+    stats.chunks[1]
+        .modules
+        .push(module("/app/src/<script>.js", 10));
+
+    let html = plugin.render(&stats);
+
+    assert!(html.starts_with("<!doctype html>"));
+    assert!(html.contains("<title>App &lt;prod&gt;</title>"));
+    assert!(html.contains("let view = \"sunburst\";"));
+    assert!(html.contains("\"name\":\"Button.tsx\",\"size\":500"));
+    assert!(html.contains("\"filename\":\"index.js\",\"size\":1200,\"is_entry\":true"));
+    assert!(html.contains("\\u003cscript>.js"));
+    assert_eq!(html.matches("</script>").count(), 1);
+    assert!(!html.contains("__DATA__"));
+    assert!(!html.contains("<script src"));
+}
+
+#[test]
+fn ignores_assets() {
+    let bundle = vec![Output::Asset(Arc::new(OutputAsset {
+        names: vec![],
+        original_file_names: vec![],
+        filename: "logo.svg".into(),
+        source: b"<svg/>".to_vec().into(),
+    }))];
+
+    assert!(BundleStats::from_bundle(&bundle).chunks.is_empty());
+    assert_eq!(VisualizerPlugin::new().options().filename, "stats.html");
+}
@@ -1,7 +1,9 @@
 //! Auto-detection of deployment targets from project files.
 
 use crate::target::DeploymentTarget;
-use crate::targets::{BrowserTarget, CloudflareWorkersTarget, VercelNodeTarget};
+use crate::targets::{
+    BrowserTarget, BunTarget, CloudflareWorkersTarget, DenoTarget, VercelNodeTarget,
+};
 use std::path::Path;
 
 /// Detect deployment target from project files
@@ -9,6 +11,8 @@ use std::path::Path;
 /// Checks for common deployment configuration files:
 /// - `vercel.json` or `.vercel/` → VercelNodeTarget
 /// - `wrangler.toml` or `_routes.json` → CloudflareWorkersTarget
+/// - `deno.json` or `deno.jsonc` → DenoTarget
+/// - `bunfig.toml`, `bun.lock` or `bun.lockb` → BunTarget
 /// - Otherwise → BrowserTarget (default)
 pub fn detect_target(project_root: &Path) -> Box<dyn DeploymentTarget> {
     // Check for Vercel
@@ -18,7 +22,20 @@ pub fn detect_target(project_root: &Path) -> Box<dyn DeploymentTarget> {
 
     // Check for Cloudflare Workers
     if project_root.join("wrangler.toml").exists() || project_root.join("_routes.json").exists() {
-        return Box::new(CloudflareWorkersTarget);
+        return Box::new(CloudflareWorkersTarget::new());
+    }
+
+    // Check for Deno
+    if project_root.join("deno.json").exists() || project_root.join("deno.jsonc").exists() {
+        return Box::new(DenoTarget);
+    }
+
+    // Check for Bun
+    if ["bunfig.toml", "bun.lock", "bun.lockb"]
+        .iter()
+        .any(|file| project_root.join(file).exists())
+    {
+        return Box::new(BunTarget::new());
     }
 
     // Default to browser
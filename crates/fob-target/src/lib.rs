@@ -2,8 +2,9 @@
 //!
 //! Deployment target adapters for Fob bundler.
 //!
-//! This crate provides adapters for different deployment targets (Vercel, Cloudflare, Browser)
-//! that configure module resolution, export conditions, and output generation.
+//! This crate provides adapters for different deployment targets (Vercel, Cloudflare,
+//! AWS Lambda, Deno, Bun, Browser) that configure module resolution, export conditions,
+//! and output generation.
 
 pub mod detection;
 pub mod target;
@@ -0,0 +1,234 @@
+//! Bun deployment target.
+
+use crate::target::DeploymentTarget;
+use fob_bundler::{
+    ExportConditions, HookResolveIdArgs, HookResolveIdOutput, HookResolveIdReturn, HookUsage,
+    NodeBuiltins, Plugin, PluginContext, RuntimeEnvironment, SharedPluginable,
+};
+use std::borrow::Cow;
+use std::path::Path;
+use std::sync::Arc;
+
+/// Shebang written to CLI entry files
+const BUN_SHEBANG: &str = "#!/usr/bin/env bun";
+
+/// Bun deployment target
+///
+/// This target configures builds for the Bun runtime:
+/// - Prefers `bun` export conditions, then Node.js ones
+/// - Externalizes `bun:*` modules (e.g. `bun:ffi`, `bun:sqlite`) and Node.js built-ins
+/// - For CLI bundles, adds a Bun shebang to entry files and makes them executable
+#[derive(Debug, Clone, Copy, Default)]
+pub struct BunTarget {
+    cli: bool,
+}
+
+impl BunTarget {
+    /// Target for libraries and servers run with `bun`
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Target for command-line tools, whose entry files run directly
+    pub fn cli() -> Self {
+        Self { cli: true }
+    }
+}
+
+impl DeploymentTarget for BunTarget {
+    fn name(&self) -> &'static str {
+        if self.cli { "bun-cli" } else { "bun" }
+    }
+
+    fn runtime(&self) -> RuntimeEnvironment {
+        RuntimeEnvironment::Node
+    }
+
+    fn conditions(&self) -> ExportConditions {
+        ExportConditions::node()
+    }
+
+    fn custom_conditions(&self) -> Vec<String> {
+        vec!["bun".to_string()]
+    }
+
+    fn node_builtins(&self) -> NodeBuiltins {
+        NodeBuiltins::External
+    }
+
+    fn plugins(&self) -> Vec<SharedPluginable> {
+        vec![Arc::new(BunModulesPlugin)]
+    }
+
+    fn generate_output(
+        &self,
+        build_result: &fob_bundler::BuildResult,
+        output_dir: &Path,
+    ) -> fob_bundler::Result<()> {
+        if !self.cli {
+            return Ok(());
+        }
+
+        for chunk in build_result.entry_chunks() {
+            let path = output_dir.join(chunk.filename.as_str());
+            if path.is_file() {
+                write_shebang(&path)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Plugin keeping Bun's own modules external
+///
+/// `bun` and `bun:*` imports only exist inside the Bun runtime, so they
+/// can't be bundled.
+#[derive(Debug, Clone, Copy)]
+pub struct BunModulesPlugin;
+
+impl Plugin for BunModulesPlugin {
+    fn name(&self) -> Cow<'static, str> {
+        "fob-bun-modules".into()
+    }
+
+    fn register_hook_usage(&self) -> HookUsage {
+        HookUsage::ResolveId
+    }
+
+    fn resolve_id(
+        &self,
+        _ctx: &PluginContext,
+        args: &HookResolveIdArgs,
+    ) -> impl std::future::Future<Output = HookResolveIdReturn> + Send {
+        let specifier = args.specifier;
+        let external =
+            (specifier == "bun" || specifier.starts_with("bun:")).then(|| HookResolveIdOutput {
+                id: specifier.into(),
+                external: Some(true.into()),
+                ..Default::default()
+            });
+
+        async move { Ok(external) }
+    }
+}
+
+/// Put the Bun shebang on an entry file and make it executable.
+///
+/// An existing shebang is replaced. Otherwise the shebang adds a line, so
+/// the file's source map is shifted to match.
+fn write_shebang(path: &Path) -> fob_bundler::Result<()> {
+    let code = std::fs::read_to_string(path)?;
+    let code = match code.strip_prefix("#!") {
+        Some(rest) => {
+            let body = rest.split_once('\n').map_or("", |(_, body)| body);
+            format!("{}\n{}", BUN_SHEBANG, body)
+        }
+        None => {
+            shift_source_map(path)?;
+            format!("{}\n{}", BUN_SHEBANG, code)
+        }
+    };
+    std::fs::write(path, code)?;
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o755))?;
+    }
+
+    Ok(())
+}
+
+/// Move a file's source map mappings down one line.
+fn shift_source_map(path: &Path) -> fob_bundler::Result<()> {
+    let mut map_path = path.as_os_str().to_owned();
+    map_path.push(".map");
+    let map_path = Path::new(&map_path);
+    if !map_path.is_file() {
+        return Ok(());
+    }
+
+    let json_error = |e: serde_json::Error| {
+        fob_bundler::Error::InvalidConfig(format!("JSON error in {}: {}", map_path.display(), e))
+    };
+    let mut map: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(map_path)?).map_err(json_error)?;
+    if let Some(mappings) = map.get_mut("mappings") {
+        if let Some(current) = mappings.as_str() {
+            *mappings = format!(";{}", current).into();
+        }
+    }
+    std::fs::write(map_path, serde_json::to_string(&map).map_err(json_error)?)?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cli_target_options() {
+        assert_eq!(BunTarget::new().name(), "bun");
+        assert_eq!(BunTarget::cli().name(), "bun-cli");
+        assert_eq!(
+            BunTarget::cli().custom_conditions(),
+            vec!["bun".to_string()]
+        );
+        assert!(matches!(
+            BunTarget::cli().node_builtins(),
+            NodeBuiltins::External
+        ));
+    }
+
+    #[test]
+    fn shebang_shifts_source_map() {
+        let dir = tempfile::tempdir().unwrap();
+        let entry = dir.path().join("cli.js");
+        std::fs::write(&entry, "console.log(1);\n").unwrap();
+        std::fs::write(
+            dir.path().join("cli.js.map"),
+            r#"{"version":3,"sources":["cli.ts"],"mappings":"AAAA"}"#,
+        )
+        .unwrap();
+
+        write_shebang(&entry).unwrap();
+
+        assert_eq!(
+            std::fs::read_to_string(&entry).unwrap(),
+            "#!/usr/bin/env bun\nconsole.log(1);\n"
+        );
+        let map: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(dir.path().join("cli.js.map")).unwrap())
+                .unwrap();
+        assert_eq!(map["mappings"], ";AAAA");
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = std::fs::metadata(&entry).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o755);
+        }
+    }
+
+    #[test]
+    fn existing_shebang_is_replaced() {
+        let dir = tempfile::tempdir().unwrap();
+        let entry = dir.path().join("cli.js");
+        std::fs::write(&entry, "#!/usr/bin/env node\nconsole.log(1);\n").unwrap();
+        let map = r#"{"version":3,"sources":["cli.ts"],"mappings":";AAAA"}"#;
+        std::fs::write(dir.path().join("cli.js.map"), map).unwrap();
+
+        write_shebang(&entry).unwrap();
+
+        assert_eq!(
+            std::fs::read_to_string(&entry).unwrap(),
+            "#!/usr/bin/env bun\nconsole.log(1);\n"
+        );
+        // The line count is unchanged, so the map is too
+        assert_eq!(
+            std::fs::read_to_string(dir.path().join("cli.js.map")).unwrap(),
+            map
+        );
+    }
+}
@@ -1,16 +1,122 @@
 //! Cloudflare Workers deployment target.
 
 use crate::target::DeploymentTarget;
-use fob_bundler::{ExportConditions, NodeBuiltins, RuntimeEnvironment};
+use fob_bundler::{
+    ExportConditions, HookResolveIdArgs, HookResolveIdOutput, HookResolveIdReturn, HookUsage,
+    NodeBuiltins, OutputFormat, Plugin, PluginContext, RuntimeEnvironment, SharedPluginable,
+};
+use std::borrow::Cow;
+use std::io::Write;
 use std::path::Path;
+use std::sync::Arc;
+
+/// Workers plan, which decides the script size limit
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CloudflarePlan {
+    /// Workers Free: 1 MB compressed
+    #[default]
+    Free,
+    /// Workers Paid: 5 MB compressed
+    Paid,
+}
+
+impl CloudflarePlan {
+    /// Maximum gzip-compressed size of a worker's modules, in bytes
+    pub fn script_size_limit(&self) -> usize {
+        match self {
+            Self::Free => 1024 * 1024,
+            Self::Paid => 5 * 1024 * 1024,
+        }
+    }
+}
 
 /// Cloudflare Workers deployment target
 ///
 /// This target configures builds for Cloudflare Workers:
-/// - Uses edge worker export conditions
-/// - Errors on Node.js built-in imports
-/// - Can generate wrangler.toml or _routes.json if needed
-pub struct CloudflareWorkersTarget;
+/// - Uses edge worker export conditions and ESM output
+/// - Errors on Node.js built-in imports, and externalizes `cloudflare:*` modules
+/// - Checks the compressed worker size against the plan's limit
+/// - Generates `wrangler.toml` pointing at the bundled worker, with module
+///   rules for the emitted chunks and source map upload enabled
+#[derive(Debug, Clone, Default)]
+pub struct CloudflareWorkersTarget {
+    plan: CloudflarePlan,
+    worker_name: Option<String>,
+    compatibility_date: Option<String>,
+}
+
+impl CloudflareWorkersTarget {
+    /// Target for the Workers Free plan
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the Workers plan used for the size limit
+    pub fn plan(mut self, plan: CloudflarePlan) -> Self {
+        self.plan = plan;
+        self
+    }
+
+    /// Set the worker name (default: the `name` in `package.json`)
+    pub fn worker_name(mut self, name: impl Into<String>) -> Self {
+        self.worker_name = Some(name.into());
+        self
+    }
+
+    /// Set the Workers compatibility date, as `YYYY-MM-DD` (default: the
+    /// build date)
+    pub fn compatibility_date(mut self, date: impl Into<String>) -> Self {
+        self.compatibility_date = Some(date.into());
+        self
+    }
+
+    /// Fail if the compressed worker exceeds the plan's size limit
+    fn check_size(&self, size: usize) -> fob_bundler::Result<()> {
+        let limit = self.plan.script_size_limit();
+        if size <= limit {
+            return Ok(());
+        }
+        let hint = match self.plan {
+            CloudflarePlan::Free => " (the Paid plan allows 5 MB)",
+            CloudflarePlan::Paid => "",
+        };
+        Err(fob_bundler::Error::InvalidConfig(format!(
+            "Worker is {} compressed, over the {} limit of the Workers {:?} plan{}",
+            format_size(size),
+            format_size(limit),
+            self.plan,
+            hint
+        )))
+    }
+
+    /// `wrangler.toml` for a worker whose entry script is `main`
+    fn wrangler_toml(&self, main: &str) -> String {
+        let name = self.worker_name.clone().unwrap_or_else(default_worker_name);
+        let compatibility_date = self
+            .compatibility_date
+            .clone()
+            .unwrap_or_else(|| chrono::Utc::now().format("%Y-%m-%d").to_string());
+        format!(
+            "# Generated by fob. `wrangler deploy` uploads this output as-is.\n\
+             name = {}\n\
+             main = {}\n\
+             compatibility_date = {}\n\
+             no_bundle = true\n\
+             upload_source_maps = true\n\
+             \n\
+             [[rules]]\n\
+             type = \"ESModule\"\n\
+             globs = [\"**/*.js\", \"**/*.mjs\"]\n\
+             \n\
+             [[rules]]\n\
+             type = \"CompiledWasm\"\n\
+             globs = [\"**/*.wasm\"]\n",
+            serde_json::Value::from(name),
+            serde_json::Value::from(main),
+            serde_json::Value::from(compatibility_date)
+        )
+    }
+}
 
 impl DeploymentTarget for CloudflareWorkersTarget {
     fn name(&self) -> &'static str {
@@ -29,13 +135,175 @@ impl DeploymentTarget for CloudflareWorkersTarget {
         NodeBuiltins::Error
     }
 
+    fn output_format(&self) -> Option<OutputFormat> {
+        Some(OutputFormat::Esm)
+    }
+
+    fn plugins(&self) -> Vec<SharedPluginable> {
+        vec![Arc::new(CloudflareModulesPlugin)]
+    }
+
     fn generate_output(
         &self,
-        _build_result: &fob_bundler::BuildResult,
-        _output_dir: &Path,
+        build_result: &fob_bundler::BuildResult,
+        output_dir: &Path,
     ) -> fob_bundler::Result<()> {
-        // Note: wrangler.toml and _routes.json are typically managed by the user
-        // or by Cloudflare's tooling. We don't generate them here to avoid conflicts.
+        let entries: Vec<_> = build_result.entry_chunks().collect();
+        let [entry] = entries.as_slice() else {
+            return Err(fob_bundler::Error::InvalidConfig(format!(
+                "A Cloudflare Worker is a single ESM script, but the build has {} entry points",
+                entries.len()
+            )));
+        };
+
+        let mut size = 0;
+        for chunk in build_result.chunks() {
+            size += gzip_size(chunk.code.as_bytes())?;
+        }
+        self.check_size(size)?;
+
+        let wrangler = self.wrangler_toml(entry.filename.as_str());
+        std::fs::write(output_dir.join("wrangler.toml"), wrangler)?;
+
         Ok(())
     }
 }
+
+/// Plugin keeping Cloudflare runtime modules external
+///
+/// `cloudflare:*` imports (e.g. `cloudflare:sockets`, `cloudflare:workers`)
+/// are provided by the Workers runtime.
+#[derive(Debug, Clone, Copy)]
+pub struct CloudflareModulesPlugin;
+
+impl Plugin for CloudflareModulesPlugin {
+    fn name(&self) -> Cow<'static, str> {
+        "fob-cloudflare-modules".into()
+    }
+
+    fn register_hook_usage(&self) -> HookUsage {
+        HookUsage::ResolveId
+    }
+
+    fn resolve_id(
+        &self,
+        _ctx: &PluginContext,
+        args: &HookResolveIdArgs,
+    ) -> impl std::future::Future<Output = HookResolveIdReturn> + Send {
+        let specifier = args.specifier;
+        let external = specifier
+            .starts_with("cloudflare:")
+            .then(|| HookResolveIdOutput {
+                id: specifier.into(),
+                external: Some(true.into()),
+                ..Default::default()
+            });
+
+        async move { Ok(external) }
+    }
+}
+
+/// Size of content after gzip compression, as Cloudflare measures it.
+fn gzip_size(content: &[u8]) -> std::io::Result<usize> {
+    let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+    encoder.write_all(content)?;
+    Ok(encoder.finish()?.len())
+}
+
+fn format_size(bytes: usize) -> String {
+    format!("{:.2} MB", bytes as f64 / (1024.0 * 1024.0))
+}
+
+/// Worker name from the `package.json` in the working directory
+fn default_worker_name() -> String {
+    let package_name = std::fs::read_to_string("package.json")
+        .ok()
+        .and_then(|json| serde_json::from_str::<serde_json::Value>(&json).ok())
+        .and_then(|package| package.get("name")?.as_str().map(str::to_string));
+    worker_name_from_package(package_name.as_deref().unwrap_or("worker"))
+}
+
+/// Worker names are lowercase letters, digits and dashes, so a package
+/// name loses its scope and other characters become dashes
+fn worker_name_from_package(package_name: &str) -> String {
+    let name = package_name.rsplit('/').next().unwrap_or(package_name);
+    let name: String = name
+        .chars()
+        .map(|c| match c.to_ascii_lowercase() {
+            c @ ('a'..='z' | '0'..='9') => c,
+            _ => '-',
+        })
+        .collect();
+    match name.trim_matches('-') {
+        "" => "worker".to_string(),
+        name => name.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn size_limit_depends_on_plan() {
+        let free = CloudflareWorkersTarget::new();
+        assert!(free.check_size(1024 * 1024).is_ok());
+
+        let err = free.check_size(2 * 1024 * 1024).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid configuration: Worker is 2.00 MB compressed, over the 1.00 MB limit \
+             of the Workers Free plan (the Paid plan allows 5 MB)"
+        );
+
+        let paid = CloudflareWorkersTarget::new().plan(CloudflarePlan::Paid);
+        assert!(paid.check_size(2 * 1024 * 1024).is_ok());
+        let err = paid.check_size(6 * 1024 * 1024).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid configuration: Worker is 6.00 MB compressed, over the 5.00 MB limit \
+             of the Workers Paid plan"
+        );
+    }
+
+    #[test]
+    fn wrangler_toml_content() {
+        let target = CloudflareWorkersTarget::new()
+            .worker_name("my-worker")
+            .compatibility_date("2026-01-15");
+
+        assert_eq!(
+            target.wrangler_toml("index.js"),
+            "# Generated by fob. `wrangler deploy` uploads this output as-is.\n\
+             name = \"my-worker\"\n\
+             main = \"index.js\"\n\
+             compatibility_date = \"2026-01-15\"\n\
+             no_bundle = true\n\
+             upload_source_maps = true\n\
+             \n\
+             [[rules]]\n\
+             type = \"ESModule\"\n\
+             globs = [\"**/*.js\", \"**/*.mjs\"]\n\
+             \n\
+             [[rules]]\n\
+             type = \"CompiledWasm\"\n\
+             globs = [\"**/*.wasm\"]\n"
+        );
+    }
+
+    #[test]
+    fn wrangler_toml_defaults_to_build_date() {
+        let toml = CloudflareWorkersTarget::new()
+            .worker_name("api")
+            .wrangler_toml("index.js");
+        let today = chrono::Utc::now().format("%Y-%m-%d").to_string();
+        assert!(toml.contains(&format!("compatibility_date = \"{}\"\n", today)));
+    }
+
+    #[test]
+    fn worker_names_from_package_names() {
+        assert_eq!(worker_name_from_package("api"), "api");
+        assert_eq!(worker_name_from_package("@acme/Edge_API"), "edge-api");
+        assert_eq!(worker_name_from_package("@acme/"), "worker");
+    }
+}
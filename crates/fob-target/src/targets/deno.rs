@@ -0,0 +1,252 @@
+//! Deno deployment target.
+
+use crate::target::DeploymentTarget;
+use fob_bundler::{
+    ExportConditions, HookResolveIdArgs, HookResolveIdOutput, HookResolveIdReturn, HookUsage,
+    NodeBuiltins, Plugin, PluginContext, RuntimeEnvironment, SharedPluginable,
+};
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::sync::Arc;
+
+/// Node.js built-in modules, which Deno only accepts with a `node:` prefix
+const NODE_BUILTINS: &[&str] = &[
+    "assert",
+    "async_hooks",
+    "buffer",
+    "child_process",
+    "cluster",
+    "console",
+    "constants",
+    "crypto",
+    "dgram",
+    "diagnostics_channel",
+    "dns",
+    "domain",
+    "events",
+    "fs",
+    "http",
+    "http2",
+    "https",
+    "inspector",
+    "module",
+    "net",
+    "os",
+    "path",
+    "perf_hooks",
+    "process",
+    "punycode",
+    "querystring",
+    "readline",
+    "repl",
+    "stream",
+    "string_decoder",
+    "sys",
+    "timers",
+    "tls",
+    "trace_events",
+    "tty",
+    "url",
+    "util",
+    "v8",
+    "vm",
+    "wasi",
+    "worker_threads",
+    "zlib",
+];
+
+/// Deno deployment target
+///
+/// This target configures builds for the Deno runtime:
+/// - Prefers `deno` export conditions, then Node.js ones
+/// - Leaves `npm:` and `jsr:` specifiers for Deno to resolve
+/// - Externalizes Node.js built-ins as `node:` imports
+/// - Generates `deno.json` with an import map for the remaining externals
+pub struct DenoTarget;
+
+impl DeploymentTarget for DenoTarget {
+    fn name(&self) -> &'static str {
+        "deno"
+    }
+
+    fn runtime(&self) -> RuntimeEnvironment {
+        RuntimeEnvironment::EdgeWorker
+    }
+
+    fn conditions(&self) -> ExportConditions {
+        ExportConditions::node()
+    }
+
+    fn custom_conditions(&self) -> Vec<String> {
+        vec!["deno".to_string()]
+    }
+
+    fn node_builtins(&self) -> NodeBuiltins {
+        NodeBuiltins::External
+    }
+
+    fn plugins(&self) -> Vec<SharedPluginable> {
+        vec![Arc::new(DenoSpecifierPlugin)]
+    }
+
+    fn generate_output(
+        &self,
+        build_result: &fob_bundler::BuildResult,
+        output_dir: &Path,
+    ) -> fob_bundler::Result<()> {
+        let chunk_files: Vec<&str> = build_result
+            .chunks()
+            .map(|chunk| chunk.filename.as_str())
+            .collect();
+        let specifiers = build_result.chunks().flat_map(|chunk| {
+            chunk
+                .imports
+                .iter()
+                .chain(&chunk.dynamic_imports)
+                .map(|specifier| specifier.as_str())
+        });
+
+        let deno_json = serde_json::json!({ "imports": import_map(&chunk_files, specifiers) });
+        let json_str = serde_json::to_string_pretty(&deno_json)
+            .map_err(|e| fob_bundler::Error::InvalidConfig(format!("JSON error: {}", e)))?;
+        std::fs::write(output_dir.join("deno.json"), json_str)?;
+
+        Ok(())
+    }
+}
+
+/// Plugin resolving Deno specifiers
+///
+/// `npm:`, `jsr:` and `node:` specifiers are kept external for Deno to
+/// resolve at runtime, and bare Node.js built-ins are rewritten to their
+/// `node:` form.
+#[derive(Debug, Clone, Copy)]
+pub struct DenoSpecifierPlugin;
+
+impl Plugin for DenoSpecifierPlugin {
+    fn name(&self) -> Cow<'static, str> {
+        "fob-deno-specifiers".into()
+    }
+
+    fn register_hook_usage(&self) -> HookUsage {
+        HookUsage::ResolveId
+    }
+
+    fn resolve_id(
+        &self,
+        _ctx: &PluginContext,
+        args: &HookResolveIdArgs,
+    ) -> impl std::future::Future<Output = HookResolveIdReturn> + Send {
+        let id = deno_specifier(args.specifier);
+
+        async move {
+            Ok(id.map(|id| HookResolveIdOutput {
+                id: id.into(),
+                external: Some(true.into()),
+                ..Default::default()
+            }))
+        }
+    }
+}
+
+/// The external Deno specifier for an import, if Deno resolves it itself.
+fn deno_specifier(specifier: &str) -> Option<String> {
+    if ["npm:", "jsr:", "node:"]
+        .iter()
+        .any(|scheme| specifier.starts_with(scheme))
+    {
+        return Some(specifier.to_string());
+    }
+    is_node_builtin(specifier).then(|| format!("node:{}", specifier))
+}
+
+/// Whether a bare specifier names a Node.js built-in, including subpaths
+/// like `fs/promises`.
+fn is_node_builtin(specifier: &str) -> bool {
+    let name = specifier.split('/').next().unwrap_or(specifier);
+    NODE_BUILTINS.contains(&name)
+}
+
+/// Import map for the specifiers imported by the output's chunks.
+///
+/// Bare specifiers left in the output need a mapping to run on Deno;
+/// imports of other chunks don't.
+fn import_map<'a>(
+    chunk_files: &[&str],
+    specifiers: impl IntoIterator<Item = &'a str>,
+) -> BTreeMap<String, String> {
+    specifiers
+        .into_iter()
+        .filter(|specifier| !chunk_files.contains(specifier))
+        .filter_map(|specifier| Some((specifier.to_string(), import_map_target(specifier)?)))
+        .collect()
+}
+
+/// Import map entry for an external specifier left in the output.
+///
+/// Relative, absolute and URL specifiers, and those with a Deno scheme,
+/// resolve without one. Other bare specifiers are mapped to npm packages.
+fn import_map_target(specifier: &str) -> Option<String> {
+    if specifier.starts_with(['.', '/']) || specifier.contains("://") {
+        return None;
+    }
+    if let Some(id) = deno_specifier(specifier) {
+        return (id != specifier).then_some(id);
+    }
+    Some(format!("npm:{}", specifier))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deno_resolves_its_own_schemes() {
+        assert_eq!(deno_specifier("npm:react@18"), Some("npm:react@18".into()));
+        assert_eq!(
+            deno_specifier("jsr:@std/path"),
+            Some("jsr:@std/path".into())
+        );
+        assert_eq!(deno_specifier("node:fs"), Some("node:fs".into()));
+        assert_eq!(
+            deno_specifier("fs/promises"),
+            Some("node:fs/promises".into())
+        );
+        assert_eq!(deno_specifier("react"), None);
+        assert_eq!(deno_specifier("https://deno.land/x/oak/mod.ts"), None);
+        assert_eq!(deno_specifier("./util.js"), None);
+    }
+
+    #[test]
+    fn import_map_targets() {
+        assert_eq!(import_map_target("react"), Some("npm:react".into()));
+        assert_eq!(
+            import_map_target("@scope/pkg/sub"),
+            Some("npm:@scope/pkg/sub".into())
+        );
+        assert_eq!(import_map_target("path"), Some("node:path".into()));
+        assert_eq!(import_map_target("npm:react"), None);
+        assert_eq!(import_map_target("jsr:@std/path"), None);
+        assert_eq!(import_map_target("https://esm.sh/react"), None);
+        assert_eq!(import_map_target("./chunk.js"), None);
+        assert_eq!(import_map_target("/abs/chunk.js"), None);
+    }
+
+    #[test]
+    fn import_map_skips_chunks() {
+        let map = import_map(
+            &["index.js", "chunk-a1b2.js"],
+            ["chunk-a1b2.js", "react", "npm:preact", "crypto", "react"],
+        );
+        assert_eq!(
+            serde_json::json!({ "imports": map }),
+            serde_json::json!({
+                "imports": {
+                    "crypto": "node:crypto",
+                    "react": "npm:react",
+                }
+            })
+        );
+    }
+}
@@ -0,0 +1,327 @@
+//! AWS Lambda and Lambda@Edge deployment targets.
+
+use crate::target::DeploymentTarget;
+use fob_bundler::{
+    ExportConditions, HookResolveIdArgs, HookResolveIdOutput, HookResolveIdReturn, HookUsage,
+    NodeBuiltins, OutputFormat, Plugin, PluginContext, RuntimeEnvironment, SharedPluginable,
+};
+use std::borrow::Cow;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// Name of the generated handler module, so the function handler is `lambda.handler`
+const WRAPPER_NAME: &str = "lambda";
+
+/// Module format of a Lambda handler
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LambdaFormat {
+    /// ES module handler (`lambda.mjs`)
+    Esm,
+    /// CommonJS handler (`lambda.cjs`)
+    Cjs,
+}
+
+/// AWS Lambda deployment target
+///
+/// This target configures builds for the Lambda Node.js runtimes:
+/// - Uses Node.js export conditions and externalizes Node.js built-ins
+/// - Externalizes the AWS SDK v3 (`@aws-sdk/*`, `@smithy/*`), which the runtime provides
+/// - Generates a `lambda.mjs` or `lambda.cjs` wrapper exporting `handler`,
+///   with source maps enabled so CloudWatch stack traces point at the source
+/// - Packages the output directory into `<output_dir>.zip`
+///
+/// The entry point must export a `handler` function.
+#[derive(Debug, Clone, Copy)]
+pub struct LambdaTarget {
+    format: LambdaFormat,
+    edge: bool,
+}
+
+impl LambdaTarget {
+    /// Target for regional Lambda functions, with an ESM handler
+    pub fn new() -> Self {
+        Self {
+            format: LambdaFormat::Esm,
+            edge: false,
+        }
+    }
+
+    /// Target for Lambda@Edge functions, with a CommonJS handler
+    pub fn edge() -> Self {
+        Self {
+            format: LambdaFormat::Cjs,
+            edge: true,
+        }
+    }
+
+    /// Set the handler module format
+    pub fn format(mut self, format: LambdaFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// The generated handler module
+    fn wrapper(&self, entry: &str) -> (String, String) {
+        let entry = serde_json::Value::from(format!("./{}", entry));
+        match self.format {
+            LambdaFormat::Esm => (
+                format!("{}.mjs", WRAPPER_NAME),
+                format!(
+                    "process.setSourceMapsEnabled(true);\n\
+                     const {{ handler }} = await import({});\n\
+                     export {{ handler }};\n",
+                    entry
+                ),
+            ),
+            LambdaFormat::Cjs => (
+                format!("{}.cjs", WRAPPER_NAME),
+                format!(
+                    "process.setSourceMapsEnabled(true);\n\
+                     module.exports = require({});\n",
+                    entry
+                ),
+            ),
+        }
+    }
+}
+
+impl Default for LambdaTarget {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DeploymentTarget for LambdaTarget {
+    fn name(&self) -> &'static str {
+        if self.edge {
+            "aws-lambda-edge"
+        } else {
+            "aws-lambda"
+        }
+    }
+
+    fn runtime(&self) -> RuntimeEnvironment {
+        RuntimeEnvironment::Node
+    }
+
+    fn conditions(&self) -> ExportConditions {
+        ExportConditions::node()
+    }
+
+    fn node_builtins(&self) -> NodeBuiltins {
+        NodeBuiltins::External
+    }
+
+    fn output_format(&self) -> Option<OutputFormat> {
+        Some(match self.format {
+            LambdaFormat::Esm => OutputFormat::Esm,
+            LambdaFormat::Cjs => OutputFormat::Cjs,
+        })
+    }
+
+    fn plugins(&self) -> Vec<SharedPluginable> {
+        vec![Arc::new(AwsSdkPlugin)]
+    }
+
+    fn generate_output(
+        &self,
+        build_result: &fob_bundler::BuildResult,
+        output_dir: &Path,
+    ) -> fob_bundler::Result<()> {
+        let entries: Vec<_> = build_result.entry_chunks().collect();
+        let [entry] = entries.as_slice() else {
+            return Err(fob_bundler::Error::InvalidConfig(format!(
+                "{} builds need exactly one entry point, found {}",
+                self.name(),
+                entries.len()
+            )));
+        };
+
+        self.package(entry.filename.as_str(), output_dir)
+    }
+}
+
+impl LambdaTarget {
+    /// Write the handler module and manifest next to the bundled `entry`,
+    /// then zip the output directory.
+    fn package(&self, entry: &str, output_dir: &Path) -> fob_bundler::Result<()> {
+        let (wrapper_name, wrapper) = self.wrapper(entry);
+        std::fs::write(output_dir.join(wrapper_name), wrapper)?;
+
+        // The bundle itself is ESM, which Node only loads from `.js` with this
+        let package_json_path = output_dir.join("package.json");
+        if self.format == LambdaFormat::Esm && !package_json_path.exists() {
+            let package_json = serde_json::json!({ "type": "module" });
+            let json_str = serde_json::to_string_pretty(&package_json)
+                .map_err(|e| fob_bundler::Error::InvalidConfig(format!("JSON error: {}", e)))?;
+            std::fs::write(&package_json_path, json_str)?;
+        }
+
+        write_zip(output_dir)
+    }
+}
+
+/// Plugin keeping the AWS SDK out of the bundle
+///
+/// The Lambda Node.js runtimes ship the AWS SDK v3, so bundling it only
+/// grows the deployment package.
+#[derive(Debug, Clone, Copy)]
+pub struct AwsSdkPlugin;
+
+impl Plugin for AwsSdkPlugin {
+    fn name(&self) -> Cow<'static, str> {
+        "fob-aws-sdk".into()
+    }
+
+    fn register_hook_usage(&self) -> HookUsage {
+        HookUsage::ResolveId
+    }
+
+    fn resolve_id(
+        &self,
+        _ctx: &PluginContext,
+        args: &HookResolveIdArgs,
+    ) -> impl std::future::Future<Output = HookResolveIdReturn> + Send {
+        let specifier = args.specifier;
+        let external = (specifier.starts_with("@aws-sdk/") || specifier.starts_with("@smithy/"))
+            .then(|| HookResolveIdOutput {
+                id: specifier.into(),
+                external: Some(true.into()),
+                ..Default::default()
+            });
+
+        async move { Ok(external) }
+    }
+}
+
+/// Package the output directory into `<output_dir>.zip` next to it.
+fn write_zip(output_dir: &Path) -> fob_bundler::Result<()> {
+    let zip_error = |e: zip::result::ZipError| {
+        fob_bundler::Error::WriteFailure(format!("Failed to write Lambda package: {}", e))
+    };
+
+    let mut files = Vec::new();
+    collect_files(output_dir, &mut files)?;
+    files.sort();
+
+    let zip_path = output_dir.with_extension("zip");
+    let mut zip = zip::ZipWriter::new(std::fs::File::create(&zip_path)?);
+    let options = zip::write::SimpleFileOptions::default()
+        .compression_method(zip::CompressionMethod::Deflated)
+        .unix_permissions(0o644);
+    for file in files {
+        let Ok(relative) = file.strip_prefix(output_dir) else {
+            continue;
+        };
+        let name = relative
+            .components()
+            .map(|component| component.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/");
+        zip.start_file(name, options).map_err(zip_error)?;
+        zip.write_all(&std::fs::read(&file)?)?;
+    }
+    zip.finish().map_err(zip_error)?;
+
+    Ok(())
+}
+
+/// Recursively collect the files under a directory.
+fn collect_files(dir: &Path, files: &mut Vec<PathBuf>) -> std::io::Result<()> {
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            collect_files(&path, files)?;
+        } else {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Read;
+
+    /// Output directory holding a bundled `index.js`
+    fn output_dir() -> (tempfile::TempDir, PathBuf) {
+        let root = tempfile::tempdir().unwrap();
+        let dir = root.path().join("dist");
+        std::fs::create_dir_all(dir.join("chunks")).unwrap();
+        std::fs::write(dir.join("index.js"), "export const handler = () => 1;\n").unwrap();
+        std::fs::write(dir.join("chunks/shared.js"), "export const x = 1;\n").unwrap();
+        (root, dir)
+    }
+
+    fn zip_entries(path: &Path) -> Vec<String> {
+        let archive = zip::ZipArchive::new(std::fs::File::open(path).unwrap()).unwrap();
+        archive.file_names().map(str::to_string).collect()
+    }
+
+    #[test]
+    fn esm_handler_package() {
+        let (_root, dir) = output_dir();
+
+        LambdaTarget::new().package("index.js", &dir).unwrap();
+
+        assert_eq!(
+            std::fs::read_to_string(dir.join("lambda.mjs")).unwrap(),
+            "process.setSourceMapsEnabled(true);\n\
+             const { handler } = await import(\"./index.js\");\n\
+             export { handler };\n"
+        );
+        let manifest: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(dir.join("package.json")).unwrap())
+                .unwrap();
+        assert_eq!(manifest, serde_json::json!({ "type": "module" }));
+
+        let zip_path = dir.with_extension("zip");
+        let mut entries = zip_entries(&zip_path);
+        entries.sort();
+        assert_eq!(
+            entries,
+            ["chunks/shared.js", "index.js", "lambda.mjs", "package.json"]
+        );
+        let mut archive = zip::ZipArchive::new(std::fs::File::open(&zip_path).unwrap()).unwrap();
+        let mut handler = String::new();
+        archive
+            .by_name("lambda.mjs")
+            .unwrap()
+            .read_to_string(&mut handler)
+            .unwrap();
+        assert!(handler.contains("await import(\"./index.js\")"));
+    }
+
+    #[test]
+    fn edge_handler_is_commonjs_without_manifest() {
+        let (_root, dir) = output_dir();
+
+        let target = LambdaTarget::edge();
+        target.package("index.js", &dir).unwrap();
+
+        assert_eq!(target.name(), "aws-lambda-edge");
+        assert_eq!(
+            std::fs::read_to_string(dir.join("lambda.cjs")).unwrap(),
+            "process.setSourceMapsEnabled(true);\n\
+             module.exports = require(\"./index.js\");\n"
+        );
+        assert!(!dir.join("package.json").exists());
+        assert!(zip_entries(&dir.with_extension("zip")).contains(&"lambda.cjs".to_string()));
+    }
+
+    #[test]
+    fn existing_manifest_is_kept() {
+        let (_root, dir) = output_dir();
+        let manifest = r#"{"type":"module","dependencies":{"sharp":"^0.33.0"}}"#;
+        std::fs::write(dir.join("package.json"), manifest).unwrap();
+
+        LambdaTarget::new().package("index.js", &dir).unwrap();
+
+        assert_eq!(
+            std::fs::read_to_string(dir.join("package.json")).unwrap(),
+            manifest
+        );
+    }
+}
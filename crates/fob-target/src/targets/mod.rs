@@ -1,9 +1,15 @@
 //! Built-in deployment targets.
 
 pub mod browser;
+pub mod bun;
 pub mod cloudflare;
+pub mod deno;
+pub mod lambda;
 pub mod vercel;
 
 pub use browser::BrowserTarget;
-pub use cloudflare::CloudflareWorkersTarget;
+pub use bun::{BunModulesPlugin, BunTarget};
+pub use cloudflare::{CloudflareModulesPlugin, CloudflarePlan, CloudflareWorkersTarget};
+pub use deno::{DenoSpecifierPlugin, DenoTarget};
+pub use lambda::{AwsSdkPlugin, LambdaFormat, LambdaTarget};
 pub use vercel::VercelNodeTarget;
@@ -7,7 +7,7 @@
 //!
 //! - Multiple entry points
 //! - Code splitting across entries
-//! - Multiple output formats (ESM, CJS, IIFE)
+//! - Multiple output formats (ESM, CJS, IIFE), two of them from one build
 //! - External dependencies
 //! - Path aliases for clean imports
 //! - Different build configurations
@@ -32,7 +32,7 @@
 //! relative paths like `../../lib/math`.
 
 use anyhow::Result;
-use fob_bundler::{BuildOptions, NativeRuntime, OutputFormat};
+use fob_bundler::{BuildOptions, NativeRuntime, OutputFormat, OutputTarget};
 use std::sync::Arc;
 
 #[tokio::main]
@@ -69,51 +69,31 @@ async fn main() -> Result<()> {
     println!();
 
     // =========================================================================
-    // Build 2: CommonJS for Node.js
+    // Build 2: CommonJS for Node.js + IIFE for Browsers, from one build
     // =========================================================================
-    println!("📦 Building CommonJS bundle for Node.js...");
+    println!("📦 Building CommonJS and IIFE bundles...");
 
-    let cjs_result = BuildOptions::new("input/app.js")
-        .runtime(runtime.clone())
-        .outdir("output/cjs")
-        .format(OutputFormat::Cjs)
-        .path_alias("@lib", "./input/lib")
-        .sourcemap(true)
-        // No minification - keep readable for Node.js debugging
-        .build()
-        .await?;
-
-    cjs_result.write_to_force("output/cjs")?;
-
-    let cjs_stats = cjs_result.stats();
-    println!("   ✓ CommonJS bundle complete");
-    println!("     • Modules: {}", cjs_stats.module_count);
-    println!("     • Output: output/cjs/");
-    println!("     • Features: require/module.exports, unminified");
-    println!();
-
-    // =========================================================================
-    // Build 3: IIFE for Browsers (No Module System)
-    // =========================================================================
-    println!("📦 Building IIFE bundle for browsers...");
-
-    let iife_result = BuildOptions::new("input/app.js")
+    // Both targets bundle the same modules and only differ in how they are
+    // rendered, so one build can produce them side by side
+    let legacy_result = BuildOptions::new("input/app.js")
         .runtime(runtime)
-        .outdir("output/iife")
-        .format(OutputFormat::Iife)
+        .outdir("output")
         .path_alias("@lib", "./input/lib")
         .sourcemap(true)
-        .minify_level("identifiers")
+        // No minification for CommonJS - keep readable for Node.js debugging
+        .add_output(OutputTarget::new("cjs", OutputFormat::Cjs))
+        .add_output(OutputTarget::new("iife", OutputFormat::Iife).minify_level("identifiers"))
         .build()
         .await?;
 
-    iife_result.write_to_force("output/iife")?;
+    // Each target is written to output/<name>/
+    legacy_result.write_to_force("output")?;
 
-    let iife_stats = iife_result.stats();
-    println!("   ✓ IIFE bundle complete");
-    println!("     • Modules: {}", iife_stats.module_count);
-    println!("     • Output: output/iife/");
-    println!("     • Features: Self-executing, minified, no module system needed");
+    let legacy_stats = legacy_result.stats();
+    println!("   ✓ CommonJS and IIFE bundles complete");
+    println!("     • Modules: {}", legacy_stats.module_count);
+    println!("     • Output: output/cjs/ (require/module.exports, unminified)");
+    println!("     • Output: output/iife/ (self-executing, minified)");
     println!();
 
     // =========================================================================
@@ -128,7 +128,7 @@ impl AppState {
                 })
                 .unwrap_or_default(),
             content,
-            html: result.code,
+            html: result.html,
         };
 
         Ok(post)
@@ -1,5 +1,5 @@
 use anyhow::Result;
-use fob_mdx::{MdxCompileOptions, compile};
+use fob_mdx::{MdxCompileOptions, render_html};
 use serde_json::Value as JsonValue;
 use std::collections::HashMap;
 
@@ -7,7 +7,7 @@ pub struct MdxCompiler;
 
 #[derive(Debug)]
 pub struct CompileResult {
-    pub code: String,
+    pub html: String,
     pub frontmatter: HashMap<String, JsonValue>,
 }
 
@@ -17,10 +17,8 @@ impl MdxCompiler {
     }
 
     pub fn compile(&self, source: &str) -> Result<CompileResult> {
-        let options = MdxCompileOptions::builder()
-            .jsx_runtime("react/jsx-runtime")
-            .build();
-        let result = compile(source, options)?;
+        // Posts only use markdown, so they can be rendered without a JS runtime
+        let result = render_html(source, MdxCompileOptions::default())?;
 
         // Extract frontmatter if present
         let frontmatter = if let Some(fm) = &result.frontmatter {
@@ -35,7 +33,7 @@ impl MdxCompiler {
         };
 
         Ok(CompileResult {
-            code: result.code,
+            html: result.html,
             frontmatter,
         })
     }
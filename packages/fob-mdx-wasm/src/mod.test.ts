@@ -22,10 +22,13 @@ const __dirname = dirname(__filename);
 const toStructuredError = (error: unknown) =>
   error as {
     kind?: string;
+    severity?: string;
     message?: string;
-    location?: { line?: number; column?: number };
-    suggestion?: string;
-    context?: string;
+    file?: string;
+    line?: number;
+    column?: number;
+    help?: string;
+    related?: unknown[];
   };
 
 // Initialize WASM once before all tests using Node.js sync method
@@ -266,7 +269,7 @@ describe('Structured Error Handling', () => {
       compile_mdx(largeInput, null);
     } catch (error: unknown) {
       const err = toStructuredError(error);
-      expect(err.kind).toBe('validationError');
+      expect(err.kind).toBe('ValidationError');
       expect(err.message).toBeTruthy();
       expect(err.message).toContain('exceeds maximum');
     }
@@ -307,7 +310,7 @@ describe('Structured Error Handling', () => {
       expect.fail('Should have thrown');
     } catch (error: unknown) {
       const err = toStructuredError(error);
-      expect(err.kind).toBe('compilationError');
+      expect(err.kind).toBe('CompilationError');
       expect(err.message).toBeDefined();
       expect(typeof err.message).toBe('string');
     }
@@ -320,39 +323,26 @@ describe('Structured Error Handling', () => {
       compile_mdx(invalidMdx, null);
     } catch (error: unknown) {
       const err = toStructuredError(error);
-      if (err.kind === 'compilationError' && err.location) {
-        expect(err.location.line).toBeGreaterThanOrEqual(1);
-        if (err.location.column !== undefined) {
-          expect(err.location.column).toBeGreaterThanOrEqual(1);
+      expect(err.severity).toBe('error');
+      if (err.kind === 'CompilationError' && err.line !== undefined) {
+        expect(err.line).toBeGreaterThanOrEqual(1);
+        if (err.column !== undefined) {
+          expect(err.column).toBeGreaterThanOrEqual(1);
         }
       }
     }
   });
 
-  it('should include suggestion in compilation errors when available', () => {
+  it('should include help in compilation errors when available', () => {
     const invalidMdx = 'import { x } fro "broken"';
 
     try {
       compile_mdx(invalidMdx, null);
     } catch (error: unknown) {
       const err = toStructuredError(error);
-      if (err.kind === 'compilationError' && err.suggestion) {
-        expect(typeof err.suggestion).toBe('string');
-        expect(err.suggestion.length).toBeGreaterThan(0);
-      }
-    }
-  });
-
-  it('should include context in compilation errors when available', () => {
-    const invalidMdx = 'import { x } fro "broken"';
-
-    try {
-      compile_mdx(invalidMdx, null);
-    } catch (error: unknown) {
-      const err = toStructuredError(error);
-      if (err.kind === 'compilationError' && err.context) {
-        expect(typeof err.context).toBe('string');
-        expect(err.context.length).toBeGreaterThan(0);
+      if (err.kind === 'CompilationError' && err.help) {
+        expect(typeof err.help).toBe('string');
+        expect(err.help.length).toBeGreaterThan(0);
       }
     }
   });
@@ -376,8 +366,8 @@ describe('Structured Error Handling', () => {
       if (err.kind) errorKinds.add(err.kind);
     }
 
-    expect(errorKinds.has('validationError')).toBe(true);
-    expect(errorKinds.has('compilationError')).toBe(true);
+    expect(errorKinds.has('ValidationError')).toBe(true);
+    expect(errorKinds.has('CompilationError')).toBe(true);
   });
 
   it('should handle errors gracefully without crashing', () => {